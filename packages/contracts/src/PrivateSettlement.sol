@@ -58,6 +58,9 @@ contract PrivateSettlement is ReentrancyGuard, Ownable {
     IPoseidonHasher public POSEIDON_HASHER;
     address public RELAYER;
     address public FEE_COLLECTOR;
+    /// @notice ERC-2771 forwarder allowed to submit claims on the relayer's
+    /// behalf. Zero disables forwarded claims.
+    address public trustedForwarder;
 
     bool public paused;
     uint256 public pausedAt;
@@ -115,6 +118,16 @@ contract PrivateSettlement is ReentrancyGuard, Ownable {
         uint256 amount,
         address indexed recipient
     );
+    event OrphanedFillRecovered(
+        bytes32 indexed intentId,
+        address indexed solver,
+        address indexed token,
+        uint256 amount
+    );
+    event TrustedForwarderUpdated(
+        address indexed oldForwarder,
+        address indexed newForwarder
+    );
 
     error InvalidProof();
     error InvalidToken();
@@ -142,12 +155,18 @@ contract PrivateSettlement is ReentrancyGuard, Ownable {
     error ContractNotPaused();
     error InvalidAddress();
     error EmergencyPeriodNotReached();
+    error IntentNotExpired();
 
     modifier onlyRelayer() {
         if (msg.sender != RELAYER) revert Unauthorized();
         _;
     }
 
+    modifier onlyRelayerOrForwarded() {
+        if (_claimSender() != RELAYER) revert Unauthorized();
+        _;
+    }
+
     modifier whenNotPaused() {
         if (paused) revert ContractIsPaused();
         _;
@@ -264,16 +283,60 @@ contract PrivateSettlement is ReentrancyGuard, Ownable {
         address recipient,
         bytes32 secret,
         bytes calldata claimAuth
-    ) external nonReentrant onlyRelayer {
+    ) external nonReentrant onlyRelayerOrForwarded {
+        _claim(
+            intentId,
+            nullifier,
+            recipient,
+            recipient,
+            keccak256(abi.encodePacked(intentId, nullifier, recipient)),
+            secret,
+            claimAuth
+        );
+    }
+
+    /// @notice Like `claimWithdrawal`, but pays `delegate`, e.g. an exchange
+    /// deposit address that cannot sign. `recipient` authorizes the claim
+    /// over the intent, nullifier and delegate.
+    function claimWithdrawalTo(
+        bytes32 intentId,
+        bytes32 nullifier,
+        address recipient,
+        address delegate,
+        bytes32 secret,
+        bytes calldata claimAuth
+    ) external nonReentrant onlyRelayerOrForwarded {
+        if (delegate == address(0)) revert InvalidAddress();
+        _claim(
+            intentId,
+            nullifier,
+            recipient,
+            delegate,
+            keccak256(
+                abi.encodePacked(intentId, nullifier, recipient, delegate)
+            ),
+            secret,
+            claimAuth
+        );
+    }
+
+    /// @dev `claimAuth` must be `recipient`'s signature over `authHash`;
+    /// the fill, less the fee, goes to `payTo`.
+    function _claim(
+        bytes32 intentId,
+        bytes32 nullifier,
+        address recipient,
+        address payTo,
+        bytes32 authHash,
+        bytes32 secret,
+        bytes calldata claimAuth
+    ) internal {
         Fill storage fill = fills[intentId];
 
         if (fill.solver == address(0)) revert NotFilled();
         if (fill.claimed) revert AlreadyClaimed();
         if (nullifiers[nullifier]) revert NullifierUsed();
 
-        bytes32 authHash = keccak256(
-            abi.encodePacked(intentId, nullifier, recipient)
-        );
         bytes32 ethSignedHash = MessageHashUtils.toEthSignedMessageHash(
             authHash
         );
@@ -315,12 +378,12 @@ contract PrivateSettlement is ReentrancyGuard, Ownable {
         uint256 userAmount = fill.amount - fee;
 
         if (fill.token == NATIVE_ETH) {
-            (bool success1, ) = recipient.call{value: userAmount}("");
+            (bool success1, ) = payTo.call{value: userAmount}("");
             if (!success1) revert TransferFailed();
             (bool success2, ) = FEE_COLLECTOR.call{value: fee}("");
             if (!success2) revert TransferFailed();
         } else {
-            if (!IERC20(fill.token).transfer(recipient, userAmount))
+            if (!IERC20(fill.token).transfer(payTo, userAmount))
                 revert TransferFailed();
             if (!IERC20(fill.token).transfer(FEE_COLLECTOR, fee))
                 revert TransferFailed();
@@ -329,6 +392,46 @@ contract PrivateSettlement is ReentrancyGuard, Ownable {
         emit WithdrawalClaimed(intentId, nullifier, fill.token);
     }
 
+    /// @notice Return a fill to its solver when the source intent was refunded
+    /// instead of settled (fill and refund racing the deadline). Marks the fill
+    /// claimed, so the user can no longer withdraw it on top of the refund.
+    function recoverOrphanedFill(
+        bytes32 intentId
+    ) external nonReentrant onlyRelayer {
+        Fill storage fill = fills[intentId];
+
+        if (fill.solver == address(0)) revert NotFilled();
+        if (fill.claimed) revert AlreadyClaimed();
+        // The source pool only refunds after the deadline
+        if (block.timestamp <= intentParams[intentId].deadline)
+            revert IntentNotExpired();
+
+        if (fill.token == NATIVE_ETH) {
+            if (address(this).balance < fill.amount)
+                revert InsufficientBalance();
+        } else {
+            if (IERC20(fill.token).balanceOf(address(this)) < fill.amount)
+                revert InsufficientBalance();
+        }
+
+        fill.claimed = true;
+
+        if (fill.token == NATIVE_ETH) {
+            (bool success, ) = fill.solver.call{value: fill.amount}("");
+            if (!success) revert TransferFailed();
+        } else {
+            if (!IERC20(fill.token).transfer(fill.solver, fill.amount))
+                revert TransferFailed();
+        }
+
+        emit OrphanedFillRecovered(
+            intentId,
+            fill.solver,
+            fill.token,
+            fill.amount
+        );
+    }
+
     function pauseContract() external onlyOwner {
         paused = !paused;
         if (paused) pausedAt = block.timestamp;
@@ -429,6 +532,26 @@ contract PrivateSettlement is ReentrancyGuard, Ownable {
         emit RelayerUpdated(oldRelayer, newRelayer);
     }
 
+    function updateTrustedForwarder(address newForwarder) external onlyOwner {
+        address oldForwarder = trustedForwarder;
+        trustedForwarder = newForwarder;
+        emit TrustedForwarderUpdated(oldForwarder, newForwarder);
+    }
+
+    /// @notice ERC-2771: the forwarder checks this before relaying a request.
+    function isTrustedForwarder(address forwarder) public view returns (bool) {
+        return forwarder != address(0) && forwarder == trustedForwarder;
+    }
+
+    /// @dev Calls from the trusted forwarder carry the request signer in the
+    /// last 20 calldata bytes; anyone else is `msg.sender`.
+    function _claimSender() internal view returns (address) {
+        if (isTrustedForwarder(msg.sender) && msg.data.length >= 20) {
+            return address(bytes20(msg.data[msg.data.length - 20:]));
+        }
+        return msg.sender;
+    }
+
     function updatePoseidonHasher(address newHasher) external onlyOwner {
         if (newHasher == address(0)) revert InvalidAddress();
         address oldHasher = address(POSEIDON_HASHER);
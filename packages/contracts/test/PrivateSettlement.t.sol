@@ -7,6 +7,7 @@ import {PrivateSettlement} from "../src/PrivateSettlement.sol";
 import {IERC20} from "@openzeppelin/contracts/token/ERC20/IERC20.sol";
 import {ERC20} from "@openzeppelin/contracts/token/ERC20/ERC20.sol";
 import {MessageHashUtils} from "@openzeppelin/contracts/utils/cryptography/MessageHashUtils.sol";
+import {ERC2771Forwarder} from "@openzeppelin/contracts/metatx/ERC2771Forwarder.sol";
 
 contract MockERC20 is ERC20 {
     uint8 private _decimals;
@@ -56,6 +57,8 @@ contract PrivateSettlementTest is Test {
     event RelayerUpdated(address indexed oldRelayer, address indexed newRelayer);
     event PoseidonHasherUpdated(address indexed oldHasher, address indexed newHasher);
     event EmergencyWithdrawal(address indexed token, uint256 amount, address indexed recipient);
+    event OrphanedFillRecovered(bytes32 indexed intentId, address indexed solver, address indexed token, uint256 amount);
+    event TrustedForwarderUpdated(address indexed oldForwarder, address indexed newForwarder);
 
     function setUp() public {
         poseidon = new PoseidonHasher();
@@ -352,6 +355,73 @@ contract PrivateSettlementTest is Test {
         settlement.claimWithdrawal(intentId, nullifier, recipientAddr, secret, claimAuth);
     }
 
+    // ========== ORPHANED FILL RECOVERY TESTS ==========
+
+    function _registerAndFill(uint64 deadline) internal {
+        bytes32 sourceRoot = _computeSingleLeafRoot(commitment);
+        bytes32[] memory proof = new bytes32[](1);
+        proof[0] = bytes32(0);
+
+        vm.prank(relayer);
+        settlement.syncSourceChainCommitmentRoot(SOURCE_CHAIN, sourceRoot);
+
+        vm.prank(relayer);
+        settlement.registerIntent(intentId, commitment, address(token), TEST_AMOUNT, SOURCE_CHAIN, deadline, sourceRoot, proof, 0);
+
+        vm.prank(solver);
+        settlement.fillIntent(intentId, commitment, SOURCE_CHAIN, address(token), TEST_AMOUNT);
+    }
+
+    function test_RecoverOrphanedFill() public {
+        uint64 deadline = uint64(block.timestamp + 1 hours);
+        _registerAndFill(deadline);
+        uint256 solverBalanceBefore = token.balanceOf(solver);
+
+        vm.warp(deadline + 1);
+
+        vm.expectEmit(true, true, true, true);
+        emit OrphanedFillRecovered(intentId, solver, address(token), TEST_AMOUNT);
+
+        vm.prank(relayer);
+        settlement.recoverOrphanedFill(intentId);
+
+        assertTrue(settlement.getFill(intentId).claimed);
+        assertEq(token.balanceOf(solver), solverBalanceBefore + TEST_AMOUNT);
+        assertEq(token.balanceOf(address(settlement)), 0);
+
+        // The refunded user can no longer withdraw the fill as well
+        bytes32 authHash = keccak256(abi.encodePacked(intentId, nullifier, recipientAddr));
+        bytes32 ethSignedHash = MessageHashUtils.toEthSignedMessageHash(authHash);
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(recipientPrivateKey, ethSignedHash);
+        bytes memory claimAuth = abi.encodePacked(r, s, v);
+
+        vm.prank(relayer);
+        vm.expectRevert(PrivateSettlement.AlreadyClaimed.selector);
+        settlement.claimWithdrawal(intentId, nullifier, recipientAddr, secret, claimAuth);
+
+        vm.prank(relayer);
+        vm.expectRevert(PrivateSettlement.AlreadyClaimed.selector);
+        settlement.recoverOrphanedFill(intentId);
+    }
+
+    function test_RevertWhen_RecoverOrphanedFill_BeforeDeadline() public {
+        _registerAndFill(uint64(block.timestamp + 1 hours));
+
+        vm.prank(relayer);
+        vm.expectRevert(PrivateSettlement.IntentNotExpired.selector);
+        settlement.recoverOrphanedFill(intentId);
+    }
+
+    function test_RevertWhen_RecoverOrphanedFill_NotRelayer() public {
+        uint64 deadline = uint64(block.timestamp + 1 hours);
+        _registerAndFill(deadline);
+        vm.warp(deadline + 1);
+
+        vm.prank(solver);
+        vm.expectRevert(PrivateSettlement.Unauthorized.selector);
+        settlement.recoverOrphanedFill(intentId);
+    }
+
     function test_RevertWhen_ClaimWithdrawal_InvalidCommitment() public {
         bytes32 sourceRoot = _computeSingleLeafRoot(commitment);
         bytes32[] memory proof = new bytes32[](1);
@@ -378,6 +448,145 @@ contract PrivateSettlementTest is Test {
         settlement.claimWithdrawal(intentId, nullifier, recipientAddr, wrongSecret, claimAuth);
     }
 
+    // ========== DELEGATED CLAIM TESTS ==========
+
+    function _fillForClaim() internal {
+        bytes32 sourceRoot = _computeSingleLeafRoot(commitment);
+        bytes32[] memory proof = new bytes32[](1);
+        proof[0] = bytes32(0);
+
+        vm.prank(relayer);
+        settlement.syncSourceChainCommitmentRoot(SOURCE_CHAIN, sourceRoot);
+
+        vm.prank(relayer);
+        settlement.registerIntent(intentId, commitment, address(token), TEST_AMOUNT, SOURCE_CHAIN, uint64(block.timestamp + 1 hours), sourceRoot, proof, 0);
+
+        vm.prank(solver);
+        settlement.fillIntent(intentId, commitment, SOURCE_CHAIN, address(token), TEST_AMOUNT);
+    }
+
+    function _delegatedClaimAuth(address delegate) internal view returns (bytes memory) {
+        bytes32 authHash = keccak256(abi.encodePacked(intentId, nullifier, recipientAddr, delegate));
+        bytes32 ethSignedHash = MessageHashUtils.toEthSignedMessageHash(authHash);
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(recipientPrivateKey, ethSignedHash);
+        return abi.encodePacked(r, s, v);
+    }
+
+    function test_ClaimWithdrawalTo_PaysDelegate() public {
+        _fillForClaim();
+        address delegate = makeAddr("exchangeDeposit");
+
+        vm.prank(relayer);
+        settlement.claimWithdrawalTo(intentId, nullifier, recipientAddr, delegate, secret, _delegatedClaimAuth(delegate));
+
+        uint256 fee = (TEST_AMOUNT * settlement.FEE_BPS()) / 10000;
+        assertEq(token.balanceOf(delegate), TEST_AMOUNT - fee);
+        assertEq(token.balanceOf(recipientAddr), 0);
+        assertTrue(settlement.getFill(intentId).claimed);
+    }
+
+    function test_RevertWhen_ClaimWithdrawalTo_DelegateNotSigned() public {
+        _fillForClaim();
+        address delegate = makeAddr("exchangeDeposit");
+        address attacker = makeAddr("attacker");
+
+        vm.prank(relayer);
+        vm.expectRevert(PrivateSettlement.InvalidSignature.selector);
+        settlement.claimWithdrawalTo(intentId, nullifier, recipientAddr, attacker, secret, _delegatedClaimAuth(delegate));
+
+        // A plain claim signature does not cover a delegate either
+        vm.prank(relayer);
+        vm.expectRevert(PrivateSettlement.InvalidSignature.selector);
+        settlement.claimWithdrawalTo(intentId, nullifier, recipientAddr, delegate, secret, _claimAuth());
+    }
+
+    // ========== FORWARDED CLAIM TESTS ==========
+
+    function _claimAuth() internal view returns (bytes memory) {
+        bytes32 authHash = keccak256(abi.encodePacked(intentId, nullifier, recipientAddr));
+        bytes32 ethSignedHash = MessageHashUtils.toEthSignedMessageHash(authHash);
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(recipientPrivateKey, ethSignedHash);
+        return abi.encodePacked(r, s, v);
+    }
+
+    function _forwardedClaim(ERC2771Forwarder forwarder) internal returns (ERC2771Forwarder.ForwardRequestData memory request) {
+        (, uint256 relayerKey) = makeAddrAndKey("relayer");
+        request = ERC2771Forwarder.ForwardRequestData({
+            from: relayer,
+            to: address(settlement),
+            value: 0,
+            gas: 500_000,
+            deadline: uint48(block.timestamp + 10 minutes),
+            data: abi.encodeCall(settlement.claimWithdrawal, (intentId, nullifier, recipientAddr, secret, _claimAuth())),
+            signature: ""
+        });
+
+        bytes32 structHash = keccak256(
+            abi.encode(
+                keccak256("ForwardRequest(address from,address to,uint256 value,uint256 gas,uint256 nonce,uint48 deadline,bytes data)"),
+                request.from,
+                request.to,
+                request.value,
+                request.gas,
+                forwarder.nonces(request.from),
+                request.deadline,
+                keccak256(request.data)
+            )
+        );
+        (, string memory name, string memory version, uint256 chainId, address verifyingContract,,) = forwarder.eip712Domain();
+        bytes32 domainSeparator = keccak256(
+            abi.encode(
+                keccak256("EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)"),
+                keccak256(bytes(name)),
+                keccak256(bytes(version)),
+                chainId,
+                verifyingContract
+            )
+        );
+        (uint8 v, bytes32 r, bytes32 s) = vm.sign(relayerKey, MessageHashUtils.toTypedDataHash(domainSeparator, structHash));
+        request.signature = abi.encodePacked(r, s, v);
+    }
+
+    function test_ClaimWithdrawal_ThroughTrustedForwarder() public {
+        ERC2771Forwarder forwarder = new ERC2771Forwarder("IntegratorForwarder");
+
+        vm.expectEmit(true, true, false, false);
+        emit TrustedForwarderUpdated(address(0), address(forwarder));
+        vm.prank(owner);
+        settlement.updateTrustedForwarder(address(forwarder));
+        assertTrue(settlement.isTrustedForwarder(address(forwarder)));
+
+        _registerAndFill(uint64(block.timestamp + 1 hours));
+        ERC2771Forwarder.ForwardRequestData memory request = _forwardedClaim(forwarder);
+
+        // Whoever submits pays the gas; the settlement sees the signing relayer
+        vm.expectEmit(true, true, false, true, address(settlement));
+        emit WithdrawalClaimed(intentId, nullifier, address(token));
+        vm.prank(solver);
+        forwarder.execute(request);
+
+        assertTrue(settlement.getFill(intentId).claimed);
+        assertEq(token.balanceOf(recipientAddr), TEST_AMOUNT - (TEST_AMOUNT * settlement.FEE_BPS()) / 10000);
+    }
+
+    function test_RevertWhen_ClaimWithdrawal_UntrustedForwarder() public {
+        ERC2771Forwarder forwarder = new ERC2771Forwarder("IntegratorForwarder");
+        _registerAndFill(uint64(block.timestamp + 1 hours));
+        ERC2771Forwarder.ForwardRequestData memory request = _forwardedClaim(forwarder);
+
+        vm.prank(solver);
+        vm.expectRevert(
+            abi.encodeWithSelector(ERC2771Forwarder.ERC2771UntrustfulTarget.selector, address(settlement), address(forwarder))
+        );
+        forwarder.execute(request);
+
+        // A spoofed sender suffix means nothing without the forwarder
+        vm.prank(solver);
+        (bool success,) = address(settlement).call(abi.encodePacked(request.data, relayer));
+        assertFalse(success);
+        assertFalse(settlement.getFill(intentId).claimed);
+    }
+
     // ========== PAUSE TESTS ==========
 
     function test_PauseContract() public {
@@ -0,0 +1,84 @@
+//! Contract bindings shared by the relayer and the solver. Both chains run the
+//! same `PrivateIntentPool` and `PrivateSettlement` deployments; claims may go
+//! through an integrator's OpenZeppelin `ERC2771Forwarder`.
+
+use ethers::contract::abigen;
+
+abigen!(
+    IntentPoolContract,
+    r#"[
+        function createIntent(bytes32 intentId, bytes32 commitment, address sourceToken, uint256 sourceAmount, address destToken, uint256 destAmount, uint32 destChain, address refundTo, uint64 customDeadline) external payable
+        function settleIntent(bytes32 intentId, address solver, bytes32[] calldata merkleProof, uint256 leafIndex) external
+        function syncDestChainRoot(uint32 chainId, bytes32 root) external
+        function syncDestChainFillRoot(uint32 chainId, bytes32 root) external
+        function refund(bytes32 intentId) external
+        function getMerkleRoot() external view returns (bytes32)
+        function getDestChainRoot(uint32 chainId) external view returns (bytes32)
+        function destChainFillRoots(uint32 chainId) external view returns (bytes32)
+        function getIntent(bytes32 intentId) external view returns (tuple(bytes32 commitment, address sourceToken, uint256 sourceAmount, address destToken, uint256 destAmount, uint32 destChain, uint64 deadline, address refundTo, bool filled, bool refunded))
+        function generateCommitmentProof(bytes32 commitment) external view returns (bytes32[] memory, uint256)
+        function paused() external view returns (bool)
+        function POSEIDON_HASHER() external view returns (address)
+        event IntentCreated(bytes32 indexed intentId, bytes32 indexed commitment, uint32 destChain, address sourceToken, uint256 sourceAmount, address destToken, uint256 destAmount)
+        event IntentSettled(bytes32 indexed intentId, address indexed solver, bytes32 fillRoot)
+    ]"#
+);
+
+abigen!(
+    PoseidonHasherContract,
+    r#"[
+        function poseidon(bytes32[4] inputs) external pure returns (bytes32)
+    ]"#
+);
+
+abigen!(
+    SettlementContract,
+    r#"[
+        function registerIntent(bytes32 intentId, bytes32 commitment, address token, uint256 amount, uint32 sourceChain, uint64 deadline, bytes32 sourceRoot, bytes32[] calldata proof, uint256 leafIndex) external
+        function fillIntent(bytes32 intentId, bytes32 commitment, uint32 sourceChain, address token, uint256 amount) external payable
+        function claimWithdrawal(bytes32 intentId, bytes32 nullifier, address recipient, bytes32 secret, bytes calldata claimAuth) external
+        function claimWithdrawalTo(bytes32 intentId, bytes32 nullifier, address recipient, address delegate, bytes32 secret, bytes calldata claimAuth) external
+        function recoverOrphanedFill(bytes32 intentId) external
+        function syncSourceChainCommitmentRoot(uint32 chainId, bytes32 root) external
+        function getMerkleRoot() external view returns (bytes32)
+        function generateFillProof(bytes32 intentId) external view returns (bytes32[] memory)
+        function getFillTreeSize() external view returns (uint256)
+        function getFillIndex(bytes32 intentId) external view returns (uint256)
+        function getFill(bytes32 intentId) external view returns (tuple(address solver, address token, uint256 amount, uint32 sourceChain, uint32 timestamp, bool claimed))
+        function getSourceChainRoot(uint32 chainId) external view returns (bytes32)
+        function sourceChainCommitmentRoots(uint32 chainId) external view returns (bytes32)
+        function getIntentParams(bytes32 intentId) external view returns (tuple(bytes32 commitment, address token, uint256 amount, uint32 sourceChain, uint64 deadline, bool exists))
+        function isTokenSupported(address token) external view returns (bool)
+        function FEE_BPS() external view returns (uint256)
+        function isNullifierUsed(bytes32 nullifier) external view returns (bool)
+        function paused() external view returns (bool)
+        function trustedForwarder() external view returns (address)
+        function isTrustedForwarder(address forwarder) external view returns (bool)
+        event IntentRegistered(bytes32 indexed intentId, bytes32 commitment, address destToken, uint256 destAmount, uint32 sourceChain, uint64 deadline, bytes32[] proof, uint256 leafIndex)
+        event IntentFilled(bytes32 indexed intentId, address indexed solver, address indexed token, uint256 amount)
+        event WithdrawalClaimed(bytes32 indexed intentId, bytes32 indexed nullifier, address token)
+        event OrphanedFillRecovered(bytes32 indexed intentId, address indexed solver, address indexed token, uint256 amount)
+    ]"#
+);
+
+abigen!(
+    TrustedForwarderContract,
+    r#"[
+        struct ForwardRequestData { address from; address to; uint256 value; uint256 gas; uint48 deadline; bytes data; bytes signature; }
+        function execute(ForwardRequestData request) external payable
+        function nonces(address owner) external view returns (uint256)
+        function eip712Domain() external view returns (bytes1 fields, string name, string version, uint256 chainId, address verifyingContract, bytes32 salt, uint256[] extensions)
+        event ExecutedForwardRequest(address indexed signer, uint256 nonce, bool success)
+    ]"#
+);
+
+abigen!(
+    ERC20Contract,
+    r#"[
+        function balanceOf(address account) external view returns (uint256)
+        function allowance(address owner, address spender) external view returns (uint256)
+        function approve(address spender, uint256 amount) external returns (bool)
+        function decimals() external view returns (uint8)
+        function symbol() external view returns (string)
+    ]"#
+);
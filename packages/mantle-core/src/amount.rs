@@ -0,0 +1,172 @@
+use anyhow::{Result, anyhow};
+use ethers::types::{U256, U512};
+use serde::{Deserialize, Serialize};
+
+/// Decimals of the fixed-point rates taken by [`convert_at_rate`].
+pub const RATE_DECIMALS: u8 = 18;
+
+/// Which way a conversion goes when the exact result is not a whole base unit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Rounding {
+    Floor,
+    Ceil,
+}
+
+/// Human-readable value of a raw token amount, e.g. `1_500_000` at 6 decimals is `1.5`.
+/// Lossy past f64 precision, which is fine for pricing.
+pub fn to_decimal(amount: U256, decimals: u8) -> f64 {
+    let raw = amount
+        .0
+        .iter()
+        .rev()
+        .fold(0.0, |acc, limb| acc * 2f64.powi(64) + *limb as f64);
+
+    raw / 10f64.powi(decimals as i32)
+}
+
+/// Move a raw amount between token decimal bases, truncating on the way down.
+pub fn rescale(amount: U256, from_decimals: u8, to_decimals: u8) -> Result<U256> {
+    if to_decimals > from_decimals {
+        let multiplier = U256::from(10u64).pow(U256::from(to_decimals - from_decimals));
+        amount
+            .checked_mul(multiplier)
+            .ok_or_else(|| anyhow!("Amount overflow"))
+    } else if from_decimals > to_decimals {
+        let divisor = U256::from(10u64).pow(U256::from(from_decimals - to_decimals));
+        amount
+            .checked_div(divisor)
+            .ok_or_else(|| anyhow!("Amount underflow"))
+    } else {
+        Ok(amount)
+    }
+}
+
+/// `amount * numerator / denominator` through a 512-bit product, rounded
+/// once at the end. Also returns whether any remainder was rounded away.
+pub fn mul_div(
+    amount: U256,
+    numerator: U256,
+    denominator: U256,
+    rounding: Rounding,
+) -> Result<(U256, bool)> {
+    if denominator.is_zero() {
+        return Err(anyhow!("Division by zero"));
+    }
+
+    let (quotient, remainder) = amount.full_mul(numerator).div_mod(U512::from(denominator));
+    let inexact = !remainder.is_zero();
+    let quotient = match rounding {
+        Rounding::Ceil if inexact => quotient + U512::one(),
+        _ => quotient,
+    };
+
+    let result = U256::try_from(quotient).map_err(|_| anyhow!("Amount overflow"))?;
+    Ok((result, inexact))
+}
+
+/// `rate` (whole destination tokens per whole source token) as a
+/// [`RATE_DECIMALS`] fixed-point integer.
+pub fn rate_to_fixed(rate: f64) -> Result<U256> {
+    if !rate.is_finite() || rate <= 0.0 {
+        return Err(anyhow!("Unusable exchange rate {}", rate));
+    }
+
+    // Scaling in f64 would drop digits past 2^53; the decimal expansion is exact
+    let digits: String = format!("{:.*}", RATE_DECIMALS as usize, rate)
+        .chars()
+        .filter(|c| *c != '.')
+        .collect();
+    let fixed =
+        U256::from_dec_str(&digits).map_err(|_| anyhow!("Unusable exchange rate {}", rate))?;
+    if fixed.is_zero() {
+        return Err(anyhow!(
+            "Exchange rate {} is below fixed-point precision",
+            rate
+        ));
+    }
+    Ok(fixed)
+}
+
+/// Convert a raw amount into another token's base units at a fixed-point
+/// rate, rescaling decimals in the same step so only one rounding applies.
+pub fn convert_at_rate(
+    amount: U256,
+    from_decimals: u8,
+    to_decimals: u8,
+    rate: U256,
+    rounding: Rounding,
+) -> Result<(U256, bool)> {
+    let ten = U256::from(10u64);
+    let numerator = rate
+        .checked_mul(ten.pow(to_decimals.into()))
+        .ok_or_else(|| anyhow!("Rate overflow"))?;
+    let denominator = ten.pow(U256::from(from_decimals as u64 + RATE_DECIMALS as u64));
+    mul_div(amount, numerator, denominator, rounding)
+}
+
+/// Inverse of [`convert_at_rate`]: the source amount that converts to `amount`
+/// of the destination token.
+pub fn convert_at_inverse_rate(
+    amount: U256,
+    from_decimals: u8,
+    to_decimals: u8,
+    rate: U256,
+    rounding: Rounding,
+) -> Result<(U256, bool)> {
+    let ten = U256::from(10u64);
+    let numerator = ten.pow(U256::from(from_decimals as u64 + RATE_DECIMALS as u64));
+    let denominator = rate
+        .checked_mul(ten.pow(to_decimals.into()))
+        .ok_or_else(|| anyhow!("Rate overflow"))?;
+    mul_div(amount, numerator, denominator, rounding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rescale_and_decimal() {
+        let one_usdc = U256::from(1_000_000u64);
+        let as_18 = rescale(one_usdc, 6, 18).unwrap();
+
+        assert_eq!(as_18, U256::exp10(18));
+        assert_eq!(rescale(as_18, 18, 6).unwrap(), one_usdc);
+        assert_eq!(rescale(U256::from(999u64), 18, 6).unwrap(), U256::zero());
+
+        assert_eq!(to_decimal(U256::from(1_500_000u64), 6), 1.5);
+        assert_eq!(to_decimal(U256::exp10(30), 18), 1e12);
+    }
+
+    #[test]
+    fn test_convert_at_rate_rounds_once() {
+        // 1 wei of ETH at 3000.5 USDC is far below one USDC base unit
+        let rate = rate_to_fixed(3000.5).unwrap();
+        assert_eq!(
+            convert_at_rate(U256::one(), 18, 6, rate, Rounding::Floor).unwrap(),
+            (U256::zero(), true)
+        );
+        assert_eq!(
+            convert_at_rate(U256::one(), 18, 6, rate, Rounding::Ceil).unwrap(),
+            (U256::one(), true)
+        );
+
+        let one_eth = U256::exp10(18);
+        assert_eq!(
+            convert_at_rate(one_eth, 18, 6, rate, Rounding::Floor).unwrap(),
+            (U256::from(3_000_500_000u64), false)
+        );
+        // 1 USDC needs 1/3000.5 ETH, not a whole number of wei
+        let (wei, inexact) =
+            convert_at_inverse_rate(U256::from(1_000_000u64), 18, 6, rate, Rounding::Ceil).unwrap();
+        assert!(inexact);
+        assert_eq!(wei, U256::from(333_277_787_035_495u64));
+
+        assert_eq!(rate_to_fixed(0.25).unwrap(), U256::exp10(17) * 25 / 10);
+        assert!(rate_to_fixed(0.0).is_err());
+        assert!(rate_to_fixed(1e-19).is_err());
+        assert!(rate_to_fixed(f64::NAN).is_err());
+        assert!(mul_div(U256::MAX, U256::MAX, U256::one(), Rounding::Floor).is_err());
+    }
+}
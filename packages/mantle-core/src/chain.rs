@@ -0,0 +1,57 @@
+use anyhow::{Result, anyhow};
+use serde::{Deserialize, Serialize};
+
+/// Ethereum Sepolia.
+pub const ETHEREUM_CHAIN_ID: u32 = 11155111;
+/// Mantle Sepolia.
+pub const MANTLE_CHAIN_ID: u32 = 5003;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Chain {
+    Ethereum,
+    Mantle,
+}
+
+impl Chain {
+    pub const ALL: [Chain; 2] = [Chain::Ethereum, Chain::Mantle];
+
+    pub fn id(&self) -> u32 {
+        match self {
+            Self::Ethereum => ETHEREUM_CHAIN_ID,
+            Self::Mantle => MANTLE_CHAIN_ID,
+        }
+    }
+
+    pub fn from_id(chain_id: u64) -> Option<Self> {
+        match chain_id {
+            id if id == ETHEREUM_CHAIN_ID as u64 => Some(Self::Ethereum),
+            id if id == MANTLE_CHAIN_ID as u64 => Some(Self::Mantle),
+            _ => None,
+        }
+    }
+
+    /// The other side of a bridge leg.
+    pub fn counterpart(&self) -> Self {
+        match self {
+            Self::Ethereum => Self::Mantle,
+            Self::Mantle => Self::Ethereum,
+        }
+    }
+
+    /// Accepts the names and the numeric chain ids used in intent rows.
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "ethereum" | "11155111" => Ok(Self::Ethereum),
+            "mantle" | "5003" => Ok(Self::Mantle),
+            other => Err(anyhow!("Unsupported chain: {}", other)),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Ethereum => "ethereum",
+            Self::Mantle => "mantle",
+        }
+    }
+}
@@ -0,0 +1,131 @@
+use std::{collections::BTreeMap, fmt, ops::AddAssign};
+
+use ethers::types::U256;
+use serde::{Deserialize, Serialize, Serializer};
+
+/// A count that keeps growing across restarts: the total earlier processes
+/// persisted plus what this process has counted since it started.
+///
+/// As long as the last snapshot was written on shutdown, the exported total
+/// never steps backwards, so a Prometheus `rate()` over it sees no resets.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MonotonicCounter {
+    restored: u64,
+    session: u64,
+}
+
+impl MonotonicCounter {
+    pub fn inc(&mut self) {
+        self.add(1);
+    }
+
+    pub fn add(&mut self, n: u64) {
+        self.session = self.session.saturating_add(n);
+    }
+
+    /// Lifetime total, including what earlier processes counted.
+    pub fn get(&self) -> u64 {
+        self.restored.saturating_add(self.session)
+    }
+
+    /// Counted by this process alone.
+    pub fn session(&self) -> u64 {
+        self.session
+    }
+
+    /// Seed with the total an earlier process saved. Whatever this process
+    /// already counted is kept on top.
+    pub fn restore(&mut self, total: u64) {
+        self.restored = total;
+    }
+}
+
+impl AddAssign<u64> for MonotonicCounter {
+    fn add_assign(&mut self, n: u64) {
+        self.add(n);
+    }
+}
+
+impl fmt::Display for MonotonicCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.get())
+    }
+}
+
+impl Serialize for MonotonicCounter {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.get())
+    }
+}
+
+/// Persisted totals of a metrics struct, keyed by stable names.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CounterSnapshot {
+    /// Unix seconds of the first start these totals cover.
+    pub since: i64,
+    pub saved_at: i64,
+    #[serde(default)]
+    pub counters: BTreeMap<String, u64>,
+    /// Running token amounts (volumes, profit) in base units.
+    #[serde(default)]
+    pub amounts: BTreeMap<String, U256>,
+}
+
+impl CounterSnapshot {
+    pub fn counter(&self, name: &str) -> u64 {
+        self.counters.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn amount(&self, name: &str) -> U256 {
+        self.amounts.get(name).copied().unwrap_or_default()
+    }
+}
+
+/// Metrics whose counters outlive the process.
+pub trait PersistentCounters {
+    /// Current totals, stamped with `now` (unix seconds).
+    fn counter_snapshot(&self, now: i64) -> CounterSnapshot;
+
+    /// Add back what an earlier process saved. Called once, at startup.
+    fn restore_counters(&mut self, snapshot: &CounterSnapshot);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_restored_counter_keeps_session_counts() {
+        let mut counter = MonotonicCounter::default();
+        counter += 2;
+        counter.inc();
+        assert_eq!(counter.get(), 3);
+
+        // Totals saved by the previous process land under this one's counts
+        counter.restore(40);
+        assert_eq!(counter.get(), 43);
+        assert_eq!(counter.session(), 3);
+        assert_eq!(
+            serde_json::to_value(counter).unwrap(),
+            serde_json::json!(43)
+        );
+
+        let snapshot = CounterSnapshot {
+            since: 1_700_000_000,
+            saved_at: 1_700_086_400,
+            counters: BTreeMap::from([("fills".to_string(), 43)]),
+            amounts: BTreeMap::from([("volume_usdc".to_string(), U256::from(5_000_000u64))]),
+        };
+        let json = serde_json::to_string(&snapshot).unwrap();
+        let decoded: CounterSnapshot = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, snapshot);
+        assert_eq!(decoded.counter("fills"), 43);
+        assert_eq!(decoded.counter("claims"), 0);
+        assert_eq!(decoded.amount("volume_usdc"), U256::from(5_000_000u64));
+        assert!(decoded.amount("volume_eth").is_zero());
+
+        // Snapshots from before a counter existed still load
+        let old: CounterSnapshot = serde_json::from_str(r#"{"since":1,"saved_at":2}"#).unwrap();
+        assert!(old.counters.is_empty());
+    }
+}
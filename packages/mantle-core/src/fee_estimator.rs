@@ -0,0 +1,342 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use ethers::{
+    providers::Middleware,
+    types::{BlockNumber, FeeHistory, U256},
+};
+use tracing::{info, warn};
+
+/// Where a chain's EIP-1559 fees come from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeeSource {
+    /// `eth_feeHistory`: the next block's base fee plus a percentile of the
+    /// tips paid in recent blocks.
+    FeeHistory,
+    /// `eth_gasPrice`, split into base fee and tip against the latest block.
+    /// On rollups the L1 data fee dominates and the L2 tip market is thin,
+    /// so fee history mostly returns zero or fallback tips.
+    GasPrice,
+}
+
+impl std::str::FromStr for FeeSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "fee_history" | "feehistory" => Ok(Self::FeeHistory),
+            "gas_price" | "gasprice" => Ok(Self::GasPrice),
+            other => Err(anyhow!("Unknown fee source: {}", other)),
+        }
+    }
+}
+
+/// Fees for one type-2 transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FeeEstimate {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+/// How EIP-1559 fees are estimated on one chain.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FeeEstimator {
+    pub source: FeeSource,
+    /// Blocks of history sampled by `eth_feeHistory`.
+    pub history_blocks: u64,
+    /// Percentile of each block's tips; the median across blocks is paid.
+    pub priority_percentile: f64,
+    /// Tip paid when recent blocks were empty or tipped less.
+    pub min_priority_fee: U256,
+    /// Headroom on the next base fee, e.g. 200 = survives about six full
+    /// blocks of base fee increases.
+    pub base_fee_multiplier_pct: u64,
+}
+
+impl FeeEstimator {
+    pub fn ethereum() -> Self {
+        Self {
+            source: FeeSource::FeeHistory,
+            history_blocks: 10,
+            priority_percentile: 25.0,
+            min_priority_fee: U256::exp10(8),
+            base_fee_multiplier_pct: 200,
+        }
+    }
+
+    pub fn mantle() -> Self {
+        Self {
+            source: FeeSource::GasPrice,
+            history_blocks: 10,
+            priority_percentile: 25.0,
+            min_priority_fee: U256::zero(),
+            base_fee_multiplier_pct: 100,
+        }
+    }
+
+    /// Override the defaults from `<PREFIX>_FEE_SOURCE` (`fee_history` or
+    /// `gas_price`), `<PREFIX>_FEE_HISTORY_BLOCKS`,
+    /// `<PREFIX>_PRIORITY_FEE_PERCENTILE`, `<PREFIX>_MIN_PRIORITY_FEE_GWEI` and
+    /// `<PREFIX>_BASE_FEE_MULTIPLIER_PCT`.
+    pub fn from_env(prefix: &str, defaults: Self) -> Result<Self> {
+        let var = |name: &str| std::env::var(format!("{}_{}", prefix, name)).ok();
+        let mut estimator = defaults;
+
+        if let Some(source) = var("FEE_SOURCE") {
+            estimator.source = source.parse()?;
+        }
+        if let Some(blocks) = var("FEE_HISTORY_BLOCKS") {
+            estimator.history_blocks = blocks
+                .parse()
+                .with_context(|| format!("Invalid {}_FEE_HISTORY_BLOCKS", prefix))?;
+        }
+        if let Some(percentile) = var("PRIORITY_FEE_PERCENTILE") {
+            estimator.priority_percentile = percentile
+                .parse()
+                .with_context(|| format!("Invalid {}_PRIORITY_FEE_PERCENTILE", prefix))?;
+        }
+        if let Some(gwei) = var("MIN_PRIORITY_FEE_GWEI") {
+            estimator.min_priority_fee = ethers::utils::parse_units(&gwei, "gwei")
+                .map(Into::into)
+                .map_err(|e| anyhow!("Invalid {}_MIN_PRIORITY_FEE_GWEI: {}", prefix, e))?;
+        }
+        if let Some(pct) = var("BASE_FEE_MULTIPLIER_PCT") {
+            estimator.base_fee_multiplier_pct = pct
+                .parse()
+                .with_context(|| format!("Invalid {}_BASE_FEE_MULTIPLIER_PCT", prefix))?;
+        }
+
+        if !(1..=1024).contains(&estimator.history_blocks) {
+            bail!("{}_FEE_HISTORY_BLOCKS must be between 1 and 1024", prefix);
+        }
+        if !(0.0..=100.0).contains(&estimator.priority_percentile) {
+            bail!(
+                "{}_PRIORITY_FEE_PERCENTILE must be between 0 and 100",
+                prefix
+            );
+        }
+        if estimator.base_fee_multiplier_pct < 100 {
+            bail!("{}_BASE_FEE_MULTIPLIER_PCT must be at least 100", prefix);
+        }
+
+        Ok(estimator)
+    }
+
+    pub async fn estimate<M: Middleware>(&self, client: &M) -> Result<FeeEstimate> {
+        match self.source {
+            FeeSource::FeeHistory => {
+                let history = client
+                    .fee_history(
+                        self.history_blocks,
+                        BlockNumber::Latest,
+                        &[self.priority_percentile],
+                    )
+                    .await
+                    .map_err(|e| anyhow!("eth_feeHistory failed: {}", e))?;
+                self.estimate_from_history(&history)
+            }
+            FeeSource::GasPrice => {
+                let gas_price = client
+                    .get_gas_price()
+                    .await
+                    .map_err(|e| anyhow!("Failed to get gas price: {}", e))?;
+                let base_fee = client
+                    .get_block(BlockNumber::Latest)
+                    .await
+                    .map_err(|e| anyhow!("Failed to get latest block: {}", e))?
+                    .and_then(|block| block.base_fee_per_gas)
+                    .unwrap_or_default();
+                Ok(self.estimate_from_gas_price(gas_price, base_fee))
+            }
+        }
+    }
+
+    /// Fees from an `eth_feeHistory` response sampled at
+    /// `priority_percentile`.
+    pub fn estimate_from_history(&self, history: &FeeHistory) -> Result<FeeEstimate> {
+        // The last entry is the base fee of the block after the newest one
+        let base_fee = *history
+            .base_fee_per_gas
+            .last()
+            .ok_or_else(|| anyhow!("eth_feeHistory returned no base fee"))?;
+
+        let mut tips: Vec<U256> = history
+            .reward
+            .iter()
+            .filter_map(|rewards| rewards.first().copied())
+            .filter(|tip| !tip.is_zero())
+            .collect();
+        tips.sort();
+        let priority_fee = tips
+            .get(tips.len() / 2)
+            .copied()
+            .unwrap_or_default()
+            .max(self.min_priority_fee);
+
+        Ok(FeeEstimate {
+            max_fee_per_gas: scale(base_fee, self.base_fee_multiplier_pct)
+                .saturating_add(priority_fee),
+            max_priority_fee_per_gas: priority_fee,
+        })
+    }
+
+    /// Fees from `eth_gasPrice`, which on OP-stack chains is the base fee
+    /// plus the suggested tip.
+    pub fn estimate_from_gas_price(&self, gas_price: U256, base_fee: U256) -> FeeEstimate {
+        let priority_fee = gas_price
+            .saturating_sub(base_fee)
+            .max(self.min_priority_fee);
+
+        FeeEstimate {
+            max_fee_per_gas: scale(base_fee, self.base_fee_multiplier_pct)
+                .saturating_add(priority_fee)
+                .max(gas_price),
+            max_priority_fee_per_gas: priority_fee,
+        }
+    }
+}
+
+/// Stops sends on a chain while its fees are above the configured cap.
+///
+/// Trips the first time a priced transaction exceeds the cap and stays open
+/// until the fee falls to `resume_pct` of the cap, so a fee hovering around
+/// the cap does not let every other transaction through. Clones share state,
+/// so every path holding the chain's strategy sees the same breaker.
+#[derive(Debug, Clone)]
+pub struct FeeCircuitBreaker {
+    pub resume_pct: u64,
+    open: Arc<AtomicBool>,
+}
+
+impl Default for FeeCircuitBreaker {
+    fn default() -> Self {
+        Self::new(90)
+    }
+}
+
+impl FeeCircuitBreaker {
+    pub fn new(resume_pct: u64) -> Self {
+        Self {
+            resume_pct,
+            open: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.open.load(Ordering::Relaxed)
+    }
+
+    /// Check a fee per gas against `cap`, tripping or closing the breaker.
+    pub fn check(&self, label: &str, fee: U256, cap: Option<U256>) -> Result<()> {
+        let Some(cap) = cap else {
+            return Ok(());
+        };
+
+        if self.is_open() {
+            let resume_at = scale(cap, self.resume_pct);
+            if fee > resume_at {
+                bail!(
+                    "[{}] Fee circuit breaker open: fee per gas {} above {} ({}% of cap {})",
+                    label,
+                    fee,
+                    resume_at,
+                    self.resume_pct,
+                    cap
+                );
+            }
+            if self.open.swap(false, Ordering::Relaxed) {
+                info!(
+                    "⛽ [{}] Fee circuit breaker closed, fee per gas back to {}",
+                    label, fee
+                );
+            }
+            return Ok(());
+        }
+
+        if fee > cap {
+            if !self.open.swap(true, Ordering::Relaxed) {
+                warn!(
+                    "⛽ [{}] Fee circuit breaker tripped: fee per gas {} exceeds cap {}",
+                    label, fee, cap
+                );
+            }
+            bail!("[{}] Fee per gas {} exceeds cap {}", label, fee, cap);
+        }
+
+        Ok(())
+    }
+}
+
+fn scale(value: U256, pct: u64) -> U256 {
+    value.saturating_mul(U256::from(pct)) / U256::from(100)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gwei(n: u64) -> U256 {
+        U256::from(n) * U256::exp10(9)
+    }
+
+    #[test]
+    fn test_fee_history_pays_median_tip_over_next_base_fee() {
+        let history = FeeHistory {
+            base_fee_per_gas: vec![gwei(10), gwei(11), gwei(12)],
+            gas_used_ratio: vec![0.5, 0.9],
+            oldest_block: U256::from(100),
+            reward: vec![
+                vec![gwei(1)],
+                vec![U256::zero()],
+                vec![gwei(3)],
+                vec![gwei(2)],
+            ],
+        };
+
+        let fees = FeeEstimator::ethereum()
+            .estimate_from_history(&history)
+            .unwrap();
+        assert_eq!(fees.max_priority_fee_per_gas, gwei(2));
+        assert_eq!(fees.max_fee_per_gas, gwei(26));
+
+        let empty = FeeHistory {
+            reward: vec![vec![U256::zero()]],
+            ..history
+        };
+        let fees = FeeEstimator::ethereum()
+            .estimate_from_history(&empty)
+            .unwrap();
+        assert_eq!(fees.max_priority_fee_per_gas, U256::exp10(8));
+    }
+
+    #[test]
+    fn test_gas_price_splits_tip_from_base_fee() {
+        let fees = FeeEstimator::mantle().estimate_from_gas_price(U256::from(25), U256::from(20));
+        assert_eq!(fees.max_priority_fee_per_gas, U256::from(5));
+        assert_eq!(fees.max_fee_per_gas, U256::from(25));
+
+        // Pre-London blocks report no base fee
+        let fees = FeeEstimator::mantle().estimate_from_gas_price(U256::from(25), U256::zero());
+        assert_eq!(fees.max_fee_per_gas, U256::from(25));
+    }
+
+    #[test]
+    fn test_breaker_stays_open_until_fees_fall_below_resume_level() {
+        let breaker = FeeCircuitBreaker::new(90);
+        let shared = breaker.clone();
+        let cap = Some(gwei(50));
+
+        assert!(breaker.check("Ethereum", gwei(50), cap).is_ok());
+        assert!(breaker.check("Ethereum", gwei(51), cap).is_err());
+        assert!(shared.is_open());
+
+        assert!(shared.check("Ethereum", gwei(48), cap).is_err());
+        assert!(shared.check("Ethereum", gwei(45), cap).is_ok());
+        assert!(!breaker.is_open());
+
+        assert!(breaker.check("Ethereum", gwei(500), None).is_ok());
+    }
+}
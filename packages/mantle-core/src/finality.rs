@@ -0,0 +1,133 @@
+use anyhow::{Context, Result, anyhow};
+use ethers::{providers::Middleware, types::BlockNumber};
+
+/// Which blocks of one chain count as final.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinalitySource {
+    /// Final once this many blocks were built on top of it.
+    Depth(u64),
+    /// Final at or below the node's `safe` block.
+    Safe,
+    /// Final at or below the node's `finalized` block.
+    Finalized,
+}
+
+impl FinalitySource {
+    /// Six blocks, roughly a minute and a half of Ethereum blocks.
+    pub fn ethereum() -> Self {
+        Self::Depth(6)
+    }
+
+    /// Six L2 blocks. Mantle's `safe` and `finalized` tags follow batch
+    /// submission to and finalization on L1, which takes minutes to hours.
+    pub fn mantle() -> Self {
+        Self::Depth(6)
+    }
+
+    /// Override the default from `<PREFIX>_FINALITY`.
+    pub fn from_env(prefix: &str, default: Self) -> Result<Self> {
+        match std::env::var(format!("{}_FINALITY", prefix)) {
+            Ok(value) => value
+                .parse()
+                .with_context(|| format!("Invalid {}_FINALITY", prefix)),
+            Err(_) => Ok(default),
+        }
+    }
+
+    /// Highest block that counts as final right now.
+    pub async fn final_block<M: Middleware>(&self, client: &M) -> Result<u64> {
+        let tag = match self {
+            Self::Depth(depth) => {
+                let head = client
+                    .get_block_number()
+                    .await
+                    .map_err(|e| anyhow!("Failed to get block number: {}", e))?;
+                return Ok(head.as_u64().saturating_sub(*depth));
+            }
+            Self::Safe => BlockNumber::Safe,
+            Self::Finalized => BlockNumber::Finalized,
+        };
+
+        client
+            .get_block(tag)
+            .await
+            .map_err(|e| anyhow!("Failed to get {} block: {}", self, e))?
+            .and_then(|block| block.number)
+            .map(|number| number.as_u64())
+            .ok_or_else(|| anyhow!("Node did not return a {} block", self))
+    }
+
+    pub async fn is_final<M: Middleware>(&self, client: &M, block: u64) -> Result<bool> {
+        Ok(block <= self.final_block(client).await?)
+    }
+}
+
+impl std::fmt::Display for FinalitySource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Depth(depth) => write!(f, "depth {}", depth),
+            Self::Safe => write!(f, "safe"),
+            Self::Finalized => write!(f, "finalized"),
+        }
+    }
+}
+
+/// `safe`, `finalized`, or a block depth such as `12` or `depth:12`.
+impl std::str::FromStr for FinalitySource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let value = s.trim().to_lowercase();
+        match value.as_str() {
+            "safe" => return Ok(Self::Safe),
+            "finalized" => return Ok(Self::Finalized),
+            _ => {}
+        }
+
+        let depth: u64 = value
+            .strip_prefix("depth:")
+            .unwrap_or(&value)
+            .parse()
+            .map_err(|_| anyhow!("Unknown finality source: {}", s))?;
+        if depth == 0 {
+            return Err(anyhow!("Finality depth must be at least 1"));
+        }
+
+        Ok(Self::Depth(depth))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(
+            "safe".parse::<FinalitySource>().unwrap(),
+            FinalitySource::Safe
+        );
+        assert_eq!(
+            "Finalized".parse::<FinalitySource>().unwrap(),
+            FinalitySource::Finalized
+        );
+    }
+
+    #[test]
+    fn test_parse_depth() {
+        assert_eq!(
+            "12".parse::<FinalitySource>().unwrap(),
+            FinalitySource::Depth(12)
+        );
+        assert_eq!(
+            "depth:3".parse::<FinalitySource>().unwrap(),
+            FinalitySource::Depth(3)
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_zero_and_unknown() {
+        assert!("0".parse::<FinalitySource>().is_err());
+        assert!("latest".parse::<FinalitySource>().is_err());
+    }
+}
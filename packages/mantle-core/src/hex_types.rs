@@ -0,0 +1,323 @@
+//! String newtypes for the `0x` hashes, addresses and decimal amounts that
+//! travel through the API, the database and the relayers. Each one is checked
+//! when parsed or deserialized, keeps the text it was given, and converts to
+//! the ethers type without a second parse.
+
+use std::{fmt, ops::Deref, str::FromStr};
+
+use ethers::types::{Address, H256, U256};
+use serde::{Deserialize, Serialize};
+
+use crate::parse::{self, ParseError, ParseErrorKind};
+
+fn require_prefix(field: &'static str, s: &str) -> Result<(), ParseError> {
+    if s.starts_with("0x") || s.starts_with("0X") {
+        Ok(())
+    } else {
+        Err(ParseError {
+            field,
+            kind: ParseErrorKind::MissingPrefix,
+        })
+    }
+}
+
+/// A 32-byte id, commitment, root or transaction hash: `0x` and 64 hex digits.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct H256Hex(String);
+
+impl H256Hex {
+    /// Check `s`, naming `field` in the error.
+    pub fn parse(field: &'static str, s: &str) -> Result<Self, ParseError> {
+        require_prefix(field, s)?;
+        parse::h256(field, s)?;
+        Ok(Self(s.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn h256(&self) -> H256 {
+        parse::h256("hash", &self.0).expect("checked on construction")
+    }
+}
+
+/// An account or contract address: `0x` and 40 hex digits, any case.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AddressHex(String);
+
+impl AddressHex {
+    /// Check `s`, naming `field` in the error.
+    pub fn parse(field: &'static str, s: &str) -> Result<Self, ParseError> {
+        require_prefix(field, s)?;
+        parse::address(field, s)?;
+        Ok(Self(s.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn address(&self) -> Address {
+        parse::address("address", &self.0).expect("checked on construction")
+    }
+}
+
+/// A base-unit token amount in decimal digits, at most `uint256::MAX`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct AmountDec(String);
+
+impl AmountDec {
+    /// Check `s`, naming `field` in the error.
+    pub fn parse(field: &'static str, s: &str) -> Result<Self, ParseError> {
+        parse::amount(field, s)?;
+        Ok(Self(s.to_string()))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn amount(&self) -> U256 {
+        parse::amount("amount", &self.0).expect("checked on construction")
+    }
+}
+
+impl From<H256> for H256Hex {
+    fn from(hash: H256) -> Self {
+        Self(format!("{:?}", hash))
+    }
+}
+
+impl From<&H256Hex> for H256 {
+    fn from(hash: &H256Hex) -> Self {
+        hash.h256()
+    }
+}
+
+impl From<Address> for AddressHex {
+    fn from(address: Address) -> Self {
+        Self(format!("{:?}", address))
+    }
+}
+
+impl From<&AddressHex> for Address {
+    fn from(address: &AddressHex) -> Self {
+        address.address()
+    }
+}
+
+impl From<U256> for AmountDec {
+    fn from(amount: U256) -> Self {
+        Self(amount.to_string())
+    }
+}
+
+impl From<&AmountDec> for U256 {
+    fn from(amount: &AmountDec) -> Self {
+        amount.amount()
+    }
+}
+
+impl FromStr for H256Hex {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Self::parse("hash", s)
+    }
+}
+
+impl FromStr for AddressHex {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Self::parse("address", s)
+    }
+}
+
+impl FromStr for AmountDec {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, ParseError> {
+        Self::parse("amount", s)
+    }
+}
+
+impl TryFrom<String> for H256Hex {
+    type Error = ParseError;
+
+    fn try_from(s: String) -> Result<Self, ParseError> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for AddressHex {
+    type Error = ParseError;
+
+    fn try_from(s: String) -> Result<Self, ParseError> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for AmountDec {
+    type Error = ParseError;
+
+    fn try_from(s: String) -> Result<Self, ParseError> {
+        s.parse()
+    }
+}
+
+impl From<H256Hex> for String {
+    fn from(hash: H256Hex) -> Self {
+        hash.0
+    }
+}
+
+impl From<AddressHex> for String {
+    fn from(address: AddressHex) -> Self {
+        address.0
+    }
+}
+
+impl From<AmountDec> for String {
+    fn from(amount: AmountDec) -> Self {
+        amount.0
+    }
+}
+
+// Read-only `str` access, so the newtypes drop into code written for
+// `String` fields: `&intent_id` coerces to `&str`, `.len()` and
+// `.eq_ignore_ascii_case()` work unchanged.
+
+impl Deref for H256Hex {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for AddressHex {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Deref for AmountDec {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for H256Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Display for AddressHex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Display for AmountDec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl PartialEq<str> for H256Hex {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for H256Hex {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<str> for AddressHex {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for AddressHex {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+impl PartialEq<str> for AmountDec {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for AmountDec {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ID: &str = "0x6c3e7a2b54d8f1c0a9e5b3d7f2a1c8e4b6d0f9a3c5e7b1d2f4a6c8e0b3d5f7a9";
+    const TOKEN: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+
+    #[test]
+    fn test_newtypes_check_on_deserialize() {
+        let id: H256Hex = serde_json::from_str(&format!("\"{}\"", ID)).unwrap();
+        assert_eq!(id, ID);
+        assert_eq!(id.h256(), ID.parse::<H256>().unwrap());
+        assert_eq!(H256Hex::from(id.h256()), id);
+        assert_eq!(serde_json::to_string(&id).unwrap(), format!("\"{}\"", ID));
+
+        // Text is kept as given, so checksummed addresses survive a round trip
+        let token: AddressHex = TOKEN.parse().unwrap();
+        assert_eq!(token.as_str(), TOKEN);
+        assert_eq!(token.address(), TOKEN.parse::<Address>().unwrap());
+        assert_eq!(
+            AddressHex::from(token.address()).as_str(),
+            TOKEN.to_lowercase()
+        );
+
+        let amount: AmountDec = serde_json::from_str("\"1500000\"").unwrap();
+        assert_eq!(amount.amount(), U256::from(1_500_000u64));
+        assert_eq!(AmountDec::from(U256::from(7u64)), "7");
+
+        for input in ["\"0x01\"", "\"6c3e\"", "\"\"", "1"] {
+            assert!(serde_json::from_str::<H256Hex>(input).is_err(), "{}", input);
+        }
+        assert_eq!(
+            H256Hex::parse("commitment", &ID[2..]).unwrap_err().kind,
+            ParseErrorKind::MissingPrefix
+        );
+        assert_eq!(
+            serde_json::from_str::<H256Hex>("\"0xabcd\"")
+                .unwrap_err()
+                .to_string(),
+            "Invalid hash length: expected 32 bytes, got 2"
+        );
+        assert!(serde_json::from_str::<AddressHex>(&format!("\"{}\"", ID)).is_err());
+        for input in ["\"-1\"", "\"1.5\"", "\"0x10\"", "\"\""] {
+            assert!(
+                serde_json::from_str::<AmountDec>(input).is_err(),
+                "{}",
+                input
+            );
+        }
+    }
+}
@@ -0,0 +1,420 @@
+//! Outbound HTTP client shared by every integration that calls a third-party
+//! API (price feeds, compliance screening, alert and webhook deliveries).
+//!
+//! Requests to one host are spaced to that host's rate limit. Transport
+//! errors, `429` and `5xx` are retried with jittered exponential backoff,
+//! and a host that keeps failing is skipped for a cooldown instead of being
+//! hammered. Per-host counters are exposed through [`OutboundClient::metrics`].
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, anyhow};
+use ethers::core::rand::{Rng, thread_rng};
+use reqwest::{Client, RequestBuilder, Response, StatusCode, header::RETRY_AFTER};
+use serde::{Deserialize, Serialize};
+use tracing::warn;
+
+/// Longest `Retry-After` we are willing to sleep through inside one call.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+pub struct OutboundPolicy {
+    /// Requests per second to a host without an entry in `host_rps`.
+    pub default_rps: f64,
+    pub host_rps: HashMap<String, f64>,
+    pub max_retries: u32,
+    pub retry_base: Duration,
+    /// Consecutive failures that open a host's circuit.
+    pub breaker_threshold: u32,
+    pub breaker_cooldown: Duration,
+    pub timeout: Duration,
+}
+
+impl Default for OutboundPolicy {
+    fn default() -> Self {
+        Self {
+            default_rps: 5.0,
+            // Public tiers of the price APIs
+            host_rps: HashMap::from([
+                ("api.coingecko.com".to_string(), 0.5),
+                ("min-api.cryptocompare.com".to_string(), 2.0),
+            ]),
+            max_retries: 2,
+            retry_base: Duration::from_millis(250),
+            breaker_threshold: 5,
+            breaker_cooldown: Duration::from_secs(60),
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl OutboundPolicy {
+    /// Reads `OUTBOUND_HTTP_DEFAULT_RPS`, `OUTBOUND_HTTP_HOST_RPS`
+    /// (`host=rps,...`, merged over the built-in limits),
+    /// `OUTBOUND_HTTP_MAX_RETRIES`, `OUTBOUND_HTTP_RETRY_BASE_MS`,
+    /// `OUTBOUND_HTTP_BREAKER_THRESHOLD`, `OUTBOUND_HTTP_BREAKER_COOLDOWN_SECS`
+    /// and `OUTBOUND_HTTP_TIMEOUT_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(rps) = std::env::var("OUTBOUND_HTTP_DEFAULT_RPS") {
+            policy.default_rps = parse_rps(&rps).context("Invalid OUTBOUND_HTTP_DEFAULT_RPS")?;
+        }
+        if let Ok(raw) = std::env::var("OUTBOUND_HTTP_HOST_RPS") {
+            policy
+                .host_rps
+                .extend(parse_host_rps(&raw).context("Invalid OUTBOUND_HTTP_HOST_RPS")?);
+        }
+        if let Ok(retries) = std::env::var("OUTBOUND_HTTP_MAX_RETRIES") {
+            policy.max_retries = retries
+                .parse()
+                .context("Invalid OUTBOUND_HTTP_MAX_RETRIES")?;
+        }
+        if let Ok(ms) = std::env::var("OUTBOUND_HTTP_RETRY_BASE_MS") {
+            policy.retry_base = Duration::from_millis(
+                ms.parse::<u64>()
+                    .context("Invalid OUTBOUND_HTTP_RETRY_BASE_MS")?
+                    .max(1),
+            );
+        }
+        if let Ok(threshold) = std::env::var("OUTBOUND_HTTP_BREAKER_THRESHOLD") {
+            policy.breaker_threshold = threshold
+                .parse::<u32>()
+                .context("Invalid OUTBOUND_HTTP_BREAKER_THRESHOLD")?
+                .max(1);
+        }
+        if let Ok(secs) = std::env::var("OUTBOUND_HTTP_BREAKER_COOLDOWN_SECS") {
+            policy.breaker_cooldown = Duration::from_secs(
+                secs.parse()
+                    .context("Invalid OUTBOUND_HTTP_BREAKER_COOLDOWN_SECS")?,
+            );
+        }
+        if let Ok(secs) = std::env::var("OUTBOUND_HTTP_TIMEOUT_SECS") {
+            policy.timeout = Duration::from_secs(
+                secs.parse::<u64>()
+                    .context("Invalid OUTBOUND_HTTP_TIMEOUT_SECS")?
+                    .max(1),
+            );
+        }
+
+        Ok(policy)
+    }
+
+    fn rps_for(&self, host: &str) -> f64 {
+        self.host_rps.get(host).copied().unwrap_or(self.default_rps)
+    }
+
+    /// Backoff before retry `attempt` (1-based): half of the exponential
+    /// delay fixed, the other half random.
+    fn backoff(&self, attempt: u32) -> Duration {
+        let full = self
+            .retry_base
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)));
+        let half = full / 2;
+        half + half.mul_f64(thread_rng().gen_range(0.0..=1.0))
+    }
+}
+
+fn parse_rps(value: &str) -> Result<f64> {
+    let rps: f64 = value.trim().parse()?;
+    if !(rps > 0.0 && rps.is_finite()) {
+        return Err(anyhow!("Rate must be positive, got {}", value));
+    }
+    Ok(rps)
+}
+
+/// `api.example.com=2,other.example.com=0.5`
+pub fn parse_host_rps(raw: &str) -> Result<HashMap<String, f64>> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (host, rps) = entry
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Expected host=rps, got {}", entry))?;
+            Ok((host.trim().to_lowercase(), parse_rps(rps)?))
+        })
+        .collect()
+}
+
+/// Counters for one host since startup.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HostMetrics {
+    pub host: String,
+    pub requests: u64,
+    pub successes: u64,
+    pub failures: u64,
+    pub retries: u64,
+    /// Requests that waited for a rate-limit slot.
+    pub throttled: u64,
+    /// Requests refused without being sent because the circuit was open.
+    pub rejected: u64,
+    pub circuit_open: bool,
+    pub last_status: Option<u16>,
+}
+
+#[derive(Debug, Default)]
+struct HostState {
+    next_slot: Option<Instant>,
+    consecutive_failures: u32,
+    open_until: Option<Instant>,
+    metrics: HostMetrics,
+}
+
+impl HostState {
+    /// Reserve the next send slot; returns how long to wait for it.
+    fn reserve(&mut self, now: Instant, rps: f64) -> Duration {
+        let slot = self.next_slot.map_or(now, |next| next.max(now));
+        self.next_slot = Some(slot + Duration::from_secs_f64(1.0 / rps));
+        let wait = slot - now;
+        if !wait.is_zero() {
+            self.metrics.throttled += 1;
+        }
+        wait
+    }
+
+    fn is_open(&self, now: Instant) -> bool {
+        self.open_until.is_some_and(|until| now < until)
+    }
+
+    fn record_success(&mut self, status: Option<u16>) {
+        self.consecutive_failures = 0;
+        self.open_until = None;
+        self.metrics.successes += 1;
+        self.metrics.last_status = status;
+    }
+
+    /// Returns true when this failure opened the circuit.
+    fn record_failure(
+        &mut self,
+        now: Instant,
+        status: Option<u16>,
+        policy: &OutboundPolicy,
+    ) -> bool {
+        self.consecutive_failures += 1;
+        self.metrics.failures += 1;
+        self.metrics.last_status = status;
+        // A failed probe after the cooldown re-opens at once
+        if self.consecutive_failures >= policy.breaker_threshold && !self.is_open(now) {
+            self.open_until = Some(now + policy.breaker_cooldown);
+            return true;
+        }
+        false
+    }
+}
+
+struct Inner {
+    client: Client,
+    policy: OutboundPolicy,
+    hosts: Mutex<HashMap<String, HostState>>,
+}
+
+/// Cheap to clone; clones share limits, breakers and metrics.
+#[derive(Clone)]
+pub struct OutboundClient {
+    inner: Arc<Inner>,
+}
+
+impl Default for OutboundClient {
+    fn default() -> Self {
+        Self::new(OutboundPolicy::default()).expect("Failed to build outbound HTTP client")
+    }
+}
+
+impl OutboundClient {
+    pub fn new(policy: OutboundPolicy) -> Result<Self> {
+        let client = Client::builder()
+            .timeout(policy.timeout)
+            .build()
+            .context("Failed to build outbound HTTP client")?;
+
+        Ok(Self {
+            inner: Arc::new(Inner {
+                client,
+                policy,
+                hosts: Mutex::new(HashMap::new()),
+            }),
+        })
+    }
+
+    pub fn from_env() -> Result<Self> {
+        Self::new(OutboundPolicy::from_env()?)
+    }
+
+    pub fn policy(&self) -> &OutboundPolicy {
+        &self.inner.policy
+    }
+
+    pub async fn get(&self, url: &str) -> Result<Response> {
+        self.send(url, |client| client.get(url)).await
+    }
+
+    /// Send the request `build` creates, retrying transport errors, `429`
+    /// and `5xx`. `build` runs once per attempt. Other statuses are returned
+    /// as they are for the caller to check.
+    pub async fn send<F>(&self, url: &str, build: F) -> Result<Response>
+    where
+        F: Fn(&Client) -> RequestBuilder,
+    {
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_lowercase))
+            .ok_or_else(|| anyhow!("Invalid outbound URL {}", url))?;
+        let policy = &self.inner.policy;
+
+        let mut attempt = 0;
+        loop {
+            let wait = self.with_host(&host, |state| {
+                let now = Instant::now();
+                if state.is_open(now) {
+                    state.metrics.rejected += 1;
+                    return Err(anyhow!(
+                        "Circuit open for {} after {} consecutive failures",
+                        host,
+                        state.consecutive_failures
+                    ));
+                }
+                state.metrics.requests += 1;
+                if attempt > 0 {
+                    state.metrics.retries += 1;
+                }
+                Ok(state.reserve(now, policy.rps_for(&host)))
+            })?;
+            if !wait.is_zero() {
+                tokio::time::sleep(wait).await;
+            }
+
+            let result = build(&self.inner.client).send().await;
+            let retry_after = match &result {
+                Ok(response) if is_retryable(response.status()) => Some(
+                    response
+                        .headers()
+                        .get(RETRY_AFTER)
+                        .and_then(|value| value.to_str().ok())
+                        .and_then(|value| value.parse::<u64>().ok())
+                        .map(Duration::from_secs),
+                ),
+                Ok(_) => None,
+                Err(_) => Some(None),
+            };
+            let status = result.as_ref().ok().map(|r| r.status().as_u16());
+
+            let Some(retry_after) = retry_after else {
+                self.with_host(&host, |state| state.record_success(status));
+                return result.context("Outbound request failed");
+            };
+
+            let opened = self.with_host(&host, |state| {
+                state.record_failure(Instant::now(), status, policy)
+            });
+            if opened {
+                warn!(
+                    "🔌 Outbound circuit for {} opened for {}s",
+                    host,
+                    policy.breaker_cooldown.as_secs()
+                );
+            }
+
+            attempt += 1;
+            if attempt > policy.max_retries || opened {
+                return match result {
+                    Ok(response) => Ok(response),
+                    Err(e) => Err(anyhow!(e).context(format!("Request to {} failed", host))),
+                };
+            }
+
+            let delay = retry_after
+                .map(|after| after.min(MAX_RETRY_AFTER))
+                .unwrap_or_else(|| policy.backoff(attempt));
+            tokio::time::sleep(delay).await;
+        }
+    }
+
+    fn with_host<T>(&self, host: &str, f: impl FnOnce(&mut HostState) -> T) -> T {
+        let mut hosts = self
+            .inner
+            .hosts
+            .lock()
+            .expect("outbound host state poisoned");
+        let state = hosts.entry(host.to_string()).or_insert_with(|| HostState {
+            metrics: HostMetrics {
+                host: host.to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        f(state)
+    }
+
+    /// Per-host counters, sorted by host.
+    pub fn metrics(&self) -> Vec<HostMetrics> {
+        let now = Instant::now();
+        let hosts = self
+            .inner
+            .hosts
+            .lock()
+            .expect("outbound host state poisoned");
+        let mut metrics: Vec<HostMetrics> = hosts
+            .values()
+            .map(|state| HostMetrics {
+                circuit_open: state.is_open(now),
+                ..state.metrics.clone()
+            })
+            .collect();
+        metrics.sort_by(|a, b| a.host.cmp(&b.host));
+        metrics
+    }
+}
+
+fn is_retryable(status: StatusCode) -> bool {
+    status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limit_and_breaker() {
+        let policy = OutboundPolicy {
+            breaker_threshold: 2,
+            breaker_cooldown: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let mut state = HostState::default();
+        let now = Instant::now();
+
+        // 2 rps: the second request waits half a second, the third a second
+        assert_eq!(state.reserve(now, 2.0), Duration::ZERO);
+        assert_eq!(state.reserve(now, 2.0), Duration::from_millis(500));
+        assert_eq!(state.reserve(now, 2.0), Duration::from_secs(1));
+        assert_eq!(state.metrics.throttled, 2);
+        // An idle host does not bank slots
+        let later = now + Duration::from_secs(10);
+        assert_eq!(state.reserve(later, 2.0), Duration::ZERO);
+
+        assert!(!state.record_failure(now, Some(503), &policy));
+        assert!(state.record_failure(now, Some(503), &policy));
+        assert!(state.is_open(now + Duration::from_secs(59)));
+        assert!(!state.is_open(now + Duration::from_secs(60)));
+        state.record_success(Some(200));
+        assert!(!state.is_open(now));
+        assert_eq!(state.consecutive_failures, 0);
+
+        for attempt in 1..=4 {
+            let full = policy.retry_base * 2u32.pow(attempt - 1);
+            let delay = policy.backoff(attempt);
+            assert!(delay >= full / 2 && delay <= full, "{:?}", delay);
+        }
+
+        let limits = parse_host_rps(" API.example.com=2, other.example.com=0.5 ").unwrap();
+        assert_eq!(limits["api.example.com"], 2.0);
+        assert_eq!(limits["other.example.com"], 0.5);
+        assert!(parse_host_rps("api.example.com").is_err());
+        assert!(parse_host_rps("api.example.com=0").is_err());
+    }
+}
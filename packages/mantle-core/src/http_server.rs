@@ -0,0 +1,132 @@
+//! Transport options for the relayer and solver HTTP servers: response
+//! compression, cleartext HTTP/2 and in-process TLS termination.
+
+use std::{path::PathBuf, sync::Arc};
+
+use anyhow::{Context, Result, anyhow};
+use rustls::{
+    ServerConfig,
+    pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject},
+};
+
+/// PEM certificate chain and private key for TLS termination.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TlsFiles {
+    pub cert_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpServerOptions {
+    /// Negotiate brotli/gzip/zstd from `Accept-Encoding`.
+    pub compression: bool,
+    /// Accept HTTP/2 with prior knowledge on plaintext listeners. TLS
+    /// listeners always offer `h2` through ALPN.
+    pub http2: bool,
+    /// Terminate TLS in-process instead of behind a proxy.
+    pub tls: Option<TlsFiles>,
+}
+
+impl Default for HttpServerOptions {
+    fn default() -> Self {
+        Self {
+            compression: true,
+            http2: true,
+            tls: None,
+        }
+    }
+}
+
+impl HttpServerOptions {
+    /// Reads `HTTP_COMPRESSION_ENABLED` (default true), `HTTP2_ENABLED`
+    /// (default true) and `TLS_CERT_PATH`/`TLS_KEY_PATH`, which must be set
+    /// together.
+    pub fn from_env() -> Result<Self> {
+        let tls = match (
+            std::env::var("TLS_CERT_PATH")
+                .ok()
+                .filter(|v| !v.is_empty()),
+            std::env::var("TLS_KEY_PATH").ok().filter(|v| !v.is_empty()),
+        ) {
+            (Some(cert), Some(key)) => Some(TlsFiles {
+                cert_path: cert.into(),
+                key_path: key.into(),
+            }),
+            (None, None) => None,
+            _ => {
+                return Err(anyhow!(
+                    "TLS_CERT_PATH and TLS_KEY_PATH must be set together"
+                ));
+            }
+        };
+
+        Ok(Self {
+            compression: std::env::var("HTTP_COMPRESSION_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .context("Invalid HTTP_COMPRESSION_ENABLED")?,
+            http2: std::env::var("HTTP2_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .context("Invalid HTTP2_ENABLED")?,
+            tls,
+        })
+    }
+
+    /// Build the rustls config for the configured certificate, if any.
+    pub fn tls_config(&self) -> Result<Option<ServerConfig>> {
+        let Some(files) = &self.tls else {
+            return Ok(None);
+        };
+
+        let certs = CertificateDer::pem_file_iter(&files.cert_path)
+            .with_context(|| format!("Failed to read {}", files.cert_path.display()))?
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("Invalid certificate in {}", files.cert_path.display()))?;
+        if certs.is_empty() {
+            return Err(anyhow!(
+                "No certificates found in {}",
+                files.cert_path.display()
+            ));
+        }
+
+        let key = PrivateKeyDer::from_pem_file(&files.key_path)
+            .with_context(|| format!("Invalid private key in {}", files.key_path.display()))?;
+
+        let config =
+            ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+                .with_safe_default_protocol_versions()
+                .context("Unsupported TLS protocol versions")?
+                .with_no_client_auth()
+                .with_single_cert(certs, key)
+                .context("Certificate does not match private key")?;
+
+        Ok(Some(config))
+    }
+
+    pub fn scheme(&self) -> &'static str {
+        if self.tls.is_some() { "https" } else { "http" }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tls_requires_cert_and_key() {
+        let plain = HttpServerOptions::default();
+        assert!(plain.tls_config().unwrap().is_none());
+        assert_eq!(plain.scheme(), "http");
+
+        let missing = HttpServerOptions {
+            tls: Some(TlsFiles {
+                cert_path: "/nonexistent/cert.pem".into(),
+                key_path: "/nonexistent/key.pem".into(),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(missing.scheme(), "https");
+        assert!(missing.tls_config().is_err());
+    }
+}
@@ -0,0 +1,26 @@
+//! Definitions shared by the relayer (`shadow-swap`) and the `solver`: supported
+//! tokens and chains, the runtime token registry, per-chain finality sources, amount scaling, contract
+//! bindings, transaction strategies and fee estimation, price feeds and their sources, per-route minimum intent
+//! sizes, HTTP server transport options, the rate-limited client for outbound
+//! HTTP calls, metric counters that survive restarts, the Prometheus exporter
+//! and checked parsing of hex ids, addresses and amounts, plus string newtypes
+//! that carry the check with them. Anything both
+//! binaries must agree on lives here.
+
+pub mod abi;
+pub mod amount;
+pub mod chain;
+pub mod counters;
+pub mod fee_estimator;
+pub mod finality;
+pub mod hex_types;
+pub mod http_client;
+pub mod http_server;
+pub mod metrics;
+pub mod parse;
+pub mod price_source;
+pub mod pricefeed;
+pub mod route;
+pub mod token;
+pub mod token_registry;
+pub mod tx_strategy;
@@ -0,0 +1,270 @@
+use anyhow::{Result, anyhow};
+use prometheus::{
+    Encoder, GaugeVec, HistogramOpts, HistogramVec, IntCounterVec, Opts, Registry, TextEncoder,
+};
+
+/// Content type of the Prometheus text exposition format.
+pub const CONTENT_TYPE: &str = "text/plain; version=0.0.4";
+
+/// Buckets for send-to-receipt latency, from one fast L2 block to the
+/// relayer's two minute receipt timeout.
+const CONFIRMATION_BUCKETS: &[f64] = &[1.0, 2.0, 5.0, 10.0, 15.0, 30.0, 60.0, 90.0, 120.0, 300.0];
+
+/// Prometheus series both binaries export under their own namespace
+/// (`shadow_swap_*`, `solver_*`). The JSON `/api/v1/metrics` payloads keep
+/// their lifetime totals; these counters start at zero with the process.
+pub struct Metrics {
+    registry: Registry,
+    /// `chain`, `outcome` (`success` or `failure`).
+    pub fills: IntCounterVec,
+    /// `chain`, `outcome`.
+    pub claims: IntCounterVec,
+    /// Transactions mined with status 0, by `chain` and `tx_type`.
+    pub reverts: IntCounterVec,
+    /// Roots pushed to `dest`, by `source`, `dest` and `tree`.
+    pub root_syncs: IntCounterVec,
+    /// Seconds from send to receipt, by `chain` and `tx_type`.
+    pub tx_confirmation_seconds: HistogramVec,
+    /// Wallet balance in whole tokens, by `chain` and `token`.
+    pub balance: GaugeVec,
+    /// Solver fills landed but not yet repaid, in USD, by `token`.
+    pub capital_at_risk_usd: GaugeVec,
+    /// Solver inventory transfers between chains, by `token`, `dest` and
+    /// `outcome` (`started`, `completed`, `failed`, `expired`, `limited`).
+    pub rebalances: IntCounterVec,
+    /// Share of a token's solver inventory held on `chain`, 0 to 1.
+    pub inventory_share: GaugeVec,
+    /// Received chain events not yet applied, by `source`.
+    pub ingestion_pending: GaugeVec,
+    /// Age of the oldest event not yet applied, by `source`.
+    pub ingestion_lag_seconds: GaugeVec,
+    /// Events that ran out of delivery attempts, by `source`.
+    pub ingestion_dead_letters: GaugeVec,
+}
+
+impl Metrics {
+    pub fn new(namespace: &str) -> Result<Self> {
+        let registry = Registry::new();
+        let opts = |name: &str, help: &str| Opts::new(name, help).namespace(namespace);
+
+        let fills = IntCounterVec::new(
+            opts(
+                "fills_total",
+                "Intent fills by destination chain and outcome",
+            ),
+            &["chain", "outcome"],
+        )?;
+        let claims = IntCounterVec::new(
+            opts("claims_total", "Withdrawal claims by chain and outcome"),
+            &["chain", "outcome"],
+        )?;
+        let reverts = IntCounterVec::new(
+            opts("tx_reverts_total", "Transactions mined with a revert"),
+            &["chain", "tx_type"],
+        )?;
+        let root_syncs = IntCounterVec::new(
+            opts("root_syncs_total", "Merkle roots pushed to another chain"),
+            &["source", "dest", "tree"],
+        )?;
+        let tx_confirmation_seconds = HistogramVec::new(
+            HistogramOpts::new(
+                "tx_confirmation_seconds",
+                "Seconds from sending a transaction to its receipt",
+            )
+            .namespace(namespace)
+            .buckets(CONFIRMATION_BUCKETS.to_vec()),
+            &["chain", "tx_type"],
+        )?;
+        let balance = GaugeVec::new(
+            opts("balance", "Wallet balance in whole tokens"),
+            &["chain", "token"],
+        )?;
+        let capital_at_risk_usd = GaugeVec::new(
+            opts(
+                "capital_at_risk_usd",
+                "Fills landed but not yet repaid, in USD",
+            ),
+            &["token"],
+        )?;
+        let rebalances = IntCounterVec::new(
+            opts(
+                "rebalances_total",
+                "Inventory transfers between chains by token, destination and outcome",
+            ),
+            &["token", "dest", "outcome"],
+        )?;
+        let inventory_share = GaugeVec::new(
+            opts(
+                "inventory_share",
+                "Share of a token's inventory held on the chain",
+            ),
+            &["chain", "token"],
+        )?;
+        let ingestion_pending = GaugeVec::new(
+            opts("ingestion_pending", "Received chain events not yet applied"),
+            &["source"],
+        )?;
+        let ingestion_lag_seconds = GaugeVec::new(
+            opts(
+                "ingestion_lag_seconds",
+                "Age of the oldest chain event not yet applied",
+            ),
+            &["source"],
+        )?;
+        let ingestion_dead_letters = GaugeVec::new(
+            opts(
+                "ingestion_dead_letters",
+                "Chain events that ran out of delivery attempts",
+            ),
+            &["source"],
+        )?;
+
+        registry.register(Box::new(fills.clone()))?;
+        registry.register(Box::new(claims.clone()))?;
+        registry.register(Box::new(reverts.clone()))?;
+        registry.register(Box::new(root_syncs.clone()))?;
+        registry.register(Box::new(tx_confirmation_seconds.clone()))?;
+        registry.register(Box::new(balance.clone()))?;
+        registry.register(Box::new(capital_at_risk_usd.clone()))?;
+        registry.register(Box::new(rebalances.clone()))?;
+        registry.register(Box::new(inventory_share.clone()))?;
+        registry.register(Box::new(ingestion_pending.clone()))?;
+        registry.register(Box::new(ingestion_lag_seconds.clone()))?;
+        registry.register(Box::new(ingestion_dead_letters.clone()))?;
+
+        Ok(Self {
+            registry,
+            fills,
+            claims,
+            reverts,
+            root_syncs,
+            tx_confirmation_seconds,
+            balance,
+            capital_at_risk_usd,
+            rebalances,
+            inventory_share,
+            ingestion_pending,
+            ingestion_lag_seconds,
+            ingestion_dead_letters,
+        })
+    }
+
+    pub fn fill(&self, chain: &str, succeeded: bool) {
+        self.fills
+            .with_label_values(&[chain, outcome(succeeded)])
+            .inc();
+    }
+
+    pub fn claim(&self, chain: &str, succeeded: bool) {
+        self.claims
+            .with_label_values(&[chain, outcome(succeeded)])
+            .inc();
+    }
+
+    /// Record a mined transaction: its latency, and a revert when it failed.
+    pub fn confirmed(
+        &self,
+        chain: &str,
+        tx_type: &str,
+        elapsed: std::time::Duration,
+        succeeded: bool,
+    ) {
+        self.tx_confirmation_seconds
+            .with_label_values(&[chain, tx_type])
+            .observe(elapsed.as_secs_f64());
+        if !succeeded {
+            self.reverts.with_label_values(&[chain, tx_type]).inc();
+        }
+    }
+
+    pub fn root_synced(&self, source: &str, dest: &str, tree: &str) {
+        self.root_syncs
+            .with_label_values(&[source, dest, tree])
+            .inc();
+    }
+
+    pub fn set_balance(&self, chain: &str, token: &str, whole_tokens: f64) {
+        self.balance
+            .with_label_values(&[chain, token])
+            .set(whole_tokens);
+    }
+
+    pub fn set_capital_at_risk(&self, token: &str, usd: f64) {
+        self.capital_at_risk_usd
+            .with_label_values(&[token])
+            .set(usd);
+    }
+
+    pub fn rebalance(&self, token: &str, dest: &str, outcome: &str) {
+        self.rebalances
+            .with_label_values(&[token, dest, outcome])
+            .inc();
+    }
+
+    pub fn set_inventory_share(&self, chain: &str, token: &str, share: f64) {
+        self.inventory_share
+            .with_label_values(&[chain, token])
+            .set(share);
+    }
+
+    pub fn set_ingestion_lag(&self, source: &str, pending: i64, dead: i64, lag_secs: u64) {
+        self.ingestion_pending
+            .with_label_values(&[source])
+            .set(pending as f64);
+        self.ingestion_dead_letters
+            .with_label_values(&[source])
+            .set(dead as f64);
+        self.ingestion_lag_seconds
+            .with_label_values(&[source])
+            .set(lag_secs as f64);
+    }
+
+    /// Every series in the text exposition format.
+    pub fn render(&self) -> Result<String> {
+        let mut buffer = Vec::new();
+        TextEncoder::new()
+            .encode(&self.registry.gather(), &mut buffer)
+            .map_err(|e| anyhow!("Failed to encode metrics: {}", e))?;
+
+        String::from_utf8(buffer).map_err(|e| anyhow!("Metrics are not UTF-8: {}", e))
+    }
+}
+
+fn outcome(succeeded: bool) -> &'static str {
+    if succeeded { "success" } else { "failure" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_includes_namespaced_series() {
+        let metrics = Metrics::new("solver").unwrap();
+        metrics.fill("mantle", true);
+        metrics.fill("mantle", true);
+        metrics.claim("ethereum", false);
+        metrics.confirmed(
+            "ethereum",
+            "fill_intent",
+            std::time::Duration::from_secs(3),
+            false,
+        );
+        metrics.set_balance("mantle", "MNT", 12.5);
+        metrics.rebalance("USDC", "mantle", "started");
+
+        let text = metrics.render().unwrap();
+        assert!(text.contains(r#"solver_fills_total{chain="mantle",outcome="success"} 2"#));
+        assert!(text.contains(r#"solver_claims_total{chain="ethereum",outcome="failure"} 1"#));
+        assert!(
+            text.contains(r#"solver_tx_reverts_total{chain="ethereum",tx_type="fill_intent"} 1"#)
+        );
+        assert!(text.contains(
+            r#"solver_tx_confirmation_seconds_bucket{chain="ethereum",tx_type="fill_intent",le="5"} 1"#
+        ));
+        assert!(text.contains(r#"solver_balance{chain="mantle",token="MNT"} 12.5"#));
+        assert!(text.contains(
+            r#"solver_rebalances_total{dest="mantle",outcome="started",token="USDC"} 1"#
+        ));
+    }
+}
@@ -0,0 +1,232 @@
+use std::fmt;
+
+use ethers::{
+    types::{Address, H256, U256},
+    utils::hex,
+};
+
+/// What was wrong with a string that should have held a hex value or amount.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    Empty,
+    /// Hex without the `0x` prefix where one is required.
+    MissingPrefix,
+    InvalidHex,
+    /// Decoded to the wrong number of bytes.
+    InvalidLength {
+        expected: usize,
+        actual: usize,
+    },
+    InvalidDigit,
+    Overflow,
+}
+
+/// A malformed input, naming the field it was read for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub field: &'static str,
+    pub kind: ParseErrorKind,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ParseErrorKind::Empty => write!(f, "Invalid {}: empty", self.field),
+            ParseErrorKind::MissingPrefix => write!(f, "Invalid {}: missing 0x prefix", self.field),
+            ParseErrorKind::InvalidHex => write!(f, "Invalid {}: not hex", self.field),
+            ParseErrorKind::InvalidLength { expected, actual } => write!(
+                f,
+                "Invalid {} length: expected {} bytes, got {}",
+                self.field, expected, actual
+            ),
+            ParseErrorKind::InvalidDigit => {
+                write!(f, "Invalid {}: not a decimal integer", self.field)
+            }
+            ParseErrorKind::Overflow => write!(f, "Invalid {}: exceeds uint256", self.field),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn error(field: &'static str, kind: ParseErrorKind) -> ParseError {
+    ParseError { field, kind }
+}
+
+/// Hex bytes, with or without a `0x` prefix.
+pub fn hex_bytes(field: &'static str, s: &str) -> Result<Vec<u8>, ParseError> {
+    let digits = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    if digits.is_empty() {
+        return Err(error(field, ParseErrorKind::Empty));
+    }
+    hex::decode(digits).map_err(|_| error(field, ParseErrorKind::InvalidHex))
+}
+
+/// Exactly `N` hex-encoded bytes.
+pub fn hex_fixed<const N: usize>(field: &'static str, s: &str) -> Result<[u8; N], ParseError> {
+    let bytes = hex_bytes(field, s)?;
+    let actual = bytes.len();
+    bytes.try_into().map_err(|_| {
+        error(
+            field,
+            ParseErrorKind::InvalidLength {
+                expected: N,
+                actual,
+            },
+        )
+    })
+}
+
+/// A 32-byte id, commitment, root, nullifier or secret.
+pub fn hex32(field: &'static str, s: &str) -> Result<[u8; 32], ParseError> {
+    hex_fixed(field, s)
+}
+
+pub fn h256(field: &'static str, s: &str) -> Result<H256, ParseError> {
+    hex32(field, s).map(H256)
+}
+
+pub fn address(field: &'static str, s: &str) -> Result<Address, ParseError> {
+    hex_fixed::<20>(field, s).map(Address::from)
+}
+
+/// A base-unit amount: decimal digits only, at most `uint256::MAX`.
+/// Unlike `U256::from_dec_str`, an empty string is an error rather than zero.
+pub fn amount(field: &'static str, s: &str) -> Result<U256, ParseError> {
+    if s.is_empty() {
+        return Err(error(field, ParseErrorKind::Empty));
+    }
+    if !s.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(error(field, ParseErrorKind::InvalidDigit));
+    }
+    U256::from_dec_str(s).map_err(|_| error(field, ParseErrorKind::Overflow))
+}
+
+/// At most the first `chars` characters, for log lines; never panics on
+/// short or non-ASCII input the way `&s[..n]` does.
+pub fn prefix(s: &str, chars: usize) -> &str {
+    let end = s
+        .char_indices()
+        .nth(chars)
+        .map_or(s.len(), |(index, _)| index);
+    &s[..end]
+}
+
+/// `0x` and the first eight hex digits of an id.
+pub fn short(s: &str) -> &str {
+    prefix(s, 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    const ID: &str = "0x6c3e7a2b54d8f1c0a9e5b3d7f2a1c8e4b6d0f9a3c5e7b1d2f4a6c8e0b3d5f7a9";
+
+    #[test]
+    fn test_checked_parsers_reject_malformed_input() {
+        assert_eq!(hex32("intent_id", ID).unwrap()[0], 0x6c);
+        assert_eq!(
+            hex32("intent_id", &ID[2..]).unwrap(),
+            hex32("intent_id", ID).unwrap()
+        );
+        assert_eq!(h256("root", ID).unwrap(), ID.parse::<H256>().unwrap());
+
+        for (input, kind) in [
+            ("", ParseErrorKind::Empty),
+            ("0x", ParseErrorKind::Empty),
+            ("0", ParseErrorKind::InvalidHex),
+            ("0xzz", ParseErrorKind::InvalidHex),
+            (
+                "0xabcd",
+                ParseErrorKind::InvalidLength {
+                    expected: 32,
+                    actual: 2,
+                },
+            ),
+        ] {
+            assert_eq!(
+                hex32("intent_id", input),
+                Err(ParseError {
+                    field: "intent_id",
+                    kind
+                })
+            );
+        }
+        assert_eq!(
+            hex32("nullifier", "0x12").unwrap_err().to_string(),
+            "Invalid nullifier length: expected 32 bytes, got 1"
+        );
+
+        let token = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+        assert_eq!(address("token", token).unwrap(), token.parse().unwrap());
+        assert!(address("token", ID).is_err());
+
+        assert_eq!(
+            amount("amount", "1500000").unwrap(),
+            U256::from(1_500_000u64)
+        );
+        assert_eq!(
+            amount("amount", "").unwrap_err().kind,
+            ParseErrorKind::Empty
+        );
+        for input in ["-1", "1.5", " 1", "0x10", "1e6"] {
+            assert_eq!(
+                amount("amount", input).unwrap_err().kind,
+                ParseErrorKind::InvalidDigit
+            );
+        }
+        let too_big = format!("{}0", U256::MAX);
+        assert_eq!(
+            amount("amount", &too_big).unwrap_err().kind,
+            ParseErrorKind::Overflow
+        );
+
+        assert_eq!(short(ID), "0x6c3e7a2b");
+        assert_eq!(short("0x1"), "0x1");
+        assert_eq!(short("ééééééééééé"), "éééééééééé");
+        assert_eq!(prefix(ID, 18), "0x6c3e7a2b54d8f1c0");
+        assert_eq!(prefix("", 18), "");
+    }
+
+    proptest! {
+        /// Arbitrary strings never panic, and anything accepted round-trips.
+        #[test]
+        fn prop_parsers_never_panic(s in "\\PC{0,80}") {
+            let _ = hex_bytes("field", &s);
+            if let Ok(bytes) = hex32("field", &s) {
+                prop_assert_eq!(hex32("field", &format!("0x{}", hex::encode(bytes))), Ok(bytes));
+            }
+            let _ = address("field", &s);
+            if let Ok(value) = amount("field", &s) {
+                prop_assert_eq!(amount("field", &value.to_string()), Ok(value));
+            }
+            prop_assert!(s.starts_with(short(&s)));
+        }
+
+        #[test]
+        fn prop_hex32_checks_length(bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+            let encoded = format!("0x{}", hex::encode(&bytes));
+            match hex32("field", &encoded) {
+                Ok(decoded) => prop_assert_eq!(decoded.to_vec(), bytes),
+                Err(e) if bytes.is_empty() => prop_assert_eq!(e.kind, ParseErrorKind::Empty),
+                Err(e) => prop_assert_eq!(
+                    e.kind,
+                    ParseErrorKind::InvalidLength { expected: 32, actual: bytes.len() }
+                ),
+            }
+        }
+
+        #[test]
+        fn prop_amount_matches_u256(value in any::<u128>(), junk in "[^0-9]") {
+            let digits = value.to_string();
+            prop_assert_eq!(amount("field", &digits), Ok(U256::from(value)));
+            let padded = format!("{}{}", digits, junk);
+            prop_assert!(amount("field", &padded).is_err());
+        }
+    }
+}
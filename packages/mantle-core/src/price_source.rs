@@ -0,0 +1,519 @@
+use std::{collections::HashMap, fmt, str::FromStr, time::Duration};
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::Utc;
+use ethers::{
+    abi::{ParamType, Token, decode},
+    providers::{Http, Middleware, Provider},
+    types::{Address, Bytes, I256, TransactionRequest, U256},
+};
+
+use crate::{http_client::OutboundClient, parse, token::TokenType};
+
+/// Chainlink's ETH / USD aggregator on Ethereum mainnet.
+const CHAINLINK_ETH_USD: &str = "0x5f4eC3Df9cbd43714FE2740f5E3616155c5b8419";
+/// `latestRoundData()`
+const LATEST_ROUND_DATA: [u8; 4] = [0xfe, 0xaf, 0x96, 0x8c];
+/// `decimals()`
+const DECIMALS: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+
+/// A provider of USD prices. Exchange tickers quoted in USDT count as USD,
+/// like the stablecoins themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PriceSourceKind {
+    /// On-chain aggregator read over `PRICE_CHAINLINK_RPC_URL`.
+    Chainlink,
+    CoinGecko,
+    Binance,
+    CryptoCompare,
+    GateIo,
+    Mexc,
+}
+
+impl PriceSourceKind {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Chainlink => "Chainlink",
+            Self::CoinGecko => "CoinGecko",
+            Self::Binance => "Binance",
+            Self::CryptoCompare => "CryptoCompare",
+            Self::GateIo => "Gate.io",
+            Self::Mexc => "MEXC",
+        }
+    }
+}
+
+impl fmt::Display for PriceSourceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for PriceSourceKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "chainlink" => Ok(Self::Chainlink),
+            "coingecko" => Ok(Self::CoinGecko),
+            "binance" => Ok(Self::Binance),
+            "cryptocompare" => Ok(Self::CryptoCompare),
+            "gateio" | "gate.io" => Ok(Self::GateIo),
+            "mexc" => Ok(Self::Mexc),
+            other => Err(anyhow!("Unknown price source: {}", other)),
+        }
+    }
+}
+
+/// One source's say in the aggregate price.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceSource {
+    pub kind: PriceSourceKind,
+    pub weight: f64,
+    /// How old this source's last good price may be and still count.
+    pub max_age: Duration,
+}
+
+/// A price as one source reported it, with the time the source says it is
+/// from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SourceQuote {
+    pub price: f64,
+    pub updated_at: i64,
+}
+
+/// On-chain aggregators by token.
+#[derive(Debug, Clone)]
+pub struct ChainlinkFeeds {
+    provider: Provider<Http>,
+    feeds: HashMap<TokenType, Address>,
+}
+
+/// The configured price sources. Chainlink is only available with an RPC
+/// URL.
+#[derive(Debug, Clone)]
+pub struct PriceSources {
+    pub sources: Vec<PriceSource>,
+    chainlink: Option<ChainlinkFeeds>,
+}
+
+impl Default for PriceSources {
+    /// The exchange and aggregator APIs, equally weighted.
+    fn default() -> Self {
+        let sources = [
+            PriceSourceKind::CoinGecko,
+            PriceSourceKind::Binance,
+            PriceSourceKind::CryptoCompare,
+            PriceSourceKind::GateIo,
+            PriceSourceKind::Mexc,
+        ]
+        .into_iter()
+        .map(|kind| PriceSource {
+            kind,
+            weight: 1.0,
+            max_age: Duration::from_secs(120),
+        })
+        .collect();
+
+        Self {
+            sources,
+            chainlink: None,
+        }
+    }
+}
+
+/// Parse `name` or `name=weight` entries, comma separated.
+pub fn parse_source_weights(raw: &str) -> Result<Vec<(PriceSourceKind, f64)>> {
+    let mut weights: Vec<(PriceSourceKind, f64)> = Vec::new();
+
+    for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (name, weight) = match entry.split_once('=') {
+            Some((name, weight)) => (
+                name,
+                weight
+                    .trim()
+                    .parse::<f64>()
+                    .with_context(|| format!("Invalid weight in {}", entry))?,
+            ),
+            None => (entry, 1.0),
+        };
+        let kind: PriceSourceKind = name.parse()?;
+        if !(weight.is_finite() && weight > 0.0) {
+            bail!("Weight of {} must be positive", kind);
+        }
+        if weights.iter().any(|(existing, _)| *existing == kind) {
+            bail!("{} listed twice", kind);
+        }
+        weights.push((kind, weight));
+    }
+
+    if weights.is_empty() {
+        bail!("No price sources listed");
+    }
+    Ok(weights)
+}
+
+/// Parse `SYMBOL=0xaggregator` entries, comma separated.
+pub fn parse_chainlink_feeds(raw: &str) -> Result<HashMap<TokenType, Address>> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|e| !e.is_empty())
+        .map(|entry| {
+            let (symbol, feed) = entry
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Expected SYMBOL=0xaggregator, got {}", entry))?;
+            Ok((
+                TokenType::from_symbol(symbol.trim())?,
+                parse::address("chainlink feed", feed.trim())?,
+            ))
+        })
+        .collect()
+}
+
+/// The weighted median of `(price, weight)` samples: the price at which half
+/// the total weight lies on either side. Averages the two middle prices on
+/// an exact split. A single wild source cannot move it past its neighbours.
+pub fn weighted_median(samples: &[(f64, f64)]) -> Option<f64> {
+    let mut samples: Vec<(f64, f64)> = samples
+        .iter()
+        .copied()
+        .filter(|(price, weight)| price.is_finite() && *price > 0.0 && *weight > 0.0)
+        .collect();
+    if samples.is_empty() {
+        return None;
+    }
+    samples.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let half = samples.iter().map(|(_, weight)| weight).sum::<f64>() / 2.0;
+    let mut below = 0.0;
+    for (i, (price, weight)) in samples.iter().enumerate() {
+        below += weight;
+        if (below - half).abs() <= f64::EPSILON * half.max(1.0) {
+            return Some(
+                samples
+                    .get(i + 1)
+                    .map_or(*price, |(next, _)| (price + next) / 2.0),
+            );
+        }
+        if below > half {
+            return Some(*price);
+        }
+    }
+    samples.last().map(|(price, _)| *price)
+}
+
+impl PriceSources {
+    /// Reads `PRICE_SOURCES` (`name[=weight]`, comma separated),
+    /// `PRICE_SOURCE_MAX_AGE_SECS`, `PRICE_CHAINLINK_RPC_URL`,
+    /// `PRICE_CHAINLINK_FEEDS` (`SYMBOL=0xaggregator`) and
+    /// `PRICE_CHAINLINK_MAX_AGE_SECS`. Chainlink joins the defaults, with
+    /// weight 2, once an RPC URL is set.
+    pub fn from_env() -> Result<Self> {
+        let secs = |name: &str, default: u64| -> Result<Duration> {
+            Ok(Duration::from_secs(match std::env::var(name) {
+                Ok(value) => value
+                    .trim()
+                    .parse::<u64>()
+                    .with_context(|| format!("Invalid {}", name))?
+                    .max(1),
+                Err(_) => default,
+            }))
+        };
+        let http_max_age = secs("PRICE_SOURCE_MAX_AGE_SECS", 120)?;
+        // Past the ETH / USD feed's one-hour heartbeat, with some slack
+        let chainlink_max_age = secs("PRICE_CHAINLINK_MAX_AGE_SECS", 3900)?;
+        let rpc_url = std::env::var("PRICE_CHAINLINK_RPC_URL")
+            .ok()
+            .map(|url| url.trim().to_string())
+            .filter(|url| !url.is_empty());
+
+        let weights = match std::env::var("PRICE_SOURCES") {
+            Ok(raw) => parse_source_weights(&raw).context("Invalid PRICE_SOURCES")?,
+            Err(_) => {
+                let mut weights: Vec<_> = Self::default()
+                    .sources
+                    .iter()
+                    .map(|source| (source.kind, source.weight))
+                    .collect();
+                if rpc_url.is_some() {
+                    weights.insert(0, (PriceSourceKind::Chainlink, 2.0));
+                }
+                weights
+            }
+        };
+
+        let uses_chainlink = weights
+            .iter()
+            .any(|(kind, _)| *kind == PriceSourceKind::Chainlink);
+        let chainlink = match (uses_chainlink, rpc_url) {
+            (false, _) => None,
+            (true, None) => {
+                bail!("PRICE_SOURCES lists chainlink but PRICE_CHAINLINK_RPC_URL is unset")
+            }
+            (true, Some(url)) => {
+                let mut feeds = HashMap::from([
+                    (
+                        TokenType::ETH,
+                        parse::address("chainlink feed", CHAINLINK_ETH_USD)?,
+                    ),
+                    (
+                        TokenType::WETH,
+                        parse::address("chainlink feed", CHAINLINK_ETH_USD)?,
+                    ),
+                ]);
+                if let Ok(raw) = std::env::var("PRICE_CHAINLINK_FEEDS") {
+                    feeds.extend(
+                        parse_chainlink_feeds(&raw).context("Invalid PRICE_CHAINLINK_FEEDS")?,
+                    );
+                }
+                Some(ChainlinkFeeds {
+                    provider: Provider::<Http>::try_from(url.as_str())
+                        .context("Invalid PRICE_CHAINLINK_RPC_URL")?,
+                    feeds,
+                })
+            }
+        };
+
+        Ok(Self {
+            sources: weights
+                .into_iter()
+                .map(|(kind, weight)| PriceSource {
+                    kind,
+                    weight,
+                    max_age: if kind == PriceSourceKind::Chainlink {
+                        chainlink_max_age
+                    } else {
+                        http_max_age
+                    },
+                })
+                .collect(),
+            chainlink,
+        })
+    }
+
+    /// Whether `kind` quotes `token` at all, so an unsupported pair is not
+    /// counted as a failure.
+    pub fn supports(&self, kind: PriceSourceKind, token: TokenType) -> bool {
+        match kind {
+            PriceSourceKind::Chainlink => self
+                .chainlink
+                .as_ref()
+                .is_some_and(|chainlink| chainlink.feeds.contains_key(&token)),
+            // CryptoCompare's MNT is a different token
+            PriceSourceKind::CryptoCompare => token != TokenType::MNT,
+            _ => true,
+        }
+    }
+
+    pub async fn fetch(
+        &self,
+        kind: PriceSourceKind,
+        client: &OutboundClient,
+        token: TokenType,
+    ) -> Result<SourceQuote> {
+        // Exchanges list ETH, not WETH
+        let symbol = match token {
+            TokenType::WETH => "ETH",
+            other => other.symbol(),
+        };
+        let price = match kind {
+            PriceSourceKind::Chainlink => return self.chainlink_price(token).await,
+            PriceSourceKind::CoinGecko => coingecko_price(client, symbol).await?,
+            PriceSourceKind::Binance => binance_price(client, symbol).await?,
+            PriceSourceKind::CryptoCompare => cryptocompare_price(client, symbol).await?,
+            PriceSourceKind::GateIo => gateio_price(client, symbol).await?,
+            PriceSourceKind::Mexc => mexc_price(client, symbol).await?,
+        };
+        if !(price.is_finite() && price > 0.0) {
+            bail!("Invalid price {}", price);
+        }
+
+        Ok(SourceQuote {
+            price,
+            updated_at: Utc::now().timestamp(),
+        })
+    }
+
+    /// The aggregator's latest answer, timestamped with its own `updatedAt`.
+    async fn chainlink_price(&self, token: TokenType) -> Result<SourceQuote> {
+        let chainlink = self
+            .chainlink
+            .as_ref()
+            .ok_or_else(|| anyhow!("Chainlink is not configured"))?;
+        let feed = *chainlink
+            .feeds
+            .get(&token)
+            .ok_or_else(|| anyhow!("No Chainlink feed for {}", token.symbol()))?;
+
+        let call = |selector: [u8; 4]| {
+            let tx = TransactionRequest::new()
+                .to(feed)
+                .data(Bytes::from(selector.to_vec()));
+            async move {
+                chainlink
+                    .provider
+                    .call(&tx.into(), None)
+                    .await
+                    .map_err(|e| anyhow!("Chainlink call failed: {}", e))
+            }
+        };
+
+        let decimals =
+            decode(&[ParamType::Uint(8)], &call(DECIMALS).await?).context("Invalid decimals()")?;
+        let round = decode(
+            &[
+                ParamType::Uint(80),
+                ParamType::Int(256),
+                ParamType::Uint(256),
+                ParamType::Uint(256),
+                ParamType::Uint(80),
+            ],
+            &call(LATEST_ROUND_DATA).await?,
+        )
+        .context("Invalid latestRoundData()")?;
+
+        let (Some(Token::Uint(decimals)), Some(Token::Int(answer)), Some(Token::Uint(updated_at))) =
+            (decimals.first(), round.get(1), round.get(3))
+        else {
+            bail!("Unexpected latestRoundData() layout");
+        };
+        let answer = I256::from_raw(*answer);
+        if answer <= I256::zero() || updated_at.is_zero() {
+            bail!("Chainlink round has no answer");
+        }
+
+        Ok(SourceQuote {
+            price: answer.as_u128() as f64 / 10f64.powi(decimals.as_u32() as i32),
+            updated_at: updated_at.min(&U256::from(i64::MAX)).as_u64() as i64,
+        })
+    }
+}
+
+// --- API INTEGRATIONS ---
+
+async fn json(client: &OutboundClient, url: &str) -> Result<serde_json::Value> {
+    let response = client
+        .send(url, |http| {
+            http.get(url).header("Accept", "application/json")
+        })
+        .await?;
+    if !response.status().is_success() {
+        bail!("API error: {}", response.status());
+    }
+    Ok(response.json().await?)
+}
+
+fn number(value: &serde_json::Value) -> Result<f64> {
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| anyhow!("Invalid price format"))
+}
+
+async fn coingecko_price(client: &OutboundClient, symbol: &str) -> Result<f64> {
+    let id = match symbol {
+        "ETH" => "ethereum",
+        "USDC" => "usd-coin",
+        "USDT" => "tether",
+        "MNT" => "mantle",
+        _ => bail!("Unsupported symbol: {}", symbol),
+    };
+    let url = format!(
+        "https://api.coingecko.com/api/v3/simple/price?ids={}&vs_currencies=usd",
+        id
+    );
+    number(&json(client, &url).await?[id]["usd"])
+}
+
+async fn binance_price(client: &OutboundClient, symbol: &str) -> Result<f64> {
+    let url = format!(
+        "https://api.binance.com/api/v3/ticker/price?symbol={}USDT",
+        symbol
+    );
+    number(&json(client, &url).await?["price"])
+}
+
+async fn cryptocompare_price(client: &OutboundClient, symbol: &str) -> Result<f64> {
+    let url = format!(
+        "https://min-api.cryptocompare.com/data/price?fsym={}&tsyms=USD",
+        symbol
+    );
+    number(&json(client, &url).await?["USD"])
+}
+
+async fn gateio_price(client: &OutboundClient, symbol: &str) -> Result<f64> {
+    let pair = format!("{}_USDT", symbol);
+    let url = format!(
+        "https://api.gateio.ws/api/v4/spot/tickers?currency_pair={}",
+        pair
+    );
+    let data = json(client, &url).await?;
+    let ticker = data
+        .as_array()
+        .and_then(|tickers| tickers.first())
+        .ok_or_else(|| anyhow!("No ticker data for pair {}", pair))?;
+    number(&ticker["last"])
+}
+
+async fn mexc_price(client: &OutboundClient, symbol: &str) -> Result<f64> {
+    let url = format!(
+        "https://api.mexc.com/api/v3/ticker/price?symbol={}USDT",
+        symbol
+    );
+    number(&json(client, &url).await?["price"])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weighted_median() {
+        assert_eq!(weighted_median(&[]), None);
+        assert_eq!(weighted_median(&[(2500.0, 1.0)]), Some(2500.0));
+        assert_eq!(
+            weighted_median(&[(3.0, 1.0), (1.0, 1.0), (2.0, 1.0)]),
+            Some(2.0)
+        );
+        // Exact split: the middle two are averaged
+        assert_eq!(weighted_median(&[(1.0, 1.0), (2.0, 1.0)]), Some(1.5));
+        // Weight decides
+        assert_eq!(weighted_median(&[(100.0, 1.0), (200.0, 3.0)]), Some(200.0));
+        // One wild source does not move it
+        assert_eq!(
+            weighted_median(&[(2500.0, 1.0), (2501.0, 1.0), (2502.0, 1.0), (25.0, 1.0)]),
+            Some(2500.5)
+        );
+        assert_eq!(
+            weighted_median(&[(2500.0, 1.0), (0.0, 5.0), (f64::NAN, 5.0)]),
+            Some(2500.0)
+        );
+    }
+
+    #[test]
+    fn test_parse_price_sources() {
+        assert_eq!(
+            parse_source_weights("chainlink=2, coingecko ,gate.io=0.5").unwrap(),
+            vec![
+                (PriceSourceKind::Chainlink, 2.0),
+                (PriceSourceKind::CoinGecko, 1.0),
+                (PriceSourceKind::GateIo, 0.5),
+            ]
+        );
+        assert!(parse_source_weights("").is_err());
+        assert!(parse_source_weights("coingecko,coingecko").is_err());
+        assert!(parse_source_weights("coingecko=0").is_err());
+        assert!(parse_source_weights("kraken").is_err());
+
+        let feeds =
+            parse_chainlink_feeds("MNT=0x0000000000000000000000000000000000000001").unwrap();
+        assert_eq!(feeds[&TokenType::MNT], Address::from_low_u64_be(1));
+        assert!(parse_chainlink_feeds("MNT").is_err());
+        assert!(parse_chainlink_feeds("DOGE=0x0000000000000000000000000000000000000001").is_err());
+
+        let defaults = PriceSources::default();
+        assert!(!defaults.supports(PriceSourceKind::Chainlink, TokenType::ETH));
+        assert!(!defaults.supports(PriceSourceKind::CryptoCompare, TokenType::MNT));
+        assert!(defaults.supports(PriceSourceKind::Binance, TokenType::MNT));
+    }
+}
@@ -0,0 +1,570 @@
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::Utc;
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tokio::time::{self, Duration};
+use tracing::{error, info, warn};
+
+use crate::{
+    amount::to_decimal,
+    http_client::OutboundClient,
+    price_source::{PriceSource, PriceSourceKind, PriceSources, SourceQuote, weighted_median},
+    token::TokenType,
+};
+
+/// How often prices are refreshed, how soon a failed refresh is retried and
+/// how long the last good price keeps standing in for a fresh one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PricePolicy {
+    pub refresh_interval: Duration,
+    /// First retry after a failed refresh, doubling up to `refresh_interval`.
+    pub retry_after: Duration,
+    /// A cached price older than this is still served, but marked stale.
+    pub stale_after: Duration,
+    /// A cached price older than this is not served at all.
+    pub max_age: Duration,
+    /// Sources with a fresh price needed for a refresh to count.
+    pub min_sources: usize,
+}
+
+impl Default for PricePolicy {
+    fn default() -> Self {
+        Self {
+            refresh_interval: Duration::from_secs(60),
+            retry_after: Duration::from_secs(5),
+            // Two missed refreshes
+            stale_after: Duration::from_secs(120),
+            max_age: Duration::from_secs(1800),
+            min_sources: 1,
+        }
+    }
+}
+
+impl PricePolicy {
+    /// Reads `PRICE_REFRESH_INTERVAL_SECS`, `PRICE_RETRY_AFTER_SECS`,
+    /// `PRICE_STALE_AFTER_SECS`, `PRICE_MAX_AGE_SECS` and
+    /// `PRICE_MIN_SOURCES`.
+    pub fn from_env() -> Result<Self> {
+        let secs = |name: &str, default: Duration| -> Result<Duration> {
+            match std::env::var(name) {
+                Ok(value) => Ok(Duration::from_secs(
+                    value
+                        .trim()
+                        .parse::<u64>()
+                        .with_context(|| format!("Invalid {}", name))?
+                        .max(1),
+                )),
+                Err(_) => Ok(default),
+            }
+        };
+        let defaults = Self::default();
+
+        let policy = Self {
+            refresh_interval: secs("PRICE_REFRESH_INTERVAL_SECS", defaults.refresh_interval)?,
+            retry_after: secs("PRICE_RETRY_AFTER_SECS", defaults.retry_after)?,
+            stale_after: secs("PRICE_STALE_AFTER_SECS", defaults.stale_after)?,
+            max_age: secs("PRICE_MAX_AGE_SECS", defaults.max_age)?,
+            min_sources: match std::env::var("PRICE_MIN_SOURCES") {
+                Ok(value) => value
+                    .trim()
+                    .parse::<usize>()
+                    .context("Invalid PRICE_MIN_SOURCES")?
+                    .max(1),
+                Err(_) => defaults.min_sources,
+            },
+        };
+        if policy.max_age < policy.stale_after {
+            bail!("PRICE_MAX_AGE_SECS must not be below PRICE_STALE_AFTER_SECS");
+        }
+
+        Ok(policy)
+    }
+
+    /// Wait before refreshing a pair again after `failures` failed refreshes
+    /// in a row.
+    pub fn refresh_delay(&self, failures: u32) -> Duration {
+        if failures == 0 {
+            return self.refresh_interval;
+        }
+        self.retry_after
+            .saturating_mul(1 << (failures - 1).min(16))
+            .min(self.refresh_interval)
+    }
+
+    /// `data` as of `now`, or `None` when it is unusable or past `max_age`.
+    pub fn quote(&self, data: &PriceData, now: i64) -> Option<PriceQuote> {
+        let age_secs = (now - data.timestamp).max(0);
+        if data.price <= 0.0 || age_secs as u64 > self.max_age.as_secs() {
+            return None;
+        }
+
+        Some(PriceQuote {
+            price: data.price,
+            age_secs,
+            stale: age_secs as u64 > self.stale_after.as_secs(),
+        })
+    }
+}
+
+/// A USD price and how far it can be trusted.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PriceQuote {
+    pub price: f64,
+    pub age_secs: i64,
+    /// Last known good price from before a run of failed refreshes.
+    pub stale: bool,
+}
+
+/// USD pricing for bridge tokens. Everything beyond `usd_price` derives from it.
+pub trait PriceFeed: Send + Sync {
+    fn usd_price(&self, token: TokenType) -> impl Future<Output = Result<f64>> + Send;
+
+    fn exchange_rate(
+        &self,
+        from: TokenType,
+        to: TokenType,
+    ) -> impl Future<Output = Result<f64>> + Send {
+        async move {
+            // Same token = 1:1, stablecoins are approximately 1:1
+            if from == to || (from.is_stablecoin() && to.is_stablecoin()) {
+                return Ok(1.0);
+            }
+
+            let from_usd = self.usd_price(from).await?;
+            let to_usd = self.usd_price(to).await?;
+
+            Ok(from_usd / to_usd)
+        }
+    }
+
+    fn convert_amount(
+        &self,
+        from: TokenType,
+        to: TokenType,
+        amount: f64,
+    ) -> impl Future<Output = Result<f64>> + Send {
+        async move { Ok(amount * self.exchange_rate(from, to).await?) }
+    }
+
+    /// USD value of a raw on-chain amount.
+    fn usd_value(
+        &self,
+        token: TokenType,
+        amount: U256,
+    ) -> impl Future<Output = Result<f64>> + Send {
+        async move { Ok(to_decimal(amount, token.decimals()) * self.usd_price(token).await?) }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct PriceData {
+    pub price: f64,
+    pub timestamp: i64,
+    pub sources: Vec<SourcePrice>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SourcePrice {
+    pub source: String,
+    pub price: f64,
+    #[serde(default)]
+    pub weight: f64,
+    /// When the source says the price is from.
+    #[serde(default)]
+    pub updated_at: i64,
+}
+
+impl Default for PriceData {
+    fn default() -> Self {
+        PriceData {
+            price: 0.0,
+            timestamp: Utc::now().timestamp(),
+            sources: Vec::new(),
+        }
+    }
+}
+
+/// What is known about one source's price of one token.
+#[derive(Debug, Clone, Default)]
+struct SourceState {
+    last_good: Option<SourceQuote>,
+    consecutive_failures: u32,
+    last_error: Option<String>,
+}
+
+/// One source's standing for one token, for the metrics endpoints.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SourceHealth {
+    pub token: String,
+    pub source: String,
+    pub weight: f64,
+    pub price: Option<f64>,
+    pub updated_at: Option<i64>,
+    /// Whether the last good price is recent enough to count.
+    pub fresh: bool,
+    pub consecutive_failures: u32,
+    pub last_error: Option<String>,
+}
+
+// --- PRICE FEED MANAGER ---
+
+/// Cheap to clone; clones share the cache and source states.
+#[derive(Clone, Default)]
+pub struct PriceFeedManager {
+    cache: Arc<RwLock<HashMap<String, PriceData>>>,
+    client: OutboundClient,
+    policy: PricePolicy,
+    sources: Arc<PriceSources>,
+    source_state: Arc<RwLock<HashMap<(TokenType, PriceSourceKind), SourceState>>>,
+}
+
+impl PriceFeedManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fetch through a shared client so provider rate limits hold process-wide.
+    pub fn with_client(client: OutboundClient) -> Self {
+        Self {
+            client,
+            ..Self::default()
+        }
+    }
+
+    pub fn with_policy(mut self, policy: PricePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_sources(mut self, sources: PriceSources) -> Self {
+        self.sources = Arc::new(sources);
+        self
+    }
+
+    /// Fetch initial prices for every non-stable bridge token and start the
+    /// background refresh.
+    pub async fn init(&self) {
+        let names: Vec<&str> = self
+            .sources
+            .sources
+            .iter()
+            .map(|source| source.kind.name())
+            .collect();
+        info!(
+            "🔄 Initializing price feeds from {} (at least {} fresh)",
+            names.join(", "),
+            self.policy.min_sources
+        );
+
+        for token in TokenType::ALL {
+            if !token.is_stablecoin()
+                && let Err(e) = self.refresh(token).await
+            {
+                error!(
+                    "Failed to fetch initial price for {}-USD: {}",
+                    token.symbol(),
+                    e
+                );
+            }
+        }
+
+        self.start_background_updates();
+    }
+
+    /// Refresh every pair each `refresh_interval`. A pair whose refresh
+    /// failed is retried sooner, with backoff, while the others keep their
+    /// schedule.
+    fn start_background_updates(&self) {
+        let manager = self.clone();
+        let policy = self.policy;
+
+        tokio::spawn(async move {
+            let first = time::Instant::now() + policy.refresh_interval;
+            let mut schedule: HashMap<TokenType, (time::Instant, u32)> = TokenType::ALL
+                .iter()
+                .filter(|t| !t.is_stablecoin())
+                .map(|token| (*token, (first, 0)))
+                .collect();
+
+            loop {
+                let next = schedule
+                    .values()
+                    .map(|(due, _)| *due)
+                    .min()
+                    .unwrap_or(first);
+                time::sleep_until(next).await;
+
+                let now = time::Instant::now();
+                for (token, (due, failures)) in schedule.iter_mut() {
+                    if *due > now {
+                        continue;
+                    }
+                    match manager.refresh(*token).await {
+                        Ok(()) => *failures = 0,
+                        Err(e) => {
+                            *failures += 1;
+                            warn!(
+                                "Failed to update {}-USD ({} in a row), retrying in {:?}: {}",
+                                token.symbol(),
+                                failures,
+                                policy.refresh_delay(*failures),
+                                e
+                            );
+                        }
+                    }
+                    *due = now + policy.refresh_delay(*failures);
+                }
+            }
+        });
+
+        info!(
+            "✅ Background price feed updates started ({}s interval)",
+            policy.refresh_interval.as_secs()
+        );
+    }
+
+    /// Ask every source for `token`, then cache the weighted median of the
+    /// sources whose last good price is still within their `max_age`. A
+    /// source that fails keeps counting with its last good price until then,
+    /// so one outage fails over to the rest without a gap. Fails, leaving the
+    /// cached price to age, when fewer than `min_sources` remain.
+    pub async fn refresh(&self, token: TokenType) -> Result<()> {
+        for source in &self.sources.sources {
+            if !self.sources.supports(source.kind, token) {
+                continue;
+            }
+            let result = self.sources.fetch(source.kind, &self.client, token).await;
+
+            let mut states = self.source_state.write().await;
+            let state = states.entry((token, source.kind)).or_default();
+            match result {
+                Ok(quote) => {
+                    state.last_good = Some(quote);
+                    state.consecutive_failures = 0;
+                    state.last_error = None;
+                }
+                Err(e) => {
+                    state.consecutive_failures += 1;
+                    state.last_error = Some(e.to_string());
+                    warn!("{} error for {}-USD: {}", source.kind, token.symbol(), e);
+                }
+            }
+        }
+
+        let now = Utc::now().timestamp();
+        let states = self.source_state.read().await;
+        let fresh: Vec<SourcePrice> = self
+            .sources
+            .sources
+            .iter()
+            .filter_map(|source| {
+                let quote = states.get(&(token, source.kind))?.last_good?;
+                is_fresh(&quote, source, now).then(|| SourcePrice {
+                    source: source.kind.name().to_string(),
+                    price: quote.price,
+                    weight: source.weight,
+                    updated_at: quote.updated_at,
+                })
+            })
+            .collect();
+        drop(states);
+
+        if fresh.len() < self.policy.min_sources {
+            return Err(anyhow!(
+                "Only {} fresh price sources, {} required",
+                fresh.len(),
+                self.policy.min_sources
+            ));
+        }
+        let samples: Vec<(f64, f64)> = fresh.iter().map(|s| (s.price, s.weight)).collect();
+        let price = weighted_median(&samples).ok_or_else(|| anyhow!("No usable prices"))?;
+
+        let pair_key = format!("{}-USD", token.symbol());
+        let source_names: Vec<&str> = fresh.iter().map(|s| s.source.as_str()).collect();
+        info!(
+            "💰 Price updated: {} = ${:.4} (median of {} sources: {})",
+            pair_key,
+            price,
+            fresh.len(),
+            source_names.join(", ")
+        );
+        self.cache.write().await.insert(
+            pair_key,
+            PriceData {
+                price,
+                timestamp: now,
+                sources: fresh,
+            },
+        );
+        Ok(())
+    }
+
+    pub async fn get_all_prices(&self) -> HashMap<String, PriceData> {
+        self.cache.read().await.clone()
+    }
+
+    /// Every configured source's standing per non-stable token, sorted by
+    /// token and then source.
+    pub async fn source_health(&self) -> Vec<SourceHealth> {
+        let now = Utc::now().timestamp();
+        let states = self.source_state.read().await;
+
+        let mut health = Vec::new();
+        for token in TokenType::ALL.into_iter().filter(|t| !t.is_stablecoin()) {
+            for source in &self.sources.sources {
+                if !self.sources.supports(source.kind, token) {
+                    continue;
+                }
+                let state = states
+                    .get(&(token, source.kind))
+                    .cloned()
+                    .unwrap_or_default();
+                health.push(SourceHealth {
+                    token: token.symbol().to_string(),
+                    source: source.kind.name().to_string(),
+                    weight: source.weight,
+                    price: state.last_good.map(|quote| quote.price),
+                    updated_at: state.last_good.map(|quote| quote.updated_at),
+                    fresh: state
+                        .last_good
+                        .is_some_and(|quote| is_fresh(&quote, source, now)),
+                    consecutive_failures: state.consecutive_failures,
+                    last_error: state.last_error,
+                });
+            }
+        }
+        health
+    }
+}
+
+/// Whether `quote` is recent enough for `source` to take part in the median.
+fn is_fresh(quote: &SourceQuote, source: &PriceSource, now: i64) -> bool {
+    (now - quote.updated_at).max(0) as u64 <= source.max_age.as_secs()
+}
+
+impl PriceFeedManager {
+    /// The USD price of `token` with its age. A price missing from the cache
+    /// or past `max_age` is fetched once on the spot before giving up, so a
+    /// single failed refresh never fails a caller.
+    pub async fn usd_quote(&self, token: TokenType) -> Result<PriceQuote> {
+        if token.is_stablecoin() {
+            return Ok(PriceQuote {
+                price: 1.0,
+                age_secs: 0,
+                stale: false,
+            });
+        }
+
+        let quote = match self.cached_quote(token).await {
+            Some(quote) => quote,
+            None => {
+                warn!("⚠️ No usable {} price cached, fetching now", token.symbol());
+                self.refresh(token)
+                    .await
+                    .with_context(|| format!("No valid price data for {}", token.symbol()))?;
+                self.cached_quote(token)
+                    .await
+                    .ok_or_else(|| anyhow!("No valid price data for {}", token.symbol()))?
+            }
+        };
+
+        if quote.stale {
+            warn!(
+                "⚠️ Price data for {}-USD is stale ({} seconds old)",
+                token.symbol(),
+                quote.age_secs
+            );
+        }
+        Ok(quote)
+    }
+
+    async fn cached_quote(&self, token: TokenType) -> Option<PriceQuote> {
+        let pair_key = format!("{}-USD", token.symbol());
+        let cache = self.cache.read().await;
+        self.policy
+            .quote(cache.get(&pair_key)?, Utc::now().timestamp())
+    }
+}
+
+impl PriceFeed for PriceFeedManager {
+    async fn usd_price(&self, token: TokenType) -> Result<f64> {
+        Ok(self.usd_quote(token).await?.price)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_stablecoin_conversion() {
+        let manager = PriceFeedManager::new();
+
+        let rate = manager
+            .exchange_rate(TokenType::USDC, TokenType::USDT)
+            .await
+            .unwrap();
+
+        assert_eq!(rate, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_same_token_conversion() {
+        let manager = PriceFeedManager::new();
+
+        let rate = manager
+            .exchange_rate(TokenType::ETH, TokenType::ETH)
+            .await
+            .unwrap();
+
+        assert_eq!(rate, 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_mnt_price_fetch() {
+        let manager = PriceFeedManager::new();
+        manager.refresh(TokenType::MNT).await.unwrap();
+
+        let price = manager.usd_price(TokenType::MNT).await;
+        assert!(price.is_ok());
+        assert!(price.unwrap() > 0.0);
+    }
+
+    #[test]
+    fn test_quote_staleness_bounds() {
+        let policy = PricePolicy::default();
+        let data = PriceData {
+            price: 2500.0,
+            timestamp: 1_000,
+            sources: Vec::new(),
+        };
+
+        let fresh = policy.quote(&data, 1_060).unwrap();
+        assert!(!fresh.stale);
+        assert_eq!(fresh.age_secs, 60);
+
+        // Last known good after a few failed refreshes
+        let stale = policy.quote(&data, 1_600).unwrap();
+        assert!(stale.stale);
+        assert_eq!(stale.price, 2500.0);
+
+        assert_eq!(policy.quote(&data, 1_000 + 1_801), None);
+        assert_eq!(policy.quote(&PriceData { price: 0.0, ..data }, 1_000), None);
+    }
+
+    #[test]
+    fn test_failed_refreshes_back_off_up_to_the_interval() {
+        let policy = PricePolicy::default();
+
+        assert_eq!(policy.refresh_delay(0), Duration::from_secs(60));
+        assert_eq!(policy.refresh_delay(1), Duration::from_secs(5));
+        assert_eq!(policy.refresh_delay(2), Duration::from_secs(10));
+        assert_eq!(policy.refresh_delay(4), Duration::from_secs(40));
+        assert_eq!(policy.refresh_delay(5), Duration::from_secs(60));
+        assert_eq!(policy.refresh_delay(40), Duration::from_secs(60));
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, anyhow};
+use ethers::{types::U256, utils::parse_units};
+
+use crate::{amount::to_decimal, chain::Chain, token::TokenType};
+
+/// Smallest intent worth routing, per token and destination chain.
+///
+/// Fills into Ethereum pay L1 gas, so dust intents on that route never cover
+/// their cost; routes into Mantle fall back to the token's global minimum.
+#[derive(Debug, Clone, Default)]
+pub struct RouteMinimums {
+    overrides: HashMap<(TokenType, Chain), U256>,
+}
+
+impl RouteMinimums {
+    /// Reads `ROUTE_MIN_AMOUNTS`, e.g. `USDC:ethereum=25,ETH:mantle=0.001`.
+    /// Amounts are in whole tokens; the chain is where the intent is filled.
+    pub fn from_env() -> Self {
+        let overrides = std::env::var("ROUTE_MIN_AMOUNTS")
+            .map(|raw| Self::parse_overrides(&raw))
+            .unwrap_or_default();
+
+        Self { overrides }
+    }
+
+    fn parse_overrides(raw: &str) -> HashMap<(TokenType, Chain), U256> {
+        parse_route_amounts(raw)
+    }
+
+    pub fn default_minimum(token: TokenType, dest: Chain) -> U256 {
+        match (token, dest) {
+            (TokenType::ETH | TokenType::WETH, Chain::Ethereum) => U256::from(5) * U256::exp10(15),
+            (TokenType::MNT, Chain::Ethereum) => U256::from(20) * U256::exp10(18),
+            (TokenType::USDC | TokenType::USDT, Chain::Ethereum) => U256::from(10) * U256::exp10(6),
+            (token, Chain::Mantle) => token.min_amount(),
+        }
+    }
+
+    pub fn minimum(&self, token: TokenType, dest: Chain) -> U256 {
+        self.overrides
+            .get(&(token, dest))
+            .copied()
+            .unwrap_or_else(|| Self::default_minimum(token, dest))
+    }
+
+    /// Errors with a user-facing message when `amount` is below the route minimum.
+    pub fn check(&self, token: TokenType, dest: Chain, amount: U256) -> Result<()> {
+        let minimum = self.minimum(token, dest);
+        if amount >= minimum {
+            return Ok(());
+        }
+
+        Err(anyhow!(
+            "Amount {} {} is below the {} {} minimum for intents filled on {}",
+            to_decimal(amount, token.decimals()),
+            token.symbol(),
+            to_decimal(minimum, token.decimals()),
+            token.symbol(),
+            dest.name()
+        ))
+    }
+}
+
+/// Parses `TOKEN:CHAIN=whole tokens` entries (comma separated) into base units,
+/// skipping malformed ones.
+pub fn parse_route_amounts(raw: &str) -> HashMap<(TokenType, Chain), U256> {
+    raw.split(',')
+        .filter_map(|entry| {
+            let (route, amount) = entry.split_once('=')?;
+            let (token, chain) = route.split_once(':')?;
+            let token = TokenType::from_symbol(token.trim()).ok()?;
+            let chain = Chain::parse(chain.trim()).ok()?;
+            let amount = parse_units(amount.trim(), token.decimals() as u32).ok()?;
+            Some(((token, chain), amount.into()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_and_route_check() {
+        let minimums = RouteMinimums {
+            overrides: RouteMinimums::parse_overrides(
+                "usdc:ethereum=25, ETH:5003=0.01,bad,MNT:mantle=x",
+            ),
+        };
+        assert_eq!(minimums.overrides.len(), 2);
+
+        let usdc = |whole: u64| U256::from(whole) * U256::exp10(6);
+        assert!(
+            minimums
+                .check(TokenType::USDC, Chain::Ethereum, usdc(24))
+                .is_err()
+        );
+        assert!(
+            minimums
+                .check(TokenType::USDC, Chain::Ethereum, usdc(25))
+                .is_ok()
+        );
+        assert!(
+            minimums
+                .check(TokenType::USDC, Chain::Mantle, usdc(1))
+                .is_ok()
+        );
+
+        assert_eq!(
+            minimums.minimum(TokenType::ETH, Chain::Mantle),
+            U256::exp10(16)
+        );
+        assert_eq!(
+            minimums.minimum(TokenType::ETH, Chain::Ethereum),
+            RouteMinimums::default_minimum(TokenType::ETH, Chain::Ethereum)
+        );
+    }
+}
@@ -0,0 +1,108 @@
+use std::str::FromStr;
+
+use anyhow::{Result, anyhow};
+use ethers::types::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::chain::Chain;
+
+/// Sentinel the pools and settlements use for the native asset.
+pub const NATIVE_TOKEN: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TokenType {
+    ETH,
+    USDC,
+    USDT,
+    WETH,
+    MNT,
+}
+
+impl TokenType {
+    pub const ALL: [TokenType; 5] = [
+        TokenType::ETH,
+        TokenType::USDC,
+        TokenType::USDT,
+        TokenType::WETH,
+        TokenType::MNT,
+    ];
+
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Self::ETH => "ETH",
+            Self::USDC => "USDC",
+            Self::USDT => "USDT",
+            Self::WETH => "WETH",
+            Self::MNT => "MNT",
+        }
+    }
+
+    pub fn from_symbol(symbol: &str) -> Result<Self> {
+        match symbol.to_uppercase().as_str() {
+            "ETH" => Ok(Self::ETH),
+            "USDC" => Ok(Self::USDC),
+            "USDT" => Ok(Self::USDT),
+            "WETH" => Ok(Self::WETH),
+            "MNT" => Ok(Self::MNT),
+            _ => Err(anyhow!("Unsupported token symbol: {}", symbol)),
+        }
+    }
+
+    pub fn decimals(&self) -> u8 {
+        match self {
+            Self::ETH | Self::WETH | Self::MNT => 18,
+            Self::USDC | Self::USDT => 6,
+        }
+    }
+
+    /// Sepolia token contract on `chain`, checksummed as deployed. This is
+    /// the built-in `TokenRegistry` listing; deployments elsewhere come
+    /// from the network profile.
+    pub fn address_on(&self, chain: Chain) -> &'static str {
+        match (self, chain) {
+            (Self::ETH, _) => NATIVE_TOKEN,
+            (Self::USDC, Chain::Ethereum) => "0x28650373758d75a8fF0B22587F111e47BAC34e21",
+            (Self::USDT, Chain::Ethereum) => "0x89F4f0e13997Ca27cEB963DEE291C607e4E59923",
+            (Self::WETH, Chain::Ethereum) => "0x50e8Da97BeEB8064714dE45ce1F250879f3bD5B5",
+            (Self::MNT, Chain::Ethereum) => "0x65e37B558F64E2Be5768DB46DF22F93d85741A9E",
+            (Self::USDC, Chain::Mantle) => "0xA4b184006B59861f80521649b14E4E8A72499A23",
+            (Self::USDT, Chain::Mantle) => "0xB0ee6EF7788E9122fc4AAE327Ed4FEf56c7da891",
+            (Self::WETH, Chain::Mantle) => "0xdeaddeaddeaddeaddeaddeaddeaddeaddead1111",
+            (Self::MNT, Chain::Mantle) => "0x44FCE297e4D6c5A50D28Fb26A58202e4D49a13E7",
+        }
+    }
+
+    pub fn is_native(&self) -> bool {
+        matches!(self, Self::ETH | Self::MNT)
+    }
+
+    pub fn is_stablecoin(&self) -> bool {
+        matches!(self, Self::USDC | Self::USDT)
+    }
+
+    /// Default per-intent limits for listings that set none.
+    pub fn min_amount(&self) -> U256 {
+        match self {
+            Self::ETH | Self::WETH | Self::MNT => U256::from(10).pow(U256::from(15)),
+            Self::USDC | Self::USDT => U256::from(10).pow(U256::from(6)),
+        }
+    }
+
+    pub fn max_amount(&self) -> U256 {
+        match self {
+            Self::ETH | Self::WETH | Self::MNT => {
+                U256::from(100) * U256::from(10).pow(U256::from(18))
+            }
+            Self::USDC | Self::USDT => U256::from(100000) * U256::from(10).pow(U256::from(6)),
+        }
+    }
+}
+
+impl FromStr for TokenType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::from_symbol(s)
+    }
+}
@@ -0,0 +1,420 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+    str::FromStr,
+    sync::RwLock,
+};
+
+use anyhow::{Context, Result, anyhow};
+use ethers::{
+    types::{Address, U256},
+    utils::parse_units,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    amount::to_decimal,
+    chain::Chain,
+    token::{NATIVE_TOKEN, TokenType},
+};
+
+/// Where one token is deployed and how much a single intent may move.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenListing {
+    pub token: TokenType,
+    pub ethereum: Address,
+    pub mantle: Address,
+    pub decimals: u8,
+    /// Base units.
+    #[serde(with = "base_units")]
+    pub min_amount: U256,
+    /// Base units.
+    #[serde(with = "base_units")]
+    pub max_amount: U256,
+    /// Disabled tokens still resolve, so intents already in flight finish,
+    /// but new intents and fills are refused.
+    pub enabled: bool,
+}
+
+impl TokenListing {
+    /// The Sepolia deployment compiled into `TokenType`.
+    pub fn builtin(token: TokenType) -> Self {
+        let address = |chain| {
+            Address::from_str(token.address_on(chain)).expect("built-in token address is valid")
+        };
+
+        Self {
+            token,
+            ethereum: address(Chain::Ethereum),
+            mantle: address(Chain::Mantle),
+            decimals: token.decimals(),
+            min_amount: token.min_amount(),
+            max_amount: token.max_amount(),
+            enabled: true,
+        }
+    }
+
+    pub fn address(&self, chain: Chain) -> Address {
+        match chain {
+            Chain::Ethereum => self.ethereum,
+            Chain::Mantle => self.mantle,
+        }
+    }
+
+    /// Amounts everywhere are scaled with `TokenType::decimals`, so a listing
+    /// that disagrees would misprice every intent in that token.
+    pub fn validate(&self) -> Result<()> {
+        let symbol = self.token.symbol();
+
+        if self.ethereum.is_zero() || self.mantle.is_zero() {
+            return Err(anyhow!(
+                "{}: addresses must be set on both chains (native ETH is {})",
+                symbol,
+                NATIVE_TOKEN
+            ));
+        }
+
+        if self.decimals != self.token.decimals() {
+            return Err(anyhow!(
+                "{}: decimals must be {}, got {}",
+                symbol,
+                self.token.decimals(),
+                self.decimals
+            ));
+        }
+
+        if self.min_amount.is_zero() || self.min_amount > self.max_amount {
+            return Err(anyhow!(
+                "{}: min_amount must be positive and at most max_amount",
+                symbol
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Errors with a user-facing message when `amount` is outside the limits.
+    pub fn check_amount(&self, amount: U256) -> Result<()> {
+        let (bound, limit) = if amount < self.min_amount {
+            ("below the minimum", self.min_amount)
+        } else if amount > self.max_amount {
+            ("above the maximum", self.max_amount)
+        } else {
+            return Ok(());
+        };
+
+        Err(anyhow!(
+            "Amount {} {} is {} of {} {}",
+            to_decimal(amount, self.decimals),
+            self.token.symbol(),
+            bound,
+            to_decimal(limit, self.decimals),
+            self.token.symbol()
+        ))
+    }
+}
+
+/// A token as configured: `[profiles.<name>.tokens.<SYMBOL>]` in
+/// `config.toml`, or the body of `POST /admin/tokens`. Amounts are whole
+/// tokens and default to the token's built-in limits.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenProfile {
+    pub ethereum: String,
+    pub mantle: String,
+    pub decimals: u8,
+    #[serde(default)]
+    pub min_amount: Option<String>,
+    #[serde(default)]
+    pub max_amount: Option<String>,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl TokenProfile {
+    pub fn listing(&self, token: TokenType) -> Result<TokenListing> {
+        let symbol = token.symbol();
+        let address = |chain: &str, value: &str| {
+            Address::from_str(value)
+                .map_err(|_| anyhow!("{}: invalid {} address {}", symbol, chain, value))
+        };
+        let whole = |field: &str, value: &Option<String>, default: U256| match value {
+            Some(value) => parse_units(value.trim(), token.decimals() as u32)
+                .map(U256::from)
+                .map_err(|e| anyhow!("{}: invalid {} {}: {}", symbol, field, value, e)),
+            None => Ok(default),
+        };
+
+        let listing = TokenListing {
+            token,
+            ethereum: address("ethereum", &self.ethereum)?,
+            mantle: address("mantle", &self.mantle)?,
+            decimals: self.decimals,
+            min_amount: whole("min_amount", &self.min_amount, token.min_amount())?,
+            max_amount: whole("max_amount", &self.max_amount, token.max_amount())?,
+            enabled: self.enabled,
+        };
+        listing.validate()?;
+
+        Ok(listing)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfilesFile {
+    #[serde(default)]
+    profiles: HashMap<String, ProfileTokens>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfileTokens {
+    #[serde(default)]
+    tokens: BTreeMap<String, TokenProfile>,
+}
+
+/// The tokens the relayer and solver accept, by symbol. Seeded from the
+/// network profile (or the built-in Sepolia listings without one) and
+/// changed at runtime through the admin API.
+#[derive(Debug, Default)]
+pub struct TokenRegistry {
+    listings: RwLock<HashMap<TokenType, TokenListing>>,
+}
+
+impl TokenRegistry {
+    pub fn new(listings: impl IntoIterator<Item = TokenListing>) -> Self {
+        Self {
+            listings: RwLock::new(
+                listings
+                    .into_iter()
+                    .map(|listing| (listing.token, listing))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Every token at its built-in Sepolia deployment.
+    pub fn builtin() -> Self {
+        Self::new(TokenType::ALL.map(TokenListing::builtin))
+    }
+
+    /// Exactly the tokens a profile lists, so a mainnet profile never falls
+    /// back to testnet addresses.
+    pub fn from_profiles(tokens: &BTreeMap<String, TokenProfile>) -> Result<Self> {
+        if tokens.is_empty() {
+            return Err(anyhow!(
+                "Profile lists no tokens; add [profiles.<name>.tokens.<SYMBOL>]"
+            ));
+        }
+
+        let listings = tokens
+            .iter()
+            .map(|(symbol, profile)| profile.listing(TokenType::from_symbol(symbol)?))
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(Self::new(listings))
+    }
+
+    /// Tokens of the profile `BRIDGE_PROFILE` selects in `BRIDGE_CONFIG_FILE`
+    /// (default `config.toml`), the built-in listings without a profile.
+    pub fn from_env() -> Result<Self> {
+        let Ok(name) = std::env::var("BRIDGE_PROFILE") else {
+            return Ok(Self::builtin());
+        };
+
+        let path =
+            std::env::var("BRIDGE_CONFIG_FILE").unwrap_or_else(|_| "config.toml".to_string());
+        let contents = std::fs::read_to_string(Path::new(&path))
+            .with_context(|| format!("Failed to read config file {}", path))?;
+
+        Self::from_toml_str(&contents, &name)
+    }
+
+    pub fn from_toml_str(contents: &str, profile: &str) -> Result<Self> {
+        let file: ProfilesFile =
+            toml::from_str(contents).map_err(|e| anyhow!("Failed to parse profiles: {}", e))?;
+        let profile = file
+            .profiles
+            .get(profile)
+            .ok_or_else(|| anyhow!("Unknown profile '{}'", profile))?;
+
+        Self::from_profiles(&profile.tokens)
+    }
+
+    pub fn listing(&self, token: TokenType) -> Option<TokenListing> {
+        self.read().get(&token).cloned()
+    }
+
+    /// Listed tokens in `TokenType::ALL` order.
+    pub fn listings(&self) -> Vec<TokenListing> {
+        let listings = self.read();
+        TokenType::ALL
+            .iter()
+            .filter_map(|token| listings.get(token).cloned())
+            .collect()
+    }
+
+    pub fn address(&self, token: TokenType, chain: Chain) -> Option<Address> {
+        self.read()
+            .get(&token)
+            .map(|listing| listing.address(chain))
+    }
+
+    /// The token at `address` on `chain`. The zero address is accepted as
+    /// native ETH alongside the `0xEeee…` sentinel.
+    pub fn resolve(&self, address: Address, chain: Chain) -> Option<TokenType> {
+        let listings = self.read();
+        if address.is_zero() {
+            return listings
+                .contains_key(&TokenType::ETH)
+                .then_some(TokenType::ETH);
+        }
+
+        listings
+            .values()
+            .find(|listing| listing.address(chain) == address)
+            .map(|listing| listing.token)
+    }
+
+    /// Resolve a `0x` address from either chain, as stored on intent rows.
+    pub fn resolve_str(&self, address: &str) -> Result<TokenType> {
+        let parsed = Address::from_str(address)
+            .map_err(|_| anyhow!("Unsupported token address: {}", address))?;
+
+        Chain::ALL
+            .into_iter()
+            .find_map(|chain| self.resolve(parsed, chain))
+            .ok_or_else(|| anyhow!("Unsupported token address: {}", address.to_lowercase()))
+    }
+
+    /// The listing of `token` if new intents may use it.
+    pub fn enabled(&self, token: TokenType) -> Result<TokenListing> {
+        match self.listing(token) {
+            Some(listing) if listing.enabled => Ok(listing),
+            Some(_) => Err(anyhow!("Token {} is disabled", token.symbol())),
+            None => Err(anyhow!("Token {} is not listed", token.symbol())),
+        }
+    }
+
+    /// Add `listing`, or replace the token's current one.
+    pub fn upsert(&self, listing: TokenListing) -> Result<()> {
+        listing.validate()?;
+        self.write().insert(listing.token, listing);
+        Ok(())
+    }
+
+    pub fn set_enabled(&self, token: TokenType, enabled: bool) -> Result<TokenListing> {
+        let mut listings = self.write();
+        let listing = listings
+            .get_mut(&token)
+            .ok_or_else(|| anyhow!("Token {} is not listed", token.symbol()))?;
+        listing.enabled = enabled;
+
+        Ok(listing.clone())
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<TokenType, TokenListing>> {
+        self.listings.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<TokenType, TokenListing>> {
+        self.listings.write().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Base-unit amounts as decimal strings, which JSON clients can hold
+/// without losing precision.
+mod base_units {
+    use ethers::types::U256;
+    use serde::{Deserialize, Deserializer, Serializer, de::Error};
+
+    pub fn serialize<S: Serializer>(amount: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&amount.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let value = String::deserialize(deserializer)?;
+        U256::from_dec_str(&value).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAINNET: &str = r#"
+[profiles.mainnet.ethereum]
+chain_id = 1
+
+[profiles.mainnet.tokens.USDC]
+ethereum = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
+mantle = "0x09Bc4E0D864854c6aFB6eB9A9cdF58aC190D0dF9"
+decimals = 6
+min_amount = "5"
+max_amount = "250000"
+"#;
+
+    #[test]
+    fn test_builtin_addresses_resolve_on_both_chains() {
+        let registry = TokenRegistry::builtin();
+        let native = Address::from_str(NATIVE_TOKEN).unwrap();
+
+        for chain in Chain::ALL {
+            assert_eq!(registry.resolve(native, chain), Some(TokenType::ETH));
+            assert_eq!(
+                registry.resolve(Address::zero(), chain),
+                Some(TokenType::ETH)
+            );
+        }
+
+        for token in TokenType::ALL {
+            for chain in Chain::ALL {
+                assert_eq!(
+                    registry.resolve_str(token.address_on(chain)).unwrap(),
+                    token
+                );
+            }
+        }
+
+        let usdc_mantle = registry.address(TokenType::USDC, Chain::Mantle).unwrap();
+        assert_eq!(registry.resolve(usdc_mantle, Chain::Ethereum), None);
+    }
+
+    #[test]
+    fn test_profile_lists_only_its_tokens() {
+        let registry = TokenRegistry::from_toml_str(MAINNET, "mainnet").unwrap();
+
+        let usdc = registry.enabled(TokenType::USDC).unwrap();
+        assert_eq!(usdc.min_amount, U256::from(5_000_000));
+        assert_eq!(usdc.max_amount, U256::from(250_000) * U256::exp10(6));
+        assert!(registry.listing(TokenType::WETH).is_none());
+        assert!(
+            registry
+                .resolve_str(TokenType::USDC.address_on(Chain::Ethereum))
+                .is_err()
+        );
+
+        let bad_decimals = MAINNET.replace("decimals = 6", "decimals = 18");
+        assert!(TokenRegistry::from_toml_str(&bad_decimals, "mainnet").is_err());
+    }
+
+    #[test]
+    fn test_disabled_tokens_resolve_but_refuse_new_intents() {
+        let registry = TokenRegistry::builtin();
+        registry.set_enabled(TokenType::USDT, false).unwrap();
+
+        let address = registry.address(TokenType::USDT, Chain::Ethereum).unwrap();
+        assert_eq!(
+            registry.resolve(address, Chain::Ethereum),
+            Some(TokenType::USDT)
+        );
+        assert!(registry.enabled(TokenType::USDT).is_err());
+
+        let usdc = registry.enabled(TokenType::USDC).unwrap();
+        assert!(usdc.check_amount(U256::from(1_000_000)).is_ok());
+        assert!(usdc.check_amount(U256::from(999_999)).is_err());
+        assert!(usdc.check_amount(usdc.max_amount + 1).is_err());
+    }
+}
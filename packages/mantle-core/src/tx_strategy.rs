@@ -0,0 +1,289 @@
+use std::{borrow::Borrow, sync::Arc};
+
+use anyhow::{Context, Result, anyhow};
+use ethers::{
+    abi::Detokenize,
+    contract::{FunctionCall, abigen},
+    providers::Middleware,
+    types::{
+        Address, Eip1559TransactionRequest, TransactionRequest, U256,
+        transaction::eip2718::TypedTransaction,
+    },
+};
+use tracing::debug;
+
+use crate::fee_estimator::{FeeCircuitBreaker, FeeEstimator};
+
+abigen!(
+    GasPriceOracle,
+    r#"[
+        function getL1Fee(bytes memory _data) external view returns (uint256)
+    ]"#
+);
+
+/// Gas price oracle predeploy on Mantle (and other OP-stack rollups).
+pub const GAS_PRICE_ORACLE: &str = "0x420000000000000000000000000000000000000F";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxType {
+    Legacy,
+    Eip1559,
+}
+
+impl std::str::FromStr for TxType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "legacy" | "0" => Ok(Self::Legacy),
+            "eip1559" | "2" => Ok(Self::Eip1559),
+            other => Err(anyhow!("Unknown tx type: {}", other)),
+        }
+    }
+}
+
+/// How transactions are typed, gas-limited and priced on one chain.
+#[derive(Debug, Clone)]
+pub struct ChainTxStrategy {
+    pub label: &'static str,
+    pub tx_type: TxType,
+    /// Applied to `eth_estimateGas`, e.g. 120 = +20%.
+    pub gas_limit_multiplier_pct: u64,
+    /// Applied to the legacy gas price or the EIP-1559 max fee.
+    pub fee_multiplier_pct: u64,
+    /// Upper bound on the EIP-1559 priority fee.
+    pub max_priority_fee: Option<U256>,
+    /// Refuse to send when the fee per gas exceeds this.
+    pub max_fee_per_gas: Option<U256>,
+    /// Rollup gas price oracle used to estimate the L1 data fee.
+    pub l1_fee_oracle: Option<Address>,
+    /// Source of EIP-1559 fees.
+    pub fees: FeeEstimator,
+    /// Enforces `max_fee_per_gas` on every send priced by this strategy.
+    pub breaker: FeeCircuitBreaker,
+}
+
+impl ChainTxStrategy {
+    /// Type-2 transactions priced from `eth_feeHistory`.
+    pub fn ethereum() -> Self {
+        Self {
+            label: "Ethereum",
+            tx_type: TxType::Eip1559,
+            gas_limit_multiplier_pct: 120,
+            fee_multiplier_pct: 100,
+            max_priority_fee: None,
+            max_fee_per_gas: None,
+            l1_fee_oracle: None,
+            fees: FeeEstimator::ethereum(),
+            breaker: FeeCircuitBreaker::default(),
+        }
+    }
+
+    /// Legacy transactions priced at `eth_gasPrice`, and type-2 ones split
+    /// from it: Mantle's cost is dominated by the L1 data fee, and fee history
+    /// tips overpay by orders of magnitude. Mantle gas estimates are already
+    /// very large, so the limit gets a smaller margin than on Ethereum.
+    pub fn mantle() -> Self {
+        Self {
+            label: "Mantle",
+            tx_type: TxType::Legacy,
+            gas_limit_multiplier_pct: 110,
+            fee_multiplier_pct: 100,
+            max_priority_fee: None,
+            max_fee_per_gas: None,
+            l1_fee_oracle: GAS_PRICE_ORACLE.parse().ok(),
+            fees: FeeEstimator::mantle(),
+            breaker: FeeCircuitBreaker::default(),
+        }
+    }
+
+    /// Override the defaults from `<PREFIX>_TX_TYPE`, `<PREFIX>_GAS_LIMIT_MULTIPLIER_PCT`,
+    /// `<PREFIX>_FEE_MULTIPLIER_PCT`, `<PREFIX>_MAX_PRIORITY_FEE_GWEI`,
+    /// `<PREFIX>_MAX_FEE_GWEI`, `<PREFIX>_FEE_BREAKER_RESUME_PCT` and
+    /// `<PREFIX>_L1_FEE_ORACLE` (`none` disables), plus the fee estimator's
+    /// variables (see [`FeeEstimator::from_env`]).
+    pub fn from_env(prefix: &str, defaults: Self) -> Result<Self> {
+        let var = |name: &str| std::env::var(format!("{}_{}", prefix, name)).ok();
+        let mut strategy = defaults;
+        strategy.fees = FeeEstimator::from_env(prefix, strategy.fees)?;
+
+        if let Some(tx_type) = var("TX_TYPE") {
+            strategy.tx_type = tx_type.parse()?;
+        }
+        if let Some(pct) = var("GAS_LIMIT_MULTIPLIER_PCT") {
+            strategy.gas_limit_multiplier_pct = pct
+                .parse()
+                .with_context(|| format!("Invalid {}_GAS_LIMIT_MULTIPLIER_PCT", prefix))?;
+        }
+        if let Some(pct) = var("FEE_MULTIPLIER_PCT") {
+            strategy.fee_multiplier_pct = pct
+                .parse()
+                .with_context(|| format!("Invalid {}_FEE_MULTIPLIER_PCT", prefix))?;
+        }
+        if let Some(gwei) = var("MAX_PRIORITY_FEE_GWEI") {
+            strategy.max_priority_fee = Some(parse_gwei(&gwei)?);
+        }
+        if let Some(gwei) = var("MAX_FEE_GWEI") {
+            strategy.max_fee_per_gas = Some(parse_gwei(&gwei)?);
+        }
+        if let Some(pct) = var("FEE_BREAKER_RESUME_PCT") {
+            let pct: u64 = pct
+                .parse()
+                .with_context(|| format!("Invalid {}_FEE_BREAKER_RESUME_PCT", prefix))?;
+            if !(1..=100).contains(&pct) {
+                return Err(anyhow!(
+                    "{}_FEE_BREAKER_RESUME_PCT must be between 1 and 100",
+                    prefix
+                ));
+            }
+            strategy.breaker = FeeCircuitBreaker::new(pct);
+        }
+        if let Some(oracle) = var("L1_FEE_ORACLE") {
+            strategy.l1_fee_oracle = match oracle.to_lowercase().as_str() {
+                "" | "none" => None,
+                address => Some(
+                    address
+                        .parse()
+                        .with_context(|| format!("Invalid {}_L1_FEE_ORACLE", prefix))?,
+                ),
+            };
+        }
+
+        Ok(strategy)
+    }
+
+    pub fn scale_gas_limit(&self, estimate: U256) -> U256 {
+        estimate.saturating_mul(U256::from(self.gas_limit_multiplier_pct)) / U256::from(100)
+    }
+
+    /// Type, gas-limit and price a contract call before `send()`.
+    pub async fn prepare<B, M, D>(
+        &self,
+        client: &M,
+        mut call: FunctionCall<B, M, D>,
+    ) -> Result<FunctionCall<B, M, D>>
+    where
+        B: Borrow<M>,
+        M: Middleware,
+        D: Detokenize,
+    {
+        self.apply(client, &mut call.tx).await?;
+        Ok(call)
+    }
+
+    pub async fn apply<M: Middleware>(&self, client: &M, tx: &mut TypedTransaction) -> Result<()> {
+        *tx = match self.tx_type {
+            TxType::Legacy => Into::<TransactionRequest>::into(tx.clone()).into(),
+            TxType::Eip1559 => Into::<Eip1559TransactionRequest>::into(tx.clone()).into(),
+        };
+
+        if tx.gas().is_none() {
+            let estimate = client
+                .estimate_gas(tx, None)
+                .await
+                .map_err(|e| anyhow!("Gas estimation failed: {}", e))?;
+            tx.set_gas(self.scale_gas_limit(estimate));
+        }
+
+        match tx {
+            TypedTransaction::Eip1559(inner) => {
+                let estimate = self.fees.estimate(client).await.with_context(|| {
+                    format!("[{}] Failed to estimate EIP-1559 fees", self.label)
+                })?;
+
+                let priority_fee = match self.max_priority_fee {
+                    Some(cap) => estimate.max_priority_fee_per_gas.min(cap),
+                    None => estimate.max_priority_fee_per_gas,
+                };
+                let max_fee = self.scale_fee(estimate.max_fee_per_gas).max(priority_fee);
+                self.check_fee_cap(max_fee)?;
+
+                inner.max_fee_per_gas = Some(max_fee);
+                inner.max_priority_fee_per_gas = Some(priority_fee);
+            }
+            _ => {
+                let gas_price = self.scale_fee(
+                    client
+                        .get_gas_price()
+                        .await
+                        .map_err(|e| anyhow!("Failed to get gas price: {}", e))?,
+                );
+                self.check_fee_cap(gas_price)?;
+                tx.set_gas_price(gas_price);
+            }
+        }
+
+        debug!(
+            "   ⛽ [{}] {:?} tx | gas limit {:?} | fee/gas {:?}",
+            self.label,
+            self.tx_type,
+            tx.gas(),
+            tx.gas_price()
+        );
+
+        Ok(())
+    }
+
+    /// Current fee per gas as this strategy would price it.
+    pub async fn fee_per_gas<M: Middleware>(&self, client: &M) -> Result<U256> {
+        let fee = match self.tx_type {
+            TxType::Legacy => client
+                .get_gas_price()
+                .await
+                .map_err(|e| anyhow!("Failed to get gas price: {}", e))?,
+            TxType::Eip1559 => {
+                self.fees
+                    .estimate(client)
+                    .await
+                    .with_context(|| format!("[{}] Failed to estimate EIP-1559 fees", self.label))?
+                    .max_fee_per_gas
+            }
+        };
+
+        Ok(self.scale_fee(fee))
+    }
+
+    /// Rollup L1 data fee for `tx`, zero on chains without an oracle.
+    pub async fn l1_data_fee<M: Middleware + 'static>(
+        &self,
+        client: Arc<M>,
+        tx: &TypedTransaction,
+    ) -> Result<U256> {
+        let Some(oracle) = self.l1_fee_oracle else {
+            return Ok(U256::zero());
+        };
+
+        GasPriceOracle::new(oracle, client)
+            .get_l1_fee(tx.rlp())
+            .call()
+            .await
+            .map_err(|e| anyhow!("[{}] L1 fee oracle call failed: {}", self.label, e))
+    }
+
+    /// Execution cost for `gas` units plus the L1 data fee for `tx`.
+    pub async fn estimate_cost<M: Middleware + 'static>(
+        &self,
+        client: Arc<M>,
+        tx: &TypedTransaction,
+        gas: U256,
+    ) -> Result<U256> {
+        let execution = self.fee_per_gas(client.as_ref()).await?.saturating_mul(gas);
+        let l1_fee = self.l1_data_fee(client, tx).await?;
+
+        Ok(execution.saturating_add(l1_fee))
+    }
+
+    fn scale_fee(&self, fee: U256) -> U256 {
+        fee.saturating_mul(U256::from(self.fee_multiplier_pct)) / U256::from(100)
+    }
+
+    fn check_fee_cap(&self, fee: U256) -> Result<()> {
+        self.breaker.check(self.label, fee, self.max_fee_per_gas)
+    }
+}
+
+fn parse_gwei(value: &str) -> Result<U256> {
+    ethers::utils::parse_units(value, "gwei")
+        .map(Into::into)
+        .map_err(|e| anyhow!("Invalid gwei amount {}: {}", value, e))
+}
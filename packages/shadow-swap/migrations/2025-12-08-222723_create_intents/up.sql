@@ -17,7 +17,7 @@ CREATE TABLE intents (
     updated_at TIMESTAMPTZ NOT NULL DEFAULT now(),
     deadline BIGINT NOT NULL,
     refund_address VARCHAR(42),
-    solver_address VARCHAR(42),
+    solver_address VARCHAR(42)
 );
 
 CREATE INDEX idx_intents_status ON intents(status);
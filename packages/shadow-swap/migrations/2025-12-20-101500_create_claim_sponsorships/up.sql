@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS claim_sponsorships (
+    intent_id TEXT PRIMARY KEY,
+    sponsored BOOLEAN NOT NULL DEFAULT TRUE,
+    chain_id INTEGER,
+    claim_tx_hash TEXT,
+    gas_used BIGINT,
+    gas_cost_wei TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_claim_sponsorships_chain_updated
+ON claim_sponsorships (chain_id, updated_at DESC);
@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_intents_source_commitment;
+DROP TABLE IF EXISTS quarantined_commitments;
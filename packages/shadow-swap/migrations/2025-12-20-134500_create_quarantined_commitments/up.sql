@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS quarantined_commitments (
+    id SERIAL PRIMARY KEY,
+    intent_id TEXT NOT NULL UNIQUE,
+    commitment TEXT NOT NULL,
+    chain TEXT NOT NULL,
+    conflicting_intent_id TEXT NOT NULL,
+    conflicting_chain TEXT NOT NULL,
+    scope TEXT NOT NULL,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_quarantined_commitments_commitment
+ON quarantined_commitments (commitment);
+
+CREATE INDEX IF NOT EXISTS idx_intents_source_commitment
+ON intents (source_commitment);
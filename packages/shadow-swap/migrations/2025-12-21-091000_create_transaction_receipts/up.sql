@@ -0,0 +1,17 @@
+-- Your SQL goes here
+CREATE TABLE transaction_receipts (
+    tx_hash TEXT PRIMARY KEY,
+    intent_id TEXT,
+    chain_id INTEGER NOT NULL,
+    tx_type TEXT NOT NULL,
+    status TEXT NOT NULL,
+    block_number BIGINT,
+    gas_used BIGINT,
+    effective_gas_price TEXT,
+    revert_reason TEXT,
+    receipt JSONB NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_tx_receipts_intent ON transaction_receipts(intent_id);
+CREATE INDEX idx_tx_receipts_created_at ON transaction_receipts(created_at);
@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE bridge_events DROP CONSTRAINT IF EXISTS bridge_events_event_type_check;
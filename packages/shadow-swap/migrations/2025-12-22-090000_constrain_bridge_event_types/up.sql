@@ -0,0 +1,24 @@
+-- Your SQL goes here
+UPDATE bridge_events
+SET event_type = 'root_sync'
+WHERE event_type IN ('root_synced', 'commitment_root_synced', 'fill_root_synced');
+
+ALTER TABLE bridge_events
+    ADD CONSTRAINT bridge_events_event_type_check CHECK (event_type IN (
+        'intent_created',
+        'intent_registered',
+        'intent_filled',
+        'intent_settled',
+        'intent_refunded',
+        'withdrawal_claimed',
+        'root_sync',
+        'nullifier_used'
+    )) NOT VALID;
+
+-- Rows written with a typo stay readable; the constraint still guards every new insert.
+DO $$
+BEGIN
+    ALTER TABLE bridge_events VALIDATE CONSTRAINT bridge_events_event_type_check;
+EXCEPTION WHEN check_violation THEN
+    RAISE WARNING 'bridge_events contains rows with an unknown event_type, constraint applies to new rows only';
+END $$;
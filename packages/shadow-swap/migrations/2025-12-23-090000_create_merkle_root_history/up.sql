@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS merkle_root_history (
+    id SERIAL PRIMARY KEY,
+    tree_name TEXT NOT NULL,
+    root TEXT NOT NULL,
+    leaf_count BIGINT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tree_name, root)
+);
+
+CREATE INDEX IF NOT EXISTS idx_merkle_root_history_tree
+ON merkle_root_history (tree_name, id DESC);
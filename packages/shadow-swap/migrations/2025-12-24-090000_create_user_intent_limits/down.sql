@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_intents_user_status;
+DROP TABLE IF EXISTS user_intent_limits;
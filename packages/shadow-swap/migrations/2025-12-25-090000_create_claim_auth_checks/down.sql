@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_claim_auth_checks_invalid;
+DROP TABLE IF EXISTS claim_auth_checks;
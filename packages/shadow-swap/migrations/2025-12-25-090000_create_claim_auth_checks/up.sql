@@ -0,0 +1,12 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS claim_auth_checks (
+    intent_id TEXT PRIMARY KEY,
+    valid BOOLEAN NOT NULL,
+    signer TEXT,
+    error TEXT,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_claim_auth_checks_invalid
+ON claim_auth_checks (checked_at)
+WHERE NOT valid;
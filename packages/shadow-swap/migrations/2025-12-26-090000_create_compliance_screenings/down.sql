@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_compliance_screenings_action;
+DROP TABLE IF EXISTS compliance_screenings;
@@ -0,0 +1,18 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS compliance_screenings (
+    intent_id TEXT PRIMARY KEY,
+    address TEXT NOT NULL,
+    chain TEXT NOT NULL,
+    risk_score DOUBLE PRECISION,
+    labels TEXT,
+    action TEXT NOT NULL CHECK (action IN ('allow', 'flag', 'block')),
+    reason TEXT,
+    provider_error TEXT,
+    override_note TEXT,
+    screened_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_compliance_screenings_action
+ON compliance_screenings (action, screened_at)
+WHERE action <> 'allow';
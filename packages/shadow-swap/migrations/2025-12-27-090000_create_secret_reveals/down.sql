@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_secret_reveals_unrevealed;
+DROP TABLE IF EXISTS secret_reveals;
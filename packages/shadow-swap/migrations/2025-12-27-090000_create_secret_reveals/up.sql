@@ -0,0 +1,14 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS secret_reveals (
+    intent_id TEXT PRIMARY KEY,
+    reveal_deadline BIGINT,
+    secret TEXT,
+    signature TEXT,
+    revealed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_secret_reveals_unrevealed
+ON secret_reveals (reveal_deadline)
+WHERE secret IS NULL;
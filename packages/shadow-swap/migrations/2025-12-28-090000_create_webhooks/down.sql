@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE IF EXISTS webhook_secrets;
+DROP TABLE IF EXISTS webhook_endpoints;
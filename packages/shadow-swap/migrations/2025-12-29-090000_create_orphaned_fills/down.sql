@@ -0,0 +1,3 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS idx_orphaned_fills_status;
+DROP TABLE IF EXISTS orphaned_fills;
@@ -0,0 +1,20 @@
+-- Your SQL goes here
+-- Fills whose source intent was refunded instead of settled. The solver's
+-- funds stay in the destination settlement until recovered.
+CREATE TABLE IF NOT EXISTS orphaned_fills (
+    intent_id TEXT PRIMARY KEY,
+    source_chain TEXT NOT NULL,
+    dest_chain TEXT NOT NULL,
+    solver_address TEXT NOT NULL,
+    token TEXT NOT NULL,
+    amount TEXT NOT NULL,
+    status TEXT NOT NULL,
+    recovery_txid TEXT,
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    approved_at TIMESTAMPTZ,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_orphaned_fills_status ON orphaned_fills (status);
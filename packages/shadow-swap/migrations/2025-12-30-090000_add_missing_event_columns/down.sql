@@ -0,0 +1,7 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE bridge_events DROP COLUMN IF EXISTS log_index;
+
+ALTER TABLE intents
+    DROP COLUMN IF EXISTS log_index,
+    DROP COLUMN IF EXISTS block_number,
+    DROP COLUMN IF EXISTS source_settlement_txid;
@@ -0,0 +1,10 @@
+-- Columns schema.rs has carried since the indexer started recording event
+-- positions, but that no migration created. Deployed databases got them by
+-- hand, hence IF NOT EXISTS.
+ALTER TABLE intents
+    ADD COLUMN IF NOT EXISTS source_settlement_txid TEXT,
+    ADD COLUMN IF NOT EXISTS block_number BIGINT,
+    ADD COLUMN IF NOT EXISTS log_index INTEGER;
+
+ALTER TABLE bridge_events
+    ADD COLUMN IF NOT EXISTS log_index INTEGER;
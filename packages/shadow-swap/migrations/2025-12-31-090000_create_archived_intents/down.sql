@@ -0,0 +1,34 @@
+INSERT INTO intents (
+    id, user_address, source_chain, dest_chain, source_token, dest_token,
+    amount, dest_amount, source_commitment, dest_fill_txid,
+    dest_registration_txid, source_complete_txid, status, created_at,
+    updated_at, deadline, refund_address, solver_address,
+    source_settlement_txid, block_number, log_index
+)
+SELECT
+    id, user_address, source_chain, dest_chain, source_token, dest_token,
+    amount, dest_amount, source_commitment, dest_fill_txid,
+    dest_registration_txid, source_complete_txid, status, created_at,
+    updated_at, deadline, refund_address, solver_address,
+    source_settlement_txid, block_number, log_index
+FROM archived_intents
+ON CONFLICT (id) DO NOTHING;
+
+DROP TABLE archived_intents;
+DROP INDEX IF EXISTS idx_intents_status_updated_at;
+
+-- Rows whose intent was cleared while the constraints were off would block them
+DELETE FROM intent_privacy_params WHERE intent_id NOT IN (SELECT id FROM intents);
+DELETE FROM chain_transactions WHERE intent_id NOT IN (SELECT id FROM intents);
+UPDATE bridge_events SET intent_id = NULL
+    WHERE intent_id IS NOT NULL AND intent_id NOT IN (SELECT id FROM intents);
+
+ALTER TABLE intent_privacy_params
+    ADD CONSTRAINT intent_privacy_params_intent_id_fkey
+    FOREIGN KEY (intent_id) REFERENCES intents(id) ON DELETE CASCADE;
+ALTER TABLE chain_transactions
+    ADD CONSTRAINT chain_transactions_intent_id_fkey
+    FOREIGN KEY (intent_id) REFERENCES intents(id) ON DELETE CASCADE;
+ALTER TABLE bridge_events
+    ADD CONSTRAINT bridge_events_intent_id_fkey
+    FOREIGN KEY (intent_id) REFERENCES intents(id) ON DELETE CASCADE;
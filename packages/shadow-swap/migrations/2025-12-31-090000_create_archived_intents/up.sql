@@ -0,0 +1,40 @@
+-- Terminal intents older than INTENT_ARCHIVE_AFTER_DAYS move here so the hot
+-- table only holds what the workers still act on. Same columns as intents.
+CREATE TABLE archived_intents (
+    id TEXT PRIMARY KEY,
+    user_address VARCHAR(42) NOT NULL,
+    source_chain VARCHAR(50) NOT NULL,
+    dest_chain VARCHAR(50) NOT NULL,
+    source_token TEXT NOT NULL,
+    dest_token VARCHAR(42) NOT NULL,
+    amount TEXT NOT NULL,
+    dest_amount VARCHAR(78) NOT NULL,
+    source_commitment TEXT,
+    dest_fill_txid TEXT,
+    dest_registration_txid VARCHAR(66),
+    source_complete_txid VARCHAR(66),
+    status TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL,
+    deadline BIGINT NOT NULL,
+    refund_address VARCHAR(42),
+    solver_address VARCHAR(42),
+    source_settlement_txid TEXT,
+    block_number BIGINT,
+    log_index INTEGER,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT now()
+);
+
+-- Commitment trees are rebuilt from both tables in event order
+CREATE INDEX idx_archived_intents_tree ON archived_intents(source_chain, block_number, log_index);
+CREATE INDEX idx_archived_intents_commitment ON archived_intents(source_commitment);
+CREATE INDEX idx_archived_intents_created_at ON archived_intents(created_at);
+
+-- The archiver looks for old terminal rows
+CREATE INDEX IF NOT EXISTS idx_intents_status_updated_at ON intents(status, updated_at);
+
+-- Privacy params, transactions and events outlive the intent row they
+-- describe, so they can no longer cascade from it.
+ALTER TABLE intent_privacy_params DROP CONSTRAINT IF EXISTS intent_privacy_params_intent_id_fkey;
+ALTER TABLE chain_transactions DROP CONSTRAINT IF EXISTS chain_transactions_intent_id_fkey;
+ALTER TABLE bridge_events DROP CONSTRAINT IF EXISTS bridge_events_intent_id_fkey;
@@ -0,0 +1 @@
+ALTER TABLE intent_privacy_params DROP COLUMN context_tag;
@@ -0,0 +1,17 @@
+-- Digest binding each row's ciphertexts to its intent id and commitment, so a
+-- row paired with the wrong intent is refused instead of burning a claim.
+-- Must match encryption::privacy_context::privacy_context.
+ALTER TABLE intent_privacy_params ADD COLUMN context_tag TEXT;
+
+UPDATE intent_privacy_params SET context_tag = encode(sha256(convert_to(concat_ws(
+    E'\n',
+    'shadow-swap/intent-privacy/v1',
+    intent_id,
+    COALESCE(commitment, ''),
+    COALESCE(nullifier, ''),
+    COALESCE(secret, ''),
+    COALESCE(recipient, ''),
+    COALESCE(claim_signature, '')
+), 'UTF8')), 'hex');
+
+ALTER TABLE intent_privacy_params ALTER COLUMN context_tag SET NOT NULL;
@@ -0,0 +1,7 @@
+-- Bridge metric counters, saved periodically so totals survive restarts.
+-- One row per relayer instance sharing the database.
+CREATE TABLE IF NOT EXISTS metric_snapshots (
+    instance TEXT PRIMARY KEY,
+    snapshot JSONB NOT NULL,
+    saved_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
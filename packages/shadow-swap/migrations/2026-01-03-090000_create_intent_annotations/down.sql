@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_intent_annotations_intent_id;
+DROP TABLE IF EXISTS intent_annotations;
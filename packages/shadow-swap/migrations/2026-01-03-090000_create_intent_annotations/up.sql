@@ -0,0 +1,15 @@
+-- Notes and manual status overrides operators attach to an intent, kept
+-- so an incident can be reconstructed afterwards.
+CREATE TABLE IF NOT EXISTS intent_annotations (
+    id SERIAL PRIMARY KEY,
+    intent_id TEXT NOT NULL,
+    operator TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('note', 'status_override')),
+    reason TEXT NOT NULL,
+    previous_status TEXT,
+    new_status TEXT,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_intent_annotations_intent_id
+ON intent_annotations (intent_id, created_at);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS partition_assignments;
+DROP TABLE IF EXISTS relayer_instances;
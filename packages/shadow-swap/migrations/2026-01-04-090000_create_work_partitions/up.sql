@@ -0,0 +1,17 @@
+-- Relayer instances sharing this database, and which one owns each intent
+-- partition. Instances that stop heartbeating lose their partitions to the
+-- survivors.
+CREATE TABLE IF NOT EXISTS relayer_instances (
+    instance_id TEXT PRIMARY KEY,
+    region TEXT NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    heartbeat_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS partition_assignments (
+    partition INTEGER PRIMARY KEY CHECK (partition >= 0),
+    instance_id TEXT,
+    -- Bumped on every reassignment; writers compare it to avoid racing
+    epoch BIGINT NOT NULL DEFAULT 0,
+    assigned_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
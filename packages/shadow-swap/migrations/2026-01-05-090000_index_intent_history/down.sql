@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_chain_tx_intent_id_id;
+DROP INDEX IF EXISTS idx_bridge_events_intent_id_id;
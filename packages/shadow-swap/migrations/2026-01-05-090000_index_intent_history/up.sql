@@ -0,0 +1,3 @@
+-- Keyset pagination of an intent's events and transactions by row id
+CREATE INDEX IF NOT EXISTS idx_bridge_events_intent_id_id ON bridge_events(intent_id, id);
+CREATE INDEX IF NOT EXISTS idx_chain_tx_intent_id_id ON chain_transactions(intent_id, id);
@@ -0,0 +1,13 @@
+-- Tokens added or changed through the admin API. On startup these rows
+-- replace the network profile's (or the built-in) listing of the same symbol.
+CREATE TABLE IF NOT EXISTS token_listings (
+    symbol TEXT PRIMARY KEY,
+    ethereum_address TEXT NOT NULL,
+    mantle_address TEXT NOT NULL,
+    decimals INTEGER NOT NULL,
+    -- Base units
+    min_amount TEXT NOT NULL,
+    max_amount TEXT NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
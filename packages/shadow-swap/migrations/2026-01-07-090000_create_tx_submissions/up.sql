@@ -0,0 +1,23 @@
+-- Two-phase journal of relayer transactions that must land at most once.
+-- A row is written as `submitting` (calldata hash, then nonce) before the
+-- send, moves to `submitted` with the hash, and is finalized from the
+-- receipt. On restart, open rows are resolved against the chain instead of
+-- being sent again.
+CREATE TABLE IF NOT EXISTS tx_submissions (
+    -- `<tx type>:<intent id>`
+    operation TEXT PRIMARY KEY,
+    intent_id TEXT NOT NULL,
+    chain_id INTEGER NOT NULL,
+    tx_type TEXT NOT NULL,
+    calldata_hash TEXT NOT NULL,
+    nonce BIGINT,
+    tx_hash TEXT,
+    -- submitting, submitted, confirmed, reverted or dropped
+    status TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_tx_submissions_open
+    ON tx_submissions (chain_id)
+    WHERE status IN ('submitting', 'submitted');
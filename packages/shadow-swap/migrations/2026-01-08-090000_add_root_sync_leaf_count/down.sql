@@ -0,0 +1,2 @@
+DROP INDEX IF EXISTS idx_root_syncs_type_created;
+ALTER TABLE root_syncs DROP COLUMN IF EXISTS leaf_count;
@@ -0,0 +1,3 @@
+-- Leaves behind a root the relayer pushed itself; NULL for indexer-reported syncs
+ALTER TABLE root_syncs ADD COLUMN IF NOT EXISTS leaf_count BIGINT;
+CREATE INDEX IF NOT EXISTS idx_root_syncs_type_created ON root_syncs(sync_type, created_at DESC);
@@ -0,0 +1 @@
+ALTER TABLE intent_privacy_params DROP COLUMN IF EXISTS claim_delegate;
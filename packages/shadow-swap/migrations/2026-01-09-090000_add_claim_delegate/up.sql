@@ -0,0 +1,2 @@
+-- Address a delegated claim pays out to instead of the recipient
+ALTER TABLE intent_privacy_params ADD COLUMN IF NOT EXISTS claim_delegate TEXT;
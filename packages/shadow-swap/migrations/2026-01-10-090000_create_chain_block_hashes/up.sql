@@ -0,0 +1,9 @@
+-- Hashes of each chain's most recent blocks, kept by the reorg monitor to
+-- notice when a block it indexed events from is replaced.
+CREATE TABLE IF NOT EXISTS chain_block_hashes (
+    chain TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    block_hash TEXT NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (chain, block_number)
+);
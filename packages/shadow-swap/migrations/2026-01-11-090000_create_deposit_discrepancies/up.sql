@@ -0,0 +1,15 @@
+-- Intents whose pool received a different amount than the intent's source
+-- amount, e.g. a fee-on-transfer token or an extra transfer in the same tx.
+CREATE TABLE IF NOT EXISTS deposit_discrepancies (
+    intent_id TEXT PRIMARY KEY,
+    source_chain TEXT NOT NULL,
+    token TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('underpaid', 'overpaid')),
+    expected_amount TEXT NOT NULL,
+    deposited_amount TEXT NOT NULL,
+    difference TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    detected_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_deposit_discrepancies_kind ON deposit_discrepancies (kind);
@@ -0,0 +1,14 @@
+-- Short exclusive windows solvers take on an intent before filling it, so
+-- two solvers don't race the same fill. One row per intent: the latest
+-- reservation, whatever became of it.
+CREATE TABLE IF NOT EXISTS intent_reservations (
+    intent_id TEXT PRIMARY KEY,
+    solver_address TEXT NOT NULL,
+    status TEXT NOT NULL CHECK (status IN ('active', 'filled', 'released', 'lapsed')),
+    reserved_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_intent_reservations_active
+    ON intent_reservations (expires_at) WHERE status = 'active';
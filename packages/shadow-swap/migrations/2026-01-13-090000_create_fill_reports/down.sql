@@ -0,0 +1 @@
+DROP TABLE IF EXISTS fill_reports;
@@ -0,0 +1,18 @@
+-- Fills reported by third-party solvers, kept until the reported
+-- transaction has been checked on the destination chain. One row per
+-- intent, chain and transaction, so a resubmitted report is a no-op.
+CREATE TABLE IF NOT EXISTS fill_reports (
+    id BIGSERIAL PRIMARY KEY,
+    intent_id TEXT NOT NULL,
+    chain TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    solver_address TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'verified', 'rejected')),
+    reason TEXT,
+    reported_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    resolved_at TIMESTAMPTZ,
+    UNIQUE (intent_id, chain, transaction_hash)
+);
+
+CREATE INDEX IF NOT EXISTS idx_fill_reports_pending
+    ON fill_reports (reported_at) WHERE status = 'pending';
@@ -0,0 +1,18 @@
+-- Registration and settlement work per intent, so retries, backoff and
+-- dead-lettering survive restarts. One row per intent and kind; a finished
+-- job is reopened when the worker finds the intent waiting again.
+CREATE TABLE IF NOT EXISTS relayer_jobs (
+    id BIGSERIAL PRIMARY KEY,
+    kind TEXT NOT NULL CHECK (kind IN ('registration', 'settlement')),
+    intent_id TEXT NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'done', 'dead', 'cancelled')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (kind, intent_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_relayer_jobs_status
+    ON relayer_jobs (status, updated_at);
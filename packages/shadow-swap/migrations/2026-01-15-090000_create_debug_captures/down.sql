@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS debug_capture_entries;
+DROP TABLE IF EXISTS debug_captures;
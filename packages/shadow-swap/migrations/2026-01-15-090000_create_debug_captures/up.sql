@@ -0,0 +1,22 @@
+-- Intents an operator marked for verbose capture, and what the relayer
+-- recorded about them while the mark lasted. Entries go with their capture
+-- once it has been expired for the retention period.
+CREATE TABLE IF NOT EXISTS debug_captures (
+    intent_id TEXT PRIMARY KEY,
+    operator TEXT NOT NULL,
+    reason TEXT NOT NULL,
+    started_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS debug_capture_entries (
+    id BIGSERIAL PRIMARY KEY,
+    intent_id TEXT NOT NULL REFERENCES debug_captures (intent_id) ON DELETE CASCADE,
+    component TEXT NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('request', 'response', 'rpc', 'decision')),
+    payload JSONB NOT NULL,
+    recorded_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_debug_capture_entries_intent
+    ON debug_capture_entries (intent_id, id);
@@ -0,0 +1,25 @@
+-- Inbox between receiving a chain event, from the indexer webhook or a log
+-- listener, and applying it to the intent and merkle pipelines. Events are
+-- stored before they are acknowledged and kept until applied, so a slow
+-- database or a crash delays them instead of losing them. The same event
+-- from both sources is stored once.
+CREATE TABLE IF NOT EXISTS ingestion_queue (
+    id BIGSERIAL PRIMARY KEY,
+    source TEXT NOT NULL CHECK (source IN ('indexer', 'log_listener')),
+    event_type TEXT NOT NULL,
+    chain TEXT NOT NULL,
+    transaction_hash TEXT NOT NULL,
+    block_number BIGINT NOT NULL,
+    log_index INTEGER NOT NULL,
+    payload JSONB NOT NULL,
+    status TEXT NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'done', 'dead')),
+    attempts INTEGER NOT NULL DEFAULT 0,
+    last_error TEXT,
+    received_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    processed_at TIMESTAMPTZ,
+    UNIQUE (chain, transaction_hash, block_number, log_index, event_type)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ingestion_queue_due
+    ON ingestion_queue (status, next_attempt_at, id);
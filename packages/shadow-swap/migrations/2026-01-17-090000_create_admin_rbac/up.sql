@@ -0,0 +1,31 @@
+-- Admin API keys, each bound to one role. Only a SHA-256 of the key is
+-- stored; the key itself is shown once, when it is issued.
+CREATE TABLE IF NOT EXISTS admin_api_keys (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    key_prefix TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    role TEXT NOT NULL CHECK (role IN ('read_only', 'operator', 'superuser')),
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
+
+-- Every request to an admin route, allowed or not.
+CREATE TABLE IF NOT EXISTS admin_audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    actor TEXT NOT NULL,
+    key_id INTEGER REFERENCES admin_api_keys (id) ON DELETE SET NULL,
+    role TEXT,
+    method TEXT NOT NULL,
+    path TEXT NOT NULL,
+    required_role TEXT NOT NULL,
+    status_code INTEGER NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_created
+    ON admin_audit_log (created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_admin_audit_log_actor
+    ON admin_audit_log (actor, created_at DESC);
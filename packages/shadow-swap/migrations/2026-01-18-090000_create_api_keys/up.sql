@@ -0,0 +1,15 @@
+-- Keys for clients of the public API. Scopes are `read`, `create_intent`
+-- and `admin`; only a SHA-256 of each key is stored.
+CREATE TABLE IF NOT EXISTS api_keys (
+    id SERIAL PRIMARY KEY,
+    name TEXT NOT NULL,
+    key_prefix TEXT NOT NULL,
+    key_hash TEXT NOT NULL UNIQUE,
+    scopes TEXT[] NOT NULL,
+    -- Overrides RATE_LIMIT_KEY_PER_MIN for this key
+    rate_limit_per_min INTEGER CHECK (rate_limit_per_min > 0),
+    created_by TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ,
+    revoked_at TIMESTAMPTZ
+);
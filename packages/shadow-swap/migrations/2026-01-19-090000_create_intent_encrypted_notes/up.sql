@@ -0,0 +1,13 @@
+-- Notes the user encrypts for the recipient before submitting the intent,
+-- e.g. an invoice reference. The relayer cannot read them and only passes
+-- them on.
+CREATE TABLE IF NOT EXISTS intent_encrypted_notes (
+    intent_id TEXT PRIMARY KEY,
+    ciphertext TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    -- Set once the `intent_note` webhook went out after the fill
+    delivered_at TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_intent_encrypted_notes_undelivered
+ON intent_encrypted_notes (created_at) WHERE delivered_at IS NULL;
@@ -0,0 +1,16 @@
+-- One row per tree the startup consistency check found broken and
+-- repaired, e.g. nodes written for a leaf the leaf count never counted
+-- after a crash mid-append.
+CREATE TABLE IF NOT EXISTS merkle_repairs (
+    id SERIAL PRIMARY KEY,
+    tree_name TEXT NOT NULL,
+    issues TEXT[] NOT NULL,
+    stored_leaf_count BIGINT NOT NULL,
+    stored_root TEXT NOT NULL,
+    repaired_leaf_count BIGINT NOT NULL,
+    repaired_root TEXT NOT NULL,
+    nodes_rewritten BIGINT NOT NULL,
+    repaired_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_merkle_repairs_tree ON merkle_repairs (tree_name, repaired_at DESC);
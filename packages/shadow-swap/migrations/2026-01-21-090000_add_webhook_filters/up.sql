@@ -0,0 +1,3 @@
+-- Which events an endpoint receives, e.g. "event = intent_stuck and chain = mantle".
+-- NULL receives every event.
+ALTER TABLE webhook_endpoints ADD COLUMN IF NOT EXISTS filter TEXT;
@@ -0,0 +1,13 @@
+-- Record of the one-time `rekey-privacy-contexts` run, which replaces the
+-- unkeyed v1 context tags with keyed ones. The command refuses to run again
+-- once a row exists. Databases without privacy params have nothing to
+-- re-key and are marked done here.
+CREATE TABLE IF NOT EXISTS privacy_context_rekeys (
+    id SERIAL PRIMARY KEY,
+    rekeyed BIGINT NOT NULL,
+    unverified TEXT[] NOT NULL,
+    completed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO privacy_context_rekeys (rekeyed, unverified)
+SELECT 0, '{}' WHERE NOT EXISTS (SELECT 1 FROM intent_privacy_params);
@@ -0,0 +1,572 @@
+use actix_web::{
+    Error, HttpMessage, HttpResponse,
+    body::{EitherBody, MessageBody},
+    dev::{Payload, ServiceRequest, ServiceResponse},
+    http::header::AUTHORIZATION,
+    middleware::Next,
+    web,
+};
+use anyhow::{Context, Result, anyhow};
+use serde::Serialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    api::{helper::validate_hmac, versioning::ApiVersion},
+    database::{
+        database::Database,
+        model::{DbAdminApiKey, NewAdminApiKey, NewAdminAuditEntry},
+    },
+};
+
+/// What a caller may do on the admin routes. Each role includes the ones
+/// below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AdminRole {
+    /// Listings, recordings and timelines.
+    ReadOnly,
+    /// Day-to-day fixes: retrying jobs, annotating intents, approving
+    /// orphaned fills, debug captures and per-user limits.
+    Operator,
+    /// Configuration, compliance overrides, webhooks and the keys
+    /// themselves.
+    Superuser,
+}
+
+impl AdminRole {
+    pub const ALL: [AdminRole; 3] = [Self::ReadOnly, Self::Operator, Self::Superuser];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read_only",
+            Self::Operator => "operator",
+            Self::Superuser => "superuser",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|role| role.as_str() == value.trim())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unknown admin role '{}' (use read_only, operator or superuser)",
+                    value.trim()
+                )
+            })
+    }
+}
+
+/// Least role each admin route needs, by method and pattern below the API
+/// prefix. Routes missing here need `Superuser`.
+const ADMIN_ROUTES: &[(&str, &str, AdminRole)] = &[
+    ("GET", "/admin/commitments/duplicates", AdminRole::ReadOnly),
+    ("GET", "/admin/intent-limits", AdminRole::ReadOnly),
+    ("POST", "/admin/intent-limits", AdminRole::Operator),
+    ("GET", "/admin/compliance", AdminRole::ReadOnly),
+    ("POST", "/admin/compliance/override", AdminRole::Superuser),
+    ("GET", "/admin/deposits/discrepancies", AdminRole::ReadOnly),
+    (
+        "POST",
+        "/admin/contracts/acknowledge-upgrade",
+        AdminRole::Superuser,
+    ),
+    ("GET", "/admin/backlog", AdminRole::ReadOnly),
+    ("GET", "/admin/claim-auth/failures", AdminRole::ReadOnly),
+    ("GET", "/admin/stuck", AdminRole::ReadOnly),
+    ("GET", "/admin/webhooks", AdminRole::ReadOnly),
+    ("POST", "/admin/webhooks", AdminRole::Superuser),
+    (
+        "POST",
+        "/admin/webhooks/{endpoint_id}/rotate",
+        AdminRole::Superuser,
+    ),
+    (
+        "POST",
+        "/admin/webhooks/{endpoint_id}/filter",
+        AdminRole::Superuser,
+    ),
+    (
+        "POST",
+        "/admin/webhooks/{endpoint_id}/test",
+        AdminRole::Superuser,
+    ),
+    (
+        "DELETE",
+        "/admin/webhooks/{endpoint_id}",
+        AdminRole::Superuser,
+    ),
+    ("GET", "/admin/orphaned-fills", AdminRole::ReadOnly),
+    (
+        "POST",
+        "/admin/orphaned-fills/{intent_id}/approve",
+        AdminRole::Operator,
+    ),
+    ("POST", "/admin/debug-captures", AdminRole::Operator),
+    ("GET", "/admin/debug-captures", AdminRole::ReadOnly),
+    (
+        "GET",
+        "/admin/debug-captures/{intent_id}",
+        AdminRole::ReadOnly,
+    ),
+    (
+        "DELETE",
+        "/admin/debug-captures/{intent_id}",
+        AdminRole::Operator,
+    ),
+    ("GET", "/admin/merkle/repairs", AdminRole::ReadOnly),
+    ("GET", "/admin/jobs", AdminRole::ReadOnly),
+    ("POST", "/admin/jobs/{id}/{action}", AdminRole::Operator),
+    (
+        "POST",
+        "/admin/intents/{intent_id}/annotations",
+        AdminRole::Operator,
+    ),
+    (
+        "GET",
+        "/admin/intents/{intent_id}/timeline",
+        AdminRole::ReadOnly,
+    ),
+    ("GET", "/admin/partitions", AdminRole::ReadOnly),
+    ("GET", "/admin/tokens", AdminRole::ReadOnly),
+    ("POST", "/admin/tokens", AdminRole::Superuser),
+    (
+        "POST",
+        "/admin/tokens/{symbol}/{action}",
+        AdminRole::Superuser,
+    ),
+    ("GET", "/admin/api-keys", AdminRole::Superuser),
+    ("POST", "/admin/api-keys", AdminRole::Superuser),
+    ("DELETE", "/admin/api-keys/{id}", AdminRole::Superuser),
+    ("GET", "/admin/audit-log", AdminRole::Superuser),
+    ("GET", "/admin/client-keys", AdminRole::Superuser),
+    ("POST", "/admin/client-keys", AdminRole::Superuser),
+    ("DELETE", "/admin/client-keys/{id}", AdminRole::Superuser),
+];
+
+/// Least role for a request, from its method and the pattern of the route it
+/// matched.
+pub fn required_role(method: &str, pattern: Option<&str>) -> AdminRole {
+    let Some(route) = pattern.and_then(api_relative) else {
+        return AdminRole::Superuser;
+    };
+    ADMIN_ROUTES
+        .iter()
+        .find(|(route_method, pattern, _)| *route_method == method && *pattern == route)
+        .map_or(AdminRole::Superuser, |(_, _, role)| *role)
+}
+
+/// `path` below `/api/v{n}` or the unversioned `/api`.
+pub(crate) fn api_relative(path: &str) -> Option<&str> {
+    let rest = path.strip_prefix("/api")?;
+    Some(
+        ApiVersion::ALL
+            .iter()
+            .find_map(|version| rest.strip_prefix(&format!("/v{}", version.number())))
+            .unwrap_or(rest),
+    )
+}
+
+pub(crate) fn is_admin_path(path: &str) -> bool {
+    api_relative(path).is_some_and(|rest| rest == "/admin" || rest.starts_with("/admin/"))
+}
+
+/// The path the router matches on: percent-encoded characters are decoded,
+/// so `/api/v1/%61dmin/jobs` is `/api/v1/admin/jobs` here but not in
+/// `req.path()`.
+pub(crate) fn routed_path(req: &ServiceRequest) -> &str {
+    req.match_info().as_str()
+}
+
+/// Pattern of the route the request will reach, from the routed path.
+/// `req.match_pattern()` looks up the raw path and misses encoded ones.
+pub(crate) fn routed_pattern(req: &ServiceRequest) -> Option<String> {
+    req.request().resource_map().match_pattern(routed_path(req))
+}
+
+/// Whether the shared HMAC secret still grants admin access.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminAuthPolicy {
+    /// Requests signed with the shared secret act as a superuser. Turn off
+    /// once every operator has a key of their own.
+    pub hmac_enabled: bool,
+}
+
+impl Default for AdminAuthPolicy {
+    fn default() -> Self {
+        Self { hmac_enabled: true }
+    }
+}
+
+impl AdminAuthPolicy {
+    /// Reads `ADMIN_HMAC_ENABLED`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(enabled) = var("ADMIN_HMAC_ENABLED") {
+            policy.hmac_enabled = enabled
+                .trim()
+                .parse()
+                .context("Invalid ADMIN_HMAC_ENABLED")?;
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Who made an admin request. Handlers read it with
+/// `web::ReqData<AdminIdentity>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminIdentity {
+    /// Key name, or `hmac` for the shared secret.
+    pub actor: String,
+    pub key_id: Option<i32>,
+    pub role: AdminRole,
+}
+
+/// A freshly issued key. `key` is not stored and cannot be shown again.
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuedAdminKey {
+    pub id: i32,
+    pub name: String,
+    pub role: AdminRole,
+    pub key: String,
+}
+
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.trim().as_bytes()))
+}
+
+pub fn issue_admin_key(
+    database: &Database,
+    name: &str,
+    role: AdminRole,
+    created_by: &str,
+) -> Result<IssuedAdminKey> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(anyhow!("name is required"));
+    }
+
+    let key = format!("ssk_{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let stored = database.insert_admin_api_key(&NewAdminApiKey {
+        name,
+        key_prefix: &key[..12],
+        key_hash: &hash_key(&key),
+        role: role.as_str(),
+        created_by,
+    })?;
+
+    info!(
+        "🔑 Admin key {} ({}) issued to {} by {}",
+        stored.id, stored.key_prefix, stored.name, created_by
+    );
+    Ok(IssuedAdminKey {
+        id: stored.id,
+        name: stored.name,
+        role,
+        key,
+    })
+}
+
+/// The identity behind an unrevoked key.
+pub fn authenticate_key(database: &Database, key: &str) -> Result<Option<AdminIdentity>> {
+    let Some(stored) = database.use_admin_api_key(&hash_key(key))? else {
+        return Ok(None);
+    };
+    identity_of(stored).map(Some)
+}
+
+fn identity_of(key: DbAdminApiKey) -> Result<AdminIdentity> {
+    Ok(AdminIdentity {
+        role: AdminRole::parse(&key.role)
+            .with_context(|| format!("Admin key {} has a bad role", key.id))?,
+        actor: key.name,
+        key_id: Some(key.id),
+    })
+}
+
+fn denied(status: actix_web::http::StatusCode, message: &str) -> HttpResponse {
+    HttpResponse::build(status).json(json!({
+        "status": "error",
+        "message": message
+    }))
+}
+
+/// Who is calling: a bearer key, or an HMAC-signed request while the
+/// shared secret is enabled. The request body is read for the signature and
+/// put back for the handler.
+async fn identify(
+    req: &mut ServiceRequest,
+    app_state: &web::Data<AppState>,
+) -> Result<std::result::Result<AdminIdentity, HttpResponse>, Error> {
+    let unauthorized = |message| denied(actix_web::http::StatusCode::UNAUTHORIZED, message);
+
+    if let Some(header) = req.headers().get(AUTHORIZATION) {
+        let Some(key) = header
+            .to_str()
+            .ok()
+            .and_then(|value| value.strip_prefix("Bearer "))
+        else {
+            return Ok(Err(unauthorized("Expected Authorization: Bearer <key>")));
+        };
+        return Ok(match authenticate_key(&app_state.database, key) {
+            Ok(Some(identity)) => Ok(identity),
+            Ok(None) => Err(unauthorized("Invalid or revoked admin key")),
+            Err(e) => {
+                error!("Failed to check admin key: {:#}", e);
+                Err(denied(
+                    actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to check admin key",
+                ))
+            }
+        });
+    }
+
+    if !req.headers().contains_key("x-signature") {
+        return Ok(Err(unauthorized("Missing admin credentials")));
+    }
+    if !app_state.admin_auth.hmac_enabled {
+        return Ok(Err(unauthorized(
+            "Shared-secret admin access is disabled, use an admin key",
+        )));
+    }
+
+    let body = req.extract::<web::Bytes>().await?;
+    req.set_payload(Payload::from(body.clone()));
+    Ok(
+        validate_hmac(req.request(), &body, app_state).map(|()| AdminIdentity {
+            actor: "hmac".to_string(),
+            key_id: None,
+            role: AdminRole::Superuser,
+        }),
+    )
+}
+
+/// Middleware for the API scopes: requests to `/admin` routes must carry a
+/// role at least as high as the route's, and every one of them is written
+/// to the audit log with its outcome. Other routes pass straight through.
+pub async fn admin_authorization<B: MessageBody + 'static>(
+    mut req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    if !is_admin_path(routed_path(&req)) {
+        return Ok(next.call(req).await?.map_into_left_body());
+    }
+    let Some(app_state) = req.app_data::<web::Data<AppState>>().cloned() else {
+        let response = denied(
+            actix_web::http::StatusCode::INTERNAL_SERVER_ERROR,
+            "Admin access is not configured",
+        );
+        return Ok(req.into_response(response).map_into_right_body());
+    };
+
+    let pattern = routed_pattern(&req);
+    let required = required_role(req.method().as_str(), pattern.as_deref());
+    let method = req.method().to_string();
+    let path = routed_path(&req).to_string();
+    let audit = |identity: Option<&AdminIdentity>, status: u16| {
+        let entry = NewAdminAuditEntry {
+            actor: identity.map_or("anonymous", |identity| identity.actor.as_str()),
+            key_id: identity.and_then(|identity| identity.key_id),
+            role: identity.map(|identity| identity.role.as_str()),
+            method: &method,
+            path: &path,
+            required_role: required.as_str(),
+            status_code: i32::from(status),
+        };
+        if let Err(e) = app_state.database.record_admin_audit_entry(&entry) {
+            error!("Failed to audit {} {}: {:#}", method, path, e);
+        }
+    };
+
+    let identity = match identify(&mut req, &app_state).await? {
+        Ok(identity) => identity,
+        Err(response) => {
+            warn!("🚫 Unauthenticated admin request: {} {}", method, path);
+            audit(None, response.status().as_u16());
+            return Ok(req.into_response(response).map_into_right_body());
+        }
+    };
+
+    // Nothing to authorize against: don't let the request reach a handler
+    if pattern.is_none() {
+        let response = denied(
+            actix_web::http::StatusCode::NOT_FOUND,
+            "Unknown admin route",
+        );
+        audit(Some(&identity), response.status().as_u16());
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    if identity.role < required {
+        warn!(
+            "🚫 {} ({}) denied {} {}: needs {}",
+            identity.actor,
+            identity.role.as_str(),
+            method,
+            path,
+            required.as_str()
+        );
+        let response = denied(
+            actix_web::http::StatusCode::FORBIDDEN,
+            &format!("Requires the {} role", required.as_str()),
+        );
+        audit(Some(&identity), response.status().as_u16());
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    req.extensions_mut().insert(identity.clone());
+    let response = next.call(req).await?;
+    audit(Some(&identity), response.status().as_u16());
+    Ok(response.map_into_left_body())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::test_database;
+    use serial_test::serial;
+
+    #[test]
+    fn test_required_role_per_route() {
+        assert_eq!(
+            required_role("GET", Some("/api/v1/admin/jobs")),
+            AdminRole::ReadOnly
+        );
+        assert_eq!(
+            required_role("POST", Some("/api/admin/jobs/{id}/{action}")),
+            AdminRole::Operator
+        );
+        assert_eq!(
+            required_role("POST", Some("/api/v1/admin/tokens")),
+            AdminRole::Superuser
+        );
+        // Unlisted or unmatched routes need the highest role
+        assert_eq!(
+            required_role("PUT", Some("/api/v1/admin/jobs")),
+            AdminRole::Superuser
+        );
+        assert_eq!(required_role("GET", None), AdminRole::Superuser);
+
+        assert!(is_admin_path("/api/v1/admin/jobs/3/retry"));
+        assert!(is_admin_path("/api/admin/stuck"));
+        assert!(!is_admin_path("/api/v1/administrators"));
+        assert!(!is_admin_path("/api/v1/intents/0x01/timeline"));
+
+        assert!(AdminRole::Superuser > AdminRole::Operator);
+        assert_eq!(AdminRole::parse("operator").unwrap(), AdminRole::Operator);
+        assert!(AdminRole::parse("root").is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_encoded_admin_paths_are_still_guarded() {
+        use actix_web::{App, get, middleware::from_fn, test};
+
+        #[get("/admin/jobs")]
+        async fn jobs() -> HttpResponse {
+            HttpResponse::Ok().finish()
+        }
+        #[get("/stats")]
+        async fn stats() -> HttpResponse {
+            HttpResponse::Ok().finish()
+        }
+
+        // Without an AppState the guard refuses every admin request, so a
+        // 200 means the request got past it
+        let app = test::init_service(
+            App::new().service(
+                web::scope("/api/v1")
+                    .wrap(from_fn(admin_authorization))
+                    .service(jobs)
+                    .service(stats),
+            ),
+        )
+        .await;
+        for uri in [
+            "/api/v1/admin/jobs",
+            "/api/v1/%61dmin/jobs",
+            "/api/v1/admin/%6Aobs",
+        ] {
+            let response =
+                test::call_service(&app, test::TestRequest::get().uri(uri).to_request()).await;
+            assert_eq!(response.status(), 500, "{}", uri);
+        }
+        let response = test::call_service(
+            &app,
+            test::TestRequest::get().uri("/api/v1/st%61ts").to_request(),
+        )
+        .await;
+        assert_eq!(response.status(), 200);
+
+        let req = test::TestRequest::get()
+            .uri("/api/v1/%61dmin/jobs")
+            .to_srv_request();
+        assert_eq!(routed_path(&req), "/api/v1/admin/jobs");
+    }
+
+    #[test]
+    fn test_every_admin_route_has_a_role() {
+        let routes = include_str!("routes.rs");
+        for line in routes.lines().map(str::trim) {
+            let Some((method, rest)) = line
+                .strip_prefix("#[")
+                .and_then(|attr| attr.split_once("(\""))
+            else {
+                continue;
+            };
+            let Some(pattern) = rest.strip_suffix("\")]") else {
+                continue;
+            };
+            if !pattern.starts_with("/admin") {
+                continue;
+            }
+            let method = method.to_uppercase();
+            assert!(
+                ADMIN_ROUTES
+                    .iter()
+                    .any(|(m, p, _)| *m == method && *p == pattern),
+                "{} {} has no role in ADMIN_ROUTES",
+                method,
+                pattern
+            );
+        }
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_issued_keys_authenticate_until_revoked() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let issued = issue_admin_key(&database, "alice", AdminRole::Operator, "hmac")?;
+        assert!(issued.key.starts_with("ssk_"));
+        assert!(issue_admin_key(&database, " ", AdminRole::Operator, "hmac").is_err());
+
+        let identity = authenticate_key(&database, &issued.key)?.expect("valid key");
+        assert_eq!(identity.actor, "alice");
+        assert_eq!(identity.role, AdminRole::Operator);
+        assert_eq!(identity.key_id, Some(issued.id));
+        assert!(authenticate_key(&database, "ssk_guess")?.is_none());
+
+        let listed = database.list_admin_api_keys()?;
+        let stored = listed
+            .iter()
+            .find(|key| key.id == issued.id)
+            .expect("listed");
+        assert!(stored.last_used_at.is_some());
+        assert!(!serde_json::to_string(stored)?.contains(&stored.key_hash));
+
+        assert!(database.revoke_admin_api_key(issued.id)?);
+        assert!(!database.revoke_admin_api_key(issued.id)?);
+        assert!(authenticate_key(&database, &issued.key)?.is_none());
+
+        Ok(())
+    }
+}
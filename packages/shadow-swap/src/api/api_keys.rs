@@ -0,0 +1,547 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use actix_web::{
+    Error, HttpMessage, HttpResponse,
+    body::{EitherBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::{
+        StatusCode,
+        header::{HeaderValue, RETRY_AFTER},
+    },
+    middleware::Next,
+    web,
+};
+use anyhow::{Context, Result, anyhow, bail};
+use serde::Serialize;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use tracing::{debug, error, info};
+use uuid::Uuid;
+
+use crate::{
+    AppState,
+    api::{
+        admin_auth::{api_relative, is_admin_path, routed_path, routed_pattern},
+        rate_limit::{RateLimitPolicy, RateLimiter},
+    },
+    database::{
+        database::Database,
+        model::{DbApiKey, NewApiKey},
+    },
+};
+
+/// Header public API clients send their key in. `Authorization` is left to
+/// admin keys and solver reservation tokens.
+pub const API_KEY_HEADER: &str = "x-api-key";
+
+/// What a client key may do on the public API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiScope {
+    /// Quotes, prices, intent status and proofs.
+    Read,
+    /// Creating intents and revealing their secrets.
+    CreateIntent,
+    /// First-party services: every scope, and no rate limit. Does not open
+    /// the `/admin` routes, which take admin keys.
+    Admin,
+}
+
+impl ApiScope {
+    pub const ALL: [ApiScope; 3] = [Self::Read, Self::CreateIntent, Self::Admin];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Read => "read",
+            Self::CreateIntent => "create_intent",
+            Self::Admin => "admin",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|scope| scope.as_str() == value.trim())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unknown API scope '{}' (use read, create_intent or admin)",
+                    value.trim()
+                )
+            })
+    }
+}
+
+/// Routes, by method and pattern below the API prefix, that need
+/// `CreateIntent`. Every other public route needs `Read`.
+const CREATE_INTENT_ROUTES: &[(&str, &str)] = &[
+    ("POST", "/bridge/initiate"),
+    ("POST", "/intents/{intent_id}/reveal"),
+];
+
+/// Routes outside client keys and rate limits: the indexer pushes events
+/// with HMAC signatures and health checks come from load balancers.
+const EXEMPT_ROUTES: &[&str] = &["/indexer/event", "/health"];
+
+/// Scope a request needs, from its method and the pattern of the route it
+/// matched. `None` when no route matched, so there is nothing to grant.
+pub fn required_scope(method: &str, pattern: Option<&str>) -> Option<ApiScope> {
+    let route = pattern.and_then(api_relative)?;
+    if CREATE_INTENT_ROUTES
+        .iter()
+        .any(|(route_method, pattern)| *route_method == method && *pattern == route)
+    {
+        Some(ApiScope::CreateIntent)
+    } else {
+        Some(ApiScope::Read)
+    }
+}
+
+fn is_exempt(path: &str) -> bool {
+    is_admin_path(path) || api_relative(path).is_some_and(|route| EXEMPT_ROUTES.contains(&route))
+}
+
+/// Whether the public API takes anonymous callers, and how long a checked
+/// key is trusted before it is looked up again.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiKeyPolicy {
+    /// Reject requests without a key instead of limiting them per IP.
+    pub required: bool,
+    /// A revoked key keeps working on each instance for up to this long.
+    pub cache_ttl: Duration,
+}
+
+impl Default for ApiKeyPolicy {
+    fn default() -> Self {
+        Self {
+            required: false,
+            cache_ttl: Duration::from_secs(60),
+        }
+    }
+}
+
+impl ApiKeyPolicy {
+    /// Reads `API_KEYS_REQUIRED` and `API_KEY_CACHE_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(required) = var("API_KEYS_REQUIRED") {
+            policy.required = required
+                .trim()
+                .parse()
+                .context("Invalid API_KEYS_REQUIRED")?;
+        }
+        if let Some(secs) = var("API_KEY_CACHE_SECS") {
+            policy.cache_ttl =
+                Duration::from_secs(secs.trim().parse().context("Invalid API_KEY_CACHE_SECS")?);
+        }
+
+        Ok(policy)
+    }
+}
+
+/// The client behind a key. Handlers can read it with
+/// `Option<web::ReqData<ApiClient>>`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ApiClient {
+    pub key_id: i32,
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    pub rate_limit_per_min: Option<u32>,
+}
+
+impl ApiClient {
+    pub fn allows(&self, scope: ApiScope) -> bool {
+        self.scopes.contains(&ApiScope::Admin) || self.scopes.contains(&scope)
+    }
+}
+
+/// A freshly issued key. `key` is not stored and cannot be shown again.
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuedApiKey {
+    pub id: i32,
+    pub name: String,
+    pub scopes: Vec<ApiScope>,
+    pub rate_limit_per_min: Option<i32>,
+    pub key: String,
+}
+
+fn hash_key(key: &str) -> String {
+    hex::encode(Sha256::digest(key.trim().as_bytes()))
+}
+
+pub fn issue_api_key(
+    database: &Database,
+    name: &str,
+    scopes: &[ApiScope],
+    rate_limit_per_min: Option<i32>,
+    created_by: &str,
+) -> Result<IssuedApiKey> {
+    let name = name.trim();
+    if name.is_empty() {
+        bail!("name is required");
+    }
+    if scopes.is_empty() {
+        bail!("at least one scope is required");
+    }
+    if rate_limit_per_min.is_some_and(|rate| rate <= 0) {
+        bail!("rate_limit_per_min must be positive");
+    }
+
+    let scopes = scopes.iter().fold(Vec::new(), |mut unique, scope| {
+        if !unique.contains(scope) {
+            unique.push(*scope);
+        }
+        unique
+    });
+    let key = format!("spk_{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let stored = database.insert_api_key(&NewApiKey {
+        name,
+        key_prefix: &key[..12],
+        key_hash: &hash_key(&key),
+        scopes: scopes.iter().map(ApiScope::as_str).collect(),
+        rate_limit_per_min,
+        created_by,
+    })?;
+
+    info!(
+        "🔑 API key {} ({}) issued to {} by {}",
+        stored.id, stored.key_prefix, stored.name, created_by
+    );
+    Ok(IssuedApiKey {
+        id: stored.id,
+        name: stored.name,
+        scopes,
+        rate_limit_per_min: stored.rate_limit_per_min,
+        key,
+    })
+}
+
+/// The client behind an unrevoked key.
+pub fn authenticate_api_key(database: &Database, key: &str) -> Result<Option<ApiClient>> {
+    let Some(stored) = database.use_api_key(&hash_key(key))? else {
+        return Ok(None);
+    };
+    client_of(stored).map(Some)
+}
+
+fn client_of(key: DbApiKey) -> Result<ApiClient> {
+    Ok(ApiClient {
+        scopes: key
+            .scopes
+            .iter()
+            .map(|scope| ApiScope::parse(scope))
+            .collect::<Result<_>>()
+            .with_context(|| format!("API key {} has a bad scope", key.id))?,
+        rate_limit_per_min: key.rate_limit_per_min.map(|rate| rate.max(1) as u32),
+        key_id: key.id,
+        name: key.name,
+    })
+}
+
+/// Key and rate policies, the token buckets and recently checked keys.
+#[derive(Debug, Default)]
+pub struct PublicAccess {
+    pub keys: ApiKeyPolicy,
+    pub limits: RateLimitPolicy,
+    limiter: RateLimiter,
+    /// By key hash, with when each was checked.
+    clients: Mutex<HashMap<String, (ApiClient, Instant)>>,
+}
+
+impl PublicAccess {
+    pub fn new(keys: ApiKeyPolicy, limits: RateLimitPolicy) -> Self {
+        Self {
+            keys,
+            limits,
+            ..Self::default()
+        }
+    }
+
+    /// Checks `key` against recent lookups, then the database.
+    fn client(&self, database: &Database, key: &str) -> Result<Option<ApiClient>> {
+        let hash = hash_key(key);
+        let mut clients = self.clients.lock().unwrap_or_else(|e| e.into_inner());
+        let ttl = self.keys.cache_ttl;
+        clients.retain(|_, (_, checked)| checked.elapsed() < ttl);
+        if let Some((client, _)) = clients.get(&hash) {
+            return Ok(Some(client.clone()));
+        }
+        drop(clients);
+
+        let client = authenticate_api_key(database, key)?;
+        if let Some(client) = &client {
+            self.clients
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(hash, (client.clone(), Instant::now()));
+        }
+        Ok(client)
+    }
+
+    /// Stop trusting a key on this instance at once, e.g. after revoking it.
+    pub fn forget_key(&self, key_id: i32) {
+        self.clients
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|_, (client, _)| client.key_id != key_id);
+    }
+}
+
+fn rejected(status: StatusCode, message: &str) -> HttpResponse {
+    HttpResponse::build(status).json(json!({
+        "status": "error",
+        "message": message
+    }))
+}
+
+fn rate_limited(retry_after: Duration) -> HttpResponse {
+    let secs = retry_after.as_secs_f64().ceil().max(1.0) as u64;
+    let mut response = rejected(StatusCode::TOO_MANY_REQUESTS, "Rate limit exceeded");
+    response
+        .headers_mut()
+        .insert(RETRY_AFTER, HeaderValue::from(secs));
+    response
+}
+
+/// Middleware for the API scopes. A request with an `X-API-Key` must use a
+/// valid key holding the route's scope, and is limited per key. Requests
+/// without one are limited per client IP, or rejected when keys are
+/// required. Admin, indexer and health routes pass straight through.
+pub async fn public_access<B: MessageBody + 'static>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    if is_exempt(routed_path(&req)) {
+        return Ok(next.call(req).await?.map_into_left_body());
+    }
+    let Some(app_state) = req.app_data::<web::Data<AppState>>().cloned() else {
+        return Ok(next.call(req).await?.map_into_left_body());
+    };
+    let access = &app_state.public_access;
+
+    let client_ip = {
+        let info = req.connection_info();
+        let ip = if access.limits.trust_forwarded {
+            info.realip_remote_addr()
+        } else {
+            info.peer_addr()
+        };
+        ip.unwrap_or("unknown").to_string()
+    };
+    let limit_ip = || match access.limits.ip_rate() {
+        Some(rate) => access.limiter.check(&format!("ip:{}", client_ip), rate),
+        None => Ok(()),
+    };
+
+    let key = req
+        .headers()
+        .get(API_KEY_HEADER)
+        .map(|value| value.to_str().unwrap_or_default().trim().to_string());
+    let Some(key) = key else {
+        if access.keys.required {
+            let response = rejected(StatusCode::UNAUTHORIZED, "Missing X-API-Key");
+            return Ok(req.into_response(response).map_into_right_body());
+        }
+        if let Err(retry_after) = limit_ip() {
+            debug!("⏳ Rate limited {} on {}", client_ip, req.path());
+            return Ok(req
+                .into_response(rate_limited(retry_after))
+                .map_into_right_body());
+        }
+        return Ok(next.call(req).await?.map_into_left_body());
+    };
+
+    let client = match access.client(&app_state.database, &key) {
+        Ok(Some(client)) => client,
+        Ok(None) => {
+            // Guessing keys costs a lookup each, so it counts against the IP
+            let response = match limit_ip() {
+                Ok(()) => rejected(StatusCode::UNAUTHORIZED, "Invalid or revoked API key"),
+                Err(retry_after) => rate_limited(retry_after),
+            };
+            return Ok(req.into_response(response).map_into_right_body());
+        }
+        Err(e) => {
+            error!("Failed to check API key: {:#}", e);
+            let response = rejected(StatusCode::INTERNAL_SERVER_ERROR, "Failed to check API key");
+            return Ok(req.into_response(response).map_into_right_body());
+        }
+    };
+
+    let Some(required) = required_scope(req.method().as_str(), routed_pattern(&req).as_deref())
+    else {
+        let response = rejected(StatusCode::NOT_FOUND, "Unknown route");
+        return Ok(req.into_response(response).map_into_right_body());
+    };
+    if !client.allows(required) {
+        let response = rejected(
+            StatusCode::FORBIDDEN,
+            &format!("Requires the {} scope", required.as_str()),
+        );
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    if !client.allows(ApiScope::Admin)
+        && let Some(rate) = access.limits.key_rate(client.rate_limit_per_min)
+        && let Err(retry_after) = access
+            .limiter
+            .check(&format!("key:{}", client.key_id), rate)
+    {
+        debug!(
+            "⏳ Rate limited API key {} on {}",
+            client.key_id,
+            req.path()
+        );
+        return Ok(req
+            .into_response(rate_limited(retry_after))
+            .map_into_right_body());
+    }
+
+    req.extensions_mut().insert(client);
+    Ok(next.call(req).await?.map_into_left_body())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::test_database;
+    use serial_test::serial;
+
+    #[test]
+    fn test_required_scope_per_route() {
+        assert_eq!(
+            required_scope("POST", Some("/api/v1/bridge/initiate")),
+            Some(ApiScope::CreateIntent)
+        );
+        assert_eq!(
+            required_scope("POST", Some("/api/intents/{intent_id}/reveal")),
+            Some(ApiScope::CreateIntent)
+        );
+        assert_eq!(
+            required_scope("GET", Some("/api/v1/intents/{intent_id}/reveal")),
+            Some(ApiScope::Read)
+        );
+        // No matched route, nothing to grant
+        assert_eq!(required_scope("GET", None), None);
+
+        assert!(is_exempt("/api/v1/indexer/event"));
+        assert!(is_exempt("/api/health"));
+        assert!(is_exempt("/api/v1/admin/jobs"));
+        assert!(!is_exempt("/api/v1/bridge/intents"));
+
+        let client = ApiClient {
+            key_id: 1,
+            name: "wallet".to_string(),
+            scopes: vec![ApiScope::Read],
+            rate_limit_per_min: None,
+        };
+        assert!(client.allows(ApiScope::Read));
+        assert!(!client.allows(ApiScope::CreateIntent));
+        let service = ApiClient {
+            scopes: vec![ApiScope::Admin],
+            ..client
+        };
+        assert!(service.allows(ApiScope::CreateIntent));
+        assert!(ApiScope::parse("write").is_err());
+    }
+
+    #[actix_web::test]
+    async fn test_encoded_paths_resolve_to_their_route() {
+        use actix_web::{App, middleware::from_fn, post, test};
+
+        #[post("/bridge/initiate")]
+        async fn initiate() -> HttpResponse {
+            HttpResponse::Ok().finish()
+        }
+        #[post("/indexer/event")]
+        async fn indexer() -> HttpResponse {
+            HttpResponse::Ok().finish()
+        }
+
+        // Answers with what `public_access` would decide for the request
+        async fn probe<B: MessageBody + 'static>(
+            req: ServiceRequest,
+            _: Next<B>,
+        ) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+            let decision = if is_exempt(routed_path(&req)) {
+                "exempt".to_string()
+            } else {
+                required_scope(req.method().as_str(), routed_pattern(&req).as_deref())
+                    .map_or("unknown", |scope| scope.as_str())
+                    .to_string()
+            };
+            let response = HttpResponse::Ok().body(decision);
+            Ok(req.into_response(response).map_into_right_body())
+        }
+
+        let app = test::init_service(
+            App::new().service(
+                web::scope("/api/v1")
+                    .wrap(from_fn(probe))
+                    .service(initiate)
+                    .service(indexer),
+            ),
+        )
+        .await;
+        for (uri, expected) in [
+            ("/api/v1/bridge/initiate", "create_intent"),
+            ("/api/v1/bridge/%69nitiate", "create_intent"),
+            ("/api/v1/%62ridge/initiate", "create_intent"),
+            ("/api/v1/%69ndexer/event", "exempt"),
+            ("/api/v1/%61dmin/api-keys", "exempt"),
+            ("/api/v1/bridge/unknown", "unknown"),
+        ] {
+            let response =
+                test::call_service(&app, test::TestRequest::post().uri(uri).to_request()).await;
+            assert_eq!(test::read_body(response).await, expected, "{}", uri);
+        }
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_issued_api_keys_authenticate_until_revoked() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let issued = issue_api_key(
+            &database,
+            "wallet",
+            &[ApiScope::Read, ApiScope::CreateIntent],
+            Some(30),
+            "alice",
+        )?;
+        assert!(issued.key.starts_with("spk_"));
+        assert!(issue_api_key(&database, "wallet", &[], None, "alice").is_err());
+        assert!(issue_api_key(&database, "wallet", &[ApiScope::Read], Some(0), "alice").is_err());
+
+        let access = PublicAccess::default();
+        let client = access.client(&database, &issued.key)?.expect("valid key");
+        assert_eq!(client.name, "wallet");
+        assert_eq!(client.scopes, vec![ApiScope::Read, ApiScope::CreateIntent]);
+        assert_eq!(client.rate_limit_per_min, Some(30));
+        assert!(access.client(&database, "spk_guess")?.is_none());
+
+        let stored = database
+            .list_api_keys()?
+            .into_iter()
+            .find(|key| key.id == issued.id)
+            .expect("listed");
+        assert!(stored.last_used_at.is_some());
+        assert!(!serde_json::to_string(&stored)?.contains(&stored.key_hash));
+
+        assert!(database.revoke_api_key(issued.id)?);
+        assert!(!database.revoke_api_key(issued.id)?);
+        // Still trusted from the cache until forgotten
+        assert!(access.client(&database, &issued.key)?.is_some());
+        access.forget_key(issued.id);
+        assert!(access.client(&database, &issued.key)?.is_none());
+
+        Ok(())
+    }
+}
@@ -1,16 +1,32 @@
-use actix_web::{HttpRequest, HttpResponse, web};
+use actix_web::{
+    HttpRequest, HttpResponse,
+    body::{BoxBody, MessageBody, to_bytes},
+    web,
+};
 use chrono::Utc;
 use hmac::{Hmac, Mac};
-use serde_json::json;
+use mantle_core::{
+    chain::Chain,
+    hex_types::{AmountDec, H256Hex},
+    parse::ParseError,
+};
+use serde_json::{Value, json};
 use sha2::Sha256;
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
 use tracing::{error, info, warn};
 
 use crate::{
     AppState,
     api::model::{IndexerEventRequest, IndexerEventResponse},
-    models::model::{Intent, IntentStatus},
+    models::model::{BridgeEventType, Intent, IntentStatus},
+    relay_coordinator::{debug_capture::CaptureKind, ingestion_queue::IngestionSource},
 };
 
+/// How often the ingestion consumer exports its lag.
+const INGESTION_LAG_REPORT_INTERVAL: Duration = Duration::from_secs(15);
+const INGESTION_PURGE_INTERVAL: Duration = Duration::from_secs(3600);
+
 type HmacSha256 = Hmac<Sha256>;
 
 // ============================================================================
@@ -126,23 +142,22 @@ pub fn validate_hmac(
 // }
 
 fn get_chain_id(chain: &str) -> u32 {
-    match chain {
-        "ethereum" => 11155111,
-        "mantle" => 5003,
-        _ => 0,
-    }
+    Chain::parse(chain).map_or(0, |chain| chain.id())
 }
 
 fn store_raw_event(
     app_state: &web::Data<AppState>,
-    event_type: &str,
+    event_type: BridgeEventType,
     request: &IndexerEventRequest,
     intent_id: Option<&str>,
 ) -> Result<(), String> {
     let chain_id = get_chain_id(&request.chain);
     let event_id = format!(
         "{}_{}_{}_{}",
-        event_type, request.chain, request.transaction_hash, request.log_index,
+        event_type.as_str(),
+        request.chain,
+        request.transaction_hash,
+        request.log_index,
     );
 
     app_state
@@ -167,10 +182,248 @@ fn store_raw_event(
         })
 }
 
+// ============================================================================
+// DEBUG CAPTURE
+// ============================================================================
+
+/// Fields of a bridge request that never go into a capture, even encrypted.
+const REDACTED_BRIDGE_FIELDS: [&str; 4] = [
+    "encrypted_secret",
+    "encrypted_nullifier",
+    "claim_auth",
+    "encrypted_note",
+];
+
+/// The bridge request body as JSON with its secrets replaced; `null` when it
+/// does not parse.
+pub fn redacted_bridge_request(body: &[u8]) -> Value {
+    let mut request: Value = serde_json::from_slice(body).unwrap_or(Value::Null);
+    if let Some(fields) = request.as_object_mut() {
+        for field in REDACTED_BRIDGE_FIELDS {
+            if let Some(value) = fields.get_mut(field).filter(|value| !value.is_null()) {
+                *value = json!("[redacted]");
+            }
+        }
+    }
+    request
+}
+
+/// Record `request` and the response about to be sent when `intent_id` is
+/// being captured. Other responses pass through untouched.
+pub async fn capture_exchange(
+    app_state: &web::Data<AppState>,
+    intent_id: &str,
+    component: &str,
+    request: Value,
+    response: HttpResponse,
+) -> HttpResponse {
+    let capture = &app_state.bridge_coordinator.debug_capture;
+    if intent_id.is_empty() || !capture.is_active(intent_id) {
+        return response;
+    }
+    capture.record(intent_id, component, CaptureKind::Request, request);
+
+    let (response, body) = response.into_parts();
+    let Ok(body) = to_bytes(body).await else {
+        return response.set_body(BoxBody::new(()));
+    };
+    capture.record(
+        intent_id,
+        component,
+        CaptureKind::Response,
+        json!({
+            "status": response.status().as_u16(),
+            "body": serde_json::from_slice::<Value>(&body)
+                .unwrap_or_else(|_| json!(String::from_utf8_lossy(&body))),
+        }),
+    );
+    response.set_body(BoxBody::new(body))
+}
+
 // ============================================================================
 // EVENT HANDLERS
 // ============================================================================
 
+/// Apply an indexer event, whether posted by the indexer or read from the
+/// relayer's own log subscription, then wake the worker it unblocks.
+/// The event type an indexer event name maps to. Types the relayer only
+/// records itself are refused like unknown ones when dispatched.
+pub fn indexer_event_type(name: &str) -> anyhow::Result<BridgeEventType> {
+    match name {
+        // The indexer reports each root sync flavour separately; they share one event type.
+        "root_synced" | "commitment_root_synced" | "fill_root_synced" => {
+            Ok(BridgeEventType::RootSync)
+        }
+        other => other.parse::<BridgeEventType>(),
+    }
+}
+
+pub async fn dispatch_indexer_event(
+    app_state: &web::Data<AppState>,
+    request: &IndexerEventRequest,
+) -> HttpResponse {
+    let event_type = indexer_event_type(&request.event_type);
+
+    let response = match event_type {
+        Ok(BridgeEventType::IntentCreated) => handle_intent_created_event(app_state, request).await,
+        Ok(BridgeEventType::IntentFilled) => handle_intent_filled_event(app_state, request).await,
+        Ok(BridgeEventType::IntentRegistered) => {
+            handle_intent_registered_event(app_state, request).await
+        }
+        Ok(BridgeEventType::IntentSettled) => handle_intent_settled_event(app_state, request).await,
+        Ok(BridgeEventType::IntentRefunded) => {
+            handle_intent_refunded_event(app_state, request).await
+        }
+        Ok(BridgeEventType::WithdrawalClaimed) => {
+            handle_withdrawal_claimed_event(app_state, request).await
+        }
+        Ok(BridgeEventType::RootSync) => handle_root_synced_event(app_state, request).await,
+
+        // Recorded by the relayer itself, never accepted from the indexer.
+        Ok(BridgeEventType::NullifierUsed) | Err(_) => {
+            warn!("Unknown event type: {}", request.event_type);
+            return HttpResponse::BadRequest().json(IndexerEventResponse {
+                success: false,
+                message: format!("Unknown event type: {}", request.event_type),
+                error: None,
+            });
+        }
+    };
+
+    if let Ok(event_type) = event_type
+        && response.status().is_success()
+    {
+        app_state.bridge_coordinator.wakeups.wake(event_type);
+    }
+    response
+}
+
+/// Store the events streamed by the chains' log listeners. While the
+/// database is slow the channel fills up and the listeners stop reading
+/// their sockets, instead of buffering without bound.
+pub async fn enqueue_chain_events(
+    app_state: web::Data<AppState>,
+    mut events: mpsc::Receiver<IndexerEventRequest>,
+) {
+    let queue = &app_state.bridge_coordinator.ingestion;
+    while let Some(request) = events.recv().await {
+        if let Err(e) = queue.enqueue(IngestionSource::LogListener, &request) {
+            warn!(
+                "⚠️ {} from {} on {} not stored ({:#}), leaving it to the indexer",
+                request.event_type, request.transaction_hash, request.chain, e
+            );
+        }
+    }
+}
+
+/// Apply stored chain events in order of receipt, each at least once.
+/// Events the handlers reject as malformed are dead-lettered; other
+/// failures are retried with backoff.
+pub async fn run_ingestion_consumer(app_state: web::Data<AppState>) {
+    let coordinator = app_state.bridge_coordinator.clone();
+    let queue = &coordinator.ingestion;
+    info!(
+        "📥 Ingestion consumer started (batches of {})",
+        queue.policy.batch_size
+    );
+
+    let mut last_lag_report: Option<Instant> = None;
+    let mut last_purge = Instant::now();
+    loop {
+        let claimed = queue.claim().unwrap_or_else(|e| {
+            error!("❌ Failed to claim ingestion events: {:#}", e);
+            Vec::new()
+        });
+        let drained = (claimed.len() as i64) < queue.policy.batch_size;
+
+        for (id, request) in claimed {
+            let intent_id = request
+                .event_data
+                .get("intentId")
+                .and_then(|id| id.as_str())
+                .unwrap_or_default()
+                .to_string();
+            let response = dispatch_indexer_event(&app_state, &request).await;
+            let status = response.status();
+            // Read in place: the response cannot be held across an await here
+            let body = response.into_body().try_into_bytes().unwrap_or_default();
+            coordinator.debug_capture.record(
+                &intent_id,
+                "ingestion",
+                CaptureKind::Decision,
+                json!({
+                    "queue_id": id,
+                    "event_type": request.event_type,
+                    "status": status.as_u16(),
+                    "response": serde_json::from_slice::<Value>(&body).unwrap_or(Value::Null),
+                }),
+            );
+
+            let recorded = if status.is_success() {
+                queue.delivered(id)
+            } else {
+                queue.failed(
+                    id,
+                    &format!("{}: {}", status, String::from_utf8_lossy(&body)),
+                    status.is_client_error(),
+                )
+            };
+            if let Err(e) = recorded {
+                error!(
+                    "❌ Failed to record delivery of ingestion event {}: {:#}",
+                    id, e
+                );
+            }
+        }
+
+        if last_lag_report.is_none_or(|at| at.elapsed() >= INGESTION_LAG_REPORT_INTERVAL) {
+            last_lag_report = Some(Instant::now());
+            match queue.lag() {
+                Ok(lag) => {
+                    for source in [IngestionSource::Indexer, IngestionSource::LogListener] {
+                        let lag = lag
+                            .by_source
+                            .get(source.as_str())
+                            .cloned()
+                            .unwrap_or_default();
+                        coordinator.prometheus.set_ingestion_lag(
+                            source.as_str(),
+                            lag.pending,
+                            lag.dead,
+                            lag.lag_secs,
+                        );
+                    }
+                }
+                Err(e) => error!("❌ Failed to measure ingestion lag: {:#}", e),
+            }
+        }
+        if last_purge.elapsed() >= INGESTION_PURGE_INTERVAL {
+            last_purge = Instant::now();
+            match queue.purge() {
+                Ok(0) => {}
+                Ok(purged) => info!("📥 Purged {} applied ingestion events", purged),
+                Err(e) => error!("❌ Failed to purge ingestion events: {:#}", e),
+            }
+        }
+
+        if !drained {
+            if coordinator.shutdown.is_triggered() {
+                break;
+            }
+            continue;
+        }
+        if coordinator
+            .shutdown
+            .sleep_or_wake(queue.policy.poll_interval, &queue.wakeup)
+            .await
+        {
+            break;
+        }
+    }
+
+    info!("📥 Ingestion consumer stopped");
+}
+
 pub async fn handle_intent_created_event(
     app_state: &web::Data<AppState>,
     request: &IndexerEventRequest,
@@ -248,16 +501,36 @@ pub async fn handle_intent_created_event(
         request.block_number, request.log_index
     );
 
+    let parsed = (|| {
+        Ok::<_, ParseError>((
+            H256Hex::parse("intentId", intent_id)?,
+            H256Hex::parse("commitment", commitment)?,
+            AmountDec::parse("sourceAmount", source_amount)?,
+            AmountDec::parse("destAmount", dest_amount)?,
+        ))
+    })();
+    let (id, source_commitment, amount, dest_amount) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            error!("Malformed intent_created event: {}", e);
+            return HttpResponse::BadRequest().json(IndexerEventResponse {
+                success: false,
+                message: "Malformed event_data".to_string(),
+                error: Some(e.to_string()),
+            });
+        }
+    };
+
     let intent = Intent {
-        id: intent_id.to_string(),
+        id,
         user_address: refund_address.to_string(),
         source_chain: request.chain.clone(),
         dest_chain: dest_chain.to_string(),
         source_token: source_token.to_string(),
         dest_token: dest_token.to_string(),
-        amount: source_amount.to_string(),
-        dest_amount: dest_amount.to_string(),
-        source_commitment: Some(commitment.to_string()),
+        amount,
+        dest_amount,
+        source_commitment: Some(source_commitment),
         dest_fill_txid: None,
         dest_registration_txid: None,
         source_complete_txid: None,
@@ -282,7 +555,38 @@ pub async fn handle_intent_created_event(
 
     info!("✅ Intent {} upserted", intent_id);
 
-    match store_raw_event(app_state, "intent_created", request, Some(intent_id)) {
+    app_state.compliance.screen_in_background(intent.clone());
+
+    if app_state.deposit_check.enabled {
+        let coordinator = app_state.bridge_coordinator.clone();
+        let (intent, tx_hash) = (intent.clone(), request.transaction_hash.clone());
+        tokio::spawn(async move {
+            if let Err(e) = coordinator.check_deposit(&intent, &tx_hash).await {
+                error!("❌ Deposit check failed for {}: {}", intent.id, e);
+            }
+        });
+    }
+
+    match app_state.database.quarantine_if_duplicate(
+        intent_id,
+        commitment,
+        &request.chain,
+        app_state.commitment_uniqueness,
+    ) {
+        Ok(Some(conflicting_id)) => warn!(
+            "🚧 Intent {} quarantined (duplicate commitment of {})",
+            intent_id, conflicting_id
+        ),
+        Ok(None) => {}
+        Err(e) => error!("Failed to check commitment uniqueness: {}", e),
+    }
+
+    match store_raw_event(
+        app_state,
+        BridgeEventType::IntentCreated,
+        request,
+        Some(intent_id),
+    ) {
         Ok(()) => info!("✅ Raw event stored"),
         Err(e) if e == "duplicate" => info!("Event already stored (idempotent)"),
         Err(e) => {
@@ -332,8 +636,21 @@ pub async fn handle_intent_filled_event(
     };
 
     // STEP 1: Store raw event FIRST (idempotency)
-    match store_raw_event(app_state, "intent_filled", request, Some(intent_id)) {
-        Ok(()) => info!("✅ Raw fill event stored"),
+    match store_raw_event(
+        app_state,
+        BridgeEventType::IntentFilled,
+        request,
+        Some(intent_id),
+    ) {
+        Ok(()) => {
+            info!("✅ Raw fill event stored");
+            if let Ok(chain) = Chain::parse(&request.chain) {
+                app_state
+                    .bridge_coordinator
+                    .prometheus
+                    .fill(chain.name(), true);
+            }
+        }
         Err(e) if e == "duplicate" => {
             info!("Fill event already stored, but continuing to rebuild tree");
             // DON'T RETURN - continue to rebuild tree
@@ -361,6 +678,9 @@ pub async fn handle_intent_filled_event(
             } else {
                 info!("✅ Intent {} marked as filled", intent_id);
             }
+
+            // Reuses the creation-time screening; covers intents that arrived via sync
+            app_state.compliance.screen_in_background(intent);
         }
         Ok(None) => {
             warn!("Intent {} not found in intents table", intent_id);
@@ -370,6 +690,13 @@ pub async fn handle_intent_filled_event(
         }
     }
 
+    if let Err(e) = app_state
+        .bridge_coordinator
+        .close_reservation_on_fill(intent_id, solver)
+    {
+        error!("Failed to close reservation of {}: {}", intent_id, e);
+    }
+
     info!("🔄 Force rebuilding fill tree for chain {}", request.chain);
 
     let rebuild_result = match request.chain.as_str() {
@@ -415,7 +742,12 @@ pub async fn handle_intent_settled_event(
     };
 
     // STEP 1: Store raw event FIRST
-    match store_raw_event(app_state, "intent_settled", request, Some(intent_id)) {
+    match store_raw_event(
+        app_state,
+        BridgeEventType::IntentSettled,
+        request,
+        Some(intent_id),
+    ) {
         Ok(()) => info!("✅ Raw settled event stored"),
         Err(e) if e == "duplicate" => {
             return HttpResponse::Ok().json(IndexerEventResponse {
@@ -490,7 +822,12 @@ pub async fn handle_intent_refunded_event(
     };
 
     // STEP 1: Store raw event FIRST
-    match store_raw_event(app_state, "intent_refunded", request, Some(intent_id)) {
+    match store_raw_event(
+        app_state,
+        BridgeEventType::IntentRefunded,
+        request,
+        Some(intent_id),
+    ) {
         Ok(()) => info!("✅ Raw refunded event stored"),
         Err(e) if e == "duplicate" => {
             return HttpResponse::Ok().json(IndexerEventResponse {
@@ -575,7 +912,12 @@ pub async fn handle_withdrawal_claimed_event(
     };
 
     // STEP 1: Store raw event FIRST
-    match store_raw_event(app_state, "withdrawal_claimed", request, Some(intent_id)) {
+    match store_raw_event(
+        app_state,
+        BridgeEventType::WithdrawalClaimed,
+        request,
+        Some(intent_id),
+    ) {
         Ok(()) => info!("✅ Raw withdrawal event stored"),
         Err(e) if e == "duplicate" => {
             return HttpResponse::Ok().json(IndexerEventResponse {
@@ -679,7 +1021,7 @@ pub async fn handle_root_synced_event(
     let sync_type = format!("{}_{}", request.chain, chain_id);
 
     // STEP 1: Store raw event FIRST
-    match store_raw_event(app_state, "root_sync", request, None) {
+    match store_raw_event(app_state, BridgeEventType::RootSync, request, None) {
         Ok(()) => info!("✅ Raw root sync event stored"),
         Err(e) if e == "duplicate" => {
             return HttpResponse::Ok().json(IndexerEventResponse {
@@ -699,9 +1041,10 @@ pub async fn handle_root_synced_event(
     }
 
     // STEP 2: Insert into root_syncs table
-    if let Err(e) = app_state
-        .database
-        .insert_root_sync(&sync_type, root, &request.transaction_hash)
+    if let Err(e) =
+        app_state
+            .database
+            .insert_root_sync(&sync_type, root, &request.transaction_hash, None)
     {
         error!("Failed to insert root sync: {}", e);
     } else {
@@ -733,7 +1076,12 @@ pub async fn handle_intent_registered_event(
     };
 
     // STEP 1: Store raw event FIRST
-    match store_raw_event(app_state, "intent_registered", request, Some(intent_id)) {
+    match store_raw_event(
+        app_state,
+        BridgeEventType::IntentRegistered,
+        request,
+        Some(intent_id),
+    ) {
         Ok(()) => info!("✅ Raw registered event stored"),
         Err(e) if e == "duplicate" => {
             return HttpResponse::Ok().json(IndexerEventResponse {
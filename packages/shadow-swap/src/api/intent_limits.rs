@@ -0,0 +1,88 @@
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::database::database::Database;
+
+/// Cap on simultaneous non-terminal intents per `user_address`, checked when
+/// an intent is initiated. An admin override in `user_intent_limits` replaces
+/// the default for one address; a limit of 0 means unlimited.
+#[derive(Debug, Clone, Copy)]
+pub struct UserIntentLimits {
+    pub default_max_active: i64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IntentLimitExceeded {
+    pub user_address: String,
+    pub active_intents: i64,
+    pub limit: i64,
+    pub overridden: bool,
+}
+
+impl UserIntentLimits {
+    /// Reads `MAX_ACTIVE_INTENTS_PER_USER` (default 20, 0 disables).
+    pub fn from_env() -> Self {
+        Self {
+            default_max_active: std::env::var("MAX_ACTIVE_INTENTS_PER_USER")
+                .unwrap_or_else(|_| "20".to_string())
+                .parse()
+                .unwrap_or(20),
+        }
+    }
+
+    /// `None` when the address has no cap.
+    fn effective_limit(&self, override_max_active: Option<i32>) -> Option<i64> {
+        let limit = override_max_active
+            .map(i64::from)
+            .unwrap_or(self.default_max_active);
+
+        (limit > 0).then_some(limit)
+    }
+
+    pub fn check(
+        &self,
+        database: &Database,
+        user_address: &str,
+    ) -> Result<Option<IntentLimitExceeded>> {
+        let override_max_active = database
+            .get_user_intent_limit(user_address)?
+            .map(|limit| limit.max_active);
+
+        let Some(limit) = self.effective_limit(override_max_active) else {
+            return Ok(None);
+        };
+
+        let active_intents = database.count_active_intents_for_user(user_address)?;
+        if active_intents < limit {
+            return Ok(None);
+        }
+
+        Ok(Some(IntentLimitExceeded {
+            user_address: user_address.to_lowercase(),
+            active_intents,
+            limit,
+            overridden: override_max_active.is_some(),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_override_replaces_default_and_zero_is_unlimited() {
+        let limits = UserIntentLimits {
+            default_max_active: 20,
+        };
+        assert_eq!(limits.effective_limit(None), Some(20));
+        assert_eq!(limits.effective_limit(Some(500)), Some(500));
+        assert_eq!(limits.effective_limit(Some(0)), None);
+
+        let disabled = UserIntentLimits {
+            default_max_active: 0,
+        };
+        assert_eq!(disabled.effective_limit(None), None);
+        assert_eq!(disabled.effective_limit(Some(5)), Some(5));
+    }
+}
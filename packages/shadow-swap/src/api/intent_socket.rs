@@ -0,0 +1,355 @@
+use std::{
+    collections::BTreeSet,
+    time::{Duration, Instant},
+};
+
+use actix_web::{HttpRequest, HttpResponse, get, web};
+use actix_ws::{Message, Session};
+use mantle_core::parse;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::{sync::broadcast::error::RecvError, time::interval};
+use tracing::{debug, warn};
+
+use crate::{AppState, api::model::IntentFeedQuery, relay_coordinator::intent_feed::IntentUpdate};
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(30);
+/// Sockets that answer no ping for this long are closed.
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(90);
+const MAX_FRAME_SIZE: usize = 16 * 1024;
+
+/// Sent by clients to change what the socket follows.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum ClientMessage {
+    Subscribe {
+        #[serde(default)]
+        intent_ids: Vec<String>,
+        #[serde(default)]
+        addresses: Vec<String>,
+    },
+    Unsubscribe {
+        #[serde(default)]
+        intent_ids: Vec<String>,
+        #[serde(default)]
+        addresses: Vec<String>,
+    },
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    IntentStatus(IntentUpdate),
+    /// The full set followed after a (un)subscribe.
+    Subscribed {
+        intent_ids: Vec<String>,
+        addresses: Vec<String>,
+    },
+    /// Updates were dropped because the socket fell behind; re-read the
+    /// followed intents over REST.
+    Lagged {
+        missed: u64,
+    },
+    Error {
+        message: String,
+    },
+}
+
+/// Intent ids and depositor addresses one socket follows, lowercased.
+#[derive(Debug, Default)]
+pub struct Subscription {
+    intent_ids: BTreeSet<String>,
+    addresses: BTreeSet<String>,
+}
+
+impl Subscription {
+    pub fn matches(&self, update: &IntentUpdate) -> bool {
+        self.intent_ids.contains(&update.intent_id.to_lowercase())
+            || self.addresses.contains(&update.user_address.to_lowercase())
+    }
+
+    /// Apply a (un)subscribe. Malformed ids or addresses, or going over
+    /// `max` entries, reject the whole message.
+    pub fn apply(&mut self, message: ClientMessage, max: usize) -> Result<(), String> {
+        match message {
+            ClientMessage::Subscribe {
+                intent_ids,
+                addresses,
+            } => {
+                let mut next_ids = self.intent_ids.clone();
+                for id in &intent_ids {
+                    parse::hex32("intent_id", id).map_err(|e| e.to_string())?;
+                    next_ids.insert(id.to_lowercase());
+                }
+                let mut next_addresses = self.addresses.clone();
+                for address in &addresses {
+                    parse::address("address", address).map_err(|e| e.to_string())?;
+                    next_addresses.insert(address.to_lowercase());
+                }
+
+                if next_ids.len() + next_addresses.len() > max {
+                    return Err(format!("At most {} subscriptions per connection", max));
+                }
+                self.intent_ids = next_ids;
+                self.addresses = next_addresses;
+            }
+            ClientMessage::Unsubscribe {
+                intent_ids,
+                addresses,
+            } => {
+                for id in intent_ids {
+                    self.intent_ids.remove(&id.to_lowercase());
+                }
+                for address in addresses {
+                    self.addresses.remove(&address.to_lowercase());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn confirmation(&self) -> ServerMessage {
+        ServerMessage::Subscribed {
+            intent_ids: self.intent_ids.iter().cloned().collect(),
+            addresses: self.addresses.iter().cloned().collect(),
+        }
+    }
+}
+
+fn split_list(list: &Option<String>) -> Vec<String> {
+    list.as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+async fn send(session: &mut Session, message: &ServerMessage) -> Result<(), actix_ws::Closed> {
+    let text = serde_json::to_string(message).unwrap_or_default();
+    session.text(text).await
+}
+
+/// Push channel for intent status transitions. Filters come from the query
+/// string and from `subscribe`/`unsubscribe` messages; a socket with no
+/// filters receives nothing.
+#[get("/ws/intents")]
+pub async fn intent_feed_socket(
+    req: HttpRequest,
+    body: web::Payload,
+    query: web::Query<IntentFeedQuery>,
+    app_state: web::Data<AppState>,
+) -> actix_web::Result<HttpResponse> {
+    let feed = app_state.intent_feed.clone();
+    if !feed.policy.enabled {
+        return Ok(HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Intent feed is disabled"
+        })));
+    }
+
+    let mut subscription = Subscription::default();
+    let initial = ClientMessage::Subscribe {
+        intent_ids: split_list(&query.intent_ids),
+        addresses: split_list(&query.addresses),
+    };
+    if let Err(message) = subscription.apply(initial, feed.policy.max_subscriptions) {
+        return Ok(HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": message
+        })));
+    }
+
+    let Some(slot) = feed.connect() else {
+        warn!(
+            "⚠️ Intent feed full ({} sockets), rejecting connection",
+            feed.policy.max_connections
+        );
+        return Ok(HttpResponse::ServiceUnavailable().json(json!({
+            "status": "error",
+            "message": "Too many intent feed connections"
+        })));
+    };
+
+    let (response, mut session, stream) = actix_ws::handle(&req, body)?;
+    let mut stream = stream.max_frame_size(MAX_FRAME_SIZE);
+    let mut updates = feed.subscribe();
+
+    actix_web::rt::spawn(async move {
+        let _slot = slot;
+        debug!("📡 Intent feed socket opened ({} open)", feed.connections());
+
+        let mut heartbeat = interval(HEARTBEAT_INTERVAL);
+        let mut last_pong = Instant::now();
+        if send(&mut session, &subscription.confirmation())
+            .await
+            .is_err()
+        {
+            return;
+        }
+
+        let close_reason = loop {
+            tokio::select! {
+                message = stream.recv() => match message {
+                    Some(Ok(Message::Text(text))) => {
+                        let reply = match serde_json::from_str::<ClientMessage>(&text) {
+                            Ok(message) => match subscription
+                                .apply(message, feed.policy.max_subscriptions)
+                            {
+                                Ok(()) => subscription.confirmation(),
+                                Err(message) => ServerMessage::Error { message },
+                            },
+                            Err(e) => ServerMessage::Error {
+                                message: format!("Invalid message: {}", e),
+                            },
+                        };
+                        if send(&mut session, &reply).await.is_err() {
+                            break None;
+                        }
+                    }
+                    Some(Ok(Message::Ping(bytes))) => {
+                        if session.pong(&bytes).await.is_err() {
+                            break None;
+                        }
+                    }
+                    Some(Ok(Message::Pong(_))) => last_pong = Instant::now(),
+                    Some(Ok(Message::Close(reason))) => break reason,
+                    Some(Ok(_)) => {}
+                    Some(Err(_)) | None => break None,
+                },
+                update = updates.recv() => match update {
+                    Ok(update) => {
+                        if subscription.matches(&update)
+                            && send(&mut session, &ServerMessage::IntentStatus(update))
+                                .await
+                                .is_err()
+                        {
+                            break None;
+                        }
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        if send(&mut session, &ServerMessage::Lagged { missed })
+                            .await
+                            .is_err()
+                        {
+                            break None;
+                        }
+                    }
+                    Err(RecvError::Closed) => break None,
+                },
+                _ = heartbeat.tick() => {
+                    if last_pong.elapsed() > CLIENT_TIMEOUT
+                        || session.ping(b"").await.is_err()
+                    {
+                        break None;
+                    }
+                }
+            }
+        };
+
+        let _ = session.close(close_reason).await;
+        debug!("📡 Intent feed socket closed");
+    });
+
+    Ok(response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+
+    const INTENT_ID: &str = "0xAB00000000000000000000000000000000000000000000000000000000000001";
+    const USER: &str = "0x1111111111111111111111111111111111111111";
+
+    fn update(intent_id: &str, user_address: &str) -> IntentUpdate {
+        IntentUpdate {
+            intent_id: intent_id.to_string(),
+            user_address: user_address.to_string(),
+            status: "filled".to_string(),
+            previous_status: Some("committed".to_string()),
+            source_chain: "ethereum".to_string(),
+            dest_chain: "mantle".to_string(),
+            updated_at: Utc::now(),
+            encrypted_note: None,
+        }
+    }
+
+    #[test]
+    fn test_subscription_filters_by_intent_and_address() {
+        let mut subscription = Subscription::default();
+        let other_id = format!("0x{}", "cd".repeat(32));
+        let other_user = format!("0x{}", "22".repeat(20));
+        assert!(!subscription.matches(&update(INTENT_ID, USER)));
+
+        let message: ClientMessage = serde_json::from_value(json!({
+            "action": "subscribe",
+            "intent_ids": [INTENT_ID],
+        }))
+        .unwrap();
+        subscription.apply(message, 2).unwrap();
+        assert!(subscription.matches(&update(&INTENT_ID.to_lowercase(), &other_user)));
+        assert!(!subscription.matches(&update(&other_id, &other_user)));
+
+        subscription
+            .apply(
+                ClientMessage::Subscribe {
+                    intent_ids: vec![],
+                    addresses: vec![USER.to_string()],
+                },
+                2,
+            )
+            .unwrap();
+        assert!(subscription.matches(&update(&other_id, USER)));
+
+        // Over the cap or malformed: rejected as a whole, nothing changes
+        assert!(
+            subscription
+                .apply(
+                    ClientMessage::Subscribe {
+                        intent_ids: vec![other_id.clone()],
+                        addresses: vec![],
+                    },
+                    2,
+                )
+                .is_err()
+        );
+        assert!(
+            subscription
+                .apply(
+                    ClientMessage::Subscribe {
+                        intent_ids: vec![],
+                        addresses: vec!["0x12".to_string()],
+                    },
+                    10,
+                )
+                .is_err()
+        );
+        let confirmed = serde_json::to_value(subscription.confirmation()).unwrap();
+        assert_eq!(confirmed["type"], "subscribed");
+        assert_eq!(confirmed["intent_ids"], json!([INTENT_ID.to_lowercase()]));
+        assert_eq!(confirmed["addresses"], json!([USER]));
+
+        subscription
+            .apply(
+                ClientMessage::Unsubscribe {
+                    intent_ids: vec![INTENT_ID.to_string()],
+                    addresses: vec![],
+                },
+                2,
+            )
+            .unwrap();
+        assert!(!subscription.matches(&update(INTENT_ID, &other_user)));
+
+        let sent =
+            serde_json::to_value(ServerMessage::IntentStatus(update(INTENT_ID, USER))).unwrap();
+        assert_eq!(sent["type"], "intent_status");
+        assert_eq!(sent["status"], "filled");
+        assert!(sent.get("user_address").is_none());
+        assert_eq!(
+            split_list(&Some(" 0x1, ,0x2".to_string())),
+            vec!["0x1", "0x2"]
+        );
+    }
+}
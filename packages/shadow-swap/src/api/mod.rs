@@ -1,3 +1,12 @@
+pub mod admin_auth;
+pub mod api_keys;
 pub mod helper;
+pub mod intent_limits;
+pub mod intent_socket;
 pub mod model;
+pub mod pagination;
+pub mod quote;
+pub mod rate_limit;
 pub mod routes;
+pub mod status_page;
+pub mod versioning;
@@ -1,9 +1,13 @@
 use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
+use mantle_core::{pricefeed::SourceHealth, token_registry::TokenProfile};
 use serde::{Deserialize, Serialize};
 
-use crate::database::model::BridgeStats;
+use crate::{
+    api::quote::QuoteSide,
+    database::model::{BridgeStats, DbBridgeEvent, DbChainTransaction},
+};
 
 // ============================================================================
 // BRIDGE REQUEST/RESPONSE MODELS
@@ -20,10 +24,189 @@ pub struct InitiateBridgeRequest {
     pub amount: String,
     pub commitment: String,
     pub refund_address: String,
-    pub encrypted_secret: String,
+    /// Omit to keep the secret and reveal it after the fill through
+    /// `POST /intents/{id}/reveal`.
+    #[serde(default)]
+    pub encrypted_secret: Option<String>,
     pub encrypted_nullifier: String,
     pub claim_auth: String,
     pub recipient: String,
+    /// Pay the claim to this address instead of `recipient`, e.g. an
+    /// exchange deposit address. `claim_auth` must then sign over it too.
+    #[serde(default)]
+    pub claim_delegate: Option<String>,
+    #[serde(default)]
+    pub sponsor_claim: Option<bool>,
+    /// Note for the recipient, encrypted client-side, as 0x hex. Passed on
+    /// as-is and never logged.
+    #[serde(default)]
+    pub encrypted_note: Option<String>,
+}
+
+/// Plaintext secret for a user-held intent, signed by the recipient.
+#[derive(Debug, Deserialize)]
+pub struct RevealSecretRequest {
+    pub secret: String,
+    pub signature: String,
+}
+
+/// `root` pins the proof to a past root; omitted means the current root.
+#[derive(Debug, Deserialize)]
+pub struct MerkleProofQuery {
+    pub leaf: String,
+    pub root: Option<String>,
+}
+
+/// Initial `/ws/intents` filters, comma-separated. More can be added over
+/// the socket.
+#[derive(Debug, Default, Deserialize)]
+pub struct IntentFeedQuery {
+    pub intent_ids: Option<String>,
+    pub addresses: Option<String>,
+}
+
+/// Admin override of one address's active intent cap. `max_active: null`
+/// removes the override, 0 lifts the cap entirely.
+#[derive(Debug, Deserialize)]
+pub struct UserIntentLimitRequest {
+    pub user_address: String,
+    pub max_active: Option<i32>,
+    pub note: Option<String>,
+}
+
+/// Admin listing of one token, added or replacing its current listing.
+/// Amounts are whole tokens.
+#[derive(Debug, Deserialize)]
+pub struct TokenListingRequest {
+    pub symbol: String,
+    #[serde(flatten)]
+    pub token: TokenProfile,
+}
+
+/// Admin decision on a screened intent; `action` is `allow`, `flag` or `block`.
+#[derive(Debug, Deserialize)]
+pub struct ComplianceOverrideRequest {
+    pub intent_id: String,
+    pub action: String,
+    pub note: String,
+}
+
+/// Operator note on an intent; with `status` set it also overrides the
+/// intent's status.
+#[derive(Debug, Deserialize)]
+pub struct IntentAnnotationRequest {
+    pub operator: String,
+    pub reason: String,
+    pub status: Option<String>,
+}
+
+/// Mark an intent for verbose capture; `ttl_secs` defaults to
+/// `DEBUG_CAPTURE_TTL_SECS`.
+#[derive(Debug, Deserialize)]
+pub struct DebugCaptureRequest {
+    pub intent_id: String,
+    pub operator: String,
+    pub reason: String,
+    pub ttl_secs: Option<u64>,
+}
+
+/// Register an outbound webhook endpoint. Without `filter` it receives
+/// every event.
+#[derive(Debug, Deserialize)]
+pub struct CreateWebhookRequest {
+    pub url: String,
+    pub description: Option<String>,
+    pub filter: Option<String>,
+}
+
+/// Replace an endpoint's filter; null or blank sends it every event.
+#[derive(Debug, Deserialize)]
+pub struct WebhookFilterRequest {
+    pub filter: Option<String>,
+}
+
+/// A sample event to check against an endpoint's filter; sent to the
+/// endpoint only with `deliver` and a match.
+#[derive(Debug, Deserialize)]
+pub struct TestWebhookRequest {
+    pub event: String,
+    #[serde(default)]
+    pub data: serde_json::Value,
+    #[serde(default)]
+    pub deliver: bool,
+}
+
+/// Rotate an endpoint's signing secret. The current secrets keep signing for
+/// `overlap_secs` (server default when absent); 0 revokes them at once.
+#[derive(Debug, Default, Deserialize)]
+pub struct RotateWebhookSecretRequest {
+    pub overlap_secs: Option<u64>,
+}
+
+/// Accept an upgraded contract implementation; `contract` is `intent_pool`
+/// or `settlement`.
+#[derive(Debug, Deserialize)]
+pub struct AcknowledgeUpgradeRequest {
+    pub chain: String,
+    pub contract: String,
+}
+
+/// Issue an admin key bound to `role` (`read_only`, `operator` or
+/// `superuser`).
+#[derive(Debug, Deserialize)]
+pub struct CreateAdminKeyRequest {
+    pub name: String,
+    pub role: String,
+}
+
+/// Issue a public API key with `scopes` (`read`, `create_intent`, `admin`).
+/// `rate_limit_per_min` overrides the default per-key rate.
+#[derive(Debug, Deserialize)]
+pub struct CreateApiKeyRequest {
+    pub name: String,
+    pub scopes: Vec<String>,
+    pub rate_limit_per_min: Option<i32>,
+}
+
+/// Pre-flight check before the intent is created on-chain.
+#[derive(Debug, Deserialize)]
+pub struct BridgeQuoteRequest {
+    pub token: String,
+    pub dest_chain: String,
+    pub amount: String,
+    /// Token received on `dest_chain`; defaults to `token`.
+    pub dest_token: Option<String>,
+    /// Whether `amount` is the source (`exact_in`) or destination (`exact_out`) amount.
+    #[serde(default)]
+    pub side: QuoteSide,
+}
+
+/// A third-party solver's fill, signed with its solver key; see
+/// `fill_report_message`.
+#[derive(Debug, Deserialize)]
+pub struct FillReportRequest {
+    pub intent_id: String,
+    /// Destination chain the fill landed on, by name or id.
+    pub chain: String,
+    pub transaction_hash: String,
+    pub solver: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ReservationRequest {
+    pub solver: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IntentQuoteRequest {
+    pub source_chain: String,
+    pub dest_chain: String,
+    pub token: String,
+    /// Base units of `token` deposited on `source_chain`.
+    pub amount: String,
+    /// Token received on `dest_chain`; defaults to `token`.
+    pub dest_token: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -51,13 +234,78 @@ pub struct IntentStatusResponse {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub has_privacy: bool,
+    pub claim_sponsored: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_note: Option<String>,
+    /// Newest first, capped; the rest is under `/intents/{id}/events`.
+    pub recent_events: Vec<IntentEventResponse>,
+    pub more_events: bool,
+    /// Newest first, capped; the rest is under `/intents/{id}/transactions`.
+    pub recent_transactions: Vec<IntentTransactionResponse>,
+    pub more_transactions: bool,
+}
+
+/// A chain event recorded for an intent. `id` orders events and is the
+/// pagination cursor.
+#[derive(Debug, Serialize)]
+pub struct IntentEventResponse {
+    pub id: i32,
+    pub event_type: String,
+    pub chain_id: i32,
+    pub block_number: i64,
+    pub log_index: Option<i32>,
+    pub transaction_hash: String,
+    pub timestamp: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+impl From<DbBridgeEvent> for IntentEventResponse {
+    fn from(event: DbBridgeEvent) -> Self {
+        Self {
+            id: event.id,
+            event_type: event.event_type,
+            chain_id: event.chain_id,
+            block_number: event.block_number,
+            log_index: event.log_index,
+            transaction_hash: event.transaction_hash,
+            timestamp: event.timestamp,
+            data: event.event_data,
+        }
+    }
+}
+
+/// A transaction the relayer sent for an intent. `id` orders transactions
+/// and is the pagination cursor.
+#[derive(Debug, Serialize)]
+pub struct IntentTransactionResponse {
+    pub id: i32,
+    pub tx_type: String,
+    pub chain_id: i32,
+    pub tx_hash: String,
+    pub status: String,
+    pub timestamp: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl From<DbChainTransaction> for IntentTransactionResponse {
+    fn from(tx: DbChainTransaction) -> Self {
+        Self {
+            id: tx.id,
+            tx_type: tx.tx_type,
+            chain_id: tx.chain_id,
+            tx_hash: tx.tx_hash,
+            status: tx.status,
+            timestamp: tx.timestamp,
+            created_at: tx.created_at,
+        }
+    }
 }
 
 // ============================================================================
 // INDEXER EVENT MODELS
 // ============================================================================
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct IndexerEventRequest {
     pub event_type: String,
     pub chain: String,
@@ -107,6 +355,8 @@ pub struct AllPricesResponse {
     pub status: String,
     pub timestamp: i64,
     pub prices: HashMap<String, f64>,
+    /// Each source's last good price and failures, per token.
+    pub sources: Vec<SourceHealth>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -0,0 +1,186 @@
+use serde::Deserialize;
+
+pub const DEFAULT_PAGE_SIZE: i64 = 50;
+pub const MAX_PAGE_SIZE: i64 = 200;
+/// Newest events and transactions embedded in `GET /bridge/intent/{id}`;
+/// the paginated endpoints serve the rest.
+pub const EMBEDDED_HISTORY_LIMIT: i64 = 10;
+
+/// `?limit=&cursor=&order=asc|desc&type=a,b` on an intent's events or
+/// transactions. `cursor` is the `next_cursor` of the previous page.
+#[derive(Debug, Default, Deserialize)]
+pub struct HistoryPageQuery {
+    pub limit: Option<i64>,
+    pub cursor: Option<i32>,
+    pub order: Option<String>,
+    #[serde(rename = "type")]
+    pub types: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoryPage {
+    pub limit: i64,
+    /// Row id the page continues after, in the page's order.
+    pub cursor: Option<i32>,
+    pub ascending: bool,
+    pub types: Vec<String>,
+}
+
+impl HistoryPage {
+    /// First page of the newest `limit` rows.
+    pub fn newest(limit: i64) -> Self {
+        Self {
+            limit,
+            cursor: None,
+            ascending: false,
+            types: Vec::new(),
+        }
+    }
+}
+
+impl HistoryPageQuery {
+    pub fn page(&self) -> Result<HistoryPage, String> {
+        let limit = self.limit.unwrap_or(DEFAULT_PAGE_SIZE);
+        if limit < 1 {
+            return Err("limit must be at least 1".to_string());
+        }
+
+        let ascending = match self.order.as_deref().map(str::to_lowercase).as_deref() {
+            None | Some("asc") => true,
+            Some("desc") => false,
+            Some(other) => return Err(format!("Invalid order: {} (use asc or desc)", other)),
+        };
+
+        let types = self
+            .types
+            .as_deref()
+            .unwrap_or_default()
+            .split(',')
+            .map(|t| t.trim().to_lowercase())
+            .filter(|t| !t.is_empty())
+            .collect();
+
+        Ok(HistoryPage {
+            limit: limit.min(MAX_PAGE_SIZE),
+            cursor: self.cursor,
+            ascending,
+            types,
+        })
+    }
+}
+
+/// Callers fetch one row past `limit`; its presence means another page
+/// follows, which continues after the last row kept.
+pub fn finish_page<T>(
+    mut rows: Vec<T>,
+    limit: i64,
+    id: impl Fn(&T) -> i32,
+) -> (Vec<T>, Option<i32>) {
+    if rows.len() as i64 <= limit {
+        return (rows, None);
+    }
+
+    rows.truncate(limit as usize);
+    let next_cursor = rows.last().map(id);
+    (rows, next_cursor)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        database::test_support::{IntentFactory, random_hash, test_database},
+        models::model::BridgeEventType,
+    };
+    use serial_test::serial;
+
+    #[test]
+    fn test_page_defaults_and_caps() {
+        let page = HistoryPageQuery::default().page().unwrap();
+        assert_eq!(page.limit, DEFAULT_PAGE_SIZE);
+        assert!(page.ascending);
+        assert!(page.types.is_empty());
+
+        let query = HistoryPageQuery {
+            limit: Some(10_000),
+            order: Some("DESC".to_string()),
+            types: Some("intent_filled, ,Intent_Registered".to_string()),
+            ..Default::default()
+        };
+        let page = query.page().unwrap();
+        assert_eq!(page.limit, MAX_PAGE_SIZE);
+        assert!(!page.ascending);
+        assert_eq!(page.types, vec!["intent_filled", "intent_registered"]);
+    }
+
+    #[test]
+    fn test_page_rejects_bad_limit_and_order() {
+        let zero = HistoryPageQuery {
+            limit: Some(0),
+            ..Default::default()
+        };
+        assert!(zero.page().is_err());
+
+        let sideways = HistoryPageQuery {
+            order: Some("newest".to_string()),
+            ..Default::default()
+        };
+        assert!(sideways.page().is_err());
+    }
+
+    #[test]
+    fn test_finish_page_sets_cursor_only_when_rows_remain() {
+        let (rows, next) = finish_page(vec![1, 2, 3], 3, |id| *id);
+        assert_eq!(rows, vec![1, 2, 3]);
+        assert_eq!(next, None);
+
+        let (rows, next) = finish_page(vec![9, 7, 4, 2], 3, |id| *id);
+        assert_eq!(rows, vec![9, 7, 4]);
+        assert_eq!(next, Some(4));
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_event_pages_follow_the_cursor() -> anyhow::Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+        let intent = IntentFactory::filled().insert(&database)?;
+        let event_types = [
+            BridgeEventType::IntentRegistered,
+            BridgeEventType::IntentFilled,
+            BridgeEventType::IntentRegistered,
+        ];
+        for (block, event_type) in event_types.into_iter().enumerate() {
+            database.store_bridge_event(
+                &random_hash(),
+                Some(&intent.id),
+                event_type,
+                serde_json::json!({}),
+                5003,
+                block as i64,
+                Some(0),
+                &random_hash(),
+            )?;
+        }
+
+        let first = database.list_intent_bridge_events(&intent.id, &[], None, true, 3)?;
+        let (first, cursor) = finish_page(first, 2, |event| event.id);
+        assert_eq!(first.len(), 2);
+        assert_eq!(cursor, Some(first[1].id));
+
+        let rest = database.list_intent_bridge_events(&intent.id, &[], cursor, true, 3)?;
+        let (rest, cursor) = finish_page(rest, 2, |event| event.id);
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].block_number, 2);
+        assert_eq!(cursor, None);
+
+        let registered = vec!["intent_registered".to_string()];
+        let newest =
+            database.list_intent_bridge_events(&intent.id, &registered, None, false, 10)?;
+        let blocks: Vec<i64> = newest.iter().map(|event| event.block_number).collect();
+        assert_eq!(blocks, vec![2, 0]);
+
+        Ok(())
+    }
+}
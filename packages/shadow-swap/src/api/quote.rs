@@ -0,0 +1,278 @@
+use anyhow::{Context, Result, bail};
+use ethers::types::U256;
+use mantle_core::{
+    amount::{Rounding, convert_at_inverse_rate, convert_at_rate, rate_to_fixed},
+    token::TokenType,
+};
+use serde::{Deserialize, Serialize};
+
+/// Which side of a quote the caller fixes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum QuoteSide {
+    /// `amount` is what the user pays in; the destination amount is derived.
+    #[default]
+    ExactIn,
+    /// `amount` is what the user wants out; the source amount is derived.
+    ExactOut,
+}
+
+/// The rounding a quote applied to its derived amount.
+#[derive(Debug, Clone, Serialize)]
+pub struct QuoteRounding {
+    pub mode: Rounding,
+    /// `dest_amount` or `source_amount`, whichever was computed.
+    pub applied_to: &'static str,
+    /// The exact result fell between two base units and was rounded.
+    pub inexact: bool,
+}
+
+/// Amounts on both sides of a quote, in each token's own base units.
+/// Rounding always favours the pool: what the user receives is floored and
+/// what the user pays is ceiled, so a quote never promises a fraction of a
+/// base unit that cannot be delivered.
+pub fn quote_amounts(
+    side: QuoteSide,
+    amount: U256,
+    source: TokenType,
+    dest: TokenType,
+    rate: f64,
+) -> Result<(U256, U256, QuoteRounding)> {
+    let fixed = rate_to_fixed(rate)?;
+    let (from, to) = (source.decimals(), dest.decimals());
+
+    Ok(match side {
+        QuoteSide::ExactIn => {
+            let (dest_amount, inexact) = convert_at_rate(amount, from, to, fixed, Rounding::Floor)?;
+            let rounding = QuoteRounding {
+                mode: Rounding::Floor,
+                applied_to: "dest_amount",
+                inexact,
+            };
+            (amount, dest_amount, rounding)
+        }
+        QuoteSide::ExactOut => {
+            let (source_amount, inexact) =
+                convert_at_inverse_rate(amount, from, to, fixed, Rounding::Ceil)?;
+            let rounding = QuoteRounding {
+                mode: Rounding::Ceil,
+                applied_to: "source_amount",
+                inexact,
+            };
+            (source_amount, amount, rounding)
+        }
+    })
+}
+
+/// Gas of the destination fill the solver sends, priced into `/quote`.
+pub const FILL_GAS: u64 = 120_000;
+/// Gas of the source `settleIntent` the relayer sends.
+pub const SETTLE_GAS: u64 = 100_000;
+
+/// Fee and ETA inputs of `POST /quote`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct QuotePolicy {
+    /// Taken from the converted amount, as the solver prices its fills.
+    pub fee_bps: u64,
+    /// How far back settled intents are sampled for the ETA.
+    pub eta_window_days: i64,
+}
+
+impl Default for QuotePolicy {
+    fn default() -> Self {
+        Self {
+            fee_bps: 200,
+            eta_window_days: 7,
+        }
+    }
+}
+
+impl QuotePolicy {
+    /// Reads `QUOTE_FEE_BPS` and `QUOTE_ETA_WINDOW_DAYS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(bps) = var("QUOTE_FEE_BPS") {
+            policy.fee_bps = bps.trim().parse().context("Invalid QUOTE_FEE_BPS")?;
+        }
+        if let Some(days) = var("QUOTE_ETA_WINDOW_DAYS") {
+            policy.eta_window_days = days
+                .trim()
+                .parse()
+                .context("Invalid QUOTE_ETA_WINDOW_DAYS")?;
+        }
+
+        if policy.fee_bps >= 10_000 {
+            bail!("QUOTE_FEE_BPS must be below 10000");
+        }
+        if policy.eta_window_days < 1 {
+            bail!("QUOTE_ETA_WINDOW_DAYS must be at least 1");
+        }
+
+        Ok(policy)
+    }
+}
+
+/// `(net, fee)` of `amount` after a `fee_bps` fee. The fee is floored, as
+/// the contracts compute theirs.
+pub fn apply_fee(amount: U256, fee_bps: u64) -> (U256, U256) {
+    let fee = amount * U256::from(fee_bps) / U256::from(10_000u64);
+    (amount - fee, fee)
+}
+
+/// Current cost of one reference transaction on a chain.
+#[derive(Debug, Clone, Serialize)]
+pub struct GasCost {
+    pub chain: &'static str,
+    /// What the transaction is: `fill` or `settle`.
+    pub operation: &'static str,
+    pub gas: u64,
+    /// Wei per gas, as the relayer would price it.
+    pub fee_per_gas: String,
+    /// Whole units of the chain's native token.
+    pub native_cost: f64,
+    pub usd: Option<f64>,
+}
+
+/// Seconds from creation to fill of recently settled intents on a route.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SettlementEta {
+    pub samples: usize,
+    pub median_secs: i64,
+    pub p90_secs: i64,
+}
+
+/// `None` without samples; negative latencies (clock skew) count as zero.
+pub fn settlement_eta(mut latencies: Vec<i64>) -> Option<SettlementEta> {
+    if latencies.is_empty() {
+        return None;
+    }
+
+    latencies.iter_mut().for_each(|secs| *secs = (*secs).max(0));
+    latencies.sort_unstable();
+    let rank = |pct: usize| latencies[(latencies.len() * pct).div_ceil(100).max(1) - 1];
+
+    Some(SettlementEta {
+        samples: latencies.len(),
+        median_secs: rank(50),
+        p90_secs: rank(90),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_apply_fee() {
+        let (net, fee) = apply_fee(U256::from(1_000_000u64), 200);
+        assert_eq!((net, fee), (U256::from(980_000u64), U256::from(20_000u64)));
+
+        // The fee floors, so dust amounts pay none
+        assert_eq!(
+            apply_fee(U256::from(49u64), 200),
+            (U256::from(49u64), U256::zero())
+        );
+    }
+
+    #[test]
+    fn test_settlement_eta() {
+        assert_eq!(settlement_eta(Vec::new()), None);
+
+        let eta = settlement_eta(vec![300, 60, -5, 120, 90, 600, 45, 75, 80, 100]).unwrap();
+        assert_eq!(eta.samples, 10);
+        assert_eq!(eta.median_secs, 80);
+        assert_eq!(eta.p90_secs, 300);
+    }
+
+    #[test]
+    fn test_eth_to_usdc_quote() {
+        let one_eth = U256::exp10(18);
+        let (source, dest, rounding) = quote_amounts(
+            QuoteSide::ExactIn,
+            one_eth,
+            TokenType::ETH,
+            TokenType::USDC,
+            3125.25,
+        )
+        .unwrap();
+        assert_eq!(source, one_eth);
+        assert_eq!(dest, U256::from(3_125_250_000u64));
+        assert!(!rounding.inexact);
+
+        let (source, dest, rounding) = quote_amounts(
+            QuoteSide::ExactOut,
+            U256::from(1_000_000u64),
+            TokenType::ETH,
+            TokenType::USDC,
+            3125.25,
+        )
+        .unwrap();
+        assert_eq!(dest, U256::from(1_000_000u64));
+        assert_eq!(rounding.mode, Rounding::Ceil);
+        assert!(rounding.inexact);
+        // 1 USDC / 3125.25 = 319974402047836.17... wei, rounded up
+        assert_eq!(source, U256::from(319_974_402_047_837u64));
+    }
+
+    fn token() -> impl Strategy<Value = TokenType> {
+        prop::sample::select(TokenType::ALL.to_vec())
+    }
+
+    proptest! {
+        /// The user never receives more than the rate allows, and a floored
+        /// quote is at most one base unit short.
+        #[test]
+        fn prop_exact_in_floors_dest(
+            raw in 1u128..u128::MAX / 2,
+            source in token(),
+            dest in token(),
+            rate in 1e-6f64..1e6,
+        ) {
+            let amount = U256::from(raw);
+            let (_, floored, rounding) =
+                quote_amounts(QuoteSide::ExactIn, amount, source, dest, rate).unwrap();
+            let fixed = rate_to_fixed(rate).unwrap();
+            let (ceiled, _) =
+                convert_at_rate(amount, source.decimals(), dest.decimals(), fixed, Rounding::Ceil)
+                    .unwrap();
+
+            prop_assert!(floored <= ceiled);
+            prop_assert!(ceiled - floored <= U256::one());
+            prop_assert_eq!(rounding.inexact, floored != ceiled);
+
+            // Paying back what was received never costs more than was paid
+            let (back, _) = convert_at_inverse_rate(
+                floored,
+                source.decimals(),
+                dest.decimals(),
+                fixed,
+                Rounding::Ceil,
+            )
+            .unwrap();
+            prop_assert!(back <= amount);
+        }
+
+        /// Paying the ceiled source amount always delivers the requested
+        /// destination amount.
+        #[test]
+        fn prop_exact_out_ceils_source(
+            raw in 1u128..u128::MAX / 2,
+            source in token(),
+            dest in token(),
+            rate in 1e-6f64..1e6,
+        ) {
+            let wanted = U256::from(raw);
+            let (source_amount, dest_amount, rounding) =
+                quote_amounts(QuoteSide::ExactOut, wanted, source, dest, rate).unwrap();
+            prop_assert_eq!(dest_amount, wanted);
+            prop_assert_eq!(rounding.mode, Rounding::Ceil);
+
+            let (_, delivered, _) =
+                quote_amounts(QuoteSide::ExactIn, source_amount, source, dest, rate).unwrap();
+            prop_assert!(delivered >= wanted);
+        }
+    }
+}
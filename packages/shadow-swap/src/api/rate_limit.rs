@@ -0,0 +1,237 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+
+/// How often idle buckets are dropped. A bucket that has refilled holds no
+/// state a fresh one would not.
+const SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Default rates for the public API. A rate of `0` turns that limit off.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimitPolicy {
+    /// Requests per minute for callers without an API key, per client IP.
+    pub ip_per_min: u32,
+    /// Requests an idle IP may make at once.
+    pub ip_burst: u32,
+    /// Requests per minute per API key, unless the key sets its own.
+    pub key_per_min: u32,
+    /// Requests an idle key may make at once.
+    pub key_burst: u32,
+    /// Take the client IP from `Forwarded` / `X-Forwarded-For`. Only safe
+    /// behind a proxy that overwrites them, or any caller can pick its IP.
+    pub trust_forwarded: bool,
+}
+
+impl Default for RateLimitPolicy {
+    fn default() -> Self {
+        Self {
+            ip_per_min: 120,
+            ip_burst: 30,
+            key_per_min: 600,
+            key_burst: 100,
+            trust_forwarded: false,
+        }
+    }
+}
+
+impl RateLimitPolicy {
+    /// Reads `RATE_LIMIT_IP_PER_MIN`, `RATE_LIMIT_IP_BURST`,
+    /// `RATE_LIMIT_KEY_PER_MIN`, `RATE_LIMIT_KEY_BURST` and
+    /// `RATE_LIMIT_TRUST_FORWARDED`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(rate) = var("RATE_LIMIT_IP_PER_MIN") {
+            policy.ip_per_min = rate
+                .trim()
+                .parse()
+                .context("Invalid RATE_LIMIT_IP_PER_MIN")?;
+        }
+        if let Some(burst) = var("RATE_LIMIT_IP_BURST") {
+            policy.ip_burst = burst
+                .trim()
+                .parse()
+                .context("Invalid RATE_LIMIT_IP_BURST")?;
+        }
+        if let Some(rate) = var("RATE_LIMIT_KEY_PER_MIN") {
+            policy.key_per_min = rate
+                .trim()
+                .parse()
+                .context("Invalid RATE_LIMIT_KEY_PER_MIN")?;
+        }
+        if let Some(burst) = var("RATE_LIMIT_KEY_BURST") {
+            policy.key_burst = burst
+                .trim()
+                .parse()
+                .context("Invalid RATE_LIMIT_KEY_BURST")?;
+        }
+        if let Some(trust) = var("RATE_LIMIT_TRUST_FORWARDED") {
+            policy.trust_forwarded = trust
+                .trim()
+                .parse()
+                .context("Invalid RATE_LIMIT_TRUST_FORWARDED")?;
+        }
+
+        if policy.ip_per_min > 0 && policy.ip_burst == 0 {
+            bail!("RATE_LIMIT_IP_BURST must be positive while RATE_LIMIT_IP_PER_MIN is set");
+        }
+        if policy.key_per_min > 0 && policy.key_burst == 0 {
+            bail!("RATE_LIMIT_KEY_BURST must be positive while RATE_LIMIT_KEY_PER_MIN is set");
+        }
+
+        Ok(policy)
+    }
+
+    pub fn ip_rate(&self) -> Option<Rate> {
+        Rate::new(self.ip_per_min, self.ip_burst)
+    }
+
+    /// `per_min` is the key's own rate, if it has one.
+    pub fn key_rate(&self, per_min: Option<u32>) -> Option<Rate> {
+        Rate::new(per_min.unwrap_or(self.key_per_min), self.key_burst)
+    }
+}
+
+/// A steady rate with room for bursts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rate {
+    pub per_min: u32,
+    pub burst: u32,
+}
+
+impl Rate {
+    /// `None` when `per_min` is 0, i.e. unlimited.
+    pub fn new(per_min: u32, burst: u32) -> Option<Self> {
+        (per_min > 0).then_some(Self {
+            per_min,
+            burst: burst.max(1),
+        })
+    }
+
+    fn per_sec(&self) -> f64 {
+        f64::from(self.per_min) / 60.0
+    }
+}
+
+#[derive(Debug)]
+struct Bucket {
+    rate: Rate,
+    tokens: f64,
+    updated: Instant,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.rate.per_sec()).min(f64::from(self.rate.burst));
+        self.updated = now;
+    }
+}
+
+/// Token buckets by client, e.g. `ip:203.0.113.9` or `key:12`.
+#[derive(Debug)]
+pub struct RateLimiter {
+    buckets: Mutex<HashMap<String, Bucket>>,
+    last_sweep: Mutex<Instant>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        Self {
+            buckets: Mutex::new(HashMap::new()),
+            last_sweep: Mutex::new(Instant::now()),
+        }
+    }
+}
+
+impl RateLimiter {
+    /// Takes a token from `client`'s bucket, or returns how long until one
+    /// is available.
+    pub fn check(&self, client: &str, rate: Rate) -> std::result::Result<(), Duration> {
+        self.check_at(client, rate, Instant::now())
+    }
+
+    fn check_at(
+        &self,
+        client: &str,
+        rate: Rate,
+        now: Instant,
+    ) -> std::result::Result<(), Duration> {
+        let mut buckets = self.buckets.lock().unwrap_or_else(|e| e.into_inner());
+        self.sweep(&mut buckets, now);
+
+        let bucket = buckets.entry(client.to_string()).or_insert_with(|| Bucket {
+            rate,
+            tokens: f64::from(rate.burst),
+            updated: now,
+        });
+        // A key's rate can change while its bucket lives
+        if bucket.rate != rate {
+            bucket.rate = rate;
+            bucket.tokens = bucket.tokens.min(f64::from(rate.burst));
+        }
+        bucket.refill(now);
+
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64(
+                (1.0 - bucket.tokens) / rate.per_sec(),
+            ))
+        }
+    }
+
+    fn sweep(&self, buckets: &mut HashMap<String, Bucket>, now: Instant) {
+        let mut last_sweep = self.last_sweep.lock().unwrap_or_else(|e| e.into_inner());
+        if now.saturating_duration_since(*last_sweep) < SWEEP_INTERVAL {
+            return;
+        }
+        *last_sweep = now;
+        buckets.retain(|_, bucket| {
+            bucket.refill(now);
+            bucket.tokens < f64::from(bucket.rate.burst)
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_allows_burst_then_refills_at_rate() {
+        let limiter = RateLimiter::default();
+        let rate = Rate::new(60, 3).unwrap();
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            assert!(limiter.check_at("ip:a", rate, start).is_ok());
+        }
+        let wait = limiter.check_at("ip:a", rate, start).unwrap_err();
+        assert!(wait > Duration::from_millis(900) && wait <= Duration::from_secs(1));
+        // Other clients have their own bucket
+        assert!(limiter.check_at("ip:b", rate, start).is_ok());
+
+        // One token a second at 60 per minute
+        let later = start + Duration::from_secs(1);
+        assert!(limiter.check_at("ip:a", rate, later).is_ok());
+        assert!(limiter.check_at("ip:a", rate, later).is_err());
+
+        // Refilled buckets are dropped on the next sweep
+        let idle = start + SWEEP_INTERVAL + Duration::from_secs(5);
+        assert!(limiter.check_at("ip:c", rate, idle).is_ok());
+        assert_eq!(limiter.buckets.lock().unwrap().len(), 1);
+
+        assert!(Rate::new(0, 10).is_none());
+        assert_eq!(
+            RateLimitPolicy::default().key_rate(Some(5)),
+            Rate::new(5, 100)
+        );
+    }
+}
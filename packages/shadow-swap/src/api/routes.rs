@@ -1,25 +1,57 @@
 use std::collections::HashMap;
 
-use actix_web::{HttpRequest, HttpResponse, Responder, get, post, web};
+use actix_web::{HttpRequest, HttpResponse, Responder, delete, get, post, web};
 use chrono::Utc;
+use ethers::types::U256;
+use mantle_core::{
+    amount::to_decimal,
+    chain::Chain,
+    hex_types::{AddressHex, H256Hex},
+    metrics::CONTENT_TYPE,
+    parse,
+    pricefeed::PriceFeed,
+    token::TokenType,
+    token_registry::TokenListing,
+};
 use serde_json::json;
 use tracing::{debug, error, info, warn};
 
 use crate::{
     AppState,
     api::{
-        helper::{
-            handle_intent_created_event, handle_intent_filled_event, handle_intent_refunded_event,
-            handle_intent_registered_event, handle_intent_settled_event, handle_root_synced_event,
-            handle_withdrawal_claimed_event, validate_hmac,
-        },
+        admin_auth::{AdminIdentity, AdminRole, issue_admin_key},
+        api_keys::{ApiScope, issue_api_key},
+        helper::{capture_exchange, indexer_event_type, redacted_bridge_request, validate_hmac},
         model::{
-            AllPricesResponse, ConvertRequest, ConvertResponse, IndexerEventRequest,
-            IndexerEventResponse, InitiateBridgeRequest, InitiateBridgeResponse,
-            IntentStatusResponse, PriceRequest, PriceResponse, PriceSourceInfo, StatsResponse,
+            AcknowledgeUpgradeRequest, AllPricesResponse, BridgeQuoteRequest,
+            ComplianceOverrideRequest, ConvertRequest, ConvertResponse, CreateAdminKeyRequest,
+            CreateApiKeyRequest, CreateWebhookRequest, DebugCaptureRequest, FillReportRequest,
+            IndexerEventRequest, IndexerEventResponse, InitiateBridgeRequest,
+            InitiateBridgeResponse, IntentAnnotationRequest, IntentEventResponse,
+            IntentQuoteRequest, IntentStatusResponse, IntentTransactionResponse, MerkleProofQuery,
+            PriceRequest, PriceResponse, PriceSourceInfo, ReservationRequest, RevealSecretRequest,
+            RotateWebhookSecretRequest, StatsResponse, TestWebhookRequest, TokenListingRequest,
+            UserIntentLimitRequest, WebhookFilterRequest,
         },
+        pagination::{EMBEDDED_HISTORY_LIMIT, HistoryPage, HistoryPageQuery, finish_page},
+        quote::{
+            FILL_GAS, GasCost, QuoteSide, SETTLE_GAS, apply_fee, quote_amounts, settlement_eta,
+        },
+    },
+    models::model::{BridgeEventType, IntentPrivacyParams},
+    relay_coordinator::{
+        claim_auth::{parse_claim_auth, parse_claim_delegate},
+        compliance::ComplianceAction,
+        contract_guard::GuardedContract,
+        deposit_check::{DepositKind, surplus_totals},
+        fill_reports::FillReportRejection,
+        ingestion_queue::IngestionSource,
+        intent_notes::AnnotationRejection,
+        orphaned_fills::{ApprovalRejection, OrphanStatus},
+        relayer_jobs::{JOB_STATUSES, JobKind, JobRejection},
+        reservations::ReservationRejection,
+        secret_reveal::RevealRejection,
     },
-    models::model::TokenType,
 };
 
 // ============================================================================
@@ -32,6 +64,22 @@ pub async fn initiate_bridge(
     body: web::Bytes,
     app_state: web::Data<AppState>,
 ) -> impl Responder {
+    let request = redacted_bridge_request(&body);
+    let intent_id = request
+        .get("intent_id")
+        .and_then(|id| id.as_str())
+        .unwrap_or_default()
+        .to_string();
+
+    let response = create_bridge_intent(req, body, app_state.clone()).await;
+    capture_exchange(&app_state, &intent_id, "api", request, response).await
+}
+
+async fn create_bridge_intent(
+    req: HttpRequest,
+    body: web::Bytes,
+    app_state: web::Data<AppState>,
+) -> HttpResponse {
     // HMAC validation
     if let Err(response) = validate_hmac(&req, &body, &app_state) {
         return response;
@@ -51,7 +99,7 @@ pub async fn initiate_bridge(
     };
 
     let intent_id = request.intent_id.to_lowercase();
-    if !intent_id.starts_with("0x") || intent_id.len() != 66 {
+    let Ok(intent_hash) = H256Hex::parse("intent_id", &intent_id) else {
         return HttpResponse::BadRequest().json(InitiateBridgeResponse {
             success: false,
             intent_id: intent_id.clone(),
@@ -59,9 +107,9 @@ pub async fn initiate_bridge(
             message: "Invalid intent_id format".to_string(),
             error: Some("intent_id must be a 32-byte hex string (0x...)".to_string()),
         });
-    }
+    };
 
-    if !request.commitment.starts_with("0x") || request.commitment.len() != 66 {
+    let Ok(commitment) = H256Hex::parse("commitment", &request.commitment) else {
         return HttpResponse::BadRequest().json(InitiateBridgeResponse {
             success: false,
             intent_id: String::new(),
@@ -69,9 +117,26 @@ pub async fn initiate_bridge(
             message: "Invalid commitment format".to_string(),
             error: Some("Commitment must be 32-byte hex string".to_string()),
         });
-    }
+    };
+
+    let recipient = match AddressHex::parse("recipient", &request.recipient) {
+        Ok(recipient) => recipient,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(InitiateBridgeResponse {
+                success: false,
+                intent_id: String::new(),
+                commitment: String::new(),
+                message: "Invalid recipient".to_string(),
+                error: Some(e.to_string()),
+            });
+        }
+    };
 
-    if !request.encrypted_secret.starts_with("0x") {
+    let encrypted_secret = request
+        .encrypted_secret
+        .as_deref()
+        .filter(|secret| !secret.is_empty());
+    if encrypted_secret.is_some_and(|secret| !secret.starts_with("0x")) {
         return HttpResponse::BadRequest().json(InitiateBridgeResponse {
             success: false,
             intent_id: String::new(),
@@ -91,151 +156,2492 @@ pub async fn initiate_bridge(
         });
     }
 
-    if !request.claim_auth.starts_with("0x") || request.claim_auth.len() != 132 {
+    if let Err(e) = parse_claim_auth(&request.claim_auth) {
         return HttpResponse::BadRequest().json(InitiateBridgeResponse {
             success: false,
             intent_id: String::new(),
             commitment: String::new(),
             message: "Invalid claim_auth format".to_string(),
-            error: Some("Claim authorization must be 65-byte hex signature".to_string()),
+            error: Some(e.to_string()),
+        });
+    }
+
+    let encrypted_note = request
+        .encrypted_note
+        .as_deref()
+        .filter(|note| !note.is_empty());
+    if let Some(Err(e)) = encrypted_note.map(|note| app_state.encrypted_notes.validate(note)) {
+        return HttpResponse::BadRequest().json(InitiateBridgeResponse {
+            success: false,
+            intent_id: String::new(),
+            commitment: String::new(),
+            message: "Invalid encrypted_note".to_string(),
+            error: Some(e.to_string()),
+        });
+    }
+
+    let claim_delegate = match request
+        .claim_delegate
+        .as_deref()
+        .filter(|delegate| !delegate.is_empty())
+        .map(parse_claim_delegate)
+        .transpose()
+    {
+        Ok(delegate) => delegate.map(AddressHex::from),
+        Err(e) => {
+            return HttpResponse::BadRequest().json(InitiateBridgeResponse {
+                success: false,
+                intent_id: String::new(),
+                commitment: String::new(),
+                message: "Invalid claim_delegate".to_string(),
+                error: Some(e.to_string()),
+            });
+        }
+    };
+    if claim_delegate
+        .as_deref()
+        .is_some_and(|delegate| delegate.eq_ignore_ascii_case(&request.recipient))
+    {
+        return HttpResponse::BadRequest().json(InitiateBridgeResponse {
+            success: false,
+            intent_id: String::new(),
+            commitment: String::new(),
+            message: "Invalid claim_delegate".to_string(),
+            error: Some("claim_delegate is the recipient; omit it".to_string()),
+        });
+    }
+
+    info!(
+        "🌉 Initiating bridge | Direction: {} -> {} | Token: {} | Amount: {}",
+        request.source_chain, request.dest_chain, request.source_token, request.amount
+    );
+
+    if !matches!(
+        (request.source_chain.as_str(), request.dest_chain.as_str()),
+        ("ethereum", "mantle") | ("mantle", "ethereum")
+    ) {
+        return HttpResponse::BadRequest().json(InitiateBridgeResponse {
+            success: false,
+            intent_id: String::new(),
+            commitment: String::new(),
+            message: "Invalid chain pair".to_string(),
+            error: Some("Must be ethereum<->mantle".to_string()),
+        });
+    }
+
+    let tokens = &app_state.bridge_coordinator.tokens;
+    let listing = match tokens
+        .resolve_str(&request.source_token)
+        .and_then(|token| tokens.enabled(token))
+    {
+        Ok(listing) => listing,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(InitiateBridgeResponse {
+                success: false,
+                intent_id: String::new(),
+                commitment: String::new(),
+                message: "Unsupported token".to_string(),
+                error: Some(e.to_string()),
+            });
+        }
+    };
+
+    let amount = match parse::amount("amount", &request.amount) {
+        Ok(amount) => amount,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(InitiateBridgeResponse {
+                success: false,
+                intent_id: String::new(),
+                commitment: String::new(),
+                message: "Invalid amount".to_string(),
+                error: Some(format!("Amount must be a base-unit integer: {}", e)),
+            });
+        }
+    };
+
+    let token_type = listing.token;
+    if let Err(e) = listing.check_amount(amount) {
+        return HttpResponse::BadRequest().json(InitiateBridgeResponse {
+            success: false,
+            intent_id: String::new(),
+            commitment: String::new(),
+            message: "Amount outside token limits".to_string(),
+            error: Some(e.to_string()),
+        });
+    }
+
+    // Chain pair was validated above
+    let dest_chain = Chain::parse(&request.dest_chain).expect("validated chain pair");
+    if let Err(e) = app_state
+        .route_minimums
+        .check(token_type, dest_chain, amount)
+    {
+        return HttpResponse::BadRequest().json(InitiateBridgeResponse {
+            success: false,
+            intent_id: intent_id.clone(),
+            commitment: String::new(),
+            message: "Amount below route minimum".to_string(),
+            error: Some(e.to_string()),
+        });
+    }
+
+    match app_state
+        .user_intent_limits
+        .check(&app_state.database, &request.user_address)
+    {
+        Ok(None) => {}
+        Ok(Some(exceeded)) => {
+            warn!(
+                "🚦 Rejecting intent {}: {} already has {} active intents (limit {})",
+                intent_id, exceeded.user_address, exceeded.active_intents, exceeded.limit
+            );
+            return HttpResponse::TooManyRequests().json(json!({
+                "success": false,
+                "intent_id": intent_id,
+                "commitment": "",
+                "message": "Too many active intents",
+                "error": format!(
+                    "{} has {} intents in flight; the limit is {}",
+                    exceeded.user_address, exceeded.active_intents, exceeded.limit
+                ),
+                "code": "active_intent_limit",
+                "active_intents": exceeded.active_intents,
+                "limit": exceeded.limit,
+            }));
+        }
+        Err(e) => {
+            error!(
+                "Failed to check active intents for {}: {}",
+                request.user_address, e
+            );
+            return HttpResponse::InternalServerError().json(InitiateBridgeResponse {
+                success: false,
+                intent_id: intent_id.clone(),
+                commitment: String::new(),
+                message: "Failed to check active intents".to_string(),
+                error: Some(e.to_string()),
+            });
+        }
+    }
+
+    // Catch signatures the contract would reject before the user deposits
+    let claim_auth = app_state
+        .bridge_coordinator
+        .check_claim_auth(
+            &intent_id,
+            &request.encrypted_nullifier,
+            &request.recipient,
+            claim_delegate.as_deref(),
+            &request.claim_auth,
+        )
+        .await;
+    if !claim_auth.valid {
+        warn!(
+            "🚩 Rejecting intent {}: claim signature invalid: {}",
+            intent_id,
+            claim_auth.error.as_deref().unwrap_or("unknown error")
+        );
+        return HttpResponse::BadRequest().json(InitiateBridgeResponse {
+            success: false,
+            intent_id: intent_id.clone(),
+            commitment: String::new(),
+            message: "Invalid claim_auth".to_string(),
+            error: claim_auth.error,
+        });
+    }
+
+    if let Err(e) = app_state
+        .database
+        .store_intent_privacy_params(&IntentPrivacyParams {
+            intent_id: intent_hash,
+            commitment: Some(commitment),
+            nullifier: Some(request.encrypted_nullifier.clone()),
+            secret: encrypted_secret.map(str::to_string),
+            recipient: Some(recipient),
+            claim_signature: Some(request.claim_auth.clone()),
+            claim_delegate: claim_delegate.clone(),
+        })
+    {
+        error!("Failed to store privacy params for {}: {}", intent_id, e);
+        return HttpResponse::InternalServerError().json(InitiateBridgeResponse {
+            success: false,
+            intent_id: intent_id.clone(),
+            commitment: String::new(),
+            message: "Failed to store privacy parameters".to_string(),
+            error: Some(e.to_string()),
+        });
+    }
+
+    if encrypted_secret.is_none()
+        && let Err(e) = app_state.database.create_secret_reveal(&intent_id)
+    {
+        error!("Failed to register secret reveal for {}: {}", intent_id, e);
+        return HttpResponse::InternalServerError().json(InitiateBridgeResponse {
+            success: false,
+            intent_id: intent_id.clone(),
+            commitment: String::new(),
+            message: "Failed to register secret reveal".to_string(),
+            error: Some(e.to_string()),
+        });
+    }
+
+    if let Err(e) = app_state.database.record_claim_auth_check(
+        &intent_id,
+        true,
+        claim_auth.signer.as_deref(),
+        None,
+    ) {
+        warn!("Failed to record claim auth check for {}: {}", intent_id, e);
+    }
+
+    match encrypted_note.map(|note| app_state.database.store_encrypted_note(&intent_id, note)) {
+        None | Some(Ok(true)) => {}
+        Some(Ok(false)) => {
+            return HttpResponse::Conflict().json(InitiateBridgeResponse {
+                success: false,
+                intent_id: intent_id.clone(),
+                commitment: String::new(),
+                message: "Encrypted note already delivered".to_string(),
+                error: Some("The note of a filled intent cannot be replaced".to_string()),
+            });
+        }
+        Some(Err(e)) => {
+            error!("Failed to store encrypted note for {}: {}", intent_id, e);
+            return HttpResponse::InternalServerError().json(InitiateBridgeResponse {
+                success: false,
+                intent_id: intent_id.clone(),
+                commitment: String::new(),
+                message: "Failed to store encrypted note".to_string(),
+                error: None,
+            });
+        }
+    }
+
+    if let Some(sponsored) = request.sponsor_claim
+        && let Err(e) = app_state
+            .database
+            .set_claim_sponsorship(&intent_id, sponsored)
+    {
+        error!("Failed to store claim sponsorship for {}: {}", intent_id, e);
+        return HttpResponse::InternalServerError().json(InitiateBridgeResponse {
+            success: false,
+            intent_id: intent_id.clone(),
+            commitment: String::new(),
+            message: "Failed to store claim sponsorship".to_string(),
+            error: Some(e.to_string()),
         });
     }
 
-    info!(
-        "🌉 Initiating bridge | Direction: {} -> {} | Token: {} | Amount: {}",
-        request.source_chain, request.dest_chain, request.source_token, request.amount
-    );
+    info!("✅ Bridge intent created: {}", intent_id);
+
+    HttpResponse::Ok().json(InitiateBridgeResponse {
+        success: true,
+        intent_id: intent_id.clone(),
+        commitment: request.commitment.clone(),
+        message: format!(
+            "Bridge intent created. Relayer will process on {}",
+            request.source_chain
+        ),
+        error: None,
+    })
+}
+
+#[get("/bridge/quote")]
+pub async fn get_bridge_quote(
+    query: web::Query<BridgeQuoteRequest>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let tokens = &app_state.bridge_coordinator.tokens;
+    let token_type = match TokenType::from_symbol(&query.token)
+        .or_else(|_| tokens.resolve_str(&query.token))
+        .and_then(|token| tokens.enabled(token))
+    {
+        Ok(listing) => listing.token,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+    };
+
+    let dest_chain = match Chain::parse(&query.dest_chain) {
+        Ok(chain) => chain,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+    };
+
+    let amount = match parse::amount("amount", &query.amount) {
+        Ok(amount) => amount,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Amount must be a base-unit integer: {}", e)
+            }));
+        }
+    };
+
+    let dest_token = match query.dest_token.as_deref() {
+        Some(token) => {
+            match TokenType::from_symbol(token)
+                .or_else(|_| tokens.resolve_str(token))
+                .and_then(|token| tokens.enabled(token))
+            {
+                Ok(listing) => listing.token,
+                Err(e) => {
+                    return HttpResponse::BadRequest().json(json!({
+                        "status": "error",
+                        "message": format!("Invalid dest_token: {}", e)
+                    }));
+                }
+            }
+        }
+        None => token_type,
+    };
+
+    let rate = match app_state
+        .price_feed
+        .exchange_rate(token_type, dest_token)
+        .await
+    {
+        Ok(rate) => rate,
+        Err(e) => {
+            error!("Failed to price quote: {}", e);
+            return HttpResponse::ServiceUnavailable().json(json!({
+                "status": "error",
+                "message": format!("Pricing unavailable: {}", e)
+            }));
+        }
+    };
+
+    let (source_amount, dest_amount, rounding) =
+        match quote_amounts(query.side, amount, token_type, dest_token, rate) {
+            Ok(amounts) => amounts,
+            Err(e) => {
+                return HttpResponse::BadRequest().json(json!({
+                    "status": "error",
+                    "message": e.to_string()
+                }));
+            }
+        };
+
+    let minimum = app_state.route_minimums.minimum(token_type, dest_chain);
+    let data = json!({
+        "token": token_type.symbol(),
+        "dest_token": dest_token.symbol(),
+        "dest_chain": dest_chain.name(),
+        "side": query.side,
+        "amount": amount.to_string(),
+        "source_amount": source_amount.to_string(),
+        "dest_amount": dest_amount.to_string(),
+        "rate": rate,
+        "rounding": rounding,
+        "minimum_amount": minimum.to_string(),
+    });
+
+    // The minimum applies to what is deposited, derived or not
+    match app_state
+        .route_minimums
+        .check(token_type, dest_chain, source_amount)
+    {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": data
+        })),
+        Err(e) => HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": e.to_string(),
+            "data": data
+        })),
+    }
+}
+
+/// Cost of `gas` on `chain` at its current fees.
+async fn gas_cost(
+    app_state: &AppState,
+    chain: Chain,
+    operation: &'static str,
+    gas: u64,
+) -> anyhow::Result<GasCost> {
+    let relayer = app_state.bridge_coordinator.chains.resolve(chain.name())?;
+    let fee_per_gas = relayer.tx_strategy.fee_per_gas(&*relayer.client).await?;
+    let native_cost = to_decimal(fee_per_gas * U256::from(gas), 18);
+    let usd = match TokenType::from_symbol(relayer.native_symbol) {
+        Ok(native) => app_state.price_feed.usd_price(native).await.ok(),
+        Err(_) => None,
+    };
+
+    Ok(GasCost {
+        chain: chain.name(),
+        operation,
+        gas,
+        fee_per_gas: fee_per_gas.to_string(),
+        native_cost,
+        usd: usd.map(|price| price * native_cost),
+    })
+}
+
+#[post("/quote")]
+pub async fn quote_intent(
+    request: web::Json<IntentQuoteRequest>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let tokens = &app_state.bridge_coordinator.tokens;
+    let resolve = |token: &str| {
+        TokenType::from_symbol(token)
+            .or_else(|_| tokens.resolve_str(token))
+            .and_then(|token| tokens.enabled(token))
+            .map(|listing| listing.token)
+    };
+
+    let (source_chain, dest_chain) = match (
+        Chain::parse(&request.source_chain),
+        Chain::parse(&request.dest_chain),
+    ) {
+        (Ok(source), Ok(dest)) if source != dest => (source, dest),
+        (Ok(_), Ok(_)) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": "source_chain and dest_chain must differ"
+            }));
+        }
+        (Err(e), _) | (_, Err(e)) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+    };
+
+    let token_type = match resolve(&request.token) {
+        Ok(token) => token,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+    };
+    let dest_token = match request.dest_token.as_deref().map(resolve) {
+        Some(Ok(token)) => token,
+        Some(Err(e)) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid dest_token: {}", e)
+            }));
+        }
+        None => token_type,
+    };
+
+    let amount = match parse::amount("amount", &request.amount) {
+        Ok(amount) => amount,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Amount must be a base-unit integer: {}", e)
+            }));
+        }
+    };
+
+    let rate = match app_state
+        .price_feed
+        .exchange_rate(token_type, dest_token)
+        .await
+    {
+        Ok(rate) => rate,
+        Err(e) => {
+            error!("Failed to price quote: {}", e);
+            return HttpResponse::ServiceUnavailable().json(json!({
+                "status": "error",
+                "message": format!("Pricing unavailable: {}", e)
+            }));
+        }
+    };
+
+    let gross = match quote_amounts(QuoteSide::ExactIn, amount, token_type, dest_token, rate) {
+        Ok((_, gross, _)) => gross,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+    };
+    let policy = app_state.quote_policy;
+    let (dest_amount, fee) = apply_fee(gross, policy.fee_bps);
+
+    // A chain whose fees can't be read is left out rather than failing the quote
+    let mut gas = Vec::new();
+    for (chain, operation, units) in [
+        (source_chain, "settle", SETTLE_GAS),
+        (dest_chain, "fill", FILL_GAS),
+    ] {
+        match gas_cost(&app_state, chain, operation, units).await {
+            Ok(cost) => gas.push(cost),
+            Err(e) => warn!("⚠️ No gas estimate for {}: {}", chain.name(), e),
+        }
+    }
+
+    let since = Utc::now() - chrono::Duration::days(policy.eta_window_days);
+    let eta = match app_state.database.get_fill_latencies(
+        source_chain.name(),
+        dest_chain.name(),
+        since,
+        500,
+    ) {
+        Ok(latencies) => settlement_eta(latencies),
+        Err(e) => {
+            warn!("⚠️ No settlement latency for quote: {}", e);
+            None
+        }
+    };
+
+    let minimum = app_state.route_minimums.minimum(token_type, dest_chain);
+    let data = json!({
+        "source_chain": source_chain.name(),
+        "dest_chain": dest_chain.name(),
+        "token": token_type.symbol(),
+        "dest_token": dest_token.symbol(),
+        "amount": amount.to_string(),
+        "rate": rate,
+        "gross_dest_amount": gross.to_string(),
+        "fee_bps": policy.fee_bps,
+        "fee_amount": fee.to_string(),
+        "dest_amount": dest_amount.to_string(),
+        "gas": gas,
+        "eta": eta,
+        "minimum_amount": minimum.to_string(),
+    });
+
+    match app_state
+        .route_minimums
+        .check(token_type, dest_chain, amount)
+    {
+        Ok(()) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": data
+        })),
+        Err(e) => HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": e.to_string(),
+            "data": data
+        })),
+    }
+}
+
+/// The solver's bearer token, checked against `RESERVATION_SOLVER_TOKENS`.
+fn authorize_solver(
+    req: &HttpRequest,
+    app_state: &AppState,
+    solver: &str,
+) -> Result<(), HttpResponse> {
+    if !app_state.reservations.enabled {
+        return Err(HttpResponse::ServiceUnavailable().json(json!({
+            "status": "error",
+            "message": "Reservations are disabled; fill on chain directly"
+        })));
+    }
+
+    let token = req
+        .headers()
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .unwrap_or_default();
+    if !app_state.reservations.authenticate(solver, token) {
+        return Err(HttpResponse::Unauthorized().json(json!({
+            "status": "error",
+            "message": "Unknown solver or invalid token"
+        })));
+    }
+
+    Ok(())
+}
+
+#[post("/intents/{intent_id}/reserve")]
+pub async fn reserve_intent(
+    req: HttpRequest,
+    path: web::Path<String>,
+    request: web::Json<ReservationRequest>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    if let Err(response) = authorize_solver(&req, &app_state, &request.solver) {
+        return response;
+    }
+    let intent_id = path.into_inner();
+
+    match app_state.bridge_coordinator.reserve_intent(
+        &intent_id,
+        &request.solver,
+        &app_state.reservations,
+    ) {
+        Ok(Ok(reservation)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": reservation
+        })),
+        Ok(Err(rejection @ ReservationRejection::NotFound)) => {
+            HttpResponse::NotFound().json(json!({
+                "status": "error",
+                "message": rejection.to_string()
+            }))
+        }
+        Ok(Err(rejection)) => {
+            let expires_at = match &rejection {
+                ReservationRejection::Held { expires_at } => Some(*expires_at),
+                _ => None,
+            };
+            HttpResponse::Conflict().json(json!({
+                "status": "error",
+                "message": rejection.to_string(),
+                "expires_at": expires_at
+            }))
+        }
+        Err(e) => {
+            error!("Failed to reserve intent {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to reserve intent"
+            }))
+        }
+    }
+}
+
+#[delete("/intents/{intent_id}/reserve")]
+pub async fn release_intent(
+    req: HttpRequest,
+    path: web::Path<String>,
+    query: web::Query<ReservationRequest>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    if let Err(response) = authorize_solver(&req, &app_state, &query.solver) {
+        return response;
+    }
+    let intent_id = path.into_inner();
+
+    match app_state
+        .bridge_coordinator
+        .release_intent_reservation(&intent_id, &query.solver)
+    {
+        Ok(true) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": format!("Intent {} released", intent_id)
+        })),
+        Ok(false) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "No active reservation of this intent by this solver"
+        })),
+        Err(e) => {
+            error!("Failed to release intent {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to release intent"
+            }))
+        }
+    }
+}
+
+#[post("/fill-reports")]
+pub async fn submit_fill_report(
+    app_state: web::Data<AppState>,
+    request: web::Json<FillReportRequest>,
+) -> impl Responder {
+    if !app_state.fill_reports.enabled {
+        return HttpResponse::ServiceUnavailable().json(json!({
+            "status": "error",
+            "message": "Fill reports are disabled"
+        }));
+    }
+
+    match app_state
+        .bridge_coordinator
+        .submit_fill_report(&app_state.fill_reports, &request)
+    {
+        Ok(Ok(report)) => {
+            app_state
+                .bridge_coordinator
+                .wakeups
+                .fill_reports
+                .notify_one();
+            HttpResponse::Accepted().json(json!({
+                "status": "success",
+                "data": report
+            }))
+        }
+        Ok(Err(rejection)) => {
+            warn!(
+                "📨 Fill report for {} rejected: {}",
+                request.intent_id, rejection
+            );
+            let mut response = match rejection {
+                FillReportRejection::Invalid(_) => HttpResponse::BadRequest(),
+                FillReportRejection::Unauthorized(_) => HttpResponse::Unauthorized(),
+                FillReportRejection::NotFound => HttpResponse::NotFound(),
+            };
+            response.json(json!({
+                "status": "error",
+                "message": rejection.to_string()
+            }))
+        }
+        Err(e) => {
+            error!(
+                "Failed to record fill report for {}: {}",
+                request.intent_id, e
+            );
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to record fill report"
+            }))
+        }
+    }
+}
+
+#[get("/fill-reports/{id}")]
+pub async fn get_fill_report(
+    app_state: web::Data<AppState>,
+    path: web::Path<i64>,
+) -> impl Responder {
+    let id = path.into_inner();
+
+    match app_state.database.get_fill_report(id) {
+        Ok(Some(report)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": report
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Fill report not found"
+        })),
+        Err(e) => {
+            error!("Failed to get fill report {}: {}", id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to get fill report"
+            }))
+        }
+    }
+}
+
+#[get("/bridge/intent/{intent_id}")]
+pub async fn get_intent_status(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let intent_id = path.into_inner();
+
+    match app_state.database.get_intent_by_id(&intent_id) {
+        Ok(Some(intent)) => {
+            let privacy_params = app_state
+                .database
+                .get_intent_privacy_params(&intent_id)
+                .ok();
+
+            let claim_sponsored = app_state
+                .database
+                .get_claim_sponsorship(&intent_id)
+                .ok()
+                .flatten()
+                .map(|s| s.sponsored);
+
+            let encrypted_note = app_state
+                .database
+                .get_encrypted_notes(&[intent.id.to_string()])
+                .map(|notes| notes.into_iter().next().map(|note| note.ciphertext))
+                .unwrap_or_else(|e| {
+                    warn!("Failed to load encrypted note of {}: {}", intent_id, e);
+                    None
+                });
+
+            let newest = HistoryPage::newest(EMBEDDED_HISTORY_LIMIT);
+            let (recent_events, more_events) = intent_events_page(&app_state, &intent_id, &newest)
+                .map(|(events, next)| (events, next.is_some()))
+                .unwrap_or_else(|e| {
+                    warn!("Failed to load events of intent {}: {}", intent_id, e);
+                    (Vec::new(), false)
+                });
+            let (recent_transactions, more_transactions) =
+                intent_transactions_page(&app_state, &intent_id, &newest)
+                    .map(|(transactions, next)| (transactions, next.is_some()))
+                    .unwrap_or_else(|e| {
+                        warn!("Failed to load transactions of intent {}: {}", intent_id, e);
+                        (Vec::new(), false)
+                    });
+
+            HttpResponse::Ok().json(IntentStatusResponse {
+                intent_id: intent.id.into(),
+                status: intent.status.as_str().to_string(),
+                source_chain: intent.source_chain,
+                dest_chain: intent.dest_chain,
+                source_token: intent.source_token,
+                dest_token: intent.dest_token,
+                amount: intent.amount.into(),
+                commitment: intent.source_commitment.map(String::from),
+                dest_fill_txid: intent.dest_fill_txid,
+                source_complete_txid: intent.source_complete_txid,
+                deadline: intent.deadline,
+                created_at: intent.created_at,
+                updated_at: intent.updated_at,
+                has_privacy: privacy_params.is_some(),
+                claim_sponsored,
+                encrypted_note,
+                recent_events,
+                more_events,
+                recent_transactions,
+                more_transactions,
+            })
+        }
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Intent not found"
+        })),
+        Err(e) => {
+            error!("Failed to get intent {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve intent"
+            }))
+        }
+    }
+}
+
+fn intent_events_page(
+    app_state: &AppState,
+    intent_id: &str,
+    page: &HistoryPage,
+) -> anyhow::Result<(Vec<IntentEventResponse>, Option<i32>)> {
+    let events = app_state.database.list_intent_bridge_events(
+        intent_id,
+        &page.types,
+        page.cursor,
+        page.ascending,
+        page.limit + 1,
+    )?;
+    let (events, next_cursor) = finish_page(events, page.limit, |event| event.id);
+
+    Ok((events.into_iter().map(Into::into).collect(), next_cursor))
+}
+
+fn intent_transactions_page(
+    app_state: &AppState,
+    intent_id: &str,
+    page: &HistoryPage,
+) -> anyhow::Result<(Vec<IntentTransactionResponse>, Option<i32>)> {
+    let transactions = app_state.database.list_intent_transactions(
+        intent_id,
+        &page.types,
+        page.cursor,
+        page.ascending,
+        page.limit + 1,
+    )?;
+    let (transactions, next_cursor) = finish_page(transactions, page.limit, |tx| tx.id);
+
+    Ok((
+        transactions.into_iter().map(Into::into).collect(),
+        next_cursor,
+    ))
+}
+
+/// Chain events of an intent, a page at a time. `type` filters by event
+/// type (comma separated).
+#[get("/intents/{intent_id}/events")]
+pub async fn list_intent_events(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<HistoryPageQuery>,
+) -> impl Responder {
+    let intent_id = path.into_inner();
+    let page = match query.page() {
+        Ok(page) => page,
+        Err(message) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": message
+            }));
+        }
+    };
+
+    let result = app_state
+        .database
+        .get_intent_by_id(&intent_id)
+        .and_then(|intent| {
+            intent
+                .map(|_| intent_events_page(&app_state, &intent_id, &page))
+                .transpose()
+        });
+
+    match result {
+        Ok(Some((events, next_cursor))) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "count": events.len(),
+            "next_cursor": next_cursor,
+            "data": events
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Intent not found"
+        })),
+        Err(e) => {
+            error!("Failed to list events of intent {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve intent events"
+            }))
+        }
+    }
+}
+
+/// Transactions the relayer sent for an intent, a page at a time. `type`
+/// filters by transaction type (comma separated).
+#[get("/intents/{intent_id}/transactions")]
+pub async fn list_intent_transactions(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<HistoryPageQuery>,
+) -> impl Responder {
+    let intent_id = path.into_inner();
+    let page = match query.page() {
+        Ok(page) => page,
+        Err(message) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": message
+            }));
+        }
+    };
+
+    let result = app_state
+        .database
+        .get_intent_by_id(&intent_id)
+        .and_then(|intent| {
+            intent
+                .map(|_| intent_transactions_page(&app_state, &intent_id, &page))
+                .transpose()
+        });
+
+    match result {
+        Ok(Some((transactions, next_cursor))) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "count": transactions.len(),
+            "next_cursor": next_cursor,
+            "data": transactions
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Intent not found"
+        })),
+        Err(e) => {
+            error!("Failed to list transactions of intent {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve intent transactions"
+            }))
+        }
+    }
+}
+
+/// Every recorded step of an intent from creation to claim, with the
+/// transactions and root syncs involved, oldest first.
+#[get("/intents/{intent_id}/timeline")]
+pub async fn get_intent_lifecycle(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let intent_id = path.into_inner();
+
+    match app_state.bridge_coordinator.intent_lifecycle(&intent_id) {
+        Ok(Some(lifecycle)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": lifecycle
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Intent not found"
+        })),
+        Err(e) => {
+            error!("Failed to build lifecycle of intent {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve intent timeline"
+            }))
+        }
+    }
+}
+
+#[get("/bridge/intent/{intent_id}/sponsorship")]
+pub async fn get_claim_sponsorship(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let intent_id = path.into_inner();
+
+    match app_state.database.get_claim_sponsorship(&intent_id) {
+        Ok(Some(sponsorship)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": sponsorship
+        })),
+        Ok(None) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": {
+                "intent_id": intent_id,
+                "sponsored": app_state.bridge_coordinator.sponsorship.default_sponsored,
+            }
+        })),
+        Err(e) => {
+            error!("Failed to get claim sponsorship for {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve claim sponsorship"
+            }))
+        }
+    }
+}
+
+#[get("/intents/{intent_id}/reveal")]
+pub async fn get_reveal_status(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let intent_id = path.into_inner();
+
+    match app_state.bridge_coordinator.get_reveal_status(&intent_id) {
+        Ok(Some(status)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": status
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": RevealRejection::NotFound.to_string()
+        })),
+        Err(e) => {
+            error!("Failed to get reveal status for {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve reveal status"
+            }))
+        }
+    }
+}
+
+/// The recipient's signature authenticates the reveal, so no HMAC here.
+#[post("/intents/{intent_id}/reveal")]
+pub async fn reveal_secret(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    request: web::Json<RevealSecretRequest>,
+) -> impl Responder {
+    let intent_id = path.into_inner();
+
+    match app_state
+        .bridge_coordinator
+        .reveal_secret(
+            &app_state.reveal_policy,
+            &intent_id,
+            &request.secret,
+            &request.signature,
+        )
+        .await
+    {
+        Ok(Ok(status)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": status
+        })),
+        Ok(Err(rejection)) => {
+            warn!("🔑 Reveal for {} rejected: {}", intent_id, rejection);
+            let mut response = match rejection {
+                RevealRejection::NotFound => HttpResponse::NotFound(),
+                RevealRejection::Invalid(_) => HttpResponse::BadRequest(),
+                RevealRejection::NotFilled | RevealRejection::AlreadyRevealed => {
+                    HttpResponse::Conflict()
+                }
+                RevealRejection::Expired => HttpResponse::Gone(),
+            };
+            response.json(json!({
+                "status": "error",
+                "message": rejection.to_string()
+            }))
+        }
+        Err(e) => {
+            error!("Failed to reveal secret for {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("Failed to reveal secret: {}", e)
+            }))
+        }
+    }
+}
+
+#[get("/intents/{intent_id}/claim-diagnosis")]
+pub async fn get_claim_diagnosis(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let intent_id = path.into_inner();
+
+    match app_state
+        .bridge_coordinator
+        .diagnose_claim(&intent_id)
+        .await
+    {
+        Ok(Some(diagnosis)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": diagnosis
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Intent not found"
+        })),
+        Err(e) => {
+            error!("Failed to diagnose claim for {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("Failed to diagnose claim: {}", e)
+            }))
+        }
+    }
+}
+
+#[get("/intents/{intent_id}/claim-estimate")]
+pub async fn get_claim_estimate(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let intent_id = path.into_inner();
+
+    match app_state
+        .bridge_coordinator
+        .estimate_claim(&intent_id, &app_state.claim_estimates)
+        .await
+    {
+        Ok(Some(estimate)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": estimate
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Intent not found"
+        })),
+        Err(e) => {
+            error!("Failed to estimate claim for {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("Failed to estimate claim: {}", e)
+            }))
+        }
+    }
+}
+
+#[get("/transactions/{tx_hash}/receipt")]
+pub async fn get_transaction_receipt(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let tx_hash = path.into_inner();
+
+    match app_state.database.get_transaction_receipt(&tx_hash) {
+        Ok(Some(receipt)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": receipt
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Receipt not found"
+        })),
+        Err(e) => {
+            error!("Failed to get receipt for {}: {}", tx_hash, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve receipt"
+            }))
+        }
+    }
+}
+
+#[get("/bridge/intents")]
+pub async fn list_intents(
+    app_state: web::Data<AppState>,
+    query: web::Query<std::collections::HashMap<String, String>>,
+) -> impl Responder {
+    let status_filter = query.get("status").map(|s| s.as_str());
+    let chain_filter = query.get("chain").map(|s| s.as_str());
+    let limit: usize = query
+        .get("limit")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(50)
+        .min(200);
+    let archived = query.get("archived").is_some_and(|a| a == "true");
+
+    let result = if archived {
+        app_state
+            .database
+            .list_archived_intents(status_filter, chain_filter, limit)
+    } else {
+        app_state
+            .database
+            .list_intents(status_filter, chain_filter, limit)
+    };
+
+    match result {
+        Ok(intents) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "count": intents.len(),
+            "data": intents
+        })),
+        Err(e) => {
+            error!("Failed to list intents: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve intents"
+            }))
+        }
+    }
+}
+
+// ============================================================================
+// ADMIN
+// ============================================================================
+
+#[get("/admin/commitments/duplicates")]
+pub async fn get_duplicate_commitments(app_state: web::Data<AppState>) -> impl Responder {
+    let quarantined = match app_state.database.list_quarantined_commitments(200) {
+        Ok(records) => records,
+        Err(e) => {
+            error!("Failed to list quarantined commitments: {}", e);
+            return HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve quarantined commitments"
+            }));
+        }
+    };
+
+    let duplicates = match app_state.database.find_duplicate_commitments() {
+        Ok(duplicates) => duplicates,
+        Err(e) => {
+            error!("Failed to scan for duplicate commitments: {}", e);
+            return HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to scan commitments"
+            }));
+        }
+    };
+
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "invariant_ok": duplicates.is_empty(),
+            "duplicates": duplicates,
+            "quarantined": quarantined,
+        }
+    }))
+}
+
+#[get("/admin/intent-limits")]
+pub async fn list_user_intent_limits(app_state: web::Data<AppState>) -> impl Responder {
+    match app_state.database.list_user_intent_limits() {
+        Ok(overrides) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": {
+                "default_max_active": app_state.user_intent_limits.default_max_active,
+                "overrides": overrides,
+            }
+        })),
+        Err(e) => {
+            error!("Failed to list user intent limits: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve intent limits"
+            }))
+        }
+    }
+}
+
+#[post("/admin/intent-limits")]
+pub async fn set_user_intent_limit(
+    body: web::Bytes,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let request: UserIntentLimitRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid request body: {}", e)
+            }));
+        }
+    };
+
+    if !request.user_address.starts_with("0x") || request.user_address.len() != 42 {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": "user_address must be a 20-byte hex address"
+        }));
+    }
+
+    let result = match request.max_active {
+        Some(max_active) if max_active < 0 => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": "max_active must be 0 (unlimited) or positive"
+            }));
+        }
+        Some(max_active) => app_state
+            .database
+            .set_user_intent_limit(&request.user_address, max_active, request.note.as_deref())
+            .map(|limit| json!({ "override": limit })),
+        None => app_state
+            .database
+            .clear_user_intent_limit(&request.user_address)
+            .map(|removed| json!({ "removed": removed })),
+    };
+
+    match result {
+        Ok(data) => {
+            info!(
+                "🚦 Intent limit for {} set to {:?}",
+                request.user_address.to_lowercase(),
+                request.max_active
+            );
+            HttpResponse::Ok().json(json!({
+                "status": "success",
+                "data": data
+            }))
+        }
+        Err(e) => {
+            error!("Failed to update intent limit: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to update intent limit"
+            }))
+        }
+    }
+}
+
+#[get("/admin/compliance")]
+pub async fn list_compliance_screenings(
+    app_state: web::Data<AppState>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let action = match query.get("action").map(|a| ComplianceAction::parse(a)) {
+        Some(Ok(action)) => Some(action),
+        Some(Err(e)) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+        None => None,
+    };
+    let limit: i64 = query
+        .get("limit")
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(100)
+        .min(500);
+
+    match app_state
+        .database
+        .list_compliance_screenings(action.map(|a| a.as_str()), limit)
+    {
+        Ok(screenings) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": {
+                "enabled": app_state.compliance.enabled(),
+                "screenings": screenings,
+            }
+        })),
+        Err(e) => {
+            error!("Failed to list compliance screenings: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve compliance screenings"
+            }))
+        }
+    }
+}
+
+#[get("/admin/deposits/discrepancies")]
+pub async fn list_deposit_discrepancies(
+    app_state: web::Data<AppState>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let kind = match query.get("kind").map(|k| DepositKind::parse(k)) {
+        Some(Ok(kind)) => Some(kind),
+        Some(Err(e)) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+        None => None,
+    };
+
+    match app_state
+        .bridge_coordinator
+        .list_deposit_discrepancies(kind)
+    {
+        Ok(discrepancies) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": {
+                "enabled": app_state.deposit_check.enabled,
+                "surplus": surplus_totals(&discrepancies),
+                "discrepancies": discrepancies,
+            }
+        })),
+        Err(e) => {
+            error!("Failed to list deposit discrepancies: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve deposit discrepancies"
+            }))
+        }
+    }
+}
+
+#[post("/admin/compliance/override")]
+pub async fn override_compliance_action(
+    body: web::Bytes,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let request: ComplianceOverrideRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid request body: {}", e)
+            }));
+        }
+    };
+
+    let action = match ComplianceAction::parse(&request.action) {
+        Ok(action) => action,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+    };
+
+    if request.note.trim().is_empty() {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": "note is required"
+        }));
+    }
+
+    match app_state.database.override_compliance_action(
+        &request.intent_id,
+        action.as_str(),
+        request.note.trim(),
+    ) {
+        Ok(Some(screening)) => {
+            info!(
+                "🔎 Compliance action for {} overridden to {}: {}",
+                screening.intent_id,
+                screening.action,
+                request.note.trim()
+            );
+            HttpResponse::Ok().json(json!({
+                "status": "success",
+                "data": screening
+            }))
+        }
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": format!("No compliance screening for {}", request.intent_id)
+        })),
+        Err(e) => {
+            error!("Failed to override compliance action: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to override compliance action"
+            }))
+        }
+    }
+}
+
+#[post("/admin/contracts/acknowledge-upgrade")]
+pub async fn acknowledge_contract_upgrade(
+    body: web::Bytes,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let request: AcknowledgeUpgradeRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid request body: {}", e)
+            }));
+        }
+    };
+
+    let contract = match GuardedContract::parse(&request.contract) {
+        Ok(contract) => contract,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+    };
+
+    match app_state
+        .contract_guard
+        .acknowledge_upgrade(&request.chain, contract)
+        .await
+    {
+        Ok(status) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": status
+        })),
+        Err(e) => HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": e.to_string()
+        })),
+    }
+}
+
+#[get("/admin/backlog")]
+pub async fn get_backlog_progress(app_state: web::Data<AppState>) -> impl Responder {
+    let progress = app_state
+        .bridge_coordinator
+        .backlog_progress
+        .read()
+        .await
+        .clone();
+
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": progress
+    }))
+}
+
+#[get("/admin/claim-auth/failures")]
+pub async fn get_claim_auth_failures(app_state: web::Data<AppState>) -> impl Responder {
+    match app_state.database.list_claim_auth_failures() {
+        Ok(failures) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": failures
+        })),
+        Err(e) => {
+            error!("Failed to list claim auth failures: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }))
+        }
+    }
+}
+
+#[get("/admin/stuck")]
+pub async fn get_stuck_intents(app_state: web::Data<AppState>) -> impl Responder {
+    match app_state.stuck_detector.scan().await {
+        Ok(intents) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": {
+                "intents": intents,
+                "summary": app_state.stuck_detector.summary().await,
+            }
+        })),
+        Err(e) => {
+            error!("Failed to scan for stuck intents: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }))
+        }
+    }
+}
+
+#[get("/admin/webhooks")]
+pub async fn list_webhooks(app_state: web::Data<AppState>) -> impl Responder {
+    match app_state.webhooks.list_endpoints() {
+        Ok(endpoints) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": { "endpoints": endpoints }
+        })),
+        Err(e) => {
+            error!("Failed to list webhooks: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to list webhooks"
+            }))
+        }
+    }
+}
+
+#[post("/admin/webhooks")]
+pub async fn create_webhook(body: web::Bytes, app_state: web::Data<AppState>) -> impl Responder {
+    let request: CreateWebhookRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid request body: {}", e)
+            }));
+        }
+    };
+
+    match app_state.webhooks.create_endpoint(
+        &request.url,
+        request.description.as_deref(),
+        request.filter.as_deref(),
+    ) {
+        Ok((endpoint, secret)) => HttpResponse::Created().json(json!({
+            "status": "success",
+            "data": { "endpoint": endpoint, "signing_key": secret }
+        })),
+        Err(e) => {
+            warn!("Failed to create webhook for {}: {}", request.url, e);
+            HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }))
+        }
+    }
+}
+
+/// Issue a new signing secret without a delivery gap: the old secrets keep
+/// signing alongside it until the overlap ends.
+#[post("/admin/webhooks/{endpoint_id}/rotate")]
+pub async fn rotate_webhook_secret(
+    path: web::Path<String>,
+    body: web::Bytes,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let request: RotateWebhookSecretRequest = if body.is_empty() {
+        RotateWebhookSecretRequest::default()
+    } else {
+        match serde_json::from_slice(&body) {
+            Ok(req) => req,
+            Err(e) => {
+                return HttpResponse::BadRequest().json(json!({
+                    "status": "error",
+                    "message": format!("Invalid request body: {}", e)
+                }));
+            }
+        }
+    };
+
+    match app_state
+        .webhooks
+        .rotate_secret(&path, request.overlap_secs)
+    {
+        Ok(Some(secret)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": { "signing_key": secret }
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": format!("Webhook {} not found", path.as_str())
+        })),
+        Err(e) => {
+            error!("Failed to rotate webhook {}: {}", path.as_str(), e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to rotate webhook secret"
+            }))
+        }
+    }
+}
+
+/// Choose which events an endpoint receives; see `WebhookFilter`.
+#[post("/admin/webhooks/{endpoint_id}/filter")]
+pub async fn set_webhook_filter(
+    path: web::Path<String>,
+    body: web::Bytes,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let request: WebhookFilterRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid request body: {}", e)
+            }));
+        }
+    };
+
+    match app_state
+        .webhooks
+        .set_filter(&path, request.filter.as_deref())
+    {
+        Ok(Some(endpoint)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": { "endpoint": endpoint }
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": format!("Webhook {} not found", path.as_str())
+        })),
+        Err(e) => HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": format!("{:#}", e)
+        })),
+    }
+}
+
+/// Show whether a sample event passes an endpoint's filter, clause by
+/// clause, and optionally send it as a test delivery.
+#[post("/admin/webhooks/{endpoint_id}/test")]
+pub async fn test_webhook(
+    path: web::Path<String>,
+    body: web::Bytes,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let request: TestWebhookRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid request body: {}", e)
+            }));
+        }
+    };
+
+    match app_state
+        .webhooks
+        .test_delivery(&path, &request.event, &request.data, request.deliver)
+        .await
+    {
+        Ok(Some(result)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": result
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": format!("Webhook {} not found", path.as_str())
+        })),
+        Err(e) => {
+            error!("Failed to test webhook {}: {}", path.as_str(), e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to test webhook"
+            }))
+        }
+    }
+}
+
+#[delete("/admin/webhooks/{endpoint_id}")]
+pub async fn delete_webhook(
+    path: web::Path<String>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    match app_state.webhooks.delete_endpoint(&path) {
+        Ok(true) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": { "deleted": path.as_str() }
+        })),
+        Ok(false) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": format!("Webhook {} not found", path.as_str())
+        })),
+        Err(e) => {
+            error!("Failed to delete webhook {}: {}", path.as_str(), e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to delete webhook"
+            }))
+        }
+    }
+}
+
+/// Fills left unclaimable because the source intent was refunded.
+#[get("/admin/orphaned-fills")]
+pub async fn list_orphaned_fills(
+    app_state: web::Data<AppState>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let status = match query.get("status").map(|s| OrphanStatus::parse(s)) {
+        Some(Ok(status)) => Some(status),
+        Some(Err(e)) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+        None => None,
+    };
+
+    match app_state.bridge_coordinator.list_orphaned_fills(status) {
+        Ok(orphans) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": {
+                "count": orphans.len(),
+                "orphaned_fills": orphans,
+            }
+        })),
+        Err(e) => {
+            error!("Failed to list orphaned fills: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve orphaned fills"
+            }))
+        }
+    }
+}
+
+/// Operator approval for a recovery above the auto-recovery threshold.
+#[post("/admin/orphaned-fills/{intent_id}/approve")]
+pub async fn approve_orphaned_fill(
+    path: web::Path<String>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let intent_id = path.to_lowercase();
+    match app_state
+        .bridge_coordinator
+        .approve_orphaned_fill(&intent_id)
+    {
+        Ok(Ok(orphan)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": orphan
+        })),
+        Ok(Err(rejection @ ApprovalRejection::NotFound)) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": rejection.to_string()
+        })),
+        Ok(Err(rejection)) => HttpResponse::Conflict().json(json!({
+            "status": "error",
+            "message": rejection.to_string()
+        })),
+        Err(e) => {
+            error!("Failed to approve orphaned fill {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to approve orphaned fill"
+            }))
+        }
+    }
+}
+
+/// Start, or extend, verbose capture of one intent.
+#[post("/admin/debug-captures")]
+pub async fn start_debug_capture(
+    body: web::Bytes,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let request: DebugCaptureRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid request body: {}", e)
+            }));
+        }
+    };
+    if H256Hex::parse("intent_id", &request.intent_id).is_err() {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": "intent_id must be a 32-byte hex string (0x...)"
+        }));
+    }
+
+    match app_state.bridge_coordinator.debug_capture.start(
+        &request.intent_id,
+        &request.operator,
+        &request.reason,
+        request.ttl_secs.map(std::time::Duration::from_secs),
+    ) {
+        Ok(Ok(capture)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": capture
+        })),
+        Ok(Err(rejection)) => HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": rejection.to_string()
+        })),
+        Err(e) => {
+            error!(
+                "Failed to start debug capture of {}: {}",
+                request.intent_id, e
+            );
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to start debug capture"
+            }))
+        }
+    }
+}
+
+/// Captures still recording, soonest to expire first.
+#[get("/admin/debug-captures")]
+pub async fn list_debug_captures(app_state: web::Data<AppState>) -> impl Responder {
+    match app_state.bridge_coordinator.debug_capture.list_active() {
+        Ok(captures) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": {
+                "count": captures.len(),
+                "captures": captures,
+            }
+        })),
+        Err(e) => {
+            error!("Failed to list debug captures: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve debug captures"
+            }))
+        }
+    }
+}
+
+/// Everything recorded for one intent, oldest first, while the recording is
+/// retained.
+#[get("/admin/debug-captures/{intent_id}")]
+pub async fn get_debug_capture(
+    path: web::Path<String>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    match app_state.bridge_coordinator.debug_capture.recording(&path) {
+        Ok(Some((capture, entries))) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": {
+                "capture": capture,
+                "active": capture.expires_at > Utc::now(),
+                "count": entries.len(),
+                "entries": entries,
+            }
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": format!("No debug capture for {}", path.as_str())
+        })),
+        Err(e) => {
+            error!("Failed to get debug capture of {}: {}", path.as_str(), e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve debug capture"
+            }))
+        }
+    }
+}
+
+/// Stop recording now; what was recorded stays readable until purged.
+#[delete("/admin/debug-captures/{intent_id}")]
+pub async fn stop_debug_capture(
+    path: web::Path<String>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    match app_state.bridge_coordinator.debug_capture.stop(&path) {
+        Ok(true) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "message": format!("Debug capture of {} stopped", path.as_str())
+        })),
+        Ok(false) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": format!("{} is not being captured", path.as_str())
+        })),
+        Err(e) => {
+            error!("Failed to stop debug capture of {}: {}", path.as_str(), e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to stop debug capture"
+            }))
+        }
+    }
+}
+
+/// Registration and settlement jobs, most recently updated first.
+#[get("/admin/jobs")]
+pub async fn list_relayer_jobs(
+    app_state: web::Data<AppState>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let status = query.get("status").map(String::as_str);
+    if let Some(status) = status.filter(|status| !JOB_STATUSES.contains(status)) {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": format!("Unknown job status: {} (use {})", status, JOB_STATUSES.join(", "))
+        }));
+    }
+    let kind = match query.get("kind").map(|kind| (kind, JobKind::parse(kind))) {
+        Some((_, Some(kind))) => Some(kind),
+        Some((kind, None)) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Unknown job kind: {} (use registration or settlement)", kind)
+            }));
+        }
+        None => None,
+    };
+    let limit = query
+        .get("limit")
+        .and_then(|limit| limit.parse::<i64>().ok())
+        .unwrap_or(100)
+        .clamp(1, 500);
+
+    match app_state.bridge_coordinator.jobs.list(status, kind, limit) {
+        Ok(jobs) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": {
+                "count": jobs.len(),
+                "jobs": jobs,
+            }
+        })),
+        Err(e) => {
+            error!("Failed to list relayer jobs: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve relayer jobs"
+            }))
+        }
+    }
+}
+
+/// Retry a dead or cancelled job with fresh attempts, or cancel one.
+#[post("/admin/jobs/{id}/{action}")]
+pub async fn update_relayer_job(
+    path: web::Path<(i64, String)>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let (id, action) = path.into_inner();
+    let jobs = &app_state.bridge_coordinator.jobs;
+    let result = match action.as_str() {
+        "retry" => jobs.retry(id),
+        "cancel" => jobs.cancel(id),
+        other => {
+            return HttpResponse::NotFound().json(json!({
+                "status": "error",
+                "message": format!("Unknown action: {} (use retry or cancel)", other)
+            }));
+        }
+    };
+
+    match result {
+        Ok(Ok(job)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": job
+        })),
+        Ok(Err(rejection @ JobRejection::NotFound)) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": rejection.to_string()
+        })),
+        Ok(Err(rejection)) => HttpResponse::Conflict().json(json!({
+            "status": "error",
+            "message": rejection.to_string()
+        })),
+        Err(e) => {
+            error!("Failed to {} relayer job {}: {}", action, id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("Failed to {} relayer job", action)
+            }))
+        }
+    }
+}
+
+/// Operator note or manual status override on an intent.
+#[post("/admin/intents/{intent_id}/annotations")]
+pub async fn annotate_intent(
+    path: web::Path<String>,
+    body: web::Bytes,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let request: IntentAnnotationRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid request body: {}", e)
+            }));
+        }
+    };
+
+    let intent_id = path.into_inner();
+    match app_state.bridge_coordinator.annotate_intent(
+        &intent_id,
+        &request.operator,
+        &request.reason,
+        request.status.as_deref(),
+    ) {
+        Ok(Ok(annotation)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": annotation
+        })),
+        Ok(Err(rejection @ AnnotationRejection::NotFound)) => {
+            HttpResponse::NotFound().json(json!({
+                "status": "error",
+                "message": rejection.to_string()
+            }))
+        }
+        Ok(Err(rejection)) => HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": rejection.to_string()
+        })),
+        Err(e) => {
+            error!("Failed to annotate intent {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to annotate intent"
+            }))
+        }
+    }
+}
+
+/// Chain events and operator actions on an intent, oldest first.
+#[get("/admin/intents/{intent_id}/timeline")]
+pub async fn get_intent_timeline(
+    path: web::Path<String>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let intent_id = path.into_inner();
+    match app_state.bridge_coordinator.intent_timeline(&intent_id) {
+        Ok(Some(timeline)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": timeline
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Intent not found"
+        })),
+        Err(e) => {
+            error!("Failed to build timeline for {}: {}", intent_id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve intent timeline"
+            }))
+        }
+    }
+}
+
+/// Relayer instances sharing the database and the partitions each works on.
+#[get("/admin/partitions")]
+pub async fn get_work_partitions(app_state: web::Data<AppState>) -> impl Responder {
+    match app_state.bridge_coordinator.partition_overview() {
+        Ok(overview) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": overview
+        })),
+        Err(e) => {
+            error!("Failed to read work partitions: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve work partitions"
+            }))
+        }
+    }
+}
+
+/// Every listed token, enabled or not.
+#[get("/admin/tokens")]
+pub async fn list_token_listings(app_state: web::Data<AppState>) -> impl Responder {
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": app_state.bridge_coordinator.tokens.listings()
+    }))
+}
+
+/// List a token or replace its listing, e.g. with mainnet addresses.
+#[post("/admin/tokens")]
+pub async fn set_token_listing(body: web::Bytes, app_state: web::Data<AppState>) -> impl Responder {
+    let request: TokenListingRequest = match serde_json::from_slice(&body) {
+        Ok(req) => req,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("Invalid request body: {}", e)
+            }));
+        }
+    };
+
+    let listing = match TokenType::from_symbol(&request.symbol)
+        .and_then(|token| request.token.listing(token))
+    {
+        Ok(listing) => listing,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+    };
+
+    store_token_listing(&app_state, listing)
+}
+
+#[post("/admin/tokens/{symbol}/{action}")]
+pub async fn toggle_token_listing(
+    path: web::Path<(String, String)>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let (symbol, action) = path.into_inner();
+    let enabled = match action.as_str() {
+        "enable" => true,
+        "disable" => false,
+        other => {
+            return HttpResponse::NotFound().json(json!({
+                "status": "error",
+                "message": format!("Unknown action: {} (use enable or disable)", other)
+            }));
+        }
+    };
+
+    let tokens = &app_state.bridge_coordinator.tokens;
+    let listing = match TokenType::from_symbol(&symbol).map(|token| tokens.listing(token)) {
+        Ok(Some(listing)) => listing,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(json!({
+                "status": "error",
+                "message": format!("Token {} is not listed", symbol.to_uppercase())
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+    };
+
+    store_token_listing(&app_state, TokenListing { enabled, ..listing })
+}
+
+/// Persist first so the change survives a restart, then apply it.
+fn store_token_listing(app_state: &AppState, listing: TokenListing) -> HttpResponse {
+    if let Err(e) = app_state.database.save_token_listing(&listing) {
+        error!("Failed to save token listing: {}", e);
+        return HttpResponse::InternalServerError().json(json!({
+            "status": "error",
+            "message": "Failed to save token listing"
+        }));
+    }
+
+    if let Err(e) = app_state.bridge_coordinator.tokens.upsert(listing.clone()) {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": e.to_string()
+        }));
+    }
+
+    info!(
+        "🪙 {} listed on ethereum {:?} and mantle {:?} (enabled: {})",
+        listing.token.symbol(),
+        listing.ethereum,
+        listing.mantle,
+        listing.enabled
+    );
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": listing
+    }))
+}
+
+/// Admin keys, revoked ones included; the keys themselves are never shown
+/// again after they are issued.
+#[get("/admin/api-keys")]
+pub async fn list_admin_keys(app_state: web::Data<AppState>) -> impl Responder {
+    match app_state.database.list_admin_api_keys() {
+        Ok(keys) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": keys
+        })),
+        Err(e) => {
+            error!("Failed to list admin keys: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve admin keys"
+            }))
+        }
+    }
+}
+
+#[post("/admin/api-keys")]
+pub async fn create_admin_key(
+    request: web::Json<CreateAdminKeyRequest>,
+    identity: web::ReqData<AdminIdentity>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let role = match AdminRole::parse(&request.role) {
+        Ok(role) => role,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
+        }
+    };
+    if request.name.trim().is_empty() {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": "name is required"
+        }));
+    }
+
+    match issue_admin_key(&app_state.database, &request.name, role, &identity.actor) {
+        Ok(issued) => HttpResponse::Created().json(json!({
+            "status": "success",
+            "data": issued
+        })),
+        Err(e) => {
+            error!("Failed to issue admin key: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to issue admin key"
+            }))
+        }
+    }
+}
+
+#[delete("/admin/api-keys/{id}")]
+pub async fn revoke_admin_key(
+    path: web::Path<i32>,
+    identity: web::ReqData<AdminIdentity>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let id = path.into_inner();
 
-    if !matches!(
-        (request.source_chain.as_str(), request.dest_chain.as_str()),
-        ("ethereum", "mantle") | ("mantle", "ethereum")
-    ) {
-        return HttpResponse::BadRequest().json(InitiateBridgeResponse {
-            success: false,
-            intent_id: String::new(),
-            commitment: String::new(),
-            message: "Invalid chain pair".to_string(),
-            error: Some("Must be ethereum<->mantle".to_string()),
-        });
+    match app_state.database.revoke_admin_api_key(id) {
+        Ok(true) => {
+            info!("🔑 Admin key {} revoked by {}", id, identity.actor);
+            HttpResponse::Ok().json(json!({
+                "status": "success",
+                "message": "Admin key revoked"
+            }))
+        }
+        Ok(false) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Admin key not found or already revoked"
+        })),
+        Err(e) => {
+            error!("Failed to revoke admin key {}: {}", id, e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to revoke admin key"
+            }))
+        }
     }
+}
 
-    let _token_type = match TokenType::from_address(&request.source_token) {
-        Ok(t) => t,
+#[get("/admin/client-keys")]
+pub async fn list_client_keys(app_state: web::Data<AppState>) -> impl Responder {
+    match app_state.database.list_api_keys() {
+        Ok(keys) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": keys
+        })),
         Err(e) => {
-            return HttpResponse::BadRequest().json(InitiateBridgeResponse {
-                success: false,
-                intent_id: String::new(),
-                commitment: String::new(),
-                message: "Unsupported token".to_string(),
-                error: Some(e.to_string()),
-            });
+            error!("Failed to list API keys: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve API keys"
+            }))
+        }
+    }
+}
+
+#[post("/admin/client-keys")]
+pub async fn create_client_key(
+    request: web::Json<CreateApiKeyRequest>,
+    identity: web::ReqData<AdminIdentity>,
+    app_state: web::Data<AppState>,
+) -> impl Responder {
+    let scopes = match request
+        .scopes
+        .iter()
+        .map(|scope| ApiScope::parse(scope))
+        .collect::<anyhow::Result<Vec<_>>>()
+    {
+        Ok(scopes) => scopes,
+        Err(e) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }));
         }
     };
+    if request.name.trim().is_empty() || scopes.is_empty() {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": "name and at least one scope are required"
+        }));
+    }
+    if request.rate_limit_per_min.is_some_and(|rate| rate <= 0) {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": "rate_limit_per_min must be positive"
+        }));
+    }
 
-    if let Err(e) = app_state.database.store_intent_privacy_params(
-        &intent_id,
-        &request.commitment,
-        &request.encrypted_secret,
-        &request.encrypted_nullifier,
-        &request.claim_auth,
-        &request.recipient,
+    match issue_api_key(
+        &app_state.database,
+        &request.name,
+        &scopes,
+        request.rate_limit_per_min,
+        &identity.actor,
     ) {
-        error!("Failed to store privacy params for {}: {}", intent_id, e);
-        return HttpResponse::InternalServerError().json(InitiateBridgeResponse {
-            success: false,
-            intent_id: intent_id.clone(),
-            commitment: String::new(),
-            message: "Failed to store privacy parameters".to_string(),
-            error: Some(e.to_string()),
-        });
+        Ok(issued) => HttpResponse::Created().json(json!({
+            "status": "success",
+            "data": issued
+        })),
+        Err(e) => {
+            error!("Failed to issue API key: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to issue API key"
+            }))
+        }
     }
-
-    info!("✅ Bridge intent created: {}", intent_id);
-
-    HttpResponse::Ok().json(InitiateBridgeResponse {
-        success: true,
-        intent_id: intent_id.clone(),
-        commitment: request.commitment.clone(),
-        message: format!(
-            "Bridge intent created. Relayer will process on {}",
-            request.source_chain
-        ),
-        error: None,
-    })
 }
 
-#[get("/bridge/intent/{intent_id}")]
-pub async fn get_intent_status(
+#[delete("/admin/client-keys/{id}")]
+pub async fn revoke_client_key(
+    path: web::Path<i32>,
+    identity: web::ReqData<AdminIdentity>,
     app_state: web::Data<AppState>,
-    path: web::Path<String>,
 ) -> impl Responder {
-    let intent_id = path.into_inner();
-
-    match app_state.database.get_intent_by_id(&intent_id) {
-        Ok(Some(intent)) => {
-            let privacy_params = app_state
-                .database
-                .get_intent_privacy_params(&intent_id)
-                .ok();
+    let id = path.into_inner();
 
-            HttpResponse::Ok().json(IntentStatusResponse {
-                intent_id: intent.id,
-                status: intent.status.as_str().to_string(),
-                source_chain: intent.source_chain,
-                dest_chain: intent.dest_chain,
-                source_token: intent.source_token,
-                dest_token: intent.dest_token,
-                amount: intent.amount,
-                commitment: intent.source_commitment,
-                dest_fill_txid: intent.dest_fill_txid,
-                source_complete_txid: intent.source_complete_txid,
-                deadline: intent.deadline,
-                created_at: intent.created_at,
-                updated_at: intent.updated_at,
-                has_privacy: privacy_params.is_some(),
-            })
+    match app_state.database.revoke_api_key(id) {
+        Ok(true) => {
+            // Other instances drop it when their cached copy expires
+            app_state.public_access.forget_key(id);
+            info!("🔑 API key {} revoked by {}", id, identity.actor);
+            HttpResponse::Ok().json(json!({
+                "status": "success",
+                "message": "API key revoked"
+            }))
         }
-        Ok(None) => HttpResponse::NotFound().json(json!({
+        Ok(false) => HttpResponse::NotFound().json(json!({
             "status": "error",
-            "message": "Intent not found"
+            "message": "API key not found or already revoked"
         })),
         Err(e) => {
-            error!("Failed to get intent {}: {}", intent_id, e);
+            error!("Failed to revoke API key {}: {}", id, e);
             HttpResponse::InternalServerError().json(json!({
                 "status": "error",
-                "message": "Failed to retrieve intent"
+                "message": "Failed to revoke API key"
             }))
         }
     }
 }
 
-#[get("/bridge/intents")]
-pub async fn list_intents(
+/// Admin requests, newest first. `actor` filters by key name (`hmac` for
+/// the shared secret, `anonymous` for rejected callers).
+#[get("/admin/audit-log")]
+pub async fn get_admin_audit_log(
     app_state: web::Data<AppState>,
-    query: web::Query<std::collections::HashMap<String, String>>,
+    query: web::Query<HashMap<String, String>>,
 ) -> impl Responder {
-    let status_filter = query.get("status").map(|s| s.as_str());
-    let chain_filter = query.get("chain").map(|s| s.as_str());
-    let limit: usize = query
+    let actor = query.get("actor").map(String::as_str);
+    let limit = query
         .get("limit")
-        .and_then(|s| s.parse().ok())
-        .unwrap_or(50)
-        .min(200);
+        .and_then(|limit| limit.parse::<i64>().ok())
+        .unwrap_or(100)
+        .clamp(1, 1000);
 
-    match app_state
-        .database
-        .list_intents(status_filter, chain_filter, limit)
-    {
-        Ok(intents) => HttpResponse::Ok().json(json!({
+    match app_state.database.list_admin_audit_log(actor, limit) {
+        Ok(entries) => HttpResponse::Ok().json(json!({
             "status": "success",
-            "count": intents.len(),
-            "data": intents
+            "data": {
+                "count": entries.len(),
+                "entries": entries,
+            }
         })),
         Err(e) => {
-            error!("Failed to list intents: {}", e);
+            error!("Failed to list admin audit log: {}", e);
             HttpResponse::InternalServerError().json(json!({
                 "status": "error",
-                "message": "Failed to retrieve intents"
+                "message": "Failed to retrieve admin audit log"
             }))
         }
     }
@@ -276,24 +2682,80 @@ pub async fn indexer_event(
         request.event_type, request.chain, request.transaction_hash
     );
 
-    match request.event_type.as_str() {
-        "intent_created" => handle_intent_created_event(&app_state, &request).await,
-        "intent_filled" => handle_intent_filled_event(&app_state, &request).await,
-        "intent_registered" => handle_intent_registered_event(&app_state, &request).await,
-        "intent_settled" => handle_intent_settled_event(&app_state, &request).await,
-        "intent_refunded" => handle_intent_refunded_event(&app_state, &request).await,
-        "withdrawal_claimed" => handle_withdrawal_claimed_event(&app_state, &request).await,
+    let intent_id = request
+        .event_data
+        .get("intentId")
+        .and_then(|id| id.as_str())
+        .unwrap_or_default()
+        .to_string();
+    let response = queue_indexer_event(&app_state, &request);
+    capture_exchange(
+        &app_state,
+        &intent_id,
+        "indexer",
+        json!({
+            "event_type": request.event_type,
+            "chain": request.chain,
+            "transaction_hash": request.transaction_hash,
+            "block_number": request.block_number,
+            "log_index": request.log_index,
+            "event_data": request.event_data,
+        }),
+        response,
+    )
+    .await
+}
 
-        "root_synced" | "commitment_root_synced" | "fill_root_synced" => {
-            handle_root_synced_event(&app_state, &request).await
-        }
+/// Store an indexer event for the ingestion consumer. The indexer retries
+/// on 5xx, which is also how it is told to back off while the queue is full.
+fn queue_indexer_event(
+    app_state: &web::Data<AppState>,
+    request: &IndexerEventRequest,
+) -> HttpResponse {
+    // Recorded by the relayer itself, never accepted from the indexer.
+    if matches!(
+        indexer_event_type(&request.event_type),
+        Ok(BridgeEventType::NullifierUsed) | Err(_)
+    ) {
+        warn!("Unknown event type: {}", request.event_type);
+        return HttpResponse::BadRequest().json(IndexerEventResponse {
+            success: false,
+            message: format!("Unknown event type: {}", request.event_type),
+            error: None,
+        });
+    }
 
-        _ => {
-            warn!("Unknown event type: {}", request.event_type);
-            HttpResponse::BadRequest().json(IndexerEventResponse {
+    let queue = &app_state.bridge_coordinator.ingestion;
+    if queue.is_saturated() {
+        warn!(
+            "⏳ Ingestion queue at {} pending events, pushing back on the indexer",
+            queue.policy.high_watermark
+        );
+        return HttpResponse::ServiceUnavailable()
+            .insert_header(("Retry-After", "5"))
+            .json(IndexerEventResponse {
                 success: false,
-                message: format!("Unknown event type: {}", request.event_type),
+                message: "Ingestion queue is full".to_string(),
                 error: None,
+            });
+    }
+
+    match queue.enqueue(IngestionSource::Indexer, request) {
+        Ok(stored) => HttpResponse::Ok().json(IndexerEventResponse {
+            success: true,
+            message: if stored {
+                "Event queued".to_string()
+            } else {
+                "Event already received".to_string()
+            },
+            error: None,
+        }),
+        Err(e) => {
+            error!("Failed to queue indexer event: {:#}", e);
+            HttpResponse::InternalServerError().json(IndexerEventResponse {
+                success: false,
+                message: "Failed to queue event".to_string(),
+                error: Some(e.to_string()),
             })
         }
     }
@@ -333,7 +2795,7 @@ pub async fn get_price(
 
     match app_state
         .price_feed
-        .get_exchange_rate(&from_token, &to_token)
+        .exchange_rate(from_token, to_token)
         .await
     {
         Ok(rate) => {
@@ -393,6 +2855,7 @@ pub async fn get_all_prices(app_state: web::Data<AppState>) -> impl Responder {
         status: "success".to_string(),
         timestamp: Utc::now().timestamp(),
         prices,
+        sources: app_state.price_feed.source_health().await,
     })
 }
 
@@ -426,7 +2889,7 @@ pub async fn convert_amount(
 
     match app_state
         .price_feed
-        .convert_amount(&from_token, &to_token, req.amount)
+        .convert_amount(from_token, to_token, req.amount)
         .await
     {
         Ok(output_amount) => {
@@ -452,6 +2915,92 @@ pub async fn convert_amount(
     }
 }
 
+// ============================================================================
+// MERKLE PROOFS
+// ============================================================================
+
+#[get("/merkle/{tree}/roots")]
+pub async fn get_root_window(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+) -> impl Responder {
+    let tree = path.into_inner();
+
+    match app_state.merkle_manager.root_window(&tree).await {
+        Ok(window) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": window
+        })),
+        Err(e) => {
+            warn!("Failed to load root window for {}: {}", tree, e);
+            HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }))
+        }
+    }
+}
+
+#[get("/merkle/{tree}/proof")]
+pub async fn get_merkle_proof(
+    app_state: web::Data<AppState>,
+    path: web::Path<String>,
+    query: web::Query<MerkleProofQuery>,
+) -> impl Responder {
+    let tree = path.into_inner();
+
+    match app_state
+        .merkle_manager
+        .proof_at_root(&tree, &query.leaf, query.root.as_deref())
+        .await
+    {
+        Ok(proof) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": proof
+        })),
+        Err(e) => {
+            warn!(
+                "Failed to build proof in {} for {}: {}",
+                tree, query.leaf, e
+            );
+            HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": e.to_string()
+            }))
+        }
+    }
+}
+
+/// Trees the startup consistency check found broken and repaired.
+#[get("/admin/merkle/repairs")]
+pub async fn list_merkle_repairs(
+    app_state: web::Data<AppState>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let limit = query
+        .get("limit")
+        .and_then(|limit| limit.parse::<i64>().ok())
+        .unwrap_or(100)
+        .clamp(1, 1000);
+
+    match app_state.database.list_merkle_repairs(limit) {
+        Ok(repairs) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": {
+                "count": repairs.len(),
+                "repairs": repairs,
+            }
+        })),
+        Err(e) => {
+            error!("Failed to list merkle repairs: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve merkle repairs"
+            }))
+        }
+    }
+}
+
 // ============================================================================
 // METRICS & MONITORING
 // ============================================================================
@@ -459,6 +3008,20 @@ pub async fn convert_amount(
 #[get("/metrics")]
 pub async fn get_metrics(app_state: web::Data<AppState>) -> impl Responder {
     let metrics = app_state.bridge_coordinator.get_metrics().await;
+    let queues = match app_state.bridge_coordinator.queue_health().await {
+        Ok(queues) => json!(queues),
+        Err(e) => {
+            error!("Failed to compute queue gauges: {}", e);
+            serde_json::Value::Null
+        }
+    };
+    let ingestion = match app_state.bridge_coordinator.ingestion.lag() {
+        Ok(lag) => json!(lag),
+        Err(e) => {
+            error!("Failed to compute ingestion lag: {}", e);
+            serde_json::Value::Null
+        }
+    };
 
     HttpResponse::Ok().json(json!({
         "status": "success",
@@ -468,6 +3031,16 @@ pub async fn get_metrics(app_state: web::Data<AppState>) -> impl Responder {
             "successful_bridges": metrics.successful_bridges,
             "failed_intents": metrics.failed_intents,
             "volumes_by_token": metrics.volumes_by_token,
+            "process_start_time": metrics.process_start_time,
+            "counters_since": metrics.counters_since,
+            "queues": queues,
+            "stuck_intents": app_state.stuck_detector.summary().await,
+            "tx_queue_depth": {
+                "ethereum": app_state.ethereum_relayer.tx_queue.depth(),
+                "mantle": app_state.mantle_relayer.tx_queue.depth(),
+            },
+            "outbound_http": app_state.outbound_http.metrics(),
+            "ingestion": ingestion,
         }
     }))
 }
@@ -489,14 +3062,88 @@ pub async fn get_stats(app_state: web::Data<AppState>) -> impl Responder {
     }
 }
 
+/// Amount histograms, fill rate and abandoned intents per token and
+/// direction over the last `days` days.
+#[get("/stats/routes")]
+pub async fn get_route_stats(
+    app_state: web::Data<AppState>,
+    query: web::Query<HashMap<String, String>>,
+) -> impl Responder {
+    let policy = app_state.route_analytics;
+    let days = match query.get("days").map(|d| d.parse::<i64>()) {
+        Some(Ok(days)) if (1..=policy.max_days).contains(&days) => days,
+        Some(_) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": format!("days must be between 1 and {}", policy.max_days)
+            }));
+        }
+        None => policy.default_days,
+    };
+
+    let to = Utc::now();
+    match app_state
+        .bridge_coordinator
+        .route_analytics(to - chrono::Duration::days(days), to)
+    {
+        Ok(analytics) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "data": analytics
+        })),
+        Err(e) => {
+            error!("Failed to get route stats: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to retrieve route statistics"
+            }))
+        }
+    }
+}
+
+#[get("/api/versions")]
+pub async fn get_api_versions(app_state: web::Data<AppState>) -> impl Responder {
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": app_state.api_versions.overview()
+    }))
+}
+
+#[get("/sync/progress")]
+pub async fn get_sync_progress(app_state: web::Data<AppState>) -> impl Responder {
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": app_state.sync_progress.report().await
+    }))
+}
+
+#[get("/contracts/status")]
+pub async fn get_contract_status(app_state: web::Data<AppState>) -> impl Responder {
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "data": {
+            "enabled": app_state.contract_guard.enabled,
+            "hold_on_upgrade": app_state.contract_guard.hold_on_upgrade,
+            "contracts": app_state.contract_guard.statuses().await,
+        }
+    }))
+}
+
 #[get("/health")]
 pub async fn health_check(app_state: web::Data<AppState>) -> impl Responder {
     // Check if critical components are healthy
     let ethereum_healthy = app_state.ethereum_relayer.health_check().await.is_ok();
     let mantle_healthy = app_state.mantle_relayer.health_check().await.is_ok();
     let db_healthy = app_state.database.health_check().is_ok();
+    let queues = app_state.bridge_coordinator.queue_health().await.ok();
+    let queues_healthy = queues.as_ref().is_some_and(|q| q.healthy);
+
+    let sync_ready = app_state.sync_progress.is_ready();
+
+    // A paused contract is an upstream decision, reported but not a reason to restart
+    let contract_alerts = app_state.contract_guard.alerts().await;
 
-    let overall_healthy = ethereum_healthy && mantle_healthy && db_healthy;
+    let components_healthy = ethereum_healthy && mantle_healthy && db_healthy && queues_healthy;
+    let overall_healthy = components_healthy && sync_ready;
 
     let status_code = if overall_healthy {
         actix_web::http::StatusCode::OK
@@ -505,23 +3152,76 @@ pub async fn health_check(app_state: web::Data<AppState>) -> impl Responder {
     };
 
     HttpResponse::build(status_code).json(json!({
-        "status": if overall_healthy { "healthy" } else { "unhealthy" },
+        "status": if overall_healthy {
+            "healthy"
+        } else if components_healthy {
+            "syncing"
+        } else {
+            "unhealthy"
+        },
         "timestamp": Utc::now().to_rfc3339(),
         "components": {
             "ethereum_relayer": if ethereum_healthy { "up" } else { "down" },
             "mantle_relayer": if mantle_healthy { "up" } else { "down" },
-            "database": if db_healthy { "up" } else { "down" }
-        }
+            "database": if db_healthy { "up" } else { "down" },
+            "queues": if queues_healthy { "up" } else { "degraded" },
+            "sync": if sync_ready { "up" } else { "syncing" },
+            "contracts": if contract_alerts.is_empty() { "up" } else { "held" }
+        },
+        "queue_alerts": queues.map(|q| q.alerts).unwrap_or_default(),
+        "contract_alerts": contract_alerts
     }))
 }
 
+/// Public status page feed, served outside the versioned API so status page
+/// providers can poll a fixed URL.
+#[get("/statuspage.json")]
+pub async fn get_status_page(app_state: web::Data<AppState>) -> impl Responder {
+    let feed = app_state.status_page.feed(&app_state).await;
+
+    HttpResponse::Ok()
+        .insert_header((
+            actix_web::http::header::CACHE_CONTROL,
+            format!(
+                "public, max-age={}",
+                app_state.status_page.cache_ttl.as_secs()
+            ),
+        ))
+        .json(feed)
+}
+
+/// Prometheus scrape target, served outside the versioned API next to the
+/// JSON `/api/v1/metrics`.
+#[get("/metrics")]
+pub async fn get_prometheus_metrics(app_state: web::Data<AppState>) -> impl Responder {
+    match app_state.bridge_coordinator.prometheus.render() {
+        Ok(text) => HttpResponse::Ok().content_type(CONTENT_TYPE).body(text),
+        Err(e) => {
+            error!("Failed to render Prometheus metrics: {}", e);
+            HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to render metrics"
+            }))
+        }
+    }
+}
+
 #[get("/")]
-pub async fn root() -> impl Responder {
+pub async fn root(app_state: web::Data<AppState>) -> impl Responder {
+    let supported_tokens: Vec<&str> = app_state
+        .bridge_coordinator
+        .tokens
+        .listings()
+        .iter()
+        .filter(|listing| listing.enabled)
+        .map(|listing| listing.token.symbol())
+        .collect();
+
     HttpResponse::Ok().json(json!({
         "service": "Mantle-Ethereum Privacy Bridge",
         "version": "1.0.0",
         "status": "operational",
         "supported_chains": ["ethereum", "mantle"],
-        "supported_tokens": ["ETH", "USDC", "USDT", "WETH", "MNT"]
+        "supported_tokens": supported_tokens
     }))
 }
@@ -0,0 +1,263 @@
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tracing::error;
+
+use crate::{AppState, database::model::IntentOutcome};
+
+/// Completions and success rates cover this trailing window.
+const WINDOW: chrono::Duration = chrono::Duration::hours(1);
+const DIRECTIONS: [(&str, &str); 2] = [("ethereum", "mantle"), ("mantle", "ethereum")];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeStatus {
+    Operational,
+    Degraded,
+    MajorOutage,
+}
+
+/// Component checks from `/health`, reduced to up or not.
+#[derive(Debug, Clone, Copy)]
+pub struct ComponentHealth {
+    pub ethereum: bool,
+    pub mantle: bool,
+    pub database: bool,
+    pub processing: bool,
+    pub sync: bool,
+    pub contracts: bool,
+}
+
+impl ComponentHealth {
+    /// Public names of the components that are not up.
+    fn degraded(&self) -> Vec<&'static str> {
+        [
+            ("ethereum", self.ethereum),
+            ("mantle", self.mantle),
+            ("api", self.database),
+            ("intent_processing", self.processing),
+            ("chain_sync", self.sync),
+            ("contracts", self.contracts),
+        ]
+        .into_iter()
+        .filter(|(_, up)| !up)
+        .map(|(name, _)| name)
+        .collect()
+    }
+
+    /// Without the database or either chain no bridge can complete.
+    fn status(&self) -> BridgeStatus {
+        if !self.database || !self.ethereum || !self.mantle {
+            BridgeStatus::MajorOutage
+        } else if self.degraded().is_empty() {
+            BridgeStatus::Operational
+        } else {
+            BridgeStatus::Degraded
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DirectionSummary {
+    pub direction: String,
+    pub completed: u64,
+    /// Failed or refunded.
+    pub unsuccessful: u64,
+    /// `None` when nothing finished in the window.
+    pub success_rate_pct: Option<f64>,
+}
+
+/// Unauthenticated summary behind `/statuspage.json`. Counts and component
+/// names only: no addresses, amounts, error messages or queue details.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusPageFeed {
+    pub status: BridgeStatus,
+    pub operational: bool,
+    pub updated_at: DateTime<Utc>,
+    pub completed_last_hour: u64,
+    pub average_completion_secs: Option<u64>,
+    pub directions: Vec<DirectionSummary>,
+    pub degraded_components: Vec<&'static str>,
+}
+
+pub fn build_feed(
+    components: ComponentHealth,
+    outcomes: &[IntentOutcome],
+    now: DateTime<Utc>,
+) -> StatusPageFeed {
+    let since = now - WINDOW;
+    let recent: Vec<&IntentOutcome> = outcomes
+        .iter()
+        .filter(|outcome| outcome.finished_at >= since)
+        .collect();
+
+    let durations: Vec<i64> = recent
+        .iter()
+        .filter(|outcome| outcome.status == "completed")
+        .map(|outcome| {
+            (outcome.finished_at - outcome.created_at)
+                .num_seconds()
+                .max(0)
+        })
+        .collect();
+    let average_completion_secs = (!durations.is_empty())
+        .then(|| (durations.iter().sum::<i64>() / durations.len() as i64) as u64);
+
+    let directions = DIRECTIONS
+        .iter()
+        .map(|(source, dest)| {
+            let (completed, unsuccessful) = recent
+                .iter()
+                .filter(|outcome| outcome.source_chain == *source && outcome.dest_chain == *dest)
+                .fold((0u64, 0u64), |(completed, unsuccessful), outcome| {
+                    if outcome.status == "completed" {
+                        (completed + 1, unsuccessful)
+                    } else {
+                        (completed, unsuccessful + 1)
+                    }
+                });
+            let total = completed + unsuccessful;
+
+            DirectionSummary {
+                direction: format!("{}_to_{}", source, dest),
+                completed,
+                unsuccessful,
+                success_rate_pct: (total > 0)
+                    .then(|| (completed as f64 * 1000.0 / total as f64).round() / 10.0),
+            }
+        })
+        .collect();
+
+    let status = components.status();
+
+    StatusPageFeed {
+        status,
+        operational: status == BridgeStatus::Operational,
+        updated_at: now,
+        completed_last_hour: durations.len() as u64,
+        average_completion_secs,
+        directions,
+        degraded_components: components.degraded(),
+    }
+}
+
+/// Serves the feed from a short cache so the public endpoint can't be used
+/// to hammer the RPCs and database.
+pub struct StatusPage {
+    pub cache_ttl: Duration,
+    cached: RwLock<Option<(Instant, StatusPageFeed)>>,
+}
+
+impl StatusPage {
+    /// Reads `STATUS_PAGE_CACHE_SECS` (default 30).
+    pub fn from_env() -> Self {
+        Self {
+            cache_ttl: Duration::from_secs(
+                std::env::var("STATUS_PAGE_CACHE_SECS")
+                    .ok()
+                    .and_then(|secs| secs.parse().ok())
+                    .unwrap_or(30),
+            ),
+            cached: RwLock::new(None),
+        }
+    }
+
+    pub async fn feed(&self, app_state: &AppState) -> StatusPageFeed {
+        if let Some((built_at, feed)) = self.cached.read().await.as_ref()
+            && built_at.elapsed() < self.cache_ttl
+        {
+            return feed.clone();
+        }
+
+        let now = Utc::now();
+        let outcomes = app_state.database.get_intent_outcomes_since(now - WINDOW);
+        if let Err(e) = &outcomes {
+            error!("Failed to load intent outcomes for status page: {}", e);
+        }
+
+        let components = ComponentHealth {
+            ethereum: app_state.ethereum_relayer.health_check().await.is_ok(),
+            mantle: app_state.mantle_relayer.health_check().await.is_ok(),
+            database: outcomes.is_ok() && app_state.database.health_check().is_ok(),
+            processing: app_state
+                .bridge_coordinator
+                .queue_health()
+                .await
+                .is_ok_and(|queues| queues.healthy),
+            sync: app_state.sync_progress.is_ready(),
+            contracts: app_state.contract_guard.alerts().await.is_empty(),
+        };
+
+        let feed = build_feed(components, &outcomes.unwrap_or_default(), now);
+        *self.cached.write().await = Some((Instant::now(), feed.clone()));
+        feed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_feed() {
+        let now = Utc::now();
+        let outcome = |source: &str, dest: &str, status: &str, secs: i64, ago_mins: i64| {
+            let finished_at = now - chrono::Duration::minutes(ago_mins);
+            IntentOutcome {
+                source_chain: source.to_string(),
+                dest_chain: dest.to_string(),
+                status: status.to_string(),
+                created_at: finished_at - chrono::Duration::seconds(secs),
+                finished_at,
+            }
+        };
+        let outcomes = vec![
+            outcome("ethereum", "mantle", "completed", 60, 5),
+            outcome("ethereum", "mantle", "completed", 180, 10),
+            outcome("ethereum", "mantle", "refunded", 3600, 20),
+            outcome("mantle", "ethereum", "completed", 900, 90),
+        ];
+        let healthy = ComponentHealth {
+            ethereum: true,
+            mantle: true,
+            database: true,
+            processing: true,
+            sync: true,
+            contracts: true,
+        };
+
+        let feed = build_feed(healthy, &outcomes, now);
+        assert_eq!(feed.status, BridgeStatus::Operational);
+        assert!(feed.operational && feed.degraded_components.is_empty());
+        assert_eq!(feed.completed_last_hour, 2);
+        assert_eq!(feed.average_completion_secs, Some(120));
+        assert_eq!(feed.directions[0].direction, "ethereum_to_mantle");
+        assert_eq!(feed.directions[0].success_rate_pct, Some(66.7));
+        // Finished outside the hour
+        assert_eq!(feed.directions[1].success_rate_pct, None);
+
+        let lagging = build_feed(
+            ComponentHealth {
+                sync: false,
+                ..healthy
+            },
+            &[],
+            now,
+        );
+        assert_eq!(lagging.status, BridgeStatus::Degraded);
+        assert_eq!(lagging.degraded_components, vec!["chain_sync"]);
+        assert_eq!(lagging.average_completion_secs, None);
+
+        let chain_down = build_feed(
+            ComponentHealth {
+                mantle: false,
+                ..healthy
+            },
+            &[],
+            now,
+        );
+        assert_eq!(chain_down.status, BridgeStatus::MajorOutage);
+    }
+}
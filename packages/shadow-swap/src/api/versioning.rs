@@ -0,0 +1,349 @@
+use std::collections::HashMap;
+
+use actix_web::{
+    Error, HttpResponse,
+    body::{EitherBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::header::{HeaderName, HeaderValue},
+    middleware::Next,
+    web,
+};
+use anyhow::{Context, Result, anyhow};
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use crate::AppState;
+
+pub const API_VERSION: HeaderName = HeaderName::from_static("api-version");
+pub const ACCEPT_VERSION: HeaderName = HeaderName::from_static("accept-version");
+const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Versions served under `/api/v{n}`. Add a variant (and its scope in
+/// `config_scope`) when a breaking shape change ships.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum ApiVersion {
+    V1,
+}
+
+impl ApiVersion {
+    pub const ALL: [ApiVersion; 1] = [ApiVersion::V1];
+    pub const LATEST: ApiVersion = ApiVersion::V1;
+
+    pub fn number(&self) -> u32 {
+        match self {
+            Self::V1 => 1,
+        }
+    }
+
+    pub fn prefix(&self) -> String {
+        format!("/api/v{}", self.number())
+    }
+
+    /// Accepts `1` or `v1`.
+    pub fn parse(value: &str) -> Result<Self> {
+        let value = value.trim();
+        let number = value
+            .strip_prefix(['v', 'V'])
+            .unwrap_or(value)
+            .parse::<u32>()
+            .map_err(|_| anyhow!("Invalid API version '{}'", value))?;
+
+        Self::ALL
+            .into_iter()
+            .find(|version| version.number() == number)
+            .ok_or_else(|| anyhow!("Unsupported API version {}", number))
+    }
+}
+
+/// Which versions are on their way out, and how long the unversioned
+/// `/api/...` aliases of the v1 routes keep answering.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionPolicy {
+    /// Deprecated versions with their optional sunset date.
+    pub deprecated: HashMap<ApiVersion, Option<NaiveDate>>,
+    pub legacy_enabled: bool,
+    pub legacy_sunset: Option<NaiveDate>,
+}
+
+impl Default for VersionPolicy {
+    fn default() -> Self {
+        Self {
+            deprecated: HashMap::new(),
+            legacy_enabled: true,
+            legacy_sunset: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionInfo {
+    pub version: u32,
+    pub prefix: String,
+    pub deprecated: bool,
+    pub sunset: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct VersionsOverview {
+    pub latest: u32,
+    pub versions: Vec<VersionInfo>,
+    pub legacy_routes_enabled: bool,
+    pub legacy_resolves_to: u32,
+    pub legacy_sunset: Option<NaiveDate>,
+}
+
+impl VersionPolicy {
+    /// Reads `API_DEPRECATED_VERSIONS` (comma separated, `1` or
+    /// `1:2027-06-30` with a sunset date), `API_LEGACY_ROUTES_ENABLED`
+    /// (default true) and `API_LEGACY_SUNSET` (`YYYY-MM-DD`).
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(deprecated) = std::env::var("API_DEPRECATED_VERSIONS") {
+            policy.deprecated =
+                parse_deprecations(&deprecated).context("Invalid API_DEPRECATED_VERSIONS")?;
+        }
+        if let Ok(enabled) = std::env::var("API_LEGACY_ROUTES_ENABLED") {
+            policy.legacy_enabled = enabled
+                .parse()
+                .context("Invalid API_LEGACY_ROUTES_ENABLED")?;
+        }
+        if let Ok(sunset) = std::env::var("API_LEGACY_SUNSET")
+            && !sunset.trim().is_empty()
+        {
+            policy.legacy_sunset = Some(parse_date(&sunset).context("Invalid API_LEGACY_SUNSET")?);
+        }
+
+        Ok(policy)
+    }
+
+    pub fn overview(&self) -> VersionsOverview {
+        VersionsOverview {
+            latest: ApiVersion::LATEST.number(),
+            versions: ApiVersion::ALL
+                .into_iter()
+                .map(|version| VersionInfo {
+                    version: version.number(),
+                    prefix: version.prefix(),
+                    deprecated: self.deprecated.contains_key(&version),
+                    sunset: self.deprecated.get(&version).copied().flatten(),
+                })
+                .collect(),
+            legacy_routes_enabled: self.legacy_enabled,
+            legacy_resolves_to: ApiVersion::V1.number(),
+            legacy_sunset: self.legacy_sunset,
+        }
+    }
+
+    /// Headers for a response served by `version`. `legacy` is set when the
+    /// request came in through an unversioned alias.
+    pub fn response_headers(
+        &self,
+        version: ApiVersion,
+        path: &str,
+        legacy: bool,
+    ) -> Vec<(HeaderName, String)> {
+        let mut headers = vec![(API_VERSION, version.number().to_string())];
+
+        let sunset = if legacy {
+            Some(self.legacy_sunset)
+        } else {
+            self.deprecated.get(&version).copied()
+        };
+        let Some(sunset) = sunset else {
+            return headers;
+        };
+
+        headers.push((DEPRECATION, "true".to_string()));
+        if let Some(date) = sunset {
+            headers.push((SUNSET, http_date(date)));
+        }
+
+        let successor = if legacy { version } else { ApiVersion::LATEST };
+        if legacy || successor != version {
+            let rest = path
+                .strip_prefix(&version.prefix())
+                .or_else(|| path.strip_prefix("/api"))
+                .unwrap_or(path);
+            headers.push((
+                actix_web::http::header::LINK,
+                format!(
+                    "<{}{}>; rel=\"successor-version\"",
+                    successor.prefix(),
+                    rest
+                ),
+            ));
+        }
+
+        headers
+    }
+}
+
+fn parse_date(value: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(value.trim(), "%Y-%m-%d")
+        .with_context(|| format!("Expected YYYY-MM-DD, got '{}'", value.trim()))
+}
+
+fn parse_deprecations(value: &str) -> Result<HashMap<ApiVersion, Option<NaiveDate>>> {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| match entry.split_once(':') {
+            Some((version, date)) => Ok((ApiVersion::parse(version)?, Some(parse_date(date)?))),
+            None => Ok((ApiVersion::parse(entry)?, None)),
+        })
+        .collect()
+}
+
+/// IMF-fixdate, as the `Sunset` header requires.
+fn http_date(date: NaiveDate) -> String {
+    date.and_hms_opt(0, 0, 0)
+        .expect("midnight is a valid time")
+        .and_utc()
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string()
+}
+
+fn version_error(status: actix_web::http::StatusCode, message: String) -> HttpResponse {
+    HttpResponse::build(status).json(serde_json::json!({
+        "status": "error",
+        "message": message,
+        "supported_versions": ApiVersion::ALL.iter().map(|v| v.number()).collect::<Vec<_>>(),
+    }))
+}
+
+async fn serve_version<B: MessageBody + 'static>(
+    version: ApiVersion,
+    legacy: bool,
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    let policy = req
+        .app_data::<web::Data<AppState>>()
+        .map(|state| state.api_versions.clone())
+        .unwrap_or_default();
+
+    // `/api/v9/...` names a version this build doesn't serve, not a legacy path
+    if legacy
+        && let Some(segment) = req.path()["/api".len()..].split('/').nth(1)
+        && segment.starts_with(['v', 'V'])
+        && segment[1..].parse::<u32>().is_ok()
+    {
+        let message = match ApiVersion::parse(segment) {
+            Ok(_) => format!("No route {}", req.path()),
+            Err(e) => e.to_string(),
+        };
+        let response = version_error(actix_web::http::StatusCode::NOT_FOUND, message);
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    if legacy && !policy.legacy_enabled {
+        let successor = format!("{}{}", version.prefix(), &req.path()["/api".len()..]);
+        let response = version_error(
+            actix_web::http::StatusCode::GONE,
+            format!("Unversioned routes are retired, use {}", successor),
+        );
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    // Accept-Version must agree with the version the path resolves to
+    if let Some(requested) = req.headers().get(ACCEPT_VERSION) {
+        let requested = requested
+            .to_str()
+            .map_err(|e| anyhow!("{}", e))
+            .and_then(ApiVersion::parse);
+        let message = match requested {
+            Ok(requested) if requested == version => None,
+            Ok(requested) => Some(format!(
+                "Accept-Version {} does not match {}, use {}{}",
+                requested.number(),
+                version.prefix(),
+                requested.prefix(),
+                req.path()
+                    .strip_prefix(&version.prefix())
+                    .or_else(|| req.path().strip_prefix("/api"))
+                    .unwrap_or_default()
+            )),
+            Err(e) => Some(e.to_string()),
+        };
+        if let Some(message) = message {
+            let response = version_error(actix_web::http::StatusCode::BAD_REQUEST, message);
+            return Ok(req.into_response(response).map_into_right_body());
+        }
+    }
+
+    let headers = policy.response_headers(version, req.path(), legacy);
+    let mut response = next.call(req).await?;
+    for (name, value) in headers {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            response.headers_mut().insert(name, value);
+        }
+    }
+
+    Ok(response.map_into_left_body())
+}
+
+/// Middleware for the `/api/v1` scope.
+pub async fn v1_versioning<B: MessageBody + 'static>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    serve_version(ApiVersion::V1, false, req, next).await
+}
+
+/// Middleware for the unversioned `/api` aliases, which resolve to v1.
+pub async fn legacy_versioning<B: MessageBody + 'static>(
+    req: ServiceRequest,
+    next: Next<B>,
+) -> Result<ServiceResponse<EitherBody<B>>, Error> {
+    serve_version(ApiVersion::V1, true, req, next).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_version_policy_headers() {
+        assert_eq!(ApiVersion::parse("v1").unwrap(), ApiVersion::V1);
+        assert_eq!(ApiVersion::parse(" 1 ").unwrap(), ApiVersion::V1);
+        assert!(ApiVersion::parse("2").is_err());
+        assert!(ApiVersion::parse("latest").is_err());
+
+        let deprecated = parse_deprecations("v1:2027-06-30").unwrap();
+        assert_eq!(
+            deprecated.get(&ApiVersion::V1),
+            Some(&NaiveDate::from_ymd_opt(2027, 6, 30))
+        );
+        assert!(parse_deprecations("1:30/06/2027").is_err());
+
+        let current = VersionPolicy::default();
+        assert_eq!(
+            current.response_headers(ApiVersion::V1, "/api/v1/stats", false),
+            vec![(API_VERSION, "1".to_string())]
+        );
+
+        let policy = VersionPolicy {
+            deprecated,
+            legacy_sunset: NaiveDate::from_ymd_opt(2027, 1, 1),
+            ..Default::default()
+        };
+        let legacy = policy.response_headers(ApiVersion::V1, "/api/bridge/quote", true);
+        assert!(legacy.contains(&(DEPRECATION, "true".to_string())));
+        assert!(legacy.contains(&(SUNSET, "Fri, 01 Jan 2027 00:00:00 GMT".to_string())));
+        assert!(legacy.contains(&(
+            actix_web::http::header::LINK,
+            "</api/v1/bridge/quote>; rel=\"successor-version\"".to_string()
+        )));
+
+        // Deprecating the latest version has no successor to point at yet
+        let v1 = policy.response_headers(ApiVersion::V1, "/api/v1/stats", false);
+        assert!(v1.contains(&(SUNSET, "Wed, 30 Jun 2027 00:00:00 GMT".to_string())));
+        assert!(
+            !v1.iter()
+                .any(|(name, _)| name == actix_web::http::header::LINK)
+        );
+    }
+}
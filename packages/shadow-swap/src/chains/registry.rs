@@ -0,0 +1,69 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::{Result, anyhow};
+use mantle_core::chain::Chain;
+
+use crate::relay_coordinator::model::ChainRelayer;
+
+/// Relayers by chain id, so callers look a chain up instead of branching on
+/// Ethereum or Mantle.
+#[derive(Clone, Default)]
+pub struct ChainRegistry {
+    relayers: BTreeMap<u32, Arc<ChainRelayer>>,
+}
+
+/// Whether `value`, a chain as stored on intent rows, names the chain with
+/// this key and id. Known chains also match their canonical id.
+fn names_chain(value: &str, key: &str, chain_id: u32) -> bool {
+    match Chain::parse(value) {
+        Ok(chain) => chain.name() == key,
+        Err(_) => value.eq_ignore_ascii_case(key) || value == chain_id.to_string(),
+    }
+}
+
+impl ChainRegistry {
+    pub fn new(relayers: impl IntoIterator<Item = Arc<ChainRelayer>>) -> Self {
+        Self {
+            relayers: relayers
+                .into_iter()
+                .map(|relayer| (relayer.chain_id, relayer))
+                .collect(),
+        }
+    }
+
+    pub fn get(&self, chain_id: u32) -> Option<&Arc<ChainRelayer>> {
+        self.relayers.get(&chain_id)
+    }
+
+    /// Accepts a chain name or chain id, as stored on intent rows.
+    pub fn resolve(&self, chain: &str) -> Result<&Arc<ChainRelayer>> {
+        self.relayers
+            .values()
+            .find(|relayer| names_chain(chain, relayer.key, relayer.chain_id))
+            .ok_or_else(|| anyhow!("Unsupported chain: {}", chain))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Arc<ChainRelayer>> {
+        self.relayers.values()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mantle_core::chain::{ETHEREUM_CHAIN_ID, MANTLE_CHAIN_ID};
+
+    #[test]
+    fn test_chain_names_match_key_or_id() {
+        // Rows carry the canonical id even when the node reports another
+        assert!(names_chain("ethereum", "ethereum", 1));
+        assert!(names_chain(&ETHEREUM_CHAIN_ID.to_string(), "ethereum", 1));
+        assert!(names_chain("Mantle", "mantle", MANTLE_CHAIN_ID));
+        assert!(!names_chain("mantle", "ethereum", ETHEREUM_CHAIN_ID));
+
+        // Chains without a built-in entry match by their own key or id
+        assert!(names_chain("arbitrum", "arbitrum", 421614));
+        assert!(names_chain("421614", "arbitrum", 421614));
+        assert!(!names_chain("421613", "arbitrum", 421614));
+    }
+}
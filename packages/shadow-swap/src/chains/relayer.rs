@@ -0,0 +1,1392 @@
+use std::sync::Arc;
+
+use anyhow::{Context, Result, anyhow};
+use ethers::{
+    contract::{ContractCall, EthCall},
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::Signer,
+    types::{
+        Address, BlockId, Bytes, H256, Transaction, TransactionReceipt, U64, U256,
+        transaction::eip2718::TypedTransaction,
+    },
+};
+use mantle_core::{
+    abi::{IntentPoolContract, SettlementContract, settlement_contract::RecoverOrphanedFillCall},
+    amount::to_decimal,
+    finality::FinalitySource,
+    metrics::Metrics,
+    parse,
+    tx_strategy::ChainTxStrategy,
+};
+use signing_service::{client::SignerHandle, protocol::RELAYER_KEY};
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    database::{database::Database, model::NewTransactionReceipt},
+    intent_workers::sync_progress::SyncProgress,
+    models::{model::IntentCreatedEvent, traits::ChainConfig},
+    relay_coordinator::{
+        claim_estimate::ClaimSimulation,
+        claim_forwarder::{ClaimForwarder, ClaimForwarderPolicy, check_claim_receipt},
+        contract_guard::{ContractGuard, GuardedContract},
+        model::ChainRelayer,
+        orphaned_fills::{DestFill, bytecode_has_selector},
+        sandbox::{SIMULATED_STATUS, sandbox_enabled, simulated_tx_hash},
+        tx_journal::SendOnce,
+        tx_queue::{SentTx, TxPriority, TxQueue, TxQueuePolicy},
+    },
+};
+
+pub type ChainClient = SignerMiddleware<Provider<Http>, SignerHandle>;
+
+const TX_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(120);
+const CLAIM_GAS_ESTIMATE: u64 = 250_000;
+
+impl ChainRelayer {
+    pub async fn new<C: ChainConfig>(
+        config: &C,
+        finality: FinalitySource,
+        database: Arc<Database>,
+        guard: ContractGuard,
+        metrics: Arc<Metrics>,
+    ) -> Result<Self> {
+        config.validate()?;
+        info!("🔗 Initializing {} relayer", C::NAME);
+
+        let provider = Provider::<Http>::try_from(config.rpc_url())
+            .with_context(|| format!("Failed to create {} provider", C::NAME))?
+            .interval(std::time::Duration::from_millis(2000));
+
+        let chain_id = provider
+            .get_chainid()
+            .await
+            .with_context(|| format!("Failed to get {} chain ID", C::NAME))?
+            .as_u64();
+
+        let wallet = SignerHandle::from_env(RELAYER_KEY, config.private_key(), chain_id)
+            .await
+            .with_context(|| format!("Failed to initialise {} signer", C::NAME))?;
+
+        let client = Arc::new(SignerMiddleware::new(provider, wallet));
+
+        let intent_pool_address: Address = config
+            .intent_pool_address()
+            .parse()
+            .with_context(|| format!("Invalid {} intent pool address", C::NAME))?;
+
+        let settlement_address: Address = config
+            .settlement_address()
+            .parse()
+            .with_context(|| format!("Invalid {} settlement address", C::NAME))?;
+
+        let intent_pool = IntentPoolContract::new(intent_pool_address, client.clone());
+        let settlement = SettlementContract::new(settlement_address, client.clone());
+
+        let tx_strategy = ChainTxStrategy::from_env(C::ENV_PREFIX, C::default_tx_strategy())
+            .with_context(|| format!("Invalid {} tx strategy", C::NAME))?;
+
+        let sandbox = sandbox_enabled();
+        if sandbox {
+            warn!(
+                "🧪 [{}] Sandbox mode: transactions are simulated, never sent",
+                C::NAME
+            );
+        }
+
+        info!("   IntentPool: {:?}", intent_pool_address);
+        info!("   Settlement: {:?}", settlement_address);
+        info!(
+            "   Tx type: {:?} (gas limit x{}%)",
+            tx_strategy.tx_type, tx_strategy.gas_limit_multiplier_pct
+        );
+        info!("   Finality: {}", finality);
+
+        let tx_queue = TxQueue::spawn(
+            C::NAME,
+            client.clone(),
+            tx_strategy.clone(),
+            TxQueuePolicy::from_env(C::ENV_PREFIX)
+                .with_context(|| format!("Invalid {} tx queue policy", C::NAME))?,
+        );
+
+        let claim_forwarder = ClaimForwarder::connect(
+            C::NAME,
+            ClaimForwarderPolicy::from_env(C::ENV_PREFIX)
+                .with_context(|| format!("Invalid {} claim forwarder policy", C::NAME))?,
+            client.clone(),
+            &settlement,
+        )
+        .await?;
+
+        Ok(Self {
+            name: C::NAME,
+            key: C::KEY,
+            native_symbol: C::NATIVE_SYMBOL,
+            low_balance: ethers::utils::parse_ether(C::LOW_BALANCE)?,
+            rpc_url: config.rpc_url().to_string(),
+            client,
+            intent_pool,
+            settlement,
+            database,
+            chain_id: chain_id as u32,
+            tx_strategy,
+            finality,
+            tx_queue,
+            sandbox,
+            guard,
+            claim_forwarder,
+            metrics,
+        })
+    }
+
+    pub async fn health_check(&self) -> Result<()> {
+        self.client
+            .get_block_number()
+            .await
+            .with_context(|| format!("{} RPC health check failed", self.name))?;
+        Ok(())
+    }
+
+    pub async fn settle_intent(
+        &self,
+        intent_id: &str,
+        solver_address: &str,
+        merkle_path: &[String],
+        leaf_index: u32,
+    ) -> Result<String> {
+        let start = std::time::Instant::now();
+        info!(
+            "✅ [{}] Settling intent {} (leaf_index: {})",
+            self.name,
+            parse::short(intent_id),
+            leaf_index
+        );
+
+        self.guard
+            .ensure_active(self.key, GuardedContract::IntentPool)
+            .await?;
+
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let proof: Vec<[u8; 32]> = merkle_path
+            .iter()
+            .map(|p| parse::hex32("proof element", p))
+            .collect::<Result<Vec<[u8; 32]>, _>>()?;
+
+        let solver_addr = parse::address("solver_address", solver_address)?;
+
+        let tx = self.intent_pool.settle_intent(
+            intent_id_bytes,
+            solver_addr,
+            proof,
+            U256::from(leaf_index),
+        );
+
+        match tx.call().await {
+            Ok(_) => debug!("   ✓ Transaction simulation successful"),
+            Err(e) => {
+                let revert_reason = Self::extract_revert_reason(&e);
+                error!(
+                    "💥 [{}] Settle intent would revert: {}",
+                    self.name, revert_reason
+                );
+                return Err(anyhow!("Settlement simulation failed: {}", revert_reason));
+            }
+        }
+
+        if self.sandbox {
+            return self.simulated_send(Some(intent_id), "settle_intent").await;
+        }
+
+        let receipt = match self
+            .send_once(intent_id, TxPriority::Fill, "settle_intent", tx.tx)
+            .await
+            .context("Failed to send settle transaction")?
+        {
+            SendOnce::Receipt(receipt) => *receipt,
+            SendOnce::AlreadyLanded(tx_hash) => {
+                info!("   ✅ Already settled by an earlier attempt");
+                return tx_hash.ok_or_else(|| anyhow!("Settled under an unrecorded transaction"));
+            }
+        };
+        let tx_hash = format!("{:?}", receipt.transaction_hash);
+
+        self.archive_receipt(Some(intent_id), "settle_intent", &receipt)
+            .await;
+
+        let status = if receipt.status == Some(1.into()) {
+            "confirmed"
+        } else {
+            "reverted"
+        };
+        self.log_transaction(intent_id, "settle_intent", &tx_hash, status)
+            .await?;
+
+        if receipt.status != Some(1.into()) {
+            return Err(anyhow!("Settlement transaction reverted"));
+        }
+
+        info!("   ✅ Settled ({}ms)", start.elapsed().as_millis());
+        Ok(format!("{:?}", receipt.transaction_hash))
+    }
+
+    pub async fn execute_refund(&self, intent_id: &str) -> Result<String> {
+        let start = std::time::Instant::now();
+        info!(
+            "♻️ [{}] Refunding intent {}",
+            self.name,
+            parse::short(intent_id)
+        );
+
+        self.guard
+            .ensure_active(self.key, GuardedContract::IntentPool)
+            .await?;
+
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let (
+            _commitment,
+            _source_token,
+            _source_amount,
+            _dest_token,
+            _dest_amount,
+            _dest_chain,
+            deadline,
+            _refund_to,
+            filled,
+            refunded,
+        ) = self.intent_pool.get_intent(intent_id_bytes).call().await?;
+
+        if filled {
+            return Err(anyhow!("Intent already filled, cannot refund"));
+        }
+
+        if refunded {
+            return Err(anyhow!("Intent already refunded"));
+        }
+
+        if deadline
+            > std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs()
+        {
+            return Err(anyhow!("Intent not expired yet, deadline: {}", deadline));
+        }
+
+        // Simulate the transaction
+        let tx = self.intent_pool.refund(intent_id_bytes);
+
+        if let Err(e) = tx.call().await {
+            let revert_reason = Self::extract_revert_reason(&e);
+            error!("💥 [{}] Refund would revert: {}", self.name, revert_reason);
+            return Err(anyhow!("Refund simulation failed: {}", revert_reason));
+        }
+
+        if self.sandbox {
+            return self.simulated_send(Some(intent_id), "refund_intent").await;
+        }
+
+        // Send the transaction
+        let pending = self
+            .send_queued(TxPriority::Fill, "refund_intent", tx.tx)
+            .await
+            .context("Failed to send refund tx")?;
+        let tx_hash = format!("{:?}", pending.tx_hash());
+        info!("   📤 Tx sent: {}", parse::short(&tx_hash));
+
+        self.log_transaction(intent_id, "refund_intent", &tx_hash, "pending")
+            .await?;
+
+        // Wait for confirmation
+        let receipt = self
+            .await_receipt("refund_intent", "Refund tx", pending)
+            .await?;
+
+        self.archive_receipt(Some(intent_id), "refund_intent", &receipt)
+            .await;
+
+        let status = if receipt.status == Some(1.into()) {
+            "confirmed"
+        } else {
+            "reverted"
+        };
+
+        self.log_transaction(intent_id, "refund_intent", &tx_hash, status)
+            .await?;
+
+        if receipt.status != Some(1.into()) {
+            return Err(anyhow!("Refund transaction reverted"));
+        }
+
+        info!("   ✅ Refunded ({}ms)", start.elapsed().as_millis());
+        Ok(format!("{:?}", receipt.transaction_hash))
+    }
+
+    pub async fn register_intent(
+        &self,
+        intent_id: &str,
+        commitment: &str,
+        token: &str,
+        amount: &str,
+        source_chain: u32,
+        deadline: u64,
+        source_root: &str,
+        merkle_path: &[String],
+        leaf_index: u32,
+    ) -> Result<String> {
+        let start = std::time::Instant::now();
+        info!(
+            "📝 [{}] Registering intent {} (leaf_index: {})",
+            self.name,
+            parse::short(intent_id),
+            leaf_index
+        );
+
+        self.guard
+            .ensure_active(self.key, GuardedContract::Settlement)
+            .await?;
+
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let commitment_bytes = parse::hex32("commitment", commitment)?;
+
+        let token_address = parse::address("token", token)?;
+        let amount_u256 = parse::amount("amount", amount)?;
+
+        let source_root_bytes = parse::hex32("source_root", source_root)?;
+
+        let proof: Vec<[u8; 32]> = merkle_path
+            .iter()
+            .map(|p| parse::hex32("proof element", p))
+            .collect::<Result<Vec<[u8; 32]>, _>>()?;
+
+        let tx = self.settlement.register_intent(
+            intent_id_bytes,
+            commitment_bytes,
+            token_address,
+            amount_u256,
+            source_chain,
+            deadline,
+            source_root_bytes,
+            proof,
+            U256::from(leaf_index),
+        );
+
+        match tx.call().await {
+            Ok(_) => info!("   ✓ Simulation successful"),
+            Err(e) => {
+                let revert_reason = Self::extract_revert_reason(&e);
+                error!(
+                    "💥 [{}] Register intent would revert: {}",
+                    self.name, revert_reason
+                );
+                return Err(anyhow!("Registration simulation failed: {}", revert_reason));
+            }
+        }
+
+        if self.sandbox {
+            return self
+                .simulated_send(Some(intent_id), "register_intent")
+                .await;
+        }
+
+        let pending = self
+            .send_queued(TxPriority::Fill, "register_intent", tx.tx)
+            .await
+            .context("Failed to send register intent tx")?;
+        let tx_hash = format!("{:?}", pending.tx_hash());
+
+        self.log_transaction(intent_id, "register_intent", &tx_hash, "pending")
+            .await?;
+
+        let receipt = self
+            .await_receipt("register_intent", "Registration tx", pending)
+            .await?;
+
+        self.archive_receipt(Some(intent_id), "register_intent", &receipt)
+            .await;
+
+        let status = if receipt.status == Some(1.into()) {
+            "confirmed"
+        } else {
+            "reverted"
+        };
+        self.log_transaction(intent_id, "register_intent", &tx_hash, status)
+            .await?;
+
+        if receipt.status != Some(1.into()) {
+            return Err(anyhow!("Registration transaction reverted on-chain"));
+        }
+
+        info!("   ✅ Registered ({}ms)", start.elapsed().as_millis());
+        Ok(format!("{:?}", receipt.transaction_hash))
+    }
+
+    pub async fn claim_withdrawal(
+        &self,
+        intent_id: &str,
+        nullifier: &str,
+        recipient: &str,
+        delegate: Option<&str>,
+        secret: &str,
+        claim_auth: &[u8],
+    ) -> Result<String> {
+        let start = std::time::Instant::now();
+        info!(
+            "🔓 [{}] Claiming withdrawal {}",
+            self.name,
+            parse::short(intent_id)
+        );
+
+        self.guard
+            .ensure_active(self.key, GuardedContract::Settlement)
+            .await?;
+
+        let tx = self.claim_call(
+            intent_id, nullifier, recipient, delegate, secret, claim_auth,
+        )?;
+
+        if let Err(e) = tx.call().await {
+            let revert_reason = Self::extract_revert_reason(&e);
+            error!("💥 [{}] Claim would revert: {}", self.name, revert_reason);
+            return Err(anyhow!("Claim simulation failed: {}", revert_reason));
+        }
+
+        if self.sandbox {
+            return self
+                .simulated_send(Some(intent_id), "claim_withdrawal")
+                .await;
+        }
+
+        // Routed through the forwarder only now: wrapping reserves its nonce
+        let tx = match &self.claim_forwarder {
+            Some(forwarder) => forwarder.wrap(&tx).await?,
+            None => tx,
+        };
+
+        let sent = self.send_claim(intent_id, tx).await;
+        if sent.is_err()
+            && let Some(forwarder) = &self.claim_forwarder
+        {
+            forwarder.reset_nonce().await;
+        }
+        let tx_hash = sent?;
+
+        info!("   ✅ Claimed ({}ms)", start.elapsed().as_millis());
+        Ok(tx_hash)
+    }
+
+    async fn send_claim(
+        &self,
+        intent_id: &str,
+        tx: ContractCall<ChainClient, ()>,
+    ) -> Result<String> {
+        let pending = self
+            .send_queued(TxPriority::Claim, "claim_withdrawal", tx.tx)
+            .await
+            .context("Failed to send claim tx")?;
+        let tx_hash = format!("{:?}", pending.tx_hash());
+
+        self.log_transaction(intent_id, "claim_withdrawal", &tx_hash, "pending")
+            .await?;
+
+        let receipt = self
+            .await_receipt("claim_withdrawal", "Claim tx", pending)
+            .await?;
+
+        self.archive_receipt(Some(intent_id), "claim_withdrawal", &receipt)
+            .await;
+
+        let status = if receipt.status == Some(1.into()) {
+            "confirmed"
+        } else {
+            "reverted"
+        };
+        self.log_transaction(intent_id, "claim_withdrawal", &tx_hash, status)
+            .await?;
+
+        check_claim_receipt(
+            &receipt,
+            intent_id,
+            self.settlement.address(),
+            self.claim_forwarder.as_ref().map(ClaimForwarder::address),
+        )?;
+
+        Ok(format!("{:?}", receipt.transaction_hash))
+    }
+
+    /// Dry-run `claimWithdrawal` from the relayer account: the gas and native
+    /// cost it would take now, or the reason it reverts.
+    pub async fn simulate_claim_withdrawal(
+        &self,
+        intent_id: &str,
+        nullifier: &str,
+        recipient: &str,
+        delegate: Option<&str>,
+        secret: &str,
+        claim_auth: &[u8],
+    ) -> Result<ClaimSimulation> {
+        let tx = self.claim_call(
+            intent_id, nullifier, recipient, delegate, secret, claim_auth,
+        )?;
+
+        if let Err(e) = tx.call().await {
+            let revert_reason = e
+                .decode_revert::<String>()
+                .unwrap_or_else(|| Self::extract_revert_reason(&e));
+            return Ok(ClaimSimulation::Reverts(revert_reason));
+        }
+
+        // Gas and cost of what would be sent, forwarder overhead included
+        let tx = match &self.claim_forwarder {
+            Some(forwarder) => forwarder.wrap_for_simulation(&tx).await?,
+            None => tx,
+        };
+
+        let gas = tx
+            .estimate_gas()
+            .await
+            .map_err(|e| anyhow!("Failed to estimate claim gas: {}", e))?;
+        let cost_wei = self
+            .tx_strategy
+            .estimate_cost(self.client.clone(), &tx.tx, gas)
+            .await
+            .context("Failed to estimate claim cost")?;
+
+        Ok(ClaimSimulation::Succeeds { gas, cost_wei })
+    }
+
+    /// `claimWithdrawalTo` when the recipient delegated the claim to
+    /// `delegate`, `claimWithdrawal` otherwise.
+    fn claim_call(
+        &self,
+        intent_id: &str,
+        nullifier: &str,
+        recipient: &str,
+        delegate: Option<&str>,
+        secret: &str,
+        claim_auth: &[u8],
+    ) -> Result<ContractCall<ChainClient, ()>> {
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let nullifier_bytes = parse::hex32("nullifier", nullifier)?;
+
+        let recipient_address = parse::address("recipient", recipient)?;
+
+        let secret_bytes = parse::hex32("secret", secret)?;
+
+        let claim_auth = Bytes::from(claim_auth.to_vec());
+        Ok(match delegate {
+            Some(delegate) => self.settlement.claim_withdrawal_to(
+                intent_id_bytes,
+                nullifier_bytes,
+                recipient_address,
+                parse::address("claim_delegate", delegate)?,
+                secret_bytes,
+                claim_auth,
+            ),
+            None => self.settlement.claim_withdrawal(
+                intent_id_bytes,
+                nullifier_bytes,
+                recipient_address,
+                secret_bytes,
+                claim_auth,
+            ),
+        })
+    }
+
+    /// Return an orphaned fill (source intent refunded) to its solver.
+    pub async fn recover_orphaned_fill(&self, intent_id: &str) -> Result<String> {
+        let start = std::time::Instant::now();
+        info!(
+            "🛟 [{}] Recovering orphaned fill {}",
+            self.name,
+            parse::short(intent_id)
+        );
+
+        self.guard
+            .ensure_active(self.key, GuardedContract::Settlement)
+            .await?;
+
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let tx = self.settlement.recover_orphaned_fill(intent_id_bytes);
+
+        if let Err(e) = tx.call().await {
+            let revert_reason = Self::extract_revert_reason(&e);
+            error!(
+                "💥 [{}] Recovery would revert: {}",
+                self.name, revert_reason
+            );
+            return Err(anyhow!("Recovery simulation failed: {}", revert_reason));
+        }
+
+        if self.sandbox {
+            return self
+                .simulated_send(Some(intent_id), "recover_orphaned_fill")
+                .await;
+        }
+
+        let pending = self
+            .send_queued(TxPriority::Fill, "recover_orphaned_fill", tx.tx)
+            .await
+            .context("Failed to send recovery tx")?;
+        let tx_hash = format!("{:?}", pending.tx_hash());
+
+        self.log_transaction(intent_id, "recover_orphaned_fill", &tx_hash, "pending")
+            .await?;
+
+        let receipt = self
+            .await_receipt("recover_orphaned_fill", "Recovery tx", pending)
+            .await?;
+
+        self.archive_receipt(Some(intent_id), "recover_orphaned_fill", &receipt)
+            .await;
+
+        let status = if receipt.status == Some(1.into()) {
+            "confirmed"
+        } else {
+            "reverted"
+        };
+
+        self.log_transaction(intent_id, "recover_orphaned_fill", &tx_hash, status)
+            .await?;
+
+        if receipt.status != Some(1.into()) {
+            return Err(anyhow!("Recovery transaction reverted"));
+        }
+
+        info!("   ✅ Recovered ({}ms)", start.elapsed().as_millis());
+        Ok(format!("{:?}", receipt.transaction_hash))
+    }
+
+    pub async fn get_intent_pool_root(&self) -> Result<String> {
+        let root = self.intent_pool.get_merkle_root().call().await?;
+        Ok(format!("0x{}", hex::encode(root)))
+    }
+
+    /// Commitment root of `source_chain_id` as last synced to this chain.
+    pub async fn get_synced_commitment_root(&self, source_chain_id: u32) -> Result<String> {
+        let root_bytes: [u8; 32] = self
+            .settlement
+            .source_chain_commitment_roots(source_chain_id)
+            .call()
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to read chain {} commitment root from {}",
+                    source_chain_id, self.name
+                )
+            })?;
+
+        Ok(format!("0x{}", hex::encode(root_bytes)))
+    }
+
+    pub async fn sync_source_chain_commitment_root_tx(
+        &self,
+        chain_id: u32,
+        root: [u8; 32],
+    ) -> Result<String> {
+        let start = std::time::Instant::now();
+        info!(
+            "🌳 [{}] Syncing source chain {} commitment root",
+            self.name, chain_id
+        );
+
+        self.guard
+            .ensure_active(self.key, GuardedContract::Settlement)
+            .await?;
+
+        self.check_balance().await?;
+
+        let tx = self
+            .settlement
+            .sync_source_chain_commitment_root(chain_id, root);
+
+        if let Err(e) = tx.call().await {
+            let revert_reason = Self::extract_revert_reason(&e);
+            error!(
+                "💥 [{}] Root sync would revert: {}",
+                self.name, revert_reason
+            );
+            return Err(anyhow!("Root sync simulation failed: {}", revert_reason));
+        }
+
+        if self.sandbox {
+            return self.simulated_send(None, "sync_commitment_root").await;
+        }
+
+        let pending = self
+            .send_queued(TxPriority::RootSync, "sync_commitment_root", tx.tx)
+            .await
+            .context("Failed to send sync tx")?;
+        let receipt = self
+            .await_receipt("sync_commitment_root", "Sync tx", pending)
+            .await?;
+
+        self.archive_receipt(None, "sync_commitment_root", &receipt)
+            .await;
+
+        if receipt.status != Some(1.into()) {
+            return Err(anyhow!("Root sync transaction reverted"));
+        }
+
+        info!("   ✅ Root synced ({}ms)", start.elapsed().as_millis());
+        Ok(format!("{:?}", receipt.transaction_hash))
+    }
+
+    pub async fn sync_dest_chain_fill_root_tx(
+        &self,
+        chain_id: u32,
+        root: [u8; 32],
+    ) -> Result<String> {
+        let start = std::time::Instant::now();
+        info!(
+            "🌳 [{}] Syncing dest chain {} fill root",
+            self.name, chain_id
+        );
+
+        self.guard
+            .ensure_active(self.key, GuardedContract::IntentPool)
+            .await?;
+
+        self.check_balance().await?;
+
+        let tx = self.intent_pool.sync_dest_chain_fill_root(chain_id, root);
+
+        if let Err(e) = tx.call().await {
+            let revert_reason = Self::extract_revert_reason(&e);
+            error!(
+                "💥 [{}] Fill root sync would revert: {}",
+                self.name, revert_reason
+            );
+            return Err(anyhow!(
+                "Fill root sync simulation failed: {}",
+                revert_reason
+            ));
+        }
+
+        if self.sandbox {
+            return self.simulated_send(None, "sync_fill_root").await;
+        }
+
+        let pending = self
+            .send_queued(TxPriority::RootSync, "sync_fill_root", tx.tx)
+            .await
+            .context("Failed to send fill root sync tx")?;
+        let receipt = self
+            .await_receipt("sync_fill_root", "Fill root sync tx", pending)
+            .await?;
+
+        self.archive_receipt(None, "sync_fill_root", &receipt).await;
+
+        if receipt.status != Some(1.into()) {
+            return Err(anyhow!("Fill root sync transaction reverted"));
+        }
+
+        info!("   ✅ Fill root synced ({}ms)", start.elapsed().as_millis());
+        Ok(format!("{:?}", receipt.transaction_hash))
+    }
+
+    /// Fill root of `dest_chain_id` as last synced to this chain.
+    pub async fn get_synced_fill_root(&self, dest_chain_id: u32) -> Result<String> {
+        let root_bytes: [u8; 32] = self
+            .intent_pool
+            .dest_chain_fill_roots(dest_chain_id)
+            .call()
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to read chain {} fill root from {}",
+                    dest_chain_id, self.name
+                )
+            })?;
+
+        Ok(format!("0x{}", hex::encode(root_bytes)))
+    }
+
+    pub async fn fetch_all_intent_created_events(
+        &self,
+        from_block: u64,
+        progress: Option<&SyncProgress>,
+    ) -> Result<Vec<IntentCreatedEvent>> {
+        use ethers::types::{Filter, H256};
+
+        const BATCH_SIZE: u64 = 2000;
+        const DELAY_MS: u64 = 300;
+
+        let provider = Provider::<Http>::try_from(self.rpc_url.as_str())
+            .map_err(|e| anyhow!("Failed to create provider: {}", e))?;
+
+        let current_block = provider
+            .get_block_number()
+            .await
+            .context("Failed to get current block number")?
+            .as_u64();
+
+        info!(
+            "📦 [{}] Fetching events from block {} to {}",
+            self.name, from_block, current_block
+        );
+
+        if let Some(progress) = progress {
+            progress
+                .start_scan(self.key, from_block, current_block)
+                .await;
+        }
+
+        let event_signature = ethers::core::utils::keccak256(
+            "IntentCreated(bytes32,bytes32,uint32,address,uint256,address,uint256)",
+        );
+        let topic = H256::from_slice(&event_signature);
+
+        let mut all_events = Vec::new();
+        let mut start = from_block;
+
+        while start <= current_block {
+            let end = std::cmp::min(start + BATCH_SIZE - 1, current_block);
+
+            let filter = Filter::new()
+                .address(self.intent_pool.address())
+                .from_block(start)
+                .to_block(end)
+                .topic0(topic);
+
+            let found_before = all_events.len();
+
+            match provider.get_logs(&filter).await {
+                Ok(logs) => {
+                    debug!(
+                        "    ✅ Found {} events in batch {}-{}",
+                        logs.len(),
+                        start,
+                        end
+                    );
+
+                    for log in logs {
+                        // Skip malformed logs
+                        if log.topics.len() < 3 || log.data.len() < 160 {
+                            continue;
+                        }
+
+                        let intent_id = format!("0x{}", hex::encode(log.topics[1]));
+                        let commitment = format!("0x{}", hex::encode(log.topics[2]));
+
+                        let data = &log.data;
+                        let dest_chain =
+                            u32::from_be_bytes([data[28], data[29], data[30], data[31]]);
+                        let source_token = Address::from_slice(&data[44..64]);
+                        let source_amount = U256::from_big_endian(&data[64..96]);
+                        let dest_token = Address::from_slice(&data[108..128]);
+                        let dest_amount = U256::from_big_endian(&data[128..160]);
+
+                        all_events.push(IntentCreatedEvent {
+                            intent_id,
+                            commitment,
+                            source_token: format!("{:?}", source_token),
+                            source_amount: source_amount.to_string(),
+                            dest_token: format!("{:?}", dest_token),
+                            dest_amount: dest_amount.to_string(),
+                            dest_chain,
+                            deadline: None,
+                            block_number: log.block_number.map(|b| b.as_u64()),
+                            transaction_hash: log.transaction_hash.map(|h| format!("{:?}", h)),
+                            log_index: log.log_index.map(|i| i.as_u64()),
+                        });
+                    }
+                }
+                Err(e) => {
+                    warn!("    ⚠️  Batch failed: {}. Retrying...", e);
+                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
+                    continue; // Retry logic
+                }
+            }
+
+            if let Some(progress) = progress {
+                progress
+                    .scanned(self.key, end, all_events.len() - found_before)
+                    .await;
+            }
+
+            start = end + 1;
+            if start <= current_block {
+                tokio::time::sleep(tokio::time::Duration::from_millis(DELAY_MS)).await;
+            }
+        }
+
+        info!(
+            "✅ [{}] Total events fetched: {}",
+            self.name,
+            all_events.len()
+        );
+        Ok(all_events)
+    }
+
+    pub async fn check_balance(&self) -> Result<U256> {
+        let address = self.client.signer().address();
+        let balance = self
+            .client
+            .get_balance(address, None)
+            .await
+            .with_context(|| format!("Failed to get {} balance", self.name))?;
+
+        debug!(
+            "💰 {} balance: {} {}",
+            self.name,
+            ethers::utils::format_ether(balance),
+            self.native_symbol
+        );
+        self.metrics
+            .set_balance(self.key, self.native_symbol, to_decimal(balance, 18));
+
+        if balance < self.low_balance {
+            warn!(
+                "⚠️  Low {} balance on {}! Please fund: {:?}",
+                self.native_symbol, self.name, address
+            );
+        }
+        Ok(balance)
+    }
+
+    /// Rough upper bound on the native cost of a claimWithdrawal at the current fee,
+    /// including any rollup L1 data fee.
+    pub async fn estimate_claim_cost(&self) -> Result<U256> {
+        // Representative calldata so rollups can price the L1 data fee
+        let sample = self.settlement.claim_withdrawal(
+            [0u8; 32],
+            [0u8; 32],
+            Address::zero(),
+            [0u8; 32],
+            Bytes::from(vec![0u8; 65]),
+        );
+
+        self.tx_strategy
+            .estimate_cost(
+                self.client.clone(),
+                &sample.tx,
+                U256::from(CLAIM_GAS_ESTIMATE),
+            )
+            .await
+            .context("Failed to estimate claim cost")
+    }
+
+    /// Gas used and native cost paid by a mined transaction.
+    pub async fn get_transaction_cost(&self, tx_hash: &str) -> Result<(u64, U256)> {
+        if self.sandbox {
+            return Ok((0, U256::zero()));
+        }
+
+        let hash = parse::h256("tx_hash", tx_hash)?;
+
+        let receipt = self
+            .client
+            .get_transaction_receipt(hash)
+            .await
+            .context("Failed to get transaction receipt")?
+            .ok_or_else(|| anyhow!("Receipt not found for {}", tx_hash))?;
+
+        let gas_used = receipt.gas_used.unwrap_or_default();
+        let gas_price = receipt.effective_gas_price.unwrap_or_default();
+
+        debug!(
+            "⛽ [{}] {} used {} gas ({} {})",
+            self.name,
+            tx_hash,
+            gas_used,
+            ethers::utils::format_ether(gas_used.saturating_mul(gas_price)),
+            self.native_symbol
+        );
+
+        Ok((gas_used.as_u64(), gas_used.saturating_mul(gas_price)))
+    }
+
+    pub(crate) fn extract_revert_reason<E: std::fmt::Display>(error: &E) -> String {
+        let error_str = error.to_string();
+
+        if let Some(start) = error_str.find("execution reverted:") {
+            return error_str[start + 19..]
+                .split('\n')
+                .next()
+                .unwrap_or("")
+                .trim()
+                .to_string();
+        }
+
+        if let Some(start) = error_str.find("reverted with reason string")
+            && let Some((_, quoted)) = error_str[start..].split_once('\'')
+            && let Some((reason, _)) = quoted.split_once('\'')
+        {
+            return reason.to_string();
+        }
+
+        if let Some(start) = error_str.find("0x") {
+            let hex_part = &error_str[start..];
+            let end = hex_part
+                .find(|c: char| !c.is_ascii_hexdigit() && c != 'x')
+                .unwrap_or(hex_part.len());
+            let error_code = &hex_part[..end];
+            if error_code.len() >= 10 {
+                return format!("Revert with error code: {}", error_code);
+            }
+        }
+
+        error_str
+    }
+
+    pub(crate) async fn log_transaction(
+        &self,
+        intent_id: &str,
+        tx_type: &str,
+        tx_hash: &str,
+        status: &str,
+    ) -> Result<()> {
+        self.database
+            .log_chain_transaction(intent_id, self.chain_id, tx_type, tx_hash, status)
+            .context("Failed to log transaction")
+    }
+
+    /// Sandbox stand-in for a send whose simulation already passed.
+    async fn simulated_send(&self, intent_id: Option<&str>, tx_type: &str) -> Result<String> {
+        let tx_hash = simulated_tx_hash(self.chain_id, tx_type, intent_id.unwrap_or_default());
+        info!(
+            "   🧪 [{}] Sandbox: {} not sent, simulated as {}",
+            self.name, tx_type, tx_hash
+        );
+
+        if let Some(intent_id) = intent_id {
+            self.log_transaction(intent_id, tx_type, &tx_hash, SIMULATED_STATUS)
+                .await?;
+        }
+
+        Ok(tx_hash)
+    }
+
+    /// Send through the chain's tx queue, which prices the call and assigns
+    /// its nonce, and return a handle for the receipt.
+    async fn send_queued(
+        &self,
+        priority: TxPriority,
+        tx_type: &'static str,
+        tx: TypedTransaction,
+    ) -> Result<SentTx> {
+        self.tx_queue.send(priority, tx_type, tx).await
+    }
+
+    /// Wait for a sent transaction's receipt, following any fee-bumped
+    /// replacements, and record its confirmation latency and whether it
+    /// reverted.
+    pub(crate) async fn await_receipt(
+        &self,
+        tx_type: &str,
+        label: &str,
+        pending: SentTx,
+    ) -> Result<TransactionReceipt> {
+        let sent_at = std::time::Instant::now();
+        let receipt = tokio::time::timeout(TX_TIMEOUT, self.tx_queue.receipt(&pending))
+            .await
+            .with_context(|| format!("{} timed out", label))?
+            .with_context(|| format!("{} failed", label))?
+            .ok_or_else(|| anyhow!("{} dropped", label))?;
+
+        self.metrics.confirmed(
+            self.key,
+            tx_type,
+            sent_at.elapsed(),
+            receipt.status == Some(1.into()),
+        );
+        Ok(receipt)
+    }
+
+    /// Persist the full receipt for forensics. Failures are logged, never propagated.
+    pub async fn archive_receipt(
+        &self,
+        intent_id: Option<&str>,
+        tx_type: &str,
+        receipt: &TransactionReceipt,
+    ) {
+        let succeeded = receipt.status == Some(1.into());
+        let revert_reason = if succeeded {
+            None
+        } else {
+            self.replay_revert_reason(receipt.transaction_hash, receipt.block_number)
+                .await
+        };
+
+        let tx_hash = format!("{:?}", receipt.transaction_hash);
+        let effective_gas_price = receipt.effective_gas_price.map(|p| p.to_string());
+
+        let receipt_json = match serde_json::to_value(receipt) {
+            Ok(value) => value,
+            Err(e) => {
+                warn!(
+                    "⚠️ [{}] Failed to serialize receipt {}: {}",
+                    self.name, tx_hash, e
+                );
+                return;
+            }
+        };
+
+        let record = NewTransactionReceipt {
+            tx_hash: &tx_hash,
+            intent_id,
+            chain_id: self.chain_id as i32,
+            tx_type,
+            status: if succeeded { "confirmed" } else { "reverted" },
+            block_number: receipt.block_number.map(|b| b.as_u64() as i64),
+            gas_used: receipt.gas_used.map(|g| g.as_u64() as i64),
+            effective_gas_price: effective_gas_price.as_deref(),
+            revert_reason: revert_reason.as_deref(),
+            receipt: receipt_json,
+            created_at: chrono::Utc::now(),
+        };
+
+        if let Err(e) = self.database.store_transaction_receipt(&record) {
+            warn!(
+                "⚠️ [{}] Failed to archive receipt {}: {}",
+                self.name, tx_hash, e
+            );
+        }
+    }
+
+    pub async fn get_receipt(&self, tx_hash: H256) -> Result<Option<TransactionReceipt>> {
+        self.client
+            .get_transaction_receipt(tx_hash)
+            .await
+            .with_context(|| format!("Failed to get {} receipt", self.name))
+    }
+
+    /// Whether the node still knows the transaction (mined or in its mempool).
+    pub async fn is_transaction_known(&self, tx_hash: H256) -> Result<bool> {
+        let tx = self
+            .client
+            .get_transaction(tx_hash)
+            .await
+            .with_context(|| format!("Failed to get {} transaction", self.name))?;
+
+        Ok(tx.is_some())
+    }
+
+    pub async fn get_block_number(&self) -> Result<u64> {
+        Ok(self
+            .client
+            .get_block_number()
+            .await
+            .with_context(|| format!("Failed to get {} block number", self.name))?
+            .as_u64())
+    }
+
+    /// Highest block of this chain that counts as final.
+    pub async fn final_block(&self) -> Result<u64> {
+        self.finality
+            .final_block(self.client.as_ref())
+            .await
+            .with_context(|| format!("Failed to get {} final block", self.name))
+    }
+
+    /// Transactions the relayer sent to the intent pool or settlement in
+    /// `from_block..=to_block`.
+    pub async fn relayer_transactions(
+        &self,
+        from_block: u64,
+        to_block: u64,
+    ) -> Result<Vec<Transaction>> {
+        let relayer = self.client.signer().address();
+        let mut contracts = vec![self.intent_pool.address(), self.settlement.address()];
+        contracts.extend(self.claim_forwarder.as_ref().map(ClaimForwarder::address));
+        let mut found = Vec::new();
+
+        for number in from_block..=to_block {
+            let Some(block) = self
+                .client
+                .get_block_with_txs(number)
+                .await
+                .with_context(|| format!("Failed to get {} block {}", self.name, number))?
+            else {
+                continue;
+            };
+
+            found.extend(block.transactions.into_iter().filter(|tx| {
+                tx.from == relayer && tx.to.is_some_and(|to| contracts.contains(&to))
+            }));
+        }
+
+        Ok(found)
+    }
+
+    /// Re-run a reverted transaction against the parent block state to recover its revert reason.
+    async fn replay_revert_reason(&self, tx_hash: H256, block: Option<U64>) -> Option<String> {
+        let tx = self.client.get_transaction(tx_hash).await.ok()??;
+        let call: TypedTransaction = (&tx).into();
+        let block_id = block.map(|b| BlockId::from(b.saturating_sub(U64::one())));
+
+        match self.client.call(&call, block_id).await {
+            Ok(_) => None,
+            Err(e) => Some(Self::extract_revert_reason(&e)),
+        }
+    }
+
+    pub async fn get_fill_proof(&self, intent_id: &str) -> Result<Vec<String>> {
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let proof = self
+            .settlement
+            .generate_fill_proof(intent_id_bytes)
+            .call()
+            .await
+            .map_err(|e| anyhow!("Failed to get fill proof: {}", e))?;
+
+        Ok(proof
+            .iter()
+            .map(|p| format!("0x{}", hex::encode(p)))
+            .collect())
+    }
+
+    pub async fn get_fill_index(&self, intent_id: &str) -> Result<u32> {
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let index = self
+            .settlement
+            .get_fill_index(intent_id_bytes)
+            .call()
+            .await
+            .map_err(|e| anyhow!("Failed to get fill index: {}", e))?;
+
+        Ok(index.as_u32())
+    }
+
+    pub async fn check_intent_registered(&self, intent_id: &str) -> Result<bool> {
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let (_, _, _, _, _, exists) = self
+            .settlement
+            .get_intent_params(intent_id_bytes)
+            .call()
+            .await?;
+
+        Ok(exists)
+    }
+
+    /// Source-pool view of an intent: `(settled, refunded)`.
+    pub async fn get_source_intent_state(&self, intent_id: &str) -> Result<(bool, bool)> {
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let (_, _, _, _, _, _, _, _, filled, refunded) = self
+            .intent_pool
+            .get_intent(intent_id_bytes)
+            .call()
+            .await
+            .context("Failed to read source intent")?;
+
+        Ok((filled, refunded))
+    }
+
+    /// Whether the intent was ever created in this chain's source pool.
+    pub async fn check_source_intent_created(&self, intent_id: &str) -> Result<bool> {
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let (commitment, ..) = self
+            .intent_pool
+            .get_intent(intent_id_bytes)
+            .call()
+            .await
+            .context("Failed to read source intent")?;
+
+        Ok(commitment != [0u8; 32])
+    }
+
+    /// Destination-settlement view of an intent: `(filled, claimed)`.
+    pub async fn get_dest_fill_state(&self, intent_id: &str) -> Result<(bool, bool)> {
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let (solver, _, _, _, _, claimed) = self
+            .settlement
+            .get_fill(intent_id_bytes)
+            .call()
+            .await
+            .context("Failed to read destination fill")?;
+
+        Ok((solver != Address::zero(), claimed))
+    }
+
+    /// The destination fill, `None` while no solver has filled.
+    pub async fn get_dest_fill(&self, intent_id: &str) -> Result<Option<DestFill>> {
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let (solver, token, amount, _, _, claimed) = self
+            .settlement
+            .get_fill(intent_id_bytes)
+            .call()
+            .await
+            .context("Failed to read destination fill")?;
+
+        Ok((solver != Address::zero()).then_some(DestFill {
+            solver,
+            token,
+            amount,
+            claimed,
+        }))
+    }
+
+    /// Whether the deployed settlement has `recoverOrphanedFill`. Older
+    /// deployments don't; their orphans need the manual runbook.
+    pub async fn supports_orphan_recovery(&self) -> Result<bool> {
+        let code = self
+            .client
+            .get_code(self.settlement.address(), None)
+            .await
+            .context("Failed to read settlement bytecode")?;
+
+        Ok(bytecode_has_selector(
+            &code,
+            RecoverOrphanedFillCall::selector(),
+        ))
+    }
+
+    /// Destination fill timestamp, `None` while no solver has filled.
+    pub async fn get_fill_timestamp(&self, intent_id: &str) -> Result<Option<u64>> {
+        let intent_id_bytes = parse::hex32("intent_id", intent_id)?;
+
+        let (solver, _, _, _, timestamp, _) = self
+            .settlement
+            .get_fill(intent_id_bytes)
+            .call()
+            .await
+            .context("Failed to read destination fill")?;
+
+        Ok((solver != Address::zero()).then_some(timestamp as u64))
+    }
+
+    /// Whether `claimWithdrawal` already consumed this nullifier.
+    pub async fn is_nullifier_used(&self, nullifier: &str) -> Result<bool> {
+        let nullifier_bytes = parse::hex32("nullifier", nullifier)?;
+
+        self.settlement
+            .is_nullifier_used(nullifier_bytes)
+            .call()
+            .await
+            .context("Failed to read nullifier state")
+    }
+
+    pub async fn get_fill_tree_size(&self) -> Result<usize> {
+        let size = self
+            .settlement
+            .get_fill_tree_size()
+            .call()
+            .await
+            .context("Failed to read fill tree size")?;
+
+        Ok(size.as_usize())
+    }
+
+    pub async fn get_fill_root(&self) -> Result<String> {
+        let root = self
+            .settlement
+            .get_merkle_root()
+            .call()
+            .await
+            .map_err(|e| anyhow!("Failed to get fill merkle root: {}", e))?;
+
+        Ok(format!("0x{}", hex::encode(root)))
+    }
+}
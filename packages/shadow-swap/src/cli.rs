@@ -0,0 +1,651 @@
+use std::io::Write;
+
+use anyhow::{Context, Result, anyhow, bail};
+use clap::{Arg, ArgAction, ArgMatches, Command};
+use mantle_core::{
+    finality::FinalitySource, http_client::OutboundPolicy, http_server::HttpServerOptions,
+    price_source::PriceSources, pricefeed::PricePolicy, tx_strategy::ChainTxStrategy,
+};
+use tracing::info;
+
+use crate::{
+    api::{
+        admin_auth::{AdminAuthPolicy, AdminRole, issue_admin_key},
+        api_keys::ApiKeyPolicy,
+        quote::QuotePolicy,
+        rate_limit::RateLimitPolicy,
+        versioning::VersionPolicy,
+    },
+    config::{
+        profile::{NetworkProfile, config_file_path},
+        schema::render_example,
+    },
+    database::{
+        database::Database,
+        schema_check::{SchemaCheckMode, check_schema_drift},
+    },
+    encryption::privacy_context::PrivacyContextKey,
+    intent_workers::{
+        event_sync::IntentSyncService, fairness::TokenBatchLimits, log_listener::LogListenerPolicy,
+        reorg_monitor::ReorgPolicy, stuck_detector::StuckPolicy, tx_repair::TxRepairPolicy,
+    },
+    models::model::{BridgeConfig, CommitmentUniqueness, Intent},
+    relay_coordinator::{
+        alert_rules::AlertRulesPolicy, claim_forwarder::ClaimForwarderPolicy,
+        compliance::CompliancePolicy, deposit_check::DepositCheckPolicy,
+        encrypted_notes::EncryptedNotePolicy, fill_reports::FillReportPolicy,
+        ingestion_queue::IngestionPolicy, intent_feed::IntentFeedPolicy,
+        metric_snapshots::MetricsPersistPolicy, model::SponsorshipPolicy,
+        orphaned_fills::OrphanPolicy, reservations::ReservationPolicy,
+        route_analytics::RouteAnalyticsPolicy, tx_queue::TxQueuePolicy,
+        work_partitions::PartitionPolicy,
+    },
+    root_sync_coordinator::root_sync_coordinator::RootSyncPolicy,
+    shutdown::ShutdownPolicy,
+    webhooks::webhook_dispatcher::WebhookPolicy,
+};
+
+const INTENT_STATUSES: [&str; 11] = [
+    "created",
+    "committed",
+    "underpaid",
+    "registered",
+    "pending",
+    "filled",
+    "solver_paid",
+    "user_claimed",
+    "refunded",
+    "failed",
+    "expired",
+];
+
+fn dry_run_arg() -> Arg {
+    Arg::new("dry-run")
+        .long("dry-run")
+        .action(ArgAction::SetTrue)
+        .help("Report what would change without writing anything")
+}
+
+pub fn cli() -> Command {
+    Command::new("shadow-swap")
+        .about("Shadow-swap bridge relayer")
+        .subcommand(Command::new("serve").about("Run the workers and the HTTP API (the default)"))
+        .subcommand(
+            Command::new("migrate")
+                .about("Apply pending database migrations and check for schema drift")
+                .arg(dry_run_arg()),
+        )
+        .subcommand(
+            Command::new("rekey-privacy-contexts")
+                .about(
+                    "Replace the unkeyed privacy context tags with keyed ones, once, \
+                     re-tagging only rows whose old tag still matches",
+                )
+                .arg(dry_run_arg()),
+        )
+        .subcommand(
+            Command::new("resync")
+                .about("Re-ingest IntentCreated events and rebuild the commitment trees")
+                .arg(
+                    Arg::new("chain")
+                        .long("chain")
+                        .value_parser(["ethereum", "mantle", "both"])
+                        .default_value("both"),
+                )
+                .arg(
+                    Arg::new("from-block")
+                        .long("from-block")
+                        .value_parser(clap::value_parser!(u64))
+                        .help(
+                            "First block to scan (default: <CHAIN>_SYNC_FROM_BLOCK, else the \
+                             profile's sync_from_block); \
+                             needs a single --chain",
+                        ),
+                )
+                .arg(
+                    Arg::new("keep-existing")
+                        .long("keep-existing")
+                        .action(ArgAction::SetTrue)
+                        .help(
+                            "Upsert into the existing intents instead of clearing the chain first",
+                        ),
+                )
+                .arg(dry_run_arg()),
+        )
+        .subcommand(
+            Command::new("reconcile")
+                .about("Compare non-terminal intents against both chains and exit")
+                .arg(
+                    Arg::new("chain")
+                        .long("chain")
+                        .value_parser(["ethereum", "mantle", "both"])
+                        .default_value("both")
+                        .help("Only check intents whose source is this chain"),
+                )
+                .arg(
+                    Arg::new("fix")
+                        .long("fix")
+                        .action(ArgAction::SetTrue)
+                        .help("Update DB statuses to match the chains"),
+                ),
+        )
+        .subcommand(
+            Command::new("replay-intent")
+                .about("Print what the relayer would do next for one intent, without sending")
+                .arg(Arg::new("intent_id").required(true))
+                .arg(
+                    Arg::new("at-block")
+                        .long("at-block")
+                        .value_parser(clap::value_parser!(u64))
+                        .help("Source chain block to read state at (default: latest)"),
+                )
+                .arg(
+                    Arg::new("dest-block")
+                        .long("dest-block")
+                        .value_parser(clap::value_parser!(u64))
+                        .help("Destination chain block to read state at (default: latest)"),
+                ),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Write intents to stdout, newest first")
+                .arg(
+                    Arg::new("status")
+                        .long("status")
+                        .value_parser(INTENT_STATUSES),
+                )
+                .arg(
+                    Arg::new("chain")
+                        .long("chain")
+                        .value_parser(["ethereum", "mantle"])
+                        .help("Intents with this chain as source or destination"),
+                )
+                .arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .value_parser(clap::value_parser!(usize))
+                        .default_value("10000"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_parser(["jsonl", "csv"])
+                        .default_value("jsonl"),
+                ),
+        )
+        .subcommand(
+            Command::new("admin-key")
+                .about("Admin API keys")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("create")
+                        .about("Issue a key and print it once, e.g. the first superuser key")
+                        .arg(Arg::new("name").long("name").required(true))
+                        .arg(
+                            Arg::new("role")
+                                .long("role")
+                                .value_parser(["read_only", "operator", "superuser"])
+                                .required(true),
+                        ),
+                ),
+        )
+        .subcommand(Command::new("devnet").about(
+            "Run both chains on local anvil nodes with the contracts deployed, \
+             plus the relayer and a solver against them",
+        ))
+        .subcommand(
+            Command::new("config")
+                .about("Configuration tools")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("validate")
+                        .about("Load every setting the relayer reads and report all errors"),
+                )
+                .subcommand(
+                    Command::new("schema")
+                        .about("Print a documented example config.toml with every key"),
+                ),
+        )
+}
+
+// ============================================================================
+// CONFIGURATION
+// ============================================================================
+
+/// The environment when it is complete, otherwise the config file; with
+/// neither, both errors are reported.
+pub fn load_config() -> Result<BridgeConfig> {
+    let config = match BridgeConfig::from_env() {
+        Ok(config) => config,
+        Err(env_error) => {
+            let path = config_file_path();
+            if !path.exists() {
+                return Err(env_error).with_context(|| {
+                    format!(
+                        "Failed to load configuration from the environment ({} not found)",
+                        path.display()
+                    )
+                });
+            }
+            BridgeConfig::from_file(&path).with_context(|| {
+                format!(
+                    "Failed to load configuration from {} (environment: {:#})",
+                    path.display(),
+                    env_error
+                )
+            })?
+        }
+    };
+
+    config.validate().context("Invalid configuration")?;
+    Ok(config)
+}
+
+/// Pool for the database-only commands. Unlike `Database::from_env` it never
+/// migrates on its own, so `migrate --dry-run` stays read-only.
+pub fn connect_database() -> Result<Database> {
+    let url = std::env::var("DATABASE_URL").context("DATABASE_URL not set")?;
+    Database::new(&url, 2)
+}
+
+/// Start block of the startup sync and `resync`: `<CHAIN>_SYNC_FROM_BLOCK`,
+/// else the active profile's `sync_from_block`. Without a profile the
+/// Sepolia deployment blocks apply.
+pub fn sync_from_block(chain: &str, profile: Option<&NetworkProfile>) -> Result<u64> {
+    let key = match chain {
+        "ethereum" => "ETHEREUM_SYNC_FROM_BLOCK",
+        "mantle" => "MANTLE_SYNC_FROM_BLOCK",
+        other => bail!("Unknown chain: {}", other),
+    };
+
+    if let Ok(block) = std::env::var(key) {
+        return block.parse().with_context(|| format!("Invalid {}", key));
+    }
+
+    let profile = profile
+        .cloned()
+        .unwrap_or_else(|| NetworkProfile::builtin("sepolia").expect("sepolia is built in"));
+    profile
+        .chain(chain)
+        .and_then(|chain| chain.sync_from_block)
+        .with_context(|| {
+            format!(
+                "{} must be set: profile '{}' has no sync_from_block",
+                key, profile.name
+            )
+        })
+}
+
+pub fn print_config_schema() -> Result<()> {
+    print!("{}", render_example()?);
+    Ok(())
+}
+
+/// Parse everything the relayer reads at startup without connecting to the
+/// database or the chains, so a bad deploy fails before it is rolled out.
+pub fn validate_config() -> Result<()> {
+    let config = load_config();
+    let profile = config
+        .as_ref()
+        .ok()
+        .and_then(|config| config.profile.as_ref());
+    let checks: Vec<(&str, Result<()>)> = vec![
+        (
+            "bridge config",
+            config.as_ref().map(|_| ()).map_err(|e| anyhow!("{:#}", e)),
+        ),
+        (
+            "database",
+            std::env::var("DATABASE_URL")
+                .map(|_| ())
+                .map_err(|_| anyhow!("DATABASE_URL not set")),
+        ),
+        (
+            "http server",
+            HttpServerOptions::from_env().and_then(|options| options.tls_config().map(|_| ())),
+        ),
+        (
+            "ethereum tx strategy",
+            ChainTxStrategy::from_env("ETHEREUM", ChainTxStrategy::ethereum()).map(|_| ()),
+        ),
+        (
+            "mantle tx strategy",
+            ChainTxStrategy::from_env("MANTLE", ChainTxStrategy::mantle()).map(|_| ()),
+        ),
+        (
+            "ethereum finality",
+            FinalitySource::from_env("ETHEREUM", FinalitySource::ethereum()).map(|_| ()),
+        ),
+        (
+            "mantle finality",
+            FinalitySource::from_env("MANTLE", FinalitySource::mantle()).map(|_| ()),
+        ),
+        (
+            "ethereum tx queue",
+            TxQueuePolicy::from_env("ETHEREUM").map(|_| ()),
+        ),
+        (
+            "mantle tx queue",
+            TxQueuePolicy::from_env("MANTLE").map(|_| ()),
+        ),
+        (
+            "ethereum claim forwarder",
+            ClaimForwarderPolicy::from_env("ETHEREUM").map(|_| ()),
+        ),
+        (
+            "mantle claim forwarder",
+            ClaimForwarderPolicy::from_env("MANTLE").map(|_| ()),
+        ),
+        ("compliance", CompliancePolicy::from_env().map(|_| ())),
+        (
+            "claim sponsorship",
+            SponsorshipPolicy::from_env().map(|_| ()),
+        ),
+        (
+            "commitment uniqueness",
+            CommitmentUniqueness::from_env().map(|_| ()),
+        ),
+        ("webhooks", WebhookPolicy::from_env().map(|_| ())),
+        ("stuck intents", StuckPolicy::from_env().map(|_| ())),
+        ("transaction repair", TxRepairPolicy::from_env().map(|_| ())),
+        ("orphaned fills", OrphanPolicy::from_env().map(|_| ())),
+        (
+            "metrics persistence",
+            MetricsPersistPolicy::from_env().map(|_| ()),
+        ),
+        ("intent feed", IntentFeedPolicy::from_env().map(|_| ())),
+        ("work partitions", PartitionPolicy::from_env().map(|_| ())),
+        ("api versions", VersionPolicy::from_env().map(|_| ())),
+        ("quote", QuotePolicy::from_env().map(|_| ())),
+        (
+            "route analytics",
+            RouteAnalyticsPolicy::from_env().map(|_| ()),
+        ),
+        ("deposit check", DepositCheckPolicy::from_env().map(|_| ())),
+        (
+            "encrypted notes",
+            EncryptedNotePolicy::from_env().map(|_| ()),
+        ),
+        ("reservations", ReservationPolicy::from_env().map(|_| ())),
+        ("fill reports", FillReportPolicy::from_env().map(|_| ())),
+        ("log listener", LogListenerPolicy::from_env().map(|_| ())),
+        ("ingestion queue", IngestionPolicy::from_env().map(|_| ())),
+        ("admin auth", AdminAuthPolicy::from_env().map(|_| ())),
+        ("api keys", ApiKeyPolicy::from_env().map(|_| ())),
+        ("rate limits", RateLimitPolicy::from_env().map(|_| ())),
+        ("reorg monitor", ReorgPolicy::from_env().map(|_| ())),
+        ("root sync", RootSyncPolicy::from_env().map(|_| ())),
+        ("alert rules", AlertRulesPolicy::from_env().map(|_| ())),
+        ("shutdown", ShutdownPolicy::from_env().map(|_| ())),
+        ("outbound http", OutboundPolicy::from_env().map(|_| ())),
+        ("price feed", PricePolicy::from_env().map(|_| ())),
+        ("price sources", PriceSources::from_env().map(|_| ())),
+        (
+            "token batch limits",
+            TokenBatchLimits::from_env().map(|_| ()),
+        ),
+        (
+            "privacy context key",
+            PrivacyContextKey::from_env().map(|_| ()),
+        ),
+        (
+            "ethereum sync",
+            sync_from_block("ethereum", profile).map(|_| ()),
+        ),
+        (
+            "mantle sync",
+            sync_from_block("mantle", profile).map(|_| ()),
+        ),
+    ];
+
+    let mut failed = 0;
+    for (name, result) in &checks {
+        match result {
+            Ok(()) => println!("✅ {}", name),
+            Err(e) => {
+                failed += 1;
+                println!("❌ {}: {:#}", name, e);
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{} of {} configuration checks failed", failed, checks.len());
+    }
+    println!("Configuration is valid");
+    Ok(())
+}
+
+// ============================================================================
+// DATABASE
+// ============================================================================
+
+pub fn migrate(database: &Database, dry_run: bool) -> Result<()> {
+    let pending = Database::pending_migrations(&database.pool)?;
+    if pending.is_empty() {
+        println!("No pending migrations");
+    }
+    for name in &pending {
+        println!("{} {}", if dry_run { "pending" } else { "applying" }, name);
+    }
+
+    if dry_run {
+        return Ok(());
+    }
+    if !pending.is_empty() {
+        Database::run_migrations(&database.pool).context("Failed to run migrations")?;
+        println!("Applied {} migration(s)", pending.len());
+    }
+
+    let drift = check_schema_drift(database, SchemaCheckMode::Warn)?;
+    if !drift.is_empty() {
+        bail!(
+            "{} schema drift issue(s) remain after migrating",
+            drift.len()
+        );
+    }
+    Ok(())
+}
+
+/// Needs the context key (`PRIVACY_CONTEXT_KEY` or `RELAYER_PRIVATE_KEY`)
+/// besides `DATABASE_URL`.
+pub fn rekey_privacy_contexts(database: &Database, dry_run: bool) -> Result<()> {
+    let database = database
+        .clone()
+        .with_privacy_key(PrivacyContextKey::from_env()?);
+    let report = database.rekey_privacy_contexts(dry_run)?;
+
+    println!(
+        "{} {} privacy params row(s)",
+        if dry_run { "would re-key" } else { "re-keyed" },
+        report.rekeyed
+    );
+    for intent_id in &report.unverified {
+        println!("unverified {}", intent_id);
+    }
+    if !report.unverified.is_empty() {
+        println!(
+            "{} row(s) no longer match their old tag and will be refused",
+            report.unverified.len()
+        );
+    }
+    Ok(())
+}
+
+pub fn export(database: &Database, args: &ArgMatches) -> Result<()> {
+    let intents = database.list_intents(
+        args.get_one::<String>("status").map(String::as_str),
+        args.get_one::<String>("chain").map(String::as_str),
+        *args.get_one::<usize>("limit").expect("has default"),
+    )?;
+
+    let mut out = std::io::stdout().lock();
+    match args
+        .get_one::<String>("format")
+        .expect("has default")
+        .as_str()
+    {
+        "csv" => write_csv(&mut out, &intents)?,
+        _ => {
+            for intent in &intents {
+                writeln!(out, "{}", serde_json::to_string(intent)?)?;
+            }
+        }
+    }
+
+    info!("📤 Exported {} intents", intents.len());
+    Ok(())
+}
+
+/// `admin-key create`: works with the shared HMAC secret disabled, so the
+/// first superuser key can always be issued from a shell.
+pub fn create_admin_key(database: &Database, args: &ArgMatches) -> Result<()> {
+    let role = AdminRole::parse(args.get_one::<String>("role").expect("required"))?;
+    let issued = issue_admin_key(
+        database,
+        args.get_one::<String>("name").expect("required"),
+        role,
+        "cli",
+    )?;
+
+    println!("{}", serde_json::to_string_pretty(&issued)?);
+    Ok(())
+}
+
+const CSV_COLUMNS: &str = "id,status,source_chain,dest_chain,source_token,dest_token,amount,\
+dest_amount,user_address,solver_address,deadline,created_at,updated_at,dest_fill_txid,\
+source_complete_txid";
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn write_csv(out: &mut impl Write, intents: &[Intent]) -> Result<()> {
+    writeln!(out, "{}", CSV_COLUMNS)?;
+    for intent in intents {
+        let row = [
+            intent.id.to_string(),
+            intent.status.as_str().to_string(),
+            intent.source_chain.clone(),
+            intent.dest_chain.clone(),
+            intent.source_token.clone(),
+            intent.dest_token.clone(),
+            intent.amount.to_string(),
+            intent.dest_amount.to_string(),
+            intent.user_address.clone(),
+            intent.solver_address.clone().unwrap_or_default(),
+            intent.deadline.to_string(),
+            intent.created_at.to_rfc3339(),
+            intent.updated_at.to_rfc3339(),
+            intent.dest_fill_txid.clone().unwrap_or_default(),
+            intent.source_complete_txid.clone().unwrap_or_default(),
+        ];
+        let row: Vec<String> = row.iter().map(|v| csv_field(v)).collect();
+        writeln!(out, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+// ============================================================================
+// CHAIN SYNC
+// ============================================================================
+
+pub async fn resync(
+    sync_service: &IntentSyncService,
+    config: &BridgeConfig,
+    args: &ArgMatches,
+) -> Result<()> {
+    let chains: Vec<&str> = match args
+        .get_one::<String>("chain")
+        .expect("has default")
+        .as_str()
+    {
+        "both" => vec!["ethereum", "mantle"],
+        chain => vec![chain],
+    };
+    let from_block = args.get_one::<u64>("from-block").copied();
+    if from_block.is_some() && chains.len() > 1 {
+        bail!("--from-block needs --chain ethereum or --chain mantle");
+    }
+
+    for chain in chains {
+        let from_block = match from_block {
+            Some(block) => block,
+            None => sync_from_block(chain, config.profile.as_ref())?,
+        };
+
+        if args.get_flag("dry-run") {
+            let preview = sync_service.preview_resync(chain, from_block).await?;
+            println!("{}", serde_json::to_string_pretty(&preview)?);
+            continue;
+        }
+
+        let clear_existing = !args.get_flag("keep-existing");
+        match chain {
+            "ethereum" => {
+                sync_service
+                    .resync_ethereum_intents(from_block, clear_existing)
+                    .await?
+            }
+            _ => {
+                sync_service
+                    .resync_mantle_intents(from_block, clear_existing)
+                    .await?
+            }
+        }
+        println!("Resynced {} from block {}", chain, from_block);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_parses_subcommands() {
+        cli().debug_assert();
+
+        let matches = cli()
+            .try_get_matches_from(["shadow-swap", "resync", "--chain", "mantle", "--dry-run"])
+            .unwrap();
+        let (name, args) = matches.subcommand().unwrap();
+        assert_eq!(name, "resync");
+        assert!(args.get_flag("dry-run"));
+        assert_eq!(args.get_one::<String>("chain").unwrap(), "mantle");
+
+        let matches = cli()
+            .try_get_matches_from(["shadow-swap", "config", "validate"])
+            .unwrap();
+        assert_eq!(
+            matches.subcommand().unwrap().1.subcommand_name(),
+            Some("validate")
+        );
+
+        // No subcommand serves, as before the CLI existed
+        assert!(
+            cli()
+                .try_get_matches_from(["shadow-swap"])
+                .unwrap()
+                .subcommand()
+                .is_none()
+        );
+        assert!(
+            cli()
+                .try_get_matches_from(["shadow-swap", "export", "--status", "bogus"])
+                .is_err()
+        );
+        assert!(
+            cli()
+                .try_get_matches_from(["shadow-swap", "config"])
+                .is_err()
+        );
+
+        assert_eq!(csv_field("0xabc"), "0xabc");
+        assert_eq!(csv_field("a,\"b\""), "\"a,\"\"b\"\"\"");
+    }
+}
@@ -1,126 +1,357 @@
-use anyhow::{Result, anyhow};
-use std::{env, path::PathBuf};
+use anyhow::{Result, anyhow, bail};
+use std::{env, path::Path};
+
+use mantle_core::{finality::FinalitySource, tx_strategy::ChainTxStrategy};
 
 use crate::{
-    models::model::{BridgeConfig, DatabaseConfig, ServerConfig},
+    config::{
+        profile::{ChainProfile, NetworkProfile},
+        schema::{ConfigFile, defaults},
+    },
+    models::{
+        model::{BridgeConfig, DatabaseConfig, ServerConfig},
+        traits::ChainConfig,
+    },
     relay_coordinator::model::{EthereumConfig, MantleConfig},
 };
 
+/// Whether transaction signing is delegated to the signing service.
+fn signer_delegated() -> bool {
+    env::var("SIGNER_SOCKET").is_ok()
+}
+
+/// Private keys are optional once signing is delegated to the signing service.
+fn env_private_key(key: &str) -> Result<String> {
+    match env::var(key) {
+        Ok(value) => Ok(value),
+        Err(_) if signer_delegated() => Ok(String::new()),
+        Err(_) => Err(anyhow!("{} must be set", key)),
+    }
+}
+
+/// Parse an optional variable, or take its default when unset.
+fn env_or_default<T: std::str::FromStr>(key: &str, default: T) -> Result<T>
+where
+    T::Err: std::fmt::Display,
+{
+    match env::var(key) {
+        Ok(value) => value.parse().map_err(|e| anyhow!("Invalid {}: {}", key, e)),
+        Err(_) => Ok(default),
+    }
+}
+
+/// Read a contract setting from the environment, falling back to the active profile.
+fn env_or_profile(key: &str, fallback: Option<&str>) -> Result<String> {
+    env::var(key)
+        .ok()
+        .or_else(|| fallback.map(str::to_string))
+        .ok_or_else(|| anyhow!("{} must be set", key))
+}
+
 impl BridgeConfig {
-    pub fn from_file(path: PathBuf) -> Result<Self> {
+    /// Load `config.toml`, applying the profile `BRIDGE_PROFILE` names or
+    /// else the built-in one `NETWORK` names. Every key is checked as it is
+    /// parsed; see `shadow-swap config schema`.
+    pub fn from_file(path: &Path) -> Result<Self> {
         let contents = std::fs::read_to_string(path)
-            .map_err(|e| anyhow!("Failed to read config file: {}", e))?;
+            .map_err(|e| anyhow!("Failed to read config file {}: {}", path.display(), e))?;
 
-        let config: Self =
-            toml::from_str(&contents).map_err(|e| anyhow!("Failed to parse config: {}", e))?;
+        let mut config = ConfigFile::from_toml_str(&contents)?
+            .into_config(env::var("BRIDGE_PROFILE").ok().as_deref())?;
+        match env::var("NETWORK") {
+            Ok(_) if config.profile.is_some() => {
+                bail!("Set either BRIDGE_PROFILE or NETWORK, not both")
+            }
+            Ok(network) => config.apply_profile(NetworkProfile::from_network(&network)?)?,
+            Err(_) => {}
+        }
 
         Ok(config)
     }
 
     pub fn from_env() -> Result<Self> {
-        Ok(BridgeConfig {
+        let profile = NetworkProfile::from_env()?;
+
+        let mut config = BridgeConfig {
             server: ServerConfig {
-                host: env::var("HOST").unwrap_or_else(|_| "0.0.0.0".to_string()),
-                port: env::var("PORT")
-                    .unwrap_or_else(|_| "8080".to_string())
-                    .parse()
-                    .map_err(|e| anyhow!("Invalid PORT: {}", e))?,
+                host: env::var("HOST").unwrap_or_else(|_| defaults::host()),
+                port: env_or_default("PORT", defaults::port())?,
                 hmac_secret: env::var("HMAC_SECRET")
                     .map_err(|_| anyhow!("HMAC_SECRET must be set"))?,
             },
             database: DatabaseConfig {
                 url: env::var("DATABASE_URL").map_err(|_| anyhow!("DATABASE_URL must be set"))?,
-                max_connections: env::var("DB_MAX_CONNECTIONS")
-                    .unwrap_or_else(|_| "10".to_string())
-                    .parse()
-                    .map_err(|e| anyhow!("Invalid DB_MAX_CONNECTIONS: {}", e))?,
+                max_connections: env_or_default("DB_MAX_CONNECTIONS", defaults::max_connections())?,
             },
-            ethereum: EthereumConfig::from_env()?,
-            mantle: MantleConfig::from_env()?,
+            ethereum: EthereumConfig::from_env(profile.as_ref().map(|p| &p.ethereum))?,
+            mantle: MantleConfig::from_env(profile.as_ref().map(|p| &p.mantle))?,
             relayer_address: env::var("RELAYER_ADDRESS")
                 .map_err(|_| anyhow!("RELAYER_ADDRESS must be set"))?,
             fee_collector: env::var("FEE_COLLECTOR")
                 .map_err(|_| anyhow!("FEE_COLLECTOR must be set"))?,
-        })
+            profile: None,
+        };
+
+        if let Some(profile) = profile {
+            config.apply_profile(profile)?;
+        }
+
+        Ok(config)
     }
-}
 
-impl EthereumConfig {
-    pub fn from_env() -> Result<Self> {
-        Ok(EthereumConfig {
-            rpc_url: env::var("ETHEREUM_RPC_URL")
-                .map_err(|_| anyhow!("ETHEREUM_RPC_URL must be set"))?,
-            ws_url: env::var("ETHEREUM_WS_URL").ok(),
-            private_key: env::var("ETHEREUM_PRIVATE_KEY")
-                .map_err(|_| anyhow!("ETHEREUM_PRIVATE_KEY must be set"))?,
-            intent_pool_address: env::var("ETHEREUM_INTENT_POOL_ADDRESS")
-                .map_err(|_| anyhow!("ETHEREUM_INTENT_POOL_ADDRESS must be set"))?,
-            settlement_address: env::var("ETHEREUM_SETTLEMENT_ADDRESS")
-                .map_err(|_| anyhow!("ETHEREUM_SETTLEMENT_ADDRESS must be set"))?,
-            chain_id: env::var("ETHEREUM_CHAIN_ID")
-                .unwrap_or_else(|_| "1".to_string())
-                .parse()
-                .map_err(|e| anyhow!("Invalid ETHEREUM_CHAIN_ID: {}", e))?,
-        })
+    /// Override chain IDs and contract addresses with the selected profile,
+    /// except where `ETHEREUM_*`/`MANTLE_*` variables set them.
+    pub fn apply_profile(&mut self, profile: NetworkProfile) -> Result<()> {
+        self.apply_profile_from(profile, |key| env::var(key).ok())
     }
 
-    pub fn validate(&self) -> Result<()> {
-        if !self.rpc_url.starts_with("http") {
-            return Err(anyhow!("Invalid RPC URL format"));
-        }
+    /// [`Self::apply_profile`] reading the variables from `var`. The stored
+    /// profile keeps the values in effect, so it validates what is used.
+    pub fn apply_profile_from(
+        &mut self,
+        mut profile: NetworkProfile,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<()> {
+        profile.ethereum.override_from("ETHEREUM", &var)?;
+        profile.mantle.override_from("MANTLE", &var)?;
 
-        if self.private_key.len() != 64 && self.private_key.len() != 66 {
-            return Err(anyhow!("Invalid private key length"));
-        }
+        self.ethereum.chain_id = profile.ethereum.chain_id;
+        self.ethereum.intent_pool_address = profile.ethereum.intent_pool_address.clone();
+        self.ethereum.settlement_address = profile.ethereum.settlement_address.clone();
 
-        if !self.intent_pool_address.starts_with("0x") || self.intent_pool_address.len() != 42 {
-            return Err(anyhow!("Invalid intent pool address"));
-        }
+        self.mantle.chain_id = profile.mantle.chain_id;
+        self.mantle.intent_pool_address = profile.mantle.intent_pool_address.clone();
+        self.mantle.settlement_address = profile.mantle.settlement_address.clone();
 
-        if !self.settlement_address.starts_with("0x") || self.settlement_address.len() != 42 {
-            return Err(anyhow!("Invalid settlement address"));
+        self.profile = Some(profile);
+        Ok(())
+    }
+
+    /// When `C`'s blocks count as final: `<CHAIN>_FINALITY`, else the
+    /// profile's confirmation depth, else the chain's default.
+    pub fn finality<C: ChainConfig>(&self) -> Result<FinalitySource> {
+        let default = self
+            .profile
+            .as_ref()
+            .and_then(|profile| profile.chain(C::KEY))
+            .and_then(ChainProfile::finality)
+            .unwrap_or_else(C::default_finality);
+        FinalitySource::from_env(C::ENV_PREFIX, default)
+    }
+
+    /// Validate the resolved configuration before any relayer is started.
+    pub fn validate(&self) -> Result<()> {
+        self.ethereum
+            .validate()
+            .map_err(|e| anyhow!("Ethereum config: {}", e))?;
+        self.mantle
+            .validate()
+            .map_err(|e| anyhow!("Mantle config: {}", e))?;
+
+        if let Some(profile) = &self.profile {
+            profile.validate()?;
         }
 
         Ok(())
     }
 }
 
+impl EthereumConfig {
+    pub fn from_env(profile: Option<&ChainProfile>) -> Result<Self> {
+        Ok(EthereumConfig {
+            rpc_url: env::var("ETHEREUM_RPC_URL")
+                .map_err(|_| anyhow!("ETHEREUM_RPC_URL must be set"))?,
+            ws_url: env::var("ETHEREUM_WS_URL").ok(),
+            private_key: env_private_key("ETHEREUM_PRIVATE_KEY")?,
+            intent_pool_address: env_or_profile(
+                "ETHEREUM_INTENT_POOL_ADDRESS",
+                profile.map(|p| p.intent_pool_address.as_str()),
+            )?,
+            settlement_address: env_or_profile(
+                "ETHEREUM_SETTLEMENT_ADDRESS",
+                profile.map(|p| p.settlement_address.as_str()),
+            )?,
+            chain_id: env_or_default("ETHEREUM_CHAIN_ID", defaults::ethereum_chain_id())?,
+        })
+    }
+}
+
 impl MantleConfig {
-    pub fn from_env() -> Result<Self> {
+    pub fn from_env(profile: Option<&ChainProfile>) -> Result<Self> {
         Ok(MantleConfig {
             rpc_url: env::var("MANTLE_RPC_URL")
                 .map_err(|_| anyhow!("MANTLE_RPC_URL must be set"))?,
             ws_url: env::var("MANTLE_WS_URL").ok(),
-            private_key: env::var("MANTLE_PRIVATE_KEY")
-                .map_err(|_| anyhow!("MANTLE_PRIVATE_KEY must be set"))?,
-            intent_pool_address: env::var("MANTLE_INTENT_POOL_ADDRESS")
-                .map_err(|_| anyhow!("MANTLE_INTENT_POOL_ADDRESS must be set"))?,
-            settlement_address: env::var("MANTLE_SETTLEMENT_ADDRESS")
-                .map_err(|_| anyhow!("MANTLE_SETTLEMENT_ADDRESS must be set"))?,
-            chain_id: env::var("MANTLE_CHAIN_ID")
-                .unwrap_or_else(|_| "5000".to_string())
-                .parse()
-                .map_err(|e| anyhow!("Invalid MANTLE_CHAIN_ID: {}", e))?,
+            private_key: env_private_key("MANTLE_PRIVATE_KEY")?,
+            intent_pool_address: env_or_profile(
+                "MANTLE_INTENT_POOL_ADDRESS",
+                profile.map(|p| p.intent_pool_address.as_str()),
+            )?,
+            settlement_address: env_or_profile(
+                "MANTLE_SETTLEMENT_ADDRESS",
+                profile.map(|p| p.settlement_address.as_str()),
+            )?,
+            chain_id: env_or_default("MANTLE_CHAIN_ID", defaults::mantle_chain_id())?,
         })
     }
+}
 
-    pub fn validate(&self) -> Result<()> {
-        if !self.rpc_url.starts_with("http") {
-            return Err(anyhow!("Invalid RPC URL format"));
-        }
+/// Endpoint checks shared by every chain.
+fn validate_chain<C: ChainConfig>(config: &C) -> Result<()> {
+    if !config.rpc_url().starts_with("http") {
+        return Err(anyhow!("Invalid RPC URL format"));
+    }
 
-        if self.private_key.len() != 64 && self.private_key.len() != 66 {
-            return Err(anyhow!("Invalid private key length"));
-        }
+    let key_len = config.private_key().len();
+    if !signer_delegated() && key_len != 64 && key_len != 66 {
+        return Err(anyhow!("Invalid private key length"));
+    }
 
-        if !self.intent_pool_address.starts_with("0x") || self.intent_pool_address.len() != 42 {
-            return Err(anyhow!("Invalid intent pool address"));
-        }
+    let is_address = |value: &str| value.starts_with("0x") && value.len() == 42;
+    if !is_address(config.intent_pool_address()) {
+        return Err(anyhow!("Invalid intent pool address"));
+    }
 
-        if !self.settlement_address.starts_with("0x") || self.settlement_address.len() != 42 {
-            return Err(anyhow!("Invalid settlement address"));
-        }
+    if !is_address(config.settlement_address()) {
+        return Err(anyhow!("Invalid settlement address"));
+    }
 
-        Ok(())
+    Ok(())
+}
+
+impl ChainConfig for EthereumConfig {
+    const NAME: &'static str = "Ethereum";
+    const KEY: &'static str = "ethereum";
+    const ENV_PREFIX: &'static str = "ETHEREUM";
+    const NATIVE_SYMBOL: &'static str = "ETH";
+    const LOW_BALANCE: &'static str = "0.1";
+
+    fn default_tx_strategy() -> ChainTxStrategy {
+        ChainTxStrategy::ethereum()
+    }
+
+    fn default_finality() -> FinalitySource {
+        FinalitySource::ethereum()
+    }
+
+    fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    fn private_key(&self) -> &str {
+        &self.private_key
+    }
+
+    fn intent_pool_address(&self) -> &str {
+        &self.intent_pool_address
+    }
+
+    fn settlement_address(&self) -> &str {
+        &self.settlement_address
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_chain(self)
+    }
+}
+
+impl ChainConfig for MantleConfig {
+    const NAME: &'static str = "Mantle";
+    const KEY: &'static str = "mantle";
+    const ENV_PREFIX: &'static str = "MANTLE";
+    const NATIVE_SYMBOL: &'static str = "MNT";
+    const LOW_BALANCE: &'static str = "0.5";
+
+    fn default_tx_strategy() -> ChainTxStrategy {
+        ChainTxStrategy::mantle()
+    }
+
+    fn default_finality() -> FinalitySource {
+        FinalitySource::mantle()
+    }
+
+    fn rpc_url(&self) -> &str {
+        &self.rpc_url
+    }
+
+    fn private_key(&self) -> &str {
+        &self.private_key
+    }
+
+    fn intent_pool_address(&self) -> &str {
+        &self.intent_pool_address
+    }
+
+    fn settlement_address(&self) -> &str {
+        &self.settlement_address
+    }
+
+    fn validate(&self) -> Result<()> {
+        validate_chain(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::schema::example;
+
+    #[test]
+    fn test_environment_wins_over_the_profile() {
+        let mut config = example().into_config(None).unwrap();
+        let profile = NetworkProfile::builtin("sepolia").unwrap();
+        let pool = "0x1111111111111111111111111111111111111111";
+
+        config
+            .apply_profile_from(profile.clone(), |key| match key {
+                "ETHEREUM_INTENT_POOL_ADDRESS" => Some(pool.to_string()),
+                "MANTLE_CHAIN_ID" => Some("5000".to_string()),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(config.ethereum.intent_pool_address, pool);
+        assert_eq!(
+            config.ethereum.settlement_address,
+            profile.ethereum.settlement_address
+        );
+        assert_eq!(config.ethereum.chain_id, profile.ethereum.chain_id);
+        assert_eq!(config.mantle.chain_id, 5000);
+        assert_eq!(
+            config.mantle.intent_pool_address,
+            profile.mantle.intent_pool_address
+        );
+
+        // The stored profile is what is in effect, so validation sees it
+        let applied = config.profile.as_ref().unwrap();
+        assert_eq!(applied.ethereum.intent_pool_address, pool);
+        let err = applied.validate().unwrap_err().to_string();
+        assert!(err.contains("chain_id 5000 is not supported"), "{}", err);
+
+        let bad = config.apply_profile_from(profile, |key| {
+            (key == "ETHEREUM_CHAIN_ID").then(|| "sepolia".to_string())
+        });
+        assert!(bad.unwrap_err().to_string().contains("ETHEREUM_CHAIN_ID"));
+    }
+
+    #[test]
+    fn test_profile_confirmations_set_the_finality_default() {
+        let mut config = example().into_config(None).unwrap();
+        assert_eq!(
+            config.finality::<EthereumConfig>().unwrap(),
+            FinalitySource::ethereum()
+        );
+
+        let mut profile = NetworkProfile::builtin("sepolia").unwrap();
+        profile.mantle.confirmations = None;
+        config.apply_profile_from(profile, |_| None).unwrap();
+        assert_eq!(
+            config.finality::<EthereumConfig>().unwrap(),
+            FinalitySource::Depth(3)
+        );
+        assert_eq!(
+            config.finality::<MantleConfig>().unwrap(),
+            FinalitySource::mantle()
+        );
     }
 }
@@ -1,23 +1,122 @@
-use actix_web::web;
+use actix_web::{middleware::from_fn, web};
 
-use crate::api::routes::{
-    convert_amount, get_all_prices, get_intent_status, get_metrics, get_price, get_stats,
-    health_check, indexer_event, initiate_bridge, list_intents, root,
+use crate::api::{
+    admin_auth::admin_authorization,
+    api_keys::public_access,
+    intent_socket::intent_feed_socket,
+    routes::{
+        acknowledge_contract_upgrade, annotate_intent, approve_orphaned_fill, convert_amount,
+        create_admin_key, create_client_key, create_webhook, delete_webhook, get_admin_audit_log,
+        get_all_prices, get_api_versions, get_backlog_progress, get_bridge_quote,
+        get_claim_auth_failures, get_claim_diagnosis, get_claim_estimate, get_claim_sponsorship,
+        get_contract_status, get_debug_capture, get_duplicate_commitments, get_fill_report,
+        get_intent_lifecycle, get_intent_status, get_intent_timeline, get_merkle_proof,
+        get_metrics, get_price, get_prometheus_metrics, get_reveal_status, get_root_window,
+        get_route_stats, get_stats, get_status_page, get_stuck_intents, get_sync_progress,
+        get_transaction_receipt, get_work_partitions, health_check, indexer_event, initiate_bridge,
+        list_admin_keys, list_client_keys, list_compliance_screenings, list_debug_captures,
+        list_deposit_discrepancies, list_intent_events, list_intent_transactions, list_intents,
+        list_merkle_repairs, list_orphaned_fills, list_relayer_jobs, list_token_listings,
+        list_user_intent_limits, list_webhooks, override_compliance_action, quote_intent,
+        release_intent, reserve_intent, reveal_secret, revoke_admin_key, revoke_client_key, root,
+        rotate_webhook_secret, set_token_listing, set_user_intent_limit, set_webhook_filter,
+        start_debug_capture, stop_debug_capture, submit_fill_report, test_webhook,
+        toggle_token_listing, update_relayer_job,
+    },
+    versioning::{legacy_versioning, v1_versioning},
 };
 
 pub fn configure(conf: &mut web::ServiceConfig) {
-    let scope = web::scope("/api/v1")
-        .service(web::resource("/bridge/initiate").route(web::post().to(initiate_bridge)))
+    // Order matters: the versioned scope and discovery route must be matched
+    // before the unversioned `/api` aliases swallow them.
+    conf.service(
+        web::scope("/api/v1")
+            .wrap(from_fn(public_access))
+            .wrap(from_fn(admin_authorization))
+            .wrap(from_fn(v1_versioning))
+            .configure(v1_routes),
+    )
+    .service(get_api_versions)
+    .service(get_status_page)
+    .service(get_prometheus_metrics)
+    .service(intent_feed_socket)
+    .service(
+        web::scope("/api")
+            .wrap(from_fn(public_access))
+            .wrap(from_fn(admin_authorization))
+            .wrap(from_fn(legacy_versioning))
+            .configure(v1_routes),
+    );
+}
+
+fn v1_routes(conf: &mut web::ServiceConfig) {
+    conf.service(web::resource("/bridge/initiate").route(web::post().to(initiate_bridge)))
+        .service(get_bridge_quote)
+        .service(quote_intent)
+        .service(reserve_intent)
+        .service(release_intent)
+        .service(submit_fill_report)
+        .service(get_fill_report)
         .service(get_intent_status)
+        .service(get_claim_sponsorship)
+        .service(list_intent_events)
+        .service(list_intent_transactions)
+        .service(get_intent_lifecycle)
+        .service(get_claim_diagnosis)
+        .service(get_claim_estimate)
+        .service(get_reveal_status)
+        .service(reveal_secret)
+        .service(get_transaction_receipt)
         .service(list_intents)
         .service(indexer_event)
+        .service(get_duplicate_commitments)
+        .service(get_backlog_progress)
+        .service(get_claim_auth_failures)
+        .service(get_stuck_intents)
+        .service(list_webhooks)
+        .service(create_webhook)
+        .service(rotate_webhook_secret)
+        .service(set_webhook_filter)
+        .service(test_webhook)
+        .service(delete_webhook)
+        .service(list_orphaned_fills)
+        .service(approve_orphaned_fill)
+        .service(list_relayer_jobs)
+        .service(start_debug_capture)
+        .service(list_debug_captures)
+        .service(get_debug_capture)
+        .service(stop_debug_capture)
+        .service(update_relayer_job)
+        .service(annotate_intent)
+        .service(get_intent_timeline)
+        .service(get_work_partitions)
+        .service(acknowledge_contract_upgrade)
+        .service(list_compliance_screenings)
+        .service(list_deposit_discrepancies)
+        .service(override_compliance_action)
+        .service(list_user_intent_limits)
+        .service(set_user_intent_limit)
+        .service(list_token_listings)
+        .service(set_token_listing)
+        .service(toggle_token_listing)
+        .service(list_admin_keys)
+        .service(create_admin_key)
+        .service(revoke_admin_key)
+        .service(get_admin_audit_log)
+        .service(list_merkle_repairs)
+        .service(list_client_keys)
+        .service(create_client_key)
+        .service(revoke_client_key)
+        .service(get_root_window)
+        .service(get_merkle_proof)
         .service(get_price)
         .service(get_all_prices)
         .service(convert_amount)
         .service(get_metrics)
         .service(get_stats)
+        .service(get_route_stats)
+        .service(get_sync_progress)
+        .service(get_contract_status)
         .service(health_check)
         .service(root);
-
-    conf.service(scope);
 }
@@ -1,2 +1,4 @@
 pub mod config;
 pub mod config_scope;
+pub mod profile;
+pub mod schema;
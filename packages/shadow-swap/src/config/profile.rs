@@ -0,0 +1,396 @@
+use anyhow::{Result, anyhow, bail};
+pub use mantle_core::token_registry::TokenProfile;
+use mantle_core::{
+    amount::to_decimal,
+    chain::{ETHEREUM_CHAIN_ID, MANTLE_CHAIN_ID},
+    finality::FinalitySource,
+    token::TokenType,
+    token_registry::TokenRegistry,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    env,
+    path::{Path, PathBuf},
+};
+
+/// Per-chain deployment settings selected by a network profile.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChainProfile {
+    pub chain_id: u32,
+    pub intent_pool_address: String,
+    pub settlement_address: String,
+    /// Blocks built on an event's block before it counts as final;
+    /// `<CHAIN>_FINALITY` takes precedence. Unset keeps the chain's default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confirmations: Option<u64>,
+    /// First block the startup sync and `resync` scan; `<CHAIN>_SYNC_FROM_BLOCK`
+    /// takes precedence.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sync_from_block: Option<u64>,
+}
+
+/// A named deployment (e.g. `sepolia`, `staging`) loaded from `[profiles.<name>]`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NetworkProfile {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub name: String,
+    pub ethereum: ChainProfile,
+    pub mantle: ChainProfile,
+    #[serde(default)]
+    pub tokens: BTreeMap<String, TokenProfile>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProfilesFile {
+    #[serde(default)]
+    profiles: BTreeMap<String, NetworkProfile>,
+}
+
+/// Profiles compiled in, selected with `NETWORK=<name>`.
+pub const BUILTIN_NETWORKS: [&str; 2] = ["sepolia", "local-anvil"];
+
+/// Contracts a fresh anvil gets from the devnet deployer's first three
+/// transactions: Poseidon, then the intent pool, then the settlement.
+const ANVIL_INTENT_POOL: &str = "0xe7f1725E7734CE288F8367e1Bb143E90bb3F0512";
+const ANVIL_SETTLEMENT: &str = "0x9fE46736679d2D9a65F0992F2272dE9f3c7fa6e0";
+
+/// The built-in token listings as profile tokens.
+pub fn builtin_tokens() -> BTreeMap<String, TokenProfile> {
+    TokenRegistry::builtin()
+        .listings()
+        .into_iter()
+        .map(|listing| {
+            let profile = TokenProfile {
+                ethereum: format!("{:?}", listing.ethereum),
+                mantle: format!("{:?}", listing.mantle),
+                decimals: listing.decimals,
+                min_amount: Some(to_decimal(listing.min_amount, listing.decimals).to_string()),
+                max_amount: Some(to_decimal(listing.max_amount, listing.decimals).to_string()),
+                enabled: listing.enabled,
+            };
+            (listing.token.symbol().to_string(), profile)
+        })
+        .collect()
+}
+
+/// `BRIDGE_CONFIG_FILE`, or `config.toml` in the working directory.
+pub fn config_file_path() -> PathBuf {
+    env::var("BRIDGE_CONFIG_FILE")
+        .unwrap_or_else(|_| "config.toml".to_string())
+        .into()
+}
+
+pub(crate) fn is_valid_address(address: &str) -> bool {
+    address.starts_with("0x")
+        && address.len() == 42
+        && address[2..].chars().all(|c| c.is_ascii_hexdigit())
+}
+
+impl ChainProfile {
+    fn new(chain_id: u32, intent_pool: &str, settlement: &str, confirmations: u64) -> Self {
+        Self {
+            chain_id,
+            intent_pool_address: intent_pool.to_string(),
+            settlement_address: settlement.to_string(),
+            confirmations: Some(confirmations),
+            sync_from_block: None,
+        }
+    }
+
+    /// The profile's confirmation depth as a finality rule.
+    pub fn finality(&self) -> Option<FinalitySource> {
+        self.confirmations.map(FinalitySource::Depth)
+    }
+
+    fn syncing_from(self, block: u64) -> Self {
+        Self {
+            sync_from_block: Some(block),
+            ..self
+        }
+    }
+
+    /// Take `<PREFIX>_CHAIN_ID`, `<PREFIX>_INTENT_POOL_ADDRESS` and
+    /// `<PREFIX>_SETTLEMENT_ADDRESS` from `var` over the profile's values.
+    pub fn override_from(
+        &mut self,
+        prefix: &str,
+        var: impl Fn(&str) -> Option<String>,
+    ) -> Result<()> {
+        if let Some(chain_id) = var(&format!("{}_CHAIN_ID", prefix)) {
+            self.chain_id = chain_id
+                .trim()
+                .parse()
+                .map_err(|e| anyhow!("Invalid {}_CHAIN_ID: {}", prefix, e))?;
+        }
+        if let Some(address) = var(&format!("{}_INTENT_POOL_ADDRESS", prefix)) {
+            self.intent_pool_address = address;
+        }
+        if let Some(address) = var(&format!("{}_SETTLEMENT_ADDRESS", prefix)) {
+            self.settlement_address = address;
+        }
+        Ok(())
+    }
+
+    fn validate(&self, label: &str) -> Result<()> {
+        if self.chain_id == 0 {
+            return Err(anyhow!("{}: chain_id must be non-zero", label));
+        }
+
+        for (field, address) in [
+            ("intent_pool_address", &self.intent_pool_address),
+            ("settlement_address", &self.settlement_address),
+        ] {
+            if address.is_empty() {
+                return Err(anyhow!("{}: {} is not set", label, field));
+            }
+            if !is_valid_address(address) {
+                return Err(anyhow!("{}: invalid {} {:?}", label, field, address));
+            }
+        }
+
+        if self.confirmations == Some(0) {
+            return Err(anyhow!("{}: confirmations must be at least 1", label));
+        }
+
+        Ok(())
+    }
+}
+
+impl NetworkProfile {
+    /// Parse `[profiles.*]` from a TOML document and return the named profile.
+    pub fn from_toml_str(contents: &str, name: &str) -> Result<Self> {
+        let file: ProfilesFile =
+            toml::from_str(contents).map_err(|e| anyhow!("Failed to parse profiles: {}", e))?;
+
+        Self::select(&file.profiles, name)
+    }
+
+    /// The profile called `name`, validated.
+    pub fn select(profiles: &BTreeMap<String, NetworkProfile>, name: &str) -> Result<Self> {
+        let mut profile = profiles.get(name).cloned().ok_or_else(|| {
+            let available: Vec<_> = profiles.keys().cloned().collect();
+            anyhow!(
+                "Unknown profile '{}' (available: {})",
+                name,
+                available.join(", ")
+            )
+        })?;
+
+        profile.name = name.to_string();
+        profile.validate()?;
+
+        Ok(profile)
+    }
+
+    pub fn from_file(path: &Path, name: &str) -> Result<Self> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| anyhow!("Failed to read config file {}: {}", path.display(), e))?;
+
+        Self::from_toml_str(&contents, name)
+    }
+
+    /// A compiled-in profile. There is no `mainnet` one until the workers,
+    /// merkle trees and event tables stop assuming the Sepolia chain IDs.
+    pub fn builtin(name: &str) -> Option<Self> {
+        let (ethereum, mantle, tokens) = match name {
+            "sepolia" => (
+                ChainProfile::new(
+                    ETHEREUM_CHAIN_ID,
+                    "0xcb46d916522D7c6853fcE2aa5F337e0a3626E263",
+                    "0x7CCC9864125143e6c530506772Eaf5595DC14897",
+                    3,
+                )
+                .syncing_from(9995018),
+                ChainProfile::new(
+                    MANTLE_CHAIN_ID,
+                    "0x6ebcF830b855108Fa44AbED6Ba964F2Af9C34424",
+                    "0x1c4F9eBeccE31cEFe2FDe415b05184b4ea46908f",
+                    1,
+                )
+                .syncing_from(33084800),
+                builtin_tokens(),
+            ),
+            // `shadow-swap devnet` runs anvil under the Sepolia chain IDs and
+            // puts mock tokens at the built-in token addresses
+            "local-anvil" => (
+                ChainProfile::new(ETHEREUM_CHAIN_ID, ANVIL_INTENT_POOL, ANVIL_SETTLEMENT, 1)
+                    .syncing_from(0),
+                ChainProfile::new(MANTLE_CHAIN_ID, ANVIL_INTENT_POOL, ANVIL_SETTLEMENT, 1)
+                    .syncing_from(0),
+                builtin_tokens(),
+            ),
+            _ => return None,
+        };
+
+        Some(Self {
+            name: name.to_string(),
+            ethereum,
+            mantle,
+            tokens,
+        })
+    }
+
+    /// The built-in profile `NETWORK` names, with contract addresses from
+    /// the environment taking precedence, validated.
+    pub fn from_network(name: &str) -> Result<Self> {
+        let mut profile = Self::builtin(name).ok_or_else(|| {
+            anyhow!(
+                "Unknown NETWORK '{}' (available: {})",
+                name,
+                BUILTIN_NETWORKS.join(", ")
+            )
+        })?;
+        profile
+            .ethereum
+            .override_from("ETHEREUM", |key| env::var(key).ok())?;
+        profile
+            .mantle
+            .override_from("MANTLE", |key| env::var(key).ok())?;
+
+        profile.validate().map_err(|e| {
+            anyhow!(
+                "{} (set ETHEREUM_/MANTLE_INTENT_POOL_ADDRESS and _SETTLEMENT_ADDRESS)",
+                e
+            )
+        })?;
+        Ok(profile)
+    }
+
+    /// The profile `BRIDGE_PROFILE` names in [`config_file_path`], or else
+    /// the built-in one `NETWORK` names. Setting both is an error.
+    pub fn from_env() -> Result<Option<Self>> {
+        match (env::var("BRIDGE_PROFILE"), env::var("NETWORK")) {
+            (Ok(_), Ok(_)) => bail!("Set either BRIDGE_PROFILE or NETWORK, not both"),
+            (Ok(name), _) => Self::from_file(&config_file_path(), &name).map(Some),
+            (_, Ok(network)) => Self::from_network(&network).map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        self.ethereum
+            .validate(&format!("profile '{}' ethereum", self.name))?;
+        self.mantle
+            .validate(&format!("profile '{}' mantle", self.name))?;
+
+        // Workers, merkle trees and event tables are keyed by these IDs
+        for (chain, profile, supported) in [
+            ("ethereum", &self.ethereum, ETHEREUM_CHAIN_ID),
+            ("mantle", &self.mantle, MANTLE_CHAIN_ID),
+        ] {
+            if profile.chain_id != supported {
+                return Err(anyhow!(
+                    "profile '{}' {}: chain_id {} is not supported (only {})",
+                    self.name,
+                    chain,
+                    profile.chain_id,
+                    supported
+                ));
+            }
+        }
+
+        for (symbol, token) in &self.tokens {
+            if !is_valid_address(&token.ethereum) || !is_valid_address(&token.mantle) {
+                return Err(anyhow!(
+                    "profile '{}': invalid address for token {}",
+                    self.name,
+                    symbol
+                ));
+            }
+
+            TokenType::from_symbol(symbol)
+                .and_then(|token_type| token.listing(token_type))
+                .map_err(|e| anyhow!("profile '{}': {}", self.name, e))?;
+        }
+
+        Ok(())
+    }
+
+    pub fn token(&self, symbol: &str) -> Option<&TokenProfile> {
+        self.tokens.get(&symbol.to_uppercase())
+    }
+
+    pub fn chain(&self, chain: &str) -> Option<&ChainProfile> {
+        match chain {
+            "ethereum" => Some(&self.ethereum),
+            "mantle" => Some(&self.mantle),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"
+[profiles.sepolia.ethereum]
+chain_id = 11155111
+intent_pool_address = "0xcb46d916522D7c6853fcE2aa5F337e0a3626E263"
+settlement_address = "0x7CCC9864125143e6c530506772Eaf5595DC14897"
+confirmations = 3
+
+[profiles.sepolia.mantle]
+chain_id = 5003
+intent_pool_address = "0x6ebcF830b855108Fa44AbED6Ba964F2Af9C34424"
+settlement_address = "0x1c4F9eBeccE31cEFe2FDe415b05184b4ea46908f"
+
+[profiles.sepolia.tokens.USDC]
+ethereum = "0x28650373758d75a8fF0B22587F111e47BAC34e21"
+mantle = "0xA4b184006B59861f80521649b14E4E8A72499A23"
+decimals = 6
+"#;
+
+    #[test]
+    fn test_load_named_profile() {
+        let profile = NetworkProfile::from_toml_str(SAMPLE, "sepolia").unwrap();
+
+        assert_eq!(profile.name, "sepolia");
+        assert_eq!(profile.ethereum.chain_id, 11155111);
+        assert_eq!(profile.ethereum.finality(), Some(FinalitySource::Depth(3)));
+        // Omitted, so the chain's default applies
+        assert_eq!(profile.mantle.finality(), None);
+        assert_eq!(profile.token("usdc").unwrap().decimals, 6);
+    }
+
+    #[test]
+    fn test_unknown_profile_rejected() {
+        let err = NetworkProfile::from_toml_str(SAMPLE, "mainnet").unwrap_err();
+        assert!(err.to_string().contains("sepolia"));
+    }
+
+    #[test]
+    fn test_invalid_address_rejected() {
+        let bad = SAMPLE.replace("0x7CCC9864125143e6c530506772Eaf5595DC14897", "0x1234");
+        assert!(NetworkProfile::from_toml_str(&bad, "sepolia").is_err());
+    }
+
+    #[test]
+    fn test_builtin_networks() {
+        for name in BUILTIN_NETWORKS {
+            let profile = NetworkProfile::builtin(name).unwrap();
+            assert_eq!(profile.name, name);
+            profile.validate().unwrap();
+        }
+
+        let sepolia = NetworkProfile::builtin("sepolia").unwrap();
+        sepolia.validate().unwrap();
+        assert_eq!(
+            sepolia.chain("mantle").unwrap().sync_from_block,
+            Some(33084800)
+        );
+        assert_eq!(sepolia.tokens.len(), 5);
+
+        // The rest of the relayer only knows the Sepolia chain IDs
+        assert!(NetworkProfile::builtin("mainnet").is_none());
+        let mainnet = SAMPLE
+            .replace("chain_id = 11155111", "chain_id = 1")
+            .replace("chain_id = 5003", "chain_id = 5000");
+        let err = NetworkProfile::from_toml_str(&mainnet, "sepolia").unwrap_err();
+        assert!(err.to_string().contains("chain_id 1 is not supported"));
+        assert!(NetworkProfile::builtin("goerli").is_none());
+    }
+}
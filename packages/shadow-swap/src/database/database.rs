@@ -1,34 +1,71 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-use anyhow::{Context, Result, anyhow};
+use anyhow::{Context, Result, anyhow, bail};
 use chrono::Utc;
 use diesel::pg::PgConnection;
 use diesel::prelude::*;
 use diesel::r2d2::{self, ConnectionManager, Pool};
 use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
 use dotenv::dotenv;
+use mantle_core::{
+    chain::{Chain, ETHEREUM_CHAIN_ID, MANTLE_CHAIN_ID},
+    hex_types::H256Hex,
+    parse,
+    token_registry::TokenListing,
+};
 use serde_json::Value;
 use tracing::{error, info, warn};
 
 use crate::database::model::{
-    BridgeStats, DbBridgeEvent, DbChainTransaction, DbMerkleNode, DbMerkleTree, NewBridgeEvent,
-    NewChainTransaction, NewMerkleNode, NewMerkleTree, NewRootSync,
+    BridgeStats, ChainRollback, ClaimAuthFailure, CommitmentOrder, DbAdminApiKey,
+    DbAdminAuditEntry, DbApiKey, DbBridgeEvent, DbChainTransaction, DbClaimAuthCheck,
+    DbClaimSponsorship, DbComplianceScreening, DbDebugCapture, DbDebugCaptureEntry,
+    DbDepositDiscrepancy, DbEncryptedNote, DbFillReport, DbIngestionEvent, DbIntentAnnotation,
+    DbIntentReservation, DbMerkleNode, DbMerkleRepair, DbMerkleRootHistory, DbMerkleTree,
+    DbOrphanedFill, DbPartitionAssignment, DbQuarantinedCommitment, DbRelayerInstance,
+    DbRelayerJob, DbRootSync, DbSecretReveal, DbTokenListing, DbTransactionReceipt, DbTxSubmission,
+    DbUserIntentLimit, DbWebhookEndpoint, DbWebhookSecret, DuplicateCommitment, IngestionBacklog,
+    IntentOutcome, IntentSample, IntentStatusGauge, NewAdminApiKey, NewAdminAuditEntry, NewApiKey,
+    NewBridgeEvent, NewChainTransaction, NewClaimAuthCheck, NewClaimSponsorship,
+    NewComplianceScreening, NewDepositDiscrepancy, NewIngestionEvent, NewIntentAnnotation,
+    NewMerkleNode, NewMerkleRepair, NewMerkleRootHistory, NewMerkleTree, NewOrphanedFill,
+    NewQuarantinedCommitment, NewRootSync, NewSecretReveal, NewTransactionReceipt,
+    NewUserIntentLimit, NewWebhookEndpoint, NewWebhookSecret,
 };
 
-use crate::models::model::{EthereumFill, IntentCreatedEvent, MantleFill};
+use crate::models::model::{BridgeEventType, EthereumFill, IntentCreatedEvent, MantleFill};
 use crate::models::schema::{
-    bridge_events, chain_transactions, indexer_checkpoints, merkle_trees, root_syncs,
+    admin_api_keys, admin_audit_log, api_keys, archived_intents, bridge_events, chain_block_hashes,
+    chain_transactions, claim_auth_checks, claim_sponsorships, compliance_screenings,
+    debug_capture_entries, debug_captures, deposit_discrepancies, fill_reports,
+    indexer_checkpoints, ingestion_queue, intent_annotations, intent_encrypted_notes,
+    intent_reservations, merkle_repairs, merkle_root_history, merkle_trees, metric_snapshots,
+    orphaned_fills, partition_assignments, quarantined_commitments, relayer_instances,
+    relayer_jobs, root_syncs, secret_reveals, token_listings, transaction_receipts, tx_submissions,
+    user_intent_limits, webhook_endpoints, webhook_secrets,
 };
 use crate::{
-    database::model::{DbIntent, DbIntentPrivacyParams, NewIntent, NewIntentPrivacyParams},
+    database::model::{
+        DbArchivedIntent, DbIntent, DbIntentPrivacyParams, DbPrivacyContextRekey, NewIntent,
+        NewIntentPrivacyParams, NewPrivacyContextRekey,
+    },
+    encryption::privacy_context::{
+        PrivacyContextKey, matches_legacy_context, privacy_context, verify_privacy_context,
+    },
     models::{
-        model::{Intent, IntentPrivacyParams, IntentStatus},
-        schema::{intent_privacy_params, intents},
+        model::{CommitmentUniqueness, Intent, IntentPrivacyParams, IntentStatus},
+        schema::{intent_privacy_params, intents, privacy_context_rekeys},
     },
 };
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+diesel::define_sql_function!(fn lower(x: diesel::sql_types::Text) -> diesel::sql_types::Text);
 pub const TREE_DEPTH: i32 = 20;
+/// Rows per multi-row insert, well under Postgres' bind parameter limit.
+const MERKLE_NODE_BATCH: usize = 1000;
+/// Advisory lock space for sponsorship reservations, one lock per chain ID.
+const SPONSORSHIP_LOCK_KEY: i64 = 0x5350_4f4e_0000_0000;
 
 pub type DbPool = Pool<ConnectionManager<PgConnection>>;
 
@@ -58,6 +95,7 @@ impl std::error::Error for DatabaseSetupError {}
 #[derive(Clone)]
 pub struct Database {
     pub pool: DbPool,
+    privacy_key: Option<PrivacyContextKey>,
 }
 
 impl Database {
@@ -68,7 +106,23 @@ impl Database {
             .build(manager)
             .context("Failed to create database pool")?;
 
-        Ok(Database { pool })
+        Ok(Database {
+            pool,
+            privacy_key: None,
+        })
+    }
+
+    /// Key for the privacy params context tags. Reading or writing privacy
+    /// params fails without one.
+    pub fn with_privacy_key(mut self, key: PrivacyContextKey) -> Self {
+        self.privacy_key = Some(key);
+        self
+    }
+
+    fn privacy_key(&self) -> Result<&PrivacyContextKey> {
+        self.privacy_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("Privacy context key not configured"))
     }
 
     pub fn health_check(&self) -> Result<()> {
@@ -94,6 +148,17 @@ impl Database {
         Ok(())
     }
 
+    /// Names of the embedded migrations not yet applied, oldest first.
+    pub fn pending_migrations(pool: &Pool<ConnectionManager<PgConnection>>) -> Result<Vec<String>> {
+        use diesel::migration::Migration;
+        let mut conn = pool.get().context("Failed to get database connection")?;
+        let pending = conn
+            .pending_migrations(MIGRATIONS)
+            .map_err(|e| anyhow!("Failed to list pending migrations: {}", e))?;
+
+        Ok(pending.iter().map(|m| m.name().to_string()).collect())
+    }
+
     pub fn from_env() -> Result<Self> {
         dotenv().ok();
 
@@ -116,7 +181,11 @@ impl Database {
             Database::run_migrations(&pool)?;
         }
 
-        Ok(Database { pool })
+        let privacy_key = PrivacyContextKey::from_env()?;
+        Ok(Database {
+            pool,
+            privacy_key: Some(privacy_key),
+        })
     }
 
     pub fn get_connection(
@@ -216,6 +285,7 @@ impl Database {
         intent: &Intent,
         privacy_params: &IntentPrivacyParams,
     ) -> Result<()> {
+        let privacy_key = self.privacy_key()?;
         let mut conn = self.get_connection()?;
 
         conn.transaction::<_, anyhow::Error, _>(|conn| {
@@ -249,6 +319,13 @@ impl Database {
                 .execute(conn)
                 .context("Failed to insert intent")?;
 
+            let context_tag = privacy_context(
+                privacy_key,
+                &IntentPrivacyParams {
+                    intent_id: intent.id.clone(),
+                    ..privacy_params.clone()
+                },
+            );
             let new_privacy = NewIntentPrivacyParams {
                 intent_id: &intent.id,
                 commitment: privacy_params.commitment.as_deref(),
@@ -258,6 +335,8 @@ impl Database {
                 claim_signature: privacy_params.claim_signature.as_deref(),
                 created_at: Utc::now(),
                 updated_at: Utc::now(),
+                context_tag: &context_tag,
+                claim_delegate: privacy_params.claim_delegate.as_deref(),
             };
 
             diesel::insert_into(intent_privacy_params::table)
@@ -272,17 +351,21 @@ impl Database {
     }
 
     pub fn upsert_intent(&self, intent: &Intent) -> Result<()> {
+        if self.is_intent_archived(&intent.id)? {
+            return Ok(());
+        }
+
         let mut conn = self.get_connection()?;
 
         let exists = intents::table
-            .filter(intents::id.eq(&intent.id))
+            .filter(intents::id.eq(intent.id.as_str()))
             .select(intents::id)
             .first::<String>(&mut conn)
             .optional()?
             .is_some();
 
         if exists {
-            diesel::update(intents::table.filter(intents::id.eq(&intent.id)))
+            diesel::update(intents::table.filter(intents::id.eq(intent.id.as_str())))
                 .set((
                     intents::user_address.eq(&intent.user_address),
                     intents::block_number.eq(intent.block_number),
@@ -332,6 +415,12 @@ impl Database {
         src_chain: &str,
     ) -> Result<()> {
         use crate::models::schema::intents::dsl::*;
+
+        // Re-reading an archived intent's event must not bring it back as committed
+        if self.is_intent_archived(&event.intent_id)? {
+            return Ok(());
+        }
+
         let mut conn = self.get_connection()?;
 
         let default_deadline = chrono::Utc::now().timestamp() + 3600;
@@ -374,18 +463,19 @@ impl Database {
         if inserted.is_some() {
             info!(
                 "✅ Inserted intent {} from chain event",
-                &event.intent_id[..10]
+                parse::short(&event.intent_id)
             );
         } else {
             info!(
                 "ℹ️ Intent {} already exists, skipping",
-                &event.intent_id[..10]
+                parse::short(&event.intent_id)
             );
         }
 
         Ok(())
     }
 
+    /// Archived intents are kept: event sync skips them when it re-reads their events.
     pub fn clear_all_intents_for_chain(&self, chain_name: &str) -> Result<()> {
         use crate::models::schema::intents::dsl::*;
         let mut conn = self.get_connection()?;
@@ -412,19 +502,13 @@ impl Database {
     }
 
     pub fn update_intent_secret(&self, intent_id: &str, secret: &str) -> Result<()> {
-        let mut conn = self.get_connection()?;
-
-        diesel::update(
-            intent_privacy_params::table.filter(intent_privacy_params::intent_id.eq(intent_id)),
-        )
-        .set((
-            intent_privacy_params::secret.eq(secret),
-            intent_privacy_params::updated_at.eq(Utc::now()),
-        ))
-        .execute(&mut conn)
-        .context("Failed to update intent secret")?;
+        let params = IntentPrivacyParams {
+            secret: Some(secret.to_string()),
+            ..self.get_intent_privacy_params(intent_id)?
+        };
 
-        Ok(())
+        self.update_privacy_params(intent_id, &params)
+            .context("Failed to update intent secret")
     }
 
     pub fn update_source_commitment(&self, intent_id: &str, commitment: &str) -> Result<()> {
@@ -464,8 +548,20 @@ impl Database {
             .first::<DbIntent>(&mut conn)
             .optional()
             .context("Failed to get intent by id")?;
+        if let Some(intent) = result {
+            return db_intent_to_model(intent).map(Some);
+        }
+
+        let archived = archived_intents::table
+            .filter(archived_intents::id.eq(intent_id))
+            .select(DbArchivedIntent::as_select())
+            .first::<DbArchivedIntent>(&mut conn)
+            .optional()
+            .context("Failed to get archived intent by id")?;
 
-        Ok(result.map(db_intent_to_model))
+        archived
+            .map(|intent| db_intent_to_model(intent.into()))
+            .transpose()
     }
 
     pub fn get_intents_by_status(&self, status: IntentStatus) -> Result<Vec<Intent>> {
@@ -478,7 +574,7 @@ impl Database {
             .load::<DbIntent>(&mut conn)
             .context("Failed to get intents by status")?;
 
-        Ok(results.into_iter().map(db_intent_to_model).collect())
+        results.into_iter().map(db_intent_to_model).collect()
     }
 
     pub fn get_pending_intents(&self) -> Result<Vec<Intent>> {
@@ -490,7 +586,86 @@ impl Database {
             .load::<DbIntent>(&mut conn)
             .context("Failed to get pending intents")?;
 
-        Ok(results.into_iter().map(db_intent_to_model).collect())
+        results.into_iter().map(db_intent_to_model).collect()
+    }
+
+    /// Count and oldest `created_at` per non-terminal status.
+    pub fn get_intent_status_gauges(&self) -> Result<Vec<IntentStatusGauge>> {
+        use diesel::dsl::count_star;
+
+        let mut conn = self.get_connection()?;
+
+        let rows: Vec<(String, i64, Option<chrono::DateTime<Utc>>)> = intents::table
+            .filter(intents::status.eq_any(vec![
+                "created",
+                "committed",
+                "registered",
+                "pending",
+                "filled",
+                "solver_paid",
+            ]))
+            .group_by(intents::status)
+            .select((
+                intents::status,
+                count_star(),
+                diesel::dsl::min(intents::created_at),
+            ))
+            .load(&mut conn)
+            .context("Failed to get intent status gauges")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(status, count, oldest_created_at)| IntentStatusGauge {
+                status,
+                count: count as u64,
+                oldest_created_at,
+            })
+            .collect())
+    }
+
+    /// Intents that reached a terminal status since `since`, using the last
+    /// status change as the finish time.
+    pub fn get_intent_outcomes_since(
+        &self,
+        since: chrono::DateTime<Utc>,
+    ) -> Result<Vec<IntentOutcome>> {
+        let mut conn = self.get_connection()?;
+
+        let results = intents::table
+            .filter(intents::status.eq_any(vec!["completed", "failed", "refunded"]))
+            .filter(intents::updated_at.ge(since))
+            .select((
+                intents::source_chain,
+                intents::dest_chain,
+                intents::status,
+                intents::created_at,
+                intents::updated_at,
+            ))
+            .load::<IntentOutcome>(&mut conn)
+            .context("Failed to get intent outcomes")?;
+
+        Ok(results)
+    }
+
+    /// All non-terminal intents, soonest deadline first.
+    pub fn get_backlog_intents(&self) -> Result<Vec<Intent>> {
+        let mut conn = self.get_connection()?;
+
+        let results = intents::table
+            .filter(intents::status.eq_any(vec![
+                "created",
+                "committed",
+                "registered",
+                "pending",
+                "filled",
+                "solver_paid",
+            ]))
+            .order(intents::deadline.asc())
+            .select(DbIntent::as_select())
+            .load::<DbIntent>(&mut conn)
+            .context("Failed to get backlog intents")?;
+
+        results.into_iter().map(db_intent_to_model).collect()
     }
 
     pub fn get_intents_awaiting_secret(&self) -> Result<Vec<Intent>> {
@@ -506,19 +681,56 @@ impl Database {
             .load::<DbIntent>(&mut conn)
             .context("Failed to get intents awaiting secret")?;
 
-        Ok(results.into_iter().map(db_intent_to_model).collect())
+        results.into_iter().map(db_intent_to_model).collect()
+    }
+
+    /// Privacy params of an intent, refused when they are not bound to it.
+    /// Intents changed at or after `since`, oldest change first.
+    pub fn get_intents_changed_since(
+        &self,
+        since: chrono::DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<Intent>> {
+        let mut conn = self.get_connection()?;
+
+        let results = intents::table
+            .filter(intents::updated_at.ge(since))
+            .order((intents::updated_at.asc(), intents::id.asc()))
+            .limit(limit)
+            .select(DbIntent::as_select())
+            .load::<DbIntent>(&mut conn)
+            .context("Failed to get changed intents")?;
+
+        results.into_iter().map(db_intent_to_model).collect()
     }
 
     pub fn get_intent_privacy_params(&self, intent_id: &str) -> Result<IntentPrivacyParams> {
         let mut conn = self.get_connection()?;
 
-        let params = intent_privacy_params::table
+        let row = intent_privacy_params::table
             .filter(intent_privacy_params::intent_id.eq(intent_id))
             .select(DbIntentPrivacyParams::as_select())
             .first::<DbIntentPrivacyParams>(&mut conn)
             .context("Failed to get intent privacy params")?;
+        drop(conn);
+
+        let context_tag = row.context_tag.clone();
+        let params = IntentPrivacyParams::try_from(row)?;
+        let source_commitment = self
+            .get_intent_by_id(intent_id)?
+            .and_then(|intent| intent.source_commitment);
+
+        if let Err(e) = verify_privacy_context(
+            self.privacy_key()?,
+            &params,
+            &context_tag,
+            source_commitment.as_deref(),
+        ) {
+            error!("🚨 Refusing privacy params for {}: {}", intent_id, e);
+            return Err(e);
+        }
 
-        Ok(IntentPrivacyParams::from(params))
+        Ok(params)
     }
 
     pub fn list_intents(
@@ -550,38 +762,33 @@ impl Database {
             .load::<DbIntent>(&mut conn)
             .context("Failed to list intents")?;
 
-        Ok(results.into_iter().map(db_intent_to_model).collect())
+        results.into_iter().map(db_intent_to_model).collect()
     }
 
-    pub fn store_intent_privacy_params(
-        &self,
-        intent_id: &str,
-        commitment: &str,
-        secret: &str,
-        nullifier: &str,
-        claim_auth: &str,
-        recipient: &str,
-    ) -> Result<()> {
+    /// Store the params submitted with an intent, replacing any earlier
+    /// submission. The intent id is lowercased.
+    pub fn store_intent_privacy_params(&self, params: &IntentPrivacyParams) -> Result<()> {
         let mut conn = self.get_connection()?;
-        let normalized_id = intent_id.to_lowercase();
-
-        if normalized_id.len() < 66 {
-            warn!(
-                "⚠️ Storing privacy params with a short ID: {} (length: {}). This may not match indexer records!",
-                normalized_id,
-                normalized_id.len()
-            );
-        }
-
+        let normalized_id = H256Hex::from(params.intent_id.h256());
+
+        let context_tag = privacy_context(
+            self.privacy_key()?,
+            &IntentPrivacyParams {
+                intent_id: normalized_id.clone(),
+                ..params.clone()
+            },
+        );
         let new_params = NewIntentPrivacyParams {
-            intent_id: &normalized_id,
-            commitment: Some(commitment),
-            secret: Some(secret),
-            nullifier: Some(nullifier),
-            claim_signature: Some(claim_auth),
-            recipient: Some(recipient),
+            intent_id: normalized_id.as_str(),
+            commitment: params.commitment.as_deref(),
+            secret: params.secret.as_deref(),
+            nullifier: params.nullifier.as_deref(),
+            claim_signature: params.claim_signature.as_deref(),
+            recipient: params.recipient.as_deref(),
             created_at: chrono::Utc::now(),
             updated_at: chrono::Utc::now(),
+            context_tag: &context_tag,
+            claim_delegate: params.claim_delegate.as_deref(),
         };
 
         diesel::insert_into(intent_privacy_params::table)
@@ -589,12 +796,14 @@ impl Database {
             .on_conflict(intent_privacy_params::intent_id)
             .do_update()
             .set((
-                intent_privacy_params::commitment.eq(Some(commitment)),
-                intent_privacy_params::secret.eq(Some(secret)),
-                intent_privacy_params::nullifier.eq(Some(nullifier)),
-                intent_privacy_params::claim_signature.eq(Some(claim_auth)),
-                intent_privacy_params::recipient.eq(Some(recipient)),
+                intent_privacy_params::commitment.eq(new_params.commitment),
+                intent_privacy_params::secret.eq(new_params.secret),
+                intent_privacy_params::nullifier.eq(new_params.nullifier),
+                intent_privacy_params::claim_signature.eq(new_params.claim_signature),
+                intent_privacy_params::recipient.eq(new_params.recipient),
+                intent_privacy_params::claim_delegate.eq(new_params.claim_delegate),
                 intent_privacy_params::updated_at.eq(chrono::Utc::now()),
+                intent_privacy_params::context_tag.eq(&context_tag),
             ))
             .execute(&mut conn)
             .context("Failed to store privacy params")?;
@@ -609,6 +818,13 @@ impl Database {
         privacy_params: &IntentPrivacyParams,
     ) -> Result<()> {
         let mut conn = self.get_connection()?;
+        let context_tag = privacy_context(
+            self.privacy_key()?,
+            &IntentPrivacyParams {
+                intent_id: H256Hex::parse("intent_id", intent_id)?,
+                ..privacy_params.clone()
+            },
+        );
 
         diesel::update(
             intent_privacy_params::table.filter(intent_privacy_params::intent_id.eq(intent_id)),
@@ -619,7 +835,9 @@ impl Database {
             intent_privacy_params::secret.eq(privacy_params.secret.as_deref()),
             intent_privacy_params::recipient.eq(privacy_params.recipient.as_deref()),
             intent_privacy_params::claim_signature.eq(privacy_params.claim_signature.as_deref()),
+            intent_privacy_params::claim_delegate.eq(privacy_params.claim_delegate.as_deref()),
             intent_privacy_params::updated_at.eq(Utc::now()),
+            intent_privacy_params::context_tag.eq(&context_tag),
         ))
         .execute(&mut conn)
         .context("Failed to update privacy params")?;
@@ -627,10 +845,88 @@ impl Database {
         Ok(())
     }
 
+    /// Replace the unkeyed v1 context tags with keyed ones, once. Only rows
+    /// whose v1 tag still matches their contents are re-tagged; the rest are
+    /// reported and left for reads to refuse. Fails if already run.
+    pub fn rekey_privacy_contexts(&self, dry_run: bool) -> Result<NewPrivacyContextRekey> {
+        let privacy_key = self.privacy_key()?;
+        let mut conn = self.get_connection()?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            diesel::sql_query("LOCK TABLE privacy_context_rekeys IN EXCLUSIVE MODE")
+                .execute(conn)
+                .context("Failed to lock privacy context rekeys")?;
+            let done = privacy_context_rekeys::table
+                .select(DbPrivacyContextRekey::as_select())
+                .first::<DbPrivacyContextRekey>(conn)
+                .optional()
+                .context("Failed to check privacy context rekeys")?;
+            if let Some(done) = done {
+                bail!(
+                    "Privacy contexts were already re-keyed at {}",
+                    done.completed_at
+                );
+            }
+
+            let rows = intent_privacy_params::table
+                .select(DbIntentPrivacyParams::as_select())
+                .order(intent_privacy_params::intent_id.asc())
+                .load::<DbIntentPrivacyParams>(conn)
+                .context("Failed to load privacy params")?;
+
+            let mut report = NewPrivacyContextRekey::default();
+            for row in rows {
+                let context_tag = row.context_tag.clone();
+                let intent_id = row.intent_id.clone();
+                let params = IntentPrivacyParams::try_from(row)?;
+
+                if !matches_legacy_context(&params, &context_tag) {
+                    warn!("⚠️ Privacy params for {} fail their v1 context", intent_id);
+                    report.unverified.push(intent_id);
+                    continue;
+                }
+
+                report.rekeyed += 1;
+                if !dry_run {
+                    diesel::update(
+                        intent_privacy_params::table
+                            .filter(intent_privacy_params::intent_id.eq(&intent_id)),
+                    )
+                    .set(
+                        intent_privacy_params::context_tag
+                            .eq(privacy_context(privacy_key, &params)),
+                    )
+                    .execute(conn)
+                    .context("Failed to re-key privacy params")?;
+                }
+            }
+
+            if !dry_run {
+                diesel::insert_into(privacy_context_rekeys::table)
+                    .values(&report)
+                    .execute(conn)
+                    .context("Failed to record privacy context rekey")?;
+            }
+
+            Ok(report)
+        })
+    }
+
+    /// Whether `rekey-privacy-contexts` still has to run.
+    pub fn privacy_contexts_need_rekey(&self) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+        let runs: i64 = privacy_context_rekeys::table
+            .count()
+            .get_result(&mut conn)
+            .context("Failed to check privacy context rekeys")?;
+
+        Ok(runs == 0)
+    }
+
     pub fn update_intent(&self, intent: &Intent) -> Result<()> {
         let mut conn = self.get_connection()?;
 
-        diesel::update(intents::table.filter(intents::id.eq(&intent.id)))
+        diesel::update(intents::table.filter(intents::id.eq(intent.id.as_str())))
             .set((
                 intents::status.eq(intent.status.as_str()),
                 intents::solver_address.eq(intent.solver_address.as_deref()),
@@ -649,26 +945,28 @@ impl Database {
     pub fn record_intent_event(
         &self,
         intent_id: &str,
-        event_type: &str,
+        event_type: BridgeEventType,
         chain: &str,
         tx_hash: &str,
         block_number: u64,
         log_index: Option<i32>,
     ) -> Result<()> {
-        let chain_id = match chain {
-            "ethereum" => 11155111,
-            "mantle" => 5003,
-            _ => 0,
-        };
+        let chain_id = Chain::parse(chain).map_or(0, |chain| chain.id() as i32);
 
         let event_data = serde_json::json!({
             "intent_id": intent_id,
-            "event_type": event_type,
+            "event_type": event_type.as_str(),
             "chain": chain,
             "block_number": block_number,
         });
 
-        let event_id = format!("{}_{}_{}_{}", event_type, chain, tx_hash, block_number);
+        let event_id = format!(
+            "{}_{}_{}_{}",
+            event_type.as_str(),
+            chain,
+            tx_hash,
+            block_number
+        );
 
         self.store_bridge_event(
             &event_id,
@@ -727,7 +1025,7 @@ impl Database {
         self.store_bridge_event(
             &event_id,
             Some(intent_id),
-            "nullifier_used",
+            BridgeEventType::NullifierUsed,
             event_data,
             0,
             0,
@@ -795,7 +1093,7 @@ impl Database {
             .load::<DbIntent>(&mut conn)
             .context("Failed to get intents by solver")?;
 
-        Ok(results.into_iter().map(db_intent_to_model).collect())
+        results.into_iter().map(db_intent_to_model).collect()
     }
 
     pub fn get_solver_stats(&self, solver_address: &str) -> Result<(i64, f64)> {
@@ -856,6 +1154,102 @@ impl Database {
         Ok(())
     }
 
+    pub fn store_transaction_receipt(&self, receipt: &NewTransactionReceipt) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::insert_into(transaction_receipts::table)
+            .values(receipt)
+            .on_conflict(transaction_receipts::tx_hash)
+            .do_update()
+            .set((
+                transaction_receipts::status.eq(receipt.status),
+                transaction_receipts::block_number.eq(receipt.block_number),
+                transaction_receipts::gas_used.eq(receipt.gas_used),
+                transaction_receipts::effective_gas_price.eq(receipt.effective_gas_price),
+                transaction_receipts::revert_reason.eq(receipt.revert_reason),
+                transaction_receipts::receipt.eq(&receipt.receipt),
+            ))
+            .execute(&mut conn)
+            .context("Failed to store transaction receipt")?;
+
+        Ok(())
+    }
+
+    pub fn get_transaction_receipt(&self, tx_hash: &str) -> Result<Option<DbTransactionReceipt>> {
+        let mut conn = self.get_connection()?;
+
+        let result = transaction_receipts::table
+            .filter(transaction_receipts::tx_hash.eq(tx_hash.to_lowercase()))
+            .select(DbTransactionReceipt::as_select())
+            .first::<DbTransactionReceipt>(&mut conn)
+            .optional()
+            .context("Failed to get transaction receipt")?;
+
+        Ok(result)
+    }
+
+    /// Receipts stored for any of `tx_hashes`; hashes without one are
+    /// skipped.
+    pub fn get_transaction_receipts(
+        &self,
+        tx_hashes: &[String],
+    ) -> Result<Vec<DbTransactionReceipt>> {
+        let mut conn = self.get_connection()?;
+        let tx_hashes: Vec<String> = tx_hashes.iter().map(|hash| hash.to_lowercase()).collect();
+
+        transaction_receipts::table
+            .filter(transaction_receipts::tx_hash.eq_any(&tx_hashes))
+            .select(DbTransactionReceipt::as_select())
+            .load(&mut conn)
+            .context("Failed to get transaction receipts")
+    }
+
+    /// Most recent reverted transaction sent for an intent.
+    pub fn get_latest_reverted_receipt(
+        &self,
+        intent_id: &str,
+    ) -> Result<Option<DbTransactionReceipt>> {
+        let mut conn = self.get_connection()?;
+
+        let result = transaction_receipts::table
+            .filter(transaction_receipts::intent_id.eq(intent_id))
+            .filter(transaction_receipts::status.eq("reverted"))
+            .order(transaction_receipts::created_at.desc())
+            .select(DbTransactionReceipt::as_select())
+            .first::<DbTransactionReceipt>(&mut conn)
+            .optional()
+            .context("Failed to get latest reverted receipt")?;
+
+        Ok(result)
+    }
+
+    /// Delete receipts older than `cutoff`. Reverted receipts are kept when
+    /// `keep_reverted` is set so failures stay available for forensics.
+    pub fn prune_transaction_receipts(
+        &self,
+        cutoff: chrono::DateTime<Utc>,
+        keep_reverted: bool,
+    ) -> Result<usize> {
+        let mut conn = self.get_connection()?;
+
+        let deleted = if keep_reverted {
+            diesel::delete(
+                transaction_receipts::table
+                    .filter(transaction_receipts::created_at.lt(cutoff))
+                    .filter(transaction_receipts::status.ne("reverted")),
+            )
+            .execute(&mut conn)
+        } else {
+            diesel::delete(
+                transaction_receipts::table.filter(transaction_receipts::created_at.lt(cutoff)),
+            )
+            .execute(&mut conn)
+        }
+        .context("Failed to prune transaction receipts")?;
+
+        Ok(deleted)
+    }
+
     pub fn get_transaction_by_hash(&self, tx_hash: &str) -> Result<Option<DbChainTransaction>> {
         let mut conn = self.get_connection()?;
 
@@ -869,58 +1263,2720 @@ impl Database {
         Ok(result)
     }
 
-    // ==================== Bridge Events ====================
+    /// Rows on `chain_id` still `pending` since before `older_than` (unix seconds), oldest first.
+    pub fn get_stale_pending_transactions(
+        &self,
+        chain_id: u32,
+        older_than: i64,
+        limit: i64,
+    ) -> Result<Vec<DbChainTransaction>> {
+        let mut conn = self.get_connection()?;
 
-    pub fn store_bridge_event(
+        let results = chain_transactions::table
+            .filter(chain_transactions::chain_id.eq(chain_id as i32))
+            .filter(chain_transactions::status.eq("pending"))
+            .filter(chain_transactions::timestamp.lt(older_than))
+            .order(chain_transactions::timestamp.asc())
+            .limit(limit)
+            .select(DbChainTransaction::as_select())
+            .load::<DbChainTransaction>(&mut conn)
+            .context("Failed to get stale pending transactions")?;
+
+        Ok(results)
+    }
+
+    // ==================== Commitment Uniqueness ====================
+
+    /// Find an intent that used `commitment` before `intent_id`, within the
+    /// same tree or globally. Quarantined intents don't count, so checking
+    /// the original again never turns up its own duplicates.
+    pub fn find_commitment_conflict(
         &self,
-        event_id: &str,
-        intent_id: Option<&str>,
-        event_type: &str,
-        event_data: Value,
-        chain_id: i32,
-        block_number: i64,
-        log_index: Option<i32>,
-        transaction_hash: &str,
+        intent_id: &str,
+        commitment: &str,
+        chain: &str,
+        scope: CommitmentUniqueness,
+    ) -> Result<Option<(String, String)>> {
+        if scope == CommitmentUniqueness::Off {
+            return Ok(None);
+        }
+
+        let mut conn = self.get_connection()?;
+        let normalized_id = intent_id.to_lowercase();
+
+        let quarantined = quarantined_commitments::table.select(quarantined_commitments::intent_id);
+        let mut query = intents::table
+            .filter(
+                intents::source_commitment
+                    .eq_any(vec![commitment.to_string(), commitment.to_lowercase()]),
+            )
+            .filter(intents::id.ne(&normalized_id))
+            .filter(intents::id.ne_all(quarantined))
+            .select(CommitmentOrder::as_select())
+            .order((intents::created_at.asc(), intents::id.asc()))
+            .into_boxed();
+
+        if scope == CommitmentUniqueness::PerTree {
+            query = query.filter(intents::source_chain.eq(chain));
+        }
+
+        let candidates = query
+            .load::<CommitmentOrder>(&mut conn)
+            .context("Failed to check commitment uniqueness")?;
+        let this = intents::table
+            .filter(intents::id.eq(&normalized_id))
+            .select(CommitmentOrder::as_select())
+            .first::<CommitmentOrder>(&mut conn)
+            .optional()
+            .context("Failed to load intent for commitment check")?;
+
+        Ok(candidates
+            .into_iter()
+            .find(|candidate| this.as_ref().is_none_or(|this| candidate.precedes(this)))
+            .map(|candidate| (candidate.id, candidate.source_chain)))
+    }
+
+    /// Quarantine `intent_id` if its commitment collides with an existing intent.
+    ///
+    /// Quarantined intents stay in the trees (the contracts already accepted them)
+    /// but are marked failed so the workers never register or settle them.
+    pub fn quarantine_if_duplicate(
+        &self,
+        intent_id: &str,
+        commitment: &str,
+        chain: &str,
+        scope: CommitmentUniqueness,
+    ) -> Result<Option<String>> {
+        let Some((conflicting_id, conflicting_chain)) =
+            self.find_commitment_conflict(intent_id, commitment, chain, scope)?
+        else {
+            return Ok(None);
+        };
+
+        let mut conn = self.get_connection()?;
+        let normalized_id = intent_id.to_lowercase();
+
+        let record = NewQuarantinedCommitment {
+            intent_id: &normalized_id,
+            commitment: &commitment.to_lowercase(),
+            chain,
+            conflicting_intent_id: &conflicting_id,
+            conflicting_chain: &conflicting_chain,
+            scope: scope.as_str(),
+            detected_at: Utc::now(),
+        };
+
+        diesel::insert_into(quarantined_commitments::table)
+            .values(&record)
+            .on_conflict(quarantined_commitments::intent_id)
+            .do_nothing()
+            .execute(&mut conn)
+            .context("Failed to quarantine commitment")?;
+
+        diesel::update(intents::table.filter(intents::id.eq(&normalized_id)))
+            .set((
+                intents::status.eq(IntentStatus::Failed.as_str()),
+                intents::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to mark quarantined intent as failed")?;
+
+        warn!(
+            "🚧 Quarantined intent {} on {}: commitment already used by {} on {}",
+            normalized_id, chain, conflicting_id, conflicting_chain
+        );
+
+        Ok(Some(conflicting_id))
+    }
+
+    pub fn list_quarantined_commitments(&self, limit: i64) -> Result<Vec<DbQuarantinedCommitment>> {
+        let mut conn = self.get_connection()?;
+
+        let records = quarantined_commitments::table
+            .order(quarantined_commitments::detected_at.desc())
+            .limit(limit)
+            .select(DbQuarantinedCommitment::as_select())
+            .load(&mut conn)
+            .context("Failed to list quarantined commitments")?;
+
+        Ok(records)
+    }
+
+    /// Commitments used by more than one intent, oldest intent first.
+    pub fn find_duplicate_commitments(&self) -> Result<Vec<DuplicateCommitment>> {
+        let mut conn = self.get_connection()?;
+
+        let duplicates = diesel::sql_query(
+            "SELECT lower(source_commitment) AS commitment, \
+                    array_agg(id ORDER BY created_at, id) AS intent_ids, \
+                    array_agg(source_chain::text ORDER BY created_at, id) AS chains \
+             FROM intents \
+             WHERE source_commitment IS NOT NULL \
+             GROUP BY lower(source_commitment) \
+             HAVING count(*) > 1",
+        )
+        .load::<DuplicateCommitment>(&mut conn)
+        .context("Failed to find duplicate commitments")?;
+
+        Ok(duplicates)
+    }
+
+    // ==================== Claim Sponsorships ====================
+
+    pub fn set_claim_sponsorship(&self, intent_id: &str, sponsored: bool) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let normalized_id = intent_id.to_lowercase();
+
+        let new_sponsorship = NewClaimSponsorship {
+            intent_id: &normalized_id,
+            sponsored,
+            chain_id: None,
+            claim_tx_hash: None,
+            gas_used: None,
+            gas_cost_wei: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        diesel::insert_into(claim_sponsorships::table)
+            .values(&new_sponsorship)
+            .on_conflict(claim_sponsorships::intent_id)
+            .do_update()
+            .set((
+                claim_sponsorships::sponsored.eq(sponsored),
+                claim_sponsorships::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to set claim sponsorship")?;
+
+        Ok(())
+    }
+
+    pub fn get_claim_sponsorship(&self, intent_id: &str) -> Result<Option<DbClaimSponsorship>> {
+        let mut conn = self.get_connection()?;
+
+        let result = claim_sponsorships::table
+            .filter(claim_sponsorships::intent_id.eq(intent_id.to_lowercase()))
+            .select(DbClaimSponsorship::as_select())
+            .first::<DbClaimSponsorship>(&mut conn)
+            .optional()
+            .context("Failed to get claim sponsorship")?;
+
+        Ok(result)
+    }
+
+    pub fn record_sponsored_claim(
+        &self,
+        intent_id: &str,
+        chain_id: u32,
+        tx_hash: &str,
+        gas_used: u64,
+        gas_cost_wei: &str,
     ) -> Result<()> {
         let mut conn = self.get_connection()?;
+        let normalized_id = intent_id.to_lowercase();
+
+        let new_sponsorship = NewClaimSponsorship {
+            intent_id: &normalized_id,
+            sponsored: true,
+            chain_id: Some(chain_id as i32),
+            claim_tx_hash: Some(tx_hash),
+            gas_used: Some(gas_used as i64),
+            gas_cost_wei: Some(gas_cost_wei),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        diesel::insert_into(claim_sponsorships::table)
+            .values(&new_sponsorship)
+            .on_conflict(claim_sponsorships::intent_id)
+            .do_update()
+            .set((
+                claim_sponsorships::chain_id.eq(Some(chain_id as i32)),
+                claim_sponsorships::claim_tx_hash.eq(Some(tx_hash)),
+                claim_sponsorships::gas_used.eq(Some(gas_used as i64)),
+                claim_sponsorships::gas_cost_wei.eq(Some(gas_cost_wei)),
+                claim_sponsorships::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to record sponsored claim")?;
+
+        Ok(())
+    }
+
+    /// Hold `estimated_wei` of a chain's daily sponsorship cap for a claim
+    /// about to be sent, unless that would go over `cap_wei`. Reservations on
+    /// one chain are serialized, so concurrent claims cannot all fit.
+    pub fn reserve_sponsored_spend(
+        &self,
+        intent_id: &str,
+        chain_id: u32,
+        estimated_wei: u128,
+        cap_wei: u128,
+        since: chrono::DateTime<Utc>,
+    ) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+        let normalized_id = intent_id.to_lowercase();
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            diesel::sql_query("SELECT pg_advisory_xact_lock($1)")
+                .bind::<diesel::sql_types::BigInt, _>(SPONSORSHIP_LOCK_KEY + i64::from(chain_id))
+                .execute(conn)?;
+
+            // A retried claim replaces its own earlier hold
+            let costs: Vec<Option<String>> = claim_sponsorships::table
+                .filter(claim_sponsorships::chain_id.eq(chain_id as i32))
+                .filter(claim_sponsorships::updated_at.ge(since))
+                .filter(claim_sponsorships::intent_id.ne(&normalized_id))
+                .select(claim_sponsorships::gas_cost_wei)
+                .load(conn)?;
+            let spent: u128 = costs
+                .into_iter()
+                .flatten()
+                .filter_map(|cost| cost.parse::<u128>().ok())
+                .sum();
+            if spent.saturating_add(estimated_wei) > cap_wei {
+                return Ok(false);
+            }
+
+            let estimated = estimated_wei.to_string();
+            diesel::insert_into(claim_sponsorships::table)
+                .values(&NewClaimSponsorship {
+                    intent_id: &normalized_id,
+                    sponsored: true,
+                    chain_id: Some(chain_id as i32),
+                    claim_tx_hash: None,
+                    gas_used: None,
+                    gas_cost_wei: Some(&estimated),
+                    created_at: Utc::now(),
+                    updated_at: Utc::now(),
+                })
+                .on_conflict(claim_sponsorships::intent_id)
+                .do_update()
+                .set((
+                    claim_sponsorships::chain_id.eq(Some(chain_id as i32)),
+                    claim_sponsorships::gas_cost_wei.eq(Some(&estimated)),
+                    claim_sponsorships::updated_at.eq(Utc::now()),
+                ))
+                .execute(conn)?;
+            Ok(true)
+        })
+        .context("Failed to reserve sponsored spend")
+    }
+
+    /// Drop the hold of a claim that was never sent. Recorded claims keep
+    /// their cost.
+    pub fn release_sponsored_spend(&self, intent_id: &str) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(
+            claim_sponsorships::table
+                .filter(claim_sponsorships::intent_id.eq(intent_id.to_lowercase()))
+                .filter(claim_sponsorships::claim_tx_hash.is_null()),
+        )
+        .set((
+            claim_sponsorships::gas_cost_wei.eq(None::<String>),
+            claim_sponsorships::updated_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .context("Failed to release sponsored spend")?;
+
+        Ok(())
+    }
+
+    /// Total wei spent on sponsored claims on a chain since the given time.
+    pub fn get_sponsored_spend_since(
+        &self,
+        chain_id: u32,
+        since: chrono::DateTime<Utc>,
+    ) -> Result<u128> {
+        let mut conn = self.get_connection()?;
+
+        let costs: Vec<Option<String>> = claim_sponsorships::table
+            .filter(claim_sponsorships::chain_id.eq(chain_id as i32))
+            .filter(claim_sponsorships::updated_at.ge(since))
+            .select(claim_sponsorships::gas_cost_wei)
+            .load(&mut conn)
+            .context("Failed to load sponsored claim costs")?;
+
+        Ok(costs
+            .into_iter()
+            .flatten()
+            .filter_map(|cost| cost.parse::<u128>().ok())
+            .sum())
+    }
+
+    // ==================== User Intent Limits ====================
+
+    /// Intents from `user_address` that still need relayer work.
+    pub fn count_active_intents_for_user(&self, user_address: &str) -> Result<i64> {
+        let mut conn = self.get_connection()?;
+
+        intents::table
+            .filter(lower(intents::user_address).eq(user_address.to_lowercase()))
+            .filter(intents::status.eq_any(vec![
+                "created",
+                "committed",
+                "registered",
+                "pending",
+                "filled",
+                "solver_paid",
+            ]))
+            .count()
+            .get_result(&mut conn)
+            .context("Failed to count active intents for user")
+    }
+
+    pub fn get_user_intent_limit(&self, user_address: &str) -> Result<Option<DbUserIntentLimit>> {
+        let mut conn = self.get_connection()?;
+
+        user_intent_limits::table
+            .find(user_address.to_lowercase())
+            .select(DbUserIntentLimit::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to get user intent limit")
+    }
+
+    pub fn list_user_intent_limits(&self) -> Result<Vec<DbUserIntentLimit>> {
+        let mut conn = self.get_connection()?;
+
+        user_intent_limits::table
+            .order(user_intent_limits::updated_at.desc())
+            .select(DbUserIntentLimit::as_select())
+            .load(&mut conn)
+            .context("Failed to list user intent limits")
+    }
+
+    pub fn set_user_intent_limit(
+        &self,
+        user_address: &str,
+        max_active: i32,
+        note: Option<&str>,
+    ) -> Result<DbUserIntentLimit> {
+        let mut conn = self.get_connection()?;
+        let normalized = user_address.to_lowercase();
+
+        let limit = NewUserIntentLimit {
+            user_address: &normalized,
+            max_active,
+            note,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+
+        diesel::insert_into(user_intent_limits::table)
+            .values(&limit)
+            .on_conflict(user_intent_limits::user_address)
+            .do_update()
+            .set((
+                user_intent_limits::max_active.eq(max_active),
+                user_intent_limits::note.eq(note),
+                user_intent_limits::updated_at.eq(Utc::now()),
+            ))
+            .returning(DbUserIntentLimit::as_returning())
+            .get_result(&mut conn)
+            .context("Failed to set user intent limit")
+    }
+
+    /// Returns false when there was no override to remove.
+    pub fn clear_user_intent_limit(&self, user_address: &str) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let deleted = diesel::delete(user_intent_limits::table.find(user_address.to_lowercase()))
+            .execute(&mut conn)
+            .context("Failed to clear user intent limit")?;
+
+        Ok(deleted > 0)
+    }
+
+    // ==================== Claim Auth Checks ====================
+
+    pub fn record_claim_auth_check(
+        &self,
+        intent_id: &str,
+        valid: bool,
+        signer: Option<&str>,
+        error: Option<&str>,
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let normalized_id = intent_id.to_lowercase();
+
+        let check = NewClaimAuthCheck {
+            intent_id: &normalized_id,
+            valid,
+            signer,
+            error,
+            checked_at: Utc::now(),
+        };
+
+        diesel::insert_into(claim_auth_checks::table)
+            .values(&check)
+            .on_conflict(claim_auth_checks::intent_id)
+            .do_update()
+            .set((
+                claim_auth_checks::valid.eq(valid),
+                claim_auth_checks::signer.eq(signer),
+                claim_auth_checks::error.eq(error),
+                claim_auth_checks::checked_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to record claim auth check")?;
+
+        Ok(())
+    }
+
+    /// Failing checks on intents that have not yet been claimed or refunded.
+    pub fn list_claim_auth_failures(&self) -> Result<Vec<ClaimAuthFailure>> {
+        let mut conn = self.get_connection()?;
+
+        let rows: Vec<(DbClaimAuthCheck, String, i64)> = claim_auth_checks::table
+            .inner_join(intents::table.on(intents::id.eq(claim_auth_checks::intent_id)))
+            .filter(claim_auth_checks::valid.eq(false))
+            .filter(intents::status.eq_any(vec![
+                "created",
+                "committed",
+                "registered",
+                "pending",
+                "filled",
+                "solver_paid",
+            ]))
+            .order(intents::deadline.asc())
+            .select((
+                DbClaimAuthCheck::as_select(),
+                intents::status,
+                intents::deadline,
+            ))
+            .load(&mut conn)
+            .context("Failed to list claim auth failures")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(check, status, deadline)| ClaimAuthFailure {
+                check,
+                status,
+                deadline,
+            })
+            .collect())
+    }
+
+    // ==================== Compliance Screenings ====================
+
+    pub fn get_compliance_screening(
+        &self,
+        intent_id: &str,
+    ) -> Result<Option<DbComplianceScreening>> {
+        let mut conn = self.get_connection()?;
+
+        compliance_screenings::table
+            .find(intent_id.to_lowercase())
+            .select(DbComplianceScreening::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to get compliance screening")
+    }
+
+    /// Store a provider screening. An admin override note on an existing row
+    /// is kept.
+    pub fn record_compliance_screening(&self, screening: &NewComplianceScreening) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let normalized_id = screening.intent_id.to_lowercase();
+        let screening = NewComplianceScreening {
+            intent_id: &normalized_id,
+            ..*screening
+        };
+
+        diesel::insert_into(compliance_screenings::table)
+            .values(&screening)
+            .on_conflict(compliance_screenings::intent_id)
+            .do_update()
+            .set((
+                compliance_screenings::address.eq(screening.address),
+                compliance_screenings::chain.eq(screening.chain),
+                compliance_screenings::risk_score.eq(screening.risk_score),
+                compliance_screenings::labels.eq(screening.labels),
+                compliance_screenings::action.eq(screening.action),
+                compliance_screenings::reason.eq(screening.reason),
+                compliance_screenings::provider_error.eq(screening.provider_error),
+                compliance_screenings::screened_at.eq(screening.screened_at),
+                compliance_screenings::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to record compliance screening")?;
+
+        Ok(())
+    }
+
+    pub fn list_compliance_screenings(
+        &self,
+        action: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<DbComplianceScreening>> {
+        let mut conn = self.get_connection()?;
+
+        let mut query = compliance_screenings::table.into_boxed();
+        if let Some(action) = action {
+            query = query.filter(compliance_screenings::action.eq(action));
+        }
+
+        query
+            .order(compliance_screenings::screened_at.desc())
+            .limit(limit)
+            .select(DbComplianceScreening::as_select())
+            .load(&mut conn)
+            .context("Failed to list compliance screenings")
+    }
+
+    /// Intents a screening (or an admin) decided to block. Blocks that came
+    /// from a provider error are left out so they get screened again.
+    pub fn get_compliance_blocked_intent_ids(&self) -> Result<HashSet<String>> {
+        let mut conn = self.get_connection()?;
+
+        let ids = compliance_screenings::table
+            .filter(compliance_screenings::action.eq("block"))
+            .filter(compliance_screenings::provider_error.is_null())
+            .select(compliance_screenings::intent_id)
+            .load::<String>(&mut conn)
+            .context("Failed to get blocked intents")?;
+
+        Ok(ids.into_iter().collect())
+    }
+
+    /// Replace the action on a screened intent. Returns `None` if the intent
+    /// was never screened.
+    pub fn override_compliance_action(
+        &self,
+        intent_id: &str,
+        action: &str,
+        note: &str,
+    ) -> Result<Option<DbComplianceScreening>> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(compliance_screenings::table.find(intent_id.to_lowercase()))
+            .set((
+                compliance_screenings::action.eq(action),
+                compliance_screenings::override_note.eq(note),
+                compliance_screenings::provider_error.eq(None::<String>),
+                compliance_screenings::updated_at.eq(Utc::now()),
+            ))
+            .returning(DbComplianceScreening::as_returning())
+            .get_result(&mut conn)
+            .optional()
+            .context("Failed to override compliance action")
+    }
+
+    // ==================== Secret Reveals ====================
+
+    /// Mark an intent as user-held: the secret arrives later through a reveal.
+    pub fn create_secret_reveal(&self, intent_id: &str) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let normalized_id = intent_id.to_lowercase();
+
+        diesel::insert_into(secret_reveals::table)
+            .values(&NewSecretReveal {
+                intent_id: &normalized_id,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+            })
+            .on_conflict(secret_reveals::intent_id)
+            .do_nothing()
+            .execute(&mut conn)
+            .context("Failed to create secret reveal")?;
+
+        Ok(())
+    }
+
+    pub fn get_secret_reveal(&self, intent_id: &str) -> Result<Option<DbSecretReveal>> {
+        let mut conn = self.get_connection()?;
+
+        secret_reveals::table
+            .find(intent_id.to_lowercase())
+            .select(DbSecretReveal::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to get secret reveal")
+    }
+
+    /// Reveals that are still waiting for the user.
+    pub fn get_unrevealed_secret_reveals(&self) -> Result<Vec<DbSecretReveal>> {
+        let mut conn = self.get_connection()?;
+
+        secret_reveals::table
+            .filter(secret_reveals::secret.is_null())
+            .select(DbSecretReveal::as_select())
+            .load(&mut conn)
+            .context("Failed to get unrevealed secret reveals")
+    }
+
+    /// Start the reveal window. Does nothing once a deadline is set.
+    pub fn open_secret_reveal(&self, intent_id: &str, reveal_deadline: i64) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(
+            secret_reveals::table
+                .find(intent_id.to_lowercase())
+                .filter(secret_reveals::reveal_deadline.is_null()),
+        )
+        .set((
+            secret_reveals::reveal_deadline.eq(reveal_deadline),
+            secret_reveals::updated_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .context("Failed to open secret reveal")?;
+
+        Ok(())
+    }
+
+    /// Store the revealed secret. Only the first reveal is accepted; returns
+    /// false when the intent was already revealed.
+    pub fn record_secret_reveal(
+        &self,
+        intent_id: &str,
+        secret: &str,
+        signature: &str,
+    ) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let updated = diesel::update(
+            secret_reveals::table
+                .find(intent_id.to_lowercase())
+                .filter(secret_reveals::secret.is_null()),
+        )
+        .set((
+            secret_reveals::secret.eq(secret),
+            secret_reveals::signature.eq(signature),
+            secret_reveals::revealed_at.eq(Utc::now()),
+            secret_reveals::updated_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .context("Failed to record secret reveal")?;
+
+        Ok(updated == 1)
+    }
+
+    // ==================== Webhooks ====================
+
+    /// Register an endpoint together with its first signing secret.
+    pub fn create_webhook_endpoint(
+        &self,
+        endpoint: &NewWebhookEndpoint,
+        key_id: &str,
+        secret: &str,
+    ) -> Result<DbWebhookEndpoint> {
+        let mut conn = self.get_connection()?;
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            let created = diesel::insert_into(webhook_endpoints::table)
+                .values(endpoint)
+                .returning(DbWebhookEndpoint::as_returning())
+                .get_result(conn)
+                .context("Failed to create webhook endpoint")?;
+
+            diesel::insert_into(webhook_secrets::table)
+                .values(&NewWebhookSecret {
+                    endpoint_id: endpoint.id,
+                    key_id,
+                    secret,
+                    created_at: endpoint.created_at,
+                })
+                .execute(conn)
+                .context("Failed to store webhook secret")?;
+
+            Ok(created)
+        })
+    }
+
+    pub fn list_webhook_endpoints(&self) -> Result<Vec<DbWebhookEndpoint>> {
+        let mut conn = self.get_connection()?;
+
+        webhook_endpoints::table
+            .order(webhook_endpoints::created_at.asc())
+            .select(DbWebhookEndpoint::as_select())
+            .load(&mut conn)
+            .context("Failed to list webhook endpoints")
+    }
+
+    pub fn get_webhook_endpoint(&self, endpoint_id: &str) -> Result<Option<DbWebhookEndpoint>> {
+        let mut conn = self.get_connection()?;
+
+        webhook_endpoints::table
+            .find(endpoint_id)
+            .select(DbWebhookEndpoint::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to get webhook endpoint")
+    }
+
+    /// Replace an endpoint's filter; `None` sends it every event.
+    pub fn set_webhook_filter(
+        &self,
+        endpoint_id: &str,
+        filter: Option<&str>,
+    ) -> Result<Option<DbWebhookEndpoint>> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(webhook_endpoints::table.find(endpoint_id))
+            .set((
+                webhook_endpoints::filter.eq(filter),
+                webhook_endpoints::updated_at.eq(Utc::now()),
+            ))
+            .returning(DbWebhookEndpoint::as_returning())
+            .get_result(&mut conn)
+            .optional()
+            .context("Failed to set webhook filter")
+    }
+
+    /// Secrets that still sign deliveries at `now`, newest first.
+    pub fn get_active_webhook_secrets(
+        &self,
+        now: chrono::DateTime<Utc>,
+    ) -> Result<Vec<DbWebhookSecret>> {
+        let mut conn = self.get_connection()?;
+
+        webhook_secrets::table
+            .filter(
+                webhook_secrets::expires_at
+                    .is_null()
+                    .or(webhook_secrets::expires_at.gt(now)),
+            )
+            .order(webhook_secrets::created_at.desc())
+            .select(DbWebhookSecret::as_select())
+            .load(&mut conn)
+            .context("Failed to get active webhook secrets")
+    }
+
+    /// Add a new signing secret and let the current ones expire at
+    /// `retire_at`. Secrets already past their expiry are deleted. Returns
+    /// false when the endpoint does not exist.
+    pub fn rotate_webhook_secret(
+        &self,
+        endpoint_id: &str,
+        key_id: &str,
+        secret: &str,
+        retire_at: chrono::DateTime<Utc>,
+    ) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+        let now = Utc::now();
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            let updated = diesel::update(webhook_endpoints::table.find(endpoint_id))
+                .set(webhook_endpoints::updated_at.eq(now))
+                .execute(conn)
+                .context("Failed to touch webhook endpoint")?;
+            if updated == 0 {
+                return Ok(false);
+            }
+
+            diesel::delete(
+                webhook_secrets::table
+                    .filter(webhook_secrets::endpoint_id.eq(endpoint_id))
+                    .filter(webhook_secrets::expires_at.le(now)),
+            )
+            .execute(conn)
+            .context("Failed to delete expired webhook secrets")?;
+
+            // Never extend a secret that was already due to expire sooner
+            diesel::update(
+                webhook_secrets::table
+                    .filter(webhook_secrets::endpoint_id.eq(endpoint_id))
+                    .filter(
+                        webhook_secrets::expires_at
+                            .is_null()
+                            .or(webhook_secrets::expires_at.gt(retire_at)),
+                    ),
+            )
+            .set(webhook_secrets::expires_at.eq(retire_at))
+            .execute(conn)
+            .context("Failed to retire webhook secrets")?;
+
+            diesel::insert_into(webhook_secrets::table)
+                .values(&NewWebhookSecret {
+                    endpoint_id,
+                    key_id,
+                    secret,
+                    created_at: now,
+                })
+                .execute(conn)
+                .context("Failed to store webhook secret")?;
+
+            Ok(true)
+        })
+    }
+
+    /// Remove an endpoint and its secrets. Returns false when it didn't exist.
+    pub fn delete_webhook_endpoint(&self, endpoint_id: &str) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let deleted = diesel::delete(webhook_endpoints::table.find(endpoint_id))
+            .execute(&mut conn)
+            .context("Failed to delete webhook endpoint")?;
+
+        Ok(deleted == 1)
+    }
+
+    // ==================== Orphaned Fills ====================
+
+    /// Intents that reached `status` since `since`, e.g. recent refunds.
+    pub fn get_intents_updated_since(
+        &self,
+        status: IntentStatus,
+        since: chrono::DateTime<Utc>,
+    ) -> Result<Vec<Intent>> {
+        let mut conn = self.get_connection()?;
+
+        let results = intents::table
+            .filter(intents::status.eq(status.as_str()))
+            .filter(intents::updated_at.ge(since))
+            .order(intents::updated_at.asc())
+            .select(DbIntent::as_select())
+            .load::<DbIntent>(&mut conn)
+            .context("Failed to get recently updated intents")?;
+
+        results.into_iter().map(db_intent_to_model).collect()
+    }
+
+    /// Record a newly found orphan. Returns false when it was already known.
+    pub fn create_orphaned_fill(&self, orphan: &NewOrphanedFill) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let inserted = diesel::insert_into(orphaned_fills::table)
+            .values(orphan)
+            .on_conflict(orphaned_fills::intent_id)
+            .do_nothing()
+            .execute(&mut conn)
+            .context("Failed to create orphaned fill")?;
+
+        Ok(inserted == 1)
+    }
+
+    pub fn get_orphaned_fill(&self, intent_id: &str) -> Result<Option<DbOrphanedFill>> {
+        let mut conn = self.get_connection()?;
+
+        orphaned_fills::table
+            .find(intent_id.to_lowercase())
+            .select(DbOrphanedFill::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to get orphaned fill")
+    }
+
+    /// Orphans, newest first, optionally only those in `status`.
+    pub fn list_orphaned_fills(&self, status: Option<&str>) -> Result<Vec<DbOrphanedFill>> {
+        let mut conn = self.get_connection()?;
+
+        let mut query = orphaned_fills::table
+            .select(DbOrphanedFill::as_select())
+            .order(orphaned_fills::detected_at.desc())
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(orphaned_fills::status.eq(status));
+        }
+
+        query
+            .load(&mut conn)
+            .context("Failed to list orphaned fills")
+    }
+
+    /// Record a deposit that differs from its intent's amount. An underpaid
+    /// intent that has not moved past `committed` becomes `underpaid` in the
+    /// same transaction. False when the intent was already recorded.
+    pub fn record_deposit_discrepancy(&self, row: &NewDepositDiscrepancy) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        conn.transaction(|conn| {
+            let inserted = diesel::insert_into(deposit_discrepancies::table)
+                .values(row)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            if inserted == 0 {
+                return Ok(false);
+            }
+
+            if row.kind == "underpaid" {
+                diesel::update(
+                    intents::table
+                        .find(row.intent_id)
+                        .filter(intents::status.eq_any([
+                            IntentStatus::Created.as_str(),
+                            IntentStatus::Committed.as_str(),
+                            IntentStatus::Pending.as_str(),
+                        ])),
+                )
+                .set((
+                    intents::status.eq(IntentStatus::Underpaid.as_str()),
+                    intents::updated_at.eq(Utc::now()),
+                ))
+                .execute(conn)?;
+            }
+
+            Ok::<_, diesel::result::Error>(true)
+        })
+        .context("Failed to record deposit discrepancy")
+    }
+
+    /// Deposit discrepancies, newest first, optionally only those of `kind`.
+    pub fn list_deposit_discrepancies(
+        &self,
+        kind: Option<&str>,
+    ) -> Result<Vec<DbDepositDiscrepancy>> {
+        let mut conn = self.get_connection()?;
+
+        let mut query = deposit_discrepancies::table
+            .select(DbDepositDiscrepancy::as_select())
+            .order(deposit_discrepancies::detected_at.desc())
+            .into_boxed();
+        if let Some(kind) = kind {
+            query = query.filter(deposit_discrepancies::kind.eq(kind));
+        }
+
+        query
+            .load(&mut conn)
+            .context("Failed to list deposit discrepancies")
+    }
+
+    // ==================== Intent Reservations ====================
+
+    pub fn get_intent_reservation(&self, intent_id: &str) -> Result<Option<DbIntentReservation>> {
+        let mut conn = self.get_connection()?;
+
+        intent_reservations::table
+            .find(intent_id.to_lowercase())
+            .select(DbIntentReservation::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to get intent reservation")
+    }
+
+    /// Give `solver` the intent until `expires_at` when `grant` accepts the
+    /// intent's current reservation, read under a row lock. Returns whether
+    /// it was granted.
+    pub fn reserve_intent(
+        &self,
+        intent_id: &str,
+        solver: &str,
+        expires_at: chrono::DateTime<Utc>,
+        mut grant: impl FnMut(Option<&DbIntentReservation>) -> bool,
+    ) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+        let (intent_id, solver) = (intent_id.to_lowercase(), solver.to_lowercase());
+
+        conn.transaction(|conn| {
+            // A second pass only runs when another reservation was inserted
+            // between the read and our insert
+            for _ in 0..2 {
+                let current: Option<DbIntentReservation> = intent_reservations::table
+                    .find(&intent_id)
+                    .select(DbIntentReservation::as_select())
+                    .for_update()
+                    .first(conn)
+                    .optional()?;
+                if !grant(current.as_ref()) {
+                    return Ok(false);
+                }
+
+                let now = Utc::now();
+                let values = (
+                    intent_reservations::intent_id.eq(&intent_id),
+                    intent_reservations::solver_address.eq(&solver),
+                    intent_reservations::status.eq("active"),
+                    intent_reservations::reserved_at.eq(now),
+                    intent_reservations::expires_at.eq(expires_at),
+                    intent_reservations::updated_at.eq(now),
+                );
+                if current.is_some() {
+                    diesel::update(intent_reservations::table.find(&intent_id))
+                        .set(values)
+                        .execute(conn)?;
+                    return Ok(true);
+                }
+                let inserted = diesel::insert_into(intent_reservations::table)
+                    .values(values)
+                    .on_conflict_do_nothing()
+                    .execute(conn)?;
+                if inserted == 1 {
+                    return Ok(true);
+                }
+            }
+
+            Ok::<_, diesel::result::Error>(false)
+        })
+        .context("Failed to reserve intent")
+    }
+
+    /// Move the intent's `active` reservation to `status`, optionally only
+    /// when `solver` holds it. Returns the reservation as it was.
+    pub fn close_intent_reservation(
+        &self,
+        intent_id: &str,
+        solver: Option<&str>,
+        status: &str,
+    ) -> Result<Option<DbIntentReservation>> {
+        let mut conn = self.get_connection()?;
+
+        let solver = solver.map(str::to_lowercase);
+
+        conn.transaction(|conn| {
+            let Some(reservation) = intent_reservations::table
+                .find(intent_id.to_lowercase())
+                .filter(intent_reservations::status.eq("active"))
+                .select(DbIntentReservation::as_select())
+                .for_update()
+                .first(conn)
+                .optional()?
+            else {
+                return Ok(None);
+            };
+            if solver
+                .as_ref()
+                .is_some_and(|solver| *solver != reservation.solver_address)
+            {
+                return Ok(None);
+            }
+
+            diesel::update(intent_reservations::table.find(&reservation.intent_id))
+                .set((
+                    intent_reservations::status.eq(status),
+                    intent_reservations::updated_at.eq(Utc::now()),
+                ))
+                .execute(conn)?;
+
+            Ok::<_, diesel::result::Error>(Some(reservation))
+        })
+        .context("Failed to close intent reservation")
+    }
+
+    /// Mark `active` reservations past `expires_at` as `lapsed` and return
+    /// them.
+    pub fn lapse_expired_reservations(
+        &self,
+        now: chrono::DateTime<Utc>,
+    ) -> Result<Vec<DbIntentReservation>> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(
+            intent_reservations::table
+                .filter(intent_reservations::status.eq("active"))
+                .filter(intent_reservations::expires_at.lt(now)),
+        )
+        .set((
+            intent_reservations::status.eq("lapsed"),
+            intent_reservations::updated_at.eq(now),
+        ))
+        .returning(DbIntentReservation::as_returning())
+        .get_results(&mut conn)
+        .context("Failed to lapse expired reservations")
+    }
+
+    // ==================== Fill Reports ====================
+
+    /// Store a solver's fill report, or return the one already stored for the
+    /// same intent, chain and transaction.
+    pub fn record_fill_report(
+        &self,
+        intent_id: &str,
+        chain: &str,
+        transaction_hash: &str,
+        solver: &str,
+    ) -> Result<DbFillReport> {
+        let mut conn = self.get_connection()?;
+        let (intent_id, transaction_hash) =
+            (intent_id.to_lowercase(), transaction_hash.to_lowercase());
+
+        diesel::insert_into(fill_reports::table)
+            .values((
+                fill_reports::intent_id.eq(&intent_id),
+                fill_reports::chain.eq(chain),
+                fill_reports::transaction_hash.eq(&transaction_hash),
+                fill_reports::solver_address.eq(solver.to_lowercase()),
+            ))
+            .on_conflict_do_nothing()
+            .execute(&mut conn)
+            .context("Failed to record fill report")?;
+
+        fill_reports::table
+            .filter(fill_reports::intent_id.eq(&intent_id))
+            .filter(fill_reports::chain.eq(chain))
+            .filter(fill_reports::transaction_hash.eq(&transaction_hash))
+            .select(DbFillReport::as_select())
+            .first(&mut conn)
+            .context("Failed to read fill report")
+    }
+
+    pub fn get_fill_report(&self, id: i64) -> Result<Option<DbFillReport>> {
+        let mut conn = self.get_connection()?;
+
+        fill_reports::table
+            .find(id)
+            .select(DbFillReport::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to get fill report")
+    }
+
+    /// Oldest pending reports first.
+    pub fn get_pending_fill_reports(&self, limit: i64) -> Result<Vec<DbFillReport>> {
+        let mut conn = self.get_connection()?;
+
+        fill_reports::table
+            .filter(fill_reports::status.eq("pending"))
+            .order(fill_reports::reported_at.asc())
+            .limit(limit)
+            .select(DbFillReport::as_select())
+            .load(&mut conn)
+            .context("Failed to get pending fill reports")
+    }
+
+    /// Move a pending report to `verified` or `rejected`.
+    pub fn resolve_fill_report(&self, id: i64, status: &str, reason: Option<&str>) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(
+            fill_reports::table
+                .find(id)
+                .filter(fill_reports::status.eq("pending")),
+        )
+        .set((
+            fill_reports::status.eq(status),
+            fill_reports::reason.eq(reason),
+            fill_reports::resolved_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .context("Failed to resolve fill report")?;
+
+        Ok(())
+    }
+
+    pub fn get_orphaned_fill_ids(&self) -> Result<HashSet<String>> {
+        let mut conn = self.get_connection()?;
+
+        let ids = orphaned_fills::table
+            .select(orphaned_fills::intent_id)
+            .load::<String>(&mut conn)
+            .context("Failed to get orphaned fill ids")?;
+
+        Ok(ids.into_iter().collect())
+    }
+
+    /// Move an orphan from `from` to `to`. Returns false when it was not in
+    /// `from`, so concurrent transitions cannot both apply.
+    pub fn transition_orphaned_fill(&self, intent_id: &str, from: &str, to: &str) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let updated = diesel::update(
+            orphaned_fills::table
+                .find(intent_id.to_lowercase())
+                .filter(orphaned_fills::status.eq(from)),
+        )
+        .set((
+            orphaned_fills::status.eq(to),
+            orphaned_fills::updated_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .context("Failed to update orphaned fill status")?;
+
+        Ok(updated == 1)
+    }
+
+    /// Operator sign-off on a large recovery. Returns false unless the orphan
+    /// was awaiting approval.
+    pub fn approve_orphaned_fill(&self, intent_id: &str) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let updated = diesel::update(
+            orphaned_fills::table
+                .find(intent_id.to_lowercase())
+                .filter(orphaned_fills::status.eq("awaiting_approval")),
+        )
+        .set((
+            orphaned_fills::status.eq("pending"),
+            orphaned_fills::approved_at.eq(Some(Utc::now())),
+            orphaned_fills::updated_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .context("Failed to approve orphaned fill")?;
+
+        Ok(updated == 1)
+    }
+
+    pub fn mark_orphaned_fill_recovered(&self, intent_id: &str, tx_hash: &str) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(orphaned_fills::table.find(intent_id.to_lowercase()))
+            .set((
+                orphaned_fills::status.eq("recovered"),
+                orphaned_fills::recovery_txid.eq(Some(tx_hash)),
+                orphaned_fills::last_error.eq(None::<String>),
+                orphaned_fills::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to mark orphaned fill recovered")?;
+
+        Ok(())
+    }
+
+    /// Count a failed recovery attempt and move the orphan to `status`.
+    pub fn record_orphan_recovery_failure(
+        &self,
+        intent_id: &str,
+        error: &str,
+        status: &str,
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(orphaned_fills::table.find(intent_id.to_lowercase()))
+            .set((
+                orphaned_fills::status.eq(status),
+                orphaned_fills::attempts.eq(orphaned_fills::attempts + 1),
+                orphaned_fills::last_error.eq(Some(error)),
+                orphaned_fills::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to record orphaned fill recovery failure")?;
+
+        Ok(())
+    }
+
+    // ==================== Relayer Jobs ====================
+
+    /// Open a `pending` job of `kind` for each intent that has none, and
+    /// reopen `done` ones, since the worker found the intent waiting again.
+    /// Dead and cancelled jobs stay as they are. Returns the jobs of these
+    /// intents.
+    pub fn open_relayer_jobs(
+        &self,
+        kind: &str,
+        intent_ids: &[String],
+    ) -> Result<Vec<DbRelayerJob>> {
+        if intent_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let mut conn = self.get_connection()?;
+        let intent_ids: Vec<String> = intent_ids.iter().map(|id| id.to_lowercase()).collect();
+        let now = Utc::now();
+
+        conn.transaction(|conn| {
+            let rows: Vec<_> = intent_ids
+                .iter()
+                .map(|id| (relayer_jobs::kind.eq(kind), relayer_jobs::intent_id.eq(id)))
+                .collect();
+            diesel::insert_into(relayer_jobs::table)
+                .values(&rows)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+
+            diesel::update(
+                relayer_jobs::table
+                    .filter(relayer_jobs::kind.eq(kind))
+                    .filter(relayer_jobs::intent_id.eq_any(&intent_ids))
+                    .filter(relayer_jobs::status.eq("done")),
+            )
+            .set((
+                relayer_jobs::status.eq("pending"),
+                relayer_jobs::attempts.eq(0),
+                relayer_jobs::last_error.eq(None::<String>),
+                relayer_jobs::next_attempt_at.eq(now),
+                relayer_jobs::updated_at.eq(now),
+            ))
+            .execute(conn)?;
+
+            relayer_jobs::table
+                .filter(relayer_jobs::kind.eq(kind))
+                .filter(relayer_jobs::intent_id.eq_any(&intent_ids))
+                .select(DbRelayerJob::as_select())
+                .load(conn)
+        })
+        .context("Failed to open relayer jobs")
+    }
+
+    /// Mark a `pending` job `done` after a successful attempt.
+    pub fn complete_relayer_job(&self, kind: &str, intent_id: &str) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(
+            relayer_jobs::table
+                .filter(relayer_jobs::kind.eq(kind))
+                .filter(relayer_jobs::intent_id.eq(intent_id.to_lowercase()))
+                .filter(relayer_jobs::status.eq("pending")),
+        )
+        .set((
+            relayer_jobs::status.eq("done"),
+            relayer_jobs::last_error.eq(None::<String>),
+            relayer_jobs::updated_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .context("Failed to complete relayer job")?;
+
+        Ok(())
+    }
+
+    /// Count a failed attempt of a `pending` job. `schedule` gets the new
+    /// attempt count and returns when to try again, or `None` to move the
+    /// job to `dead`. Returns the job as updated, or `None` when it was no
+    /// longer pending, e.g. cancelled during the attempt.
+    pub fn fail_relayer_job(
+        &self,
+        kind: &str,
+        intent_id: &str,
+        error: &str,
+        schedule: impl FnOnce(i32) -> Option<chrono::DateTime<Utc>>,
+    ) -> Result<Option<DbRelayerJob>> {
+        let mut conn = self.get_connection()?;
+
+        conn.transaction(|conn| {
+            let Some(job) = relayer_jobs::table
+                .filter(relayer_jobs::kind.eq(kind))
+                .filter(relayer_jobs::intent_id.eq(intent_id.to_lowercase()))
+                .filter(relayer_jobs::status.eq("pending"))
+                .select(DbRelayerJob::as_select())
+                .for_update()
+                .first(conn)
+                .optional()?
+            else {
+                return Ok(None);
+            };
+
+            let now = Utc::now();
+            let attempts = job.attempts + 1;
+            let next_attempt_at = schedule(attempts);
+            diesel::update(relayer_jobs::table.find(job.id))
+                .set((
+                    relayer_jobs::status.eq(if next_attempt_at.is_some() {
+                        "pending"
+                    } else {
+                        "dead"
+                    }),
+                    relayer_jobs::attempts.eq(attempts),
+                    relayer_jobs::last_error.eq(Some(error)),
+                    relayer_jobs::next_attempt_at.eq(next_attempt_at.unwrap_or(now)),
+                    relayer_jobs::updated_at.eq(now),
+                ))
+                .returning(DbRelayerJob::as_returning())
+                .get_result(conn)
+                .map(Some)
+        })
+        .context("Failed to record relayer job failure")
+    }
+
+    pub fn get_relayer_job(&self, id: i64) -> Result<Option<DbRelayerJob>> {
+        let mut conn = self.get_connection()?;
+
+        relayer_jobs::table
+            .find(id)
+            .select(DbRelayerJob::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to get relayer job")
+    }
+
+    /// Most recently updated first.
+    pub fn list_relayer_jobs(
+        &self,
+        status: Option<&str>,
+        kind: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<DbRelayerJob>> {
+        let mut conn = self.get_connection()?;
+
+        let mut query = relayer_jobs::table
+            .order(relayer_jobs::updated_at.desc())
+            .limit(limit)
+            .select(DbRelayerJob::as_select())
+            .into_boxed();
+        if let Some(status) = status {
+            query = query.filter(relayer_jobs::status.eq(status));
+        }
+        if let Some(kind) = kind {
+            query = query.filter(relayer_jobs::kind.eq(kind));
+        }
+
+        query.load(&mut conn).context("Failed to list relayer jobs")
+    }
+
+    /// Move job `id` to `status` when it is in one of `from`. Reopening it
+    /// as `pending` clears the attempt count and makes it due at once.
+    /// Returns the job as updated, or `None` when it was in none of `from`.
+    pub fn transition_relayer_job(
+        &self,
+        id: i64,
+        from: &[&str],
+        status: &str,
+    ) -> Result<Option<DbRelayerJob>> {
+        let mut conn = self.get_connection()?;
+        let now = Utc::now();
+        let target = relayer_jobs::table
+            .find(id)
+            .filter(relayer_jobs::status.eq_any(from));
+
+        let updated = if status == "pending" {
+            diesel::update(target)
+                .set((
+                    relayer_jobs::status.eq(status),
+                    relayer_jobs::attempts.eq(0),
+                    relayer_jobs::next_attempt_at.eq(now),
+                    relayer_jobs::updated_at.eq(now),
+                ))
+                .returning(DbRelayerJob::as_returning())
+                .get_result(&mut conn)
+        } else {
+            diesel::update(target)
+                .set((
+                    relayer_jobs::status.eq(status),
+                    relayer_jobs::updated_at.eq(now),
+                ))
+                .returning(DbRelayerJob::as_returning())
+                .get_result(&mut conn)
+        };
+
+        updated
+            .optional()
+            .context("Failed to update relayer job status")
+    }
+
+    // ==================== Debug Captures ====================
+
+    /// Mark an intent for capture until `expires_at`. Marking it again
+    /// replaces the operator, reason and expiry but keeps what was recorded.
+    pub fn start_debug_capture(
+        &self,
+        intent_id: &str,
+        operator: &str,
+        reason: &str,
+        expires_at: chrono::DateTime<Utc>,
+    ) -> Result<DbDebugCapture> {
+        let mut conn = self.get_connection()?;
+
+        diesel::insert_into(debug_captures::table)
+            .values((
+                debug_captures::intent_id.eq(intent_id.to_lowercase()),
+                debug_captures::operator.eq(operator),
+                debug_captures::reason.eq(reason),
+                debug_captures::expires_at.eq(expires_at),
+            ))
+            .on_conflict(debug_captures::intent_id)
+            .do_update()
+            .set((
+                debug_captures::operator.eq(operator),
+                debug_captures::reason.eq(reason),
+                debug_captures::expires_at.eq(expires_at),
+            ))
+            .returning(DbDebugCapture::as_returning())
+            .get_result(&mut conn)
+            .context("Failed to start debug capture")
+    }
+
+    /// End a live capture now. Returns false when the intent has none.
+    pub fn stop_debug_capture(&self, intent_id: &str) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+        let now = Utc::now();
+
+        let updated = diesel::update(
+            debug_captures::table
+                .find(intent_id.to_lowercase())
+                .filter(debug_captures::expires_at.gt(now)),
+        )
+        .set(debug_captures::expires_at.eq(now))
+        .execute(&mut conn)
+        .context("Failed to stop debug capture")?;
+
+        Ok(updated > 0)
+    }
+
+    pub fn get_debug_capture(&self, intent_id: &str) -> Result<Option<DbDebugCapture>> {
+        let mut conn = self.get_connection()?;
+
+        debug_captures::table
+            .find(intent_id.to_lowercase())
+            .select(DbDebugCapture::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to get debug capture")
+    }
+
+    /// Captures still recording, soonest to expire first.
+    pub fn get_active_debug_captures(&self) -> Result<Vec<DbDebugCapture>> {
+        let mut conn = self.get_connection()?;
+
+        debug_captures::table
+            .filter(debug_captures::expires_at.gt(Utc::now()))
+            .order(debug_captures::expires_at.asc())
+            .select(DbDebugCapture::as_select())
+            .load(&mut conn)
+            .context("Failed to get active debug captures")
+    }
+
+    /// Dropped when the capture has expired in the meantime.
+    pub fn record_debug_capture_entry(
+        &self,
+        intent_id: &str,
+        component: &str,
+        kind: &str,
+        payload: &serde_json::Value,
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let intent_id = intent_id.to_lowercase();
+
+        diesel::insert_into(debug_capture_entries::table)
+            .values(
+                debug_captures::table
+                    .filter(debug_captures::intent_id.eq(&intent_id))
+                    .filter(debug_captures::expires_at.gt(Utc::now()))
+                    .select((
+                        debug_captures::intent_id,
+                        component.into_sql::<diesel::sql_types::Text>(),
+                        kind.into_sql::<diesel::sql_types::Text>(),
+                        payload.into_sql::<diesel::sql_types::Jsonb>(),
+                    )),
+            )
+            .into_columns((
+                debug_capture_entries::intent_id,
+                debug_capture_entries::component,
+                debug_capture_entries::kind,
+                debug_capture_entries::payload,
+            ))
+            .execute(&mut conn)
+            .context("Failed to record debug capture entry")?;
+
+        Ok(())
+    }
+
+    /// Oldest first.
+    pub fn get_debug_capture_entries(&self, intent_id: &str) -> Result<Vec<DbDebugCaptureEntry>> {
+        let mut conn = self.get_connection()?;
+
+        debug_capture_entries::table
+            .filter(debug_capture_entries::intent_id.eq(intent_id.to_lowercase()))
+            .order(debug_capture_entries::id.asc())
+            .select(DbDebugCaptureEntry::as_select())
+            .load(&mut conn)
+            .context("Failed to get debug capture entries")
+    }
+
+    /// Delete captures that expired before `cutoff`, with their entries.
+    pub fn purge_debug_captures(&self, cutoff: chrono::DateTime<Utc>) -> Result<usize> {
+        let mut conn = self.get_connection()?;
+
+        diesel::delete(debug_captures::table.filter(debug_captures::expires_at.lt(cutoff)))
+            .execute(&mut conn)
+            .context("Failed to purge debug captures")
+    }
+
+    // ==================== Ingestion Queue ====================
+
+    /// Store a received event as `pending`. An event already stored, from
+    /// either source, is left alone unless it was dead-lettered, in which
+    /// case it gets a fresh set of attempts. Returns false for duplicates.
+    pub fn enqueue_ingestion_event(&self, event: &NewIngestionEvent) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        conn.transaction(|conn| {
+            let inserted = diesel::insert_into(ingestion_queue::table)
+                .values(event)
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+            if inserted > 0 {
+                return Ok::<_, diesel::result::Error>(true);
+            }
+
+            let revived = diesel::update(
+                ingestion_queue::table
+                    .filter(ingestion_queue::chain.eq(event.chain))
+                    .filter(ingestion_queue::transaction_hash.eq(&event.transaction_hash))
+                    .filter(ingestion_queue::block_number.eq(event.block_number))
+                    .filter(ingestion_queue::log_index.eq(event.log_index))
+                    .filter(ingestion_queue::event_type.eq(event.event_type))
+                    .filter(ingestion_queue::status.eq("dead")),
+            )
+            .set((
+                ingestion_queue::status.eq("pending"),
+                ingestion_queue::attempts.eq(0),
+                ingestion_queue::payload.eq(&event.payload),
+                ingestion_queue::next_attempt_at.eq(Utc::now()),
+                ingestion_queue::processed_at.eq(None::<chrono::DateTime<Utc>>),
+            ))
+            .execute(conn)?;
+            Ok(revived > 0)
+        })
+        .context("Failed to enqueue ingestion event")
+    }
+
+    /// Claim up to `limit` due events, oldest first, hiding them from other
+    /// consumers until `lease_until`. An event whose consumer dies is
+    /// claimed again once its lease runs out.
+    pub fn claim_ingestion_events(
+        &self,
+        limit: i64,
+        lease_until: chrono::DateTime<Utc>,
+    ) -> Result<Vec<DbIngestionEvent>> {
+        let mut conn = self.get_connection()?;
+
+        conn.transaction(|conn| {
+            let ids: Vec<i64> = ingestion_queue::table
+                .filter(ingestion_queue::status.eq("pending"))
+                .filter(ingestion_queue::next_attempt_at.le(Utc::now()))
+                .order(ingestion_queue::id.asc())
+                .limit(limit)
+                .select(ingestion_queue::id)
+                .for_update()
+                .skip_locked()
+                .load(conn)?;
+            if ids.is_empty() {
+                return Ok::<_, diesel::result::Error>(Vec::new());
+            }
+
+            let mut events =
+                diesel::update(ingestion_queue::table.filter(ingestion_queue::id.eq_any(&ids)))
+                    .set(ingestion_queue::next_attempt_at.eq(lease_until))
+                    .returning(DbIngestionEvent::as_returning())
+                    .get_results(conn)?;
+            events.sort_by_key(|event: &DbIngestionEvent| event.id);
+            Ok(events)
+        })
+        .context("Failed to claim ingestion events")
+    }
+
+    pub fn complete_ingestion_event(&self, id: i64) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(
+            ingestion_queue::table
+                .find(id)
+                .filter(ingestion_queue::status.eq("pending")),
+        )
+        .set((
+            ingestion_queue::status.eq("done"),
+            ingestion_queue::last_error.eq(None::<String>),
+            ingestion_queue::processed_at.eq(Some(Utc::now())),
+        ))
+        .execute(&mut conn)
+        .context("Failed to complete ingestion event")?;
+
+        Ok(())
+    }
+
+    /// Count a failed delivery. `schedule` gets the new attempt count and
+    /// returns when to try again, or `None` to dead-letter the event.
+    pub fn fail_ingestion_event(
+        &self,
+        id: i64,
+        error: &str,
+        schedule: impl FnOnce(i32) -> Option<chrono::DateTime<Utc>>,
+    ) -> Result<Option<DbIngestionEvent>> {
+        let mut conn = self.get_connection()?;
+
+        conn.transaction(|conn| {
+            let Some(event) = ingestion_queue::table
+                .find(id)
+                .filter(ingestion_queue::status.eq("pending"))
+                .select(DbIngestionEvent::as_select())
+                .for_update()
+                .first(conn)
+                .optional()?
+            else {
+                return Ok(None);
+            };
+
+            let now = Utc::now();
+            let attempts = event.attempts + 1;
+            let next_attempt_at = schedule(attempts);
+            diesel::update(ingestion_queue::table.find(id))
+                .set((
+                    ingestion_queue::status.eq(if next_attempt_at.is_some() {
+                        "pending"
+                    } else {
+                        "dead"
+                    }),
+                    ingestion_queue::attempts.eq(attempts),
+                    ingestion_queue::last_error.eq(Some(error)),
+                    ingestion_queue::next_attempt_at.eq(next_attempt_at.unwrap_or(now)),
+                    ingestion_queue::processed_at.eq(next_attempt_at.is_none().then_some(now)),
+                ))
+                .returning(DbIngestionEvent::as_returning())
+                .get_result(conn)
+                .map(Some)
+        })
+        .context("Failed to record ingestion failure")
+    }
+
+    /// Pending and dead events by source, with the oldest receipt of each.
+    pub fn get_ingestion_backlog(&self) -> Result<Vec<IngestionBacklog>> {
+        let mut conn = self.get_connection()?;
+
+        let rows: Vec<(String, String, i64, Option<chrono::DateTime<Utc>>)> =
+            ingestion_queue::table
+                .filter(ingestion_queue::status.ne("done"))
+                .group_by((ingestion_queue::source, ingestion_queue::status))
+                .select((
+                    ingestion_queue::source,
+                    ingestion_queue::status,
+                    diesel::dsl::count_star(),
+                    diesel::dsl::min(ingestion_queue::received_at),
+                ))
+                .load(&mut conn)
+                .context("Failed to get ingestion backlog")?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(source, status, count, oldest_received_at)| IngestionBacklog {
+                    source,
+                    status,
+                    count,
+                    oldest_received_at,
+                },
+            )
+            .collect())
+    }
+
+    /// Delete events applied before `cutoff`. Dead ones stay for operators.
+    pub fn purge_ingestion_events(&self, cutoff: chrono::DateTime<Utc>) -> Result<usize> {
+        let mut conn = self.get_connection()?;
+
+        diesel::delete(
+            ingestion_queue::table
+                .filter(ingestion_queue::status.eq("done"))
+                .filter(ingestion_queue::processed_at.lt(cutoff)),
+        )
+        .execute(&mut conn)
+        .context("Failed to purge ingestion events")
+    }
+
+    // ==================== Admin Access ====================
+
+    pub fn insert_admin_api_key(&self, key: &NewAdminApiKey) -> Result<DbAdminApiKey> {
+        let mut conn = self.get_connection()?;
+
+        diesel::insert_into(admin_api_keys::table)
+            .values(key)
+            .returning(DbAdminApiKey::as_returning())
+            .get_result(&mut conn)
+            .context("Failed to insert admin API key")
+    }
+
+    /// The unrevoked key with this hash, marked as used now.
+    pub fn use_admin_api_key(&self, key_hash: &str) -> Result<Option<DbAdminApiKey>> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(
+            admin_api_keys::table
+                .filter(admin_api_keys::key_hash.eq(key_hash))
+                .filter(admin_api_keys::revoked_at.is_null()),
+        )
+        .set(admin_api_keys::last_used_at.eq(Utc::now()))
+        .returning(DbAdminApiKey::as_returning())
+        .get_result(&mut conn)
+        .optional()
+        .context("Failed to look up admin API key")
+    }
+
+    pub fn list_admin_api_keys(&self) -> Result<Vec<DbAdminApiKey>> {
+        let mut conn = self.get_connection()?;
+
+        admin_api_keys::table
+            .order(admin_api_keys::id.asc())
+            .select(DbAdminApiKey::as_select())
+            .load(&mut conn)
+            .context("Failed to list admin API keys")
+    }
+
+    /// Returns false when the key does not exist or was already revoked.
+    pub fn revoke_admin_api_key(&self, id: i32) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let revoked = diesel::update(
+            admin_api_keys::table
+                .filter(admin_api_keys::id.eq(id))
+                .filter(admin_api_keys::revoked_at.is_null()),
+        )
+        .set(admin_api_keys::revoked_at.eq(Utc::now()))
+        .execute(&mut conn)
+        .context("Failed to revoke admin API key")?;
+
+        Ok(revoked > 0)
+    }
+
+    pub fn record_admin_audit_entry(&self, entry: &NewAdminAuditEntry) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::insert_into(admin_audit_log::table)
+            .values(entry)
+            .execute(&mut conn)
+            .context("Failed to record admin audit entry")?;
+
+        Ok(())
+    }
+
+    /// Newest first, limited to `actor` when set.
+    pub fn list_admin_audit_log(
+        &self,
+        actor: Option<&str>,
+        limit: i64,
+    ) -> Result<Vec<DbAdminAuditEntry>> {
+        let mut conn = self.get_connection()?;
+
+        let mut query = admin_audit_log::table
+            .select(DbAdminAuditEntry::as_select())
+            .into_boxed();
+        if let Some(actor) = actor {
+            query = query.filter(admin_audit_log::actor.eq(actor));
+        }
+
+        query
+            .order(admin_audit_log::id.desc())
+            .limit(limit)
+            .load(&mut conn)
+            .context("Failed to list admin audit log")
+    }
+
+    // ==================== API Keys ====================
+
+    pub fn insert_api_key(&self, key: &NewApiKey) -> Result<DbApiKey> {
+        let mut conn = self.get_connection()?;
+
+        diesel::insert_into(api_keys::table)
+            .values(key)
+            .returning(DbApiKey::as_returning())
+            .get_result(&mut conn)
+            .context("Failed to insert API key")
+    }
+
+    /// The unrevoked key with this hash, marked as used now.
+    pub fn use_api_key(&self, key_hash: &str) -> Result<Option<DbApiKey>> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(
+            api_keys::table
+                .filter(api_keys::key_hash.eq(key_hash))
+                .filter(api_keys::revoked_at.is_null()),
+        )
+        .set(api_keys::last_used_at.eq(Utc::now()))
+        .returning(DbApiKey::as_returning())
+        .get_result(&mut conn)
+        .optional()
+        .context("Failed to look up API key")
+    }
+
+    pub fn list_api_keys(&self) -> Result<Vec<DbApiKey>> {
+        let mut conn = self.get_connection()?;
+
+        api_keys::table
+            .order(api_keys::id.asc())
+            .select(DbApiKey::as_select())
+            .load(&mut conn)
+            .context("Failed to list API keys")
+    }
+
+    /// Returns false when the key does not exist or was already revoked.
+    pub fn revoke_api_key(&self, id: i32) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let revoked = diesel::update(
+            api_keys::table
+                .filter(api_keys::id.eq(id))
+                .filter(api_keys::revoked_at.is_null()),
+        )
+        .set(api_keys::revoked_at.eq(Utc::now()))
+        .execute(&mut conn)
+        .context("Failed to revoke API key")?;
+
+        Ok(revoked > 0)
+    }
+
+    // ==================== Encrypted Notes ====================
+
+    /// Store or replace the note of an intent whose note was not delivered
+    /// yet. Returns false once it was.
+    pub fn store_encrypted_note(&self, intent_id: &str, ciphertext: &str) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let intent_id = intent_id.to_lowercase();
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            let delivered: Option<Option<chrono::DateTime<Utc>>> = intent_encrypted_notes::table
+                .filter(intent_encrypted_notes::intent_id.eq(&intent_id))
+                .select(intent_encrypted_notes::delivered_at)
+                .for_update()
+                .first(conn)
+                .optional()?;
+            if delivered.flatten().is_some() {
+                return Ok(false);
+            }
+
+            diesel::insert_into(intent_encrypted_notes::table)
+                .values((
+                    intent_encrypted_notes::intent_id.eq(&intent_id),
+                    intent_encrypted_notes::ciphertext.eq(ciphertext),
+                ))
+                .on_conflict(intent_encrypted_notes::intent_id)
+                .do_update()
+                .set(intent_encrypted_notes::ciphertext.eq(ciphertext))
+                .execute(conn)?;
+            Ok(true)
+        })
+        .context("Failed to store encrypted note")
+    }
+
+    pub fn get_encrypted_notes(&self, intent_ids: &[String]) -> Result<Vec<DbEncryptedNote>> {
+        let mut conn = self.get_connection()?;
+        let intent_ids: Vec<String> = intent_ids.iter().map(|id| id.to_lowercase()).collect();
+
+        intent_encrypted_notes::table
+            .filter(intent_encrypted_notes::intent_id.eq_any(&intent_ids))
+            .select(DbEncryptedNote::as_select())
+            .load(&mut conn)
+            .context("Failed to get encrypted notes")
+    }
+
+    /// Undelivered notes of intents in one of `statuses`, oldest first.
+    pub fn get_undelivered_encrypted_notes(
+        &self,
+        statuses: &[&str],
+        limit: i64,
+    ) -> Result<Vec<(DbEncryptedNote, Intent)>> {
+        let mut conn = self.get_connection()?;
+
+        let rows: Vec<(DbEncryptedNote, DbIntent)> = intent_encrypted_notes::table
+            .inner_join(intents::table.on(intents::id.eq(intent_encrypted_notes::intent_id)))
+            .filter(intent_encrypted_notes::delivered_at.is_null())
+            .filter(intents::status.eq_any(statuses))
+            .order(intent_encrypted_notes::created_at.asc())
+            .limit(limit)
+            .select((DbEncryptedNote::as_select(), DbIntent::as_select()))
+            .load(&mut conn)
+            .context("Failed to get undelivered encrypted notes")?;
+
+        rows.into_iter()
+            .map(|(note, intent)| Ok((note, Intent::try_from(intent)?)))
+            .collect()
+    }
+
+    pub fn mark_encrypted_note_delivered(&self, intent_id: &str) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(
+            intent_encrypted_notes::table.filter(intent_encrypted_notes::intent_id.eq(intent_id)),
+        )
+        .set(intent_encrypted_notes::delivered_at.eq(Utc::now()))
+        .execute(&mut conn)
+        .context("Failed to mark encrypted note delivered")?;
+
+        Ok(())
+    }
+
+    // ==================== Intent Archive ====================
+
+    /// Move up to `batch_size` intents in one of `statuses` that have not
+    /// changed since `cutoff` into `archived_intents`. Returns how many moved.
+    pub fn archive_intents(
+        &self,
+        statuses: &[&str],
+        cutoff: chrono::DateTime<Utc>,
+        batch_size: i64,
+    ) -> Result<usize> {
+        let mut conn = self.get_connection()?;
+
+        conn.transaction(|conn| {
+            let ids: Vec<String> = intents::table
+                .filter(intents::status.eq_any(statuses))
+                .filter(intents::updated_at.lt(cutoff))
+                .order(intents::updated_at.asc())
+                .limit(batch_size)
+                .select(intents::id)
+                .for_update()
+                .skip_locked()
+                .load(conn)?;
+            if ids.is_empty() {
+                return Ok(0);
+            }
+
+            diesel::insert_into(archived_intents::table)
+                .values(intents::table.filter(intents::id.eq_any(&ids)).select((
+                    intents::id,
+                    intents::user_address,
+                    intents::source_chain,
+                    intents::dest_chain,
+                    intents::source_token,
+                    intents::dest_token,
+                    intents::amount,
+                    intents::dest_amount,
+                    intents::source_commitment,
+                    intents::dest_fill_txid,
+                    intents::dest_registration_txid,
+                    intents::source_complete_txid,
+                    intents::status,
+                    intents::created_at,
+                    intents::updated_at,
+                    intents::deadline,
+                    intents::refund_address,
+                    intents::solver_address,
+                    intents::source_settlement_txid,
+                    intents::block_number,
+                    intents::log_index,
+                )))
+                .into_columns((
+                    archived_intents::id,
+                    archived_intents::user_address,
+                    archived_intents::source_chain,
+                    archived_intents::dest_chain,
+                    archived_intents::source_token,
+                    archived_intents::dest_token,
+                    archived_intents::amount,
+                    archived_intents::dest_amount,
+                    archived_intents::source_commitment,
+                    archived_intents::dest_fill_txid,
+                    archived_intents::dest_registration_txid,
+                    archived_intents::source_complete_txid,
+                    archived_intents::status,
+                    archived_intents::created_at,
+                    archived_intents::updated_at,
+                    archived_intents::deadline,
+                    archived_intents::refund_address,
+                    archived_intents::solver_address,
+                    archived_intents::source_settlement_txid,
+                    archived_intents::block_number,
+                    archived_intents::log_index,
+                ))
+                .on_conflict_do_nothing()
+                .execute(conn)?;
+
+            diesel::delete(intents::table.filter(intents::id.eq_any(&ids))).execute(conn)
+        })
+        .context("Failed to archive intents")
+    }
+
+    pub fn is_intent_archived(&self, intent_id: &str) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let archived = archived_intents::table
+            .filter(archived_intents::id.eq(intent_id))
+            .select(archived_intents::id)
+            .first::<String>(&mut conn)
+            .optional()
+            .context("Failed to check intent archive")?;
+
+        Ok(archived.is_some())
+    }
+
+    pub fn list_archived_intents(
+        &self,
+        status_filter: Option<&str>,
+        chain_filter: Option<&str>,
+        limit: usize,
+    ) -> Result<Vec<Intent>> {
+        let mut conn = self.get_connection()?;
+
+        let mut query = archived_intents::table.into_boxed();
+
+        if let Some(status) = status_filter {
+            query = query.filter(archived_intents::status.eq(status));
+        }
+
+        if let Some(chain) = chain_filter {
+            query = query.filter(
+                archived_intents::source_chain
+                    .eq(chain)
+                    .or(archived_intents::dest_chain.eq(chain)),
+            );
+        }
+
+        let results = query
+            .order(archived_intents::created_at.desc())
+            .limit(limit as i64)
+            .select(DbArchivedIntent::as_select())
+            .load::<DbArchivedIntent>(&mut conn)
+            .context("Failed to list archived intents")?;
+
+        results
+            .into_iter()
+            .map(|intent| db_intent_to_model(intent.into()))
+            .collect()
+    }
+
+    // ==================== Intent Annotations ====================
+
+    /// Attach an operator note to a live or archived intent. Returns `None`
+    /// if the intent does not exist.
+    pub fn add_intent_note(
+        &self,
+        intent_id: &str,
+        operator: &str,
+        reason: &str,
+    ) -> Result<Option<DbIntentAnnotation>> {
+        let mut conn = self.get_connection()?;
+
+        conn.transaction(|conn| {
+            let exists = diesel::select(diesel::dsl::exists(intents::table.find(intent_id)))
+                .get_result::<bool>(conn)?
+                || diesel::select(diesel::dsl::exists(archived_intents::table.find(intent_id)))
+                    .get_result::<bool>(conn)?;
+            if !exists {
+                return Ok(None);
+            }
+
+            diesel::insert_into(intent_annotations::table)
+                .values(&NewIntentAnnotation {
+                    intent_id,
+                    operator,
+                    kind: "note",
+                    reason,
+                    previous_status: None,
+                    new_status: None,
+                    created_at: Utc::now(),
+                })
+                .returning(DbIntentAnnotation::as_returning())
+                .get_result(conn)
+                .map(Some)
+        })
+        .context("Failed to add intent note")
+    }
+
+    /// Set a live intent's status by hand and record who did it and why, in
+    /// one transaction. Returns `None` if the intent is not in `intents`.
+    pub fn override_intent_status(
+        &self,
+        intent_id: &str,
+        operator: &str,
+        reason: &str,
+        status: IntentStatus,
+    ) -> Result<Option<DbIntentAnnotation>> {
+        let mut conn = self.get_connection()?;
+
+        conn.transaction(|conn| {
+            let Some(previous) = intents::table
+                .find(intent_id)
+                .select(intents::status)
+                .for_update()
+                .first::<String>(conn)
+                .optional()?
+            else {
+                return Ok(None);
+            };
+
+            diesel::update(intents::table.find(intent_id))
+                .set((
+                    intents::status.eq(status.as_str()),
+                    intents::updated_at.eq(Utc::now()),
+                ))
+                .execute(conn)?;
+
+            diesel::insert_into(intent_annotations::table)
+                .values(&NewIntentAnnotation {
+                    intent_id,
+                    operator,
+                    kind: "status_override",
+                    reason,
+                    previous_status: Some(&previous),
+                    new_status: Some(status.as_str()),
+                    created_at: Utc::now(),
+                })
+                .returning(DbIntentAnnotation::as_returning())
+                .get_result(conn)
+                .map(Some)
+        })
+        .context("Failed to override intent status")
+    }
+
+    /// Notes and overrides on an intent, oldest first.
+    pub fn list_intent_annotations(&self, intent_id: &str) -> Result<Vec<DbIntentAnnotation>> {
+        let mut conn = self.get_connection()?;
+
+        intent_annotations::table
+            .filter(intent_annotations::intent_id.eq(intent_id))
+            .order((
+                intent_annotations::created_at.asc(),
+                intent_annotations::id.asc(),
+            ))
+            .select(DbIntentAnnotation::as_select())
+            .load(&mut conn)
+            .context("Failed to list intent annotations")
+    }
+
+    // ==================== Work Partitions ====================
+
+    pub fn record_relayer_heartbeat(&self, instance_id: &str, region: &str) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::insert_into(relayer_instances::table)
+            .values((
+                relayer_instances::instance_id.eq(instance_id),
+                relayer_instances::region.eq(region),
+                relayer_instances::started_at.eq(Utc::now()),
+                relayer_instances::heartbeat_at.eq(Utc::now()),
+            ))
+            .on_conflict(relayer_instances::instance_id)
+            .do_update()
+            .set((
+                relayer_instances::region.eq(region),
+                relayer_instances::heartbeat_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to record relayer heartbeat")?;
+
+        Ok(())
+    }
+
+    pub fn get_relayer_instances(&self) -> Result<Vec<DbRelayerInstance>> {
+        let mut conn = self.get_connection()?;
+
+        relayer_instances::table
+            .order(relayer_instances::instance_id.asc())
+            .select(DbRelayerInstance::as_select())
+            .load(&mut conn)
+            .context("Failed to get relayer instances")
+    }
+
+    /// Partitions `0..count`, creating unowned rows for any that are missing.
+    pub fn get_partition_assignments(&self, count: i32) -> Result<Vec<DbPartitionAssignment>> {
+        let mut conn = self.get_connection()?;
+
+        let rows: Vec<_> = (0..count)
+            .map(|partition| partition_assignments::partition.eq(partition))
+            .collect();
+        diesel::insert_into(partition_assignments::table)
+            .values(&rows)
+            .on_conflict_do_nothing()
+            .execute(&mut conn)
+            .context("Failed to create partition assignments")?;
+
+        partition_assignments::table
+            .filter(partition_assignments::partition.lt(count))
+            .order(partition_assignments::partition.asc())
+            .select(DbPartitionAssignment::as_select())
+            .load(&mut conn)
+            .context("Failed to get partition assignments")
+    }
+
+    /// Move a partition to `instance_id` (or release it with `None`) if it is
+    /// still at `epoch`. Returns `false` when another instance got there
+    /// first.
+    pub fn reassign_partition(
+        &self,
+        partition: i32,
+        epoch: i64,
+        instance_id: Option<&str>,
+    ) -> Result<bool> {
+        let mut conn = self.get_connection()?;
+
+        let updated = diesel::update(
+            partition_assignments::table
+                .filter(partition_assignments::partition.eq(partition))
+                .filter(partition_assignments::epoch.eq(epoch)),
+        )
+        .set((
+            partition_assignments::instance_id.eq(instance_id),
+            partition_assignments::epoch.eq(epoch + 1),
+            partition_assignments::assigned_at.eq(Utc::now()),
+        ))
+        .execute(&mut conn)
+        .context("Failed to reassign partition")?;
+
+        Ok(updated == 1)
+    }
+
+    // ==================== Token Listings ====================
+
+    pub fn list_token_listings(&self) -> Result<Vec<DbTokenListing>> {
+        let mut conn = self.get_connection()?;
+
+        token_listings::table
+            .order(token_listings::symbol.asc())
+            .select(DbTokenListing::as_select())
+            .load(&mut conn)
+            .context("Failed to list token listings")
+    }
+
+    pub fn save_token_listing(&self, listing: &TokenListing) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let values = (
+            token_listings::symbol.eq(listing.token.symbol()),
+            token_listings::ethereum_address.eq(format!("{:?}", listing.ethereum)),
+            token_listings::mantle_address.eq(format!("{:?}", listing.mantle)),
+            token_listings::decimals.eq(i32::from(listing.decimals)),
+            token_listings::min_amount.eq(listing.min_amount.to_string()),
+            token_listings::max_amount.eq(listing.max_amount.to_string()),
+            token_listings::enabled.eq(listing.enabled),
+            token_listings::updated_at.eq(Utc::now()),
+        );
+
+        diesel::insert_into(token_listings::table)
+            .values(values.clone())
+            .on_conflict(token_listings::symbol)
+            .do_update()
+            .set(values)
+            .execute(&mut conn)
+            .context("Failed to save token listing")?;
+
+        Ok(())
+    }
+
+    // ==================== Tx Submissions ====================
+
+    pub fn get_tx_submission(&self, operation: &str) -> Result<Option<DbTxSubmission>> {
+        let mut conn = self.get_connection()?;
+
+        tx_submissions::table
+            .find(operation)
+            .select(DbTxSubmission::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to load tx submission")
+    }
+
+    /// Phase one: journal `operation` as `submitting` before it is queued,
+    /// replacing a dropped or reverted earlier attempt.
+    pub fn begin_tx_submission(
+        &self,
+        operation: &str,
+        intent_id: &str,
+        chain_id: u32,
+        tx_type: &str,
+        calldata_hash: &str,
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+        let now = Utc::now();
+
+        diesel::insert_into(tx_submissions::table)
+            .values((
+                tx_submissions::operation.eq(operation),
+                tx_submissions::intent_id.eq(intent_id),
+                tx_submissions::chain_id.eq(chain_id as i32),
+                tx_submissions::tx_type.eq(tx_type),
+                tx_submissions::calldata_hash.eq(calldata_hash),
+                tx_submissions::status.eq("submitting"),
+                tx_submissions::created_at.eq(now),
+                tx_submissions::updated_at.eq(now),
+            ))
+            .on_conflict(tx_submissions::operation)
+            .do_update()
+            .set((
+                tx_submissions::chain_id.eq(chain_id as i32),
+                tx_submissions::calldata_hash.eq(calldata_hash),
+                tx_submissions::nonce.eq(None::<i64>),
+                tx_submissions::tx_hash.eq(None::<String>),
+                tx_submissions::status.eq("submitting"),
+                tx_submissions::updated_at.eq(now),
+            ))
+            .execute(&mut conn)
+            .context("Failed to journal tx submission")?;
+
+        Ok(())
+    }
+
+    pub fn set_tx_submission_nonce(&self, operation: &str, nonce: u64) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(tx_submissions::table.find(operation))
+            .set((
+                tx_submissions::nonce.eq(nonce as i64),
+                tx_submissions::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to journal tx nonce")?;
+
+        Ok(())
+    }
+
+    pub fn set_tx_submission_sent(&self, operation: &str, tx_hash: &str) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(tx_submissions::table.find(operation))
+            .set((
+                tx_submissions::tx_hash.eq(tx_hash),
+                tx_submissions::status.eq("submitted"),
+                tx_submissions::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to journal tx hash")?;
+
+        Ok(())
+    }
+
+    /// Phase two: record how the submission ended.
+    pub fn finish_tx_submission(
+        &self,
+        operation: &str,
+        status: &str,
+        tx_hash: Option<&str>,
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::update(tx_submissions::table.find(operation))
+            .set((
+                tx_submissions::status.eq(status),
+                tx_submissions::tx_hash.eq(tx_hash),
+                tx_submissions::updated_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to finish tx submission")?;
+
+        Ok(())
+    }
+
+    /// Submissions on `chain_id` still `submitting` or `submitted`.
+    pub fn get_open_tx_submissions(&self, chain_id: u32) -> Result<Vec<DbTxSubmission>> {
+        let mut conn = self.get_connection()?;
+
+        tx_submissions::table
+            .filter(tx_submissions::chain_id.eq(chain_id as i32))
+            .filter(tx_submissions::status.eq_any(["submitting", "submitted"]))
+            .order(tx_submissions::created_at.asc())
+            .select(DbTxSubmission::as_select())
+            .load(&mut conn)
+            .context("Failed to load open tx submissions")
+    }
+
+    // ==================== Route Analytics ====================
+
+    /// Intents created in `[from, to)`, archived ones included.
+    pub fn get_intent_samples(
+        &self,
+        from: chrono::DateTime<Utc>,
+        to: chrono::DateTime<Utc>,
+    ) -> Result<Vec<IntentSample>> {
+        let mut conn = self.get_connection()?;
+
+        let mut samples: Vec<IntentSample> = intents::table
+            .filter(intents::created_at.ge(from))
+            .filter(intents::created_at.lt(to))
+            .select((
+                intents::source_chain,
+                intents::dest_chain,
+                intents::source_token,
+                intents::amount,
+                intents::status,
+                intents::deadline,
+            ))
+            .load(&mut conn)
+            .context("Failed to load intent samples")?;
+
+        let archived: Vec<IntentSample> = archived_intents::table
+            .filter(archived_intents::created_at.ge(from))
+            .filter(archived_intents::created_at.lt(to))
+            .select((
+                archived_intents::source_chain,
+                archived_intents::dest_chain,
+                archived_intents::source_token,
+                archived_intents::amount,
+                archived_intents::status,
+                archived_intents::deadline,
+            ))
+            .load(&mut conn)
+            .context("Failed to load archived intent samples")?;
+        samples.extend(archived);
+
+        Ok(samples)
+    }
+
+    /// Seconds from creation to the destination fill of intents on a route
+    /// filled since `since`, newest first.
+    pub fn get_fill_latencies(
+        &self,
+        source_chain: &str,
+        dest_chain: &str,
+        since: chrono::DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<i64>> {
+        let mut conn = self.get_connection()?;
+
+        let rows: Vec<(chrono::DateTime<Utc>, chrono::DateTime<Utc>)> = bridge_events::table
+            .inner_join(intents::table)
+            .filter(bridge_events::event_type.eq(BridgeEventType::IntentFilled.as_str()))
+            .filter(bridge_events::timestamp.ge(since))
+            .filter(intents::source_chain.eq(source_chain))
+            .filter(intents::dest_chain.eq(dest_chain))
+            .order(bridge_events::timestamp.desc())
+            .limit(limit)
+            .select((intents::created_at, bridge_events::timestamp))
+            .load(&mut conn)
+            .context("Failed to load fill latencies")?;
+
+        Ok(rows
+            .into_iter()
+            .map(|(created, filled)| (filled - created).num_seconds())
+            .collect())
+    }
+
+    // ==================== Metric Snapshots ====================
+
+    pub fn save_metric_snapshot(&self, instance: &str, snapshot: &Value) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::insert_into(metric_snapshots::table)
+            .values((
+                metric_snapshots::instance.eq(instance),
+                metric_snapshots::snapshot.eq(snapshot),
+                metric_snapshots::saved_at.eq(Utc::now()),
+            ))
+            .on_conflict(metric_snapshots::instance)
+            .do_update()
+            .set((
+                metric_snapshots::snapshot.eq(snapshot),
+                metric_snapshots::saved_at.eq(Utc::now()),
+            ))
+            .execute(&mut conn)
+            .context("Failed to save metric snapshot")?;
+
+        Ok(())
+    }
+
+    pub fn get_metric_snapshot(&self, instance: &str) -> Result<Option<Value>> {
+        let mut conn = self.get_connection()?;
+
+        metric_snapshots::table
+            .filter(metric_snapshots::instance.eq(instance))
+            .select(metric_snapshots::snapshot)
+            .first::<Value>(&mut conn)
+            .optional()
+            .context("Failed to get metric snapshot")
+    }
+
+    // ==================== Bridge Events ====================
+
+    pub fn store_bridge_event(
+        &self,
+        event_id: &str,
+        intent_id: Option<&str>,
+        event_type: BridgeEventType,
+        event_data: Value,
+        chain_id: i32,
+        block_number: i64,
+        log_index: Option<i32>,
+        transaction_hash: &str,
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        let new_event = NewBridgeEvent {
+            event_id,
+            intent_id,
+            event_type: event_type.as_str(),
+            event_data,
+            chain_id,
+            block_number,
+            log_index,
+            transaction_hash,
+            timestamp: Utc::now(),
+            created_at: Utc::now(),
+        };
+
+        diesel::insert_into(bridge_events::table)
+            .values(&new_event)
+            .on_conflict(bridge_events::event_id)
+            .do_nothing() // Idempotency: if event_id exists, skip
+            .execute(&mut conn)
+            .map_err(|e| {
+                error!("🔴 DB INSERT FAILED: event_id={}, error={}", event_id, e);
+                e
+            })
+            .context("Failed to store bridge event")?;
+
+        Ok(())
+    }
+
+    /// Chain events recorded for an intent, oldest first.
+    pub fn get_intent_bridge_events(&self, intent_id: &str) -> Result<Vec<DbBridgeEvent>> {
+        let mut conn = self.get_connection()?;
+
+        bridge_events::table
+            .filter(bridge_events::intent_id.eq(intent_id))
+            .order((bridge_events::timestamp.asc(), bridge_events::id.asc()))
+            .select(DbBridgeEvent::as_select())
+            .load(&mut conn)
+            .context("Failed to get intent bridge events")
+    }
+
+    /// Page of an intent's events by row id, after `cursor` in the given
+    /// order, limited to `event_types` when not empty.
+    pub fn list_intent_bridge_events(
+        &self,
+        intent_id: &str,
+        event_types: &[String],
+        cursor: Option<i32>,
+        ascending: bool,
+        limit: i64,
+    ) -> Result<Vec<DbBridgeEvent>> {
+        let mut conn = self.get_connection()?;
+
+        let mut query = bridge_events::table
+            .filter(bridge_events::intent_id.eq(intent_id))
+            .select(DbBridgeEvent::as_select())
+            .into_boxed();
+        if !event_types.is_empty() {
+            query = query.filter(bridge_events::event_type.eq_any(event_types));
+        }
+        query = match (ascending, cursor) {
+            (true, Some(cursor)) => query.filter(bridge_events::id.gt(cursor)),
+            (false, Some(cursor)) => query.filter(bridge_events::id.lt(cursor)),
+            (_, None) => query,
+        };
+        query = if ascending {
+            query.order(bridge_events::id.asc())
+        } else {
+            query.order(bridge_events::id.desc())
+        };
+
+        query
+            .limit(limit)
+            .load(&mut conn)
+            .context("Failed to list intent bridge events")
+    }
+
+    /// Every transaction sent for an intent, oldest first.
+    pub fn get_intent_transactions(&self, intent_id: &str) -> Result<Vec<DbChainTransaction>> {
+        let mut conn = self.get_connection()?;
+
+        chain_transactions::table
+            .filter(chain_transactions::intent_id.eq(intent_id))
+            .order(chain_transactions::id.asc())
+            .select(DbChainTransaction::as_select())
+            .load(&mut conn)
+            .context("Failed to get intent transactions")
+    }
+
+    /// Page of an intent's transactions by row id, after `cursor` in the
+    /// given order, limited to `tx_types` when not empty.
+    pub fn list_intent_transactions(
+        &self,
+        intent_id: &str,
+        tx_types: &[String],
+        cursor: Option<i32>,
+        ascending: bool,
+        limit: i64,
+    ) -> Result<Vec<DbChainTransaction>> {
+        let mut conn = self.get_connection()?;
 
-        let new_event = NewBridgeEvent {
-            event_id,
-            intent_id,
-            event_type,
-            event_data,
-            chain_id,
-            block_number,
-            log_index,
-            transaction_hash,
-            timestamp: Utc::now(),
-            created_at: Utc::now(),
+        let mut query = chain_transactions::table
+            .filter(chain_transactions::intent_id.eq(intent_id))
+            .select(DbChainTransaction::as_select())
+            .into_boxed();
+        if !tx_types.is_empty() {
+            query = query.filter(chain_transactions::tx_type.eq_any(tx_types));
+        }
+        query = match (ascending, cursor) {
+            (true, Some(cursor)) => query.filter(chain_transactions::id.gt(cursor)),
+            (false, Some(cursor)) => query.filter(chain_transactions::id.lt(cursor)),
+            (_, None) => query,
+        };
+        query = if ascending {
+            query.order(chain_transactions::id.asc())
+        } else {
+            query.order(chain_transactions::id.desc())
         };
 
-        diesel::insert_into(bridge_events::table)
-            .values(&new_event)
-            .on_conflict(bridge_events::event_id)
-            .do_nothing() // Idempotency: if event_id exists, skip
-            .execute(&mut conn)
-            .map_err(|e| {
-                error!("🔴 DB INSERT FAILED: event_id={}, error={}", event_id, e);
-                e
-            })
-            .context("Failed to store bridge event")?;
-
-        Ok(())
+        query
+            .limit(limit)
+            .load(&mut conn)
+            .context("Failed to list intent transactions")
     }
 
     pub fn get_bridge_event_by_nullifier(
         &self,
         nullifier: &str,
-        event_type: &str,
+        event_type: BridgeEventType,
         chain_id: u32,
     ) -> Result<Option<serde_json::Value>> {
         let mut conn = self.get_connection()?;
 
         let result = bridge_events::table
-            .filter(bridge_events::event_type.eq(event_type))
+            .filter(bridge_events::event_type.eq(event_type.as_str()))
             .filter(bridge_events::chain_id.eq(chain_id as i32))
             .filter(
                 bridge_events::event_data
@@ -936,7 +3992,15 @@ impl Database {
         Ok(result)
     }
 
-    pub fn insert_root_sync(&self, sync_type: &str, root: &str, tx_hash: &str) -> Result<()> {
+    /// Record a root synced to another chain. `leaf_count` is the number of
+    /// leaves behind it, when known.
+    pub fn insert_root_sync(
+        &self,
+        sync_type: &str,
+        root: &str,
+        tx_hash: &str,
+        leaf_count: Option<i64>,
+    ) -> Result<()> {
         let mut conn = self.get_connection()?;
 
         let new_sync = NewRootSync {
@@ -944,6 +4008,7 @@ impl Database {
             root,
             tx_hash,
             created_at: Utc::now(),
+            leaf_count,
         };
 
         diesel::insert_into(root_syncs::table)
@@ -1439,6 +4504,72 @@ impl Database {
         Ok(())
     }
 
+    /// Upsert `nodes` as `(level, node_index, hash)` and set the tree's root
+    /// and leaf count in one transaction, so a tree is never left half
+    /// written. `replace` drops the tree's existing nodes first.
+    pub fn write_merkle_nodes(
+        &self,
+        tree_id: i32,
+        nodes: &[(i32, i64, String)],
+        root: &str,
+        leaf_count: i64,
+        replace: bool,
+    ) -> Result<()> {
+        use crate::models::schema::merkle_nodes;
+        use diesel::upsert::excluded;
+
+        let mut conn = self.get_connection()?;
+        let now = Utc::now();
+
+        conn.transaction::<_, anyhow::Error, _>(|conn| {
+            if replace {
+                diesel::delete(merkle_nodes::table.filter(merkle_nodes::tree_id.eq(tree_id)))
+                    .execute(conn)
+                    .context("Failed to clear merkle nodes")?;
+            }
+
+            for batch in nodes.chunks(MERKLE_NODE_BATCH) {
+                let rows: Vec<NewMerkleNode> = batch
+                    .iter()
+                    .map(|(level, node_index, hash)| NewMerkleNode {
+                        tree_id,
+                        level: *level,
+                        node_index: *node_index,
+                        hash,
+                        created_at: now,
+                        updated_at: now,
+                    })
+                    .collect();
+
+                diesel::insert_into(merkle_nodes::table)
+                    .values(&rows)
+                    .on_conflict((
+                        merkle_nodes::tree_id,
+                        merkle_nodes::level,
+                        merkle_nodes::node_index,
+                    ))
+                    .do_update()
+                    .set((
+                        merkle_nodes::hash.eq(excluded(merkle_nodes::hash)),
+                        merkle_nodes::updated_at.eq(now),
+                    ))
+                    .execute(conn)
+                    .context("Failed to store merkle nodes")?;
+            }
+
+            diesel::update(merkle_trees::table.filter(merkle_trees::tree_id.eq(tree_id)))
+                .set((
+                    merkle_trees::root.eq(root),
+                    merkle_trees::leaf_count.eq(leaf_count),
+                    merkle_trees::updated_at.eq(now),
+                ))
+                .execute(conn)
+                .context("Failed to update merkle tree")?;
+
+            Ok(())
+        })
+    }
+
     pub fn get_merkle_node(
         &self,
         tree_id: i32,
@@ -1461,6 +4592,74 @@ impl Database {
         Ok(node)
     }
 
+    /// The stored nodes at `positions`, given as `(level, node_index)`, in one
+    /// query. Positions without a row are left out.
+    pub fn get_merkle_nodes_at(
+        &self,
+        tree_id: i32,
+        positions: &[(i32, i64)],
+    ) -> Result<Vec<DbMerkleNode>> {
+        use crate::models::schema::merkle_nodes;
+
+        if positions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut conn = self.get_connection()?;
+
+        let mut query = merkle_nodes::table.into_boxed();
+        for (level, node_index) in positions {
+            query = query.or_filter(
+                merkle_nodes::tree_id
+                    .eq(tree_id)
+                    .and(merkle_nodes::level.eq(*level))
+                    .and(merkle_nodes::node_index.eq(*node_index)),
+            );
+        }
+
+        query
+            .select(DbMerkleNode::as_select())
+            .load::<DbMerkleNode>(&mut conn)
+            .context("Failed to get merkle nodes")
+    }
+
+    /// Index of `leaf` among the first `leaf_count` stored leaves.
+    pub fn find_merkle_leaf_index(
+        &self,
+        tree_id: i32,
+        leaf: &str,
+        leaf_count: i64,
+    ) -> Result<Option<i64>> {
+        use crate::models::schema::merkle_nodes;
+
+        let mut conn = self.get_connection()?;
+
+        merkle_nodes::table
+            .filter(merkle_nodes::tree_id.eq(tree_id))
+            .filter(merkle_nodes::level.eq(0))
+            .filter(merkle_nodes::node_index.lt(leaf_count))
+            .filter(merkle_nodes::hash.eq_any([leaf.to_string(), leaf.to_lowercase()]))
+            .select(merkle_nodes::node_index)
+            .order(merkle_nodes::node_index.asc())
+            .first::<i64>(&mut conn)
+            .optional()
+            .context("Failed to find merkle leaf")
+    }
+
+    /// Every stored node of a tree, by level then index.
+    pub fn get_all_merkle_nodes(&self, tree_id: i32) -> Result<Vec<DbMerkleNode>> {
+        use crate::models::schema::merkle_nodes;
+
+        let mut conn = self.get_connection()?;
+
+        merkle_nodes::table
+            .filter(merkle_nodes::tree_id.eq(tree_id))
+            .order((merkle_nodes::level.asc(), merkle_nodes::node_index.asc()))
+            .select(DbMerkleNode::as_select())
+            .load::<DbMerkleNode>(&mut conn)
+            .context("Failed to get merkle nodes")
+    }
+
     pub fn get_merkle_nodes_by_level(&self, tree_id: i32, level: i32) -> Result<Vec<DbMerkleNode>> {
         use crate::models::schema::merkle_nodes;
 
@@ -1528,6 +4727,107 @@ impl Database {
         Ok(())
     }
 
+    /// Remember `root` for `tree_name`, keeping only the newest `keep` roots.
+    pub fn record_root_history(
+        &self,
+        tree_name: &str,
+        root: &str,
+        leaf_count: i64,
+        keep: i64,
+    ) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        let record = NewMerkleRootHistory {
+            tree_name,
+            root: &root.to_lowercase(),
+            leaf_count,
+            created_at: Utc::now(),
+        };
+
+        diesel::insert_into(merkle_root_history::table)
+            .values(&record)
+            .on_conflict((merkle_root_history::tree_name, merkle_root_history::root))
+            .do_nothing()
+            .execute(&mut conn)
+            .context("Failed to record root history")?;
+
+        let cutoff = merkle_root_history::table
+            .filter(merkle_root_history::tree_name.eq(tree_name))
+            .order(merkle_root_history::id.desc())
+            .offset(keep.max(1) - 1)
+            .select(merkle_root_history::id)
+            .first::<i32>(&mut conn)
+            .optional()
+            .context("Failed to find root history cutoff")?;
+
+        if let Some(cutoff) = cutoff {
+            diesel::delete(
+                merkle_root_history::table
+                    .filter(merkle_root_history::tree_name.eq(tree_name))
+                    .filter(merkle_root_history::id.lt(cutoff)),
+            )
+            .execute(&mut conn)
+            .context("Failed to prune root history")?;
+        }
+
+        Ok(())
+    }
+
+    /// Newest first.
+    pub fn get_root_history(
+        &self,
+        tree_name: &str,
+        limit: i64,
+    ) -> Result<Vec<DbMerkleRootHistory>> {
+        let mut conn = self.get_connection()?;
+
+        merkle_root_history::table
+            .filter(merkle_root_history::tree_name.eq(tree_name))
+            .order(merkle_root_history::id.desc())
+            .limit(limit)
+            .select(DbMerkleRootHistory::as_select())
+            .load(&mut conn)
+            .context("Failed to load root history")
+    }
+
+    pub fn get_historical_root(
+        &self,
+        tree_name: &str,
+        root: &str,
+    ) -> Result<Option<DbMerkleRootHistory>> {
+        let mut conn = self.get_connection()?;
+
+        merkle_root_history::table
+            .filter(merkle_root_history::tree_name.eq(tree_name))
+            .filter(merkle_root_history::root.eq(root.to_lowercase()))
+            .select(DbMerkleRootHistory::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to look up historical root")
+    }
+
+    pub fn record_merkle_repair(&self, repair: &NewMerkleRepair) -> Result<()> {
+        let mut conn = self.get_connection()?;
+
+        diesel::insert_into(merkle_repairs::table)
+            .values(repair)
+            .execute(&mut conn)
+            .context("Failed to record merkle repair")?;
+
+        Ok(())
+    }
+
+    pub fn list_merkle_repairs(&self, limit: i64) -> Result<Vec<DbMerkleRepair>> {
+        let mut conn = self.get_connection()?;
+
+        merkle_repairs::table
+            .order(merkle_repairs::id.desc())
+            .limit(limit)
+            .select(DbMerkleRepair::as_select())
+            .load(&mut conn)
+            .context("Failed to load merkle repairs")
+    }
+
     pub fn get_tree_size(&self, tree_name: &str) -> Result<usize> {
         let tree = self
             .get_merkle_tree_by_name(tree_name)?
@@ -1756,6 +5056,159 @@ impl Database {
         Ok(result)
     }
 
+    pub fn get_last_root_sync(&self, sync_type: &str) -> Result<Option<DbRootSync>> {
+        let mut conn = self.get_connection()?;
+
+        root_syncs::table
+            .filter(root_syncs::sync_type.eq(sync_type))
+            .order(root_syncs::created_at.desc())
+            .select(DbRootSync::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to fetch last root sync")
+    }
+
+    /// The first `sync_type` push at or after `after`: the one that carried
+    /// a leaf added at that time to the other chain.
+    pub fn get_first_root_sync_after(
+        &self,
+        sync_type: &str,
+        after: chrono::DateTime<Utc>,
+    ) -> Result<Option<DbRootSync>> {
+        let mut conn = self.get_connection()?;
+
+        root_syncs::table
+            .filter(root_syncs::sync_type.eq(sync_type))
+            .filter(root_syncs::created_at.ge(after))
+            .order((root_syncs::created_at.asc(), root_syncs::id.asc()))
+            .select(DbRootSync::as_select())
+            .first(&mut conn)
+            .optional()
+            .context("Failed to fetch root sync")
+    }
+
+    /// When any root was last pushed, across every chain and tree.
+    pub fn get_last_root_sync_at(&self) -> Result<Option<chrono::DateTime<Utc>>> {
+        let mut conn = self.get_connection()?;
+
+        root_syncs::table
+            .select(diesel::dsl::max(root_syncs::created_at))
+            .first(&mut conn)
+            .context("Failed to fetch last root sync time")
+    }
+
+    /// Block hashes tracked for `chain`, newest first.
+    pub fn get_block_hashes(&self, chain: &str) -> Result<Vec<(i64, String)>> {
+        let mut conn = self.get_connection()?;
+
+        chain_block_hashes::table
+            .filter(chain_block_hashes::chain.eq(chain))
+            .order(chain_block_hashes::block_number.desc())
+            .select((
+                chain_block_hashes::block_number,
+                chain_block_hashes::block_hash,
+            ))
+            .load(&mut conn)
+            .context("Failed to fetch block hashes")
+    }
+
+    /// Record `blocks` for `chain`, replacing any hash already held for the
+    /// same height, and forget everything more than `keep` blocks below the
+    /// newest one.
+    pub fn record_block_hashes(
+        &self,
+        chain: &str,
+        blocks: &[(i64, String)],
+        keep: i64,
+    ) -> Result<()> {
+        let Some(newest) = blocks.iter().map(|(number, _)| *number).max() else {
+            return Ok(());
+        };
+        let mut conn = self.get_connection()?;
+
+        conn.transaction(|conn| {
+            let rows: Vec<_> = blocks
+                .iter()
+                .map(|(number, hash)| {
+                    (
+                        chain_block_hashes::chain.eq(chain),
+                        chain_block_hashes::block_number.eq(number),
+                        chain_block_hashes::block_hash.eq(hash),
+                    )
+                })
+                .collect();
+            diesel::insert_into(chain_block_hashes::table)
+                .values(&rows)
+                .on_conflict((chain_block_hashes::chain, chain_block_hashes::block_number))
+                .do_update()
+                .set((
+                    chain_block_hashes::block_hash
+                        .eq(diesel::upsert::excluded(chain_block_hashes::block_hash)),
+                    chain_block_hashes::recorded_at.eq(Utc::now()),
+                ))
+                .execute(conn)?;
+
+            diesel::delete(
+                chain_block_hashes::table
+                    .filter(chain_block_hashes::chain.eq(chain))
+                    .filter(chain_block_hashes::block_number.le(newest - keep)),
+            )
+            .execute(conn)?;
+
+            Ok::<_, diesel::result::Error>(())
+        })
+        .context("Failed to record block hashes")
+    }
+
+    /// Undo everything indexed from `chain` at or after `from_block`: its
+    /// bridge events are deleted, intents it created lose their tree
+    /// position, and the block hashes from there on are forgotten. The
+    /// caller replays the canonical logs afterwards.
+    pub fn rollback_chain_events(
+        &self,
+        chain: &str,
+        chain_id: u32,
+        from_block: i64,
+    ) -> Result<ChainRollback> {
+        let mut conn = self.get_connection()?;
+
+        conn.transaction(|conn| {
+            let events = diesel::delete(
+                bridge_events::table
+                    .filter(bridge_events::chain_id.eq(chain_id as i32))
+                    .filter(bridge_events::block_number.ge(from_block)),
+            )
+            .returning((bridge_events::intent_id, bridge_events::event_type))
+            .get_results(conn)?;
+
+            let unpositioned_intents = diesel::update(
+                intents::table
+                    .filter(intents::source_chain.eq(chain))
+                    .filter(intents::block_number.ge(from_block)),
+            )
+            .set((
+                intents::block_number.eq(None::<i64>),
+                intents::log_index.eq(None::<i32>),
+                intents::updated_at.eq(Utc::now()),
+            ))
+            .returning(intents::id)
+            .get_results(conn)?;
+
+            diesel::delete(
+                chain_block_hashes::table
+                    .filter(chain_block_hashes::chain.eq(chain))
+                    .filter(chain_block_hashes::block_number.ge(from_block)),
+            )
+            .execute(conn)?;
+
+            Ok::<_, diesel::result::Error>(ChainRollback {
+                events,
+                unpositioned_intents,
+            })
+        })
+        .context("Failed to roll back chain events")
+    }
+
     pub fn get_latest_root(&self, chain: &str) -> Result<Option<String>> {
         let tree = self.get_merkle_tree_by_name(chain)?;
         Ok(tree.map(|t| t.root))
@@ -1773,7 +5226,7 @@ impl Database {
         self.store_bridge_event(
             &event_id,
             None,
-            "root_sync",
+            BridgeEventType::RootSync,
             event_data,
             0, // Chain ID 0 for cross-chain syncs
             0, // Block number not applicable
@@ -1790,8 +5243,8 @@ impl Database {
         let mut conn = self.get_connection()?;
 
         let events = bridge_events::table
-            .filter(bridge_events::event_type.eq("intent_filled"))
-            .filter(bridge_events::chain_id.eq(5003)) // Mantle Sepolia chain ID
+            .filter(bridge_events::event_type.eq(BridgeEventType::IntentFilled.as_str()))
+            .filter(bridge_events::chain_id.eq(MANTLE_CHAIN_ID as i32))
             .order((
                 bridge_events::block_number.asc(),
                 bridge_events::created_at.asc(),
@@ -1826,8 +5279,8 @@ impl Database {
         let mut conn = self.get_connection()?;
 
         let events = bridge_events::table
-            .filter(bridge_events::event_type.eq("intent_filled"))
-            .filter(bridge_events::chain_id.eq(11155111)) // Ethereum Sepolia chain ID
+            .filter(bridge_events::event_type.eq(BridgeEventType::IntentFilled.as_str()))
+            .filter(bridge_events::chain_id.eq(ETHEREUM_CHAIN_ID as i32))
             .order((
                 bridge_events::block_number.asc(),
                 bridge_events::created_at.asc(),
@@ -1851,24 +5304,60 @@ impl Database {
         Ok(fills)
     }
 
-    pub fn get_all_ethereum_commitments(&self) -> Result<Vec<String>> {
-        use crate::models::schema::intents::dsl::*;
+    /// Commitments of `chain_name` from live and archived intents, in event
+    /// order with unpositioned rows last. Archiving must not shrink a tree.
+    fn load_chain_commitments(
+        &self,
+        chain_name: &str,
+        positioned_only: bool,
+    ) -> Result<Vec<String>> {
         let mut conn = self.get_connection()?;
 
-        let rows: Vec<Option<String>> = intents
-            .filter(
-                source_chain
-                    .eq("ethereum")
-                    .and(source_commitment.is_not_null()),
-            )
-            .select(source_commitment)
-            .order((
-                block_number.asc().nulls_last(),
-                log_index.asc().nulls_last(),
+        let mut live = intents::table
+            .filter(intents::source_chain.eq(chain_name))
+            .filter(intents::source_commitment.is_not_null())
+            .select((
+                intents::source_commitment,
+                intents::block_number,
+                intents::log_index,
             ))
-            .load(&mut conn)?;
+            .into_boxed();
+        let mut archived = archived_intents::table
+            .filter(archived_intents::source_chain.eq(chain_name))
+            .filter(archived_intents::source_commitment.is_not_null())
+            .select((
+                archived_intents::source_commitment,
+                archived_intents::block_number,
+                archived_intents::log_index,
+            ))
+            .into_boxed();
+        if positioned_only {
+            live = live
+                .filter(intents::block_number.is_not_null())
+                .filter(intents::log_index.is_not_null());
+            archived = archived
+                .filter(archived_intents::block_number.is_not_null())
+                .filter(archived_intents::log_index.is_not_null());
+        }
+
+        let mut rows: Vec<(Option<String>, Option<i64>, Option<i32>)> = live
+            .load(&mut conn)
+            .context("Failed to load commitments from intents")?;
+        rows.extend(
+            archived
+                .load::<(Option<String>, Option<i64>, Option<i32>)>(&mut conn)
+                .context("Failed to load commitments from archived intents")?,
+        );
+        rows.sort_by_key(|(_, block, log)| (block.is_none(), *block, log.is_none(), *log));
+
+        Ok(rows
+            .into_iter()
+            .filter_map(|(commitment, _, _)| commitment)
+            .collect())
+    }
 
-        let commitments: Vec<String> = rows.into_iter().flatten().collect();
+    pub fn get_all_ethereum_commitments(&self) -> Result<Vec<String>> {
+        let commitments = self.load_chain_commitments("ethereum", false)?;
 
         if commitments.is_empty() {
             return Err(anyhow!(
@@ -1880,23 +5369,7 @@ impl Database {
     }
 
     pub fn get_all_mantle_commitments(&self) -> Result<Vec<String>> {
-        use crate::models::schema::intents::dsl::*;
-        let mut conn = self.get_connection()?;
-
-        let rows: Vec<Option<String>> = intents
-            .filter(
-                source_chain
-                    .eq("mantle")
-                    .and(source_commitment.is_not_null()),
-            )
-            .select(source_commitment)
-            .order((
-                block_number.asc().nulls_last(),
-                log_index.asc().nulls_last(),
-            ))
-            .load(&mut conn)?;
-
-        let commitments: Vec<String> = rows.into_iter().flatten().collect();
+        let commitments = self.load_chain_commitments("mantle", false)?;
 
         if commitments.is_empty() {
             return Err(anyhow!(
@@ -1908,21 +5381,7 @@ impl Database {
     }
 
     pub fn get_all_commitments_for_chain(&self, chain_name: &str) -> Result<Vec<String>> {
-        use crate::models::schema::intents::dsl::*;
-        let mut conn = self.get_connection()?;
-
-        let commitments: Vec<String> = intents
-            .filter(source_chain.eq(chain_name))
-            .filter(source_commitment.is_not_null())
-            .filter(block_number.is_not_null())
-            .filter(log_index.is_not_null())
-            .order((block_number.asc(), log_index.asc()))
-            .select(source_commitment)
-            .load::<Option<String>>(&mut conn)
-            .context("Failed to load commitments column from intents table")?
-            .into_iter()
-            .flatten()
-            .collect();
+        let commitments = self.load_chain_commitments(chain_name, true)?;
 
         info!(
             "📊 Loaded {} commitments for chain '{}'",
@@ -1934,21 +5393,8 @@ impl Database {
     }
 
     pub fn get_commitments_for_tree(&self, chain_name: &str, limit: i64) -> Result<Vec<String>> {
-        use crate::models::schema::intents::dsl::*;
-        let mut conn = self.get_connection()?;
-
-        let commitments: Vec<String> = intents
-            .filter(source_chain.eq(chain_name))
-            .filter(source_commitment.is_not_null())
-            .filter(block_number.is_not_null()) // ✅ CRITICAL
-            .filter(log_index.is_not_null()) // ✅ CRITICAL
-            .order((block_number.asc(), log_index.asc()))
-            .limit(limit) // Only take first N commitments
-            .select(source_commitment)
-            .load::<Option<String>>(&mut conn)?
-            .into_iter()
-            .flatten()
-            .collect();
+        let mut commitments = self.load_chain_commitments(chain_name, true)?;
+        commitments.truncate(limit.max(0) as usize); // Only take first N commitments
 
         info!(
             "📊 Loaded {} commitments (limit: {}) for chain '{}'",
@@ -1964,14 +5410,10 @@ impl Database {
         use crate::models::schema::bridge_events::dsl::*;
         let mut conn = self.get_connection()?;
 
-        let chain_id_value = match chain_name {
-            "ethereum" => 11155111,
-            "mantle" => 5003,
-            _ => return Err(anyhow!("Unknown chain: {}", chain_name)),
-        };
+        let chain_id_value = Chain::parse(chain_name)?.id() as i32;
 
         let fills: Vec<String> = bridge_events
-            .filter(event_type.eq("intent_filled"))
+            .filter(event_type.eq(BridgeEventType::IntentFilled.as_str()))
             .filter(chain_id.eq(chain_id_value))
             .filter(block_number.is_not_null())
             .filter(log_index.is_not_null())
@@ -1997,14 +5439,10 @@ impl Database {
         use crate::models::schema::bridge_events::dsl::*;
         let mut conn = self.get_connection()?;
 
-        let chain_id_value = match chain_name {
-            "ethereum" => 11155111,
-            "mantle" => 5003,
-            _ => return Err(anyhow!("Unknown chain: {}", chain_name)),
-        };
+        let chain_id_value = Chain::parse(chain_name)?.id() as i32;
 
         let fills: Vec<String> = bridge_events
-            .filter(event_type.eq("intent_filled"))
+            .filter(event_type.eq(BridgeEventType::IntentFilled.as_str()))
             .filter(chain_id.eq(chain_id_value))
             .filter(block_number.is_not_null())
             .filter(log_index.is_not_null())
@@ -2049,12 +5487,29 @@ impl Database {
         Ok(last_block)
     }
 
+    /// Block of the newest fill leaf made on `chain_id`.
+    pub fn get_last_fill_block(&self, chain_id: u32) -> Result<Option<u64>> {
+        use crate::models::schema::bridge_events;
+        let mut conn = self.get_connection()?;
+
+        let last_block = bridge_events::table
+            .filter(bridge_events::event_type.eq(BridgeEventType::IntentFilled.as_str()))
+            .filter(bridge_events::chain_id.eq(chain_id as i32))
+            .filter(bridge_events::log_index.is_not_null())
+            .select(diesel::dsl::max(bridge_events::block_number))
+            .first::<Option<i64>>(&mut conn)
+            .context("Failed to get last fill block")?
+            .map(|b| b as u64);
+
+        Ok(last_block)
+    }
+
     // ==================== Statistics ====================
 
     pub fn get_bridge_stats(&self) -> Result<BridgeStats> {
         let mut conn = self.get_connection()?;
 
-        let total_intents: i64 = intents::table.count().get_result(&mut conn)?;
+        let mut total_intents: i64 = intents::table.count().get_result(&mut conn)?;
 
         let pending_intents: i64 = intents::table
             .filter(intents::status.eq_any(vec!["created", "committed"]))
@@ -2071,23 +5526,23 @@ impl Database {
             .count()
             .get_result(&mut conn)?;
 
-        let failed_intents: i64 = intents::table
+        let mut failed_intents: i64 = intents::table
             .filter(intents::status.eq("failed"))
             .count()
             .get_result(&mut conn)?;
 
-        let refunded_intents: i64 = intents::table
+        let mut refunded_intents: i64 = intents::table
             .filter(intents::status.eq("refunded"))
             .count()
             .get_result(&mut conn)?;
 
-        let ethereum_to_mantle: i64 = intents::table
+        let mut ethereum_to_mantle: i64 = intents::table
             .filter(intents::source_chain.eq("ethereum"))
             .filter(intents::dest_chain.eq("mantle"))
             .count()
             .get_result(&mut conn)?;
 
-        let mantle_to_ethereum: i64 = intents::table
+        let mut mantle_to_ethereum: i64 = intents::table
             .filter(intents::source_chain.eq("mantle"))
             .filter(intents::dest_chain.eq("ethereum"))
             .count()
@@ -2098,7 +5553,38 @@ impl Database {
             .select(DbIntent::as_select()) // ✅ Add this
             .load::<DbIntent>(&mut conn)?;
 
-        let completed: Vec<Intent> = completed.into_iter().map(db_intent_to_model).collect();
+        let completed: Vec<Intent> = completed
+            .into_iter()
+            .map(db_intent_to_model)
+            .collect::<Result<_>>()?;
+
+        // Lifetime totals include intents the archiver has moved out
+        let archived: Vec<(String, String, String, i64)> = archived_intents::table
+            .group_by((
+                archived_intents::status,
+                archived_intents::source_chain,
+                archived_intents::dest_chain,
+            ))
+            .select((
+                archived_intents::status,
+                archived_intents::source_chain,
+                archived_intents::dest_chain,
+                diesel::dsl::count_star(),
+            ))
+            .load(&mut conn)?;
+        for (status, source, dest, count) in archived {
+            total_intents += count;
+            match status.as_str() {
+                "failed" => failed_intents += count,
+                "refunded" => refunded_intents += count,
+                _ => {}
+            }
+            match (source.as_str(), dest.as_str()) {
+                ("ethereum", "mantle") => ethereum_to_mantle += count,
+                ("mantle", "ethereum") => mantle_to_ethereum += count,
+                _ => {}
+            }
+        }
 
         let mut total_volumes_u128 = HashMap::new();
         for intent in completed {
@@ -2138,31 +5624,15 @@ fn parse_status(s: &str) -> IntentStatus {
         "solver_paid" => IntentStatus::SolverPaid,
         "refunded" => IntentStatus::Refunded,
         "failed" => IntentStatus::Failed,
+        "underpaid" => IntentStatus::Underpaid,
         _ => IntentStatus::Failed,
     }
 }
 
-fn db_intent_to_model(r: DbIntent) -> Intent {
-    Intent {
-        id: r.id,
-        user_address: r.user_address,
-        source_chain: r.source_chain,
-        dest_chain: r.dest_chain,
-        source_token: r.source_token,
-        dest_token: r.dest_token,
-        amount: r.amount,
-        dest_amount: r.dest_amount,
-        source_commitment: r.source_commitment,
-        dest_fill_txid: r.dest_fill_txid,
-        dest_registration_txid: r.dest_registration_txid,
-        source_complete_txid: r.source_complete_txid,
-        status: parse_status(&r.status),
-        created_at: r.created_at,
-        updated_at: r.updated_at,
-        deadline: r.deadline as u64,
-        refund_address: r.refund_address,
-        solver_address: r.solver_address,
-        block_number: r.block_number,
-        log_index: r.log_index,
-    }
+fn db_intent_to_model(r: DbIntent) -> Result<Intent> {
+    let status = parse_status(&r.status);
+    Ok(Intent {
+        status,
+        ..Intent::try_from(r)?
+    })
 }
@@ -1,2 +1,7 @@
 pub mod database;
 pub mod model;
+pub mod schema_check;
+// Factories are shared by tests across modules; not every helper is used yet
+#[cfg(test)]
+#[allow(dead_code)]
+pub mod test_support;
@@ -1,15 +1,29 @@
 use std::collections::HashMap;
 
+use anyhow::Context;
 use chrono::{DateTime, Utc};
 use diesel::prelude::*;
+use mantle_core::{
+    hex_types::{AddressHex, AmountDec, H256Hex},
+    parse,
+    token::TokenType,
+    token_registry::TokenListing,
+};
 use serde::{Deserialize, Serialize};
 
 use crate::models::{
-    model::{Intent, IntentPrivacyParams, IntentStatus},
+    model::{BridgeEventType, Intent, IntentPrivacyParams, IntentStatus},
     schema::{
-        bridge_events, chain_transactions, ethereum_sepolia_intent_created, indexer_checkpoints,
-        intent_privacy_params, intents, mantle_sepolia_intent_created, merkle_nodes, merkle_roots,
-        merkle_tree_ethereum_commitments, merkle_trees, root_syncs,
+        admin_api_keys, admin_audit_log, api_keys, archived_intents, bridge_events,
+        chain_transactions, claim_auth_checks, claim_sponsorships, compliance_screenings,
+        debug_capture_entries, debug_captures, deposit_discrepancies,
+        ethereum_sepolia_intent_created, fill_reports, indexer_checkpoints, ingestion_queue,
+        intent_annotations, intent_encrypted_notes, intent_privacy_params, intent_reservations,
+        intents, mantle_sepolia_intent_created, merkle_nodes, merkle_repairs, merkle_root_history,
+        merkle_roots, merkle_tree_ethereum_commitments, merkle_trees, orphaned_fills,
+        partition_assignments, privacy_context_rekeys, quarantined_commitments, relayer_instances,
+        relayer_jobs, root_syncs, secret_reveals, token_listings, transaction_receipts,
+        tx_submissions, user_intent_limits, webhook_endpoints, webhook_secrets,
     },
 };
 
@@ -65,6 +79,60 @@ pub struct NewIntent<'a> {
     pub log_index: Option<i32>,
 }
 
+/// A terminal intent moved out of `intents` by the archiver.
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = archived_intents)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbArchivedIntent {
+    pub id: String,
+    pub user_address: String,
+    pub source_chain: String,
+    pub dest_chain: String,
+    pub source_token: String,
+    pub dest_token: String,
+    pub amount: String,
+    pub dest_amount: String,
+    pub source_commitment: Option<String>,
+    pub dest_fill_txid: Option<String>,
+    pub dest_registration_txid: Option<String>,
+    pub source_complete_txid: Option<String>,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub deadline: i64,
+    pub refund_address: Option<String>,
+    pub solver_address: Option<String>,
+    pub block_number: Option<i64>,
+    pub log_index: Option<i32>,
+}
+
+impl From<DbArchivedIntent> for DbIntent {
+    fn from(r: DbArchivedIntent) -> Self {
+        Self {
+            id: r.id,
+            user_address: r.user_address,
+            source_chain: r.source_chain,
+            dest_chain: r.dest_chain,
+            source_token: r.source_token,
+            dest_token: r.dest_token,
+            amount: r.amount,
+            dest_amount: r.dest_amount,
+            source_commitment: r.source_commitment,
+            dest_fill_txid: r.dest_fill_txid,
+            dest_registration_txid: r.dest_registration_txid,
+            source_complete_txid: r.source_complete_txid,
+            status: r.status,
+            created_at: r.created_at,
+            updated_at: r.updated_at,
+            deadline: r.deadline,
+            refund_address: r.refund_address,
+            solver_address: r.solver_address,
+            block_number: r.block_number,
+            log_index: r.log_index,
+        }
+    }
+}
+
 // ==================== Intent Privacy Params ====================
 
 #[derive(Debug, Clone, Queryable, Selectable)]
@@ -79,6 +147,8 @@ pub struct DbIntentPrivacyParams {
     pub claim_signature: Option<String>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub context_tag: String,
+    pub claim_delegate: Option<String>,
 }
 
 #[derive(Debug, Insertable)]
@@ -92,6 +162,28 @@ pub struct NewIntentPrivacyParams<'a> {
     pub claim_signature: Option<&'a str>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    pub context_tag: &'a str,
+    pub claim_delegate: Option<&'a str>,
+}
+
+/// The one-time `rekey-privacy-contexts` run.
+#[derive(Queryable, Debug, Clone, Serialize, Selectable)]
+#[diesel(table_name = privacy_context_rekeys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbPrivacyContextRekey {
+    pub id: i32,
+    pub rekeyed: i64,
+    pub unverified: Vec<String>,
+    pub completed_at: DateTime<Utc>,
+}
+
+/// Rows given a keyed tag, and intent ids whose v1 tag no longer matched
+/// their contents and were left for reads to refuse.
+#[derive(Insertable, Debug, Clone, Default, Serialize)]
+#[diesel(table_name = privacy_context_rekeys)]
+pub struct NewPrivacyContextRekey {
+    pub rekeyed: i64,
+    pub unverified: Vec<String>,
 }
 
 // ==================== Chain Transactions ====================
@@ -122,6 +214,712 @@ pub struct NewChainTransaction<'a> {
     pub created_at: DateTime<Utc>,
 }
 
+// ==================== Transaction Receipts ====================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = transaction_receipts)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbTransactionReceipt {
+    pub tx_hash: String,
+    pub intent_id: Option<String>,
+    pub chain_id: i32,
+    pub tx_type: String,
+    pub status: String,
+    pub block_number: Option<i64>,
+    pub gas_used: Option<i64>,
+    pub effective_gas_price: Option<String>,
+    pub revert_reason: Option<String>,
+    pub receipt: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = transaction_receipts)]
+pub struct NewTransactionReceipt<'a> {
+    pub tx_hash: &'a str,
+    pub intent_id: Option<&'a str>,
+    pub chain_id: i32,
+    pub tx_type: &'a str,
+    pub status: &'a str,
+    pub block_number: Option<i64>,
+    pub gas_used: Option<i64>,
+    pub effective_gas_price: Option<&'a str>,
+    pub revert_reason: Option<&'a str>,
+    pub receipt: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+// ==================== Claim Sponsorships ====================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = claim_sponsorships)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbClaimSponsorship {
+    pub intent_id: String,
+    pub sponsored: bool,
+    pub chain_id: Option<i32>,
+    pub claim_tx_hash: Option<String>,
+    pub gas_used: Option<i64>,
+    pub gas_cost_wei: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = claim_sponsorships)]
+pub struct NewClaimSponsorship<'a> {
+    pub intent_id: &'a str,
+    pub sponsored: bool,
+    pub chain_id: Option<i32>,
+    pub claim_tx_hash: Option<&'a str>,
+    pub gas_used: Option<i64>,
+    pub gas_cost_wei: Option<&'a str>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================== User Intent Limits ====================
+
+/// Admin override of the per-user active intent cap.
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = user_intent_limits)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbUserIntentLimit {
+    pub user_address: String,
+    pub max_active: i32,
+    pub note: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = user_intent_limits)]
+pub struct NewUserIntentLimit<'a> {
+    pub user_address: &'a str,
+    pub max_active: i32,
+    pub note: Option<&'a str>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================== Claim Auth Checks ====================
+
+/// Latest verification of an intent's stored claim signature.
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = claim_auth_checks)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbClaimAuthCheck {
+    pub intent_id: String,
+    pub valid: bool,
+    pub signer: Option<String>,
+    pub error: Option<String>,
+    pub checked_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = claim_auth_checks)]
+pub struct NewClaimAuthCheck<'a> {
+    pub intent_id: &'a str,
+    pub valid: bool,
+    pub signer: Option<&'a str>,
+    pub error: Option<&'a str>,
+    pub checked_at: DateTime<Utc>,
+}
+
+/// A failing claim signature on an intent that can still be claimed.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimAuthFailure {
+    #[serde(flatten)]
+    pub check: DbClaimAuthCheck,
+    pub status: String,
+    pub deadline: i64,
+}
+
+// ==================== Compliance Screenings ====================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = compliance_screenings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbComplianceScreening {
+    pub intent_id: String,
+    pub address: String,
+    pub chain: String,
+    pub risk_score: Option<f64>,
+    pub labels: Option<String>,
+    pub action: String,
+    pub reason: Option<String>,
+    pub provider_error: Option<String>,
+    pub override_note: Option<String>,
+    pub screened_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = compliance_screenings)]
+pub struct NewComplianceScreening<'a> {
+    pub intent_id: &'a str,
+    pub address: &'a str,
+    pub chain: &'a str,
+    pub risk_score: Option<f64>,
+    pub labels: Option<&'a str>,
+    pub action: &'a str,
+    pub reason: Option<&'a str>,
+    pub provider_error: Option<&'a str>,
+    pub screened_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================== Secret Reveals ====================
+
+/// Intent whose secret the user keeps and reveals after the fill.
+/// `reveal_deadline` is unset until the fill is seen.
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = secret_reveals)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbSecretReveal {
+    pub intent_id: String,
+    pub reveal_deadline: Option<i64>,
+    #[serde(skip_serializing)]
+    pub secret: Option<String>,
+    #[serde(skip_serializing)]
+    pub signature: Option<String>,
+    pub revealed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = secret_reveals)]
+pub struct NewSecretReveal<'a> {
+    pub intent_id: &'a str,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================== Webhooks ====================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = webhook_endpoints)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbWebhookEndpoint {
+    pub id: String,
+    pub url: String,
+    pub description: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    /// Events the endpoint receives; every event when unset.
+    pub filter: Option<String>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = webhook_endpoints)]
+pub struct NewWebhookEndpoint<'a> {
+    pub id: &'a str,
+    pub url: &'a str,
+    pub description: Option<&'a str>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub filter: Option<&'a str>,
+}
+
+/// Signing secret of an endpoint. `expires_at` is set once a newer secret
+/// replaces it; until then both sign every delivery.
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = webhook_secrets)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbWebhookSecret {
+    pub endpoint_id: String,
+    pub key_id: String,
+    #[serde(skip_serializing)]
+    pub secret: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = webhook_secrets)]
+pub struct NewWebhookSecret<'a> {
+    pub endpoint_id: &'a str,
+    pub key_id: &'a str,
+    pub secret: &'a str,
+    pub created_at: DateTime<Utc>,
+}
+
+// ==================== Orphaned Fills ====================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = orphaned_fills)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbOrphanedFill {
+    pub intent_id: String,
+    pub source_chain: String,
+    pub dest_chain: String,
+    pub solver_address: String,
+    pub token: String,
+    /// Base units of `token`.
+    pub amount: String,
+    pub status: String,
+    pub recovery_txid: Option<String>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub approved_at: Option<DateTime<Utc>>,
+    pub detected_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = intent_reservations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbIntentReservation {
+    pub intent_id: String,
+    /// Lowercase.
+    pub solver_address: String,
+    /// `active`, `filled`, `released` or `lapsed`.
+    pub status: String,
+    pub reserved_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = fill_reports)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbFillReport {
+    pub id: i64,
+    /// Lowercase, like the transaction hash and solver address.
+    pub intent_id: String,
+    /// Chain key the fill was reported on, e.g. `mantle`.
+    pub chain: String,
+    pub transaction_hash: String,
+    pub solver_address: String,
+    /// `pending`, `verified` or `rejected`.
+    pub status: String,
+    /// Why a report was rejected.
+    pub reason: Option<String>,
+    pub reported_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = relayer_jobs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbRelayerJob {
+    pub id: i64,
+    /// `registration` or `settlement`.
+    pub kind: String,
+    /// Lowercase.
+    pub intent_id: String,
+    /// `pending`, `done`, `dead` or `cancelled`.
+    pub status: String,
+    /// Failed attempts since the job was last (re)opened.
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    /// A `pending` job is not picked up before this.
+    pub next_attempt_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = debug_captures)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbDebugCapture {
+    /// Lowercase.
+    pub intent_id: String,
+    pub operator: String,
+    pub reason: String,
+    pub started_at: DateTime<Utc>,
+    /// Nothing more is recorded after this.
+    pub expires_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = debug_capture_entries)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbDebugCaptureEntry {
+    pub id: i64,
+    pub intent_id: String,
+    /// Which part of the relayer recorded it, e.g. `api` or `settlement`.
+    pub component: String,
+    /// `request`, `response`, `rpc` or `decision`.
+    pub kind: String,
+    pub payload: serde_json::Value,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = ingestion_queue)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbIngestionEvent {
+    pub id: i64,
+    /// `indexer` or `log_listener`: whichever delivered it first.
+    pub source: String,
+    pub event_type: String,
+    pub chain: String,
+    pub transaction_hash: String,
+    pub block_number: i64,
+    pub log_index: i32,
+    /// The event as received, in the indexer's format.
+    pub payload: serde_json::Value,
+    /// `pending`, `done` or `dead`.
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub received_at: DateTime<Utc>,
+    /// A `pending` event is not claimed before this; claiming moves it
+    /// forward by the lease.
+    pub next_attempt_at: DateTime<Utc>,
+    pub processed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = ingestion_queue)]
+pub struct NewIngestionEvent<'a> {
+    pub source: &'a str,
+    pub event_type: &'a str,
+    pub chain: &'a str,
+    /// Lowercase.
+    pub transaction_hash: String,
+    pub block_number: i64,
+    pub log_index: i32,
+    pub payload: serde_json::Value,
+}
+
+/// Events of one source in one status, for consumer lag.
+#[derive(Debug, Clone, Serialize)]
+pub struct IngestionBacklog {
+    pub source: String,
+    pub status: String,
+    pub count: i64,
+    pub oldest_received_at: Option<DateTime<Utc>>,
+}
+
+/// An admin API key; the key itself is never stored.
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = admin_api_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbAdminApiKey {
+    pub id: i32,
+    pub name: String,
+    /// First characters of the key, to tell keys apart in listings.
+    pub key_prefix: String,
+    #[serde(skip)]
+    pub key_hash: String,
+    pub role: String,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = admin_api_keys)]
+pub struct NewAdminApiKey<'a> {
+    pub name: &'a str,
+    pub key_prefix: &'a str,
+    pub key_hash: &'a str,
+    pub role: &'a str,
+    pub created_by: &'a str,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = admin_audit_log)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbAdminAuditEntry {
+    pub id: i64,
+    /// Key name, `hmac` for the shared secret, or `anonymous`.
+    pub actor: String,
+    pub key_id: Option<i32>,
+    pub role: Option<String>,
+    pub method: String,
+    pub path: String,
+    pub required_role: String,
+    pub status_code: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = admin_audit_log)]
+pub struct NewAdminAuditEntry<'a> {
+    pub actor: &'a str,
+    pub key_id: Option<i32>,
+    pub role: Option<&'a str>,
+    pub method: &'a str,
+    pub path: &'a str,
+    pub required_role: &'a str,
+    pub status_code: i32,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = api_keys)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbApiKey {
+    pub id: i32,
+    pub name: String,
+    pub key_prefix: String,
+    #[serde(skip)]
+    pub key_hash: String,
+    /// `read`, `create_intent` and/or `admin`.
+    pub scopes: Vec<String>,
+    /// Overrides the default per-key rate when set.
+    pub rate_limit_per_min: Option<i32>,
+    pub created_by: String,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = api_keys)]
+pub struct NewApiKey<'a> {
+    pub name: &'a str,
+    pub key_prefix: &'a str,
+    pub key_hash: &'a str,
+    pub scopes: Vec<&'a str>,
+    pub rate_limit_per_min: Option<i32>,
+    pub created_by: &'a str,
+}
+
+/// A note only the recipient can read. Never log `ciphertext`.
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = intent_encrypted_notes)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbEncryptedNote {
+    pub intent_id: String,
+    pub ciphertext: String,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = deposit_discrepancies)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbDepositDiscrepancy {
+    pub intent_id: String,
+    pub source_chain: String,
+    pub token: String,
+    pub kind: String,
+    /// Base units of `token`, as are the other amounts.
+    pub expected_amount: String,
+    pub deposited_amount: String,
+    pub difference: String,
+    pub transaction_hash: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = deposit_discrepancies)]
+pub struct NewDepositDiscrepancy<'a> {
+    pub intent_id: &'a str,
+    pub source_chain: &'a str,
+    pub token: &'a str,
+    pub kind: &'a str,
+    pub expected_amount: &'a str,
+    pub deposited_amount: &'a str,
+    pub difference: &'a str,
+    pub transaction_hash: &'a str,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = orphaned_fills)]
+pub struct NewOrphanedFill<'a> {
+    pub intent_id: &'a str,
+    pub source_chain: &'a str,
+    pub dest_chain: &'a str,
+    pub solver_address: &'a str,
+    pub token: &'a str,
+    pub amount: &'a str,
+    pub status: &'a str,
+    pub detected_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================== Intent Annotations ====================
+
+/// An operator note or manual status change on an intent.
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = intent_annotations)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbIntentAnnotation {
+    pub id: i32,
+    pub intent_id: String,
+    pub operator: String,
+    /// `note` or `status_override`.
+    pub kind: String,
+    pub reason: String,
+    /// Set on overrides only.
+    pub previous_status: Option<String>,
+    pub new_status: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = intent_annotations)]
+pub struct NewIntentAnnotation<'a> {
+    pub intent_id: &'a str,
+    pub operator: &'a str,
+    pub kind: &'a str,
+    pub reason: &'a str,
+    pub previous_status: Option<&'a str>,
+    pub new_status: Option<&'a str>,
+    pub created_at: DateTime<Utc>,
+}
+
+// ==================== Work Partitions ====================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = relayer_instances)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbRelayerInstance {
+    pub instance_id: String,
+    pub region: String,
+    pub started_at: DateTime<Utc>,
+    pub heartbeat_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = partition_assignments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbPartitionAssignment {
+    pub partition: i32,
+    /// `None` while unowned.
+    pub instance_id: Option<String>,
+    pub epoch: i64,
+    pub assigned_at: DateTime<Utc>,
+}
+
+// ==================== Token Listings ====================
+
+/// What route analytics reads of an intent, live or archived.
+#[derive(Debug, Clone, Queryable)]
+pub struct IntentSample {
+    pub source_chain: String,
+    pub dest_chain: String,
+    pub source_token: String,
+    pub amount: String,
+    pub status: String,
+    pub deadline: i64,
+}
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = token_listings)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbTokenListing {
+    pub symbol: String,
+    pub ethereum_address: String,
+    pub mantle_address: String,
+    pub decimals: i32,
+    pub min_amount: String,
+    pub max_amount: String,
+    pub enabled: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl DbTokenListing {
+    pub fn listing(&self) -> anyhow::Result<TokenListing> {
+        let listing = TokenListing {
+            token: TokenType::from_symbol(&self.symbol)?,
+            ethereum: parse::address("ethereum_address", &self.ethereum_address)?,
+            mantle: parse::address("mantle_address", &self.mantle_address)?,
+            decimals: u8::try_from(self.decimals)?,
+            min_amount: parse::amount("min_amount", &self.min_amount)?,
+            max_amount: parse::amount("max_amount", &self.max_amount)?,
+            enabled: self.enabled,
+        };
+        listing.validate()?;
+
+        Ok(listing)
+    }
+}
+
+// ==================== Tx Submissions ====================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = tx_submissions)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbTxSubmission {
+    pub operation: String,
+    pub intent_id: String,
+    pub chain_id: i32,
+    pub tx_type: String,
+    pub calldata_hash: String,
+    /// Set from inside the tx queue, right before the send.
+    pub nonce: Option<i64>,
+    pub tx_hash: Option<String>,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+// ==================== Quarantined Commitments ====================
+
+#[derive(Debug, Clone, Queryable, Selectable, Serialize)]
+#[diesel(table_name = quarantined_commitments)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbQuarantinedCommitment {
+    pub id: i32,
+    pub intent_id: String,
+    pub commitment: String,
+    pub chain: String,
+    pub conflicting_intent_id: String,
+    pub conflicting_chain: String,
+    pub scope: String,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Insertable)]
+#[diesel(table_name = quarantined_commitments)]
+pub struct NewQuarantinedCommitment<'a> {
+    pub intent_id: &'a str,
+    pub commitment: &'a str,
+    pub chain: &'a str,
+    pub conflicting_intent_id: &'a str,
+    pub conflicting_chain: &'a str,
+    pub scope: &'a str,
+    pub detected_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, QueryableByName)]
+pub struct DuplicateCommitment {
+    #[diesel(sql_type = diesel::sql_types::Text)]
+    pub commitment: String,
+    #[diesel(sql_type = diesel::sql_types::Array<diesel::sql_types::Text>)]
+    pub intent_ids: Vec<String>,
+    #[diesel(sql_type = diesel::sql_types::Array<diesel::sql_types::Text>)]
+    pub chains: Vec<String>,
+}
+
+/// Where an intent sits in commitment order, to tell the original of a
+/// duplicate commitment from its copies.
+#[derive(Debug, Clone, Queryable, Selectable)]
+#[diesel(table_name = intents)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct CommitmentOrder {
+    pub id: String,
+    pub source_chain: String,
+    pub block_number: Option<i64>,
+    pub log_index: Option<i32>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl CommitmentOrder {
+    /// Whether this intent came first. Within one chain the log position
+    /// decides; across chains, or without one, the time the relayer saw it.
+    pub fn precedes(&self, other: &CommitmentOrder) -> bool {
+        let position = |order: &CommitmentOrder| {
+            order
+                .block_number
+                .map(|block| (block, order.log_index.unwrap_or(0)))
+        };
+        match (position(self), position(other)) {
+            (Some(ours), Some(theirs)) if self.source_chain == other.source_chain => {
+                (ours, &self.id) < (theirs, &other.id)
+            }
+            _ => (self.created_at, &self.id) < (other.created_at, &other.id),
+        }
+    }
+}
+
 // ==================== Bridge Events ====================
 
 #[derive(Debug, Clone, Queryable, Selectable)]
@@ -156,6 +954,15 @@ pub struct NewBridgeEvent<'a> {
     pub log_index: Option<i32>,
 }
 
+/// What was undone on a chain after a reorg.
+#[derive(Debug, Clone, Default)]
+pub struct ChainRollback {
+    /// Deleted `bridge_events` as `(intent_id, event_type)`.
+    pub events: Vec<(Option<String>, String)>,
+    /// Intents whose creation log was dropped, until the replay puts it back.
+    pub unpositioned_intents: Vec<String>,
+}
+
 // ==================== Indexer Checkpoints ====================
 
 #[derive(Debug, Clone, Queryable, Selectable)]
@@ -182,6 +989,24 @@ pub struct BridgeStats {
     pub total_volume_by_token: HashMap<String, String>,
 }
 
+/// Number of intents in a non-terminal status and when the oldest was created.
+#[derive(Debug, Clone, Serialize)]
+pub struct IntentStatusGauge {
+    pub status: String,
+    pub count: u64,
+    pub oldest_created_at: Option<DateTime<Utc>>,
+}
+
+/// An intent that reached `completed`, `failed` or `refunded`.
+#[derive(Queryable, Debug, Clone)]
+pub struct IntentOutcome {
+    pub source_chain: String,
+    pub dest_chain: String,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub finished_at: DateTime<Utc>,
+}
+
 // ==================== Merkle Structs ====================
 #[derive(Queryable, Debug, Clone, Serialize, Deserialize, Selectable)]
 #[diesel(table_name = merkle_trees)]
@@ -252,6 +1077,56 @@ pub struct NewMerkleRoot<'a> {
     pub created_at: DateTime<Utc>,
 }
 
+/// A past root of a tree and the leaf count that produced it.
+#[derive(Queryable, Debug, Clone, Serialize, Selectable)]
+#[diesel(table_name = merkle_root_history)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbMerkleRootHistory {
+    pub id: i32,
+    pub tree_name: String,
+    pub root: String,
+    pub leaf_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = merkle_root_history)]
+pub struct NewMerkleRootHistory<'a> {
+    pub tree_name: &'a str,
+    pub root: &'a str,
+    pub leaf_count: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A startup repair of a tree whose stored nodes, leaf count and root
+/// disagreed.
+#[derive(Queryable, Debug, Clone, Serialize, Selectable)]
+#[diesel(table_name = merkle_repairs)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+pub struct DbMerkleRepair {
+    pub id: i32,
+    pub tree_name: String,
+    pub issues: Vec<String>,
+    pub stored_leaf_count: i64,
+    pub stored_root: String,
+    pub repaired_leaf_count: i64,
+    pub repaired_root: String,
+    pub nodes_rewritten: i64,
+    pub repaired_at: DateTime<Utc>,
+}
+
+#[derive(Insertable, Debug)]
+#[diesel(table_name = merkle_repairs)]
+pub struct NewMerkleRepair<'a> {
+    pub tree_name: &'a str,
+    pub issues: Vec<String>,
+    pub stored_leaf_count: i64,
+    pub stored_root: &'a str,
+    pub repaired_leaf_count: i64,
+    pub repaired_root: &'a str,
+    pub nodes_rewritten: i64,
+}
+
 #[derive(Queryable, Selectable)]
 #[diesel(table_name = ethereum_sepolia_intent_created)]
 pub struct DbEthereumIntentCreated {
@@ -297,6 +1172,18 @@ pub struct NewRootSync<'a> {
     pub root: &'a str,
     pub tx_hash: &'a str,
     pub created_at: chrono::DateTime<Utc>,
+    pub leaf_count: Option<i64>,
+}
+
+#[derive(Queryable, Selectable, Debug, Clone)]
+#[diesel(table_name = root_syncs)]
+pub struct DbRootSync {
+    pub id: i32,
+    pub sync_type: String,
+    pub root: String,
+    pub tx_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub leaf_count: Option<i64>,
 }
 
 #[derive(Insertable)]
@@ -314,6 +1201,7 @@ impl IntentStatus {
             Self::Registered => "registered",
             Self::Pending => "pending",
             Self::Committed => "committed",
+            Self::Underpaid => "underpaid",
             Self::Filled => "filled",
             Self::UserClaimed => "user_claimed",
             Self::SolverPaid => "solver_paid",
@@ -329,28 +1217,76 @@ impl IntentStatus {
             "registered" => Ok(Self::Registered),
             "pending" => Ok(Self::Pending),
             "committed" => Ok(Self::Committed),
+            "underpaid" => Ok(Self::Underpaid),
             "filled" => Ok(Self::Filled),
             "user_claimed" => Ok(Self::UserClaimed),
             "solver_paid" => Ok(Self::SolverPaid),
             "refunded" => Ok(Self::Refunded),
             "failed" => Ok(Self::Failed),
+            "expired" => Ok(Self::Expired),
             _ => Err(format!("Invalid intent status: {}", s).into()),
         }
     }
 }
 
-impl From<DbIntent> for Intent {
-    fn from(db: DbIntent) -> Self {
-        Self {
-            id: db.id,
+impl BridgeEventType {
+    pub const ALL: [Self; 8] = [
+        Self::IntentCreated,
+        Self::IntentRegistered,
+        Self::IntentFilled,
+        Self::IntentSettled,
+        Self::IntentRefunded,
+        Self::WithdrawalClaimed,
+        Self::RootSync,
+        Self::NullifierUsed,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::IntentCreated => "intent_created",
+            Self::IntentRegistered => "intent_registered",
+            Self::IntentFilled => "intent_filled",
+            Self::IntentSettled => "intent_settled",
+            Self::IntentRefunded => "intent_refunded",
+            Self::WithdrawalClaimed => "withdrawal_claimed",
+            Self::RootSync => "root_sync",
+            Self::NullifierUsed => "nullifier_used",
+        }
+    }
+}
+
+impl std::str::FromStr for BridgeEventType {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|event_type| event_type.as_str() == s)
+            .ok_or_else(|| anyhow::anyhow!("Invalid bridge event type: {}", s))
+    }
+}
+
+/// Fails on a row whose id, amounts or commitment are malformed.
+impl TryFrom<DbIntent> for Intent {
+    type Error = anyhow::Error;
+
+    fn try_from(db: DbIntent) -> anyhow::Result<Self> {
+        let context = || format!("Malformed intent row {}", db.id);
+        Ok(Self {
+            id: H256Hex::parse("id", &db.id).with_context(context)?,
             user_address: db.user_address,
             source_chain: db.source_chain,
             dest_chain: db.dest_chain,
             source_token: db.source_token,
             dest_token: db.dest_token,
-            amount: db.amount,
-            dest_amount: db.dest_amount,
-            source_commitment: db.source_commitment,
+            amount: AmountDec::parse("amount", &db.amount).with_context(context)?,
+            dest_amount: AmountDec::parse("dest_amount", &db.dest_amount).with_context(context)?,
+            source_commitment: db
+                .source_commitment
+                .as_deref()
+                .map(|commitment| H256Hex::parse("source_commitment", commitment))
+                .transpose()
+                .with_context(context)?,
             dest_fill_txid: db.dest_fill_txid,
             dest_registration_txid: db.dest_registration_txid,
             source_complete_txid: db.source_complete_txid,
@@ -362,7 +1298,7 @@ impl From<DbIntent> for Intent {
             solver_address: db.solver_address,
             block_number: db.block_number,
             log_index: db.log_index,
-        }
+        })
     }
 }
 
@@ -393,15 +1329,215 @@ impl<'a> From<&'a Intent> for NewIntent<'a> {
     }
 }
 
-impl From<DbIntentPrivacyParams> for IntentPrivacyParams {
-    fn from(db: DbIntentPrivacyParams) -> Self {
-        Self {
-            intent_id: db.intent_id,
-            commitment: db.commitment,
+/// Fails on a row whose id, commitment or addresses are malformed.
+impl TryFrom<DbIntentPrivacyParams> for IntentPrivacyParams {
+    type Error = anyhow::Error;
+
+    fn try_from(db: DbIntentPrivacyParams) -> anyhow::Result<Self> {
+        let context = || format!("Malformed privacy params row {}", db.intent_id);
+        let address = |field, value: Option<String>| {
+            value
+                .map(|value| AddressHex::parse(field, &value))
+                .transpose()
+                .with_context(context)
+        };
+        Ok(Self {
+            intent_id: H256Hex::parse("intent_id", &db.intent_id).with_context(context)?,
+            commitment: db
+                .commitment
+                .as_deref()
+                .map(|commitment| H256Hex::parse("commitment", commitment))
+                .transpose()
+                .with_context(context)?,
             nullifier: db.nullifier,
             secret: db.secret,
-            recipient: db.recipient,
+            recipient: address("recipient", db.recipient)?,
             claim_signature: db.claim_signature,
+            claim_delegate: address("claim_delegate", db.claim_delegate)?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        database::test_support::{random_hash, test_database},
+        models::model::{CommitmentUniqueness, IntentCreatedEvent},
+    };
+    use serial_test::serial;
+
+    #[test]
+    fn test_bridge_event_types_match_check_constraint() {
+        let migration =
+            include_str!("../../migrations/2025-12-22-090000_constrain_bridge_event_types/up.sql");
+
+        for event_type in BridgeEventType::ALL {
+            assert_eq!(
+                event_type.as_str().parse::<BridgeEventType>().unwrap(),
+                event_type
+            );
+            assert_eq!(
+                serde_json::to_value(event_type).unwrap(),
+                event_type.as_str()
+            );
+            assert!(migration.contains(&format!("'{}'", event_type.as_str())));
         }
+
+        assert!("intent_fileld".parse::<BridgeEventType>().is_err());
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_only_later_copies_of_a_commitment_are_quarantined() -> anyhow::Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let commitment = random_hash();
+        let event = |block: u64| IntentCreatedEvent {
+            intent_id: random_hash(),
+            commitment: commitment.clone(),
+            source_token: "0x0000000000000000000000000000000000000001".to_string(),
+            source_amount: "1000".to_string(),
+            dest_token: "0x0000000000000000000000000000000000000002".to_string(),
+            dest_amount: "1000".to_string(),
+            dest_chain: 5003,
+            deadline: Some(u64::MAX / 4),
+            block_number: Some(block),
+            transaction_hash: None,
+            log_index: Some(0),
+        };
+        // Seen out of order: the copy at block 10 reaches the relayer first
+        let copy = event(10);
+        let original = event(5);
+        let on_mantle = event(1);
+        database.upsert_intent_from_event(&copy, "ethereum")?;
+        database.upsert_intent_from_event(&original, "ethereum")?;
+        database.upsert_intent_from_event(&on_mantle, "mantle")?;
+
+        let check = |intent: &IntentCreatedEvent, chain, scope| {
+            database.quarantine_if_duplicate(&intent.intent_id, &commitment, chain, scope)
+        };
+        let global = CommitmentUniqueness::Global;
+        assert_eq!(check(&original, "ethereum", global)?, None);
+        assert_eq!(
+            check(&copy, "ethereum", global)?,
+            Some(original.intent_id.clone())
+        );
+        // Resyncs and redeliveries check every intent again
+        assert_eq!(check(&original, "ethereum", global)?, None);
+        assert_eq!(
+            check(&copy, "ethereum", global)?,
+            Some(original.intent_id.clone())
+        );
+        assert_eq!(
+            check(&on_mantle, "mantle", CommitmentUniqueness::PerTree)?,
+            None
+        );
+        assert_eq!(
+            check(&on_mantle, "mantle", global)?,
+            Some(original.intent_id.clone())
+        );
+
+        let status = |intent: &IntentCreatedEvent| -> anyhow::Result<IntentStatus> {
+            Ok(database
+                .get_intent_by_id(&intent.intent_id)?
+                .unwrap()
+                .status)
+        };
+        assert_ne!(status(&original)?, IntentStatus::Failed);
+        assert_eq!(status(&copy)?, IntentStatus::Failed);
+
+        let duplicates = database.find_duplicate_commitments()?;
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].commitment, commitment.to_lowercase());
+        assert_eq!(duplicates[0].intent_ids.len(), 3);
+        assert_eq!(duplicates[0].chains.len(), 3);
+
+        assert!(CommitmentUniqueness::parse("globel").is_err());
+        assert_eq!(
+            CommitmentUniqueness::parse(" Tree ")?,
+            CommitmentUniqueness::PerTree
+        );
+        assert_eq!(
+            CommitmentUniqueness::parse("")?,
+            CommitmentUniqueness::Global
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_rekey_only_trusts_matching_v1_tags() -> anyhow::Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let params = |secret: &str| IntentPrivacyParams {
+            intent_id: random_hash().parse().unwrap(),
+            commitment: Some(random_hash().parse().unwrap()),
+            nullifier: Some("0xaa".to_string()),
+            secret: Some(secret.to_string()),
+            recipient: None,
+            claim_signature: None,
+            claim_delegate: None,
+        };
+        let intact = params("0xbb");
+        let tampered = params("0xcc");
+        database.store_intent_privacy_params(&intact)?;
+        database.store_intent_privacy_params(&tampered)?;
+
+        // Tags as the add_privacy_context migration wrote them, then a secret
+        // swapped in without touching the tag
+        let mut conn = database.get_connection()?;
+        diesel::sql_query(
+            "UPDATE intent_privacy_params SET context_tag = encode(sha256(convert_to(concat_ws(
+                E'\\n', 'shadow-swap/intent-privacy/v1', intent_id, COALESCE(commitment, ''),
+                COALESCE(nullifier, ''), COALESCE(secret, ''), COALESCE(recipient, ''),
+                COALESCE(claim_signature, '')), 'UTF8')), 'hex')",
+        )
+        .execute(&mut conn)?;
+        diesel::update(
+            intent_privacy_params::table
+                .filter(intent_privacy_params::intent_id.eq(tampered.intent_id.as_str())),
+        )
+        .set(intent_privacy_params::secret.eq("0xee"))
+        .execute(&mut conn)?;
+        drop(conn);
+
+        assert!(database.privacy_contexts_need_rekey()?);
+        assert!(
+            database
+                .get_intent_privacy_params(&intact.intent_id)
+                .is_err()
+        );
+
+        let preview = database.rekey_privacy_contexts(true)?;
+        assert_eq!(preview.rekeyed, 1);
+        assert_eq!(preview.unverified, vec![tampered.intent_id.to_string()]);
+        assert!(
+            database
+                .get_intent_privacy_params(&intact.intent_id)
+                .is_err()
+        );
+
+        database.rekey_privacy_contexts(false)?;
+        assert_eq!(
+            database
+                .get_intent_privacy_params(&intact.intent_id)?
+                .secret,
+            intact.secret
+        );
+        assert!(
+            database
+                .get_intent_privacy_params(&tampered.intent_id)
+                .is_err()
+        );
+
+        // Once only, so a forged v1 tag written later is never trusted
+        assert!(!database.privacy_contexts_need_rekey()?);
+        assert!(database.rekey_privacy_contexts(false).is_err());
+        Ok(())
     }
 }
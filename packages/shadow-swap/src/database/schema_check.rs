@@ -0,0 +1,378 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result, anyhow};
+use diesel::prelude::*;
+use diesel::sql_types::{Nullable, Text};
+use serde::Serialize;
+use tracing::{error, info, warn};
+
+use crate::database::database::Database;
+
+/// The Diesel schema compiled into this binary.
+const EMBEDDED_SCHEMA: &str = include_str!("../models/schema.rs");
+
+// ==================== Expected Schema ====================
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExpectedColumn {
+    pub name: String,
+    /// Postgres `udt_name` the Diesel type maps to.
+    pub udt_name: String,
+    pub nullable: bool,
+}
+
+/// Parse `diesel::table!` blocks into table -> columns.
+pub fn parse_schema(source: &str) -> Result<BTreeMap<String, Vec<ExpectedColumn>>> {
+    let mut tables: BTreeMap<String, Vec<ExpectedColumn>> = BTreeMap::new();
+    let mut current: Option<String> = None;
+
+    for line in source.lines() {
+        let trimmed = line.trim();
+
+        if trimmed == "}" {
+            current = None;
+            continue;
+        }
+
+        if let Some((name, _)) = trimmed.split_once(" (")
+            && trimmed.ends_with('{')
+        {
+            let name = name.trim().to_string();
+            tables.entry(name.clone()).or_default();
+            current = Some(name);
+            continue;
+        }
+
+        let (Some(table), Some((column, sql_type))) = (&current, trimmed.split_once(" -> ")) else {
+            continue;
+        };
+
+        let sql_type = sql_type.trim_end_matches(',');
+        let (inner, nullable) = match sql_type
+            .strip_prefix("Nullable<")
+            .and_then(|s| s.strip_suffix('>'))
+        {
+            Some(inner) => (inner, true),
+            None => (sql_type, false),
+        };
+
+        let udt_name = diesel_to_udt(inner)
+            .ok_or_else(|| anyhow!("Unsupported Diesel type {} on {}.{}", inner, table, column))?;
+
+        tables
+            .entry(table.clone())
+            .or_default()
+            .push(ExpectedColumn {
+                name: column.trim().to_string(),
+                udt_name: udt_name.to_string(),
+                nullable,
+            });
+    }
+
+    Ok(tables)
+}
+
+fn diesel_to_udt(sql_type: &str) -> Option<&'static str> {
+    Some(match sql_type {
+        "Int2" | "SmallInt" => "int2",
+        "Int4" | "Integer" => "int4",
+        "Int8" | "BigInt" => "int8",
+        "Float4" => "float4",
+        "Float8" => "float8",
+        "Numeric" => "numeric",
+        "Text" => "text",
+        "Varchar" => "varchar",
+        "Bool" => "bool",
+        "Jsonb" => "jsonb",
+        "Json" => "json",
+        "Timestamptz" => "timestamptz",
+        "Timestamp" => "timestamp",
+        "Date" => "date",
+        "Uuid" => "uuid",
+        "Bytea" => "bytea",
+        // Postgres names array types after their element with a leading `_`
+        "Array<Text>" => "_text",
+        _ => return None,
+    })
+}
+
+// ==================== Live Schema ====================
+
+#[derive(Debug, Clone, QueryableByName)]
+pub struct LiveColumn {
+    #[diesel(sql_type = Text)]
+    pub table_name: String,
+    #[diesel(sql_type = Text)]
+    pub column_name: String,
+    #[diesel(sql_type = Text)]
+    pub udt_name: String,
+    #[diesel(sql_type = Text)]
+    pub is_nullable: String,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub column_default: Option<String>,
+}
+
+impl Database {
+    pub fn get_live_columns(&self) -> Result<Vec<LiveColumn>> {
+        let mut conn = self.get_connection()?;
+
+        diesel::sql_query(
+            "SELECT table_name::text, column_name::text, udt_name::text, \
+                    is_nullable::text, column_default::text \
+             FROM information_schema.columns \
+             WHERE table_schema = current_schema() \
+             ORDER BY table_name, ordinal_position",
+        )
+        .load::<LiveColumn>(&mut conn)
+        .context("Failed to introspect live schema")
+    }
+}
+
+// ==================== Drift ====================
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SchemaDrift {
+    MissingTable {
+        table: String,
+    },
+    MissingColumn {
+        table: String,
+        column: String,
+    },
+    TypeMismatch {
+        table: String,
+        column: String,
+        expected: String,
+        actual: String,
+    },
+    NullabilityMismatch {
+        table: String,
+        column: String,
+        expected_nullable: bool,
+    },
+    /// Unknown NOT NULL column without a default, so every insert fails.
+    UnexpectedRequiredColumn {
+        table: String,
+        column: String,
+    },
+}
+
+impl std::fmt::Display for SchemaDrift {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingTable { table } => write!(f, "table {} is missing", table),
+            Self::MissingColumn { table, column } => {
+                write!(f, "column {}.{} is missing", table, column)
+            }
+            Self::TypeMismatch {
+                table,
+                column,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "column {}.{} is {} but schema.rs expects {}",
+                table, column, actual, expected
+            ),
+            Self::NullabilityMismatch {
+                table,
+                column,
+                expected_nullable,
+            } => write!(
+                f,
+                "column {}.{} should be {}",
+                table,
+                column,
+                if *expected_nullable {
+                    "NULL"
+                } else {
+                    "NOT NULL"
+                }
+            ),
+            Self::UnexpectedRequiredColumn { table, column } => write!(
+                f,
+                "column {}.{} is NOT NULL without default and unknown to schema.rs",
+                table, column
+            ),
+        }
+    }
+}
+
+pub fn compare_schema(
+    expected: &BTreeMap<String, Vec<ExpectedColumn>>,
+    live: &[LiveColumn],
+) -> Vec<SchemaDrift> {
+    let mut live_tables: BTreeMap<&str, Vec<&LiveColumn>> = BTreeMap::new();
+    for column in live {
+        live_tables
+            .entry(column.table_name.as_str())
+            .or_default()
+            .push(column);
+    }
+
+    let mut drift = Vec::new();
+
+    for (table, columns) in expected {
+        let Some(live_columns) = live_tables.get(table.as_str()) else {
+            drift.push(SchemaDrift::MissingTable {
+                table: table.clone(),
+            });
+            continue;
+        };
+
+        for column in columns {
+            let Some(actual) = live_columns.iter().find(|c| c.column_name == column.name) else {
+                drift.push(SchemaDrift::MissingColumn {
+                    table: table.clone(),
+                    column: column.name.clone(),
+                });
+                continue;
+            };
+
+            if actual.udt_name != column.udt_name {
+                drift.push(SchemaDrift::TypeMismatch {
+                    table: table.clone(),
+                    column: column.name.clone(),
+                    expected: column.udt_name.clone(),
+                    actual: actual.udt_name.clone(),
+                });
+            }
+
+            if (actual.is_nullable == "YES") != column.nullable {
+                drift.push(SchemaDrift::NullabilityMismatch {
+                    table: table.clone(),
+                    column: column.name.clone(),
+                    expected_nullable: column.nullable,
+                });
+            }
+        }
+
+        for actual in live_columns {
+            let known = columns.iter().any(|c| c.name == actual.column_name);
+            if !known && actual.is_nullable == "NO" && actual.column_default.is_none() {
+                drift.push(SchemaDrift::UnexpectedRequiredColumn {
+                    table: table.clone(),
+                    column: actual.column_name.clone(),
+                });
+            }
+        }
+    }
+
+    drift
+}
+
+// ==================== Startup Check ====================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaCheckMode {
+    Off,
+    Warn,
+    Strict,
+}
+
+impl SchemaCheckMode {
+    pub fn from_env() -> Self {
+        match std::env::var("SCHEMA_DRIFT_MODE")
+            .unwrap_or_else(|_| "warn".to_string())
+            .to_lowercase()
+            .as_str()
+        {
+            "off" => Self::Off,
+            "strict" => Self::Strict,
+            _ => Self::Warn,
+        }
+    }
+}
+
+/// Compare the live database against the embedded schema.rs. In strict mode
+/// any drift is returned as an error so the relayer refuses to start.
+pub fn check_schema_drift(database: &Database, mode: SchemaCheckMode) -> Result<Vec<SchemaDrift>> {
+    if mode == SchemaCheckMode::Off {
+        return Ok(Vec::new());
+    }
+
+    info!("🧬 Checking live schema against schema.rs");
+
+    let expected = parse_schema(EMBEDDED_SCHEMA)?;
+    let live = database.get_live_columns()?;
+    let drift = compare_schema(&expected, &live);
+
+    if drift.is_empty() {
+        info!("✅ No schema drift across {} tables", expected.len());
+        return Ok(drift);
+    }
+
+    for item in &drift {
+        match mode {
+            SchemaCheckMode::Strict => error!("🧬 Schema drift: {}", item),
+            _ => warn!("🧬 Schema drift: {}", item),
+        }
+    }
+
+    if mode == SchemaCheckMode::Strict {
+        return Err(anyhow!(
+            "Detected {} schema drift issue(s), refusing to start (SCHEMA_DRIFT_MODE=strict)",
+            drift.len()
+        ));
+    }
+
+    Ok(drift)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn live(table: &str, column: &str, udt: &str, nullable: bool) -> LiveColumn {
+        LiveColumn {
+            table_name: table.to_string(),
+            column_name: column.to_string(),
+            udt_name: udt.to_string(),
+            is_nullable: if nullable { "YES" } else { "NO" }.to_string(),
+            column_default: None,
+        }
+    }
+
+    #[test]
+    fn test_embedded_schema_parses() {
+        let tables = parse_schema(EMBEDDED_SCHEMA).unwrap();
+        let intents = tables.get("intents").expect("intents table");
+
+        assert!(
+            intents
+                .iter()
+                .any(|c| c.name == "id" && c.udt_name == "text" && !c.nullable)
+        );
+        assert!(tables.contains_key("bridge_events"));
+    }
+
+    #[test]
+    fn test_detects_drift() {
+        let source = "diesel::table! {\n    t (id) {\n        id -> Int4,\n        note -> Nullable<Text>,\n        amount -> Text,\n    }\n}\n";
+        let expected = parse_schema(source).unwrap();
+
+        let live_columns = vec![
+            live("t", "id", "int8", false),
+            live("t", "note", "text", false),
+            live("t", "hotfix", "text", false),
+        ];
+
+        let drift = compare_schema(&expected, &live_columns);
+
+        assert_eq!(drift.len(), 4);
+        assert!(drift.contains(&SchemaDrift::MissingColumn {
+            table: "t".into(),
+            column: "amount".into()
+        }));
+        assert!(
+            drift
+                .iter()
+                .any(|d| matches!(d, SchemaDrift::TypeMismatch { actual, .. } if actual == "int8"))
+        );
+        assert!(drift.contains(&SchemaDrift::UnexpectedRequiredColumn {
+            table: "t".into(),
+            column: "hotfix".into()
+        }));
+    }
+}
@@ -0,0 +1,351 @@
+//! Fixtures for tests that touch the database.
+//!
+//! Tests that need Postgres call [`test_database`], which returns `None` when
+//! `TEST_DATABASE_URL` is unset so `cargo test` keeps working without one.
+//! `make backend-test-db` starts a throwaway container and sets the variable.
+//! Tests sharing the database must be marked `#[serial(db)]`.
+
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use diesel::{QueryableByName, RunQueryDsl, sql_query, sql_types::Text};
+use ethers::{types::U256, utils::parse_units};
+use mantle_core::{chain::Chain, token::TokenType};
+use uuid::Uuid;
+
+use crate::{
+    database::database::Database,
+    encryption::privacy_context::PrivacyContextKey,
+    models::model::{Intent, IntentStatus},
+};
+
+/// A migrated, empty database from `TEST_DATABASE_URL`, or `None` when unset.
+pub fn test_database() -> Result<Option<Database>> {
+    let Ok(url) = std::env::var("TEST_DATABASE_URL") else {
+        return Ok(None);
+    };
+
+    let database = Database::new(&url, 4)?
+        .with_privacy_key(PrivacyContextKey::from_secret("test-privacy-context-key"));
+    Database::run_migrations(&database.pool).context("Failed to migrate test database")?;
+    truncate_all(&database)?;
+    Ok(Some(database))
+}
+
+#[derive(QueryableByName)]
+struct TableName {
+    #[diesel(sql_type = Text)]
+    tablename: String,
+}
+
+/// Empty every table except diesel's migration bookkeeping. The table list is
+/// read from the catalog so new migrations are covered without changes here.
+pub fn truncate_all(database: &Database) -> Result<()> {
+    let mut conn = database.get_connection()?;
+
+    let tables = sql_query(
+        "SELECT tablename FROM pg_tables \
+         WHERE schemaname = 'public' AND tablename <> '__diesel_schema_migrations'",
+    )
+    .load::<TableName>(&mut conn)
+    .context("Failed to list tables")?;
+    if tables.is_empty() {
+        return Ok(());
+    }
+
+    let names = tables
+        .iter()
+        .map(|t| format!("\"{}\"", t.tablename))
+        .collect::<Vec<_>>()
+        .join(", ");
+    sql_query(format!("TRUNCATE {} RESTART IDENTITY CASCADE", names))
+        .execute(&mut conn)
+        .context("Failed to truncate tables")?;
+
+    Ok(())
+}
+
+/// Random 32-byte hex id, the shape of intent ids, commitments and tx hashes.
+pub fn random_hash() -> String {
+    format!("0x{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple())
+}
+
+/// Builds [`Intent`]s whose fields agree with their status: a committed
+/// intent has a commitment, a filled one a fill tx and solver, and so on.
+///
+/// `IntentFactory::created().on_mantle().with_amount("250").insert(&db)?`
+#[derive(Debug, Clone)]
+pub struct IntentFactory {
+    intent: Intent,
+    token: TokenType,
+}
+
+impl IntentFactory {
+    fn new(status: IntentStatus) -> Self {
+        let now = Utc::now();
+        let user = format!("{:?}", ethers::types::Address::random());
+        let mut factory = Self {
+            intent: Intent {
+                id: random_hash().parse().unwrap(),
+                user_address: user.clone(),
+                source_chain: String::new(),
+                dest_chain: String::new(),
+                source_token: String::new(),
+                dest_token: String::new(),
+                amount: "0".parse().unwrap(),
+                dest_amount: "0".parse().unwrap(),
+                source_commitment: None,
+                dest_fill_txid: None,
+                dest_registration_txid: None,
+                source_complete_txid: None,
+                status,
+                created_at: now,
+                updated_at: now,
+                deadline: (now + Duration::hours(1)).timestamp() as u64,
+                refund_address: Some(user),
+                solver_address: None,
+                block_number: Some(1),
+                log_index: Some(0),
+            },
+            token: TokenType::USDC,
+        }
+        .on_ethereum()
+        .with_amount("100");
+
+        // How far through the pipeline the status is
+        let stage = match status {
+            IntentStatus::Created => 0,
+            IntentStatus::Committed
+            | IntentStatus::Pending
+            | IntentStatus::Refunded
+            | IntentStatus::Failed
+            | IntentStatus::Expired
+            | IntentStatus::Underpaid => 1,
+            IntentStatus::Registered => 2,
+            IntentStatus::Filled => 3,
+            IntentStatus::SolverPaid | IntentStatus::UserClaimed => 4,
+        };
+        if stage >= 1 {
+            factory.intent.source_commitment = Some(random_hash().parse().unwrap());
+        }
+        if stage >= 2 {
+            factory.intent.dest_registration_txid = Some(random_hash());
+        }
+        if stage >= 3 {
+            factory.intent.dest_fill_txid = Some(random_hash());
+            factory.intent.solver_address = Some(format!("{:?}", ethers::types::Address::random()));
+        }
+        if stage >= 4 {
+            factory.intent.source_complete_txid = Some(random_hash());
+        }
+        if matches!(status, IntentStatus::Refunded | IntentStatus::Expired) {
+            factory = factory.expired();
+        }
+        factory
+    }
+
+    pub fn created() -> Self {
+        Self::new(IntentStatus::Created)
+    }
+
+    pub fn committed() -> Self {
+        Self::new(IntentStatus::Committed)
+    }
+
+    pub fn registered() -> Self {
+        Self::new(IntentStatus::Registered)
+    }
+
+    pub fn filled() -> Self {
+        Self::new(IntentStatus::Filled)
+    }
+
+    pub fn solver_paid() -> Self {
+        Self::new(IntentStatus::SolverPaid)
+    }
+
+    pub fn refunded() -> Self {
+        Self::new(IntentStatus::Refunded)
+    }
+
+    /// Bridges from `source` to the other chain.
+    pub fn source(mut self, source: Chain) -> Self {
+        self.intent.source_chain = source.name().to_string();
+        self.intent.dest_chain = source.counterpart().name().to_string();
+        let token = self.token;
+        self.with_token(token)
+    }
+
+    pub fn on_ethereum(self) -> Self {
+        self.source(Chain::Ethereum)
+    }
+
+    pub fn on_mantle(self) -> Self {
+        self.source(Chain::Mantle)
+    }
+
+    /// Same token on both sides, at its address on each chain.
+    pub fn with_token(mut self, token: TokenType) -> Self {
+        let source = Chain::parse(&self.intent.source_chain).unwrap_or(Chain::Ethereum);
+        let dest = Chain::parse(&self.intent.dest_chain).unwrap_or(Chain::Mantle);
+        // Keep the amount in whole tokens when the decimals change
+        let whole = ethers::utils::format_units(
+            ethers::types::U256::from_dec_str(&self.intent.amount).unwrap_or_default(),
+            self.token.decimals() as u32,
+        )
+        .unwrap_or_default();
+        let whole = match whole.trim_end_matches('0').trim_end_matches('.') {
+            "" => "0".to_string(),
+            trimmed => trimmed.to_string(),
+        };
+
+        self.token = token;
+        self.intent.source_token = token.address_on(source).to_lowercase();
+        self.intent.dest_token = token.address_on(dest).to_lowercase();
+        if self.intent.amount.is_empty() {
+            self
+        } else {
+            self.with_amount(&whole)
+        }
+    }
+
+    /// Amount in whole tokens, e.g. `"250"` or `"0.5"`. The destination
+    /// amount is the same, fees aside.
+    pub fn with_amount(mut self, whole_tokens: &str) -> Self {
+        let amount =
+            parse_units(whole_tokens, self.token.decimals() as u32).expect("Invalid test amount");
+        self.intent.amount = U256::from(amount).into();
+        self.intent.dest_amount = U256::from(amount).into();
+        self
+    }
+
+    pub fn with_id(mut self, id: &str) -> Self {
+        self.intent.id = id.parse().expect("Invalid test intent id");
+        self
+    }
+
+    pub fn with_user(mut self, user: &str) -> Self {
+        self.intent.user_address = user.to_string();
+        self.intent.refund_address = Some(user.to_string());
+        self
+    }
+
+    pub fn with_solver(mut self, solver: &str) -> Self {
+        self.intent.solver_address = Some(solver.to_string());
+        self
+    }
+
+    pub fn deadline_in(mut self, duration: Duration) -> Self {
+        self.intent.deadline = (Utc::now() + duration).timestamp() as u64;
+        self
+    }
+
+    /// Deadline an hour in the past.
+    pub fn expired(self) -> Self {
+        self.deadline_in(Duration::hours(-1))
+    }
+
+    /// Shifts both timestamps, for tests of age-based queries.
+    pub fn updated_ago(mut self, age: Duration) -> Self {
+        self.intent.updated_at = Utc::now() - age;
+        self.intent.created_at = self.intent.created_at.min(self.intent.updated_at);
+        self
+    }
+
+    pub fn build(self) -> Intent {
+        self.intent
+    }
+
+    pub fn insert(self, database: &Database) -> Result<Intent> {
+        let intent = self.build();
+        database.create_intent(&intent)?;
+        Ok(intent)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::schema_check::{SchemaCheckMode, check_schema_drift};
+    use serial_test::serial;
+
+    #[test]
+    fn test_factory_keeps_fields_consistent() {
+        let intent = IntentFactory::created()
+            .on_mantle()
+            .with_amount("250")
+            .build();
+        assert_eq!(intent.status, IntentStatus::Created);
+        assert_eq!(intent.source_chain, "mantle");
+        assert_eq!(intent.dest_chain, "ethereum");
+        assert_eq!(intent.amount, "250000000");
+        assert!(intent.source_commitment.is_none());
+
+        let filled = IntentFactory::filled()
+            .with_amount("0.5")
+            .with_token(TokenType::WETH)
+            .build();
+        assert_eq!(filled.amount, "500000000000000000");
+        assert_eq!(
+            filled.dest_token,
+            TokenType::WETH.address_on(Chain::Mantle).to_lowercase()
+        );
+        assert!(filled.dest_fill_txid.is_some() && filled.solver_address.is_some());
+        assert!(filled.source_complete_txid.is_none());
+
+        let refunded = IntentFactory::refunded().build();
+        assert!(refunded.deadline < Utc::now().timestamp() as u64);
+        assert_ne!(refunded.id, IntentFactory::refunded().build().id);
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_migrations_match_schema() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let drift = check_schema_drift(&database, SchemaCheckMode::Warn)?;
+        assert!(
+            drift.is_empty(),
+            "migrations disagree with schema.rs: {}",
+            drift
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+        Ok(())
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_database_round_trip() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let fresh = IntentFactory::refunded().on_mantle().insert(&database)?;
+        IntentFactory::refunded()
+            .updated_ago(Duration::days(10))
+            .insert(&database)?;
+        IntentFactory::filled().insert(&database)?;
+
+        let stored = database
+            .get_intent_by_id(&fresh.id)?
+            .expect("inserted intent");
+        assert_eq!(stored.status, IntentStatus::Refunded);
+        assert_eq!(stored.amount, fresh.amount);
+        assert_eq!(stored.source_chain, "mantle");
+
+        let recent = database
+            .get_intents_updated_since(IntentStatus::Refunded, Utc::now() - Duration::days(1))?;
+        assert_eq!(
+            recent.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(),
+            [fresh.id.as_str()]
+        );
+
+        truncate_all(&database)?;
+        assert!(database.get_intent_by_id(&fresh.id)?.is_none());
+        Ok(())
+    }
+}
@@ -0,0 +1,584 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Stdio,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Context, Result, anyhow, bail};
+use ethers::{
+    abi::{Abi, Tokenize},
+    contract::{Contract, ContractFactory},
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, Bytes, H256, U256},
+};
+use mantle_core::{
+    chain::Chain,
+    token::{NATIVE_TOKEN, TokenType},
+};
+use serde::Deserialize;
+use tokio::{
+    process::{Child, Command},
+    time::sleep,
+};
+use tracing::{info, warn};
+
+type DevnetClient = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+/// Anvil's default dev accounts. The keys are public; never fund them on a
+/// real network.
+struct DevAccount {
+    address: &'static str,
+    key: &'static str,
+}
+
+const DEPLOYER: DevAccount = DevAccount {
+    address: "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266",
+    key: "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80",
+};
+const SOLVER: DevAccount = DevAccount {
+    address: "0x70997970C51812dc3A010C7d01b50e0d17dc79C8",
+    key: "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+};
+const USER: DevAccount = DevAccount {
+    address: "0x3C44CdDdB6a900fa2b585dd299e03d12FA4293BC",
+    key: "0x5de4111afa1a4b94908f83103eb1d1706367c2e68ca870fc3fb9d8929b4b919a",
+};
+const FEE_COLLECTOR: &str = "0x90F79bf6EB2c4f870365E785982E1f101E93b906";
+
+/// Whole tokens minted to the user and the solver per ERC20.
+const SEED_TOKENS: u64 = 1_000_000;
+/// Storage slot of `MockToken._decimals`, after OpenZeppelin's ERC20 fields.
+const MOCK_DECIMALS_SLOT: u64 = 5;
+
+#[derive(Debug, Clone)]
+pub struct DevnetPolicy {
+    pub anvil_bin: String,
+    /// Forge build output of `packages/contracts`.
+    pub artifacts_dir: PathBuf,
+    pub ethereum_port: u16,
+    pub mantle_port: u16,
+    pub solver_enabled: bool,
+    pub solver_bin: String,
+}
+
+impl Default for DevnetPolicy {
+    fn default() -> Self {
+        Self {
+            anvil_bin: "anvil".to_string(),
+            artifacts_dir: PathBuf::from("../contracts/out"),
+            ethereum_port: 8545,
+            mantle_port: 8546,
+            solver_enabled: true,
+            solver_bin: "solver".to_string(),
+        }
+    }
+}
+
+impl DevnetPolicy {
+    /// Reads `DEVNET_ANVIL_BIN`, `DEVNET_ARTIFACTS_DIR`, `DEVNET_ETHEREUM_PORT`,
+    /// `DEVNET_MANTLE_PORT`, `DEVNET_SOLVER_ENABLED` and `DEVNET_SOLVER_BIN`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(bin) = std::env::var("DEVNET_ANVIL_BIN") {
+            policy.anvil_bin = bin;
+        }
+        if let Ok(dir) = std::env::var("DEVNET_ARTIFACTS_DIR") {
+            policy.artifacts_dir = PathBuf::from(dir);
+        }
+        if let Ok(port) = std::env::var("DEVNET_ETHEREUM_PORT") {
+            policy.ethereum_port = port.parse().context("Invalid DEVNET_ETHEREUM_PORT")?;
+        }
+        if let Ok(port) = std::env::var("DEVNET_MANTLE_PORT") {
+            policy.mantle_port = port.parse().context("Invalid DEVNET_MANTLE_PORT")?;
+        }
+        if policy.ethereum_port == policy.mantle_port {
+            bail!("DEVNET_ETHEREUM_PORT and DEVNET_MANTLE_PORT must differ");
+        }
+        if let Ok(enabled) = std::env::var("DEVNET_SOLVER_ENABLED") {
+            policy.solver_enabled = enabled.parse().context("Invalid DEVNET_SOLVER_ENABLED")?;
+        }
+        if let Ok(bin) = std::env::var("DEVNET_SOLVER_BIN") {
+            policy.solver_bin = bin;
+        }
+
+        Ok(policy)
+    }
+
+    fn port(&self, chain: Chain) -> u16 {
+        match chain {
+            Chain::Ethereum => self.ethereum_port,
+            Chain::Mantle => self.mantle_port,
+        }
+    }
+}
+
+// ============================================================================
+// ARTIFACTS
+// ============================================================================
+
+#[derive(Deserialize)]
+struct ArtifactBytecode {
+    object: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ArtifactFile {
+    abi: Abi,
+    bytecode: ArtifactBytecode,
+    deployed_bytecode: ArtifactBytecode,
+}
+
+/// ABI and bytecode of one contract from `forge build`.
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    pub abi: Abi,
+    pub bytecode: Bytes,
+    pub deployed_bytecode: Bytes,
+}
+
+impl Artifact {
+    /// Reads `<dir>/<name>.sol/<name>.json`.
+    pub fn load(dir: &Path, name: &str) -> Result<Self> {
+        let path = dir.join(format!("{name}.sol")).join(format!("{name}.json"));
+        let raw = std::fs::read_to_string(&path).with_context(|| {
+            format!(
+                "Missing {} (run `forge build` in packages/contracts or set DEVNET_ARTIFACTS_DIR)",
+                path.display()
+            )
+        })?;
+        let file: ArtifactFile = serde_json::from_str(&raw)
+            .with_context(|| format!("Invalid artifact {}", path.display()))?;
+
+        let code = |object: &str, kind: &str| -> Result<Bytes> {
+            if object.contains("__$") {
+                bail!("{} {} has unlinked libraries", name, kind);
+            }
+            object
+                .parse()
+                .with_context(|| format!("Invalid {} {}", name, kind))
+        };
+
+        Ok(Self {
+            abi: file.abi,
+            bytecode: code(&file.bytecode.object, "bytecode")?,
+            deployed_bytecode: code(&file.deployed_bytecode.object, "deployed bytecode")?,
+        })
+    }
+
+    async fn deploy<T: Tokenize>(
+        &self,
+        client: Arc<DevnetClient>,
+        args: T,
+    ) -> Result<Contract<DevnetClient>> {
+        ContractFactory::new(self.abi.clone(), self.bytecode.clone(), client)
+            .deploy(args)?
+            .send()
+            .await
+            .map_err(|e| anyhow!("Deployment failed: {}", e))
+    }
+}
+
+struct Artifacts {
+    poseidon: Artifact,
+    intent_pool: Artifact,
+    settlement: Artifact,
+    mock_token: Artifact,
+}
+
+impl Artifacts {
+    fn load(dir: &Path) -> Result<Self> {
+        Ok(Self {
+            poseidon: Artifact::load(dir, "PoseidonHasher")?,
+            intent_pool: Artifact::load(dir, "PrivateIntentPool")?,
+            settlement: Artifact::load(dir, "PrivateSettlement")?,
+            mock_token: Artifact::load(dir, "MockToken")?,
+        })
+    }
+}
+
+// ============================================================================
+// CHAINS
+// ============================================================================
+
+/// One local chain with the bridge contracts deployed.
+#[derive(Debug, Clone)]
+pub struct ChainDeployment {
+    pub chain: Chain,
+    pub port: u16,
+    pub intent_pool: Address,
+    pub settlement: Address,
+}
+
+impl ChainDeployment {
+    fn rpc_url(&self) -> String {
+        format!("http://127.0.0.1:{}", self.port)
+    }
+
+    fn ws_url(&self) -> String {
+        format!("ws://127.0.0.1:{}", self.port)
+    }
+}
+
+fn spawn_node(policy: &DevnetPolicy, chain: Chain) -> Result<Child> {
+    Command::new(&policy.anvil_bin)
+        .args(["--silent", "--port"])
+        .arg(policy.port(chain).to_string())
+        .arg("--chain-id")
+        .arg(chain.id().to_string())
+        .stdout(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| {
+            format!(
+                "Failed to start {} (install Foundry or set DEVNET_ANVIL_BIN)",
+                policy.anvil_bin
+            )
+        })
+}
+
+async fn connect(chain: Chain, port: u16) -> Result<Arc<DevnetClient>> {
+    let provider = Provider::<Http>::try_from(format!("http://127.0.0.1:{}", port))?
+        .interval(Duration::from_millis(100));
+
+    for _ in 0..50 {
+        if provider.get_chainid().await.is_ok() {
+            let wallet = DEPLOYER
+                .key
+                .parse::<LocalWallet>()?
+                .with_chain_id(chain.id());
+            return Ok(Arc::new(SignerMiddleware::new(provider, wallet)));
+        }
+        sleep(Duration::from_millis(200)).await;
+    }
+
+    Err(anyhow!(
+        "{} devnet node on port {} did not start",
+        chain.name(),
+        port
+    ))
+}
+
+async fn add_supported_token(
+    contract: &Contract<DevnetClient>,
+    token: Address,
+    decimals: u8,
+) -> Result<()> {
+    let unit = U256::exp10(decimals as usize);
+    contract
+        .method::<_, ()>(
+            "addSupportedToken",
+            (token, unit / 100, unit * SEED_TOKENS, U256::from(decimals)),
+        )?
+        .send()
+        .await?
+        .await?;
+    Ok(())
+}
+
+/// Put a `MockToken` at the address the relayer knows `token` by on `chain`,
+/// then mint to the user and the solver.
+async fn seed_token(
+    client: &Arc<DevnetClient>,
+    artifacts: &Artifacts,
+    chain: Chain,
+    token: TokenType,
+) -> Result<Address> {
+    let address: Address = token.address_on(chain).parse()?;
+    let provider = client.provider();
+
+    provider
+        .request::<_, ()>(
+            "anvil_setCode",
+            (address, artifacts.mock_token.deployed_bytecode.clone()),
+        )
+        .await?;
+    provider
+        .request::<_, bool>(
+            "anvil_setStorageAt",
+            (
+                address,
+                H256::from_low_u64_be(MOCK_DECIMALS_SLOT),
+                H256::from_low_u64_be(token.decimals().into()),
+            ),
+        )
+        .await?;
+
+    let mock = Contract::new(address, artifacts.mock_token.abi.clone(), client.clone());
+    let amount = U256::exp10(token.decimals() as usize) * SEED_TOKENS;
+    for holder in [USER.address, SOLVER.address] {
+        mock.method::<_, ()>("mint", (holder.parse::<Address>()?, amount))?
+            .send()
+            .await?
+            .await?;
+    }
+
+    Ok(address)
+}
+
+async fn deploy_chain(
+    policy: &DevnetPolicy,
+    artifacts: &Artifacts,
+    chain: Chain,
+) -> Result<ChainDeployment> {
+    let port = policy.port(chain);
+    let client = connect(chain, port).await?;
+    let owner: Address = DEPLOYER.address.parse()?;
+    let fee_collector: Address = FEE_COLLECTOR.parse()?;
+
+    let poseidon = artifacts.poseidon.deploy(client.clone(), ()).await?;
+    let args = (owner, owner, fee_collector, poseidon.address());
+    let intent_pool = artifacts.intent_pool.deploy(client.clone(), args).await?;
+    let settlement = artifacts.settlement.deploy(client.clone(), args).await?;
+
+    for token in TokenType::ALL {
+        let address = if token.address_on(chain) == NATIVE_TOKEN {
+            NATIVE_TOKEN.parse()?
+        } else {
+            seed_token(&client, artifacts, chain, token).await?
+        };
+        add_supported_token(&intent_pool, address, token.decimals()).await?;
+        add_supported_token(&settlement, address, token.decimals()).await?;
+    }
+
+    info!(
+        "🧪 [{}] Devnet on :{} (IntentPool {:?}, Settlement {:?})",
+        chain.name(),
+        port,
+        intent_pool.address(),
+        settlement.address()
+    );
+
+    Ok(ChainDeployment {
+        chain,
+        port,
+        intent_pool: intent_pool.address(),
+        settlement: settlement.address(),
+    })
+}
+
+// ============================================================================
+// PROCESSES
+// ============================================================================
+
+/// Settings handed to `shadow-swap serve`. The database and HMAC secret come
+/// from the caller's environment.
+fn relayer_env(chains: &[ChainDeployment]) -> Vec<(String, String)> {
+    let mut env = vec![
+        ("RELAYER_ADDRESS".to_string(), DEPLOYER.address.to_string()),
+        ("FEE_COLLECTOR".to_string(), FEE_COLLECTOR.to_string()),
+        ("SYNC_ON_STARTUP".to_string(), "true".to_string()),
+    ];
+    for deployment in chains {
+        let prefix = deployment.chain.name().to_uppercase();
+        env.extend([
+            (format!("{prefix}_RPC_URL"), deployment.rpc_url()),
+            (format!("{prefix}_WS_URL"), deployment.ws_url()),
+            (format!("{prefix}_PRIVATE_KEY"), DEPLOYER.key.to_string()),
+            (
+                format!("{prefix}_CHAIN_ID"),
+                deployment.chain.id().to_string(),
+            ),
+            (
+                format!("{prefix}_INTENT_POOL_ADDRESS"),
+                format!("{:?}", deployment.intent_pool),
+            ),
+            (
+                format!("{prefix}_SETTLEMENT_ADDRESS"),
+                format!("{:?}", deployment.settlement),
+            ),
+            (format!("{prefix}_SYNC_FROM_BLOCK"), "0".to_string()),
+        ]);
+    }
+    env
+}
+
+fn solver_env(chains: &[ChainDeployment]) -> Vec<(String, String)> {
+    let mut env = vec![
+        ("SOLVER_ADDRESS".to_string(), SOLVER.address.to_string()),
+        ("SOLVER_PRIVATE_KEY".to_string(), SOLVER.key.to_string()),
+    ];
+    for deployment in chains {
+        let prefix = deployment.chain.name().to_uppercase();
+        env.extend([
+            (format!("{prefix}_WS_RPC"), deployment.ws_url()),
+            (
+                format!("{prefix}_INTENT_POOL"),
+                format!("{:?}", deployment.intent_pool),
+            ),
+            (
+                format!("{prefix}_SETTLEMENT"),
+                format!("{:?}", deployment.settlement),
+            ),
+        ]);
+    }
+    env
+}
+
+fn spawn_child(program: &Path, args: &[&str], env: Vec<(String, String)>) -> Result<Child> {
+    Command::new(program)
+        .args(args)
+        .envs(env)
+        // Devnet keys are local; a signing service or profile would point elsewhere
+        .env_remove("SIGNER_SOCKET")
+        .env_remove("BRIDGE_PROFILE")
+        .env_remove("NETWORK")
+        .kill_on_drop(true)
+        .spawn()
+        .with_context(|| format!("Failed to start {}", program.display()))
+}
+
+/// Start both chains, deploy and seed them, then run the relayer (and a
+/// solver) against them until one exits or Ctrl-C.
+pub async fn run(policy: DevnetPolicy) -> Result<()> {
+    info!("🧪 Starting devnet");
+    let artifacts = Artifacts::load(&policy.artifacts_dir)?;
+
+    // Dropped on return, which stops every child
+    let _nodes = Chain::ALL
+        .iter()
+        .map(|chain| spawn_node(&policy, *chain))
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut chains = Vec::new();
+    for chain in Chain::ALL {
+        chains.push(
+            deploy_chain(&policy, &artifacts, chain)
+                .await
+                .with_context(|| format!("Failed to set up {} devnet", chain.name()))?,
+        );
+    }
+
+    let relayer_bin = std::env::current_exe().context("Failed to locate relayer binary")?;
+    let mut relayer = spawn_child(&relayer_bin, &["serve"], relayer_env(&chains))?;
+    let mut solver = if policy.solver_enabled {
+        Some(
+            spawn_child(Path::new(&policy.solver_bin), &[], solver_env(&chains)).context(
+                "Build the solver with `cargo build -p solver` or set DEVNET_SOLVER_BIN",
+            )?,
+        )
+    } else {
+        None
+    };
+
+    info!("🧪 Devnet ready");
+    info!("   User:   {} (key {})", USER.address, USER.key);
+    info!("   Solver: {}", SOLVER.address);
+    for deployment in &chains {
+        info!("   {}: {}", deployment.chain.name(), deployment.rpc_url());
+    }
+
+    let solver_exit = async {
+        match solver.as_mut() {
+            Some(solver) => solver.wait().await,
+            None => std::future::pending().await,
+        }
+    };
+
+    tokio::select! {
+        status = relayer.wait() => Err(anyhow!("Relayer exited ({})", status?)),
+        status = solver_exit => Err(anyhow!("Solver exited ({})", status?)),
+        _ = tokio::signal::ctrl_c() => {
+            warn!("🧪 Stopping devnet");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_artifact_load_rejects_unlinked_bytecode() -> Result<()> {
+        let dir = std::env::temp_dir().join(format!("devnet-artifacts-{}", std::process::id()));
+        let write = |name: &str, bytecode: &str| -> Result<()> {
+            std::fs::create_dir_all(dir.join(format!("{name}.sol")))?;
+            std::fs::write(
+                dir.join(format!("{name}.sol")).join(format!("{name}.json")),
+                serde_json::json!({
+                    "abi": [],
+                    "bytecode": { "object": bytecode },
+                    "deployedBytecode": { "object": "0x6000" },
+                })
+                .to_string(),
+            )?;
+            Ok(())
+        };
+        write("Plain", "0x600060005260206000f3")?;
+        write("Linked", "0x73__$abc$__")?;
+
+        let plain = Artifact::load(&dir, "Plain")?;
+        assert_eq!(plain.bytecode.len(), 10);
+        assert_eq!(plain.deployed_bytecode.to_vec(), vec![0x60, 0x00]);
+        assert!(Artifact::load(&dir, "Linked").is_err());
+        assert!(Artifact::load(&dir, "Missing").is_err());
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_child_env_points_at_local_chains() {
+        let chains: Vec<ChainDeployment> = Chain::ALL
+            .iter()
+            .enumerate()
+            .map(|(i, chain)| ChainDeployment {
+                chain: *chain,
+                port: 8545 + i as u16,
+                intent_pool: Address::from_low_u64_be(1 + i as u64),
+                settlement: Address::from_low_u64_be(10 + i as u64),
+            })
+            .collect();
+        let lookup = |env: &[(String, String)], key: &str| {
+            env.iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.clone())
+                .unwrap_or_default()
+        };
+
+        let relayer = relayer_env(&chains);
+        assert_eq!(lookup(&relayer, "MANTLE_RPC_URL"), "http://127.0.0.1:8546");
+        assert_eq!(lookup(&relayer, "ETHEREUM_CHAIN_ID"), "11155111");
+        assert_eq!(
+            lookup(&relayer, "MANTLE_SETTLEMENT_ADDRESS"),
+            format!("{:?}", Address::from_low_u64_be(11))
+        );
+        assert_eq!(lookup(&relayer, "RELAYER_ADDRESS"), DEPLOYER.address);
+
+        let solver = solver_env(&chains);
+        assert_eq!(lookup(&solver, "ETHEREUM_WS_RPC"), "ws://127.0.0.1:8545");
+        assert_eq!(
+            lookup(&solver, "ETHEREUM_INTENT_POOL"),
+            format!("{:?}", Address::from_low_u64_be(1))
+        );
+        assert_eq!(lookup(&solver, "SOLVER_PRIVATE_KEY"), SOLVER.key);
+    }
+
+    #[test]
+    fn test_local_anvil_profile_matches_devnet_deploy_order() {
+        let profile = crate::config::profile::NetworkProfile::builtin("local-anvil").unwrap();
+        let deployer: Address = DEPLOYER.address.parse().unwrap();
+        let deployed =
+            |nonce: u64| format!("{:?}", ethers::utils::get_contract_address(deployer, nonce));
+
+        // Poseidon, IntentPool, Settlement
+        assert!(
+            profile
+                .ethereum
+                .intent_pool_address
+                .eq_ignore_ascii_case(&deployed(1))
+        );
+        assert!(
+            profile
+                .mantle
+                .settlement_address
+                .eq_ignore_ascii_case(&deployed(2))
+        );
+        assert_eq!(profile.ethereum.chain_id, Chain::Ethereum.id());
+    }
+}
@@ -1 +1,2 @@
 pub mod encryption_utils;
+pub mod privacy_context;
@@ -0,0 +1,208 @@
+use anyhow::{Result, anyhow};
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+use crate::models::model::IntentPrivacyParams;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Domain separator and version of the context tag.
+const CONTEXT_DOMAIN: &str = "shadow-swap/intent-privacy/v2";
+
+/// Domain of the unkeyed v1 digest written by the `add_privacy_context`
+/// migration and the relayer before tags were keyed.
+const LEGACY_CONTEXT_DOMAIN: &str = "shadow-swap/intent-privacy/v1";
+
+/// Domain the MAC key is derived under, so the relayer key is never used
+/// as-is outside ECIES.
+const KEY_DOMAIN: &str = "shadow-swap/intent-privacy-key/v1";
+
+/// Secret keying the privacy context tags. Without it a tag can be
+/// recomputed by anyone able to write the privacy params table.
+#[derive(Clone)]
+pub struct PrivacyContextKey([u8; 32]);
+
+impl PrivacyContextKey {
+    pub fn from_secret(secret: &str) -> Self {
+        let digest = Sha256::new()
+            .chain_update(KEY_DOMAIN)
+            .chain_update(b"\n")
+            .chain_update(secret.trim())
+            .finalize();
+        Self(digest.into())
+    }
+
+    /// `PRIVACY_CONTEXT_KEY`, else the relayer's encryption key
+    /// `RELAYER_PRIVATE_KEY`. Deployments decrypting through the signing
+    /// service must set the former.
+    pub fn from_env() -> Result<Self> {
+        let var = |key| {
+            std::env::var(key)
+                .ok()
+                .filter(|v: &String| !v.trim().is_empty())
+        };
+        var("PRIVACY_CONTEXT_KEY")
+            .or_else(|| var("RELAYER_PRIVATE_KEY"))
+            .map(|secret| Self::from_secret(&secret))
+            .ok_or_else(|| anyhow!("Neither PRIVACY_CONTEXT_KEY nor RELAYER_PRIVATE_KEY is set"))
+    }
+
+    fn mac(&self, params: &IntentPrivacyParams) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.0).expect("HMAC can take key of any size");
+        mac.update(context_message(CONTEXT_DOMAIN, params).as_bytes());
+        mac
+    }
+}
+
+/// The tagged fields, newline separated. Undelegated params leave the
+/// delegate out, as they did before delegation existed.
+fn context_message(domain: &str, params: &IntentPrivacyParams) -> String {
+    let mut fields = vec![
+        domain,
+        &params.intent_id,
+        params.commitment.as_deref().unwrap_or_default(),
+        params.nullifier.as_deref().unwrap_or_default(),
+        params.secret.as_deref().unwrap_or_default(),
+        params.recipient.as_deref().unwrap_or_default(),
+        params.claim_signature.as_deref().unwrap_or_default(),
+    ];
+    if let Some(delegate) = &params.claim_delegate {
+        fields.push(delegate);
+    }
+
+    fields.join("\n")
+}
+
+impl std::fmt::Debug for PrivacyContextKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PrivacyContextKey(..)")
+    }
+}
+
+/// Keyed tag binding the stored ciphertexts, recipient, claim signature and
+/// any claim delegate to the intent id and commitment they were submitted with.
+pub fn privacy_context(key: &PrivacyContextKey, params: &IntentPrivacyParams) -> String {
+    hex::encode(key.mac(params).finalize().into_bytes())
+}
+
+/// Whether `context_tag` is the unkeyed v1 digest of `params`. Only the
+/// one-time re-keying may trust it; reads accept keyed tags alone.
+pub fn matches_legacy_context(params: &IntentPrivacyParams, context_tag: &str) -> bool {
+    let digest = Sha256::digest(context_message(LEGACY_CONTEXT_DOMAIN, params).as_bytes());
+    hex::encode(digest).eq_ignore_ascii_case(context_tag)
+}
+
+/// Refuse privacy params whose fields no longer match the stored context,
+/// or whose commitment differs from the intent's on-chain one.
+pub fn verify_privacy_context(
+    key: &PrivacyContextKey,
+    params: &IntentPrivacyParams,
+    context_tag: &str,
+    source_commitment: Option<&str>,
+) -> Result<()> {
+    let tag = hex::decode(context_tag).unwrap_or_default();
+    if key.mac(params).verify_slice(&tag).is_err() {
+        return Err(anyhow!(
+            "Privacy params for {} do not match their stored context",
+            params.intent_id
+        ));
+    }
+
+    if let (Some(stored), Some(onchain)) = (params.commitment.as_deref(), source_commitment)
+        && !stored.eq_ignore_ascii_case(onchain)
+    {
+        return Err(anyhow!(
+            "Privacy params for {} belong to commitment {}, intent has {}",
+            params.intent_id,
+            stored,
+            onchain
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hash(byte: &str) -> String {
+        format!("0x{}", byte.repeat(32))
+    }
+
+    #[test]
+    fn test_context_detects_mixed_up_params() {
+        let params = IntentPrivacyParams {
+            intent_id: hash("01").parse().unwrap(),
+            commitment: Some(hash("c0").parse().unwrap()),
+            nullifier: Some("0xaa".to_string()),
+            secret: Some("0xbb".to_string()),
+            recipient: Some(format!("0x{}", "cc".repeat(20)).parse().unwrap()),
+            claim_signature: Some("0xdd".to_string()),
+            claim_delegate: None,
+        };
+        let key = PrivacyContextKey::from_secret("0x01");
+        let tag = privacy_context(&key, &params);
+
+        assert!(verify_privacy_context(&key, &params, &tag, Some(&hash("C0"))).is_ok());
+        assert!(verify_privacy_context(&key, &params, &tag.to_uppercase(), None).is_ok());
+
+        // Another intent's secret, or the same row under another id
+        let swapped = IntentPrivacyParams {
+            secret: Some("0xee".to_string()),
+            ..params.clone()
+        };
+        assert!(verify_privacy_context(&key, &swapped, &tag, None).is_err());
+        let moved = IntentPrivacyParams {
+            intent_id: hash("02").parse().unwrap(),
+            ..params.clone()
+        };
+        assert!(verify_privacy_context(&key, &moved, &tag, None).is_err());
+        // Funds redirected to a delegate the user never submitted
+        let delegated = IntentPrivacyParams {
+            claim_delegate: Some(format!("0x{}", "ff".repeat(20)).parse().unwrap()),
+            ..params.clone()
+        };
+        assert!(verify_privacy_context(&key, &delegated, &tag, None).is_err());
+
+        assert!(verify_privacy_context(&key, &params, &tag, Some(&hash("c1"))).is_err());
+    }
+
+    #[test]
+    fn test_context_cannot_be_forged_without_the_key() {
+        let params = IntentPrivacyParams {
+            intent_id: hash("01").parse().unwrap(),
+            commitment: Some(hash("c0").parse().unwrap()),
+            nullifier: Some("0xaa".to_string()),
+            secret: Some("0xbb".to_string()),
+            recipient: None,
+            claim_signature: None,
+            claim_delegate: None,
+        };
+        let key = PrivacyContextKey::from_secret("0x01");
+
+        // Someone with table access recomputing the tag under another key,
+        // or the old unkeyed digest, is refused
+        let forged = privacy_context(&PrivacyContextKey::from_secret("0x02"), &params);
+        assert!(verify_privacy_context(&key, &params, &forged, None).is_err());
+        let fields = [
+            "shadow-swap/intent-privacy/v1",
+            &params.intent_id,
+            &hash("c0"),
+            "0xaa",
+            "0xbb",
+            "",
+            "",
+        ];
+        let unkeyed = hex::encode(Sha256::digest(fields.join("\n").as_bytes()));
+        assert!(matches_legacy_context(&params, &unkeyed));
+        assert!(verify_privacy_context(&key, &params, &unkeyed, None).is_err());
+
+        let swapped = IntentPrivacyParams {
+            secret: Some("0xee".to_string()),
+            ..params.clone()
+        };
+        assert!(!matches_legacy_context(&swapped, &unkeyed));
+        assert!(verify_privacy_context(&key, &params, "not hex", None).is_err());
+    }
+}
@@ -1,32 +1,52 @@
 use anyhow::{Result, anyhow};
-use std::sync::Arc;
+use serde::Serialize;
+use std::{collections::HashSet, sync::Arc};
 use tracing::{error, info, warn};
 
 use crate::{
     database::database::Database,
+    intent_workers::sync_progress::{SyncPhase, SyncProgress},
     merkle_manager::merkle_manager::MerkleTreeManager,
-    relay_coordinator::model::{EthereumRelayer, MantleRelayer},
+    models::model::CommitmentUniqueness,
+    relay_coordinator::model::ChainRelayer,
 };
 
+/// What a resync would change, computed without writing anything.
+#[derive(Debug, Clone, Serialize)]
+pub struct ResyncPreview {
+    pub chain: String,
+    pub from_block: u64,
+    pub onchain_events: usize,
+    pub db_commitments: usize,
+    /// On-chain commitments the database does not have yet.
+    pub missing_from_db: usize,
+    pub db_root: String,
+    pub onchain_root: String,
+    pub roots_match: bool,
+}
+
 pub struct IntentSyncService {
     database: Arc<Database>,
-    mantle_relayer: Arc<MantleRelayer>,
-    ethereum_relayer: Arc<EthereumRelayer>,
+    mantle_relayer: Arc<ChainRelayer>,
+    ethereum_relayer: Arc<ChainRelayer>,
     merkle_manager: Arc<MerkleTreeManager>,
+    progress: SyncProgress,
 }
 
 impl IntentSyncService {
     pub fn new(
         database: Arc<Database>,
-        mantle_relayer: Arc<MantleRelayer>,
-        ethereum_relayer: Arc<EthereumRelayer>,
+        mantle_relayer: Arc<ChainRelayer>,
+        ethereum_relayer: Arc<ChainRelayer>,
         merkle_manager: Arc<MerkleTreeManager>,
+        progress: SyncProgress,
     ) -> Self {
         Self {
             database,
             mantle_relayer,
             ethereum_relayer,
             merkle_manager,
+            progress,
         }
     }
 
@@ -35,6 +55,68 @@ impl IntentSyncService {
         from_block: u64,
         clear_existing: bool,
     ) -> Result<()> {
+        let result = self.resync_ethereum(from_block, clear_existing).await;
+        self.record_outcome("ethereum", &result).await;
+        result
+    }
+
+    pub async fn resync_mantle_intents(&self, from_block: u64, clear_existing: bool) -> Result<()> {
+        let result = self.resync_mantle(from_block, clear_existing).await;
+        self.record_outcome("mantle", &result).await;
+        result
+    }
+
+    /// Read-only counterpart of `resync_*_intents` for `resync --dry-run`.
+    pub async fn preview_resync(&self, chain: &str, from_block: u64) -> Result<ResyncPreview> {
+        let (events, db_root, onchain_root) = match chain {
+            "ethereum" => (
+                self.ethereum_relayer
+                    .fetch_all_intent_created_events(from_block, None)
+                    .await?,
+                self.merkle_manager.compute_ethereum_commitments_root()?,
+                self.ethereum_relayer.get_intent_pool_root().await?,
+            ),
+            "mantle" => (
+                self.mantle_relayer
+                    .fetch_all_intent_created_events(from_block, None)
+                    .await?,
+                self.merkle_manager.compute_mantle_commitments_root()?,
+                self.mantle_relayer.get_intent_pool_root().await?,
+            ),
+            other => return Err(anyhow!("Unknown chain: {}", other)),
+        };
+
+        let known: HashSet<String> = self
+            .database
+            .get_all_commitments_for_chain(chain)?
+            .into_iter()
+            .map(|c| c.to_lowercase())
+            .collect();
+        let missing_from_db = events
+            .iter()
+            .filter(|e| !known.contains(&e.commitment.to_lowercase()))
+            .count();
+
+        Ok(ResyncPreview {
+            chain: chain.to_string(),
+            from_block,
+            onchain_events: events.len(),
+            db_commitments: known.len(),
+            missing_from_db,
+            roots_match: db_root.eq_ignore_ascii_case(&onchain_root),
+            db_root,
+            onchain_root,
+        })
+    }
+
+    async fn record_outcome(&self, chain: &str, result: &Result<()>) {
+        match result {
+            Ok(()) => self.progress.set_phase(chain, SyncPhase::Complete).await,
+            Err(e) => self.progress.failed(chain, e.to_string()).await,
+        }
+    }
+
+    async fn resync_ethereum(&self, from_block: u64, clear_existing: bool) -> Result<()> {
         info!("🔄 Starting Ethereum resync from block {}", from_block);
 
         if clear_existing {
@@ -45,10 +127,15 @@ impl IntentSyncService {
         // The relayer now uses the corrected 160-byte data length check
         let events = self
             .ethereum_relayer
-            .fetch_all_intent_created_events(from_block)
+            .fetch_all_intent_created_events(from_block, Some(&self.progress))
             .await?;
 
         info!("📥 Processing {} events for Ethereum", events.len());
+        self.progress
+            .set_phase("ethereum", SyncPhase::Ingesting)
+            .await;
+
+        let uniqueness = CommitmentUniqueness::from_env()?;
 
         for (idx, event) in events.iter().enumerate() {
             // CRITICAL: Ensure we aren't inserting nulls that break Merkle ordering
@@ -64,12 +151,24 @@ impl IntentSyncService {
 
             if idx % 100 == 0 {
                 info!("  Progress: {}/{}", idx, events.len());
+                self.progress.ingested("ethereum", idx).await;
             }
 
             // This now includes the block_number and log_index for the ORDER BY clause
             self.database.upsert_intent_from_event(event, "ethereum")?;
+            self.database.quarantine_if_duplicate(
+                &event.intent_id,
+                &event.commitment,
+                "ethereum",
+                uniqueness,
+            )?;
         }
 
+        self.progress.ingested("ethereum", events.len()).await;
+        self.progress
+            .set_phase("ethereum", SyncPhase::Verifying)
+            .await;
+
         info!("✅ Rebuilding Ethereum Merkle tree with deterministic ordering");
         self.merkle_manager
             .rebuild_ethereum_commitments_tree()
@@ -91,7 +190,7 @@ impl IntentSyncService {
         }
     }
 
-    pub async fn resync_mantle_intents(&self, from_block: u64, clear_existing: bool) -> Result<()> {
+    async fn resync_mantle(&self, from_block: u64, clear_existing: bool) -> Result<()> {
         info!("🔄 Starting Mantle resync from block {}", from_block);
 
         if clear_existing {
@@ -101,10 +200,15 @@ impl IntentSyncService {
 
         let events = self
             .mantle_relayer
-            .fetch_all_intent_created_events(from_block)
+            .fetch_all_intent_created_events(from_block, Some(&self.progress))
             .await?;
 
         info!("📥 Processing {} events for Mantle", events.len());
+        self.progress
+            .set_phase("mantle", SyncPhase::Ingesting)
+            .await;
+
+        let uniqueness = CommitmentUniqueness::from_env()?;
 
         for (idx, event) in events.iter().enumerate() {
             if event.block_number.is_none() {
@@ -113,10 +217,22 @@ impl IntentSyncService {
 
             if idx % 100 == 0 {
                 info!("  Progress: {}/{}", idx, events.len());
+                self.progress.ingested("mantle", idx).await;
             }
             self.database.upsert_intent_from_event(event, "mantle")?;
+            self.database.quarantine_if_duplicate(
+                &event.intent_id,
+                &event.commitment,
+                "mantle",
+                uniqueness,
+            )?;
         }
 
+        self.progress.ingested("mantle", events.len()).await;
+        self.progress
+            .set_phase("mantle", SyncPhase::Verifying)
+            .await;
+
         info!("✅ Rebuilding Mantle Merkle tree");
         self.merkle_manager
             .rebuild_mantle_commitments_tree()
@@ -143,7 +259,7 @@ impl IntentSyncService {
         info!("\n=== MANTLE ===");
         let mantle_events = self
             .mantle_relayer
-            .fetch_all_intent_created_events(33091000)
+            .fetch_all_intent_created_events(33091000, None)
             .await?;
         let mantle_db_count = self.database.get_all_commitments_for_chain("mantle")?.len();
         let mantle_onchain_count = mantle_events.len();
@@ -168,7 +284,7 @@ impl IntentSyncService {
         info!("\n=== ETHEREUM ===");
         let eth_events = self
             .ethereum_relayer
-            .fetch_all_intent_created_events(9993815)
+            .fetch_all_intent_created_events(9993815, None)
             .await?;
         let eth_db_count = self
             .database
@@ -0,0 +1,257 @@
+use std::collections::{HashMap, VecDeque};
+
+use anyhow::{Context, Result, anyhow};
+
+use mantle_core::token_registry::TokenRegistry;
+
+use crate::models::model::Intent;
+
+const DEFAULT_TOKEN_BATCH: usize = 2;
+
+/// Per-token caps on how many intents a worker picks up in one cycle.
+///
+/// Workers finish a cycle before starting the next, so this also bounds the
+/// work in flight per token, but only within a single worker.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenBatchLimits {
+    pub default_limit: usize,
+    pub overrides: HashMap<String, usize>,
+}
+
+/// Intents chosen for one worker cycle plus the backlog per token.
+#[derive(Debug, Clone)]
+pub struct FairBatch {
+    pub intents: Vec<Intent>,
+    pub queue_depths: HashMap<String, usize>,
+}
+
+impl Default for TokenBatchLimits {
+    fn default() -> Self {
+        Self {
+            default_limit: DEFAULT_TOKEN_BATCH,
+            overrides: HashMap::new(),
+        }
+    }
+}
+
+impl TokenBatchLimits {
+    /// Reads `TOKEN_CONCURRENCY_LIMIT` (default 2) and
+    /// `TOKEN_CONCURRENCY_OVERRIDES` (e.g. `USDC=1,ETH=4`).
+    pub fn from_env() -> Result<Self> {
+        let var = |key| {
+            std::env::var(key)
+                .ok()
+                .filter(|v: &String| !v.trim().is_empty())
+        };
+        let mut limits = Self::default();
+
+        if let Some(value) = var("TOKEN_CONCURRENCY_LIMIT") {
+            limits.default_limit =
+                parse_limit(&value).context("Invalid TOKEN_CONCURRENCY_LIMIT")?;
+        }
+        if let Some(value) = var("TOKEN_CONCURRENCY_OVERRIDES") {
+            limits.overrides =
+                Self::parse_overrides(&value).context("Invalid TOKEN_CONCURRENCY_OVERRIDES")?;
+        }
+
+        Ok(limits)
+    }
+
+    fn parse_overrides(raw: &str) -> Result<HashMap<String, usize>> {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let (token, limit) = entry
+                    .split_once('=')
+                    .filter(|(token, _)| !token.trim().is_empty())
+                    .ok_or_else(|| anyhow!("expected TOKEN=LIMIT, got '{}'", entry))?;
+                let limit = parse_limit(limit).with_context(|| format!("in '{}'", entry))?;
+                Ok((token.trim().to_uppercase(), limit))
+            })
+            .collect()
+    }
+
+    pub fn limit_for(&self, token: &str) -> usize {
+        self.overrides
+            .get(token)
+            .copied()
+            .unwrap_or(self.default_limit)
+    }
+}
+
+fn parse_limit(value: &str) -> Result<usize> {
+    match value.trim().parse::<usize>()? {
+        0 => Err(anyhow!("limit must be at least 1")),
+        limit => Ok(limit),
+    }
+}
+
+/// Queue key for an intent: the token symbol when known, otherwise the raw address.
+pub fn token_key(tokens: &TokenRegistry, intent: &Intent) -> String {
+    tokens
+        .resolve_str(&intent.source_token)
+        .map(|t| t.symbol().to_string())
+        .unwrap_or_else(|_| intent.source_token.to_lowercase())
+}
+
+/// Number of intents per token queue.
+pub fn queue_depths(tokens: &TokenRegistry, intents: &[Intent]) -> HashMap<String, usize> {
+    let mut depths = HashMap::new();
+    for intent in intents {
+        *depths.entry(token_key(tokens, intent)).or_insert(0) += 1;
+    }
+    depths
+}
+
+/// Pick up to `total_limit` intents for one cycle, round-robin across
+/// per-token queues.
+///
+/// Each token queue keeps its original (oldest-first) order and never
+/// contributes more than its batch limit, so a burst on one token cannot
+/// starve the others. Intents still in flight from an earlier cycle are not
+/// counted.
+pub fn select_cycle_batch(
+    intents: Vec<Intent>,
+    tokens: &TokenRegistry,
+    limits: &TokenBatchLimits,
+    total_limit: usize,
+) -> FairBatch {
+    let queue_depths = queue_depths(tokens, &intents);
+
+    let mut order: Vec<String> = Vec::new();
+    let mut queues: HashMap<String, VecDeque<Intent>> = HashMap::new();
+
+    for intent in intents {
+        let key = token_key(tokens, &intent);
+        if !queues.contains_key(&key) {
+            order.push(key.clone());
+        }
+        queues.entry(key).or_default().push_back(intent);
+    }
+
+    let mut taken: HashMap<String, usize> = HashMap::new();
+    let mut selected = Vec::new();
+
+    while selected.len() < total_limit {
+        let mut progressed = false;
+
+        for key in &order {
+            if selected.len() >= total_limit {
+                break;
+            }
+
+            let count = taken.entry(key.clone()).or_insert(0);
+            if *count >= limits.limit_for(key) {
+                continue;
+            }
+
+            if let Some(intent) = queues.get_mut(key).and_then(|q| q.pop_front()) {
+                *count += 1;
+                selected.push(intent);
+                progressed = true;
+            }
+        }
+
+        if !progressed {
+            break;
+        }
+    }
+
+    FairBatch {
+        intents: selected,
+        queue_depths,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::IntentStatus;
+    use chrono::Utc;
+    use ethers::types::H256;
+
+    const USDC: &str = "0x28650373758d75a8fF0B22587F111e47BAC34e21";
+    const ETH: &str = "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE";
+
+    /// Intent whose id ends in `n`.
+    fn intent(n: u64, token: &str) -> Intent {
+        Intent {
+            id: H256::from_low_u64_be(n).into(),
+            user_address: String::new(),
+            source_chain: "ethereum".to_string(),
+            dest_chain: "mantle".to_string(),
+            source_token: token.to_string(),
+            dest_token: token.to_string(),
+            amount: "1".parse().unwrap(),
+            dest_amount: "1".parse().unwrap(),
+            source_commitment: None,
+            dest_fill_txid: None,
+            dest_registration_txid: None,
+            source_complete_txid: None,
+            status: IntentStatus::Filled,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deadline: 0,
+            refund_address: None,
+            solver_address: None,
+            block_number: None,
+            log_index: None,
+        }
+    }
+
+    fn limits(default_limit: usize) -> TokenBatchLimits {
+        TokenBatchLimits {
+            default_limit,
+            overrides: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_burst_does_not_starve_other_tokens() {
+        let mut intents: Vec<Intent> = (0..5).map(|i| intent(i, USDC)).collect();
+        intents.push(intent(100, ETH));
+
+        let batch = select_cycle_batch(intents, &TokenRegistry::builtin(), &limits(10), 2);
+        let ids: Vec<u64> = batch
+            .intents
+            .iter()
+            .map(|i| i.id.h256().to_low_u64_be())
+            .collect();
+
+        assert_eq!(ids, vec![0, 100]);
+        assert_eq!(batch.queue_depths.get("USDC"), Some(&5));
+        assert_eq!(batch.queue_depths.get("ETH"), Some(&1));
+    }
+
+    #[test]
+    fn test_per_token_limit_applies() {
+        let intents: Vec<Intent> = (0..5).map(|i| intent(i, USDC)).collect();
+
+        let mut limits = limits(3);
+        limits.overrides.insert("USDC".to_string(), 1);
+
+        let batch = select_cycle_batch(intents, &TokenRegistry::builtin(), &limits, 10);
+        assert_eq!(batch.intents.len(), 1);
+        assert_eq!(batch.intents[0].id.h256(), H256::zero());
+    }
+
+    #[test]
+    fn test_parse_overrides() {
+        let overrides = TokenBatchLimits::parse_overrides("usdc=1, ETH=4,").unwrap();
+        assert_eq!(overrides.get("USDC"), Some(&1));
+        assert_eq!(overrides.get("ETH"), Some(&4));
+        assert_eq!(overrides.len(), 2);
+
+        for bad in ["usdc=1,bad", "MNT=x", "=3", "ETH=0", "ETH=-1"] {
+            assert!(
+                TokenBatchLimits::parse_overrides(bad).is_err(),
+                "{} should be rejected",
+                bad
+            );
+        }
+        assert!(parse_limit("0").is_err());
+        assert!(parse_limit("two").is_err());
+        assert_eq!(parse_limit(" 3 ").unwrap(), 3);
+    }
+}
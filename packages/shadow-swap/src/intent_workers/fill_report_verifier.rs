@@ -0,0 +1,118 @@
+use actix_web::web;
+use anyhow::Result;
+use chrono::Utc;
+use tracing::{error, info, warn};
+
+use crate::{
+    AppState,
+    api::helper::dispatch_indexer_event,
+    relay_coordinator::fill_reports::{FillReportCheck, FillReportPolicy},
+    shutdown::Shutdown,
+};
+
+const BATCH_SIZE: i64 = 50;
+
+/// Checks third-party fill reports against the destination chain and
+/// applies the verified ones as `IntentFilled` events, which wakes the
+/// settlement worker without waiting for the indexer.
+pub struct FillReportVerifier {
+    policy: FillReportPolicy,
+    app_state: web::Data<AppState>,
+    shutdown: Shutdown,
+}
+
+impl FillReportVerifier {
+    pub fn new(
+        policy: FillReportPolicy,
+        app_state: web::Data<AppState>,
+        shutdown: Shutdown,
+    ) -> Self {
+        Self {
+            policy,
+            app_state,
+            shutdown,
+        }
+    }
+
+    pub async fn run(&self) {
+        let wake = self
+            .app_state
+            .bridge_coordinator
+            .wakeups
+            .fill_reports
+            .clone();
+
+        loop {
+            if let Err(e) = self.verify_pending().await {
+                error!("❌ Fill report verification failed: {:#}", e);
+            }
+
+            if self
+                .shutdown
+                .sleep_or_wake(self.policy.check_interval, &wake)
+                .await
+            {
+                break;
+            }
+        }
+
+        info!("📨 Fill report verifier stopped");
+    }
+
+    async fn verify_pending(&self) -> Result<()> {
+        let coordinator = &self.app_state.bridge_coordinator;
+
+        for report in self
+            .app_state
+            .database
+            .get_pending_fill_reports(BATCH_SIZE)?
+        {
+            let check = match coordinator
+                .check_fill_report(&self.policy, &report, Utc::now())
+                .await
+            {
+                Ok(check) => check,
+                Err(e) => {
+                    warn!("⚠️ Could not check fill report {}: {:#}", report.id, e);
+                    continue;
+                }
+            };
+
+            match check {
+                FillReportCheck::Verified(request) => {
+                    // Left pending when not applied, so the next pass retries
+                    let response = dispatch_indexer_event(&self.app_state, &request).await;
+                    if !response.status().is_success() {
+                        warn!(
+                            "⚠️ Verified fill report {} not applied ({})",
+                            report.id,
+                            response.status()
+                        );
+                        continue;
+                    }
+                    self.app_state
+                        .database
+                        .resolve_fill_report(report.id, "verified", None)?;
+                    info!(
+                        "📨 Fill of {} reported by {} verified in {}",
+                        report.intent_id, report.solver_address, report.transaction_hash
+                    );
+                }
+                FillReportCheck::Rejected(reason) => {
+                    self.app_state.database.resolve_fill_report(
+                        report.id,
+                        "rejected",
+                        Some(&reason),
+                    )?;
+                    warn!(
+                        "🚫 Fill report {} from {} rejected: {}",
+                        report.id, report.solver_address, reason
+                    );
+                }
+                FillReportCheck::Pending => {}
+            }
+        }
+
+        Ok(())
+    }
+}
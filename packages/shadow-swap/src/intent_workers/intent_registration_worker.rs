@@ -1,48 +1,118 @@
 use anyhow::{Context, Result, anyhow};
-use ethers::types::U256;
+use mantle_core::{
+    amount::rescale,
+    chain::{Chain, ETHEREUM_CHAIN_ID, MANTLE_CHAIN_ID},
+    parse,
+    token::TokenType,
+    token_registry::TokenRegistry,
+};
+use serde_json::json;
 use std::sync::Arc;
-use tokio::time::{Duration, sleep};
+use tokio::{
+    sync::{Notify, RwLock},
+    time::Duration,
+};
 use tracing::{error, info, warn};
 
 use crate::{
     database::database::Database,
+    intent_workers::fairness::queue_depths,
     merkle_manager::merkle_manager::MerkleTreeManager,
-    models::model::{Intent, IntentStatus, TokenType},
-    relay_coordinator::model::{EthereumRelayer, MantleRelayer},
+    models::model::{BridgeMetrics, Intent, IntentStatus},
+    relay_coordinator::{
+        compliance::{ComplianceAction, ComplianceScreener},
+        debug_capture::{CaptureKind, DebugCapture, DebugCapturePolicy},
+        model::ChainRelayer,
+        relayer_jobs::{JobKind, RelayerJobPolicy, RelayerJobs},
+        work_partitions::{PartitionPolicy, WorkPartitions},
+    },
     root_sync_coordinator::root_sync_coordinator::RootSyncCoordinator,
+    shutdown::Shutdown,
 };
 
-const ETHEREUM_CHAIN_ID: u32 = 11155111;
-const MANTLE_CHAIN_ID: u32 = 5003;
 const MAX_CONCURRENT_REGISTRATIONS: usize = 5;
 
 pub struct IntentRegistrationWorker {
     database: Arc<Database>,
-    mantle_relayer: Arc<MantleRelayer>,
-    ethereum_relayer: Arc<EthereumRelayer>,
+    mantle_relayer: Arc<ChainRelayer>,
+    ethereum_relayer: Arc<ChainRelayer>,
     merkle_manager: Arc<MerkleTreeManager>,
     root_sync_coordinator: Arc<RootSyncCoordinator>,
+    metrics: Arc<RwLock<BridgeMetrics>>,
+    compliance: Arc<ComplianceScreener>,
+    tokens: Arc<TokenRegistry>,
+    partitions: WorkPartitions,
+    jobs: RelayerJobs,
+    debug_capture: DebugCapture,
+    shutdown: Shutdown,
+    wake: Arc<Notify>,
     poll_interval: Duration,
 }
 
 impl IntentRegistrationWorker {
     pub fn new(
         database: Arc<Database>,
-        mantle_relayer: Arc<MantleRelayer>,
-        ethereum_relayer: Arc<EthereumRelayer>,
+        mantle_relayer: Arc<ChainRelayer>,
+        ethereum_relayer: Arc<ChainRelayer>,
         merkle_manager: Arc<MerkleTreeManager>,
         root_sync_coordinator: Arc<RootSyncCoordinator>,
+        metrics: Arc<RwLock<BridgeMetrics>>,
+        compliance: Arc<ComplianceScreener>,
     ) -> Self {
         Self {
+            jobs: RelayerJobs::new(database.clone(), RelayerJobPolicy::default()),
+            debug_capture: DebugCapture::new(database.clone(), DebugCapturePolicy::default()),
             database,
             mantle_relayer,
             ethereum_relayer,
             merkle_manager,
             root_sync_coordinator,
+            metrics,
+            compliance,
+            tokens: Arc::new(TokenRegistry::builtin()),
+            partitions: WorkPartitions::new(PartitionPolicy::default()),
+            shutdown: Shutdown::new(),
+            wake: Arc::new(Notify::new()),
             poll_interval: Duration::from_secs(10),
         }
     }
 
+    /// Resolve tokens through the relayer's shared registry.
+    pub fn with_tokens(mut self, tokens: Arc<TokenRegistry>) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
+    /// Stop picking up intents once `shutdown` is triggered.
+    pub fn with_shutdown(mut self, shutdown: Shutdown) -> Self {
+        self.shutdown = shutdown;
+        self
+    }
+
+    /// Poll again as soon as `wake` is notified of a new intent.
+    pub fn with_wake(mut self, wake: Arc<Notify>) -> Self {
+        self.wake = wake;
+        self
+    }
+
+    /// Only register intents in this instance's partitions.
+    pub fn with_partitions(mut self, partitions: WorkPartitions) -> Self {
+        self.partitions = partitions;
+        self
+    }
+
+    /// Keep attempts and backoff in the relayer's shared job queue.
+    pub fn with_jobs(mut self, jobs: RelayerJobs) -> Self {
+        self.jobs = jobs;
+        self
+    }
+
+    /// Record the intents operators are debugging.
+    pub fn with_debug_capture(mut self, debug_capture: DebugCapture) -> Self {
+        self.debug_capture = debug_capture;
+        self
+    }
+
     pub async fn run(&self) {
         info!("🔄 Intent registration worker started");
 
@@ -50,15 +120,46 @@ impl IntentRegistrationWorker {
             if let Err(e) = self.process_pending_registrations().await {
                 error!("Registration worker error: {}", e);
             }
-            sleep(self.poll_interval).await;
+            if self
+                .shutdown
+                .sleep_or_wake(self.poll_interval, &self.wake)
+                .await
+            {
+                break;
+            }
         }
+
+        info!("🔄 Intent registration worker stopped");
     }
 
     async fn process_pending_registrations(&self) -> Result<()> {
-        let pending = self
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut pending = self
             .database
             .get_intents_by_status(IntentStatus::Committed)
             .context("Failed to fetch pending intents")?;
+        // Underpaid intents are never registered, only refunded below once
+        // their deadline passes
+        pending.extend(
+            self.database
+                .get_intents_by_status(IntentStatus::Underpaid)
+                .context("Failed to fetch underpaid intents")?
+                .into_iter()
+                .filter(|intent| intent.deadline < now),
+        );
+        let mut pending = self.partitions.retain_owned(pending);
+
+        // Blocked intents wait for their deadline and the refund below, without
+        // taking registration slots from everyone else
+        if self.compliance.enabled() {
+            let blocked = self.database.get_compliance_blocked_intent_ids()?;
+            pending.retain(|intent| {
+                intent.deadline < now || !blocked.contains(&intent.id.to_lowercase())
+            });
+        }
+        // Failed intents back off in `relayer_jobs`; dead-lettered ones wait
+        // for an operator
+        let pending = self.jobs.due(JobKind::Registration, pending)?;
 
         if pending.is_empty() {
             return Ok(());
@@ -66,15 +167,49 @@ impl IntentRegistrationWorker {
 
         info!("📋 Found {} intents pending registration", pending.len());
 
+        self.metrics.write().await.queue_depths.insert(
+            "registration".to_string(),
+            queue_depths(&self.tokens, &pending),
+        );
+
         let mut tasks = Vec::new();
 
         for intent in pending.into_iter().take(MAX_CONCURRENT_REGISTRATIONS) {
             let worker = self.clone_for_task();
             let task = tokio::spawn(async move {
                 let intent_id = intent.id.clone();
-                match worker.process_single_intent_with_retry(&intent).await {
-                    Ok(_) => info!("✅ Processed intent {}", &intent_id[..10]),
-                    Err(e) => error!("❌ Failed to process intent {}: {:#?}", &intent_id[..10], e),
+                match worker.process_single_intent(&intent).await {
+                    Ok(_) => {
+                        info!("✅ Processed intent {}", parse::short(&intent_id));
+                        worker.capture(&intent_id, CaptureKind::Decision, json!({"outcome": "ok"}));
+                        if let Err(e) = worker.jobs.succeeded(JobKind::Registration, &intent_id) {
+                            error!("Failed to complete registration job: {:#}", e);
+                        }
+                        let mut metrics = worker.metrics.write().await;
+                        metrics.record_processed("registration");
+                        metrics.intent_errors.remove(intent_id.as_str());
+                    }
+                    Err(e) => {
+                        error!(
+                            "❌ Failed to process intent {}: {:#?}",
+                            parse::short(&intent_id),
+                            e
+                        );
+                        worker.capture(
+                            &intent_id,
+                            CaptureKind::Decision,
+                            json!({"outcome": "failed", "error": format!("{:#}", e)}),
+                        );
+                        if let Err(err) = worker.jobs.failed(JobKind::Registration, &intent_id, &e)
+                        {
+                            error!("Failed to record registration job failure: {:#}", err);
+                        }
+                        worker.metrics.write().await.record_intent_error(
+                            &intent_id,
+                            "registration",
+                            &e,
+                        );
+                    }
                 }
             });
             tasks.push(task);
@@ -94,52 +229,81 @@ impl IntentRegistrationWorker {
             ethereum_relayer: self.ethereum_relayer.clone(),
             merkle_manager: self.merkle_manager.clone(),
             root_sync_coordinator: self.root_sync_coordinator.clone(),
+            metrics: self.metrics.clone(),
+            compliance: self.compliance.clone(),
+            tokens: self.tokens.clone(),
+            partitions: self.partitions.clone(),
+            jobs: self.jobs.clone(),
+            debug_capture: self.debug_capture.clone(),
+            shutdown: self.shutdown.clone(),
+            wake: self.wake.clone(),
             poll_interval: self.poll_interval,
         }
     }
 
-    async fn process_single_intent_with_retry(&self, intent: &Intent) -> Result<()> {
-        let mut last_error = None;
-
-        for attempt in 1..=3 {
-            match self.process_single_intent(intent).await {
-                Ok(_) => return Ok(()),
-                Err(e) => {
-                    warn!(
-                        "Attempt {}/3 failed for intent {}: {}",
-                        attempt,
-                        &intent.id[..10],
-                        e
-                    );
-                    last_error = Some(e);
-
-                    if attempt < 3 {
-                        sleep(Duration::from_secs(2 * attempt as u64)).await;
-                    }
-                }
-            }
-        }
-
-        Err(last_error.unwrap())
+    fn capture(&self, intent_id: &str, kind: CaptureKind, payload: serde_json::Value) {
+        self.debug_capture
+            .record(intent_id, "registration", kind, payload);
     }
 
     async fn process_single_intent(&self, intent: &Intent) -> Result<()> {
         if intent.deadline < chrono::Utc::now().timestamp() as u64 {
-            warn!("Intent {} expired, processing refund", &intent.id[..10]);
+            warn!(
+                "Intent {} expired, processing refund",
+                parse::short(&intent.id)
+            );
+            self.capture(
+                &intent.id,
+                CaptureKind::Decision,
+                json!({"action": "refund", "reason": "deadline passed", "deadline": intent.deadline}),
+            );
             self.database
                 .update_intent_status(&intent.id, IntentStatus::Expired)?;
 
-            match intent.source_chain.as_str() {
+            let refund = match intent.source_chain.as_str() {
                 "mantle" => match self.mantle_relayer.execute_refund(&intent.id).await {
-                    Ok(tx_hash) => info!("✅ Refunded on Mantle: {}", tx_hash),
-                    Err(e) => error!("❌ Mantle refund failed: {:#?}", e),
+                    Ok(tx_hash) => {
+                        info!("✅ Refunded on Mantle: {}", tx_hash);
+                        json!({"tx_hash": tx_hash})
+                    }
+                    Err(e) => {
+                        error!("❌ Mantle refund failed: {:#?}", e);
+                        json!({"error": format!("{:#}", e)})
+                    }
                 },
                 "ethereum" => match self.ethereum_relayer.execute_refund(&intent.id).await {
-                    Ok(tx_hash) => info!("✅ Refunded on Ethereum: {}", tx_hash),
-                    Err(e) => error!("❌ Ethereum refund failed: {:#?}", e),
+                    Ok(tx_hash) => {
+                        info!("✅ Refunded on Ethereum: {}", tx_hash);
+                        json!({"tx_hash": tx_hash})
+                    }
+                    Err(e) => {
+                        error!("❌ Ethereum refund failed: {:#?}", e);
+                        json!({"error": format!("{:#}", e)})
+                    }
                 },
-                _ => warn!("Unknown source chain for refund"),
-            }
+                _ => {
+                    warn!("Unknown source chain for refund");
+                    return Ok(());
+                }
+            };
+            self.capture(
+                &intent.id,
+                CaptureKind::Rpc,
+                json!({"call": "refund", "chain": intent.source_chain, "result": refund}),
+            );
+            return Ok(());
+        }
+
+        if self.compliance.screen(intent).await? == ComplianceAction::Block {
+            self.capture(
+                &intent.id,
+                CaptureKind::Decision,
+                json!({"action": "hold", "reason": "blocked by compliance screening"}),
+            );
+            warn!(
+                "⛔ Not registering intent {}: blocked by compliance screening",
+                parse::short(&intent.id)
+            );
             return Ok(());
         }
 
@@ -153,7 +317,7 @@ impl IntentRegistrationWorker {
                 if self.check_already_registered_on_mantle(&intent.id).await? {
                     info!(
                         "✅ Intent {} already registered on Mantle",
-                        &intent.id[..10]
+                        parse::short(&intent.id)
                     );
                     self.database
                         .update_intent_status(&intent.id, IntentStatus::Registered)?;
@@ -168,7 +332,7 @@ impl IntentRegistrationWorker {
                 {
                     info!(
                         "✅ Intent {} already registered on Ethereum",
-                        &intent.id[..10]
+                        parse::short(&intent.id)
                     );
                     self.database
                         .update_intent_status(&intent.id, IntentStatus::Registered)?;
@@ -181,17 +345,36 @@ impl IntentRegistrationWorker {
     }
 
     async fn check_already_registered_on_mantle(&self, intent_id: &str) -> Result<bool> {
-        self.mantle_relayer.check_intent_registered(intent_id).await
+        let registered = self
+            .mantle_relayer
+            .check_intent_registered(intent_id)
+            .await?;
+        self.capture(
+            intent_id,
+            CaptureKind::Rpc,
+            json!({"call": "check_intent_registered", "chain": "mantle", "registered": registered}),
+        );
+        Ok(registered)
     }
 
     async fn check_already_registered_on_ethereum(&self, intent_id: &str) -> Result<bool> {
-        self.ethereum_relayer
+        let registered = self
+            .ethereum_relayer
             .check_intent_registered(intent_id)
-            .await
+            .await?;
+        self.capture(
+            intent_id,
+            CaptureKind::Rpc,
+            json!({"call": "check_intent_registered", "chain": "ethereum", "registered": registered}),
+        );
+        Ok(registered)
     }
 
     async fn register_on_ethereum(&self, intent: &Intent, commitment: &str) -> Result<()> {
-        info!("📝 [Ethereum] Registering intent {}", &intent.id[..10]);
+        info!(
+            "📝 [Ethereum] Registering intent {}",
+            parse::short(&intent.id)
+        );
 
         info!("   Rebuilding Mantle commitments tree...");
         self.merkle_manager
@@ -203,11 +386,11 @@ impl IntentRegistrationWorker {
             .get_latest_root("mantle_commitments")?
             .ok_or_else(|| anyhow!("Mantle commitments root not found"))?;
 
-        info!("   DB root (Mantle): {}", &db_root[..18]);
+        info!("   DB root (Mantle): {}", parse::prefix(&db_root, 18));
 
         let sync_result = tokio::time::timeout(
             Duration::from_secs(120),
-            self.ensure_root_synced_on_ethereum(&db_root),
+            self.ensure_root_synced(&self.mantle_relayer, &self.ethereum_relayer, &db_root),
         )
         .await;
 
@@ -232,17 +415,17 @@ impl IntentRegistrationWorker {
             proof.len()
         );
 
-        let token_type = TokenType::from_address(&intent.source_token)?;
-        let dest_token = token_type.get_ethereum_address();
+        let token_type = self.tokens.resolve_str(&intent.source_token)?;
+        let dest_token = self.dest_token(token_type, Chain::Ethereum)?;
         let dest_amount =
-            self.convert_amount(&intent.dest_amount, &intent.source_token, dest_token)?;
+            self.convert_amount(&intent.dest_amount, &intent.source_token, &dest_token)?;
 
         let txid = self
             .ethereum_relayer
             .register_intent(
                 &intent.id,
                 commitment,
-                dest_token,
+                &dest_token,
                 &dest_amount,
                 MANTLE_CHAIN_ID,
                 intent.deadline,
@@ -251,6 +434,20 @@ impl IntentRegistrationWorker {
                 commitment_index as u32,
             )
             .await?;
+        self.capture(
+            &intent.id,
+            CaptureKind::Rpc,
+            json!({
+                "call": "register_intent",
+                "chain": "ethereum",
+                "dest_token": dest_token,
+                "dest_amount": dest_amount,
+                "root": root,
+                "commitment_index": commitment_index,
+                "proof_length": proof.len(),
+                "tx_hash": txid,
+            }),
+        );
 
         self.database
             .update_dest_registration_txid(&intent.id, &txid)?;
@@ -262,7 +459,10 @@ impl IntentRegistrationWorker {
     }
 
     async fn register_on_mantle(&self, intent: &Intent, commitment: &str) -> Result<()> {
-        info!("📝 [Mantle] Registering intent {}", &intent.id[..10]);
+        info!(
+            "📝 [Mantle] Registering intent {}",
+            parse::short(&intent.id)
+        );
 
         info!("   Rebuilding Ethereum commitments tree...");
         self.merkle_manager
@@ -274,11 +474,11 @@ impl IntentRegistrationWorker {
             .get_latest_root("ethereum_commitments")?
             .ok_or_else(|| anyhow!("Ethereum commitments root not found"))?;
 
-        info!("   DB root (Ethereum): {}", &db_root[..18]);
+        info!("   DB root (Ethereum): {}", parse::prefix(&db_root, 18));
 
         let sync_result = tokio::time::timeout(
             Duration::from_secs(120),
-            self.ensure_root_synced_on_mantle(&db_root),
+            self.ensure_root_synced(&self.ethereum_relayer, &self.mantle_relayer, &db_root),
         )
         .await;
 
@@ -303,17 +503,17 @@ impl IntentRegistrationWorker {
             proof.len()
         );
 
-        let token_type = TokenType::from_address(&intent.source_token)?;
-        let dest_token = token_type.get_mantle_address();
+        let token_type = self.tokens.resolve_str(&intent.source_token)?;
+        let dest_token = self.dest_token(token_type, Chain::Mantle)?;
         let dest_amount =
-            self.convert_amount(&intent.dest_amount, &intent.source_token, dest_token)?;
+            self.convert_amount(&intent.dest_amount, &intent.source_token, &dest_token)?;
 
         let txid = self
             .mantle_relayer
             .register_intent(
                 &intent.id,
                 commitment,
-                dest_token,
+                &dest_token,
                 &dest_amount,
                 ETHEREUM_CHAIN_ID,
                 intent.deadline,
@@ -322,6 +522,20 @@ impl IntentRegistrationWorker {
                 commitment_index as u32,
             )
             .await?;
+        self.capture(
+            &intent.id,
+            CaptureKind::Rpc,
+            json!({
+                "call": "register_intent",
+                "chain": "mantle",
+                "dest_token": dest_token,
+                "dest_amount": dest_amount,
+                "root": root,
+                "commitment_index": commitment_index,
+                "proof_length": proof.len(),
+                "tx_hash": txid,
+            }),
+        );
 
         self.database
             .update_dest_registration_txid(&intent.id, &txid)?;
@@ -332,28 +546,34 @@ impl IntentRegistrationWorker {
         Ok(())
     }
 
-    async fn ensure_root_synced_on_ethereum(&self, expected_root: &str) -> Result<()> {
-        let synced = self
-            .ethereum_relayer
-            .get_synced_mantle_commitment_root()
-            .await?;
+    /// Make sure `dest` has `source`'s commitment root `expected_root`,
+    /// syncing it first if needed.
+    async fn ensure_root_synced(
+        &self,
+        source: &ChainRelayer,
+        dest: &ChainRelayer,
+        expected_root: &str,
+    ) -> Result<()> {
+        let synced = dest.get_synced_commitment_root(source.chain_id).await?;
 
-        info!("   Ethereum's view of Mantle root: {}", &synced[..18]);
+        info!(
+            "   {}'s view of {} root: {}",
+            dest.name,
+            source.name,
+            parse::prefix(&synced, 18)
+        );
 
         if synced.to_lowercase() == expected_root.to_lowercase() {
             info!("   ✅ Already synced");
             return Ok(());
         }
 
-        info!("   🔄 Syncing root to Ethereum...");
+        info!("   🔄 Syncing root to {}...", dest.name);
         self.root_sync_coordinator
-            .sync_mantle_commitments_to_ethereum()
+            .sync_commitments(source, dest)
             .await?;
 
-        let new_synced = self
-            .ethereum_relayer
-            .get_synced_mantle_commitment_root()
-            .await?;
+        let new_synced = dest.get_synced_commitment_root(source.chain_id).await?;
 
         if new_synced.to_lowercase() != expected_root.to_lowercase() {
             return Err(anyhow!(
@@ -367,65 +587,20 @@ impl IntentRegistrationWorker {
         Ok(())
     }
 
-    async fn ensure_root_synced_on_mantle(&self, expected_root: &str) -> Result<()> {
-        let synced = self
-            .mantle_relayer
-            .get_synced_ethereum_commitment_root()
-            .await?;
-
-        info!("   Mantle's view of Ethereum root: {}", &synced[..18]);
-
-        if synced.to_lowercase() == expected_root.to_lowercase() {
-            info!("   ✅ Already synced");
-            return Ok(());
-        }
-
-        info!("   🔄 Syncing root to Mantle...");
-        self.root_sync_coordinator
-            .sync_ethereum_commitments_to_mantle()
-            .await?;
-
-        let new_synced = self
-            .mantle_relayer
-            .get_synced_ethereum_commitment_root()
-            .await?;
-
-        if new_synced.to_lowercase() != expected_root.to_lowercase() {
-            return Err(anyhow!(
-                "Root sync failed. Expected: {}, Got: {}",
-                expected_root,
-                new_synced
-            ));
-        }
-
-        info!("   ✅ Root synced successfully");
-        Ok(())
+    /// The token's contract on `dest`, as the registry lists it.
+    fn dest_token(&self, token: TokenType, dest: Chain) -> Result<String> {
+        self.tokens
+            .address(token, dest)
+            .map(|address| format!("{:?}", address))
+            .ok_or_else(|| anyhow!("Token {} is not listed", token.symbol()))
     }
 
     fn convert_amount(&self, amount: &str, source_token: &str, dest_token: &str) -> Result<String> {
-        let source_type = TokenType::from_address(source_token)?;
-        let dest_type = TokenType::from_address(dest_token)?;
-
-        let source_decimals = source_type.get_decimals();
-        let dest_decimals = dest_type.get_decimals();
-
-        let amount_u256 = U256::from_dec_str(amount).context("Invalid amount format")?;
-
-        let converted = if dest_decimals > source_decimals {
-            let diff = dest_decimals - source_decimals;
-            let multiplier = U256::from(10u64).pow(U256::from(diff));
-            amount_u256
-                .checked_mul(multiplier)
-                .ok_or_else(|| anyhow!("Amount overflow"))?
-        } else if source_decimals > dest_decimals {
-            let diff = source_decimals - dest_decimals;
-            let divisor = U256::from(10u64).pow(U256::from(diff));
-            amount_u256
-                .checked_div(divisor)
-                .ok_or_else(|| anyhow!("Amount underflow"))?
-        } else {
-            amount_u256
-        };
+        let source_type = self.tokens.resolve_str(source_token)?;
+        let dest_type = self.tokens.resolve_str(dest_token)?;
+
+        let amount_u256 = parse::amount("amount", amount)?;
+        let converted = rescale(amount_u256, source_type.decimals(), dest_type.decimals())?;
 
         Ok(converted.to_string())
     }
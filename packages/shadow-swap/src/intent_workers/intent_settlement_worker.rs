@@ -1,23 +1,32 @@
 use anyhow::{Result, anyhow};
+use mantle_core::{
+    chain::{Chain, ETHEREUM_CHAIN_ID, MANTLE_CHAIN_ID},
+    parse,
+};
+use serde_json::json;
 use std::sync::Arc;
-use tokio::time::{Duration, sleep};
-use tracing::{error, info, warn};
+use tokio::time::Duration;
+use tracing::{error, info};
 
 use crate::{
     database::database::Database,
+    intent_workers::fairness::select_cycle_batch,
     models::model::{Intent, IntentStatus},
-    relay_coordinator::model::{BridgeCoordinator, EthereumRelayer, MantleRelayer},
+    relay_coordinator::{
+        debug_capture::CaptureKind,
+        model::{BridgeCoordinator, ChainRelayer},
+        relayer_jobs::JobKind,
+        settlement_strategy::{FillObservation, SettlementStep, next_step},
+    },
 };
 
-const ETHEREUM_CHAIN_ID: u32 = 11155111;
-const MANTLE_CHAIN_ID: u32 = 5003;
 const MAX_CONCURRENT_SETTLEMENTS: usize = 3;
 const ZERO_LEAF: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
 
 pub struct IntentSettlementWorker {
     database: Arc<Database>,
-    mantle_relayer: Arc<MantleRelayer>,
-    ethereum_relayer: Arc<EthereumRelayer>,
+    mantle_relayer: Arc<ChainRelayer>,
+    ethereum_relayer: Arc<ChainRelayer>,
     coordinator: Arc<BridgeCoordinator>,
     poll_interval: Duration,
 }
@@ -25,8 +34,8 @@ pub struct IntentSettlementWorker {
 impl IntentSettlementWorker {
     pub fn new(
         database: Arc<Database>,
-        mantle_relayer: Arc<MantleRelayer>,
-        ethereum_relayer: Arc<EthereumRelayer>,
+        mantle_relayer: Arc<ChainRelayer>,
+        ethereum_relayer: Arc<ChainRelayer>,
         coordinator: Arc<BridgeCoordinator>,
     ) -> Self {
         Self {
@@ -40,16 +49,35 @@ impl IntentSettlementWorker {
 
     pub async fn run(&self) {
         info!("🔄 Intent settlement worker started");
+        if let Err(e) = self.coordinator.recover_submissions().await {
+            error!("Failed to resolve open settlement submissions: {:#}", e);
+        }
         loop {
             if let Err(e) = self.process_pending_settlements().await {
                 error!("Settlement worker error: {}", e);
             }
-            sleep(self.poll_interval).await;
+            if self
+                .coordinator
+                .shutdown
+                .sleep_or_wake(self.poll_interval, &self.coordinator.wakeups.settlement)
+                .await
+            {
+                break;
+            }
         }
+
+        info!("🔄 Intent settlement worker stopped");
     }
 
     async fn process_pending_settlements(&self) -> Result<()> {
-        let filled_intents = self.database.get_intents_by_status(IntentStatus::Filled)?;
+        let filled_intents = self
+            .coordinator
+            .partitions
+            .retain_owned(self.database.get_intents_by_status(IntentStatus::Filled)?);
+        let filled_intents = self
+            .coordinator
+            .jobs
+            .due(JobKind::Settlement, filled_intents)?;
 
         if filled_intents.is_empty() {
             return Ok(());
@@ -60,14 +88,66 @@ impl IntentSettlementWorker {
             filled_intents.len()
         );
 
+        let batch = select_cycle_batch(
+            filled_intents,
+            &self.coordinator.tokens,
+            &self.coordinator.token_limits,
+            MAX_CONCURRENT_SETTLEMENTS,
+        );
+
+        {
+            let mut metrics = self.coordinator.metrics.write().await;
+            metrics
+                .queue_depths
+                .insert("settlement".to_string(), batch.queue_depths);
+        }
+
         let mut tasks = Vec::new();
-        for intent in filled_intents.into_iter().take(MAX_CONCURRENT_SETTLEMENTS) {
+        for intent in batch.intents {
             let worker = self.clone_for_task();
             let task = tokio::spawn(async move {
                 let intent_id = intent.id.clone();
                 match worker.process_single_settlement(&intent).await {
-                    Ok(_) => info!("✅ Settled intent {}", &intent_id[..10]),
-                    Err(e) => error!("❌ Failed to settle intent {}: {:#?}", &intent_id[..10], e),
+                    Ok(_) => {
+                        info!("✅ Settled intent {}", parse::short(&intent_id));
+                        worker.capture(&intent_id, CaptureKind::Decision, json!({"outcome": "ok"}));
+                        if let Err(e) = worker
+                            .coordinator
+                            .jobs
+                            .succeeded(JobKind::Settlement, &intent_id)
+                        {
+                            error!("Failed to complete settlement job: {:#}", e);
+                        }
+                        let mut metrics = worker.coordinator.metrics.write().await;
+                        metrics.record_processed("settlement");
+                        metrics.intent_errors.remove(intent_id.as_str());
+                    }
+                    Err(e) => {
+                        error!(
+                            "❌ Failed to settle intent {}: {:#?}",
+                            parse::short(&intent_id),
+                            e
+                        );
+                        worker.capture(
+                            &intent_id,
+                            CaptureKind::Decision,
+                            json!({"outcome": "failed", "error": format!("{:#}", e)}),
+                        );
+                        if let Err(err) =
+                            worker
+                                .coordinator
+                                .jobs
+                                .failed(JobKind::Settlement, &intent_id, &e)
+                        {
+                            error!("Failed to record settlement job failure: {:#}", err);
+                        }
+                        worker
+                            .coordinator
+                            .metrics
+                            .write()
+                            .await
+                            .record_intent_error(&intent_id, "settlement", &e);
+                    }
                 }
             });
             tasks.push(task);
@@ -79,8 +159,24 @@ impl IntentSettlementWorker {
         Ok(())
     }
 
+    fn source_relayer(&self, source_chain: &str) -> &ChainRelayer {
+        match source_chain {
+            "ethereum" => &self.ethereum_relayer,
+            _ => &self.mantle_relayer,
+        }
+    }
+
+    fn capture(&self, intent_id: &str, kind: CaptureKind, payload: serde_json::Value) {
+        self.coordinator
+            .debug_capture
+            .record(intent_id, "settlement", kind, payload);
+    }
+
     async fn process_single_settlement(&self, intent: &Intent) -> Result<()> {
-        info!("⚙️ Processing settlement for intent {}", &intent.id[..10]);
+        info!(
+            "⚙️ Processing settlement for intent {}",
+            parse::short(&intent.id)
+        );
 
         let (source_chain, dest_chain, dest_chain_id) = match intent.source_chain.as_str() {
             "ethereum" => ("ethereum", "mantle", MANTLE_CHAIN_ID),
@@ -88,19 +184,94 @@ impl IntentSettlementWorker {
             chain => return Err(anyhow!("Unsupported source chain: {}", chain)),
         };
 
-        let is_filled = match dest_chain_id {
-            ETHEREUM_CHAIN_ID => {
-                self.ethereum_relayer
-                    .check_intent_filled(&intent.id)
+        let strategy = self
+            .coordinator
+            .settlement_strategies
+            .for_route(Chain::parse(source_chain)?, Chain::parse(dest_chain)?);
+
+        let fill = match source_chain {
+            "ethereum" => FillObservation {
+                filled_at: self.mantle_relayer.get_fill_timestamp(&intent.id).await?,
+                settled_on_source: self
+                    .ethereum_relayer
+                    .get_source_intent_state(&intent.id)
                     .await?
-            }
-            MANTLE_CHAIN_ID => self.mantle_relayer.check_intent_filled(&intent.id).await?,
+                    .0,
+            },
+            "mantle" => FillObservation {
+                filled_at: self.ethereum_relayer.get_fill_timestamp(&intent.id).await?,
+                settled_on_source: self
+                    .mantle_relayer
+                    .get_source_intent_state(&intent.id)
+                    .await?
+                    .0,
+            },
             _ => unreachable!(),
         };
+        self.capture(
+            &intent.id,
+            CaptureKind::Rpc,
+            json!({
+                "call": "fill_observation",
+                "dest_chain": dest_chain,
+                "filled_at": fill.filled_at,
+                "settled_on_source": fill.settled_on_source,
+            }),
+        );
 
-        if !is_filled {
-            warn!("⚠️ Intent {} not filled on-chain yet", &intent.id[..10]);
-            return Ok(());
+        let step = next_step(strategy, &fill, chrono::Utc::now().timestamp() as u64);
+        self.capture(
+            &intent.id,
+            CaptureKind::Decision,
+            json!({"strategy": strategy.name(), "step": format!("{:?}", step)}),
+        );
+        match step {
+            // Settling spends the deposit, which must stay refundable until
+            // the user reveals
+            SettlementStep::Settle if self.coordinator.awaiting_reveal(&intent.id)? => {
+                info!(
+                    "⏸️ Intent {} not settling yet: waiting for the user to reveal the secret",
+                    parse::short(&intent.id)
+                );
+                return Ok(());
+            }
+            // Sending would trip the fee cap; holding keeps the job's attempts
+            // for when fees come back down
+            SettlementStep::Settle
+                if self
+                    .source_relayer(source_chain)
+                    .tx_strategy
+                    .breaker
+                    .is_open() =>
+            {
+                info!(
+                    "⏸️ Intent {} not settling yet: {} fees are above the cap",
+                    parse::short(&intent.id),
+                    source_chain
+                );
+                return Ok(());
+            }
+            SettlementStep::Settle => {}
+            SettlementStep::Wait(reason) => {
+                info!(
+                    "⏸️ Intent {} not settling yet ({}): {}",
+                    parse::short(&intent.id),
+                    strategy.name(),
+                    reason
+                );
+                return Ok(());
+            }
+            SettlementStep::MarkSettled => {
+                self.database
+                    .update_intent_status(&intent.id, IntentStatus::SolverPaid)?;
+                info!(
+                    "✅ Intent {} already settled on {} ({}), marked solver_paid",
+                    parse::short(&intent.id),
+                    source_chain,
+                    strategy.name()
+                );
+                return Ok(());
+            }
         }
 
         let dest_fill_root = self
@@ -113,7 +284,10 @@ impl IntentSettlementWorker {
             )
             .await?;
 
-        info!("   Destination fill root: {}", &dest_fill_root[..18]);
+        info!(
+            "   Destination fill root: {}",
+            parse::prefix(&dest_fill_root, 18)
+        );
 
         let sync_result = tokio::time::timeout(
             Duration::from_secs(120),
@@ -154,13 +328,29 @@ impl IntentSettlementWorker {
             }
             _ => unreachable!(),
         };
+        self.capture(
+            &intent.id,
+            CaptureKind::Rpc,
+            json!({
+                "call": "settle_intent",
+                "chain": source_chain,
+                "solver": solver_address,
+                "leaf_index": leaf_index,
+                "proof_length": fill_proof.len(),
+                "tx_hash": tx_hash,
+            }),
+        );
 
         self.database
             .update_source_settlement_txid(&intent.id, &tx_hash)?;
         self.database
             .update_intent_status(&intent.id, IntentStatus::SolverPaid)?;
 
-        info!("🎉 Intent {} settled: {}", &intent.id[..10], tx_hash);
+        info!(
+            "🎉 Intent {} settled: {}",
+            parse::short(&intent.id),
+            tx_hash
+        );
 
         tokio::spawn({
             let coordinator = self.coordinator.clone();
@@ -171,13 +361,24 @@ impl IntentSettlementWorker {
 
                 match coordinator.database.get_intent_by_id(&intent_id) {
                     Ok(Some(intent)) => {
-                        info!("🔄 Auto-claiming for intent {}", &intent_id[..10]);
+                        info!("🔄 Auto-claiming for intent {}", parse::short(&intent_id));
                         if let Err(e) = coordinator.claim_for_user(&intent).await {
-                            error!("❌ Auto-claim failed for {}: {}", &intent_id[..10], e);
+                            error!(
+                                "❌ Auto-claim failed for {}: {}",
+                                parse::short(&intent_id),
+                                e
+                            );
                         }
                     }
-                    Ok(None) => error!("Intent {} not found for auto-claim", &intent_id[..10]),
-                    Err(e) => error!("DB error during auto-claim for {}: {}", &intent_id[..10], e),
+                    Ok(None) => error!(
+                        "Intent {} not found for auto-claim",
+                        parse::short(&intent_id)
+                    ),
+                    Err(e) => error!(
+                        "DB error during auto-claim for {}: {}",
+                        parse::short(&intent_id),
+                        e
+                    ),
                 }
             }
         });
@@ -185,6 +386,21 @@ impl IntentSettlementWorker {
         Ok(())
     }
 
+    /// Relayers of the source chain and of destination `dest_chain_id`.
+    fn leg(
+        &self,
+        source_chain: &str,
+        dest_chain_id: u32,
+    ) -> Result<(&ChainRelayer, &ChainRelayer)> {
+        let chains = &self.coordinator.chains;
+        let source = chains.resolve(source_chain)?;
+        let dest = chains
+            .get(dest_chain_id)
+            .filter(|dest| dest.chain_id != source.chain_id)
+            .ok_or_else(|| anyhow!("Invalid chain combination"))?;
+        Ok((source, dest))
+    }
+
     async fn wait_for_db_sync_with_fill_tree(
         &self,
         source_chain: &str,
@@ -194,6 +410,7 @@ impl IntentSettlementWorker {
         timeout: Duration,
     ) -> Result<String> {
         let start = tokio::time::Instant::now();
+        let (source, _) = self.leg(source_chain, dest_chain_id)?;
 
         info!("⏳ Waiting for DB to sync with {} fill tree...", dest_chain);
 
@@ -201,16 +418,12 @@ impl IntentSettlementWorker {
             if start.elapsed() > timeout {
                 return Err(anyhow!("Timeout waiting for DB fill tree sync"));
             }
+            // Nothing is sent yet, so the next start picks the intent up again
+            if self.coordinator.shutdown.is_triggered() {
+                return Err(anyhow!("Shutting down before the fill tree synced"));
+            }
 
-            let onchain_fill_root = match (source_chain, dest_chain_id) {
-                ("mantle", ETHEREUM_CHAIN_ID) => {
-                    self.mantle_relayer.get_synced_ethereum_fill_root().await?
-                }
-                ("ethereum", MANTLE_CHAIN_ID) => {
-                    self.ethereum_relayer.get_synced_mantle_fill_root().await?
-                }
-                _ => return Err(anyhow!("Invalid chain combination")),
-            };
+            let onchain_fill_root = source.get_synced_fill_root(dest_chain_id).await?;
 
             let db_root = self
                 .database
@@ -226,8 +439,8 @@ impl IntentSettlementWorker {
             info!(
                 "   Synced on {}: {} | DB fill: {}",
                 source_chain,
-                &onchain_fill_root[..18],
-                &db_root_normalized[..18]
+                parse::prefix(&onchain_fill_root, 18),
+                parse::prefix(&db_root_normalized, 18)
             );
 
             if onchain_fill_root.to_lowercase() == db_root_normalized {
@@ -246,28 +459,13 @@ impl IntentSettlementWorker {
         dest_chain_id: u32,
         expected_fill_root: &str,
     ) -> Result<()> {
-        let synced_root = match source_chain {
-            "ethereum" => {
-                if dest_chain_id == MANTLE_CHAIN_ID {
-                    self.ethereum_relayer.get_synced_mantle_fill_root().await?
-                } else {
-                    return Err(anyhow!("Invalid chain combination"));
-                }
-            }
-            "mantle" => {
-                if dest_chain_id == ETHEREUM_CHAIN_ID {
-                    self.mantle_relayer.get_synced_ethereum_fill_root().await?
-                } else {
-                    return Err(anyhow!("Invalid chain combination"));
-                }
-            }
-            _ => return Err(anyhow!("Unknown source chain")),
-        };
+        let (source, dest) = self.leg(source_chain, dest_chain_id)?;
+        let synced_root = source.get_synced_fill_root(dest_chain_id).await?;
 
         info!(
             "   Checking fill root sync - Expected: {} | Synced: {}",
-            &expected_fill_root[..18],
-            &synced_root[..18]
+            parse::prefix(expected_fill_root, 18),
+            parse::prefix(&synced_root, 18)
         );
 
         if synced_root.to_lowercase() == expected_fill_root.to_lowercase() {
@@ -277,35 +475,20 @@ impl IntentSettlementWorker {
 
         info!("   🔄 Fill root out of sync, triggering sync...");
 
-        match (source_chain, dest_chain_id) {
-            ("ethereum", MANTLE_CHAIN_ID) => {
-                let db_root = self.get_standardized_db_root("mantle_fills")?;
-                if db_root != ZERO_LEAF {
-                    let root_bytes = self.hex_to_bytes32(&db_root)?;
-                    self.ethereum_relayer
-                        .sync_dest_chain_fill_root_tx(MANTLE_CHAIN_ID, root_bytes)
-                        .await?;
-                }
-            }
-            ("mantle", ETHEREUM_CHAIN_ID) => {
-                let db_root = self.get_standardized_db_root("ethereum_fills")?;
-                if db_root != ZERO_LEAF {
-                    let root_bytes = self.hex_to_bytes32(&db_root)?;
-                    self.mantle_relayer
-                        .sync_dest_chain_fill_root_tx(ETHEREUM_CHAIN_ID, root_bytes)
-                        .await?;
-                }
-            }
-            _ => return Err(anyhow!("Invalid chain combination")),
+        let db_root = self.get_standardized_db_root(&format!("{}_fills", dest.key))?;
+        if db_root != ZERO_LEAF {
+            let root_bytes = parse::hex32("root", &db_root)?;
+            source
+                .sync_dest_chain_fill_root_tx(dest_chain_id, root_bytes)
+                .await?;
         }
 
-        let new_synced = match source_chain {
-            "ethereum" => self.ethereum_relayer.get_synced_mantle_fill_root().await?,
-            "mantle" => self.mantle_relayer.get_synced_ethereum_fill_root().await?,
-            _ => unreachable!(),
-        };
+        let new_synced = source.get_synced_fill_root(dest_chain_id).await?;
 
-        info!("   After sync - Synced root: {}", &new_synced[..18]);
+        info!(
+            "   After sync - Synced root: {}",
+            parse::prefix(&new_synced, 18)
+        );
 
         if new_synced.to_lowercase() != expected_fill_root.to_lowercase() {
             return Err(anyhow!(
@@ -348,13 +531,6 @@ impl IntentSettlementWorker {
         })
     }
 
-    fn hex_to_bytes32(&self, hex_str: &str) -> Result<[u8; 32]> {
-        let s = hex_str.trim_start_matches("0x");
-        let vec = hex::decode(s).map_err(|e| anyhow!("Invalid hex format: {}", e))?;
-        vec.try_into()
-            .map_err(|_| anyhow!("Hex string must be exactly 32 bytes"))
-    }
-
     fn clone_for_task(&self) -> Self {
         Self {
             database: self.database.clone(),
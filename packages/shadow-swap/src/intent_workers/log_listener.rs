@@ -0,0 +1,381 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{Context, Result};
+use ethers::{
+    abi::RawLog,
+    contract::{EthEvent, EthLogDecode},
+    providers::{Middleware, Provider, StreamExt, Ws},
+    types::{Address, Filter, H256, Log},
+};
+use mantle_core::abi::{
+    intent_pool_contract::{IntentCreatedFilter, IntentPoolContractEvents, IntentSettledFilter},
+    settlement_contract::{
+        IntentFilledFilter, IntentRegisteredFilter, SettlementContractEvents,
+        WithdrawalClaimedFilter,
+    },
+};
+use serde_json::json;
+use tokio::sync::{Notify, mpsc};
+use tracing::{debug, info, warn};
+
+use crate::{
+    api::model::IndexerEventRequest, models::model::BridgeEventType,
+    relay_coordinator::model::ChainRelayer, shutdown::Shutdown,
+};
+
+/// Whether chains with a `ws_url` stream contract logs, and how far apart
+/// resubscription attempts may grow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogListenerPolicy {
+    pub enabled: bool,
+    pub max_backoff: Duration,
+}
+
+impl Default for LogListenerPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_backoff: Duration::from_secs(60),
+        }
+    }
+}
+
+impl LogListenerPolicy {
+    /// Reads `LOG_LISTENER_ENABLED` and `LOG_LISTENER_MAX_BACKOFF_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(enabled) = std::env::var("LOG_LISTENER_ENABLED") {
+            policy.enabled = enabled
+                .trim()
+                .parse()
+                .context("Invalid LOG_LISTENER_ENABLED")?;
+        }
+        if let Ok(secs) = std::env::var("LOG_LISTENER_MAX_BACKOFF_SECS") {
+            let secs: u64 = secs
+                .trim()
+                .parse()
+                .context("Invalid LOG_LISTENER_MAX_BACKOFF_SECS")?;
+            policy.max_backoff = Duration::from_secs(secs.max(1));
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Lets an event cut a worker's poll interval short. A wake with nobody
+/// waiting is kept for the worker's next sleep.
+#[derive(Debug, Clone, Default)]
+pub struct WorkerWakeups {
+    pub registration: Arc<Notify>,
+    pub settlement: Arc<Notify>,
+    pub claims: Arc<Notify>,
+    /// New third-party fill reports to verify.
+    pub fill_reports: Arc<Notify>,
+}
+
+impl WorkerWakeups {
+    /// Wake the worker that acts next on an intent after `event`.
+    pub fn wake(&self, event: BridgeEventType) {
+        match event {
+            BridgeEventType::IntentCreated => self.registration.notify_one(),
+            BridgeEventType::IntentFilled => self.settlement.notify_one(),
+            BridgeEventType::IntentSettled => self.claims.notify_one(),
+            _ => {}
+        }
+    }
+}
+
+/// Streams one chain's pool and settlement logs over `eth_subscribe` and
+/// hands them on in the indexer's format. The indexer webhook and the
+/// workers' polling stay as the fallback for anything missed while the
+/// socket is down.
+pub struct LogListener {
+    relayer: Arc<ChainRelayer>,
+    ws_url: String,
+    policy: LogListenerPolicy,
+    events: mpsc::Sender<IndexerEventRequest>,
+    shutdown: Shutdown,
+}
+
+impl LogListener {
+    pub fn new(
+        relayer: Arc<ChainRelayer>,
+        ws_url: String,
+        policy: LogListenerPolicy,
+        events: mpsc::Sender<IndexerEventRequest>,
+        shutdown: Shutdown,
+    ) -> Self {
+        Self {
+            relayer,
+            ws_url,
+            policy,
+            events,
+            shutdown,
+        }
+    }
+
+    pub async fn run(&self) {
+        let filter = Filter::new()
+            .address(vec![
+                self.relayer.intent_pool.address(),
+                self.relayer.settlement.address(),
+            ])
+            .topic0(topics());
+        let mut backoff = Duration::from_secs(1);
+
+        loop {
+            match self.stream(&filter, &mut backoff).await {
+                Ok(()) if self.shutdown.is_triggered() => break,
+                Ok(()) => warn!(
+                    "⚠️ [{}] Log subscription closed, resubscribing in {:?}",
+                    self.relayer.name, backoff
+                ),
+                Err(e) => warn!(
+                    "⚠️ [{}] Log subscription failed: {:#}. Retrying in {:?}",
+                    self.relayer.name, e, backoff
+                ),
+            }
+
+            if self.shutdown.sleep(backoff).await {
+                break;
+            }
+            backoff = (backoff * 2).min(self.policy.max_backoff);
+        }
+
+        info!("📡 [{}] Log listener stopped", self.relayer.name);
+    }
+
+    /// Forward logs until the subscription ends or shutdown starts.
+    async fn stream(&self, filter: &Filter, backoff: &mut Duration) -> Result<()> {
+        let provider = Provider::<Ws>::connect(self.ws_url.as_str())
+            .await
+            .context("Failed to connect")?;
+        let mut logs = provider
+            .subscribe_logs(filter)
+            .await
+            .context("Failed to subscribe")?;
+
+        info!("📡 [{}] Subscribed to contract logs", self.relayer.name);
+        *backoff = Duration::from_secs(1);
+
+        loop {
+            let log = tokio::select! {
+                log = logs.next() => log,
+                _ = self.shutdown.wait() => return Ok(()),
+            };
+            let Some(log) = log else {
+                return Ok(());
+            };
+
+            let Some(mut request) =
+                event_request(self.relayer.key, self.relayer.intent_pool.address(), log)
+            else {
+                continue;
+            };
+            debug!(
+                "📡 [{}] {} in {}",
+                self.relayer.name, request.event_type, request.transaction_hash
+            );
+
+            if request.event_type == BridgeEventType::IntentCreated.as_str()
+                && let Err(e) = add_refund_address(&self.relayer, &mut request).await
+            {
+                // Left to the indexer, so the depositor is never recorded blank
+                warn!(
+                    "⚠️ [{}] Skipping intent_created in {}: {:#}",
+                    self.relayer.name, request.transaction_hash, e
+                );
+                continue;
+            }
+
+            if self.events.send(request).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// `IntentCreated` does not carry the refund address; read it from the
+/// pool.
+pub async fn add_refund_address(
+    relayer: &ChainRelayer,
+    request: &mut IndexerEventRequest,
+) -> Result<()> {
+    let intent_id = request.event_data["intentId"]
+        .as_str()
+        .context("Missing intentId")?;
+    let id = mantle_core::parse::hex32("intent_id", intent_id)?;
+
+    let (.., refund_to, _, _) = relayer
+        .intent_pool
+        .get_intent(id)
+        .call()
+        .await
+        .context("Failed to read intent")?;
+    request.event_data["refundAddress"] = json!(format!("{:?}", refund_to));
+    Ok(())
+}
+
+pub fn topics() -> Vec<H256> {
+    vec![
+        IntentCreatedFilter::signature(),
+        IntentSettledFilter::signature(),
+        IntentRegisteredFilter::signature(),
+        IntentFilledFilter::signature(),
+        WithdrawalClaimedFilter::signature(),
+    ]
+}
+
+fn hex32(bytes: [u8; 32]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+/// The indexer payload for a confirmed log of `chain`'s pool (at `pool`)
+/// or settlement contract. `None` for removed, pending or unrelated logs.
+pub fn event_request(chain: &str, pool: Address, log: Log) -> Option<IndexerEventRequest> {
+    if log.removed == Some(true) {
+        return None;
+    }
+    let transaction_hash = log.transaction_hash?;
+    let block_number = log.block_number?.as_u64();
+    let log_index = log.log_index?.as_u32();
+    let from_pool = log.address == pool;
+    let raw = RawLog::from(log);
+
+    let (event_type, event_data) = if from_pool {
+        match IntentPoolContractEvents::decode_log(&raw).ok()? {
+            IntentPoolContractEvents::IntentCreatedFilter(event) => (
+                BridgeEventType::IntentCreated,
+                json!({
+                    "intentId": hex32(event.intent_id),
+                    "commitment": hex32(event.commitment),
+                    "sourceToken": format!("{:?}", event.source_token),
+                    "sourceAmount": event.source_amount.to_string(),
+                    "destToken": format!("{:?}", event.dest_token),
+                    "destAmount": event.dest_amount.to_string(),
+                    "destChain": event.dest_chain.to_string(),
+                }),
+            ),
+            IntentPoolContractEvents::IntentSettledFilter(event) => (
+                BridgeEventType::IntentSettled,
+                json!({
+                    "intentId": hex32(event.intent_id),
+                    "solver": format!("{:?}", event.solver),
+                    "fillRoot": hex32(event.fill_root),
+                }),
+            ),
+        }
+    } else {
+        match SettlementContractEvents::decode_log(&raw).ok()? {
+            SettlementContractEvents::IntentRegisteredFilter(event) => (
+                BridgeEventType::IntentRegistered,
+                json!({
+                    "intentId": hex32(event.intent_id),
+                    "commitment": hex32(event.commitment),
+                    "sourceChain": event.source_chain.to_string(),
+                }),
+            ),
+            SettlementContractEvents::IntentFilledFilter(event) => (
+                BridgeEventType::IntentFilled,
+                json!({
+                    "intentId": hex32(event.intent_id),
+                    "solver": format!("{:?}", event.solver),
+                    "token": format!("{:?}", event.token),
+                    "amount": event.amount.to_string(),
+                }),
+            ),
+            SettlementContractEvents::WithdrawalClaimedFilter(event) => (
+                BridgeEventType::WithdrawalClaimed,
+                json!({
+                    "intentId": hex32(event.intent_id),
+                    "nullifier": hex32(event.nullifier),
+                }),
+            ),
+            SettlementContractEvents::OrphanedFillRecoveredFilter(_) => return None,
+        }
+    };
+
+    Some(IndexerEventRequest {
+        event_type: event_type.as_str().to_string(),
+        chain: chain.to_string(),
+        transaction_hash: format!("{:?}", transaction_hash),
+        block_number,
+        event_data,
+        log_index,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        abi::{Token, encode},
+        types::{U64, U256},
+    };
+
+    fn log(address: Address, topics: Vec<H256>, data: Vec<u8>) -> Log {
+        Log {
+            address,
+            topics,
+            data: data.into(),
+            transaction_hash: Some(H256::repeat_byte(0xaa)),
+            block_number: Some(U64::from(42)),
+            log_index: Some(U256::from(3)),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_logs_become_indexer_events() {
+        let pool = Address::repeat_byte(1);
+        let settlement = Address::repeat_byte(2);
+        let solver = Address::repeat_byte(9);
+        let intent_id = H256::repeat_byte(7);
+
+        let created = log(
+            pool,
+            vec![
+                IntentCreatedFilter::signature(),
+                intent_id,
+                H256::repeat_byte(8),
+            ],
+            encode(&[
+                Token::Uint(5003.into()),
+                Token::Address(Address::repeat_byte(3)),
+                Token::Uint(1_000.into()),
+                Token::Address(Address::repeat_byte(4)),
+                Token::Uint(999.into()),
+            ]),
+        );
+        let request = event_request("ethereum", pool, created).unwrap();
+        assert_eq!(request.event_type, "intent_created");
+        assert_eq!(request.chain, "ethereum");
+        assert_eq!((request.block_number, request.log_index), (42, 3));
+        assert_eq!(request.event_data["intentId"], hex32(intent_id.0));
+        assert_eq!(request.event_data["destChain"], "5003");
+        assert_eq!(request.event_data["sourceAmount"], "1000");
+
+        let filled = log(
+            settlement,
+            vec![
+                IntentFilledFilter::signature(),
+                intent_id,
+                H256::from(solver),
+                H256::from(Address::repeat_byte(4)),
+            ],
+            encode(&[Token::Uint(999.into())]),
+        );
+        let request = event_request("mantle", pool, filled).unwrap();
+        assert_eq!(request.event_type, "intent_filled");
+        assert_eq!(request.event_data["solver"], format!("{:?}", solver));
+
+        let mut removed = log(
+            settlement,
+            vec![IntentFilledFilter::signature(), intent_id],
+            vec![],
+        );
+        removed.removed = Some(true);
+        assert!(event_request("mantle", pool, removed).is_none());
+    }
+}
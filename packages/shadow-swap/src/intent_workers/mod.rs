@@ -1,3 +1,11 @@
 pub mod event_sync;
+pub mod fairness;
+pub mod fill_report_verifier;
 pub mod intent_registration_worker;
 pub mod intent_settlement_worker;
+pub mod log_listener;
+pub mod queue_metrics;
+pub mod reorg_monitor;
+pub mod stuck_detector;
+pub mod sync_progress;
+pub mod tx_repair;
@@ -0,0 +1,213 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::{database::model::IntentStatusGauge, relay_coordinator::model::BridgeCoordinator};
+
+/// Window the per-worker processing rate is averaged over.
+const RATE_WINDOW: Duration = Duration::from_secs(300);
+
+/// Completions of one worker inside the rate window.
+#[derive(Debug, Clone, Default)]
+pub struct ProcessingRate {
+    completed: VecDeque<Instant>,
+}
+
+impl ProcessingRate {
+    pub fn record(&mut self, now: Instant) {
+        self.completed.push_back(now);
+        self.evict(now);
+    }
+
+    /// Items completed per minute over the last five minutes.
+    pub fn per_minute(&mut self, now: Instant) -> f64 {
+        self.evict(now);
+        self.completed.len() as f64 / (RATE_WINDOW.as_secs_f64() / 60.0)
+    }
+
+    fn evict(&mut self, now: Instant) {
+        while let Some(oldest) = self.completed.front() {
+            if now.duration_since(*oldest) > RATE_WINDOW {
+                self.completed.pop_front();
+            } else {
+                break;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct QueueAlertPolicy {
+    /// Unhealthy once the oldest pending intent is older than this.
+    pub max_oldest_pending_secs: u64,
+}
+
+impl QueueAlertPolicy {
+    pub fn from_env() -> Self {
+        Self {
+            max_oldest_pending_secs: std::env::var("QUEUE_MAX_OLDEST_PENDING_SECS")
+                .unwrap_or_else(|_| "1800".to_string())
+                .parse()
+                .unwrap_or(1800),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StatusGauge {
+    pub count: u64,
+    pub oldest_age_secs: u64,
+}
+
+/// Queue gauges exported on `/metrics` and checked by `/health`.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueueHealth {
+    pub healthy: bool,
+    pub pending_by_status: HashMap<String, StatusGauge>,
+    pub oldest_pending_age_secs: u64,
+    pub queue_depths: HashMap<String, usize>,
+    pub processing_rate_per_min: HashMap<String, f64>,
+    pub alerts: Vec<String>,
+}
+
+pub fn evaluate_queue_health(
+    gauges: &[IntentStatusGauge],
+    now: DateTime<Utc>,
+    queue_depths: &HashMap<String, HashMap<String, usize>>,
+    processing_rate_per_min: HashMap<String, f64>,
+    policy: &QueueAlertPolicy,
+) -> QueueHealth {
+    let pending_by_status: HashMap<String, StatusGauge> = gauges
+        .iter()
+        .map(|gauge| {
+            let oldest_age_secs = gauge
+                .oldest_created_at
+                .map(|created| (now - created).num_seconds().max(0) as u64)
+                .unwrap_or(0);
+
+            (
+                gauge.status.clone(),
+                StatusGauge {
+                    count: gauge.count,
+                    oldest_age_secs,
+                },
+            )
+        })
+        .collect();
+
+    let mut alerts = Vec::new();
+    for (status, gauge) in &pending_by_status {
+        if gauge.oldest_age_secs > policy.max_oldest_pending_secs {
+            alerts.push(format!(
+                "oldest '{}' intent is {}s old (threshold {}s)",
+                status, gauge.oldest_age_secs, policy.max_oldest_pending_secs
+            ));
+        }
+    }
+    alerts.sort();
+
+    let oldest_pending_age_secs = pending_by_status
+        .values()
+        .map(|g| g.oldest_age_secs)
+        .max()
+        .unwrap_or(0);
+
+    let queue_depths = queue_depths
+        .iter()
+        .map(|(queue, tokens)| (queue.clone(), tokens.values().sum()))
+        .collect();
+
+    QueueHealth {
+        healthy: alerts.is_empty(),
+        pending_by_status,
+        oldest_pending_age_secs,
+        queue_depths,
+        processing_rate_per_min,
+        alerts,
+    }
+}
+
+impl BridgeCoordinator {
+    pub async fn queue_health(&self) -> Result<QueueHealth> {
+        let gauges = self.database.get_intent_status_gauges()?;
+
+        let (queue_depths, rates) = {
+            let mut metrics = self.metrics.write().await;
+            let now = Instant::now();
+            let rates = metrics
+                .processing_rates
+                .iter_mut()
+                .map(|(worker, rate)| (worker.clone(), rate.per_minute(now)))
+                .collect();
+            (metrics.queue_depths.clone(), rates)
+        };
+
+        Ok(evaluate_queue_health(
+            &gauges,
+            Utc::now(),
+            &queue_depths,
+            rates,
+            &self.queue_alerts,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oldest_pending_over_threshold_is_unhealthy() {
+        let now = Utc::now();
+        let gauges = vec![
+            IntentStatusGauge {
+                status: "committed".to_string(),
+                count: 4,
+                oldest_created_at: Some(now - chrono::Duration::seconds(120)),
+            },
+            IntentStatusGauge {
+                status: "filled".to_string(),
+                count: 2,
+                oldest_created_at: Some(now - chrono::Duration::seconds(4000)),
+            },
+        ];
+        let depths = HashMap::from([(
+            "settlement".to_string(),
+            HashMap::from([("USDC".to_string(), 3), ("ETH".to_string(), 1)]),
+        )]);
+        let policy = QueueAlertPolicy {
+            max_oldest_pending_secs: 1800,
+        };
+
+        let health = evaluate_queue_health(&gauges, now, &depths, HashMap::new(), &policy);
+
+        assert!(!health.healthy);
+        assert_eq!(health.alerts.len(), 1);
+        assert_eq!(health.oldest_pending_age_secs, 4000);
+        assert_eq!(health.queue_depths.get("settlement"), Some(&4));
+        assert_eq!(health.pending_by_status["committed"].count, 4);
+
+        let relaxed = QueueAlertPolicy {
+            max_oldest_pending_secs: 5000,
+        };
+        assert!(evaluate_queue_health(&gauges, now, &depths, HashMap::new(), &relaxed).healthy);
+    }
+
+    #[test]
+    fn test_processing_rate_window() {
+        let start = Instant::now();
+        let mut rate = ProcessingRate::default();
+
+        for _ in 0..10 {
+            rate.record(start);
+        }
+
+        assert_eq!(rate.per_minute(start), 2.0);
+        assert_eq!(rate.per_minute(start + Duration::from_secs(301)), 0.0);
+    }
+}
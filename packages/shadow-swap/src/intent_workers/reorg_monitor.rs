@@ -0,0 +1,406 @@
+use std::{collections::BTreeSet, sync::Arc, time::Duration};
+
+use actix_web::web;
+use anyhow::{Context, Result};
+use ethers::{
+    providers::Middleware,
+    types::{BlockNumber, Filter},
+};
+use serde_json::json;
+use tracing::{error, info, warn};
+
+use crate::{
+    AppState,
+    api::helper::dispatch_indexer_event,
+    intent_workers::log_listener::{add_refund_address, event_request, topics},
+    models::model::{BridgeEventType, IntentStatus},
+    relay_coordinator::{model::ChainRelayer, reconcile::find_mismatch},
+    shutdown::Shutdown,
+};
+
+/// How many recent block hashes are kept per chain and how often they are
+/// compared with the chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReorgPolicy {
+    pub enabled: bool,
+    /// Reorgs deeper than this are still rolled back, but only from the
+    /// oldest tracked block.
+    pub track_blocks: u64,
+    pub interval: Duration,
+}
+
+impl Default for ReorgPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            track_blocks: 64,
+            interval: Duration::from_secs(15),
+        }
+    }
+}
+
+impl ReorgPolicy {
+    /// Reads `REORG_MONITOR_ENABLED`, `REORG_TRACK_BLOCKS` and
+    /// `REORG_CHECK_INTERVAL_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(enabled) = std::env::var("REORG_MONITOR_ENABLED") {
+            policy.enabled = enabled
+                .trim()
+                .parse()
+                .context("Invalid REORG_MONITOR_ENABLED")?;
+        }
+        if let Ok(blocks) = std::env::var("REORG_TRACK_BLOCKS") {
+            let blocks: u64 = blocks
+                .trim()
+                .parse()
+                .context("Invalid REORG_TRACK_BLOCKS")?;
+            policy.track_blocks = blocks.max(1);
+        }
+        if let Ok(secs) = std::env::var("REORG_CHECK_INTERVAL_SECS") {
+            let secs: u64 = secs
+                .trim()
+                .parse()
+                .context("Invalid REORG_CHECK_INTERVAL_SECS")?;
+            policy.interval = Duration::from_secs(secs.max(1));
+        }
+
+        Ok(policy)
+    }
+}
+
+/// The first height at which `tracked` (newest first) left the chain, given
+/// the canonical hashes read for the same heights, newest first, up to and
+/// including the first one that still matched. `None` when the newest
+/// tracked block is still canonical.
+pub fn fork_point(tracked: &[(i64, String)], canonical: &[(i64, Option<String>)]) -> Option<i64> {
+    tracked
+        .iter()
+        .zip(canonical)
+        .take_while(|((_, hash), (_, found))| found.as_deref() != Some(hash.as_str()))
+        .map(|((number, _), _)| *number)
+        .last()
+}
+
+/// Keeps the hashes of one chain's last blocks and, when they stop matching
+/// the chain, undoes what was indexed from the dropped blocks and replays
+/// the canonical logs in their place.
+pub struct ReorgMonitor {
+    relayer: Arc<ChainRelayer>,
+    policy: ReorgPolicy,
+    app_state: web::Data<AppState>,
+    shutdown: Shutdown,
+}
+
+impl ReorgMonitor {
+    pub fn new(
+        relayer: Arc<ChainRelayer>,
+        policy: ReorgPolicy,
+        app_state: web::Data<AppState>,
+        shutdown: Shutdown,
+    ) -> Self {
+        Self {
+            relayer,
+            policy,
+            app_state,
+            shutdown,
+        }
+    }
+
+    pub async fn run(&self) {
+        loop {
+            if let Err(e) = self.check().await {
+                error!("❌ [{}] Reorg check failed: {:#}", self.relayer.name, e);
+            }
+
+            if self.shutdown.sleep(self.policy.interval).await {
+                break;
+            }
+        }
+
+        info!("🔀 [{}] Reorg monitor stopped", self.relayer.name);
+    }
+
+    async fn check(&self) -> Result<()> {
+        let database = &self.app_state.database;
+        let head = self
+            .relayer
+            .client
+            .get_block_number()
+            .await
+            .context("Failed to read block number")?
+            .as_u64() as i64;
+        let tracked = database.get_block_hashes(self.relayer.key)?;
+
+        // Each block commits to its parent, so the chain below the newest
+        // block that still matches is unchanged
+        let mut canonical = Vec::new();
+        let mut anchored = false;
+        for (number, hash) in &tracked {
+            let found = self.block_hash(*number).await?;
+            anchored = found.as_deref() == Some(hash.as_str());
+            canonical.push((*number, found));
+            if anchored {
+                break;
+            }
+        }
+
+        let mut newest = tracked.first().map(|(number, _)| *number);
+        if let Some(fork) = fork_point(&tracked, &canonical) {
+            if !anchored {
+                warn!(
+                    "⚠️ [{}] Reorg is deeper than the {} tracked blocks, rolling back from {}",
+                    self.relayer.name, self.policy.track_blocks, fork
+                );
+            }
+            self.recover(fork, head, tracked[0].0 - fork + 1).await?;
+            newest = Some(fork - 1);
+        }
+
+        let from = newest
+            .map_or(i64::MIN, |n| n + 1)
+            .max(head - self.policy.track_blocks as i64 + 1)
+            .max(0);
+        let mut blocks = Vec::new();
+        for number in from..=head {
+            if let Some(hash) = self.block_hash(number).await? {
+                blocks.push((number, hash));
+            }
+        }
+        database.record_block_hashes(self.relayer.key, &blocks, self.policy.track_blocks as i64)
+    }
+
+    async fn block_hash(&self, number: i64) -> Result<Option<String>> {
+        let block = self
+            .relayer
+            .client
+            .get_block(BlockNumber::Number((number as u64).into()))
+            .await
+            .with_context(|| format!("Failed to read block {}", number))?;
+        Ok(block.and_then(|b| b.hash).map(|hash| format!("{:?}", hash)))
+    }
+
+    /// Roll the chain's indexed state back to before `fork`, replay the
+    /// canonical logs up to `head`, rebuild its trees and correct the
+    /// intents the dropped blocks touched.
+    async fn recover(&self, fork: i64, head: i64, depth: i64) -> Result<()> {
+        let state = &self.app_state;
+        let relayer = &self.relayer;
+
+        warn!(
+            "🔀 [{}] Reorg detected at block {} ({} blocks), rolling back",
+            relayer.name, fork, depth
+        );
+
+        let pool = relayer.intent_pool.address();
+        let filter = Filter::new()
+            .address(vec![pool, relayer.settlement.address()])
+            .topic0(topics())
+            .from_block(fork as u64)
+            .to_block(head as u64);
+        let logs = relayer
+            .client
+            .get_logs(&filter)
+            .await
+            .context("Failed to read canonical logs")?;
+
+        // Read before rolling back, so a failed read leaves the fork to be
+        // found again on the next pass
+        let rollback = state
+            .database
+            .rollback_chain_events(relayer.key, relayer.chain_id, fork)?;
+
+        let mut replayed = 0;
+        for log in logs {
+            let Some(mut request) = event_request(relayer.key, pool, log) else {
+                continue;
+            };
+            if request.event_type == BridgeEventType::IntentCreated.as_str()
+                && let Err(e) = add_refund_address(relayer, &mut request).await
+            {
+                warn!(
+                    "⚠️ [{}] Not replaying intent_created in {}: {:#}",
+                    relayer.name, request.transaction_hash, e
+                );
+                continue;
+            }
+
+            let response = dispatch_indexer_event(state, &request).await;
+            if response.status().is_success() {
+                replayed += 1;
+            } else {
+                warn!(
+                    "⚠️ [{}] Replaying {} from {} failed ({})",
+                    relayer.name,
+                    request.event_type,
+                    request.transaction_hash,
+                    response.status()
+                );
+            }
+        }
+
+        state
+            .merkle_manager
+            .rebuild_chain_trees_from_scratch(relayer.key)
+            .await?;
+
+        let affected: BTreeSet<String> = rollback
+            .events
+            .iter()
+            .filter_map(|(intent_id, _)| intent_id.clone())
+            .chain(rollback.unpositioned_intents.iter().cloned())
+            .collect();
+        for intent_id in &affected {
+            if let Err(e) = self.correct_status(intent_id).await {
+                error!("❌ Failed to correct {} after reorg: {:#}", intent_id, e);
+            }
+        }
+
+        warn!(
+            "🔀 [{}] Reorg recovered: {} events removed, {} replayed, {} intents checked",
+            relayer.name,
+            rollback.events.len(),
+            replayed,
+            affected.len()
+        );
+
+        let payload = json!({
+            "chain": relayer.key,
+            "fork_block": fork,
+            "depth": depth,
+            "events_removed": rollback.events.len(),
+            "events_replayed": replayed,
+            "intents_affected": affected,
+        });
+        state.webhooks.dispatch("chain_reorg", &payload).await;
+
+        Ok(())
+    }
+
+    /// Move an intent to the status the chains now prove. One whose creation
+    /// only existed on the dropped fork is marked failed.
+    async fn correct_status(&self, intent_id: &str) -> Result<()> {
+        let state = &self.app_state;
+        let Some(intent) = state.database.get_intent_by_id(intent_id)? else {
+            return Ok(());
+        };
+        let on_chain = state
+            .bridge_coordinator
+            .read_on_chain_state(&intent)
+            .await?;
+        if find_mismatch(&intent, on_chain).is_none() {
+            return Ok(());
+        }
+
+        let status = on_chain.expected_status().unwrap_or(IntentStatus::Failed);
+        if status == intent.status {
+            return Ok(());
+        }
+        state.database.update_intent_status(intent_id, status)?;
+        info!(
+            "🔧 {} moved {} -> {} after reorg",
+            intent_id,
+            intent.status.as_str(),
+            status.as_str()
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{IntentFactory, random_hash, test_database};
+    use serial_test::serial;
+
+    fn tracked(blocks: &[(i64, &str)]) -> Vec<(i64, String)> {
+        blocks
+            .iter()
+            .map(|(number, hash)| (*number, hash.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_fork_point_is_lowest_dropped_block() {
+        let tracked = tracked(&[(103, "0xd"), (102, "0xc"), (101, "0xb"), (100, "0xa")]);
+
+        let unchanged = [(103, Some("0xd".to_string()))];
+        assert_eq!(fork_point(&tracked, &unchanged), None);
+
+        let reorged = [
+            (103, Some("0xd2".to_string())),
+            (102, Some("0xc2".to_string())),
+            (101, Some("0xb".to_string())),
+        ];
+        assert_eq!(fork_point(&tracked, &reorged), Some(102));
+
+        // The chain is shorter than what was tracked
+        let shortened = [(103, None), (102, Some("0xc".to_string()))];
+        assert_eq!(fork_point(&tracked, &shortened), Some(103));
+
+        let deep = [
+            (103, Some("0xd2".to_string())),
+            (102, Some("0xc2".to_string())),
+            (101, Some("0xb2".to_string())),
+            (100, Some("0xa2".to_string())),
+        ];
+        assert_eq!(fork_point(&tracked, &deep), Some(100));
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_rollback_drops_events_and_positions_from_fork() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let mut kept = IntentFactory::created().on_ethereum().build();
+        kept.block_number = Some(100);
+        database.create_intent(&kept)?;
+        let mut dropped = IntentFactory::created().on_ethereum().build();
+        dropped.block_number = Some(110);
+        database.create_intent(&dropped)?;
+
+        for (intent, block) in [(&kept, 100), (&dropped, 110)] {
+            database.store_bridge_event(
+                &format!("intent_created_ethereum_{}", block),
+                Some(&intent.id),
+                BridgeEventType::IntentCreated,
+                json!({}),
+                11155111,
+                block,
+                Some(0),
+                &random_hash(),
+            )?;
+        }
+        let hashes: Vec<_> = (100..=110).map(|n| (n, format!("0x{:x}", n))).collect();
+        database.record_block_hashes("ethereum", &hashes, 8)?;
+        assert_eq!(database.get_block_hashes("ethereum")?.len(), 8);
+
+        let rollback = database.rollback_chain_events("ethereum", 11155111, 105)?;
+
+        assert_eq!(
+            rollback.events,
+            vec![(
+                Some(dropped.id.to_string()),
+                BridgeEventType::IntentCreated.as_str().to_string()
+            )]
+        );
+        assert_eq!(rollback.unpositioned_intents, vec![dropped.id.to_string()]);
+        assert_eq!(database.get_intent_bridge_events(&kept.id)?.len(), 1);
+        assert_eq!(
+            database
+                .get_intent_by_id(&dropped.id)?
+                .and_then(|i| i.block_number),
+            None
+        );
+        assert_eq!(
+            database.get_block_hashes("ethereum")?.first().map(|b| b.0),
+            Some(104)
+        );
+
+        Ok(())
+    }
+}
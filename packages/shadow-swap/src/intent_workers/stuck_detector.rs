@@ -0,0 +1,482 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use mantle_core::{chain::Chain, http_client::OutboundClient};
+use serde::Serialize;
+use tokio::{sync::RwLock, time::interval};
+use tracing::{error, info, warn};
+
+use crate::{
+    models::model::{Intent, IntentError, IntentStatus},
+    relay_coordinator::{model::BridgeCoordinator, settlement_strategy::SettlementStep},
+    webhooks::webhook_dispatcher::WebhookDispatcher,
+};
+
+/// Age thresholds (seconds in the current status) at which an intent
+/// escalates from warning to critical.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StuckThresholds {
+    pub warning_secs: u64,
+    pub critical_secs: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct StuckPolicy {
+    pub enabled: bool,
+    pub interval: Duration,
+    pub committed: StuckThresholds,
+    pub filled: StuckThresholds,
+    /// Receives an unsigned POST for every escalation, on top of the
+    /// endpoints registered through `/admin/webhooks`.
+    pub webhook_url: Option<String>,
+}
+
+impl Default for StuckPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(60),
+            committed: StuckThresholds {
+                warning_secs: 900,
+                critical_secs: 3600,
+            },
+            filled: StuckThresholds {
+                warning_secs: 1800,
+                critical_secs: 7200,
+            },
+            webhook_url: None,
+        }
+    }
+}
+
+impl StuckPolicy {
+    /// Reads `STUCK_DETECTOR_ENABLED`, `STUCK_CHECK_INTERVAL_SECS`,
+    /// `STUCK_COMMITTED_WARNING_SECS`, `STUCK_COMMITTED_CRITICAL_SECS`,
+    /// `STUCK_FILLED_WARNING_SECS`, `STUCK_FILLED_CRITICAL_SECS` and
+    /// `STUCK_WEBHOOK_URL`.
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(format!("STUCK_{}", name)).ok();
+        let secs = |name: &str, default: u64| -> Result<u64> {
+            match var(name) {
+                Some(value) => value
+                    .parse()
+                    .with_context(|| format!("Invalid STUCK_{}", name)),
+                None => Ok(default),
+            }
+        };
+        let defaults = Self::default();
+
+        let policy = Self {
+            enabled: match var("DETECTOR_ENABLED") {
+                Some(enabled) => enabled.parse().context("Invalid STUCK_DETECTOR_ENABLED")?,
+                None => defaults.enabled,
+            },
+            interval: Duration::from_secs(
+                secs("CHECK_INTERVAL_SECS", defaults.interval.as_secs())?.max(1),
+            ),
+            committed: StuckThresholds {
+                warning_secs: secs("COMMITTED_WARNING_SECS", defaults.committed.warning_secs)?,
+                critical_secs: secs("COMMITTED_CRITICAL_SECS", defaults.committed.critical_secs)?,
+            },
+            filled: StuckThresholds {
+                warning_secs: secs("FILLED_WARNING_SECS", defaults.filled.warning_secs)?,
+                critical_secs: secs("FILLED_CRITICAL_SECS", defaults.filled.critical_secs)?,
+            },
+            webhook_url: var("WEBHOOK_URL").filter(|url| !url.trim().is_empty()),
+        };
+
+        for (status, thresholds) in [("COMMITTED", policy.committed), ("FILLED", policy.filled)] {
+            if thresholds.warning_secs > thresholds.critical_secs {
+                return Err(anyhow::anyhow!(
+                    "STUCK_{}_WARNING_SECS must not exceed STUCK_{}_CRITICAL_SECS",
+                    status,
+                    status
+                ));
+            }
+        }
+
+        Ok(policy)
+    }
+
+    pub fn level(&self, status: IntentStatus, age_secs: u64) -> Option<StuckLevel> {
+        let thresholds = match status {
+            IntentStatus::Committed => self.committed,
+            IntentStatus::Filled => self.filled,
+            _ => return None,
+        };
+
+        if age_secs >= thresholds.critical_secs {
+            Some(StuckLevel::Critical)
+        } else if age_secs >= thresholds.warning_secs {
+            Some(StuckLevel::Warning)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StuckLevel {
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StuckIntent {
+    pub intent_id: String,
+    pub status: String,
+    pub level: StuckLevel,
+    pub source_chain: String,
+    pub dest_chain: String,
+    pub source_token: String,
+    pub amount: String,
+    /// Seconds since the intent entered its current status.
+    pub age_secs: u64,
+    pub status_since: DateTime<Utc>,
+    pub deadline: u64,
+    pub last_error: Option<IntentError>,
+    pub next_action: String,
+}
+
+/// Gauges exported on `/metrics`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StuckSummary {
+    pub warning: usize,
+    pub critical: usize,
+    pub by_status: HashMap<String, usize>,
+    pub oldest_age_secs: u64,
+    pub escalations_total: u64,
+    pub webhook_failures_total: u64,
+    pub last_scan_at: Option<DateTime<Utc>>,
+}
+
+/// Intents whose level rose since the last scan. `notified` keeps the level
+/// each stuck intent was last announced at; recovered intents drop out.
+pub fn escalations<'a>(
+    stuck: &'a [StuckIntent],
+    notified: &mut HashMap<String, StuckLevel>,
+) -> Vec<&'a StuckIntent> {
+    let current: HashSet<&str> = stuck.iter().map(|s| s.intent_id.as_str()).collect();
+    notified.retain(|intent_id, _| current.contains(intent_id.as_str()));
+
+    stuck
+        .iter()
+        .filter(|s| {
+            let previous = notified.insert(s.intent_id.clone(), s.level);
+            previous.is_none_or(|previous| previous < s.level)
+        })
+        .collect()
+}
+
+/// Finds intents sitting in `committed` or `filled` for too long and
+/// escalates them through logs, `/metrics` and webhooks.
+pub struct StuckDetector {
+    pub policy: StuckPolicy,
+    coordinator: Arc<BridgeCoordinator>,
+    webhooks: Arc<WebhookDispatcher>,
+    http: OutboundClient,
+    summary: RwLock<StuckSummary>,
+    notified: RwLock<HashMap<String, StuckLevel>>,
+}
+
+impl StuckDetector {
+    pub fn new(
+        policy: StuckPolicy,
+        coordinator: Arc<BridgeCoordinator>,
+        webhooks: Arc<WebhookDispatcher>,
+        http: OutboundClient,
+    ) -> Self {
+        Self {
+            policy,
+            coordinator,
+            webhooks,
+            http,
+            summary: RwLock::new(StuckSummary::default()),
+            notified: RwLock::new(HashMap::new()),
+        }
+    }
+
+    pub async fn summary(&self) -> StuckSummary {
+        self.summary.read().await.clone()
+    }
+
+    /// Current stuck intents, oldest first.
+    pub async fn scan(&self) -> Result<Vec<StuckIntent>> {
+        let database = &self.coordinator.database;
+        let now = Utc::now();
+
+        let mut intents = database.get_intents_by_status(IntentStatus::Committed)?;
+        intents.extend(database.get_intents_by_status(IntentStatus::Filled)?);
+
+        // Errors of intents that left these queues are of no further use
+        let errors = {
+            let mut metrics = self.coordinator.metrics.write().await;
+            let live: HashSet<&str> = intents.iter().map(|i| i.id.as_str()).collect();
+            metrics
+                .intent_errors
+                .retain(|intent_id, _| live.contains(intent_id.as_str()));
+            metrics.intent_errors.clone()
+        };
+        let blocked = database.get_compliance_blocked_intent_ids()?;
+
+        let mut stuck = Vec::new();
+        for intent in intents {
+            let age_secs = (now - intent.updated_at).num_seconds().max(0) as u64;
+            let Some(level) = self.policy.level(intent.status, age_secs) else {
+                continue;
+            };
+
+            let last_error = match errors.get(intent.id.as_str()) {
+                Some(error) => Some(error.clone()),
+                None => database
+                    .get_latest_reverted_receipt(&intent.id)?
+                    .map(|receipt| IntentError {
+                        queue: receipt.tx_type,
+                        error: receipt
+                            .revert_reason
+                            .unwrap_or_else(|| format!("Transaction {} reverted", receipt.tx_hash)),
+                        at: receipt.created_at,
+                    }),
+            };
+
+            stuck.push(StuckIntent {
+                next_action: self.next_action(&intent, now, &blocked),
+                intent_id: intent.id.into(),
+                status: intent.status.as_str().to_string(),
+                level,
+                source_chain: intent.source_chain,
+                dest_chain: intent.dest_chain,
+                source_token: intent.source_token,
+                amount: intent.amount.into(),
+                age_secs,
+                status_since: intent.updated_at,
+                deadline: intent.deadline,
+                last_error,
+            });
+        }
+
+        stuck.sort_by_key(|s| std::cmp::Reverse(s.age_secs));
+        Ok(stuck)
+    }
+
+    /// What the workers will try next for this intent.
+    fn next_action(
+        &self,
+        intent: &Intent,
+        now: DateTime<Utc>,
+        blocked: &HashSet<String>,
+    ) -> String {
+        let now_secs = now.timestamp() as u64;
+
+        match intent.status {
+            IntentStatus::Committed if intent.deadline < now_secs => {
+                format!("Refund on {} (deadline passed)", intent.source_chain)
+            }
+            IntentStatus::Committed if blocked.contains(&intent.id.to_lowercase()) => {
+                "Held by compliance screening, refund at deadline".to_string()
+            }
+            IntentStatus::Committed => format!(
+                "Sync the commitment root and register on {}",
+                intent.dest_chain
+            ),
+            IntentStatus::Filled => {
+                let route = Chain::parse(&intent.source_chain)
+                    .and_then(|source| Ok((source, Chain::parse(&intent.dest_chain)?)));
+                let Ok((source, dest)) = route else {
+                    return format!(
+                        "Unsupported route {} -> {}",
+                        intent.source_chain, intent.dest_chain
+                    );
+                };
+
+                let strategy = self
+                    .coordinator
+                    .settlement_strategies
+                    .for_route(source, dest);
+                // `updated_at` is when the intent moved to filled
+                match strategy.on_fill(intent.updated_at.timestamp() as u64, now_secs) {
+                    SettlementStep::Wait(reason) => {
+                        format!("{} ({})", reason, strategy.name())
+                    }
+                    _ => format!(
+                        "Prove the fill and settle on {} ({})",
+                        intent.source_chain,
+                        strategy.name()
+                    ),
+                }
+            }
+            _ => "None".to_string(),
+        }
+    }
+
+    pub async fn run(&self) {
+        if !self.policy.enabled {
+            info!("🧭 Stuck intent detector disabled");
+            return;
+        }
+
+        info!(
+            "🧭 Stuck intent detector started (every {}s)",
+            self.policy.interval.as_secs()
+        );
+
+        let mut ticker = interval(self.policy.interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.check().await {
+                error!("❌ Stuck intent scan failed: {}", e);
+            }
+        }
+    }
+
+    async fn check(&self) -> Result<()> {
+        let stuck = self.scan().await?;
+
+        let escalated: Vec<StuckIntent> = {
+            let mut notified = self.notified.write().await;
+            escalations(&stuck, &mut notified)
+                .into_iter()
+                .cloned()
+                .collect()
+        };
+
+        let mut webhook_failures = 0;
+        for intent in &escalated {
+            warn!(
+                "🚨 Intent {} stuck in {} for {}s ({:?}): next {}; last error: {}",
+                intent.intent_id,
+                intent.status,
+                intent.age_secs,
+                intent.level,
+                intent.next_action,
+                intent
+                    .last_error
+                    .as_ref()
+                    .map(|e| e.error.as_str())
+                    .unwrap_or("none")
+            );
+
+            let payload = serde_json::json!({
+                "level": intent.level,
+                "intent": intent,
+            });
+            webhook_failures += self.webhooks.dispatch("intent_stuck", &payload).await as u64;
+
+            if let Err(e) = self.post_webhook(intent).await {
+                webhook_failures += 1;
+                error!("❌ Stuck webhook failed for {}: {}", intent.intent_id, e);
+            }
+        }
+
+        let mut summary = self.summary.write().await;
+        summary.warning = stuck
+            .iter()
+            .filter(|s| s.level == StuckLevel::Warning)
+            .count();
+        summary.critical = stuck
+            .iter()
+            .filter(|s| s.level == StuckLevel::Critical)
+            .count();
+        summary.by_status = HashMap::new();
+        for intent in &stuck {
+            *summary.by_status.entry(intent.status.clone()).or_default() += 1;
+        }
+        summary.oldest_age_secs = stuck.first().map(|s| s.age_secs).unwrap_or(0);
+        summary.escalations_total += escalated.len() as u64;
+        summary.webhook_failures_total += webhook_failures;
+        summary.last_scan_at = Some(Utc::now());
+
+        if !stuck.is_empty() {
+            warn!(
+                "🚨 {} stuck intents ({} critical)",
+                stuck.len(),
+                summary.critical
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn post_webhook(&self, intent: &StuckIntent) -> Result<()> {
+        let Some(url) = &self.policy.webhook_url else {
+            return Ok(());
+        };
+
+        let body = serde_json::json!({
+            "event": "intent_stuck",
+            "level": intent.level,
+            "intent": intent,
+        });
+        let response = self
+            .http
+            .send(url, |http| http.post(url).json(&body))
+            .await
+            .context("Webhook request failed")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow::anyhow!("Webhook returned {}", response.status()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stuck(intent_id: &str, level: StuckLevel) -> StuckIntent {
+        StuckIntent {
+            intent_id: intent_id.to_string(),
+            status: "filled".to_string(),
+            level,
+            source_chain: "ethereum".to_string(),
+            dest_chain: "mantle".to_string(),
+            source_token: "USDC".to_string(),
+            amount: "1000000".to_string(),
+            age_secs: 0,
+            status_since: Utc::now(),
+            deadline: 0,
+            last_error: None,
+            next_action: String::new(),
+        }
+    }
+
+    #[test]
+    fn test_levels_and_escalation() {
+        let policy = StuckPolicy::default();
+        assert_eq!(policy.level(IntentStatus::Committed, 899), None);
+        assert_eq!(
+            policy.level(IntentStatus::Committed, 900),
+            Some(StuckLevel::Warning)
+        );
+        assert_eq!(
+            policy.level(IntentStatus::Filled, 7200),
+            Some(StuckLevel::Critical)
+        );
+        assert_eq!(policy.level(IntentStatus::Registered, 100_000), None);
+
+        let mut notified = HashMap::new();
+        let first = vec![
+            stuck("a", StuckLevel::Warning),
+            stuck("b", StuckLevel::Warning),
+        ];
+        assert_eq!(escalations(&first, &mut notified).len(), 2);
+        // Same levels again are not re-announced
+        assert!(escalations(&first, &mut notified).is_empty());
+
+        // a escalates, b recovers and is announced again if it gets stuck later
+        let second = vec![stuck("a", StuckLevel::Critical)];
+        let escalated = escalations(&second, &mut notified);
+        assert_eq!(escalated.len(), 1);
+        assert_eq!(escalated[0].level, StuckLevel::Critical);
+        assert!(!notified.contains_key("b"));
+        assert_eq!(escalations(&first, &mut notified).len(), 1);
+    }
+}
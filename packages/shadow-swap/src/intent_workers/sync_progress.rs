@@ -0,0 +1,222 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::sync::{RwLock, watch};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SyncPhase {
+    Pending,
+    Scanning,
+    Ingesting,
+    Verifying,
+    Complete,
+    Failed,
+}
+
+/// Catch-up state of one chain's startup sync.
+#[derive(Debug, Clone, Serialize)]
+pub struct ChainSyncProgress {
+    pub chain: String,
+    pub phase: SyncPhase,
+    pub from_block: u64,
+    pub to_block: u64,
+    pub last_scanned_block: Option<u64>,
+    pub blocks_scanned: u64,
+    pub blocks_total: u64,
+    pub events_found: usize,
+    pub events_ingested: usize,
+    pub started_at: DateTime<Utc>,
+    pub phase_started_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub eta_secs: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl ChainSyncProgress {
+    fn new(chain: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            chain: chain.to_string(),
+            phase: SyncPhase::Pending,
+            from_block: 0,
+            to_block: 0,
+            last_scanned_block: None,
+            blocks_scanned: 0,
+            blocks_total: 0,
+            events_found: 0,
+            events_ingested: 0,
+            started_at: now,
+            phase_started_at: now,
+            updated_at: now,
+            finished_at: None,
+            eta_secs: None,
+            error: None,
+        }
+    }
+
+    /// Extrapolate from the rate in the current phase: blocks while scanning,
+    /// events while ingesting.
+    fn estimate_eta(&self, now: DateTime<Utc>) -> Option<u64> {
+        let elapsed = (now - self.phase_started_at).num_seconds().max(1) as f64;
+
+        let (done, total) = match self.phase {
+            SyncPhase::Scanning => (self.blocks_scanned as f64, self.blocks_total as f64),
+            SyncPhase::Ingesting => (self.events_ingested as f64, self.events_found as f64),
+            SyncPhase::Verifying | SyncPhase::Complete | SyncPhase::Failed => return Some(0),
+            SyncPhase::Pending => return None,
+        };
+
+        if done <= 0.0 {
+            return None;
+        }
+
+        Some(((total - done).max(0.0) / (done / elapsed)).ceil() as u64)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncProgressReport {
+    pub enabled: bool,
+    pub concurrent: bool,
+    pub ready: bool,
+    pub chains: Vec<ChainSyncProgress>,
+}
+
+/// Shared between the startup sync, the relayers' event scans and the API.
+#[derive(Debug, Clone)]
+pub struct SyncProgress {
+    enabled: bool,
+    concurrent: bool,
+    chains: Arc<RwLock<BTreeMap<String, ChainSyncProgress>>>,
+    ready: Arc<watch::Sender<bool>>,
+}
+
+impl SyncProgress {
+    /// Readiness starts closed when a startup sync will run, open otherwise.
+    pub fn new(enabled: bool, concurrent: bool) -> Self {
+        let (ready, _) = watch::channel(!enabled);
+        Self {
+            enabled,
+            concurrent,
+            chains: Arc::new(RwLock::new(BTreeMap::new())),
+            ready: Arc::new(ready),
+        }
+    }
+
+    pub fn is_ready(&self) -> bool {
+        *self.ready.borrow()
+    }
+
+    pub fn mark_ready(&self) {
+        self.ready.send_replace(true);
+    }
+
+    /// Resolves once the startup sync has finished (immediately if none runs).
+    pub async fn wait_ready(&self) {
+        let mut rx = self.ready.subscribe();
+        let _ = rx.wait_for(|ready| *ready).await;
+    }
+
+    async fn update(&self, chain: &str, apply: impl FnOnce(&mut ChainSyncProgress)) {
+        let mut chains = self.chains.write().await;
+        let progress = chains
+            .entry(chain.to_string())
+            .or_insert_with(|| ChainSyncProgress::new(chain));
+        apply(progress);
+        progress.updated_at = Utc::now();
+    }
+
+    pub async fn start_scan(&self, chain: &str, from_block: u64, to_block: u64) {
+        self.update(chain, |p| {
+            *p = ChainSyncProgress::new(chain);
+            p.phase = SyncPhase::Scanning;
+            p.from_block = from_block;
+            p.to_block = to_block;
+            p.blocks_total = to_block.saturating_sub(from_block) + 1;
+        })
+        .await;
+    }
+
+    pub async fn scanned(&self, chain: &str, up_to_block: u64, events: usize) {
+        self.update(chain, |p| {
+            p.last_scanned_block = Some(up_to_block);
+            p.blocks_scanned = up_to_block.saturating_sub(p.from_block) + 1;
+            p.events_found += events;
+        })
+        .await;
+    }
+
+    pub async fn set_phase(&self, chain: &str, phase: SyncPhase) {
+        self.update(chain, |p| {
+            p.phase = phase;
+            p.phase_started_at = Utc::now();
+            if phase == SyncPhase::Complete {
+                p.finished_at = Some(Utc::now());
+            }
+        })
+        .await;
+    }
+
+    pub async fn ingested(&self, chain: &str, events: usize) {
+        self.update(chain, |p| p.events_ingested = events).await;
+    }
+
+    pub async fn failed(&self, chain: &str, error: String) {
+        self.update(chain, |p| {
+            p.phase = SyncPhase::Failed;
+            p.finished_at = Some(Utc::now());
+            p.error = Some(error);
+        })
+        .await;
+    }
+
+    pub async fn report(&self) -> SyncProgressReport {
+        let now = Utc::now();
+        let chains = self
+            .chains
+            .read()
+            .await
+            .values()
+            .map(|p| ChainSyncProgress {
+                eta_secs: p.estimate_eta(now),
+                ..p.clone()
+            })
+            .collect();
+
+        SyncProgressReport {
+            enabled: self.enabled,
+            concurrent: self.concurrent,
+            ready: self.is_ready(),
+            chains,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_eta_follows_phase_rate() {
+        let mut progress = ChainSyncProgress::new("ethereum");
+        let now = progress.started_at + Duration::seconds(10);
+        assert_eq!(progress.estimate_eta(now), None);
+
+        progress.phase = SyncPhase::Scanning;
+        progress.blocks_total = 10_000;
+        progress.blocks_scanned = 2_000;
+        assert_eq!(progress.estimate_eta(now), Some(40));
+
+        progress.phase = SyncPhase::Ingesting;
+        progress.events_found = 300;
+        progress.events_ingested = 100;
+        assert_eq!(progress.estimate_eta(now), Some(20));
+
+        progress.phase = SyncPhase::Complete;
+        assert_eq!(progress.estimate_eta(now), Some(0));
+    }
+}
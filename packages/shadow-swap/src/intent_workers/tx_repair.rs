@@ -0,0 +1,379 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use ethers::{
+    abi::AbiDecode,
+    types::{H256, TransactionReceipt},
+    utils::hex,
+};
+use mantle_core::abi::{
+    intent_pool_contract::IntentPoolContractCalls, settlement_contract::SettlementContractCalls,
+};
+use tokio::{sync::Mutex, time::interval};
+use tracing::{debug, error, info, warn};
+
+use crate::{
+    chains::registry::ChainRegistry,
+    database::database::Database,
+    relay_coordinator::{claim_forwarder::unwrap_forwarded, model::ChainRelayer},
+};
+
+/// Upper bound on pending rows looked up per chain and pass.
+const PENDING_BATCH: i64 = 200;
+
+#[derive(Debug, Clone)]
+pub struct TxRepairPolicy {
+    pub enabled: bool,
+    pub interval: Duration,
+    /// Pending rows younger than this are left to the sender still awaiting them.
+    pub pending_after_secs: u64,
+    /// Pending rows the node no longer knows are marked `dropped` past this age.
+    pub dropped_after_secs: u64,
+    /// Blocks scanned for relayer transactions missing from `chain_transactions`.
+    pub scan_blocks: u64,
+}
+
+impl Default for TxRepairPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(120),
+            pending_after_secs: 600,
+            dropped_after_secs: 3600,
+            scan_blocks: 300,
+        }
+    }
+}
+
+impl TxRepairPolicy {
+    /// Reads `TX_REPAIR_ENABLED`, `TX_REPAIR_INTERVAL_SECS`,
+    /// `TX_REPAIR_PENDING_AFTER_SECS`, `TX_REPAIR_DROPPED_AFTER_SECS` and
+    /// `TX_REPAIR_SCAN_BLOCKS`.
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(format!("TX_REPAIR_{}", name)).ok();
+        let number = |name: &str, default: u64| -> Result<u64> {
+            match var(name) {
+                Some(value) => value
+                    .parse()
+                    .with_context(|| format!("Invalid TX_REPAIR_{}", name)),
+                None => Ok(default),
+            }
+        };
+        let defaults = Self::default();
+
+        Ok(Self {
+            enabled: match var("ENABLED") {
+                Some(enabled) => enabled.parse().context("Invalid TX_REPAIR_ENABLED")?,
+                None => defaults.enabled,
+            },
+            interval: Duration::from_secs(
+                number("INTERVAL_SECS", defaults.interval.as_secs())?.max(1),
+            ),
+            pending_after_secs: number("PENDING_AFTER_SECS", defaults.pending_after_secs)?,
+            dropped_after_secs: number("DROPPED_AFTER_SECS", defaults.dropped_after_secs)?,
+            scan_blocks: number("SCAN_BLOCKS", defaults.scan_blocks)?,
+        })
+    }
+}
+
+/// The relayer call in a transaction's calldata, as `(tx_type, intent_id)`
+/// using the names the relayers log under. Claims sent through a forwarder
+/// are decoded from the request they wrap. Root syncs and anything else
+/// without an intent yield `None`.
+pub fn decode_intent_call(input: &[u8]) -> Option<(&'static str, String)> {
+    let intent_id = |id: [u8; 32]| format!("0x{}", hex::encode(id));
+
+    if let Some(inner) = unwrap_forwarded(input) {
+        return decode_intent_call(&inner);
+    }
+
+    if let Ok(call) = IntentPoolContractCalls::decode(input) {
+        return match call {
+            IntentPoolContractCalls::SettleIntent(call) => {
+                Some(("settle_intent", intent_id(call.intent_id)))
+            }
+            IntentPoolContractCalls::Refund(call) => {
+                Some(("refund_intent", intent_id(call.intent_id)))
+            }
+            _ => None,
+        };
+    }
+
+    match SettlementContractCalls::decode(input).ok()? {
+        SettlementContractCalls::RegisterIntent(call) => {
+            Some(("register_intent", intent_id(call.intent_id)))
+        }
+        SettlementContractCalls::ClaimWithdrawal(call) => {
+            Some(("claim_withdrawal", intent_id(call.intent_id)))
+        }
+        SettlementContractCalls::ClaimWithdrawalTo(call) => {
+            Some(("claim_withdrawal", intent_id(call.intent_id)))
+        }
+        SettlementContractCalls::RecoverOrphanedFill(call) => {
+            Some(("recover_orphaned_fill", intent_id(call.intent_id)))
+        }
+        _ => None,
+    }
+}
+
+fn receipt_status(receipt: &TransactionReceipt) -> &'static str {
+    if receipt.status == Some(1.into()) {
+        "confirmed"
+    } else {
+        "reverted"
+    }
+}
+
+/// Settles `chain_transactions` rows left `pending` by a process that died
+/// before the receipt arrived, and records relayer transactions that never
+/// got a row at all by decoding their calldata.
+pub struct TxRepairWorker {
+    pub policy: TxRepairPolicy,
+    database: Arc<Database>,
+    chains: ChainRegistry,
+    /// Last block scanned per chain id.
+    cursors: Mutex<HashMap<u32, u64>>,
+}
+
+impl TxRepairWorker {
+    pub fn new(policy: TxRepairPolicy, database: Arc<Database>, chains: ChainRegistry) -> Self {
+        Self {
+            policy,
+            database,
+            chains,
+            cursors: Mutex::new(HashMap::new()),
+        }
+    }
+
+    pub async fn run(&self) {
+        if !self.policy.enabled {
+            info!("🩹 Transaction repair worker disabled");
+            return;
+        }
+
+        info!(
+            "🩹 Transaction repair worker started (every {}s, pending after {}s)",
+            self.policy.interval.as_secs(),
+            self.policy.pending_after_secs
+        );
+
+        let mut ticker = interval(self.policy.interval);
+        loop {
+            ticker.tick().await;
+
+            for relayer in self.chains.iter() {
+                if let Err(e) = self.repair_chain(relayer).await {
+                    error!("❌ [{}] Transaction repair failed: {}", relayer.name, e);
+                }
+            }
+        }
+    }
+
+    async fn repair_chain(&self, relayer: &ChainRelayer) -> Result<()> {
+        let repaired = self.repair_pending(relayer).await?;
+        let discovered = self.discover_missing(relayer).await?;
+
+        if repaired + discovered > 0 {
+            info!(
+                "🩹 Chain {}: {} pending transactions resolved, {} missing transactions recorded",
+                relayer.chain_id, repaired, discovered
+            );
+        }
+
+        Ok(())
+    }
+
+    /// Look up receipts for rows pending longer than `pending_after_secs`.
+    async fn repair_pending(&self, relayer: &ChainRelayer) -> Result<usize> {
+        let now = Utc::now().timestamp();
+        let stale = self.database.get_stale_pending_transactions(
+            relayer.chain_id,
+            now - self.policy.pending_after_secs as i64,
+            PENDING_BATCH,
+        )?;
+
+        let mut repaired = 0;
+        for row in stale {
+            let Ok(tx_hash) = row.tx_hash.parse::<H256>() else {
+                warn!("⚠️ Pending transaction with invalid hash: {}", row.tx_hash);
+                continue;
+            };
+
+            let status = match relayer.get_receipt(tx_hash).await? {
+                Some(receipt) => {
+                    relayer
+                        .archive_receipt(Some(&row.intent_id), &row.tx_type, &receipt)
+                        .await;
+                    receipt_status(&receipt)
+                }
+                None if (now - row.timestamp) as u64 >= self.policy.dropped_after_secs
+                    && !relayer.is_transaction_known(tx_hash).await? =>
+                {
+                    "dropped"
+                }
+                None => {
+                    debug!("⏳ {} {} still unmined", row.tx_type, row.tx_hash);
+                    continue;
+                }
+            };
+
+            self.database.log_chain_transaction(
+                &row.intent_id,
+                relayer.chain_id,
+                &row.tx_type,
+                &row.tx_hash,
+                status,
+            )?;
+            info!(
+                "🩹 {} {} for intent {}: pending -> {}",
+                row.tx_type, row.tx_hash, row.intent_id, status
+            );
+            repaired += 1;
+        }
+
+        Ok(repaired)
+    }
+
+    /// Scan blocks since the last pass (at most `scan_blocks`) for relayer
+    /// transactions without a row, e.g. sent just before a crash.
+    async fn discover_missing(&self, relayer: &ChainRelayer) -> Result<usize> {
+        let chain_id = relayer.chain_id;
+        let head = relayer.get_block_number().await?;
+        let earliest = head.saturating_sub(self.policy.scan_blocks.saturating_sub(1));
+
+        let from = match self.cursors.lock().await.get(&chain_id) {
+            Some(&cursor) if cursor >= head => return Ok(0),
+            Some(&cursor) if cursor + 1 < earliest => {
+                warn!(
+                    "⚠️ Chain {}: blocks {}..{} not scanned for missing transactions",
+                    chain_id,
+                    cursor + 1,
+                    earliest - 1
+                );
+                earliest
+            }
+            Some(&cursor) => cursor + 1,
+            None => earliest,
+        };
+
+        let mut discovered = 0;
+        for tx in relayer.relayer_transactions(from, head).await? {
+            let tx_hash = format!("{:?}", tx.hash);
+            if self.database.get_transaction_by_hash(&tx_hash)?.is_some() {
+                continue;
+            }
+            let Some((tx_type, intent_id)) = decode_intent_call(&tx.input) else {
+                continue;
+            };
+            if self.database.get_intent_by_id(&intent_id)?.is_none() {
+                debug!(
+                    "Skipping {} {} for unknown intent {}",
+                    tx_type, tx_hash, intent_id
+                );
+                continue;
+            }
+
+            let status = match relayer.get_receipt(tx.hash).await? {
+                Some(receipt) => {
+                    relayer
+                        .archive_receipt(Some(&intent_id), tx_type, &receipt)
+                        .await;
+                    receipt_status(&receipt)
+                }
+                None => "pending",
+            };
+
+            self.database
+                .log_chain_transaction(&intent_id, chain_id, tx_type, &tx_hash, status)?;
+            info!(
+                "🩹 Recorded missing {} {} for intent {} ({})",
+                tx_type, tx_hash, intent_id, status
+            );
+            discovered += 1;
+        }
+
+        self.cursors.lock().await.insert(chain_id, head);
+        Ok(discovered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        abi::AbiEncode,
+        types::{Address, U256},
+    };
+    use mantle_core::abi::{
+        intent_pool_contract::{RefundCall, SettleIntentCall, SyncDestChainRootCall},
+        settlement_contract::{
+            ClaimWithdrawalCall, ClaimWithdrawalToCall, RecoverOrphanedFillCall,
+        },
+    };
+
+    #[test]
+    fn test_decode_intent_call() {
+        let id = [0xab; 32];
+        let expected_id = format!("0x{}", "ab".repeat(32));
+
+        let settle = SettleIntentCall {
+            intent_id: id,
+            solver: Address::repeat_byte(1),
+            merkle_proof: vec![[2; 32]],
+            leaf_index: U256::from(7),
+        }
+        .encode();
+        assert_eq!(
+            decode_intent_call(&settle),
+            Some(("settle_intent", expected_id.clone()))
+        );
+
+        let refund = RefundCall { intent_id: id }.encode();
+        assert_eq!(
+            decode_intent_call(&refund),
+            Some(("refund_intent", expected_id.clone()))
+        );
+
+        let claim = ClaimWithdrawalCall {
+            intent_id: id,
+            nullifier: [3; 32],
+            recipient: Address::repeat_byte(4),
+            secret: [5; 32],
+            claim_auth: vec![6u8; 65].into(),
+        }
+        .encode();
+        assert_eq!(
+            decode_intent_call(&claim),
+            Some(("claim_withdrawal", expected_id.clone()))
+        );
+
+        let delegated = ClaimWithdrawalToCall {
+            intent_id: id,
+            nullifier: [3; 32],
+            recipient: Address::repeat_byte(4),
+            delegate: Address::repeat_byte(7),
+            secret: [5; 32],
+            claim_auth: vec![6u8; 65].into(),
+        }
+        .encode();
+        assert_eq!(
+            decode_intent_call(&delegated),
+            Some(("claim_withdrawal", expected_id.clone()))
+        );
+
+        let recover = RecoverOrphanedFillCall { intent_id: id }.encode();
+        assert_eq!(
+            decode_intent_call(&recover),
+            Some(("recover_orphaned_fill", expected_id))
+        );
+
+        let root_sync = SyncDestChainRootCall {
+            chain_id: 5003,
+            root: [7; 32],
+        }
+        .encode();
+        assert_eq!(decode_intent_call(&root_sync), None);
+        assert_eq!(decode_intent_call(&[0xde, 0xad, 0xbe, 0xef]), None);
+    }
+}
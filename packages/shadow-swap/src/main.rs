@@ -1,52 +1,257 @@
 mod api;
+mod chains;
+mod cli;
 mod config;
 mod database;
+mod devnet;
 mod encryption;
-mod ethereum;
 mod intent_workers;
-mod mantle;
 mod merkle_manager;
 mod models;
-mod pricefeed;
 mod relay_coordinator;
 mod root_sync_coordinator;
+mod shutdown;
+mod webhooks;
 
 use std::sync::Arc;
 
 use actix_cors::Cors;
-use actix_web::{App, HttpServer, http::header, middleware::Logger, web};
+use actix_web::{
+    App, HttpServer,
+    http::header,
+    middleware::{Compress, Condition, Logger},
+    web,
+};
 use anyhow::{Context, Result};
-use tokio::task;
-use tracing::{error, info};
+use mantle_core::{
+    http_client::OutboundClient,
+    http_server::HttpServerOptions,
+    metrics::Metrics,
+    price_source::PriceSources,
+    pricefeed::{PriceFeedManager, PricePolicy},
+    route::RouteMinimums,
+};
+use tokio::{
+    sync::mpsc,
+    task::{self, JoinSet},
+};
+use tracing::{error, info, warn};
 
 use crate::{
-    database::database::Database,
+    api::{
+        admin_auth::AdminAuthPolicy,
+        api_keys::{ApiKeyPolicy, PublicAccess},
+        helper::{enqueue_chain_events, run_ingestion_consumer},
+        intent_limits::UserIntentLimits,
+        quote::QuotePolicy,
+        rate_limit::RateLimitPolicy,
+        status_page::StatusPage,
+        versioning::VersionPolicy,
+    },
+    database::{
+        database::Database,
+        schema_check::{SchemaCheckMode, check_schema_drift},
+    },
+    devnet::DevnetPolicy,
     intent_workers::{
+        fairness::TokenBatchLimits,
+        fill_report_verifier::FillReportVerifier,
         intent_registration_worker::IntentRegistrationWorker,
         intent_settlement_worker::IntentSettlementWorker,
+        log_listener::{LogListener, LogListenerPolicy},
+        reorg_monitor::{ReorgMonitor, ReorgPolicy},
+        stuck_detector::{StuckDetector, StuckPolicy},
+        sync_progress::SyncProgress,
+        tx_repair::{TxRepairPolicy, TxRepairWorker},
     },
     merkle_manager::merkle_manager::MerkleTreeManager,
-    models::model::BridgeConfig,
-    pricefeed::pricefeed::PriceFeedManager,
-    relay_coordinator::model::{BridgeCoordinator, EthereumRelayer, MantleRelayer},
-    root_sync_coordinator::root_sync_coordinator::RootSyncCoordinator,
+    models::model::{BridgeConfig, CommitmentUniqueness},
+    relay_coordinator::{
+        alert_rules::AlertRulesPolicy,
+        claim_auth::ClaimAuthPolicy,
+        claim_estimate::ClaimEstimateCache,
+        compliance::{CompliancePolicy, ComplianceScreener},
+        contract_guard::ContractGuard,
+        debug_capture::DebugCapturePolicy,
+        deposit_check::DepositCheckPolicy,
+        encrypted_notes::EncryptedNotePolicy,
+        fill_reports::FillReportPolicy,
+        ingestion_queue::IngestionPolicy,
+        intent_archive::IntentArchivePolicy,
+        intent_feed::{IntentFeed, IntentFeedPolicy},
+        metric_snapshots::MetricsPersistPolicy,
+        model::{BridgeCoordinator, ChainRelayer, EthereumConfig, MantleConfig, SponsorshipPolicy},
+        orphaned_fills::OrphanPolicy,
+        reconcile::ReconcileChain,
+        relayer_jobs::RelayerJobPolicy,
+        reservations::ReservationPolicy,
+        route_analytics::RouteAnalyticsPolicy,
+        secret_reveal::RevealPolicy,
+        token_listings::load_token_registry,
+        work_partitions::{PartitionPolicy, WorkPartitions},
+    },
+    root_sync_coordinator::root_sync_coordinator::{RootSyncCoordinator, RootSyncPolicy},
+    shutdown::{Shutdown, ShutdownPolicy},
+    webhooks::webhook_dispatcher::{WebhookDispatcher, WebhookPolicy},
 };
 
 pub struct AppState {
     pub database: Arc<Database>,
     pub config: BridgeConfig,
-    pub ethereum_relayer: Arc<EthereumRelayer>,
-    pub mantle_relayer: Arc<MantleRelayer>,
+    pub ethereum_relayer: Arc<ChainRelayer>,
+    pub mantle_relayer: Arc<ChainRelayer>,
     pub bridge_coordinator: Arc<BridgeCoordinator>,
     pub merkle_manager: Arc<MerkleTreeManager>,
     pub price_feed: Arc<PriceFeedManager>,
+    pub route_minimums: RouteMinimums,
+    pub user_intent_limits: UserIntentLimits,
     pub root_sync_coordinator: Arc<RootSyncCoordinator>,
+    pub sync_progress: SyncProgress,
+    pub contract_guard: ContractGuard,
+    pub compliance: Arc<ComplianceScreener>,
+    pub api_versions: VersionPolicy,
+    pub stuck_detector: Arc<StuckDetector>,
+    pub webhooks: Arc<WebhookDispatcher>,
+    pub reveal_policy: RevealPolicy,
+    pub status_page: StatusPage,
+    pub claim_estimates: ClaimEstimateCache,
+    pub outbound_http: OutboundClient,
+    pub intent_feed: IntentFeed,
+    pub route_analytics: RouteAnalyticsPolicy,
+    pub deposit_check: DepositCheckPolicy,
+    pub encrypted_notes: EncryptedNotePolicy,
+    pub quote_policy: QuotePolicy,
+    pub reservations: ReservationPolicy,
+    pub fill_reports: FillReportPolicy,
+    pub admin_auth: AdminAuthPolicy,
+    pub public_access: PublicAccess,
+    pub commitment_uniqueness: CommitmentUniqueness,
+}
+
+/// Database, relayers and coordinator: what every command that reads the
+/// chains needs.
+struct Core {
+    database: Arc<Database>,
+    contract_guard: ContractGuard,
+    ethereum_relayer: Arc<ChainRelayer>,
+    mantle_relayer: Arc<ChainRelayer>,
+    merkle_manager: Arc<MerkleTreeManager>,
+    bridge_coordinator: Arc<BridgeCoordinator>,
+}
+
+impl Core {
+    async fn init(config: &BridgeConfig, run_migrations: bool) -> Result<Self> {
+        if let Some(profile) = &config.profile {
+            info!(
+                "🧭 Using network profile '{}' (ethereum={}, mantle={})",
+                profile.name, profile.ethereum.chain_id, profile.mantle.chain_id
+            );
+        }
+
+        let database = Arc::new(Database::from_env().context("Failed to initialize database")?);
+
+        if run_migrations {
+            info!("📊 Running database migrations");
+            Database::run_migrations(&database.pool).context("Failed to run migrations")?;
+        }
+
+        check_schema_drift(&database, SchemaCheckMode::from_env())
+            .context("Schema drift check failed")?;
+
+        if database.privacy_contexts_need_rekey()? {
+            warn!(
+                "⚠️ Privacy params still carry unkeyed context tags and will be refused; \
+                 run `shadow-swap rekey-privacy-contexts`"
+            );
+        }
+
+        let contract_guard = ContractGuard::from_env();
+        let metrics = Arc::new(Metrics::new("shadow_swap").context("Failed to register metrics")?);
+
+        let ethereum_relayer = Arc::new(
+            ChainRelayer::new(
+                &config.ethereum,
+                config
+                    .finality::<EthereumConfig>()
+                    .context("Invalid Ethereum finality")?,
+                database.clone(),
+                contract_guard.clone(),
+                metrics.clone(),
+            )
+            .await
+            .context("Failed to initialize Ethereum relayer")?,
+        );
+
+        let mantle_relayer = Arc::new(
+            ChainRelayer::new(
+                &config.mantle,
+                config
+                    .finality::<MantleConfig>()
+                    .context("Invalid Mantle finality")?,
+                database.clone(),
+                contract_guard.clone(),
+                metrics.clone(),
+            )
+            .await
+            .context("Failed to initialize Mantle relayer")?,
+        );
+
+        info!("🌳 Initializing Merkle Tree Manager");
+        let merkle_manager = Arc::new(MerkleTreeManager::new(database.clone(), 10));
+
+        let tokens = Arc::new(
+            load_token_registry(config.profile.as_ref(), &database)
+                .context("Failed to load token registry")?,
+        );
+        let listed: Vec<_> = tokens
+            .listings()
+            .iter()
+            .map(|listing| listing.token.symbol())
+            .collect();
+        info!("🪙 Listed tokens: {}", listed.join(", "));
+
+        info!("🎯 Initializing bridge coordinator");
+        let partitions = WorkPartitions::new(
+            PartitionPolicy::from_env().context("Invalid work partition policy")?,
+        );
+        let bridge_coordinator = Arc::new(
+            BridgeCoordinator::new(
+                ethereum_relayer.clone(),
+                mantle_relayer.clone(),
+                database.clone(),
+                merkle_manager.clone(),
+                partitions,
+                metrics,
+                tokens,
+            )
+            .with_jobs(RelayerJobPolicy::from_env().context("Invalid relayer job policy")?)
+            .with_debug_capture(
+                DebugCapturePolicy::from_env().context("Invalid debug capture policy")?,
+            )
+            .with_ingestion(IngestionPolicy::from_env().context("Invalid ingestion policy")?)
+            .with_sponsorship(
+                SponsorshipPolicy::from_env().context("Invalid claim sponsorship policy")?,
+            )
+            .with_token_limits(TokenBatchLimits::from_env().context("Invalid token batch limits")?),
+        );
+
+        Ok(Self {
+            database,
+            contract_guard,
+            ethereum_relayer,
+            mantle_relayer,
+            merkle_manager,
+            bridge_coordinator,
+        })
+    }
 }
 
 #[actix_web::main]
 async fn main() -> Result<()> {
     dotenv::dotenv().ok();
 
+    let matches = cli::cli().get_matches();
+
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
@@ -54,69 +259,195 @@ async fn main() -> Result<()> {
         )
         .init();
 
-    info!("🚀 Starting Mantle Bridge Relayer");
+    match matches.subcommand() {
+        Some(("config", args)) => match args.subcommand_name() {
+            Some("schema") => cli::print_config_schema(),
+            _ => cli::validate_config(),
+        },
+        Some(("devnet", _)) => {
+            devnet::run(DevnetPolicy::from_env().context("Invalid devnet policy")?).await
+        }
+        Some(("migrate", args)) => {
+            cli::migrate(&cli::connect_database()?, args.get_flag("dry-run"))
+        }
+        Some(("export", args)) => cli::export(&cli::connect_database()?, args),
+        Some(("rekey-privacy-contexts", args)) => {
+            cli::rekey_privacy_contexts(&cli::connect_database()?, args.get_flag("dry-run"))
+        }
+        Some(("admin-key", args)) => match args.subcommand() {
+            Some(("create", args)) => cli::create_admin_key(&cli::connect_database()?, args),
+            _ => unreachable!("subcommand required"),
+        },
+        Some(("reconcile", args)) => {
+            let config = cli::load_config()?;
+            let core = Core::init(&config, args.get_flag("fix")).await?;
+            let chain =
+                ReconcileChain::parse(args.get_one::<String>("chain").expect("has default"))?;
+            let report = core
+                .bridge_coordinator
+                .reconcile_intents(chain, args.get_flag("fix"))
+                .await?;
+
+            println!("{}", serde_json::to_string_pretty(&report)?);
+            Ok(())
+        }
+        Some(("replay-intent", args)) => {
+            let config = cli::load_config()?;
+            let core = Core::init(&config, false).await?;
+            let replay = core
+                .bridge_coordinator
+                .replay_intent(
+                    args.get_one::<String>("intent_id").expect("required"),
+                    args.get_one::<u64>("at-block").copied(),
+                    args.get_one::<u64>("dest-block").copied(),
+                )
+                .await?;
+
+            println!("{}", serde_json::to_string_pretty(&replay)?);
+            Ok(())
+        }
+        Some(("resync", args)) => {
+            let config = cli::load_config()?;
+            let core = Core::init(&config, !args.get_flag("dry-run")).await?;
+            let sync_service = intent_workers::event_sync::IntentSyncService::new(
+                core.database.clone(),
+                core.mantle_relayer.clone(),
+                core.ethereum_relayer.clone(),
+                core.merkle_manager.clone(),
+                SyncProgress::new(true, false),
+            );
+            cli::resync(&sync_service, &config, args).await
+        }
+        _ => serve().await,
+    }
+}
 
-    let config = BridgeConfig::from_env()
-        .or_else(|_| BridgeConfig::from_file("config.toml".into()))
-        .context("Failed to load configuration")?;
+async fn serve() -> Result<()> {
+    info!("🚀 Starting Mantle Bridge Relayer");
 
-    let database = Arc::new(Database::from_env().context("Failed to initialize database")?);
+    let config = cli::load_config()?;
+    let Core {
+        database,
+        contract_guard,
+        ethereum_relayer,
+        mantle_relayer,
+        merkle_manager,
+        bridge_coordinator,
+    } = Core::init(&config, true).await?;
 
-    info!("📊 Running database migrations");
-    Database::run_migrations(&database.pool).context("Failed to run migrations")?;
+    // One client for every third-party API so per-host limits hold process-wide
+    let outbound_http = OutboundClient::from_env().context("Invalid outbound HTTP policy")?;
 
     info!("💱 Initializing price feeds");
-    let price_feed = Arc::new(PriceFeedManager::new());
+    let price_feed = Arc::new(
+        PriceFeedManager::with_client(outbound_http.clone())
+            .with_policy(PricePolicy::from_env().context("Invalid price feed policy")?)
+            .with_sources(PriceSources::from_env().context("Invalid price sources")?),
+    );
 
     info!("📈 Starting ETH<->MNT price feeds");
-    price_feed.init_all_feeds().await;
+    price_feed.init().await;
 
-    info!("🔗 Initializing Ethereum relayer");
-    let ethereum_relayer = Arc::new(
-        EthereumRelayer::new(config.ethereum.clone(), database.clone())
-            .await
-            .context("Failed to initialize Ethereum relayer")?,
-    );
+    info!("🔄 Initializing root sync coordinator");
+    let root_sync_coordinator = Arc::new(RootSyncCoordinator::new(
+        database.clone(),
+        bridge_coordinator.chains.clone(),
+        RootSyncPolicy::from_env().context("Invalid root sync policy")?,
+        bridge_coordinator.prometheus.clone(),
+    ));
 
-    info!("🔗 Initializing Mantle relayer");
-    let mantle_relayer = Arc::new(
-        MantleRelayer::new(config.mantle.clone(), database.clone())
-            .await
-            .context("Failed to initialize Mantle relayer")?,
-    );
+    let should_sync_on_startup = std::env::var("SYNC_ON_STARTUP")
+        .unwrap_or_else(|_| "false".to_string())
+        .parse::<bool>()
+        .unwrap_or(false);
+
+    let sync_concurrently = std::env::var("SYNC_CONCURRENT")
+        .unwrap_or_else(|_| "true".to_string())
+        .parse::<bool>()
+        .unwrap_or(true);
+
+    let sync_progress = SyncProgress::new(should_sync_on_startup, sync_concurrently);
 
-    info!("🌳 Initializing Merkle Tree Manager");
-    let merkle_manager = Arc::new(MerkleTreeManager::new(
+    info!("🔄 Initializing intent sync service");
+    let intent_sync_service = Arc::new(intent_workers::event_sync::IntentSyncService::new(
+        database.clone(),
         mantle_relayer.clone(),
         ethereum_relayer.clone(),
-        database.clone(),
-        10,
+        merkle_manager.clone(),
+        sync_progress.clone(),
     ));
 
-    info!("🎯 Initializing bridge coordinator");
-    let bridge_coordinator = Arc::new(BridgeCoordinator::new(
-        ethereum_relayer.clone(),
-        mantle_relayer.clone(),
+    let compliance = Arc::new(ComplianceScreener::new(
+        CompliancePolicy::from_env().context("Invalid compliance policy")?,
         database.clone(),
-        merkle_manager.clone(),
+        outbound_http.clone(),
     ));
+    if compliance.enabled() {
+        info!("🔎 Compliance screening enabled");
+    }
 
-    info!("🔄 Initializing root sync coordinator");
-    let root_sync_coordinator = Arc::new(RootSyncCoordinator::new(
+    let webhooks = Arc::new(WebhookDispatcher::new(
+        WebhookPolicy::from_env().context("Invalid webhook policy")?,
         database.clone(),
-        ethereum_relayer.clone(),
-        mantle_relayer.clone(),
-        10,
+        outbound_http.clone(),
     ));
 
-    info!("🔄 Initializing intent sync service");
-    let intent_sync_service = Arc::new(intent_workers::event_sync::IntentSyncService::new(
-        database.clone(),
-        mantle_relayer.clone(),
-        ethereum_relayer.clone(),
-        merkle_manager.clone(),
+    let stuck_detector = Arc::new(StuckDetector::new(
+        StuckPolicy::from_env().context("Invalid stuck intent policy")?,
+        bridge_coordinator.clone(),
+        webhooks.clone(),
+        outbound_http.clone(),
     ));
 
+    let tx_repair = TxRepairWorker::new(
+        TxRepairPolicy::from_env().context("Invalid transaction repair policy")?,
+        database.clone(),
+        bridge_coordinator.chains.clone(),
+    );
+
+    let orphan_policy = OrphanPolicy::from_env().context("Invalid orphaned fill policy")?;
+    let archive_policy =
+        IntentArchivePolicy::from_env().context("Invalid intent archive policy")?;
+    let metrics_policy =
+        MetricsPersistPolicy::from_env().context("Invalid metrics persistence policy")?;
+    if metrics_policy.enabled
+        && let Err(e) = bridge_coordinator.restore_metrics(&metrics_policy).await
+    {
+        warn!("⚠️ Starting metric counters from zero: {}", e);
+    }
+    let intent_feed =
+        IntentFeed::new(IntentFeedPolicy::from_env().context("Invalid intent feed policy")?);
+    let route_analytics =
+        RouteAnalyticsPolicy::from_env().context("Invalid route analytics policy")?;
+    let reservations = ReservationPolicy::from_env().context("Invalid reservation policy")?;
+    let fill_reports = FillReportPolicy::from_env().context("Invalid fill report policy")?;
+    let quote_policy = QuotePolicy::from_env().context("Invalid quote policy")?;
+    let deposit_check = DepositCheckPolicy::from_env().context("Invalid deposit check policy")?;
+    let encrypted_notes =
+        EncryptedNotePolicy::from_env().context("Invalid encrypted note policy")?;
+    let alert_rules = AlertRulesPolicy::from_env().context("Invalid alert rules policy")?;
+    let api_versions = VersionPolicy::from_env().context("Invalid API version policy")?;
+    let admin_auth = AdminAuthPolicy::from_env().context("Invalid admin auth policy")?;
+    if admin_auth.hmac_enabled {
+        info!("🔑 Requests signed with the shared HMAC secret act as admin superuser");
+    }
+    let commitment_uniqueness = CommitmentUniqueness::from_env()?;
+    let api_keys = ApiKeyPolicy::from_env().context("Invalid API key policy")?;
+    let rate_limits = RateLimitPolicy::from_env().context("Invalid rate limit policy")?;
+    info!(
+        "🚦 Public API: {}/min per IP, {}/min per key{}",
+        rate_limits.ip_per_min,
+        rate_limits.key_per_min,
+        if api_keys.required {
+            ", API key required"
+        } else {
+            ""
+        }
+    );
+    if api_versions.legacy_enabled {
+        info!("🔀 Unversioned /api routes alias /api/v1 (deprecated)");
+    }
+
     let app_state = web::Data::new(AppState {
         database: database.clone(),
         config: config.clone(),
@@ -125,98 +456,295 @@ async fn main() -> Result<()> {
         bridge_coordinator: bridge_coordinator.clone(),
         merkle_manager: merkle_manager.clone(),
         price_feed,
+        route_minimums: RouteMinimums::from_env(),
+        user_intent_limits: UserIntentLimits::from_env(),
         root_sync_coordinator: root_sync_coordinator.clone(),
+        sync_progress: sync_progress.clone(),
+        contract_guard: contract_guard.clone(),
+        compliance: compliance.clone(),
+        api_versions,
+        stuck_detector: stuck_detector.clone(),
+        webhooks: webhooks.clone(),
+        reveal_policy: RevealPolicy::from_env(),
+        status_page: StatusPage::from_env(),
+        claim_estimates: ClaimEstimateCache::default(),
+        outbound_http,
+        intent_feed: intent_feed.clone(),
+        route_analytics,
+        deposit_check,
+        encrypted_notes: encrypted_notes.clone(),
+        quote_policy,
+        reservations: reservations.clone(),
+        fill_reports: fill_reports.clone(),
+        admin_auth,
+        public_access: PublicAccess::new(api_keys, rate_limits),
+        commitment_uniqueness,
+    });
+
+    let shutdown = bridge_coordinator.shutdown.clone();
+    let shutdown_policy = ShutdownPolicy::from_env().context("Invalid shutdown policy")?;
+    task::spawn({
+        let shutdown = shutdown.clone();
+        async move {
+            if let Err(e) = shutdown.listen_for_signals().await {
+                error!("❌ {:#}", e);
+            }
+        }
     });
 
+    // Workers that drain on shutdown; each returns its name when it stops
+    let mut workers = JoinSet::new();
+
     info!("🌳 Starting Merkle Tree Manager service");
-    let tree_manager_handle = task::spawn({
+    workers.spawn({
         let manager = merkle_manager.clone();
+        let shutdown = shutdown.clone();
         async move {
-            if let Err(e) = manager.start().await {
+            if let Err(e) = manager.start(&shutdown).await {
                 error!("❌ Merkle Tree Manager error: {}", e);
             }
+            "Merkle Tree Manager"
         }
     });
 
-    let should_sync_on_startup = std::env::var("SYNC_ON_STARTUP")
-        .unwrap_or_else(|_| "false".to_string())
-        .parse::<bool>()
-        .unwrap_or(false);
+    info!("🛡️  Starting contract guard");
+    task::spawn({
+        let guard = contract_guard.clone();
+        let chains = bridge_coordinator.chains.clone();
+        async move { guard.run(chains).await }
+    });
 
-    if should_sync_on_startup {
-        info!("🔄 Performing initial sync on startup");
-
-        let ethereum_from_block = std::env::var("ETHEREUM_SYNC_FROM_BLOCK")
-            .unwrap_or_else(|_| "9995018".to_string())
-            .parse::<u64>()
-            .context("Invalid ETHEREUM_SYNC_FROM_BLOCK")?;
-
-        let mantle_from_block = std::env::var("MANTLE_SYNC_FROM_BLOCK")
-            .unwrap_or_else(|_| "33084800".to_string())
-            .parse::<u64>()
-            .context("Invalid MANTLE_SYNC_FROM_BLOCK")?;
-
-        // --- Ethereum Sync ---
-        info!("  Syncing Ethereum from block {}", ethereum_from_block);
-        if let Err(e) = intent_sync_service
-            .resync_ethereum_intents(ethereum_from_block, true)
-            .await
-        {
-            error!("❌ Ethereum sync failed: {}", e);
+    info!("🔏 Starting claim signature verifier");
+    task::spawn({
+        let coordinator = bridge_coordinator.clone();
+        async move {
+            coordinator
+                .run_claim_auth_verifier(ClaimAuthPolicy::from_env())
+                .await
         }
+    });
 
-        // --- Mantle Sync ---
-        info!("  Syncing Mantle from block {}", mantle_from_block);
-        if let Err(e) = intent_sync_service
-            .resync_mantle_intents(mantle_from_block, true)
-            .await
-        {
-            error!("❌ Mantle sync failed: {}", e);
-        }
+    info!("🔑 Starting secret reveal monitor");
+    task::spawn({
+        let coordinator = bridge_coordinator.clone();
+        let policy = app_state.reveal_policy;
+        async move { coordinator.run_reveal_monitor(policy).await }
+    });
 
-        // --- Final Verification ---
-        info!("🔍 Running final verification post-sync...");
-        if let Err(e) = intent_sync_service.verify_sync_status().await {
-            error!(
-                "❌ Post-sync verification failed! Roots still do not match: {}",
-                e
-            );
-        } else {
-            info!("✅ Post-sync verification successful. All roots are consistent.");
+    info!("🛟 Starting orphaned fill monitor");
+    task::spawn({
+        let coordinator = bridge_coordinator.clone();
+        async move { coordinator.run_orphan_monitor(orphan_policy).await }
+    });
+
+    if reservations.enabled {
+        info!("🔒 Starting reservation expiry");
+        task::spawn({
+            let coordinator = bridge_coordinator.clone();
+            async move { coordinator.run_reservation_expiry(reservations).await }
+        });
+    }
+
+    if archive_policy.enabled {
+        info!("🗄️ Starting intent archiver");
+        task::spawn({
+            let coordinator = bridge_coordinator.clone();
+            async move { coordinator.run_intent_archiver(archive_policy).await }
+        });
+    }
+
+    info!("🔬 Starting debug capture refresher");
+    task::spawn({
+        let coordinator = bridge_coordinator.clone();
+        async move { coordinator.run_debug_capture_refresher().await }
+    });
+
+    if metrics_policy.enabled {
+        info!("📈 Starting metrics persister");
+        task::spawn({
+            let coordinator = bridge_coordinator.clone();
+            let policy = metrics_policy.clone();
+            async move { coordinator.run_metrics_persister(policy).await }
+        });
+    }
+
+    if intent_feed.policy.enabled {
+        info!("📡 Starting intent feed");
+        task::spawn({
+            let coordinator = bridge_coordinator.clone();
+            async move { coordinator.run_intent_feed(intent_feed).await }
+        });
+    }
+
+    if bridge_coordinator.partitions.policy.enabled {
+        info!("🧩 Starting work partition heartbeat");
+        task::spawn({
+            let coordinator = bridge_coordinator.clone();
+            async move { coordinator.run_work_partitions().await }
+        });
+    }
+
+    if alert_rules.rules.is_some() {
+        info!("🚨 Starting alert rules engine");
+        task::spawn({
+            let coordinator = bridge_coordinator.clone();
+            let http = app_state.outbound_http.clone();
+            let webhooks = webhooks.clone();
+            async move {
+                coordinator
+                    .run_alert_rules(alert_rules, http, webhooks)
+                    .await
+            }
+        });
+    }
+
+    info!("✉️ Starting encrypted note delivery");
+    task::spawn({
+        let coordinator = bridge_coordinator.clone();
+        let webhooks = webhooks.clone();
+        async move {
+            coordinator
+                .run_note_delivery(encrypted_notes, webhooks)
+                .await
         }
+    });
+
+    if route_analytics.daily_report {
+        info!("📊 Starting daily report");
+        task::spawn({
+            let coordinator = bridge_coordinator.clone();
+            async move {
+                coordinator
+                    .run_daily_report(route_analytics, webhooks)
+                    .await
+            }
+        });
+    }
+
+    info!("🧭 Starting stuck intent detector");
+    task::spawn({
+        let detector = stuck_detector.clone();
+        async move { detector.run().await }
+    });
+
+    info!("🩹 Starting transaction repair worker");
+    task::spawn(async move { tx_repair.run().await });
+
+    if should_sync_on_startup {
+        let ethereum_from_block = cli::sync_from_block("ethereum", config.profile.as_ref())?;
+        let mantle_from_block = cli::sync_from_block("mantle", config.profile.as_ref())?;
+
+        info!(
+            "🔄 Performing initial sync in the background ({}); workers start once it completes",
+            if sync_concurrently {
+                "chains in parallel"
+            } else {
+                "chains sequentially"
+            }
+        );
+
+        task::spawn({
+            let sync_service = intent_sync_service.clone();
+            let progress = sync_progress.clone();
+            async move {
+                let sync_ethereum = async {
+                    info!("  Syncing Ethereum from block {}", ethereum_from_block);
+                    if let Err(e) = sync_service
+                        .resync_ethereum_intents(ethereum_from_block, true)
+                        .await
+                    {
+                        error!("❌ Ethereum sync failed: {}", e);
+                    }
+                };
+
+                let sync_mantle = async {
+                    info!("  Syncing Mantle from block {}", mantle_from_block);
+                    if let Err(e) = sync_service
+                        .resync_mantle_intents(mantle_from_block, true)
+                        .await
+                    {
+                        error!("❌ Mantle sync failed: {}", e);
+                    }
+                };
+
+                if sync_concurrently {
+                    tokio::join!(sync_ethereum, sync_mantle);
+                } else {
+                    sync_ethereum.await;
+                    sync_mantle.await;
+                }
+
+                info!("🔍 Running final verification post-sync...");
+                if let Err(e) = sync_service.verify_sync_status().await {
+                    error!(
+                        "❌ Post-sync verification failed! Roots still do not match: {}",
+                        e
+                    );
+                } else {
+                    info!("✅ Post-sync verification successful. All roots are consistent.");
+                }
+
+                progress.mark_ready();
+                info!("✅ Startup sync finished, starting workers");
+            }
+        });
     }
 
     info!("⚙️  Starting bridge coordinator service");
-    let coordinator_handle = task::spawn({
+    workers.spawn({
         let coordinator = bridge_coordinator.clone();
+        let progress = sync_progress.clone();
         async move {
-            if let Err(e) = coordinator.start().await {
+            if ready_or_shutdown(&progress, &coordinator.shutdown).await
+                && let Err(e) = coordinator.start().await
+            {
                 error!("❌ Bridge coordinator error: {}", e);
             }
+            "Bridge coordinator"
         }
     });
 
     info!("🔄 Starting root sync coordinator service");
-    let root_sync_handle = task::spawn({
+    workers.spawn({
         let coordinator = root_sync_coordinator.clone();
+        let progress = sync_progress.clone();
+        let shutdown = shutdown.clone();
         async move {
-            coordinator.run().await;
+            if ready_or_shutdown(&progress, &shutdown).await {
+                coordinator.run(shutdown).await;
+            }
+            "Root sync coordinator"
         }
     });
 
     info!("📝 Starting intent registration worker");
-    let registration_worker = Arc::new(IntentRegistrationWorker::new(
-        database.clone(),
-        mantle_relayer.clone(),
-        ethereum_relayer.clone(),
-        merkle_manager.clone(),
-        root_sync_coordinator.clone(),
-    ));
+    let registration_worker = Arc::new(
+        IntentRegistrationWorker::new(
+            database.clone(),
+            mantle_relayer.clone(),
+            ethereum_relayer.clone(),
+            merkle_manager.clone(),
+            root_sync_coordinator.clone(),
+            bridge_coordinator.metrics.clone(),
+            compliance.clone(),
+        )
+        .with_tokens(bridge_coordinator.tokens.clone())
+        .with_partitions(bridge_coordinator.partitions.clone())
+        .with_jobs(bridge_coordinator.jobs.clone())
+        .with_debug_capture(bridge_coordinator.debug_capture.clone())
+        .with_shutdown(shutdown.clone())
+        .with_wake(bridge_coordinator.wakeups.registration.clone()),
+    );
 
-    let registration_handle = task::spawn({
+    workers.spawn({
         let worker = registration_worker.clone();
+        let progress = sync_progress.clone();
+        let shutdown = shutdown.clone();
         async move {
-            worker.run().await;
+            if ready_or_shutdown(&progress, &shutdown).await {
+                worker.run().await;
+            }
+            "Intent registration worker"
         }
     });
 
@@ -228,17 +756,79 @@ async fn main() -> Result<()> {
         bridge_coordinator.clone(),
     ));
 
-    let settlement_handle = task::spawn({
+    workers.spawn({
         let worker = settlement_worker.clone();
+        let progress = sync_progress.clone();
+        let shutdown = shutdown.clone();
         async move {
-            worker.run().await;
+            if ready_or_shutdown(&progress, &shutdown).await {
+                worker.run().await;
+            }
+            "Intent settlement worker"
         }
     });
 
+    info!("📥 Starting ingestion consumer");
+    task::spawn(run_ingestion_consumer(app_state.clone()));
+
+    let log_listener = LogListenerPolicy::from_env().context("Invalid log listener policy")?;
+    let ws_urls = [
+        (ethereum_relayer.clone(), config.ethereum.ws_url.clone()),
+        (mantle_relayer.clone(), config.mantle.ws_url.clone()),
+    ];
+    if log_listener.enabled && ws_urls.iter().any(|(_, url)| url.is_some()) {
+        let (events, receiver) = mpsc::channel(1024);
+        for (relayer, ws_url) in ws_urls {
+            let Some(ws_url) = ws_url else {
+                info!("📡 No WebSocket URL for {}, polling only", relayer.name);
+                continue;
+            };
+            info!("📡 Starting {} log listener", relayer.name);
+            let listener = LogListener::new(
+                relayer,
+                ws_url,
+                log_listener,
+                events.clone(),
+                shutdown.clone(),
+            );
+            task::spawn(async move { listener.run().await });
+        }
+        task::spawn(enqueue_chain_events(app_state.clone(), receiver));
+    }
+
+    let reorg = ReorgPolicy::from_env().context("Invalid reorg policy")?;
+    if reorg.enabled {
+        for relayer in [ethereum_relayer.clone(), mantle_relayer.clone()] {
+            info!("🔀 Starting {} reorg monitor", relayer.name);
+            let monitor = ReorgMonitor::new(relayer, reorg, app_state.clone(), shutdown.clone());
+            task::spawn(async move { monitor.run().await });
+        }
+    }
+
+    if fill_reports.enabled {
+        info!(
+            "📨 Accepting fill reports from {} solvers",
+            fill_reports.solvers.len()
+        );
+        let verifier = FillReportVerifier::new(fill_reports, app_state.clone(), shutdown.clone());
+        task::spawn(async move { verifier.run().await });
+    }
+
     let host = config.server.host.clone();
     let port = config.server.port;
-
-    info!("🌐 Starting HTTP server on {}:{}", host, port);
+    let http_options = HttpServerOptions::from_env().context("Invalid HTTP server options")?;
+    let tls_config = http_options
+        .tls_config()
+        .context("Failed to load TLS certificate")?;
+
+    info!(
+        "🌐 Starting HTTP server on {}://{}:{} (compression: {}, HTTP/2: {})",
+        http_options.scheme(),
+        host,
+        port,
+        http_options.compression,
+        http_options.http2 || tls_config.is_some()
+    );
 
     // let server = HttpServer::new(move || {
     //     let cors = Cors::default()
@@ -261,29 +851,81 @@ async fn main() -> Result<()> {
     //         .app_data(app_state.clone())
     //         .configure(config::config_scope::configure)
     // })
+    let compression = http_options.compression;
     let server = HttpServer::new(move || {
         let cors = Cors::permissive();
 
         App::new()
+            .wrap(Condition::new(compression, Compress::default()))
             .wrap(cors)
             .wrap(Logger::default())
             .app_data(app_state.clone())
             .configure(config::config_scope::configure)
-    })
-    .bind((host.as_str(), port))
+    });
+    // Signals are handled by `Shutdown` so the workers drain alongside the server
+    let server = server
+        .disable_signals()
+        .shutdown_timeout(shutdown_policy.grace.as_secs());
+    let server = match tls_config {
+        Some(tls) => server.bind_rustls_0_23((host.as_str(), port), tls),
+        None if http_options.http2 => server.bind_auto_h2c((host.as_str(), port)),
+        None => server.bind((host.as_str(), port)),
+    }
     .context("Failed to bind HTTP server")?
     .run();
+    let server_handle = server.handle();
+    let mut server = task::spawn(server);
 
     info!("✅ All services started successfully");
 
     tokio::select! {
-        result = server => error!("HTTP server stopped: {:?}", result),
-        _ = tree_manager_handle => error!("Merkle Tree Manager stopped unexpectedly"),
-        _ = coordinator_handle => error!("Bridge coordinator stopped unexpectedly"),
-        _ = root_sync_handle => error!("Root sync coordinator stopped unexpectedly"),
-        _ = registration_handle => error!("Intent registration worker stopped unexpectedly"),
-        _ = settlement_handle => error!("Intent settlement worker stopped unexpectedly"),
+        result = &mut server => error!("HTTP server stopped: {:?}", result),
+        Some(stopped) = workers.join_next() => match stopped {
+            Ok(name) => error!("{} stopped unexpectedly", name),
+            Err(e) => error!("Worker task failed: {}", e),
+        },
+        _ = shutdown.wait() => {}
     }
 
+    info!(
+        "🛑 Shutting down: finishing in-flight intents (up to {}s)",
+        shutdown_policy.grace.as_secs()
+    );
+    shutdown.trigger();
+    let drain = tokio::time::timeout(shutdown_policy.grace, async {
+        while let Some(stopped) = workers.join_next().await {
+            match stopped {
+                Ok(name) => info!("✅ {} drained", name),
+                Err(e) => error!("Worker task failed: {}", e),
+            }
+        }
+    });
+    let (_, drained) = tokio::join!(server_handle.stop(true), drain);
+    if drained.is_err() {
+        warn!(
+            "⚠️ {} worker(s) still busy after {}s, stopping them",
+            workers.len(),
+            shutdown_policy.grace.as_secs()
+        );
+        workers.abort_all();
+    }
+
+    // Save the final counts so the next process resumes without a dip
+    if metrics_policy.enabled
+        && let Err(e) = bridge_coordinator.save_metrics(&metrics_policy).await
+    {
+        warn!("⚠️ Failed to save metric counters on shutdown: {}", e);
+    }
+
+    info!("👋 Relayer stopped");
     Ok(())
 }
+
+/// Hold a worker until the startup sync finishes; false when shutdown
+/// comes first.
+async fn ready_or_shutdown(progress: &SyncProgress, shutdown: &Shutdown) -> bool {
+    tokio::select! {
+        _ = progress.wait_ready() => true,
+        _ = shutdown.wait() => false,
+    }
+}
@@ -0,0 +1,332 @@
+use std::collections::{HashMap, HashSet};
+
+use anyhow::Result;
+use mantle_core::parse;
+use tracing::{info, warn};
+
+use crate::{
+    database::model::{DbMerkleNode, NewMerkleRepair},
+    merkle_manager::{merkle_manager::MerkleTreeManager, proof_generator::MerkleProofGenerator},
+};
+
+const ZERO_LEAF: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Trees checked at startup; the ones proofs are served from.
+const CHECKED_TREES: [&str; 4] = [
+    "mantle_commitments",
+    "ethereum_commitments",
+    "mantle_fills",
+    "ethereum_fills",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TreeIssue {
+    /// `leaf_count` differs from the leaves stored at level 0.
+    LeafCount,
+    /// The stored root is not the root over the level-0 leaves.
+    Root,
+    /// Inner nodes missing or not hashing up from their children.
+    Nodes,
+    /// Nodes outside the tree the leaves give, e.g. leaves after a gap.
+    StrayNodes,
+}
+
+impl TreeIssue {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::LeafCount => "leaf_count_mismatch",
+            Self::Root => "root_mismatch",
+            Self::Nodes => "node_mismatch",
+            Self::StrayNodes => "stray_nodes",
+        }
+    }
+}
+
+/// A tree recomputed from its stored level-0 leaves, and what it takes to
+/// bring the stored nodes and metadata in line with it.
+#[derive(Debug, Clone)]
+pub struct TreeCheck {
+    pub leaf_count: usize,
+    pub root: String,
+    pub issues: Vec<TreeIssue>,
+    /// Nodes to upsert, as `(level, node_index, hash)`. Every node when
+    /// `replace` is set.
+    pub repair_nodes: Vec<(i32, i64, String)>,
+    pub replace: bool,
+}
+
+/// Recompute a tree from the leading run of leaves stored at level 0 and
+/// compare it with the stored nodes, leaf count and root.
+pub fn check_stored_tree(
+    nodes: &[DbMerkleNode],
+    stored_leaf_count: i64,
+    stored_root: &str,
+) -> Result<TreeCheck> {
+    let stored: HashMap<(i32, i64), &str> = nodes
+        .iter()
+        .map(|node| ((node.level, node.node_index), node.hash.as_str()))
+        .collect();
+
+    let leaves: Vec<String> = (0..)
+        .map_while(|index| {
+            stored
+                .get(&(0, index))
+                .filter(|hash| !hash.eq_ignore_ascii_case(ZERO_LEAF))
+                .map(|hash| hash.to_string())
+        })
+        .collect();
+    let leaf_count = leaves.len();
+
+    let expected: Vec<(i32, i64, String)> = if leaves.is_empty() {
+        Vec::new()
+    } else {
+        MerkleProofGenerator::tree_layers(leaves)?
+            .into_iter()
+            .enumerate()
+            .flat_map(|(level, layer)| {
+                layer
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(index, hash)| (level as i32, index as i64, hash))
+            })
+            .collect()
+    };
+    let root = expected
+        .last()
+        .map_or(ZERO_LEAF.to_string(), |(_, _, hash)| hash.clone());
+
+    let mut issues = Vec::new();
+    if stored_leaf_count != leaf_count as i64 {
+        issues.push(TreeIssue::LeafCount);
+    }
+    if !stored_root.eq_ignore_ascii_case(&root) {
+        issues.push(TreeIssue::Root);
+    }
+
+    let mut repair_nodes: Vec<(i32, i64, String)> = expected
+        .iter()
+        .filter(|(level, index, hash)| {
+            stored
+                .get(&(*level, *index))
+                .is_none_or(|stored| !stored.eq_ignore_ascii_case(hash))
+        })
+        .cloned()
+        .collect();
+    if !repair_nodes.is_empty() {
+        issues.push(TreeIssue::Nodes);
+    }
+
+    let positions: HashSet<(i32, i64)> = expected
+        .iter()
+        .map(|(level, index, _)| (*level, *index))
+        .collect();
+    let replace = stored.keys().any(|position| !positions.contains(position));
+    if replace {
+        issues.push(TreeIssue::StrayNodes);
+        repair_nodes = expected;
+    }
+
+    Ok(TreeCheck {
+        leaf_count,
+        root,
+        issues,
+        repair_nodes,
+        replace,
+    })
+}
+
+impl MerkleTreeManager {
+    /// Check each proof tree's stored nodes, leaf count and root against a
+    /// recomputation from its level-0 leaves, and rewrite only what
+    /// disagrees. Each repair leaves an entry in `merkle_repairs`. Runs
+    /// before the startup rebuild, which trusts the stored leaf count.
+    pub async fn repair_inconsistent_trees(&self) -> Result<usize> {
+        let _lock = self.tree_locks.write().await;
+        let mut repaired = 0;
+
+        for tree_name in CHECKED_TREES {
+            let Some(tree) = self.database.get_merkle_tree_by_name(tree_name)? else {
+                continue;
+            };
+            let nodes = self.database.get_all_merkle_nodes(tree.tree_id)?;
+            let check = check_stored_tree(&nodes, tree.leaf_count, &tree.root)?;
+            if check.issues.is_empty() {
+                info!("✅ Tree '{}' matches its stored nodes", tree_name);
+                continue;
+            }
+
+            let issues: Vec<String> = check
+                .issues
+                .iter()
+                .map(|issue| issue.as_str().to_string())
+                .collect();
+            warn!(
+                "🩹 Repairing tree '{}' ({}): leaves {} -> {}, root {} -> {}, {} nodes rewritten",
+                tree_name,
+                issues.join(", "),
+                tree.leaf_count,
+                check.leaf_count,
+                parse::short(&tree.root),
+                parse::short(&check.root),
+                check.repair_nodes.len()
+            );
+
+            self.database.write_merkle_nodes(
+                tree.tree_id,
+                &check.repair_nodes,
+                &check.root,
+                check.leaf_count as i64,
+                check.replace,
+            )?;
+            self.remember_root(tree_name, &check.root, check.leaf_count);
+            self.database.record_merkle_repair(&NewMerkleRepair {
+                tree_name,
+                issues,
+                stored_leaf_count: tree.leaf_count,
+                stored_root: &tree.root,
+                repaired_leaf_count: check.leaf_count as i64,
+                repaired_root: &check.root,
+                nodes_rewritten: check.repair_nodes.len() as i64,
+            })?;
+            repaired += 1;
+        }
+
+        Ok(repaired)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::test_database;
+    use chrono::Utc;
+    use serial_test::serial;
+    use std::sync::Arc;
+
+    fn leaf(n: u8) -> String {
+        format!("0x{}", hex::encode([n; 32]))
+    }
+
+    fn stored_nodes(leaves: &[String]) -> Vec<DbMerkleNode> {
+        MerkleProofGenerator::tree_layers(leaves.to_vec())
+            .unwrap()
+            .into_iter()
+            .enumerate()
+            .flat_map(|(level, layer)| {
+                layer
+                    .into_iter()
+                    .enumerate()
+                    .map(move |(index, hash)| DbMerkleNode {
+                        node_id: 0,
+                        tree_id: 1,
+                        level: level as i32,
+                        node_index: index as i64,
+                        hash,
+                        created_at: Utc::now(),
+                        updated_at: Utc::now(),
+                    })
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_consistent_tree_needs_no_repair() {
+        let leaves: Vec<String> = (1..=3).map(leaf).collect();
+        let root = MerkleProofGenerator::root_from_leaves(leaves.clone()).unwrap();
+
+        let check = check_stored_tree(&stored_nodes(&leaves), 3, &root).unwrap();
+        assert!(check.issues.is_empty());
+        assert!(check.repair_nodes.is_empty());
+
+        let empty = check_stored_tree(&[], 0, ZERO_LEAF).unwrap();
+        assert!(empty.issues.is_empty());
+    }
+
+    #[test]
+    fn test_leaf_stored_without_count_or_parents_is_repaired_along_its_path() {
+        let leaves: Vec<String> = (1..=3).map(leaf).collect();
+        let old_root = MerkleProofGenerator::root_from_leaves(leaves.clone()).unwrap();
+        // The crash wrote leaf 3 but neither its parents nor the count
+        let mut nodes = stored_nodes(&leaves);
+        nodes
+            .iter_mut()
+            .find(|node| node.level == 0 && node.node_index == 3)
+            .unwrap()
+            .hash = leaf(4);
+
+        let check = check_stored_tree(&nodes, 3, &old_root).unwrap();
+        assert_eq!(
+            check.issues,
+            vec![TreeIssue::LeafCount, TreeIssue::Root, TreeIssue::Nodes]
+        );
+        assert_eq!(check.leaf_count, 4);
+        assert_eq!(
+            check.root,
+            MerkleProofGenerator::root_from_leaves((1..=4).map(leaf).collect()).unwrap()
+        );
+        let positions: Vec<(i32, i64)> = check
+            .repair_nodes
+            .iter()
+            .map(|(level, index, _)| (*level, *index))
+            .collect();
+        assert_eq!(positions, vec![(1, 1), (2, 0)]);
+        assert!(!check.replace);
+    }
+
+    #[test]
+    fn test_count_bumped_without_leaf_falls_back_to_stored_leaves() {
+        let leaves: Vec<String> = (1..=2).map(leaf).collect();
+        let root = MerkleProofGenerator::root_from_leaves(leaves.clone()).unwrap();
+
+        let check = check_stored_tree(&stored_nodes(&leaves), 3, &root).unwrap();
+        assert_eq!(check.issues, vec![TreeIssue::LeafCount]);
+        assert_eq!(check.leaf_count, 2);
+
+        // A gap at level 0 drops every leaf after it
+        let mut nodes = stored_nodes(&(1..=4).map(leaf).collect::<Vec<_>>());
+        nodes.retain(|node| !(node.level == 0 && node.node_index == 1));
+        let check = check_stored_tree(&nodes, 4, &root).unwrap();
+        assert_eq!(check.leaf_count, 1);
+        assert!(check.replace);
+        assert_eq!(check.issues.last(), Some(&TreeIssue::StrayNodes));
+    }
+
+    #[tokio::test]
+    #[serial(db)]
+    async fn test_startup_repair_rewrites_tree_and_logs_incident() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+        let database = Arc::new(database);
+        let manager = MerkleTreeManager::new(database.clone(), 20);
+
+        let leaves: Vec<String> = (1..=3).map(leaf).collect();
+        let nodes: Vec<(i32, i64, String)> = stored_nodes(&leaves)
+            .into_iter()
+            .map(|node| (node.level, node.node_index, node.hash))
+            .collect();
+        let root = MerkleProofGenerator::root_from_leaves(leaves.clone())?;
+        let tree = database.ensure_merkle_tree("ethereum_fills", 20)?;
+        database.ensure_merkle_tree("mantle_fills", 20)?;
+        // Leaf count never caught up with the third leaf
+        database.write_merkle_nodes(tree.tree_id, &nodes, &leaf(9), 2, true)?;
+
+        assert_eq!(manager.repair_inconsistent_trees().await?, 1);
+        let repaired = database.get_merkle_tree_by_name("ethereum_fills")?.unwrap();
+        assert_eq!(repaired.leaf_count, 3);
+        assert_eq!(repaired.root, root);
+
+        let incidents = database.list_merkle_repairs(10)?;
+        assert_eq!(incidents.len(), 1);
+        assert_eq!(incidents[0].tree_name, "ethereum_fills");
+        assert_eq!(
+            incidents[0].issues,
+            vec!["leaf_count_mismatch", "root_mismatch"]
+        );
+        assert_eq!(incidents[0].nodes_rewritten, 0);
+
+        // A second pass finds nothing left to repair
+        assert_eq!(manager.repair_inconsistent_trees().await?, 0);
+        Ok(())
+    }
+}
@@ -1,48 +1,44 @@
 use anyhow::{Result, anyhow};
+use mantle_core::{
+    chain::{ETHEREUM_CHAIN_ID, MANTLE_CHAIN_ID},
+    parse,
+};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tracing::{info, warn};
 
 use crate::{
     database::database::Database,
-    merkle_manager::proof_generator::MerkleProofGenerator,
-    relay_coordinator::model::{EthereumRelayer, MantleRelayer},
+    merkle_manager::{proof_generator::MerkleProofGenerator, root_history::RootHistoryPolicy},
+    shutdown::Shutdown,
 };
 
 const ZERO_LEAF: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
-const MANTLE_CHAIN_ID: u32 = 5003;
-const ETHEREUM_CHAIN_ID: u32 = 11155111;
 
 pub struct MerkleTreeManager {
-    mantle_relayer: Arc<MantleRelayer>,
-    ethereum_relayer: Arc<EthereumRelayer>,
-    database: Arc<Database>,
+    pub(super) database: Arc<Database>,
     tree_depth: usize,
-    tree_locks: Arc<RwLock<()>>,
+    pub(super) tree_locks: Arc<RwLock<()>>,
+    pub(super) root_history: RootHistoryPolicy,
     pub proof_generator: Arc<MerkleProofGenerator>,
 }
 
 impl MerkleTreeManager {
-    pub fn new(
-        mantle_relayer: Arc<MantleRelayer>,
-        ethereum_relayer: Arc<EthereumRelayer>,
-        database: Arc<Database>,
-        tree_depth: usize,
-    ) -> Self {
+    pub fn new(database: Arc<Database>, tree_depth: usize) -> Self {
         let proof_generator = Arc::new(MerkleProofGenerator::new(database.clone()));
 
         Self {
-            mantle_relayer,
-            ethereum_relayer,
             database,
             tree_depth,
             tree_locks: Arc::new(RwLock::new(())),
+            root_history: RootHistoryPolicy::from_env(),
             proof_generator,
         }
     }
 
-    /// Initialize all trees and rebuild from database
-    pub async fn start(&self) -> Result<()> {
+    /// Initialize all trees and rebuild from database, then hold until
+    /// shutdown.
+    pub async fn start(&self, shutdown: &Shutdown) -> Result<()> {
         info!("🌳 Merkle Tree Manager starting...");
 
         // Initialize all trees
@@ -58,6 +54,16 @@ impl MerkleTreeManager {
             info!("✅ Ensured tree '{}' exists", tree_name);
         }
 
+        // A crash mid-write can leave nodes, leaf count and root disagreeing,
+        // which the incremental rebuild below would build on
+        let repaired = self.repair_inconsistent_trees().await?;
+        if repaired > 0 {
+            warn!(
+                "🩹 Repaired {} inconsistent tree(s), see merkle_repairs",
+                repaired
+            );
+        }
+
         // Rebuild commitment trees from database
         info!("🔄 Rebuilding Mantle commitments tree...");
         self.rebuild_mantle_commitments_tree().await?;
@@ -82,8 +88,10 @@ impl MerkleTreeManager {
 
         info!("🌳 Merkle Tree Manager started successfully");
 
-        // Keep running
-        std::future::pending::<()>().await;
+        shutdown.wait().await;
+        // Let an append in progress finish before the process exits
+        let _lock = self.tree_locks.write().await;
+        info!("🌳 Merkle Tree Manager stopped");
         Ok(())
     }
 
@@ -134,7 +142,7 @@ impl MerkleTreeManager {
         {
             info!(
                 "⚠️  Leaf {} already exists in tree '{}' at index {}",
-                &leaf_hash[..10],
+                parse::short(leaf_hash),
                 tree_name,
                 existing_index
             );
@@ -154,11 +162,12 @@ impl MerkleTreeManager {
         // ✅ FIX: Set leaf count to ACTUAL count, not increment
         self.database
             .set_leaf_count(tree.tree_id, leaves.len() as i64)?;
+        self.remember_root(tree_name, &new_root, leaves.len());
 
         info!(
             "🌳 Tree '{}' updated: root={}, total_leaves={}",
             tree_name,
-            &new_root[..10],
+            parse::short(&new_root),
             leaves.len()
         );
 
@@ -186,7 +195,7 @@ impl MerkleTreeManager {
         {
             info!(
                 "⚠️  Fill {} already exists in tree '{}' at index {}, rebuilding tree anyway",
-                &intent_id[..10],
+                parse::short(intent_id),
                 tree_name,
                 existing_index
             );
@@ -208,11 +217,12 @@ impl MerkleTreeManager {
         self.database.update_merkle_root(tree.tree_id, &new_root)?;
         self.database
             .set_leaf_count(tree.tree_id, fills.len() as i64)?;
+        self.remember_root(tree_name, &new_root, fills.len());
 
         info!(
             "✅ Fill tree '{}' rebuilt: root={}, total_fills={}",
             tree_name,
-            &new_root[..10],
+            parse::short(&new_root),
             fills.len()
         );
 
@@ -281,6 +291,29 @@ impl MerkleTreeManager {
             .await
     }
 
+    /// Rewrite `chain`'s commitments and fills trees from scratch. Used after
+    /// a reorg, where a replaced leaf can sit anywhere in the tree and the
+    /// incremental last-leaf check would miss it.
+    pub async fn rebuild_chain_trees_from_scratch(&self, chain_name: &str) -> Result<()> {
+        let _lock = self.tree_locks.write().await;
+
+        let commitments_name = format!("{}_commitments", chain_name);
+        let tree = self
+            .database
+            .ensure_merkle_tree(&commitments_name, self.tree_depth as i32)?;
+        let leaves = self.database.get_all_commitments_for_chain(chain_name)?;
+        self.rebuild_tree_internal(tree.tree_id, &commitments_name, leaves, true)
+            .await?;
+
+        let fills_name = format!("{}_fills", chain_name);
+        let tree = self
+            .database
+            .ensure_merkle_tree(&fills_name, self.tree_depth as i32)?;
+        let fills = self.database.get_all_fills_for_chain(chain_name)?;
+        self.rebuild_tree_internal(tree.tree_id, &fills_name, fills, true)
+            .await
+    }
+
     /// Generic tree rebuild from chain commitments - FIXED VERSION
     async fn rebuild_tree_from_chain(
         &self,
@@ -298,7 +331,8 @@ impl MerkleTreeManager {
         // ✅ FIX: Fetch ALL leaves from database, don't use limit
         let leaves = self.database.get_all_commitments_for_chain(chain_name)?;
 
-        self.rebuild_tree_internal(tree_id, tree_name, leaves).await
+        self.rebuild_tree_internal(tree_id, tree_name, leaves, false)
+            .await
     }
 
     /// Generic tree rebuild from provided leaves
@@ -316,7 +350,8 @@ impl MerkleTreeManager {
             leaves.len()
         );
 
-        self.rebuild_tree_internal(tree_id, tree_name, leaves).await
+        self.rebuild_tree_internal(tree_id, tree_name, leaves, false)
+            .await
     }
 
     async fn rebuild_tree_internal(
@@ -324,85 +359,90 @@ impl MerkleTreeManager {
         tree_id: i32,
         tree_name: &str,
         leaves: Vec<String>,
+        full: bool,
     ) -> Result<()> {
-        self.database.clear_merkle_nodes_by_tree(tree_id)?;
-
         if leaves.is_empty() {
             info!(
                 "⚠️  Tree '{}' has no leaves, setting to zero root",
                 tree_name
             );
-            self.database.update_merkle_root(tree_id, ZERO_LEAF)?;
-            self.database.set_leaf_count(tree_id, 0)?;
+            self.database
+                .write_merkle_nodes(tree_id, &[], ZERO_LEAF, 0, true)?;
+            self.remember_root(tree_name, ZERO_LEAF, 0);
+            return Ok(());
+        }
+
+        let synced = if full {
+            0
+        } else {
+            self.synced_leaf_count(tree_id, tree_name, &leaves)?
+        };
+        if synced == leaves.len() {
+            info!(
+                "✅ Tree '{}' already in sync at {} leaves",
+                tree_name,
+                leaves.len()
+            );
             return Ok(());
         }
 
         info!(
-            "📊 Building tree '{}' with {} leaves",
+            "📊 Building tree '{}' with {} leaves ({} new)",
             tree_name,
-            leaves.len()
+            leaves.len(),
+            leaves.len() - synced
         );
 
-        let tree_size = std::cmp::max(2, Self::next_power_of_2(leaves.len()));
-        let mut current_layer = leaves.clone();
-        current_layer.resize(tree_size, ZERO_LEAF.to_string());
-
-        let mut level = 0;
-        let mut current_size = tree_size;
-
-        while current_size > 0 {
-            for (idx, hash) in current_layer.iter().enumerate() {
-                self.database
-                    .store_merkle_node(tree_id, level, idx as i64, hash)?;
-            }
-
-            if current_size == 1 {
-                break;
-            }
-
-            let mut next_layer = Vec::with_capacity(current_size / 2);
-            for i in 0..(current_size / 2) {
-                next_layer.push(self.hash_pair(&current_layer[2 * i], &current_layer[2 * i + 1])?);
-            }
-
-            current_layer = next_layer;
-            current_size /= 2;
-            level += 1;
-        }
-
-        let root = &current_layer[0];
+        let leaf_count = leaves.len();
+        let layers = MerkleProofGenerator::tree_layers(leaves)?;
+        let nodes = changed_nodes(&layers, synced);
+        let root = &layers[layers.len() - 1][0];
 
-        self.database.update_merkle_root(tree_id, root)?;
-        self.database.set_leaf_count(tree_id, leaves.len() as i64)?;
+        self.database
+            .write_merkle_nodes(tree_id, &nodes, root, leaf_count as i64, synced == 0)?;
+        self.remember_root(tree_name, root, leaf_count);
 
         info!(
-            "✅ Tree '{}' rebuilt: root={}, leaves={}",
+            "✅ Tree '{}' rebuilt: root={}, leaves={}, nodes_written={}",
             tree_name,
-            &root[..10],
-            leaves.len()
+            parse::short(root),
+            leaf_count,
+            nodes.len()
         );
 
         Ok(())
     }
 
-    fn compute_root_from_leaves(&self, leaves: &[String]) -> Result<String> {
-        if leaves.is_empty() {
-            return Ok(ZERO_LEAF.to_string());
-        }
+    /// How many leading `leaves` the stored tree already covers. Zero when the
+    /// stored tree is larger than the event set or its last leaf no longer
+    /// matches, which forces a full rebuild.
+    fn synced_leaf_count(&self, tree_id: i32, tree_name: &str, leaves: &[String]) -> Result<usize> {
+        let stored = match self.database.get_merkle_tree_by_name(tree_name)? {
+            Some(tree) => tree.leaf_count as usize,
+            None => return Ok(0),
+        };
 
-        let tree_size = std::cmp::max(2, Self::next_power_of_2(leaves.len()));
-        let mut layer: Vec<String> = leaves.to_vec();
-        layer.resize(tree_size, ZERO_LEAF.to_string());
+        if stored == 0 || stored > leaves.len() {
+            return Ok(0);
+        }
 
-        while layer.len() > 1 {
-            let mut next_layer = Vec::with_capacity(layer.len() / 2);
-            for i in 0..(layer.len() / 2) {
-                next_layer.push(self.hash_pair(&layer[2 * i], &layer[2 * i + 1])?);
+        let last = self
+            .database
+            .get_merkle_node(tree_id, 0, stored as i64 - 1)?;
+        match last {
+            Some(node) if node.hash.eq_ignore_ascii_case(&leaves[stored - 1]) => Ok(stored),
+            _ => {
+                warn!(
+                    "⚠️  Tree '{}' diverged from stored leaves, rebuilding from scratch",
+                    tree_name
+                );
+                Ok(0)
             }
-            layer = next_layer;
         }
+    }
 
-        Ok(layer[0].clone())
+    fn compute_root_from_leaves(&self, leaves: &[String]) -> Result<String> {
+        MerkleProofGenerator::root_from_leaves(leaves.to_vec())
     }
 
     /// Get commitment proof with specific tree size
@@ -503,16 +543,6 @@ impl MerkleTreeManager {
         intent_id: &str,
         limit: usize,
     ) -> Result<(Vec<String>, u32)> {
-        // Get fills instead of commitments for fill tree
-        let fills = self.database.get_fills_for_tree("mantle", limit as i64)?;
-
-        // Find intent_id position in fills
-        let index = fills
-            .iter()
-            .position(|f| f.to_lowercase() == intent_id.to_lowercase())
-            .ok_or_else(|| anyhow!("Intent {} not found in mantle fills", intent_id))?;
-
-        // Generate proof using the same proof generator logic
         let (proof, index, _root) = self
             .proof_generator
             .generate_fill_proof("mantle", intent_id, limit)?;
@@ -525,13 +555,6 @@ impl MerkleTreeManager {
         intent_id: &str,
         limit: usize,
     ) -> Result<(Vec<String>, u32)> {
-        let fills = self.database.get_fills_for_tree("ethereum", limit as i64)?;
-
-        let index = fills
-            .iter()
-            .position(|f| f.to_lowercase() == intent_id.to_lowercase())
-            .ok_or_else(|| anyhow!("Intent {} not found in ethereum fills", intent_id))?;
-
         let (proof, index, _root) = self
             .proof_generator
             .generate_fill_proof("ethereum", intent_id, limit)?;
@@ -556,42 +579,80 @@ impl MerkleTreeManager {
     pub fn get_proof_generator(&self) -> Arc<MerkleProofGenerator> {
         self.proof_generator.clone()
     }
+}
 
-    /// Hash a pair of nodes (sorted)
-    fn hash_pair(&self, a: &str, b: &str) -> Result<String> {
-        use ethers::core::utils::keccak256;
-        use ethers::types::H256;
+/// Nodes of `layers` touched by appending leaves from index `from_leaf` on,
+/// as `(level, node_index, hash)`. Every node at or right of the first new
+/// leaf's ancestor changes, including zero padding and any levels added
+/// because the tree grew to the next power of two.
+fn changed_nodes(layers: &[Vec<String>], from_leaf: usize) -> Vec<(i32, i64, String)> {
+    layers
+        .iter()
+        .enumerate()
+        .flat_map(|(level, layer)| {
+            layer
+                .iter()
+                .enumerate()
+                .skip(from_leaf >> level)
+                .map(move |(idx, hash)| (level as i32, idx as i64, hash.clone()))
+        })
+        .collect()
+}
 
-        let a_bytes = H256::from_slice(&hex::decode(a.trim_start_matches("0x"))?);
-        let b_bytes = H256::from_slice(&hex::decode(b.trim_start_matches("0x"))?);
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaf(n: u8) -> String {
+        format!("0x{}", hex::encode([n; 32]))
+    }
+
+    #[test]
+    fn test_changed_nodes_full_rebuild_covers_every_node() {
+        let layers = MerkleProofGenerator::tree_layers((1..=3).map(leaf).collect()).unwrap();
+        let total: usize = layers.iter().map(Vec::len).sum();
+
+        assert_eq!(changed_nodes(&layers, 0).len(), total);
+    }
+
+    #[test]
+    fn test_changed_nodes_append_touches_only_right_path() {
+        let layers = MerkleProofGenerator::tree_layers((1..=5).map(leaf).collect()).unwrap();
+        let positions: Vec<(i32, i64)> = changed_nodes(&layers, 4)
+            .into_iter()
+            .map(|(level, idx, _)| (level, idx))
+            .collect();
+
+        assert_eq!(
+            positions,
+            vec![
+                (0, 4),
+                (0, 5),
+                (0, 6),
+                (0, 7),
+                (1, 2),
+                (1, 3),
+                (2, 1),
+                (3, 0)
+            ]
+        );
+    }
 
-        let hash = if a_bytes < b_bytes {
-            let mut concat = [0u8; 64];
-            concat[..32].copy_from_slice(a_bytes.as_bytes());
-            concat[32..].copy_from_slice(b_bytes.as_bytes());
-            keccak256(concat)
-        } else {
-            let mut concat = [0u8; 64];
-            concat[..32].copy_from_slice(b_bytes.as_bytes());
-            concat[32..].copy_from_slice(a_bytes.as_bytes());
-            keccak256(concat)
-        };
+    #[test]
+    fn test_incremental_nodes_match_full_rebuild() {
+        let before = MerkleProofGenerator::tree_layers((1..=3).map(leaf).collect()).unwrap();
+        let after = MerkleProofGenerator::tree_layers((1..=6).map(leaf).collect()).unwrap();
 
-        Ok(format!("0x{}", hex::encode(hash)))
-    }
+        let mut stored: std::collections::HashMap<(i32, i64), String> = changed_nodes(&before, 0)
+            .into_iter()
+            .map(|(level, idx, hash)| ((level, idx), hash))
+            .collect();
+        for (level, idx, hash) in changed_nodes(&after, 3) {
+            stored.insert((level, idx), hash);
+        }
 
-    /// Calculate next power of 2
-    fn next_power_of_2(n: usize) -> usize {
-        if n == 0 {
-            return 1;
+        for (level, idx, hash) in changed_nodes(&after, 0) {
+            assert_eq!(stored.get(&(level, idx)), Some(&hash));
         }
-        let mut p = n - 1;
-        p |= p >> 1;
-        p |= p >> 2;
-        p |= p >> 4;
-        p |= p >> 8;
-        p |= p >> 16;
-        p |= p >> 32;
-        p + 1
     }
 }
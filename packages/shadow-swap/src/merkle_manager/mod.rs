@@ -1,3 +1,6 @@
+pub mod consistency;
 pub mod merkle_manager;
 pub mod model;
+pub mod proof_cache;
 pub mod proof_generator;
+pub mod root_history;
@@ -0,0 +1,108 @@
+use std::{collections::HashMap, sync::Mutex};
+
+/// Proofs per tree against its current root. A tree holds proofs for one
+/// root at a time, so an entry for a replaced root is never served.
+#[derive(Debug)]
+pub struct ProofCache {
+    /// Proofs kept per tree; `0` turns the cache off.
+    max_entries: usize,
+    trees: Mutex<HashMap<String, CachedRoot>>,
+}
+
+#[derive(Debug)]
+struct CachedRoot {
+    root: String,
+    proofs: HashMap<usize, Vec<String>>,
+}
+
+impl ProofCache {
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries,
+            trees: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Reads `MERKLE_PROOF_CACHE_SIZE` (default 4096).
+    pub fn from_env() -> Self {
+        Self::new(
+            std::env::var("MERKLE_PROOF_CACHE_SIZE")
+                .unwrap_or_else(|_| "4096".to_string())
+                .parse()
+                .unwrap_or(4096),
+        )
+    }
+
+    pub fn get(&self, tree_name: &str, root: &str, leaf_index: usize) -> Option<Vec<String>> {
+        let trees = self.trees.lock().unwrap_or_else(|e| e.into_inner());
+        let cached = trees.get(tree_name)?;
+        if !cached.root.eq_ignore_ascii_case(root) {
+            return None;
+        }
+        cached.proofs.get(&leaf_index).cloned()
+    }
+
+    pub fn insert(&self, tree_name: &str, root: &str, leaf_index: usize, proof: Vec<String>) {
+        if self.max_entries == 0 {
+            return;
+        }
+
+        let mut trees = self.trees.lock().unwrap_or_else(|e| e.into_inner());
+        let cached = trees
+            .entry(tree_name.to_string())
+            .or_insert_with(|| CachedRoot {
+                root: root.to_string(),
+                proofs: HashMap::new(),
+            });
+        if !cached.root.eq_ignore_ascii_case(root) {
+            cached.root = root.to_string();
+            cached.proofs.clear();
+        }
+        // Path proofs are cheap to recompute, so a full tree simply starts over
+        if cached.proofs.len() >= self.max_entries {
+            cached.proofs.clear();
+        }
+        cached.proofs.insert(leaf_index, proof);
+    }
+
+    /// Drop `tree_name`'s proofs unless they were built against `root`.
+    pub fn invalidate(&self, tree_name: &str, root: &str) {
+        let mut trees = self.trees.lock().unwrap_or_else(|e| e.into_inner());
+        if trees
+            .get(tree_name)
+            .is_some_and(|cached| !cached.root.eq_ignore_ascii_case(root))
+        {
+            trees.remove(tree_name);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_serves_only_the_current_root() {
+        let cache = ProofCache::new(2);
+        cache.insert("mantle_commitments", "0xaa", 0, vec!["0x01".into()]);
+        cache.insert("mantle_commitments", "0xaa", 1, vec!["0x02".into()]);
+
+        assert_eq!(
+            cache.get("mantle_commitments", "0xAA", 1),
+            Some(vec!["0x02".to_string()])
+        );
+        assert!(cache.get("mantle_fills", "0xaa", 1).is_none());
+
+        // A new root evicts every proof built against the old one
+        cache.invalidate("mantle_commitments", "0xbb");
+        assert!(cache.get("mantle_commitments", "0xaa", 0).is_none());
+
+        cache.insert("mantle_commitments", "0xbb", 0, vec!["0x03".into()]);
+        cache.insert("mantle_commitments", "0xcc", 1, vec!["0x04".into()]);
+        assert!(cache.get("mantle_commitments", "0xbb", 0).is_none());
+
+        let disabled = ProofCache::new(0);
+        disabled.insert("mantle_commitments", "0xcc", 1, vec!["0x04".into()]);
+        assert!(disabled.get("mantle_commitments", "0xcc", 1).is_none());
+    }
+}
@@ -1,23 +1,33 @@
 use anyhow::{Context, Result, anyhow};
 use ethers::utils::keccak256;
-use std::sync::Arc;
-use tracing::{debug, info};
+use mantle_core::parse;
+use std::{collections::HashMap, sync::Arc};
+use tracing::{debug, info, warn};
 
-use crate::database::database::Database;
+use crate::{database::database::Database, merkle_manager::proof_cache::ProofCache};
 
 const ZERO_LEAF: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
 
 pub struct MerkleProofGenerator {
     database: Arc<Database>,
+    cache: ProofCache,
 }
 
 impl MerkleProofGenerator {
     pub fn new(database: Arc<Database>) -> Self {
-        Self { database }
+        Self {
+            database,
+            cache: ProofCache::from_env(),
+        }
+    }
+
+    /// Forget cached proofs of `tree_name` built against any root but `root`.
+    pub fn invalidate_proofs(&self, tree_name: &str, root: &str) {
+        self.cache.invalidate(tree_name, root);
     }
 
     /// Hash a pair of nodes (sorted order like Solidity)
-    fn hash_pair(left: &str, right: &str) -> Result<String> {
+    pub(crate) fn hash_pair(left: &str, right: &str) -> Result<String> {
         let left_bytes =
             hex::decode(left.trim_start_matches("0x")).context("Failed to decode left hash")?;
         let right_bytes =
@@ -58,6 +68,115 @@ impl MerkleProofGenerator {
         p + 1
     }
 
+    /// Every layer of the tree over `leaves`, padded with zero leaves to a
+    /// power of two (at least 2). Layer 0 is the padded leaves and the last
+    /// layer holds only the root, matching the rows in `merkle_nodes`.
+    pub fn tree_layers(mut leaves: Vec<String>) -> Result<Vec<Vec<String>>> {
+        let tree_size = std::cmp::max(2, Self::next_power_of_2(leaves.len()));
+        leaves.resize(tree_size, ZERO_LEAF.to_string());
+
+        let mut layers = vec![leaves];
+        while let Some(layer) = layers.last()
+            && layer.len() > 1
+        {
+            let next_layer = layer
+                .chunks(2)
+                .map(|pair| Self::hash_pair(&pair[0], &pair[1]))
+                .collect::<Result<Vec<String>>>()?;
+            layers.push(next_layer);
+        }
+
+        Ok(layers)
+    }
+
+    /// Root over `leaves`; the zero leaf for an empty tree.
+    pub fn root_from_leaves(leaves: Vec<String>) -> Result<String> {
+        if leaves.is_empty() {
+            return Ok(ZERO_LEAF.to_string());
+        }
+
+        let layers = Self::tree_layers(leaves)?;
+        Ok(layers[layers.len() - 1][0].clone())
+    }
+
+    /// Sibling of the path node at each level below the root.
+    pub fn proof_from_layers(layers: &[Vec<String>], index: usize) -> Vec<String> {
+        layers[..layers.len() - 1]
+            .iter()
+            .enumerate()
+            .map(|(level, layer)| layer[(index >> level) ^ 1].clone())
+            .collect()
+    }
+
+    /// Proof for `leaf` read from the stored nodes of `tree_name`: one
+    /// sibling per level instead of rebuilding the tree from every leaf.
+    /// `None` when the stored nodes are not the tree over exactly `limit`
+    /// leaves, e.g. after an append that did not rewrite them or for a
+    /// limit behind the current size; callers then rebuild from the leaves.
+    fn stored_proof(
+        &self,
+        tree_name: &str,
+        leaf: &str,
+        limit: usize,
+    ) -> Result<Option<(Vec<String>, usize, String)>> {
+        let Some(tree) = self.database.get_merkle_tree_by_name(tree_name)? else {
+            return Ok(None);
+        };
+        if limit == 0 || tree.leaf_count != limit as i64 {
+            return Ok(None);
+        }
+        let Some(index) =
+            self.database
+                .find_merkle_leaf_index(tree.tree_id, leaf, tree.leaf_count)?
+        else {
+            return Ok(None);
+        };
+        let index = index as usize;
+
+        if let Some(proof) = self.cache.get(tree_name, &tree.root, index) {
+            debug!("📋 Proof cache hit for '{}' leaf {}", tree_name, index);
+            return Ok(Some((proof, index, tree.root)));
+        }
+
+        let height = std::cmp::max(2, Self::next_power_of_2(limit)).trailing_zeros() as i32;
+        let mut positions: Vec<(i32, i64)> = (0..height)
+            .map(|level| (level, ((index >> level) ^ 1) as i64))
+            .collect();
+        positions.push((height, 0));
+
+        let hashes: HashMap<(i32, i64), String> = self
+            .database
+            .get_merkle_nodes_at(tree.tree_id, &positions)?
+            .into_iter()
+            .map(|node| ((node.level, node.node_index), node.hash))
+            .collect();
+
+        match hashes.get(&(height, 0)) {
+            Some(stored_root) if stored_root.eq_ignore_ascii_case(&tree.root) => {}
+            _ => return Ok(None),
+        }
+        let Some(proof) = positions[..height as usize]
+            .iter()
+            .map(|position| hashes.get(position).cloned())
+            .collect::<Option<Vec<String>>>()
+        else {
+            return Ok(None);
+        };
+
+        // Leftover rows from an older tree must never yield a proof
+        if !Self::verify_proof(&proof, &tree.root, leaf, index)? {
+            warn!(
+                "⚠️  Stored nodes of '{}' do not prove leaf {}, rebuilding from leaves",
+                tree_name, index
+            );
+            return Ok(None);
+        }
+
+        self.cache
+            .insert(tree_name, &tree.root, index, proof.clone());
+        Ok(Some((proof, index, tree.root)))
+    }
+
     /// Generate Merkle proof for a commitment - FIXED VERSION
     ///
     /// # Arguments
@@ -74,11 +193,17 @@ impl MerkleProofGenerator {
         info!(
             "📋 Generating proof for chain '{}', commitment={}, limit={}",
             chain,
-            &commitment[..10],
+            parse::short(commitment),
             limit
         );
 
-        let mut leaves = self
+        if let Some(stored) =
+            self.stored_proof(&format!("{}_commitments", chain), commitment, limit)?
+        {
+            return Ok(stored);
+        }
+
+        let leaves = self
             .database
             .get_commitments_for_tree(chain, limit as i64)?;
 
@@ -97,7 +222,7 @@ impl MerkleProofGenerator {
             .ok_or_else(|| {
                 anyhow!(
                     "Commitment {} not found in first {} leaves for chain '{}'",
-                    &commitment[..10],
+                    parse::short(commitment),
                     limit,
                     chain
                 )
@@ -109,43 +234,20 @@ impl MerkleProofGenerator {
             leaves.len()
         );
 
-        let tree_size = std::cmp::max(2, Self::next_power_of_2(leaves.len()));
-        leaves.resize(tree_size, ZERO_LEAF.to_string());
-
-        let height = (tree_size as f64).log2() as usize;
-
-        info!("🌳 Tree size: {} (min 2), height: {}", tree_size, height);
-
-        let mut layer = leaves;
-        let mut proof = Vec::with_capacity(height);
-        let mut current_index = leaf_index;
-
-        for level in 0..height {
-            let sibling_index = current_index ^ 1;
-            proof.push(layer[sibling_index].clone());
-
-            debug!(
-                "  Level {}: index={}, sibling={}",
-                level,
-                current_index,
-                &layer[sibling_index][..10]
-            );
-
-            let mut next_layer = Vec::with_capacity(layer.len() / 2);
-            for i in 0..(layer.len() / 2) {
-                next_layer.push(Self::hash_pair(&layer[2 * i], &layer[2 * i + 1])?);
-            }
-
-            layer = next_layer;
-            current_index /= 2;
-        }
+        let layers = Self::tree_layers(leaves)?;
+        info!(
+            "🌳 Tree size: {} (min 2), height: {}",
+            layers[0].len(),
+            layers.len() - 1
+        );
 
-        let root = layer[0].clone();
+        let proof = Self::proof_from_layers(&layers, leaf_index);
+        let root = layers[layers.len() - 1][0].clone();
 
         info!(
             "✅ Proof generated: {} siblings, root={}",
             proof.len(),
-            &root[..10]
+            parse::short(&root)
         );
 
         Ok((proof, leaf_index, root))
@@ -154,40 +256,18 @@ impl MerkleProofGenerator {
     pub fn compute_root(&self, chain: &str) -> Result<String> {
         let leaves = self.database.get_all_commitments_for_chain(chain)?;
 
-        if leaves.is_empty() {
-            return Ok(ZERO_LEAF.to_string());
-        }
-
-        let tree_size = std::cmp::max(2, Self::next_power_of_2(leaves.len()));
-        let mut layer: Vec<String> = leaves;
-        layer.resize(tree_size, ZERO_LEAF.to_string());
-
-        while layer.len() > 1 {
-            let mut next_layer = Vec::with_capacity(layer.len() / 2);
-            for i in 0..(layer.len() / 2) {
-                next_layer.push(Self::hash_pair(&layer[2 * i], &layer[2 * i + 1])?);
-            }
-            layer = next_layer;
-        }
-
-        Ok(layer[0].clone())
+        Self::root_from_leaves(leaves)
     }
 
     /// Verify a Merkle proof
-    pub fn verify_proof(
-        &self,
-        proof: &[String],
-        root: &str,
-        leaf: &str,
-        index: usize,
-    ) -> Result<bool> {
+    pub fn verify_proof(proof: &[String], root: &str, leaf: &str, index: usize) -> Result<bool> {
         let mut computed_hash = leaf.to_string();
         let mut current_index = index;
 
         debug!("🔍 Verifying proof:");
-        debug!("  Leaf: {}", &leaf[..10]);
+        debug!("  Leaf: {}", parse::short(leaf));
         debug!("  Index: {}", index);
-        debug!("  Expected root: {}", &root[..10]);
+        debug!("  Expected root: {}", parse::short(root));
 
         for (level, proof_element) in proof.iter().enumerate() {
             let is_right = (current_index & 1) == 1;
@@ -202,13 +282,13 @@ impl MerkleProofGenerator {
                 "  Level {}: {} + sibling → {}",
                 level,
                 if is_right { "sibling" } else { "hash" },
-                &computed_hash[..10]
+                parse::short(&computed_hash)
             );
 
             current_index >>= 1;
         }
 
-        debug!("  Computed root: {}", &computed_hash[..10]);
+        debug!("  Computed root: {}", parse::short(&computed_hash));
 
         let is_valid = computed_hash.to_lowercase() == root.to_lowercase();
 
@@ -237,11 +317,15 @@ impl MerkleProofGenerator {
         info!(
             "📋 Generating fill proof for chain '{}', intent_id={}, limit={}",
             chain,
-            &intent_id[..10],
+            parse::short(intent_id),
             limit
         );
 
-        let mut fills = self.database.get_fills_for_tree(chain, limit as i64)?;
+        if let Some(stored) = self.stored_proof(&format!("{}_fills", chain), intent_id, limit)? {
+            return Ok(stored);
+        }
+
+        let fills = self.database.get_fills_for_tree(chain, limit as i64)?;
 
         if fills.is_empty() {
             return Err(anyhow!(
@@ -257,7 +341,7 @@ impl MerkleProofGenerator {
             .ok_or_else(|| {
                 anyhow!(
                     "Intent ID {} not found in first {} fills for chain '{}'",
-                    &intent_id[..10],
+                    parse::short(intent_id),
                     limit,
                     chain
                 )
@@ -269,46 +353,20 @@ impl MerkleProofGenerator {
             fills.len()
         );
 
-        let tree_size = std::cmp::max(2, Self::next_power_of_2(fills.len()));
-        fills.resize(tree_size, ZERO_LEAF.to_string());
-
-        let height = (tree_size as f64).log2() as usize;
-
+        let layers = Self::tree_layers(fills)?;
         info!(
             "🌳 Fill tree size: {} (min 2), height: {}",
-            tree_size, height
+            layers[0].len(),
+            layers.len() - 1
         );
 
-        let mut layer = fills;
-        let mut proof = Vec::with_capacity(height);
-        let mut current_index = fill_index;
-
-        for level in 0..height {
-            let sibling_index = current_index ^ 1;
-            proof.push(layer[sibling_index].clone());
-
-            debug!(
-                "  Level {}: index={}, sibling={}",
-                level,
-                current_index,
-                &layer[sibling_index][..10]
-            );
-
-            let mut next_layer = Vec::with_capacity(layer.len() / 2);
-            for i in 0..(layer.len() / 2) {
-                next_layer.push(Self::hash_pair(&layer[2 * i], &layer[2 * i + 1])?);
-            }
-
-            layer = next_layer;
-            current_index /= 2;
-        }
-
-        let root = layer[0].clone();
+        let proof = Self::proof_from_layers(&layers, fill_index);
+        let root = layers[layers.len() - 1][0].clone();
 
         info!(
             "✅ Fill proof generated: {} siblings, root={}",
             proof.len(),
-            &root[..10]
+            parse::short(&root)
         );
 
         Ok((proof, fill_index, root))
@@ -317,23 +375,7 @@ impl MerkleProofGenerator {
     pub fn compute_fill_root(&self, chain: &str) -> Result<String> {
         let fills = self.database.get_all_fills_for_chain(chain)?;
 
-        if fills.is_empty() {
-            return Ok(ZERO_LEAF.to_string());
-        }
-
-        let tree_size = std::cmp::max(2, Self::next_power_of_2(fills.len()));
-        let mut layer: Vec<String> = fills;
-        layer.resize(tree_size, ZERO_LEAF.to_string());
-
-        while layer.len() > 1 {
-            let mut next_layer = Vec::with_capacity(layer.len() / 2);
-            for i in 0..(layer.len() / 2) {
-                next_layer.push(Self::hash_pair(&layer[2 * i], &layer[2 * i + 1])?);
-            }
-            layer = next_layer;
-        }
-
-        Ok(layer[0].clone())
+        Self::root_from_leaves(fills)
     }
 
     /// Get Ethereum proof
@@ -478,4 +520,233 @@ mod tests {
         let result = MerkleProofGenerator::hash_pair(a, b);
         assert!(result.is_err());
     }
+
+    #[test]
+    #[serial_test::serial(db)]
+    fn test_stored_proof_reads_path_from_nodes() -> Result<()> {
+        let Some(database) = crate::database::test_support::test_database()? else {
+            return Ok(());
+        };
+        let database = Arc::new(database);
+        let generator = MerkleProofGenerator::new(database.clone());
+
+        let leaves: Vec<String> = (1..=5u8)
+            .map(|n| format!("0x{}", hex::encode([n; 32])))
+            .collect();
+        let layers = MerkleProofGenerator::tree_layers(leaves.clone())?;
+        let root = layers[layers.len() - 1][0].clone();
+        let nodes: Vec<(i32, i64, String)> = layers
+            .iter()
+            .enumerate()
+            .flat_map(|(level, layer)| {
+                layer
+                    .iter()
+                    .enumerate()
+                    .map(move |(index, hash)| (level as i32, index as i64, hash.clone()))
+            })
+            .collect();
+        let tree = database.ensure_merkle_tree("mantle_fills", 20)?;
+        database.write_merkle_nodes(tree.tree_id, &nodes, &root, 5, true)?;
+
+        let (proof, index, proven_root) = generator
+            .stored_proof("mantle_fills", &leaves[3], 5)?
+            .unwrap();
+        assert_eq!(index, 3);
+        assert_eq!(proven_root, root);
+        assert_eq!(proof, MerkleProofGenerator::proof_from_layers(&layers, 3));
+        assert!(generator.cache.get("mantle_fills", &root, 3).is_some());
+
+        // Only the tree over exactly the stored leaves is served from nodes
+        assert!(
+            generator
+                .stored_proof("mantle_fills", &leaves[3], 4)?
+                .is_none()
+        );
+        assert!(generator.stored_proof("mantle_fills", &root, 5)?.is_none());
+
+        // An append that moved the root without rewriting nodes
+        database.update_merkle_root(tree.tree_id, &leaves[0])?;
+        assert!(
+            generator
+                .stored_proof("mantle_fills", &leaves[3], 5)?
+                .is_none()
+        );
+
+        Ok(())
+    }
+
+    // ============   PROPERTIES    ===============
+
+    use proptest::prelude::*;
+
+    fn to_hex(bytes: &[u8; 32]) -> String {
+        format!("0x{}", hex::encode(bytes))
+    }
+
+    /// Independent of the string-based tree code: raw bytes, recursion.
+    fn reference_root(nodes: &[[u8; 32]]) -> [u8; 32] {
+        if nodes.len() == 1 {
+            return nodes[0];
+        }
+        let (left, right) = nodes.split_at(nodes.len() / 2);
+        let (a, b) = (reference_root(left), reference_root(right));
+        let (first, second) = if a < b { (a, b) } else { (b, a) };
+        keccak256([first, second].concat())
+    }
+
+    fn reference_proof(nodes: &[[u8; 32]], index: usize) -> Vec<[u8; 32]> {
+        if nodes.len() == 1 {
+            return Vec::new();
+        }
+        let half = nodes.len() / 2;
+        let (left, right) = nodes.split_at(half);
+        let (mut proof, sibling) = if index < half {
+            (reference_proof(left, index), reference_root(right))
+        } else {
+            (reference_proof(right, index - half), reference_root(left))
+        };
+        proof.push(sibling);
+        proof
+    }
+
+    fn padded(leaves: &[[u8; 32]]) -> Vec<[u8; 32]> {
+        let mut nodes = leaves.to_vec();
+        nodes.resize(leaves.len().next_power_of_two().max(2), [0u8; 32]);
+        nodes
+    }
+
+    fn leaf_set() -> impl Strategy<Value = Vec<[u8; 32]>> {
+        prop::collection::hash_set(any::<[u8; 32]>(), 1..48)
+            .prop_map(|leaves| leaves.into_iter().collect())
+    }
+
+    proptest! {
+        #[test]
+        fn prop_proof_verifies_for_every_leaf(
+            leaves in leaf_set(),
+            pick in any::<prop::sample::Index>(),
+        ) {
+            let index = pick.index(leaves.len());
+            let hex_leaves: Vec<String> = leaves.iter().map(to_hex).collect();
+
+            let layers = MerkleProofGenerator::tree_layers(hex_leaves.clone()).unwrap();
+            let root = MerkleProofGenerator::root_from_leaves(hex_leaves.clone()).unwrap();
+            let proof = MerkleProofGenerator::proof_from_layers(&layers, index);
+
+            prop_assert_eq!(&layers[layers.len() - 1][0], &root);
+            prop_assert_eq!(1usize << proof.len(), layers[0].len());
+            prop_assert!(
+                MerkleProofGenerator::verify_proof(&proof, &root, &hex_leaves[index], index)
+                    .unwrap()
+            );
+
+            // A different leaf never verifies against the same path
+            let other = keccak256(leaves[index]);
+            prop_assume!(!leaves.contains(&other));
+            prop_assert!(
+                !MerkleProofGenerator::verify_proof(&proof, &root, &to_hex(&other), index)
+                    .unwrap()
+            );
+        }
+
+        /// Proofs read back from the stored layers (`merkle_nodes`) match a
+        /// proof computed straight from the leaves.
+        #[test]
+        fn prop_layers_match_reference_tree(
+            leaves in leaf_set(),
+            pick in any::<prop::sample::Index>(),
+        ) {
+            let index = pick.index(leaves.len());
+            let hex_leaves: Vec<String> = leaves.iter().map(to_hex).collect();
+            let nodes = padded(&leaves);
+
+            let layers = MerkleProofGenerator::tree_layers(hex_leaves.clone()).unwrap();
+            prop_assert_eq!(
+                MerkleProofGenerator::root_from_leaves(hex_leaves).unwrap(),
+                to_hex(&reference_root(&nodes))
+            );
+            prop_assert_eq!(
+                MerkleProofGenerator::proof_from_layers(&layers, index),
+                reference_proof(&nodes, index)
+                    .iter()
+                    .map(to_hex)
+                    .collect::<Vec<String>>()
+            );
+        }
+
+        /// Appending one leaf at a time ends on the root a full rebuild gives,
+        /// and every earlier leaf's proof stays valid against the new root.
+        #[test]
+        fn prop_appends_match_rebuild(leaves in leaf_set()) {
+            let hex_leaves: Vec<String> = leaves.iter().map(to_hex).collect();
+
+            let mut appended = Vec::new();
+            let mut root = String::new();
+            for leaf in &hex_leaves {
+                appended.push(leaf.clone());
+                root = MerkleProofGenerator::root_from_leaves(appended.clone()).unwrap();
+            }
+            prop_assert_eq!(
+                &root,
+                &MerkleProofGenerator::root_from_leaves(hex_leaves.clone()).unwrap()
+            );
+
+            let layers = MerkleProofGenerator::tree_layers(appended).unwrap();
+            for (index, leaf) in hex_leaves.iter().enumerate() {
+                let proof = MerkleProofGenerator::proof_from_layers(&layers, index);
+                prop_assert!(MerkleProofGenerator::verify_proof(&proof, &root, leaf, index).unwrap());
+            }
+        }
+
+        /// Sorted pair hashing makes siblings interchangeable, so the root
+        /// survives swapping a leaf with its sibling but not a reordering
+        /// across subtrees: append order has to be preserved on rebuild.
+        #[test]
+        fn prop_root_under_permutation(
+            leaves in prop::collection::hash_set(any::<[u8; 32]>(), 4..48)
+                .prop_map(|leaves| leaves.into_iter().collect::<Vec<_>>()),
+            pick in any::<prop::sample::Index>(),
+        ) {
+            let root = |leaves: &[[u8; 32]]| {
+                MerkleProofGenerator::root_from_leaves(leaves.iter().map(to_hex).collect())
+                    .unwrap()
+            };
+            let original = root(&leaves);
+            let index = pick.index(leaves.len() / 2 * 2);
+
+            let mut siblings_swapped = leaves.clone();
+            siblings_swapped.swap(index, index ^ 1);
+            prop_assert_eq!(&root(&siblings_swapped), &original);
+
+            let mut halves_swapped = leaves.clone();
+            halves_swapped.swap(0, 2);
+            prop_assert_ne!(&root(&halves_swapped), &original);
+
+            // Parity of the index does not change the outcome
+            let hex_leaves: Vec<String> = leaves.iter().map(to_hex).collect();
+            let layers = MerkleProofGenerator::tree_layers(hex_leaves.clone()).unwrap();
+            let proof = MerkleProofGenerator::proof_from_layers(&layers, index);
+            prop_assert!(
+                MerkleProofGenerator::verify_proof(&proof, &original, &hex_leaves[index], index ^ 1)
+                    .unwrap()
+            );
+        }
+
+        /// Byte-level fuzz: malformed nodes are rejected, never a panic.
+        #[test]
+        fn prop_hash_pair_rejects_malformed_input(
+            left in prop::collection::vec(any::<u8>(), 0..40),
+            right in prop::collection::vec(any::<u8>(), 0..40),
+            raw in "\\PC{0,70}",
+        ) {
+            let result = MerkleProofGenerator::hash_pair(
+                &format!("0x{}", hex::encode(&left)),
+                &format!("0x{}", hex::encode(&right)),
+            );
+            prop_assert_eq!(result.is_ok(), left.len() == 32 && right.len() == 32);
+
+            let _ = MerkleProofGenerator::hash_pair(&raw, &raw);
+            let _ = MerkleProofGenerator::tree_layers(vec![raw.clone()]);
+        }
+    }
 }
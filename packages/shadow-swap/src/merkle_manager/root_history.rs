@@ -0,0 +1,218 @@
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use tracing::warn;
+
+use crate::{
+    database::model::DbMerkleRootHistory, merkle_manager::merkle_manager::MerkleTreeManager,
+};
+
+/// How many past roots per tree stay provable, and whether the deployed
+/// contracts accept proofs against any of them or only the current root.
+#[derive(Debug, Clone, Copy)]
+pub struct RootHistoryPolicy {
+    pub window: i64,
+    pub known_root_verification: bool,
+}
+
+impl RootHistoryPolicy {
+    /// Reads `MERKLE_ROOT_HISTORY_SIZE` (default 64) and
+    /// `MERKLE_KNOWN_ROOT_VERIFICATION` (default false).
+    pub fn from_env() -> Self {
+        Self {
+            window: std::env::var("MERKLE_ROOT_HISTORY_SIZE")
+                .unwrap_or_else(|_| "64".to_string())
+                .parse()
+                .unwrap_or(64)
+                .max(1),
+            known_root_verification: std::env::var("MERKLE_KNOWN_ROOT_VERIFICATION")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+        }
+    }
+}
+
+/// Trees that can serve proofs: commitments and fills, per chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ProofTree<'a> {
+    Commitments(&'a str),
+    Fills(&'a str),
+}
+
+impl<'a> ProofTree<'a> {
+    fn parse(tree_name: &'a str) -> Result<Self> {
+        let (chain, kind) = tree_name
+            .split_once('_')
+            .ok_or_else(|| anyhow!("Unknown tree '{}'", tree_name))?;
+
+        if !matches!(chain, "ethereum" | "mantle") {
+            return Err(anyhow!("Unknown tree '{}'", tree_name));
+        }
+
+        match kind {
+            "commitments" => Ok(Self::Commitments(chain)),
+            "fills" => Ok(Self::Fills(chain)),
+            _ => Err(anyhow!("Tree '{}' does not serve proofs", tree_name)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RootWindow {
+    pub tree: String,
+    pub current_root: String,
+    pub current_leaf_count: i64,
+    pub window: i64,
+    pub known_root_verification: bool,
+    pub roots: Vec<DbMerkleRootHistory>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PinnedProof {
+    pub tree: String,
+    pub leaf: String,
+    pub leaf_index: usize,
+    pub proof: Vec<String>,
+    pub root: String,
+    pub leaf_count: i64,
+    pub is_current_root: bool,
+    /// False when the proof targets a past root and the contracts only
+    /// verify against the current one.
+    pub onchain_verifiable: bool,
+}
+
+impl MerkleTreeManager {
+    /// Record a freshly computed root and drop proofs cached against the
+    /// previous one. Failures only cost the ability to prove against this
+    /// root later, so they are logged, never propagated.
+    pub(crate) fn remember_root(&self, tree_name: &str, root: &str, leaf_count: usize) {
+        self.proof_generator.invalidate_proofs(tree_name, root);
+        if leaf_count == 0 {
+            return;
+        }
+
+        if let Err(e) = self.database.record_root_history(
+            tree_name,
+            root,
+            leaf_count as i64,
+            self.root_history.window,
+        ) {
+            warn!(
+                "⚠️  Failed to record root history for '{}': {}",
+                tree_name, e
+            );
+        }
+    }
+
+    pub async fn root_window(&self, tree_name: &str) -> Result<RootWindow> {
+        ProofTree::parse(tree_name)?;
+
+        let _lock = self.tree_locks.read().await;
+        let tree = self
+            .database
+            .get_merkle_tree_by_name(tree_name)?
+            .ok_or_else(|| anyhow!("Tree '{}' not found", tree_name))?;
+
+        Ok(RootWindow {
+            tree: tree_name.to_string(),
+            current_root: tree.root,
+            current_leaf_count: tree.leaf_count,
+            window: self.root_history.window,
+            known_root_verification: self.root_history.known_root_verification,
+            roots: self
+                .database
+                .get_root_history(tree_name, self.root_history.window)?,
+        })
+    }
+
+    /// Proof for `leaf` against `root`, or against the current root when none
+    /// is given. Past roots must still be inside the history window.
+    pub async fn proof_at_root(
+        &self,
+        tree_name: &str,
+        leaf: &str,
+        root: Option<&str>,
+    ) -> Result<PinnedProof> {
+        let tree = ProofTree::parse(tree_name)?;
+
+        let _lock = self.tree_locks.read().await;
+        let current = self
+            .database
+            .get_merkle_tree_by_name(tree_name)?
+            .ok_or_else(|| anyhow!("Tree '{}' not found", tree_name))?;
+
+        let (pinned_root, leaf_count) = match root {
+            None => (current.root.clone(), current.leaf_count),
+            Some(root) if root.eq_ignore_ascii_case(&current.root) => {
+                (current.root.clone(), current.leaf_count)
+            }
+            Some(root) => {
+                let historical = self
+                    .database
+                    .get_historical_root(tree_name, root)?
+                    .ok_or_else(|| {
+                        anyhow!(
+                            "Root {} is not among the last {} roots of '{}'",
+                            root,
+                            self.root_history.window,
+                            tree_name
+                        )
+                    })?;
+                (historical.root, historical.leaf_count)
+            }
+        };
+
+        let (proof, leaf_index, computed_root) = match tree {
+            ProofTree::Commitments(chain) => {
+                self.proof_generator
+                    .generate_proof(chain, leaf, leaf_count as usize)?
+            }
+            ProofTree::Fills(chain) => {
+                self.proof_generator
+                    .generate_fill_proof(chain, leaf, leaf_count as usize)?
+            }
+        };
+
+        // A rebuild that reordered leaves makes old roots unreproducible
+        if !computed_root.eq_ignore_ascii_case(&pinned_root) {
+            return Err(anyhow!(
+                "Leaves behind root {} changed since it was recorded (now {})",
+                pinned_root,
+                computed_root
+            ));
+        }
+
+        let is_current_root = pinned_root.eq_ignore_ascii_case(&current.root);
+
+        Ok(PinnedProof {
+            tree: tree_name.to_string(),
+            leaf: leaf.to_lowercase(),
+            leaf_index,
+            proof,
+            root: pinned_root,
+            leaf_count,
+            is_current_root,
+            onchain_verifiable: is_current_root || self.root_history.known_root_verification,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_commitment_and_fill_trees_serve_proofs() {
+        assert_eq!(
+            ProofTree::parse("mantle_commitments").unwrap(),
+            ProofTree::Commitments("mantle")
+        );
+        assert_eq!(
+            ProofTree::parse("ethereum_fills").unwrap(),
+            ProofTree::Fills("ethereum")
+        );
+        assert!(ProofTree::parse("ethereum_intents").is_err());
+        assert!(ProofTree::parse("arbitrum_fills").is_err());
+        assert!(ProofTree::parse("fills").is_err());
+    }
+}
@@ -1,9 +1,21 @@
 use std::collections::HashMap;
 
 use chrono::{DateTime, Utc};
+use mantle_core::{
+    counters::MonotonicCounter,
+    hex_types::{AddressHex, AmountDec, H256Hex},
+    token::TokenType,
+};
 use serde::{Deserialize, Serialize};
 
-use crate::relay_coordinator::model::{EthereumConfig, MantleConfig};
+use crate::{
+    config::{
+        profile::NetworkProfile,
+        schema::{self, defaults},
+    },
+    intent_workers::queue_metrics::ProcessingRate,
+    relay_coordinator::model::{EthereumConfig, MantleConfig},
+};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeConfig {
@@ -13,18 +25,30 @@ pub struct BridgeConfig {
     pub mantle: MantleConfig,
     pub relayer_address: String,
     pub fee_collector: String,
+    #[serde(default)]
+    pub profile: Option<NetworkProfile>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct ServerConfig {
+    #[serde(default = "defaults::host")]
     pub host: String,
+    #[serde(default = "defaults::port")]
     pub port: u16,
+    #[serde(deserialize_with = "schema::non_empty")]
     pub hmac_secret: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct DatabaseConfig {
+    #[serde(deserialize_with = "schema::postgres_url")]
     pub url: String,
+    #[serde(
+        default = "defaults::max_connections",
+        deserialize_with = "schema::positive"
+    )]
     pub max_connections: u32,
 }
 
@@ -58,15 +82,15 @@ pub struct MantleFill {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Intent {
-    pub id: String,
+    pub id: H256Hex,
     pub user_address: String,
     pub source_chain: String,
     pub dest_chain: String,
     pub source_token: String,
     pub dest_token: String,
-    pub amount: String,
-    pub dest_amount: String,
-    pub source_commitment: Option<String>,
+    pub amount: AmountDec,
+    pub dest_amount: AmountDec,
+    pub source_commitment: Option<H256Hex>,
     pub dest_fill_txid: Option<String>,
     pub dest_registration_txid: Option<String>,
     pub source_complete_txid: Option<String>,
@@ -82,12 +106,17 @@ pub struct Intent {
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IntentPrivacyParams {
-    pub intent_id: String,
-    pub commitment: Option<String>,
+    pub intent_id: H256Hex,
+    pub commitment: Option<H256Hex>,
+    /// ECIES ciphertexts, not the raw values.
     pub nullifier: Option<String>,
     pub secret: Option<String>,
-    pub recipient: Option<String>,
+    pub recipient: Option<AddressHex>,
     pub claim_signature: Option<String>,
+    /// Address the claim pays out to when the recipient delegated it, e.g.
+    /// an exchange deposit address. The claim signature covers it.
+    #[serde(default)]
+    pub claim_delegate: Option<AddressHex>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -96,6 +125,9 @@ pub enum IntentStatus {
     Committed,
     Registered,
     Pending,
+    /// The pool received less than the intent's amount; never registered,
+    /// refunded once the deadline passes.
+    Underpaid,
     Filled,
     SolverPaid,
     UserClaimed,
@@ -104,6 +136,55 @@ pub enum IntentStatus {
     Expired,
 }
 
+/// `bridge_events.event_type`, stored as its snake_case name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BridgeEventType {
+    IntentCreated,
+    IntentRegistered,
+    IntentFilled,
+    IntentSettled,
+    IntentRefunded,
+    WithdrawalClaimed,
+    RootSync,
+    NullifierUsed,
+}
+
+/// How strictly commitments must be unique across the merkle trees.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CommitmentUniqueness {
+    Off,
+    PerTree,
+    Global,
+}
+
+impl CommitmentUniqueness {
+    /// Reads `COMMITMENT_UNIQUENESS` (`off`, `tree` or `global`, default `global`).
+    pub fn from_env() -> anyhow::Result<Self> {
+        Self::parse(&std::env::var("COMMITMENT_UNIQUENESS").unwrap_or_default())
+    }
+
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "global" => Ok(Self::Global),
+            "off" => Ok(Self::Off),
+            "tree" | "per_tree" => Ok(Self::PerTree),
+            other => Err(anyhow::anyhow!(
+                "Invalid COMMITMENT_UNIQUENESS '{}' (use off, tree or global)",
+                other
+            )),
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::PerTree => "tree",
+            Self::Global => "global",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct IntentCreatedEvent {
     pub intent_id: String,
@@ -137,18 +218,36 @@ pub enum BridgeDirection {
 
 #[derive(Debug, Clone)]
 pub struct BridgeMetrics {
-    pub total_intents_processed: u64,
-    pub successful_bridges: u64,
-    pub failed_intents: u64,
-    pub refunded_intents: u64,
-    pub ethereum_fills: u64,
-    pub mantle_fills: u64,
-    pub ethereum_claims: u64,
-    pub mantle_claims: u64,
-    pub retry_attempts: u64,
+    pub total_intents_processed: MonotonicCounter,
+    pub successful_bridges: MonotonicCounter,
+    pub failed_intents: MonotonicCounter,
+    pub refunded_intents: MonotonicCounter,
+    pub ethereum_fills: MonotonicCounter,
+    pub mantle_fills: MonotonicCounter,
+    pub ethereum_claims: MonotonicCounter,
+    pub mantle_claims: MonotonicCounter,
+    pub retry_attempts: MonotonicCounter,
     pub last_error: Option<String>,
     pub uptime_seconds: u64,
+    /// Unix seconds this process started.
+    pub process_start_time: i64,
+    /// Unix seconds of the first start the restored counters cover.
+    pub counters_since: i64,
     pub volumes_by_token: HashMap<TokenType, u128>,
+    /// Backlog per token, keyed by worker queue (`registration`, `claim`, `settlement`).
+    pub queue_depths: HashMap<String, HashMap<String, usize>>,
+    /// Recent completions per worker queue.
+    pub processing_rates: HashMap<String, ProcessingRate>,
+    /// Latest worker failure per intent, cleared once the intent goes through.
+    pub intent_errors: HashMap<String, IntentError>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IntentError {
+    /// Worker queue that failed (`registration`, `settlement`).
+    pub queue: String,
+    pub error: String,
+    pub at: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone)]
@@ -160,15 +259,6 @@ pub struct TokenBridgeInfo {
     pub decimals: u8,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Serialize)]
-pub enum TokenType {
-    ETH,
-    USDC,
-    USDT,
-    WETH,
-    MNT,
-}
-
 // #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 // pub enum Chain {
 //     Ethereum,
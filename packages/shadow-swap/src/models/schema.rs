@@ -29,6 +29,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    claim_sponsorships (intent_id) {
+        intent_id -> Text,
+        sponsored -> Bool,
+        chain_id -> Nullable<Int4>,
+        claim_tx_hash -> Nullable<Text>,
+        gas_used -> Nullable<Int8>,
+        gas_cost_wei -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     ethereum_sepolia_intent_created (id) {
         id -> Int4,
@@ -47,6 +60,27 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    intent_annotations (id) {
+        id -> Int4,
+        intent_id -> Text,
+        operator -> Text,
+        kind -> Text,
+        reason -> Text,
+        previous_status -> Nullable<Text>,
+        new_status -> Nullable<Text>,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    metric_snapshots (instance) {
+        instance -> Text,
+        snapshot -> Jsonb,
+        saved_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     intent_privacy_params (intent_id) {
         intent_id -> Text,
@@ -57,6 +91,8 @@ diesel::table! {
         claim_signature -> Nullable<Text>,
         created_at -> Timestamptz,
         updated_at -> Timestamptz,
+        context_tag -> Text,
+        claim_delegate -> Nullable<Text>,
     }
 }
 
@@ -117,6 +153,16 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    merkle_root_history (id) {
+        id -> Int4,
+        tree_name -> Text,
+        root -> Text,
+        leaf_count -> Int8,
+        created_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     merkle_roots (tree_id) {
         tree_id -> Int4,
@@ -147,6 +193,19 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    quarantined_commitments (id) {
+        id -> Int4,
+        intent_id -> Text,
+        commitment -> Text,
+        chain -> Text,
+        conflicting_intent_id -> Text,
+        conflicting_chain -> Text,
+        scope -> Text,
+        detected_at -> Timestamptz,
+    }
+}
+
 diesel::table! {
     root_syncs (id) {
         id -> Int4,
@@ -154,25 +213,423 @@ diesel::table! {
         root -> Text,
         tx_hash -> Text,
         created_at -> Timestamptz,
+        leaf_count -> Nullable<Int8>,
+    }
+}
+
+diesel::table! {
+    claim_auth_checks (intent_id) {
+        intent_id -> Text,
+        valid -> Bool,
+        signer -> Nullable<Text>,
+        error -> Nullable<Text>,
+        checked_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    user_intent_limits (user_address) {
+        user_address -> Text,
+        max_active -> Int4,
+        note -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    transaction_receipts (tx_hash) {
+        tx_hash -> Text,
+        intent_id -> Nullable<Text>,
+        chain_id -> Int4,
+        tx_type -> Text,
+        status -> Text,
+        block_number -> Nullable<Int8>,
+        gas_used -> Nullable<Int8>,
+        effective_gas_price -> Nullable<Text>,
+        revert_reason -> Nullable<Text>,
+        receipt -> Jsonb,
+        created_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    compliance_screenings (intent_id) {
+        intent_id -> Text,
+        address -> Text,
+        chain -> Text,
+        risk_score -> Nullable<Float8>,
+        labels -> Nullable<Text>,
+        action -> Text,
+        reason -> Nullable<Text>,
+        provider_error -> Nullable<Text>,
+        override_note -> Nullable<Text>,
+        screened_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    secret_reveals (intent_id) {
+        intent_id -> Text,
+        reveal_deadline -> Nullable<Int8>,
+        secret -> Nullable<Text>,
+        signature -> Nullable<Text>,
+        revealed_at -> Nullable<Timestamptz>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    webhook_endpoints (id) {
+        id -> Text,
+        url -> Text,
+        description -> Nullable<Text>,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        filter -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    webhook_secrets (endpoint_id, key_id) {
+        endpoint_id -> Text,
+        key_id -> Text,
+        secret -> Text,
+        created_at -> Timestamptz,
+        expires_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    orphaned_fills (intent_id) {
+        intent_id -> Text,
+        source_chain -> Text,
+        dest_chain -> Text,
+        solver_address -> Text,
+        token -> Text,
+        amount -> Text,
+        status -> Text,
+        recovery_txid -> Nullable<Text>,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        approved_at -> Nullable<Timestamptz>,
+        detected_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    archived_intents (id) {
+        id -> Text,
+        #[max_length = 42]
+        user_address -> Varchar,
+        #[max_length = 50]
+        source_chain -> Varchar,
+        #[max_length = 50]
+        dest_chain -> Varchar,
+        source_token -> Text,
+        #[max_length = 42]
+        dest_token -> Varchar,
+        amount -> Text,
+        #[max_length = 78]
+        dest_amount -> Varchar,
+        source_commitment -> Nullable<Text>,
+        dest_fill_txid -> Nullable<Text>,
+        #[max_length = 66]
+        dest_registration_txid -> Nullable<Varchar>,
+        #[max_length = 66]
+        source_complete_txid -> Nullable<Varchar>,
+        status -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+        deadline -> Int8,
+        #[max_length = 42]
+        refund_address -> Nullable<Varchar>,
+        #[max_length = 42]
+        solver_address -> Nullable<Varchar>,
+        source_settlement_txid -> Nullable<Text>,
+        block_number -> Nullable<Int8>,
+        log_index -> Nullable<Int4>,
+        archived_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    relayer_instances (instance_id) {
+        instance_id -> Text,
+        region -> Text,
+        started_at -> Timestamptz,
+        heartbeat_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    partition_assignments (partition) {
+        partition -> Int4,
+        instance_id -> Nullable<Text>,
+        epoch -> Int8,
+        assigned_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    token_listings (symbol) {
+        symbol -> Text,
+        ethereum_address -> Text,
+        mantle_address -> Text,
+        decimals -> Int4,
+        min_amount -> Text,
+        max_amount -> Text,
+        enabled -> Bool,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    tx_submissions (operation) {
+        operation -> Text,
+        intent_id -> Text,
+        chain_id -> Int4,
+        tx_type -> Text,
+        calldata_hash -> Text,
+        nonce -> Nullable<Int8>,
+        tx_hash -> Nullable<Text>,
+        status -> Text,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    chain_block_hashes (chain, block_number) {
+        chain -> Text,
+        block_number -> Int8,
+        block_hash -> Text,
+        recorded_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    deposit_discrepancies (intent_id) {
+        intent_id -> Text,
+        source_chain -> Text,
+        token -> Text,
+        kind -> Text,
+        expected_amount -> Text,
+        deposited_amount -> Text,
+        difference -> Text,
+        transaction_hash -> Text,
+        detected_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    intent_reservations (intent_id) {
+        intent_id -> Text,
+        solver_address -> Text,
+        status -> Text,
+        reserved_at -> Timestamptz,
+        expires_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    fill_reports (id) {
+        id -> Int8,
+        intent_id -> Text,
+        chain -> Text,
+        transaction_hash -> Text,
+        solver_address -> Text,
+        status -> Text,
+        reason -> Nullable<Text>,
+        reported_at -> Timestamptz,
+        resolved_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    relayer_jobs (id) {
+        id -> Int8,
+        kind -> Text,
+        intent_id -> Text,
+        status -> Text,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        next_attempt_at -> Timestamptz,
+        created_at -> Timestamptz,
+        updated_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    debug_captures (intent_id) {
+        intent_id -> Text,
+        operator -> Text,
+        reason -> Text,
+        started_at -> Timestamptz,
+        expires_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    debug_capture_entries (id) {
+        id -> Int8,
+        intent_id -> Text,
+        component -> Text,
+        kind -> Text,
+        payload -> Jsonb,
+        recorded_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    ingestion_queue (id) {
+        id -> Int8,
+        source -> Text,
+        event_type -> Text,
+        chain -> Text,
+        transaction_hash -> Text,
+        block_number -> Int8,
+        log_index -> Int4,
+        payload -> Jsonb,
+        status -> Text,
+        attempts -> Int4,
+        last_error -> Nullable<Text>,
+        received_at -> Timestamptz,
+        next_attempt_at -> Timestamptz,
+        processed_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    admin_api_keys (id) {
+        id -> Int4,
+        name -> Text,
+        key_prefix -> Text,
+        key_hash -> Text,
+        role -> Text,
+        created_by -> Text,
+        created_at -> Timestamptz,
+        last_used_at -> Nullable<Timestamptz>,
+        revoked_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    admin_audit_log (id) {
+        id -> Int8,
+        actor -> Text,
+        key_id -> Nullable<Int4>,
+        role -> Nullable<Text>,
+        method -> Text,
+        path -> Text,
+        required_role -> Text,
+        status_code -> Int4,
+        created_at -> Timestamptz,
     }
 }
 
 diesel::joinable!(bridge_events -> intents (intent_id));
+diesel::joinable!(debug_capture_entries -> debug_captures (intent_id));
 diesel::joinable!(chain_transactions -> intents (intent_id));
 diesel::joinable!(intent_privacy_params -> intents (intent_id));
 diesel::joinable!(merkle_nodes -> merkle_trees (tree_id));
+diesel::table! {
+    api_keys (id) {
+        id -> Int4,
+        name -> Text,
+        key_prefix -> Text,
+        key_hash -> Text,
+        scopes -> Array<Text>,
+        rate_limit_per_min -> Nullable<Int4>,
+        created_by -> Text,
+        created_at -> Timestamptz,
+        last_used_at -> Nullable<Timestamptz>,
+        revoked_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    intent_encrypted_notes (intent_id) {
+        intent_id -> Text,
+        ciphertext -> Text,
+        created_at -> Timestamptz,
+        delivered_at -> Nullable<Timestamptz>,
+    }
+}
+
+diesel::table! {
+    merkle_repairs (id) {
+        id -> Int4,
+        tree_name -> Text,
+        issues -> Array<Text>,
+        stored_leaf_count -> Int8,
+        stored_root -> Text,
+        repaired_leaf_count -> Int8,
+        repaired_root -> Text,
+        nodes_rewritten -> Int8,
+        repaired_at -> Timestamptz,
+    }
+}
+
+diesel::table! {
+    privacy_context_rekeys (id) {
+        id -> Int4,
+        rekeyed -> Int8,
+        unverified -> Array<Text>,
+        completed_at -> Timestamptz,
+    }
+}
+
+diesel::joinable!(admin_audit_log -> admin_api_keys (key_id));
+diesel::joinable!(webhook_secrets -> webhook_endpoints (endpoint_id));
 
 diesel::allow_tables_to_appear_in_same_query!(
     bridge_events,
     chain_transactions,
+    claim_sponsorships,
     ethereum_sepolia_intent_created,
     indexer_checkpoints,
     intent_privacy_params,
     intents,
     mantle_sepolia_intent_created,
     merkle_nodes,
+    merkle_root_history,
     merkle_roots,
     merkle_tree_ethereum_commitments,
     merkle_trees,
+    quarantined_commitments,
     root_syncs,
+    transaction_receipts,
+    user_intent_limits,
+    claim_auth_checks,
+    compliance_screenings,
+    secret_reveals,
+    webhook_endpoints,
+    webhook_secrets,
+    orphaned_fills,
+    archived_intents,
+    metric_snapshots,
+    intent_annotations,
+    relayer_instances,
+    partition_assignments,
+    token_listings,
+    tx_submissions,
+    chain_block_hashes,
+    deposit_discrepancies,
+    intent_reservations,
+    fill_reports,
+    relayer_jobs,
+    debug_captures,
+    debug_capture_entries,
+    ingestion_queue,
+    admin_api_keys,
+    admin_audit_log,
+    api_keys,
+    intent_encrypted_notes,
+    merkle_repairs,
+    privacy_context_rekeys,
 );
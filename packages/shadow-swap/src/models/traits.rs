@@ -1,34 +1,26 @@
 use anyhow::Result;
+use mantle_core::{finality::FinalitySource, tx_strategy::ChainTxStrategy};
 
-pub trait ChainRelayer: Send + Sync {
-    fn get_merkle_root(&self) -> impl std::future::Future<Output = Result<String>> + Send;
-    fn sync_source_chain_root(
-        &self,
-        chain_id: u32,
-        root: [u8; 32],
-    ) -> impl std::future::Future<Output = Result<String>> + Send;
-    fn sync_dest_chain_root(
-        &self,
-        chain_id: u32,
-        root: [u8; 32],
-    ) -> impl std::future::Future<Output = Result<String>> + Send;
-    fn claim_withdrawal(
-        &self,
-        intent_id: &str,
-        nullifier: &str,
-        recipient: &str,
-        secret: &str,
-        claim_auth: &[u8],
-    ) -> impl std::future::Future<Output = Result<String>> + Send;
-    fn mark_filled(
-        &self,
-        intent_id: &str,
-        solver_address: &str,
-        merkle_path: &[String],
-        leaf_index: u32,
-    ) -> impl std::future::Future<Output = Result<String>> + Send;
-    fn refund_intent(
-        &self,
-        intent_id: &str,
-    ) -> impl std::future::Future<Output = Result<String>> + Send;
+/// Settings of one EVM chain the relayer serves. `ChainRelayer` is the same
+/// for every chain, so supporting another one means implementing this.
+pub trait ChainConfig {
+    /// Shown in logs and errors, e.g. `Mantle`.
+    const NAME: &'static str;
+    /// Lowercase name, as stored on intent rows and used in merkle tree names.
+    const KEY: &'static str;
+    /// Prefix of the chain's tx strategy, finality, tx queue and forwarder settings.
+    const ENV_PREFIX: &'static str;
+    const NATIVE_SYMBOL: &'static str;
+    /// Relayer balance, in whole native tokens, below which it asks to be funded.
+    const LOW_BALANCE: &'static str;
+
+    fn default_tx_strategy() -> ChainTxStrategy;
+    fn default_finality() -> FinalitySource;
+
+    fn rpc_url(&self) -> &str;
+    fn private_key(&self) -> &str;
+    fn intent_pool_address(&self) -> &str;
+    fn settlement_address(&self) -> &str;
+
+    fn validate(&self) -> Result<()>;
 }
@@ -0,0 +1,691 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::PathBuf,
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use mantle_core::http_client::OutboundClient;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+use crate::{
+    relay_coordinator::model::BridgeCoordinator, webhooks::webhook_dispatcher::WebhookDispatcher,
+};
+
+const PAGERDUTY_EVENTS_URL: &str = "https://events.pagerduty.com/v2/enqueue";
+
+/// Counters sampled from `BridgeMetrics`; rules usually watch their `rate`.
+const COUNTERS: [&str; 9] = [
+    "total_intents_processed",
+    "successful_bridges",
+    "failed_intents",
+    "refunded_intents",
+    "ethereum_fills",
+    "mantle_fills",
+    "ethereum_claims",
+    "mantle_claims",
+    "retry_attempts",
+];
+
+const GAUGES: [&str; 5] = [
+    "root_sync_age_secs",
+    "db_pool_in_use",
+    "db_pool_saturation",
+    "oldest_pending_age_secs",
+    "uptime_secs",
+];
+
+/// Gauges with one series per worker queue, worker or chain.
+const GAUGE_PREFIXES: [&str; 3] = ["queue_depth_", "processing_rate_", "tx_queue_depth_"];
+
+#[derive(Debug, Clone)]
+pub struct AlertRulesPolicy {
+    pub interval: Duration,
+    /// Parsed `ALERT_RULES_FILE`; the engine stays off without one.
+    pub rules: Option<AlertRules>,
+}
+
+impl Default for AlertRulesPolicy {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            rules: None,
+        }
+    }
+}
+
+impl AlertRulesPolicy {
+    /// Reads `ALERT_RULES_FILE` and `ALERT_EVAL_INTERVAL_SECS`. The rules
+    /// file is loaded and checked here so a bad one fails startup.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(secs) = std::env::var("ALERT_EVAL_INTERVAL_SECS") {
+            policy.interval = Duration::from_secs(
+                secs.trim()
+                    .parse::<u64>()
+                    .context("Invalid ALERT_EVAL_INTERVAL_SECS")?
+                    .max(5),
+            );
+        }
+        if let Ok(path) = std::env::var("ALERT_RULES_FILE")
+            && !path.trim().is_empty()
+        {
+            policy.rules = Some(AlertRules::load(PathBuf::from(path.trim()))?);
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Where notifications go. A webhook without a `url` goes to the
+/// registered, signed webhook endpoints as `alert_firing` / `alert_resolved`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+pub enum AlertSink {
+    Webhook { url: Option<String> },
+    Slack { url: String },
+    Pagerduty { routing_key: String },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum Comparison {
+    #[serde(rename = ">")]
+    Above,
+    #[serde(rename = ">=")]
+    AtLeast,
+    #[serde(rename = "<")]
+    Below,
+    #[serde(rename = "<=")]
+    AtMost,
+}
+
+impl Comparison {
+    fn holds(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Comparison::Above => value > threshold,
+            Comparison::AtLeast => value >= threshold,
+            Comparison::Below => value < threshold,
+            Comparison::AtMost => value <= threshold,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Comparison::Above => ">",
+            Comparison::AtLeast => ">=",
+            Comparison::Below => "<",
+            Comparison::AtMost => "<=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Info,
+    #[default]
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRule {
+    pub name: String,
+    pub metric: String,
+    /// Compare the per-minute increase since the last evaluation instead
+    /// of the value itself.
+    #[serde(default)]
+    pub rate: bool,
+    pub op: Comparison,
+    pub threshold: f64,
+    /// How long the condition must hold before the alert fires.
+    #[serde(default)]
+    pub for_secs: u64,
+    #[serde(default)]
+    pub severity: Severity,
+    pub sinks: Vec<String>,
+    pub summary: Option<String>,
+}
+
+impl AlertRule {
+    fn describe(&self, value: f64) -> String {
+        match &self.summary {
+            Some(summary) => summary.clone(),
+            None => format!(
+                "{}{} {} {} (now {:.2})",
+                self.metric,
+                if self.rate { " per minute" } else { "" },
+                self.op.as_str(),
+                self.threshold,
+                value
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AlertRules {
+    #[serde(default)]
+    pub sinks: HashMap<String, AlertSink>,
+    pub rules: Vec<AlertRule>,
+}
+
+impl AlertRules {
+    pub fn load(path: PathBuf) -> Result<Self> {
+        let raw = std::fs::read_to_string(&path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        Self::parse(&raw).with_context(|| format!("Invalid alert rules in {}", path.display()))
+    }
+
+    pub fn parse(raw: &str) -> Result<Self> {
+        let rules: Self = serde_yaml::from_str(raw)?;
+
+        let mut names = HashSet::new();
+        for rule in &rules.rules {
+            if !names.insert(rule.name.as_str()) {
+                return Err(anyhow!("Duplicate rule '{}'", rule.name));
+            }
+            if !known_metric(&rule.metric) {
+                return Err(anyhow!(
+                    "Rule '{}' watches unknown metric '{}'",
+                    rule.name,
+                    rule.metric
+                ));
+            }
+            if rule.sinks.is_empty() {
+                return Err(anyhow!("Rule '{}' has no sinks", rule.name));
+            }
+            if let Some(sink) = rule.sinks.iter().find(|s| !rules.sinks.contains_key(*s)) {
+                return Err(anyhow!(
+                    "Rule '{}' uses undefined sink '{}'",
+                    rule.name,
+                    sink
+                ));
+            }
+        }
+
+        Ok(rules)
+    }
+}
+
+fn known_metric(metric: &str) -> bool {
+    COUNTERS.contains(&metric)
+        || GAUGES.contains(&metric)
+        || GAUGE_PREFIXES
+            .iter()
+            .any(|prefix| metric.len() > prefix.len() && metric.starts_with(prefix))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertTransition {
+    Firing,
+    Resolved,
+}
+
+/// A rule that started firing or resolved on this evaluation.
+#[derive(Debug, Clone, Serialize)]
+pub struct AlertEvent {
+    pub rule: String,
+    pub status: AlertTransition,
+    pub severity: Severity,
+    pub metric: String,
+    pub value: f64,
+    pub threshold: f64,
+    pub summary: String,
+    /// When the condition first held.
+    pub since: DateTime<Utc>,
+    pub at: DateTime<Utc>,
+    #[serde(skip)]
+    pub sinks: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum RuleState {
+    Pending(DateTime<Utc>),
+    Firing(DateTime<Utc>),
+}
+
+/// Tracks which rules are pending or firing so each alert is sent once
+/// when it fires and once when it resolves.
+#[derive(Debug, Default)]
+pub struct AlertEngine {
+    states: HashMap<String, RuleState>,
+    previous: Option<(DateTime<Utc>, HashMap<String, f64>)>,
+}
+
+impl AlertEngine {
+    pub fn evaluate(
+        &mut self,
+        rules: &[AlertRule],
+        samples: HashMap<String, f64>,
+        now: DateTime<Utc>,
+    ) -> Vec<AlertEvent> {
+        let mut events = Vec::new();
+
+        for rule in rules {
+            // Rules without data keep their state until the metric reports again
+            let Some(value) = self.value(rule, &samples, now) else {
+                continue;
+            };
+
+            let state = self.states.get(&rule.name).copied();
+            let event = |status, since| AlertEvent {
+                rule: rule.name.clone(),
+                status,
+                severity: rule.severity,
+                metric: rule.metric.clone(),
+                value,
+                threshold: rule.threshold,
+                summary: rule.describe(value),
+                since,
+                at: now,
+                sinks: rule.sinks.clone(),
+            };
+
+            if rule.op.holds(value, rule.threshold) {
+                let since = match state {
+                    Some(RuleState::Firing(_)) => continue,
+                    Some(RuleState::Pending(since)) => since,
+                    None => now,
+                };
+                if (now - since).num_seconds() >= rule.for_secs as i64 {
+                    self.states
+                        .insert(rule.name.clone(), RuleState::Firing(since));
+                    events.push(event(AlertTransition::Firing, since));
+                } else {
+                    self.states
+                        .insert(rule.name.clone(), RuleState::Pending(since));
+                }
+            } else if let Some(state) = self.states.remove(&rule.name)
+                && let RuleState::Firing(since) = state
+            {
+                events.push(event(AlertTransition::Resolved, since));
+            }
+        }
+
+        self.previous = Some((now, samples));
+        events
+    }
+
+    /// The sample for `rule`, or its per-minute increase since the last
+    /// evaluation. `None` for the first evaluation and across counter resets.
+    fn value(
+        &self,
+        rule: &AlertRule,
+        samples: &HashMap<String, f64>,
+        now: DateTime<Utc>,
+    ) -> Option<f64> {
+        let current = *samples.get(&rule.metric)?;
+        if !rule.rate {
+            return Some(current);
+        }
+
+        let (at, previous) = self.previous.as_ref()?;
+        let previous = *previous.get(&rule.metric)?;
+        let elapsed = (now - *at).num_milliseconds() as f64 / 1000.0;
+        if elapsed <= 0.0 || current < previous {
+            return None;
+        }
+        Some((current - previous) / elapsed * 60.0)
+    }
+}
+
+/// Sends alert events to the configured sinks.
+pub struct AlertNotifier {
+    sinks: HashMap<String, AlertSink>,
+    http: OutboundClient,
+    webhooks: Arc<WebhookDispatcher>,
+}
+
+impl AlertNotifier {
+    pub fn new(
+        sinks: HashMap<String, AlertSink>,
+        http: OutboundClient,
+        webhooks: Arc<WebhookDispatcher>,
+    ) -> Self {
+        Self {
+            sinks,
+            http,
+            webhooks,
+        }
+    }
+
+    pub async fn notify(&self, event: &AlertEvent) {
+        for name in &event.sinks {
+            let Some(sink) = self.sinks.get(name) else {
+                continue;
+            };
+            if let Err(e) = self.send(sink, event).await {
+                error!(
+                    "❌ Alert {} ({:?}) not delivered to sink '{}': {:#}",
+                    event.rule, event.status, name, e
+                );
+            }
+        }
+    }
+
+    async fn send(&self, sink: &AlertSink, event: &AlertEvent) -> Result<()> {
+        let event_name = match event.status {
+            AlertTransition::Firing => "alert_firing",
+            AlertTransition::Resolved => "alert_resolved",
+        };
+
+        match sink {
+            AlertSink::Webhook { url: None } => {
+                if self.webhooks.dispatch(event_name, &json!(event)).await > 0 {
+                    return Err(anyhow!("Not delivered to every webhook endpoint"));
+                }
+                Ok(())
+            }
+            AlertSink::Webhook { url: Some(url) } => {
+                self.post(url, &json!({ "event": event_name, "data": event }))
+                    .await
+            }
+            AlertSink::Slack { url } => self.post(url, &slack_message(event)).await,
+            AlertSink::Pagerduty { routing_key } => {
+                self.post(PAGERDUTY_EVENTS_URL, &pagerduty_event(routing_key, event))
+                    .await
+            }
+        }
+    }
+
+    async fn post(&self, url: &str, body: &Value) -> Result<()> {
+        let body = serde_json::to_vec(body)?;
+        let response = self
+            .http
+            .send(url, |http| {
+                http.post(url)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .body(body.clone())
+            })
+            .await
+            .context("Alert request failed")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Sink returned {}", response.status()));
+        }
+        Ok(())
+    }
+}
+
+fn slack_message(event: &AlertEvent) -> Value {
+    let text = match event.status {
+        AlertTransition::Firing => format!(
+            ":rotating_light: *{}* firing ({:?}): {}",
+            event.rule, event.severity, event.summary
+        ),
+        AlertTransition::Resolved => format!(
+            ":white_check_mark: *{}* resolved after {}s",
+            event.rule,
+            (event.at - event.since).num_seconds()
+        ),
+    };
+    json!({ "text": text })
+}
+
+/// Events API v2 body. Trigger and resolve share a dedup key so PagerDuty
+/// folds repeats into one incident and closes it on resolve.
+fn pagerduty_event(routing_key: &str, event: &AlertEvent) -> Value {
+    let dedup_key = format!("shadow-swap/{}", event.rule);
+    match event.status {
+        AlertTransition::Firing => json!({
+            "routing_key": routing_key,
+            "event_action": "trigger",
+            "dedup_key": dedup_key,
+            "payload": {
+                "summary": format!("{}: {}", event.rule, event.summary),
+                "source": "shadow-swap",
+                "severity": event.severity,
+                "timestamp": event.since.to_rfc3339(),
+                "custom_details": {
+                    "metric": event.metric,
+                    "value": event.value,
+                    "threshold": event.threshold,
+                },
+            },
+        }),
+        AlertTransition::Resolved => json!({
+            "routing_key": routing_key,
+            "event_action": "resolve",
+            "dedup_key": dedup_key,
+        }),
+    }
+}
+
+impl BridgeCoordinator {
+    /// Current values of every metric the alert rules can watch.
+    pub async fn alert_samples(&self) -> Result<HashMap<String, f64>> {
+        let mut samples = HashMap::new();
+
+        {
+            let metrics = self.metrics.read().await;
+            let counters = [
+                metrics.total_intents_processed.get(),
+                metrics.successful_bridges.get(),
+                metrics.failed_intents.get(),
+                metrics.refunded_intents.get(),
+                metrics.ethereum_fills.get(),
+                metrics.mantle_fills.get(),
+                metrics.ethereum_claims.get(),
+                metrics.mantle_claims.get(),
+                metrics.retry_attempts.get(),
+            ];
+            for (name, value) in COUNTERS.iter().zip(counters) {
+                samples.insert(name.to_string(), value as f64);
+            }
+        }
+
+        let now = Utc::now();
+        samples.insert(
+            "uptime_secs".to_string(),
+            self.start_time.elapsed().as_secs_f64(),
+        );
+
+        let pool = &self.database.pool;
+        let state = pool.state();
+        let in_use = state.connections.saturating_sub(state.idle_connections) as f64;
+        samples.insert("db_pool_in_use".to_string(), in_use);
+        samples.insert(
+            "db_pool_saturation".to_string(),
+            in_use / pool.max_size().max(1) as f64,
+        );
+
+        samples.insert(
+            "tx_queue_depth_ethereum".to_string(),
+            self.ethereum_relayer.tx_queue.depth() as f64,
+        );
+        samples.insert(
+            "tx_queue_depth_mantle".to_string(),
+            self.mantle_relayer.tx_queue.depth() as f64,
+        );
+
+        if let Some(synced_at) = self.database.get_last_root_sync_at()? {
+            samples.insert(
+                "root_sync_age_secs".to_string(),
+                (now - synced_at).num_seconds().max(0) as f64,
+            );
+        }
+
+        let queues = self.queue_health().await?;
+        samples.insert(
+            "oldest_pending_age_secs".to_string(),
+            queues.oldest_pending_age_secs as f64,
+        );
+        for (queue, depth) in queues.queue_depths {
+            samples.insert(format!("queue_depth_{}", queue), depth as f64);
+        }
+        for (worker, rate) in queues.processing_rate_per_min {
+            samples.insert(format!("processing_rate_{}", worker), rate);
+        }
+
+        Ok(samples)
+    }
+
+    /// Evaluate the alert rules every `policy.interval` and notify their
+    /// sinks when one fires or resolves.
+    pub async fn run_alert_rules(
+        &self,
+        policy: AlertRulesPolicy,
+        http: OutboundClient,
+        webhooks: Arc<WebhookDispatcher>,
+    ) {
+        let Some(rules) = policy.rules else {
+            return;
+        };
+        info!(
+            "🚨 Alert rules engine started ({} rules, every {}s)",
+            rules.rules.len(),
+            policy.interval.as_secs()
+        );
+
+        let notifier = AlertNotifier::new(rules.sinks, http, webhooks);
+        let mut engine = AlertEngine::default();
+        let mut ticker = interval(policy.interval);
+        loop {
+            ticker.tick().await;
+
+            let samples = match self.alert_samples().await {
+                Ok(samples) => samples,
+                Err(e) => {
+                    warn!("⚠️ Failed to sample metrics for alert rules: {:#}", e);
+                    continue;
+                }
+            };
+
+            for event in engine.evaluate(&rules.rules, samples, Utc::now()) {
+                match event.status {
+                    AlertTransition::Firing => {
+                        warn!("🚨 Alert {} firing: {}", event.rule, event.summary)
+                    }
+                    AlertTransition::Resolved => info!("✅ Alert {} resolved", event.rule),
+                }
+                notifier.notify(&event).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r#"
+sinks:
+  oncall:
+    type: pagerduty
+    routing_key: abc123
+  chat:
+    type: slack
+    url: https://hooks.slack.com/services/T/B/X
+rules:
+  - name: failed_fills_spike
+    metric: failed_intents
+    rate: true
+    op: ">"
+    threshold: 2
+    severity: critical
+    sinks: [oncall, chat]
+  - name: root_sync_stale
+    metric: root_sync_age_secs
+    op: ">="
+    threshold: 600
+    for_secs: 60
+    sinks: [chat]
+"#;
+
+    fn samples(values: &[(&str, f64)]) -> HashMap<String, f64> {
+        values.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    #[test]
+    fn test_rules_are_validated() {
+        let rules = AlertRules::parse(RULES).unwrap();
+        assert_eq!(rules.rules.len(), 2);
+        assert_eq!(rules.rules[0].severity, Severity::Critical);
+        assert_eq!(rules.rules[1].severity, Severity::Warning);
+
+        let undefined = RULES.replace("sinks: [chat]", "sinks: [email]");
+        assert!(AlertRules::parse(&undefined).is_err());
+        let unknown = RULES.replace("metric: root_sync_age_secs", "metric: root_age");
+        assert!(AlertRules::parse(&unknown).is_err());
+        assert!(known_metric("queue_depth_claim"));
+        assert!(!known_metric("queue_depth_"));
+    }
+
+    #[test]
+    fn test_alert_fires_once_after_for_and_resolves() {
+        let rules = AlertRules::parse(RULES).unwrap().rules;
+        let mut engine = AlertEngine::default();
+        let start = Utc::now();
+        let at = |secs| start + chrono::Duration::seconds(secs);
+        let stale = samples(&[("root_sync_age_secs", 900.0)]);
+
+        assert!(engine.evaluate(&rules, stale.clone(), at(0)).is_empty());
+        assert!(engine.evaluate(&rules, stale.clone(), at(30)).is_empty());
+        let fired = engine.evaluate(&rules, stale.clone(), at(60));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].status, AlertTransition::Firing);
+        assert_eq!(fired[0].since, at(0));
+
+        // Still firing: nothing new is sent
+        assert!(engine.evaluate(&rules, stale, at(90)).is_empty());
+        // No data leaves the alert as it is
+        assert!(engine.evaluate(&rules, HashMap::new(), at(100)).is_empty());
+
+        let resolved = engine.evaluate(&rules, samples(&[("root_sync_age_secs", 5.0)]), at(120));
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].status, AlertTransition::Resolved);
+        assert_eq!(
+            pagerduty_event("key", &resolved[0])["dedup_key"],
+            "shadow-swap/root_sync_stale"
+        );
+    }
+
+    #[test]
+    fn test_rate_rules_compare_per_minute_increase() {
+        let rules = AlertRules::parse(RULES).unwrap().rules;
+        let mut engine = AlertEngine::default();
+        let start = Utc::now();
+        let at = |secs| start + chrono::Duration::seconds(secs);
+
+        // The first sample only sets the baseline
+        assert!(
+            engine
+                .evaluate(&rules, samples(&[("failed_intents", 10.0)]), at(0))
+                .is_empty()
+        );
+        // 1 more in 30s is 2 per minute, not above the threshold
+        assert!(
+            engine
+                .evaluate(&rules, samples(&[("failed_intents", 11.0)]), at(30))
+                .is_empty()
+        );
+
+        let fired = engine.evaluate(&rules, samples(&[("failed_intents", 14.0)]), at(60));
+        assert_eq!(fired.len(), 1);
+        assert_eq!(fired[0].value, 6.0);
+        assert_eq!(fired[0].sinks, vec!["oncall", "chat"]);
+        assert_eq!(
+            pagerduty_event("key", &fired[0])["payload"]["severity"],
+            "critical"
+        );
+
+        // A counter reset is skipped rather than read as a drop
+        assert!(
+            engine
+                .evaluate(&rules, samples(&[("failed_intents", 0.0)]), at(90))
+                .is_empty()
+        );
+        let resolved = engine.evaluate(&rules, samples(&[("failed_intents", 0.0)]), at(120));
+        assert_eq!(resolved[0].status, AlertTransition::Resolved);
+    }
+}
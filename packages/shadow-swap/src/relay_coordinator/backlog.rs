@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::{
+    models::model::{Intent, IntentStatus},
+    relay_coordinator::model::BridgeCoordinator,
+};
+
+const PROGRESS_LOG_EVERY: usize = 10;
+
+/// Queue an intent is assigned to during cold-start triage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BacklogQueue {
+    Refund,
+    Priority,
+    Reconciliation,
+    Normal,
+}
+
+impl BacklogQueue {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Refund => "refund",
+            Self::Priority => "priority",
+            Self::Reconciliation => "reconciliation",
+            Self::Normal => "normal",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct TriagePolicy {
+    pub enabled: bool,
+    pub near_deadline_secs: u64,
+    pub stale_filled_secs: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct QueueProgress {
+    pub total: usize,
+    pub processed: usize,
+    pub failed: usize,
+}
+
+/// Progress of the startup triage, exposed via `/admin/backlog`.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BacklogProgress {
+    pub phase: String,
+    pub started_at: Option<DateTime<Utc>>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub queues: HashMap<String, QueueProgress>,
+}
+
+impl TriagePolicy {
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("BACKLOG_TRIAGE_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            near_deadline_secs: std::env::var("BACKLOG_NEAR_DEADLINE_SECS")
+                .unwrap_or_else(|_| "1800".to_string())
+                .parse()
+                .unwrap_or(1800),
+            stale_filled_secs: std::env::var("BACKLOG_STALE_FILLED_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .unwrap_or(3600),
+        }
+    }
+
+    pub fn classify(&self, intent: &Intent, now: DateTime<Utc>) -> BacklogQueue {
+        let now_secs = now.timestamp().max(0) as u64;
+
+        match intent.status {
+            IntentStatus::Created
+            | IntentStatus::Committed
+            | IntentStatus::Registered
+            | IntentStatus::Pending
+                if intent.deadline <= now_secs =>
+            {
+                return BacklogQueue::Refund;
+            }
+            IntentStatus::Filled
+                if (now - intent.updated_at).num_seconds() >= self.stale_filled_secs as i64 =>
+            {
+                return BacklogQueue::Reconciliation;
+            }
+            IntentStatus::UserClaimed
+            | IntentStatus::Refunded
+            | IntentStatus::Failed
+            | IntentStatus::Expired => return BacklogQueue::Normal,
+            _ => {}
+        }
+
+        if intent.deadline > now_secs && intent.deadline - now_secs <= self.near_deadline_secs {
+            BacklogQueue::Priority
+        } else {
+            BacklogQueue::Normal
+        }
+    }
+}
+
+impl BridgeCoordinator {
+    /// Classify the backlog left over from downtime and work through it in
+    /// order: refunds, near-deadline intents, then stale fills to reconcile.
+    /// Everything else is left to the regular workers.
+    pub async fn triage_backlog(&self) -> Result<()> {
+        let backlog = self
+            .partitions
+            .retain_owned(self.database.get_backlog_intents()?);
+        let now = Utc::now();
+
+        let mut queues: HashMap<BacklogQueue, Vec<Intent>> = HashMap::new();
+        for intent in backlog {
+            let queue = self.triage_policy.classify(&intent, now);
+            queues.entry(queue).or_default().push(intent);
+        }
+
+        {
+            let mut progress = self.backlog_progress.write().await;
+            progress.phase = "running".to_string();
+            progress.started_at = Some(now);
+            progress.queues = queues
+                .iter()
+                .map(|(queue, intents)| {
+                    (
+                        queue.as_str().to_string(),
+                        QueueProgress {
+                            total: intents.len(),
+                            ..Default::default()
+                        },
+                    )
+                })
+                .collect();
+        }
+
+        let count = |queue: BacklogQueue| queues.get(&queue).map_or(0, Vec::len);
+        info!(
+            "📦 Backlog triage: {} refund, {} priority, {} reconciliation, {} normal",
+            count(BacklogQueue::Refund),
+            count(BacklogQueue::Priority),
+            count(BacklogQueue::Reconciliation),
+            count(BacklogQueue::Normal)
+        );
+
+        for queue in [
+            BacklogQueue::Refund,
+            BacklogQueue::Priority,
+            BacklogQueue::Reconciliation,
+        ] {
+            let Some(intents) = queues.get(&queue) else {
+                continue;
+            };
+
+            for (i, intent) in intents.iter().enumerate() {
+                let result = match queue {
+                    BacklogQueue::Refund => self.handle_refund(intent).await,
+                    BacklogQueue::Priority => self.claim_for_user(intent).await,
+                    BacklogQueue::Reconciliation => self.reconcile_filled_intent(intent).await,
+                    BacklogQueue::Normal => Ok(()),
+                };
+
+                if let Err(e) = &result {
+                    warn!(
+                        "⚠️ Backlog {} failed for {}: {}",
+                        queue.as_str(),
+                        intent.id,
+                        e
+                    );
+                }
+
+                {
+                    let mut progress = self.backlog_progress.write().await;
+                    if let Some(entry) = progress.queues.get_mut(queue.as_str()) {
+                        entry.processed += 1;
+                        if result.is_err() {
+                            entry.failed += 1;
+                        }
+                    }
+                }
+
+                if (i + 1) % PROGRESS_LOG_EVERY == 0 || i + 1 == intents.len() {
+                    info!(
+                        "📦 Backlog {}: {}/{} processed",
+                        queue.as_str(),
+                        i + 1,
+                        intents.len()
+                    );
+                }
+            }
+        }
+
+        let mut progress = self.backlog_progress.write().await;
+        progress.phase = "complete".to_string();
+        progress.finished_at = Some(Utc::now());
+
+        info!("✅ Backlog triage complete");
+        Ok(())
+    }
+
+    /// Bring a long-`filled` intent in line with the source pool.
+    async fn reconcile_filled_intent(&self, intent: &Intent) -> Result<()> {
+        let (settled, refunded) = match intent.source_chain.as_str() {
+            "ethereum" | "11155111" => {
+                self.ethereum_relayer
+                    .get_source_intent_state(&intent.id)
+                    .await?
+            }
+            "mantle" | "5003" => {
+                self.mantle_relayer
+                    .get_source_intent_state(&intent.id)
+                    .await?
+            }
+            _ => return Err(anyhow!("Unsupported source chain: {}", intent.source_chain)),
+        };
+
+        let status = if refunded {
+            IntentStatus::Refunded
+        } else if settled {
+            IntentStatus::SolverPaid
+        } else {
+            info!(
+                "🔍 Intent {} still awaiting settlement on {}",
+                intent.id, intent.source_chain
+            );
+            return Ok(());
+        };
+
+        info!(
+            "🔧 Reconciled intent {}: filled -> {}",
+            intent.id,
+            status.as_str()
+        );
+
+        self.database.update_intent_status(&intent.id, status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use ethers::types::H256;
+
+    fn policy() -> TriagePolicy {
+        TriagePolicy {
+            enabled: true,
+            near_deadline_secs: 1800,
+            stale_filled_secs: 3600,
+        }
+    }
+
+    fn intent(status: IntentStatus, deadline: u64, updated_at: DateTime<Utc>) -> Intent {
+        Intent {
+            id: H256::from_low_u64_be(1).into(),
+            user_address: String::new(),
+            source_chain: "ethereum".to_string(),
+            dest_chain: "mantle".to_string(),
+            source_token: String::new(),
+            dest_token: String::new(),
+            amount: "1".parse().unwrap(),
+            dest_amount: "1".parse().unwrap(),
+            source_commitment: None,
+            dest_fill_txid: None,
+            dest_registration_txid: None,
+            source_complete_txid: None,
+            status,
+            created_at: updated_at,
+            updated_at,
+            deadline,
+            refund_address: None,
+            solver_address: None,
+            block_number: None,
+            log_index: None,
+        }
+    }
+
+    #[test]
+    fn test_classify_backlog() {
+        let now = Utc::now();
+        let secs = now.timestamp() as u64;
+        let policy = policy();
+
+        let expired = intent(IntentStatus::Registered, secs - 10, now);
+        assert_eq!(policy.classify(&expired, now), BacklogQueue::Refund);
+
+        let near = intent(IntentStatus::SolverPaid, secs + 600, now);
+        assert_eq!(policy.classify(&near, now), BacklogQueue::Priority);
+
+        let stale = intent(IntentStatus::Filled, secs + 86400, now - Duration::hours(2));
+        assert_eq!(policy.classify(&stale, now), BacklogQueue::Reconciliation);
+
+        let fresh = intent(IntentStatus::Filled, secs + 86400, now);
+        assert_eq!(policy.classify(&fresh, now), BacklogQueue::Normal);
+    }
+}
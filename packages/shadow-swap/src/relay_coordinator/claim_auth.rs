@@ -0,0 +1,311 @@
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use ethers::types::Address;
+use mantle_core::parse;
+use serde::Serialize;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+use crate::relay_coordinator::{claim_diagnosis::recover_claim_signer, model::BridgeCoordinator};
+
+/// How often stored claim signatures are re-verified in the background.
+#[derive(Debug, Clone, Copy)]
+pub struct ClaimAuthPolicy {
+    pub enabled: bool,
+    pub interval: Duration,
+}
+
+impl ClaimAuthPolicy {
+    /// Reads `CLAIM_AUTH_VERIFY_ENABLED` (default true) and
+    /// `CLAIM_AUTH_VERIFY_INTERVAL_SECS` (default 900).
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("CLAIM_AUTH_VERIFY_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            interval: Duration::from_secs(
+                std::env::var("CLAIM_AUTH_VERIFY_INTERVAL_SECS")
+                    .unwrap_or_else(|_| "900".to_string())
+                    .parse()
+                    .unwrap_or(900)
+                    .max(1),
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ClaimAuthVerdict {
+    pub valid: bool,
+    pub signer: Option<String>,
+    pub error: Option<String>,
+}
+
+impl ClaimAuthVerdict {
+    fn invalid(error: impl Into<String>) -> Self {
+        Self {
+            valid: false,
+            signer: None,
+            error: Some(error.into()),
+        }
+    }
+}
+
+/// Shape check: `0x` plus 65 bytes of hex with an Ethereum recovery id.
+pub fn parse_claim_auth(claim_auth: &str) -> Result<Vec<u8>> {
+    let hex_part = claim_auth
+        .strip_prefix("0x")
+        .ok_or_else(|| anyhow!("Claim signature must start with 0x"))?;
+
+    let bytes =
+        hex::decode(hex_part).map_err(|e| anyhow!("Claim signature is not valid hex: {}", e))?;
+    if bytes.len() != 65 {
+        return Err(anyhow!(
+            "Claim signature must be 65 bytes, got {}",
+            bytes.len()
+        ));
+    }
+
+    let v = bytes[64];
+    if !matches!(v, 0 | 1 | 27 | 28) {
+        return Err(anyhow!("Invalid signature recovery id {}", v));
+    }
+
+    Ok(bytes)
+}
+
+/// A claim delegate: a non-zero address. Deposit addresses of exchanges
+/// qualify; they never sign anything themselves.
+pub fn parse_claim_delegate(delegate: &str) -> Result<Address> {
+    let address = parse::address("claim_delegate", delegate)?;
+    if address == Address::zero() {
+        return Err(anyhow!("claim_delegate must not be the zero address"));
+    }
+    Ok(address)
+}
+
+/// Check a claim signature against the authority `claimWithdrawal` expects:
+/// the recipient, signing over the intent id and plaintext nullifier. A
+/// delegated claim (`claimWithdrawalTo`) signs over the delegate as well.
+pub fn verify_claim_signature(
+    intent_id: &str,
+    nullifier: &str,
+    recipient: &str,
+    delegate: Option<&str>,
+    claim_auth: &str,
+) -> ClaimAuthVerdict {
+    if let Err(e) = parse_claim_auth(claim_auth) {
+        return ClaimAuthVerdict::invalid(e.to_string());
+    }
+
+    let recipient: Address = match recipient.parse() {
+        Ok(address) => address,
+        Err(e) => return ClaimAuthVerdict::invalid(format!("Invalid recipient: {}", e)),
+    };
+    let delegate = match delegate.map(parse_claim_delegate).transpose() {
+        Ok(delegate) => delegate,
+        Err(e) => return ClaimAuthVerdict::invalid(e.to_string()),
+    };
+
+    match recover_claim_signer(intent_id, nullifier, recipient, delegate, claim_auth) {
+        Ok(signer) if signer == recipient && signer != Address::zero() => ClaimAuthVerdict {
+            valid: true,
+            signer: Some(format!("{:?}", signer)),
+            error: None,
+        },
+        Ok(signer) => ClaimAuthVerdict {
+            valid: false,
+            signer: Some(format!("{:?}", signer)),
+            error: Some(format!(
+                "Signed by {:?}, expected recipient {:?}",
+                signer, recipient
+            )),
+        },
+        Err(e) => ClaimAuthVerdict::invalid(e.to_string()),
+    }
+}
+
+impl BridgeCoordinator {
+    /// Decrypt the nullifier and verify the claim signature the way the
+    /// relayer will submit it. Decryption failures count as invalid.
+    pub async fn check_claim_auth(
+        &self,
+        intent_id: &str,
+        encrypted_nullifier: &str,
+        recipient: &str,
+        delegate: Option<&str>,
+        claim_auth: &str,
+    ) -> ClaimAuthVerdict {
+        match self.decrypt_privacy_param(encrypted_nullifier).await {
+            Ok(nullifier) => {
+                verify_claim_signature(intent_id, &nullifier, recipient, delegate, claim_auth)
+            }
+            Err(e) => ClaimAuthVerdict::invalid(format!("Failed to decrypt nullifier: {}", e)),
+        }
+    }
+
+    pub async fn run_claim_auth_verifier(&self, policy: ClaimAuthPolicy) {
+        if !policy.enabled {
+            info!("🔏 Claim signature verification disabled");
+            return;
+        }
+
+        info!(
+            "🔏 Claim signature verifier started (every {}s)",
+            policy.interval.as_secs()
+        );
+
+        let mut ticker = interval(policy.interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.verify_stored_claim_auths().await {
+                error!("❌ Claim signature verification failed: {}", e);
+            }
+        }
+    }
+
+    /// Re-verify every live intent's stored claim signature and record the
+    /// result, so broken ones surface before their deadline. Returns how
+    /// many failed.
+    pub async fn verify_stored_claim_auths(&self) -> Result<usize> {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let intents = self.database.get_backlog_intents()?;
+
+        let mut failed = 0;
+        for intent in intents.iter().filter(|intent| intent.deadline > now) {
+            let params = match self.database.get_intent_privacy_params(&intent.id) {
+                Ok(params) => params,
+                // Privacy params arrive separately from the indexer event
+                Err(_) => continue,
+            };
+
+            let verdict = match (
+                params.nullifier.as_deref(),
+                params.recipient.as_deref(),
+                params.claim_signature.as_deref(),
+            ) {
+                (Some(nullifier), Some(recipient), Some(claim_auth)) => {
+                    self.check_claim_auth(
+                        &intent.id,
+                        nullifier,
+                        recipient,
+                        params.claim_delegate.as_deref(),
+                        claim_auth,
+                    )
+                    .await
+                }
+                _ => ClaimAuthVerdict::invalid("Nullifier, recipient or claim signature missing"),
+            };
+
+            if !verdict.valid {
+                failed += 1;
+                warn!(
+                    "🚩 Claim for {} will fail ({}s before deadline): {}",
+                    intent.id,
+                    intent.deadline - now,
+                    verdict.error.as_deref().unwrap_or("invalid signature")
+                );
+            }
+
+            self.database.record_claim_auth_check(
+                &intent.id,
+                verdict.valid,
+                verdict.signer.as_deref(),
+                verdict.error.as_deref(),
+            )?;
+        }
+
+        if failed > 0 {
+            warn!("🚩 {} live intents have failing claim signatures", failed);
+        }
+
+        Ok(failed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        signers::{LocalWallet, Signer},
+        utils::keccak256,
+    };
+
+    const INTENT_ID: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+    const NULLIFIER: &str = "0x0202020202020202020202020202020202020202020202020202020202020202";
+
+    #[tokio::test]
+    async fn test_claim_signature_checks() {
+        let wallet: LocalWallet =
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let recipient = format!("{:?}", wallet.address());
+
+        let mut packed = Vec::new();
+        packed.extend_from_slice(&hex::decode(&INTENT_ID[2..]).unwrap());
+        packed.extend_from_slice(&hex::decode(&NULLIFIER[2..]).unwrap());
+        packed.extend_from_slice(wallet.address().as_bytes());
+        let signature = format!(
+            "0x{}",
+            wallet.sign_message(keccak256(&packed)).await.unwrap()
+        );
+
+        let verdict = verify_claim_signature(INTENT_ID, NULLIFIER, &recipient, None, &signature);
+        assert!(verdict.valid, "{:?}", verdict);
+        assert_eq!(verdict.signer, Some(recipient.clone()));
+
+        // Recipient swapped after signing recovers to some unrelated address
+        let other = "0x0000000000000000000000000000000000000001";
+        let verdict = verify_claim_signature(INTENT_ID, NULLIFIER, other, None, &signature);
+        assert!(!verdict.valid);
+        assert!(verdict.signer.is_some_and(|signer| signer != other));
+
+        // A plain signature does not authorize paying a delegate
+        let verdict =
+            verify_claim_signature(INTENT_ID, NULLIFIER, &recipient, Some(other), &signature);
+        assert!(!verdict.valid);
+
+        assert!(parse_claim_auth(&signature[2..]).is_err());
+        assert!(parse_claim_auth(&signature[..130]).is_err());
+        let bad_v = format!("{}05", &signature[..130]);
+        assert!(parse_claim_auth(&bad_v).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_delegated_claim_signature_covers_the_delegate() {
+        let wallet: LocalWallet =
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let recipient = format!("{:?}", wallet.address());
+        let delegate = "0x00000000000000000000000000000000000000d1";
+
+        let mut packed = Vec::new();
+        packed.extend_from_slice(&hex::decode(&INTENT_ID[2..]).unwrap());
+        packed.extend_from_slice(&hex::decode(&NULLIFIER[2..]).unwrap());
+        packed.extend_from_slice(wallet.address().as_bytes());
+        packed.extend_from_slice(&hex::decode(&delegate[2..]).unwrap());
+        let signature = format!(
+            "0x{}",
+            wallet.sign_message(keccak256(&packed)).await.unwrap()
+        );
+
+        let verdict =
+            verify_claim_signature(INTENT_ID, NULLIFIER, &recipient, Some(delegate), &signature);
+        assert!(verdict.valid, "{:?}", verdict);
+
+        // Redirected to another delegate, or claimed without one
+        let other = "0x00000000000000000000000000000000000000d2";
+        let verdict =
+            verify_claim_signature(INTENT_ID, NULLIFIER, &recipient, Some(other), &signature);
+        assert!(!verdict.valid);
+        let verdict = verify_claim_signature(INTENT_ID, NULLIFIER, &recipient, None, &signature);
+        assert!(!verdict.valid);
+
+        assert!(parse_claim_delegate(&format!("{:?}", Address::zero())).is_err());
+        assert!(parse_claim_delegate("0x1234").is_err());
+    }
+}
@@ -0,0 +1,463 @@
+use anyhow::{Result, anyhow};
+use ethers::{
+    types::{Address, Signature},
+    utils::keccak256,
+};
+use mantle_core::{chain::Chain, parse};
+use serde::Serialize;
+use tracing::info;
+
+use crate::{
+    merkle_manager::proof_generator::MerkleProofGenerator, models::model::Intent,
+    relay_coordinator::model::BridgeCoordinator,
+};
+
+/// Value read from a chain or the DB, with the error text when the read failed.
+type Read<T> = std::result::Result<T, String>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CheckOutcome {
+    Pass,
+    Fail,
+    /// The check depends on something we could not read.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimCheck {
+    pub name: &'static str,
+    pub outcome: CheckOutcome,
+    pub detail: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimDiagnosis {
+    pub intent_id: String,
+    pub dest_chain: String,
+    pub status: String,
+    pub claimable: bool,
+    /// First check that did not pass, in the order `claimWithdrawal` reverts.
+    pub blocking_check: Option<&'static str>,
+    pub explanation: String,
+    pub checks: Vec<ClaimCheck>,
+}
+
+/// Decrypted claim parameters as the relayer would submit them.
+#[derive(Debug, Clone)]
+pub struct ClaimSecrets {
+    pub nullifier: String,
+    pub recipient: String,
+    pub claim_delegate: Option<String>,
+    pub claim_auth: String,
+}
+
+/// Everything the checks need, gathered up front so evaluation stays pure.
+#[derive(Debug, Clone)]
+pub struct ClaimInputs {
+    pub now: u64,
+    /// Destination settlement `(filled, claimed)`.
+    pub fill_state: Read<(bool, bool)>,
+    pub registered: Read<bool>,
+    pub secrets: Read<ClaimSecrets>,
+    pub nullifier_used: Read<bool>,
+    /// Current fill root on the destination settlement.
+    pub fill_root: Read<String>,
+    /// Destination fill root as synced into the source pool.
+    pub synced_fill_root: Read<String>,
+    /// Relayer fill proof checked against `fill_root`.
+    pub proof_valid: Read<bool>,
+}
+
+fn check(name: &'static str, passed: bool, pass: &str, fail: &str) -> ClaimCheck {
+    ClaimCheck {
+        name,
+        outcome: if passed {
+            CheckOutcome::Pass
+        } else {
+            CheckOutcome::Fail
+        },
+        detail: if passed { pass } else { fail }.to_string(),
+    }
+}
+
+fn unknown(name: &'static str, reason: impl Into<String>) -> ClaimCheck {
+    ClaimCheck {
+        name,
+        outcome: CheckOutcome::Unknown,
+        detail: reason.into(),
+    }
+}
+
+/// Recover the `claimAuth` signer the same way `PrivateSettlement` does:
+/// eth-signed `keccak256(abi.encodePacked(intentId, nullifier, recipient))`,
+/// with the delegate appended for `claimWithdrawalTo`.
+pub fn recover_claim_signer(
+    intent_id: &str,
+    nullifier: &str,
+    recipient: Address,
+    delegate: Option<Address>,
+    claim_auth: &str,
+) -> Result<Address> {
+    let mut packed = Vec::with_capacity(104);
+    packed.extend_from_slice(&parse::hex32("intent_id", intent_id)?);
+    packed.extend_from_slice(&parse::hex32("nullifier", nullifier)?);
+    packed.extend_from_slice(recipient.as_bytes());
+    if let Some(delegate) = delegate {
+        packed.extend_from_slice(delegate.as_bytes());
+    }
+    let auth_hash = keccak256(&packed);
+
+    let signature_bytes = hex::decode(claim_auth.trim_start_matches("0x"))
+        .map_err(|e| anyhow!("Invalid claim signature hex: {}", e))?;
+    let signature = Signature::try_from(signature_bytes.as_slice())
+        .map_err(|e| anyhow!("Invalid claim signature: {}", e))?;
+    signature
+        .recover(&auth_hash[..])
+        .map_err(|e| anyhow!("Signature recovery failed: {}", e))
+}
+
+/// Whether `claim_auth` was signed by `recipient` for this intent and
+/// nullifier, and for `delegate` when the claim is delegated.
+pub fn verify_claim_auth(
+    intent_id: &str,
+    nullifier: &str,
+    recipient: &str,
+    delegate: Option<&str>,
+    claim_auth: &str,
+) -> Result<bool> {
+    let recipient: Address = recipient
+        .parse()
+        .map_err(|e| anyhow!("Invalid recipient: {}", e))?;
+    let delegate = delegate
+        .map(|delegate| parse::address("claim_delegate", delegate))
+        .transpose()?;
+
+    let signer = recover_claim_signer(intent_id, nullifier, recipient, delegate, claim_auth)?;
+    Ok(signer == recipient && signer != Address::zero())
+}
+
+/// Run every precondition against the gathered inputs.
+pub fn evaluate_claim(intent: &Intent, dest_chain: Chain, inputs: &ClaimInputs) -> ClaimDiagnosis {
+    let chain = dest_chain.name();
+    let mut checks = Vec::new();
+
+    match &inputs.fill_state {
+        Ok((filled, claimed)) => {
+            checks.push(check(
+                "intent_filled",
+                *filled,
+                &format!("A solver filled the intent on {}", chain),
+                &format!("No fill recorded on the {} settlement yet", chain),
+            ));
+            checks.push(check(
+                "not_claimed",
+                !claimed,
+                "The fill has not been claimed",
+                "The fill is already claimed, the withdrawal went through",
+            ));
+        }
+        Err(e) => {
+            checks.push(unknown("intent_filled", e.clone()));
+            checks.push(unknown("not_claimed", e.clone()));
+        }
+    }
+
+    match &inputs.secrets {
+        Ok(_) => checks.push(check(
+            "privacy_params",
+            true,
+            "Secret, nullifier, recipient and claim signature are stored and decrypt",
+            "",
+        )),
+        Err(e) => checks.push(check("privacy_params", false, "", e)),
+    }
+
+    match (&inputs.secrets, &inputs.nullifier_used) {
+        (Err(_), _) => checks.push(unknown("nullifier_unused", "Nullifier not available")),
+        (Ok(_), Ok(used)) => checks.push(check(
+            "nullifier_unused",
+            !used,
+            "Nullifier has not been used",
+            "Nullifier was already spent by another claim",
+        )),
+        (Ok(_), Err(e)) => checks.push(unknown("nullifier_unused", e.clone())),
+    }
+
+    match &inputs.secrets {
+        Ok(secrets) => match verify_claim_auth(
+            &intent.id,
+            &secrets.nullifier,
+            &secrets.recipient,
+            secrets.claim_delegate.as_deref(),
+            &secrets.claim_auth,
+        ) {
+            Ok(valid) => checks.push(check(
+                "signature_valid",
+                valid,
+                "Claim signature recovers to the recipient",
+                "Claim signature was not made by the recipient for this intent and nullifier",
+            )),
+            Err(e) => checks.push(check("signature_valid", false, "", &e.to_string())),
+        },
+        Err(_) => checks.push(unknown("signature_valid", "Claim signature not available")),
+    }
+
+    match &inputs.registered {
+        Ok(registered) => checks.push(check(
+            "intent_registered",
+            *registered,
+            &format!("Intent params are registered on {}", chain),
+            &format!("Intent is not registered on the {} settlement", chain),
+        )),
+        Err(e) => checks.push(unknown("intent_registered", e.clone())),
+    }
+
+    checks.push(check(
+        "deadline",
+        inputs.now <= intent.deadline,
+        "Deadline has not passed",
+        "Deadline passed, the intent will be refunded instead of claimed",
+    ));
+
+    match (&inputs.fill_root, &inputs.synced_fill_root) {
+        (Ok(root), Ok(synced)) => checks.push(check(
+            "fill_root_synced",
+            root.eq_ignore_ascii_case(synced),
+            "Source pool holds the current destination fill root",
+            &format!(
+                "Source pool has fill root {} but the {} settlement is at {}",
+                synced, chain, root
+            ),
+        )),
+        (Err(e), _) | (_, Err(e)) => checks.push(unknown("fill_root_synced", e.clone())),
+    }
+
+    match &inputs.proof_valid {
+        Ok(valid) => checks.push(check(
+            "fill_proof_valid",
+            *valid,
+            "Relayer fill proof verifies against the on-chain fill root",
+            "Relayer fill tree disagrees with the on-chain fill root",
+        )),
+        Err(e) => checks.push(unknown("fill_proof_valid", e.clone())),
+    }
+
+    let blocking = checks
+        .iter()
+        .find(|c| c.outcome != CheckOutcome::Pass)
+        .cloned();
+
+    let explanation = match &blocking {
+        None => "All claim preconditions hold".to_string(),
+        Some(c) if c.outcome == CheckOutcome::Unknown => {
+            format!("Could not verify {}: {}", c.name, c.detail)
+        }
+        Some(c) => c.detail.clone(),
+    };
+
+    ClaimDiagnosis {
+        intent_id: intent.id.to_string(),
+        dest_chain: chain.to_string(),
+        status: intent.status.as_str().to_string(),
+        claimable: blocking.is_none(),
+        blocking_check: blocking.map(|c| c.name),
+        explanation,
+        checks,
+    }
+}
+
+impl BridgeCoordinator {
+    /// Check each `claimWithdrawal` precondition off-chain. `None` if the intent is unknown.
+    pub async fn diagnose_claim(&self, intent_id: &str) -> Result<Option<ClaimDiagnosis>> {
+        let Some(intent) = self.database.get_intent_by_id(intent_id)? else {
+            return Ok(None);
+        };
+
+        let dest_chain = Chain::parse(&intent.dest_chain)?;
+        let secrets = self.load_claim_secrets(&intent.id).await;
+        let nullifier = secrets.as_ref().ok().map(|s| s.nullifier.clone());
+
+        let relayer = self.relayer(dest_chain);
+        let fill_state = relayer.get_dest_fill_state(&intent.id).await;
+        let registered = relayer.check_intent_registered(&intent.id).await;
+        let tree_size = relayer.get_fill_tree_size().await;
+        let fill_root = relayer.get_fill_root().await;
+        let synced_fill_root = self
+            .relayer(dest_chain.counterpart())
+            .get_synced_fill_root(dest_chain.id())
+            .await;
+        let nullifier_used = match &nullifier {
+            Some(n) => Some(relayer.is_nullifier_used(n).await),
+            None => None,
+        };
+
+        let fill_root = fill_root.map_err(|e| e.to_string());
+        let proof_valid = match (&tree_size, &fill_root) {
+            (Ok(size), Ok(root)) => self.verify_fill_proof(dest_chain, &intent.id, *size, root),
+            (Err(e), _) => Err(e.to_string()),
+            (_, Err(e)) => Err(e.clone()),
+        };
+
+        let inputs = ClaimInputs {
+            now: chrono::Utc::now().timestamp() as u64,
+            fill_state: fill_state.map_err(|e| e.to_string()),
+            registered: registered.map_err(|e| e.to_string()),
+            secrets,
+            nullifier_used: nullifier_used
+                .unwrap_or_else(|| Err(anyhow!("Nullifier not available")))
+                .map_err(|e| e.to_string()),
+            fill_root,
+            synced_fill_root: synced_fill_root.map_err(|e| e.to_string()),
+            proof_valid,
+        };
+
+        let diagnosis = evaluate_claim(&intent, dest_chain, &inputs);
+
+        info!(
+            "🩺 Claim diagnosis for {}: claimable={} blocking={}",
+            intent.id,
+            diagnosis.claimable,
+            diagnosis.blocking_check.unwrap_or("none")
+        );
+
+        Ok(Some(diagnosis))
+    }
+
+    async fn load_claim_secrets(&self, intent_id: &str) -> Read<ClaimSecrets> {
+        let params = self
+            .database
+            .get_intent_privacy_params(intent_id)
+            .map_err(|e| format!("Privacy params not stored: {}", e))?;
+
+        let encrypted_nullifier = params.nullifier.ok_or("Encrypted nullifier not stored")?;
+        let recipient = params.recipient.ok_or("Recipient not stored")?;
+        let claim_auth = params.claim_signature.ok_or("Claim signature not stored")?;
+        if params.secret.is_none() {
+            match self.database.get_secret_reveal(intent_id) {
+                Ok(Some(reveal)) if reveal.secret.is_some() => {}
+                Ok(Some(_)) => return Err("Secret not revealed by the user yet".to_string()),
+                Ok(None) => return Err("Encrypted secret not stored".to_string()),
+                Err(e) => return Err(format!("Failed to read secret reveal: {}", e)),
+            }
+        }
+
+        let nullifier = self
+            .decrypt_privacy_param(&encrypted_nullifier)
+            .await
+            .map_err(|e| format!("Failed to decrypt nullifier: {}", e))?;
+
+        Ok(ClaimSecrets {
+            nullifier,
+            recipient: recipient.into(),
+            claim_delegate: params.claim_delegate.map(String::from),
+            claim_auth,
+        })
+    }
+
+    /// Rebuild the fill proof from the relayer's tree, limited to the leaves
+    /// the settlement holds, and verify it against the on-chain root.
+    fn verify_fill_proof(
+        &self,
+        chain: Chain,
+        intent_id: &str,
+        tree_size: usize,
+        onchain_root: &str,
+    ) -> Read<bool> {
+        let generator = &self.merkle_tree_manager.proof_generator;
+
+        let (proof, index, _root) = generator
+            .generate_fill_proof(chain.name(), intent_id, tree_size)
+            .map_err(|e| e.to_string())?;
+
+        MerkleProofGenerator::verify_proof(&proof, onchain_root, intent_id, index)
+            .map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::model::IntentStatus;
+    use chrono::Utc;
+    use ethers::signers::{LocalWallet, Signer};
+
+    const INTENT_ID: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+    const NULLIFIER: &str = "0x0202020202020202020202020202020202020202020202020202020202020202";
+
+    fn intent() -> Intent {
+        Intent {
+            id: INTENT_ID.parse().unwrap(),
+            user_address: String::new(),
+            source_chain: "ethereum".to_string(),
+            dest_chain: "mantle".to_string(),
+            source_token: String::new(),
+            dest_token: String::new(),
+            amount: "0".parse().unwrap(),
+            dest_amount: "0".parse().unwrap(),
+            source_commitment: None,
+            dest_fill_txid: None,
+            dest_registration_txid: None,
+            source_complete_txid: None,
+            status: IntentStatus::SolverPaid,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deadline: 2_000,
+            refund_address: None,
+            solver_address: None,
+            block_number: None,
+            log_index: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_claim() {
+        let wallet: LocalWallet =
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let recipient = format!("{:?}", wallet.address());
+
+        let mut packed = Vec::new();
+        packed.extend_from_slice(&parse::hex32("intent_id", INTENT_ID).unwrap());
+        packed.extend_from_slice(&parse::hex32("nullifier", NULLIFIER).unwrap());
+        packed.extend_from_slice(wallet.address().as_bytes());
+        let signature = wallet.sign_message(keccak256(&packed)).await.unwrap();
+
+        let mut inputs = ClaimInputs {
+            now: 1_000,
+            fill_state: Ok((true, false)),
+            registered: Ok(true),
+            secrets: Ok(ClaimSecrets {
+                nullifier: NULLIFIER.to_string(),
+                recipient: recipient.clone(),
+                claim_delegate: None,
+                claim_auth: format!("0x{}", signature),
+            }),
+            nullifier_used: Ok(false),
+            fill_root: Ok("0xAB".to_string()),
+            synced_fill_root: Ok("0xab".to_string()),
+            proof_valid: Ok(true),
+        };
+
+        let diagnosis = evaluate_claim(&intent(), Chain::Mantle, &inputs);
+        assert!(diagnosis.claimable, "{:?}", diagnosis.checks);
+
+        inputs.nullifier_used = Ok(true);
+        inputs.now = 3_000;
+        let diagnosis = evaluate_claim(&intent(), Chain::Mantle, &inputs);
+        assert!(!diagnosis.claimable);
+        assert_eq!(diagnosis.blocking_check, Some("nullifier_unused"));
+
+        assert!(
+            !verify_claim_auth(
+                INTENT_ID,
+                NULLIFIER,
+                "0x0000000000000000000000000000000000000001",
+                None,
+                &format!("0x{}", signature),
+            )
+            .unwrap()
+        );
+    }
+}
@@ -0,0 +1,301 @@
+use std::collections::HashMap;
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use ethers::types::U256;
+use mantle_core::chain::Chain;
+use serde::Serialize;
+use tokio::sync::RwLock;
+use tracing::info;
+
+use crate::relay_coordinator::model::BridgeCoordinator;
+
+/// Decrypted `claimWithdrawal` arguments of an intent.
+#[derive(Debug, Clone)]
+pub struct ClaimParams {
+    pub nullifier: String,
+    pub recipient: String,
+    /// Paid instead of `recipient` when the claim was delegated.
+    pub delegate: Option<String>,
+    pub secret: String,
+    pub claim_auth: Vec<u8>,
+}
+
+/// Outcome of a `claimWithdrawal` dry run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClaimSimulation {
+    Succeeds { gas: U256, cost_wei: U256 },
+    Reverts(String),
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ClaimEstimate {
+    pub intent_id: String,
+    pub dest_chain: String,
+    pub status: String,
+    pub deadline: u64,
+    pub deadline_passed: bool,
+    /// False when the claim could not be dry-run, e.g. the secret is not
+    /// revealed yet; `reason` says why.
+    pub simulated: bool,
+    pub will_succeed: bool,
+    pub estimated_gas: Option<u64>,
+    pub estimated_cost_wei: Option<String>,
+    /// Decoded revert reason, or why the claim was not simulated.
+    pub reason: Option<String>,
+    /// Destination fill root and its copy on the source pool at estimate time.
+    pub fill_root: String,
+    pub synced_fill_root: String,
+    pub estimated_at: DateTime<Utc>,
+    pub cached: bool,
+}
+
+impl ClaimEstimate {
+    fn is_current(&self, status: &str, fill_root: &str, synced_fill_root: &str) -> bool {
+        self.status == status
+            && self.fill_root.eq_ignore_ascii_case(fill_root)
+            && self.synced_fill_root.eq_ignore_ascii_case(synced_fill_root)
+    }
+}
+
+/// Dry runs keyed by intent, valid until either fill root moves or the
+/// intent changes status. A root update evicts every older entry.
+#[derive(Default)]
+pub struct ClaimEstimateCache {
+    entries: RwLock<HashMap<String, ClaimEstimate>>,
+}
+
+impl ClaimEstimateCache {
+    pub async fn get(
+        &self,
+        intent_id: &str,
+        status: &str,
+        fill_root: &str,
+        synced_fill_root: &str,
+    ) -> Option<ClaimEstimate> {
+        self.entries
+            .read()
+            .await
+            .get(intent_id)
+            .filter(|estimate| estimate.is_current(status, fill_root, synced_fill_root))
+            .map(|estimate| ClaimEstimate {
+                cached: true,
+                ..estimate.clone()
+            })
+    }
+
+    pub async fn insert(&self, estimate: ClaimEstimate) {
+        let mut entries = self.entries.write().await;
+        entries.retain(|_, cached| {
+            cached.dest_chain != estimate.dest_chain
+                || (cached.fill_root.eq_ignore_ascii_case(&estimate.fill_root)
+                    && cached
+                        .synced_fill_root
+                        .eq_ignore_ascii_case(&estimate.synced_fill_root))
+        });
+        entries.insert(estimate.intent_id.clone(), estimate);
+    }
+}
+
+impl BridgeCoordinator {
+    /// Everything `claimWithdrawal` needs, decrypted.
+    pub(crate) async fn claim_params(&self, intent_id: &str) -> Result<ClaimParams> {
+        let privacy_params = self
+            .database
+            .get_intent_privacy_params(intent_id)
+            .map_err(|e| anyhow!("Failed to get privacy params: {}", e))?;
+
+        let encrypted_nullifier = privacy_params
+            .nullifier
+            .as_ref()
+            .ok_or_else(|| anyhow!("Encrypted nullifier not available"))?;
+
+        let recipient = privacy_params
+            .recipient
+            .clone()
+            .ok_or_else(|| anyhow!("Recipient not available"))?;
+
+        let claim_auth_hex = privacy_params
+            .claim_signature
+            .as_ref()
+            .ok_or_else(|| anyhow!("Claim signature not available"))?;
+
+        let secret = self
+            .claim_secret(intent_id, privacy_params.secret.as_deref())
+            .await?;
+
+        let nullifier = self
+            .decrypt_privacy_param(encrypted_nullifier)
+            .await
+            .map_err(|e| anyhow!("Failed to decrypt nullifier: {}", e))?;
+
+        let claim_auth_hex_clean = claim_auth_hex.strip_prefix("0x").unwrap_or(claim_auth_hex);
+        let claim_auth = hex::decode(claim_auth_hex_clean)
+            .map_err(|e| anyhow!("Failed to decode claim signature hex: {}", e))?;
+
+        if claim_auth.len() != 65 {
+            return Err(anyhow!(
+                "Invalid signature length: expected 65 bytes, got {}",
+                claim_auth.len()
+            ));
+        }
+
+        Ok(ClaimParams {
+            nullifier,
+            recipient: recipient.into(),
+            delegate: privacy_params.claim_delegate.map(String::from),
+            secret,
+            claim_auth,
+        })
+    }
+
+    /// Dry-run the claim on the destination settlement. `None` if the intent
+    /// is unknown. Results are reused until a fill root changes.
+    pub async fn estimate_claim(
+        &self,
+        intent_id: &str,
+        cache: &ClaimEstimateCache,
+    ) -> Result<Option<ClaimEstimate>> {
+        let Some(intent) = self.database.get_intent_by_id(intent_id)? else {
+            return Ok(None);
+        };
+        let status = intent.status.as_str();
+        let dest_chain = Chain::parse(&intent.dest_chain)?;
+
+        let relayer = self.relayer(dest_chain);
+        let fill_root = relayer.get_fill_root().await?;
+        let synced_fill_root = self
+            .relayer(dest_chain.counterpart())
+            .get_synced_fill_root(dest_chain.id())
+            .await?;
+
+        if let Some(estimate) = cache
+            .get(&intent.id, status, &fill_root, &synced_fill_root)
+            .await
+        {
+            return Ok(Some(estimate));
+        }
+
+        // Err when the claim can't be dry-run yet, e.g. the secret is unrevealed
+        let simulation = match self.claim_params(&intent.id).await {
+            Ok(params) => Ok(relayer
+                .simulate_claim_withdrawal(
+                    &intent.id,
+                    &params.nullifier,
+                    &params.recipient,
+                    params.delegate.as_deref(),
+                    &params.secret,
+                    &params.claim_auth,
+                )
+                .await?),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let now = Utc::now();
+        let mut estimate = ClaimEstimate {
+            intent_id: intent.id.to_string(),
+            dest_chain: dest_chain.name().to_string(),
+            status: status.to_string(),
+            deadline: intent.deadline,
+            deadline_passed: now.timestamp() as u64 > intent.deadline,
+            simulated: simulation.is_ok(),
+            will_succeed: false,
+            estimated_gas: None,
+            estimated_cost_wei: None,
+            reason: None,
+            fill_root,
+            synced_fill_root,
+            estimated_at: now,
+            cached: false,
+        };
+        match simulation {
+            Ok(ClaimSimulation::Succeeds { gas, cost_wei }) => {
+                estimate.will_succeed = true;
+                estimate.estimated_gas = Some(gas.as_u64());
+                estimate.estimated_cost_wei = Some(cost_wei.to_string());
+            }
+            Ok(ClaimSimulation::Reverts(reason)) | Err(reason) => estimate.reason = Some(reason),
+        }
+
+        info!(
+            "🧮 Claim estimate for {}: simulated={} will_succeed={} gas={:?}",
+            intent.id, estimate.simulated, estimate.will_succeed, estimate.estimated_gas
+        );
+
+        // The secret may be revealed without a root update, so only dry runs are kept
+        if estimate.simulated {
+            cache.insert(estimate.clone()).await;
+        }
+        Ok(Some(estimate))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(intent_id: &str, dest_chain: &str, fill_root: &str) -> ClaimEstimate {
+        ClaimEstimate {
+            intent_id: intent_id.to_string(),
+            dest_chain: dest_chain.to_string(),
+            status: "solver_paid".to_string(),
+            deadline: 0,
+            deadline_passed: false,
+            simulated: true,
+            will_succeed: true,
+            estimated_gas: Some(120_000),
+            estimated_cost_wei: Some("1".to_string()),
+            reason: None,
+            fill_root: fill_root.to_string(),
+            synced_fill_root: "0xaa".to_string(),
+            estimated_at: Utc::now(),
+            cached: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cache_is_keyed_on_roots_and_status() {
+        let cache = ClaimEstimateCache::default();
+        cache.insert(estimate("0x01", "mantle", "0x11")).await;
+        cache.insert(estimate("0x02", "ethereum", "0x22")).await;
+
+        let hit = cache
+            .get("0x01", "solver_paid", "0x11", "0xAA")
+            .await
+            .unwrap();
+        assert!(hit.cached);
+        assert!(
+            cache
+                .get("0x01", "user_claimed", "0x11", "0xaa")
+                .await
+                .is_none()
+        );
+        assert!(
+            cache
+                .get("0x01", "solver_paid", "0x12", "0xaa")
+                .await
+                .is_none()
+        );
+
+        // A newer Mantle root evicts stale Mantle entries, not Ethereum ones
+        cache.insert(estimate("0x03", "mantle", "0x12")).await;
+        assert!(
+            cache
+                .get("0x01", "solver_paid", "0x11", "0xaa")
+                .await
+                .is_none()
+        );
+        assert!(
+            cache
+                .get("0x02", "solver_paid", "0x22", "0xaa")
+                .await
+                .is_some()
+        );
+        assert!(
+            cache
+                .get("0x03", "solver_paid", "0x12", "0xaa")
+                .await
+                .is_some()
+        );
+    }
+}
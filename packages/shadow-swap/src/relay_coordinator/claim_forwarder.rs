@@ -0,0 +1,406 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{Context, Result, anyhow};
+use ethers::{
+    abi::AbiDecode,
+    contract::{ContractCall, EthEvent, parse_log},
+    middleware::SignerMiddleware,
+    providers::{Http, Provider},
+    signers::Signer,
+    types::{Address, Bytes, H256, TransactionReceipt, U256},
+};
+use mantle_core::abi::{
+    SettlementContract, TrustedForwarderContract, WithdrawalClaimedFilter,
+    trusted_forwarder_contract::{ExecuteCall, ExecutedForwardRequestFilter, ForwardRequestData},
+};
+use signing_service::{
+    client::SignerHandle,
+    forward_request::{ForwardRequest, ForwarderDomain},
+};
+use tokio::sync::Mutex;
+use tracing::{info, warn};
+
+type ForwarderClient = SignerMiddleware<Provider<Http>, SignerHandle>;
+
+/// Optional ERC-2771 forwarder claims are routed through, per chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClaimForwarderPolicy {
+    /// Forwarder claims are wrapped for; `None` calls the settlement directly.
+    pub forwarder: Option<Address>,
+    /// Gas the forwarder must hand on to `claimWithdrawal`.
+    pub inner_gas: u64,
+    /// How long a signed forward request stays valid.
+    pub deadline: Duration,
+}
+
+impl Default for ClaimForwarderPolicy {
+    fn default() -> Self {
+        Self {
+            forwarder: None,
+            inner_gas: 300_000,
+            deadline: Duration::from_secs(600),
+        }
+    }
+}
+
+impl ClaimForwarderPolicy {
+    /// Reads `<PREFIX>_CLAIM_FORWARDER`, `<PREFIX>_CLAIM_FORWARDER_GAS` and
+    /// `<PREFIX>_CLAIM_FORWARDER_DEADLINE_SECS`.
+    pub fn from_env(prefix: &str) -> Result<Self> {
+        let var = |name: &str| {
+            let key = format!("{}_CLAIM_FORWARDER{}", prefix, name);
+            std::env::var(&key)
+                .ok()
+                .filter(|value| !value.trim().is_empty())
+                .map(|value| (key, value))
+        };
+        let defaults = Self::default();
+
+        Ok(Self {
+            forwarder: var("")
+                .map(|(key, value)| {
+                    value
+                        .trim()
+                        .parse()
+                        .with_context(|| format!("Invalid {}", key))
+                })
+                .transpose()?,
+            inner_gas: match var("_GAS") {
+                Some((key, value)) => value.parse().with_context(|| format!("Invalid {}", key))?,
+                None => defaults.inner_gas,
+            },
+            deadline: match var("_DEADLINE_SECS") {
+                Some((key, value)) => Duration::from_secs(
+                    value
+                        .parse::<u64>()
+                        .with_context(|| format!("Invalid {}", key))?
+                        .max(30),
+                ),
+                None => defaults.deadline,
+            },
+        })
+    }
+}
+
+/// Wraps `claimWithdrawal` calls into forward requests the relayer signs and
+/// submits through the configured forwarder. The settlement takes the signer
+/// from the calldata suffix, so the relayer stays the authorised claimer.
+pub struct ClaimForwarder {
+    pub policy: ClaimForwarderPolicy,
+    address: Address,
+    contract: TrustedForwarderContract<ForwarderClient>,
+    domain: ForwarderDomain,
+    /// Next request nonce, ahead of the chain while earlier claims are in flight.
+    next_nonce: Mutex<Option<U256>>,
+}
+
+impl ClaimForwarder {
+    /// `None` when no forwarder is configured; an error when the settlement
+    /// does not trust it, since every forwarded claim would revert.
+    pub async fn connect(
+        chain: &str,
+        policy: ClaimForwarderPolicy,
+        client: Arc<ForwarderClient>,
+        settlement: &SettlementContract<ForwarderClient>,
+    ) -> Result<Option<Self>> {
+        let Some(address) = policy.forwarder else {
+            return Ok(None);
+        };
+
+        let trusted = settlement
+            .is_trusted_forwarder(address)
+            .call()
+            .await
+            .with_context(|| format!("Failed to check {} claim forwarder", chain))?;
+        if !trusted {
+            return Err(anyhow!(
+                "{} settlement {:?} does not trust claim forwarder {:?}",
+                chain,
+                settlement.address(),
+                address
+            ));
+        }
+
+        let contract = TrustedForwarderContract::new(address, client);
+        let (_, name, version, chain_id, verifying_contract, _, _) = contract
+            .eip_712_domain()
+            .call()
+            .await
+            .with_context(|| format!("Failed to read {} claim forwarder domain", chain))?;
+
+        info!(
+            "   Claim forwarder: {:?} ({} v{}, inner gas {})",
+            address, name, version, policy.inner_gas
+        );
+
+        Ok(Some(Self {
+            policy,
+            address,
+            contract,
+            domain: ForwarderDomain {
+                name,
+                version,
+                chain_id: chain_id.as_u64(),
+                verifying_contract,
+            },
+            next_nonce: Mutex::new(None),
+        }))
+    }
+
+    pub fn address(&self) -> Address {
+        self.address
+    }
+
+    /// Signed `execute` for a claim about to be sent. Reserves a nonce; call
+    /// [`Self::reset_nonce`] if the claim then fails.
+    pub async fn wrap(
+        &self,
+        inner: &ContractCall<ForwarderClient, ()>,
+    ) -> Result<ContractCall<ForwarderClient, ()>> {
+        let from = self.contract.client().signer().address();
+        let onchain = self.chain_nonce(from).await?;
+
+        let nonce = {
+            let mut next = self.next_nonce.lock().await;
+            let nonce = next.map_or(onchain, |next| next.max(onchain));
+            *next = Some(nonce + 1);
+            nonce
+        };
+
+        self.wrap_with_nonce(inner, from, nonce).await
+    }
+
+    /// Signed `execute` for a dry run at the chain's current nonce.
+    pub async fn wrap_for_simulation(
+        &self,
+        inner: &ContractCall<ForwarderClient, ()>,
+    ) -> Result<ContractCall<ForwarderClient, ()>> {
+        let from = self.contract.client().signer().address();
+        let nonce = self.chain_nonce(from).await?;
+        self.wrap_with_nonce(inner, from, nonce).await
+    }
+
+    /// Start again from the chain's nonce after a forwarded claim failed, so
+    /// a skipped nonce does not block every later request.
+    pub async fn reset_nonce(&self) {
+        warn!("🔁 Resetting claim forwarder nonce for {:?}", self.address);
+        *self.next_nonce.lock().await = None;
+    }
+
+    async fn chain_nonce(&self, from: Address) -> Result<U256> {
+        self.contract
+            .nonces(from)
+            .call()
+            .await
+            .context("Failed to read forwarder nonce")
+    }
+
+    async fn wrap_with_nonce(
+        &self,
+        inner: &ContractCall<ForwarderClient, ()>,
+        from: Address,
+        nonce: U256,
+    ) -> Result<ContractCall<ForwarderClient, ()>> {
+        let to = *inner
+            .tx
+            .to_addr()
+            .ok_or_else(|| anyhow!("Claim call has no target"))?;
+        let request = ForwardRequest {
+            from,
+            to,
+            value: U256::zero(),
+            gas: self.policy.inner_gas.into(),
+            nonce,
+            deadline: chrono::Utc::now().timestamp() as u64 + self.policy.deadline.as_secs(),
+            data: inner.tx.data().cloned().unwrap_or_default(),
+        };
+
+        let signature = self
+            .contract
+            .client()
+            .signer()
+            .sign_forward_request(&self.domain, &request)
+            .await
+            .map_err(|e| anyhow!("Failed to sign forward request: {}", e))?;
+
+        Ok(self.contract.execute(ForwardRequestData {
+            from: request.from,
+            to: request.to,
+            value: request.value,
+            gas: request.gas,
+            deadline: request.deadline,
+            data: request.data,
+            signature: Bytes::from(signature.to_vec()),
+        }))
+    }
+}
+
+/// The call a forwarder `execute` relays, for scans that decode relayer
+/// calldata by the transaction's `to`.
+pub fn unwrap_forwarded(input: &[u8]) -> Option<Bytes> {
+    ExecuteCall::decode(input)
+        .ok()
+        .map(|call| call.request.data)
+}
+
+/// Confirm a mined claim paid out. A forwarded claim is sent to the
+/// forwarder, so `WithdrawalClaimed` is matched by the settlement's address
+/// rather than the transaction's, and the forwarder's own event must report
+/// the inner call succeeded.
+pub fn check_claim_receipt(
+    receipt: &TransactionReceipt,
+    intent_id: &str,
+    settlement: Address,
+    forwarder: Option<Address>,
+) -> Result<()> {
+    if receipt.status != Some(1.into()) {
+        return Err(anyhow!("Claim transaction reverted"));
+    }
+
+    if let Some(forwarder) = forwarder {
+        let executed = receipt
+            .logs
+            .iter()
+            .filter(|log| {
+                log.address == forwarder
+                    && log.topics.first() == Some(&ExecutedForwardRequestFilter::signature())
+            })
+            .find_map(|log| parse_log::<ExecutedForwardRequestFilter>(log.clone()).ok())
+            .ok_or_else(|| {
+                anyhow!(
+                    "Claim tx has no ExecutedForwardRequest from forwarder {:?}",
+                    forwarder
+                )
+            })?;
+
+        if !executed.success {
+            return Err(anyhow!(
+                "Forwarder {:?} ran the claim request (nonce {}) but claimWithdrawal reverted",
+                forwarder,
+                executed.nonce
+            ));
+        }
+    }
+
+    let intent_id: H256 = intent_id.parse().context("Invalid intent_id hex")?;
+    let claimed = receipt
+        .logs
+        .iter()
+        .filter(|log| log.address == settlement)
+        .filter_map(|log| parse_log::<WithdrawalClaimedFilter>(log.clone()).ok())
+        .any(|event| H256(event.intent_id) == intent_id);
+
+    if !claimed {
+        return Err(anyhow!(
+            "Claim tx has no WithdrawalClaimed for {:?} from settlement {:?}",
+            intent_id,
+            settlement
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        abi::{AbiEncode, Token, encode},
+        types::Log,
+    };
+    use mantle_core::abi::settlement_contract::ClaimWithdrawalCall;
+
+    #[test]
+    fn test_forwarded_claim_receipt_and_calldata() {
+        let settlement = Address::repeat_byte(0x5e);
+        let forwarder = Address::repeat_byte(0xf0);
+        let intent_id = H256::repeat_byte(0x11);
+        let id = format!("{:?}", intent_id);
+
+        let claimed = Log {
+            address: settlement,
+            topics: vec![
+                WithdrawalClaimedFilter::signature(),
+                intent_id,
+                H256::repeat_byte(0x22),
+            ],
+            data: encode(&[Token::Address(Address::repeat_byte(0x70))]).into(),
+            ..Default::default()
+        };
+        let executed = |success: bool| Log {
+            address: forwarder,
+            topics: vec![
+                ExecutedForwardRequestFilter::signature(),
+                H256::from(Address::repeat_byte(0x44)),
+            ],
+            data: encode(&[Token::Uint(3.into()), Token::Bool(success)]).into(),
+            ..Default::default()
+        };
+        let receipt = |logs: Vec<Log>| TransactionReceipt {
+            status: Some(1.into()),
+            to: Some(forwarder),
+            logs,
+            ..Default::default()
+        };
+
+        // Direct claims still need the settlement's event
+        assert!(
+            check_claim_receipt(&receipt(vec![claimed.clone()]), &id, settlement, None).is_ok()
+        );
+        assert!(check_claim_receipt(&receipt(vec![]), &id, settlement, None).is_err());
+
+        let forwarded = receipt(vec![claimed.clone(), executed(true)]);
+        assert!(check_claim_receipt(&forwarded, &id, settlement, Some(forwarder)).is_ok());
+        // Some forwarders mine a failed inner call instead of reverting
+        let swallowed = receipt(vec![executed(false)]);
+        let err = check_claim_receipt(&swallowed, &id, settlement, Some(forwarder)).unwrap_err();
+        assert!(err.to_string().contains("claimWithdrawal reverted"));
+        assert!(
+            check_claim_receipt(
+                &receipt(vec![claimed.clone()]),
+                &id,
+                settlement,
+                Some(forwarder)
+            )
+            .is_err()
+        );
+
+        // The same event from another contract does not count
+        let spoofed = Log {
+            address: forwarder,
+            ..claimed
+        };
+        assert!(
+            check_claim_receipt(
+                &receipt(vec![spoofed, executed(true)]),
+                &id,
+                settlement,
+                Some(forwarder)
+            )
+            .is_err()
+        );
+
+        let inner = ClaimWithdrawalCall {
+            intent_id: intent_id.0,
+            nullifier: [3; 32],
+            recipient: Address::repeat_byte(4),
+            secret: [5; 32],
+            claim_auth: vec![6u8; 65].into(),
+        }
+        .encode();
+        let execute = ExecuteCall {
+            request: ForwardRequestData {
+                from: Address::repeat_byte(0x44),
+                to: settlement,
+                value: U256::zero(),
+                gas: 300_000.into(),
+                deadline: 1_700_000_000,
+                data: inner.clone().into(),
+                signature: vec![7u8; 65].into(),
+            },
+        }
+        .encode();
+        assert_eq!(unwrap_forwarded(&execute), Some(Bytes::from(inner.clone())));
+        assert_eq!(unwrap_forwarded(&inner), None);
+    }
+}
@@ -0,0 +1,338 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{Context, Result, anyhow};
+use chrono::Utc;
+use mantle_core::http_client::OutboundClient;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
+
+use crate::{
+    database::{database::Database, model::NewComplianceScreening},
+    models::model::Intent,
+};
+
+/// What the coordinator does with an intent after screening its depositor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ComplianceAction {
+    Allow,
+    /// Processed normally, listed for review.
+    Flag,
+    /// Never registered on the destination chain; refunded at the deadline.
+    Block,
+}
+
+impl ComplianceAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Allow => "allow",
+            Self::Flag => "flag",
+            Self::Block => "block",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "allow" => Ok(Self::Allow),
+            "flag" => Ok(Self::Flag),
+            "block" => Ok(Self::Block),
+            other => Err(anyhow!(
+                "Unknown compliance action '{}', expected allow, flag or block",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CompliancePolicy {
+    pub enabled: bool,
+    pub provider_url: Option<String>,
+    pub api_key: Option<String>,
+    pub timeout: Duration,
+    /// Scores at or above this are flagged.
+    pub flag_score: f64,
+    /// Scores at or above this are blocked.
+    pub block_score: f64,
+    /// Provider labels (case-insensitive) that block regardless of score.
+    pub block_labels: Vec<String>,
+    /// Applied when the provider cannot be reached or answers garbage.
+    pub on_error: ComplianceAction,
+}
+
+impl Default for CompliancePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            provider_url: None,
+            api_key: None,
+            timeout: Duration::from_secs(10),
+            flag_score: 50.0,
+            block_score: 80.0,
+            block_labels: vec!["sanctions".to_string()],
+            on_error: ComplianceAction::Flag,
+        }
+    }
+}
+
+impl CompliancePolicy {
+    /// Reads `COMPLIANCE_ENABLED`, `COMPLIANCE_PROVIDER_URL`,
+    /// `COMPLIANCE_API_KEY`, `COMPLIANCE_TIMEOUT_SECS`,
+    /// `COMPLIANCE_FLAG_SCORE`, `COMPLIANCE_BLOCK_SCORE`,
+    /// `COMPLIANCE_BLOCK_LABELS` and `COMPLIANCE_ON_ERROR`.
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(format!("COMPLIANCE_{}", name)).ok();
+        let mut policy = Self::default();
+
+        if let Some(enabled) = var("ENABLED") {
+            policy.enabled = enabled.parse().context("Invalid COMPLIANCE_ENABLED")?;
+        }
+        policy.provider_url = var("PROVIDER_URL").filter(|url| !url.trim().is_empty());
+        policy.api_key = var("API_KEY").filter(|key| !key.trim().is_empty());
+        if let Some(secs) = var("TIMEOUT_SECS") {
+            policy.timeout =
+                Duration::from_secs(secs.parse().context("Invalid COMPLIANCE_TIMEOUT_SECS")?);
+        }
+        if let Some(score) = var("FLAG_SCORE") {
+            policy.flag_score = score.parse().context("Invalid COMPLIANCE_FLAG_SCORE")?;
+        }
+        if let Some(score) = var("BLOCK_SCORE") {
+            policy.block_score = score.parse().context("Invalid COMPLIANCE_BLOCK_SCORE")?;
+        }
+        if let Some(labels) = var("BLOCK_LABELS") {
+            policy.block_labels = labels
+                .split(',')
+                .map(|label| label.trim().to_lowercase())
+                .filter(|label| !label.is_empty())
+                .collect();
+        }
+        if let Some(action) = var("ON_ERROR") {
+            policy.on_error = ComplianceAction::parse(&action)?;
+        }
+
+        if policy.enabled && policy.provider_url.is_none() {
+            return Err(anyhow!(
+                "COMPLIANCE_PROVIDER_URL is required when COMPLIANCE_ENABLED=true"
+            ));
+        }
+        if policy.flag_score > policy.block_score {
+            return Err(anyhow!(
+                "COMPLIANCE_FLAG_SCORE must not exceed COMPLIANCE_BLOCK_SCORE"
+            ));
+        }
+
+        Ok(policy)
+    }
+
+    /// Map a provider answer (or failure) to an action and a reason.
+    pub fn decide(&self, assessment: &Result<RiskAssessment>) -> (ComplianceAction, String) {
+        let assessment = match assessment {
+            Ok(assessment) => assessment,
+            Err(e) => {
+                return (
+                    self.on_error,
+                    format!("Provider unavailable ({}): {}", self.on_error.as_str(), e),
+                );
+            }
+        };
+
+        if let Some(label) = assessment
+            .labels
+            .iter()
+            .find(|label| self.block_labels.contains(&label.to_lowercase()))
+        {
+            return (ComplianceAction::Block, format!("Labelled '{}'", label));
+        }
+
+        if assessment.risk_score >= self.block_score {
+            (
+                ComplianceAction::Block,
+                format!(
+                    "Risk score {} >= block threshold {}",
+                    assessment.risk_score, self.block_score
+                ),
+            )
+        } else if assessment.risk_score >= self.flag_score {
+            (
+                ComplianceAction::Flag,
+                format!(
+                    "Risk score {} >= flag threshold {}",
+                    assessment.risk_score, self.flag_score
+                ),
+            )
+        } else {
+            (
+                ComplianceAction::Allow,
+                format!("Risk score {}", assessment.risk_score),
+            )
+        }
+    }
+}
+
+/// Provider response. Field aliases cover the common REST shapes.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+pub struct RiskAssessment {
+    #[serde(alias = "riskScore", alias = "score")]
+    pub risk_score: f64,
+    #[serde(default, alias = "categories")]
+    pub labels: Vec<String>,
+}
+
+/// Screens intent depositors against the configured chain-analytics provider
+/// and remembers the outcome per intent.
+pub struct ComplianceScreener {
+    pub policy: CompliancePolicy,
+    http: OutboundClient,
+    database: Arc<Database>,
+}
+
+impl ComplianceScreener {
+    pub fn new(policy: CompliancePolicy, database: Arc<Database>, http: OutboundClient) -> Self {
+        Self {
+            policy,
+            http,
+            database,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.policy.enabled
+    }
+
+    async fn assess(&self, address: &str, chain: &str) -> Result<RiskAssessment> {
+        let url = self
+            .policy
+            .provider_url
+            .as_deref()
+            .ok_or_else(|| anyhow!("No compliance provider configured"))?;
+
+        let body = serde_json::json!({
+            "address": address,
+            "chain": chain,
+        });
+        let response = self
+            .http
+            .send(url, |http| {
+                let request = http.post(url).timeout(self.policy.timeout).json(&body);
+                match &self.policy.api_key {
+                    Some(key) => request.bearer_auth(key),
+                    None => request,
+                }
+            })
+            .await
+            .context("Compliance provider request failed")?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(anyhow!("Compliance provider returned {}", status));
+        }
+
+        response
+            .json::<RiskAssessment>()
+            .await
+            .context("Invalid compliance provider response")
+    }
+
+    /// Action for this intent's depositor, screening it on first sight.
+    /// Earlier answers are reused unless they came from a provider error
+    /// and no admin has overridden them.
+    pub async fn screen(&self, intent: &Intent) -> Result<ComplianceAction> {
+        if !self.enabled() {
+            return Ok(ComplianceAction::Allow);
+        }
+
+        if let Some(existing) = self.database.get_compliance_screening(&intent.id)?
+            && (existing.provider_error.is_none() || existing.override_note.is_some())
+        {
+            return ComplianceAction::parse(&existing.action);
+        }
+
+        let address = intent.user_address.to_lowercase();
+        let assessment = self.assess(&address, &intent.source_chain).await;
+        let (action, reason) = self.policy.decide(&assessment);
+
+        let labels = assessment
+            .as_ref()
+            .ok()
+            .filter(|assessment| !assessment.labels.is_empty())
+            .map(|assessment| assessment.labels.join(","));
+        let provider_error = assessment.as_ref().err().map(|e| format!("{:#}", e));
+
+        self.database
+            .record_compliance_screening(&NewComplianceScreening {
+                intent_id: &intent.id,
+                address: &address,
+                chain: &intent.source_chain,
+                risk_score: assessment.as_ref().ok().map(|a| a.risk_score),
+                labels: labels.as_deref(),
+                action: action.as_str(),
+                reason: Some(&reason),
+                provider_error: provider_error.as_deref(),
+                screened_at: Utc::now(),
+                updated_at: Utc::now(),
+            })?;
+
+        match action {
+            ComplianceAction::Allow => info!("🔎 Intent {} screened: {}", intent.id, reason),
+            ComplianceAction::Flag => warn!("🚩 Intent {} flagged: {}", intent.id, reason),
+            ComplianceAction::Block => warn!("⛔ Intent {} blocked: {}", intent.id, reason),
+        }
+
+        Ok(action)
+    }
+
+    /// Screen in the background so event handlers return immediately.
+    pub fn screen_in_background(self: &Arc<Self>, intent: Intent) {
+        if !self.enabled() {
+            return;
+        }
+
+        let screener = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = screener.screen(&intent).await {
+                error!("❌ Compliance screening failed for {}: {}", intent.id, e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decide() {
+        let policy = CompliancePolicy::default();
+        let assessed = |risk_score: f64, labels: &[&str]| {
+            Ok(RiskAssessment {
+                risk_score,
+                labels: labels.iter().map(|l| l.to_string()).collect(),
+            })
+        };
+
+        assert_eq!(
+            policy.decide(&assessed(10.0, &[])).0,
+            ComplianceAction::Allow
+        );
+        assert_eq!(
+            policy.decide(&assessed(50.0, &[])).0,
+            ComplianceAction::Flag
+        );
+        assert_eq!(
+            policy.decide(&assessed(95.0, &[])).0,
+            ComplianceAction::Block
+        );
+        assert_eq!(
+            policy.decide(&assessed(0.0, &["Sanctions"])).0,
+            ComplianceAction::Block
+        );
+        assert_eq!(
+            policy.decide(&Err(anyhow!("timeout"))).0,
+            ComplianceAction::Flag
+        );
+
+        let parsed: RiskAssessment =
+            serde_json::from_str(r#"{"riskScore": 72.5, "categories": ["mixer"]}"#).unwrap();
+        assert_eq!(parsed.risk_score, 72.5);
+        assert_eq!(parsed.labels, vec!["mixer"]);
+    }
+}
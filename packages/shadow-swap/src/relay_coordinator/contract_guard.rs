@@ -0,0 +1,494 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use ethers::{
+    providers::Middleware,
+    types::{Address, Filter, H256, Log, U256},
+    utils::keccak256,
+};
+use mantle_core::abi::{IntentPoolContract, SettlementContract};
+use serde::Serialize;
+use tokio::{sync::RwLock, time::sleep};
+use tracing::{error, info, warn};
+
+use crate::chains::{registry::ChainRegistry, relayer::ChainClient};
+
+const MAX_LOG_RANGE: u64 = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum GuardedContract {
+    IntentPool,
+    Settlement,
+}
+
+impl GuardedContract {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "intent_pool" => Ok(Self::IntentPool),
+            "settlement" => Ok(Self::Settlement),
+            other => Err(anyhow!("Unknown contract '{}'", other)),
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::IntentPool => "IntentPool",
+            Self::Settlement => "Settlement",
+        }
+    }
+}
+
+/// Pause and upgrade events the guard reacts to. The bridge contracts emit
+/// `ContractPaused(bool)`; OpenZeppelin `Paused`/`Unpaused` and ERC-1967
+/// proxy events are covered for redeployments behind a proxy.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GuardEvent {
+    Paused(bool),
+    Upgraded(Address),
+    AdminChanged(Address),
+}
+
+fn topic(signature: &str) -> H256 {
+    H256::from(keccak256(signature.as_bytes()))
+}
+
+fn guard_topics() -> Vec<H256> {
+    [
+        "ContractPaused(bool)",
+        "Paused(address)",
+        "Unpaused(address)",
+        "Upgraded(address)",
+        "AdminChanged(address,address)",
+    ]
+    .iter()
+    .map(|signature| topic(signature))
+    .collect()
+}
+
+/// ERC-1967 implementation slot, `keccak256("eip1967.proxy.implementation") - 1`.
+fn implementation_slot() -> H256 {
+    let slot = U256::from_big_endian(&keccak256(b"eip1967.proxy.implementation")) - 1;
+    let mut bytes = [0u8; 32];
+    slot.to_big_endian(&mut bytes);
+    H256::from(bytes)
+}
+
+fn word_address(word: &[u8]) -> Option<Address> {
+    (word.len() == 32).then(|| Address::from_slice(&word[12..]))
+}
+
+impl GuardEvent {
+    fn decode(log: &Log) -> Option<Self> {
+        let signature = *log.topics.first()?;
+
+        if signature == topic("ContractPaused(bool)") {
+            Some(Self::Paused(
+                log.data.last().copied().unwrap_or_default() != 0,
+            ))
+        } else if signature == topic("Paused(address)") {
+            Some(Self::Paused(true))
+        } else if signature == topic("Unpaused(address)") {
+            Some(Self::Paused(false))
+        } else if signature == topic("Upgraded(address)") {
+            Some(Self::Upgraded(word_address(log.topics.get(1)?.as_bytes())?))
+        } else if signature == topic("AdminChanged(address,address)") {
+            Some(Self::AdminChanged(word_address(log.data.get(32..64)?)?))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractStatus {
+    pub chain: &'static str,
+    pub contract: GuardedContract,
+    pub address: String,
+    pub paused: bool,
+    /// ERC-1967 implementation behind the address, `None` when not a proxy.
+    pub implementation: Option<String>,
+    /// Implementation seen in an `Upgraded` event, held until acknowledged.
+    pub pending_upgrade: Option<String>,
+    pub last_event: Option<String>,
+    pub last_event_tx: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ContractStatus {
+    pub fn held(&self) -> bool {
+        self.paused || self.pending_upgrade.is_some()
+    }
+
+    /// Applies an event and returns the alert it raises, if any.
+    fn apply(&mut self, event: GuardEvent, hold_on_upgrade: bool) -> Option<String> {
+        let name = format!("{} {}", self.chain, self.contract.label());
+
+        let alert = match event {
+            GuardEvent::Paused(paused) if paused == self.paused => None,
+            GuardEvent::Paused(true) => Some(format!("{} paused, holding its transactions", name)),
+            GuardEvent::Paused(false) => Some(format!("{} unpaused, resuming", name)),
+            GuardEvent::Upgraded(implementation) => {
+                let implementation = format!("{:?}", implementation);
+                if self.implementation.as_deref() == Some(implementation.as_str()) {
+                    None
+                } else if hold_on_upgrade {
+                    self.pending_upgrade = Some(implementation.clone());
+                    Some(format!(
+                        "{} upgraded to {}, holding until acknowledged",
+                        name, implementation
+                    ))
+                } else {
+                    self.implementation = Some(implementation.clone());
+                    Some(format!("{} upgraded to {}", name, implementation))
+                }
+            }
+            GuardEvent::AdminChanged(admin) => {
+                Some(format!("{} proxy admin changed to {:?}", name, admin))
+            }
+        };
+
+        if let GuardEvent::Paused(paused) = event {
+            self.paused = paused;
+        }
+        if alert.is_some() {
+            self.last_event = Some(format!("{:?}", event));
+            self.updated_at = Utc::now();
+        }
+
+        alert
+    }
+}
+
+/// Watches the configured contracts for pauses and upgrades and holds relayer
+/// transactions against a held contract instead of letting them revert.
+#[derive(Clone)]
+pub struct ContractGuard {
+    pub enabled: bool,
+    pub poll_interval: Duration,
+    pub hold_on_upgrade: bool,
+    statuses: Arc<RwLock<HashMap<(&'static str, GuardedContract), ContractStatus>>>,
+}
+
+/// One chain's contracts as the guard sees them. Both relayers use the same
+/// signer middleware type, so one shape covers both chains.
+struct ChainTargets {
+    chain: &'static str,
+    client: Arc<ChainClient>,
+    intent_pool: IntentPoolContract<ChainClient>,
+    settlement: SettlementContract<ChainClient>,
+    next_block: Option<u64>,
+}
+
+impl ContractGuard {
+    /// Reads `CONTRACT_GUARD_ENABLED` (default true),
+    /// `CONTRACT_GUARD_INTERVAL_SECS` (default 15) and
+    /// `CONTRACT_GUARD_HOLD_ON_UPGRADE` (default true).
+    pub fn from_env() -> Self {
+        Self {
+            enabled: std::env::var("CONTRACT_GUARD_ENABLED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            poll_interval: Duration::from_secs(
+                std::env::var("CONTRACT_GUARD_INTERVAL_SECS")
+                    .unwrap_or_else(|_| "15".to_string())
+                    .parse()
+                    .unwrap_or(15),
+            ),
+            hold_on_upgrade: std::env::var("CONTRACT_GUARD_HOLD_ON_UPGRADE")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+            statuses: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Errors when `contract` on `chain` is paused or awaiting an upgrade
+    /// acknowledgement. Called by the relayers before every send.
+    pub async fn ensure_active(
+        &self,
+        chain: &'static str,
+        contract: GuardedContract,
+    ) -> Result<()> {
+        match self.statuses.read().await.get(&(chain, contract)) {
+            Some(status) if status.paused => Err(anyhow!(
+                "{} {} is paused; holding transaction",
+                chain,
+                contract.label()
+            )),
+            Some(status) if status.pending_upgrade.is_some() => Err(anyhow!(
+                "{} {} was upgraded; holding transaction until acknowledged",
+                chain,
+                contract.label()
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    pub async fn statuses(&self) -> Vec<ContractStatus> {
+        let mut statuses: Vec<_> = self.statuses.read().await.values().cloned().collect();
+        statuses.sort_by_key(|status| (status.chain, status.contract.label()));
+        statuses
+    }
+
+    pub async fn alerts(&self) -> Vec<String> {
+        self.statuses()
+            .await
+            .into_iter()
+            .filter(ContractStatus::held)
+            .map(|status| match status.pending_upgrade {
+                Some(implementation) => format!(
+                    "{} {} upgraded to {}, awaiting acknowledgement",
+                    status.chain,
+                    status.contract.label(),
+                    implementation
+                ),
+                None => format!("{} {} paused", status.chain, status.contract.label()),
+            })
+            .collect()
+    }
+
+    /// Accept a pending upgrade and release the hold it caused.
+    pub async fn acknowledge_upgrade(
+        &self,
+        chain: &str,
+        contract: GuardedContract,
+    ) -> Result<ContractStatus> {
+        let mut statuses = self.statuses.write().await;
+        let status = statuses
+            .iter_mut()
+            .find(|((c, k), _)| *c == chain && *k == contract)
+            .map(|(_, status)| status)
+            .ok_or_else(|| anyhow!("{} {} is not monitored", chain, contract.label()))?;
+
+        let implementation = status
+            .pending_upgrade
+            .take()
+            .ok_or_else(|| anyhow!("No pending upgrade for {} {}", chain, contract.label()))?;
+
+        info!(
+            "✅ Upgrade of {} {} to {} acknowledged, resuming",
+            chain,
+            contract.label(),
+            implementation
+        );
+        status.implementation = Some(implementation);
+        status.updated_at = Utc::now();
+
+        Ok(status.clone())
+    }
+
+    pub async fn run(self, chains: ChainRegistry) {
+        if !self.enabled {
+            warn!("⚠️  Contract guard disabled, pauses and upgrades are not watched");
+            return;
+        }
+
+        info!("🛡️  Contract guard watching for pauses and upgrades");
+
+        let mut chains: Vec<ChainTargets> = chains
+            .iter()
+            .map(|relayer| ChainTargets {
+                chain: relayer.key,
+                client: relayer.client.clone(),
+                intent_pool: relayer.intent_pool.clone(),
+                settlement: relayer.settlement.clone(),
+                next_block: None,
+            })
+            .collect();
+
+        loop {
+            for targets in chains.iter_mut() {
+                if let Err(e) = self.poll_chain(targets).await {
+                    warn!("⚠️  [{}] Contract guard poll failed: {}", targets.chain, e);
+                }
+            }
+            sleep(self.poll_interval).await;
+        }
+    }
+
+    async fn poll_chain(&self, targets: &mut ChainTargets) -> Result<()> {
+        let latest = targets
+            .client
+            .get_block_number()
+            .await
+            .map_err(|e| anyhow!("Failed to get block number: {}", e))?
+            .as_u64();
+
+        let Some(from_block) = targets.next_block else {
+            self.load_initial_state(targets).await?;
+            targets.next_block = Some(latest + 1);
+            return Ok(());
+        };
+
+        if from_block > latest {
+            return Ok(());
+        }
+
+        let to_block = latest.min(from_block + MAX_LOG_RANGE - 1);
+        let filter = Filter::new()
+            .address(vec![
+                targets.intent_pool.address(),
+                targets.settlement.address(),
+            ])
+            .topic0(guard_topics())
+            .from_block(from_block)
+            .to_block(to_block);
+
+        let logs = targets
+            .client
+            .get_logs(&filter)
+            .await
+            .map_err(|e| anyhow!("Failed to fetch guard logs: {}", e))?;
+
+        for log in logs {
+            let contract = if log.address == targets.intent_pool.address() {
+                GuardedContract::IntentPool
+            } else {
+                GuardedContract::Settlement
+            };
+
+            if let Some(event) = GuardEvent::decode(&log) {
+                self.record(targets.chain, contract, event, log.transaction_hash)
+                    .await;
+            }
+        }
+
+        targets.next_block = Some(to_block + 1);
+        Ok(())
+    }
+
+    async fn load_initial_state(&self, targets: &ChainTargets) -> Result<()> {
+        for contract in [GuardedContract::IntentPool, GuardedContract::Settlement] {
+            let (address, paused) = match contract {
+                GuardedContract::IntentPool => (
+                    targets.intent_pool.address(),
+                    targets.intent_pool.paused().call().await,
+                ),
+                GuardedContract::Settlement => (
+                    targets.settlement.address(),
+                    targets.settlement.paused().call().await,
+                ),
+            };
+            let paused = paused
+                .map_err(|e| anyhow!("Failed to read {} paused(): {}", contract.label(), e))?;
+
+            let implementation = targets
+                .client
+                .get_storage_at(address, implementation_slot(), None)
+                .await
+                .map_err(|e| anyhow!("Failed to read implementation slot: {}", e))?;
+            let implementation = (!implementation.is_zero()).then(|| {
+                format!(
+                    "{:?}",
+                    Address::from_slice(&implementation.as_bytes()[12..])
+                )
+            });
+
+            if paused {
+                error!(
+                    "🚨 [{}] {} is paused at startup, holding its transactions",
+                    targets.chain,
+                    contract.label()
+                );
+            }
+
+            self.statuses.write().await.insert(
+                (targets.chain, contract),
+                ContractStatus {
+                    chain: targets.chain,
+                    contract,
+                    address: format!("{:?}", address),
+                    paused,
+                    implementation,
+                    pending_upgrade: None,
+                    last_event: None,
+                    last_event_tx: None,
+                    updated_at: Utc::now(),
+                },
+            );
+        }
+
+        Ok(())
+    }
+
+    async fn record(
+        &self,
+        chain: &'static str,
+        contract: GuardedContract,
+        event: GuardEvent,
+        tx_hash: Option<H256>,
+    ) {
+        let mut statuses = self.statuses.write().await;
+        let Some(status) = statuses.get_mut(&(chain, contract)) else {
+            return;
+        };
+
+        if let Some(alert) = status.apply(event, self.hold_on_upgrade) {
+            status.last_event_tx = tx_hash.map(|hash| format!("{:?}", hash));
+            if status.held() {
+                error!("🚨 [{}] {} (tx {:?})", chain, alert, tx_hash);
+            } else {
+                warn!("⚠️  [{}] {} (tx {:?})", chain, alert, tx_hash);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::Bytes;
+
+    fn log(topics: Vec<H256>, data: Vec<u8>) -> Log {
+        Log {
+            topics,
+            data: Bytes::from(data),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_pause_and_upgrade_events_hold_and_release() {
+        let mut status = ContractStatus {
+            chain: "mantle",
+            contract: GuardedContract::Settlement,
+            address: String::new(),
+            paused: false,
+            implementation: None,
+            pending_upgrade: None,
+            last_event: None,
+            last_event_tx: None,
+            updated_at: Utc::now(),
+        };
+
+        let mut paused = vec![0u8; 32];
+        paused[31] = 1;
+        let event = GuardEvent::decode(&log(vec![topic("ContractPaused(bool)")], paused)).unwrap();
+        assert!(status.apply(event, true).is_some());
+        assert!(status.held());
+
+        // A repeated pause is not a new alert
+        assert!(status.apply(event, true).is_none());
+
+        let event =
+            GuardEvent::decode(&log(vec![topic("Unpaused(address)"), H256::zero()], vec![]))
+                .unwrap();
+        assert!(status.apply(event, true).is_some());
+        assert!(!status.held());
+
+        let implementation = H256::from(Address::repeat_byte(7));
+        let event = GuardEvent::decode(&log(
+            vec![topic("Upgraded(address)"), implementation],
+            vec![],
+        ))
+        .unwrap();
+        assert_eq!(event, GuardEvent::Upgraded(Address::repeat_byte(7)));
+        assert!(status.apply(event, true).is_some());
+        assert!(status.held());
+
+        assert!(GuardEvent::decode(&log(vec![topic("Transfer(address)")], vec![])).is_none());
+    }
+}
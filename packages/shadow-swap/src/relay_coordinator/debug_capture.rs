@@ -0,0 +1,343 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::Duration,
+};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use tokio::time::interval;
+use tracing::{error, info};
+
+use crate::{
+    database::{
+        database::Database,
+        model::{DbDebugCapture, DbDebugCaptureEntry},
+    },
+    relay_coordinator::model::BridgeCoordinator,
+};
+
+/// How long operators may capture an intent for, and how long the
+/// recording is kept afterwards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DebugCapturePolicy {
+    /// Used when the operator does not ask for a duration.
+    pub default_ttl: Duration,
+    pub max_ttl: Duration,
+    /// Entries stay readable for this long after their capture ends.
+    pub retention: chrono::Duration,
+    /// How often captures started on other instances are picked up and
+    /// old ones purged.
+    pub refresh_interval: Duration,
+}
+
+impl Default for DebugCapturePolicy {
+    fn default() -> Self {
+        Self {
+            default_ttl: Duration::from_secs(3600),
+            max_ttl: Duration::from_secs(24 * 3600),
+            retention: chrono::Duration::days(7),
+            refresh_interval: Duration::from_secs(30),
+        }
+    }
+}
+
+impl DebugCapturePolicy {
+    /// Reads `DEBUG_CAPTURE_TTL_SECS`, `DEBUG_CAPTURE_MAX_TTL_SECS`,
+    /// `DEBUG_CAPTURE_RETENTION_DAYS` and `DEBUG_CAPTURE_REFRESH_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(secs) = var("DEBUG_CAPTURE_TTL_SECS") {
+            policy.default_ttl = Duration::from_secs(
+                secs.trim()
+                    .parse()
+                    .context("Invalid DEBUG_CAPTURE_TTL_SECS")?,
+            );
+        }
+        if let Some(secs) = var("DEBUG_CAPTURE_MAX_TTL_SECS") {
+            policy.max_ttl = Duration::from_secs(
+                secs.trim()
+                    .parse()
+                    .context("Invalid DEBUG_CAPTURE_MAX_TTL_SECS")?,
+            );
+        }
+        if let Some(days) = var("DEBUG_CAPTURE_RETENTION_DAYS") {
+            policy.retention = chrono::Duration::days(
+                days.trim()
+                    .parse()
+                    .context("Invalid DEBUG_CAPTURE_RETENTION_DAYS")?,
+            );
+        }
+        if let Some(secs) = var("DEBUG_CAPTURE_REFRESH_SECS") {
+            policy.refresh_interval = Duration::from_secs(
+                secs.trim()
+                    .parse::<u64>()
+                    .context("Invalid DEBUG_CAPTURE_REFRESH_SECS")?
+                    .max(1),
+            );
+        }
+
+        if policy.default_ttl.is_zero() || policy.default_ttl > policy.max_ttl {
+            bail!("DEBUG_CAPTURE_TTL_SECS must be between 1 and DEBUG_CAPTURE_MAX_TTL_SECS");
+        }
+        if policy.retention < chrono::Duration::zero() {
+            bail!("DEBUG_CAPTURE_RETENTION_DAYS must not be negative");
+        }
+
+        Ok(policy)
+    }
+}
+
+/// What an entry holds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CaptureKind {
+    /// A payload the relayer received, from a client or the indexer.
+    Request,
+    /// What the relayer answered to it.
+    Response,
+    /// A chain read or transaction and its result.
+    Rpc,
+    /// A branch a worker took, and why.
+    Decision,
+}
+
+impl CaptureKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Request => "request",
+            Self::Response => "response",
+            Self::Rpc => "rpc",
+            Self::Decision => "decision",
+        }
+    }
+}
+
+/// Why a capture was not started.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CaptureRejection {
+    MissingOperator,
+    MissingReason,
+    TtlTooLong(Duration),
+}
+
+impl std::fmt::Display for CaptureRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::MissingOperator => write!(f, "operator is required"),
+            Self::MissingReason => write!(f, "reason is required"),
+            Self::TtlTooLong(max) => write!(f, "ttl_secs must be at most {}", max.as_secs()),
+        }
+    }
+}
+
+/// Verbose recording for the few intents an operator is debugging.
+///
+/// Components call [`DebugCapture::record`] freely: for intents that are not
+/// marked it is a map lookup and nothing is written.
+#[derive(Clone)]
+pub struct DebugCapture {
+    database: Arc<Database>,
+    pub policy: DebugCapturePolicy,
+    /// Expiry of each live capture, keyed by lowercase intent id.
+    active: Arc<RwLock<HashMap<String, DateTime<Utc>>>>,
+}
+
+impl DebugCapture {
+    pub fn new(database: Arc<Database>, policy: DebugCapturePolicy) -> Self {
+        Self {
+            database,
+            policy,
+            active: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    pub fn is_active(&self, intent_id: &str) -> bool {
+        let active = self.active.read().unwrap_or_else(|e| e.into_inner());
+        active
+            .get(&intent_id.to_lowercase())
+            .is_some_and(|expires_at| *expires_at > Utc::now())
+    }
+
+    /// Store `payload` if `intent_id` is being captured. Failures are
+    /// logged, never passed on: capture must not change what it observes.
+    pub fn record(&self, intent_id: &str, component: &str, kind: CaptureKind, payload: Value) {
+        if !self.is_active(intent_id) {
+            return;
+        }
+        if let Err(e) =
+            self.database
+                .record_debug_capture_entry(intent_id, component, kind.as_str(), &payload)
+        {
+            error!("Failed to record debug capture of {}: {:#}", intent_id, e);
+        }
+    }
+
+    /// Capture `intent_id` for `ttl`, or the default duration. The intent
+    /// need not exist yet, so an operator can mark it before the user retries.
+    pub fn start(
+        &self,
+        intent_id: &str,
+        operator: &str,
+        reason: &str,
+        ttl: Option<Duration>,
+    ) -> Result<std::result::Result<DbDebugCapture, CaptureRejection>> {
+        if operator.trim().is_empty() {
+            return Ok(Err(CaptureRejection::MissingOperator));
+        }
+        if reason.trim().is_empty() {
+            return Ok(Err(CaptureRejection::MissingReason));
+        }
+        let ttl = ttl.unwrap_or(self.policy.default_ttl);
+        if ttl > self.policy.max_ttl {
+            return Ok(Err(CaptureRejection::TtlTooLong(self.policy.max_ttl)));
+        }
+
+        let expires_at = Utc::now() + chrono::Duration::from_std(ttl)?;
+        let capture = self.database.start_debug_capture(
+            intent_id,
+            operator.trim(),
+            reason.trim(),
+            expires_at,
+        )?;
+        self.active
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(capture.intent_id.clone(), capture.expires_at);
+
+        info!(
+            "🔬 Debug capture of {} started by {} until {}",
+            capture.intent_id, capture.operator, capture.expires_at
+        );
+        Ok(Ok(capture))
+    }
+
+    /// Returns false when the intent was not being captured.
+    pub fn stop(&self, intent_id: &str) -> Result<bool> {
+        let stopped = self.database.stop_debug_capture(intent_id)?;
+        self.active
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&intent_id.to_lowercase());
+        Ok(stopped)
+    }
+
+    pub fn list_active(&self) -> Result<Vec<DbDebugCapture>> {
+        self.database.get_active_debug_captures()
+    }
+
+    /// The capture and everything recorded under it, oldest first.
+    pub fn recording(
+        &self,
+        intent_id: &str,
+    ) -> Result<Option<(DbDebugCapture, Vec<DbDebugCaptureEntry>)>> {
+        let Some(capture) = self.database.get_debug_capture(intent_id)? else {
+            return Ok(None);
+        };
+        let entries = self.database.get_debug_capture_entries(intent_id)?;
+        Ok(Some((capture, entries)))
+    }
+
+    /// Reload live captures, including those started on other instances,
+    /// and purge the ones past retention. Returns how many were purged.
+    pub fn refresh(&self) -> Result<usize> {
+        let active = self
+            .database
+            .get_active_debug_captures()?
+            .into_iter()
+            .map(|capture| (capture.intent_id, capture.expires_at))
+            .collect();
+        *self.active.write().unwrap_or_else(|e| e.into_inner()) = active;
+
+        self.database
+            .purge_debug_captures(Utc::now() - self.policy.retention)
+    }
+}
+
+impl BridgeCoordinator {
+    pub async fn run_debug_capture_refresher(&self) {
+        info!(
+            "🔬 Debug capture refresher started (every {}s)",
+            self.debug_capture.policy.refresh_interval.as_secs()
+        );
+
+        let mut ticker = interval(self.debug_capture.policy.refresh_interval);
+        loop {
+            ticker.tick().await;
+            match self.debug_capture.refresh() {
+                Ok(0) => {}
+                Ok(purged) => info!("🔬 Purged {} expired debug captures", purged),
+                Err(e) => error!("❌ Debug capture refresh failed: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::test_database;
+    use serde_json::json;
+    use serial_test::serial;
+
+    const INTENT: &str = "0x00000000000000000000000000000000000000000000000000000000000d0b06";
+
+    #[test]
+    #[serial(db)]
+    fn test_records_only_while_capturing() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+        let capture = DebugCapture::new(Arc::new(database), DebugCapturePolicy::default());
+
+        capture.record(INTENT, "api", CaptureKind::Request, json!({"n": 0}));
+        assert!(capture.recording(INTENT)?.is_none());
+
+        assert_eq!(
+            capture.start(INTENT, "ops", "", None)?.err(),
+            Some(CaptureRejection::MissingReason)
+        );
+        assert!(
+            capture
+                .start(
+                    INTENT,
+                    "ops",
+                    "ticket 42",
+                    Some(Duration::from_secs(10 * 24 * 3600))
+                )?
+                .is_err()
+        );
+        capture
+            .start(
+                &INTENT.to_uppercase().replace("0X", "0x"),
+                "ops",
+                "ticket 42",
+                None,
+            )?
+            .expect("started");
+        assert!(capture.is_active(INTENT));
+
+        capture.record(INTENT, "api", CaptureKind::Request, json!({"n": 1}));
+        capture.record(INTENT, "settlement", CaptureKind::Decision, json!({"n": 2}));
+        assert!(capture.stop(INTENT)?);
+        capture.record(INTENT, "settlement", CaptureKind::Rpc, json!({"n": 3}));
+
+        let (recorded, entries) = capture.recording(INTENT)?.expect("recording");
+        assert_eq!(recorded.operator, "ops");
+        let kinds: Vec<_> = entries
+            .iter()
+            .map(|entry| (entry.component.as_str(), entry.kind.as_str()))
+            .collect();
+        assert_eq!(kinds, [("api", "request"), ("settlement", "decision")]);
+
+        // Past retention on the next refresh
+        capture
+            .database
+            .purge_debug_captures(Utc::now() + chrono::Duration::seconds(1))?;
+        assert!(capture.recording(INTENT)?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,361 @@
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result, anyhow};
+use ethers::{
+    providers::Middleware,
+    types::{Address, H256, Log, U256},
+    utils::keccak256,
+};
+use mantle_core::token::NATIVE_TOKEN;
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::{
+    database::model::{DbDepositDiscrepancy, NewDepositDiscrepancy},
+    models::model::Intent,
+    relay_coordinator::model::BridgeCoordinator,
+};
+
+/// Whether `IntentCreated` deposits are checked against what the pool received.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DepositCheckPolicy {
+    pub enabled: bool,
+}
+
+impl Default for DepositCheckPolicy {
+    fn default() -> Self {
+        Self { enabled: true }
+    }
+}
+
+impl DepositCheckPolicy {
+    /// Reads `DEPOSIT_CHECK_ENABLED`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(enabled) = std::env::var("DEPOSIT_CHECK_ENABLED")
+            && !enabled.trim().is_empty()
+        {
+            policy.enabled = enabled
+                .trim()
+                .parse()
+                .context("Invalid DEPOSIT_CHECK_ENABLED")?;
+        }
+
+        Ok(policy)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositKind {
+    /// The pool received less than the intent's amount, e.g. a fee-on-transfer
+    /// token. The intent is never registered and is refunded at its deadline.
+    Underpaid,
+    /// The pool received more; the surplus stays in the pool and is reported.
+    Overpaid,
+}
+
+impl DepositKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Underpaid => "underpaid",
+            Self::Overpaid => "overpaid",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "underpaid" => Ok(Self::Underpaid),
+            "overpaid" => Ok(Self::Overpaid),
+            other => Err(anyhow!(
+                "Unknown deposit kind '{}', expected underpaid or overpaid",
+                other
+            )),
+        }
+    }
+}
+
+/// How a deposit differs from the intent's amount, `None` when it matches.
+pub fn classify_deposit(expected: U256, deposited: U256) -> Option<(DepositKind, U256)> {
+    if deposited < expected {
+        Some((DepositKind::Underpaid, expected - deposited))
+    } else if deposited > expected {
+        Some((DepositKind::Overpaid, deposited - expected))
+    } else {
+        None
+    }
+}
+
+/// Sum of the ERC20 `Transfer`s of `token` into `pool` among `logs`.
+pub fn deposited_to(logs: &[Log], token: Address, pool: Address) -> U256 {
+    let transfer = H256::from(keccak256("Transfer(address,address,uint256)"));
+
+    logs.iter()
+        .filter(|log| log.address == token)
+        .filter(|log| log.topics.len() == 3 && log.topics[0] == transfer)
+        .filter(|log| Address::from(log.topics[2]) == pool)
+        .filter(|log| log.data.len() == 32)
+        .fold(U256::zero(), |total, log| {
+            total.saturating_add(U256::from_big_endian(&log.data))
+        })
+}
+
+/// Overpaid surplus held by the pools, per source chain and token.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SurplusTotal {
+    pub source_chain: String,
+    pub token: String,
+    pub intents: u64,
+    /// Base units of `token`.
+    pub surplus: String,
+}
+
+pub fn surplus_totals(rows: &[DbDepositDiscrepancy]) -> Vec<SurplusTotal> {
+    let mut totals: BTreeMap<(String, String), (u64, U256)> = BTreeMap::new();
+
+    for row in rows.iter().filter(|row| row.kind == "overpaid") {
+        let difference = U256::from_dec_str(&row.difference).unwrap_or_default();
+        let entry = totals
+            .entry((row.source_chain.clone(), row.token.to_lowercase()))
+            .or_default();
+        entry.0 += 1;
+        entry.1 = entry.1.saturating_add(difference);
+    }
+
+    totals
+        .into_iter()
+        .map(|((source_chain, token), (intents, surplus))| SurplusTotal {
+            source_chain,
+            token,
+            intents,
+            surplus: surplus.to_string(),
+        })
+        .collect()
+}
+
+impl BridgeCoordinator {
+    /// Compare what the pool received in the intent's creation transaction
+    /// with the intent's amount, and record any difference. Native deposits
+    /// are skipped: the pool requires `msg.value` to match exactly.
+    pub async fn check_deposit(
+        &self,
+        intent: &Intent,
+        tx_hash: &str,
+    ) -> Result<Option<DepositKind>> {
+        if intent.source_token.eq_ignore_ascii_case(NATIVE_TOKEN) {
+            return Ok(None);
+        }
+
+        let relayer = self.chains.resolve(&intent.source_chain)?;
+        let token: Address = intent
+            .source_token
+            .parse()
+            .context("Invalid source token")?;
+        let expected = U256::from_dec_str(&intent.amount).context("Invalid intent amount")?;
+        let hash: H256 = tx_hash.parse().context("Invalid transaction hash")?;
+
+        let receipt = relayer
+            .client
+            .get_transaction_receipt(hash)
+            .await
+            .context("Failed to fetch receipt")?
+            .ok_or_else(|| anyhow!("No receipt for {}", tx_hash))?;
+
+        let deposited = deposited_to(&receipt.logs, token, relayer.intent_pool.address());
+        let Some((kind, difference)) = classify_deposit(expected, deposited) else {
+            return Ok(None);
+        };
+
+        let (expected, deposited, difference) = (
+            expected.to_string(),
+            deposited.to_string(),
+            difference.to_string(),
+        );
+        let recorded = self
+            .database
+            .record_deposit_discrepancy(&NewDepositDiscrepancy {
+                intent_id: &intent.id,
+                source_chain: &intent.source_chain,
+                token: &intent.source_token,
+                kind: kind.as_str(),
+                expected_amount: &expected,
+                deposited_amount: &deposited,
+                difference: &difference,
+                transaction_hash: tx_hash,
+            })?;
+
+        if recorded {
+            match kind {
+                DepositKind::Underpaid => warn!(
+                    "⚠️ Intent {} underpaid by {} ({} of {} received), refunding at deadline",
+                    intent.id, difference, deposited, expected
+                ),
+                DepositKind::Overpaid => info!(
+                    "💰 Intent {} overpaid by {} ({} of {} received)",
+                    intent.id, difference, deposited, expected
+                ),
+            }
+        }
+
+        Ok(Some(kind))
+    }
+
+    pub fn list_deposit_discrepancies(
+        &self,
+        kind: Option<DepositKind>,
+    ) -> Result<Vec<DbDepositDiscrepancy>> {
+        self.database
+            .list_deposit_discrepancies(kind.as_ref().map(DepositKind::as_str))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        database::test_support::{IntentFactory, random_hash, test_database},
+        models::model::IntentStatus,
+    };
+    use chrono::Utc;
+    use ethers::types::Bytes;
+    use serial_test::serial;
+
+    fn transfer(token: Address, to: Address, value: u64) -> Log {
+        let mut data = [0u8; 32];
+        U256::from(value).to_big_endian(&mut data);
+        Log {
+            address: token,
+            topics: vec![
+                H256::from(keccak256("Transfer(address,address,uint256)")),
+                H256::from(Address::repeat_byte(0xaa)),
+                H256::from(to),
+            ],
+            data: Bytes::from(data.to_vec()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_classify_deposit() {
+        let (expected, more, less) = (U256::from(100), U256::from(103), U256::from(98));
+
+        assert_eq!(classify_deposit(expected, expected), None);
+        assert_eq!(
+            classify_deposit(expected, less),
+            Some((DepositKind::Underpaid, U256::from(2)))
+        );
+        assert_eq!(
+            classify_deposit(expected, more),
+            Some((DepositKind::Overpaid, U256::from(3)))
+        );
+    }
+
+    #[test]
+    fn test_deposited_to_counts_only_token_transfers_into_the_pool() {
+        let (token, other_token) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let (pool, elsewhere) = (Address::repeat_byte(3), Address::repeat_byte(4));
+
+        let logs = vec![
+            transfer(token, pool, 98),
+            // The token's fee leg goes to its treasury, not the pool
+            transfer(token, elsewhere, 2),
+            transfer(other_token, pool, 500),
+        ];
+
+        assert_eq!(deposited_to(&logs, token, pool), U256::from(98));
+        assert_eq!(deposited_to(&[], token, pool), U256::zero());
+    }
+
+    #[test]
+    fn test_surplus_totals() {
+        let row = |id: &str, token: &str, kind: &str, difference: &str| DbDepositDiscrepancy {
+            intent_id: id.to_string(),
+            source_chain: "ethereum".to_string(),
+            token: token.to_string(),
+            kind: kind.to_string(),
+            expected_amount: "100".to_string(),
+            deposited_amount: "0".to_string(),
+            difference: difference.to_string(),
+            transaction_hash: "0x".to_string(),
+            detected_at: Utc::now(),
+        };
+        let rows = [
+            row("a", "0xAB", "overpaid", "5"),
+            row("b", "0xab", "overpaid", "7"),
+            row("c", "0xab", "underpaid", "40"),
+        ];
+
+        assert_eq!(
+            surplus_totals(&rows),
+            vec![SurplusTotal {
+                source_chain: "ethereum".to_string(),
+                token: "0xab".to_string(),
+                intents: 2,
+                surplus: "12".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_underpaid_deposit_holds_intent_back() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let underpaid = IntentFactory::committed().build();
+        database.create_intent(&underpaid)?;
+        let overpaid = IntentFactory::committed().build();
+        database.create_intent(&overpaid)?;
+
+        let tx_hash = random_hash();
+        fn row<'a>(
+            intent: &'a Intent,
+            kind: DepositKind,
+            tx_hash: &'a str,
+        ) -> NewDepositDiscrepancy<'a> {
+            NewDepositDiscrepancy {
+                intent_id: &intent.id,
+                source_chain: "ethereum",
+                token: "0x0000000000000000000000000000000000000001",
+                kind: kind.as_str(),
+                expected_amount: "100",
+                deposited_amount: "98",
+                difference: "2",
+                transaction_hash: tx_hash,
+            }
+        }
+        assert!(database.record_deposit_discrepancy(&row(
+            &underpaid,
+            DepositKind::Underpaid,
+            &tx_hash
+        ))?);
+        // Replayed events record once
+        assert!(!database.record_deposit_discrepancy(&row(
+            &underpaid,
+            DepositKind::Underpaid,
+            &tx_hash
+        ))?);
+        assert!(database.record_deposit_discrepancy(&row(
+            &overpaid,
+            DepositKind::Overpaid,
+            &tx_hash
+        ))?);
+
+        let status = |id: &str| -> Result<IntentStatus> {
+            Ok(database.get_intent_by_id(id)?.unwrap().status)
+        };
+        assert_eq!(status(&underpaid.id)?, IntentStatus::Underpaid);
+        assert_eq!(status(&overpaid.id)?, IntentStatus::Committed);
+
+        let listed = database.list_deposit_discrepancies(Some("underpaid"))?;
+        assert!(
+            listed
+                .iter()
+                .any(|row| row.intent_id == underpaid.id.as_str())
+        );
+        assert!(listed.iter().all(|row| row.kind == "underpaid"));
+        Ok(())
+    }
+}
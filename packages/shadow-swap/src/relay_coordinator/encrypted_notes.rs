@@ -0,0 +1,189 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{Context, Result, anyhow, bail};
+use serde_json::json;
+use tokio::time::interval;
+use tracing::{error, info};
+
+use crate::{
+    relay_coordinator::model::BridgeCoordinator, webhooks::webhook_dispatcher::WebhookDispatcher,
+};
+
+/// Notes handed to the webhook per tick.
+const DELIVERY_BATCH: i64 = 100;
+
+/// Statuses in which the recipient side has been paid, so its note is due.
+pub const NOTE_DUE_STATUSES: [&str; 3] = ["filled", "solver_paid", "user_claimed"];
+
+/// Size cap on notes users encrypt for their recipient.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EncryptedNotePolicy {
+    /// Ciphertext bytes, after hex decoding.
+    pub max_bytes: usize,
+    pub delivery_interval: Duration,
+}
+
+impl Default for EncryptedNotePolicy {
+    fn default() -> Self {
+        Self {
+            max_bytes: 1024,
+            delivery_interval: Duration::from_secs(15),
+        }
+    }
+}
+
+impl EncryptedNotePolicy {
+    /// Reads `ENCRYPTED_NOTE_MAX_BYTES` and
+    /// `ENCRYPTED_NOTE_DELIVERY_INTERVAL_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(max) = var("ENCRYPTED_NOTE_MAX_BYTES") {
+            policy.max_bytes = max
+                .trim()
+                .parse()
+                .context("Invalid ENCRYPTED_NOTE_MAX_BYTES")?;
+        }
+        if let Some(secs) = var("ENCRYPTED_NOTE_DELIVERY_INTERVAL_SECS") {
+            policy.delivery_interval = Duration::from_secs(
+                secs.trim()
+                    .parse::<u64>()
+                    .context("Invalid ENCRYPTED_NOTE_DELIVERY_INTERVAL_SECS")?
+                    .max(1),
+            );
+        }
+
+        if policy.max_bytes == 0 {
+            bail!("ENCRYPTED_NOTE_MAX_BYTES must be positive");
+        }
+
+        Ok(policy)
+    }
+
+    /// Checks the note is a `0x` hex blob within the cap. The content is
+    /// never inspected, and errors never quote it.
+    pub fn validate(&self, note: &str) -> Result<()> {
+        let hex_part = note
+            .strip_prefix("0x")
+            .ok_or_else(|| anyhow!("encrypted_note must be 0x-prefixed hex"))?;
+        if hex_part.is_empty() {
+            bail!("encrypted_note is empty");
+        }
+        if hex_part.len() > self.max_bytes * 2 {
+            bail!("encrypted_note exceeds {} bytes", self.max_bytes);
+        }
+        if hex_part.len() % 2 != 0 || !hex_part.bytes().all(|b| b.is_ascii_hexdigit()) {
+            bail!("encrypted_note must be 0x-prefixed hex");
+        }
+        Ok(())
+    }
+}
+
+impl BridgeCoordinator {
+    /// Send each note with an `intent_note` webhook once its intent is
+    /// filled. A note is retried until every endpoint accepts it.
+    pub async fn run_note_delivery(
+        &self,
+        policy: EncryptedNotePolicy,
+        webhooks: Arc<WebhookDispatcher>,
+    ) {
+        info!(
+            "✉️ Encrypted note delivery started (every {}s)",
+            policy.delivery_interval.as_secs()
+        );
+        let mut ticker = interval(policy.delivery_interval);
+        loop {
+            ticker.tick().await;
+            let due = match self
+                .database
+                .get_undelivered_encrypted_notes(&NOTE_DUE_STATUSES, DELIVERY_BATCH)
+            {
+                Ok(due) => due,
+                Err(e) => {
+                    error!("❌ Failed to load encrypted notes: {}", e);
+                    continue;
+                }
+            };
+
+            for (note, intent) in due {
+                let payload = json!({
+                    "intent_id": note.intent_id,
+                    "status": intent.status.as_str(),
+                    "source_chain": intent.source_chain,
+                    "dest_chain": intent.dest_chain,
+                    "encrypted_note": note.ciphertext,
+                });
+                if webhooks.dispatch("intent_note", &payload).await > 0 {
+                    continue;
+                }
+                if let Err(e) = self.database.mark_encrypted_note_delivered(&note.intent_id) {
+                    error!(
+                        "❌ Failed to mark note of {} delivered: {}",
+                        note.intent_id, e
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{IntentFactory, test_database};
+    use serial_test::serial;
+
+    #[test]
+    fn test_notes_must_be_hex_within_the_cap() {
+        let policy = EncryptedNotePolicy {
+            max_bytes: 4,
+            ..Default::default()
+        };
+
+        assert!(policy.validate("0xdeadbeef").is_ok());
+        assert!(policy.validate("0xdeadbeef00").is_err());
+        assert!(policy.validate("deadbeef").is_err());
+        assert!(policy.validate("0x").is_err());
+        assert!(policy.validate("0xabc").is_err());
+        let error = policy.validate("0xinvoice-42").unwrap_err().to_string();
+        assert!(!error.contains("invoice"));
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_notes_are_due_once_filled_and_frozen_after_delivery() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+        let committed = IntentFactory::committed().insert(&database)?;
+        let filled = IntentFactory::filled().insert(&database)?;
+        let committed_id = committed.id.to_string();
+        let filled_id = filled.id.to_string();
+
+        assert!(database.store_encrypted_note(&committed_id, "0x01")?);
+        assert!(database.store_encrypted_note(&filled_id, "0x02")?);
+        // Resubmitting replaces an undelivered note
+        assert!(database.store_encrypted_note(&filled_id.to_uppercase(), "0x03")?);
+
+        let due = database.get_undelivered_encrypted_notes(&NOTE_DUE_STATUSES, 1000)?;
+        let due: Vec<&str> = due
+            .iter()
+            .filter(|(note, _)| note.intent_id == committed_id || note.intent_id == filled_id)
+            .map(|(note, _)| note.ciphertext.as_str())
+            .collect();
+        assert_eq!(due, vec!["0x03"]);
+
+        database.mark_encrypted_note_delivered(&filled_id)?;
+        assert!(!database.store_encrypted_note(&filled_id, "0x04")?);
+        let notes = database.get_encrypted_notes(&[filled_id.clone(), committed_id.clone()])?;
+        assert_eq!(notes.len(), 2);
+        assert!(
+            notes
+                .iter()
+                .any(|note| note.intent_id == filled_id && note.ciphertext == "0x03")
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,430 @@
+use std::{collections::HashSet, time::Duration};
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, Utc};
+use ethers::{
+    providers::Middleware,
+    types::{Address, H256, Log, Signature},
+    utils::{hash_message, keccak256},
+};
+
+use crate::{
+    api::model::{FillReportRequest, IndexerEventRequest},
+    database::model::DbFillReport,
+    intent_workers::log_listener::event_request,
+    models::model::BridgeEventType,
+    relay_coordinator::{claim_auth::parse_claim_auth, model::BridgeCoordinator},
+};
+
+/// Domain tag that keeps a fill report signature from being replayed as any
+/// other message the solver signs.
+const FILL_REPORT_DOMAIN: &[u8] = b"shadow-swap:fill-report";
+
+/// Which third-party solvers may report fills and how long a reported
+/// transaction may take to show up on chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FillReportPolicy {
+    pub enabled: bool,
+    pub solvers: HashSet<Address>,
+    pub check_interval: Duration,
+    /// Reports whose transaction has no receipt after this long are rejected.
+    pub max_age: Duration,
+}
+
+impl Default for FillReportPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            solvers: HashSet::new(),
+            check_interval: Duration::from_secs(2),
+            max_age: Duration::from_secs(600),
+        }
+    }
+}
+
+impl FillReportPolicy {
+    /// Reads `FILL_REPORTS_ENABLED`, `FILL_REPORT_SOLVERS` (comma separated
+    /// addresses), `FILL_REPORT_CHECK_INTERVAL_SECS` and
+    /// `FILL_REPORT_MAX_AGE_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(enabled) = std::env::var("FILL_REPORTS_ENABLED") {
+            policy.enabled = enabled
+                .trim()
+                .parse()
+                .context("Invalid FILL_REPORTS_ENABLED")?;
+        }
+        if let Ok(solvers) = std::env::var("FILL_REPORT_SOLVERS") {
+            policy.solvers = solvers
+                .split(',')
+                .map(str::trim)
+                .filter(|solver| !solver.is_empty())
+                .map(|solver| {
+                    solver
+                        .parse()
+                        .with_context(|| format!("Invalid FILL_REPORT_SOLVERS entry {}", solver))
+                })
+                .collect::<Result<_>>()?;
+        }
+        if let Ok(secs) = std::env::var("FILL_REPORT_CHECK_INTERVAL_SECS") {
+            let secs: u64 = secs
+                .trim()
+                .parse()
+                .context("Invalid FILL_REPORT_CHECK_INTERVAL_SECS")?;
+            policy.check_interval = Duration::from_secs(secs.max(1));
+        }
+        if let Ok(secs) = std::env::var("FILL_REPORT_MAX_AGE_SECS") {
+            let secs: u64 = secs
+                .trim()
+                .parse()
+                .context("Invalid FILL_REPORT_MAX_AGE_SECS")?;
+            policy.max_age = Duration::from_secs(secs.max(1));
+        }
+
+        if policy.enabled && policy.solvers.is_empty() {
+            bail!("FILL_REPORTS_ENABLED needs at least one address in FILL_REPORT_SOLVERS");
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Why a report was refused, mapped to an HTTP status by the route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FillReportRejection {
+    Invalid(String),
+    /// Signed by an address missing from `FILL_REPORT_SOLVERS`, or not by
+    /// the solver the report names.
+    Unauthorized(String),
+    NotFound,
+}
+
+impl std::fmt::Display for FillReportRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Invalid(reason) | Self::Unauthorized(reason) => write!(f, "{}", reason),
+            Self::NotFound => write!(f, "Intent not found"),
+        }
+    }
+}
+
+/// What the chain says about a pending report.
+#[derive(Debug)]
+pub enum FillReportCheck {
+    /// The fill, as the log listener would have delivered it.
+    Verified(IndexerEventRequest),
+    Rejected(String),
+    /// No receipt yet.
+    Pending,
+}
+
+fn parse_hash(value: &str, what: &str) -> Result<H256> {
+    if !value.starts_with("0x") || value.len() != 66 {
+        bail!("{} must be 0x plus 32 bytes of hex", what);
+    }
+    value
+        .parse()
+        .map_err(|e| anyhow!("{} is not valid hex: {}", what, e))
+}
+
+/// `keccak256("shadow-swap:fill-report" ‖ intentId ‖ uint256(chainId) ‖
+/// txHash)`, signed by the solver as an Ethereum personal message.
+pub fn fill_report_message(intent_id: &str, chain_id: u32, tx_hash: &str) -> Result<[u8; 32]> {
+    let mut packed = FILL_REPORT_DOMAIN.to_vec();
+    packed.extend_from_slice(parse_hash(intent_id, "Intent id")?.as_bytes());
+    packed.extend_from_slice(&[0u8; 28]);
+    packed.extend_from_slice(&chain_id.to_be_bytes());
+    packed.extend_from_slice(parse_hash(tx_hash, "Transaction hash")?.as_bytes());
+    Ok(keccak256(packed))
+}
+
+/// The address that signed this report.
+pub fn recover_fill_reporter(
+    intent_id: &str,
+    chain_id: u32,
+    tx_hash: &str,
+    signature: &str,
+) -> Result<Address> {
+    let bytes = parse_claim_auth(signature)?;
+    let signature =
+        Signature::try_from(bytes.as_slice()).map_err(|e| anyhow!("Invalid signature: {}", e))?;
+
+    signature
+        .recover(hash_message(fill_report_message(
+            intent_id, chain_id, tx_hash,
+        )?))
+        .map_err(|e| anyhow!("Failed to recover signer: {}", e))
+}
+
+/// Check a receipt's logs for the reported fill: an `IntentFilled` for
+/// `intent_id` from `settlement`, by `solver`.
+pub fn verify_fill_logs(
+    chain: &str,
+    pool: Address,
+    settlement: Address,
+    logs: Vec<Log>,
+    intent_id: &str,
+    solver: &str,
+) -> FillReportCheck {
+    let fill = logs
+        .into_iter()
+        .filter(|log| log.address == settlement)
+        .filter_map(|log| event_request(chain, pool, log))
+        .find(|request| {
+            request.event_type == BridgeEventType::IntentFilled.as_str()
+                && request.event_data["intentId"]
+                    .as_str()
+                    .is_some_and(|id| id.eq_ignore_ascii_case(intent_id))
+        });
+
+    let Some(fill) = fill else {
+        return FillReportCheck::Rejected(
+            "No IntentFilled for this intent in the transaction".to_string(),
+        );
+    };
+    let filler = fill.event_data["solver"].as_str().unwrap_or_default();
+    if !filler.eq_ignore_ascii_case(solver) {
+        return FillReportCheck::Rejected(format!("Filled by {}, reported by {}", filler, solver));
+    }
+
+    FillReportCheck::Verified(fill)
+}
+
+impl BridgeCoordinator {
+    /// Check the report's signature and intent, then queue it for
+    /// verification on chain. A repeated report returns the stored one.
+    pub fn submit_fill_report(
+        &self,
+        policy: &FillReportPolicy,
+        report: &FillReportRequest,
+    ) -> Result<std::result::Result<DbFillReport, FillReportRejection>> {
+        let invalid = |e: anyhow::Error| FillReportRejection::Invalid(e.to_string());
+
+        let relayer = match self.chains.resolve(&report.chain) {
+            Ok(relayer) => relayer,
+            Err(e) => return Ok(Err(invalid(e))),
+        };
+        let solver: Address = match report.solver.parse() {
+            Ok(solver) => solver,
+            Err(_) => return Ok(Err(invalid(anyhow!("Invalid solver address")))),
+        };
+        let signer = match recover_fill_reporter(
+            &report.intent_id,
+            relayer.chain_id,
+            &report.transaction_hash,
+            &report.signature,
+        ) {
+            Ok(signer) => signer,
+            Err(e) => return Ok(Err(invalid(e))),
+        };
+        if signer != solver {
+            return Ok(Err(FillReportRejection::Unauthorized(format!(
+                "Signed by {:?}, not by solver {:?}",
+                signer, solver
+            ))));
+        }
+        if !policy.solvers.contains(&solver) {
+            return Ok(Err(FillReportRejection::Unauthorized(format!(
+                "Solver {:?} is not registered for fill reports",
+                solver
+            ))));
+        }
+
+        let Some(intent) = self.database.get_intent_by_id(&report.intent_id)? else {
+            return Ok(Err(FillReportRejection::NotFound));
+        };
+        if self.chains.resolve(&intent.dest_chain)?.chain_id != relayer.chain_id {
+            return Ok(Err(FillReportRejection::Invalid(format!(
+                "Intent is filled on {}, not {}",
+                intent.dest_chain, relayer.key
+            ))));
+        }
+
+        let stored = self.database.record_fill_report(
+            &report.intent_id,
+            relayer.key,
+            &report.transaction_hash,
+            &format!("{:?}", solver),
+        )?;
+        Ok(Ok(stored))
+    }
+
+    /// Read the reported transaction's receipt and check it for the fill.
+    pub async fn check_fill_report(
+        &self,
+        policy: &FillReportPolicy,
+        report: &DbFillReport,
+        now: DateTime<Utc>,
+    ) -> Result<FillReportCheck> {
+        let relayer = self.chains.resolve(&report.chain)?;
+        let hash: H256 = report
+            .transaction_hash
+            .parse()
+            .context("Invalid transaction hash")?;
+
+        let Some(receipt) = relayer
+            .client
+            .get_transaction_receipt(hash)
+            .await
+            .context("Failed to fetch receipt")?
+        else {
+            let waited = (now - report.reported_at).to_std().unwrap_or_default();
+            if waited > policy.max_age {
+                return Ok(FillReportCheck::Rejected(format!(
+                    "No receipt after {}s",
+                    waited.as_secs()
+                )));
+            }
+            return Ok(FillReportCheck::Pending);
+        };
+        if receipt.status.map(|status| status.as_u64()) != Some(1) {
+            return Ok(FillReportCheck::Rejected(
+                "Transaction reverted".to_string(),
+            ));
+        }
+
+        Ok(verify_fill_logs(
+            relayer.key,
+            relayer.intent_pool.address(),
+            relayer.settlement.address(),
+            receipt.logs,
+            &report.intent_id,
+            &report.solver_address,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::test_database;
+    use ethers::{
+        abi::{Token, encode},
+        contract::EthEvent,
+        signers::{LocalWallet, Signer},
+        types::{U64, U256},
+    };
+    use mantle_core::abi::settlement_contract::IntentFilledFilter;
+    use serial_test::serial;
+
+    const INTENT_ID: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+    const TX_HASH: &str = "0x0202020202020202020202020202020202020202020202020202020202020202";
+
+    #[tokio::test]
+    async fn test_fill_report_signature() {
+        let wallet: LocalWallet =
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let signature = format!(
+            "0x{}",
+            wallet
+                .sign_message(fill_report_message(INTENT_ID, 5003, TX_HASH).unwrap())
+                .await
+                .unwrap()
+        );
+
+        assert_eq!(
+            recover_fill_reporter(INTENT_ID, 5003, TX_HASH, &signature).unwrap(),
+            wallet.address()
+        );
+        // Bound to the chain, intent and transaction
+        assert_ne!(
+            recover_fill_reporter(INTENT_ID, 11155111, TX_HASH, &signature).unwrap(),
+            wallet.address()
+        );
+        assert_ne!(
+            recover_fill_reporter(TX_HASH, 5003, INTENT_ID, &signature).unwrap(),
+            wallet.address()
+        );
+        assert!(recover_fill_reporter("0x1234", 5003, TX_HASH, &signature).is_err());
+    }
+
+    #[test]
+    fn test_verify_fill_logs() {
+        let (pool, settlement) = (Address::repeat_byte(1), Address::repeat_byte(2));
+        let solver = Address::repeat_byte(9);
+        let filled = |address: Address, intent_id: H256| Log {
+            address,
+            topics: vec![
+                IntentFilledFilter::signature(),
+                intent_id,
+                H256::from(solver),
+                H256::from(Address::repeat_byte(4)),
+            ],
+            data: encode(&[Token::Uint(999.into())]).into(),
+            transaction_hash: Some(TX_HASH.parse().unwrap()),
+            block_number: Some(U64::from(42)),
+            log_index: Some(U256::from(3)),
+            ..Default::default()
+        };
+        let intent_id: H256 = INTENT_ID.parse().unwrap();
+        let solver_hex = format!("{:?}", solver);
+
+        let FillReportCheck::Verified(request) = verify_fill_logs(
+            "mantle",
+            pool,
+            settlement,
+            vec![filled(settlement, intent_id)],
+            INTENT_ID,
+            &solver_hex,
+        ) else {
+            panic!("fill not verified");
+        };
+        assert_eq!(request.event_type, "intent_filled");
+        assert_eq!(request.transaction_hash, TX_HASH);
+        assert_eq!((request.block_number, request.log_index), (42, 3));
+
+        // Another solver's fill, another intent's fill, or a lookalike event
+        // from a contract other than settlement
+        for (logs, solver) in [
+            (
+                vec![filled(settlement, intent_id)],
+                format!("{:?}", Address::repeat_byte(8)),
+            ),
+            (
+                vec![filled(settlement, H256::repeat_byte(5))],
+                solver_hex.clone(),
+            ),
+            (
+                vec![filled(Address::repeat_byte(3), intent_id)],
+                solver_hex.clone(),
+            ),
+        ] {
+            assert!(matches!(
+                verify_fill_logs("mantle", pool, settlement, logs, INTENT_ID, &solver),
+                FillReportCheck::Rejected(_)
+            ));
+        }
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_fill_reports_are_recorded_once() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+        let solver = format!("{:?}", Address::repeat_byte(9));
+
+        let report = database.record_fill_report(INTENT_ID, "mantle", TX_HASH, &solver)?;
+        assert_eq!(report.status, "pending");
+        assert_eq!(report.solver_address, solver.to_lowercase());
+
+        // Hashes are matched case-insensitively
+        let again =
+            database.record_fill_report(INTENT_ID, "mantle", &TX_HASH.to_uppercase(), &solver)?;
+        assert_eq!(again.id, report.id);
+        assert_eq!(database.get_pending_fill_reports(10)?.len(), 1);
+
+        database.resolve_fill_report(report.id, "verified", None)?;
+        // Resolved reports stay as they are
+        database.resolve_fill_report(report.id, "rejected", Some("late"))?;
+        let resolved = database.get_fill_report(report.id)?.unwrap();
+        assert_eq!(resolved.status, "verified");
+        assert!(resolved.resolved_at.is_some());
+        assert!(database.get_pending_fill_reports(10)?.is_empty());
+
+        Ok(())
+    }
+}
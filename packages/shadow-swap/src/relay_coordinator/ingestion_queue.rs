@@ -0,0 +1,416 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        Arc,
+        atomic::{AtomicI64, Ordering},
+    },
+    time::Duration,
+};
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use serde::Serialize;
+use tokio::sync::Notify;
+use tracing::{error, warn};
+
+use crate::{
+    api::model::IndexerEventRequest,
+    database::{
+        database::Database,
+        model::{IngestionBacklog, NewIngestionEvent},
+    },
+};
+
+/// Wait after the first failed delivery, doubled after each one after that.
+const BASE_RETRY: Duration = Duration::from_secs(2);
+const MAX_RETRY: Duration = Duration::from_secs(300);
+
+/// How received chain events are stored and handed to the pipelines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IngestionPolicy {
+    /// Events claimed per round.
+    pub batch_size: i64,
+    /// Sleep between rounds when the queue is drained; a new event cuts it
+    /// short.
+    pub poll_interval: Duration,
+    /// How long a claimed event stays hidden from other consumers. An event
+    /// whose consumer died is delivered again after this.
+    pub lease: Duration,
+    /// Failed deliveries before an event is dead-lettered.
+    pub max_attempts: i32,
+    /// Pending events above which the indexer webhook answers 503, so the
+    /// indexer backs off and retries.
+    pub high_watermark: i64,
+    /// Applied events are deleted after this.
+    pub retention: chrono::Duration,
+}
+
+impl Default for IngestionPolicy {
+    fn default() -> Self {
+        Self {
+            batch_size: 100,
+            poll_interval: Duration::from_millis(500),
+            lease: Duration::from_secs(60),
+            max_attempts: 10,
+            high_watermark: 10_000,
+            retention: chrono::Duration::hours(24),
+        }
+    }
+}
+
+impl IngestionPolicy {
+    /// Reads `INGESTION_BATCH_SIZE`, `INGESTION_POLL_INTERVAL_MS`,
+    /// `INGESTION_LEASE_SECS`, `INGESTION_MAX_ATTEMPTS`,
+    /// `INGESTION_HIGH_WATERMARK` and `INGESTION_RETENTION_HOURS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(size) = var("INGESTION_BATCH_SIZE") {
+            policy.batch_size = size
+                .trim()
+                .parse()
+                .context("Invalid INGESTION_BATCH_SIZE")?;
+        }
+        if let Some(ms) = var("INGESTION_POLL_INTERVAL_MS") {
+            policy.poll_interval = Duration::from_millis(
+                ms.trim()
+                    .parse::<u64>()
+                    .context("Invalid INGESTION_POLL_INTERVAL_MS")?
+                    .max(10),
+            );
+        }
+        if let Some(secs) = var("INGESTION_LEASE_SECS") {
+            policy.lease = Duration::from_secs(
+                secs.trim()
+                    .parse()
+                    .context("Invalid INGESTION_LEASE_SECS")?,
+            );
+        }
+        if let Some(attempts) = var("INGESTION_MAX_ATTEMPTS") {
+            policy.max_attempts = attempts
+                .trim()
+                .parse()
+                .context("Invalid INGESTION_MAX_ATTEMPTS")?;
+        }
+        if let Some(pending) = var("INGESTION_HIGH_WATERMARK") {
+            policy.high_watermark = pending
+                .trim()
+                .parse()
+                .context("Invalid INGESTION_HIGH_WATERMARK")?;
+        }
+        if let Some(hours) = var("INGESTION_RETENTION_HOURS") {
+            policy.retention = chrono::Duration::hours(
+                hours
+                    .trim()
+                    .parse()
+                    .context("Invalid INGESTION_RETENTION_HOURS")?,
+            );
+        }
+
+        if policy.batch_size < 1 {
+            bail!("INGESTION_BATCH_SIZE must be at least 1");
+        }
+        if policy.lease.is_zero() {
+            bail!("INGESTION_LEASE_SECS must be at least 1");
+        }
+        if policy.max_attempts < 1 {
+            bail!("INGESTION_MAX_ATTEMPTS must be at least 1");
+        }
+        if policy.high_watermark < 1 {
+            bail!("INGESTION_HIGH_WATERMARK must be at least 1");
+        }
+        if policy.retention < chrono::Duration::zero() {
+            bail!("INGESTION_RETENTION_HOURS must not be negative");
+        }
+
+        Ok(policy)
+    }
+
+    /// Wait before the next delivery once `attempts` have failed, or `None`
+    /// when the event has used them all up.
+    pub fn retry_after(&self, attempts: i32) -> Option<Duration> {
+        if attempts >= self.max_attempts {
+            return None;
+        }
+        let doublings = attempts.saturating_sub(1).clamp(0, 31) as u32;
+        Some(
+            BASE_RETRY
+                .saturating_mul(2u32.saturating_pow(doublings))
+                .min(MAX_RETRY),
+        )
+    }
+}
+
+/// Where an event came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IngestionSource {
+    Indexer,
+    LogListener,
+}
+
+impl IngestionSource {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Indexer => "indexer",
+            Self::LogListener => "log_listener",
+        }
+    }
+}
+
+/// Consumer lag of one source.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SourceLag {
+    pub pending: i64,
+    pub dead: i64,
+    /// Age of the oldest event not yet applied.
+    pub lag_secs: u64,
+}
+
+/// Consumer lag across sources, for `/metrics` and the webhook's
+/// backpressure.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct IngestionLag {
+    pub pending: i64,
+    pub dead: i64,
+    pub lag_secs: u64,
+    pub by_source: BTreeMap<String, SourceLag>,
+}
+
+impl IngestionLag {
+    pub fn from_backlog(backlog: &[IngestionBacklog], now: chrono::DateTime<Utc>) -> Self {
+        let mut lag = Self::default();
+        for row in backlog {
+            let source = lag.by_source.entry(row.source.clone()).or_default();
+            match row.status.as_str() {
+                "pending" => {
+                    let age = row
+                        .oldest_received_at
+                        .map(|received_at| (now - received_at).num_seconds().max(0) as u64)
+                        .unwrap_or_default();
+                    source.pending += row.count;
+                    source.lag_secs = source.lag_secs.max(age);
+                    lag.pending += row.count;
+                    lag.lag_secs = lag.lag_secs.max(age);
+                }
+                "dead" => {
+                    source.dead += row.count;
+                    lag.dead += row.count;
+                }
+                _ => {}
+            }
+        }
+        lag
+    }
+}
+
+/// Inbox between receiving chain events and applying them. Events are
+/// stored before they are acknowledged and delivered at least once; the
+/// handlers they reach are idempotent.
+#[derive(Clone)]
+pub struct IngestionQueue {
+    database: Arc<Database>,
+    pub policy: IngestionPolicy,
+    /// Cuts the consumer's sleep short when an event is stored.
+    pub wakeup: Arc<Notify>,
+    /// Pending events as of the last lag check.
+    pending: Arc<AtomicI64>,
+}
+
+impl IngestionQueue {
+    pub fn new(database: Arc<Database>, policy: IngestionPolicy) -> Self {
+        Self {
+            database,
+            policy,
+            wakeup: Arc::new(Notify::new()),
+            pending: Arc::new(AtomicI64::new(0)),
+        }
+    }
+
+    /// Store `request` for the consumer. Returns false when the event was
+    /// already stored, from either source.
+    pub fn enqueue(&self, source: IngestionSource, request: &IndexerEventRequest) -> Result<bool> {
+        let stored = self.database.enqueue_ingestion_event(&NewIngestionEvent {
+            source: source.as_str(),
+            event_type: &request.event_type,
+            chain: &request.chain,
+            transaction_hash: request.transaction_hash.to_lowercase(),
+            block_number: i64::try_from(request.block_number).context("Block number too large")?,
+            log_index: i32::try_from(request.log_index).context("Log index too large")?,
+            payload: serde_json::to_value(request)?,
+        })?;
+        if stored {
+            self.pending.fetch_add(1, Ordering::Relaxed);
+            self.wakeup.notify_one();
+        }
+        Ok(stored)
+    }
+
+    /// Whether the webhook should push back on the indexer.
+    pub fn is_saturated(&self) -> bool {
+        self.pending.load(Ordering::Relaxed) >= self.policy.high_watermark
+    }
+
+    /// The next due events, oldest first, leased to this consumer. Events
+    /// whose payload no longer parses are dead-lettered on the spot.
+    pub fn claim(&self) -> Result<Vec<(i64, IndexerEventRequest)>> {
+        let lease_until = Utc::now() + chrono::Duration::from_std(self.policy.lease)?;
+        let events = self
+            .database
+            .claim_ingestion_events(self.policy.batch_size, lease_until)?;
+
+        let mut claimed = Vec::with_capacity(events.len());
+        for event in events {
+            match serde_json::from_value(event.payload) {
+                Ok(request) => claimed.push((event.id, request)),
+                Err(e) => {
+                    self.failed(event.id, &format!("Unreadable payload: {}", e), true)?;
+                }
+            }
+        }
+        Ok(claimed)
+    }
+
+    pub fn delivered(&self, id: i64) -> Result<()> {
+        self.database.complete_ingestion_event(id)
+    }
+
+    /// Record a failed delivery and schedule the next one. `permanent`
+    /// failures, such as a malformed event, are dead-lettered at once.
+    pub fn failed(&self, id: i64, error: &str, permanent: bool) -> Result<()> {
+        let now = Utc::now();
+        let event = self.database.fail_ingestion_event(id, error, |attempts| {
+            if permanent {
+                return None;
+            }
+            self.policy
+                .retry_after(attempts)
+                .map(|wait| now + chrono::Duration::from_std(wait).unwrap_or_default())
+        })?;
+
+        match event {
+            Some(event) if event.status == "dead" => error!(
+                "☠️ {} from {} on {} dead-lettered after {} attempts: {}",
+                event.event_type, event.transaction_hash, event.chain, event.attempts, error
+            ),
+            Some(event) => warn!(
+                "🔁 {} from {} on {} not applied (attempt {}/{}), retrying at {}: {}",
+                event.event_type,
+                event.transaction_hash,
+                event.chain,
+                event.attempts,
+                self.policy.max_attempts,
+                event.next_attempt_at,
+                error
+            ),
+            None => {}
+        }
+        Ok(())
+    }
+
+    /// Current consumer lag. Also refreshes the count the webhook's
+    /// backpressure reads.
+    pub fn lag(&self) -> Result<IngestionLag> {
+        let backlog = self.database.get_ingestion_backlog()?;
+        let lag = IngestionLag::from_backlog(&backlog, Utc::now());
+        self.pending.store(lag.pending, Ordering::Relaxed);
+        Ok(lag)
+    }
+
+    /// Delete applied events past retention. Returns how many went.
+    pub fn purge(&self) -> Result<usize> {
+        self.database
+            .purge_ingestion_events(Utc::now() - self.policy.retention)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::test_database;
+    use serde_json::json;
+    use serial_test::serial;
+
+    fn event(transaction_hash: &str, log_index: u32) -> IndexerEventRequest {
+        IndexerEventRequest {
+            event_type: "intent_filled".to_string(),
+            chain: "mantle".to_string(),
+            transaction_hash: transaction_hash.to_string(),
+            block_number: 42,
+            event_data: json!({"intentId": "0x01"}),
+            log_index,
+        }
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_events_are_stored_once_and_redelivered_until_applied() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+        let queue = IngestionQueue::new(
+            Arc::new(database),
+            IngestionPolicy {
+                max_attempts: 2,
+                lease: Duration::from_secs(1),
+                ..IngestionPolicy::default()
+            },
+        );
+        // Rows of earlier runs are never deleted while dead
+        let tx = format!("0x{:064x}", Utc::now().timestamp_micros());
+
+        assert!(queue.enqueue(IngestionSource::LogListener, &event(&tx, 0))?);
+        assert!(!queue.enqueue(IngestionSource::Indexer, &event(&tx, 0))?);
+        assert!(queue.enqueue(IngestionSource::Indexer, &event(&tx, 1))?);
+
+        let claimed = queue.claim()?;
+        let ours: Vec<_> = claimed
+            .iter()
+            .filter(|(_, request)| request.transaction_hash == tx)
+            .map(|(id, request)| (*id, request.log_index))
+            .collect();
+        assert_eq!(ours.iter().map(|(_, i)| *i).collect::<Vec<_>>(), [0, 1]);
+        // Leased to this consumer
+        assert!(
+            queue
+                .claim()?
+                .iter()
+                .all(|(id, _)| !ours.iter().any(|(o, _)| o == id))
+        );
+
+        queue.delivered(ours[0].0)?;
+        queue.failed(ours[1].0, "500: database busy", false)?;
+        queue.failed(ours[1].0, "500: database busy", false)?;
+
+        let lag = queue.lag()?;
+        assert!(lag.by_source["indexer"].dead >= 1);
+        assert!(!queue.is_saturated());
+
+        // A dead event delivered again gets a fresh set of attempts
+        assert!(queue.enqueue(IngestionSource::Indexer, &event(&tx, 1))?);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_lag_sums_sources_and_takes_the_oldest_pending() {
+        let now = Utc::now();
+        let row = |source: &str, status: &str, count, age: i64| IngestionBacklog {
+            source: source.to_string(),
+            status: status.to_string(),
+            count,
+            oldest_received_at: Some(now - chrono::Duration::seconds(age)),
+        };
+
+        let lag = IngestionLag::from_backlog(
+            &[
+                row("indexer", "pending", 3, 40),
+                row("log_listener", "pending", 2, 90),
+                row("indexer", "dead", 1, 600),
+            ],
+            now,
+        );
+        assert_eq!((lag.pending, lag.dead, lag.lag_secs), (5, 1, 90));
+        assert_eq!(lag.by_source["indexer"].lag_secs, 40);
+    }
+}
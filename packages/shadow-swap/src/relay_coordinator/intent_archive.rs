@@ -0,0 +1,194 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use tokio::time::interval;
+use tracing::{error, info};
+
+use crate::relay_coordinator::model::BridgeCoordinator;
+
+/// Statuses no worker acts on again. `solver_paid` still waits for the
+/// user's claim, so it stays in the hot table.
+pub const ARCHIVABLE_STATUSES: [&str; 4] = ["user_claimed", "refunded", "failed", "expired"];
+
+#[derive(Debug, Clone)]
+pub struct IntentArchivePolicy {
+    pub enabled: bool,
+    pub interval: Duration,
+    /// Terminal intents untouched for this long are archived.
+    pub archive_after: chrono::Duration,
+    /// Rows moved per transaction.
+    pub batch_size: i64,
+}
+
+impl Default for IntentArchivePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(3600),
+            archive_after: chrono::Duration::days(30),
+            batch_size: 500,
+        }
+    }
+}
+
+impl IntentArchivePolicy {
+    /// Reads `INTENT_ARCHIVE_ENABLED`, `INTENT_ARCHIVE_INTERVAL_SECS`,
+    /// `INTENT_ARCHIVE_AFTER_DAYS` and `INTENT_ARCHIVE_BATCH_SIZE`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(enabled) = std::env::var("INTENT_ARCHIVE_ENABLED") {
+            policy.enabled = enabled.parse().context("Invalid INTENT_ARCHIVE_ENABLED")?;
+        }
+        if let Ok(secs) = std::env::var("INTENT_ARCHIVE_INTERVAL_SECS") {
+            policy.interval = Duration::from_secs(
+                secs.parse::<u64>()
+                    .context("Invalid INTENT_ARCHIVE_INTERVAL_SECS")?
+                    .max(1),
+            );
+        }
+        if let Ok(days) = std::env::var("INTENT_ARCHIVE_AFTER_DAYS") {
+            policy.archive_after = chrono::Duration::days(
+                days.parse::<i64>()
+                    .context("Invalid INTENT_ARCHIVE_AFTER_DAYS")?
+                    .max(1),
+            );
+        }
+        if let Ok(size) = std::env::var("INTENT_ARCHIVE_BATCH_SIZE") {
+            policy.batch_size = size
+                .parse::<i64>()
+                .context("Invalid INTENT_ARCHIVE_BATCH_SIZE")?
+                .max(1);
+        }
+
+        Ok(policy)
+    }
+}
+
+impl BridgeCoordinator {
+    pub async fn run_intent_archiver(&self, policy: IntentArchivePolicy) {
+        info!(
+            "🗄️ Intent archiver started (every {}s, after {} days)",
+            policy.interval.as_secs(),
+            policy.archive_after.num_days()
+        );
+
+        let mut ticker = interval(policy.interval);
+        loop {
+            ticker.tick().await;
+            match self.archive_terminal_intents(&policy) {
+                Ok(0) => {}
+                Ok(moved) => info!("🗄️ Archived {} terminal intents", moved),
+                Err(e) => error!("❌ Intent archival failed: {}", e),
+            }
+        }
+    }
+
+    /// Archive in batches until no old terminal intents are left.
+    pub fn archive_terminal_intents(&self, policy: &IntentArchivePolicy) -> Result<usize> {
+        let cutoff = Utc::now() - policy.archive_after;
+
+        let mut total = 0;
+        loop {
+            let moved =
+                self.database
+                    .archive_intents(&ARCHIVABLE_STATUSES, cutoff, policy.batch_size)?;
+            total += moved;
+            if (moved as i64) < policy.batch_size {
+                return Ok(total);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        database::test_support::{IntentFactory, test_database},
+        models::model::{IntentCreatedEvent, IntentStatus},
+    };
+    use chrono::Duration as ChronoDuration;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(db)]
+    fn test_archive_moves_old_terminal_intents() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let old_refund = IntentFactory::refunded()
+            .updated_ago(ChronoDuration::days(40))
+            .insert(&database)?;
+        let fresh_refund = IntentFactory::refunded().insert(&database)?;
+        let old_fill = IntentFactory::filled()
+            .updated_ago(ChronoDuration::days(40))
+            .insert(&database)?;
+
+        let mut commitments_before = database.get_all_commitments_for_chain("ethereum")?;
+        commitments_before.sort();
+        let moved = database.archive_intents(
+            &ARCHIVABLE_STATUSES,
+            Utc::now() - ChronoDuration::days(30),
+            1,
+        )?;
+        assert_eq!(moved, 1);
+        assert_eq!(
+            database.archive_intents(
+                &ARCHIVABLE_STATUSES,
+                Utc::now() - ChronoDuration::days(30),
+                1
+            )?,
+            0
+        );
+
+        // Gone from the hot queries, still found by id and still in the tree
+        let refunded = database.get_intents_by_status(IntentStatus::Refunded)?;
+        assert_eq!(
+            refunded.iter().map(|i| i.id.as_str()).collect::<Vec<_>>(),
+            [fresh_refund.id.as_str()]
+        );
+        assert_eq!(
+            database.get_intent_by_id(&old_refund.id)?.unwrap().status,
+            IntentStatus::Refunded
+        );
+        assert_eq!(
+            database.get_intents_by_status(IntentStatus::Filled)?[0].id,
+            old_fill.id
+        );
+        // The factory gives every intent the same position, so compare as sets
+        let mut commitments_after = database.get_all_commitments_for_chain("ethereum")?;
+        commitments_after.sort();
+        assert_eq!(commitments_after, commitments_before);
+        assert_eq!(
+            database.list_archived_intents(None, Some("ethereum"), 10)?[0].id,
+            old_refund.id
+        );
+
+        // Re-reading its creation event does not revive it
+        database.upsert_intent_from_event(
+            &IntentCreatedEvent {
+                intent_id: old_refund.id.to_string(),
+                commitment: old_refund.source_commitment.clone().unwrap().into(),
+                source_token: old_refund.source_token.clone(),
+                source_amount: old_refund.amount.to_string(),
+                dest_token: old_refund.dest_token.clone(),
+                dest_amount: old_refund.dest_amount.to_string(),
+                dest_chain: 5003,
+                deadline: Some(old_refund.deadline),
+                block_number: Some(1),
+                transaction_hash: None,
+                log_index: Some(0),
+            },
+            "ethereum",
+        )?;
+        assert!(
+            database
+                .get_intents_by_status(IntentStatus::Committed)?
+                .is_empty()
+        );
+        Ok(())
+    }
+}
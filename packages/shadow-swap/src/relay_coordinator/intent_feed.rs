@@ -0,0 +1,327 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tokio::{sync::broadcast, time::interval};
+use tracing::{info, warn};
+
+use crate::{models::model::Intent, relay_coordinator::model::BridgeCoordinator};
+
+/// Changed rows read per poll.
+const POLL_BATCH: i64 = 500;
+/// Updates buffered per subscriber before it is told it lagged.
+const CHANNEL_CAPACITY: usize = 1024;
+/// How long an intent's last status is remembered once it stops changing.
+const STATUS_MEMORY: chrono::Duration = chrono::Duration::hours(1);
+
+#[derive(Debug, Clone)]
+pub struct IntentFeedPolicy {
+    pub enabled: bool,
+    pub poll_interval: Duration,
+    pub max_connections: usize,
+    /// Intent ids plus addresses one socket may follow.
+    pub max_subscriptions: usize,
+}
+
+impl Default for IntentFeedPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval: Duration::from_millis(1000),
+            max_connections: 1000,
+            max_subscriptions: 50,
+        }
+    }
+}
+
+impl IntentFeedPolicy {
+    /// Reads `INTENT_FEED_ENABLED`, `INTENT_FEED_POLL_MS`,
+    /// `INTENT_FEED_MAX_CONNECTIONS` and `INTENT_FEED_MAX_SUBSCRIPTIONS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(enabled) = std::env::var("INTENT_FEED_ENABLED") {
+            policy.enabled = enabled.parse().context("Invalid INTENT_FEED_ENABLED")?;
+        }
+        if let Ok(ms) = std::env::var("INTENT_FEED_POLL_MS") {
+            policy.poll_interval = Duration::from_millis(
+                ms.parse::<u64>()
+                    .context("Invalid INTENT_FEED_POLL_MS")?
+                    .max(200),
+            );
+        }
+        if let Ok(max) = std::env::var("INTENT_FEED_MAX_CONNECTIONS") {
+            policy.max_connections = max.parse().context("Invalid INTENT_FEED_MAX_CONNECTIONS")?;
+        }
+        if let Ok(max) = std::env::var("INTENT_FEED_MAX_SUBSCRIPTIONS") {
+            policy.max_subscriptions = max
+                .parse::<usize>()
+                .context("Invalid INTENT_FEED_MAX_SUBSCRIPTIONS")?
+                .max(1);
+        }
+
+        Ok(policy)
+    }
+}
+
+/// A status transition pushed to `/ws/intents` subscribers.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IntentUpdate {
+    pub intent_id: String,
+    /// Used to match address subscriptions; not sent to clients.
+    #[serde(skip)]
+    pub user_address: String,
+    pub status: String,
+    /// `None` when the feed had not seen the intent before.
+    pub previous_status: Option<String>,
+    pub source_chain: String,
+    pub dest_chain: String,
+    pub updated_at: DateTime<Utc>,
+    /// The user's note for the recipient, still encrypted.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_note: Option<String>,
+}
+
+/// Fan-out of intent status transitions to connected sockets.
+#[derive(Clone)]
+pub struct IntentFeed {
+    pub policy: IntentFeedPolicy,
+    sender: broadcast::Sender<IntentUpdate>,
+    connections: Arc<AtomicUsize>,
+}
+
+/// Holds one of the feed's connection slots until dropped.
+pub struct FeedConnection {
+    connections: Arc<AtomicUsize>,
+}
+
+impl Drop for FeedConnection {
+    fn drop(&mut self) {
+        self.connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl IntentFeed {
+    pub fn new(policy: IntentFeedPolicy) -> Self {
+        let (sender, _) = broadcast::channel(CHANNEL_CAPACITY);
+        Self {
+            policy,
+            sender,
+            connections: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<IntentUpdate> {
+        self.sender.subscribe()
+    }
+
+    pub fn publish(&self, update: IntentUpdate) {
+        // No receivers just means nobody is connected
+        let _ = self.sender.send(update);
+    }
+
+    /// A connection slot, or `None` at `max_connections`.
+    pub fn connect(&self) -> Option<FeedConnection> {
+        self.connections
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |open| {
+                (open < self.policy.max_connections).then_some(open + 1)
+            })
+            .ok()
+            .map(|_| FeedConnection {
+                connections: self.connections.clone(),
+            })
+    }
+
+    pub fn connections(&self) -> usize {
+        self.connections.load(Ordering::Relaxed)
+    }
+}
+
+/// Turns changed intent rows into status transitions. Rows whose status did
+/// not change since the last poll (a txid or solver update) are dropped.
+#[derive(Debug)]
+pub struct StatusTracker {
+    cursor: DateTime<Utc>,
+    last_seen: HashMap<String, (String, DateTime<Utc>)>,
+}
+
+impl StatusTracker {
+    /// Only changes at or after `start` are reported.
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            cursor: start,
+            last_seen: HashMap::new(),
+        }
+    }
+
+    pub fn cursor(&self) -> DateTime<Utc> {
+        self.cursor
+    }
+
+    pub fn observe(&mut self, changed: Vec<Intent>) -> Vec<IntentUpdate> {
+        let mut updates = Vec::new();
+
+        for intent in changed {
+            let status = intent.status.as_str().to_string();
+            self.cursor = self.cursor.max(intent.updated_at);
+
+            let previous = self
+                .last_seen
+                .insert(intent.id.to_string(), (status.clone(), intent.updated_at))
+                .map(|(previous, _)| previous);
+            if previous.as_deref() == Some(status.as_str()) {
+                continue;
+            }
+
+            updates.push(IntentUpdate {
+                intent_id: intent.id.into(),
+                user_address: intent.user_address,
+                status,
+                previous_status: previous,
+                source_chain: intent.source_chain,
+                dest_chain: intent.dest_chain,
+                updated_at: intent.updated_at,
+                encrypted_note: None,
+            });
+        }
+
+        let forget_before = self.cursor - STATUS_MEMORY;
+        self.last_seen
+            .retain(|_, (_, updated_at)| *updated_at >= forget_before);
+
+        updates
+    }
+}
+
+impl BridgeCoordinator {
+    /// Poll `intents` for status changes and publish them. Every writer goes
+    /// through the table, so workers, API handlers, admin overrides and other
+    /// replicas are all covered.
+    pub async fn run_intent_feed(&self, feed: IntentFeed) {
+        info!(
+            "📡 Intent feed started (every {}ms, up to {} sockets)",
+            feed.policy.poll_interval.as_millis(),
+            feed.policy.max_connections
+        );
+
+        let mut tracker = StatusTracker::new(Utc::now());
+        let mut ticker = interval(feed.policy.poll_interval);
+        loop {
+            ticker.tick().await;
+            let changed = match self
+                .database
+                .get_intents_changed_since(tracker.cursor(), POLL_BATCH)
+            {
+                Ok(changed) => changed,
+                Err(e) => {
+                    warn!("⚠️ Intent feed poll failed: {}", e);
+                    continue;
+                }
+            };
+
+            let mut updates = tracker.observe(changed);
+            if updates.is_empty() {
+                continue;
+            }
+            let intent_ids: Vec<String> = updates
+                .iter()
+                .map(|update| update.intent_id.clone())
+                .collect();
+            match self.database.get_encrypted_notes(&intent_ids) {
+                Ok(notes) => {
+                    let notes: HashMap<String, String> = notes
+                        .into_iter()
+                        .map(|note| (note.intent_id, note.ciphertext))
+                        .collect();
+                    for update in &mut updates {
+                        update.encrypted_note =
+                            notes.get(&update.intent_id.to_lowercase()).cloned();
+                    }
+                }
+                Err(e) => warn!("⚠️ Intent feed could not load encrypted notes: {}", e),
+            }
+
+            for update in updates {
+                feed.publish(update);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{database::test_support::IntentFactory, models::model::IntentStatus};
+
+    #[test]
+    fn test_tracker_reports_status_transitions_only() {
+        let start = Utc::now();
+        let mut tracker = StatusTracker::new(start);
+        let row = |intent: &Intent, status, secs| Intent {
+            status,
+            updated_at: start + chrono::Duration::seconds(secs),
+            ..intent.clone()
+        };
+        let intent = IntentFactory::committed().build();
+
+        let updates = tracker.observe(vec![row(&intent, IntentStatus::Committed, 1)]);
+        assert_eq!(updates.len(), 1);
+        assert_eq!(updates[0].status, "committed");
+        assert_eq!(updates[0].previous_status, None);
+        assert_eq!(tracker.cursor(), start + chrono::Duration::seconds(1));
+
+        // Re-read at the cursor, then a txid-only update: nothing new
+        assert!(
+            tracker
+                .observe(vec![
+                    row(&intent, IntentStatus::Committed, 1),
+                    row(&intent, IntentStatus::Committed, 2),
+                ])
+                .is_empty()
+        );
+
+        let updates = tracker.observe(vec![
+            row(&intent, IntentStatus::Filled, 3),
+            row(&intent, IntentStatus::UserClaimed, 4),
+        ]);
+        let steps: Vec<(Option<&str>, &str)> = updates
+            .iter()
+            .map(|u| (u.previous_status.as_deref(), u.status.as_str()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                (Some("committed"), "filled"),
+                (Some("filled"), "user_claimed")
+            ]
+        );
+        assert_eq!(updates[1].user_address, intent.user_address);
+
+        // Quiet intents are forgotten once the cursor moves past the memory
+        let other = IntentFactory::created().build();
+        tracker.observe(vec![row(&other, IntentStatus::Created, 7200)]);
+        assert_eq!(tracker.last_seen.len(), 1);
+    }
+
+    #[test]
+    fn test_feed_caps_connections() {
+        let feed = IntentFeed::new(IntentFeedPolicy {
+            max_connections: 1,
+            ..Default::default()
+        });
+
+        let first = feed.connect().expect("slot free");
+        assert!(feed.connect().is_none());
+        drop(first);
+        assert_eq!(feed.connections(), 0);
+        assert!(feed.connect().is_some());
+    }
+}
@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use mantle_core::chain::Chain;
+use serde::Serialize;
+
+use crate::{
+    database::model::{DbBridgeEvent, DbChainTransaction, DbRootSync, DbTransactionReceipt},
+    models::model::Intent,
+    relay_coordinator::model::BridgeCoordinator,
+};
+
+/// One step of an intent's lifecycle, from the relayer's own records.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LifecycleStep {
+    pub at: DateTime<Utc>,
+    /// `created`, `committed`, `registered`, `filled`, `settled`, `claimed`,
+    /// `refunded`, `root_sync` or `other`.
+    pub stage: &'static str,
+    /// `relayer` for what it stored or sent, `chain` for events it observed.
+    pub source: &'static str,
+    /// Event type, transaction type or root sync type.
+    pub kind: String,
+    pub chain_id: Option<i32>,
+    pub transaction_hash: Option<String>,
+    /// Known for events, and for transactions once their receipt is stored.
+    pub block_number: Option<i64>,
+    /// Transaction status; events are final once recorded.
+    pub status: Option<String>,
+    /// Root pushed by a root sync.
+    pub root: Option<String>,
+}
+
+/// Where an intent is and every step recorded on the way there.
+#[derive(Debug, Clone, Serialize)]
+pub struct IntentLifecycle {
+    pub intent_id: String,
+    pub status: String,
+    pub source_chain: String,
+    pub dest_chain: String,
+    pub steps: Vec<LifecycleStep>,
+}
+
+/// The stage a chain event or relayer transaction moves the intent to.
+pub fn stage_of(kind: &str) -> &'static str {
+    match kind {
+        "intent_created" => "committed",
+        "intent_registered" | "register_intent" => "registered",
+        "intent_filled" | "recover_orphaned_fill" => "filled",
+        "intent_settled" | "settle_intent" => "settled",
+        "withdrawal_claimed" | "claim_withdrawal" => "claimed",
+        "intent_refunded" | "refund_intent" => "refunded",
+        "root_sync" | "sync_commitment_root" | "sync_fill_root" => "root_sync",
+        _ => "other",
+    }
+}
+
+/// Creation, chain events, relayer transactions and the root syncs that
+/// carried the intent across, oldest first. Steps at the same instant keep
+/// that order.
+pub fn build_lifecycle(
+    intent: &Intent,
+    events: Vec<DbBridgeEvent>,
+    transactions: Vec<DbChainTransaction>,
+    receipts: Vec<DbTransactionReceipt>,
+    root_syncs: Vec<DbRootSync>,
+) -> Vec<LifecycleStep> {
+    let blocks: HashMap<String, i64> = receipts
+        .into_iter()
+        .filter_map(|receipt| Some((receipt.tx_hash, receipt.block_number?)))
+        .collect();
+    let block_of = |tx_hash: &str| blocks.get(&tx_hash.to_lowercase()).copied();
+
+    let mut steps = vec![LifecycleStep {
+        at: intent.created_at,
+        stage: "created",
+        source: "relayer",
+        kind: "intent_created".to_string(),
+        chain_id: Chain::parse(&intent.source_chain)
+            .ok()
+            .map(|chain| chain.id() as i32),
+        transaction_hash: None,
+        block_number: None,
+        status: None,
+        root: None,
+    }];
+
+    steps.extend(events.into_iter().map(|event| LifecycleStep {
+        at: event.timestamp,
+        stage: stage_of(&event.event_type),
+        source: "chain",
+        kind: event.event_type,
+        chain_id: Some(event.chain_id),
+        transaction_hash: Some(event.transaction_hash),
+        block_number: Some(event.block_number),
+        status: None,
+        root: None,
+    }));
+
+    steps.extend(transactions.into_iter().map(|tx| LifecycleStep {
+        at: tx.created_at,
+        stage: stage_of(&tx.tx_type),
+        source: "relayer",
+        block_number: block_of(&tx.tx_hash),
+        kind: tx.tx_type,
+        chain_id: Some(tx.chain_id),
+        transaction_hash: Some(tx.tx_hash),
+        status: Some(tx.status),
+        root: None,
+    }));
+
+    steps.extend(root_syncs.into_iter().map(|sync| {
+        LifecycleStep {
+            at: sync.created_at,
+            stage: "root_sync",
+            source: "relayer",
+            // `<source>_<tree>_to_<dest>`: the root is pushed to `dest`
+            chain_id: sync
+                .sync_type
+                .rsplit_once("_to_")
+                .and_then(|(_, dest)| Chain::parse(dest).ok())
+                .map(|chain| chain.id() as i32),
+            block_number: block_of(&sync.tx_hash),
+            kind: sync.sync_type,
+            transaction_hash: Some(sync.tx_hash),
+            status: None,
+            root: Some(sync.root),
+        }
+    }));
+
+    steps.sort_by_key(|step| step.at);
+    steps
+}
+
+impl BridgeCoordinator {
+    /// `None` if the intent does not exist.
+    pub fn intent_lifecycle(&self, intent_id: &str) -> Result<Option<IntentLifecycle>> {
+        let Some(intent) = self.database.get_intent_by_id(intent_id)? else {
+            return Ok(None);
+        };
+        let events = self.database.get_intent_bridge_events(intent_id)?;
+        let transactions = self.database.get_intent_transactions(intent_id)?;
+
+        // The commitment reaches the destination with the first commitment
+        // root pushed after it was added, and the fill returns with the
+        // first fill root pushed after it
+        let committed_at = events
+            .iter()
+            .find(|event| event.event_type == "intent_created")
+            .map_or(intent.created_at, |event| event.timestamp);
+        let filled_at = events
+            .iter()
+            .find(|event| event.event_type == "intent_filled")
+            .map(|event| event.timestamp);
+        let mut root_syncs = Vec::new();
+        if let Some(sync) = self.database.get_first_root_sync_after(
+            &format!(
+                "{}_commitments_to_{}",
+                intent.source_chain, intent.dest_chain
+            ),
+            committed_at,
+        )? {
+            root_syncs.push(sync);
+        }
+        if let Some(filled_at) = filled_at
+            && let Some(sync) = self.database.get_first_root_sync_after(
+                &format!("{}_fills_to_{}", intent.dest_chain, intent.source_chain),
+                filled_at,
+            )?
+        {
+            root_syncs.push(sync);
+        }
+
+        let tx_hashes: Vec<String> = transactions
+            .iter()
+            .map(|tx| tx.tx_hash.clone())
+            .chain(root_syncs.iter().map(|sync| sync.tx_hash.clone()))
+            .collect();
+        let receipts = self.database.get_transaction_receipts(&tx_hashes)?;
+
+        Ok(Some(IntentLifecycle {
+            intent_id: intent.id.to_string(),
+            status: intent.status.as_str().to_string(),
+            source_chain: intent.source_chain.clone(),
+            dest_chain: intent.dest_chain.clone(),
+            steps: build_lifecycle(&intent, events, transactions, receipts, root_syncs),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{IntentFactory, test_database};
+    use serde_json::json;
+    use serial_test::serial;
+
+    #[test]
+    fn test_build_lifecycle_orders_events_transactions_and_root_syncs() {
+        let at = |secs: i64| DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap();
+        let mut intent = IntentFactory::filled().build();
+        intent.created_at = at(0);
+        let event = |id: i32, event_type: &str, chain_id, secs| DbBridgeEvent {
+            id,
+            event_id: format!("{}_{}", event_type, id),
+            intent_id: Some(intent.id.to_string()),
+            event_type: event_type.to_string(),
+            event_data: json!({}),
+            chain_id,
+            block_number: 100 + i64::from(id),
+            transaction_hash: format!("0xevent{}", id),
+            timestamp: at(secs),
+            created_at: at(secs),
+            log_index: None,
+        };
+
+        let steps = build_lifecycle(
+            &intent,
+            vec![
+                event(1, "intent_created", 11155111, 10),
+                event(2, "intent_registered", 5003, 60),
+                event(3, "intent_filled", 5003, 90),
+            ],
+            vec![DbChainTransaction {
+                id: 1,
+                intent_id: intent.id.to_string(),
+                chain_id: 5003,
+                tx_type: "register_intent".to_string(),
+                tx_hash: "0xREGISTER".to_string(),
+                status: "confirmed".to_string(),
+                timestamp: 1_700_000_050,
+                created_at: at(50),
+            }],
+            vec![DbTransactionReceipt {
+                tx_hash: "0xregister".to_string(),
+                intent_id: Some(intent.id.to_string()),
+                chain_id: 5003,
+                tx_type: "register_intent".to_string(),
+                status: "confirmed".to_string(),
+                block_number: Some(777),
+                gas_used: None,
+                effective_gas_price: None,
+                revert_reason: None,
+                receipt: json!({}),
+                created_at: at(55),
+            }],
+            vec![DbRootSync {
+                id: 1,
+                sync_type: "ethereum_commitments_to_mantle".to_string(),
+                root: "0xroot".to_string(),
+                tx_hash: "0xsync".to_string(),
+                created_at: at(30),
+                leaf_count: Some(4),
+            }],
+        );
+
+        let stages: Vec<(&str, &str)> =
+            steps.iter().map(|step| (step.source, step.stage)).collect();
+        assert_eq!(
+            stages,
+            vec![
+                ("relayer", "created"),
+                ("chain", "committed"),
+                ("relayer", "root_sync"),
+                ("relayer", "registered"),
+                ("chain", "registered"),
+                ("chain", "filled"),
+            ]
+        );
+        assert_eq!(steps[0].chain_id, Some(11155111));
+        assert_eq!(steps[2].chain_id, Some(5003));
+        assert_eq!(steps[2].root.as_deref(), Some("0xroot"));
+        assert_eq!(steps[3].block_number, Some(777));
+        assert_eq!(steps[3].status.as_deref(), Some("confirmed"));
+        assert_eq!(steps[5].block_number, Some(103));
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_root_sync_lookup_takes_the_first_push_after_the_step() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+        // Unique per run so earlier rows do not match
+        let sync_type = format!(
+            "ethereum_commitments_to_mantle_test_{}",
+            Utc::now().timestamp_micros()
+        );
+        let before = Utc::now();
+
+        assert!(
+            database
+                .get_first_root_sync_after(&sync_type, before)?
+                .is_none()
+        );
+        database.insert_root_sync(&sync_type, "0xfirst", "0xsync1", Some(1))?;
+        database.insert_root_sync(&sync_type, "0xsecond", "0xsync2", Some(2))?;
+
+        let sync = database
+            .get_first_root_sync_after(&sync_type, before)?
+            .expect("root sync");
+        assert_eq!(sync.root, "0xfirst");
+        assert!(
+            database
+                .get_first_root_sync_after(&sync_type, Utc::now())?
+                .is_none()
+        );
+
+        Ok(())
+    }
+}
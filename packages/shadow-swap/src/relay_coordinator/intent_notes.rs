@@ -0,0 +1,311 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use serde_json::{Value, json};
+use tracing::{info, warn};
+
+use crate::{
+    database::model::{DbBridgeEvent, DbIntentAnnotation},
+    models::model::{Intent, IntentStatus},
+    relay_coordinator::model::BridgeCoordinator,
+};
+
+/// Why a note or override was not recorded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AnnotationRejection {
+    NotFound,
+    MissingOperator,
+    MissingReason,
+    InvalidStatus(String),
+}
+
+impl std::fmt::Display for AnnotationRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "Intent not found"),
+            Self::MissingOperator => write!(f, "operator is required"),
+            Self::MissingReason => write!(f, "reason is required"),
+            Self::InvalidStatus(status) => write!(f, "Invalid intent status: {}", status),
+        }
+    }
+}
+
+/// One step in an intent's history, from the chain or from an operator.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TimelineEntry {
+    pub at: DateTime<Utc>,
+    /// `relayer`, `chain` or `operator`.
+    pub source: &'static str,
+    pub kind: String,
+    pub detail: Value,
+}
+
+/// `0x1234…abcd`: enough to match against what the user submitted without
+/// publishing where their funds went.
+pub fn redact_address(address: &str) -> String {
+    match (
+        address.get(..6),
+        address.get(address.len().saturating_sub(4)..),
+    ) {
+        (Some(head), Some(tail)) if address.len() > 10 => format!("{}…{}", head, tail),
+        _ => "…".to_string(),
+    }
+}
+
+/// Creation, any claim delegation, recorded chain events and operator
+/// annotations, oldest first. Entries at the same instant keep that order.
+pub fn build_timeline(
+    intent: &Intent,
+    claim_delegate: Option<&str>,
+    events: Vec<DbBridgeEvent>,
+    annotations: Vec<DbIntentAnnotation>,
+) -> Vec<TimelineEntry> {
+    let mut timeline = vec![TimelineEntry {
+        at: intent.created_at,
+        source: "relayer",
+        kind: "intent_created".to_string(),
+        detail: json!({
+            "source_chain": intent.source_chain,
+            "dest_chain": intent.dest_chain,
+            "amount": intent.amount,
+        }),
+    }];
+
+    if let Some(delegate) = claim_delegate {
+        timeline.push(TimelineEntry {
+            at: intent.created_at,
+            source: "relayer",
+            kind: "claim_delegated".to_string(),
+            detail: json!({ "recipient": redact_address(delegate) }),
+        });
+    }
+
+    timeline.extend(events.into_iter().map(|event| TimelineEntry {
+        at: event.timestamp,
+        source: "chain",
+        kind: event.event_type,
+        detail: json!({
+            "chain_id": event.chain_id,
+            "block_number": event.block_number,
+            "transaction_hash": event.transaction_hash,
+        }),
+    }));
+
+    timeline.extend(annotations.into_iter().map(|annotation| TimelineEntry {
+        at: annotation.created_at,
+        source: "operator",
+        kind: annotation.kind,
+        detail: json!({
+            "operator": annotation.operator,
+            "reason": annotation.reason,
+            "previous_status": annotation.previous_status,
+            "new_status": annotation.new_status,
+        }),
+    }));
+
+    timeline.sort_by_key(|entry| entry.at);
+    timeline
+}
+
+impl BridgeCoordinator {
+    /// Record an operator note, or a manual status change when `status` is
+    /// set. Both keep the operator and reason for post-incident review.
+    pub fn annotate_intent(
+        &self,
+        intent_id: &str,
+        operator: &str,
+        reason: &str,
+        status: Option<&str>,
+    ) -> Result<std::result::Result<DbIntentAnnotation, AnnotationRejection>> {
+        let (operator, reason) = (operator.trim(), reason.trim());
+        if operator.is_empty() {
+            return Ok(Err(AnnotationRejection::MissingOperator));
+        }
+        if reason.is_empty() {
+            return Ok(Err(AnnotationRejection::MissingReason));
+        }
+
+        let Some(status) = status else {
+            let Some(note) = self.database.add_intent_note(intent_id, operator, reason)? else {
+                return Ok(Err(AnnotationRejection::NotFound));
+            };
+            info!(
+                "📝 Note on intent {} by {}: {}",
+                intent_id, operator, reason
+            );
+            return Ok(Ok(note));
+        };
+
+        let Ok(status) = IntentStatus::from_str(status) else {
+            return Ok(Err(AnnotationRejection::InvalidStatus(status.to_string())));
+        };
+        let Some(annotation) = self
+            .database
+            .override_intent_status(intent_id, operator, reason, status)?
+        else {
+            return Ok(Err(AnnotationRejection::NotFound));
+        };
+
+        warn!(
+            "✍️ Intent {} manually moved from {} to {} by {}: {}",
+            intent_id,
+            annotation.previous_status.as_deref().unwrap_or("unknown"),
+            status.as_str(),
+            operator,
+            reason
+        );
+        Ok(Ok(annotation))
+    }
+
+    /// `None` if the intent does not exist.
+    pub fn intent_timeline(&self, intent_id: &str) -> Result<Option<Vec<TimelineEntry>>> {
+        let Some(intent) = self.database.get_intent_by_id(intent_id)? else {
+            return Ok(None);
+        };
+        // Privacy params arrive separately and may not be stored
+        let claim_delegate = self
+            .database
+            .get_intent_privacy_params(intent_id)
+            .ok()
+            .and_then(|params| params.claim_delegate);
+        let events = self.database.get_intent_bridge_events(intent_id)?;
+        let annotations = self.database.list_intent_annotations(intent_id)?;
+
+        Ok(Some(build_timeline(
+            &intent,
+            claim_delegate.as_deref(),
+            events,
+            annotations,
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{IntentFactory, test_database};
+    use serial_test::serial;
+
+    #[test]
+    fn test_build_timeline_interleaves_operator_actions() {
+        let at = |secs: i64| DateTime::from_timestamp(1_700_000_000 + secs, 0).unwrap();
+        let mut intent = IntentFactory::filled().build();
+        intent.created_at = at(0);
+        let event = |id: i32, event_type: &str, secs| DbBridgeEvent {
+            id,
+            event_id: format!("{}_{}", event_type, id),
+            intent_id: Some(intent.id.to_string()),
+            event_type: event_type.to_string(),
+            event_data: json!({}),
+            chain_id: 5003,
+            block_number: 100 + i64::from(id),
+            transaction_hash: format!("0xtx{}", id),
+            timestamp: at(secs),
+            created_at: at(secs),
+            log_index: None,
+        };
+        let annotation = |id, kind: &str, secs| DbIntentAnnotation {
+            id,
+            intent_id: intent.id.to_string(),
+            operator: "alice".to_string(),
+            kind: kind.to_string(),
+            reason: "stuck after RPC outage".to_string(),
+            previous_status: (kind == "status_override").then(|| "filled".to_string()),
+            new_status: (kind == "status_override").then(|| "refunded".to_string()),
+            created_at: at(secs),
+        };
+
+        let timeline = build_timeline(
+            &intent,
+            None,
+            vec![
+                event(1, "intent_registered", 60),
+                event(2, "intent_filled", 120),
+            ],
+            vec![
+                annotation(1, "note", 90),
+                annotation(2, "status_override", 120),
+            ],
+        );
+
+        let steps: Vec<(&str, &str)> = timeline
+            .iter()
+            .map(|entry| (entry.source, entry.kind.as_str()))
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("relayer", "intent_created"),
+                ("chain", "intent_registered"),
+                ("operator", "note"),
+                ("chain", "intent_filled"),
+                ("operator", "status_override"),
+            ]
+        );
+        assert_eq!(timeline[3].detail["transaction_hash"], "0xtx2");
+        assert_eq!(timeline[4].detail["operator"], "alice");
+        assert_eq!(timeline[4].detail["previous_status"], "filled");
+    }
+
+    #[test]
+    fn test_timeline_records_redacted_claim_delegate() {
+        let intent = IntentFactory::filled().build();
+        let delegate = "0x28c6c06298d514db089934071355e5743bf21d60";
+
+        let timeline = build_timeline(&intent, Some(delegate), vec![], vec![]);
+        assert_eq!(timeline[1].kind, "claim_delegated");
+        assert_eq!(timeline[1].detail["recipient"], "0x28c6…1d60");
+        assert!(!timeline[1].detail.to_string().contains(delegate));
+
+        assert_eq!(redact_address("0x1234"), "…");
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_status_override_is_recorded() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+        let intent = IntentFactory::filled().insert(&database)?;
+
+        let note = database
+            .add_intent_note(&intent.id, "alice", "solver reports fill tx dropped")?
+            .expect("intent exists");
+        assert_eq!(note.kind, "note");
+        assert!(note.new_status.is_none());
+
+        let annotation = database
+            .override_intent_status(
+                &intent.id,
+                "bob",
+                "refunded by hand",
+                IntentStatus::Refunded,
+            )?
+            .expect("intent exists");
+        assert_eq!(annotation.previous_status.as_deref(), Some("filled"));
+        assert_eq!(annotation.new_status.as_deref(), Some("refunded"));
+
+        let stored = database
+            .get_intent_by_id(&intent.id)?
+            .expect("intent exists");
+        assert_eq!(stored.status, IntentStatus::Refunded);
+
+        let annotations = database.list_intent_annotations(&intent.id)?;
+        let operators: Vec<&str> = annotations.iter().map(|a| a.operator.as_str()).collect();
+        assert_eq!(operators, vec!["alice", "bob"]);
+
+        assert!(
+            database
+                .add_intent_note("0xmissing", "alice", "typo")?
+                .is_none()
+        );
+        assert!(
+            database
+                .override_intent_status("0xmissing", "alice", "typo", IntentStatus::Failed)?
+                .is_none()
+        );
+        assert_eq!(database.list_intent_annotations("0xmissing")?.len(), 0);
+
+        Ok(())
+    }
+}
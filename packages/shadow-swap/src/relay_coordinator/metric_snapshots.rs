@@ -0,0 +1,216 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use ethers::types::U256;
+use mantle_core::{
+    counters::{CounterSnapshot, PersistentCounters},
+    token::TokenType,
+};
+use tokio::time::interval;
+use tracing::{info, warn};
+
+use crate::{models::model::BridgeMetrics, relay_coordinator::model::BridgeCoordinator};
+
+#[derive(Debug, Clone)]
+pub struct MetricsPersistPolicy {
+    pub enabled: bool,
+    pub interval: Duration,
+    /// Row the counters are saved under, one per relayer sharing the database.
+    pub instance: String,
+}
+
+impl Default for MetricsPersistPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            interval: Duration::from_secs(60),
+            instance: "default".to_string(),
+        }
+    }
+}
+
+impl MetricsPersistPolicy {
+    /// Reads `METRICS_PERSIST_ENABLED`, `METRICS_PERSIST_INTERVAL_SECS` and
+    /// `METRICS_INSTANCE`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(enabled) = std::env::var("METRICS_PERSIST_ENABLED") {
+            policy.enabled = enabled.parse().context("Invalid METRICS_PERSIST_ENABLED")?;
+        }
+        if let Ok(secs) = std::env::var("METRICS_PERSIST_INTERVAL_SECS") {
+            policy.interval = Duration::from_secs(
+                secs.parse::<u64>()
+                    .context("Invalid METRICS_PERSIST_INTERVAL_SECS")?
+                    .max(5),
+            );
+        }
+        if let Ok(instance) = std::env::var("METRICS_INSTANCE")
+            && !instance.trim().is_empty()
+        {
+            policy.instance = instance.trim().to_string();
+        }
+
+        Ok(policy)
+    }
+}
+
+fn volume_key(token: TokenType) -> String {
+    format!("volume_{}", token.symbol().to_lowercase())
+}
+
+impl PersistentCounters for BridgeMetrics {
+    fn counter_snapshot(&self, now: i64) -> CounterSnapshot {
+        let counters = [
+            ("total_intents_processed", self.total_intents_processed),
+            ("successful_bridges", self.successful_bridges),
+            ("failed_intents", self.failed_intents),
+            ("refunded_intents", self.refunded_intents),
+            ("ethereum_fills", self.ethereum_fills),
+            ("mantle_fills", self.mantle_fills),
+            ("ethereum_claims", self.ethereum_claims),
+            ("mantle_claims", self.mantle_claims),
+            ("retry_attempts", self.retry_attempts),
+        ];
+
+        CounterSnapshot {
+            since: self.counters_since,
+            saved_at: now,
+            counters: counters
+                .into_iter()
+                .map(|(name, counter)| (name.to_string(), counter.get()))
+                .collect(),
+            amounts: self
+                .volumes_by_token
+                .iter()
+                .map(|(token, volume)| (volume_key(*token), U256::from(*volume)))
+                .collect(),
+        }
+    }
+
+    fn restore_counters(&mut self, snapshot: &CounterSnapshot) {
+        let counters = [
+            ("total_intents_processed", &mut self.total_intents_processed),
+            ("successful_bridges", &mut self.successful_bridges),
+            ("failed_intents", &mut self.failed_intents),
+            ("refunded_intents", &mut self.refunded_intents),
+            ("ethereum_fills", &mut self.ethereum_fills),
+            ("mantle_fills", &mut self.mantle_fills),
+            ("ethereum_claims", &mut self.ethereum_claims),
+            ("mantle_claims", &mut self.mantle_claims),
+            ("retry_attempts", &mut self.retry_attempts),
+        ];
+        for (name, counter) in counters {
+            counter.restore(snapshot.counter(name));
+        }
+
+        for token in TokenType::ALL {
+            let saved = snapshot.amount(&volume_key(token));
+            if saved.is_zero() {
+                continue;
+            }
+            let saved = if saved > U256::from(u128::MAX) {
+                u128::MAX
+            } else {
+                saved.as_u128()
+            };
+            let volume = self.volumes_by_token.entry(token).or_default();
+            *volume = volume.saturating_add(saved);
+        }
+
+        if snapshot.since > 0 {
+            self.counters_since = self.counters_since.min(snapshot.since);
+        }
+    }
+}
+
+impl BridgeCoordinator {
+    /// Seed the counters with what the previous process saved. Call once,
+    /// before the workers start counting.
+    pub async fn restore_metrics(&self, policy: &MetricsPersistPolicy) -> Result<()> {
+        let Some(saved) = self.database.get_metric_snapshot(&policy.instance)? else {
+            return Ok(());
+        };
+        let snapshot: CounterSnapshot =
+            serde_json::from_value(saved).context("Unreadable metric snapshot")?;
+
+        self.metrics.write().await.restore_counters(&snapshot);
+        info!(
+            "📈 Restored metric counters for '{}' (saved at {}, counting since {})",
+            policy.instance, snapshot.saved_at, snapshot.since
+        );
+        Ok(())
+    }
+
+    pub async fn save_metrics(&self, policy: &MetricsPersistPolicy) -> Result<()> {
+        let snapshot = self
+            .metrics
+            .read()
+            .await
+            .counter_snapshot(Utc::now().timestamp());
+        self.database
+            .save_metric_snapshot(&policy.instance, &serde_json::to_value(&snapshot)?)
+    }
+
+    pub async fn run_metrics_persister(&self, policy: MetricsPersistPolicy) {
+        info!(
+            "📈 Metrics persister started (every {}s, instance '{}')",
+            policy.interval.as_secs(),
+            policy.instance
+        );
+
+        let mut ticker = interval(policy.interval);
+        // The first tick fires immediately; nothing new has been counted yet
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.save_metrics(&policy).await {
+                warn!("⚠️ Failed to save metric counters: {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::test_database;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(db)]
+    fn test_bridge_metrics_survive_restart() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let mut before = BridgeMetrics::default();
+        before.successful_bridges += 7;
+        before.mantle_claims += 3;
+        before.counters_since = 1_700_000_000;
+        before.volumes_by_token.insert(TokenType::USDC, 12_500_000);
+        let snapshot = before.counter_snapshot(1_700_086_400);
+        database.save_metric_snapshot("test", &serde_json::to_value(&snapshot)?)?;
+
+        // The new process counted a little before the snapshot was loaded
+        let mut after = BridgeMetrics::default();
+        after.successful_bridges += 1;
+        after.volumes_by_token.insert(TokenType::USDC, 500_000);
+        let saved = database
+            .get_metric_snapshot("test")?
+            .expect("snapshot saved");
+        after.restore_counters(&serde_json::from_value(saved)?);
+
+        assert_eq!(after.successful_bridges.get(), 8);
+        assert_eq!(after.successful_bridges.session(), 1);
+        assert_eq!(after.mantle_claims.get(), 3);
+        assert_eq!(after.failed_intents.get(), 0);
+        assert_eq!(after.volumes_by_token[&TokenType::USDC], 13_000_000);
+        assert_eq!(after.counters_since, 1_700_000_000);
+        assert!(after.process_start_time > after.counters_since);
+        assert!(database.get_metric_snapshot("other")?.is_none());
+
+        Ok(())
+    }
+}
@@ -1,2 +1,33 @@
+pub mod alert_rules;
+pub mod backlog;
+pub mod claim_auth;
+pub mod claim_diagnosis;
+pub mod claim_estimate;
+pub mod claim_forwarder;
+pub mod compliance;
+pub mod contract_guard;
+pub mod debug_capture;
+pub mod deposit_check;
+pub mod encrypted_notes;
+pub mod fill_reports;
+pub mod ingestion_queue;
+pub mod intent_archive;
+pub mod intent_feed;
+pub mod intent_lifecycle;
+pub mod intent_notes;
+pub mod metric_snapshots;
 pub mod model;
+pub mod orphaned_fills;
+pub mod reconcile;
 pub mod relay_coordinator;
+pub mod relayer_jobs;
+pub mod replay;
+pub mod reservations;
+pub mod route_analytics;
+pub mod sandbox;
+pub mod secret_reveal;
+pub mod settlement_strategy;
+pub mod token_listings;
+pub mod tx_journal;
+pub mod tx_queue;
+pub mod work_partitions;
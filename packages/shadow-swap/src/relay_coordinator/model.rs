@@ -1,60 +1,168 @@
+use ethers::types::U256;
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 use tokio::time;
 
 use crate::models::model::{BridgeMetrics, IntentOperationState};
 use crate::{
+    chains::{registry::ChainRegistry, relayer::ChainClient},
+    config::schema::{self, defaults},
     database::database::Database,
-    ethereum::relayer::{EthClient, ethereum_contracts},
-    mantle::relayer::{MantleClient, mantle_contracts},
+    intent_workers::{
+        fairness::TokenBatchLimits, log_listener::WorkerWakeups, queue_metrics::QueueAlertPolicy,
+    },
     merkle_manager::merkle_manager::MerkleTreeManager,
     models::model::{DatabaseConfig, ServerConfig},
+    relay_coordinator::{
+        backlog::{BacklogProgress, TriagePolicy},
+        claim_forwarder::ClaimForwarder,
+        contract_guard::ContractGuard,
+        debug_capture::DebugCapture,
+        ingestion_queue::IngestionQueue,
+        relayer_jobs::RelayerJobs,
+        settlement_strategy::SettlementStrategies,
+        tx_queue::TxQueue,
+        work_partitions::WorkPartitions,
+    },
+    shutdown::Shutdown,
+};
+use mantle_core::{
+    abi::{IntentPoolContract, SettlementContract},
+    finality::FinalitySource,
+    metrics::Metrics,
+    token_registry::TokenRegistry,
+    tx_strategy::ChainTxStrategy,
 };
 use tokio::sync::RwLock;
 
 pub struct BridgeCoordinator {
-    pub ethereum_relayer: Arc<EthereumRelayer>,
-    pub mantle_relayer: Arc<MantleRelayer>,
+    pub ethereum_relayer: Arc<ChainRelayer>,
+    pub mantle_relayer: Arc<ChainRelayer>,
+    pub chains: ChainRegistry,
     pub database: Arc<Database>,
     pub merkle_tree_manager: Arc<MerkleTreeManager>,
     pub metrics: Arc<RwLock<BridgeMetrics>>,
+    /// Prometheus series behind `GET /metrics`, shared with the relayers.
+    pub prometheus: Arc<Metrics>,
+    /// Listed tokens, shared with the API and workers.
+    pub tokens: Arc<TokenRegistry>,
     pub operation_states: Arc<RwLock<HashMap<String, IntentOperationState>>>,
     pub start_time: time::Instant,
+    pub sponsorship: SponsorshipPolicy,
+    pub token_limits: TokenBatchLimits,
+    pub receipt_retention: ReceiptRetention,
+    pub triage_policy: TriagePolicy,
+    pub backlog_progress: Arc<RwLock<BacklogProgress>>,
+    pub queue_alerts: QueueAlertPolicy,
+    pub settlement_strategies: SettlementStrategies,
+    pub partitions: WorkPartitions,
+    /// Attempts and backoff of registration and settlement, kept across
+    /// restarts.
+    pub jobs: RelayerJobs,
+    /// Verbose recording of the intents an operator marked.
+    pub debug_capture: DebugCapture,
+    /// Chain events stored on receipt, waiting to be applied.
+    pub ingestion: IngestionQueue,
+    /// Raised on SIGTERM/SIGINT; the workers drain and return.
+    pub shutdown: Shutdown,
+    /// Cut the claim loop's sleep short when an intent is settled.
+    pub wakeups: WorkerWakeups,
+}
+
+/// How long archived transaction receipts are kept.
+///
+/// `retention_days` of `0` keeps receipts forever.
+#[derive(Debug, Clone)]
+pub struct ReceiptRetention {
+    pub retention_days: u64,
+    pub keep_reverted: bool,
+}
+
+/// Limits on how much native gas the relayer spends claiming on behalf of users.
+///
+/// Caps of `0` mean unlimited.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SponsorshipPolicy {
+    pub enabled: bool,
+    pub default_sponsored: bool,
+    pub max_cost_per_claim_wei: u128,
+    pub daily_cap_wei: u128,
+}
+
+impl Default for SponsorshipPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            default_sponsored: true,
+            max_cost_per_claim_wei: 0,
+            daily_cap_wei: 0,
+        }
+    }
 }
 
-pub struct EthereumRelayer {
-    pub client: Arc<EthClient>,
-    pub intent_pool: ethereum_contracts::EthIntentPool<EthClient>,
-    pub settlement: ethereum_contracts::EthSettlement<EthClient>,
+/// Relayer for one EVM chain. Every chain runs the same code; what differs
+/// comes from the `ChainConfig` it was built from.
+pub struct ChainRelayer {
+    /// Shown in logs, e.g. `Mantle`.
+    pub name: &'static str,
+    /// Lowercase name, as stored on intent rows and used in merkle tree names.
+    pub key: &'static str,
+    pub native_symbol: &'static str,
+    pub low_balance: U256,
+    pub rpc_url: String,
+    pub client: Arc<ChainClient>,
+    pub intent_pool: IntentPoolContract<ChainClient>,
+    pub settlement: SettlementContract<ChainClient>,
     pub database: Arc<Database>,
     pub chain_id: u32,
+    pub tx_strategy: ChainTxStrategy,
+    /// Which blocks count as final when pushing this chain's roots elsewhere.
+    pub finality: FinalitySource,
+    pub tx_queue: TxQueue,
+    pub sandbox: bool,
+    pub guard: ContractGuard,
+    /// ERC-2771 forwarder claims are routed through, when configured.
+    pub claim_forwarder: Option<ClaimForwarder>,
+    pub metrics: Arc<Metrics>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct EthereumConfig {
+    #[serde(deserialize_with = "schema::http_url")]
     pub rpc_url: String,
+    #[serde(default, deserialize_with = "schema::ws_url")]
     pub ws_url: Option<String>,
+    #[serde(default, deserialize_with = "schema::private_key")]
     pub private_key: String,
+    #[serde(deserialize_with = "schema::address")]
     pub intent_pool_address: String,
+    #[serde(deserialize_with = "schema::address")]
     pub settlement_address: String,
-    pub chain_id: u32,
-}
-
-pub struct MantleRelayer {
-    pub client: Arc<MantleClient>,
-    pub intent_pool: mantle_contracts::MantleIntentPool<MantleClient>,
-    pub settlement: mantle_contracts::MantleSettlement<MantleClient>,
-    pub database: Arc<Database>,
+    #[serde(
+        default = "defaults::ethereum_chain_id",
+        deserialize_with = "schema::positive"
+    )]
     pub chain_id: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct MantleConfig {
+    #[serde(deserialize_with = "schema::http_url")]
     pub rpc_url: String,
+    #[serde(default, deserialize_with = "schema::ws_url")]
     pub ws_url: Option<String>,
+    #[serde(default, deserialize_with = "schema::private_key")]
     pub private_key: String,
+    #[serde(deserialize_with = "schema::address")]
     pub intent_pool_address: String,
+    #[serde(deserialize_with = "schema::address")]
     pub settlement_address: String,
+    #[serde(
+        default = "defaults::mantle_chain_id",
+        deserialize_with = "schema::positive"
+    )]
     pub chain_id: u32,
 }
 
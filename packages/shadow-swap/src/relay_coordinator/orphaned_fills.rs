@@ -0,0 +1,512 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Context, Result, anyhow};
+use chrono::Utc;
+use ethers::types::{Address, U256};
+use mantle_core::{chain::Chain, route::parse_route_amounts, token::TokenType};
+use serde::Serialize;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+use crate::{
+    database::model::{DbOrphanedFill, NewOrphanedFill},
+    models::model::{Intent, IntentStatus},
+    relay_coordinator::model::BridgeCoordinator,
+};
+
+/// A fill on the destination settlement as `getFill` reports it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DestFill {
+    pub solver: Address,
+    pub token: Address,
+    pub amount: U256,
+    pub claimed: bool,
+}
+
+/// Whether `code` dispatches on `selector`. Solidity compares the calldata
+/// selector against a `PUSH4 <selector>` for every external function.
+pub fn bytecode_has_selector(code: &[u8], selector: [u8; 4]) -> bool {
+    code.windows(5)
+        .any(|window| window[0] == 0x63 && window[1..] == selector)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanStatus {
+    /// Above the approval threshold; waits for an operator.
+    AwaitingApproval,
+    /// Will be recovered on the next pass.
+    Pending,
+    /// The destination settlement has no `recoverOrphanedFill`; follow the
+    /// manual runbook.
+    Unsupported,
+    Recovered,
+    /// Claimed or settled by other means before recovery ran.
+    Resolved,
+    /// Recovery kept failing; see `last_error`.
+    Failed,
+}
+
+impl OrphanStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AwaitingApproval => "awaiting_approval",
+            Self::Pending => "pending",
+            Self::Unsupported => "unsupported",
+            Self::Recovered => "recovered",
+            Self::Resolved => "resolved",
+            Self::Failed => "failed",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "awaiting_approval" => Ok(Self::AwaitingApproval),
+            "pending" => Ok(Self::Pending),
+            "unsupported" => Ok(Self::Unsupported),
+            "recovered" => Ok(Self::Recovered),
+            "resolved" => Ok(Self::Resolved),
+            "failed" => Ok(Self::Failed),
+            other => Err(anyhow!("Unknown orphaned fill status: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OrphanPolicy {
+    pub interval: Duration,
+    /// Refunds older than this are not re-checked for an orphaned fill.
+    pub lookback: chrono::Duration,
+    /// When false every recovery waits for operator approval.
+    pub auto_recovery: bool,
+    /// Largest amount per (token, fill chain) recovered without approval.
+    pub approval_amounts: HashMap<(TokenType, Chain), U256>,
+    pub max_attempts: i32,
+}
+
+impl Default for OrphanPolicy {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(300),
+            lookback: chrono::Duration::hours(72),
+            auto_recovery: true,
+            approval_amounts: HashMap::new(),
+            max_attempts: 5,
+        }
+    }
+}
+
+impl OrphanPolicy {
+    /// Reads `ORPHAN_CHECK_INTERVAL_SECS`, `ORPHAN_LOOKBACK_HOURS`,
+    /// `ORPHAN_AUTO_RECOVERY_ENABLED`, `ORPHAN_APPROVAL_AMOUNTS`
+    /// (`TOKEN:CHAIN=whole tokens`) and `ORPHAN_MAX_RECOVERY_ATTEMPTS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(secs) = std::env::var("ORPHAN_CHECK_INTERVAL_SECS") {
+            policy.interval = Duration::from_secs(
+                secs.parse::<u64>()
+                    .context("Invalid ORPHAN_CHECK_INTERVAL_SECS")?
+                    .max(1),
+            );
+        }
+        if let Ok(hours) = std::env::var("ORPHAN_LOOKBACK_HOURS") {
+            policy.lookback = chrono::Duration::hours(
+                hours
+                    .parse::<i64>()
+                    .context("Invalid ORPHAN_LOOKBACK_HOURS")?
+                    .max(1),
+            );
+        }
+        if let Ok(enabled) = std::env::var("ORPHAN_AUTO_RECOVERY_ENABLED") {
+            policy.auto_recovery = enabled
+                .parse()
+                .context("Invalid ORPHAN_AUTO_RECOVERY_ENABLED")?;
+        }
+        if let Ok(raw) = std::env::var("ORPHAN_APPROVAL_AMOUNTS") {
+            policy.approval_amounts = parse_route_amounts(&raw);
+        }
+        if let Ok(attempts) = std::env::var("ORPHAN_MAX_RECOVERY_ATTEMPTS") {
+            policy.max_attempts = attempts
+                .parse::<i32>()
+                .context("Invalid ORPHAN_MAX_RECOVERY_ATTEMPTS")?
+                .max(1);
+        }
+
+        Ok(policy)
+    }
+
+    /// Built-in thresholds: 1,000 of a stablecoin or MNT, 0.5 ETH or WETH.
+    fn default_approval_amount(token: TokenType) -> U256 {
+        match token {
+            TokenType::ETH | TokenType::WETH => U256::from(5) * U256::exp10(17),
+            TokenType::USDC | TokenType::USDT | TokenType::MNT => {
+                U256::from(1000) * U256::exp10(token.decimals() as usize)
+            }
+        }
+    }
+
+    /// Where a newly found orphan starts. Unknown tokens always need approval.
+    pub fn initial_status(
+        &self,
+        supported: bool,
+        token: Option<TokenType>,
+        dest: Chain,
+        amount: U256,
+    ) -> OrphanStatus {
+        if !supported {
+            return OrphanStatus::Unsupported;
+        }
+        let Some(token) = token else {
+            return OrphanStatus::AwaitingApproval;
+        };
+        let threshold = self
+            .approval_amounts
+            .get(&(token, dest))
+            .copied()
+            .unwrap_or_else(|| Self::default_approval_amount(token));
+
+        if self.auto_recovery && amount <= threshold {
+            OrphanStatus::Pending
+        } else {
+            OrphanStatus::AwaitingApproval
+        }
+    }
+}
+
+/// Why an approval was refused, mapped to an HTTP status by the route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalRejection {
+    NotFound,
+    NotAwaitingApproval(String),
+}
+
+impl std::fmt::Display for ApprovalRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "No orphaned fill for this intent"),
+            Self::NotAwaitingApproval(status) => {
+                write!(f, "Orphaned fill is {}, not awaiting approval", status)
+            }
+        }
+    }
+}
+
+impl BridgeCoordinator {
+    pub async fn run_orphan_monitor(&self, policy: OrphanPolicy) {
+        info!(
+            "🛟 Orphaned fill monitor started (every {}s, auto recovery: {})",
+            policy.interval.as_secs(),
+            policy.auto_recovery
+        );
+
+        let mut ticker = interval(policy.interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.detect_orphaned_fills(&policy).await {
+                error!("❌ Orphaned fill detection failed: {}", e);
+            }
+            if let Err(e) = self.recover_orphaned_fills(&policy).await {
+                error!("❌ Orphaned fill recovery failed: {}", e);
+            }
+        }
+    }
+
+    pub fn list_orphaned_fills(&self, status: Option<OrphanStatus>) -> Result<Vec<DbOrphanedFill>> {
+        self.database
+            .list_orphaned_fills(status.as_ref().map(OrphanStatus::as_str))
+    }
+
+    /// Operator sign-off for a recovery above the threshold. The next monitor
+    /// pass sends it.
+    pub fn approve_orphaned_fill(
+        &self,
+        intent_id: &str,
+    ) -> Result<std::result::Result<DbOrphanedFill, ApprovalRejection>> {
+        let Some(orphan) = self.database.get_orphaned_fill(intent_id)? else {
+            return Ok(Err(ApprovalRejection::NotFound));
+        };
+        if !self.database.approve_orphaned_fill(intent_id)? {
+            return Ok(Err(ApprovalRejection::NotAwaitingApproval(orphan.status)));
+        }
+
+        warn!(
+            "🛟 Recovery of orphaned fill {} approved ({} of {} to {})",
+            orphan.intent_id, orphan.amount, orphan.token, orphan.solver_address
+        );
+
+        self.database
+            .get_orphaned_fill(intent_id)?
+            .map(Ok)
+            .ok_or_else(|| anyhow!("Orphaned fill {} disappeared", intent_id))
+    }
+
+    /// Recently refunded intents whose destination fill is still unclaimed.
+    async fn detect_orphaned_fills(&self, policy: &OrphanPolicy) -> Result<()> {
+        let refunded = self
+            .database
+            .get_intents_updated_since(IntentStatus::Refunded, Utc::now() - policy.lookback)?;
+        let known = self.database.get_orphaned_fill_ids()?;
+        let mut supported: HashMap<Chain, bool> = HashMap::new();
+
+        for intent in refunded {
+            if known.contains(&intent.id.to_lowercase()) {
+                continue;
+            }
+            let (source, dest) = match (
+                Chain::parse(&intent.source_chain),
+                Chain::parse(&intent.dest_chain),
+            ) {
+                (Ok(source), Ok(dest)) => (source, dest),
+                _ => continue,
+            };
+
+            let fill = match self.dest_fill(dest, &intent.id).await {
+                Ok(Some(fill)) if !fill.claimed => fill,
+                Ok(_) => continue,
+                Err(e) => {
+                    warn!("⚠️ Could not read fill of refunded {}: {}", intent.id, e);
+                    continue;
+                }
+            };
+
+            let supported = match supported.get(&dest) {
+                Some(supported) => *supported,
+                None => {
+                    let value = self.orphan_recovery_supported(dest).await?;
+                    supported.insert(dest, value);
+                    value
+                }
+            };
+            let token = self.tokens.resolve(fill.token, dest);
+            let status = policy.initial_status(supported, token, dest, fill.amount);
+
+            self.record_orphan(&intent, source, dest, &fill, status)?;
+        }
+
+        Ok(())
+    }
+
+    fn record_orphan(
+        &self,
+        intent: &Intent,
+        source: Chain,
+        dest: Chain,
+        fill: &DestFill,
+        status: OrphanStatus,
+    ) -> Result<()> {
+        let intent_id = intent.id.to_lowercase();
+        let solver = format!("{:?}", fill.solver);
+        let token = format!("{:?}", fill.token);
+        let amount = fill.amount.to_string();
+
+        let created = self.database.create_orphaned_fill(&NewOrphanedFill {
+            intent_id: &intent_id,
+            source_chain: source.name(),
+            dest_chain: dest.name(),
+            solver_address: &solver,
+            token: &token,
+            amount: &amount,
+            status: status.as_str(),
+            detected_at: Utc::now(),
+            updated_at: Utc::now(),
+        })?;
+
+        if created {
+            error!(
+                "🚨 Orphaned fill: intent {} refunded on {} but filled on {} by {} ({} of {}) -> {}",
+                intent_id,
+                source.name(),
+                dest.name(),
+                solver,
+                amount,
+                token,
+                status.as_str()
+            );
+        }
+        Ok(())
+    }
+
+    /// Send recoveries that are due, and re-check unsupported chains in case
+    /// the settlement has been upgraded.
+    async fn recover_orphaned_fills(&self, policy: &OrphanPolicy) -> Result<()> {
+        for orphan in self
+            .database
+            .list_orphaned_fills(Some(OrphanStatus::Unsupported.as_str()))?
+        {
+            let dest = Chain::parse(&orphan.dest_chain)?;
+            if !self.orphan_recovery_supported(dest).await? {
+                continue;
+            }
+            let token = orphan
+                .token
+                .parse()
+                .ok()
+                .and_then(|token| self.tokens.resolve(token, dest));
+            let amount = U256::from_dec_str(&orphan.amount).unwrap_or(U256::MAX);
+            let status = policy.initial_status(true, token, dest, amount);
+
+            if self.database.transition_orphaned_fill(
+                &orphan.intent_id,
+                OrphanStatus::Unsupported.as_str(),
+                status.as_str(),
+            )? {
+                info!(
+                    "🛟 {} settlement now supports recovery, orphan {} -> {}",
+                    dest.name(),
+                    orphan.intent_id,
+                    status.as_str()
+                );
+            }
+        }
+
+        for orphan in self
+            .database
+            .list_orphaned_fills(Some(OrphanStatus::Pending.as_str()))?
+        {
+            if let Err(e) = self.recover_orphan(&orphan).await {
+                let status = if orphan.attempts + 1 >= policy.max_attempts {
+                    OrphanStatus::Failed
+                } else {
+                    OrphanStatus::Pending
+                };
+                error!(
+                    "❌ Recovering orphaned fill {} failed (attempt {}/{}): {}",
+                    orphan.intent_id,
+                    orphan.attempts + 1,
+                    policy.max_attempts,
+                    e
+                );
+                self.database.record_orphan_recovery_failure(
+                    &orphan.intent_id,
+                    &e.to_string(),
+                    status.as_str(),
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Re-checks both chains right before sending: the source must still show
+    /// a refund and the fill must still be unclaimed.
+    async fn recover_orphan(&self, orphan: &DbOrphanedFill) -> Result<()> {
+        let source = Chain::parse(&orphan.source_chain)?;
+        let dest = Chain::parse(&orphan.dest_chain)?;
+
+        let (settled, refunded) = self
+            .relayer(source)
+            .get_source_intent_state(&orphan.intent_id)
+            .await?;
+        let still_open = self
+            .dest_fill(dest, &orphan.intent_id)
+            .await?
+            .is_some_and(|fill| !fill.claimed);
+
+        if settled || !refunded || !still_open {
+            info!(
+                "🛟 Orphaned fill {} no longer needs recovery (settled: {}, refunded: {}, unclaimed: {})",
+                orphan.intent_id, settled, refunded, still_open
+            );
+            self.database.transition_orphaned_fill(
+                &orphan.intent_id,
+                OrphanStatus::Pending.as_str(),
+                OrphanStatus::Resolved.as_str(),
+            )?;
+            return Ok(());
+        }
+
+        let tx_hash = self
+            .relayer(dest)
+            .recover_orphaned_fill(&orphan.intent_id)
+            .await?;
+
+        self.database
+            .mark_orphaned_fill_recovered(&orphan.intent_id, &tx_hash)?;
+        info!(
+            "✅ Orphaned fill {} returned to {} on {}: {}",
+            orphan.intent_id,
+            orphan.solver_address,
+            dest.name(),
+            tx_hash
+        );
+        Ok(())
+    }
+
+    async fn dest_fill(&self, dest: Chain, intent_id: &str) -> Result<Option<DestFill>> {
+        self.relayer(dest).get_dest_fill(intent_id).await
+    }
+
+    async fn orphan_recovery_supported(&self, dest: Chain) -> Result<bool> {
+        self.relayer(dest).supports_orphan_recovery().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{contract::EthCall, utils::hex};
+    use mantle_core::abi::settlement_contract::{ClaimWithdrawalCall, RecoverOrphanedFillCall};
+
+    #[test]
+    fn test_initial_status_and_selector_detection() {
+        let usdc = |whole: u64| U256::from(whole) * U256::exp10(6);
+        let policy = OrphanPolicy {
+            approval_amounts: parse_route_amounts("USDC:mantle=250"),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            policy.initial_status(true, Some(TokenType::USDC), Chain::Mantle, usdc(250)),
+            OrphanStatus::Pending
+        );
+        assert_eq!(
+            policy.initial_status(true, Some(TokenType::USDC), Chain::Mantle, usdc(251)),
+            OrphanStatus::AwaitingApproval
+        );
+        // No override on Ethereum: the 1,000 USDC default applies
+        assert_eq!(
+            policy.initial_status(true, Some(TokenType::USDC), Chain::Ethereum, usdc(900)),
+            OrphanStatus::Pending
+        );
+        assert_eq!(
+            policy.initial_status(true, None, Chain::Ethereum, U256::one()),
+            OrphanStatus::AwaitingApproval
+        );
+        assert_eq!(
+            policy.initial_status(false, Some(TokenType::USDC), Chain::Mantle, usdc(1)),
+            OrphanStatus::Unsupported
+        );
+        let manual = OrphanPolicy {
+            auto_recovery: false,
+            ..policy
+        };
+        assert_eq!(
+            manual.initial_status(true, Some(TokenType::USDC), Chain::Mantle, usdc(1)),
+            OrphanStatus::AwaitingApproval
+        );
+
+        for status in [
+            OrphanStatus::AwaitingApproval,
+            OrphanStatus::Pending,
+            OrphanStatus::Unsupported,
+            OrphanStatus::Recovered,
+            OrphanStatus::Resolved,
+            OrphanStatus::Failed,
+        ] {
+            assert_eq!(OrphanStatus::parse(status.as_str()).unwrap(), status);
+        }
+
+        // Dispatcher fragment: DUP1 PUSH4 <selector> EQ
+        let recover = RecoverOrphanedFillCall::selector();
+        let mut code = hex::decode("6080604052").unwrap();
+        code.extend([0x80, 0x63]);
+        code.extend(ClaimWithdrawalCall::selector());
+        code.push(0x14);
+        assert!(!bytecode_has_selector(&code, recover));
+        code.extend([0x80, 0x63]);
+        code.extend(recover);
+        code.push(0x14);
+        assert!(bytecode_has_selector(&code, recover));
+    }
+}
@@ -0,0 +1,317 @@
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use tracing::{error, info, warn};
+
+use crate::{
+    models::model::{Intent, IntentStatus},
+    relay_coordinator::model::BridgeCoordinator,
+};
+
+/// Which source chain's intents a reconciliation run covers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReconcileChain {
+    Ethereum,
+    Mantle,
+    Both,
+}
+
+impl ReconcileChain {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value.to_lowercase().as_str() {
+            "ethereum" => Ok(Self::Ethereum),
+            "mantle" => Ok(Self::Mantle),
+            "both" => Ok(Self::Both),
+            other => Err(anyhow!(
+                "Unknown chain '{}', expected ethereum, mantle or both",
+                other
+            )),
+        }
+    }
+
+    pub fn includes(&self, source_chain: &str) -> bool {
+        match self {
+            Self::Both => true,
+            Self::Ethereum => matches!(source_chain, "ethereum" | "11155111"),
+            Self::Mantle => matches!(source_chain, "mantle" | "5003"),
+        }
+    }
+}
+
+/// What the source pool and destination settlement say about an intent.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct OnChainIntentState {
+    pub created: bool,
+    pub registered: bool,
+    pub filled: bool,
+    pub claimed: bool,
+    pub settled: bool,
+    pub refunded: bool,
+}
+
+impl OnChainIntentState {
+    /// Furthest status the chains prove, `None` if the intent never landed on the source chain.
+    pub fn expected_status(&self) -> Option<IntentStatus> {
+        if self.refunded {
+            Some(IntentStatus::Refunded)
+        } else if self.claimed {
+            Some(IntentStatus::UserClaimed)
+        } else if self.settled {
+            Some(IntentStatus::SolverPaid)
+        } else if self.filled {
+            Some(IntentStatus::Filled)
+        } else if self.registered {
+            Some(IntentStatus::Registered)
+        } else if self.created {
+            Some(IntentStatus::Committed)
+        } else {
+            None
+        }
+    }
+}
+
+/// The pre-registration statuses are indistinguishable on chain.
+fn status_matches(db: IntentStatus, expected: IntentStatus) -> bool {
+    match expected {
+        IntentStatus::Committed => matches!(
+            db,
+            IntentStatus::Created
+                | IntentStatus::Committed
+                | IntentStatus::Pending
+                | IntentStatus::Underpaid
+        ),
+        _ => db == expected,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReconcileMismatch {
+    pub intent_id: String,
+    pub source_chain: String,
+    pub db_status: String,
+    pub chain_status: Option<String>,
+    pub on_chain: OnChainIntentState,
+    pub fixed: bool,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReconcileReport {
+    pub checked: usize,
+    pub errors: usize,
+    pub mismatches: Vec<ReconcileMismatch>,
+}
+
+/// Compare the DB status with the on-chain state, `None` when they agree.
+pub fn find_mismatch(intent: &Intent, on_chain: OnChainIntentState) -> Option<ReconcileMismatch> {
+    let expected = on_chain.expected_status();
+
+    if let Some(expected) = expected
+        && status_matches(intent.status, expected)
+    {
+        return None;
+    }
+
+    Some(ReconcileMismatch {
+        intent_id: intent.id.to_string(),
+        source_chain: intent.source_chain.clone(),
+        db_status: intent.status.as_str().to_string(),
+        chain_status: expected.map(|s| s.as_str().to_string()),
+        on_chain,
+        fixed: false,
+    })
+}
+
+impl BridgeCoordinator {
+    /// Walk every non-terminal intent, compare it with both chains and, when
+    /// `fix` is set, move the DB status to what the chains prove.
+    pub async fn reconcile_intents(
+        &self,
+        chain: ReconcileChain,
+        fix: bool,
+    ) -> Result<ReconcileReport> {
+        let intents: Vec<Intent> = self
+            .database
+            .get_backlog_intents()?
+            .into_iter()
+            .filter(|intent| chain.includes(&intent.source_chain))
+            .collect();
+
+        info!(
+            "🔍 Reconciling {} non-terminal intents against chain state (fix={})",
+            intents.len(),
+            fix
+        );
+
+        let mut report = ReconcileReport::default();
+
+        for intent in &intents {
+            report.checked += 1;
+
+            let on_chain = match self.read_on_chain_state(intent).await {
+                Ok(state) => state,
+                Err(e) => {
+                    error!("❌ Failed to read chain state for {}: {}", intent.id, e);
+                    report.errors += 1;
+                    continue;
+                }
+            };
+
+            let Some(mut mismatch) = find_mismatch(intent, on_chain) else {
+                continue;
+            };
+
+            warn!(
+                "⚠️ {} ({}): db={} chain={}",
+                mismatch.intent_id,
+                mismatch.source_chain,
+                mismatch.db_status,
+                mismatch.chain_status.as_deref().unwrap_or("not created")
+            );
+
+            if fix && let Some(expected) = on_chain.expected_status() {
+                match self.database.update_intent_status(&intent.id, expected) {
+                    Ok(()) => {
+                        info!(
+                            "🔧 Fixed {}: {} -> {}",
+                            intent.id,
+                            mismatch.db_status,
+                            expected.as_str()
+                        );
+                        mismatch.fixed = true;
+                    }
+                    Err(e) => {
+                        error!("❌ Failed to fix {}: {}", intent.id, e);
+                        report.errors += 1;
+                    }
+                }
+            }
+
+            report.mismatches.push(mismatch);
+        }
+
+        info!(
+            "✅ Reconciliation complete: {} checked, {} mismatched, {} fixed, {} errors",
+            report.checked,
+            report.mismatches.len(),
+            report.mismatches.iter().filter(|m| m.fixed).count(),
+            report.errors
+        );
+
+        Ok(report)
+    }
+
+    pub(crate) async fn read_on_chain_state(&self, intent: &Intent) -> Result<OnChainIntentState> {
+        let ((created, (settled, refunded)), (registered, (filled, claimed))) =
+            match intent.source_chain.as_str() {
+                "ethereum" | "11155111" => (
+                    (
+                        self.ethereum_relayer
+                            .check_source_intent_created(&intent.id)
+                            .await?,
+                        self.ethereum_relayer
+                            .get_source_intent_state(&intent.id)
+                            .await?,
+                    ),
+                    (
+                        self.mantle_relayer
+                            .check_intent_registered(&intent.id)
+                            .await?,
+                        self.mantle_relayer.get_dest_fill_state(&intent.id).await?,
+                    ),
+                ),
+                "mantle" | "5003" => (
+                    (
+                        self.mantle_relayer
+                            .check_source_intent_created(&intent.id)
+                            .await?,
+                        self.mantle_relayer
+                            .get_source_intent_state(&intent.id)
+                            .await?,
+                    ),
+                    (
+                        self.ethereum_relayer
+                            .check_intent_registered(&intent.id)
+                            .await?,
+                        self.ethereum_relayer
+                            .get_dest_fill_state(&intent.id)
+                            .await?,
+                    ),
+                ),
+                _ => return Err(anyhow!("Unsupported source chain: {}", intent.source_chain)),
+            };
+
+        Ok(OnChainIntentState {
+            created,
+            registered,
+            filled,
+            claimed,
+            settled,
+            refunded,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use ethers::types::H256;
+
+    fn intent(status: IntentStatus) -> Intent {
+        Intent {
+            id: H256::from_low_u64_be(1).into(),
+            user_address: String::new(),
+            source_chain: "ethereum".to_string(),
+            dest_chain: "mantle".to_string(),
+            source_token: String::new(),
+            dest_token: String::new(),
+            amount: "0".parse().unwrap(),
+            dest_amount: "0".parse().unwrap(),
+            source_commitment: None,
+            dest_fill_txid: None,
+            dest_registration_txid: None,
+            source_complete_txid: None,
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deadline: 0,
+            refund_address: None,
+            solver_address: None,
+            block_number: None,
+            log_index: None,
+        }
+    }
+
+    #[test]
+    fn test_find_mismatch() {
+        let created = OnChainIntentState {
+            created: true,
+            ..Default::default()
+        };
+        assert!(find_mismatch(&intent(IntentStatus::Created), created).is_none());
+        assert!(find_mismatch(&intent(IntentStatus::Committed), created).is_none());
+
+        let settled = OnChainIntentState {
+            created: true,
+            registered: true,
+            filled: true,
+            settled: true,
+            ..Default::default()
+        };
+        let mismatch = find_mismatch(&intent(IntentStatus::Filled), settled).unwrap();
+        assert_eq!(mismatch.chain_status.as_deref(), Some("solver_paid"));
+
+        let refunded = OnChainIntentState {
+            created: true,
+            refunded: true,
+            ..Default::default()
+        };
+        assert_eq!(refunded.expected_status(), Some(IntentStatus::Refunded));
+
+        let missing = find_mismatch(
+            &intent(IntentStatus::Registered),
+            OnChainIntentState::default(),
+        )
+        .unwrap();
+        assert!(missing.chain_status.is_none());
+    }
+}
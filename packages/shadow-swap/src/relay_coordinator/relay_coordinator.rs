@@ -1,109 +1,84 @@
 use std::{collections::HashMap, sync::Arc, time::Duration};
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
+use mantle_core::{
+    chain::Chain, counters::MonotonicCounter, metrics::Metrics, token_registry::TokenRegistry,
+};
 use tokio::{
     sync::RwLock,
-    time::{self, interval, sleep},
+    time::{self, interval},
 };
-use tracing::{error, info};
+use tracing::{debug, error, info, warn};
 
 use crate::{
+    chains::registry::ChainRegistry,
     database::database::Database,
     encryption::encryption_utils::decrypt_with_ecies,
+    intent_workers::{
+        fairness::{TokenBatchLimits, select_cycle_batch},
+        log_listener::WorkerWakeups,
+        queue_metrics::QueueAlertPolicy,
+    },
     merkle_manager::merkle_manager::MerkleTreeManager,
-    models::{
-        model::{BridgeMetrics, Intent, IntentOperationState, IntentStatus, TokenType},
-        traits::ChainRelayer,
+    models::model::{BridgeMetrics, Intent, IntentError, IntentOperationState, IntentStatus},
+    relay_coordinator::backlog::{BacklogProgress, TriagePolicy},
+    relay_coordinator::debug_capture::{DebugCapture, DebugCapturePolicy},
+    relay_coordinator::ingestion_queue::{IngestionPolicy, IngestionQueue},
+    relay_coordinator::model::{
+        BridgeCoordinator, ChainRelayer, ReceiptRetention, SponsorshipPolicy,
     },
-    relay_coordinator::model::{BridgeCoordinator, EthereumRelayer, MantleRelayer},
+    relay_coordinator::relayer_jobs::{RelayerJobPolicy, RelayerJobs},
+    relay_coordinator::settlement_strategy::SettlementStrategies,
+    relay_coordinator::work_partitions::WorkPartitions,
+    shutdown::Shutdown,
 };
 
-impl TokenType {
-    pub fn from_address(address: &str) -> Result<Self> {
-        match address.to_lowercase().as_str() {
-            "0x0000000000000000000000000000000000000000" => Ok(Self::ETH),
-            "0xeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeeee" => Ok(Self::ETH),
-            "0x28650373758d75a8ff0b22587f111e47bac34e21" => Ok(Self::USDC),
-            "0x89f4f0e13997ca27ceb963dee291c607e4e59923" => Ok(Self::USDT),
-            "0x50e8da97beeb8064714de45ce1f250879f3bd5b5" => Ok(Self::WETH),
-            "0x65e37b558f64e2be5768db46df22f93d85741a9e" => Ok(Self::MNT),
-            "0xa4b184006b59861f80521649b14e4e8a72499a23" => Ok(Self::USDC),
-            "0xb0ee6ef7788e9122fc4aae327ed4fef56c7da891" => Ok(Self::USDT),
-            "0xdeaddeaddeaddeaddeaddeaddeaddeaddead1111" => Ok(Self::WETH),
-            "0x44fce297e4d6c5a50d28fb26a58202e4d49a13e7" => Ok(Self::MNT),
-            _ => Err(anyhow!("Unsupported token address: {}", address)),
-        }
-    }
-
-    pub fn from_symbol(symbol: &str) -> Result<Self> {
-        match symbol.to_uppercase().as_str() {
-            "ETH" => Ok(Self::ETH),
-            "USDC" => Ok(Self::USDC),
-            "USDT" => Ok(Self::USDT),
-            "WETH" => Ok(Self::WETH),
-            "MNT" => Ok(Self::MNT),
-            _ => Err(anyhow!("Unsupported token symbol: {}", symbol)),
-        }
-    }
-
-    pub fn get_ethereum_address(&self) -> &str {
-        match self {
-            Self::ETH => "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
-            Self::USDC => "0x28650373758d75a8fF0B22587F111e47BAC34e21",
-            Self::USDT => "0x89F4f0e13997Ca27cEB963DEE291C607e4E59923",
-            Self::WETH => "0x50e8Da97BeEB8064714dE45ce1F250879f3bD5B5",
-            Self::MNT => "0x65e37B558F64E2Be5768DB46DF22F93d85741A9E",
-        }
-    }
-
-    pub fn get_mantle_address(&self) -> &str {
-        match self {
-            Self::ETH => "0xEeeeeEeeeEeEeeEeEeEeeEEEeeeeEeeeeeeeEEeE",
-            Self::USDC => "0xA4b184006B59861f80521649b14E4E8A72499A23",
-            Self::USDT => "0xB0ee6EF7788E9122fc4AAE327Ed4FEf56c7da891",
-            Self::WETH => "0xdeaddeaddeaddeaddeaddeaddeaddeaddead1111",
-            Self::MNT => "0x44FCE297e4D6c5A50D28Fb26A58202e4D49a13E7",
-        }
-    }
-
-    pub fn get_decimals(&self) -> u8 {
-        match self {
-            Self::ETH | Self::WETH | Self::MNT => 18,
-            Self::USDC | Self::USDT => 6,
-        }
-    }
-
-    pub fn symbol(&self) -> &str {
-        match self {
-            Self::ETH => "ETH",
-            Self::USDC => "USDC",
-            Self::USDT => "USDT",
-            Self::WETH => "WETH",
-            Self::MNT => "MNT",
-        }
-    }
-}
+const MAX_CLAIMS_PER_CYCLE: usize = 10;
 
 impl Default for BridgeMetrics {
     fn default() -> Self {
+        let now = chrono::Utc::now().timestamp();
         Self {
-            total_intents_processed: 0,
-            successful_bridges: 0,
-            failed_intents: 0,
-            refunded_intents: 0,
-            ethereum_fills: 0,
-            mantle_fills: 0,
-            ethereum_claims: 0,
-            mantle_claims: 0,
-            retry_attempts: 0,
+            total_intents_processed: MonotonicCounter::default(),
+            successful_bridges: MonotonicCounter::default(),
+            failed_intents: MonotonicCounter::default(),
+            refunded_intents: MonotonicCounter::default(),
+            ethereum_fills: MonotonicCounter::default(),
+            mantle_fills: MonotonicCounter::default(),
+            ethereum_claims: MonotonicCounter::default(),
+            mantle_claims: MonotonicCounter::default(),
+            retry_attempts: MonotonicCounter::default(),
             last_error: None,
             uptime_seconds: 0,
+            process_start_time: now,
+            counters_since: now,
             volumes_by_token: HashMap::new(),
+            queue_depths: HashMap::new(),
+            processing_rates: HashMap::new(),
+            intent_errors: HashMap::new(),
         }
     }
 }
 
 impl BridgeMetrics {
+    pub fn record_processed(&mut self, queue: &str) {
+        self.processing_rates
+            .entry(queue.to_string())
+            .or_default()
+            .record(std::time::Instant::now());
+    }
+
+    pub fn record_intent_error(&mut self, intent_id: &str, queue: &str, error: &anyhow::Error) {
+        self.intent_errors.insert(
+            intent_id.to_string(),
+            IntentError {
+                queue: queue.to_string(),
+                error: format!("{:#}", error),
+                at: chrono::Utc::now(),
+            },
+        );
+    }
+
     pub fn to_json(&self) -> serde_json::Value {
         let volumes: HashMap<String, String> = self
             .volumes_by_token
@@ -123,26 +98,143 @@ impl BridgeMetrics {
             "retry_attempts": self.retry_attempts,
             "last_error": self.last_error,
             "uptime_seconds": self.uptime_seconds,
+            "process_start_time": self.process_start_time,
+            "counters_since": self.counters_since,
             "volumes_by_token": volumes,
+            "queue_depths": self.queue_depths,
+            "processing_rates_per_min": self
+                .processing_rates
+                .iter()
+                .map(|(queue, rate)| (queue.clone(), rate.clone().per_minute(std::time::Instant::now())))
+                .collect::<HashMap<String, f64>>(),
         })
     }
 }
 
+impl SponsorshipPolicy {
+    /// Reads `CLAIM_SPONSORSHIP_ENABLED`, `CLAIM_SPONSORSHIP_DEFAULT`,
+    /// `CLAIM_SPONSORSHIP_MAX_PER_CLAIM_WEI` and
+    /// `CLAIM_SPONSORSHIP_DAILY_CAP_WEI`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(enabled) = var("CLAIM_SPONSORSHIP_ENABLED") {
+            policy.enabled = enabled
+                .trim()
+                .parse()
+                .context("Invalid CLAIM_SPONSORSHIP_ENABLED")?;
+        }
+        if let Some(sponsored) = var("CLAIM_SPONSORSHIP_DEFAULT") {
+            policy.default_sponsored = sponsored
+                .trim()
+                .parse()
+                .context("Invalid CLAIM_SPONSORSHIP_DEFAULT")?;
+        }
+        if let Some(wei) = var("CLAIM_SPONSORSHIP_MAX_PER_CLAIM_WEI") {
+            policy.max_cost_per_claim_wei = wei
+                .trim()
+                .parse()
+                .context("Invalid CLAIM_SPONSORSHIP_MAX_PER_CLAIM_WEI")?;
+        }
+        if let Some(wei) = var("CLAIM_SPONSORSHIP_DAILY_CAP_WEI") {
+            policy.daily_cap_wei = wei
+                .trim()
+                .parse()
+                .context("Invalid CLAIM_SPONSORSHIP_DAILY_CAP_WEI")?;
+        }
+
+        Ok(policy)
+    }
+}
+
+impl ReceiptRetention {
+    pub fn from_env() -> Self {
+        Self {
+            retention_days: std::env::var("RECEIPT_RETENTION_DAYS")
+                .unwrap_or_else(|_| "90".to_string())
+                .parse()
+                .unwrap_or(90),
+            keep_reverted: std::env::var("RECEIPT_KEEP_REVERTED")
+                .unwrap_or_else(|_| "true".to_string())
+                .parse()
+                .unwrap_or(true),
+        }
+    }
+}
+
 impl BridgeCoordinator {
     pub fn new(
-        ethereum_relayer: Arc<EthereumRelayer>,
-        mantle_relayer: Arc<MantleRelayer>,
+        ethereum_relayer: Arc<ChainRelayer>,
+        mantle_relayer: Arc<ChainRelayer>,
         database: Arc<Database>,
         merkle_tree_manager: Arc<MerkleTreeManager>,
+        partitions: WorkPartitions,
+        prometheus: Arc<Metrics>,
+        tokens: Arc<TokenRegistry>,
     ) -> Self {
         Self {
+            chains: ChainRegistry::new([ethereum_relayer.clone(), mantle_relayer.clone()]),
             ethereum_relayer,
             mantle_relayer,
-            database,
+            database: database.clone(),
             merkle_tree_manager,
             metrics: Arc::new(RwLock::new(BridgeMetrics::default())),
+            prometheus,
+            tokens,
             operation_states: Arc::new(RwLock::new(HashMap::new())),
             start_time: time::Instant::now(),
+            sponsorship: SponsorshipPolicy::default(),
+            token_limits: TokenBatchLimits::default(),
+            receipt_retention: ReceiptRetention::from_env(),
+            triage_policy: TriagePolicy::from_env(),
+            queue_alerts: QueueAlertPolicy::from_env(),
+            settlement_strategies: SettlementStrategies::from_env(),
+            backlog_progress: Arc::new(RwLock::new(BacklogProgress::default())),
+            partitions,
+            jobs: RelayerJobs::new(database.clone(), RelayerJobPolicy::default()),
+            debug_capture: DebugCapture::new(database.clone(), DebugCapturePolicy::default()),
+            ingestion: IngestionQueue::new(database.clone(), IngestionPolicy::default()),
+            shutdown: Shutdown::new(),
+            wakeups: WorkerWakeups::default(),
+        }
+    }
+
+    /// Retry failed registrations and settlements per `policy`.
+    pub fn with_jobs(mut self, policy: RelayerJobPolicy) -> Self {
+        self.jobs = RelayerJobs::new(self.database.clone(), policy);
+        self
+    }
+
+    /// Bound debug captures per `policy`.
+    pub fn with_debug_capture(mut self, policy: DebugCapturePolicy) -> Self {
+        self.debug_capture = DebugCapture::new(self.database.clone(), policy);
+        self
+    }
+
+    /// Store and deliver chain events per `policy`.
+    pub fn with_ingestion(mut self, policy: IngestionPolicy) -> Self {
+        self.ingestion = IngestionQueue::new(self.database.clone(), policy);
+        self
+    }
+
+    /// Sponsor user claims per `policy`.
+    pub fn with_sponsorship(mut self, policy: SponsorshipPolicy) -> Self {
+        self.sponsorship = policy;
+        self
+    }
+
+    /// Cap what each worker cycle takes per token at `limits`.
+    pub fn with_token_limits(mut self, limits: TokenBatchLimits) -> Self {
+        self.token_limits = limits;
+        self
+    }
+
+    /// Relayer for one of the built-in chains.
+    pub fn relayer(&self, chain: Chain) -> &Arc<ChainRelayer> {
+        match chain {
+            Chain::Ethereum => &self.ethereum_relayer,
+            Chain::Mantle => &self.mantle_relayer,
         }
     }
 
@@ -160,20 +252,56 @@ impl BridgeCoordinator {
             }
         });
 
+        if self.receipt_retention.retention_days > 0 {
+            let database = Arc::clone(&self.database);
+            let retention = self.receipt_retention.clone();
+            tokio::spawn(async move {
+                let mut interval = interval(Duration::from_secs(3600));
+                loop {
+                    interval.tick().await;
+                    let cutoff = chrono::Utc::now()
+                        - chrono::Duration::days(retention.retention_days as i64);
+                    match database.prune_transaction_receipts(cutoff, retention.keep_reverted) {
+                        Ok(0) => {}
+                        Ok(n) => info!("🧹 Pruned {} archived receipts", n),
+                        Err(e) => warn!("⚠️ Receipt pruning failed: {}", e),
+                    }
+                }
+            });
+        }
+
         let merkle_manager = Arc::clone(&self.merkle_tree_manager);
+        let shutdown = self.shutdown.clone();
         tokio::spawn(async move {
-            if let Err(e) = merkle_manager.start().await {
+            if let Err(e) = merkle_manager.start(&shutdown).await {
                 error!("❌ Merkle manager failed: {}", e);
             }
         });
 
+        if self.triage_policy.enabled
+            && let Err(e) = self.triage_backlog().await
+        {
+            error!("❌ Backlog triage failed: {}", e);
+            self.record_error(format!("Backlog triage failed: {}", e))
+                .await;
+        }
+
         loop {
             if let Err(e) = self.process_pending_intents().await {
                 error!("❌ Error processing intents: {}", e);
                 self.record_error(e.to_string()).await;
             }
-            sleep(Duration::from_secs(10)).await;
+            if self
+                .shutdown
+                .sleep_or_wake(Duration::from_secs(10), &self.wakeups.claims)
+                .await
+            {
+                break;
+            }
         }
+
+        info!("🌉 Bridge coordinator stopped");
+        Ok(())
     }
 
     async fn process_pending_intents(&self) -> Result<()> {
@@ -181,19 +309,41 @@ impl BridgeCoordinator {
             .database
             .get_pending_intents()
             .map_err(|e| anyhow!("Failed to get pending intents: {}", e))?;
+        let pending_intents = self.partitions.retain_owned(pending_intents);
 
         if pending_intents.is_empty() {
             return Ok(());
         }
 
-        for intent in pending_intents {
-            {
-                let mut metrics = self.metrics.write().await;
-                metrics.total_intents_processed += 1;
-            }
+        let claimable: Vec<Intent> = pending_intents
+            .iter()
+            .filter(|intent| intent.status == IntentStatus::SolverPaid)
+            .cloned()
+            .collect();
+
+        let batch = select_cycle_batch(
+            claimable,
+            &self.tokens,
+            &self.token_limits,
+            MAX_CLAIMS_PER_CYCLE,
+        );
+
+        {
+            let mut metrics = self.metrics.write().await;
+            metrics.total_intents_processed += pending_intents.len() as u64;
+            metrics
+                .queue_depths
+                .insert("claim".to_string(), batch.queue_depths);
+        }
 
-            if intent.status == IntentStatus::SolverPaid {
-                if let Err(e) = self.claim_for_user(&intent).await {
+        for intent in batch.intents {
+            if self.shutdown.is_triggered() {
+                info!("🛑 Leaving remaining claims for the next start");
+                break;
+            }
+            match self.claim_for_user(&intent).await {
+                Ok(()) => self.metrics.write().await.record_processed("claim"),
+                Err(e) => {
                     error!("Failed to claim for user (intent {}): {}", intent.id, e);
                     self.record_error(format!("Claim failed: {}", e)).await;
                 }
@@ -208,20 +358,11 @@ impl BridgeCoordinator {
             IntentStatus::SolverPaid => {
                 info!("💸 Claiming for user on {}", intent.dest_chain);
 
-                match intent.dest_chain.as_str() {
-                    "mantle" | "5003" => {
-                        self.claim_on_chain(&*self.mantle_relayer, intent, true)
-                            .await
-                    }
-                    "ethereum" | "11155111" => {
-                        self.claim_on_chain(&*self.ethereum_relayer, intent, false)
-                            .await
-                    }
-                    _ => Err(anyhow!(
-                        "Unsupported destination chain: {}",
-                        intent.dest_chain
-                    )),
-                }
+                let relayer = self
+                    .chains
+                    .resolve(&intent.dest_chain)
+                    .map_err(|_| anyhow!("Unsupported destination chain: {}", intent.dest_chain))?;
+                self.claim_on_chain(relayer, intent).await
             }
             IntentStatus::Registered | IntentStatus::Filled => {
                 let now = chrono::Utc::now().timestamp() as u64;
@@ -237,87 +378,68 @@ impl BridgeCoordinator {
         }
     }
 
-    async fn claim_on_chain<T: ChainRelayer>(
-        &self,
-        relayer: &T,
-        intent: &Intent,
-        is_mantle: bool,
-    ) -> Result<()> {
-        info!(
-            "🔓 Claiming on {} for intent {}",
-            if is_mantle { "Mantle" } else { "Ethereum" },
-            intent.id
-        );
-
-        let privacy_params = self
-            .database
-            .get_intent_privacy_params(&intent.id)
-            .map_err(|e| anyhow!("Failed to get privacy params: {}", e))?;
-
-        let encrypted_secret = privacy_params
-            .secret
-            .as_ref()
-            .ok_or_else(|| anyhow!("Encrypted secret not available"))?;
-
-        let encrypted_nullifier = privacy_params
-            .nullifier
-            .as_ref()
-            .ok_or_else(|| anyhow!("Encrypted nullifier not available"))?;
+    async fn claim_on_chain(&self, relayer: &ChainRelayer, intent: &Intent) -> Result<()> {
+        info!("🔓 Claiming on {} for intent {}", relayer.name, intent.id);
 
-        let recipient = privacy_params
-            .recipient
-            .as_ref()
-            .ok_or_else(|| anyhow!("Recipient not available"))?;
+        let chain_id = relayer.chain_id;
 
-        let claim_auth_hex = privacy_params
-            .claim_signature
-            .as_ref()
-            .ok_or_else(|| anyhow!("Claim signature not available"))?;
-
-        let relayer_private_key = std::env::var("RELAYER_PRIVATE_KEY")
-            .map_err(|_| anyhow!("RELAYER_PRIVATE_KEY not set"))?;
-
-        let secret = decrypt_with_ecies(encrypted_secret, &relayer_private_key)
-            .map_err(|e| anyhow!("Failed to decrypt secret: {}", e))?;
-
-        let nullifier = decrypt_with_ecies(encrypted_nullifier, &relayer_private_key)
-            .map_err(|e| anyhow!("Failed to decrypt nullifier: {}", e))?;
-
-        let claim_auth_hex_clean = claim_auth_hex.strip_prefix("0x").unwrap_or(claim_auth_hex);
-        let claim_auth_bytes = hex::decode(claim_auth_hex_clean)
-            .map_err(|e| anyhow!("Failed to decode claim signature hex: {}", e))?;
+        let Some(estimated_cost) = self.check_sponsorship(relayer, intent, chain_id).await? else {
+            return Ok(());
+        };
 
-        if claim_auth_bytes.len() != 65 {
-            return Err(anyhow!(
-                "Invalid signature length: expected 65 bytes, got {}",
-                claim_auth_bytes.len()
-            ));
+        // Concurrent claims each see the spend before the others land, so
+        // the estimate is held against the cap until the real cost replaces it
+        let cap = self.sponsorship.daily_cap_wei;
+        if cap > 0 {
+            let since = chrono::Utc::now() - chrono::Duration::hours(24);
+            if !self.database.reserve_sponsored_spend(
+                &intent.id,
+                chain_id,
+                estimated_cost,
+                cap,
+                since,
+            )? {
+                warn!(
+                    "⚠️ Daily sponsorship cap reached on chain {}: no room for {} wei",
+                    chain_id, estimated_cost
+                );
+                return Ok(());
+            }
         }
 
+        let params = match self.claim_params(&intent.id).await {
+            Ok(params) => params,
+            Err(e) => {
+                self.release_sponsored_spend(&intent.id);
+                return Err(e);
+            }
+        };
+
         let result = relayer
             .claim_withdrawal(
                 &intent.id,
-                &nullifier,
-                recipient,
-                &secret,
-                &claim_auth_bytes,
+                &params.nullifier,
+                &params.recipient,
+                params.delegate.as_deref(),
+                &params.secret,
+                &params.claim_auth,
             )
             .await;
 
         match result {
             Ok(txid) => {
-                info!(
-                    "✅ Claimed on {}: {}",
-                    if is_mantle { "Mantle" } else { "Ethereum" },
-                    txid
-                );
+                info!("✅ Claimed on {}: {}", relayer.name, txid);
+                self.prometheus.claim(relayer.key, true);
 
                 self.database
                     .update_intent_status(&intent.id, IntentStatus::UserClaimed)
                     .map_err(|e| anyhow!("Failed to update status: {}", e))?;
 
+                self.record_sponsored_claim(relayer, &intent.id, chain_id, &txid)
+                    .await;
+
                 let mut metrics = self.metrics.write().await;
-                if is_mantle {
+                if relayer.key == Chain::Mantle.name() {
                     metrics.mantle_claims += 1;
                 } else {
                     metrics.ethereum_claims += 1;
@@ -326,22 +448,122 @@ impl BridgeCoordinator {
             }
             Err(e) => {
                 error!("❌ Claim failed: {}", e);
+                self.prometheus.claim(relayer.key, false);
+                self.release_sponsored_spend(&intent.id);
                 Err(anyhow!("Claim failed: {}", e))
             }
         }
     }
 
+    fn release_sponsored_spend(&self, intent_id: &str) {
+        if self.sponsorship.daily_cap_wei == 0 {
+            return;
+        }
+        if let Err(e) = self.database.release_sponsored_spend(intent_id) {
+            error!(
+                "❌ Failed to release sponsored spend for {}: {}",
+                intent_id, e
+            );
+        }
+    }
+
+    /// Decide whether the relayer should pay gas to claim this intent for
+    /// the user, and what it is expected to cost. The cost is `0` when no cap
+    /// needs it.
+    pub(crate) async fn check_sponsorship(
+        &self,
+        relayer: &ChainRelayer,
+        intent: &Intent,
+        chain_id: u32,
+    ) -> Result<Option<u128>> {
+        let policy = &self.sponsorship;
+
+        if !policy.enabled {
+            debug!("Claim sponsorship disabled, skipping {}", intent.id);
+            return Ok(None);
+        }
+
+        let sponsored = self
+            .database
+            .get_claim_sponsorship(&intent.id)?
+            .map(|s| s.sponsored)
+            .unwrap_or(policy.default_sponsored);
+
+        if !sponsored {
+            debug!("Intent {} opted out of claim sponsorship", intent.id);
+            return Ok(None);
+        }
+
+        if policy.max_cost_per_claim_wei == 0 && policy.daily_cap_wei == 0 {
+            return Ok(Some(0));
+        }
+
+        let estimated_cost = relayer.estimate_claim_cost().await?.as_u128();
+
+        if policy.max_cost_per_claim_wei > 0 && estimated_cost > policy.max_cost_per_claim_wei {
+            warn!(
+                "⚠️ Sponsored claim for {} too expensive: {} > {} wei",
+                intent.id, estimated_cost, policy.max_cost_per_claim_wei
+            );
+            return Ok(None);
+        }
+
+        if policy.daily_cap_wei > 0 {
+            let since = chrono::Utc::now() - chrono::Duration::hours(24);
+            let spent = self.database.get_sponsored_spend_since(chain_id, since)?;
+
+            if spent.saturating_add(estimated_cost) > policy.daily_cap_wei {
+                warn!(
+                    "⚠️ Daily sponsorship cap reached on chain {}: spent {} + {} > {} wei",
+                    chain_id, spent, estimated_cost, policy.daily_cap_wei
+                );
+                return Ok(None);
+            }
+        }
+
+        Ok(Some(estimated_cost))
+    }
+
+    async fn record_sponsored_claim(
+        &self,
+        relayer: &ChainRelayer,
+        intent_id: &str,
+        chain_id: u32,
+        txid: &str,
+    ) {
+        let (gas_used, cost) = match relayer.get_transaction_cost(txid).await {
+            Ok(cost) => cost,
+            Err(e) => {
+                warn!("⚠️ Failed to read claim cost for {}: {}", intent_id, e);
+                return;
+            }
+        };
+
+        if let Err(e) = self.database.record_sponsored_claim(
+            intent_id,
+            chain_id,
+            txid,
+            gas_used,
+            &cost.to_string(),
+        ) {
+            error!(
+                "❌ Failed to record sponsored claim for {}: {}",
+                intent_id, e
+            );
+        }
+    }
+
     pub async fn handle_refund(&self, intent: &Intent) -> Result<()> {
         info!(
             "♻️ Refunding intent {} on {}",
             intent.id, intent.source_chain
         );
 
-        let result = match intent.source_chain.as_str() {
-            "ethereum" | "11155111" => self.ethereum_relayer.refund_intent(&intent.id).await,
-            "mantle" | "5003" => self.mantle_relayer.refund_intent(&intent.id).await,
-            _ => return Err(anyhow!("Unsupported source chain: {}", intent.source_chain)),
-        };
+        let relayer = self
+            .chains
+            .resolve(&intent.source_chain)
+            .map_err(|_| anyhow!("Unsupported source chain: {}", intent.source_chain))?;
+        let result = relayer.execute_refund(&intent.id).await;
 
         match result {
             Ok(_) => {
@@ -366,6 +588,18 @@ impl BridgeCoordinator {
         Ok(())
     }
 
+    /// Decrypt with the relayer key, via the signing service when one is configured.
+    pub(crate) async fn decrypt_privacy_param(&self, ciphertext: &str) -> Result<String> {
+        if let Some(remote) = self.ethereum_relayer.client.signer().remote() {
+            return remote.decrypt(ciphertext).await.map_err(|e| anyhow!(e));
+        }
+
+        let relayer_private_key = std::env::var("RELAYER_PRIVATE_KEY")
+            .map_err(|_| anyhow!("RELAYER_PRIVATE_KEY not set"))?;
+
+        decrypt_with_ecies(ciphertext, &relayer_private_key)
+    }
+
     async fn record_error(&self, error: String) {
         let mut metrics = self.metrics.write().await;
         metrics.last_error = Some(error);
@@ -385,16 +619,44 @@ impl BridgeCoordinator {
     }
 
     pub fn is_token_supported(&self, token_address: &str, chain_id: u32) -> bool {
-        TokenType::from_address(token_address)
-            .map(|token_type| {
-                let dest_address = match chain_id {
-                    11155111 => token_type.get_ethereum_address(),
-                    5003 => token_type.get_mantle_address(),
-                    _ => return false,
-                };
-                dest_address != "0x0000000000000000000000000000000000000000"
-                    || token_type == TokenType::ETH
-            })
-            .unwrap_or(false)
+        Chain::from_id(chain_id as u64).is_some() && self.tokens.resolve_str(token_address).is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{random_hash, test_database};
+    use serial_test::serial;
+
+    #[test]
+    #[serial(db)]
+    fn test_sponsored_spend_is_reserved_before_claiming() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+        let since = chrono::Utc::now() - chrono::Duration::hours(24);
+        let (first, second) = (random_hash(), random_hash());
+
+        // Two claims that each fit the cap, but not together
+        assert!(database.reserve_sponsored_spend(&first, 5003, 600, 1000, since)?);
+        assert!(!database.reserve_sponsored_spend(&second, 5003, 600, 1000, since)?);
+        assert_eq!(database.get_sponsored_spend_since(5003, since)?, 600);
+        // Other chains have their own cap, and a retry replaces its own hold
+        assert!(database.reserve_sponsored_spend(&second, 11155111, 600, 1000, since)?);
+        assert!(database.reserve_sponsored_spend(&first, 5003, 700, 1000, since)?);
+        assert_eq!(database.get_sponsored_spend_since(5003, since)?, 700);
+
+        // A claim that was never sent frees its hold
+        database.release_sponsored_spend(&first)?;
+        assert!(database.reserve_sponsored_spend(&second, 5003, 600, 1000, since)?);
+
+        // A sent claim keeps its real cost
+        database.record_sponsored_claim(&second, 5003, &random_hash(), 21000, "550")?;
+        database.release_sponsored_spend(&second)?;
+        assert_eq!(database.get_sponsored_spend_since(5003, since)?, 550);
+        assert!(!database.reserve_sponsored_spend(&first, 5003, 600, 1000, since)?);
+
+        Ok(())
     }
 }
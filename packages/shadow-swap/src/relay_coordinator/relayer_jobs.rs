@@ -0,0 +1,311 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{Context, Result, bail};
+use chrono::Utc;
+use mantle_core::parse;
+use tracing::{error, warn};
+
+use crate::{
+    database::{database::Database, model::DbRelayerJob},
+    models::model::Intent,
+};
+
+/// Job statuses, in the order a job normally moves through them.
+pub const JOB_STATUSES: [&str; 4] = ["pending", "done", "dead", "cancelled"];
+
+/// How failed registration and settlement attempts are retried.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelayerJobPolicy {
+    /// Failed attempts before a job is dead-lettered.
+    pub max_attempts: i32,
+    /// Wait after the first failure, doubled after each one after that.
+    pub base_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RelayerJobPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            base_backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+impl RelayerJobPolicy {
+    /// Reads `RELAYER_JOB_MAX_ATTEMPTS`, `RELAYER_JOB_BACKOFF_SECS` and
+    /// `RELAYER_JOB_MAX_BACKOFF_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(attempts) = var("RELAYER_JOB_MAX_ATTEMPTS") {
+            policy.max_attempts = attempts
+                .trim()
+                .parse()
+                .context("Invalid RELAYER_JOB_MAX_ATTEMPTS")?;
+        }
+        if let Some(secs) = var("RELAYER_JOB_BACKOFF_SECS") {
+            policy.base_backoff = Duration::from_secs(
+                secs.trim()
+                    .parse()
+                    .context("Invalid RELAYER_JOB_BACKOFF_SECS")?,
+            );
+        }
+        if let Some(secs) = var("RELAYER_JOB_MAX_BACKOFF_SECS") {
+            policy.max_backoff = Duration::from_secs(
+                secs.trim()
+                    .parse()
+                    .context("Invalid RELAYER_JOB_MAX_BACKOFF_SECS")?,
+            );
+        }
+
+        if policy.max_attempts < 1 {
+            bail!("RELAYER_JOB_MAX_ATTEMPTS must be at least 1");
+        }
+        if policy.base_backoff.is_zero() {
+            bail!("RELAYER_JOB_BACKOFF_SECS must be at least 1");
+        }
+        if policy.max_backoff < policy.base_backoff {
+            bail!("RELAYER_JOB_MAX_BACKOFF_SECS must not be below RELAYER_JOB_BACKOFF_SECS");
+        }
+
+        Ok(policy)
+    }
+
+    /// Wait before the next attempt once `attempts` have failed, or `None`
+    /// when the job has used them all up.
+    pub fn retry_after(&self, attempts: i32) -> Option<Duration> {
+        if attempts >= self.max_attempts {
+            return None;
+        }
+        let doublings = attempts.saturating_sub(1).clamp(0, 31) as u32;
+        Some(
+            self.base_backoff
+                .saturating_mul(2u32.saturating_pow(doublings))
+                .min(self.max_backoff),
+        )
+    }
+}
+
+/// Which worker a job belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    Registration,
+    Settlement,
+}
+
+impl JobKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Registration => "registration",
+            Self::Settlement => "settlement",
+        }
+    }
+
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw {
+            "registration" => Some(Self::Registration),
+            "settlement" => Some(Self::Settlement),
+            _ => None,
+        }
+    }
+}
+
+/// Why a job could not be retried or cancelled, mapped to an HTTP status by
+/// the route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum JobRejection {
+    NotFound,
+    /// The job is already in a status the action does not apply to.
+    Finished(String),
+}
+
+impl std::fmt::Display for JobRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "Job not found"),
+            Self::Finished(status) => write!(f, "Job is {}", status),
+        }
+    }
+}
+
+/// Attempt counts and backoff of the registration and settlement workers,
+/// kept in `relayer_jobs` so they survive restarts.
+#[derive(Clone)]
+pub struct RelayerJobs {
+    database: Arc<Database>,
+    pub policy: RelayerJobPolicy,
+}
+
+impl RelayerJobs {
+    pub fn new(database: Arc<Database>, policy: RelayerJobPolicy) -> Self {
+        Self { database, policy }
+    }
+
+    /// The intents whose `kind` job is due now, opening jobs for intents
+    /// seen for the first time. Intents still backing off, or whose job is
+    /// dead or cancelled, are left out.
+    pub fn due(&self, kind: JobKind, intents: Vec<Intent>) -> Result<Vec<Intent>> {
+        let ids: Vec<String> = intents.iter().map(|intent| intent.id.to_string()).collect();
+        let jobs = self.database.open_relayer_jobs(kind.as_str(), &ids)?;
+
+        let now = Utc::now();
+        Ok(intents
+            .into_iter()
+            .filter(|intent| {
+                jobs.iter().any(|job| {
+                    job.intent_id == intent.id.to_lowercase()
+                        && job.status == "pending"
+                        && job.next_attempt_at <= now
+                })
+            })
+            .collect())
+    }
+
+    pub fn succeeded(&self, kind: JobKind, intent_id: &str) -> Result<()> {
+        self.database.complete_relayer_job(kind.as_str(), intent_id)
+    }
+
+    /// Record a failed attempt and schedule the next one, dead-lettering
+    /// the job once it runs out of attempts.
+    pub fn failed(&self, kind: JobKind, intent_id: &str, err: &anyhow::Error) -> Result<()> {
+        let now = Utc::now();
+        let job = self.database.fail_relayer_job(
+            kind.as_str(),
+            intent_id,
+            &format!("{:#}", err),
+            |attempts| {
+                self.policy
+                    .retry_after(attempts)
+                    .map(|wait| now + chrono::Duration::from_std(wait).unwrap_or_default())
+            },
+        )?;
+
+        match job {
+            Some(job) if job.status == "dead" => error!(
+                "☠️ {} job for intent {} dead-lettered after {} attempts",
+                job.kind,
+                parse::short(&job.intent_id),
+                job.attempts
+            ),
+            Some(job) => warn!(
+                "🔁 {} of intent {} failed (attempt {}/{}), retrying at {}",
+                job.kind,
+                parse::short(&job.intent_id),
+                job.attempts,
+                self.policy.max_attempts,
+                job.next_attempt_at
+            ),
+            None => {}
+        }
+        Ok(())
+    }
+
+    pub fn list(
+        &self,
+        status: Option<&str>,
+        kind: Option<JobKind>,
+        limit: i64,
+    ) -> Result<Vec<DbRelayerJob>> {
+        self.database
+            .list_relayer_jobs(status, kind.as_ref().map(JobKind::as_str), limit)
+    }
+
+    /// Reopen a job with a fresh set of attempts, due at once.
+    pub fn retry(&self, id: i64) -> Result<std::result::Result<DbRelayerJob, JobRejection>> {
+        self.transition(id, &["pending", "dead", "cancelled"], "pending")
+    }
+
+    /// Stop retrying a job; its intent is left to the operator.
+    pub fn cancel(&self, id: i64) -> Result<std::result::Result<DbRelayerJob, JobRejection>> {
+        self.transition(id, &["pending", "dead"], "cancelled")
+    }
+
+    fn transition(
+        &self,
+        id: i64,
+        from: &[&str],
+        status: &str,
+    ) -> Result<std::result::Result<DbRelayerJob, JobRejection>> {
+        let Some(job) = self.database.get_relayer_job(id)? else {
+            return Ok(Err(JobRejection::NotFound));
+        };
+        match self.database.transition_relayer_job(id, from, status)? {
+            Some(job) => Ok(Ok(job)),
+            None => Ok(Err(JobRejection::Finished(job.status))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{IntentFactory, test_database};
+    use serial_test::serial;
+
+    #[test]
+    fn test_retry_after_doubles_and_caps() {
+        let policy = RelayerJobPolicy {
+            max_attempts: 5,
+            base_backoff: Duration::from_secs(10),
+            max_backoff: Duration::from_secs(60),
+        };
+
+        assert_eq!(policy.retry_after(1), Some(Duration::from_secs(10)));
+        assert_eq!(policy.retry_after(2), Some(Duration::from_secs(20)));
+        assert_eq!(policy.retry_after(3), Some(Duration::from_secs(40)));
+        assert_eq!(policy.retry_after(4), Some(Duration::from_secs(60)));
+        assert_eq!(policy.retry_after(5), None);
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_job_lifecycle() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+        let intent = IntentFactory::registered().build();
+        database.create_intent(&intent)?;
+        let jobs = RelayerJobs::new(
+            Arc::new(database),
+            RelayerJobPolicy {
+                max_attempts: 2,
+                ..RelayerJobPolicy::default()
+            },
+        );
+        let kind = JobKind::Registration;
+        let err = anyhow::anyhow!("rpc timeout");
+
+        assert_eq!(jobs.due(kind, vec![intent.clone()])?.len(), 1);
+
+        // Backing off after the first failure
+        jobs.failed(kind, &intent.id, &err)?;
+        assert!(jobs.due(kind, vec![intent.clone()])?.is_empty());
+
+        // Out of attempts
+        jobs.failed(kind, &intent.id, &err)?;
+        let job = jobs.list(Some("dead"), Some(kind), 10)?;
+        let job = job
+            .iter()
+            .find(|job| job.intent_id == intent.id.to_lowercase())
+            .expect("dead job");
+        assert_eq!(job.attempts, 2);
+        assert_eq!(job.last_error.as_deref(), Some("rpc timeout"));
+        assert!(jobs.due(kind, vec![intent.clone()])?.is_empty());
+
+        // Retried by an operator, then done
+        let retried = jobs.retry(job.id)?.expect("retry");
+        assert_eq!((retried.status.as_str(), retried.attempts), ("pending", 0));
+        assert_eq!(jobs.due(kind, vec![intent.clone()])?.len(), 1);
+        jobs.succeeded(kind, &intent.id)?;
+        assert_eq!(
+            jobs.cancel(job.id)?.err(),
+            Some(JobRejection::Finished("done".to_string()))
+        );
+        assert_eq!(jobs.retry(-1)?.err(), Some(JobRejection::NotFound));
+
+        Ok(())
+    }
+}
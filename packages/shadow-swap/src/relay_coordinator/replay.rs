@@ -0,0 +1,926 @@
+use anyhow::{Context, Result, anyhow};
+use ethers::{
+    abi::Detokenize,
+    contract::ContractCall,
+    providers::Middleware,
+    types::{Address, Bytes, U256},
+};
+use mantle_core::{amount::rescale, chain::Chain, parse};
+use serde::Serialize;
+use tracing::info;
+
+use crate::{
+    chains::relayer::ChainClient,
+    models::model::{Intent, IntentStatus},
+    relay_coordinator::{
+        model::{BridgeCoordinator, ChainRelayer},
+        reconcile::OnChainIntentState,
+        settlement_strategy::{FillObservation, SettlementStep, SettlementStrategy, next_step},
+    },
+};
+use mantle_core::abi::{IntentPoolContract, SettlementContract};
+
+/// Leaf limit the settlement worker passes when building fill proofs.
+const SETTLEMENT_FILL_PROOF_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReplayAction {
+    Register,
+    /// Already registered on chain, only the DB status would change.
+    MarkRegistered,
+    Settle,
+    /// Already settled on chain, only the DB status would change.
+    MarkSettled,
+    Claim,
+    Refund,
+    Wait,
+    Nothing,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayDecision {
+    pub action: ReplayAction,
+    /// Loop that would pick the intent up, `None` when nothing polls this status.
+    pub worker: Option<&'static str>,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayStep {
+    pub step: &'static str,
+    pub detail: String,
+}
+
+/// A transaction the relayer would send, simulated at the pinned block.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayCall {
+    pub chain: &'static str,
+    pub contract: &'static str,
+    pub to: String,
+    pub function: &'static str,
+    pub args: Vec<String>,
+    pub calldata: String,
+    pub block: u64,
+    pub simulation: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IntentReplay {
+    pub intent_id: String,
+    pub status: String,
+    pub source_chain: &'static str,
+    pub dest_chain: &'static str,
+    pub source_block: u64,
+    pub dest_block: u64,
+    /// Source block timestamp, used in place of the wall clock for deadlines.
+    pub now: u64,
+    pub on_chain: OnChainIntentState,
+    pub decision: ReplayDecision,
+    pub steps: Vec<ReplayStep>,
+    pub calls: Vec<ReplayCall>,
+    /// Why the coordinator would fail before sending, if it would.
+    pub error: Option<String>,
+}
+
+impl IntentReplay {
+    fn step(&mut self, step: &'static str, detail: impl Into<String>) {
+        let detail = detail.into();
+        info!("   ▶ {}: {}", step, detail);
+        self.steps.push(ReplayStep { step, detail });
+    }
+}
+
+/// What the workers and the coordinator loop do with an intent in this
+/// state. Mirrors the registration worker (`committed`), the settlement
+/// worker (`filled`) and the claim loop (`solver_paid`).
+pub fn decide(intent: &Intent, on_chain: &OnChainIntentState, now: u64) -> ReplayDecision {
+    let (action, worker, reason) = match intent.status {
+        IntentStatus::Committed if intent.deadline < now => (
+            ReplayAction::Refund,
+            Some("registration"),
+            format!(
+                "Deadline {} passed before {}: marked expired and refunded on the source chain",
+                intent.deadline, now
+            ),
+        ),
+        IntentStatus::Committed if on_chain.registered => (
+            ReplayAction::MarkRegistered,
+            Some("registration"),
+            "Already registered on the destination, only the DB status moves to registered"
+                .to_string(),
+        ),
+        IntentStatus::Committed => (
+            ReplayAction::Register,
+            Some("registration"),
+            "Committed on the source chain and not yet registered on the destination".to_string(),
+        ),
+        IntentStatus::Underpaid if intent.deadline < now => (
+            ReplayAction::Refund,
+            Some("registration"),
+            "Underpaid deposit past its deadline: refunded on the source chain".to_string(),
+        ),
+        IntentStatus::Underpaid => (
+            ReplayAction::Wait,
+            None,
+            "Underpaid deposit, never registered; refunded once the deadline passes".to_string(),
+        ),
+        IntentStatus::Filled if !on_chain.filled && !on_chain.settled => (
+            ReplayAction::Wait,
+            Some("settlement"),
+            "No fill on the destination settlement yet, the settlement worker skips it".to_string(),
+        ),
+        IntentStatus::Filled => (
+            ReplayAction::Settle,
+            Some("settlement"),
+            "Filled on the destination, the solver is paid out on the source chain".to_string(),
+        ),
+        IntentStatus::SolverPaid => (
+            ReplayAction::Claim,
+            Some("claim"),
+            "Solver paid, the relayer claims the withdrawal for the user".to_string(),
+        ),
+        IntentStatus::Registered => (
+            ReplayAction::Wait,
+            None,
+            "Waiting for a solver fill; nothing acts until IntentFilled is indexed".to_string(),
+        ),
+        IntentStatus::Created | IntentStatus::Pending => (
+            ReplayAction::Wait,
+            None,
+            "Waiting for the source commitment to be indexed".to_string(),
+        ),
+        IntentStatus::UserClaimed
+        | IntentStatus::Refunded
+        | IntentStatus::Failed
+        | IntentStatus::Expired => (
+            ReplayAction::Nothing,
+            None,
+            format!("Status {} is terminal", intent.status.as_str()),
+        ),
+    };
+
+    ReplayDecision {
+        action,
+        worker,
+        reason,
+    }
+}
+
+/// Let the direction's settlement strategy decide whether a `settle` happens now.
+fn apply_settlement_strategy(
+    decision: ReplayDecision,
+    strategy: &dyn SettlementStrategy,
+    fill: &FillObservation,
+    now: u64,
+) -> ReplayDecision {
+    if decision.action != ReplayAction::Settle {
+        return decision;
+    }
+
+    let (action, reason) = match next_step(strategy, fill, now) {
+        SettlementStep::Settle => (ReplayAction::Settle, decision.reason),
+        SettlementStep::MarkSettled => (
+            ReplayAction::MarkSettled,
+            "Already settled on the source chain, only the DB status moves to solver_paid"
+                .to_string(),
+        ),
+        SettlementStep::Wait(reason) => (ReplayAction::Wait, reason),
+    };
+
+    ReplayDecision {
+        action,
+        worker: decision.worker,
+        reason: format!("{} ({} settlement)", reason, strategy.name()),
+    }
+}
+
+fn hex32(value: [u8; 32]) -> String {
+    format!("0x{}", hex::encode(value))
+}
+
+async fn simulate<D: Detokenize>(
+    chain: Chain,
+    contract: &'static str,
+    function: &'static str,
+    args: Vec<String>,
+    call: ContractCall<ChainClient, D>,
+    block: u64,
+) -> ReplayCall {
+    let to = call
+        .tx
+        .to_addr()
+        .map(|address| format!("{:?}", address))
+        .unwrap_or_default();
+    let calldata = call
+        .calldata()
+        .map(|data| data.to_string())
+        .unwrap_or_default();
+
+    let simulation = match call.block(block).call().await {
+        Ok(_) => "ok".to_string(),
+        Err(e) => format!("revert: {}", ChainRelayer::extract_revert_reason(&e)),
+    };
+
+    ReplayCall {
+        chain: chain.name(),
+        contract,
+        to,
+        function,
+        args,
+        calldata,
+        block,
+        simulation,
+    }
+}
+
+impl BridgeCoordinator {
+    /// Rebuild what the relayer would do next for one intent, with chain
+    /// reads pinned to `source_block` / `dest_block` (latest when unset).
+    /// Every transaction is only simulated; nothing is sent or written.
+    pub async fn replay_intent(
+        &self,
+        intent_id: &str,
+        source_block: Option<u64>,
+        dest_block: Option<u64>,
+    ) -> Result<IntentReplay> {
+        let intent = self
+            .database
+            .get_intent_by_id(intent_id)?
+            .ok_or_else(|| anyhow!("Intent {} not found", intent_id))?;
+
+        let source = Chain::parse(&intent.source_chain)?;
+        let dest = Chain::parse(&intent.dest_chain)?;
+        let id = intent.id.h256().0;
+
+        let (source_block, now) = self.pinned_block(source, source_block).await?;
+        let (dest_block, _) = self.pinned_block(dest, dest_block).await?;
+
+        info!(
+            "🔁 Replaying {} ({} -> {}) at {} block {} / {} block {}",
+            intent.id,
+            source.name(),
+            dest.name(),
+            source.name(),
+            source_block,
+            dest.name(),
+            dest_block
+        );
+
+        let (commitment, .., filled_on_source, refunded) = self
+            .intent_pool(source)
+            .get_intent(id)
+            .block(source_block)
+            .call()
+            .await
+            .context("Failed to read source intent")?;
+        let (.., registered) = self
+            .settlement(dest)
+            .get_intent_params(id)
+            .block(dest_block)
+            .call()
+            .await
+            .context("Failed to read destination registration")?;
+        let (solver, _, _, _, filled_at, claimed) = self
+            .settlement(dest)
+            .get_fill(id)
+            .block(dest_block)
+            .call()
+            .await
+            .context("Failed to read destination fill")?;
+
+        let on_chain = OnChainIntentState {
+            created: commitment != [0u8; 32],
+            registered,
+            filled: solver != Address::zero(),
+            claimed,
+            settled: filled_on_source,
+            refunded,
+        };
+
+        let fill = FillObservation {
+            filled_at: on_chain.filled.then_some(filled_at as u64),
+            settled_on_source: on_chain.settled,
+        };
+        let decision = apply_settlement_strategy(
+            decide(&intent, &on_chain, now),
+            self.settlement_strategies.for_route(source, dest),
+            &fill,
+            now,
+        );
+
+        // Stored screenings only; replay never calls the compliance provider
+        let decision = match self.database.get_compliance_screening(&intent.id)? {
+            Some(screening)
+                if decision.action == ReplayAction::Register && screening.action == "block" =>
+            {
+                ReplayDecision {
+                    action: ReplayAction::Wait,
+                    worker: decision.worker,
+                    reason: format!(
+                        "Blocked by compliance screening: {}",
+                        screening.reason.unwrap_or_default()
+                    ),
+                }
+            }
+            _ => decision,
+        };
+        info!("   ▶ decision: {:?} ({})", decision.action, decision.reason);
+
+        let mut replay = IntentReplay {
+            intent_id: intent.id.to_string(),
+            status: intent.status.as_str().to_string(),
+            source_chain: source.name(),
+            dest_chain: dest.name(),
+            source_block,
+            dest_block,
+            now,
+            on_chain,
+            decision: decision.clone(),
+            steps: Vec::new(),
+            calls: Vec::new(),
+            error: None,
+        };
+
+        replay.step(
+            "direction",
+            format!(
+                "{} ({}) -> {} ({})",
+                source.name(),
+                source.id(),
+                dest.name(),
+                dest.id()
+            ),
+        );
+
+        let planned = match decision.action {
+            ReplayAction::Register => self.plan_register(&intent, source, dest, &mut replay).await,
+            ReplayAction::Settle => self.plan_settle(&intent, source, dest, &mut replay).await,
+            ReplayAction::Claim => self.plan_claim(&intent, dest, &mut replay).await,
+            ReplayAction::Refund => self.plan_refund(&intent, source, &mut replay).await,
+            ReplayAction::MarkRegistered
+            | ReplayAction::MarkSettled
+            | ReplayAction::Wait
+            | ReplayAction::Nothing => Ok(()),
+        };
+
+        if let Err(e) = planned {
+            replay.step("error", e.to_string());
+            replay.error = Some(e.to_string());
+        }
+
+        Ok(replay)
+    }
+
+    fn intent_pool(&self, chain: Chain) -> &IntentPoolContract<ChainClient> {
+        &self.relayer(chain).intent_pool
+    }
+
+    fn settlement(&self, chain: Chain) -> &SettlementContract<ChainClient> {
+        &self.relayer(chain).settlement
+    }
+
+    /// `(number, timestamp)` of the requested block, or the latest one.
+    async fn pinned_block(&self, chain: Chain, block: Option<u64>) -> Result<(u64, u64)> {
+        let client = &self.relayer(chain).client;
+
+        let number = match block {
+            Some(number) => number,
+            None => client
+                .get_block_number()
+                .await
+                .map_err(|e| anyhow!("Failed to read {} block number: {}", chain.name(), e))?
+                .as_u64(),
+        };
+
+        let header = client
+            .get_block(number)
+            .await
+            .map_err(|e| anyhow!("Failed to read {} block {}: {}", chain.name(), number, e))?
+            .ok_or_else(|| anyhow!("{} block {} not found", chain.name(), number))?;
+
+        Ok((number, header.timestamp.as_u64()))
+    }
+
+    async fn check_paused(
+        &self,
+        chain: Chain,
+        contract: &'static str,
+        paused: ContractCall<ChainClient, bool>,
+        block: u64,
+        replay: &mut IntentReplay,
+    ) {
+        match paused.block(block).call().await {
+            Ok(true) => replay.step(
+                "contract_guard",
+                format!(
+                    "{} {} is paused, the send would be held",
+                    chain.name(),
+                    contract
+                ),
+            ),
+            Ok(false) => replay.step(
+                "contract_guard",
+                format!("{} {} is not paused", chain.name(), contract),
+            ),
+            Err(e) => replay.step("contract_guard", format!("Could not read paused(): {}", e)),
+        }
+    }
+
+    async fn plan_register(
+        &self,
+        intent: &Intent,
+        source: Chain,
+        dest: Chain,
+        replay: &mut IntentReplay,
+    ) -> Result<()> {
+        let block = replay.dest_block;
+        let settlement = self.settlement(dest);
+
+        let commitment = intent
+            .source_commitment
+            .as_ref()
+            .ok_or_else(|| anyhow!("Missing commitment"))?;
+
+        let token_type = self.tokens.resolve_str(&intent.source_token)?;
+        let dest_token = self
+            .tokens
+            .address(token_type, dest)
+            .ok_or_else(|| anyhow!("Token {} is not listed", token_type.symbol()))?;
+        let dest_type = self
+            .tokens
+            .resolve(dest_token, dest)
+            .ok_or_else(|| anyhow!("Unsupported token address: {:?}", dest_token))?;
+        let dest_amount = rescale(
+            parse::amount("dest_amount", &intent.dest_amount)?,
+            token_type.decimals(),
+            dest_type.decimals(),
+        )?;
+        replay.step(
+            "token",
+            format!(
+                "{} {} on {} -> {} {:?} on {} ({} decimals -> {})",
+                token_type.symbol(),
+                intent.source_token,
+                source.name(),
+                dest_type.symbol(),
+                dest_token,
+                dest.name(),
+                token_type.decimals(),
+                dest_type.decimals()
+            ),
+        );
+        replay.step(
+            "amount",
+            format!("{} -> {}", intent.dest_amount, dest_amount),
+        );
+
+        let tree_name = format!("{}_commitments", source.name());
+        let tree = self
+            .database
+            .get_merkle_tree_by_name(&tree_name)?
+            .ok_or_else(|| anyhow!("{} tree metadata not found", tree_name))?;
+        let synced = hex32(
+            settlement
+                .source_chain_commitment_roots(source.id())
+                .block(block)
+                .call()
+                .await
+                .context("Failed to read synced commitment root")?,
+        );
+
+        // The worker rebuilds the tree first; the replay uses it as stored
+        if synced.eq_ignore_ascii_case(&tree.root) {
+            replay.step(
+                "root_sync",
+                format!(
+                    "{} already holds {} root {}",
+                    dest.name(),
+                    tree_name,
+                    synced
+                ),
+            );
+        } else {
+            replay.step(
+                "root_sync",
+                format!(
+                    "{} holds {} but the DB {} root is {}, the root is synced first",
+                    dest.name(),
+                    synced,
+                    tree_name,
+                    tree.root
+                ),
+            );
+            let root = parse::hex32("root", &tree.root)?;
+            replay.calls.push(
+                simulate(
+                    dest,
+                    "settlement",
+                    "syncSourceChainCommitmentRoot",
+                    vec![source.id().to_string(), tree.root.clone()],
+                    settlement.sync_source_chain_commitment_root(source.id(), root),
+                    block,
+                )
+                .await,
+            );
+        }
+
+        let (proof, leaf_index, root) = self.merkle_tree_manager.proof_generator.generate_proof(
+            source.name(),
+            commitment,
+            tree.leaf_count as usize,
+        )?;
+        replay.step(
+            "proof",
+            format!(
+                "Commitment {} at leaf {} of {}, {} siblings, root {}",
+                commitment,
+                leaf_index,
+                tree.leaf_count,
+                proof.len(),
+                root
+            ),
+        );
+
+        self.check_paused(dest, "settlement", settlement.paused(), block, replay)
+            .await;
+
+        let proof_bytes = proof
+            .iter()
+            .map(|p| parse::hex32("proof element", p))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let call = settlement.register_intent(
+            intent.id.h256().0,
+            parse::hex32("commitment", commitment)?,
+            dest_token,
+            dest_amount,
+            source.id(),
+            intent.deadline,
+            parse::hex32("root", &root)?,
+            proof_bytes,
+            U256::from(leaf_index),
+        );
+        replay.calls.push(
+            simulate(
+                dest,
+                "settlement",
+                "registerIntent",
+                vec![
+                    intent.id.to_string(),
+                    commitment.to_string(),
+                    format!("{:?}", dest_token),
+                    dest_amount.to_string(),
+                    source.id().to_string(),
+                    intent.deadline.to_string(),
+                    root,
+                    format!("[{}]", proof.join(", ")),
+                    leaf_index.to_string(),
+                ],
+                call,
+                block,
+            )
+            .await,
+        );
+
+        Ok(())
+    }
+
+    async fn plan_settle(
+        &self,
+        intent: &Intent,
+        source: Chain,
+        dest: Chain,
+        replay: &mut IntentReplay,
+    ) -> Result<()> {
+        let block = replay.source_block;
+        let intent_pool = self.intent_pool(source);
+
+        let tree_name = format!("{}_fills", dest.name());
+        let db_root = self
+            .database
+            .get_latest_root(&tree_name)?
+            .map(|root| format!("0x{}", root.trim_start_matches("0x").to_lowercase()))
+            .unwrap_or_default();
+        let synced = hex32(
+            intent_pool
+                .dest_chain_fill_roots(dest.id())
+                .block(block)
+                .call()
+                .await
+                .context("Failed to read synced fill root")?,
+        );
+
+        if synced.eq_ignore_ascii_case(&db_root) {
+            replay.step(
+                "fill_root",
+                format!(
+                    "{} holds the DB {} root {}",
+                    source.name(),
+                    tree_name,
+                    synced
+                ),
+            );
+        } else {
+            replay.step(
+                "fill_root",
+                format!(
+                    "{} holds {} but the DB {} root is {}; the worker waits 60s for them to match and fails otherwise",
+                    source.name(),
+                    synced,
+                    tree_name,
+                    db_root
+                ),
+            );
+        }
+
+        let (proof, leaf_index, root) = self
+            .merkle_tree_manager
+            .proof_generator
+            .generate_fill_proof(dest.name(), &intent.id, SETTLEMENT_FILL_PROOF_LIMIT)?;
+        replay.step(
+            "proof",
+            format!(
+                "Fill at leaf {}, {} siblings, root {}",
+                leaf_index,
+                proof.len(),
+                root
+            ),
+        );
+
+        let solver_address = intent
+            .solver_address
+            .as_ref()
+            .ok_or_else(|| anyhow!("Missing solver address"))?;
+        replay.step("solver", solver_address.clone());
+
+        self.check_paused(source, "intent_pool", intent_pool.paused(), block, replay)
+            .await;
+
+        let proof_bytes = proof
+            .iter()
+            .map(|p| parse::hex32("proof element", p))
+            .collect::<Result<Vec<_>, _>>()?;
+        let solver: Address = solver_address.parse().context("Invalid solver address")?;
+
+        let call = intent_pool.settle_intent(
+            intent.id.h256().0,
+            solver,
+            proof_bytes,
+            U256::from(leaf_index),
+        );
+        replay.calls.push(
+            simulate(
+                source,
+                "intent_pool",
+                "settleIntent",
+                vec![
+                    intent.id.to_string(),
+                    solver_address.clone(),
+                    format!("[{}]", proof.join(", ")),
+                    leaf_index.to_string(),
+                ],
+                call,
+                block,
+            )
+            .await,
+        );
+
+        Ok(())
+    }
+
+    async fn plan_claim(
+        &self,
+        intent: &Intent,
+        dest: Chain,
+        replay: &mut IntentReplay,
+    ) -> Result<()> {
+        let block = replay.dest_block;
+        let settlement = self.settlement(dest);
+
+        let sponsored = self
+            .check_sponsorship(self.relayer(dest), intent, dest.id())
+            .await?;
+        if sponsored.is_none() {
+            replay.step(
+                "sponsorship",
+                "Claim sponsorship declined, the user has to claim themselves",
+            );
+            return Ok(());
+        }
+        replay.step("sponsorship", "The relayer pays for the claim");
+
+        let params = self.database.get_intent_privacy_params(&intent.id)?;
+        let secret = self
+            .claim_secret(&intent.id, params.secret.as_deref())
+            .await?;
+        let nullifier = self
+            .decrypt_privacy_param(
+                params
+                    .nullifier
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("Encrypted nullifier not available"))?,
+            )
+            .await
+            .map_err(|e| anyhow!("Failed to decrypt nullifier: {}", e))?;
+        let recipient = params
+            .recipient
+            .ok_or_else(|| anyhow!("Recipient not available"))?;
+        let claim_auth = hex::decode(
+            params
+                .claim_signature
+                .as_ref()
+                .ok_or_else(|| anyhow!("Claim signature not available"))?
+                .trim_start_matches("0x"),
+        )
+        .map_err(|e| anyhow!("Failed to decode claim signature hex: {}", e))?;
+        replay.step(
+            "privacy_params",
+            format!(
+                "Decrypted secret and nullifier {}, recipient {}",
+                nullifier, recipient
+            ),
+        );
+
+        self.check_paused(dest, "settlement", settlement.paused(), block, replay)
+            .await;
+
+        let recipient_address: Address = recipient.parse().context("Invalid recipient address")?;
+        let intent_id = intent.id.h256().0;
+        let nullifier_bytes = parse::hex32("nullifier", &nullifier)?;
+        let secret = parse::hex32("secret", &secret)?;
+        let claim_auth_hex = format!("0x{}", hex::encode(&claim_auth));
+        let claim_auth = Bytes::from(claim_auth);
+
+        // Printed calldata still carries the secret, as the sent tx would
+        let (function, call, args) = match params.claim_delegate {
+            Some(delegate) => {
+                replay.step(
+                    "claim_delegate",
+                    format!("Claim is delegated to {}", delegate),
+                );
+                let call = settlement.claim_withdrawal_to(
+                    intent_id,
+                    nullifier_bytes,
+                    recipient_address,
+                    parse::address("claim_delegate", &delegate)?,
+                    secret,
+                    claim_auth,
+                );
+                let args = vec![
+                    intent.id.to_string(),
+                    nullifier,
+                    recipient.to_string(),
+                    delegate.to_string(),
+                    "<secret>".to_string(),
+                    claim_auth_hex,
+                ];
+                ("claimWithdrawalTo", call, args)
+            }
+            None => {
+                let call = settlement.claim_withdrawal(
+                    intent_id,
+                    nullifier_bytes,
+                    recipient_address,
+                    secret,
+                    claim_auth,
+                );
+                let args = vec![
+                    intent.id.to_string(),
+                    nullifier,
+                    recipient.to_string(),
+                    "<secret>".to_string(),
+                    claim_auth_hex,
+                ];
+                ("claimWithdrawal", call, args)
+            }
+        };
+        replay
+            .calls
+            .push(simulate(dest, "settlement", function, args, call, block).await);
+
+        Ok(())
+    }
+
+    async fn plan_refund(
+        &self,
+        intent: &Intent,
+        source: Chain,
+        replay: &mut IntentReplay,
+    ) -> Result<()> {
+        let block = replay.source_block;
+        let intent_pool = self.intent_pool(source);
+
+        if replay.on_chain.settled {
+            return Err(anyhow!("Intent already filled, cannot refund"));
+        }
+        if replay.on_chain.refunded {
+            return Err(anyhow!("Intent already refunded"));
+        }
+
+        self.check_paused(source, "intent_pool", intent_pool.paused(), block, replay)
+            .await;
+
+        let call = intent_pool.refund(intent.id.h256().0);
+        replay.calls.push(
+            simulate(
+                source,
+                "intent_pool",
+                "refund",
+                vec![intent.id.to_string()],
+                call,
+                block,
+            )
+            .await,
+        );
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use ethers::types::H256;
+
+    fn intent(status: IntentStatus, deadline: u64) -> Intent {
+        Intent {
+            id: H256::from_low_u64_be(1).into(),
+            user_address: String::new(),
+            source_chain: "ethereum".to_string(),
+            dest_chain: "mantle".to_string(),
+            source_token: String::new(),
+            dest_token: String::new(),
+            amount: "0".parse().unwrap(),
+            dest_amount: "0".parse().unwrap(),
+            source_commitment: None,
+            dest_fill_txid: None,
+            dest_registration_txid: None,
+            source_complete_txid: None,
+            status,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            deadline,
+            refund_address: None,
+            solver_address: None,
+            block_number: None,
+            log_index: None,
+        }
+    }
+
+    #[test]
+    fn test_decide_follows_the_workers() {
+        let created = OnChainIntentState {
+            created: true,
+            ..Default::default()
+        };
+        let registered = OnChainIntentState {
+            registered: true,
+            ..created
+        };
+        let filled = OnChainIntentState {
+            filled: true,
+            ..registered
+        };
+
+        let action = |status, deadline, state: &OnChainIntentState| {
+            decide(&intent(status, deadline), state, 1_000).action
+        };
+
+        assert_eq!(
+            action(IntentStatus::Committed, 2_000, &created),
+            ReplayAction::Register
+        );
+        assert_eq!(
+            action(IntentStatus::Committed, 2_000, &registered),
+            ReplayAction::MarkRegistered
+        );
+        // The deadline is checked before anything else
+        assert_eq!(
+            action(IntentStatus::Committed, 500, &registered),
+            ReplayAction::Refund
+        );
+        assert_eq!(
+            action(IntentStatus::Filled, 2_000, &registered),
+            ReplayAction::Wait
+        );
+        assert_eq!(
+            action(IntentStatus::Filled, 2_000, &filled),
+            ReplayAction::Settle
+        );
+        assert_eq!(
+            action(IntentStatus::SolverPaid, 2_000, &filled),
+            ReplayAction::Claim
+        );
+        assert_eq!(
+            action(IntentStatus::Expired, 500, &created),
+            ReplayAction::Nothing
+        );
+        assert!(
+            decide(&intent(IntentStatus::Registered, 2_000), &registered, 1_000)
+                .worker
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,424 @@
+use std::{collections::HashMap, time::Duration};
+
+use anyhow::{Context, Result, anyhow, bail};
+use chrono::{DateTime, TimeZone, Utc};
+use ethers::types::Address;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+use crate::{
+    database::model::DbIntentReservation, models::model::IntentStatus,
+    relay_coordinator::model::BridgeCoordinator,
+};
+
+/// Exclusive fill windows solvers take on registered intents before
+/// committing capital.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReservationPolicy {
+    pub enabled: bool,
+    /// How long a reservation holds, cut short by the intent's deadline.
+    pub ttl: Duration,
+    pub cleanup_interval: Duration,
+    /// Bearer token of each solver allowed to reserve, keyed by lowercase
+    /// address.
+    pub solver_tokens: HashMap<String, String>,
+}
+
+impl Default for ReservationPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl: Duration::from_secs(30),
+            cleanup_interval: Duration::from_secs(5),
+            solver_tokens: HashMap::new(),
+        }
+    }
+}
+
+impl ReservationPolicy {
+    /// Reads `RESERVATIONS_ENABLED`, `RESERVATION_TTL_SECS`,
+    /// `RESERVATION_CLEANUP_INTERVAL_SECS` and `RESERVATION_SOLVER_TOKENS`
+    /// (`0xsolver=token,...`).
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(enabled) = var("RESERVATIONS_ENABLED") {
+            policy.enabled = enabled
+                .trim()
+                .parse()
+                .context("Invalid RESERVATIONS_ENABLED")?;
+        }
+        if let Some(secs) = var("RESERVATION_TTL_SECS") {
+            policy.ttl = Duration::from_secs(
+                secs.trim()
+                    .parse()
+                    .context("Invalid RESERVATION_TTL_SECS")?,
+            );
+        }
+        if let Some(secs) = var("RESERVATION_CLEANUP_INTERVAL_SECS") {
+            policy.cleanup_interval = Duration::from_secs(
+                secs.trim()
+                    .parse::<u64>()
+                    .context("Invalid RESERVATION_CLEANUP_INTERVAL_SECS")?
+                    .max(1),
+            );
+        }
+        if let Some(raw) = var("RESERVATION_SOLVER_TOKENS") {
+            policy.solver_tokens =
+                parse_solver_tokens(&raw).context("Invalid RESERVATION_SOLVER_TOKENS")?;
+        }
+
+        if policy.ttl.is_zero() || policy.ttl > Duration::from_secs(600) {
+            bail!("RESERVATION_TTL_SECS must be between 1 and 600");
+        }
+        if policy.enabled && policy.solver_tokens.is_empty() {
+            bail!("RESERVATIONS_ENABLED needs at least one solver in RESERVATION_SOLVER_TOKENS");
+        }
+
+        Ok(policy)
+    }
+
+    /// Whether `token` is the bearer token configured for `solver`.
+    pub fn authenticate(&self, solver: &str, token: &str) -> bool {
+        let Some(expected) = self.solver_tokens.get(&solver.to_lowercase()) else {
+            return false;
+        };
+
+        // Length is not secret; the contents are compared in constant time
+        expected.len() == token.len()
+            && expected
+                .bytes()
+                .zip(token.bytes())
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    }
+}
+
+/// `0xsolver=token,...`; addresses are checked and lowercased.
+pub fn parse_solver_tokens(raw: &str) -> Result<HashMap<String, String>> {
+    raw.split(',')
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (solver, token) = entry
+                .split_once('=')
+                .ok_or_else(|| anyhow!("Expected solver=token, got '{}'", entry))?;
+            let solver: Address = solver
+                .trim()
+                .parse()
+                .map_err(|e| anyhow!("Invalid solver address '{}': {}", solver, e))?;
+            let token = token.trim();
+            if token.is_empty() {
+                bail!("Empty token for {:?}", solver);
+            }
+            Ok((format!("{:?}", solver), token.to_string()))
+        })
+        .collect()
+}
+
+/// Why a reservation was refused, mapped to an HTTP status by the route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReservationRejection {
+    NotFound,
+    /// Only `registered` intents can be filled.
+    NotFillable(String),
+    PastDeadline,
+    /// Another solver holds the intent until `expires_at`.
+    Held {
+        expires_at: DateTime<Utc>,
+    },
+    /// This solver's own reservation ran out without a fill; the intent is
+    /// left to the others.
+    Lapsed,
+    Filled,
+}
+
+impl std::fmt::Display for ReservationRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "Intent not found"),
+            Self::NotFillable(status) => write!(f, "Intent is {}, not registered", status),
+            Self::PastDeadline => write!(f, "Intent is past its deadline"),
+            Self::Held { expires_at } => {
+                write!(f, "Reserved by another solver until {}", expires_at)
+            }
+            Self::Lapsed => write!(f, "Your reservation of this intent lapsed"),
+            Self::Filled => write!(f, "Intent is already filled"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReservationDecision {
+    Grant,
+    /// The solver already holds it; the expiry is not extended.
+    Keep,
+    Reject(ReservationRejection),
+}
+
+/// What `solver` gets given the intent's current reservation.
+pub fn decide_reservation(
+    current: Option<&DbIntentReservation>,
+    solver: &str,
+    now: DateTime<Utc>,
+) -> ReservationDecision {
+    let Some(current) = current else {
+        return ReservationDecision::Grant;
+    };
+    let own = current.solver_address.eq_ignore_ascii_case(solver);
+
+    match current.status.as_str() {
+        "filled" => ReservationDecision::Reject(ReservationRejection::Filled),
+        "active" if current.expires_at > now && own => ReservationDecision::Keep,
+        "active" if current.expires_at > now => {
+            ReservationDecision::Reject(ReservationRejection::Held {
+                expires_at: current.expires_at,
+            })
+        }
+        // Expired but not swept yet counts as lapsed
+        "active" | "lapsed" if own => ReservationDecision::Reject(ReservationRejection::Lapsed),
+        _ => ReservationDecision::Grant,
+    }
+}
+
+impl BridgeCoordinator {
+    /// Give `solver` an exclusive window on a registered intent.
+    pub fn reserve_intent(
+        &self,
+        intent_id: &str,
+        solver: &str,
+        policy: &ReservationPolicy,
+    ) -> Result<std::result::Result<DbIntentReservation, ReservationRejection>> {
+        let Some(intent) = self.database.get_intent_by_id(intent_id)? else {
+            return Ok(Err(ReservationRejection::NotFound));
+        };
+        if intent.status != IntentStatus::Registered {
+            return Ok(Err(ReservationRejection::NotFillable(
+                intent.status.as_str().to_string(),
+            )));
+        }
+
+        let now = Utc::now();
+        let deadline = Utc
+            .timestamp_opt(intent.deadline as i64, 0)
+            .single()
+            .unwrap_or(now);
+        if deadline <= now {
+            return Ok(Err(ReservationRejection::PastDeadline));
+        }
+        let expires_at = (now + policy.ttl).min(deadline);
+
+        let mut decision = ReservationDecision::Grant;
+        let granted = self
+            .database
+            .reserve_intent(intent_id, solver, expires_at, |current| {
+                decision = decide_reservation(current, solver, now);
+                decision == ReservationDecision::Grant
+            })?;
+
+        match decision {
+            ReservationDecision::Reject(rejection) => return Ok(Err(rejection)),
+            ReservationDecision::Grant if granted => info!(
+                "🔒 Intent {} reserved by {} until {}",
+                intent_id, solver, expires_at
+            ),
+            _ => {}
+        }
+
+        self.database
+            .get_intent_reservation(intent_id)?
+            .map(Ok)
+            .ok_or_else(|| anyhow!("Reservation of {} disappeared", intent_id))
+    }
+
+    /// Hand an intent back before the reservation runs out. Returns false
+    /// when `solver` held no active reservation on it.
+    pub fn release_intent_reservation(&self, intent_id: &str, solver: &str) -> Result<bool> {
+        let released =
+            self.database
+                .close_intent_reservation(intent_id, Some(solver), "released")?;
+        if released.is_some() {
+            info!("🔓 Intent {} released by {}", intent_id, solver);
+        }
+        Ok(released.is_some())
+    }
+
+    /// Close the reservation of an intent that was just filled on chain.
+    pub fn close_reservation_on_fill(&self, intent_id: &str, filler: &str) -> Result<()> {
+        let Some(reservation) = self
+            .database
+            .close_intent_reservation(intent_id, None, "filled")?
+        else {
+            return Ok(());
+        };
+
+        if !reservation.solver_address.eq_ignore_ascii_case(filler) {
+            warn!(
+                "⚠️ Intent {} was filled by {} during {}'s reservation",
+                intent_id, filler, reservation.solver_address
+            );
+        }
+        Ok(())
+    }
+
+    /// Lapse reservations that ran out without a fill. The intent is open to
+    /// every other solver again, by reservation or straight on chain.
+    pub async fn run_reservation_expiry(&self, policy: ReservationPolicy) {
+        info!(
+            "🔒 Reservation expiry started (every {}s, {}s windows)",
+            policy.cleanup_interval.as_secs(),
+            policy.ttl.as_secs()
+        );
+
+        let mut ticker = interval(policy.cleanup_interval);
+        loop {
+            ticker.tick().await;
+            match self.database.lapse_expired_reservations(Utc::now()) {
+                Ok(lapsed) => {
+                    for reservation in lapsed {
+                        warn!(
+                            "⌛ Reservation of {} by {} lapsed without a fill, open to other solvers",
+                            reservation.intent_id, reservation.solver_address
+                        );
+                    }
+                }
+                Err(e) => error!("❌ Reservation expiry failed: {}", e),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{IntentFactory, test_database};
+    use serial_test::serial;
+
+    const SOLVER: &str = "0x1111111111111111111111111111111111111111";
+    const OTHER: &str = "0x2222222222222222222222222222222222222222";
+
+    fn reservation(solver: &str, status: &str, expires_at: DateTime<Utc>) -> DbIntentReservation {
+        DbIntentReservation {
+            intent_id: "0xabc".to_string(),
+            solver_address: solver.to_string(),
+            status: status.to_string(),
+            reserved_at: expires_at - chrono::Duration::seconds(30),
+            expires_at,
+            updated_at: expires_at - chrono::Duration::seconds(30),
+        }
+    }
+
+    #[test]
+    fn test_decide_reservation() {
+        let now = Utc::now();
+        let later = now + chrono::Duration::seconds(10);
+        let earlier = now - chrono::Duration::seconds(10);
+
+        assert_eq!(
+            decide_reservation(None, SOLVER, now),
+            ReservationDecision::Grant
+        );
+        let held = reservation(OTHER, "active", later);
+        assert_eq!(
+            decide_reservation(Some(&held), SOLVER, now),
+            ReservationDecision::Reject(ReservationRejection::Held { expires_at: later })
+        );
+        assert_eq!(
+            decide_reservation(Some(&held), OTHER, now),
+            ReservationDecision::Keep
+        );
+
+        // A lapsed holder can't take it back; anyone else can
+        for lapsed in [
+            reservation(OTHER, "lapsed", earlier),
+            reservation(OTHER, "active", earlier),
+        ] {
+            assert_eq!(
+                decide_reservation(Some(&lapsed), OTHER, now),
+                ReservationDecision::Reject(ReservationRejection::Lapsed)
+            );
+            assert_eq!(
+                decide_reservation(Some(&lapsed), SOLVER, now),
+                ReservationDecision::Grant
+            );
+        }
+
+        let released = reservation(OTHER, "released", earlier);
+        assert_eq!(
+            decide_reservation(Some(&released), OTHER, now),
+            ReservationDecision::Grant
+        );
+        let filled = reservation(OTHER, "filled", earlier);
+        assert_eq!(
+            decide_reservation(Some(&filled), SOLVER, now),
+            ReservationDecision::Reject(ReservationRejection::Filled)
+        );
+    }
+
+    #[test]
+    fn test_solver_tokens() {
+        let tokens = parse_solver_tokens(&format!(
+            " 0xAbCdEf0000000000000000000000000000000001 = s3cret , {}=other",
+            OTHER
+        ))
+        .unwrap();
+        let policy = ReservationPolicy {
+            enabled: true,
+            solver_tokens: tokens,
+            ..Default::default()
+        };
+
+        assert!(policy.authenticate("0xabcdef0000000000000000000000000000000001", "s3cret"));
+        assert!(!policy.authenticate("0xabcdef0000000000000000000000000000000001", "s3cre"));
+        assert!(!policy.authenticate(OTHER, "s3cret"));
+        assert!(!policy.authenticate(SOLVER, "s3cret"));
+
+        assert!(parse_solver_tokens("0x1234=token").is_err());
+        assert!(parse_solver_tokens(&format!("{}=", SOLVER)).is_err());
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_reservation_lifecycle() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let intent = IntentFactory::registered().build();
+        database.create_intent(&intent)?;
+        let now = Utc::now();
+
+        let grant = |solver: &'static str, expires_at| {
+            database.reserve_intent(&intent.id, solver, expires_at, |current| {
+                decide_reservation(current, solver, now) == ReservationDecision::Grant
+            })
+        };
+        assert!(grant(SOLVER, now + chrono::Duration::seconds(30))?);
+        assert!(!grant(OTHER, now + chrono::Duration::seconds(30))?);
+
+        // Expired but still active until the sweep lapses it
+        database.reserve_intent(
+            &intent.id,
+            SOLVER,
+            now - chrono::Duration::seconds(1),
+            |_| true,
+        )?;
+        let lapsed = database.lapse_expired_reservations(Utc::now())?;
+        assert!(
+            lapsed
+                .iter()
+                .any(|r| r.intent_id == intent.id.to_lowercase())
+        );
+        assert!(!grant(SOLVER, now + chrono::Duration::seconds(30))?);
+        assert!(grant(OTHER, now + chrono::Duration::seconds(30))?);
+
+        let closed = database.close_intent_reservation(&intent.id, None, "filled")?;
+        assert_eq!(closed.map(|r| r.solver_address), Some(OTHER.to_string()));
+        assert_eq!(
+            database.get_intent_reservation(&intent.id)?.unwrap().status,
+            "filled"
+        );
+        Ok(())
+    }
+}
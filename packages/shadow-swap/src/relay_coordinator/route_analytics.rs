@@ -0,0 +1,499 @@
+use std::{collections::BTreeMap, sync::Arc};
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use ethers::types::U256;
+use mantle_core::{amount::to_decimal, token_registry::TokenRegistry};
+use serde::Serialize;
+use tracing::{error, info, warn};
+
+use crate::{
+    database::model::IntentSample, models::model::IntentStatus,
+    relay_coordinator::model::BridgeCoordinator, webhooks::webhook_dispatcher::WebhookDispatcher,
+};
+
+/// Window of `/stats/routes` and when the daily report is sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteAnalyticsPolicy {
+    pub default_days: i64,
+    pub max_days: i64,
+    pub daily_report: bool,
+    /// UTC hour the previous day's report goes out.
+    pub report_hour_utc: u32,
+}
+
+impl Default for RouteAnalyticsPolicy {
+    fn default() -> Self {
+        Self {
+            default_days: 7,
+            max_days: 90,
+            daily_report: true,
+            report_hour_utc: 0,
+        }
+    }
+}
+
+impl RouteAnalyticsPolicy {
+    /// Reads `ROUTE_STATS_DEFAULT_DAYS`, `ROUTE_STATS_MAX_DAYS`,
+    /// `DAILY_REPORT_ENABLED` and `DAILY_REPORT_HOUR_UTC`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(days) = var("ROUTE_STATS_DEFAULT_DAYS") {
+            policy.default_days = days
+                .trim()
+                .parse()
+                .context("Invalid ROUTE_STATS_DEFAULT_DAYS")?;
+        }
+        if let Some(days) = var("ROUTE_STATS_MAX_DAYS") {
+            policy.max_days = days
+                .trim()
+                .parse()
+                .context("Invalid ROUTE_STATS_MAX_DAYS")?;
+        }
+        if let Some(enabled) = var("DAILY_REPORT_ENABLED") {
+            policy.daily_report = enabled
+                .trim()
+                .parse()
+                .context("Invalid DAILY_REPORT_ENABLED")?;
+        }
+        if let Some(hour) = var("DAILY_REPORT_HOUR_UTC") {
+            policy.report_hour_utc = hour
+                .trim()
+                .parse()
+                .context("Invalid DAILY_REPORT_HOUR_UTC")?;
+        }
+
+        if policy.default_days < 1 || policy.max_days < policy.default_days {
+            anyhow::bail!("ROUTE_STATS_DEFAULT_DAYS must be between 1 and ROUTE_STATS_MAX_DAYS");
+        }
+        if policy.report_hour_utc > 23 {
+            anyhow::bail!("DAILY_REPORT_HOUR_UTC must be 0-23");
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Intents in one bucket of a 1-2-5 series: amounts in `(min, max]`.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AmountBucket {
+    pub min: f64,
+    pub max: f64,
+    pub count: u64,
+}
+
+/// Demand and fills for one token in one direction. Amounts are whole
+/// tokens.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RouteStats {
+    pub token: String,
+    pub source_chain: String,
+    pub dest_chain: String,
+    pub intents: u64,
+    pub filled: u64,
+    /// Past their deadline without a fill, refunded or not.
+    pub abandoned: u64,
+    pub failed: u64,
+    /// Before their deadline and not filled yet.
+    pub open: u64,
+    /// `filled / (filled + abandoned)`; `None` until one is decided.
+    pub fill_rate: Option<f64>,
+    pub volume: f64,
+    pub abandoned_volume: f64,
+    pub median_amount: f64,
+    pub p90_amount: f64,
+    pub histogram: Vec<AmountBucket>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct RouteAnalytics {
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub intents: u64,
+    pub filled: u64,
+    pub abandoned: u64,
+    pub fill_rate: Option<f64>,
+    /// Intents whose token is not listed, left out of `routes`.
+    pub unlisted: u64,
+    /// Busiest first.
+    pub routes: Vec<RouteStats>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DailyReport {
+    pub date: NaiveDate,
+    pub routes: RouteAnalytics,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Filled,
+    Abandoned,
+    Failed,
+    Open,
+}
+
+fn outcome(status: &str, deadline: i64, now: i64) -> Outcome {
+    match IntentStatus::from_str(status) {
+        Ok(IntentStatus::Filled | IntentStatus::SolverPaid | IntentStatus::UserClaimed) => {
+            Outcome::Filled
+        }
+        Ok(IntentStatus::Refunded | IntentStatus::Expired | IntentStatus::Underpaid) => {
+            Outcome::Abandoned
+        }
+        Ok(
+            IntentStatus::Created
+            | IntentStatus::Committed
+            | IntentStatus::Registered
+            | IntentStatus::Pending,
+        ) if deadline < now => Outcome::Abandoned,
+        Ok(
+            IntentStatus::Created
+            | IntentStatus::Committed
+            | IntentStatus::Registered
+            | IntentStatus::Pending,
+        ) => Outcome::Open,
+        Ok(IntentStatus::Failed) | Err(_) => Outcome::Failed,
+    }
+}
+
+const STEPS: [f64; 3] = [1.0, 2.0, 5.0];
+
+/// Index of the 1-2-5 bucket holding `amount`; bucket `i` ends at
+/// `STEPS[i mod 3] * 10^(i div 3)`.
+fn bucket_of(amount: f64) -> Option<i32> {
+    if amount <= 0.0 || !amount.is_finite() {
+        return None;
+    }
+
+    let exponent = amount.log10().floor() as i32;
+    let mantissa = amount / 10f64.powi(exponent);
+    let index = match STEPS
+        .iter()
+        .position(|step| mantissa <= step * (1.0 + 1e-9))
+    {
+        Some(step) => exponent * 3 + step as i32,
+        None => (exponent + 1) * 3,
+    };
+    Some(index)
+}
+
+fn bucket_max(index: i32) -> f64 {
+    STEPS[index.rem_euclid(3) as usize] * 10f64.powi(index.div_euclid(3))
+}
+
+/// Nearest-rank percentile of sorted amounts.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn rate(filled: u64, abandoned: u64) -> Option<f64> {
+    let decided = filled + abandoned;
+    (decided > 0).then(|| filled as f64 / decided as f64)
+}
+
+/// Group intents by token and direction. `now` (unix seconds) decides
+/// which unfilled intents are past their deadline.
+pub fn aggregate(
+    samples: &[IntentSample],
+    tokens: &TokenRegistry,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    now: i64,
+) -> RouteAnalytics {
+    let mut analytics = RouteAnalytics {
+        from,
+        to,
+        ..Default::default()
+    };
+    let mut routes: BTreeMap<(String, String, String), (RouteStats, Vec<f64>)> = BTreeMap::new();
+
+    for sample in samples {
+        analytics.intents += 1;
+        let outcome = outcome(&sample.status, sample.deadline, now);
+        match outcome {
+            Outcome::Filled => analytics.filled += 1,
+            Outcome::Abandoned => analytics.abandoned += 1,
+            _ => {}
+        }
+
+        let Ok(token) = tokens.resolve_str(&sample.source_token) else {
+            analytics.unlisted += 1;
+            continue;
+        };
+        let decimals = tokens
+            .listing(token)
+            .map_or(token.decimals(), |listing| listing.decimals);
+        let amount = U256::from_dec_str(&sample.amount)
+            .map(|amount| to_decimal(amount, decimals))
+            .unwrap_or(0.0);
+
+        let key = (
+            token.symbol().to_string(),
+            sample.source_chain.clone(),
+            sample.dest_chain.clone(),
+        );
+        let (route, amounts) = routes.entry(key).or_insert_with_key(|(t, s, d)| {
+            let route = RouteStats {
+                token: t.clone(),
+                source_chain: s.clone(),
+                dest_chain: d.clone(),
+                ..Default::default()
+            };
+            (route, Vec::new())
+        });
+
+        route.intents += 1;
+        route.volume += amount;
+        amounts.push(amount);
+        match outcome {
+            Outcome::Filled => route.filled += 1,
+            Outcome::Abandoned => {
+                route.abandoned += 1;
+                route.abandoned_volume += amount;
+            }
+            Outcome::Failed => route.failed += 1,
+            Outcome::Open => route.open += 1,
+        }
+    }
+
+    for (_, (mut route, mut amounts)) in routes {
+        amounts.sort_by(f64::total_cmp);
+        route.median_amount = percentile(&amounts, 50.0);
+        route.p90_amount = percentile(&amounts, 90.0);
+        route.fill_rate = rate(route.filled, route.abandoned);
+
+        let mut buckets: BTreeMap<i32, u64> = BTreeMap::new();
+        for index in amounts.iter().filter_map(|amount| bucket_of(*amount)) {
+            *buckets.entry(index).or_default() += 1;
+        }
+        route.histogram = buckets
+            .into_iter()
+            .map(|(index, count)| AmountBucket {
+                min: bucket_max(index - 1),
+                max: bucket_max(index),
+                count,
+            })
+            .collect();
+
+        analytics.routes.push(route);
+    }
+
+    analytics.fill_rate = rate(analytics.filled, analytics.abandoned);
+    analytics
+        .routes
+        .sort_by_key(|route| std::cmp::Reverse(route.intents));
+    analytics
+}
+
+impl BridgeCoordinator {
+    /// Route analytics for intents created in `[from, to)`.
+    pub fn route_analytics(
+        &self,
+        from: DateTime<Utc>,
+        to: DateTime<Utc>,
+    ) -> Result<RouteAnalytics> {
+        let samples = self.database.get_intent_samples(from, to)?;
+        Ok(aggregate(
+            &samples,
+            &self.tokens,
+            from,
+            to,
+            Utc::now().timestamp(),
+        ))
+    }
+
+    /// The previous UTC day's route analytics, sent every day at
+    /// `report_hour_utc` to the log and the `daily_report` webhook.
+    pub async fn run_daily_report(
+        &self,
+        policy: RouteAnalyticsPolicy,
+        webhooks: Arc<WebhookDispatcher>,
+    ) {
+        info!(
+            "📊 Daily report scheduled at {:02}:00 UTC",
+            policy.report_hour_utc
+        );
+
+        loop {
+            let now = Utc::now();
+            let today = now.date_naive();
+            let mut next = today
+                .and_hms_opt(policy.report_hour_utc, 0, 0)
+                .expect("hour validated")
+                .and_utc();
+            if next <= now {
+                next += Duration::days(1);
+            }
+            tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+
+            let date = next.date_naive() - Duration::days(1);
+            match self.daily_report(date) {
+                Ok(report) => {
+                    log_report(&report);
+                    let payload = match serde_json::to_value(&report) {
+                        Ok(payload) => payload,
+                        Err(e) => {
+                            error!("❌ Failed to encode daily report: {}", e);
+                            continue;
+                        }
+                    };
+                    if webhooks.dispatch("daily_report", &payload).await > 0 {
+                        warn!(
+                            "⚠️ Daily report for {} not delivered to every webhook",
+                            date
+                        );
+                    }
+                }
+                Err(e) => error!("❌ Daily report for {} failed: {}", date, e),
+            }
+        }
+    }
+
+    pub fn daily_report(&self, date: NaiveDate) -> Result<DailyReport> {
+        let from = date
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight exists")
+            .and_utc();
+        Ok(DailyReport {
+            date,
+            routes: self.route_analytics(from, from + Duration::days(1))?,
+        })
+    }
+}
+
+fn log_report(report: &DailyReport) {
+    let routes = &report.routes;
+    let pct = |rate: Option<f64>| rate.map_or("-".to_string(), |r| format!("{:.1}%", r * 100.0));
+
+    info!(
+        "📊 Daily report {}: {} intents, {} filled, {} abandoned, fill rate {}",
+        report.date,
+        routes.intents,
+        routes.filled,
+        routes.abandoned,
+        pct(routes.fill_rate)
+    );
+    for route in &routes.routes {
+        info!(
+            "   {} {}→{}: {} intents, median {} {}, fill rate {}, {} abandoned ({} {})",
+            route.token,
+            route.source_chain,
+            route.dest_chain,
+            route.intents,
+            route.median_amount,
+            route.token,
+            pct(route.fill_rate),
+            route.abandoned,
+            route.abandoned_volume,
+            route.token
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mantle_core::{chain::Chain, token::TokenType};
+
+    fn sample(token: TokenType, whole: u64, status: &str, deadline: i64) -> IntentSample {
+        let amount = U256::from(whole) * U256::exp10(token.decimals() as usize);
+        IntentSample {
+            source_chain: "ethereum".to_string(),
+            dest_chain: "mantle".to_string(),
+            source_token: format!(
+                "{:?}",
+                TokenRegistry::builtin()
+                    .address(token, Chain::Ethereum)
+                    .expect("built-in token")
+            ),
+            amount: amount.to_string(),
+            status: status.to_string(),
+            deadline,
+        }
+    }
+
+    #[test]
+    fn test_buckets_follow_a_one_two_five_series() {
+        assert_eq!(bucket_max(bucket_of(1.0).unwrap()), 1.0);
+        assert_eq!(bucket_max(bucket_of(1.5).unwrap()), 2.0);
+        assert_eq!(bucket_max(bucket_of(100.0).unwrap()), 100.0);
+        assert_eq!(bucket_max(bucket_of(600.0).unwrap()), 1000.0);
+        assert_eq!(bucket_max(bucket_of(0.03).unwrap()), 0.05);
+        assert_eq!(bucket_max(bucket_of(600.0).unwrap() - 1), 500.0);
+        assert!(bucket_of(0.0).is_none());
+    }
+
+    #[test]
+    fn test_routes_count_fills_and_abandoned_intents() {
+        let now = 1_000;
+        let samples = [
+            sample(TokenType::USDC, 100, "user_claimed", 500),
+            sample(TokenType::USDC, 150, "filled", 2_000),
+            sample(TokenType::USDC, 1_000, "refunded", 500),
+            sample(TokenType::USDC, 40, "committed", 500),
+            sample(TokenType::USDC, 40, "committed", 2_000),
+            sample(TokenType::ETH, 1, "failed", 500),
+            IntentSample {
+                source_token: "0x000000000000000000000000000000000000dEaD".to_string(),
+                ..sample(TokenType::USDC, 5, "filled", 500)
+            },
+        ];
+
+        let analytics = aggregate(
+            &samples,
+            &TokenRegistry::builtin(),
+            Utc::now(),
+            Utc::now(),
+            now,
+        );
+        assert_eq!(analytics.intents, 7);
+        assert_eq!(analytics.unlisted, 1);
+        assert_eq!(analytics.filled, 3);
+        assert_eq!(analytics.abandoned, 2);
+
+        let usdc = &analytics.routes[0];
+        assert_eq!(usdc.token, "USDC");
+        assert_eq!(usdc.intents, 5);
+        assert_eq!((usdc.filled, usdc.abandoned, usdc.open), (2, 2, 1));
+        assert_eq!(usdc.fill_rate, Some(0.5));
+        assert_eq!(usdc.abandoned_volume, 1_040.0);
+        assert_eq!(usdc.median_amount, 100.0);
+        assert_eq!(usdc.p90_amount, 1_000.0);
+        assert_eq!(
+            usdc.histogram,
+            vec![
+                AmountBucket {
+                    min: 20.0,
+                    max: 50.0,
+                    count: 2
+                },
+                AmountBucket {
+                    min: 50.0,
+                    max: 100.0,
+                    count: 1
+                },
+                AmountBucket {
+                    min: 100.0,
+                    max: 200.0,
+                    count: 1
+                },
+                AmountBucket {
+                    min: 500.0,
+                    max: 1000.0,
+                    count: 1
+                },
+            ]
+        );
+
+        let eth = &analytics.routes[1];
+        assert_eq!(eth.failed, 1);
+        assert_eq!(eth.fill_rate, None);
+    }
+}
@@ -0,0 +1,46 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ethers::{types::H256, utils::keccak256};
+
+static SIMULATED_SENDS: AtomicU64 = AtomicU64::new(0);
+
+/// Chain transaction status recorded for sends skipped by the sandbox.
+pub const SIMULATED_STATUS: &str = "simulated";
+
+/// Reads `RELAYER_SANDBOX`. When set, every transaction-sending relayer method
+/// stops after its `eth_call` simulation and returns a fake hash; indexing, DB
+/// writes and merkle logic are unaffected.
+pub fn sandbox_enabled() -> bool {
+    std::env::var("RELAYER_SANDBOX")
+        .unwrap_or_else(|_| "false".to_string())
+        .parse()
+        .unwrap_or(false)
+}
+
+/// Unique per call, so repeated simulations of one action never collide in
+/// `chain_transactions`.
+pub fn simulated_tx_hash(chain_id: u32, tx_type: &str, subject: &str) -> String {
+    let nanos = chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default();
+    let sequence = SIMULATED_SENDS.fetch_add(1, Ordering::Relaxed);
+    let seed = format!(
+        "sandbox:{}:{}:{}:{}:{}",
+        chain_id, tx_type, subject, nanos, sequence
+    );
+
+    format!("{:?}", H256::from(keccak256(seed.as_bytes())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_simulated_hashes_are_well_formed_and_distinct() {
+        let first = simulated_tx_hash(5003, "claim_withdrawal", "0xabc");
+        let second = simulated_tx_hash(5003, "claim_withdrawal", "0xabc");
+
+        assert_eq!(first.len(), 66);
+        assert!(first.starts_with("0x"));
+        assert_ne!(first, second);
+    }
+}
@@ -0,0 +1,399 @@
+use std::time::Duration;
+
+use anyhow::{Result, anyhow};
+use ethers::{
+    types::{Address, Signature},
+    utils::{hash_message, keccak256},
+};
+use serde::Serialize;
+use tokio::time::interval;
+use tracing::{error, info, warn};
+
+use crate::{
+    database::model::DbSecretReveal,
+    models::model::{Intent, IntentStatus},
+    relay_coordinator::{claim_auth::parse_claim_auth, model::BridgeCoordinator},
+};
+
+/// Domain tag that keeps a reveal signature from being mistaken for any
+/// other message the recipient signs, including the claim authorization.
+const REVEAL_DOMAIN: &[u8] = b"shadow-swap:reveal";
+
+/// Reveal window for intents whose users keep the secret themselves.
+#[derive(Debug, Clone, Copy)]
+pub struct RevealPolicy {
+    /// Seconds after the fill the user has to reveal.
+    pub window_secs: u64,
+    pub check_interval: Duration,
+}
+
+impl RevealPolicy {
+    /// Reads `SECRET_REVEAL_WINDOW_SECS` (default 3600) and
+    /// `SECRET_REVEAL_CHECK_INTERVAL_SECS` (default 30).
+    pub fn from_env() -> Self {
+        Self {
+            window_secs: std::env::var("SECRET_REVEAL_WINDOW_SECS")
+                .unwrap_or_else(|_| "3600".to_string())
+                .parse()
+                .unwrap_or(3600),
+            check_interval: Duration::from_secs(
+                std::env::var("SECRET_REVEAL_CHECK_INTERVAL_SECS")
+                    .unwrap_or_else(|_| "30".to_string())
+                    .parse()
+                    .unwrap_or(30)
+                    .max(1),
+            ),
+        }
+    }
+
+    /// The window opens at the fill and never outlasts the intent deadline,
+    /// so an unrevealed intent is always refundable on the source chain.
+    pub fn deadline(&self, opened_at: u64, intent_deadline: u64) -> u64 {
+        opened_at
+            .saturating_add(self.window_secs)
+            .min(intent_deadline)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RevealState {
+    /// Not filled yet; revealing now would expose the secret early.
+    AwaitingFill,
+    Open,
+    Revealed,
+    /// Window closed without a reveal; the intent will be refunded.
+    Expired,
+}
+
+pub fn reveal_state(reveal: &DbSecretReveal, now: u64) -> RevealState {
+    match (reveal.secret.is_some(), reveal.reveal_deadline) {
+        (true, _) => RevealState::Revealed,
+        (false, None) => RevealState::AwaitingFill,
+        (false, Some(deadline)) if now > deadline as u64 => RevealState::Expired,
+        (false, Some(_)) => RevealState::Open,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RevealStatus {
+    pub intent_id: String,
+    pub state: RevealState,
+    pub reveal_deadline: Option<i64>,
+    pub revealed_at: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// Why a reveal was refused, mapped to an HTTP status by the route.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RevealRejection {
+    /// Unknown intent, or one whose secret the relayer already holds.
+    NotFound,
+    Invalid(String),
+    NotFilled,
+    AlreadyRevealed,
+    Expired,
+}
+
+impl std::fmt::Display for RevealRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "No user-held secret for this intent"),
+            Self::Invalid(reason) => write!(f, "{}", reason),
+            Self::NotFilled => write!(f, "The reveal window opens once the intent is filled"),
+            Self::AlreadyRevealed => write!(f, "Secret already revealed"),
+            Self::Expired => write!(f, "Reveal deadline passed, the intent will be refunded"),
+        }
+    }
+}
+
+fn parse_bytes32(value: &str, what: &str) -> Result<[u8; 32]> {
+    let hex_part = value
+        .strip_prefix("0x")
+        .ok_or_else(|| anyhow!("{} must start with 0x", what))?;
+    hex::decode(hex_part)
+        .map_err(|e| anyhow!("{} is not valid hex: {}", what, e))?
+        .try_into()
+        .map_err(|_| anyhow!("{} must be 32 bytes", what))
+}
+
+/// `keccak256("shadow-swap:reveal" ‖ intentId ‖ secret)`, signed by the
+/// recipient as an Ethereum personal message.
+pub fn reveal_message(intent_id: &str, secret: &str) -> Result<[u8; 32]> {
+    let mut packed = REVEAL_DOMAIN.to_vec();
+    packed.extend_from_slice(&parse_bytes32(intent_id, "Intent id")?);
+    packed.extend_from_slice(&parse_bytes32(secret, "Secret")?);
+    Ok(keccak256(packed))
+}
+
+/// Check that `signature` is the recipient's signature over this reveal.
+pub fn verify_reveal_signature(
+    intent_id: &str,
+    secret: &str,
+    recipient: &str,
+    signature: &str,
+) -> Result<()> {
+    let bytes = parse_claim_auth(signature)?;
+    let signature =
+        Signature::try_from(bytes.as_slice()).map_err(|e| anyhow!("Invalid signature: {}", e))?;
+    let recipient: Address = recipient
+        .parse()
+        .map_err(|e| anyhow!("Invalid stored recipient: {}", e))?;
+
+    let signer = signature
+        .recover(hash_message(reveal_message(intent_id, secret)?))
+        .map_err(|e| anyhow!("Failed to recover signer: {}", e))?;
+    if signer != recipient {
+        return Err(anyhow!(
+            "Signed by {:?}, expected recipient {:?}",
+            signer,
+            recipient
+        ));
+    }
+
+    Ok(())
+}
+
+/// A fill has been observed for this intent, so the secret may be revealed.
+fn is_filled(status: IntentStatus) -> bool {
+    matches!(status, IntentStatus::Filled | IntentStatus::SolverPaid)
+}
+
+impl BridgeCoordinator {
+    /// Open the reveal window the first time the intent is seen filled.
+    fn open_reveal_window(&self, policy: &RevealPolicy, intent: &Intent, now: u64) -> Result<()> {
+        let deadline = policy.deadline(now, intent.deadline);
+        self.database
+            .open_secret_reveal(&intent.id, deadline as i64)?;
+        info!(
+            "🔓 Reveal window open for {} until {} ({}s)",
+            intent.id,
+            deadline,
+            deadline.saturating_sub(now)
+        );
+        Ok(())
+    }
+
+    pub fn get_reveal_status(&self, intent_id: &str) -> Result<Option<RevealStatus>> {
+        let now = chrono::Utc::now().timestamp() as u64;
+        Ok(self
+            .database
+            .get_secret_reveal(intent_id)?
+            .map(|reveal| RevealStatus {
+                state: reveal_state(&reveal, now),
+                intent_id: reveal.intent_id,
+                reveal_deadline: reveal.reveal_deadline,
+                revealed_at: reveal.revealed_at,
+            }))
+    }
+
+    /// Accept the user's secret once the intent is filled. The first valid
+    /// reveal wins; later ones are rejected so a captured request cannot be
+    /// replayed.
+    pub async fn reveal_secret(
+        &self,
+        policy: &RevealPolicy,
+        intent_id: &str,
+        secret: &str,
+        signature: &str,
+    ) -> Result<std::result::Result<RevealStatus, RevealRejection>> {
+        let intent_id = intent_id.to_lowercase();
+        let (Some(mut reveal), Some(intent)) = (
+            self.database.get_secret_reveal(&intent_id)?,
+            self.database.get_intent_by_id(&intent_id)?,
+        ) else {
+            return Ok(Err(RevealRejection::NotFound));
+        };
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        if reveal.reveal_deadline.is_none() && is_filled(intent.status) {
+            self.open_reveal_window(policy, &intent, now)?;
+            reveal = self
+                .database
+                .get_secret_reveal(&intent_id)?
+                .ok_or_else(|| anyhow!("Secret reveal for {} disappeared", intent_id))?;
+        }
+
+        match reveal_state(&reveal, now) {
+            RevealState::AwaitingFill => return Ok(Err(RevealRejection::NotFilled)),
+            RevealState::Revealed => return Ok(Err(RevealRejection::AlreadyRevealed)),
+            RevealState::Expired => return Ok(Err(RevealRejection::Expired)),
+            RevealState::Open => {}
+        }
+
+        let recipient = self
+            .database
+            .get_intent_privacy_params(&intent_id)?
+            .recipient
+            .ok_or_else(|| anyhow!("Recipient not stored for {}", intent_id))?;
+        let secret = secret.to_lowercase();
+        if let Err(e) = verify_reveal_signature(&intent_id, &secret, &recipient, signature) {
+            return Ok(Err(RevealRejection::Invalid(e.to_string())));
+        }
+
+        if !self
+            .database
+            .record_secret_reveal(&intent_id, &secret, signature)?
+        {
+            return Ok(Err(RevealRejection::AlreadyRevealed));
+        }
+
+        info!(
+            "🔑 Secret revealed for {}, settlement and claim can proceed",
+            intent_id
+        );
+
+        Ok(self
+            .get_reveal_status(&intent_id)?
+            .ok_or(RevealRejection::NotFound))
+    }
+
+    /// Secret to claim with: the relayer-held one decrypted, or the one the
+    /// user revealed.
+    pub(crate) async fn claim_secret(
+        &self,
+        intent_id: &str,
+        encrypted_secret: Option<&str>,
+    ) -> Result<String> {
+        if let Some(encrypted) = encrypted_secret {
+            return self
+                .decrypt_privacy_param(encrypted)
+                .await
+                .map_err(|e| anyhow!("Failed to decrypt secret: {}", e));
+        }
+
+        self.database
+            .get_secret_reveal(intent_id)?
+            .and_then(|reveal| reveal.secret)
+            .ok_or_else(|| anyhow!("Secret not revealed by the user yet"))
+    }
+
+    /// True while a user-held secret has not been revealed. Settlement waits
+    /// so the deposit stays refundable if the user never reveals.
+    pub fn awaiting_reveal(&self, intent_id: &str) -> Result<bool> {
+        Ok(self
+            .database
+            .get_secret_reveal(intent_id)?
+            .is_some_and(|reveal| reveal.secret.is_none()))
+    }
+
+    pub async fn run_reveal_monitor(&self, policy: RevealPolicy) {
+        info!(
+            "🔑 Secret reveal monitor started (window {}s, every {}s)",
+            policy.window_secs,
+            policy.check_interval.as_secs()
+        );
+
+        let mut ticker = interval(policy.check_interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.check_secret_reveals(&policy).await {
+                error!("❌ Secret reveal check failed: {}", e);
+            }
+        }
+    }
+
+    /// Open windows for newly filled intents and refund the ones whose
+    /// window closed unrevealed, once the source deadline allows it.
+    async fn check_secret_reveals(&self, policy: &RevealPolicy) -> Result<()> {
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        for reveal in self.database.get_unrevealed_secret_reveals()? {
+            let Some(intent) = self.database.get_intent_by_id(&reveal.intent_id)? else {
+                continue;
+            };
+            if matches!(
+                intent.status,
+                IntentStatus::Refunded | IntentStatus::UserClaimed | IntentStatus::Failed
+            ) {
+                continue;
+            }
+
+            match reveal_state(&reveal, now) {
+                RevealState::AwaitingFill if is_filled(intent.status) => {
+                    self.open_reveal_window(policy, &intent, now)?;
+                }
+                RevealState::Expired if now > intent.deadline => {
+                    warn!(
+                        "⌛ Secret for {} never revealed, refunding on {}",
+                        intent.id, intent.source_chain
+                    );
+                    if let Err(e) = self.handle_refund(&intent).await {
+                        error!("❌ Refund of unrevealed intent {} failed: {}", intent.id, e);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use ethers::signers::{LocalWallet, Signer};
+
+    const INTENT_ID: &str = "0x0101010101010101010101010101010101010101010101010101010101010101";
+    const SECRET: &str = "0x0303030303030303030303030303030303030303030303030303030303030303";
+
+    fn reveal(deadline: Option<i64>, secret: Option<&str>) -> DbSecretReveal {
+        DbSecretReveal {
+            intent_id: INTENT_ID.to_string(),
+            reveal_deadline: deadline,
+            secret: secret.map(str::to_string),
+            signature: None,
+            revealed_at: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reveal_window_and_signature() {
+        let policy = RevealPolicy {
+            window_secs: 3600,
+            check_interval: Duration::from_secs(30),
+        };
+        assert_eq!(policy.deadline(1_000, 100_000), 4_600);
+        assert_eq!(policy.deadline(1_000, 2_000), 2_000);
+
+        assert_eq!(
+            reveal_state(&reveal(None, None), 0),
+            RevealState::AwaitingFill
+        );
+        assert_eq!(
+            reveal_state(&reveal(Some(500), None), 500),
+            RevealState::Open
+        );
+        assert_eq!(
+            reveal_state(&reveal(Some(500), None), 501),
+            RevealState::Expired
+        );
+        assert_eq!(
+            reveal_state(&reveal(Some(500), Some(SECRET)), 501),
+            RevealState::Revealed
+        );
+
+        let wallet: LocalWallet =
+            "0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318"
+                .parse()
+                .unwrap();
+        let recipient = format!("{:?}", wallet.address());
+        let signature = format!(
+            "0x{}",
+            wallet
+                .sign_message(reveal_message(INTENT_ID, SECRET).unwrap())
+                .await
+                .unwrap()
+        );
+
+        assert!(verify_reveal_signature(INTENT_ID, SECRET, &recipient, &signature).is_ok());
+        // Bound to the secret and the intent
+        let other = "0x0404040404040404040404040404040404040404040404040404040404040404";
+        assert!(verify_reveal_signature(INTENT_ID, other, &recipient, &signature).is_err());
+        assert!(verify_reveal_signature(other, SECRET, &recipient, &signature).is_err());
+        assert!(verify_reveal_signature(INTENT_ID, "0x1234", &recipient, &signature).is_err());
+    }
+}
@@ -0,0 +1,261 @@
+use std::{collections::HashMap, sync::Arc};
+
+use anyhow::{Result, anyhow};
+use mantle_core::chain::Chain;
+use tracing::warn;
+
+const DEFAULT_CHALLENGE_WINDOW_SECS: u64 = 3600;
+
+/// What the chains show about an intent in `filled` status.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FillObservation {
+    /// Destination fill timestamp, `None` while no solver has filled.
+    pub filled_at: Option<u64>,
+    /// The source pool already paid the solver.
+    pub settled_on_source: bool,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SettlementStep {
+    /// Leave the intent in `filled` and look again next cycle.
+    Wait(String),
+    /// Prove the fill and call `settleIntent` on the source pool.
+    Settle,
+    /// Settled on chain by someone else, only the DB status moves.
+    MarkSettled,
+}
+
+/// How the solver gets paid on the source chain once a fill lands.
+pub trait SettlementStrategy: Send + Sync + std::fmt::Debug {
+    fn name(&self) -> String;
+
+    /// Step for an intent filled at `filled_at` and not yet settled.
+    fn on_fill(&self, filled_at: u64, now: u64) -> SettlementStep;
+}
+
+/// The relayer settles as soon as the fill is observed.
+#[derive(Debug, Clone, Copy)]
+pub struct RelayerSettled;
+
+impl SettlementStrategy for RelayerSettled {
+    fn name(&self) -> String {
+        "relayer".to_string()
+    }
+
+    fn on_fill(&self, _filled_at: u64, _now: u64) -> SettlementStep {
+        SettlementStep::Settle
+    }
+}
+
+/// The solver calls `settleIntent` itself; the relayer only tracks it.
+#[derive(Debug, Clone, Copy)]
+pub struct SolverSettled;
+
+impl SettlementStrategy for SolverSettled {
+    fn name(&self) -> String {
+        "solver".to_string()
+    }
+
+    fn on_fill(&self, _filled_at: u64, _now: u64) -> SettlementStep {
+        SettlementStep::Wait("Waiting for the solver to settle on the source chain".to_string())
+    }
+}
+
+/// The relayer settles once the fill has been visible for the whole
+/// challenge window, giving operators time to stop a disputed fill.
+#[derive(Debug, Clone, Copy)]
+pub struct OptimisticSettlement {
+    pub challenge_window_secs: u64,
+}
+
+impl SettlementStrategy for OptimisticSettlement {
+    fn name(&self) -> String {
+        format!("optimistic:{}", self.challenge_window_secs)
+    }
+
+    fn on_fill(&self, filled_at: u64, now: u64) -> SettlementStep {
+        let ends_at = filled_at.saturating_add(self.challenge_window_secs);
+        if now >= ends_at {
+            SettlementStep::Settle
+        } else {
+            SettlementStep::Wait(format!(
+                "Challenge window open for another {}s",
+                ends_at - now
+            ))
+        }
+    }
+}
+
+/// Steps shared by every strategy: skip unfilled intents and catch up on
+/// ones already settled, then let the strategy decide.
+pub fn next_step(
+    strategy: &dyn SettlementStrategy,
+    fill: &FillObservation,
+    now: u64,
+) -> SettlementStep {
+    if fill.settled_on_source {
+        return SettlementStep::MarkSettled;
+    }
+
+    match fill.filled_at {
+        None => SettlementStep::Wait("Not filled on the destination yet".to_string()),
+        Some(filled_at) => strategy.on_fill(filled_at, now),
+    }
+}
+
+/// Parse `relayer`, `solver`, `optimistic` or `optimistic:<window_secs>`.
+pub fn parse_strategy(value: &str) -> Result<Arc<dyn SettlementStrategy>> {
+    let value = value.trim().to_lowercase();
+    let (kind, window) = match value.split_once(':') {
+        Some((kind, window)) => (kind, Some(window)),
+        None => (value.as_str(), None),
+    };
+
+    match (kind, window) {
+        ("relayer", None) => Ok(Arc::new(RelayerSettled)),
+        ("solver", None) => Ok(Arc::new(SolverSettled)),
+        ("optimistic", window) => Ok(Arc::new(OptimisticSettlement {
+            challenge_window_secs: match window {
+                Some(secs) => secs
+                    .parse()
+                    .map_err(|_| anyhow!("Invalid challenge window '{}'", secs))?,
+                None => DEFAULT_CHALLENGE_WINDOW_SECS,
+            },
+        })),
+        _ => Err(anyhow!(
+            "Unknown settlement strategy '{}', expected relayer, solver or optimistic[:secs]",
+            value
+        )),
+    }
+}
+
+/// Settlement strategy per `(source, dest)` direction.
+#[derive(Debug, Clone)]
+pub struct SettlementStrategies {
+    default: Arc<dyn SettlementStrategy>,
+    routes: HashMap<(Chain, Chain), Arc<dyn SettlementStrategy>>,
+}
+
+impl Default for SettlementStrategies {
+    fn default() -> Self {
+        Self {
+            default: Arc::new(RelayerSettled),
+            routes: HashMap::new(),
+        }
+    }
+}
+
+impl SettlementStrategies {
+    /// Reads `SETTLEMENT_STRATEGY_DEFAULT` (default `relayer`) and
+    /// `SETTLEMENT_STRATEGIES`, e.g. `ethereum:mantle=optimistic:3600,mantle:ethereum=solver`.
+    /// Invalid entries are logged and ignored.
+    pub fn from_env() -> Self {
+        let mut strategies = Self::default();
+
+        if let Ok(raw) = std::env::var("SETTLEMENT_STRATEGY_DEFAULT") {
+            match parse_strategy(&raw) {
+                Ok(strategy) => strategies.default = strategy,
+                Err(e) => warn!("⚠️ Ignoring SETTLEMENT_STRATEGY_DEFAULT: {}", e),
+            }
+        }
+
+        if let Ok(raw) = std::env::var("SETTLEMENT_STRATEGIES") {
+            strategies.routes = Self::parse_routes(&raw);
+        }
+
+        strategies
+    }
+
+    fn parse_routes(raw: &str) -> HashMap<(Chain, Chain), Arc<dyn SettlementStrategy>> {
+        raw.split(',')
+            .filter(|entry| !entry.trim().is_empty())
+            .filter_map(|entry| {
+                let parsed = entry
+                    .split_once('=')
+                    .ok_or_else(|| anyhow!("missing '='"))
+                    .and_then(|(route, strategy)| {
+                        let (source, dest) = route
+                            .split_once(':')
+                            .ok_or_else(|| anyhow!("route must be source:dest"))?;
+                        let source = Chain::parse(source.trim())?;
+                        let dest = Chain::parse(dest.trim())?;
+                        if source == dest {
+                            return Err(anyhow!("source and destination are the same chain"));
+                        }
+                        Ok(((source, dest), parse_strategy(strategy)?))
+                    });
+
+                match parsed {
+                    Ok(route) => Some(route),
+                    Err(e) => {
+                        warn!("⚠️ Ignoring settlement strategy '{}': {}", entry.trim(), e);
+                        None
+                    }
+                }
+            })
+            .collect()
+    }
+
+    pub fn for_route(&self, source: Chain, dest: Chain) -> &dyn SettlementStrategy {
+        self.routes
+            .get(&(source, dest))
+            .unwrap_or(&self.default)
+            .as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strategies_per_direction() {
+        let strategies = SettlementStrategies {
+            routes: SettlementStrategies::parse_routes(
+                "ethereum:mantle=optimistic:600, mantle:ethereum=solver, mantle:mantle=relayer, bogus",
+            ),
+            ..Default::default()
+        };
+        assert_eq!(strategies.routes.len(), 2);
+
+        let optimistic = strategies.for_route(Chain::Ethereum, Chain::Mantle);
+        assert_eq!(optimistic.name(), "optimistic:600");
+
+        let filled = |filled_at| FillObservation {
+            filled_at: Some(filled_at),
+            settled_on_source: false,
+        };
+        assert!(matches!(
+            next_step(optimistic, &filled(1_000), 1_300),
+            SettlementStep::Wait(_)
+        ));
+        assert_eq!(
+            next_step(optimistic, &filled(1_000), 1_600),
+            SettlementStep::Settle
+        );
+
+        let solver = strategies.for_route(Chain::Mantle, Chain::Ethereum);
+        assert!(matches!(
+            next_step(solver, &filled(1_000), 5_000),
+            SettlementStep::Wait(_)
+        ));
+        let settled = FillObservation {
+            filled_at: Some(1_000),
+            settled_on_source: true,
+        };
+        assert_eq!(
+            next_step(solver, &settled, 5_000),
+            SettlementStep::MarkSettled
+        );
+
+        assert!(matches!(
+            next_step(&RelayerSettled, &FillObservation::default(), 5_000),
+            SettlementStep::Wait(_)
+        ));
+        assert!(parse_strategy("optimistic:soon").is_err());
+        assert_eq!(
+            parse_strategy("optimistic").unwrap().name(),
+            "optimistic:3600"
+        );
+    }
+}
@@ -0,0 +1,63 @@
+use anyhow::{Context, Result};
+use mantle_core::token_registry::TokenRegistry;
+use tracing::{info, warn};
+
+use crate::{config::profile::NetworkProfile, database::database::Database};
+
+/// The tokens the relayer starts with: the profile's, or the built-in
+/// Sepolia listings without one, with the admin API's changes from
+/// `token_listings` applied on top.
+pub fn load_token_registry(
+    profile: Option<&NetworkProfile>,
+    database: &Database,
+) -> Result<TokenRegistry> {
+    let registry = match profile {
+        Some(profile) => TokenRegistry::from_profiles(&profile.tokens)
+            .with_context(|| format!("Invalid tokens in profile '{}'", profile.name))?,
+        None => TokenRegistry::builtin(),
+    };
+
+    for row in database.list_token_listings()? {
+        match row.listing().and_then(|listing| registry.upsert(listing)) {
+            Ok(()) => info!(
+                "🪙 {} listing from the admin API (enabled: {})",
+                row.symbol, row.enabled
+            ),
+            Err(e) => warn!("⚠️ Ignoring stored listing for {}: {:#}", row.symbol, e),
+        }
+    }
+
+    Ok(registry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::test_database;
+    use mantle_core::{chain::Chain, token::TokenType, token_registry::TokenListing};
+    use serial_test::serial;
+
+    #[test]
+    #[serial(db)]
+    fn test_stored_listings_override_the_defaults() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let mut usdc = TokenListing::builtin(TokenType::USDC);
+        usdc.mantle = "0x09Bc4E0D864854c6aFB6eB9A9cdF58aC190D0dF9".parse()?;
+        usdc.enabled = false;
+        database.save_token_listing(&usdc)?;
+
+        let registry = load_token_registry(None, &database)?;
+        assert_eq!(registry.listing(TokenType::USDC), Some(usdc.clone()));
+        assert!(registry.enabled(TokenType::USDC).is_err());
+        assert_eq!(
+            registry.resolve(usdc.mantle, Chain::Mantle),
+            Some(TokenType::USDC)
+        );
+        assert!(registry.enabled(TokenType::ETH).is_ok());
+
+        Ok(())
+    }
+}
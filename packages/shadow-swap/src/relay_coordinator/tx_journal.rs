@@ -0,0 +1,370 @@
+use anyhow::{Context, Result, anyhow};
+use ethers::{
+    providers::Middleware,
+    types::{BlockNumber, H256, TransactionReceipt, transaction::eip2718::TypedTransaction},
+    utils::keccak256,
+};
+use mantle_core::parse;
+use tracing::{info, warn};
+
+use crate::{
+    database::model::DbTxSubmission,
+    models::model::IntentStatus,
+    relay_coordinator::{
+        model::{BridgeCoordinator, ChainRelayer},
+        tx_queue::TxPriority,
+    },
+};
+
+/// Where a journaled submission stands. `Submitting` and `Submitted` are
+/// open: the transaction may or may not be on chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionStatus {
+    /// Journaled before the send; the nonce is set once the queue picks it.
+    Submitting,
+    /// Sent under `tx_hash`, receipt not seen yet.
+    Submitted,
+    Confirmed,
+    Reverted,
+    /// Never sent, or its nonce went elsewhere without the operation
+    /// landing. Safe to send again.
+    Dropped,
+}
+
+impl SubmissionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Submitting => "submitting",
+            Self::Submitted => "submitted",
+            Self::Confirmed => "confirmed",
+            Self::Reverted => "reverted",
+            Self::Dropped => "dropped",
+        }
+    }
+
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "submitting" => Ok(Self::Submitting),
+            "submitted" => Ok(Self::Submitted),
+            "confirmed" => Ok(Self::Confirmed),
+            "reverted" => Ok(Self::Reverted),
+            "dropped" => Ok(Self::Dropped),
+            other => Err(anyhow!("Unknown tx submission status: {}", other)),
+        }
+    }
+}
+
+/// An open submission checked against the chain.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// The operation took effect, under this hash when it is known.
+    Landed(Option<String>),
+    Reverted,
+    Dropped,
+    /// Its nonce is not mined yet but something is pending under it.
+    Pending,
+}
+
+/// What [`ChainRelayer::send_once`] did.
+pub enum SendOnce {
+    Receipt(Box<TransactionReceipt>),
+    /// An earlier attempt already landed; nothing was sent.
+    AlreadyLanded(Option<String>),
+}
+
+pub fn operation_key(tx_type: &str, intent_id: &str) -> String {
+    format!("{}:{}", tx_type, intent_id)
+}
+
+pub fn calldata_hash(calldata: &[u8]) -> String {
+    format!("0x{}", hex::encode(keccak256(calldata)))
+}
+
+fn tx_calldata(tx: &TypedTransaction) -> &[u8] {
+    tx.data().map(|data| data.as_ref()).unwrap_or_default()
+}
+
+impl ChainRelayer {
+    /// Send `tx` for `intent_id` at most once. The attempt is journaled as
+    /// `submitting` with its calldata hash, gets its nonce from inside the
+    /// queue just before the send, then its hash, and is finalized from the
+    /// receipt. An attempt left open by a crash is resolved against the
+    /// chain first, and one that landed is never sent again.
+    pub(crate) async fn send_once(
+        &self,
+        intent_id: &str,
+        priority: TxPriority,
+        tx_type: &'static str,
+        tx: TypedTransaction,
+    ) -> Result<SendOnce> {
+        let operation = operation_key(tx_type, intent_id);
+
+        if let Some(previous) = self.database.get_tx_submission(&operation)? {
+            match self.resolve_submission(&previous).await? {
+                Resolution::Landed(tx_hash) => return Ok(SendOnce::AlreadyLanded(tx_hash)),
+                Resolution::Pending => {
+                    return Err(anyhow!(
+                        "{} for {} from an earlier attempt is still pending (nonce {:?})",
+                        tx_type,
+                        parse::short(intent_id),
+                        previous.nonce
+                    ));
+                }
+                Resolution::Reverted | Resolution::Dropped => {}
+            }
+        }
+
+        self.database.begin_tx_submission(
+            &operation,
+            intent_id,
+            self.chain_id,
+            tx_type,
+            &calldata_hash(tx_calldata(&tx)),
+        )?;
+
+        let before_send = {
+            let database = self.database.clone();
+            let operation = operation.clone();
+            Box::new(move |nonce: ethers::types::U256| {
+                database.set_tx_submission_nonce(&operation, nonce.as_u64())
+            })
+        };
+        // A failed send stays open; the next attempt resolves it by nonce
+        let pending = self
+            .tx_queue
+            .send_recorded(priority, tx_type, tx, before_send)
+            .await
+            .with_context(|| format!("Failed to send {} transaction", tx_type))?;
+        let tx_hash = format!("{:?}", pending.tx_hash());
+
+        self.database.set_tx_submission_sent(&operation, &tx_hash)?;
+        self.log_transaction(intent_id, tx_type, &tx_hash, "pending")
+            .await?;
+
+        let receipt = self.await_receipt(tx_type, "Transaction", pending).await?;
+        let status = if receipt.status == Some(1.into()) {
+            SubmissionStatus::Confirmed
+        } else {
+            SubmissionStatus::Reverted
+        };
+        self.database.finish_tx_submission(
+            &operation,
+            status.as_str(),
+            Some(&format!("{:?}", receipt.transaction_hash)),
+        )?;
+
+        Ok(SendOnce::Receipt(Box::new(receipt)))
+    }
+
+    /// Settle an open submission from what the chain shows, and journal
+    /// the outcome. Finished submissions are returned as recorded.
+    pub async fn resolve_submission(&self, submission: &DbTxSubmission) -> Result<Resolution> {
+        let resolution = match SubmissionStatus::parse(&submission.status)? {
+            SubmissionStatus::Confirmed => {
+                return Ok(Resolution::Landed(submission.tx_hash.clone()));
+            }
+            SubmissionStatus::Reverted => return Ok(Resolution::Reverted),
+            SubmissionStatus::Dropped => return Ok(Resolution::Dropped),
+            SubmissionStatus::Submitting | SubmissionStatus::Submitted => {
+                self.check_submission(submission).await?
+            }
+        };
+
+        let (status, tx_hash) = match &resolution {
+            Resolution::Landed(tx_hash) => (SubmissionStatus::Confirmed, tx_hash.as_deref()),
+            Resolution::Reverted => (SubmissionStatus::Reverted, submission.tx_hash.as_deref()),
+            Resolution::Dropped => (SubmissionStatus::Dropped, None),
+            Resolution::Pending => return Ok(resolution),
+        };
+        self.database
+            .finish_tx_submission(&submission.operation, status.as_str(), tx_hash)?;
+        info!(
+            "📒 [{}] {} resolved as {}",
+            self.name,
+            submission.operation,
+            status.as_str()
+        );
+
+        Ok(resolution)
+    }
+
+    async fn check_submission(&self, submission: &DbTxSubmission) -> Result<Resolution> {
+        // The recorded hash, if it is ours and mined, decides it
+        if let Some(tx_hash) = &submission.tx_hash {
+            let hash: H256 = parse::hex32("tx_hash", tx_hash)?.into();
+            let transaction = self
+                .client
+                .get_transaction(hash)
+                .await
+                .context("Failed to fetch transaction")?;
+
+            match transaction {
+                Some(transaction)
+                    if calldata_hash(&transaction.input) != submission.calldata_hash =>
+                {
+                    warn!(
+                        "⚠️ [{}] {} recorded {} with other calldata, resolving by nonce",
+                        self.name, submission.operation, tx_hash
+                    );
+                }
+                Some(_) => {
+                    let receipt = self
+                        .client
+                        .get_transaction_receipt(hash)
+                        .await
+                        .context("Failed to fetch receipt")?;
+                    match receipt {
+                        Some(receipt) if receipt.status == Some(1.into()) => {
+                            return Ok(Resolution::Landed(Some(tx_hash.clone())));
+                        }
+                        Some(_) => return Ok(Resolution::Reverted),
+                        None => return Ok(Resolution::Pending),
+                    }
+                }
+                None => {}
+            }
+        }
+
+        // No nonce means the queue never got as far as sending
+        let Some(nonce) = submission.nonce else {
+            return Ok(Resolution::Dropped);
+        };
+        let nonce = nonce as u64;
+
+        let address = self.client.address();
+        let mined = self
+            .client
+            .get_transaction_count(address, Some(BlockNumber::Latest.into()))
+            .await
+            .context("Failed to fetch mined nonce")?
+            .as_u64();
+
+        if mined > nonce {
+            // Mined under a hash we did not record (a fee bump) or used by
+            // another transaction: the contract state decides
+            return Ok(if self.operation_landed(submission).await? {
+                Resolution::Landed(None)
+            } else {
+                Resolution::Dropped
+            });
+        }
+
+        let pending = self
+            .client
+            .get_transaction_count(address, Some(BlockNumber::Pending.into()))
+            .await
+            .context("Failed to fetch pending nonce")?
+            .as_u64();
+
+        Ok(if pending > nonce {
+            Resolution::Pending
+        } else {
+            Resolution::Dropped
+        })
+    }
+
+    /// Whether the contract shows the operation's effect.
+    async fn operation_landed(&self, submission: &DbTxSubmission) -> Result<bool> {
+        match submission.tx_type.as_str() {
+            "settle_intent" => Ok(self.get_source_intent_state(&submission.intent_id).await?.0),
+            other => Err(anyhow!("No on-chain check for {} submissions", other)),
+        }
+    }
+}
+
+impl BridgeCoordinator {
+    /// Resolve the submissions a previous run left open, so nothing it may
+    /// have sent is sent again. Settlements found on chain mark their
+    /// intent `solver_paid`.
+    pub async fn recover_submissions(&self) -> Result<()> {
+        for relayer in self.chains.iter() {
+            let open = self.database.get_open_tx_submissions(relayer.chain_id)?;
+            if open.is_empty() {
+                continue;
+            }
+            info!(
+                "📒 [{}] Resolving {} open submission(s) from the last run",
+                relayer.name,
+                open.len()
+            );
+
+            for submission in open {
+                match relayer.resolve_submission(&submission).await {
+                    Ok(Resolution::Landed(tx_hash)) if submission.tx_type == "settle_intent" => {
+                        if let Some(tx_hash) = tx_hash {
+                            self.database
+                                .update_source_settlement_txid(&submission.intent_id, &tx_hash)?;
+                        }
+                        self.database.update_intent_status(
+                            &submission.intent_id,
+                            IntentStatus::SolverPaid,
+                        )?;
+                    }
+                    Ok(Resolution::Pending) => warn!(
+                        "⏳ [{}] {} still pending, left open",
+                        relayer.name, submission.operation
+                    ),
+                    Ok(_) => {}
+                    Err(e) => warn!(
+                        "⚠️ [{}] Could not resolve {}: {:#}",
+                        relayer.name, submission.operation, e
+                    ),
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::{random_hash, test_database};
+    use serial_test::serial;
+
+    #[test]
+    #[serial(db)]
+    fn test_journal_moves_through_both_phases() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        let intent_id = random_hash();
+        let operation = operation_key("settle_intent", &intent_id);
+        let calldata = calldata_hash(b"settleIntent");
+
+        database.begin_tx_submission(&operation, &intent_id, 5003, "settle_intent", &calldata)?;
+        database.set_tx_submission_nonce(&operation, 7)?;
+        let open = database.get_open_tx_submissions(5003)?;
+        assert_eq!(open.len(), 1);
+        assert_eq!(open[0].status, SubmissionStatus::Submitting.as_str());
+        assert_eq!((open[0].nonce, open[0].tx_hash.as_deref()), (Some(7), None));
+
+        let tx_hash = random_hash();
+        database.set_tx_submission_sent(&operation, &tx_hash)?;
+        database.finish_tx_submission(&operation, "confirmed", Some(&tx_hash))?;
+        assert!(database.get_open_tx_submissions(5003)?.is_empty());
+
+        // A new attempt starts over, without the old nonce or hash
+        database.begin_tx_submission(&operation, &intent_id, 5003, "settle_intent", &calldata)?;
+        let retry = database.get_tx_submission(&operation)?.unwrap();
+        assert_eq!(retry.status, "submitting");
+        assert_eq!((retry.nonce, retry.tx_hash), (None, None));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_statuses_round_trip() {
+        for status in [
+            SubmissionStatus::Submitting,
+            SubmissionStatus::Submitted,
+            SubmissionStatus::Confirmed,
+            SubmissionStatus::Reverted,
+            SubmissionStatus::Dropped,
+        ] {
+            assert_eq!(SubmissionStatus::parse(status.as_str()).unwrap(), status);
+        }
+    }
+}
@@ -0,0 +1,788 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BTreeMap, BinaryHeap},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, AtomicUsize, Ordering as AtomicOrdering},
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, anyhow};
+use ethers::{
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    types::{BlockNumber, H256, TransactionReceipt, U256, transaction::eip2718::TypedTransaction},
+};
+use mantle_core::tx_strategy::ChainTxStrategy;
+use signing_service::client::SignerHandle;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, error, info, warn};
+
+type QueueClient = SignerMiddleware<Provider<Http>, SignerHandle>;
+
+/// Runs inside the queue once a nonce is assigned and before the send, e.g.
+/// to journal the nonce. An error cancels the send and frees the nonce.
+pub type BeforeSend = Box<dyn FnMut(U256) -> Result<()> + Send>;
+
+/// Nodes accept a same-nonce replacement at 10% above the previous fees; a
+/// little headroom avoids `replacement transaction underpriced` on rounding.
+const REPLACEMENT_BUMP_PCT: u64 = 112;
+/// How often the queue looks at its sent, unmined transactions.
+const IN_FLIGHT_CHECK_INTERVAL: Duration = Duration::from_secs(5);
+const RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Send order when several transactions wait on the same chain. Claims pay
+/// users out, fills move intents forward, root syncs batch and can wait.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TxPriority {
+    RootSync,
+    Fill,
+    Claim,
+}
+
+#[derive(Debug, Clone)]
+pub struct TxQueuePolicy {
+    /// Minimum gap between two sends on the chain.
+    pub min_interval: Duration,
+    /// Retries of a send that failed on a transient RPC error.
+    pub max_retries: u32,
+    /// Backoff before the first retry, doubled on each further one.
+    pub retry_backoff: Duration,
+    /// Blocks a sent transaction may stay unmined before it is replaced
+    /// with higher fees under the same nonce.
+    pub replace_after_blocks: u64,
+    /// Replacements per transaction; `0` never replaces.
+    pub max_replacements: u32,
+}
+
+impl Default for TxQueuePolicy {
+    fn default() -> Self {
+        Self {
+            min_interval: Duration::from_millis(200),
+            max_retries: 3,
+            retry_backoff: Duration::from_secs(2),
+            replace_after_blocks: 5,
+            max_replacements: 3,
+        }
+    }
+}
+
+impl TxQueuePolicy {
+    /// Reads `<PREFIX>_TX_QUEUE_MIN_INTERVAL_MS`, `<PREFIX>_TX_QUEUE_MAX_RETRIES`,
+    /// `<PREFIX>_TX_QUEUE_RETRY_BACKOFF_MS`, `<PREFIX>_TX_QUEUE_REPLACE_AFTER_BLOCKS`
+    /// and `<PREFIX>_TX_QUEUE_MAX_REPLACEMENTS`.
+    pub fn from_env(prefix: &str) -> Result<Self> {
+        let number = |name: &str, default: u64| -> Result<u64> {
+            let key = format!("{}_TX_QUEUE_{}", prefix, name);
+            match std::env::var(&key) {
+                Ok(value) => value.parse().with_context(|| format!("Invalid {}", key)),
+                Err(_) => Ok(default),
+            }
+        };
+        let defaults = Self::default();
+
+        Ok(Self {
+            min_interval: Duration::from_millis(number(
+                "MIN_INTERVAL_MS",
+                defaults.min_interval.as_millis() as u64,
+            )?),
+            max_retries: number("MAX_RETRIES", defaults.max_retries as u64)? as u32,
+            retry_backoff: Duration::from_millis(number(
+                "RETRY_BACKOFF_MS",
+                defaults.retry_backoff.as_millis() as u64,
+            )?),
+            replace_after_blocks: number("REPLACE_AFTER_BLOCKS", defaults.replace_after_blocks)?
+                .max(1),
+            max_replacements: number("MAX_REPLACEMENTS", defaults.max_replacements as u64)? as u32,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SendFailure {
+    /// The node disagrees with the nonce we assigned.
+    Nonce,
+    /// The RPC was unreachable or rate limited; the same tx can be resent.
+    Transient,
+    /// Anything else, e.g. insufficient funds or a revert.
+    Fatal,
+}
+
+fn classify_send_error(message: &str) -> SendFailure {
+    let message = message.to_lowercase();
+    let contains_any = |needles: &[&str]| needles.iter().any(|needle| message.contains(needle));
+
+    if contains_any(&[
+        "nonce too low",
+        "nonce too high",
+        "already known",
+        "known transaction",
+        "replacement transaction underpriced",
+    ]) {
+        SendFailure::Nonce
+    } else if contains_any(&[
+        "timed out",
+        "timeout",
+        "connection",
+        "error sending request",
+        "too many requests",
+        "429",
+        "502",
+        "503",
+        "temporarily unavailable",
+    ]) {
+        SendFailure::Transient
+    } else {
+        SendFailure::Fatal
+    }
+}
+
+/// Every hash a sent transaction went out under, shared between the queue
+/// and whoever waits for its receipt.
+#[derive(Debug, Default)]
+struct SentHashes {
+    hashes: Vec<H256>,
+    /// The nonce was mined, or the queue gave up on it.
+    settled: bool,
+}
+
+/// A transaction the queue sent. Replacements keep its nonce and add hashes,
+/// so wait on this rather than on the first hash.
+#[derive(Debug, Clone)]
+pub struct SentTx {
+    pub nonce: U256,
+    first_hash: H256,
+    hashes: Arc<Mutex<SentHashes>>,
+}
+
+impl SentTx {
+    /// Hash of the first send, which is what `chain_transactions` records.
+    pub fn tx_hash(&self) -> H256 {
+        self.first_hash
+    }
+
+    fn snapshot(&self) -> (Vec<H256>, bool) {
+        let hashes = self.hashes.lock().expect("sent hashes lock");
+        (hashes.hashes.clone(), hashes.settled)
+    }
+}
+
+/// A sent transaction whose nonce has not been mined yet.
+struct InFlight {
+    tx_type: &'static str,
+    tx: TypedTransaction,
+    sent: SentTx,
+    sent_block: u64,
+    replacements: u32,
+}
+
+impl InFlight {
+    fn settle(&self) {
+        self.sent.hashes.lock().expect("sent hashes lock").settled = true;
+    }
+}
+
+struct QueuedTx {
+    priority: TxPriority,
+    seq: u64,
+    tx_type: &'static str,
+    tx: TypedTransaction,
+    enqueued_at: Instant,
+    before_send: Option<BeforeSend>,
+    reply: oneshot::Sender<Result<SentTx>>,
+}
+
+// Highest priority first, then first come first served
+impl Ord for QueuedTx {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.priority, Reverse(self.seq)).cmp(&(other.priority, Reverse(other.seq)))
+    }
+}
+
+impl PartialOrd for QueuedTx {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for QueuedTx {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedTx {}
+
+/// Handle to a chain's send queue. Every relayer transaction on the chain
+/// goes through one actor task, which prices it, assigns the nonce and sends
+/// it, so concurrent workers never race for a nonce. The actor also watches
+/// what it sent: stuck transactions are replaced with higher fees and
+/// dropped ones are resent, so one lost nonce cannot block the ones after it.
+#[derive(Clone)]
+pub struct TxQueue {
+    sender: mpsc::UnboundedSender<QueuedTx>,
+    client: Arc<QueueClient>,
+    next_seq: Arc<AtomicU64>,
+    depth: Arc<AtomicUsize>,
+}
+
+impl TxQueue {
+    pub fn spawn(
+        label: &'static str,
+        client: Arc<QueueClient>,
+        strategy: ChainTxStrategy,
+        policy: TxQueuePolicy,
+    ) -> Self {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let depth = Arc::new(AtomicUsize::new(0));
+
+        info!(
+            "   Tx queue: min interval {}ms, {} retries, replace after {} blocks (max {})",
+            policy.min_interval.as_millis(),
+            policy.max_retries,
+            policy.replace_after_blocks,
+            policy.max_replacements
+        );
+
+        let actor = TxQueueActor {
+            label,
+            client: client.clone(),
+            strategy,
+            policy,
+            nonce: None,
+            last_sent: None,
+            in_flight: BTreeMap::new(),
+            last_check: Instant::now(),
+        };
+        tokio::spawn(actor.run(receiver, depth.clone()));
+
+        Self {
+            sender,
+            client,
+            next_seq: Arc::new(AtomicU64::new(0)),
+            depth,
+        }
+    }
+
+    /// Queue `tx` and wait until it has been sent. The caller waits for the
+    /// receipt itself, through [`TxQueue::receipt`].
+    pub async fn send(
+        &self,
+        priority: TxPriority,
+        tx_type: &'static str,
+        tx: TypedTransaction,
+    ) -> Result<SentTx> {
+        self.enqueue(priority, tx_type, tx, None).await
+    }
+
+    /// Like [`TxQueue::send`], calling `before_send` with the nonce right
+    /// before each send attempt.
+    pub async fn send_recorded(
+        &self,
+        priority: TxPriority,
+        tx_type: &'static str,
+        tx: TypedTransaction,
+        before_send: BeforeSend,
+    ) -> Result<SentTx> {
+        self.enqueue(priority, tx_type, tx, Some(before_send)).await
+    }
+
+    async fn enqueue(
+        &self,
+        priority: TxPriority,
+        tx_type: &'static str,
+        tx: TypedTransaction,
+        before_send: Option<BeforeSend>,
+    ) -> Result<SentTx> {
+        let (reply, response) = oneshot::channel();
+
+        self.depth.fetch_add(1, AtomicOrdering::Relaxed);
+        self.sender
+            .send(QueuedTx {
+                priority,
+                seq: self.next_seq.fetch_add(1, AtomicOrdering::Relaxed),
+                tx_type,
+                tx,
+                enqueued_at: Instant::now(),
+                before_send,
+                reply,
+            })
+            .map_err(|_| {
+                self.depth.fetch_sub(1, AtomicOrdering::Relaxed);
+                anyhow!("Transaction queue stopped")
+            })?;
+
+        response
+            .await
+            .map_err(|_| anyhow!("Transaction queue dropped {}", tx_type))?
+    }
+
+    /// Receipt of whichever version of `sent` was mined. `None` when its
+    /// nonce went to another transaction or the queue gave up resending it.
+    pub async fn receipt(&self, sent: &SentTx) -> Result<Option<TransactionReceipt>> {
+        loop {
+            // Read `settled` first: a receipt mined before it was set is
+            // still found below
+            let (hashes, settled) = sent.snapshot();
+            for hash in hashes {
+                let receipt = self
+                    .client
+                    .get_transaction_receipt(hash)
+                    .await
+                    .context("Failed to fetch receipt")?;
+                if receipt.is_some() {
+                    return Ok(receipt);
+                }
+            }
+
+            if settled {
+                return Ok(None);
+            }
+            tokio::time::sleep(RECEIPT_POLL_INTERVAL).await;
+        }
+    }
+
+    /// Transactions waiting to be sent.
+    pub fn depth(&self) -> usize {
+        self.depth.load(AtomicOrdering::Relaxed)
+    }
+}
+
+struct TxQueueActor {
+    label: &'static str,
+    client: Arc<QueueClient>,
+    strategy: ChainTxStrategy,
+    policy: TxQueuePolicy,
+    /// Next nonce to assign; `None` re-reads the pending count.
+    nonce: Option<U256>,
+    last_sent: Option<Instant>,
+    /// Sent and not yet mined, by nonce.
+    in_flight: BTreeMap<U256, InFlight>,
+    last_check: Instant,
+}
+
+impl TxQueueActor {
+    async fn run(
+        mut self,
+        mut receiver: mpsc::UnboundedReceiver<QueuedTx>,
+        depth: Arc<AtomicUsize>,
+    ) {
+        let mut heap = BinaryHeap::new();
+
+        loop {
+            if !self.in_flight.is_empty() && self.last_check.elapsed() >= IN_FLIGHT_CHECK_INTERVAL {
+                if let Err(e) = self.check_in_flight().await {
+                    warn!("⚠️ [{}] In-flight check failed: {}", self.label, e);
+                }
+                self.last_check = Instant::now();
+            }
+
+            if heap.is_empty() {
+                let next_check = self.last_check + IN_FLIGHT_CHECK_INTERVAL;
+                let watching = !self.in_flight.is_empty();
+                tokio::select! {
+                    queued = receiver.recv() => match queued {
+                        Some(queued) => heap.push(queued),
+                        None => return,
+                    },
+                    _ = tokio::time::sleep_until(next_check.into()), if watching => continue,
+                }
+            }
+
+            // Wait out the rate limit before picking, so anything queued
+            // meanwhile competes on priority
+            if let Some(last_sent) = self.last_sent {
+                let ready_at = last_sent + self.policy.min_interval;
+                tokio::time::sleep_until(ready_at.into()).await;
+            }
+            while let Ok(queued) = receiver.try_recv() {
+                heap.push(queued);
+            }
+
+            let Some(queued) = heap.pop() else {
+                continue;
+            };
+            depth.fetch_sub(1, AtomicOrdering::Relaxed);
+
+            if queued.reply.is_closed() {
+                debug!(
+                    "[{}] {} abandoned by its caller, not sent",
+                    self.label, queued.tx_type
+                );
+                continue;
+            }
+
+            let waited = queued.enqueued_at.elapsed();
+            if waited > Duration::from_secs(5) {
+                warn!(
+                    "⏳ [{}] {} waited {}s in the tx queue ({} behind it)",
+                    self.label,
+                    queued.tx_type,
+                    waited.as_secs(),
+                    heap.len()
+                );
+            }
+
+            let result = self
+                .submit(queued.tx_type, queued.tx, queued.before_send)
+                .await;
+            self.last_sent = Some(Instant::now());
+
+            if let Err(e) = &result {
+                error!(
+                    "❌ [{}] Failed to send {}: {}",
+                    self.label, queued.tx_type, e
+                );
+            }
+            let _ = queued.reply.send(result);
+        }
+    }
+
+    async fn submit(
+        &mut self,
+        tx_type: &'static str,
+        mut tx: TypedTransaction,
+        mut before_send: Option<BeforeSend>,
+    ) -> Result<SentTx> {
+        self.strategy.apply(self.client.as_ref(), &mut tx).await?;
+
+        let mut attempt = 0;
+        loop {
+            let nonce = match self.nonce {
+                Some(nonce) => nonce,
+                None => self.pending_nonce().await?,
+            };
+            tx.set_nonce(nonce);
+            attempt += 1;
+
+            if let Some(before_send) = before_send.as_mut() {
+                before_send(nonce).with_context(|| format!("{} not sent", tx_type))?;
+            }
+
+            let sent = self
+                .client
+                .send_transaction(tx.clone(), None)
+                .await
+                .map(|pending| pending.tx_hash());
+            let message = match sent {
+                Ok(hash) => {
+                    self.nonce = Some(nonce + 1);
+                    debug!(
+                        "   📤 [{}] {} sent with nonce {}",
+                        self.label, tx_type, nonce
+                    );
+                    return Ok(self.track(tx_type, tx, nonce, hash).await);
+                }
+                Err(e) => e.to_string(),
+            };
+
+            // A nonce error after a transient one may mean the earlier
+            // attempt landed after all, so it is not resent under a new nonce
+            match classify_send_error(&message) {
+                SendFailure::Nonce if attempt == 1 => {
+                    warn!(
+                        "⚠️ [{}] Nonce {} rejected for {}, resyncing: {}",
+                        self.label, nonce, tx_type, message
+                    );
+                    self.nonce = None;
+                }
+                SendFailure::Transient if attempt <= self.policy.max_retries => {
+                    let backoff = self.policy.retry_backoff * 2u32.pow(attempt - 1);
+                    warn!(
+                        "⚠️ [{}] Sending {} failed ({}), retry {}/{} in {}ms",
+                        self.label,
+                        tx_type,
+                        message,
+                        attempt,
+                        self.policy.max_retries,
+                        backoff.as_millis()
+                    );
+                    tokio::time::sleep(backoff).await;
+                }
+                _ => {
+                    self.nonce = None;
+                    return Err(anyhow!(message));
+                }
+            }
+        }
+    }
+
+    async fn track(
+        &mut self,
+        tx_type: &'static str,
+        tx: TypedTransaction,
+        nonce: U256,
+        hash: H256,
+    ) -> SentTx {
+        let sent = SentTx {
+            nonce,
+            first_hash: hash,
+            hashes: Arc::new(Mutex::new(SentHashes {
+                hashes: vec![hash],
+                settled: false,
+            })),
+        };
+        // An unknown block only delays the first replacement check
+        let sent_block = self
+            .client
+            .get_block_number()
+            .await
+            .map(|block| block.as_u64())
+            .unwrap_or_default();
+
+        if let Some(previous) = self.in_flight.insert(
+            nonce,
+            InFlight {
+                tx_type,
+                tx,
+                sent: sent.clone(),
+                sent_block,
+                replacements: 0,
+            },
+        ) {
+            // The node took the nonce back, so the earlier send is gone
+            previous.settle();
+        }
+
+        sent
+    }
+
+    /// Forget mined nonces, then look at the oldest unmined one: resend it
+    /// when the node dropped it, and replace it with higher fees when it has
+    /// sat for `replace_after_blocks`. Later nonces cannot mine before it.
+    async fn check_in_flight(&mut self) -> Result<()> {
+        let address = self.client.address();
+        let mined = self
+            .client
+            .get_transaction_count(address, Some(BlockNumber::Latest.into()))
+            .await
+            .context("Failed to fetch mined nonce")?;
+
+        let pending = self.in_flight.split_off(&mined);
+        for done in std::mem::replace(&mut self.in_flight, pending).into_values() {
+            done.settle();
+        }
+
+        let Some((&nonce, oldest)) = self.in_flight.iter().next() else {
+            return Ok(());
+        };
+        let (tx_type, sent_block, replacements) =
+            (oldest.tx_type, oldest.sent_block, oldest.replacements);
+
+        let mut known = false;
+        for hash in oldest.sent.snapshot().0 {
+            if self.client.get_transaction(hash).await?.is_some() {
+                known = true;
+                break;
+            }
+        }
+
+        let head = self.client.get_block_number().await?.as_u64();
+        let waited = head.saturating_sub(sent_block);
+        if known && waited < self.policy.replace_after_blocks {
+            return Ok(());
+        }
+
+        if replacements >= self.policy.max_replacements {
+            if !known {
+                // Leave the gap to the next send, which re-reads the pending
+                // nonce and so takes this one
+                error!(
+                    "❌ [{}] {} with nonce {} dropped and out of resends, freeing the nonce",
+                    self.label, tx_type, nonce
+                );
+                if let Some(gone) = self.in_flight.remove(&nonce) {
+                    gone.settle();
+                }
+                self.nonce = None;
+            }
+            return Ok(());
+        }
+
+        let reason = if known {
+            format!("unmined after {} blocks", waited)
+        } else {
+            "dropped by the node".to_string()
+        };
+        match self.replace(nonce).await {
+            Ok(hash) => warn!(
+                "🔁 [{}] {} with nonce {} {}, resent as {:?}",
+                self.label, tx_type, nonce, reason, hash
+            ),
+            Err(e) => warn!(
+                "⚠️ [{}] Could not replace nonce {} ({}): {}",
+                self.label, nonce, reason, e
+            ),
+        }
+
+        Ok(())
+    }
+
+    /// Resend the in-flight transaction at `nonce` at the current price, and
+    /// at least the minimum replacement bump above its last send.
+    async fn replace(&mut self, nonce: U256) -> Result<H256> {
+        let entry = self
+            .in_flight
+            .get_mut(&nonce)
+            .ok_or_else(|| anyhow!("Nonce {} is not in flight", nonce))?;
+        entry.replacements += 1;
+
+        let mut market = entry.tx.clone();
+        self.strategy
+            .apply(self.client.as_ref(), &mut market)
+            .await?;
+        let mut tx = replacement_fees(&entry.tx, &market);
+        if let (Some(cap), Some(fee)) = (self.strategy.max_fee_per_gas, fee_per_gas(&tx))
+            && fee > cap
+        {
+            return Err(anyhow!("Replacement fee {} above the cap {}", fee, cap));
+        }
+        tx.set_nonce(nonce);
+
+        let hash = self
+            .client
+            .send_transaction(tx.clone(), None)
+            .await
+            .map_err(|e| anyhow!("{}", e))?
+            .tx_hash();
+
+        let head = self.client.get_block_number().await?.as_u64();
+        entry.tx = tx;
+        entry.sent_block = head;
+        entry
+            .sent
+            .hashes
+            .lock()
+            .expect("sent hashes lock")
+            .hashes
+            .push(hash);
+
+        Ok(hash)
+    }
+
+    async fn pending_nonce(&mut self) -> Result<U256> {
+        let nonce = self
+            .client
+            .get_transaction_count(self.client.address(), Some(BlockNumber::Pending.into()))
+            .await
+            .context("Failed to fetch relayer nonce")?;
+
+        self.nonce = Some(nonce);
+        Ok(nonce)
+    }
+}
+
+fn fee_per_gas(tx: &TypedTransaction) -> Option<U256> {
+    match tx {
+        TypedTransaction::Eip1559(inner) => inner.max_fee_per_gas,
+        _ => tx.gas_price(),
+    }
+}
+
+/// `previous` priced at the higher of the `market` fees and the minimum
+/// replacement bump over what `previous` paid.
+fn replacement_fees(previous: &TypedTransaction, market: &TypedTransaction) -> TypedTransaction {
+    let bump = |fee: Option<U256>| {
+        fee.map(|fee| fee.saturating_mul(U256::from(REPLACEMENT_BUMP_PCT)) / U256::from(100))
+    };
+    let higher = |a: Option<U256>, b: Option<U256>| a.max(b);
+
+    let mut tx = previous.clone();
+    match (&mut tx, market) {
+        (TypedTransaction::Eip1559(inner), TypedTransaction::Eip1559(market)) => {
+            inner.max_fee_per_gas = higher(market.max_fee_per_gas, bump(inner.max_fee_per_gas));
+            inner.max_priority_fee_per_gas = higher(
+                market.max_priority_fee_per_gas,
+                bump(inner.max_priority_fee_per_gas),
+            );
+        }
+        _ => {
+            if let Some(price) = higher(market.gas_price(), bump(previous.gas_price())) {
+                tx.set_gas_price(price);
+            }
+        }
+    }
+    tx
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Eip1559TransactionRequest, TransactionRequest};
+
+    #[test]
+    fn test_queue_order_and_send_errors() {
+        let queued = |priority, seq| QueuedTx {
+            priority,
+            seq,
+            tx_type: "test",
+            tx: TypedTransaction::default(),
+            enqueued_at: Instant::now(),
+            before_send: None,
+            reply: oneshot::channel().0,
+        };
+
+        let mut heap = BinaryHeap::from(vec![
+            queued(TxPriority::RootSync, 0),
+            queued(TxPriority::Fill, 1),
+            queued(TxPriority::Claim, 2),
+            queued(TxPriority::Fill, 3),
+            queued(TxPriority::Claim, 4),
+        ]);
+        let order: Vec<(TxPriority, u64)> = std::iter::from_fn(|| heap.pop())
+            .map(|queued| (queued.priority, queued.seq))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                (TxPriority::Claim, 2),
+                (TxPriority::Claim, 4),
+                (TxPriority::Fill, 1),
+                (TxPriority::Fill, 3),
+                (TxPriority::RootSync, 0),
+            ]
+        );
+
+        assert_eq!(
+            classify_send_error("(code: -32000, message: nonce too low, data: None)"),
+            SendFailure::Nonce
+        );
+        assert_eq!(
+            classify_send_error("HTTP error 429 Too Many Requests"),
+            SendFailure::Transient
+        );
+        assert_eq!(
+            classify_send_error("insufficient funds for gas * price + value"),
+            SendFailure::Fatal
+        );
+    }
+
+    #[test]
+    fn test_replacement_fees_clear_the_minimum_bump() {
+        let gwei = |amount: u64| U256::from(amount) * U256::exp10(9);
+
+        let sent: TypedTransaction = Eip1559TransactionRequest::new()
+            .max_fee_per_gas(gwei(30))
+            .max_priority_fee_per_gas(gwei(2))
+            .into();
+        let calm: TypedTransaction = Eip1559TransactionRequest::new()
+            .max_fee_per_gas(gwei(20))
+            .max_priority_fee_per_gas(gwei(1))
+            .into();
+        let next = replacement_fees(&sent, &calm);
+        assert_eq!(fee_per_gas(&next), Some(gwei(30) * 112 / 100));
+        let TypedTransaction::Eip1559(inner) = &next else {
+            panic!("replacement changed the tx type");
+        };
+        assert_eq!(inner.max_priority_fee_per_gas, Some(gwei(2) * 112 / 100));
+
+        // A market that moved past the bump sets the price instead
+        let legacy: TypedTransaction = TransactionRequest::new().gas_price(gwei(10)).into();
+        let busy: TypedTransaction = TransactionRequest::new().gas_price(gwei(25)).into();
+        assert_eq!(
+            fee_per_gas(&replacement_fees(&legacy, &busy)),
+            Some(gwei(25))
+        );
+    }
+}
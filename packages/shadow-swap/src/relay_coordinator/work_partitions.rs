@@ -0,0 +1,470 @@
+use std::{
+    collections::{BTreeSet, HashSet},
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, bail};
+use chrono::{DateTime, Utc};
+use ethers::utils::keccak256;
+use serde::Serialize;
+use tokio::time::interval;
+use tracing::{info, warn};
+
+use crate::{
+    database::model::DbPartitionAssignment, models::model::Intent,
+    relay_coordinator::model::BridgeCoordinator,
+};
+
+#[derive(Debug, Clone)]
+pub struct PartitionPolicy {
+    pub enabled: bool,
+    /// Unique per relayer process sharing the database.
+    pub instance_id: String,
+    pub region: String,
+    /// Must be the same on every instance.
+    pub partition_count: i32,
+    pub heartbeat_interval: Duration,
+    /// Instances silent for this long lose their partitions.
+    pub takeover_after: Duration,
+}
+
+impl Default for PartitionPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            instance_id: String::new(),
+            region: "default".to_string(),
+            partition_count: 16,
+            heartbeat_interval: Duration::from_secs(10),
+            takeover_after: Duration::from_secs(30),
+        }
+    }
+}
+
+impl PartitionPolicy {
+    /// Reads `PARTITIONING_ENABLED`, `RELAYER_INSTANCE_ID`, `RELAYER_REGION`,
+    /// `PARTITION_COUNT`, `PARTITION_HEARTBEAT_SECS` and
+    /// `PARTITION_TAKEOVER_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(enabled) = std::env::var("PARTITIONING_ENABLED") {
+            policy.enabled = enabled.parse().context("Invalid PARTITIONING_ENABLED")?;
+        }
+        if let Ok(instance_id) = std::env::var("RELAYER_INSTANCE_ID") {
+            policy.instance_id = instance_id.trim().to_string();
+        }
+        if let Ok(region) = std::env::var("RELAYER_REGION")
+            && !region.trim().is_empty()
+        {
+            policy.region = region.trim().to_string();
+        }
+        if let Ok(count) = std::env::var("PARTITION_COUNT") {
+            policy.partition_count = count
+                .parse::<i32>()
+                .context("Invalid PARTITION_COUNT")?
+                .clamp(1, 1024);
+        }
+        if let Ok(secs) = std::env::var("PARTITION_HEARTBEAT_SECS") {
+            policy.heartbeat_interval = Duration::from_secs(
+                secs.parse::<u64>()
+                    .context("Invalid PARTITION_HEARTBEAT_SECS")?
+                    .max(1),
+            );
+        }
+        if let Ok(secs) = std::env::var("PARTITION_TAKEOVER_SECS") {
+            policy.takeover_after =
+                Duration::from_secs(secs.parse().context("Invalid PARTITION_TAKEOVER_SECS")?);
+        }
+
+        if policy.enabled {
+            if policy.instance_id.is_empty() {
+                bail!("RELAYER_INSTANCE_ID is required when PARTITIONING_ENABLED=true");
+            }
+            // An instance stops work at half the takeover window, so a
+            // healthy one must renew well inside it
+            if policy.takeover_after <= policy.heartbeat_interval * 2 {
+                bail!("PARTITION_TAKEOVER_SECS must be more than twice PARTITION_HEARTBEAT_SECS");
+            }
+        }
+
+        Ok(policy)
+    }
+
+    /// How long partitions are still worked on without a successful
+    /// heartbeat. Half the takeover window, so an instance cut off from the
+    /// database stops before anyone else can take its partitions.
+    pub fn fence_after(&self) -> Duration {
+        self.takeover_after / 2
+    }
+}
+
+/// Partition an intent belongs to. Case-insensitive, stable across
+/// instances and restarts.
+pub fn partition_of(intent_id: &str, partition_count: i32) -> i32 {
+    let hash = keccak256(intent_id.to_lowercase().as_bytes());
+    let prefix = u64::from_be_bytes(hash[..8].try_into().expect("8 bytes"));
+    (prefix % partition_count.max(1) as u64) as i32
+}
+
+/// One reassignment this instance wants to make.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartitionMove {
+    pub partition: i32,
+    /// Epoch the move was planned against.
+    pub epoch: i64,
+    pub from: Option<String>,
+    /// `None` releases the partition.
+    pub to: Option<String>,
+}
+
+/// Moves that bring `instance_id` to its fair share of partitions: release
+/// any above `ceil(partitions / live instances)`, otherwise claim unowned
+/// partitions and those of instances that stopped heartbeating. Each
+/// instance only moves partitions to or from itself, so instances can plan
+/// concurrently.
+pub fn plan_rebalance(
+    instance_id: &str,
+    assignments: &[DbPartitionAssignment],
+    live: &HashSet<String>,
+) -> Vec<PartitionMove> {
+    let live_count = live.len() + usize::from(!live.contains(instance_id));
+    let share = assignments.len().div_ceil(live_count);
+    let owned: Vec<&DbPartitionAssignment> = assignments
+        .iter()
+        .filter(|a| a.instance_id.as_deref() == Some(instance_id))
+        .collect();
+
+    if owned.len() > share {
+        return owned
+            .iter()
+            .rev()
+            .take(owned.len() - share)
+            .map(|a| PartitionMove {
+                partition: a.partition,
+                epoch: a.epoch,
+                from: a.instance_id.clone(),
+                to: None,
+            })
+            .collect();
+    }
+
+    assignments
+        .iter()
+        .filter(|a| match &a.instance_id {
+            None => true,
+            Some(owner) => owner != instance_id && !live.contains(owner),
+        })
+        .take(share - owned.len())
+        .map(|a| PartitionMove {
+            partition: a.partition,
+            epoch: a.epoch,
+            from: a.instance_id.clone(),
+            to: Some(instance_id.to_string()),
+        })
+        .collect()
+}
+
+#[derive(Debug, Default)]
+struct OwnedPartitions {
+    partitions: BTreeSet<i32>,
+    renewed_at: Option<Instant>,
+}
+
+/// The partitions this instance currently works on. Every intent is owned
+/// when partitioning is disabled.
+#[derive(Debug, Clone)]
+pub struct WorkPartitions {
+    pub policy: PartitionPolicy,
+    owned: Arc<RwLock<OwnedPartitions>>,
+}
+
+impl WorkPartitions {
+    pub fn new(policy: PartitionPolicy) -> Self {
+        Self {
+            policy,
+            owned: Arc::new(RwLock::new(OwnedPartitions::default())),
+        }
+    }
+
+    /// Partitions held after the last successful heartbeat, or none once
+    /// heartbeats have failed for longer than the fence window.
+    pub fn owned(&self) -> BTreeSet<i32> {
+        let owned = self.owned.read().expect("partition lock poisoned");
+        match owned.renewed_at {
+            Some(at) if at.elapsed() < self.policy.fence_after() => owned.partitions.clone(),
+            _ => BTreeSet::new(),
+        }
+    }
+
+    /// Drop intents another instance is responsible for.
+    pub fn retain_owned(&self, mut intents: Vec<Intent>) -> Vec<Intent> {
+        if self.policy.enabled {
+            let owned = self.owned();
+            intents.retain(|intent| {
+                owned.contains(&partition_of(&intent.id, self.policy.partition_count))
+            });
+        }
+        intents
+    }
+
+    /// `started` is taken before the heartbeat write, so the fence window
+    /// never outlasts what other instances see in the database.
+    fn renew(&self, partitions: BTreeSet<i32>, started: Instant) {
+        let mut owned = self.owned.write().expect("partition lock poisoned");
+        owned.partitions = partitions;
+        owned.renewed_at = Some(started);
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct InstanceSummary {
+    pub instance_id: String,
+    pub region: String,
+    pub heartbeat_at: DateTime<Utc>,
+    pub live: bool,
+    pub partitions: Vec<i32>,
+}
+
+/// Who owns what, for the admin API.
+#[derive(Debug, Serialize)]
+pub struct PartitionOverview {
+    pub enabled: bool,
+    pub instance_id: String,
+    pub region: String,
+    pub partition_count: i32,
+    pub owned: BTreeSet<i32>,
+    pub instances: Vec<InstanceSummary>,
+    pub unassigned: Vec<i32>,
+}
+
+impl BridgeCoordinator {
+    /// Heartbeat and rebalance partitions until shutdown.
+    pub async fn run_work_partitions(&self) {
+        let policy = &self.partitions.policy;
+        info!(
+            "🧩 Work partitioning started as {} in {} ({} partitions, takeover after {}s)",
+            policy.instance_id,
+            policy.region,
+            policy.partition_count,
+            policy.takeover_after.as_secs()
+        );
+
+        let mut ticker = interval(policy.heartbeat_interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.renew_partitions() {
+                warn!(
+                    "⚠️ Partition heartbeat failed (work stops after {}s without one): {}",
+                    policy.fence_after().as_secs(),
+                    e
+                );
+            }
+        }
+    }
+
+    fn live_instances(&self) -> Result<HashSet<String>> {
+        let cutoff = Utc::now()
+            - chrono::Duration::from_std(self.partitions.policy.takeover_after)
+                .context("Takeover window out of range")?;
+
+        Ok(self
+            .database
+            .get_relayer_instances()?
+            .into_iter()
+            .filter(|instance| instance.heartbeat_at >= cutoff)
+            .map(|instance| instance.instance_id)
+            .collect())
+    }
+
+    /// Record a heartbeat, move partitions towards a fair share and refresh
+    /// what this instance owns from the database.
+    pub fn renew_partitions(&self) -> Result<()> {
+        let policy = &self.partitions.policy;
+        let started = Instant::now();
+        self.database
+            .record_relayer_heartbeat(&policy.instance_id, &policy.region)?;
+
+        let live = self.live_instances()?;
+        let assignments = self
+            .database
+            .get_partition_assignments(policy.partition_count)?;
+
+        for step in plan_rebalance(&policy.instance_id, &assignments, &live) {
+            let moved =
+                self.database
+                    .reassign_partition(step.partition, step.epoch, step.to.as_deref())?;
+            if !moved {
+                continue;
+            }
+            match (&step.from, &step.to) {
+                (Some(from), Some(_)) => warn!(
+                    "🔁 Took over partition {} from {} after missed heartbeats",
+                    step.partition, from
+                ),
+                (None, Some(_)) => info!("🧩 Claimed partition {}", step.partition),
+                (_, None) => info!("🧩 Released partition {} to rebalance", step.partition),
+            }
+        }
+
+        let owned = self
+            .database
+            .get_partition_assignments(policy.partition_count)?
+            .into_iter()
+            .filter(|a| a.instance_id.as_deref() == Some(policy.instance_id.as_str()))
+            .map(|a| a.partition)
+            .collect();
+        self.partitions.renew(owned, started);
+
+        Ok(())
+    }
+
+    pub fn partition_overview(&self) -> Result<PartitionOverview> {
+        let policy = &self.partitions.policy;
+        let live = self.live_instances()?;
+        let assignments = self
+            .database
+            .get_partition_assignments(policy.partition_count)?;
+
+        let instances = self
+            .database
+            .get_relayer_instances()?
+            .into_iter()
+            .map(|instance| InstanceSummary {
+                live: live.contains(&instance.instance_id),
+                partitions: assignments
+                    .iter()
+                    .filter(|a| a.instance_id.as_deref() == Some(instance.instance_id.as_str()))
+                    .map(|a| a.partition)
+                    .collect(),
+                instance_id: instance.instance_id,
+                region: instance.region,
+                heartbeat_at: instance.heartbeat_at,
+            })
+            .collect();
+
+        Ok(PartitionOverview {
+            enabled: policy.enabled,
+            instance_id: policy.instance_id.clone(),
+            region: policy.region.clone(),
+            partition_count: policy.partition_count,
+            owned: self.partitions.owned(),
+            instances,
+            unassigned: assignments
+                .iter()
+                .filter(|a| a.instance_id.is_none())
+                .map(|a| a.partition)
+                .collect(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::database::test_support::test_database;
+    use serial_test::serial;
+
+    fn assignment(partition: i32, instance_id: Option<&str>) -> DbPartitionAssignment {
+        DbPartitionAssignment {
+            partition,
+            instance_id: instance_id.map(str::to_string),
+            epoch: 3,
+            assigned_at: Utc::now(),
+        }
+    }
+
+    fn live(ids: &[&str]) -> HashSet<String> {
+        ids.iter().map(|id| id.to_string()).collect()
+    }
+
+    #[test]
+    fn test_plan_rebalance_shares_and_takes_over() {
+        let partitions = |owners: [Option<&str>; 6]| -> Vec<DbPartitionAssignment> {
+            owners
+                .iter()
+                .enumerate()
+                .map(|(i, owner)| assignment(i as i32, *owner))
+                .collect()
+        };
+        let targets = |moves: Vec<PartitionMove>| -> Vec<(i32, Option<String>)> {
+            moves.into_iter().map(|m| (m.partition, m.to)).collect()
+        };
+
+        // Alone on a fresh table: everything
+        let fresh = partitions([None; 6]);
+        assert_eq!(plan_rebalance("us-1", &fresh, &live(&["us-1"])).len(), 6);
+
+        // A second region joins: the first gives up half, the newcomer
+        // waits for them
+        let held = partitions([Some("us-1"); 6]);
+        let both = live(&["us-1", "eu-1"]);
+        assert!(plan_rebalance("eu-1", &held, &both).is_empty());
+        assert_eq!(
+            targets(plan_rebalance("us-1", &held, &both)),
+            vec![(5, None), (4, None), (3, None)]
+        );
+
+        let split = partitions([Some("us-1"), Some("us-1"), Some("us-1"), None, None, None]);
+        let claimed = plan_rebalance("eu-1", &split, &both);
+        assert_eq!(
+            targets(claimed.clone()),
+            vec![
+                (3, Some("eu-1".to_string())),
+                (4, Some("eu-1".to_string())),
+                (5, Some("eu-1".to_string())),
+            ]
+        );
+        assert_eq!(claimed[0].epoch, 3);
+
+        // us-1 stops heartbeating: eu-1 takes its partitions over
+        let even = partitions([
+            Some("us-1"),
+            Some("us-1"),
+            Some("us-1"),
+            Some("eu-1"),
+            Some("eu-1"),
+            Some("eu-1"),
+        ]);
+        assert!(plan_rebalance("eu-1", &even, &both).is_empty());
+        let takeover = plan_rebalance("eu-1", &even, &live(&["eu-1"]));
+        assert_eq!(takeover.len(), 3);
+        assert!(
+            takeover
+                .iter()
+                .all(|m| m.from.as_deref() == Some("us-1") && m.to.as_deref() == Some("eu-1"))
+        );
+
+        assert_eq!(partition_of("0xABCD", 16), partition_of("0xabcd", 16));
+        assert!((0..16).contains(&partition_of("0xabcd", 16)));
+    }
+
+    #[test]
+    #[serial(db)]
+    fn test_partition_reassignment_is_compare_and_set() -> Result<()> {
+        let Some(database) = test_database()? else {
+            return Ok(());
+        };
+
+        database.record_relayer_heartbeat("us-1", "us-east")?;
+        let assignments = database.get_partition_assignments(4)?;
+        assert_eq!(assignments.len(), 4);
+        assert!(assignments.iter().all(|a| a.instance_id.is_none()));
+
+        let epoch = assignments[0].epoch;
+        assert!(database.reassign_partition(0, epoch, Some("us-1"))?);
+        // eu-1 planned against the same epoch and lost the race
+        assert!(!database.reassign_partition(0, epoch, Some("eu-1"))?);
+
+        let assignments = database.get_partition_assignments(4)?;
+        assert_eq!(assignments[0].instance_id.as_deref(), Some("us-1"));
+        assert_eq!(assignments[0].epoch, epoch + 1);
+
+        let instances = database.get_relayer_instances()?;
+        assert_eq!(instances.len(), 1);
+        assert_eq!(instances[0].region, "us-east");
+
+        Ok(())
+    }
+}
@@ -1,189 +1,360 @@
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use mantle_core::{metrics::Metrics, parse};
 use std::sync::Arc;
-use tokio::time::{Duration, sleep};
-use tracing::{error, info};
+use tokio::time::Duration;
+use tracing::{debug, error, info};
 
 use crate::{
-    database::database::Database,
-    relay_coordinator::model::{EthereumRelayer, MantleRelayer},
+    chains::registry::ChainRegistry,
+    database::{database::Database, model::DbRootSync},
+    relay_coordinator::model::ChainRelayer,
+    shutdown::Shutdown,
 };
 
-const MANTLE_CHAIN_ID: u32 = 5003;
-const ETHEREUM_CHAIN_ID: u32 = 11155111;
 const ZERO_LEAF: &str = "0x0000000000000000000000000000000000000000000000000000000000000000";
 
+/// How often the sync loop looks at the trees, and when a changed root is
+/// worth a transaction: once `min_new_leaves` leaves were added since the
+/// last push, or once that push is `max_staleness` old.
+///
+/// Workers that need a root on chain right away push it regardless.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RootSyncPolicy {
+    pub interval: Duration,
+    pub min_new_leaves: u64,
+    pub max_staleness: Duration,
+}
+
+impl Default for RootSyncPolicy {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(10),
+            min_new_leaves: 1,
+            max_staleness: Duration::from_secs(300),
+        }
+    }
+}
+
+impl RootSyncPolicy {
+    /// Reads `ROOT_SYNC_INTERVAL_SECS`, `ROOT_SYNC_MIN_NEW_LEAVES` and
+    /// `ROOT_SYNC_MAX_STALENESS_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+        let var = |key: &str| std::env::var(key).ok().filter(|v| !v.trim().is_empty());
+
+        if let Some(secs) = var("ROOT_SYNC_INTERVAL_SECS") {
+            let secs: u64 = secs
+                .trim()
+                .parse()
+                .context("Invalid ROOT_SYNC_INTERVAL_SECS")?;
+            policy.interval = Duration::from_secs(secs.max(1));
+        }
+        if let Some(leaves) = var("ROOT_SYNC_MIN_NEW_LEAVES") {
+            let leaves: u64 = leaves
+                .trim()
+                .parse()
+                .context("Invalid ROOT_SYNC_MIN_NEW_LEAVES")?;
+            policy.min_new_leaves = leaves.max(1);
+        }
+        if let Some(secs) = var("ROOT_SYNC_MAX_STALENESS_SECS") {
+            let secs: u64 = secs
+                .trim()
+                .parse()
+                .context("Invalid ROOT_SYNC_MAX_STALENESS_SECS")?;
+            policy.max_staleness = Duration::from_secs(secs);
+        }
+
+        Ok(policy)
+    }
+
+    /// Whether the loop should push `root`, with `leaf_count` leaves behind
+    /// it, given the last push it recorded for that tree and chain.
+    pub fn push_due(
+        &self,
+        root: &str,
+        leaf_count: i64,
+        last: Option<&DbRootSync>,
+        now: DateTime<Utc>,
+    ) -> bool {
+        let Some(last) = last else {
+            return true;
+        };
+        if last.root.eq_ignore_ascii_case(root) {
+            return false;
+        }
+
+        let new_leaves = leaf_count - last.leaf_count.unwrap_or(0);
+        let age = (now - last.created_at).to_std().unwrap_or_default();
+        new_leaves >= self.min_new_leaves as i64 || age >= self.max_staleness
+    }
+}
+
+/// A local tree as it stands: its root and how many leaves are behind it.
+struct LocalTree {
+    root: String,
+    leaf_count: i64,
+}
+
+/// Keeps every chain's view of every other chain's commitment and fill
+/// roots in line with the local trees.
 pub struct RootSyncCoordinator {
     db: Arc<Database>,
-    ethereum_relayer: Arc<EthereumRelayer>,
-    mantle_relayer: Arc<MantleRelayer>,
-    sync_interval_secs: u64,
+    chains: ChainRegistry,
+    policy: RootSyncPolicy,
+    metrics: Arc<Metrics>,
 }
 
 impl RootSyncCoordinator {
     pub fn new(
         db: Arc<Database>,
-        ethereum_relayer: Arc<EthereumRelayer>,
-        mantle_relayer: Arc<MantleRelayer>,
-        sync_interval_secs: u64,
+        chains: ChainRegistry,
+        policy: RootSyncPolicy,
+        metrics: Arc<Metrics>,
     ) -> Self {
         Self {
             db,
-            ethereum_relayer,
-            mantle_relayer,
-            sync_interval_secs,
+            chains,
+            policy,
+            metrics,
         }
     }
 
+    /// One pass of the sync loop: push each root that changed and is due
+    /// under the policy.
     pub async fn sync_all_roots(&self) -> Result<()> {
-        let results = tokio::join!(
-            self.sync_ethereum_commitments_to_mantle(),
-            self.sync_mantle_fills_to_ethereum(),
-            self.sync_mantle_commitments_to_ethereum(),
-            self.sync_ethereum_fills_to_mantle()
-        );
+        for source in self.chains.iter() {
+            for dest in self
+                .chains
+                .iter()
+                .filter(|dest| dest.chain_id != source.chain_id)
+            {
+                let (commitments, fills) = tokio::join!(
+                    async {
+                        if self.due(source, dest, "commitments")? {
+                            self.sync_commitments(source, dest).await?;
+                        }
+                        Ok::<_, anyhow::Error>(())
+                    },
+                    async {
+                        if self.due(source, dest, "fills")? {
+                            self.sync_fills(source, dest).await?;
+                        }
+                        Ok::<_, anyhow::Error>(())
+                    }
+                );
 
-        if let Err(e) = results.0 {
-            error!("❌ Eth-Commit → Mantle: {}", e);
-        }
-        if let Err(e) = results.1 {
-            error!("❌ Mantle-Fill → Eth: {}", e);
-        }
-        if let Err(e) = results.2 {
-            error!("❌ Mantle-Commit → Eth: {}", e);
-        }
-        if let Err(e) = results.3 {
-            error!("❌ Eth-Fill → Mantle: {}", e);
+                if let Err(e) = commitments {
+                    error!("❌ {}-Commit → {}: {}", source.name, dest.name, e);
+                }
+                if let Err(e) = fills {
+                    error!("❌ {}-Fill → {}: {}", source.name, dest.name, e);
+                }
+            }
         }
 
         Ok(())
     }
 
-    pub async fn sync_ethereum_commitments_to_mantle(&self) -> Result<()> {
-        let db_root = self.get_db_root_standardized("ethereum_commitments")?;
-        let onchain_root = self
-            .mantle_relayer
-            .get_synced_ethereum_commitment_root()
-            .await?
-            .to_lowercase();
+    /// Whether `source`'s `tree` root has changed enough since it was last
+    /// pushed to `dest` to push it again.
+    fn due(&self, source: &ChainRelayer, dest: &ChainRelayer, tree: &str) -> Result<bool> {
+        let local = self.local_tree(&format!("{}_{}", source.key, tree))?;
+        let last = self.db.get_last_root_sync(&sync_type(source, dest, tree))?;
 
-        if db_root != onchain_root {
-            info!(
-                "🌉 [ETH → MANTLE] Syncing commitment root: {}",
-                &db_root[..10]
+        let due = self
+            .policy
+            .push_due(&local.root, local.leaf_count, last.as_ref(), Utc::now());
+        if !due && let Some(last) = last.filter(|last| last.root != local.root) {
+            debug!(
+                "⏳ [{} → {}] Holding {} root: {} new leaves since {}",
+                source.name,
+                dest.name,
+                tree,
+                local.leaf_count - last.leaf_count.unwrap_or(0),
+                last.created_at
             );
-            let root_bytes = self.hex_to_bytes32(&db_root)?;
-            self.mantle_relayer
-                .sync_source_chain_commitment_root_tx(ETHEREUM_CHAIN_ID, root_bytes)
-                .await?;
-            info!("✅ Commitment root synced");
         }
-
-        Ok(())
+        Ok(due)
     }
 
-    async fn sync_mantle_fills_to_ethereum(&self) -> Result<()> {
-        let db_root = self.get_db_root_standardized("mantle_fills")?;
-        if db_root == ZERO_LEAF {
+    /// Push `source`'s commitment root to `dest` if `dest` is behind.
+    pub async fn sync_commitments(&self, source: &ChainRelayer, dest: &ChainRelayer) -> Result<()> {
+        let newest_leaf = self.db.get_last_indexed_block(source.key)?;
+        if !self.is_final(source, newest_leaf, "commitment").await? {
             return Ok(());
         }
 
-        let onchain_root = self
-            .ethereum_relayer
-            .get_synced_mantle_fill_root()
-            .await?
-            .to_lowercase();
-
-        if db_root != onchain_root {
-            info!("🌉 [MANTLE → ETH] Syncing fill root: {}", &db_root[..10]);
-            let root_bytes = self.hex_to_bytes32(&db_root)?;
-            self.ethereum_relayer
-                .sync_dest_chain_fill_root_tx(MANTLE_CHAIN_ID, root_bytes)
-                .await?;
-            info!("✅ Fill root synced");
-        }
-
-        Ok(())
-    }
-
-    pub async fn sync_mantle_commitments_to_ethereum(&self) -> Result<()> {
-        let db_root = self.get_db_root_standardized("mantle_commitments")?;
-        let onchain_root = self
-            .ethereum_relayer
-            .get_synced_mantle_commitment_root()
+        let local = self.local_tree(&format!("{}_commitments", source.key))?;
+        let db_root = local.root;
+        let onchain_root = dest
+            .get_synced_commitment_root(source.chain_id)
             .await?
             .to_lowercase();
 
         if db_root != onchain_root {
             info!(
-                "🌉 [MANTLE → ETH] Syncing commitment root: {}",
-                &db_root[..10]
+                "🌉 [{} → {}] Syncing commitment root: {}",
+                source.name,
+                dest.name,
+                parse::short(&db_root)
             );
-            let root_bytes = self.hex_to_bytes32(&db_root)?;
-            self.ethereum_relayer
-                .sync_source_chain_commitment_root_tx(MANTLE_CHAIN_ID, root_bytes)
+            let root_bytes = parse::hex32("root", &db_root)?;
+            let tx_hash = dest
+                .sync_source_chain_commitment_root_tx(source.chain_id, root_bytes)
                 .await?;
+            self.db.insert_root_sync(
+                &sync_type(source, dest, "commitments"),
+                &db_root,
+                &tx_hash,
+                Some(local.leaf_count),
+            )?;
+            self.metrics
+                .root_synced(source.key, dest.key, "commitments");
             info!("✅ Commitment root synced");
         }
 
         Ok(())
     }
 
-    async fn sync_ethereum_fills_to_mantle(&self) -> Result<()> {
-        let db_root = self.get_db_root_standardized("ethereum_fills")?;
+    /// Push the root of fills made on `source` to `dest` if `dest` is behind.
+    async fn sync_fills(&self, source: &ChainRelayer, dest: &ChainRelayer) -> Result<()> {
+        let local = self.local_tree(&format!("{}_fills", source.key))?;
+        let db_root = local.root;
         if db_root == ZERO_LEAF {
             return Ok(());
         }
 
-        let onchain_root = self
-            .mantle_relayer
-            .get_synced_ethereum_fill_root()
+        let newest_leaf = self.db.get_last_fill_block(source.chain_id)?;
+        if !self.is_final(source, newest_leaf, "fill").await? {
+            return Ok(());
+        }
+
+        let onchain_root = dest
+            .get_synced_fill_root(source.chain_id)
             .await?
             .to_lowercase();
 
         if db_root != onchain_root {
-            info!("🌉 [ETH → MANTLE] Syncing fill root: {}", &db_root[..10]);
-            let root_bytes = self.hex_to_bytes32(&db_root)?;
-            self.mantle_relayer
-                .sync_dest_chain_fill_root_tx(ETHEREUM_CHAIN_ID, root_bytes)
+            info!(
+                "🌉 [{} → {}] Syncing fill root: {}",
+                source.name,
+                dest.name,
+                parse::short(&db_root)
+            );
+            let root_bytes = parse::hex32("root", &db_root)?;
+            let tx_hash = dest
+                .sync_dest_chain_fill_root_tx(source.chain_id, root_bytes)
                 .await?;
+            self.db.insert_root_sync(
+                &sync_type(source, dest, "fills"),
+                &db_root,
+                &tx_hash,
+                Some(local.leaf_count),
+            )?;
+            self.metrics.root_synced(source.key, dest.key, "fills");
             info!("✅ Fill root synced");
         }
 
         Ok(())
     }
 
-    fn get_db_root_standardized(&self, tree_name: &str) -> Result<String> {
-        let root = self
+    /// Whether the newest leaf behind `source`'s local tree is final, so its
+    /// root cannot be undone by a reorg after it was pushed to another chain.
+    async fn is_final(
+        &self,
+        source: &ChainRelayer,
+        newest_leaf: Option<u64>,
+        tree: &str,
+    ) -> Result<bool> {
+        let Some(block) = newest_leaf else {
+            return Ok(true);
+        };
+
+        let final_block = source.final_block().await?;
+        if block > final_block {
+            debug!(
+                "⏳ [{}] Holding {} root: leaf at block {} not final yet ({}, final block {})",
+                source.name, tree, block, source.finality, final_block
+            );
+            return Ok(false);
+        }
+
+        Ok(true)
+    }
+
+    fn local_tree(&self, tree_name: &str) -> Result<LocalTree> {
+        let (root, leaf_count) = self
             .db
-            .get_latest_root(tree_name)?
-            .unwrap_or_else(|| ZERO_LEAF.to_string());
-        let cleaned = if root.starts_with("0x") {
+            .get_merkle_tree_by_name(tree_name)?
+            .map(|tree| (tree.root, tree.leaf_count))
+            .unwrap_or_else(|| (ZERO_LEAF.to_string(), 0));
+        let root = if root.starts_with("0x") {
             root.to_lowercase()
         } else {
             format!("0x{}", root.to_lowercase())
         };
-        Ok(cleaned)
+        Ok(LocalTree { root, leaf_count })
     }
 
-    fn hex_to_bytes32(&self, hex_str: &str) -> Result<[u8; 32]> {
-        let s = hex_str.trim_start_matches("0x");
-        let vec = hex::decode(s).map_err(|e| anyhow!("Invalid hex format: {}", e))?;
-        vec.try_into()
-            .map_err(|_| anyhow!("Hex string must be exactly 32 bytes"))
-    }
-
-    pub async fn run(self: Arc<Self>) {
+    pub async fn run(self: Arc<Self>, shutdown: Shutdown) {
         info!(
-            "🔄 RootSyncCoordinator started ({}s interval)",
-            self.sync_interval_secs
+            "🔄 RootSyncCoordinator started ({:?} interval, {} new leaves or {:?} between pushes)",
+            self.policy.interval, self.policy.min_new_leaves, self.policy.max_staleness
         );
         loop {
             let _ = self.sync_all_roots().await;
-            sleep(Duration::from_secs(self.sync_interval_secs)).await;
+            if shutdown.sleep(self.policy.interval).await {
+                break;
+            }
         }
+        info!("🔄 RootSyncCoordinator stopped");
     }
 
     pub async fn sync_now(&self) -> Result<()> {
         self.sync_all_roots().await
     }
 }
+
+/// `root_syncs` key for pushes of `source`'s `tree` root to `dest`.
+fn sync_type(source: &ChainRelayer, dest: &ChainRelayer, tree: &str) -> String {
+    format!("{}_{}_to_{}", source.key, tree, dest.key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pushed(root: &str, leaf_count: i64, at: DateTime<Utc>) -> DbRootSync {
+        DbRootSync {
+            id: 1,
+            sync_type: "ethereum_commitments_to_mantle".to_string(),
+            root: root.to_string(),
+            tx_hash: "0xabc".to_string(),
+            created_at: at,
+            leaf_count: Some(leaf_count),
+        }
+    }
+
+    #[test]
+    fn test_push_waits_for_leaves_or_staleness() {
+        let policy = RootSyncPolicy {
+            interval: Duration::from_secs(10),
+            min_new_leaves: 5,
+            max_staleness: Duration::from_secs(300),
+        };
+        let now = Utc::now();
+        let ago = |secs| now - chrono::Duration::seconds(secs);
+
+        // Never pushed
+        assert!(policy.push_due("0x02", 1, None, now));
+        // Unchanged, however old
+        assert!(!policy.push_due("0x01", 10, Some(&pushed("0x01", 10, ago(3_600))), now));
+        // Changed, but too few leaves and too recent
+        assert!(!policy.push_due("0x02", 14, Some(&pushed("0x01", 10, ago(60))), now));
+        // Enough leaves
+        assert!(policy.push_due("0x02", 15, Some(&pushed("0x01", 10, ago(60))), now));
+        // Too stale
+        assert!(policy.push_due("0x02", 11, Some(&pushed("0x01", 10, ago(300))), now));
+    }
+}
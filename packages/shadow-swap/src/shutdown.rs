@@ -0,0 +1,155 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use tokio::{
+    sync::{Notify, broadcast},
+    time::sleep,
+};
+use tracing::info;
+
+/// How long in-flight work gets to finish once shutdown starts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ShutdownPolicy {
+    pub grace: Duration,
+}
+
+impl Default for ShutdownPolicy {
+    fn default() -> Self {
+        Self {
+            grace: Duration::from_secs(60),
+        }
+    }
+}
+
+impl ShutdownPolicy {
+    /// Override the default from `SHUTDOWN_GRACE_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(secs) = std::env::var("SHUTDOWN_GRACE_SECS") {
+            policy.grace =
+                Duration::from_secs(secs.trim().parse().context("Invalid SHUTDOWN_GRACE_SECS")?);
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Broadcast to every worker when the relayer is asked to stop. Workers
+/// finish the intent in hand, then return instead of picking up more.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    sender: broadcast::Sender<()>,
+    triggered: Arc<AtomicBool>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Shutdown {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(1);
+        Self {
+            sender,
+            triggered: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn trigger(&self) {
+        if !self.triggered.swap(true, Ordering::SeqCst) {
+            let _ = self.sender.send(());
+        }
+    }
+
+    pub fn is_triggered(&self) -> bool {
+        self.triggered.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once shutdown is triggered (immediately if it already was).
+    pub async fn wait(&self) {
+        let mut receiver = self.sender.subscribe();
+        if self.is_triggered() {
+            return;
+        }
+        let _ = receiver.recv().await;
+    }
+
+    /// Sleep between polls; true when shutdown cut it short.
+    pub async fn sleep(&self, duration: Duration) -> bool {
+        tokio::select! {
+            _ = sleep(duration) => self.is_triggered(),
+            _ = self.wait() => true,
+        }
+    }
+
+    /// Like [`Shutdown::sleep`], but `wake` ends it early so a worker can
+    /// poll again as soon as there is new work.
+    pub async fn sleep_or_wake(&self, duration: Duration, wake: &Notify) -> bool {
+        tokio::select! {
+            stopped = self.sleep(duration) => stopped,
+            _ = wake.notified() => self.is_triggered(),
+        }
+    }
+
+    /// Trigger on SIGINT or SIGTERM.
+    pub async fn listen_for_signals(&self) -> Result<()> {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{SignalKind, signal};
+
+            let mut terminate =
+                signal(SignalKind::terminate()).context("Failed to listen for SIGTERM")?;
+            tokio::select! {
+                result = tokio::signal::ctrl_c() => {
+                    result.context("Failed to listen for SIGINT")?;
+                    info!("🛑 SIGINT received");
+                }
+                _ = terminate.recv() => info!("🛑 SIGTERM received"),
+            }
+        }
+
+        #[cfg(not(unix))]
+        {
+            tokio::signal::ctrl_c()
+                .await
+                .context("Failed to listen for Ctrl-C")?;
+            info!("🛑 Ctrl-C received");
+        }
+
+        self.trigger();
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_trigger_wakes_sleepers_and_later_waiters() {
+        let shutdown = Shutdown::new();
+        assert!(!shutdown.sleep(Duration::from_millis(1)).await);
+
+        let sleeper = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.sleep(Duration::from_secs(3600)).await }
+        });
+        tokio::task::yield_now().await;
+        shutdown.trigger();
+
+        assert!(sleeper.await.unwrap());
+        assert!(shutdown.is_triggered());
+        tokio::time::timeout(Duration::from_secs(1), shutdown.wait())
+            .await
+            .expect("wait returns once triggered");
+    }
+}
@@ -0,0 +1,2 @@
+pub mod webhook_dispatcher;
+pub mod webhook_filter;
@@ -0,0 +1,484 @@
+use std::{collections::HashMap, sync::Arc, time::Duration};
+
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use mantle_core::http_client::OutboundClient;
+use serde::Serialize;
+use sha2::Sha256;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use crate::{
+    database::{
+        database::Database,
+        model::{DbWebhookEndpoint, DbWebhookSecret, NewWebhookEndpoint},
+    },
+    webhooks::webhook_filter::{ClauseMatch, WebhookFilter},
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// `t=<unix seconds>,<key id>=<hex hmac>,...`, one entry per active secret.
+pub const SIGNATURE_HEADER: &str = "X-Webhook-Signature";
+/// Unique per delivery, for receivers to drop duplicates.
+pub const DELIVERY_HEADER: &str = "X-Webhook-Id";
+/// Longest overlap a rotation may ask for.
+const MAX_ROTATION_OVERLAP_SECS: u64 = 365 * 86_400;
+
+#[derive(Debug, Clone, Copy)]
+pub struct WebhookPolicy {
+    /// How long a rotated-out secret keeps signing next to its replacement.
+    pub rotation_overlap_secs: u64,
+    pub timeout: Duration,
+}
+
+impl Default for WebhookPolicy {
+    fn default() -> Self {
+        Self {
+            rotation_overlap_secs: 86_400,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+impl WebhookPolicy {
+    /// Reads `WEBHOOK_ROTATION_OVERLAP_SECS` and `WEBHOOK_TIMEOUT_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+        let secs = |name: &str, default: u64| -> Result<u64> {
+            match std::env::var(name) {
+                Ok(value) => value.parse().with_context(|| format!("Invalid {}", name)),
+                Err(_) => Ok(default),
+            }
+        };
+
+        Ok(Self {
+            rotation_overlap_secs: secs(
+                "WEBHOOK_ROTATION_OVERLAP_SECS",
+                defaults.rotation_overlap_secs,
+            )?,
+            timeout: Duration::from_secs(
+                secs("WEBHOOK_TIMEOUT_SECS", defaults.timeout.as_secs())?.max(1),
+            ),
+        })
+    }
+}
+
+/// Signature header for `body` sent at `timestamp`. Each secret signs
+/// `"{timestamp}.{body}"`; receivers check the entry for the key id they hold.
+pub fn signature_header(timestamp: i64, body: &[u8], secrets: &[&DbWebhookSecret]) -> String {
+    let mut header = format!("t={}", timestamp);
+
+    for secret in secrets {
+        let mut mac = HmacSha256::new_from_slice(secret.secret.as_bytes())
+            .expect("HMAC can take key of any size");
+        mac.update(timestamp.to_string().as_bytes());
+        mac.update(b".");
+        mac.update(body);
+
+        header.push_str(&format!(
+            ",{}={}",
+            secret.key_id,
+            hex::encode(mac.finalize().into_bytes())
+        ));
+    }
+
+    header
+}
+
+/// Fresh `(key_id, secret)` pair.
+fn new_signing_secret() -> (String, String) {
+    let key_id = format!("whk_{}", &Uuid::new_v4().simple().to_string()[..12]);
+    let secret = format!(
+        "whsec_{}{}",
+        Uuid::new_v4().simple(),
+        Uuid::new_v4().simple()
+    );
+
+    (key_id, secret)
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookKey {
+    pub key_id: String,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// Admin view of an endpoint; secrets are never listed.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEndpointView {
+    #[serde(flatten)]
+    pub endpoint: DbWebhookEndpoint,
+    pub keys: Vec<WebhookKey>,
+}
+
+/// Returned once when an endpoint is created or its secret rotated.
+#[derive(Debug, Clone, Serialize)]
+pub struct IssuedSecret {
+    pub endpoint_id: String,
+    pub key_id: String,
+    pub secret: String,
+    /// When the secrets this one replaces stop signing.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_keys_expire_at: Option<DateTime<Utc>>,
+}
+
+/// Outcome of `POST /admin/webhooks/:id/test`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TestDelivery {
+    pub endpoint_id: String,
+    pub event: String,
+    pub matched: bool,
+    /// Each filter clause against the sample; empty without a filter.
+    pub clauses: Vec<ClauseMatch>,
+    /// Whether the sample was sent; `None` when it was not attempted.
+    pub delivered: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// A filter as given, parsed; blank means every event.
+fn parse_filter(filter: Option<&str>) -> Result<Option<WebhookFilter>> {
+    filter
+        .map(str::trim)
+        .filter(|filter| !filter.is_empty())
+        .map(WebhookFilter::parse)
+        .transpose()
+}
+
+/// Delivers signed event notifications to the registered endpoints.
+pub struct WebhookDispatcher {
+    pub policy: WebhookPolicy,
+    database: Arc<Database>,
+    http: OutboundClient,
+}
+
+impl WebhookDispatcher {
+    pub fn new(policy: WebhookPolicy, database: Arc<Database>, http: OutboundClient) -> Self {
+        Self {
+            policy,
+            database,
+            http,
+        }
+    }
+
+    pub fn create_endpoint(
+        &self,
+        url: &str,
+        description: Option<&str>,
+        filter: Option<&str>,
+    ) -> Result<(DbWebhookEndpoint, IssuedSecret)> {
+        let parsed = reqwest::Url::parse(url).context("Invalid webhook URL")?;
+        if !matches!(parsed.scheme(), "https" | "http") {
+            return Err(anyhow!("Webhook URL must be http or https"));
+        }
+        let filter = parse_filter(filter)?.map(|filter| filter.to_string());
+
+        let id = format!("wh_{}", Uuid::new_v4().simple());
+        let (key_id, secret) = new_signing_secret();
+        let endpoint = self.database.create_webhook_endpoint(
+            &NewWebhookEndpoint {
+                id: &id,
+                url,
+                description,
+                created_at: Utc::now(),
+                updated_at: Utc::now(),
+                filter: filter.as_deref(),
+            },
+            &key_id,
+            &secret,
+        )?;
+
+        info!("🪝 Webhook endpoint {} registered for {}", id, url);
+        Ok((
+            endpoint,
+            IssuedSecret {
+                endpoint_id: id,
+                key_id,
+                secret,
+                previous_keys_expire_at: None,
+            },
+        ))
+    }
+
+    /// Issue a new secret. Existing ones keep signing for `overlap_secs`
+    /// (the policy default when unset, capped at a year); `0` revokes them
+    /// immediately.
+    pub fn rotate_secret(
+        &self,
+        endpoint_id: &str,
+        overlap_secs: Option<u64>,
+    ) -> Result<Option<IssuedSecret>> {
+        let overlap = overlap_secs
+            .unwrap_or(self.policy.rotation_overlap_secs)
+            .min(MAX_ROTATION_OVERLAP_SECS);
+        let retire_at = Utc::now() + chrono::Duration::seconds(overlap as i64);
+        let (key_id, secret) = new_signing_secret();
+
+        if !self
+            .database
+            .rotate_webhook_secret(endpoint_id, &key_id, &secret, retire_at)?
+        {
+            return Ok(None);
+        }
+
+        info!(
+            "🔁 Webhook {} rotated to {}; previous keys expire at {}",
+            endpoint_id, key_id, retire_at
+        );
+        Ok(Some(IssuedSecret {
+            endpoint_id: endpoint_id.to_string(),
+            key_id,
+            secret,
+            previous_keys_expire_at: Some(retire_at),
+        }))
+    }
+
+    pub fn list_endpoints(&self) -> Result<Vec<WebhookEndpointView>> {
+        let mut keys = self.active_secrets()?;
+
+        Ok(self
+            .database
+            .list_webhook_endpoints()?
+            .into_iter()
+            .map(|endpoint| WebhookEndpointView {
+                keys: keys
+                    .remove(&endpoint.id)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(|secret| WebhookKey {
+                        key_id: secret.key_id,
+                        created_at: secret.created_at,
+                        expires_at: secret.expires_at,
+                    })
+                    .collect(),
+                endpoint,
+            })
+            .collect())
+    }
+
+    /// Validate and store `filter`; blank clears it. `Ok(None)` when the
+    /// endpoint does not exist.
+    pub fn set_filter(
+        &self,
+        endpoint_id: &str,
+        filter: Option<&str>,
+    ) -> Result<Option<DbWebhookEndpoint>> {
+        let filter = parse_filter(filter)?.map(|filter| filter.to_string());
+        let endpoint = self
+            .database
+            .set_webhook_filter(endpoint_id, filter.as_deref())?;
+
+        if endpoint.is_some() {
+            info!(
+                "🪝 Webhook {} filter set to {}",
+                endpoint_id,
+                filter.as_deref().unwrap_or("every event")
+            );
+        }
+        Ok(endpoint)
+    }
+
+    /// Check a sample event against the endpoint's filter and, when it
+    /// matches and `deliver` is set, send it marked `"test": true`.
+    pub async fn test_delivery(
+        &self,
+        endpoint_id: &str,
+        event: &str,
+        data: &serde_json::Value,
+        deliver: bool,
+    ) -> Result<Option<TestDelivery>> {
+        let Some(endpoint) = self.database.get_webhook_endpoint(endpoint_id)? else {
+            return Ok(None);
+        };
+
+        let clauses = parse_filter(endpoint.filter.as_deref())?
+            .map(|filter| filter.explain(event, data))
+            .unwrap_or_default();
+        let matched = clauses.iter().all(|clause| clause.matched);
+        let mut result = TestDelivery {
+            endpoint_id: endpoint.id.clone(),
+            event: event.to_string(),
+            matched,
+            clauses,
+            delivered: None,
+            error: None,
+        };
+        if !matched || !deliver {
+            return Ok(Some(result));
+        }
+
+        let secrets = self.active_secrets()?;
+        let keys: Vec<&DbWebhookSecret> = secrets
+            .get(&endpoint.id)
+            .map(|keys| keys.iter().collect())
+            .unwrap_or_default();
+        let delivery = if keys.is_empty() {
+            Err(anyhow!("Endpoint has no active secret"))
+        } else {
+            self.deliver(&endpoint, &keys, event, data, true).await
+        };
+
+        result.delivered = Some(delivery.is_ok());
+        result.error = delivery.err().map(|e| format!("{:#}", e));
+        Ok(Some(result))
+    }
+
+    pub fn delete_endpoint(&self, endpoint_id: &str) -> Result<bool> {
+        let deleted = self.database.delete_webhook_endpoint(endpoint_id)?;
+        if deleted {
+            info!("🗑️ Webhook endpoint {} removed", endpoint_id);
+        }
+        Ok(deleted)
+    }
+
+    fn active_secrets(&self) -> Result<HashMap<String, Vec<DbWebhookSecret>>> {
+        let mut by_endpoint: HashMap<String, Vec<DbWebhookSecret>> = HashMap::new();
+        for secret in self.database.get_active_webhook_secrets(Utc::now())? {
+            by_endpoint
+                .entry(secret.endpoint_id.clone())
+                .or_default()
+                .push(secret);
+        }
+
+        Ok(by_endpoint)
+    }
+
+    /// POST `event` to every endpoint whose filter it matches, signed with
+    /// each of its active secrets. Returns the number of failed deliveries.
+    pub async fn dispatch(&self, event: &str, data: &serde_json::Value) -> usize {
+        let (endpoints, secrets) = match self
+            .database
+            .list_webhook_endpoints()
+            .and_then(|endpoints| Ok((endpoints, self.active_secrets()?)))
+        {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                error!("❌ Failed to load webhook endpoints: {}", e);
+                return 1;
+            }
+        };
+
+        let mut failures = 0;
+        for endpoint in endpoints {
+            match parse_filter(endpoint.filter.as_deref()) {
+                Ok(Some(filter)) if !filter.matches(event, data) => continue,
+                Ok(_) => {}
+                Err(e) => {
+                    warn!(
+                        "⚠️ Webhook {} has an invalid filter, skipped: {:#}",
+                        endpoint.id, e
+                    );
+                    continue;
+                }
+            }
+
+            let keys: Vec<&DbWebhookSecret> = secrets
+                .get(&endpoint.id)
+                .map(|keys| keys.iter().collect())
+                .unwrap_or_default();
+            if keys.is_empty() {
+                warn!("⚠️ Webhook {} has no active secret, skipped", endpoint.id);
+                continue;
+            }
+
+            if let Err(e) = self.deliver(&endpoint, &keys, event, data, false).await {
+                failures += 1;
+                error!(
+                    "❌ Webhook {} delivery of {} failed: {}",
+                    endpoint.id, event, e
+                );
+            }
+        }
+
+        failures
+    }
+
+    async fn deliver(
+        &self,
+        endpoint: &DbWebhookEndpoint,
+        keys: &[&DbWebhookSecret],
+        event: &str,
+        data: &serde_json::Value,
+        test: bool,
+    ) -> Result<()> {
+        let delivery_id = Uuid::new_v4().to_string();
+        let timestamp = Utc::now().timestamp();
+        let mut body = serde_json::json!({
+            "id": delivery_id,
+            "event": event,
+            "created_at": timestamp,
+            "data": data,
+        });
+        if test {
+            body["test"] = serde_json::Value::Bool(true);
+        }
+        let body = serde_json::to_vec(&body)?;
+
+        // Retries reuse the delivery id so receivers can drop duplicates
+        let signature = signature_header(timestamp, &body, keys);
+        let response = self
+            .http
+            .send(&endpoint.url, |http| {
+                http.post(&endpoint.url)
+                    .timeout(self.policy.timeout)
+                    .header(reqwest::header::CONTENT_TYPE, "application/json")
+                    .header(DELIVERY_HEADER, &delivery_id)
+                    .header(SIGNATURE_HEADER, &signature)
+                    .body(body.clone())
+            })
+            .await
+            .context("Webhook request failed")?;
+
+        if !response.status().is_success() {
+            return Err(anyhow!("Webhook returned {}", response.status()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secret(key_id: &str, secret: &str) -> DbWebhookSecret {
+        DbWebhookSecret {
+            endpoint_id: "wh_test".to_string(),
+            key_id: key_id.to_string(),
+            secret: secret.to_string(),
+            created_at: Utc::now(),
+            expires_at: None,
+        }
+    }
+
+    #[test]
+    fn test_signature_header_signs_with_every_key() {
+        let current = secret("whk_new", "whsec_new");
+        let retiring = secret("whk_old", "whsec_old");
+        let body = br#"{"event":"intent_stuck"}"#;
+
+        let header = signature_header(1_735_300_000, body, &[&current, &retiring]);
+        let parts: Vec<(&str, &str)> = header
+            .split(',')
+            .map(|part| part.split_once('=').unwrap())
+            .collect();
+        assert_eq!(parts[0], ("t", "1735300000"));
+        assert_eq!(parts.len(), 3);
+
+        // A receiver holding either secret verifies its own entry
+        for (key, (key_id, signature)) in [&current, &retiring].iter().zip(&parts[1..]) {
+            assert_eq!(*key_id, key.key_id);
+            let mut mac = HmacSha256::new_from_slice(key.secret.as_bytes()).unwrap();
+            mac.update(b"1735300000.");
+            mac.update(body);
+            mac.verify_slice(&hex::decode(signature).unwrap()).unwrap();
+        }
+
+        let (key_id, fresh) = new_signing_secret();
+        assert!(key_id.starts_with("whk_") && key_id.len() == 16);
+        assert!(fresh.starts_with("whsec_") && fresh.len() == 70);
+    }
+}
@@ -0,0 +1,417 @@
+use std::fmt;
+
+use anyhow::{Result, anyhow, bail};
+use ethers::types::U256;
+use mantle_core::{amount::to_decimal, chain::Chain, token::TokenType};
+use serde::Serialize;
+use serde_json::Value;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Field {
+    Event,
+    Chain,
+    Token,
+    Direction,
+    Amount,
+}
+
+impl Field {
+    fn parse(name: &str) -> Result<Self> {
+        match name {
+            "event" => Ok(Self::Event),
+            "chain" => Ok(Self::Chain),
+            "token" => Ok(Self::Token),
+            "direction" => Ok(Self::Direction),
+            "amount" => Ok(Self::Amount),
+            other => Err(anyhow!(
+                "Unknown field '{}' (expected event, chain, token, direction or amount)",
+                other
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Eq,
+    Ne,
+    In,
+    Gt,
+    Ge,
+    Lt,
+    Le,
+}
+
+impl Op {
+    /// Longest first, so `>=` is not read as `>`.
+    const SYMBOLS: [(&'static str, Op); 6] = [
+        ("!=", Op::Ne),
+        (">=", Op::Ge),
+        ("<=", Op::Le),
+        ("=", Op::Eq),
+        (">", Op::Gt),
+        ("<", Op::Lt),
+    ];
+
+    fn is_comparison(&self) -> bool {
+        matches!(self, Self::Gt | Self::Ge | Self::Lt | Self::Le)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Values {
+    /// Event names; a trailing `*` matches by prefix.
+    Events(Vec<String>),
+    Chains(Vec<Chain>),
+    Tokens(Vec<TokenType>),
+    Directions(Vec<(Chain, Chain)>),
+    /// Whole tokens.
+    Amount(f64),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Clause {
+    text: String,
+    op: Op,
+    values: Values,
+}
+
+/// Which events an endpoint receives: clauses joined by `and`, e.g.
+/// `event in (intent_stuck, chain_reorg) and chain = mantle and amount >= 100`.
+/// An event without the field a clause tests does not match it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookFilter {
+    clauses: Vec<Clause>,
+}
+
+/// One clause of a filter and whether an event satisfied it.
+#[derive(Debug, Clone, Serialize)]
+pub struct ClauseMatch {
+    pub clause: String,
+    pub matched: bool,
+}
+
+impl fmt::Display for WebhookFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let clauses: Vec<&str> = self.clauses.iter().map(|c| c.text.as_str()).collect();
+        write!(f, "{}", clauses.join(" and "))
+    }
+}
+
+impl WebhookFilter {
+    pub fn parse(source: &str) -> Result<Self> {
+        let clauses = split_and(source)
+            .into_iter()
+            .map(|text| parse_clause(text).map_err(|e| anyhow!("Invalid clause '{}': {}", text, e)))
+            .collect::<Result<Vec<_>>>()?;
+        if clauses.is_empty() {
+            bail!("Filter is empty");
+        }
+
+        Ok(Self { clauses })
+    }
+
+    pub fn matches(&self, event: &str, data: &Value) -> bool {
+        self.clauses
+            .iter()
+            .all(|clause| clause.matches(event, data))
+    }
+
+    /// Every clause with its outcome, for the test-delivery endpoint.
+    pub fn explain(&self, event: &str, data: &Value) -> Vec<ClauseMatch> {
+        self.clauses
+            .iter()
+            .map(|clause| ClauseMatch {
+                clause: clause.text.clone(),
+                matched: clause.matches(event, data),
+            })
+            .collect()
+    }
+}
+
+/// Split on ` and ` outside parentheses, case-insensitively.
+fn split_and(source: &str) -> Vec<&str> {
+    let lower = source.to_ascii_lowercase();
+    let bytes = lower.as_bytes();
+    let mut parts = Vec::new();
+    let (mut depth, mut start, mut i) = (0i32, 0, 0);
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'(' => depth += 1,
+            b')' => depth -= 1,
+            _ if depth == 0
+                && bytes[i..].starts_with(b"and")
+                && i > 0
+                && bytes[i - 1].is_ascii_whitespace()
+                && bytes.get(i + 3).is_some_and(|b| b.is_ascii_whitespace()) =>
+            {
+                parts.push(source[start..i].trim());
+                start = i + 3;
+                i += 3;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    parts.push(source[start..].trim());
+
+    parts.into_iter().filter(|part| !part.is_empty()).collect()
+}
+
+fn parse_clause(text: &str) -> Result<Clause> {
+    let name_end = text
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+        .unwrap_or(text.len());
+    let field = Field::parse(&text[..name_end].to_ascii_lowercase())?;
+    let rest = text[name_end..].trim_start();
+
+    let list = rest
+        .get(..2)
+        .filter(|word| word.eq_ignore_ascii_case("in"))
+        .map(|_| &rest[2..])
+        .filter(|list| list.starts_with(|c: char| c.is_whitespace() || c == '('));
+    let (op, value) = if let Some(list) = list {
+        (Op::In, list.trim())
+    } else {
+        Op::SYMBOLS
+            .iter()
+            .find_map(|(symbol, op)| rest.strip_prefix(symbol).map(|value| (*op, value.trim())))
+            .ok_or_else(|| anyhow!("expected =, !=, in, >, >=, < or <="))?
+    };
+
+    let raw: Vec<&str> = if op == Op::In {
+        value
+            .strip_prefix('(')
+            .and_then(|list| list.strip_suffix(')'))
+            .ok_or_else(|| anyhow!("'in' takes a list like (a, b)"))?
+            .split(',')
+            .map(str::trim)
+            .collect()
+    } else {
+        vec![value]
+    };
+    if raw.iter().any(|value| value.is_empty()) {
+        bail!("missing value");
+    }
+
+    if (field == Field::Amount) != op.is_comparison() {
+        bail!(if field == Field::Amount {
+            "amount takes >, >=, < or <="
+        } else {
+            "only amount takes >, >=, < or <="
+        });
+    }
+
+    let values = match field {
+        Field::Event => Values::Events(raw.iter().map(|v| v.to_ascii_lowercase()).collect()),
+        Field::Chain => Values::Chains(raw.iter().map(|v| Chain::parse(v)).collect::<Result<_>>()?),
+        Field::Token => Values::Tokens(
+            raw.iter()
+                .map(|v| TokenType::from_symbol(v))
+                .collect::<Result<_>>()?,
+        ),
+        Field::Direction => Values::Directions(
+            raw.iter()
+                .map(|v| {
+                    let (source, dest) = v
+                        .split_once("->")
+                        .ok_or_else(|| anyhow!("direction is <source>-><dest>"))?;
+                    Ok((Chain::parse(source.trim())?, Chain::parse(dest.trim())?))
+                })
+                .collect::<Result<_>>()?,
+        ),
+        Field::Amount => {
+            let amount: f64 = raw[0]
+                .parse()
+                .map_err(|_| anyhow!("amount must be a number of whole tokens"))?;
+            if !amount.is_finite() {
+                bail!("amount must be finite");
+            }
+            Values::Amount(amount)
+        }
+    };
+
+    Ok(Clause {
+        text: text.to_string(),
+        op,
+        values,
+    })
+}
+
+impl Clause {
+    fn matches(&self, event: &str, data: &Value) -> bool {
+        match &self.values {
+            Values::Events(names) => self.any_of(names, |name| match name.strip_suffix('*') {
+                Some(prefix) => event.starts_with(prefix),
+                None => event == name,
+            }),
+            Values::Chains(chains) => {
+                let present = event_chains(data);
+                !present.is_empty() && self.any_of(chains, |chain| present.contains(chain))
+            }
+            Values::Tokens(tokens) => {
+                event_token(data).is_some_and(|token| self.any_of(tokens, |t| *t == token))
+            }
+            Values::Directions(directions) => event_direction(data)
+                .is_some_and(|direction| self.any_of(directions, |d| *d == direction)),
+            Values::Amount(threshold) => event_amount(data).is_some_and(|amount| match self.op {
+                Op::Gt => amount > *threshold,
+                Op::Ge => amount >= *threshold,
+                Op::Lt => amount < *threshold,
+                _ => amount <= *threshold,
+            }),
+        }
+    }
+
+    /// `=` and `in` need a match among `values`, `!=` needs none.
+    fn any_of<T>(&self, values: &[T], hit: impl Fn(&T) -> bool) -> bool {
+        let any = values.iter().any(hit);
+        if self.op == Op::Ne { !any } else { any }
+    }
+}
+
+/// `key` at the top of the payload, else under `intent` as stuck-intent
+/// events carry it.
+fn lookup<'a>(data: &'a Value, key: &str) -> Option<&'a Value> {
+    data.get(key)
+        .or_else(|| data.get("intent").and_then(|intent| intent.get(key)))
+        .filter(|value| !value.is_null())
+}
+
+fn as_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+fn chain_at(data: &Value, key: &str) -> Option<Chain> {
+    lookup(data, key)
+        .and_then(as_text)
+        .and_then(|text| Chain::parse(&text).ok())
+}
+
+fn event_chains(data: &Value) -> Vec<Chain> {
+    ["chain", "source_chain", "dest_chain"]
+        .iter()
+        .filter_map(|key| chain_at(data, key))
+        .collect()
+}
+
+fn event_direction(data: &Value) -> Option<(Chain, Chain)> {
+    Some((
+        chain_at(data, "source_chain")?,
+        chain_at(data, "dest_chain")?,
+    ))
+}
+
+/// A symbol, or a built-in token address on either chain.
+fn event_token(data: &Value) -> Option<TokenType> {
+    let token = ["token", "source_token", "token_type"]
+        .iter()
+        .find_map(|key| lookup(data, key).and_then(as_text))?;
+
+    TokenType::from_symbol(&token).ok().or_else(|| {
+        TokenType::ALL.into_iter().find(|token_type| {
+            Chain::ALL
+                .iter()
+                .any(|chain| token_type.address_on(*chain).eq_ignore_ascii_case(&token))
+        })
+    })
+}
+
+/// The payload's base-unit `amount` in whole tokens of its token.
+fn event_amount(data: &Value) -> Option<f64> {
+    let token = event_token(data)?;
+    let amount = lookup(data, "amount").and_then(as_text)?;
+    let amount = U256::from_dec_str(&amount).ok()?;
+    Some(to_decimal(amount, token.decimals()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn stuck_usdc(amount: &str) -> Value {
+        json!({
+            "level": "critical",
+            "intent": {
+                "intent_id": "0xabc",
+                "source_chain": "ethereum",
+                "dest_chain": "mantle",
+                "source_token": "0x28650373758d75a8fF0B22587F111e47BAC34e21",
+                "amount": amount,
+            }
+        })
+    }
+
+    #[test]
+    fn test_filter_matches_on_every_field() {
+        let filter = WebhookFilter::parse(
+            "event in (intent_stuck, alert_*) AND chain = mantle and token = usdc \
+             and direction = ethereum->mantle and amount >= 100",
+        )
+        .unwrap();
+
+        assert!(filter.matches("intent_stuck", &stuck_usdc("250000000")));
+        // 99 USDC
+        assert!(!filter.matches("intent_stuck", &stuck_usdc("99000000")));
+        assert!(!filter.matches("intent_note", &stuck_usdc("250000000")));
+
+        let explained = filter.explain("intent_stuck", &stuck_usdc("99000000"));
+        assert_eq!(explained.len(), 5);
+        assert!(explained[..4].iter().all(|clause| clause.matched));
+        assert_eq!(explained[4].clause, "amount >= 100");
+        assert!(!explained[4].matched);
+
+        // Chain ids count as chains; a reorg carries no token
+        let reorg = json!({ "chain": "5003", "depth": 2 });
+        assert!(
+            WebhookFilter::parse("chain=mantle")
+                .unwrap()
+                .matches("chain_reorg", &reorg)
+        );
+        assert!(
+            !WebhookFilter::parse("chain != mantle")
+                .unwrap()
+                .matches("chain_reorg", &reorg)
+        );
+        assert!(
+            !WebhookFilter::parse("token = USDC")
+                .unwrap()
+                .matches("chain_reorg", &reorg)
+        );
+        assert!(
+            WebhookFilter::parse("event != intent_stuck")
+                .unwrap()
+                .matches("chain_reorg", &reorg)
+        );
+    }
+
+    #[test]
+    fn test_invalid_filters_are_rejected() {
+        for (filter, error) in [
+            ("", "empty"),
+            ("network = mantle", "Unknown field 'network'"),
+            ("chain = solana", "Unsupported chain"),
+            ("token in USDC", "list like"),
+            ("amount = 5", "amount takes"),
+            ("chain > mantle", "only amount"),
+            ("direction = mantle", "<source>-><dest>"),
+            ("event =", "missing value"),
+            ("é = x", "Unknown field"),
+        ] {
+            let err = WebhookFilter::parse(filter).unwrap_err();
+            assert!(
+                format!("{:#}", err).contains(error),
+                "{}: {:#}",
+                filter,
+                err
+            );
+        }
+    }
+}
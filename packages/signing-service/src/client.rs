@@ -0,0 +1,287 @@
+use std::{fmt, path::PathBuf};
+
+use async_trait::async_trait;
+use ethers::{
+    signers::{LocalWallet, Signer},
+    types::{
+        Address, Signature, transaction::eip712::Eip712, transaction::eip2718::TypedTransaction,
+    },
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::UnixStream,
+};
+
+use crate::{
+    forward_request::{ForwardRequest, ForwarderDomain},
+    protocol::{SignRequest, SignResponse},
+};
+
+#[derive(Debug)]
+pub struct SignerError(pub String);
+
+impl fmt::Display for SignerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SignerError {}
+
+/// Signer backed by the signing daemon over a unix socket.
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    socket_path: PathBuf,
+    key: String,
+    address: Address,
+    chain_id: u64,
+}
+
+impl RemoteSigner {
+    pub async fn connect(socket_path: impl Into<PathBuf>, key: &str) -> Result<Self, SignerError> {
+        let mut signer = Self {
+            socket_path: socket_path.into(),
+            key: key.to_string(),
+            address: Address::zero(),
+            chain_id: 1,
+        };
+
+        match signer
+            .request(&SignRequest::Address {
+                key: key.to_string(),
+            })
+            .await?
+        {
+            SignResponse::Address { address } => signer.address = address,
+            other => return Err(unexpected(other)),
+        }
+
+        Ok(signer)
+    }
+
+    /// ECIES-decrypt `ciphertext` (hex) with the daemon-held key.
+    pub async fn decrypt(&self, ciphertext: &str) -> Result<String, SignerError> {
+        match self
+            .request(&SignRequest::Decrypt {
+                key: self.key.clone(),
+                ciphertext: ciphertext.to_string(),
+            })
+            .await?
+        {
+            SignResponse::Plaintext { plaintext } => Ok(plaintext),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    pub async fn sign_forward_request(
+        &self,
+        domain: &ForwarderDomain,
+        request: &ForwardRequest,
+    ) -> Result<Signature, SignerError> {
+        match self
+            .request(&SignRequest::SignForwardRequest {
+                key: self.key.clone(),
+                domain: domain.clone(),
+                request: Box::new(request.clone()),
+            })
+            .await?
+        {
+            SignResponse::Signature { signature } => Ok(signature),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    async fn request(&self, request: &SignRequest) -> Result<SignResponse, SignerError> {
+        let stream = UnixStream::connect(&self.socket_path).await.map_err(|e| {
+            SignerError(format!(
+                "Failed to connect to signer at {}: {}",
+                self.socket_path.display(),
+                e
+            ))
+        })?;
+
+        let (reader, mut writer) = stream.into_split();
+
+        let mut encoded = serde_json::to_vec(request).map_err(|e| SignerError(e.to_string()))?;
+        encoded.push(b'\n');
+        writer
+            .write_all(&encoded)
+            .await
+            .map_err(|e| SignerError(format!("Failed to send signer request: {}", e)))?;
+
+        let line = BufReader::new(reader)
+            .lines()
+            .next_line()
+            .await
+            .map_err(|e| SignerError(format!("Failed to read signer response: {}", e)))?
+            .ok_or_else(|| SignerError("Signer closed the connection".to_string()))?;
+
+        match serde_json::from_str(&line).map_err(|e| SignerError(e.to_string()))? {
+            SignResponse::Error { message } => Err(SignerError(message)),
+            response => Ok(response),
+        }
+    }
+}
+
+fn unexpected(response: SignResponse) -> SignerError {
+    SignerError(format!("Unexpected signer response: {:?}", response))
+}
+
+#[async_trait]
+impl Signer for RemoteSigner {
+    type Error = SignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        _message: S,
+    ) -> Result<Signature, Self::Error> {
+        Err(SignerError(
+            "Message signing is not permitted by the signing service".to_string(),
+        ))
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        let chain_id = tx.chain_id().map(|id| id.as_u64()).unwrap_or(self.chain_id);
+
+        match self
+            .request(&SignRequest::SignTransaction {
+                key: self.key.clone(),
+                chain_id,
+                tx: Box::new(tx.clone()),
+            })
+            .await?
+        {
+            SignResponse::Signature { signature } => Ok(signature),
+            other => Err(unexpected(other)),
+        }
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        _payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        Err(SignerError(
+            "Typed data signing is not permitted by the signing service".to_string(),
+        ))
+    }
+
+    fn address(&self) -> Address {
+        self.address
+    }
+
+    fn chain_id(&self) -> u64 {
+        self.chain_id
+    }
+
+    fn with_chain_id<T: Into<u64>>(mut self, chain_id: T) -> Self {
+        self.chain_id = chain_id.into();
+        self
+    }
+}
+
+/// Either an in-process wallet or the signing daemon, chosen by `SIGNER_SOCKET`.
+#[derive(Debug, Clone)]
+pub enum SignerHandle {
+    Local(LocalWallet),
+    Remote(RemoteSigner),
+}
+
+impl SignerHandle {
+    /// Use the daemon's `key` when `SIGNER_SOCKET` is set, otherwise parse `private_key`.
+    pub async fn from_env(key: &str, private_key: &str, chain_id: u64) -> anyhow::Result<Self> {
+        let handle = match std::env::var("SIGNER_SOCKET") {
+            Ok(socket) => Self::Remote(RemoteSigner::connect(socket, key).await?),
+            Err(_) => Self::Local(
+                private_key
+                    .parse::<LocalWallet>()
+                    .map_err(|e| anyhow::anyhow!("Invalid private key: {}", e))?,
+            ),
+        };
+
+        Ok(handle.with_chain_id(chain_id))
+    }
+
+    /// Sign an ERC-2771 forward request. The daemon applies its contract
+    /// policy to both the forwarder and the forwarded target.
+    pub async fn sign_forward_request(
+        &self,
+        domain: &ForwarderDomain,
+        request: &ForwardRequest,
+    ) -> Result<Signature, SignerError> {
+        match self {
+            Self::Local(wallet) => wallet
+                .sign_hash(request.digest(domain))
+                .map_err(|e| SignerError(e.to_string())),
+            Self::Remote(remote) => remote.sign_forward_request(domain, request).await,
+        }
+    }
+
+    pub fn remote(&self) -> Option<&RemoteSigner> {
+        match self {
+            Self::Remote(remote) => Some(remote),
+            Self::Local(_) => None,
+        }
+    }
+}
+
+#[async_trait]
+impl Signer for SignerHandle {
+    type Error = SignerError;
+
+    async fn sign_message<S: Send + Sync + AsRef<[u8]>>(
+        &self,
+        message: S,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            Self::Local(wallet) => wallet
+                .sign_message(message)
+                .await
+                .map_err(|e| SignerError(e.to_string())),
+            Self::Remote(remote) => remote.sign_message(message).await,
+        }
+    }
+
+    async fn sign_transaction(&self, tx: &TypedTransaction) -> Result<Signature, Self::Error> {
+        match self {
+            Self::Local(wallet) => wallet
+                .sign_transaction(tx)
+                .await
+                .map_err(|e| SignerError(e.to_string())),
+            Self::Remote(remote) => remote.sign_transaction(tx).await,
+        }
+    }
+
+    async fn sign_typed_data<T: Eip712 + Send + Sync>(
+        &self,
+        payload: &T,
+    ) -> Result<Signature, Self::Error> {
+        match self {
+            Self::Local(wallet) => wallet
+                .sign_typed_data(payload)
+                .await
+                .map_err(|e| SignerError(e.to_string())),
+            Self::Remote(remote) => remote.sign_typed_data(payload).await,
+        }
+    }
+
+    fn address(&self) -> Address {
+        match self {
+            Self::Local(wallet) => wallet.address(),
+            Self::Remote(remote) => remote.address(),
+        }
+    }
+
+    fn chain_id(&self) -> u64 {
+        match self {
+            Self::Local(wallet) => wallet.chain_id(),
+            Self::Remote(remote) => remote.chain_id(),
+        }
+    }
+
+    fn with_chain_id<T: Into<u64>>(self, chain_id: T) -> Self {
+        match self {
+            Self::Local(wallet) => Self::Local(wallet.with_chain_id(chain_id)),
+            Self::Remote(remote) => Self::Remote(remote.with_chain_id(chain_id)),
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod client;
+pub mod forward_request;
+pub mod policy;
+pub mod protocol;
+pub mod server;
@@ -0,0 +1,51 @@
+use std::{os::unix::fs::PermissionsExt, path::Path, sync::Arc};
+
+use anyhow::{Context, Result};
+use signing_service::{
+    policy::SigningPolicy,
+    protocol::DEFAULT_SOCKET_PATH,
+    server::{self, SignerState},
+};
+use tokio::net::UnixListener;
+use tracing::{info, warn};
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    dotenv::dotenv().ok();
+
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| "signing_service=info".into()),
+        )
+        .init();
+
+    info!("🔐 Starting signing service");
+
+    let socket_path =
+        std::env::var("SIGNER_SOCKET").unwrap_or_else(|_| DEFAULT_SOCKET_PATH.to_string());
+
+    let keys = SignerState::keys_from_env().context("Failed to load signing keys")?;
+    let policy = SigningPolicy::from_env().context("Failed to load signing policy")?;
+
+    if policy.allow_any_contract {
+        warn!("⚠️ SIGNER_ALLOW_ANY_CONTRACT is set, any destination will be signed");
+    }
+
+    let state = Arc::new(SignerState::new(keys, policy));
+    for (name, address) in state.addresses() {
+        info!("   Key '{}': {:?}", name, address);
+    }
+
+    if Path::new(&socket_path).exists() {
+        std::fs::remove_file(&socket_path).context("Failed to remove stale socket")?;
+    }
+
+    let listener = UnixListener::bind(&socket_path).context("Failed to bind signer socket")?;
+    std::fs::set_permissions(&socket_path, std::fs::Permissions::from_mode(0o600))
+        .context("Failed to restrict socket permissions")?;
+
+    info!("✅ Listening on {}", socket_path);
+
+    server::serve(listener, state).await
+}
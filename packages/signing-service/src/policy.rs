@@ -0,0 +1,270 @@
+use std::{
+    collections::{HashSet, VecDeque},
+    time::{Duration, Instant},
+};
+
+use anyhow::{Result, anyhow};
+use ethers::types::{Address, NameOrAddress, U256, transaction::eip2718::TypedTransaction};
+
+use crate::forward_request::{ForwardRequest, ForwarderDomain};
+
+/// Rules every transaction must satisfy before the daemon signs it.
+#[derive(Debug, Clone)]
+pub struct SigningPolicy {
+    /// Contracts transactions may be sent to.
+    pub allowed_contracts: HashSet<Address>,
+    /// Sign for any destination regardless of `allowed_contracts`.
+    pub allow_any_contract: bool,
+    /// Largest native value per transaction. Zero means unlimited.
+    pub max_value_wei: U256,
+    /// Signatures (and decryptions) allowed per key per minute. Zero disables the limit.
+    pub rate_limit_per_min: u32,
+}
+
+impl SigningPolicy {
+    /// Reads `SIGNER_ALLOWED_CONTRACTS`, `SIGNER_ALLOW_ANY_CONTRACT`,
+    /// `SIGNER_MAX_VALUE_WEI` and `SIGNER_RATE_LIMIT_PER_MIN`.
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(|key| std::env::var(key).ok())
+    }
+
+    /// An empty allow-list is refused unless `SIGNER_ALLOW_ANY_CONTRACT=true`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        let allowed_contracts = var("SIGNER_ALLOWED_CONTRACTS")
+            .unwrap_or_default()
+            .split(',')
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .map(|s| {
+                s.parse::<Address>()
+                    .map_err(|e| anyhow!("Invalid SIGNER_ALLOWED_CONTRACTS entry {}: {}", s, e))
+            })
+            .collect::<Result<HashSet<_>>>()?;
+
+        let allow_any_contract: bool = var("SIGNER_ALLOW_ANY_CONTRACT")
+            .unwrap_or_else(|| "false".to_string())
+            .trim()
+            .parse()
+            .map_err(|e| anyhow!("Invalid SIGNER_ALLOW_ANY_CONTRACT: {}", e))?;
+
+        if allowed_contracts.is_empty() && !allow_any_contract {
+            return Err(anyhow!(
+                "SIGNER_ALLOWED_CONTRACTS is empty; list the contracts to sign for, \
+                 or set SIGNER_ALLOW_ANY_CONTRACT=true"
+            ));
+        }
+
+        let max_value_wei =
+            U256::from_dec_str(&var("SIGNER_MAX_VALUE_WEI").unwrap_or_else(|| "0".to_string()))
+                .map_err(|e| anyhow!("Invalid SIGNER_MAX_VALUE_WEI: {}", e))?;
+
+        let rate_limit_per_min = var("SIGNER_RATE_LIMIT_PER_MIN")
+            .unwrap_or_else(|| "60".to_string())
+            .parse()
+            .map_err(|e| anyhow!("Invalid SIGNER_RATE_LIMIT_PER_MIN: {}", e))?;
+
+        Ok(Self {
+            allowed_contracts,
+            allow_any_contract,
+            max_value_wei,
+            rate_limit_per_min,
+        })
+    }
+
+    fn allows(&self, contract: &Address) -> bool {
+        self.allow_any_contract || self.allowed_contracts.contains(contract)
+    }
+
+    pub fn check_transaction(&self, tx: &TypedTransaction) -> Result<()> {
+        let to = match tx.to() {
+            Some(NameOrAddress::Address(address)) => *address,
+            Some(NameOrAddress::Name(name)) => {
+                return Err(anyhow!("ENS destinations are not allowed: {}", name));
+            }
+            None => return Err(anyhow!("Contract creation is not allowed")),
+        };
+
+        if !self.allows(&to) {
+            return Err(anyhow!("Destination {:?} is not an allowed contract", to));
+        }
+
+        self.check_value(tx.value().copied().unwrap_or_default())
+    }
+
+    /// Both the forwarder and the contract it calls must be allowed.
+    pub fn check_forward_request(
+        &self,
+        domain: &ForwarderDomain,
+        request: &ForwardRequest,
+    ) -> Result<()> {
+        for contract in [domain.verifying_contract, request.to] {
+            if !self.allows(&contract) {
+                return Err(anyhow!(
+                    "Forward request via {:?} to {:?}: {:?} is not an allowed contract",
+                    domain.verifying_contract,
+                    request.to,
+                    contract
+                ));
+            }
+        }
+
+        self.check_value(request.value)
+    }
+
+    fn check_value(&self, value: U256) -> Result<()> {
+        if !self.max_value_wei.is_zero() && value > self.max_value_wei {
+            return Err(anyhow!(
+                "Value {} exceeds max {} wei",
+                value,
+                self.max_value_wei
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Sliding one-minute window of operations for a single key.
+#[derive(Debug, Default)]
+pub struct RateLimiter {
+    hits: VecDeque<Instant>,
+}
+
+impl RateLimiter {
+    pub fn try_acquire(&mut self, limit: u32, now: Instant) -> bool {
+        if limit == 0 {
+            return true;
+        }
+
+        while let Some(oldest) = self.hits.front() {
+            if now.duration_since(*oldest) >= Duration::from_secs(60) {
+                self.hits.pop_front();
+            } else {
+                break;
+            }
+        }
+
+        if self.hits.len() >= limit as usize {
+            return false;
+        }
+
+        self.hits.push_back(now);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::TransactionRequest;
+
+    fn policy(allowed: Address) -> SigningPolicy {
+        SigningPolicy {
+            allowed_contracts: HashSet::from([allowed]),
+            allow_any_contract: false,
+            max_value_wei: U256::from(1000),
+            rate_limit_per_min: 2,
+        }
+    }
+
+    #[test]
+    fn test_policy_checks_destination_and_value() {
+        let allowed = Address::repeat_byte(0x11);
+        let policy = policy(allowed);
+
+        let ok: TypedTransaction = TransactionRequest::new().to(allowed).value(10).into();
+        assert!(policy.check_transaction(&ok).is_ok());
+
+        let other: TypedTransaction = TransactionRequest::new()
+            .to(Address::repeat_byte(0x22))
+            .into();
+        assert!(policy.check_transaction(&other).is_err());
+
+        let too_much: TypedTransaction = TransactionRequest::new().to(allowed).value(1001).into();
+        assert!(policy.check_transaction(&too_much).is_err());
+
+        let deploy: TypedTransaction = TransactionRequest::new().into();
+        assert!(policy.check_transaction(&deploy).is_err());
+    }
+
+    #[test]
+    fn test_policy_checks_forward_request() {
+        let allowed = Address::repeat_byte(0x11);
+        let forwarder = Address::repeat_byte(0x33);
+        let mut policy = policy(allowed);
+
+        let domain = ForwarderDomain {
+            name: "IntegratorForwarder".to_string(),
+            version: "1".to_string(),
+            chain_id: 5003,
+            verifying_contract: forwarder,
+        };
+        let request = ForwardRequest {
+            from: Address::repeat_byte(0x44),
+            to: allowed,
+            value: U256::zero(),
+            gas: 300_000.into(),
+            nonce: U256::zero(),
+            deadline: 1_700_000_000,
+            data: Default::default(),
+        };
+
+        // The forwarder itself must be allowed too
+        assert!(policy.check_forward_request(&domain, &request).is_err());
+        policy.allowed_contracts.insert(forwarder);
+        assert!(policy.check_forward_request(&domain, &request).is_ok());
+
+        let elsewhere = ForwardRequest {
+            to: Address::repeat_byte(0x22),
+            ..request.clone()
+        };
+        assert!(policy.check_forward_request(&domain, &elsewhere).is_err());
+        let too_much = ForwardRequest {
+            value: 1001.into(),
+            ..request
+        };
+        assert!(policy.check_forward_request(&domain, &too_much).is_err());
+    }
+
+    #[test]
+    fn test_empty_allow_list_needs_explicit_opt_out() {
+        let vars = |pairs: &'static [(&'static str, &'static str)]| {
+            move |key: &str| {
+                pairs
+                    .iter()
+                    .find(|(k, _)| *k == key)
+                    .map(|(_, v)| v.to_string())
+            }
+        };
+
+        assert!(SigningPolicy::from_vars(vars(&[])).is_err());
+        assert!(SigningPolicy::from_vars(vars(&[("SIGNER_ALLOWED_CONTRACTS", " , ")])).is_err());
+        assert!(SigningPolicy::from_vars(vars(&[("SIGNER_ALLOW_ANY_CONTRACT", "yes")])).is_err());
+
+        let open =
+            SigningPolicy::from_vars(vars(&[("SIGNER_ALLOW_ANY_CONTRACT", "true")])).unwrap();
+        let anywhere: TypedTransaction = TransactionRequest::new()
+            .to(Address::repeat_byte(0x22))
+            .into();
+        assert!(open.check_transaction(&anywhere).is_ok());
+
+        let listed = SigningPolicy::from_vars(vars(&[(
+            "SIGNER_ALLOWED_CONTRACTS",
+            "0x1111111111111111111111111111111111111111",
+        )]))
+        .unwrap();
+        assert!(!listed.allow_any_contract);
+        assert!(listed.check_transaction(&anywhere).is_err());
+    }
+
+    #[test]
+    fn test_rate_limiter_window() {
+        let mut limiter = RateLimiter::default();
+        let start = Instant::now();
+
+        assert!(limiter.try_acquire(2, start));
+        assert!(limiter.try_acquire(2, start));
+        assert!(!limiter.try_acquire(2, start));
+        assert!(limiter.try_acquire(2, start + Duration::from_secs(61)));
+    }
+}
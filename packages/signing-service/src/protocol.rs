@@ -0,0 +1,58 @@
+use ethers::types::{Address, Signature, transaction::eip2718::TypedTransaction};
+use serde::{Deserialize, Serialize};
+
+use crate::forward_request::{ForwardRequest, ForwarderDomain};
+
+/// Socket used when `SIGNER_SOCKET` is not set on the daemon.
+pub const DEFAULT_SOCKET_PATH: &str = "/tmp/shadow-swap-signer.sock";
+
+/// Key name the relayer signs and decrypts with.
+pub const RELAYER_KEY: &str = "relayer";
+
+/// Key name the solver signs with.
+pub const SOLVER_KEY: &str = "solver";
+
+/// One request per line, JSON encoded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum SignRequest {
+    Address {
+        key: String,
+    },
+    SignTransaction {
+        key: String,
+        chain_id: u64,
+        tx: Box<TypedTransaction>,
+    },
+    Decrypt {
+        key: String,
+        ciphertext: String,
+    },
+    /// EIP-712 signature over an ERC-2771 forward request.
+    SignForwardRequest {
+        key: String,
+        domain: ForwarderDomain,
+        request: Box<ForwardRequest>,
+    },
+}
+
+/// One response per request line, JSON encoded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "result", rename_all = "snake_case")]
+pub enum SignResponse {
+    Address { address: Address },
+    Signature { signature: Signature },
+    Plaintext { plaintext: String },
+    Error { message: String },
+}
+
+impl SignRequest {
+    pub fn key(&self) -> &str {
+        match self {
+            Self::Address { key }
+            | Self::SignTransaction { key, .. }
+            | Self::Decrypt { key, .. }
+            | Self::SignForwardRequest { key, .. } => key,
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use std::{collections::HashMap, sync::Arc, time::Instant};
+
+use anyhow::{Result, anyhow};
+use ethers::signers::{LocalWallet, Signer};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader},
+    net::{UnixListener, UnixStream},
+    sync::Mutex,
+};
+use tracing::{error, info, warn};
+
+use crate::{
+    policy::{RateLimiter, SigningPolicy},
+    protocol::{SignRequest, SignResponse},
+};
+
+/// Longest request line accepted, newline included. Requests are small JSON
+/// objects; a client exceeding this is answered with an error and dropped.
+const MAX_REQUEST_BYTES: u64 = 64 * 1024;
+
+/// Keys and policy held by the signing daemon.
+pub struct SignerState {
+    keys: HashMap<String, LocalWallet>,
+    policy: SigningPolicy,
+    limiters: Mutex<HashMap<String, RateLimiter>>,
+}
+
+impl SignerState {
+    pub fn new(keys: HashMap<String, LocalWallet>, policy: SigningPolicy) -> Self {
+        Self {
+            keys,
+            policy,
+            limiters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Load every `SIGNER_KEY_<NAME>` variable as a key named `<name>`.
+    pub fn keys_from_env() -> Result<HashMap<String, LocalWallet>> {
+        let mut keys = HashMap::new();
+
+        for (var, value) in std::env::vars() {
+            let Some(name) = var.strip_prefix("SIGNER_KEY_") else {
+                continue;
+            };
+
+            let wallet = value
+                .parse::<LocalWallet>()
+                .map_err(|e| anyhow!("Invalid key in {}: {}", var, e))?;
+
+            keys.insert(name.to_lowercase(), wallet);
+        }
+
+        if keys.is_empty() {
+            return Err(anyhow!("No SIGNER_KEY_<NAME> variables set"));
+        }
+
+        Ok(keys)
+    }
+
+    pub fn addresses(&self) -> Vec<(String, ethers::types::Address)> {
+        self.keys
+            .iter()
+            .map(|(name, wallet)| (name.clone(), wallet.address()))
+            .collect()
+    }
+
+    pub async fn handle(&self, request: SignRequest) -> SignResponse {
+        match self.try_handle(request).await {
+            Ok(response) => response,
+            Err(e) => SignResponse::Error {
+                message: e.to_string(),
+            },
+        }
+    }
+
+    async fn try_handle(&self, request: SignRequest) -> Result<SignResponse> {
+        let wallet = self
+            .keys
+            .get(request.key())
+            .ok_or_else(|| anyhow!("Unknown key '{}'", request.key()))?;
+
+        match request {
+            SignRequest::Address { .. } => Ok(SignResponse::Address {
+                address: wallet.address(),
+            }),
+            SignRequest::SignTransaction { key, chain_id, tx } => {
+                self.policy.check_transaction(&tx)?;
+                self.acquire(&key).await?;
+
+                let signature = wallet
+                    .clone()
+                    .with_chain_id(chain_id)
+                    .sign_transaction(&tx)
+                    .await
+                    .map_err(|e| anyhow!("Signing failed: {}", e))?;
+
+                info!(
+                    "✍️ Signed tx for key '{}' to {:?} on chain {}",
+                    key,
+                    tx.to(),
+                    chain_id
+                );
+
+                Ok(SignResponse::Signature { signature })
+            }
+            SignRequest::SignForwardRequest {
+                key,
+                domain,
+                request,
+            } => {
+                self.policy.check_forward_request(&domain, &request)?;
+                self.acquire(&key).await?;
+
+                let signature = wallet
+                    .sign_hash(request.digest(&domain))
+                    .map_err(|e| anyhow!("Signing failed: {}", e))?;
+
+                info!(
+                    "✍️ Signed forward request for key '{}' to {:?} via {:?} on chain {}",
+                    key, request.to, domain.verifying_contract, domain.chain_id
+                );
+
+                Ok(SignResponse::Signature { signature })
+            }
+            SignRequest::Decrypt { key, ciphertext } => {
+                self.acquire(&key).await?;
+
+                let ciphertext = ciphertext.strip_prefix("0x").unwrap_or(&ciphertext);
+                let encrypted = hex::decode(ciphertext)
+                    .map_err(|e| anyhow!("Invalid ciphertext hex: {}", e))?;
+
+                let plaintext = ecies::decrypt(&wallet.signer().to_bytes(), &encrypted)
+                    .map_err(|e| anyhow!("ECIES decryption failed: {}", e))?;
+
+                Ok(SignResponse::Plaintext {
+                    plaintext: format!("0x{}", hex::encode(plaintext)),
+                })
+            }
+        }
+    }
+
+    async fn acquire(&self, key: &str) -> Result<()> {
+        let mut limiters = self.limiters.lock().await;
+        let limiter = limiters.entry(key.to_string()).or_default();
+
+        if !limiter.try_acquire(self.policy.rate_limit_per_min, Instant::now()) {
+            warn!("🚦 Rate limit hit for key '{}'", key);
+            return Err(anyhow!("Rate limit exceeded for key '{}'", key));
+        }
+
+        Ok(())
+    }
+}
+
+/// Accept connections until the listener fails.
+pub async fn serve(listener: UnixListener, state: Arc<SignerState>) -> Result<()> {
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let state = Arc::clone(&state);
+
+        tokio::spawn(async move {
+            if let Err(e) = handle_connection(stream, state).await {
+                error!("❌ Signer connection error: {}", e);
+            }
+        });
+    }
+}
+
+async fn handle_connection(stream: UnixStream, state: Arc<SignerState>) -> Result<()> {
+    let (reader, mut writer) = stream.into_split();
+    let mut reader = BufReader::new(reader);
+    let mut line = Vec::new();
+
+    loop {
+        line.clear();
+        let read = (&mut reader)
+            .take(MAX_REQUEST_BYTES)
+            .read_until(b'\n', &mut line)
+            .await?;
+        if read == 0 {
+            break;
+        }
+
+        if line.last() != Some(&b'\n') && read as u64 == MAX_REQUEST_BYTES {
+            let response = SignResponse::Error {
+                message: format!("Request exceeds {} bytes", MAX_REQUEST_BYTES),
+            };
+            write_response(&mut writer, &response).await?;
+            return Err(anyhow!("Request exceeds {} bytes", MAX_REQUEST_BYTES));
+        }
+
+        let response = match serde_json::from_slice::<SignRequest>(line.trim_ascii_end()) {
+            Ok(request) => state.handle(request).await,
+            Err(e) => SignResponse::Error {
+                message: format!("Malformed request: {}", e),
+            },
+        };
+        write_response(&mut writer, &response).await?;
+    }
+
+    Ok(())
+}
+
+async fn write_response(
+    writer: &mut (impl AsyncWrite + Unpin),
+    response: &SignResponse,
+) -> Result<()> {
+    let mut encoded = serde_json::to_vec(response)?;
+    encoded.push(b'\n');
+    writer.write_all(&encoded).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    use ethers::types::U256;
+
+    fn state() -> Arc<SignerState> {
+        let key = "0x0123456789012345678901234567890123456789012345678901234567890123";
+        let keys = HashMap::from([("relayer".to_string(), key.parse().unwrap())]);
+        let policy = SigningPolicy {
+            allowed_contracts: HashSet::new(),
+            allow_any_contract: false,
+            max_value_wei: U256::zero(),
+            rate_limit_per_min: 0,
+        };
+        Arc::new(SignerState::new(keys, policy))
+    }
+
+    #[tokio::test]
+    async fn test_oversized_request_is_refused() {
+        let (client, server) = UnixStream::pair().unwrap();
+        let connection = tokio::spawn(handle_connection(server, state()));
+        let (reader, mut writer) = client.into_split();
+        let mut responses = BufReader::new(reader).lines();
+
+        let mut request = serde_json::to_vec(&SignRequest::Address {
+            key: "relayer".to_string(),
+        })
+        .unwrap();
+        request.push(b'\n');
+        writer.write_all(&request).await.unwrap();
+        let line = responses.next_line().await.unwrap().unwrap();
+        assert!(matches!(
+            serde_json::from_str(&line).unwrap(),
+            SignResponse::Address { .. }
+        ));
+
+        let huge = vec![b' '; MAX_REQUEST_BYTES as usize + 10];
+        writer.write_all(&huge).await.unwrap();
+        let line = responses.next_line().await.unwrap().unwrap();
+        let SignResponse::Error { message } = serde_json::from_str(&line).unwrap() else {
+            panic!("expected an error, got {}", line);
+        };
+        assert!(message.contains("exceeds"), "{}", message);
+
+        assert!(connection.await.unwrap().is_err());
+        // Closed with our excess bytes unread, which may surface as a reset
+        assert!(!matches!(responses.next_line().await, Ok(Some(_))));
+    }
+}
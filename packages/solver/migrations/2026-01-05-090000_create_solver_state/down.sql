@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS solver_processed_intents;
+DROP TABLE IF EXISTS solver_fills;
@@ -0,0 +1,23 @@
+-- Fills the solver has sent and intents it has picked up, so a restarted
+-- solver neither forgets a fill in flight nor fills the same intent twice.
+CREATE TABLE IF NOT EXISTS solver_fills (
+    intent_id TEXT PRIMARY KEY,
+    -- Latest tx for the fill; replaced when fee escalation re-sends it
+    tx_hash TEXT NOT NULL,
+    -- Base units, as a decimal string
+    amount TEXT NOT NULL,
+    token TEXT NOT NULL,
+    token_type TEXT NOT NULL,
+    filled_at BIGINT NOT NULL,
+    confirmed_at BIGINT,
+    status TEXT NOT NULL CHECK (status IN ('Pending', 'Confirmed', 'Claimed', 'Failed')),
+    dest_chain BIGINT NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_solver_fills_status ON solver_fills (status);
+
+CREATE TABLE IF NOT EXISTS solver_processed_intents (
+    intent_id TEXT PRIMARY KEY,
+    -- Unix seconds after which the intent may be picked up again
+    expires_at BIGINT NOT NULL
+);
@@ -0,0 +1,2 @@
+ALTER TABLE solver_fills DROP COLUMN IF EXISTS chunks;
+ALTER TABLE solver_fills DROP COLUMN IF EXISTS filled_amount;
@@ -0,0 +1,4 @@
+-- Intents filled in several chunks: how much has been sent so far and in
+-- how many fills. Rows from before partial fills were filled whole.
+ALTER TABLE solver_fills ADD COLUMN IF NOT EXISTS filled_amount TEXT;
+ALTER TABLE solver_fills ADD COLUMN IF NOT EXISTS chunks INTEGER NOT NULL DEFAULT 1;
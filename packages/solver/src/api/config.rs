@@ -1,6 +1,9 @@
 use actix_web::web;
 
-use crate::api::routes::{get_status, health_check, metrics, ready};
+use crate::api::routes::{
+    get_balance_history, get_drain_status, get_fill_report, get_status, health_check, list_tokens,
+    metrics, prometheus_metrics, ready, set_token, start_drain, toggle_token,
+};
 
 pub fn configure_routes(cfg: &mut web::ServiceConfig) {
     cfg.service(
@@ -8,6 +11,14 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
             .service(health_check)
             .service(metrics)
             .service(get_status)
-            .service(ready),
-    );
+            .service(ready)
+            .service(get_fill_report)
+            .service(get_balance_history)
+            .service(start_drain)
+            .service(get_drain_status)
+            .service(list_tokens)
+            .service(set_token)
+            .service(toggle_token),
+    )
+    .service(prometheus_metrics);
 }
@@ -1,7 +1,20 @@
-use actix_web::{HttpResponse, Responder, get, web};
+use std::time::Duration;
+
+use actix_web::{HttpRequest, HttpResponse, Responder, get, post, web};
+use ethers::types::H256;
+use mantle_core::{
+    chain::Chain, metrics::CONTENT_TYPE, token::TokenType, token_registry::TokenProfile,
+};
+use serde::Deserialize;
 use serde_json::json;
 
-use crate::{AppState, model::MetricsResponse};
+use crate::{
+    AppState, balance_history::BalanceHistoryQuery, capital_check::RouteCapacity,
+    drain::authorize_admin, model::MetricsResponse,
+};
+
+/// Longest a `POST /admin/drain?wait_secs=` call blocks.
+const MAX_DRAIN_WAIT_SECS: u64 = 600;
 
 #[get("/health")]
 pub async fn health_check(data: web::Data<AppState>) -> impl Responder {
@@ -28,10 +41,10 @@ pub async fn metrics(data: web::Data<AppState>) -> impl Responder {
     let metrics = data.solver.get_metrics().await;
 
     let response = MetricsResponse {
-        total_intents_evaluated: metrics.total_intents_evaluated,
-        total_fills_attempted: metrics.total_fills_attempted,
-        successful_fills: metrics.successful_fills,
-        failed_fills: metrics.failed_fills,
+        total_intents_evaluated: metrics.total_intents_evaluated.get(),
+        total_fills_attempted: metrics.total_fills_attempted.get(),
+        successful_fills: metrics.successful_fills.get(),
+        failed_fills: metrics.failed_fills.get(),
         active_fills_count: metrics.active_fills_count,
         average_fill_time_secs: metrics.average_fill_time_secs,
         capital_deployed: metrics
@@ -49,12 +62,35 @@ pub async fn metrics(data: web::Data<AppState>) -> impl Responder {
             .iter()
             .map(|(k, v)| (format!("{:?}", k), v.to_string()))
             .collect(),
+        competitor_fills_detected: metrics.competitor_fills_detected.get(),
+        fills_aborted_for_competitor: metrics.fills_aborted_for_competitor.get(),
+        pending_opportunities: metrics.pending_opportunities,
+        duplicate_deliveries: metrics.duplicate_deliveries.get(),
         last_error: metrics.last_error,
+        outbound_http: data.outbound_http.metrics(),
+        price_sources: data.solver.price_sources().await,
+        processed_intents: data.solver.processed_intent_stats().await,
+        capital_at_risk: data.solver.capital_at_risk().await,
+        dry_run: data.solver.dry_run_stats().await,
+        rebalance: data.solver.rebalance_stats().await,
+        process_start_time: metrics.process_start_time,
+        counters_since: metrics.counters_since,
     };
 
     HttpResponse::Ok().json(response)
 }
 
+/// Prometheus scrape target; served outside `/api/v1` like the relayer's.
+#[get("/metrics")]
+pub async fn prometheus_metrics(data: web::Data<AppState>) -> impl Responder {
+    match data.solver.prometheus.render() {
+        Ok(text) => HttpResponse::Ok().content_type(CONTENT_TYPE).body(text),
+        Err(e) => HttpResponse::InternalServerError().json(json!({
+            "error": e.to_string()
+        })),
+    }
+}
+
 #[get("/status")]
 pub async fn get_status(data: web::Data<AppState>) -> impl Responder {
     let metric = data.solver.get_metrics().await;
@@ -66,26 +102,241 @@ pub async fn get_status(data: web::Data<AppState>) -> impl Responder {
         "mantle_chain_id": config.mantle_chain_id,
         "max_concurrent_fills": config.max_concurrent_fills,
         "min_profit_bps": config.min_profit_bps,
+        "stale_price_margin_bps": config.stale_price_margin_bps,
+        "dry_run": config.dry_run.enabled,
         "uptime_secs": data.start_time.elapsed().as_secs(),
         "metrics": {
             "total_intents_evaluated": metric.total_intents_evaluated,
             "successful_fills": metric.successful_fills,
             "active_fills": metric.active_fills_count,
         },
+        "rpc_endpoints": {
+            "ethereum": data.solver.get_rpc_endpoints(config.ethereum_chain_id).await,
+            "mantle": data.solver.get_rpc_endpoints(config.mantle_chain_id).await,
+        },
     }))
 }
 
 #[get("/ready")]
 pub async fn ready(data: web::Data<AppState>) -> impl Responder {
+    if data.solver.is_draining() {
+        return HttpResponse::ServiceUnavailable().json(json!({
+            "ready": false,
+            "reason": "Draining for shutdown",
+            "drain": data.solver.drain_status().await,
+        }));
+    }
+
     let metric = data.solver.get_metrics().await;
 
+    let Some(routes) = data.solver.get_route_capital().await else {
+        return HttpResponse::ServiceUnavailable().json(json!({
+            "ready": false,
+            "reason": "Capital check pending"
+        }));
+    };
+    let disabled_routes: Vec<String> = routes
+        .iter()
+        .filter(|route| route.capacity == RouteCapacity::Disabled)
+        .map(|route| route.route())
+        .collect();
+
     // Consider ready if no critical errors and can process fills
-    if metric.last_error.is_none() || metric.successful_fills > 0 {
-        HttpResponse::Ok().json(json!({"ready": true}))
+    if disabled_routes.len() == routes.len() {
+        HttpResponse::ServiceUnavailable().json(json!({
+            "ready": false,
+            "reason": "No route has enough capital to fill an intent",
+            "disabled_routes": disabled_routes,
+            "routes": routes,
+        }))
+    } else if metric.last_error.is_none() || metric.successful_fills.session() > 0 {
+        HttpResponse::Ok().json(json!({
+            "ready": true,
+            "disabled_routes": disabled_routes,
+            "routes": routes,
+        }))
     } else {
         HttpResponse::ServiceUnavailable().json(json!({
             "ready": false,
-            "reason": metric.last_error
+            "reason": metric.last_error,
+            "disabled_routes": disabled_routes,
+            "routes": routes,
         }))
     }
 }
+
+#[get("/fills/{intent_id}")]
+pub async fn get_fill_report(data: web::Data<AppState>, path: web::Path<String>) -> impl Responder {
+    let Ok(intent_id) = path.parse::<H256>() else {
+        return HttpResponse::BadRequest().json(json!({
+            "error": format!("Invalid intent id: {}", path.as_str())
+        }));
+    };
+
+    match data.solver.get_execution_report(&intent_id).await {
+        Some(report) => HttpResponse::Ok().json(report),
+        None => HttpResponse::NotFound().json(json!({
+            "error": format!("No execution record for {:?}", intent_id)
+        })),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BalanceHistoryParams {
+    pub token: Option<String>,
+    pub chain: Option<String>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    pub step_secs: Option<u64>,
+}
+
+#[get("/balances/history")]
+pub async fn get_balance_history(
+    data: web::Data<AppState>,
+    params: web::Query<BalanceHistoryParams>,
+) -> impl Responder {
+    let token = match params.token.as_deref().map(TokenType::from_symbol) {
+        Some(Err(e)) => {
+            return HttpResponse::BadRequest().json(json!({ "error": e.to_string() }));
+        }
+        Some(Ok(token)) => Some(token),
+        None => None,
+    };
+    let chain = match params.chain.as_deref().map(Chain::parse) {
+        Some(Err(e)) => {
+            return HttpResponse::BadRequest().json(json!({ "error": e.to_string() }));
+        }
+        Some(Ok(chain)) => Some(chain),
+        None => None,
+    };
+
+    let query = BalanceHistoryQuery {
+        token,
+        chain_id: chain.map(|chain| chain.id() as u64),
+        since: params.since,
+        until: params.until,
+        step_secs: params.step_secs,
+    };
+    let snapshots = data.solver.get_balance_history(&query).await;
+    let (interval_secs, retention_secs) = data.solver.balance_history_window().await;
+
+    HttpResponse::Ok().json(json!({
+        "snapshot_interval_secs": interval_secs,
+        "retention_secs": retention_secs,
+        "count": snapshots.len(),
+        "snapshots": snapshots,
+    }))
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DrainParams {
+    /// Block until drained or this many seconds pass.
+    pub wait_secs: Option<u64>,
+}
+
+/// Stop picking up intents and let executing fills finish. `200` once
+/// drained, `202` while fills are still running.
+#[post("/admin/drain")]
+pub async fn start_drain(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    params: web::Query<DrainParams>,
+) -> impl Responder {
+    if !authorize_admin(&req, data.admin_token.as_deref()) {
+        return HttpResponse::Unauthorized().json(json!({ "error": "Unauthorized" }));
+    }
+
+    let mut status = data.solver.start_drain().await;
+    let wait = Duration::from_secs(params.wait_secs.unwrap_or(0).min(MAX_DRAIN_WAIT_SECS));
+    let deadline = std::time::Instant::now() + wait;
+    while !status.drained && std::time::Instant::now() < deadline {
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        status = data.solver.drain_status().await;
+    }
+
+    if status.drained {
+        HttpResponse::Ok().json(status)
+    } else {
+        HttpResponse::Accepted().json(status)
+    }
+}
+
+#[get("/admin/drain")]
+pub async fn get_drain_status(req: HttpRequest, data: web::Data<AppState>) -> impl Responder {
+    if !authorize_admin(&req, data.admin_token.as_deref()) {
+        return HttpResponse::Unauthorized().json(json!({ "error": "Unauthorized" }));
+    }
+
+    HttpResponse::Ok().json(data.solver.drain_status().await)
+}
+
+#[get("/admin/tokens")]
+pub async fn list_tokens(req: HttpRequest, data: web::Data<AppState>) -> impl Responder {
+    if !authorize_admin(&req, data.admin_token.as_deref()) {
+        return HttpResponse::Unauthorized().json(json!({ "error": "Unauthorized" }));
+    }
+
+    HttpResponse::Ok().json(data.solver.config.tokens.listings())
+}
+
+/// Admin listing of one token. Amounts are whole tokens.
+#[derive(Debug, Deserialize)]
+pub struct TokenListingRequest {
+    pub symbol: String,
+    #[serde(flatten)]
+    pub token: TokenProfile,
+}
+
+/// List a token or replace its listing until the next restart; lasting
+/// changes belong in the network profile.
+#[post("/admin/tokens")]
+pub async fn set_token(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    request: web::Json<TokenListingRequest>,
+) -> impl Responder {
+    if !authorize_admin(&req, data.admin_token.as_deref()) {
+        return HttpResponse::Unauthorized().json(json!({ "error": "Unauthorized" }));
+    }
+
+    let listing = match TokenType::from_symbol(&request.symbol)
+        .and_then(|token| request.token.listing(token))
+    {
+        Ok(listing) => listing,
+        Err(e) => return HttpResponse::BadRequest().json(json!({ "error": e.to_string() })),
+    };
+
+    match data.solver.config.tokens.upsert(listing.clone()) {
+        Ok(()) => HttpResponse::Ok().json(listing),
+        Err(e) => HttpResponse::BadRequest().json(json!({ "error": e.to_string() })),
+    }
+}
+
+/// `enable` or `disable` a listed token until the next restart.
+#[post("/admin/tokens/{symbol}/{action}")]
+pub async fn toggle_token(
+    req: HttpRequest,
+    data: web::Data<AppState>,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    if !authorize_admin(&req, data.admin_token.as_deref()) {
+        return HttpResponse::Unauthorized().json(json!({ "error": "Unauthorized" }));
+    }
+
+    let (symbol, action) = path.into_inner();
+    let enabled = match action.as_str() {
+        "enable" => true,
+        "disable" => false,
+        other => {
+            return HttpResponse::NotFound()
+                .json(json!({ "error": format!("Unknown action: {}", other) }));
+        }
+    };
+
+    match TokenType::from_symbol(&symbol)
+        .and_then(|token| data.solver.config.tokens.set_enabled(token, enabled))
+    {
+        Ok(listing) => HttpResponse::Ok().json(listing),
+        Err(e) => HttpResponse::NotFound().json(json!({ "error": e.to_string() })),
+    }
+}
@@ -0,0 +1,334 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result};
+use mantle_core::token::TokenType;
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+const DAY_SECS: u64 = 86_400;
+
+/// Where and how often balance snapshots are kept for `GET /balances/history`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BalanceHistoryPolicy {
+    /// JSON lines file the history survives restarts in; `None` keeps it in memory only.
+    pub path: Option<PathBuf>,
+    pub snapshot_interval_secs: u64,
+    pub retention_secs: u64,
+}
+
+impl Default for BalanceHistoryPolicy {
+    fn default() -> Self {
+        Self {
+            path: Some(PathBuf::from("balance_history.jsonl")),
+            snapshot_interval_secs: 300,
+            retention_secs: 30 * DAY_SECS,
+        }
+    }
+}
+
+impl BalanceHistoryPolicy {
+    /// Override the defaults from `BALANCE_HISTORY_PATH` (empty disables the
+    /// file), `BALANCE_SNAPSHOT_INTERVAL_SECS` and `BALANCE_HISTORY_RETENTION_DAYS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(path) = std::env::var("BALANCE_HISTORY_PATH") {
+            policy.path = (!path.trim().is_empty()).then(|| PathBuf::from(path.trim()));
+        }
+        if let Ok(secs) = std::env::var("BALANCE_SNAPSHOT_INTERVAL_SECS") {
+            policy.snapshot_interval_secs = secs
+                .parse::<u64>()
+                .context("Invalid BALANCE_SNAPSHOT_INTERVAL_SECS")?
+                .max(1);
+        }
+        if let Ok(days) = std::env::var("BALANCE_HISTORY_RETENTION_DAYS") {
+            policy.retention_secs = days
+                .parse::<u64>()
+                .context("Invalid BALANCE_HISTORY_RETENTION_DAYS")?
+                .max(1)
+                * DAY_SECS;
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Solver inventory of one token on one chain, with the fill counters at the
+/// same moment so drawdown can be lined up against activity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceSnapshot {
+    pub at: u64,
+    pub token: TokenType,
+    pub chain_id: u64,
+    /// Base units of `token`.
+    pub balance: String,
+    pub active_fills: usize,
+    /// Lifetime total, including runs before the last restart.
+    pub successful_fills: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct BalanceHistoryQuery {
+    pub token: Option<TokenType>,
+    pub chain_id: Option<u64>,
+    pub since: Option<u64>,
+    pub until: Option<u64>,
+    /// Keep only the last snapshot per series in each bucket of this size.
+    pub step_secs: Option<u64>,
+}
+
+/// Periodic balance snapshots, oldest first, bounded by the retention window.
+#[derive(Debug)]
+pub struct BalanceHistory {
+    policy: BalanceHistoryPolicy,
+    snapshots: VecDeque<BalanceSnapshot>,
+    last_snapshot_at: Option<u64>,
+}
+
+impl BalanceHistory {
+    /// Read back the persisted history, dropping expired and unreadable lines.
+    pub fn load(policy: BalanceHistoryPolicy, now: u64) -> Self {
+        let mut history = Self {
+            policy,
+            snapshots: VecDeque::new(),
+            last_snapshot_at: None,
+        };
+
+        let Some(path) = history.policy.path.clone() else {
+            return history;
+        };
+
+        let skipped = match history.read_file() {
+            Ok(skipped) => skipped,
+            Err(e) => {
+                warn!(
+                    "⚠️ Failed to read balance history {}: {}",
+                    path.display(),
+                    e
+                );
+                return history;
+            }
+        };
+
+        let expired = history.prune(now);
+        if (expired > 0 || skipped > 0)
+            && let Err(e) = history.rewrite()
+        {
+            warn!("⚠️ Failed to compact balance history: {}", e);
+        }
+
+        info!(
+            "📈 Loaded {} balance snapshots from {} ({} expired, {} unreadable)",
+            history.snapshots.len(),
+            path.display(),
+            expired,
+            skipped
+        );
+
+        history
+    }
+
+    /// The persisted history as is, for `solver export`. Never rewrites the
+    /// file, which the running solver may be appending to.
+    pub fn read_only(policy: BalanceHistoryPolicy) -> Result<Self> {
+        let mut history = Self {
+            policy,
+            snapshots: VecDeque::new(),
+            last_snapshot_at: None,
+        };
+        if history.policy.path.is_none() {
+            anyhow::bail!("BALANCE_HISTORY_PATH is empty, the history is kept in memory only");
+        }
+
+        history
+            .read_file()
+            .context("Failed to read balance history")?;
+        Ok(history)
+    }
+
+    /// Load the file into memory, returning the number of unreadable lines.
+    /// A missing file is an empty history.
+    fn read_file(&mut self) -> std::io::Result<usize> {
+        let Some(path) = &self.policy.path else {
+            return Ok(0);
+        };
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+
+        let mut skipped = 0;
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str::<BalanceSnapshot>(line) {
+                Ok(snapshot) => self.snapshots.push_back(snapshot),
+                Err(_) => skipped += 1,
+            }
+        }
+        self.snapshots.make_contiguous().sort_by_key(|s| s.at);
+        self.last_snapshot_at = self.snapshots.back().map(|s| s.at);
+        Ok(skipped)
+    }
+
+    pub fn is_due(&self, now: u64) -> bool {
+        self.last_snapshot_at
+            .is_none_or(|last| now >= last + self.policy.snapshot_interval_secs)
+    }
+
+    /// Append one round of snapshots taken at `now`.
+    pub fn record(&mut self, snapshots: Vec<BalanceSnapshot>, now: u64) -> Result<()> {
+        self.last_snapshot_at = Some(now);
+        self.snapshots.extend(snapshots.iter().cloned());
+        self.prune(now);
+
+        let Some(path) = &self.policy.path else {
+            return Ok(());
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut lines = String::new();
+        for snapshot in &snapshots {
+            lines.push_str(&serde_json::to_string(snapshot)?);
+            lines.push('\n');
+        }
+        file.write_all(lines.as_bytes())
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    pub fn query(&self, query: &BalanceHistoryQuery) -> Vec<BalanceSnapshot> {
+        let matching = self.snapshots.iter().filter(|s| {
+            query.token.is_none_or(|token| s.token == token)
+                && query.chain_id.is_none_or(|chain_id| s.chain_id == chain_id)
+                && query.since.is_none_or(|since| s.at >= since)
+                && query.until.is_none_or(|until| s.at <= until)
+        });
+
+        let Some(step) = query.step_secs.filter(|step| *step > 0) else {
+            return matching.cloned().collect();
+        };
+
+        // Snapshots are in time order, so the last one seen per bucket wins
+        let mut buckets: HashMap<(TokenType, u64, u64), usize> = HashMap::new();
+        let mut sampled: Vec<BalanceSnapshot> = Vec::new();
+        for snapshot in matching {
+            let key = (snapshot.token, snapshot.chain_id, snapshot.at / step);
+            match buckets.get(&key) {
+                Some(&index) => sampled[index] = snapshot.clone(),
+                None => {
+                    buckets.insert(key, sampled.len());
+                    sampled.push(snapshot.clone());
+                }
+            }
+        }
+        sampled
+    }
+
+    pub fn snapshot_interval_secs(&self) -> u64 {
+        self.policy.snapshot_interval_secs
+    }
+
+    pub fn retention_secs(&self) -> u64 {
+        self.policy.retention_secs
+    }
+
+    fn prune(&mut self, now: u64) -> usize {
+        let cutoff = now.saturating_sub(self.policy.retention_secs);
+        let before = self.snapshots.len();
+        while self.snapshots.front().is_some_and(|s| s.at < cutoff) {
+            self.snapshots.pop_front();
+        }
+        before - self.snapshots.len()
+    }
+
+    fn rewrite(&self) -> Result<()> {
+        let Some(path) = &self.policy.path else {
+            return Ok(());
+        };
+
+        let mut lines = String::new();
+        for snapshot in &self.snapshots {
+            lines.push_str(&serde_json::to_string(snapshot)?);
+            lines.push('\n');
+        }
+
+        let tmp = path.with_extension("jsonl.tmp");
+        fs::write(&tmp, lines).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(at: u64, token: TokenType, chain_id: u64, balance: u64) -> BalanceSnapshot {
+        BalanceSnapshot {
+            at,
+            token,
+            chain_id,
+            balance: balance.to_string(),
+            active_fills: 0,
+            successful_fills: 0,
+        }
+    }
+
+    #[test]
+    fn test_history_persists_prunes_and_samples() {
+        let path = std::env::temp_dir().join(format!(
+            "solver-balance-history-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let policy = BalanceHistoryPolicy {
+            path: Some(path.clone()),
+            snapshot_interval_secs: 60,
+            retention_secs: 1_000,
+        };
+
+        let mut history = BalanceHistory::load(policy.clone(), 0);
+        assert!(history.is_due(0));
+        for at in [0, 60, 120, 180] {
+            history
+                .record(
+                    vec![
+                        snapshot(at, TokenType::USDC, 5003, 1_000 - at),
+                        snapshot(at, TokenType::ETH, 11155111, 50),
+                    ],
+                    at,
+                )
+                .unwrap();
+        }
+        assert!(!history.is_due(200));
+        assert!(history.is_due(240));
+
+        // Reloading after the first round expired drops it from memory and file
+        let reloaded = BalanceHistory::load(policy.clone(), 1_100);
+        let usdc = reloaded.query(&BalanceHistoryQuery {
+            token: Some(TokenType::USDC),
+            ..Default::default()
+        });
+        assert_eq!(
+            usdc.iter().map(|s| s.at).collect::<Vec<_>>(),
+            vec![120, 180]
+        );
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 4);
+
+        let sampled = reloaded.query(&BalanceHistoryQuery {
+            chain_id: Some(5003),
+            step_secs: Some(600),
+            ..Default::default()
+        });
+        assert_eq!(sampled, vec![snapshot(180, TokenType::USDC, 5003, 820)]);
+
+        let _ = fs::remove_file(&path);
+    }
+}
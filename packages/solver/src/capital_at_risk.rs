@@ -0,0 +1,162 @@
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{Context, Result};
+use mantle_core::{amount::to_decimal, token::TokenType};
+use serde::{Deserialize, Serialize};
+
+use crate::model::{ActiveFill, FillStatus};
+
+/// Ceiling on capital at risk: inventory that landed on the destination chain
+/// and has not been paid back on the source chain yet. A relayer that stops
+/// settling otherwise drains the inventory one fill at a time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CapitalAtRiskPolicy {
+    /// `None` never pauses fills; capital at risk is still reported.
+    pub max_usd: Option<f64>,
+}
+
+impl CapitalAtRiskPolicy {
+    /// Override the default from `MAX_CAPITAL_AT_RISK_USD` (empty or `0`
+    /// disables the ceiling).
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(max) = std::env::var("MAX_CAPITAL_AT_RISK_USD") {
+            let max = max.trim();
+            if !max.is_empty() {
+                let max: f64 = max.parse().context("Invalid MAX_CAPITAL_AT_RISK_USD")?;
+                policy.max_usd = (max > 0.0).then_some(max);
+            }
+        }
+
+        Ok(policy)
+    }
+
+    /// Why a fill worth `fill_usd` must wait, if it would take capital at
+    /// risk past the ceiling.
+    pub fn blocks(&self, at_risk: &CapitalAtRisk, fill_usd: f64) -> Option<String> {
+        let max = self.max_usd?;
+        let projected = at_risk.total_usd + fill_usd;
+        (projected > max).then(|| {
+            format!(
+                "Capital at risk ${:.2} + ${:.2} exceeds ${:.2}",
+                at_risk.total_usd, fill_usd, max
+            )
+        })
+    }
+}
+
+/// Whether the fill's inventory has left the wallet without being repaid.
+pub fn is_at_risk(fill: &ActiveFill) -> bool {
+    matches!(fill.status, FillStatus::Confirmed | FillStatus::Claimed)
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenAtRisk {
+    pub fills: usize,
+    /// Whole tokens.
+    pub amount: f64,
+    pub usd: f64,
+}
+
+/// Capital at risk for `GET /metrics`, by token symbol.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CapitalAtRisk {
+    pub total_usd: f64,
+    pub by_token: BTreeMap<String, TokenAtRisk>,
+    /// Configured ceiling, if any.
+    pub max_usd: Option<f64>,
+}
+
+impl CapitalAtRisk {
+    /// Sum the at-risk fills, priced with `prices` (USD per whole token).
+    /// A token without a price counts in `amount` but not in `usd`.
+    pub fn of<'a>(
+        fills: impl IntoIterator<Item = &'a ActiveFill>,
+        prices: &HashMap<TokenType, f64>,
+        max_usd: Option<f64>,
+    ) -> Self {
+        let mut at_risk = Self {
+            max_usd,
+            ..Default::default()
+        };
+
+        for fill in fills.into_iter().filter(|fill| is_at_risk(fill)) {
+            let amount = to_decimal(fill.filled_amount, fill.token_type.decimals());
+            let usd = prices
+                .get(&fill.token_type)
+                .map_or(0.0, |price| amount * price);
+
+            let token = at_risk
+                .by_token
+                .entry(fill.token_type.symbol().to_string())
+                .or_default();
+            token.fills += 1;
+            token.amount += amount;
+            token.usd += usd;
+            at_risk.total_usd += usd;
+        }
+
+        at_risk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::{Address, H256, U256};
+
+    fn fill(token_type: TokenType, whole: u64, status: FillStatus) -> ActiveFill {
+        let amount = U256::from(whole) * U256::exp10(token_type.decimals() as usize);
+        ActiveFill {
+            intent_id: H256::random(),
+            tx_hash: H256::random(),
+            amount,
+            filled_amount: amount,
+            chunks: 1,
+            token: Address::zero(),
+            token_type,
+            filled_at: 0,
+            confirmed_at: None,
+            status,
+            dest_chain: 5003,
+        }
+    }
+
+    #[test]
+    fn test_only_landed_unsettled_fills_count() {
+        let fills = [
+            fill(TokenType::ETH, 2, FillStatus::Confirmed),
+            fill(TokenType::ETH, 1, FillStatus::Claimed),
+            fill(TokenType::ETH, 5, FillStatus::Pending),
+            fill(TokenType::ETH, 5, FillStatus::Settled),
+            fill(TokenType::USDC, 100, FillStatus::Claimed),
+            fill(TokenType::MNT, 10, FillStatus::Failed),
+        ];
+        let prices = HashMap::from([(TokenType::ETH, 3000.0), (TokenType::USDC, 1.0)]);
+
+        let at_risk = CapitalAtRisk::of(&fills, &prices, Some(10_000.0));
+        assert_eq!(at_risk.total_usd, 9100.0);
+        assert_eq!(at_risk.by_token["ETH"].fills, 2);
+        assert_eq!(at_risk.by_token["ETH"].amount, 3.0);
+        assert_eq!(at_risk.by_token["USDC"].usd, 100.0);
+        assert!(!at_risk.by_token.contains_key("MNT"));
+    }
+
+    #[test]
+    fn test_ceiling_blocks_fills_that_would_cross_it() {
+        let at_risk = CapitalAtRisk {
+            total_usd: 9_000.0,
+            ..Default::default()
+        };
+
+        let unlimited = CapitalAtRiskPolicy::default();
+        assert!(unlimited.blocks(&at_risk, 1_000_000.0).is_none());
+
+        let policy = CapitalAtRiskPolicy {
+            max_usd: Some(10_000.0),
+        };
+        assert!(policy.blocks(&at_risk, 1_000.0).is_none());
+        assert!(policy.blocks(&at_risk, 1_000.01).is_some());
+    }
+}
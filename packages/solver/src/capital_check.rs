@@ -0,0 +1,166 @@
+use std::collections::HashMap;
+
+use ethers::types::U256;
+use mantle_core::{amount::to_decimal, chain::Chain, route::RouteMinimums, token::TokenType};
+use serde::Serialize;
+
+use crate::optimizer::with_margin;
+
+/// Whether the solver's inventory on a route can actually fill intents.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteCapacity {
+    /// Covers the reserve plus a typical intent.
+    Ready,
+    /// Small intents still fit, but typical ones eat into the reserve.
+    Limited,
+    /// Not even the route minimum fits; every intent on the route is skipped.
+    Disabled,
+}
+
+/// Capital assessment of one token on the chain intents are filled on.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RouteCapital {
+    pub token: TokenType,
+    pub chain: Chain,
+    /// Whole tokens, for operators reading logs and `/ready`.
+    pub balance: f64,
+    pub reserve: f64,
+    pub min_intent: f64,
+    pub typical_intent: f64,
+    pub capacity: RouteCapacity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl RouteCapital {
+    pub fn route(&self) -> String {
+        format!("{}:{}", self.token.symbol(), self.chain.name())
+    }
+}
+
+/// Amounts a route's inventory is measured against.
+#[derive(Debug, Clone, Copy)]
+pub struct RouteRequirement {
+    pub reserve: U256,
+    pub min_intent: U256,
+    pub typical_intent: U256,
+}
+
+impl RouteRequirement {
+    /// Typical sizes come from `ROUTE_TYPICAL_AMOUNTS` and fall back to the
+    /// route minimum; a typical size below the minimum is raised to it.
+    pub fn for_route(
+        token: TokenType,
+        chain: Chain,
+        reserves: &HashMap<TokenType, U256>,
+        minimums: &RouteMinimums,
+        typical: &HashMap<(TokenType, Chain), U256>,
+    ) -> Self {
+        let min_intent = minimums.minimum(token, chain);
+
+        Self {
+            reserve: reserves.get(&token).copied().unwrap_or_default(),
+            min_intent,
+            typical_intent: typical
+                .get(&(token, chain))
+                .copied()
+                .unwrap_or(min_intent)
+                .max(min_intent),
+        }
+    }
+}
+
+/// Compare `balance` against the same 5% fill margin `should_fill` applies.
+pub fn assess_route(
+    token: TokenType,
+    chain: Chain,
+    balance: U256,
+    requirement: RouteRequirement,
+) -> RouteCapital {
+    let decimals = token.decimals();
+    let whole = |amount: U256| to_decimal(amount, decimals);
+
+    let smallest_fill = with_margin(requirement.min_intent);
+    let comfortable = requirement
+        .reserve
+        .saturating_add(with_margin(requirement.typical_intent));
+
+    let (capacity, reason) = if balance < smallest_fill {
+        (
+            RouteCapacity::Disabled,
+            Some(format!(
+                "Balance {} {} can't fill the {} {} route minimum",
+                whole(balance),
+                token.symbol(),
+                whole(requirement.min_intent),
+                token.symbol()
+            )),
+        )
+    } else if balance < comfortable {
+        (
+            RouteCapacity::Limited,
+            Some(format!(
+                "Balance {} {} is below the {} reserve plus a typical {} intent",
+                whole(balance),
+                token.symbol(),
+                whole(requirement.reserve),
+                whole(requirement.typical_intent)
+            )),
+        )
+    } else {
+        (RouteCapacity::Ready, None)
+    };
+
+    RouteCapital {
+        token,
+        chain,
+        balance: whole(balance),
+        reserve: whole(requirement.reserve),
+        min_intent: whole(requirement.min_intent),
+        typical_intent: whole(requirement.typical_intent),
+        capacity,
+        reason,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assess_route_capacity() {
+        let usdc = |whole: u64| U256::from(whole) * U256::exp10(6);
+        let reserves = HashMap::from([(TokenType::USDC, usdc(1000))]);
+        let typical = HashMap::from([((TokenType::USDC, Chain::Mantle), usdc(500))]);
+        let requirement = RouteRequirement::for_route(
+            TokenType::USDC,
+            Chain::Mantle,
+            &reserves,
+            &RouteMinimums::default(),
+            &typical,
+        );
+        assert_eq!(requirement.min_intent, usdc(1));
+
+        let assess = |balance| assess_route(TokenType::USDC, Chain::Mantle, balance, requirement);
+
+        let empty = assess(U256::zero());
+        assert_eq!(empty.capacity, RouteCapacity::Disabled);
+        assert_eq!(empty.route(), "USDC:mantle");
+        assert_eq!(assess(usdc(1)).capacity, RouteCapacity::Disabled);
+        assert_eq!(assess(usdc(2)).capacity, RouteCapacity::Limited);
+        assert_eq!(assess(usdc(1524)).capacity, RouteCapacity::Limited);
+        assert_eq!(assess(usdc(1525)).capacity, RouteCapacity::Ready);
+        assert!(assess(usdc(1525)).reason.is_none());
+
+        // A typical size under the route minimum is raised to it
+        let floor = RouteRequirement::for_route(
+            TokenType::USDC,
+            Chain::Ethereum,
+            &HashMap::new(),
+            &RouteMinimums::default(),
+            &HashMap::from([((TokenType::USDC, Chain::Ethereum), usdc(1))]),
+        );
+        assert_eq!(floor.typical_intent, usdc(10));
+    }
+}
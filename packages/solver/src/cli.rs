@@ -0,0 +1,184 @@
+use std::io::Write;
+
+use anyhow::{Result, bail};
+use clap::{Arg, ArgMatches, Command};
+use mantle_core::{
+    chain::Chain, http_client::OutboundPolicy, http_server::HttpServerOptions,
+    price_source::PriceSources, token::TokenType,
+};
+use tracing::info;
+
+use crate::{
+    balance_history::{BalanceHistory, BalanceHistoryPolicy, BalanceHistoryQuery},
+    load_config,
+};
+
+pub fn cli() -> Command {
+    Command::new("solver")
+        .about("Cross-chain intent solver")
+        .subcommand(
+            Command::new("serve").about("Watch and fill intents and serve the API (the default)"),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Write the persisted balance history to stdout, oldest first")
+                .arg(
+                    Arg::new("token")
+                        .long("token")
+                        .help("Token symbol, e.g. USDC"),
+                )
+                .arg(
+                    Arg::new("chain")
+                        .long("chain")
+                        .help("ethereum, mantle or a chain id"),
+                )
+                .arg(
+                    Arg::new("since")
+                        .long("since")
+                        .value_parser(clap::value_parser!(u64))
+                        .help("Unix seconds"),
+                )
+                .arg(
+                    Arg::new("until")
+                        .long("until")
+                        .value_parser(clap::value_parser!(u64))
+                        .help("Unix seconds"),
+                )
+                .arg(
+                    Arg::new("step-secs")
+                        .long("step-secs")
+                        .value_parser(clap::value_parser!(u64))
+                        .help("Keep the last snapshot per token and chain in each bucket"),
+                )
+                .arg(
+                    Arg::new("format")
+                        .long("format")
+                        .value_parser(["jsonl", "csv"])
+                        .default_value("jsonl"),
+                ),
+        )
+        .subcommand(
+            Command::new("config")
+                .about("Configuration tools")
+                .subcommand_required(true)
+                .subcommand(
+                    Command::new("validate")
+                        .about("Load every setting the solver reads and report all errors"),
+                ),
+        )
+}
+
+/// Parse the configuration without connecting to any RPC or signer.
+pub fn validate_config() -> Result<()> {
+    let checks: Vec<(&str, Result<()>)> = vec![
+        ("solver config", load_config().map(|_| ())),
+        (
+            "http server",
+            HttpServerOptions::from_env().and_then(|options| options.tls_config().map(|_| ())),
+        ),
+        ("outbound http", OutboundPolicy::from_env().map(|_| ())),
+        ("price sources", PriceSources::from_env().map(|_| ())),
+    ];
+
+    let mut failed = 0;
+    for (name, result) in &checks {
+        match result {
+            Ok(()) => println!("✅ {}", name),
+            Err(e) => {
+                failed += 1;
+                println!("❌ {}: {:#}", name, e);
+            }
+        }
+    }
+
+    if failed > 0 {
+        bail!("{} of {} configuration checks failed", failed, checks.len());
+    }
+    println!("Configuration is valid");
+    Ok(())
+}
+
+pub fn export(args: &ArgMatches) -> Result<()> {
+    let query = BalanceHistoryQuery {
+        token: args
+            .get_one::<String>("token")
+            .map(|symbol| TokenType::from_symbol(symbol))
+            .transpose()?,
+        chain_id: args
+            .get_one::<String>("chain")
+            .map(|chain| Chain::parse(chain))
+            .transpose()?
+            .map(|chain| chain.id() as u64),
+        since: args.get_one::<u64>("since").copied(),
+        until: args.get_one::<u64>("until").copied(),
+        step_secs: args.get_one::<u64>("step-secs").copied(),
+    };
+
+    let history = BalanceHistory::read_only(BalanceHistoryPolicy::from_env()?)?;
+    let snapshots = history.query(&query);
+
+    let mut out = std::io::stdout().lock();
+    match args
+        .get_one::<String>("format")
+        .expect("has default")
+        .as_str()
+    {
+        "csv" => {
+            writeln!(
+                out,
+                "at,token,chain_id,balance,active_fills,successful_fills"
+            )?;
+            for s in &snapshots {
+                writeln!(
+                    out,
+                    "{},{},{},{},{},{}",
+                    s.at,
+                    s.token.symbol(),
+                    s.chain_id,
+                    s.balance,
+                    s.active_fills,
+                    s.successful_fills
+                )?;
+            }
+        }
+        _ => {
+            for snapshot in &snapshots {
+                writeln!(out, "{}", serde_json::to_string(snapshot)?)?;
+            }
+        }
+    }
+
+    info!("📤 Exported {} balance snapshots", snapshots.len());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cli_parses_subcommands() {
+        cli().debug_assert();
+
+        let matches = cli()
+            .try_get_matches_from(["solver", "export", "--token", "USDC", "--format", "csv"])
+            .unwrap();
+        let (name, args) = matches.subcommand().unwrap();
+        assert_eq!(name, "export");
+        assert_eq!(args.get_one::<String>("format").unwrap(), "csv");
+
+        assert!(
+            cli()
+                .try_get_matches_from(["solver"])
+                .unwrap()
+                .subcommand()
+                .is_none()
+        );
+        assert!(cli().try_get_matches_from(["solver", "config"]).is_err());
+        assert!(
+            cli()
+                .try_get_matches_from(["solver", "export", "--since", "yesterday"])
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,112 @@
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+
+use actix_web::HttpRequest;
+use serde::Serialize;
+
+/// Soft-shutdown switch. Once draining, no new intent is picked up, while
+/// fills already executing run until their transaction is mined or dropped.
+#[derive(Debug, Default)]
+pub struct DrainState {
+    /// Unix seconds the drain started, 0 while serving.
+    started_at: AtomicU64,
+    executing: AtomicUsize,
+}
+
+impl DrainState {
+    /// Start draining; false if a drain was already under way.
+    pub fn start(&self) -> bool {
+        let now = (chrono::Utc::now().timestamp() as u64).max(1);
+        self.started_at
+            .compare_exchange(0, now, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.started_at.load(Ordering::SeqCst) != 0
+    }
+
+    pub fn started_at(&self) -> Option<u64> {
+        Some(self.started_at.load(Ordering::SeqCst)).filter(|at| *at != 0)
+    }
+
+    /// Count a fill as executing until the guard is dropped.
+    pub fn begin_fill(&self) -> FillGuard<'_> {
+        self.executing.fetch_add(1, Ordering::SeqCst);
+        FillGuard(self)
+    }
+
+    pub fn executing(&self) -> usize {
+        self.executing.load(Ordering::SeqCst)
+    }
+}
+
+pub struct FillGuard<'a>(&'a DrainState);
+
+impl Drop for FillGuard<'_> {
+    fn drop(&mut self) {
+        self.0.executing.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DrainStatus {
+    pub draining: bool,
+    /// Draining and no fill transaction left in flight: safe to stop.
+    pub drained: bool,
+    pub started_at: Option<u64>,
+    pub elapsed_secs: Option<u64>,
+    /// Fills between approval and a mined (or dropped) transaction.
+    pub executing_fills: usize,
+    /// Mined fills still waiting for finality; the relayer settles them
+    /// without the solver, so they do not hold the drain.
+    pub unconfirmed_fills: usize,
+}
+
+/// Admin routes take `Authorization: Bearer $SOLVER_ADMIN_TOKEN`, or only
+/// loopback callers (a preStop hook in the pod) when no token is set.
+pub fn authorize_admin(req: &HttpRequest, admin_token: Option<&str>) -> bool {
+    match admin_token {
+        Some(token) => req
+            .headers()
+            .get(actix_web::http::header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|given| given == token),
+        None => req.peer_addr().is_some_and(|peer| peer.ip().is_loopback()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use actix_web::test::TestRequest;
+
+    #[test]
+    fn test_drain_counts_executing_fills() {
+        let drain = DrainState::default();
+        assert!(!drain.is_draining());
+        assert_eq!(drain.started_at(), None);
+
+        let first = drain.begin_fill();
+        let second = drain.begin_fill();
+        assert!(drain.start());
+        assert!(!drain.start());
+        assert!(drain.is_draining());
+        assert_eq!(drain.executing(), 2);
+        drop(first);
+        drop(second);
+        assert_eq!(drain.executing(), 0);
+
+        let local = TestRequest::default()
+            .peer_addr("127.0.0.1:50000".parse().unwrap())
+            .to_http_request();
+        let remote = TestRequest::default()
+            .peer_addr("10.0.0.7:50000".parse().unwrap())
+            .insert_header(("Authorization", "Bearer s3cret"))
+            .to_http_request();
+        assert!(authorize_admin(&local, None));
+        assert!(!authorize_admin(&remote, None));
+        assert!(authorize_admin(&remote, Some("s3cret")));
+        assert!(!authorize_admin(&local, Some("s3cret")));
+    }
+}
@@ -0,0 +1,263 @@
+use std::{collections::BTreeMap, fs::OpenOptions, io::Write, path::PathBuf};
+
+use anyhow::{Context, Result};
+use ethers::types::H256;
+use mantle_core::token::TokenType;
+use serde::{Deserialize, Serialize};
+
+use crate::model::FillOpportunity;
+
+/// `min_profit_bps` values the dry-run stats are swept over.
+const PROFIT_SWEEP_BPS: [u16; 8] = [0, 5, 10, 25, 50, 100, 150, 200];
+
+/// Run the whole detection and decision pipeline without sending fills.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DryRunPolicy {
+    pub enabled: bool,
+    /// JSON lines file every decision is appended to; `None` keeps only the
+    /// totals on `GET /metrics`.
+    pub path: Option<PathBuf>,
+}
+
+impl Default for DryRunPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: Some(PathBuf::from("dry_run_decisions.jsonl")),
+        }
+    }
+}
+
+impl DryRunPolicy {
+    /// Override the defaults from `DRY_RUN` and `DRY_RUN_LOG_PATH` (empty
+    /// disables the file).
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(enabled) = std::env::var("DRY_RUN") {
+            policy.enabled = enabled.trim().parse().context("Invalid DRY_RUN")?;
+        }
+        if let Ok(path) = std::env::var("DRY_RUN_LOG_PATH") {
+            policy.path = (!path.trim().is_empty()).then(|| PathBuf::from(path.trim()));
+        }
+
+        Ok(policy)
+    }
+}
+
+/// What the solver would have done with one evaluated intent.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DryRunDecision {
+    pub at: u64,
+    pub intent_id: H256,
+    pub token: TokenType,
+    pub source_chain: u32,
+    pub dest_chain: u32,
+    /// Base units of `token`.
+    pub amount: String,
+    pub profit_bps: u16,
+    pub profit_usd: f64,
+    pub risk_score: u8,
+    pub gas_estimate: String,
+    pub would_fill: bool,
+    pub reason: Option<String>,
+}
+
+impl DryRunDecision {
+    pub fn of(
+        opportunity: &FillOpportunity,
+        would_fill: bool,
+        reason: Option<String>,
+        at: u64,
+    ) -> Self {
+        let intent = &opportunity.intent;
+        Self {
+            at,
+            intent_id: intent.intent_id,
+            token: intent.token_type,
+            source_chain: intent.source_chain,
+            dest_chain: intent.dest_chain,
+            amount: intent.amount.to_string(),
+            profit_bps: opportunity.profit_bps,
+            profit_usd: opportunity.profit_usd,
+            risk_score: opportunity.risk_score,
+            gas_estimate: opportunity.gas_estimate.to_string(),
+            would_fill,
+            reason,
+        }
+    }
+}
+
+/// Opportunities that clear one `min_profit_bps` value, whatever else
+/// decided them.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfitSweepPoint {
+    pub opportunities: u64,
+    pub profit_usd: f64,
+}
+
+/// Dry-run totals since the solver started, for `GET /metrics`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DryRunStats {
+    pub decisions: u64,
+    pub would_fill: u64,
+    pub skipped: u64,
+    /// Estimated profit of the fills that would have been sent.
+    pub would_fill_profit_usd: f64,
+    /// Skips by reason, with the amounts left out so they group.
+    pub skip_reasons: BTreeMap<String, u64>,
+    /// Keyed by `min_profit_bps`.
+    pub profit_sweep: BTreeMap<u16, ProfitSweepPoint>,
+}
+
+/// `"Low profit: 4 bps < 10 bps required"` counts as `"Low profit"`.
+fn reason_kind(reason: &str) -> String {
+    reason
+        .split_once(':')
+        .map_or(reason, |(kind, _)| kind)
+        .trim()
+        .to_string()
+}
+
+/// Decisions recorded in place of sending fills.
+#[derive(Debug)]
+pub struct DryRunLog {
+    policy: DryRunPolicy,
+    stats: DryRunStats,
+}
+
+impl DryRunLog {
+    pub fn new(policy: DryRunPolicy) -> Self {
+        Self {
+            policy,
+            stats: DryRunStats::default(),
+        }
+    }
+
+    pub fn stats(&self) -> DryRunStats {
+        self.stats.clone()
+    }
+
+    /// Count the decision and append it to the log file. The totals are
+    /// updated even when the write fails.
+    pub fn record(&mut self, decision: &DryRunDecision) -> Result<()> {
+        let stats = &mut self.stats;
+        stats.decisions += 1;
+        if decision.would_fill {
+            stats.would_fill += 1;
+            stats.would_fill_profit_usd += decision.profit_usd;
+        } else {
+            stats.skipped += 1;
+            let kind = decision
+                .reason
+                .as_deref()
+                .map_or_else(|| "unknown".to_string(), reason_kind);
+            *stats.skip_reasons.entry(kind).or_default() += 1;
+        }
+        for bps in PROFIT_SWEEP_BPS {
+            let point = stats.profit_sweep.entry(bps).or_default();
+            if decision.profit_bps >= bps {
+                point.opportunities += 1;
+                point.profit_usd += decision.profit_usd;
+            }
+        }
+
+        let Some(path) = &self.policy.path else {
+            return Ok(());
+        };
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(decision)?)
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::DetectedIntent;
+    use ethers::types::{Address, U256};
+
+    fn opportunity(id: u8, profit_bps: u16, profit_usd: f64) -> FillOpportunity {
+        FillOpportunity {
+            intent: DetectedIntent {
+                intent_id: H256::repeat_byte(id),
+                commitment: H256::zero(),
+                token: Address::zero(),
+                token_type: TokenType::USDC,
+                amount: U256::from(1_000_000),
+                source_chain: 11155111,
+                dest_chain: 5003,
+                source_block: 0,
+                detected_at: 1,
+                deadline: u64::MAX,
+            },
+            estimated_profit: U256::zero(),
+            profit_bps,
+            profit_usd,
+            risk_score: 10,
+            capital_required: U256::from(1_000_000),
+            gas_estimate: U256::from(21_000),
+            stale_prices: false,
+        }
+    }
+
+    #[test]
+    fn test_decisions_are_counted_and_logged() {
+        let path = std::env::temp_dir().join(format!("dry_run_test_{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut log = DryRunLog::new(DryRunPolicy {
+            enabled: true,
+            path: Some(path.clone()),
+        });
+
+        let decisions = [
+            DryRunDecision::of(&opportunity(1, 30, 3.0), true, None, 10),
+            DryRunDecision::of(
+                &opportunity(2, 4, 0.4),
+                false,
+                Some("Low profit: 4 bps < 10 bps required".to_string()),
+                11,
+            ),
+            DryRunDecision::of(
+                &opportunity(3, 8, 0.8),
+                false,
+                Some("Low profit: 8 bps < 10 bps required".to_string()),
+                12,
+            ),
+            DryRunDecision::of(
+                &opportunity(4, 60, 6.0),
+                false,
+                Some("Capital locked".to_string()),
+                13,
+            ),
+        ];
+        for decision in &decisions {
+            log.record(decision).unwrap();
+        }
+
+        let stats = log.stats();
+        assert_eq!(
+            (stats.decisions, stats.would_fill, stats.skipped),
+            (4, 1, 3)
+        );
+        assert_eq!(stats.would_fill_profit_usd, 3.0);
+        assert_eq!(stats.skip_reasons["Low profit"], 2);
+        assert_eq!(stats.skip_reasons["Capital locked"], 1);
+        // Lowering min_profit_bps to 5 would have admitted the 8 bps intent
+        assert_eq!(stats.profit_sweep[&5].opportunities, 3);
+        assert_eq!(stats.profit_sweep[&10].opportunities, 2);
+        assert_eq!(stats.profit_sweep[&100].opportunities, 0);
+
+        let logged: Vec<DryRunDecision> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(logged, decisions);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
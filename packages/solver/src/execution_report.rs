@@ -0,0 +1,272 @@
+use std::collections::{HashMap, VecDeque};
+
+use ethers::types::{H256, U256};
+use serde::Serialize;
+
+use crate::model::{DetectedIntent, FillOpportunity};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStatus {
+    Detected,
+    Rejected,
+    Queued,
+    Approved,
+    /// Approved in dry-run mode; no fill was sent.
+    Simulated,
+    Sent,
+    Confirmed,
+    Claimed,
+    Failed,
+}
+
+/// Outputs of `evaluate_fill_opportunity`.
+#[derive(Debug, Clone, Serialize)]
+pub struct EvaluationRecord {
+    pub evaluated_at: u64,
+    pub profit_bps: u16,
+    pub profit_usd: f64,
+    pub risk_score: u8,
+    pub estimated_profit: String,
+    pub capital_required: String,
+    pub gas_estimate: String,
+}
+
+/// Fill/skip decision and the capital picture it was made against.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DecisionRecord {
+    pub decided_at: u64,
+    pub approved: bool,
+    pub reason: Option<String>,
+    pub balance: Option<String>,
+    pub locked: Option<String>,
+    pub available: Option<String>,
+    pub required: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionError {
+    pub at: u64,
+    pub message: String,
+}
+
+/// Everything the solver did with one intent, served by `GET /fills/{intent_id}`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExecutionReport {
+    pub intent_id: String,
+    pub source_chain: u32,
+    pub dest_chain: u32,
+    pub token: String,
+    pub amount: String,
+    pub detected_at: u64,
+    pub status: ExecutionStatus,
+    pub evaluation: Option<EvaluationRecord>,
+    pub decision: Option<DecisionRecord>,
+    pub fill_tx_hash: Option<String>,
+    pub fill_sent_at: Option<u64>,
+    /// Same-nonce re-sends with escalated fees; `fill_tx_hash` is the latest.
+    pub fill_replacements: u32,
+    pub fill_block: Option<u64>,
+    pub confirmed_at: Option<u64>,
+    pub confirmations: u64,
+    pub errors: Vec<ExecutionError>,
+    pub updated_at: u64,
+}
+
+/// Bounded in-memory store, oldest intents are dropped first.
+#[derive(Debug)]
+pub struct ExecutionReports {
+    capacity: usize,
+    reports: HashMap<H256, ExecutionReport>,
+    order: VecDeque<H256>,
+}
+
+fn now() -> u64 {
+    chrono::Utc::now().timestamp() as u64
+}
+
+fn amount(value: U256) -> String {
+    value.to_string()
+}
+
+impl ExecutionReports {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            reports: HashMap::new(),
+            order: VecDeque::new(),
+        }
+    }
+
+    pub fn get(&self, intent_id: &H256) -> Option<&ExecutionReport> {
+        self.reports.get(intent_id)
+    }
+
+    /// Start (or restart, on retry) the report for a detected intent. Earlier
+    /// errors are kept so retries stay visible.
+    pub fn detected(&mut self, intent: &DetectedIntent) {
+        let report = ExecutionReport {
+            intent_id: format!("{:?}", intent.intent_id),
+            source_chain: intent.source_chain,
+            dest_chain: intent.dest_chain,
+            token: format!("{:?}", intent.token_type),
+            amount: amount(intent.amount),
+            detected_at: intent.detected_at,
+            status: ExecutionStatus::Detected,
+            evaluation: None,
+            decision: None,
+            fill_tx_hash: None,
+            fill_sent_at: None,
+            fill_replacements: 0,
+            fill_block: None,
+            confirmed_at: None,
+            confirmations: 0,
+            errors: Vec::new(),
+            updated_at: now(),
+        };
+
+        match self.reports.get_mut(&intent.intent_id) {
+            Some(existing) => {
+                let errors = std::mem::take(&mut existing.errors);
+                *existing = ExecutionReport { errors, ..report };
+            }
+            None => {
+                self.reports.insert(intent.intent_id, report);
+                self.order.push_back(intent.intent_id);
+
+                while self.order.len() > self.capacity {
+                    if let Some(oldest) = self.order.pop_front() {
+                        self.reports.remove(&oldest);
+                    }
+                }
+            }
+        }
+    }
+
+    pub fn evaluated(&mut self, opportunity: &FillOpportunity) {
+        self.update(&opportunity.intent.intent_id, |report| {
+            report.evaluation = Some(EvaluationRecord {
+                evaluated_at: now(),
+                profit_bps: opportunity.profit_bps,
+                profit_usd: opportunity.profit_usd,
+                risk_score: opportunity.risk_score,
+                estimated_profit: amount(opportunity.estimated_profit),
+                capital_required: amount(opportunity.capital_required),
+                gas_estimate: amount(opportunity.gas_estimate),
+            });
+        });
+    }
+
+    pub fn decided(&mut self, intent_id: &H256, status: ExecutionStatus, decision: DecisionRecord) {
+        self.update(intent_id, |report| {
+            report.status = status;
+            report.decision = Some(DecisionRecord {
+                decided_at: now(),
+                approved: status != ExecutionStatus::Rejected,
+                ..decision
+            });
+        });
+    }
+
+    pub fn simulated(&mut self, intent_id: &H256) {
+        self.update(intent_id, |report| {
+            report.status = ExecutionStatus::Simulated;
+        });
+    }
+
+    pub fn fill_sent(&mut self, intent_id: &H256, tx_hash: H256) {
+        self.update(intent_id, |report| {
+            report.status = ExecutionStatus::Sent;
+            report.fill_tx_hash = Some(format!("{:?}", tx_hash));
+            report.fill_sent_at = Some(now());
+        });
+    }
+
+    /// The fill was re-sent, or an earlier version than the latest was mined.
+    pub fn fill_replaced(&mut self, intent_id: &H256, tx_hash: H256, replacements: u32) {
+        self.update(intent_id, |report| {
+            report.fill_tx_hash = Some(format!("{:?}", tx_hash));
+            report.fill_replacements = replacements;
+        });
+    }
+
+    pub fn fill_confirmed(&mut self, intent_id: &H256, block: Option<u64>) {
+        self.update(intent_id, |report| {
+            report.status = ExecutionStatus::Confirmed;
+            report.fill_block = block;
+            report.confirmed_at = Some(now());
+        });
+    }
+
+    pub fn confirmations(&mut self, intent_id: &H256, confirmations: u64, claimed: bool) {
+        self.update(intent_id, |report| {
+            report.confirmations = confirmations;
+            if claimed {
+                report.status = ExecutionStatus::Claimed;
+            }
+        });
+    }
+
+    pub fn failed(&mut self, intent_id: &H256, message: String) {
+        self.update(intent_id, |report| {
+            report.status = ExecutionStatus::Failed;
+            report.errors.push(ExecutionError { at: now(), message });
+        });
+    }
+
+    fn update(&mut self, intent_id: &H256, apply: impl FnOnce(&mut ExecutionReport)) {
+        if let Some(report) = self.reports.get_mut(intent_id) {
+            apply(report);
+            report.updated_at = now();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::Address;
+    use mantle_core::token::TokenType;
+
+    fn intent(id: u8) -> DetectedIntent {
+        DetectedIntent {
+            intent_id: H256::repeat_byte(id),
+            commitment: H256::zero(),
+            token: Address::zero(),
+            token_type: TokenType::USDC,
+            amount: U256::from(1000),
+            source_chain: 11155111,
+            dest_chain: 5003,
+            source_block: 0,
+            detected_at: 1,
+            deadline: u64::MAX,
+        }
+    }
+
+    #[test]
+    fn test_lifecycle_and_retention() {
+        let mut reports = ExecutionReports::new(2);
+        let id = H256::repeat_byte(1);
+
+        reports.detected(&intent(1));
+        reports.failed(&id, "Confirmation timeout".to_string());
+
+        // A retry restarts the report but keeps the earlier error
+        reports.detected(&intent(1));
+        reports.decided(&id, ExecutionStatus::Approved, DecisionRecord::default());
+        reports.fill_sent(&id, H256::repeat_byte(9));
+        reports.fill_confirmed(&id, Some(42));
+        reports.confirmations(&id, 6, true);
+
+        let report = reports.get(&id).unwrap();
+        assert_eq!(report.status, ExecutionStatus::Claimed);
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.fill_block, Some(42));
+        assert!(report.decision.as_ref().unwrap().approved);
+
+        reports.detected(&intent(2));
+        reports.detected(&intent(3));
+        assert!(reports.get(&id).is_none());
+        assert!(reports.get(&H256::repeat_byte(3)).is_some());
+    }
+}
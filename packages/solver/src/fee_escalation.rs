@@ -0,0 +1,321 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result, anyhow};
+use ethers::types::{U256, transaction::eip2718::TypedTransaction};
+
+/// Minimum bump nodes accept for a same-nonce replacement is 10%; a little
+/// headroom avoids `replacement transaction underpriced` on rounding.
+const REPLACEMENT_BUMP_PCT: u64 = 112;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EscalationCurve {
+    /// Urgency grows evenly across the window.
+    Linear,
+    /// Stays close to the base fee until the last part of the window.
+    Quadratic,
+    /// Base fee until half the window is gone, then the full multiplier.
+    Step,
+}
+
+impl std::str::FromStr for EscalationCurve {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "linear" => Ok(Self::Linear),
+            "quadratic" => Ok(Self::Quadratic),
+            "step" => Ok(Self::Step),
+            other => Err(anyhow!("Unknown escalation curve: {}", other)),
+        }
+    }
+}
+
+/// Pays more for inclusion as an intent's deadline approaches.
+///
+/// Inside the last `window_secs` before the deadline the priority fee (or the
+/// legacy gas price) is scaled up to `max_multiplier_pct` along `curve`. The
+/// extra spend is capped at `profit_share_bps` of the fill's expected profit,
+/// and the fee per gas never exceeds `max_gas_price_gwei`.
+#[derive(Debug, Clone)]
+pub struct FeeEscalationPolicy {
+    pub enabled: bool,
+    pub window_secs: u64,
+    pub max_multiplier_pct: u64,
+    pub curve: EscalationCurve,
+    pub profit_share_bps: u64,
+    /// A fill still unmined after this long is re-sent with a higher fee.
+    pub replace_after: Duration,
+    pub max_replacements: u32,
+}
+
+impl Default for FeeEscalationPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            window_secs: 600,
+            max_multiplier_pct: 300,
+            curve: EscalationCurve::Quadratic,
+            profit_share_bps: 5000,
+            replace_after: Duration::from_secs(45),
+            max_replacements: 3,
+        }
+    }
+}
+
+/// Fee fields of a priced transaction. `priority_fee` is `None` for legacy
+/// transactions, where `fee_per_gas` is the gas price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Fees {
+    fee_per_gas: U256,
+    priority_fee: Option<U256>,
+}
+
+impl Fees {
+    fn read(tx: &TypedTransaction) -> Option<Self> {
+        match tx {
+            TypedTransaction::Eip1559(inner) => Some(Self {
+                fee_per_gas: inner.max_fee_per_gas?,
+                priority_fee: Some(inner.max_priority_fee_per_gas?),
+            }),
+            _ => Some(Self {
+                fee_per_gas: tx.gas_price()?,
+                priority_fee: None,
+            }),
+        }
+    }
+
+    fn write(self, tx: &mut TypedTransaction) {
+        match tx {
+            TypedTransaction::Eip1559(inner) => {
+                inner.max_fee_per_gas = Some(self.fee_per_gas);
+                inner.max_priority_fee_per_gas = self.priority_fee;
+            }
+            _ => {
+                tx.set_gas_price(self.fee_per_gas);
+            }
+        }
+    }
+}
+
+impl FeeEscalationPolicy {
+    /// Override the defaults from `FEE_ESCALATION_ENABLED`,
+    /// `FEE_ESCALATION_WINDOW_SECS`, `FEE_ESCALATION_MAX_MULTIPLIER_PCT`,
+    /// `FEE_ESCALATION_CURVE`, `FEE_ESCALATION_PROFIT_SHARE_BPS`,
+    /// `FEE_ESCALATION_REPLACE_AFTER_SECS` and `FEE_ESCALATION_MAX_REPLACEMENTS`.
+    pub fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(format!("FEE_ESCALATION_{}", name)).ok();
+        let mut policy = Self::default();
+
+        if let Some(enabled) = var("ENABLED") {
+            policy.enabled = enabled.parse().context("Invalid FEE_ESCALATION_ENABLED")?;
+        }
+        if let Some(secs) = var("WINDOW_SECS") {
+            policy.window_secs = secs.parse().context("Invalid FEE_ESCALATION_WINDOW_SECS")?;
+        }
+        if let Some(pct) = var("MAX_MULTIPLIER_PCT") {
+            policy.max_multiplier_pct = pct
+                .parse::<u64>()
+                .context("Invalid FEE_ESCALATION_MAX_MULTIPLIER_PCT")?
+                .max(100);
+        }
+        if let Some(curve) = var("CURVE") {
+            policy.curve = curve.parse()?;
+        }
+        if let Some(bps) = var("PROFIT_SHARE_BPS") {
+            policy.profit_share_bps = bps
+                .parse::<u64>()
+                .context("Invalid FEE_ESCALATION_PROFIT_SHARE_BPS")?
+                .min(10_000);
+        }
+        if let Some(secs) = var("REPLACE_AFTER_SECS") {
+            policy.replace_after = Duration::from_secs(
+                secs.parse()
+                    .context("Invalid FEE_ESCALATION_REPLACE_AFTER_SECS")?,
+            );
+        }
+        if let Some(count) = var("MAX_REPLACEMENTS") {
+            policy.max_replacements = count
+                .parse()
+                .context("Invalid FEE_ESCALATION_MAX_REPLACEMENTS")?;
+        }
+
+        Ok(policy)
+    }
+
+    /// 0.0 outside the window, 1.0 at (or past) the deadline.
+    fn urgency(&self, remaining_secs: u64) -> f64 {
+        if !self.enabled || self.window_secs == 0 || remaining_secs >= self.window_secs {
+            return 0.0;
+        }
+
+        let elapsed = 1.0 - remaining_secs as f64 / self.window_secs as f64;
+        match self.curve {
+            EscalationCurve::Linear => elapsed,
+            EscalationCurve::Quadratic => elapsed * elapsed,
+            EscalationCurve::Step if elapsed >= 0.5 => 1.0,
+            EscalationCurve::Step => 0.0,
+        }
+    }
+
+    pub fn multiplier_pct(&self, remaining_secs: u64) -> u64 {
+        let headroom = self.max_multiplier_pct.saturating_sub(100) as f64;
+        100 + (headroom * self.urgency(remaining_secs)).round() as u64
+    }
+
+    /// Share of a fill's expected profit, in wei of the chain's gas token,
+    /// that escalation may spend on top of the base fee.
+    pub fn profit_budget(&self, profit_usd: f64, gas_token_usd: f64) -> U256 {
+        if profit_usd <= 0.0 || gas_token_usd <= 0.0 {
+            return U256::zero();
+        }
+
+        let budget = profit_usd * self.profit_share_bps as f64 / 10_000.0 / gas_token_usd;
+        U256::from((budget * 1e18) as u128)
+    }
+
+    fn escalated(
+        &self,
+        base: Fees,
+        gas: U256,
+        remaining_secs: u64,
+        budget: U256,
+        cap: U256,
+    ) -> Fees {
+        let bumped = base.priority_fee.unwrap_or(base.fee_per_gas);
+        let wanted = bumped.saturating_mul(U256::from(self.multiplier_pct(remaining_secs) - 100))
+            / U256::from(100);
+        let affordable = if gas.is_zero() {
+            U256::zero()
+        } else {
+            budget / gas
+        };
+
+        let fee_per_gas = base
+            .fee_per_gas
+            .saturating_add(wanted.min(affordable))
+            .min(cap)
+            .max(base.fee_per_gas);
+        let extra = fee_per_gas - base.fee_per_gas;
+
+        Fees {
+            fee_per_gas,
+            priority_fee: base
+                .priority_fee
+                .map(|priority| priority.saturating_add(extra).min(fee_per_gas)),
+        }
+    }
+
+    /// Raise the fees of a priced transaction for the time left before the
+    /// intent's deadline. Returns the multiplier that was applied.
+    pub fn escalate(
+        &self,
+        tx: &mut TypedTransaction,
+        remaining_secs: u64,
+        budget: U256,
+        cap: U256,
+    ) -> u64 {
+        let (Some(base), Some(gas)) = (Fees::read(tx), tx.gas().copied()) else {
+            return 100;
+        };
+
+        self.escalated(base, gas, remaining_secs, budget, cap)
+            .write(tx);
+        self.multiplier_pct(remaining_secs)
+    }
+
+    /// Same-nonce replacement for `previous`, escalated from the `original`
+    /// pricing and at least the minimum replacement bump above `previous`.
+    /// `None` when the bump would break the fee cap or the profit budget.
+    pub fn replacement(
+        &self,
+        original: &TypedTransaction,
+        previous: &TypedTransaction,
+        remaining_secs: u64,
+        budget: U256,
+        cap: U256,
+    ) -> Option<TypedTransaction> {
+        let base = Fees::read(original)?;
+        let last = Fees::read(previous)?;
+        let gas = *original.gas()?;
+
+        let target = self.escalated(base, gas, remaining_secs, budget, cap);
+        let bump =
+            |fee: U256| fee.saturating_mul(U256::from(REPLACEMENT_BUMP_PCT)) / U256::from(100);
+
+        let next = Fees {
+            fee_per_gas: target.fee_per_gas.max(bump(last.fee_per_gas)),
+            priority_fee: match (target.priority_fee, last.priority_fee) {
+                (Some(target), Some(last)) => Some(target.max(bump(last))),
+                (target, _) => target,
+            },
+        };
+
+        let extra_spend = (next.fee_per_gas - base.fee_per_gas).saturating_mul(gas);
+        if next.fee_per_gas > cap || extra_spend > budget {
+            return None;
+        }
+
+        let mut tx = previous.clone();
+        next.write(&mut tx);
+        Some(tx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::Eip1559TransactionRequest;
+
+    fn gwei(amount: u64) -> U256 {
+        U256::from(amount) * U256::exp10(9)
+    }
+
+    #[test]
+    fn test_escalation_is_bounded_and_replacements_bump() {
+        let policy = FeeEscalationPolicy {
+            curve: EscalationCurve::Linear,
+            ..Default::default()
+        };
+        assert_eq!(policy.multiplier_pct(900), 100);
+        assert_eq!(policy.multiplier_pct(300), 200);
+        assert_eq!(policy.multiplier_pct(0), 300);
+
+        let original: TypedTransaction = Eip1559TransactionRequest::new()
+            .gas(100_000)
+            .max_fee_per_gas(gwei(30))
+            .max_priority_fee_per_gas(gwei(2))
+            .into();
+        let budget = gwei(10) * U256::from(100_000);
+
+        // Far from the deadline nothing changes
+        let mut tx = original.clone();
+        policy.escalate(&mut tx, 3600, budget, gwei(50));
+        assert_eq!(tx, original);
+
+        // At the deadline the tip triples and the max fee rises with it
+        let mut urgent = original.clone();
+        policy.escalate(&mut urgent, 0, budget, gwei(50));
+        assert_eq!(Fees::read(&urgent).unwrap().priority_fee, Some(gwei(6)));
+        assert_eq!(Fees::read(&urgent).unwrap().fee_per_gas, gwei(34));
+
+        // A small profit budget limits the extra spend
+        let mut capped = original.clone();
+        policy.escalate(&mut capped, 0, gwei(1) * U256::from(100_000), gwei(50));
+        assert_eq!(Fees::read(&capped).unwrap().priority_fee, Some(gwei(3)));
+
+        // Replacements always clear the minimum bump over the last send
+        let next = policy
+            .replacement(&original, &urgent, 0, budget, gwei(50))
+            .unwrap();
+        let fees = Fees::read(&next).unwrap();
+        assert!(fees.fee_per_gas >= gwei(34) * U256::from(112) / U256::from(100));
+        assert!(fees.priority_fee.unwrap() >= gwei(6) * U256::from(112) / U256::from(100));
+
+        // ...unless that would break the fee cap
+        assert!(
+            policy
+                .replacement(&original, &urgent, 0, budget, gwei(35))
+                .is_none()
+        );
+    }
+}
@@ -1,30 +1,84 @@
 mod api;
+mod balance_history;
+mod capital_at_risk;
+mod capital_check;
+mod cli;
+mod drain;
+mod dry_run;
+mod execution_report;
+mod fee_escalation;
+mod metrics_snapshot;
 mod model;
-mod pricefeed;
+mod optimizer;
+mod partial_fills;
+mod processed_intents;
+mod rebalancer;
+mod registered_logs;
+mod reservations;
+mod rpc_selection;
+mod schema;
+mod settlement_fees;
 mod solver;
+mod state_store;
 
 use std::sync::Arc;
 
 use actix_cors::Cors;
-use actix_web::{App, HttpServer, http::header, middleware::Logger, web};
+use actix_web::{
+    App, HttpServer,
+    http::header,
+    middleware::{Compress, Condition, Logger},
+    web,
+};
 use anyhow::{Context, Result};
+use mantle_core::{
+    http_client::OutboundClient,
+    http_server::HttpServerOptions,
+    price_source::PriceSources,
+    pricefeed::{PriceFeedManager, PricePolicy},
+    route::{RouteMinimums, parse_route_amounts},
+    token_registry::TokenRegistry,
+};
 use tokio::signal;
 use tracing::{error, info, warn};
 
 use crate::api::config::configure_routes;
-use crate::{model::SolverConfig, solver::CrossChainSolver};
+use crate::{
+    balance_history::BalanceHistoryPolicy,
+    capital_at_risk::CapitalAtRiskPolicy,
+    dry_run::DryRunPolicy,
+    fee_escalation::FeeEscalationPolicy,
+    metrics_snapshot::MetricsSnapshotPolicy,
+    model::SolverConfig,
+    partial_fills::PartialFillPolicy,
+    processed_intents::ProcessedIntentPolicy,
+    rebalancer::RebalancePolicy,
+    registered_logs::RegisteredLogPolicy,
+    reservations::ReservationPolicy,
+    rpc_selection::{RpcEndpoint, RpcSelectionPolicy},
+    settlement_fees::SettlementFeePolicy,
+    solver::CrossChainSolver,
+    state_store::StateStorePolicy,
+};
 
 pub struct AppState {
     pub solver: Arc<CrossChainSolver>,
     pub start_time: std::time::Instant,
+    pub outbound_http: OutboundClient,
+    /// Bearer token for `/admin/*`; loopback callers only when unset.
+    pub admin_token: Option<String>,
 }
 
 fn load_config() -> Result<SolverConfig> {
     Ok(SolverConfig {
         ethereum_rpc: std::env::var("ETHEREUM_WS_RPC").context("ETHEREUM_WS_RPC not set")?,
         mantle_rpc: std::env::var("MANTLE_WS_RPC").context("MANTLE_WS_RPC not set")?,
-        solver_private_key: std::env::var("SOLVER_PRIVATE_KEY")
-            .context("SOLVER_PRIVATE_KEY not set")?,
+        solver_private_key: match std::env::var("SOLVER_PRIVATE_KEY") {
+            Ok(key) => key,
+            // Signing is delegated to the signing service, no key in this process
+            Err(_) if std::env::var("SIGNER_SOCKET").is_ok() => String::new(),
+            Err(_) => anyhow::bail!("SOLVER_PRIVATE_KEY not set"),
+        },
         ethereum_settlement: std::env::var("ETHEREUM_SETTLEMENT")
             .context("ETHEREUM_SETTLEMENT not set")?
             .parse()?,
@@ -37,9 +91,63 @@ fn load_config() -> Result<SolverConfig> {
         mantle_intent_pool: std::env::var("MANTLE_INTENT_POOL")
             .context("MANTLE_INTENT_POOL not set")?
             .parse()?,
+        ethereum_rpc_endpoints: match std::env::var("ETHEREUM_WS_RPC_ENDPOINTS") {
+            Ok(raw) => {
+                RpcEndpoint::parse_list(&raw).context("Invalid ETHEREUM_WS_RPC_ENDPOINTS")?
+            }
+            Err(_) => Vec::new(),
+        },
+        mantle_rpc_endpoints: match std::env::var("MANTLE_WS_RPC_ENDPOINTS") {
+            Ok(raw) => RpcEndpoint::parse_list(&raw).context("Invalid MANTLE_WS_RPC_ENDPOINTS")?,
+            Err(_) => Vec::new(),
+        },
+        rpc_selection: RpcSelectionPolicy::from_env().context("Invalid RPC selection policy")?,
         solver_address: std::env::var("SOLVER_ADDRESS")
             .context("SOLVER_ADDRESS not set")?
             .parse()?,
+        mempool_watch_enabled: std::env::var("MEMPOOL_WATCH_ENABLED")
+            .unwrap_or_else(|_| "false".to_string())
+            .parse()
+            .context("Invalid MEMPOOL_WATCH_ENABLED")?,
+        stale_price_margin_bps: std::env::var("STALE_PRICE_MARGIN_BPS")
+            .unwrap_or_else(|_| "50".to_string())
+            .parse()
+            .context("Invalid STALE_PRICE_MARGIN_BPS")?,
+        gap_fill_interval_secs: std::env::var("GAP_FILL_INTERVAL_SECS")
+            .unwrap_or_else(|_| "30".to_string())
+            .parse()
+            .context("Invalid GAP_FILL_INTERVAL_SECS")?,
+        optimizer_enabled: std::env::var("FILL_OPTIMIZER_ENABLED")
+            .unwrap_or_else(|_| "true".to_string())
+            .parse()
+            .context("Invalid FILL_OPTIMIZER_ENABLED")?,
+        execution_report_capacity: std::env::var("EXECUTION_REPORT_CAPACITY")
+            .unwrap_or_else(|_| "5000".to_string())
+            .parse()
+            .context("Invalid EXECUTION_REPORT_CAPACITY")?,
+        route_minimums: RouteMinimums::from_env(),
+        tokens: Arc::new(TokenRegistry::from_env().context("Invalid token registry")?),
+        route_typical_amounts: std::env::var("ROUTE_TYPICAL_AMOUNTS")
+            .map(|raw| parse_route_amounts(&raw))
+            .unwrap_or_default(),
+        fee_escalation: FeeEscalationPolicy::from_env().context("Invalid fee escalation policy")?,
+        balance_history: BalanceHistoryPolicy::from_env()
+            .context("Invalid balance history policy")?,
+        registered_logs: RegisteredLogPolicy::from_env()
+            .context("Invalid registered log policy")?,
+        metrics_snapshot: MetricsSnapshotPolicy::from_env()
+            .context("Invalid metrics snapshot policy")?,
+        processed_intents: ProcessedIntentPolicy::from_env()
+            .context("Invalid processed intent policy")?,
+        state_store: StateStorePolicy::from_env().context("Invalid solver state store policy")?,
+        capital_at_risk: CapitalAtRiskPolicy::from_env()
+            .context("Invalid capital at risk policy")?,
+        dry_run: DryRunPolicy::from_env().context("Invalid dry-run policy")?,
+        reservations: ReservationPolicy::from_env().context("Invalid reservation policy")?,
+        settlement_fees: SettlementFeePolicy::from_env()
+            .context("Invalid settlement fee policy")?,
+        partial_fills: PartialFillPolicy::from_env().context("Invalid partial fill policy")?,
+        rebalance: RebalancePolicy::from_env().context("Invalid rebalance policy")?,
         ..Default::default()
     })
 }
@@ -56,6 +164,8 @@ fn mask_url(url: &str) -> String {
 async fn main() -> Result<()> {
     dotenv::dotenv().ok();
 
+    let matches = cli::cli().get_matches();
+
     tracing_subscriber::fmt()
         .with_env_filter(
             tracing_subscriber::EnvFilter::try_from_default_env()
@@ -63,6 +173,14 @@ async fn main() -> Result<()> {
         )
         .init();
 
+    match matches.subcommand() {
+        Some(("config", _)) => cli::validate_config(),
+        Some(("export", args)) => cli::export(args),
+        _ => serve().await,
+    }
+}
+
+async fn serve() -> Result<()> {
     info!("🚀 Starting Private Bridge Solver v1.0.0");
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
@@ -72,18 +190,29 @@ async fn main() -> Result<()> {
     info!("📡 Network Configuration:");
     info!("   • Ethereum RPC: {}", mask_url(&config.ethereum_rpc));
     info!("   • Mantle RPC: {}", mask_url(&config.mantle_rpc));
+    info!(
+        "   • Extra RPC endpoints: {} Ethereum, {} Mantle (region: {})",
+        config.ethereum_rpc_endpoints.len(),
+        config.mantle_rpc_endpoints.len(),
+        config.rpc_selection.region.as_deref().unwrap_or("unset")
+    );
     info!("   • Solver Address: {:?}", config.solver_address);
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
     info!("💱 Initializing price feeds");
-    let price_feed = Arc::new(crate::pricefeed::PriceFeedManager::new());
+    let outbound_http = OutboundClient::from_env().context("Invalid outbound HTTP policy")?;
+    let price_feed = Arc::new(
+        PriceFeedManager::with_client(outbound_http.clone())
+            .with_policy(PricePolicy::from_env().context("Invalid price feed policy")?)
+            .with_sources(PriceSources::from_env().context("Invalid price sources")?),
+    );
     price_feed.init().await;
     info!("✅ Price feeds initialized");
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
 
     info!("🔧 Initializing solver");
     let solver = Arc::new(
-        CrossChainSolver::new(config.clone(), price_feed)
+        CrossChainSolver::new(config.clone(), price_feed, outbound_http.clone())
             .await
             .context("Failed to initialize solver")?,
     );
@@ -93,6 +222,10 @@ async fn main() -> Result<()> {
     info!("📊 Solver Configuration:");
     info!("   • Max concurrent fills: {}", config.max_concurrent_fills);
     info!("   • Min profit threshold: {} bps", config.min_profit_bps);
+    info!(
+        "   • Stale price margin: +{} bps",
+        config.stale_price_margin_bps
+    );
     info!(
         "   • Source confirmations: {}",
         config.source_confirmations_required
@@ -118,6 +251,10 @@ async fn main() -> Result<()> {
     let app_state = web::Data::new(AppState {
         solver: solver.clone(),
         start_time: std::time::Instant::now(),
+        outbound_http,
+        admin_token: std::env::var("SOLVER_ADMIN_TOKEN")
+            .ok()
+            .filter(|token| !token.is_empty()),
     });
 
     info!("🏃 Starting solver main loop");
@@ -135,9 +272,22 @@ async fn main() -> Result<()> {
         .unwrap_or_else(|_| "8000".to_string())
         .parse::<u16>()
         .context("Invalid HTTP_PORT")?;
+    let http_options = HttpServerOptions::from_env().context("Invalid HTTP server options")?;
+    let tls_config = http_options
+        .tls_config()
+        .context("Failed to load TLS certificate")?;
+    let scheme = http_options.scheme();
 
-    info!("🌐 Starting HTTP server on {}:{}", host, port);
+    info!(
+        "🌐 Starting HTTP server on {}://{}:{} (compression: {}, HTTP/2: {})",
+        scheme,
+        host,
+        port,
+        http_options.compression,
+        http_options.http2 || tls_config.is_some()
+    );
 
+    let compression = http_options.compression;
     let server = HttpServer::new(move || {
         let cors = Cors::default()
             .allowed_origin(
@@ -151,19 +301,25 @@ async fn main() -> Result<()> {
         App::new()
             .app_data(app_state.clone())
             .configure(configure_routes)
+            .wrap(Condition::new(compression, Compress::default()))
             .wrap(cors)
             .wrap(Logger::default())
-    })
-    .bind((host.as_str(), port))
+    });
+    let server = match tls_config {
+        Some(tls) => server.bind_rustls_0_23((host.as_str(), port), tls),
+        None if http_options.http2 => server.bind_auto_h2c((host.as_str(), port)),
+        None => server.bind((host.as_str(), port)),
+    }
     .context("Failed to bind HTTP server")?
     .run();
 
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     info!("🌐 HTTP Endpoints:");
-    info!("   • Health:    http://{}:{}/health", host, port);
-    info!("   • Metrics:   http://{}:{}/metrics", host, port);
-    info!("   • Status:    http://{}:{}/status", host, port);
-    info!("   • Readiness: http://{}:{}/ready", host, port);
+    info!("   • Health:    {}://{}:{}/health", scheme, host, port);
+    info!("   • Metrics:   {}://{}:{}/metrics", scheme, host, port);
+    info!("   • Status:    {}://{}:{}/status", scheme, host, port);
+    info!("   • Readiness: {}://{}:{}/ready", scheme, host, port);
+    info!("   • Drain:     {}://{}:{}/admin/drain", scheme, host, port);
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     info!("✅ All services started successfully");
     info!("👀 Monitoring for intents...");
@@ -183,6 +339,11 @@ async fn main() -> Result<()> {
         }
     }
 
+    // Save the final counts so the next process resumes without a dip
+    if let Err(e) = solver.save_metrics_snapshot().await {
+        warn!("⚠️ Failed to save metric counters on shutdown: {}", e);
+    }
+
     info!("━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━");
     info!("✅ Solver stopped gracefully");
     info!("👋 Goodbye!");
@@ -0,0 +1,223 @@
+use std::{fs, path::PathBuf};
+
+use anyhow::{Context, Result};
+use mantle_core::{
+    counters::{CounterSnapshot, PersistentCounters},
+    token::TokenType,
+};
+use tracing::{info, warn};
+
+use crate::model::SolverMetrics;
+
+/// Where the metric counters are saved so totals survive restarts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MetricsSnapshotPolicy {
+    /// JSON file rewritten on every snapshot; `None` counts from zero each start.
+    pub path: Option<PathBuf>,
+    pub interval_secs: u64,
+}
+
+impl Default for MetricsSnapshotPolicy {
+    fn default() -> Self {
+        Self {
+            path: Some(PathBuf::from("metrics_snapshot.json")),
+            interval_secs: 60,
+        }
+    }
+}
+
+impl MetricsSnapshotPolicy {
+    /// Override the defaults from `METRICS_SNAPSHOT_PATH` (empty disables the
+    /// file) and `METRICS_SNAPSHOT_INTERVAL_SECS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(path) = std::env::var("METRICS_SNAPSHOT_PATH") {
+            policy.path = (!path.trim().is_empty()).then(|| PathBuf::from(path.trim()));
+        }
+        if let Ok(secs) = std::env::var("METRICS_SNAPSHOT_INTERVAL_SECS") {
+            policy.interval_secs = secs
+                .parse::<u64>()
+                .context("Invalid METRICS_SNAPSHOT_INTERVAL_SECS")?
+                .max(5);
+        }
+
+        Ok(policy)
+    }
+
+    /// Read back the last snapshot. A missing or unreadable file starts the
+    /// counters from zero.
+    pub fn load(&self) -> Option<CounterSnapshot> {
+        let path = self.path.as_ref()?;
+
+        let contents = match fs::read_to_string(path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return None,
+            Err(e) => {
+                warn!(
+                    "⚠️ Failed to read metrics snapshot {}: {}",
+                    path.display(),
+                    e
+                );
+                return None;
+            }
+        };
+
+        match serde_json::from_str::<CounterSnapshot>(&contents) {
+            Ok(snapshot) => {
+                info!(
+                    "📈 Loaded metric counters from {} (saved at {}, counting since {})",
+                    path.display(),
+                    snapshot.saved_at,
+                    snapshot.since
+                );
+                Some(snapshot)
+            }
+            Err(e) => {
+                warn!(
+                    "⚠️ Ignoring unreadable metrics snapshot {}: {}",
+                    path.display(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    pub fn save(&self, snapshot: &CounterSnapshot) -> Result<()> {
+        let Some(path) = &self.path else {
+            return Ok(());
+        };
+
+        let tmp = path.with_extension("json.tmp");
+        fs::write(&tmp, serde_json::to_vec(snapshot)?)
+            .with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+}
+
+fn profit_key(token: TokenType) -> String {
+    format!("profit_{}", token.symbol().to_lowercase())
+}
+
+impl PersistentCounters for SolverMetrics {
+    fn counter_snapshot(&self, now: i64) -> CounterSnapshot {
+        let counters = [
+            ("total_intents_evaluated", self.total_intents_evaluated),
+            ("total_fills_attempted", self.total_fills_attempted),
+            ("successful_fills", self.successful_fills),
+            ("failed_fills", self.failed_fills),
+            ("competitor_fills_detected", self.competitor_fills_detected),
+            (
+                "fills_aborted_for_competitor",
+                self.fills_aborted_for_competitor,
+            ),
+            ("duplicate_deliveries", self.duplicate_deliveries),
+        ];
+
+        CounterSnapshot {
+            since: self.counters_since,
+            saved_at: now,
+            counters: counters
+                .into_iter()
+                .map(|(name, counter)| (name.to_string(), counter.get()))
+                .collect(),
+            amounts: self
+                .total_profit_earned
+                .iter()
+                .map(|(token, profit)| (profit_key(*token), *profit))
+                .collect(),
+        }
+    }
+
+    fn restore_counters(&mut self, snapshot: &CounterSnapshot) {
+        let counters = [
+            ("total_intents_evaluated", &mut self.total_intents_evaluated),
+            ("total_fills_attempted", &mut self.total_fills_attempted),
+            ("successful_fills", &mut self.successful_fills),
+            ("failed_fills", &mut self.failed_fills),
+            (
+                "competitor_fills_detected",
+                &mut self.competitor_fills_detected,
+            ),
+            (
+                "fills_aborted_for_competitor",
+                &mut self.fills_aborted_for_competitor,
+            ),
+            ("duplicate_deliveries", &mut self.duplicate_deliveries),
+        ];
+        for (name, counter) in counters {
+            counter.restore(snapshot.counter(name));
+        }
+
+        for token in TokenType::ALL {
+            let saved = snapshot.amount(&profit_key(token));
+            if saved.is_zero() {
+                continue;
+            }
+            let profit = self.total_profit_earned.entry(token).or_default();
+            *profit = profit.saturating_add(saved);
+        }
+
+        if snapshot.since > 0 {
+            self.counters_since = self.counters_since.min(snapshot.since);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::U256;
+
+    #[test]
+    fn test_metrics_snapshot_round_trip() {
+        let path = std::env::temp_dir().join(format!(
+            "solver-metrics-snapshot-{}.json",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let policy = MetricsSnapshotPolicy {
+            path: Some(path.clone()),
+            interval_secs: 60,
+        };
+        assert!(policy.load().is_none());
+
+        let mut before = SolverMetrics {
+            counters_since: 1_700_000_000,
+            ..Default::default()
+        };
+        before.successful_fills += 12;
+        before.duplicate_deliveries += 2;
+        before
+            .total_profit_earned
+            .insert(TokenType::USDC, U256::from(4_200_000u64));
+        policy
+            .save(&before.counter_snapshot(1_700_086_400))
+            .unwrap();
+
+        let mut after = SolverMetrics {
+            process_start_time: 1_700_090_000,
+            counters_since: 1_700_090_000,
+            ..Default::default()
+        };
+        after.successful_fills.inc();
+        after.restore_counters(&policy.load().unwrap());
+
+        assert_eq!(after.successful_fills.get(), 13);
+        assert_eq!(after.successful_fills.session(), 1);
+        assert_eq!(after.duplicate_deliveries.get(), 2);
+        assert_eq!(after.failed_fills.get(), 0);
+        assert_eq!(
+            after.total_profit_earned[&TokenType::USDC],
+            U256::from(4_200_000u64)
+        );
+        assert_eq!(after.counters_since, 1_700_000_000);
+        assert_eq!(after.process_start_time, 1_700_090_000);
+
+        // A corrupt file is ignored rather than failing startup
+        fs::write(&path, "{not json").unwrap();
+        assert!(policy.load().is_none());
+        let _ = fs::remove_file(&path);
+    }
+}
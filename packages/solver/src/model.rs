@@ -1,26 +1,39 @@
-use std::collections::HashMap;
+use std::{collections::HashMap, sync::Arc};
 
 use ethers::types::{Address, H256, U256};
-use serde::{Deserialize, Serialize};
+use mantle_core::{
+    chain::Chain, counters::MonotonicCounter, http_client::HostMetrics, pricefeed::SourceHealth,
+    route::RouteMinimums, token::TokenType, token_registry::TokenRegistry,
+};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-pub enum SupportedToken {
-    ETH,
-    USDC,
-    USDT,
-    WETH,
-    MNT,
-}
+use crate::{
+    balance_history::BalanceHistoryPolicy,
+    capital_at_risk::{CapitalAtRisk, CapitalAtRiskPolicy},
+    dry_run::{DryRunPolicy, DryRunStats},
+    fee_escalation::FeeEscalationPolicy,
+    metrics_snapshot::MetricsSnapshotPolicy,
+    partial_fills::PartialFillPolicy,
+    processed_intents::{ProcessedIntentPolicy, ProcessedIntentStats},
+    rebalancer::{RebalancePolicy, RebalanceStats},
+    registered_logs::RegisteredLogPolicy,
+    reservations::ReservationPolicy,
+    rpc_selection::{RpcEndpoint, RpcSelectionPolicy},
+    settlement_fees::SettlementFeePolicy,
+    state_store::StateStorePolicy,
+};
+use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone)]
 pub struct SolverConfig {
     // Capital Management per token
-    pub max_capital_per_fill: HashMap<SupportedToken, U256>,
-    pub min_capital_reserve: HashMap<SupportedToken, U256>,
+    pub max_capital_per_fill: HashMap<TokenType, U256>,
+    pub min_capital_reserve: HashMap<TokenType, U256>,
     pub max_concurrent_fills: usize,
 
     // Risk Parameters
     pub min_profit_bps: u16,
+    // Added to min_profit_bps while an opportunity was priced from stale prices
+    pub stale_price_margin_bps: u16,
     pub source_confirmations_required: u64,
     pub max_intent_age_secs: u64,
 
@@ -32,6 +45,12 @@ pub struct SolverConfig {
     pub ethereum_intent_pool: Address,
     pub mantle_intent_pool: Address,
 
+    // Extra WS endpoints tagged with region and RTT target; the log subscription
+    // follows the fastest healthy one, backfill uses another
+    pub ethereum_rpc_endpoints: Vec<RpcEndpoint>,
+    pub mantle_rpc_endpoints: Vec<RpcEndpoint>,
+    pub rpc_selection: RpcSelectionPolicy,
+
     // Chain IDs
     pub ethereum_chain_id: u64,
     pub mantle_chain_id: u64,
@@ -47,6 +66,63 @@ pub struct SolverConfig {
     // Monitoring
     pub health_check_interval_secs: u64,
     pub balance_check_interval_secs: u64,
+
+    // Watch pending transactions for competitor fills (needs RPC support)
+    pub mempool_watch_enabled: bool,
+
+    // Safety-net get_logs poll behind the WS log subscriptions
+    pub gap_fill_interval_secs: u64,
+
+    // Queue approved opportunities and fill the most profitable subset
+    pub optimizer_enabled: bool,
+
+    // Per-intent execution reports kept in memory for GET /fills/{intent_id}
+    pub execution_report_capacity: usize,
+
+    // Intents below the per-route minimum are skipped before any RPC work
+    pub route_minimums: RouteMinimums,
+
+    // Listed tokens from the network profile; shared so admin changes reach every task
+    pub tokens: Arc<TokenRegistry>,
+
+    // Usual intent size per (token, fill chain), checked against inventory on each balance refresh
+    pub route_typical_amounts: HashMap<(TokenType, Chain), U256>,
+
+    // Priority fees rise as an intent's deadline nears, bounded by profit and max_gas_price_gwei
+    pub fee_escalation: FeeEscalationPolicy,
+
+    // Periodic per-(token, chain) balance snapshots for GET /balances/history
+    pub balance_history: BalanceHistoryPolicy,
+
+    // Handled IntentRegistered logs, persisted so redeliveries are skipped across restarts
+    pub registered_logs: RegisteredLogPolicy,
+
+    // Metric counters saved periodically and on shutdown, reloaded on startup
+    pub metrics_snapshot: MetricsSnapshotPolicy,
+
+    // Intents already picked up, kept until their deadline plus a grace period
+    pub processed_intents: ProcessedIntentPolicy,
+
+    // Postgres copy of active fills and processed intents, reconciled on startup
+    pub state_store: StateStorePolicy,
+
+    // New fills pause while landed, unrepaid fills exceed this much USD
+    pub capital_at_risk: CapitalAtRiskPolicy,
+
+    // Decisions are recorded instead of sending fills, for tuning against live traffic
+    pub dry_run: DryRunPolicy,
+
+    // Relayer endpoint for reserving intents before filling; unset fills first-come on chain
+    pub reservations: ReservationPolicy,
+
+    // Settlement contract fee per chain, read at startup and refreshed; priced into every fill
+    pub settlement_fees: SettlementFeePolicy,
+
+    // Intents above max_capital_per_fill are filled in chunks, if the contract accepts them
+    pub partial_fills: PartialFillPolicy,
+
+    // Per-chain inventory targets; a low chain is topped up through the bridge or a webhook
+    pub rebalance: RebalancePolicy,
 }
 
 #[derive(Debug, Clone)]
@@ -54,12 +130,13 @@ pub struct DetectedIntent {
     pub intent_id: H256,
     pub commitment: H256,
     pub token: Address,
-    pub token_type: SupportedToken,
+    pub token_type: TokenType,
     pub amount: U256,
     pub source_chain: u32,
     pub dest_chain: u32,
     pub source_block: u64,
     pub detected_at: u64,
+    pub deadline: u64,
 }
 
 #[derive(Debug, Clone)]
@@ -67,16 +144,23 @@ pub struct FillOpportunity {
     pub intent: DetectedIntent,
     pub estimated_profit: U256,
     pub profit_bps: u16,
+    pub profit_usd: f64,
     pub risk_score: u8,
     pub capital_required: U256,
     pub gas_estimate: U256,
+    /// Priced from a last known good price because the feed is failing.
+    pub stale_prices: bool,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum FillStatus {
     Pending,
     Confirmed,
+    /// Final on the destination chain; waiting for the relayer to repay us
+    /// on the source chain.
     Claimed,
+    /// Repaid on the source chain.
+    Settled,
     Failed,
 }
 
@@ -84,32 +168,53 @@ pub enum FillStatus {
 #[derive(Debug, Clone)]
 pub struct ActiveFill {
     pub intent_id: H256,
+    /// The latest fill tx; with partial fills, the latest chunk's.
     pub tx_hash: H256,
+    /// The intent's full amount.
     pub amount: U256,
+    /// Sent so far, below `amount` while a partial fill is in progress.
+    pub filled_amount: U256,
+    /// Fill transactions that landed or are in flight.
+    pub chunks: u32,
     pub token: Address,
-    pub token_type: SupportedToken,
+    pub token_type: TokenType,
     pub filled_at: u64,
     pub confirmed_at: Option<u64>,
     pub status: FillStatus,
     pub dest_chain: u32,
 }
 
+impl ActiveFill {
+    /// Every chunk of the intent has been sent.
+    pub fn is_complete(&self) -> bool {
+        self.filled_amount >= self.amount
+    }
+}
+
 // ============================================================================
 // SOLVER METRICS
 // ============================================================================
 
 #[derive(Debug, Clone, Default)]
 pub struct SolverMetrics {
-    pub total_intents_evaluated: u64,
-    pub total_fills_attempted: u64,
-    pub successful_fills: u64,
-    pub failed_fills: u64,
-    pub total_profit_earned: HashMap<SupportedToken, U256>,
-    pub capital_deployed: HashMap<SupportedToken, U256>,
-    pub capital_available: HashMap<(SupportedToken, u64), U256>,
+    pub total_intents_evaluated: MonotonicCounter,
+    pub total_fills_attempted: MonotonicCounter,
+    pub successful_fills: MonotonicCounter,
+    pub failed_fills: MonotonicCounter,
+    pub total_profit_earned: HashMap<TokenType, U256>,
+    pub capital_deployed: HashMap<TokenType, U256>,
+    pub capital_available: HashMap<(TokenType, u64), U256>,
     pub active_fills_count: usize,
     pub average_fill_time_secs: f64,
+    pub competitor_fills_detected: MonotonicCounter,
+    pub fills_aborted_for_competitor: MonotonicCounter,
+    pub pending_opportunities: usize,
+    pub duplicate_deliveries: MonotonicCounter,
     pub last_error: Option<String>,
+    /// Unix seconds this process started.
+    pub process_start_time: i64,
+    /// Unix seconds of the first start the restored counters cover.
+    pub counters_since: i64,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -123,5 +228,35 @@ pub struct MetricsResponse {
     pub capital_deployed: HashMap<String, String>,
     pub capital_available: HashMap<String, String>,
     pub total_profit_earned: HashMap<String, String>,
+    pub competitor_fills_detected: u64,
+    pub fills_aborted_for_competitor: u64,
+    pub pending_opportunities: usize,
+    /// `IntentRegistered` logs delivered again after being handled.
+    #[serde(default)]
+    pub duplicate_deliveries: u64,
     pub last_error: Option<String>,
+    /// Price provider calls, per host.
+    #[serde(default)]
+    pub outbound_http: Vec<HostMetrics>,
+    /// Each price source's last good price and failures, per token.
+    #[serde(default)]
+    pub price_sources: Vec<SourceHealth>,
+    /// Intents held back from re-processing, with eviction counts.
+    #[serde(default)]
+    pub processed_intents: ProcessedIntentStats,
+    /// Landed fills not yet repaid on the source chain, in USD.
+    #[serde(default)]
+    pub capital_at_risk: CapitalAtRisk,
+    /// Would-fill and skip totals; `None` unless `DRY_RUN` is set.
+    #[serde(default)]
+    pub dry_run: Option<DryRunStats>,
+    /// Inventory transfers between chains; `None` unless `REBALANCE_ENABLED` is set.
+    #[serde(default)]
+    pub rebalance: Option<RebalanceStats>,
+    /// Unix seconds this process started; counters above include earlier runs.
+    #[serde(default)]
+    pub process_start_time: i64,
+    /// Unix seconds of the first start the counters cover.
+    #[serde(default)]
+    pub counters_since: i64,
 }
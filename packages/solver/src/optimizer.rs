@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use ethers::types::{H256, U256};
+use mantle_core::token::TokenType;
+
+use crate::model::FillOpportunity;
+
+/// Same 5% headroom `should_fill` requires on top of the fill amount.
+const SAFETY_MARGIN_PCT: u64 = 105;
+
+/// Capital bucket a fill draws from: token on the chain it is filled on.
+pub type CapitalKey = (TokenType, u32);
+
+pub fn capital_key(opportunity: &FillOpportunity) -> CapitalKey {
+    (opportunity.intent.token_type, opportunity.intent.dest_chain)
+}
+
+pub fn capital_with_margin(opportunity: &FillOpportunity) -> U256 {
+    with_margin(opportunity.capital_required)
+}
+
+pub fn with_margin(amount: U256) -> U256 {
+    amount.saturating_mul(U256::from(SAFETY_MARGIN_PCT)) / U256::from(100)
+}
+
+/// Profit in USD discounted by the opportunity's risk score.
+pub fn expected_profit_usd(opportunity: &FillOpportunity) -> f64 {
+    let risk = opportunity.risk_score.min(100) as f64;
+    opportunity.profit_usd.max(0.0) * (100.0 - risk) / 100.0
+}
+
+/// Pick the subset of `candidates` with the highest expected profit that fits
+/// in `slots` concurrent fills and the `available` capital per bucket.
+///
+/// Runs two greedy passes (by absolute profit, since every fill costs one slot,
+/// and by profit per share of bucket capital) and keeps the better plan.
+pub fn plan_fills(
+    candidates: &[FillOpportunity],
+    available: &HashMap<CapitalKey, U256>,
+    slots: usize,
+) -> Vec<H256> {
+    if slots == 0 || candidates.is_empty() {
+        return Vec::new();
+    }
+
+    let mut by_profit: Vec<&FillOpportunity> = candidates.iter().collect();
+    by_profit.sort_by(|a, b| {
+        expected_profit_usd(b)
+            .total_cmp(&expected_profit_usd(a))
+            .then(a.intent.detected_at.cmp(&b.intent.detected_at))
+    });
+
+    let density = |opportunity: &FillOpportunity| {
+        let bucket = available
+            .get(&capital_key(opportunity))
+            .copied()
+            .unwrap_or_default();
+        let needed = capital_with_margin(opportunity);
+        if needed.is_zero() || needed > bucket {
+            return 0.0;
+        }
+        expected_profit_usd(opportunity) / u256_ratio(needed, bucket).max(f64::MIN_POSITIVE)
+    };
+
+    let mut by_density = by_profit.clone();
+    by_density.sort_by(|a, b| {
+        density(b)
+            .total_cmp(&density(a))
+            .then(a.intent.detected_at.cmp(&b.intent.detected_at))
+    });
+
+    let (profit_plan, profit_total) = greedy(&by_profit, available, slots);
+    let (density_plan, density_total) = greedy(&by_density, available, slots);
+
+    if density_total > profit_total {
+        density_plan
+    } else {
+        profit_plan
+    }
+}
+
+fn greedy(
+    ordered: &[&FillOpportunity],
+    available: &HashMap<CapitalKey, U256>,
+    slots: usize,
+) -> (Vec<H256>, f64) {
+    let mut remaining = available.clone();
+    let mut plan = Vec::new();
+    let mut total = 0.0;
+
+    for opportunity in ordered {
+        if plan.len() >= slots {
+            break;
+        }
+
+        let needed = capital_with_margin(opportunity);
+        let Some(bucket) = remaining.get_mut(&capital_key(opportunity)) else {
+            continue;
+        };
+
+        if *bucket < needed {
+            continue;
+        }
+
+        *bucket -= opportunity.capital_required;
+        plan.push(opportunity.intent.intent_id);
+        total += expected_profit_usd(opportunity);
+    }
+
+    (plan, total)
+}
+
+/// `numerator / denominator` for `numerator <= denominator`.
+fn u256_ratio(numerator: U256, denominator: U256) -> f64 {
+    // Scale down together so both fit in f64 without losing the ratio.
+    let shift = denominator.bits().saturating_sub(52);
+    let numerator = (numerator >> shift).as_u128() as f64;
+    let denominator = (denominator >> shift).as_u128().max(1) as f64;
+    numerator / denominator
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::DetectedIntent;
+    use ethers::types::Address;
+
+    fn opportunity(id: u8, amount: u64, profit_usd: f64, detected_at: u64) -> FillOpportunity {
+        FillOpportunity {
+            intent: DetectedIntent {
+                intent_id: H256::repeat_byte(id),
+                commitment: H256::zero(),
+                token: Address::zero(),
+                token_type: TokenType::USDC,
+                amount: U256::from(amount),
+                source_chain: 11155111,
+                dest_chain: 5003,
+                source_block: 0,
+                detected_at,
+                deadline: u64::MAX,
+            },
+            estimated_profit: U256::zero(),
+            profit_bps: 0,
+            profit_usd,
+            risk_score: 0,
+            capital_required: U256::from(amount),
+            gas_estimate: U256::zero(),
+            stale_prices: false,
+        }
+    }
+
+    fn capital(amount: u64) -> HashMap<CapitalKey, U256> {
+        HashMap::from([((TokenType::USDC, 5003), U256::from(amount))])
+    }
+
+    #[test]
+    fn test_prefers_profit_over_arrival_order() {
+        // First-come-first-served would fill #1 and have no capital left for #2 and #3.
+        let candidates = vec![
+            opportunity(1, 900, 5.0, 1),
+            opportunity(2, 400, 4.0, 2),
+            opportunity(3, 400, 4.0, 3),
+        ];
+
+        let plan = plan_fills(&candidates, &capital(1000), 10);
+
+        assert_eq!(plan, vec![H256::repeat_byte(2), H256::repeat_byte(3)]);
+    }
+
+    #[test]
+    fn test_respects_slots_and_capital() {
+        let candidates = vec![
+            opportunity(1, 100, 1.0, 1),
+            opportunity(2, 100, 3.0, 2),
+            opportunity(3, 100, 2.0, 3),
+            opportunity(4, 5000, 50.0, 4),
+        ];
+
+        let plan = plan_fills(&candidates, &capital(1000), 2);
+
+        assert_eq!(plan, vec![H256::repeat_byte(2), H256::repeat_byte(3)]);
+        assert!(plan_fills(&candidates, &capital(1000), 0).is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+use anyhow::{Context, Result, bail};
+use ethers::types::{U256, U512};
+use mantle_core::amount::to_decimal;
+
+use crate::model::FillOpportunity;
+
+/// Splitting intents above `max_capital_per_fill` into several fills.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialFillPolicy {
+    /// Only for settlement contracts that accept a fill below the intent
+    /// amount; the stock `PrivateSettlement` reverts with `AmountMismatch`.
+    pub enabled: bool,
+    /// Intents that would need more fills than this are still skipped.
+    pub max_chunks: usize,
+}
+
+impl Default for PartialFillPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_chunks: 4,
+        }
+    }
+}
+
+impl PartialFillPolicy {
+    /// Override the defaults from `PARTIAL_FILLS_ENABLED` and
+    /// `PARTIAL_FILL_MAX_CHUNKS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(enabled) = std::env::var("PARTIAL_FILLS_ENABLED") {
+            policy.enabled = enabled.parse().context("Invalid PARTIAL_FILLS_ENABLED")?;
+        }
+        if let Ok(max) = std::env::var("PARTIAL_FILL_MAX_CHUNKS") {
+            policy.max_chunks = max.parse().context("Invalid PARTIAL_FILL_MAX_CHUNKS")?;
+        }
+
+        if policy.max_chunks < 2 {
+            bail!("PARTIAL_FILL_MAX_CHUNKS must be at least 2");
+        }
+
+        Ok(policy)
+    }
+
+    /// Near-equal chunks of `amount`, none above `max_per_fill`. `None` when
+    /// the amount fits one fill, partial fills are off, or it would take
+    /// more than `max_chunks` fills.
+    pub fn chunks(&self, amount: U256, max_per_fill: U256) -> Option<Vec<U256>> {
+        if !self.enabled || max_per_fill.is_zero() || amount <= max_per_fill {
+            return None;
+        }
+
+        let count = amount.saturating_add(max_per_fill - 1) / max_per_fill;
+        if count > U256::from(self.max_chunks) {
+            return None;
+        }
+
+        let count = count.as_usize();
+        let base = amount / count;
+        let remainder = (amount % count).as_usize();
+        Some(
+            (0..count)
+                .map(|i| if i < remainder { base + 1 } else { base })
+                .collect(),
+        )
+    }
+}
+
+/// `value` scaled by `part / whole`, e.g. one chunk's share of the profit.
+pub fn pro_rata(value: U256, part: U256, whole: U256) -> U256 {
+    if whole.is_zero() {
+        return U256::zero();
+    }
+    U256::try_from(value.full_mul(part) / U512::from(whole)).unwrap_or(value)
+}
+
+impl FillOpportunity {
+    /// The share of this opportunity one chunk of `amount` carries. The
+    /// intent stays whole, so the tracked fill knows the full amount.
+    pub fn chunk(&self, amount: U256) -> Self {
+        let whole = self.intent.amount;
+        let fraction = if whole.is_zero() {
+            0.0
+        } else {
+            to_decimal(amount, 0) / to_decimal(whole, 0)
+        };
+
+        Self {
+            estimated_profit: pro_rata(self.estimated_profit, amount, whole),
+            profit_usd: self.profit_usd * fraction,
+            capital_required: pro_rata(self.capital_required, amount, whole),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> PartialFillPolicy {
+        PartialFillPolicy {
+            enabled: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_chunks_split_evenly_below_the_cap() {
+        let chunks = enabled().chunks(U256::from(1001), U256::from(400)).unwrap();
+        assert_eq!(chunks, vec![334.into(), 334.into(), 333.into()]);
+
+        // Fits one fill, too many fills, or switched off
+        assert!(enabled().chunks(U256::from(400), U256::from(400)).is_none());
+        assert!(
+            enabled()
+                .chunks(U256::from(1601), U256::from(400))
+                .is_none()
+        );
+        assert!(
+            PartialFillPolicy::default()
+                .chunks(U256::from(1001), U256::from(400))
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_pro_rata_shares_sum_to_at_most_the_whole() {
+        let whole = U256::from(1001);
+        let profit = U256::from(97);
+        let shares: U256 = enabled()
+            .chunks(whole, U256::from(400))
+            .unwrap()
+            .into_iter()
+            .map(|chunk| pro_rata(profit, chunk, whole))
+            .fold(U256::zero(), |acc, share| acc + share);
+
+        assert!(shares <= profit && shares + 3 > profit);
+        assert_eq!(pro_rata(profit, whole, U256::zero()), U256::zero());
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use ethers::types::H256;
+use serde::{Deserialize, Serialize};
+
+/// How long intents stay in the in-flight/handled set.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProcessedIntentPolicy {
+    /// Kept this long past the intent deadline, after which no fill can land.
+    pub grace_secs: u64,
+    /// Hard cap; the entries closest to expiry are evicted first.
+    pub max_entries: usize,
+}
+
+impl Default for ProcessedIntentPolicy {
+    fn default() -> Self {
+        Self {
+            grace_secs: 3600,
+            max_entries: 100_000,
+        }
+    }
+}
+
+impl ProcessedIntentPolicy {
+    /// Override the defaults from `PROCESSED_INTENT_GRACE_SECS` and
+    /// `PROCESSED_INTENT_MAX_ENTRIES`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(secs) = std::env::var("PROCESSED_INTENT_GRACE_SECS") {
+            policy.grace_secs = secs
+                .parse()
+                .context("Invalid PROCESSED_INTENT_GRACE_SECS")?;
+        }
+        if let Ok(max) = std::env::var("PROCESSED_INTENT_MAX_ENTRIES") {
+            policy.max_entries = max
+                .parse::<usize>()
+                .context("Invalid PROCESSED_INTENT_MAX_ENTRIES")?
+                .max(1);
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Size and eviction counts for `GET /metrics`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProcessedIntentStats {
+    pub size: usize,
+    /// Dropped once their deadline plus grace had passed.
+    pub expired: u64,
+    /// Dropped early to stay under `max_entries`.
+    pub evicted: u64,
+}
+
+/// Intents being handled or already handled, so concurrent and repeated
+/// deliveries are skipped. Each entry expires after its intent's deadline.
+#[derive(Debug)]
+pub struct ProcessedIntents {
+    policy: ProcessedIntentPolicy,
+    expires_at: HashMap<H256, u64>,
+    stats: ProcessedIntentStats,
+}
+
+impl ProcessedIntents {
+    pub fn new(policy: ProcessedIntentPolicy) -> Self {
+        Self {
+            policy,
+            expires_at: HashMap::new(),
+            stats: ProcessedIntentStats::default(),
+        }
+    }
+
+    /// Mark the intent as taken. `false` when it already is and has not expired.
+    pub fn try_claim(&mut self, intent_id: H256, deadline: u64, now: u64) -> bool {
+        if self
+            .expires_at
+            .get(&intent_id)
+            .is_some_and(|expires_at| *expires_at > now)
+        {
+            return false;
+        }
+
+        if self.expires_at.len() >= self.policy.max_entries {
+            self.evict_expired(now);
+        }
+        if self.expires_at.len() >= self.policy.max_entries {
+            self.evict_soonest(self.expires_at.len() + 1 - self.policy.max_entries);
+        }
+
+        let expires_at = deadline.max(now).saturating_add(self.policy.grace_secs);
+        self.expires_at.insert(intent_id, expires_at);
+        true
+    }
+
+    /// When a held intent's entry lapses, for persisting the claim.
+    pub fn expires_at(&self, intent_id: &H256) -> Option<u64> {
+        self.expires_at.get(intent_id).copied()
+    }
+
+    /// Put back a claim persisted before a restart.
+    pub fn restore(&mut self, intent_id: H256, expires_at: u64) {
+        self.expires_at.insert(intent_id, expires_at);
+    }
+
+    /// Let the intent be picked up again, e.g. after a failed attempt.
+    pub fn release(&mut self, intent_id: &H256) {
+        self.expires_at.remove(intent_id);
+    }
+
+    /// Drop entries past their expiry; returns how many went.
+    pub fn evict_expired(&mut self, now: u64) -> usize {
+        let before = self.expires_at.len();
+        self.expires_at.retain(|_, expires_at| *expires_at > now);
+        let expired = before - self.expires_at.len();
+        self.stats.expired += expired as u64;
+        expired
+    }
+
+    fn evict_soonest(&mut self, count: usize) {
+        let mut by_expiry: Vec<(u64, H256)> = self
+            .expires_at
+            .iter()
+            .map(|(intent_id, expires_at)| (*expires_at, *intent_id))
+            .collect();
+        by_expiry.sort_unstable();
+
+        for (_, intent_id) in by_expiry.into_iter().take(count) {
+            self.expires_at.remove(&intent_id);
+        }
+        self.stats.evicted += count as u64;
+    }
+
+    pub fn stats(&self) -> ProcessedIntentStats {
+        ProcessedIntentStats {
+            size: self.expires_at.len(),
+            ..self.stats.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_processed_intents_expire_after_deadline() {
+        let mut processed = ProcessedIntents::new(ProcessedIntentPolicy {
+            grace_secs: 100,
+            max_entries: 2,
+        });
+        let (a, b, c) = (
+            H256::repeat_byte(1),
+            H256::repeat_byte(2),
+            H256::repeat_byte(3),
+        );
+
+        assert!(processed.try_claim(a, 1_000, 0));
+        assert!(!processed.try_claim(a, 1_000, 500));
+        assert!(processed.try_claim(b, 2_000, 500));
+
+        // Released intents can be retried straight away
+        processed.release(&b);
+        assert!(processed.try_claim(b, 2_000, 600));
+
+        // Past deadline + grace the entry no longer blocks and is swept
+        assert!(processed.try_claim(a, 1_000, 1_101));
+        assert_eq!(processed.evict_expired(1_200), 0);
+        assert_eq!(processed.evict_expired(1_201), 1);
+        assert_eq!(processed.stats().size, 1);
+
+        // At capacity the entry closest to expiry goes first
+        assert!(processed.try_claim(c, 5_000, 1_300));
+        assert!(processed.try_claim(a, 9_000, 1_300));
+        assert!(processed.try_claim(b, 2_000, 1_400));
+        assert!(!processed.try_claim(a, 9_000, 1_400));
+        assert_eq!(
+            processed.stats(),
+            ProcessedIntentStats {
+                size: 2,
+                expired: 2,
+                evicted: 2,
+            }
+        );
+    }
+}
@@ -0,0 +1,839 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+use anyhow::{Context, Result, anyhow, bail};
+use ethers::{
+    types::{Address, H256, U256},
+    utils::{hex, keccak256},
+};
+use hmac::{Hmac, Mac};
+use mantle_core::{chain::Chain, http_client::OutboundClient, token::TokenType};
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+use sha2::Sha256;
+
+const DAY_SECS: u64 = 86_400;
+const BPS: u64 = 10_000;
+/// Wait after a transfer failed to start before trying the token again.
+const FAILED_START_BACKOFF_SECS: u64 = 15 * 60;
+
+/// How inventory is moved to the chain that runs low.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RebalanceMethod {
+    /// An intent through this bridge, filled by another solver and claimed
+    /// to us by the relayer.
+    Bridge,
+    /// A request to `REBALANCE_WEBHOOK_URL`, for a CEX or OTC desk to carry out.
+    Webhook,
+}
+
+impl RebalanceMethod {
+    fn parse(value: &str) -> Result<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "bridge" => Ok(Self::Bridge),
+            "webhook" => Ok(Self::Webhook),
+            other => bail!(
+                "Unknown REBALANCE_METHOD '{}' (use bridge or webhook)",
+                other
+            ),
+        }
+    }
+}
+
+/// Keeping each token's inventory split between the chains near a target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebalancePolicy {
+    pub enabled: bool,
+    /// Target share of each token's inventory per chain, in bps. Tokens not
+    /// listed are left alone.
+    pub targets: HashMap<(TokenType, Chain), u64>,
+    /// A chain is low once its share is this far below its target.
+    pub trigger_bps: u64,
+    /// A transfer is done once the low chain is back within this distance
+    /// of its target. Until then, or until it times out, the token gets no
+    /// other transfer.
+    pub rearm_bps: u64,
+    /// Transfers started per token in any 24 hours, failed ones included.
+    pub max_per_day: usize,
+    pub transfer_timeout_secs: u64,
+    pub method: RebalanceMethod,
+    pub webhook_url: Option<String>,
+    pub webhook_token: Option<String>,
+    /// The relayer's `/api/v1` base URL, for `bridge`.
+    pub relayer_url: Option<String>,
+    /// The relayer's `HMAC_SECRET`; `/bridge/initiate` takes signed requests.
+    pub relayer_hmac_secret: Option<String>,
+    /// Sent as `X-API-Key` when the relayer requires client keys.
+    pub relayer_api_key: Option<String>,
+    /// The relayer's secp256k1 key the intent secrets are encrypted to.
+    pub relayer_public_key: Option<Vec<u8>>,
+}
+
+impl Default for RebalancePolicy {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            targets: HashMap::new(),
+            trigger_bps: 1500,
+            rearm_bps: 500,
+            max_per_day: 4,
+            transfer_timeout_secs: 3 * 3600,
+            method: RebalanceMethod::Bridge,
+            webhook_url: None,
+            webhook_token: None,
+            relayer_url: None,
+            relayer_hmac_secret: None,
+            relayer_api_key: None,
+            relayer_public_key: None,
+        }
+    }
+}
+
+impl RebalancePolicy {
+    /// Override the defaults from `REBALANCE_ENABLED`, `REBALANCE_TARGETS`,
+    /// `REBALANCE_TRIGGER_BPS`, `REBALANCE_REARM_BPS`, `REBALANCE_MAX_PER_DAY`,
+    /// `REBALANCE_TRANSFER_TIMEOUT_SECS`, `REBALANCE_METHOD` and the
+    /// `REBALANCE_WEBHOOK_*` or `REBALANCE_RELAYER_*` settings of the method.
+    pub fn from_env() -> Result<Self> {
+        let var = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let mut policy = Self::default();
+
+        if let Some(enabled) = var("REBALANCE_ENABLED") {
+            policy.enabled = enabled.parse().context("Invalid REBALANCE_ENABLED")?;
+        }
+        if let Some(targets) = var("REBALANCE_TARGETS") {
+            policy.targets = parse_targets(&targets).context("Invalid REBALANCE_TARGETS")?;
+        }
+        if let Some(bps) = var("REBALANCE_TRIGGER_BPS") {
+            policy.trigger_bps = bps.parse().context("Invalid REBALANCE_TRIGGER_BPS")?;
+        }
+        if let Some(bps) = var("REBALANCE_REARM_BPS") {
+            policy.rearm_bps = bps.parse().context("Invalid REBALANCE_REARM_BPS")?;
+        }
+        if let Some(max) = var("REBALANCE_MAX_PER_DAY") {
+            policy.max_per_day = max.parse().context("Invalid REBALANCE_MAX_PER_DAY")?;
+        }
+        if let Some(secs) = var("REBALANCE_TRANSFER_TIMEOUT_SECS") {
+            policy.transfer_timeout_secs = secs
+                .parse()
+                .context("Invalid REBALANCE_TRANSFER_TIMEOUT_SECS")?;
+        }
+        if let Some(method) = var("REBALANCE_METHOD") {
+            policy.method = RebalanceMethod::parse(&method)?;
+        }
+        policy.webhook_url = var("REBALANCE_WEBHOOK_URL");
+        policy.webhook_token = var("REBALANCE_WEBHOOK_TOKEN");
+        policy.relayer_url =
+            var("REBALANCE_RELAYER_URL").map(|url| url.trim_end_matches('/').to_string());
+        policy.relayer_hmac_secret = var("REBALANCE_RELAYER_HMAC_SECRET");
+        policy.relayer_api_key = var("REBALANCE_RELAYER_API_KEY");
+        if let Some(key) = var("REBALANCE_RELAYER_PUBLIC_KEY") {
+            policy.relayer_public_key =
+                Some(parse_public_key(&key).context("Invalid REBALANCE_RELAYER_PUBLIC_KEY")?);
+        }
+
+        if policy.enabled {
+            policy.validate()?;
+        }
+
+        Ok(policy)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.targets.is_empty() {
+            bail!("REBALANCE_TARGETS must list at least one token");
+        }
+        if self.trigger_bps == 0 || self.rearm_bps >= self.trigger_bps {
+            bail!("REBALANCE_REARM_BPS must be below a non-zero REBALANCE_TRIGGER_BPS");
+        }
+        if self.max_per_day == 0 {
+            bail!("REBALANCE_MAX_PER_DAY must be at least 1");
+        }
+
+        let url = match self.method {
+            RebalanceMethod::Webhook => self
+                .webhook_url
+                .as_ref()
+                .ok_or_else(|| anyhow!("REBALANCE_METHOD=webhook needs REBALANCE_WEBHOOK_URL"))?,
+            RebalanceMethod::Bridge => {
+                if self.relayer_hmac_secret.is_none() || self.relayer_public_key.is_none() {
+                    bail!(
+                        "REBALANCE_METHOD=bridge needs REBALANCE_RELAYER_HMAC_SECRET and REBALANCE_RELAYER_PUBLIC_KEY"
+                    );
+                }
+                self.relayer_url
+                    .as_ref()
+                    .ok_or_else(|| anyhow!("REBALANCE_METHOD=bridge needs REBALANCE_RELAYER_URL"))?
+            }
+        };
+        if !(url.starts_with("http://") || url.starts_with("https://")) {
+            bail!("Invalid rebalance URL: {}", url);
+        }
+
+        Ok(())
+    }
+
+    /// Tokens with a target, in `TokenType::ALL` order.
+    pub fn tokens(&self) -> Vec<TokenType> {
+        TokenType::ALL
+            .into_iter()
+            .filter(|token| self.targets.contains_key(&(*token, Chain::Ethereum)))
+            .collect()
+    }
+
+    /// The transfer that brings the low chain of `token` back to its target,
+    /// leaving the other chain at least `reserve`. `None` while both chains
+    /// are within `trigger_bps` of their targets or nothing can be spared.
+    pub fn plan(
+        &self,
+        token: TokenType,
+        inventory: &HashMap<Chain, U256>,
+        reserve: U256,
+    ) -> Option<RebalancePlan> {
+        let total = total(inventory);
+        for to in Chain::ALL {
+            let target_bps = *self.targets.get(&(token, to))?;
+            let share_bps = share_bps(inventory, to)?;
+            if share_bps + self.trigger_bps >= target_bps {
+                continue;
+            }
+
+            let from = to.counterpart();
+            let wanted = scale(total, target_bps).saturating_sub(balance(inventory, to));
+            let spare = balance(inventory, from).saturating_sub(reserve);
+            let amount = wanted.min(spare);
+            if amount.is_zero() {
+                return None;
+            }
+
+            return Some(RebalancePlan {
+                token,
+                from,
+                to,
+                amount,
+                share_bps,
+                target_bps,
+            });
+        }
+        None
+    }
+}
+
+/// `TOKEN:chain=percent` entries, such as `USDC:mantle=60`. The other chain
+/// gets the rest. ETH and MNT are native on one chain each, so their two
+/// sides are different assets and cannot be rebalanced.
+fn parse_targets(raw: &str) -> Result<HashMap<(TokenType, Chain), u64>> {
+    let mut targets = HashMap::new();
+    for entry in raw.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+        let (route, percent) = entry
+            .split_once('=')
+            .ok_or_else(|| anyhow!("'{}' is not TOKEN:chain=percent", entry))?;
+        let (token, chain) = route
+            .split_once(':')
+            .ok_or_else(|| anyhow!("'{}' is not TOKEN:chain=percent", entry))?;
+        let token = TokenType::from_symbol(token.trim())?;
+        let chain = Chain::parse(chain.trim())?;
+        if token.is_native() {
+            bail!("{} cannot be rebalanced", token.symbol());
+        }
+
+        let percent: f64 = percent
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("Invalid percent in '{}'", entry))?;
+        if !(percent > 0.0 && percent < 100.0) {
+            bail!("Target in '{}' must be between 0 and 100", entry);
+        }
+        let bps = (percent * 100.0).round() as u64;
+
+        for (side, side_bps) in [(chain, bps), (chain.counterpart(), BPS - bps)] {
+            if let Some(previous) = targets.insert((token, side), side_bps)
+                && previous != side_bps
+            {
+                bail!("Targets for {} do not add up to 100", token.symbol());
+            }
+        }
+    }
+    Ok(targets)
+}
+
+/// A compressed or uncompressed secp256k1 key as hex.
+fn parse_public_key(raw: &str) -> Result<Vec<u8>> {
+    let key = hex::decode(raw.trim_start_matches("0x")).context("Not hex")?;
+    if !matches!(key.len(), 33 | 65) {
+        bail!("Expected a 33 or 65 byte key, got {} bytes", key.len());
+    }
+    Ok(key)
+}
+
+fn balance(inventory: &HashMap<Chain, U256>, chain: Chain) -> U256 {
+    inventory.get(&chain).copied().unwrap_or_default()
+}
+
+fn total(inventory: &HashMap<Chain, U256>) -> U256 {
+    Chain::ALL.into_iter().fold(U256::zero(), |sum, chain| {
+        sum.saturating_add(balance(inventory, chain))
+    })
+}
+
+fn scale(amount: U256, bps: u64) -> U256 {
+    amount.saturating_mul(U256::from(bps)) / BPS
+}
+
+/// Share of the token's inventory held on `chain`, in bps; `None` when
+/// there is none anywhere.
+pub fn share_bps(inventory: &HashMap<Chain, U256>, chain: Chain) -> Option<u64> {
+    let total = total(inventory);
+    if total.is_zero() {
+        return None;
+    }
+    Some((balance(inventory, chain).saturating_mul(U256::from(BPS)) / total).as_u64())
+}
+
+/// A transfer the policy asks for.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RebalancePlan {
+    pub token: TokenType,
+    pub from: Chain,
+    pub to: Chain,
+    /// Base units of `token`.
+    pub amount: U256,
+    /// Share of `to` when the plan was made, and its target, in bps.
+    pub share_bps: u64,
+    pub target_bps: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RebalanceOutcome {
+    /// The low chain is back within `rearm_bps` of its target.
+    Completed,
+    /// Not arrived within `transfer_timeout_secs`.
+    Expired,
+}
+
+impl RebalanceOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Completed => "completed",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+/// What to do about one token this round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RebalanceDecision {
+    Hold,
+    /// Low, but the daily limit is used up. Returned once per streak.
+    Limited(RebalancePlan),
+    Start(RebalancePlan),
+}
+
+/// A transfer sent and not yet arrived.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RebalanceTransfer {
+    /// The bridge intent id, or the id sent to the webhook.
+    pub id: H256,
+    pub token: TokenType,
+    pub from: Chain,
+    pub to: Chain,
+    /// Base units of `token`.
+    pub amount: String,
+    pub method: RebalanceMethod,
+    pub started_at: u64,
+    /// The `createIntent` transaction of a bridge transfer.
+    pub tx_hash: Option<H256>,
+}
+
+/// Lifetime totals for `GET /metrics`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RebalanceStats {
+    pub started: u64,
+    pub completed: u64,
+    pub failed: u64,
+    pub expired: u64,
+    /// Times a token ran low with its daily limit used up.
+    pub limited: u64,
+    pub in_flight: Vec<RebalanceTransfer>,
+    /// Transfers started in the last 24 hours, per token symbol.
+    pub last_24h: BTreeMap<String, usize>,
+}
+
+/// Transfers in flight and the limits on starting more.
+#[derive(Debug)]
+pub struct Rebalancer {
+    policy: RebalancePolicy,
+    in_flight: HashMap<TokenType, RebalanceTransfer>,
+    /// Start times of each token's transfers within the last day.
+    recent: HashMap<TokenType, VecDeque<u64>>,
+    /// Our own bridge intents, so we never fill them ourselves.
+    own_intents: HashMap<H256, u64>,
+    backoff_until: HashMap<TokenType, u64>,
+    limited: HashSet<TokenType>,
+    stats: RebalanceStats,
+}
+
+impl Rebalancer {
+    pub fn new(policy: RebalancePolicy) -> Self {
+        Self {
+            policy,
+            in_flight: HashMap::new(),
+            recent: HashMap::new(),
+            own_intents: HashMap::new(),
+            backoff_until: HashMap::new(),
+            limited: HashSet::new(),
+            stats: RebalanceStats::default(),
+        }
+    }
+
+    /// Close the token's transfer in flight once it has arrived or timed out.
+    pub fn resolve(
+        &mut self,
+        token: TokenType,
+        inventory: &HashMap<Chain, U256>,
+        now: u64,
+    ) -> Option<(RebalanceTransfer, RebalanceOutcome)> {
+        let transfer = self.in_flight.get(&token)?;
+        let target_bps = self.policy.targets.get(&(token, transfer.to))?;
+        let arrived = share_bps(inventory, transfer.to)
+            .is_some_and(|share| share + self.policy.rearm_bps >= *target_bps);
+
+        let outcome = if arrived {
+            self.stats.completed += 1;
+            RebalanceOutcome::Completed
+        } else if now.saturating_sub(transfer.started_at) >= self.policy.transfer_timeout_secs {
+            self.stats.expired += 1;
+            RebalanceOutcome::Expired
+        } else {
+            return None;
+        };
+
+        let transfer = self.in_flight.remove(&token)?;
+        Some((transfer, outcome))
+    }
+
+    pub fn decide(
+        &mut self,
+        token: TokenType,
+        inventory: &HashMap<Chain, U256>,
+        reserve: U256,
+        now: u64,
+    ) -> RebalanceDecision {
+        if self.in_flight.contains_key(&token)
+            || self
+                .backoff_until
+                .get(&token)
+                .is_some_and(|until| now < *until)
+        {
+            return RebalanceDecision::Hold;
+        }
+        let Some(plan) = self.policy.plan(token, inventory, reserve) else {
+            self.limited.remove(&token);
+            return RebalanceDecision::Hold;
+        };
+
+        let recent = self.recent.entry(token).or_default();
+        while recent.front().is_some_and(|at| at + DAY_SECS <= now) {
+            recent.pop_front();
+        }
+        if recent.len() < self.policy.max_per_day {
+            return RebalanceDecision::Start(plan);
+        }
+
+        if self.limited.insert(token) {
+            self.stats.limited += 1;
+            RebalanceDecision::Limited(plan)
+        } else {
+            RebalanceDecision::Hold
+        }
+    }
+
+    /// Record a transfer about to be sent; it counts against the daily limit
+    /// whether or not it gets out.
+    pub fn begin(&mut self, plan: &RebalancePlan, id: H256, now: u64) -> RebalanceTransfer {
+        let transfer = RebalanceTransfer {
+            id,
+            token: plan.token,
+            from: plan.from,
+            to: plan.to,
+            amount: plan.amount.to_string(),
+            method: self.policy.method,
+            started_at: now,
+            tx_hash: None,
+        };
+
+        self.recent.entry(plan.token).or_default().push_back(now);
+        self.limited.remove(&plan.token);
+        if transfer.method == RebalanceMethod::Bridge {
+            self.own_intents.retain(|_, at| *at + DAY_SECS > now);
+            self.own_intents.insert(id, now);
+        }
+        self.in_flight.insert(plan.token, transfer.clone());
+        self.stats.started += 1;
+        transfer
+    }
+
+    pub fn sent(&mut self, token: TokenType, tx_hash: Option<H256>) {
+        if let Some(transfer) = self.in_flight.get_mut(&token) {
+            transfer.tx_hash = tx_hash;
+        }
+    }
+
+    pub fn failed(&mut self, token: TokenType, now: u64) {
+        self.in_flight.remove(&token);
+        self.back_off(token, now);
+        self.stats.failed += 1;
+    }
+
+    /// Leave the token alone for a while, e.g. after logging a dry-run plan.
+    pub fn back_off(&mut self, token: TokenType, now: u64) {
+        self.backoff_until
+            .insert(token, now + FAILED_START_BACKOFF_SECS);
+    }
+
+    /// A bridge intent this solver created to move its own inventory.
+    pub fn is_own_intent(&self, intent_id: &H256) -> bool {
+        self.own_intents.contains_key(intent_id)
+    }
+
+    pub fn stats(&self, now: u64) -> RebalanceStats {
+        let mut stats = self.stats.clone();
+        stats.in_flight = self.in_flight.values().cloned().collect();
+        stats.in_flight.sort_by_key(|transfer| transfer.started_at);
+        stats.last_24h = self
+            .recent
+            .iter()
+            .map(|(token, starts)| {
+                let count = starts.iter().filter(|at| *at + DAY_SECS > now).count();
+                (token.symbol().to_string(), count)
+            })
+            .collect();
+        stats
+    }
+}
+
+/// Secret values of one bridge transfer. The nullifier is derived from
+/// the secret as the web app derives it.
+#[derive(Debug, Clone)]
+pub struct BridgeSecrets {
+    pub intent_id: H256,
+    pub secret: H256,
+    pub nullifier: H256,
+}
+
+impl BridgeSecrets {
+    pub fn generate() -> Self {
+        let secret = H256::random();
+        Self {
+            intent_id: H256::random(),
+            secret,
+            nullifier: H256(keccak256(secret)),
+        }
+    }
+
+    /// Inputs of the intent's Poseidon commitment, which the settlement
+    /// checks at claim time.
+    pub fn commitment_inputs(&self, amount: U256, source: Chain) -> [[u8; 32]; 4] {
+        let mut amount_bytes = [0u8; 32];
+        amount.to_big_endian(&mut amount_bytes);
+        let mut chain_bytes = [0u8; 32];
+        U256::from(source.id()).to_big_endian(&mut chain_bytes);
+        [self.secret.0, self.nullifier.0, amount_bytes, chain_bytes]
+    }
+
+    /// What the recipient signs to let the relayer claim for it:
+    /// `keccak256(abi.encodePacked(intentId, nullifier, recipient))`.
+    pub fn claim_auth_hash(&self, recipient: Address) -> H256 {
+        let mut packed = Vec::with_capacity(84);
+        packed.extend_from_slice(self.intent_id.as_bytes());
+        packed.extend_from_slice(self.nullifier.as_bytes());
+        packed.extend_from_slice(recipient.as_bytes());
+        H256(keccak256(packed))
+    }
+}
+
+/// Sends transfers out: bridge intents to the relayer, or requests to the
+/// configured webhook.
+#[derive(Clone)]
+pub struct RebalanceClient {
+    policy: RebalancePolicy,
+    solver: Address,
+    http: OutboundClient,
+}
+
+impl RebalanceClient {
+    /// `None` when rebalancing is off.
+    pub fn new(policy: &RebalancePolicy, solver: Address, http: OutboundClient) -> Option<Self> {
+        policy.enabled.then(|| Self {
+            policy: policy.clone(),
+            solver,
+            http,
+        })
+    }
+
+    /// Ask the webhook to move the transfer. Retries carry the same `id`, so
+    /// the receiver can drop duplicates.
+    pub async fn request_transfer(&self, transfer: &RebalanceTransfer) -> Result<()> {
+        let url = self
+            .policy
+            .webhook_url
+            .clone()
+            .ok_or_else(|| anyhow!("REBALANCE_WEBHOOK_URL is not set"))?;
+        let body = json!({
+            "id": transfer.id,
+            "token": transfer.token.symbol(),
+            "from_chain": transfer.from.name(),
+            "to_chain": transfer.to.name(),
+            "amount": transfer.amount,
+            "decimals": transfer.token.decimals(),
+            "solver": format!("{:?}", self.solver),
+            "requested_at": transfer.started_at,
+        });
+
+        let response = self
+            .http
+            .send(&url, |http| {
+                let request = http.post(&url).json(&body);
+                match &self.policy.webhook_token {
+                    Some(token) => request.bearer_auth(token),
+                    None => request,
+                }
+            })
+            .await?;
+        if !response.status().is_success() {
+            bail!("Rebalance webhook answered {}", response.status());
+        }
+        Ok(())
+    }
+
+    /// ECIES ciphertext of `value` for the relayer, as 0x hex.
+    pub fn encrypt_for_relayer(&self, value: H256) -> Result<String> {
+        let key = self
+            .policy
+            .relayer_public_key
+            .as_ref()
+            .ok_or_else(|| anyhow!("REBALANCE_RELAYER_PUBLIC_KEY is not set"))?;
+        let encrypted = ecies::encrypt(key, value.as_bytes())
+            .map_err(|e| anyhow!("Failed to encrypt for the relayer: {}", e))?;
+        Ok(format!("0x{}", hex::encode(encrypted)))
+    }
+
+    /// Hand a created intent to the relayer through `POST /bridge/initiate`,
+    /// signed like the web app's requests.
+    pub async fn submit_intent(&self, request: &Value) -> Result<()> {
+        let base = self
+            .policy
+            .relayer_url
+            .as_ref()
+            .ok_or_else(|| anyhow!("REBALANCE_RELAYER_URL is not set"))?;
+        let secret = self
+            .policy
+            .relayer_hmac_secret
+            .as_ref()
+            .ok_or_else(|| anyhow!("REBALANCE_RELAYER_HMAC_SECRET is not set"))?;
+        let url = format!("{}/bridge/initiate", base);
+        let body = request.to_string();
+        let timestamp = chrono::Utc::now().timestamp().to_string();
+        let signature = sign_request(secret, &timestamp, &body);
+
+        let response = self
+            .http
+            .send(&url, |http| {
+                let request = http
+                    .post(&url)
+                    .header("content-type", "application/json")
+                    .header("x-timestamp", &timestamp)
+                    .header("x-signature", &signature)
+                    .body(body.clone());
+                match &self.policy.relayer_api_key {
+                    Some(key) => request.header("x-api-key", key),
+                    None => request,
+                }
+            })
+            .await?;
+        let status = response.status();
+        let answer: Value = response.json().await.unwrap_or_default();
+        if !status.is_success() || answer["success"] == Value::Bool(false) {
+            bail!(
+                "Relayer answered {}: {}",
+                status,
+                answer["error"]
+                    .as_str()
+                    .or(answer["message"].as_str())
+                    .unwrap_or("no message")
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Hex HMAC-SHA256 of `timestamp` followed by the body, as the relayer
+/// checks it.
+fn sign_request(secret: &str, timestamp: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC can take key of any size");
+    mac.update(timestamp.as_bytes());
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy() -> RebalancePolicy {
+        RebalancePolicy {
+            enabled: true,
+            targets: parse_targets("USDC:mantle=60").unwrap(),
+            max_per_day: 2,
+            ..Default::default()
+        }
+    }
+
+    fn usdc(ethereum: u64, mantle: u64) -> HashMap<Chain, U256> {
+        HashMap::from([
+            (Chain::Ethereum, U256::from(ethereum) * U256::exp10(6)),
+            (Chain::Mantle, U256::from(mantle) * U256::exp10(6)),
+        ])
+    }
+
+    #[test]
+    fn test_parse_targets() {
+        let targets = parse_targets("USDC:mantle=60, usdt:ethereum=25").unwrap();
+        assert_eq!(targets[&(TokenType::USDC, Chain::Ethereum)], 4000);
+        assert_eq!(targets[&(TokenType::USDT, Chain::Mantle)], 7500);
+
+        assert!(parse_targets("USDC:mantle=60,USDC:ethereum=40").is_ok());
+        for bad in [
+            "USDC:mantle=60,USDC:ethereum=50",
+            "ETH:mantle=50",
+            "USDC:mantle=100",
+            "USDC=50",
+            "USDC:solana=50",
+        ] {
+            assert!(parse_targets(bad).is_err(), "{}", bad);
+        }
+    }
+
+    #[test]
+    fn test_plan_restores_the_low_chain_above_the_reserve() {
+        let policy = policy();
+        let reserve = U256::from(100) * U256::exp10(6);
+
+        // 50% on Mantle is within 15 points of 60%
+        assert!(
+            policy
+                .plan(TokenType::USDC, &usdc(500, 500), reserve)
+                .is_none()
+        );
+
+        let plan = policy
+            .plan(TokenType::USDC, &usdc(800, 200), reserve)
+            .unwrap();
+        assert_eq!((plan.from, plan.to), (Chain::Ethereum, Chain::Mantle));
+        assert_eq!(plan.amount, U256::from(400) * U256::exp10(6));
+        assert_eq!((plan.share_bps, plan.target_bps), (2000, 6000));
+
+        // Ethereum low the other way, capped so Mantle keeps its reserve
+        let plan = policy
+            .plan(TokenType::USDC, &usdc(10, 150), reserve)
+            .unwrap();
+        assert_eq!((plan.from, plan.to), (Chain::Mantle, Chain::Ethereum));
+        assert_eq!(plan.amount, U256::from(50) * U256::exp10(6));
+
+        assert!(policy.plan(TokenType::USDC, &usdc(0, 0), reserve).is_none());
+        assert!(
+            policy
+                .plan(TokenType::USDT, &usdc(800, 200), reserve)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_hysteresis_and_daily_limit() {
+        let mut rebalancer = Rebalancer::new(policy());
+        let low = usdc(800, 200);
+        let now = 1_000_000;
+
+        let RebalanceDecision::Start(plan) =
+            rebalancer.decide(TokenType::USDC, &low, U256::zero(), now)
+        else {
+            panic!("expected a transfer");
+        };
+        rebalancer.begin(&plan, H256::random(), now);
+
+        // In flight: no second transfer, and 54% is not yet within 5 points
+        assert_eq!(
+            rebalancer.decide(TokenType::USDC, &low, U256::zero(), now + 60),
+            RebalanceDecision::Hold
+        );
+        assert!(
+            rebalancer
+                .resolve(TokenType::USDC, &usdc(460, 540), now + 60)
+                .is_none()
+        );
+        let (_, outcome) = rebalancer
+            .resolve(TokenType::USDC, &usdc(440, 560), now + 120)
+            .unwrap();
+        assert_eq!(outcome, RebalanceOutcome::Completed);
+
+        // Second transfer never arrives and times out
+        let RebalanceDecision::Start(plan) =
+            rebalancer.decide(TokenType::USDC, &low, U256::zero(), now + 180)
+        else {
+            panic!("expected a transfer");
+        };
+        rebalancer.begin(&plan, H256::random(), now + 180);
+        let timeout = RebalancePolicy::default().transfer_timeout_secs;
+        let (_, outcome) = rebalancer
+            .resolve(TokenType::USDC, &low, now + 180 + timeout)
+            .unwrap();
+        assert_eq!(outcome, RebalanceOutcome::Expired);
+
+        // Two a day: the third is limited, and reported once
+        let later = now + 180 + timeout;
+        assert!(matches!(
+            rebalancer.decide(TokenType::USDC, &low, U256::zero(), later),
+            RebalanceDecision::Limited(_)
+        ));
+        assert_eq!(
+            rebalancer.decide(TokenType::USDC, &low, U256::zero(), later + 60),
+            RebalanceDecision::Hold
+        );
+        let stats = rebalancer.stats(later);
+        assert_eq!((stats.started, stats.completed, stats.expired), (2, 1, 1));
+        assert_eq!((stats.limited, stats.last_24h["USDC"]), (1, 2));
+
+        assert!(matches!(
+            rebalancer.decide(TokenType::USDC, &low, U256::zero(), now + DAY_SECS),
+            RebalanceDecision::Start(_)
+        ));
+    }
+
+    #[test]
+    fn test_bridge_secrets_match_the_contract() {
+        let secrets = BridgeSecrets::generate();
+        assert_eq!(secrets.nullifier, H256(keccak256(secrets.secret)));
+
+        let inputs = secrets.commitment_inputs(U256::from(1_000_000), Chain::Mantle);
+        assert_eq!(U256::from_big_endian(&inputs[2]), U256::from(1_000_000));
+        assert_eq!(U256::from_big_endian(&inputs[3]), U256::from(5003));
+
+        let recipient = Address::repeat_byte(0xab);
+        let mut packed = secrets.intent_id.as_bytes().to_vec();
+        packed.extend_from_slice(secrets.nullifier.as_bytes());
+        packed.extend_from_slice(&[0xab; 20]);
+        assert_eq!(secrets.claim_auth_hash(recipient), H256(keccak256(packed)));
+
+        // The relayer's check: hex HMAC of timestamp then body
+        assert_eq!(
+            sign_request("key", "1700000000", r#"{"a":1}"#),
+            "241653ad78c4f3ee1dba548cbb1ca6120ea634c9ba4b7e5824b048b05ff028c9"
+        );
+    }
+}
@@ -0,0 +1,254 @@
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use anyhow::{Context, Result};
+use ethers::types::{H256, Log};
+use serde::{Deserialize, Serialize};
+use tracing::{info, warn};
+
+const DAY_SECS: u64 = 86_400;
+
+/// Where handled `IntentRegistered` logs are remembered across restarts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegisteredLogPolicy {
+    /// JSON lines file; `None` remembers handled logs in memory only.
+    pub path: Option<PathBuf>,
+    pub retention_secs: u64,
+}
+
+impl Default for RegisteredLogPolicy {
+    fn default() -> Self {
+        Self {
+            path: Some(PathBuf::from("registered_logs.jsonl")),
+            retention_secs: 7 * DAY_SECS,
+        }
+    }
+}
+
+impl RegisteredLogPolicy {
+    /// Override the defaults from `REGISTERED_LOG_PATH` (empty disables the
+    /// file) and `REGISTERED_LOG_RETENTION_DAYS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(path) = std::env::var("REGISTERED_LOG_PATH") {
+            policy.path = (!path.trim().is_empty()).then(|| PathBuf::from(path.trim()));
+        }
+        if let Ok(days) = std::env::var("REGISTERED_LOG_RETENTION_DAYS") {
+            policy.retention_secs = days
+                .parse::<u64>()
+                .context("Invalid REGISTERED_LOG_RETENTION_DAYS")?
+                .max(1)
+                * DAY_SECS;
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Identity of one log delivery: the same event redelivered by a WS
+/// reconnect or an overlapping `get_logs` window has the same key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct LogKey {
+    pub chain_id: u64,
+    pub tx_hash: H256,
+    pub log_index: u64,
+}
+
+impl LogKey {
+    /// `None` for logs without a position, e.g. from a pending block.
+    pub fn of(log: &Log, chain_id: u64) -> Option<Self> {
+        Some(Self {
+            chain_id,
+            tx_hash: log.transaction_hash?,
+            log_index: log.log_index?.as_u64(),
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct HandledLog {
+    #[serde(flatten)]
+    key: LogKey,
+    intent_id: H256,
+    at: u64,
+}
+
+/// `IntentRegistered` logs already handled to completion, persisted so a
+/// redelivery after a restart is ignored like one in the same process.
+#[derive(Debug)]
+pub struct RegisteredLogs {
+    policy: RegisteredLogPolicy,
+    handled: HashMap<LogKey, HandledLog>,
+}
+
+impl RegisteredLogs {
+    /// Read back the persisted logs, dropping expired and unreadable lines.
+    pub fn load(policy: RegisteredLogPolicy, now: u64) -> Self {
+        let mut logs = Self {
+            policy,
+            handled: HashMap::new(),
+        };
+
+        let Some(path) = logs.policy.path.clone() else {
+            return logs;
+        };
+
+        let contents = match fs::read_to_string(&path) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return logs,
+            Err(e) => {
+                warn!("⚠️ Failed to read handled logs {}: {}", path.display(), e);
+                return logs;
+            }
+        };
+
+        let mut skipped = 0;
+        for line in contents.lines().filter(|line| !line.trim().is_empty()) {
+            match serde_json::from_str::<HandledLog>(line) {
+                Ok(entry) => {
+                    logs.handled.insert(entry.key, entry);
+                }
+                Err(_) => skipped += 1,
+            }
+        }
+
+        let expired = logs.prune(now);
+        if (expired > 0 || skipped > 0)
+            && let Err(e) = logs.rewrite()
+        {
+            warn!("⚠️ Failed to compact handled logs: {}", e);
+        }
+
+        info!(
+            "🧾 Loaded {} handled IntentRegistered logs from {} ({} expired, {} unreadable)",
+            logs.handled.len(),
+            path.display(),
+            expired,
+            skipped
+        );
+
+        logs
+    }
+
+    pub fn contains(&self, key: &LogKey) -> bool {
+        self.handled.contains_key(key)
+    }
+
+    /// Remember a log as handled. Entries past retention are dropped from
+    /// memory here and from the file on the next start.
+    pub fn record(&mut self, key: LogKey, intent_id: H256, now: u64) -> Result<()> {
+        let entry = HandledLog {
+            key,
+            intent_id,
+            at: now,
+        };
+        self.handled.insert(key, entry.clone());
+        self.prune(now);
+
+        let Some(path) = &self.policy.path else {
+            return Ok(());
+        };
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+        file.write_all(line.as_bytes())
+            .with_context(|| format!("Failed to write {}", path.display()))
+    }
+
+    /// Let redeliveries of an intent's log be handled again, after a fill
+    /// that was queued on its behalf failed.
+    pub fn forget_intent(&mut self, intent_id: H256) -> Result<()> {
+        let before = self.handled.len();
+        self.handled.retain(|_, entry| entry.intent_id != intent_id);
+        if self.handled.len() == before {
+            return Ok(());
+        }
+        self.rewrite()
+    }
+
+    fn prune(&mut self, now: u64) -> usize {
+        let cutoff = now.saturating_sub(self.policy.retention_secs);
+        let before = self.handled.len();
+        self.handled.retain(|_, entry| entry.at >= cutoff);
+        before - self.handled.len()
+    }
+
+    fn rewrite(&self) -> Result<()> {
+        let Some(path) = &self.policy.path else {
+            return Ok(());
+        };
+
+        let mut entries: Vec<&HandledLog> = self.handled.values().collect();
+        entries.sort_by_key(|entry| entry.at);
+        let mut lines = String::new();
+        for entry in entries {
+            lines.push_str(&serde_json::to_string(entry)?);
+            lines.push('\n');
+        }
+
+        let tmp = path.with_extension("jsonl.tmp");
+        fs::write(&tmp, lines).with_context(|| format!("Failed to write {}", tmp.display()))?;
+        fs::rename(&tmp, path).with_context(|| format!("Failed to replace {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_handled_logs_survive_restart() {
+        let path = std::env::temp_dir().join(format!(
+            "solver-registered-logs-{}.jsonl",
+            std::process::id()
+        ));
+        let _ = fs::remove_file(&path);
+        let policy = RegisteredLogPolicy {
+            path: Some(path.clone()),
+            retention_secs: 1_000,
+        };
+
+        let log = Log {
+            transaction_hash: Some(H256::repeat_byte(1)),
+            log_index: Some(3.into()),
+            ..Default::default()
+        };
+        let key = LogKey::of(&log, 5003).unwrap();
+        assert!(LogKey::of(&Log::default(), 5003).is_none());
+        let other = LogKey {
+            log_index: 4,
+            ..key
+        };
+
+        let mut logs = RegisteredLogs::load(policy.clone(), 0);
+        logs.record(key, H256::repeat_byte(0xa), 0).unwrap();
+        logs.record(other, H256::repeat_byte(0xb), 900).unwrap();
+
+        // Same log on another chain is a different delivery
+        let reloaded = RegisteredLogs::load(policy.clone(), 500);
+        assert!(reloaded.contains(&key));
+        assert!(!reloaded.contains(&LogKey {
+            chain_id: 11155111,
+            ..key
+        }));
+
+        let mut reloaded = RegisteredLogs::load(policy.clone(), 1_500);
+        assert!(!reloaded.contains(&key));
+        assert!(reloaded.contains(&other));
+        assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 1);
+
+        reloaded.forget_intent(H256::repeat_byte(0xb)).unwrap();
+        assert!(!RegisteredLogs::load(policy, 1_500).contains(&other));
+        let _ = fs::remove_file(&path);
+    }
+}
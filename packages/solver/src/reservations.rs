@@ -0,0 +1,158 @@
+use anyhow::{Context, Result, anyhow, bail};
+use ethers::types::{Address, H256};
+use mantle_core::http_client::OutboundClient;
+use serde_json::{Value, json};
+
+/// Where the solver reserves intents before filling them. Unset fills
+/// first-come, first-served on chain.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReservationPolicy {
+    /// The relayer's `/api/v1` base URL.
+    pub url: Option<String>,
+    /// This solver's entry in the relayer's `RESERVATION_SOLVER_TOKENS`.
+    pub token: Option<String>,
+}
+
+impl ReservationPolicy {
+    /// Reads `RELAYER_RESERVATION_URL` and `RELAYER_RESERVATION_TOKEN`; set
+    /// both or neither.
+    pub fn from_env() -> Result<Self> {
+        let var = |key: &str| {
+            std::env::var(key)
+                .ok()
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let policy = Self {
+            url: var("RELAYER_RESERVATION_URL").map(|url| url.trim_end_matches('/').to_string()),
+            token: var("RELAYER_RESERVATION_TOKEN"),
+        };
+
+        if policy.url.is_some() != policy.token.is_some() {
+            bail!("Set both RELAYER_RESERVATION_URL and RELAYER_RESERVATION_TOKEN, or neither");
+        }
+        if let Some(url) = &policy.url
+            && !(url.starts_with("http://") || url.starts_with("https://"))
+        {
+            bail!("Invalid RELAYER_RESERVATION_URL: {}", url);
+        }
+
+        Ok(policy)
+    }
+}
+
+/// The relayer's answer to a reservation request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Reservation {
+    /// Ours until `expires_at` (RFC 3339).
+    Granted { expires_at: String },
+    /// Another solver holds it, it is filled, or our own window lapsed.
+    Refused(String),
+}
+
+/// Map a reservation response. Anything but a grant or a `409` is an error,
+/// and the caller falls back to filling on chain.
+pub fn parse_reservation(status: u16, body: &Value) -> Result<Reservation> {
+    let message = body["message"].as_str().unwrap_or("no message");
+
+    match status {
+        200 => Ok(Reservation::Granted {
+            expires_at: body["data"]["expires_at"]
+                .as_str()
+                .ok_or_else(|| anyhow!("Reservation response without expires_at"))?
+                .to_string(),
+        }),
+        409 => Ok(Reservation::Refused(message.to_string())),
+        other => Err(anyhow!("Relayer answered {}: {}", other, message)),
+    }
+}
+
+/// Reserves intents on the relayer so competing solvers don't send the
+/// same fill.
+#[derive(Clone)]
+pub struct ReservationClient {
+    url: String,
+    token: String,
+    solver: Address,
+    http: OutboundClient,
+}
+
+impl ReservationClient {
+    /// `None` when reservations are not configured.
+    pub fn new(policy: &ReservationPolicy, solver: Address, http: OutboundClient) -> Option<Self> {
+        Some(Self {
+            url: policy.url.clone()?,
+            token: policy.token.clone()?,
+            solver,
+            http,
+        })
+    }
+
+    fn endpoint(&self, intent_id: H256) -> String {
+        format!("{}/intents/{:?}/reserve", self.url, intent_id)
+    }
+
+    pub async fn reserve(&self, intent_id: H256) -> Result<Reservation> {
+        let url = self.endpoint(intent_id);
+        let body = json!({ "solver": format!("{:?}", self.solver) });
+
+        let response = self
+            .http
+            .send(&url, |http| {
+                http.post(&url).bearer_auth(&self.token).json(&body)
+            })
+            .await?;
+        let status = response.status().as_u16();
+        let body: Value = response
+            .json()
+            .await
+            .context("Invalid reservation response")?;
+
+        parse_reservation(status, &body)
+    }
+
+    /// Hand the intent back early, e.g. when the fill is not sent after all.
+    pub async fn release(&self, intent_id: H256) -> Result<()> {
+        let url = format!("{}?solver={:?}", self.endpoint(intent_id), self.solver);
+
+        let response = self
+            .http
+            .send(&url, |http| http.delete(&url).bearer_auth(&self.token))
+            .await?;
+        if !response.status().is_success() && response.status().as_u16() != 404 {
+            bail!("Relayer answered {} to release", response.status());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reservation() {
+        let granted = json!({
+            "status": "success",
+            "data": { "intent_id": "0x01", "expires_at": "2026-01-12T09:00:30Z" }
+        });
+        assert_eq!(
+            parse_reservation(200, &granted).unwrap(),
+            Reservation::Granted {
+                expires_at: "2026-01-12T09:00:30Z".to_string()
+            }
+        );
+
+        let held = json!({ "status": "error", "message": "Reserved by another solver" });
+        assert_eq!(
+            parse_reservation(409, &held).unwrap(),
+            Reservation::Refused("Reserved by another solver".to_string())
+        );
+
+        // Disabled, unauthorised or unknown to the relayer: fill on chain
+        for status in [401, 404, 503] {
+            assert!(parse_reservation(status, &held).is_err());
+        }
+        assert!(parse_reservation(200, &json!({ "data": {} })).is_err());
+    }
+}
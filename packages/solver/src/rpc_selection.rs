@@ -0,0 +1,518 @@
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use anyhow::{Context, Result, anyhow};
+use ethers::providers::{Middleware, Provider, Ws};
+use serde::Serialize;
+use tokio::{
+    sync::{RwLock, watch},
+    task::JoinSet,
+};
+use tracing::{info, warn};
+
+/// Weight of the newest sample in the RTT moving average.
+const RTT_EWMA_ALPHA: f64 = 0.3;
+const PROBE_TIMEOUT: Duration = Duration::from_secs(5);
+/// A healthy subscription endpoint is only replaced by one under this share
+/// of its RTT, so jitter doesn't keep tearing the log subscription down.
+const SWITCH_THRESHOLD_PCT: u128 = 80;
+
+/// A configured WS endpoint with its optional region tag and RTT target.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RpcEndpoint {
+    pub url: String,
+    pub region: Option<String>,
+    /// Endpoints measured above their target rank behind those within theirs.
+    pub latency_target: Option<Duration>,
+}
+
+impl RpcEndpoint {
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            region: None,
+            latency_target: None,
+        }
+    }
+
+    /// Parses comma separated `url|region|target_ms` entries. Region and
+    /// target are optional, e.g. `wss://a|eu-west|80,wss://b|us-east,wss://c`.
+    pub fn parse_list(raw: &str) -> Result<Vec<Self>> {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| {
+                let mut parts = entry.split('|').map(str::trim);
+                let url = parts.next().unwrap_or_default();
+                if !url.starts_with("ws://") && !url.starts_with("wss://") {
+                    return Err(anyhow!("Not a WebSocket URL: {}", display_host(url)));
+                }
+                let region = parts
+                    .next()
+                    .filter(|region| !region.is_empty())
+                    .map(str::to_lowercase);
+                let latency_target = parts
+                    .next()
+                    .filter(|target| !target.is_empty())
+                    .map(|target| {
+                        target
+                            .parse()
+                            .map(Duration::from_millis)
+                            .with_context(|| format!("Invalid latency target: {}", target))
+                    })
+                    .transpose()?;
+                if parts.next().is_some() {
+                    return Err(anyhow!("Too many fields in {}", display_host(url)));
+                }
+
+                Ok(Self {
+                    url: url.to_string(),
+                    region,
+                    latency_target,
+                })
+            })
+            .collect()
+    }
+
+    /// The chain's endpoints with `primary` first. Its tags come from `tagged`
+    /// when the primary URL is listed there too.
+    pub fn with_primary(primary: &str, tagged: &[Self]) -> Vec<Self> {
+        let primary = tagged
+            .iter()
+            .find(|endpoint| endpoint.url == primary)
+            .cloned()
+            .unwrap_or_else(|| Self::new(primary));
+
+        std::iter::once(primary.clone())
+            .chain(tagged.iter().filter(|e| e.url != primary.url).cloned())
+            .collect()
+    }
+}
+
+/// Scheme and host only; paths and credentials often carry API keys.
+pub fn display_host(url: &str) -> String {
+    let (scheme, rest) = url.split_once("://").unwrap_or(("", url));
+    let host = rest.split('/').next().unwrap_or_default();
+    let host = host.rsplit('@').next().unwrap_or_default();
+    if scheme.is_empty() {
+        host.to_string()
+    } else {
+        format!("{}://{}", scheme, host)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RpcSelectionPolicy {
+    /// Region the solver runs in. Orders endpoints that have no RTT yet.
+    pub region: Option<String>,
+    pub probe_interval: Duration,
+    /// Consecutive failed probes before an endpoint is treated as down.
+    pub failure_threshold: u32,
+}
+
+impl Default for RpcSelectionPolicy {
+    fn default() -> Self {
+        Self {
+            region: None,
+            probe_interval: Duration::from_secs(5),
+            failure_threshold: 3,
+        }
+    }
+}
+
+impl RpcSelectionPolicy {
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(region) = std::env::var("SOLVER_REGION") {
+            policy.region = (!region.trim().is_empty()).then(|| region.trim().to_lowercase());
+        }
+        if let Ok(secs) = std::env::var("RPC_PROBE_INTERVAL_SECS") {
+            policy.probe_interval = Duration::from_secs(
+                secs.parse::<u64>()
+                    .context("Invalid RPC_PROBE_INTERVAL_SECS")?
+                    .max(1),
+            );
+        }
+        if let Ok(failures) = std::env::var("RPC_FAILURE_THRESHOLD") {
+            policy.failure_threshold = failures
+                .parse::<u32>()
+                .context("Invalid RPC_FAILURE_THRESHOLD")?
+                .max(1);
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Probe results of one endpoint.
+#[derive(Debug, Clone, Default)]
+struct EndpointHealth {
+    /// Moving average of `eth_blockNumber` round trips; `None` until one succeeds.
+    rtt: Option<Duration>,
+    consecutive_failures: u32,
+}
+
+impl EndpointHealth {
+    fn record(&mut self, sample: Option<Duration>) {
+        match sample {
+            Some(sample) => {
+                self.rtt = Some(match self.rtt {
+                    Some(rtt) => rtt.mul_f64(1.0 - RTT_EWMA_ALPHA) + sample.mul_f64(RTT_EWMA_ALPHA),
+                    None => sample,
+                });
+                self.consecutive_failures = 0;
+            }
+            None => self.consecutive_failures += 1,
+        }
+    }
+}
+
+/// Endpoints used for the log subscription and for `get_logs` backfill.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Selection {
+    subscription: usize,
+    backfill: usize,
+}
+
+/// Sort key, lowest first: healthy, within latency target, fastest, then in
+/// the solver's region.
+fn rank(
+    endpoint: &RpcEndpoint,
+    health: &EndpointHealth,
+    policy: &RpcSelectionPolicy,
+) -> (bool, bool, Duration, bool) {
+    let over_target = matches!(
+        (health.rtt, endpoint.latency_target),
+        (Some(rtt), Some(target)) if rtt > target
+    );
+    let in_region = policy.region.is_some() && endpoint.region == policy.region;
+
+    (
+        health.consecutive_failures >= policy.failure_threshold,
+        over_target,
+        health.rtt.unwrap_or(Duration::MAX),
+        !in_region,
+    )
+}
+
+/// Picks the best endpoint for the subscription, keeping `current` unless it
+/// went down, missed its target, or another one is clearly faster. Backfill
+/// goes to the best other healthy endpoint so heavy `get_logs` ranges don't
+/// compete with the stream; with none left it shares the subscription one.
+fn select(
+    endpoints: &[RpcEndpoint],
+    health: &[EndpointHealth],
+    policy: &RpcSelectionPolicy,
+    current: Option<usize>,
+) -> Selection {
+    let ranks: Vec<_> = endpoints
+        .iter()
+        .zip(health)
+        .map(|(endpoint, health)| rank(endpoint, health, policy))
+        .collect();
+    let mut order: Vec<usize> = (0..endpoints.len()).collect();
+    order.sort_by_key(|&index| ranks[index]);
+
+    let best = order[0];
+    let subscription = match current {
+        Some(current) if current != best => {
+            let (current_down, current_over, current_rtt, _) = ranks[current];
+            let (_, best_over, best_rtt, _) = ranks[best];
+            let clearly_faster =
+                best_rtt.as_micros() * 100 < current_rtt.as_micros() * SWITCH_THRESHOLD_PCT;
+
+            if current_down || current_over != best_over || clearly_faster {
+                best
+            } else {
+                current
+            }
+        }
+        _ => best,
+    };
+    let backfill = order
+        .iter()
+        .copied()
+        .find(|&index| index != subscription && !ranks[index].0)
+        .unwrap_or(subscription);
+
+    Selection {
+        subscription,
+        backfill,
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EndpointStatus {
+    pub endpoint: String,
+    pub region: Option<String>,
+    pub latency_target_ms: Option<u64>,
+    pub rtt_ms: Option<f64>,
+    pub healthy: bool,
+    pub consecutive_failures: u32,
+    pub subscription: bool,
+    pub backfill: bool,
+}
+
+/// The WS endpoints of one chain. A probe loop measures each endpoint's RTT
+/// and moves the log subscription to the fastest healthy one, while `get_logs`
+/// backfill runs against a different endpoint.
+pub struct RpcPool {
+    label: &'static str,
+    policy: RpcSelectionPolicy,
+    endpoints: Vec<RpcEndpoint>,
+    providers: Vec<Arc<Provider<Ws>>>,
+    health: RwLock<Vec<EndpointHealth>>,
+    subscription: watch::Sender<usize>,
+    backfill: AtomicUsize,
+}
+
+impl RpcPool {
+    /// `endpoints[0]` is the primary, whose provider is already connected.
+    /// Other endpoints that can't be reached at startup are left out.
+    pub async fn connect(
+        label: &'static str,
+        policy: RpcSelectionPolicy,
+        endpoints: Vec<RpcEndpoint>,
+        primary: Arc<Provider<Ws>>,
+        max_reconnects: usize,
+    ) -> Self {
+        let mut connected = Vec::new();
+        let mut providers = vec![primary];
+
+        for (index, endpoint) in endpoints.into_iter().enumerate() {
+            if index > 0 {
+                match Provider::<Ws>::connect_with_reconnects(&endpoint.url, max_reconnects).await {
+                    Ok(provider) => providers.push(Arc::new(provider)),
+                    Err(e) => {
+                        warn!(
+                            "⚠️ [{}] Skipping RPC endpoint {}: {}",
+                            label,
+                            display_host(&endpoint.url),
+                            e
+                        );
+                        continue;
+                    }
+                }
+            }
+            info!(
+                "   📡 [{}] RPC endpoint {} (region: {}, target: {})",
+                label,
+                display_host(&endpoint.url),
+                endpoint.region.as_deref().unwrap_or("-"),
+                endpoint
+                    .latency_target
+                    .map(|target| format!("{}ms", target.as_millis()))
+                    .unwrap_or_else(|| "-".to_string())
+            );
+            connected.push(endpoint);
+        }
+
+        let health = vec![EndpointHealth::default(); connected.len()];
+        let selection = select(&connected, &health, &policy, None);
+
+        Self {
+            label,
+            policy,
+            endpoints: connected,
+            providers,
+            health: RwLock::new(health),
+            subscription: watch::Sender::new(selection.subscription),
+            backfill: AtomicUsize::new(selection.backfill),
+        }
+    }
+
+    /// Provider for the event subscription: the lowest-latency healthy endpoint.
+    pub fn subscription_provider(&self) -> Arc<Provider<Ws>> {
+        self.providers[*self.subscription.borrow()].clone()
+    }
+
+    /// Provider for `get_logs` backfill, kept off the subscription endpoint
+    /// when another healthy one exists.
+    pub fn backfill_provider(&self) -> Arc<Provider<Ws>> {
+        self.providers[self.backfill.load(Ordering::Relaxed)].clone()
+    }
+
+    /// Changes whenever the subscription moves to another endpoint.
+    pub fn watch_subscription(&self) -> watch::Receiver<usize> {
+        self.subscription.subscribe()
+    }
+
+    pub async fn run_probes(self: Arc<Self>) {
+        let mut ticker = tokio::time::interval(self.policy.probe_interval);
+        loop {
+            ticker.tick().await;
+            self.probe().await;
+        }
+    }
+
+    async fn probe(&self) {
+        let mut probes = JoinSet::new();
+        for (index, provider) in self.providers.iter().enumerate() {
+            let provider = provider.clone();
+            probes.spawn(async move {
+                let started = Instant::now();
+                let result = tokio::time::timeout(PROBE_TIMEOUT, provider.get_block_number()).await;
+                let sample = match result {
+                    Ok(Ok(_)) => Ok(started.elapsed()),
+                    Ok(Err(e)) => Err(e.to_string()),
+                    Err(_) => Err("timed out".to_string()),
+                };
+                (index, sample)
+            });
+        }
+        let samples = probes.join_all().await;
+
+        let mut health = self.health.write().await;
+        for (index, sample) in samples {
+            let was_down = health[index].consecutive_failures >= self.policy.failure_threshold;
+            health[index].record(sample.as_ref().ok().copied());
+            let is_down = health[index].consecutive_failures >= self.policy.failure_threshold;
+
+            let host = display_host(&self.endpoints[index].url);
+            match (&sample, was_down, is_down) {
+                (Err(e), false, true) => {
+                    warn!("⚠️ [{}] RPC endpoint {} is down: {}", self.label, host, e)
+                }
+                (Ok(_), true, false) => {
+                    info!("✅ [{}] RPC endpoint {} recovered", self.label, host)
+                }
+                _ => {}
+            }
+        }
+
+        let current = *self.subscription.borrow();
+        let selection = select(&self.endpoints, &health, &self.policy, Some(current));
+        self.backfill.store(selection.backfill, Ordering::Relaxed);
+        if selection.subscription != current {
+            info!(
+                "🔀 [{}] Log subscription moving {} -> {} ({}ms)",
+                self.label,
+                display_host(&self.endpoints[current].url),
+                display_host(&self.endpoints[selection.subscription].url),
+                health[selection.subscription]
+                    .rtt
+                    .map(|rtt| rtt.as_millis().to_string())
+                    .unwrap_or_else(|| "-".to_string())
+            );
+            self.subscription.send_replace(selection.subscription);
+        }
+    }
+
+    pub async fn status(&self) -> Vec<EndpointStatus> {
+        let health = self.health.read().await;
+        let subscription = *self.subscription.borrow();
+        let backfill = self.backfill.load(Ordering::Relaxed);
+
+        self.endpoints
+            .iter()
+            .zip(health.iter())
+            .enumerate()
+            .map(|(index, (endpoint, health))| EndpointStatus {
+                endpoint: display_host(&endpoint.url),
+                region: endpoint.region.clone(),
+                latency_target_ms: endpoint
+                    .latency_target
+                    .map(|target| target.as_millis() as u64),
+                rtt_ms: health
+                    .rtt
+                    .map(|rtt| (rtt.as_secs_f64() * 10_000.0).round() / 10.0),
+                healthy: health.consecutive_failures < self.policy.failure_threshold,
+                consecutive_failures: health.consecutive_failures,
+                subscription: index == subscription,
+                backfill: index == backfill,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_parsing_and_selection() {
+        let tagged =
+            RpcEndpoint::parse_list("wss://eu.node/key|EU-West|80, wss://us.node|us-east,")
+                .unwrap();
+        assert_eq!(tagged[0].region.as_deref(), Some("eu-west"));
+        assert_eq!(tagged[0].latency_target, Some(Duration::from_millis(80)));
+        assert_eq!(tagged[1].latency_target, None);
+        assert!(RpcEndpoint::parse_list("https://eu.node").is_err());
+        assert!(RpcEndpoint::parse_list("wss://eu.node|eu|fast").is_err());
+        assert_eq!(
+            display_host("wss://user:pw@eu.node/v2/key"),
+            "wss://eu.node"
+        );
+
+        // Primary listed with tags keeps them and is not duplicated
+        let endpoints = RpcEndpoint::with_primary("wss://us.node", &tagged);
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[0].region.as_deref(), Some("us-east"));
+        let mut endpoints = RpcEndpoint::with_primary("wss://primary", &tagged);
+        endpoints.push(RpcEndpoint::new("wss://spare"));
+        assert_eq!(endpoints.len(), 4);
+
+        let policy = RpcSelectionPolicy {
+            region: Some("us-east".to_string()),
+            ..Default::default()
+        };
+        let measured = |ms: u64| EndpointHealth {
+            rtt: Some(Duration::from_millis(ms)),
+            consecutive_failures: 0,
+        };
+
+        // Nothing measured yet: the in-region endpoint leads
+        let health = vec![EndpointHealth::default(); 4];
+        let selection = select(&endpoints, &health, &policy, None);
+        assert_eq!(selection.subscription, 2);
+        assert_ne!(selection.backfill, selection.subscription);
+
+        // Fastest wins, backfill takes the next best
+        let mut health = vec![measured(60), measured(50), measured(90), measured(120)];
+        let selection = select(&endpoints, &health, &policy, None);
+        assert_eq!(
+            selection,
+            Selection {
+                subscription: 1,
+                backfill: 0
+            }
+        );
+
+        // Only a clearly faster endpoint replaces a healthy subscription
+        assert_eq!(
+            select(&endpoints, &health, &policy, Some(0)).subscription,
+            0
+        );
+        health[1] = measured(30);
+        assert_eq!(
+            select(&endpoints, &health, &policy, Some(0)).subscription,
+            1
+        );
+
+        // Over its 80ms target, eu ranks behind slower endpoints without one
+        health[1] = measured(95);
+        assert_eq!(
+            select(&endpoints, &health, &policy, Some(1)).subscription,
+            0
+        );
+
+        // Down endpoints are skipped; a lone survivor serves both paths
+        for index in [0, 1, 2] {
+            health[index].consecutive_failures = policy.failure_threshold;
+        }
+        let selection = select(&endpoints, &health, &policy, Some(0));
+        assert_eq!(
+            selection,
+            Selection {
+                subscription: 3,
+                backfill: 3
+            }
+        );
+    }
+}
@@ -0,0 +1,125 @@
+use std::collections::HashMap;
+
+use anyhow::{Context, Result, bail};
+use ethers::types::U256;
+
+/// Basis points the contracts use; fees above this are nonsense reads.
+const BPS_DENOMINATOR: u64 = 10_000;
+
+/// How the fee charged by each chain's settlement contract is kept current.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettlementFeePolicy {
+    /// Seconds between contract reads after the one at startup.
+    pub refresh_interval_secs: u64,
+    /// Used for a chain whose fee has never been read.
+    pub fallback_bps: u64,
+}
+
+impl Default for SettlementFeePolicy {
+    fn default() -> Self {
+        Self {
+            refresh_interval_secs: 300,
+            fallback_bps: 200,
+        }
+    }
+}
+
+impl SettlementFeePolicy {
+    /// Override the defaults from `SETTLEMENT_FEE_REFRESH_SECS` and
+    /// `SETTLEMENT_FEE_FALLBACK_BPS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(secs) = std::env::var("SETTLEMENT_FEE_REFRESH_SECS") {
+            policy.refresh_interval_secs = secs
+                .parse::<u64>()
+                .context("Invalid SETTLEMENT_FEE_REFRESH_SECS")?
+                .max(10);
+        }
+        if let Ok(bps) = std::env::var("SETTLEMENT_FEE_FALLBACK_BPS") {
+            policy.fallback_bps = bps.parse().context("Invalid SETTLEMENT_FEE_FALLBACK_BPS")?;
+        }
+
+        if policy.fallback_bps > BPS_DENOMINATOR {
+            bail!(
+                "SETTLEMENT_FEE_FALLBACK_BPS must be at most {}",
+                BPS_DENOMINATOR
+            );
+        }
+
+        Ok(policy)
+    }
+}
+
+/// Last fee read from each chain's settlement contract, keyed by chain id.
+#[derive(Debug, Clone, Default)]
+pub struct SettlementFees {
+    fallback_bps: u64,
+    by_chain: HashMap<u64, u64>,
+}
+
+impl SettlementFees {
+    pub fn new(policy: &SettlementFeePolicy) -> Self {
+        Self {
+            fallback_bps: policy.fallback_bps,
+            by_chain: HashMap::new(),
+        }
+    }
+
+    /// The chain's fee, or the fallback until it has been read once.
+    pub fn bps(&self, chain_id: u64) -> u64 {
+        self.by_chain
+            .get(&chain_id)
+            .copied()
+            .unwrap_or(self.fallback_bps)
+    }
+
+    pub fn is_known(&self, chain_id: u64) -> bool {
+        self.by_chain.contains_key(&chain_id)
+    }
+
+    /// Store a contract read. Returns the previous value when it changed,
+    /// `Ok(None)` on the first read or no change.
+    pub fn update(&mut self, chain_id: u64, raw: U256) -> Result<Option<u64>> {
+        if raw > U256::from(BPS_DENOMINATOR) {
+            bail!("Settlement fee of {} bps is above 100%", raw);
+        }
+        let bps = raw.as_u64();
+        match self.by_chain.insert(chain_id, bps) {
+            Some(previous) if previous != bps => Ok(Some(previous)),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// `amount` times `bps`, rounded down like the contracts do.
+pub fn fee_amount(amount: U256, bps: u64) -> U256 {
+    amount * U256::from(bps) / U256::from(BPS_DENOMINATOR)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fees_fall_back_until_read_and_report_changes() {
+        let mut fees = SettlementFees::new(&SettlementFeePolicy::default());
+        assert_eq!(fees.bps(5003), 200);
+        assert!(!fees.is_known(5003));
+
+        assert_eq!(fees.update(5003, U256::from(20)).unwrap(), None);
+        assert_eq!(fees.bps(5003), 20);
+        assert_eq!(fees.update(5003, U256::from(20)).unwrap(), None);
+        assert_eq!(fees.update(5003, U256::from(35)).unwrap(), Some(20));
+        assert_eq!(fees.bps(11155111), 200);
+
+        assert!(fees.update(5003, U256::from(10_001)).is_err());
+        assert_eq!(fees.bps(5003), 35);
+    }
+
+    #[test]
+    fn test_fee_amount_rounds_down() {
+        assert_eq!(fee_amount(U256::from(1_000_000), 20), U256::from(2_000));
+        assert_eq!(fee_amount(U256::from(499), 20), U256::zero());
+    }
+}
@@ -1,171 +1,101 @@
-use std::{collections::HashMap, str::FromStr, sync::Arc, time::Duration};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use crate::{
-    model::{
-        ActiveFill, DetectedIntent, FillOpportunity, FillStatus, SolverConfig, SolverMetrics,
-        SupportedToken,
+    balance_history::{BalanceHistory, BalanceHistoryPolicy, BalanceHistoryQuery, BalanceSnapshot},
+    capital_at_risk::{CapitalAtRisk, CapitalAtRiskPolicy, is_at_risk},
+    capital_check::{RouteCapacity, RouteCapital, RouteRequirement, assess_route},
+    drain::{DrainState, DrainStatus},
+    dry_run::{DryRunDecision, DryRunLog, DryRunPolicy, DryRunStats},
+    execution_report::{DecisionRecord, ExecutionReport, ExecutionReports, ExecutionStatus},
+    fee_escalation::FeeEscalationPolicy,
+    metrics_snapshot::MetricsSnapshotPolicy,
+    model::{ActiveFill, DetectedIntent, FillOpportunity, FillStatus, SolverConfig, SolverMetrics},
+    optimizer::{CapitalKey, capital_key, capital_with_margin, plan_fills},
+    partial_fills::PartialFillPolicy,
+    processed_intents::{ProcessedIntentPolicy, ProcessedIntentStats, ProcessedIntents},
+    rebalancer::{
+        BridgeSecrets, RebalanceClient, RebalanceDecision, RebalanceMethod, RebalancePlan,
+        RebalancePolicy, RebalanceStats, Rebalancer, share_bps,
     },
-    pricefeed::PriceFeedManager,
+    registered_logs::{LogKey, RegisteredLogPolicy, RegisteredLogs},
+    reservations::{Reservation, ReservationClient, ReservationPolicy},
+    rpc_selection::{EndpointStatus, RpcEndpoint, RpcPool, RpcSelectionPolicy},
+    settlement_fees::{SettlementFeePolicy, SettlementFees, fee_amount},
+    state_store::{RecoveredFill, StateStore, StateStorePolicy},
 };
 use anyhow::{Context, Result, anyhow};
 use ethers::{
-    contract::abigen,
-    core::k256::ecdsa::SigningKey,
+    abi::AbiDecode,
     middleware::SignerMiddleware,
-    providers::{Middleware, Provider, Ws},
-    signers::{LocalWallet, Signer, Wallet},
-    types::{Address, Filter, H256, Log, U256},
+    providers::{Middleware, Provider, StreamExt, Ws},
+    signers::Signer,
+    types::{
+        Address, Filter, H256, Log, TransactionReceipt, U256,
+        transaction::eip2718::TypedTransaction,
+    },
     utils::hex,
 };
-use tokio::{sync::RwLock, time::interval};
+use mantle_core::{
+    abi::{
+        ERC20Contract, FillIntentCall, IntentPoolContract, IntentRegisteredFilter,
+        PoseidonHasherContract, SettlementContract,
+    },
+    amount::to_decimal,
+    chain::Chain,
+    counters::PersistentCounters,
+    finality::FinalitySource,
+    http_client::OutboundClient,
+    metrics::Metrics,
+    pricefeed::{PriceFeed, PriceFeedManager, SourceHealth},
+    route::RouteMinimums,
+    token::TokenType,
+    token_registry::TokenRegistry,
+    tx_strategy::ChainTxStrategy,
+};
+use signing_service::{client::SignerHandle, protocol::SOLVER_KEY};
+use tokio::{
+    sync::{Notify, RwLock},
+    time::interval,
+};
 use tracing::{debug, error, info, warn};
 
-abigen!(
-    SettlementContract,
-    r#"[
-            function fillIntent(bytes32 intentId, bytes32 commitment, uint32 sourceChain, address token, uint256 amount) external payable
-            function getMerkleRoot() external view returns (bytes32)
-            function getIntentParams(bytes32 intentId) external view returns (tuple(bytes32 commitment, address token, uint256 amount, uint32 sourceChain, uint64 deadline, bool exists))
-            function getFill(bytes32 intentId) external view returns (tuple(address solver, address token, uint256 amount, uint32 sourceChain, uint32 timestamp, bool claimed))
-            function isTokenSupported(address token) external view returns (bool)
-            function generateFillProof(bytes32 intentId) external view returns (bytes32[] memory)
-            function getFillTreeSize() external view returns (uint256)
-            function getFillIndex(bytes32 intentId) external view returns (uint256)
-            event IntentRegistered(bytes32 indexed intentId, bytes32 commitment, address destToken, uint256 destAmount, uint32 sourceChain, uint64 deadline, bytes32[] proof, uint256 leafIndex)
-            event IntentFilled(bytes32 indexed intentId, address indexed solver, address indexed token, uint256 amount)
-            event WithdrawalClaimed(bytes32 indexed intentId, bytes32 indexed nullifier, address token)
-    ]"#
-);
-
-abigen!(
-    IntentPoolContract,
-    r#"[
-        function generateCommitmentProof(bytes32 commitment) external view returns (bytes32[] memory, uint256)
-        function settleIntent(bytes32 intentId, address solver, bytes32[] calldata merkleProof, uint256 leafIndex) external
-        event IntentCreated(bytes32 indexed intentId, bytes32 indexed commitment, uint32 destChain, address sourceToken, uint256 sourceAmount, address destToken, uint256 destAmount)
-        event IntentSettled(bytes32 indexed intentId, address indexed solver, bytes32 fillRoot)
-    ]"#
-);
-
-abigen!(
-    ERC20Contract,
-    r#"[
-        function balanceOf(address account) external view returns (uint256)
-        function allowance(address owner, address spender) external view returns (uint256)
-        function approve(address spender, uint256 amount) external returns (bool)
-        function decimals() external view returns (uint8)
-        function symbol() external view returns (string)
-    ]"#
-);
-
-impl SupportedToken {
-    pub fn symbol(&self) -> &str {
-        match self {
-            Self::ETH => "ETH",
-            Self::WETH => "WETH",
-            Self::USDC => "USDC",
-            Self::USDT => "USDT",
-            Self::MNT => "MNT",
-        }
-    }
-
-    pub fn address(&self, chain_id: u64) -> Address {
-        match (self, chain_id) {
-            (Self::ETH, 11155111) => {
-                Address::from_str("0x0000000000000000000000000000000000000000").unwrap()
-            }
-            (Self::WETH, 11155111) => {
-                Address::from_str("0x50e8Da97BeEB8064714dE45ce1F250879f3bD5B5").unwrap()
-            }
-            (Self::USDC, 11155111) => {
-                Address::from_str("0x28650373758d75a8fF0B22587F111e47BAC34e21").unwrap()
-            }
-            (Self::USDT, 11155111) => {
-                Address::from_str("0x89F4f0e13997Ca27cEB963DEE291C607e4E59923").unwrap()
-            }
-            (Self::MNT, 11155111) => {
-                Address::from_str("0x65e37B558F64E2Be5768DB46DF22F93d85741A9E").unwrap()
-            }
-            (Self::MNT, 5003) => {
-                Address::from_str("0x44FCE297e4D6c5A50D28Fb26A58202e4D49a13E7").unwrap()
-            }
-            (Self::WETH, 5003) => {
-                Address::from_str("0xdeaddeaddeaddeaddeaddeaddeaddeaddead1111").unwrap()
-            }
-            (Self::USDC, 5003) => {
-                Address::from_str("0xA4b184006B59861f80521649b14E4E8A72499A23").unwrap()
-            }
-            (Self::USDT, 5003) => {
-                Address::from_str("0xB0ee6EF7788E9122fc4AAE327Ed4FEf56c7da891").unwrap()
-            }
-            _ => Address::zero(),
-        }
-    }
-
-    pub fn decimals(&self) -> u8 {
-        match self {
-            Self::ETH | Self::WETH | Self::MNT => 18,
-            Self::USDC | Self::USDT => 6,
-        }
-    }
-
-    pub fn min_amount(&self) -> U256 {
-        match self {
-            Self::ETH | Self::WETH | Self::MNT => U256::from(10).pow(U256::from(15)),
-            Self::USDC | Self::USDT => U256::from(10).pow(U256::from(6)),
-        }
-    }
-
-    pub fn max_amount(&self) -> U256 {
-        match self {
-            Self::ETH | Self::WETH | Self::MNT => {
-                U256::from(100) * U256::from(10).pow(U256::from(18))
-            }
-            Self::USDC | Self::USDT => U256::from(100000) * U256::from(10).pow(U256::from(6)),
-        }
-    }
-
-    pub fn is_native(&self) -> bool {
-        matches!(self, Self::ETH | Self::MNT)
-    }
-}
-
-impl FromStr for SupportedToken {
-    type Err = anyhow::Error;
-
-    fn from_str(s: &str) -> Result<Self> {
-        match s.to_uppercase().as_str() {
-            "ETH" => Ok(Self::ETH),
-            "USDC" => Ok(Self::USDC),
-            "USDT" => Ok(Self::USDT),
-            "WETH" => Ok(Self::WETH),
-            "MNT" => Ok(Self::MNT),
-            _ => Err(anyhow!("Unsupported token: {}", s)),
-        }
-    }
-}
+const WS_MAX_RECONNECTS: usize = 10;
+const MAX_RESUBSCRIBE_BACKOFF: Duration = Duration::from_secs(30);
+const REPLAN_INTERVAL: Duration = Duration::from_secs(5);
+const RETRY_UNLOCK_DELAY: Duration = Duration::from_secs(12);
+const FILL_RECEIPT_POLL_INTERVAL: Duration = Duration::from_secs(4);
+const RECOVERED_FILL_POLL_INTERVAL: Duration = Duration::from_secs(15);
+const FINALITY_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// Longest wait for a registration to become final, enough for Ethereum's
+/// `finalized` tag (two epochs).
+const FINALITY_WAIT_TIMEOUT: Duration = Duration::from_secs(20 * 60);
 
 impl Default for SolverConfig {
     fn default() -> Self {
         let mut max_capital = HashMap::new();
-        max_capital.insert(SupportedToken::ETH, U256::from(10) * U256::exp10(18));
-        max_capital.insert(SupportedToken::WETH, U256::from(10) * U256::exp10(18));
-        max_capital.insert(SupportedToken::MNT, U256::from(1000) * U256::exp10(18));
-        max_capital.insert(SupportedToken::USDC, U256::from(10000) * U256::exp10(6));
-        max_capital.insert(SupportedToken::USDT, U256::from(10000) * U256::exp10(6));
+        max_capital.insert(TokenType::ETH, U256::from(10) * U256::exp10(18));
+        max_capital.insert(TokenType::WETH, U256::from(10) * U256::exp10(18));
+        max_capital.insert(TokenType::MNT, U256::from(1000) * U256::exp10(18));
+        max_capital.insert(TokenType::USDC, U256::from(10000) * U256::exp10(6));
+        max_capital.insert(TokenType::USDT, U256::from(10000) * U256::exp10(6));
 
         let mut min_reserve = HashMap::new();
-        min_reserve.insert(SupportedToken::ETH, U256::from(1) * U256::exp10(18));
-        min_reserve.insert(SupportedToken::WETH, U256::from(1) * U256::exp10(18));
-        min_reserve.insert(SupportedToken::MNT, U256::from(100) * U256::exp10(18));
-        min_reserve.insert(SupportedToken::USDC, U256::from(1000) * U256::exp10(6));
-        min_reserve.insert(SupportedToken::USDT, U256::from(1000) * U256::exp10(6));
+        min_reserve.insert(TokenType::ETH, U256::from(1) * U256::exp10(18));
+        min_reserve.insert(TokenType::WETH, U256::from(1) * U256::exp10(18));
+        min_reserve.insert(TokenType::MNT, U256::from(100) * U256::exp10(18));
+        min_reserve.insert(TokenType::USDC, U256::from(1000) * U256::exp10(6));
+        min_reserve.insert(TokenType::USDT, U256::from(1000) * U256::exp10(6));
 
         Self {
             max_capital_per_fill: max_capital,
             min_capital_reserve: min_reserve,
             max_concurrent_fills: 10,
             min_profit_bps: 10,
+            stale_price_margin_bps: 50,
             source_confirmations_required: 12,
             max_intent_age_secs: 3600,
             ethereum_rpc: String::new(),
@@ -174,6 +104,9 @@ impl Default for SolverConfig {
             mantle_settlement: Address::zero(),
             ethereum_intent_pool: Address::zero(),
             mantle_intent_pool: Address::zero(),
+            ethereum_rpc_endpoints: Vec::new(),
+            mantle_rpc_endpoints: Vec::new(),
+            rpc_selection: RpcSelectionPolicy::default(),
             ethereum_chain_id: 11155111,
             mantle_chain_id: 5003,
             solver_address: Address::zero(),
@@ -182,6 +115,25 @@ impl Default for SolverConfig {
             priority_fee_gwei: U256::from(2),
             health_check_interval_secs: 30,
             balance_check_interval_secs: 60,
+            mempool_watch_enabled: false,
+            gap_fill_interval_secs: 30,
+            optimizer_enabled: true,
+            execution_report_capacity: 5000,
+            route_minimums: RouteMinimums::default(),
+            tokens: Arc::new(TokenRegistry::builtin()),
+            route_typical_amounts: HashMap::new(),
+            fee_escalation: FeeEscalationPolicy::default(),
+            balance_history: BalanceHistoryPolicy::default(),
+            registered_logs: RegisteredLogPolicy::default(),
+            metrics_snapshot: MetricsSnapshotPolicy::default(),
+            processed_intents: ProcessedIntentPolicy::default(),
+            state_store: StateStorePolicy::default(),
+            capital_at_risk: CapitalAtRiskPolicy::default(),
+            dry_run: DryRunPolicy::default(),
+            reservations: ReservationPolicy::default(),
+            settlement_fees: SettlementFeePolicy::default(),
+            partial_fills: PartialFillPolicy::default(),
+            rebalance: RebalancePolicy::default(),
         }
     }
 }
@@ -190,41 +142,100 @@ pub struct CrossChainSolver {
     pub config: SolverConfig,
     ethereum_provider: Arc<Provider<Ws>>,
     mantle_provider: Arc<Provider<Ws>>,
-    ethereum_client: Arc<SignerMiddleware<Arc<Provider<Ws>>, Wallet<SigningKey>>>,
-    mantle_client: Arc<SignerMiddleware<Arc<Provider<Ws>>, Wallet<SigningKey>>>,
-    ethereum_settlement:
-        SettlementContract<SignerMiddleware<Arc<Provider<Ws>>, Wallet<SigningKey>>>,
-    mantle_settlement: SettlementContract<SignerMiddleware<Arc<Provider<Ws>>, Wallet<SigningKey>>>,
+    ethereum_rpc_pool: Arc<RpcPool>,
+    mantle_rpc_pool: Arc<RpcPool>,
+    ethereum_client: Arc<SignerMiddleware<Arc<Provider<Ws>>, SignerHandle>>,
+    mantle_client: Arc<SignerMiddleware<Arc<Provider<Ws>>, SignerHandle>>,
+    ethereum_settlement: SettlementContract<SignerMiddleware<Arc<Provider<Ws>>, SignerHandle>>,
+    mantle_settlement: SettlementContract<SignerMiddleware<Arc<Provider<Ws>>, SignerHandle>>,
+    ethereum_tx_strategy: ChainTxStrategy,
+    mantle_tx_strategy: ChainTxStrategy,
+    ethereum_finality: FinalitySource,
+    mantle_finality: FinalitySource,
     active_fills: Arc<RwLock<HashMap<H256, ActiveFill>>>,
-    processed_intents: Arc<RwLock<HashMap<H256, bool>>>,
+    processed_intents: Arc<RwLock<ProcessedIntents>>,
+    competitor_fills: Arc<RwLock<HashMap<H256, u64>>>,
+    pending_opportunities: Arc<RwLock<HashMap<H256, FillOpportunity>>>,
+    dispatched_fills: Arc<RwLock<HashMap<H256, FillOpportunity>>>,
+    replan: Arc<Notify>,
     metrics: Arc<RwLock<SolverMetrics>>,
-    token_balances: Arc<RwLock<HashMap<(SupportedToken, u64), U256>>>,
+    /// Prometheus series behind `GET /metrics`.
+    pub prometheus: Metrics,
+    token_balances: Arc<RwLock<HashMap<(TokenType, u64), U256>>>,
+    execution_reports: Arc<RwLock<ExecutionReports>>,
+    balance_history: Arc<RwLock<BalanceHistory>>,
+    registered_logs: Arc<RwLock<RegisteredLogs>>,
+    /// Written through on every fill and claim change; `None` keeps state in
+    /// memory only.
+    state_store: Option<StateStore>,
+    /// `None` until the first balance refresh has run.
+    route_capital: Arc<RwLock<Option<Vec<RouteCapital>>>>,
     price_feed: Arc<PriceFeedManager>,
+    drain: DrainState,
+    /// `Some` in dry-run mode: fills are recorded here instead of sent.
+    dry_run: Option<RwLock<DryRunLog>>,
+    /// `Some` when intents are reserved on the relayer before filling.
+    reservations: Option<ReservationClient>,
+    /// Fee each chain's settlement contract charges, as last read.
+    settlement_fees: Arc<RwLock<SettlementFees>>,
+    /// `Some` when inventory is rebalanced between the chains.
+    rebalancer: Option<RwLock<Rebalancer>>,
+    rebalance_client: Option<RebalanceClient>,
 }
 
 impl CrossChainSolver {
-    pub async fn new(config: SolverConfig, price_feed: Arc<PriceFeedManager>) -> Result<Self> {
+    pub async fn new(
+        config: SolverConfig,
+        price_feed: Arc<PriceFeedManager>,
+        outbound_http: OutboundClient,
+    ) -> Result<Self> {
         info!("🚀 Initializing CrossChainSolver");
 
         let ethereum_provider = Arc::new(
-            Provider::<Ws>::connect(&config.ethereum_rpc)
+            Provider::<Ws>::connect_with_reconnects(&config.ethereum_rpc, WS_MAX_RECONNECTS)
                 .await
                 .context("Failed to connect to Ethereum")?,
         );
         let mantle_provider = Arc::new(
-            Provider::<Ws>::connect(&config.mantle_rpc)
+            Provider::<Ws>::connect_with_reconnects(&config.mantle_rpc, WS_MAX_RECONNECTS)
                 .await
                 .context("Failed to connect to Mantle")?,
         );
+        let ethereum_rpc_pool = Arc::new(
+            RpcPool::connect(
+                "Ethereum",
+                config.rpc_selection.clone(),
+                RpcEndpoint::with_primary(&config.ethereum_rpc, &config.ethereum_rpc_endpoints),
+                ethereum_provider.clone(),
+                WS_MAX_RECONNECTS,
+            )
+            .await,
+        );
+        let mantle_rpc_pool = Arc::new(
+            RpcPool::connect(
+                "Mantle",
+                config.rpc_selection.clone(),
+                RpcEndpoint::with_primary(&config.mantle_rpc, &config.mantle_rpc_endpoints),
+                mantle_provider.clone(),
+                WS_MAX_RECONNECTS,
+            )
+            .await,
+        );
 
-        let ethereum_wallet = config
-            .solver_private_key
-            .parse::<LocalWallet>()?
-            .with_chain_id(config.ethereum_chain_id);
-        let mantle_wallet = config
-            .solver_private_key
-            .parse::<LocalWallet>()?
-            .with_chain_id(config.mantle_chain_id);
+        let ethereum_wallet = SignerHandle::from_env(
+            SOLVER_KEY,
+            &config.solver_private_key,
+            config.ethereum_chain_id,
+        )
+        .await
+        .context("Failed to initialise Ethereum signer")?;
+        let mantle_wallet = SignerHandle::from_env(
+            SOLVER_KEY,
+            &config.solver_private_key,
+            config.mantle_chain_id,
+        )
+        .await
+        .context("Failed to initialise Mantle signer")?;
 
         let ethereum_client = Arc::new(SignerMiddleware::new(
             ethereum_provider.clone(),
@@ -240,30 +251,121 @@ impl CrossChainSolver {
         let mantle_settlement =
             SettlementContract::new(config.mantle_settlement, mantle_client.clone());
 
+        let ethereum_tx_strategy = ChainTxStrategy::from_env(
+            "ETHEREUM",
+            ChainTxStrategy {
+                max_fee_per_gas: Some(config.max_gas_price_gwei * U256::exp10(9)),
+                ..ChainTxStrategy::ethereum()
+            },
+        )
+        .context("Invalid Ethereum tx strategy")?;
+        let mantle_tx_strategy = ChainTxStrategy::from_env("MANTLE", ChainTxStrategy::mantle())
+            .context("Invalid Mantle tx strategy")?;
+        let ethereum_finality = FinalitySource::from_env("ETHEREUM", FinalitySource::ethereum())
+            .context("Invalid Ethereum finality")?;
+        let mantle_finality = FinalitySource::from_env("MANTLE", FinalitySource::mantle())
+            .context("Invalid Mantle finality")?;
+
         info!(
             "✅ Solver initialized with address: {:?}",
             config.solver_address
         );
 
+        let execution_reports = Arc::new(RwLock::new(ExecutionReports::new(
+            config.execution_report_capacity,
+        )));
+        let balance_history = Arc::new(RwLock::new(BalanceHistory::load(
+            config.balance_history.clone(),
+            chrono::Utc::now().timestamp() as u64,
+        )));
+        let registered_logs = Arc::new(RwLock::new(RegisteredLogs::load(
+            config.registered_logs.clone(),
+            chrono::Utc::now().timestamp() as u64,
+        )));
+        let started_at = chrono::Utc::now().timestamp();
+        let mut metrics = SolverMetrics {
+            process_start_time: started_at,
+            counters_since: started_at,
+            ..Default::default()
+        };
+        if let Some(snapshot) = config.metrics_snapshot.load() {
+            metrics.restore_counters(&snapshot);
+        }
+        let processed_intents = Arc::new(RwLock::new(ProcessedIntents::new(
+            config.processed_intents.clone(),
+        )));
+        let state_store = StateStore::connect(&config.state_store)
+            .context("Failed to open solver state store")?;
+        let prometheus = Metrics::new("solver").context("Failed to register metrics")?;
+        let dry_run = config.dry_run.enabled.then(|| {
+            warn!("🧪 Dry-run mode: decisions are recorded, no fills are sent");
+            RwLock::new(DryRunLog::new(config.dry_run.clone()))
+        });
+        let reservations = ReservationClient::new(
+            &config.reservations,
+            config.solver_address,
+            outbound_http.clone(),
+        );
+        if let Some(url) = &config.reservations.url {
+            info!("🎟️ Reserving intents on {} before filling", url);
+        }
+        let settlement_fees = Arc::new(RwLock::new(SettlementFees::new(&config.settlement_fees)));
+        let rebalance_client =
+            RebalanceClient::new(&config.rebalance, config.solver_address, outbound_http);
+        let rebalancer = config
+            .rebalance
+            .enabled
+            .then(|| RwLock::new(Rebalancer::new(config.rebalance.clone())));
+
         Ok(Self {
             config,
             ethereum_provider,
             mantle_provider,
+            ethereum_rpc_pool,
+            mantle_rpc_pool,
             ethereum_client,
             mantle_client,
             ethereum_settlement,
             mantle_settlement,
+            ethereum_tx_strategy,
+            mantle_tx_strategy,
+            ethereum_finality,
+            mantle_finality,
             active_fills: Arc::new(RwLock::new(HashMap::new())),
-            processed_intents: Arc::new(RwLock::new(HashMap::new())),
-            metrics: Arc::new(RwLock::new(SolverMetrics::default())),
+            processed_intents,
+            competitor_fills: Arc::new(RwLock::new(HashMap::new())),
+            pending_opportunities: Arc::new(RwLock::new(HashMap::new())),
+            dispatched_fills: Arc::new(RwLock::new(HashMap::new())),
+            replan: Arc::new(Notify::new()),
+            metrics: Arc::new(RwLock::new(metrics)),
+            prometheus,
             token_balances: Arc::new(RwLock::new(HashMap::new())),
+            execution_reports,
+            balance_history,
+            registered_logs,
+            state_store,
+            route_capital: Arc::new(RwLock::new(None)),
             price_feed,
+            drain: DrainState::default(),
+            dry_run,
+            reservations,
+            settlement_fees,
+            rebalancer,
+            rebalance_client,
         })
     }
 
     pub async fn run(self: Arc<Self>) -> Result<()> {
         info!("🏃 Starting solver main loop");
 
+        self.recover_state().await;
+        self.refresh_settlement_fees().await;
+
+        let fee_refresher = Arc::clone(&self);
+        tokio::spawn(async move {
+            fee_refresher.run_settlement_fee_refresh().await;
+        });
+
         let health_monitor = Arc::clone(&self);
         tokio::spawn(async move {
             if let Err(e) = health_monitor.run_health_checks().await {
@@ -278,6 +380,17 @@ impl CrossChainSolver {
             }
         });
 
+        for pool in [&self.ethereum_rpc_pool, &self.mantle_rpc_pool] {
+            tokio::spawn(Arc::clone(pool).run_probes());
+        }
+
+        if self.config.metrics_snapshot.path.is_some() {
+            let snapshotter = Arc::clone(&self);
+            tokio::spawn(async move {
+                snapshotter.run_metrics_snapshots().await;
+            });
+        }
+
         let fill_monitor = Arc::clone(&self);
         tokio::spawn(async move {
             if let Err(e) = fill_monitor.monitor_active_fills().await {
@@ -285,127 +398,278 @@ impl CrossChainSolver {
             }
         });
 
+        if self.rebalancer.is_some() {
+            let rebalancer = Arc::clone(&self);
+            tokio::spawn(async move {
+                rebalancer.run_rebalancer().await;
+            });
+        }
+
+        if self.config.optimizer_enabled {
+            let planner = Arc::clone(&self);
+            tokio::spawn(async move {
+                planner.run_fill_planner().await;
+            });
+        }
+
+        if self.config.mempool_watch_enabled {
+            for chain_id in [self.config.ethereum_chain_id, self.config.mantle_chain_id] {
+                let mempool_watcher = Arc::clone(&self);
+                tokio::spawn(async move {
+                    if let Err(e) = mempool_watcher.watch_pending_fills(chain_id).await {
+                        error!("Mempool watcher error on chain {}: {}", chain_id, e);
+                    }
+                });
+            }
+        }
+
         tokio::try_join!(
-            self.clone().monitor_ethereum_registered_intents(),
-            self.clone().monitor_mantle_registered_intents(),
+            self.clone()
+                .monitor_registered_intents(self.config.ethereum_chain_id),
+            self.clone()
+                .monitor_registered_intents(self.config.mantle_chain_id),
         )?;
 
         Ok(())
     }
 
-    async fn monitor_ethereum_registered_intents(self: Arc<Self>) -> Result<()> {
-        info!("👀 Monitoring Ethereum Settlement IntentRegistered events");
+    fn rpc_pool(&self, chain_id: u64) -> &RpcPool {
+        if chain_id == self.config.ethereum_chain_id {
+            &self.ethereum_rpc_pool
+        } else {
+            &self.mantle_rpc_pool
+        }
+    }
+
+    /// Stream `IntentRegistered` logs over WS, resubscribing with backoff when the
+    /// subscription drops or a lower-latency endpoint takes over. A periodic
+    /// `get_logs` gap-fill catches anything the stream missed (including blocks
+    /// produced while disconnected).
+    async fn monitor_registered_intents(self: Arc<Self>, chain_id: u64) -> Result<()> {
+        let (settlement, label) = if chain_id == self.config.ethereum_chain_id {
+            (self.config.ethereum_settlement, "Ethereum")
+        } else {
+            (self.config.mantle_settlement, "Mantle")
+        };
+        let pool = self.rpc_pool(chain_id);
+        let mut endpoint_changes = pool.watch_subscription();
 
-        let filter = Filter::new()
-            .address(self.config.ethereum_settlement)
-            .event(
-                "IntentRegistered(bytes32,bytes32,address,uint256,uint32,uint64,bytes32[],uint256)",
-            );
-        let mut last_block = self.ethereum_provider.get_block_number().await?.as_u64();
-        let mut poll_interval = interval(Duration::from_secs(12));
+        info!("👀 Monitoring {} Settlement IntentRegistered events", label);
 
-        loop {
-            poll_interval.tick().await;
+        let filter = Filter::new().address(settlement).event(
+            "IntentRegistered(bytes32,bytes32,address,uint256,uint32,uint64,bytes32[],uint256)",
+        );
+        let mut last_block = pool.backfill_provider().get_block_number().await?.as_u64();
+        let mut backoff = Duration::from_secs(1);
 
-            let current_block = match self.ethereum_provider.get_block_number().await {
-                Ok(block) => block.as_u64(),
+        loop {
+            endpoint_changes.borrow_and_update();
+            let provider = pool.subscription_provider();
+            let mut stream = match provider.subscribe_logs(&filter).await {
+                Ok(stream) => {
+                    info!("📡 Subscribed to {} IntentRegistered logs", label);
+                    backoff = Duration::from_secs(1);
+                    stream
+                }
                 Err(e) => {
-                    warn!("⚠️ Failed to get Ethereum block number: {}", e);
+                    warn!(
+                        "⚠️ {} log subscription failed: {}. Retrying in {:?}",
+                        label, e, backoff
+                    );
+                    self.gap_fill_registered_intents(chain_id, &filter, &mut last_block)
+                        .await;
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_RESUBSCRIBE_BACKOFF);
                     continue;
                 }
             };
 
-            if current_block <= last_block {
-                continue;
-            }
+            // First tick fires immediately, covering blocks missed while resubscribing.
+            let mut gap_fill = interval(Duration::from_secs(
+                self.config.gap_fill_interval_secs.max(1),
+            ));
 
-            let logs = match self
-                .ethereum_provider
-                .get_logs(
-                    &filter
-                        .clone()
-                        .from_block(last_block + 1)
-                        .to_block(current_block),
-                )
-                .await
-            {
-                Ok(logs) => logs,
-                Err(e) => {
-                    warn!("⚠️ Failed to fetch Ethereum logs: {}", e);
-                    continue;
+            loop {
+                tokio::select! {
+                    next = stream.next() => match next {
+                        Some(log) => {
+                            if log.removed == Some(true) {
+                                continue;
+                            }
+                            self.dispatch_registered_log(log, chain_id).await;
+                        }
+                        None => {
+                            warn!("⚠️ {} log subscription closed, resubscribing", label);
+                            break;
+                        }
+                    },
+                    _ = gap_fill.tick() => {
+                        self.gap_fill_registered_intents(chain_id, &filter, &mut last_block)
+                            .await;
+                    }
+                    Ok(()) = endpoint_changes.changed() => {
+                        info!("🔀 {} log subscription switching endpoint", label);
+                        break;
+                    }
                 }
-            };
+            }
+        }
+    }
 
-            for log in logs {
-                if let Err(e) = self
-                    .handle_registered_intent(log, self.config.ethereum_chain_id as u32)
-                    .await
-                {
-                    error!("❌ Error handling registered intent: {}", e);
-                    self.record_error(e.to_string()).await;
-                }
+    /// Poll `get_logs` from `last_block + 1` to head and dispatch anything found.
+    /// Runs on the pool's backfill endpoint, away from the subscription.
+    async fn gap_fill_registered_intents(
+        &self,
+        chain_id: u64,
+        filter: &Filter,
+        last_block: &mut u64,
+    ) {
+        let provider = self.rpc_pool(chain_id).backfill_provider();
+
+        let current_block = match provider.get_block_number().await {
+            Ok(block) => block.as_u64(),
+            Err(e) => {
+                warn!(
+                    "⚠️ Gap-fill failed to get block number on {}: {}",
+                    chain_id, e
+                );
+                return;
+            }
+        };
+
+        if current_block <= *last_block {
+            return;
+        }
+
+        let logs = match provider
+            .get_logs(
+                &filter
+                    .clone()
+                    .from_block(*last_block + 1)
+                    .to_block(current_block),
+            )
+            .await
+        {
+            Ok(logs) => logs,
+            Err(e) => {
+                warn!("⚠️ Gap-fill failed to fetch logs on {}: {}", chain_id, e);
+                return;
             }
+        };
+
+        if !logs.is_empty() {
+            debug!(
+                "🩹 Gap-fill found {} IntentRegistered logs on {} ({}..={})",
+                logs.len(),
+                chain_id,
+                *last_block + 1,
+                current_block
+            );
+        }
 
-            last_block = current_block;
+        for log in logs {
+            self.dispatch_registered_log(log, chain_id).await;
         }
+
+        *last_block = current_block;
     }
 
-    async fn monitor_mantle_registered_intents(self: Arc<Self>) -> Result<()> {
-        info!("👀 Monitoring Mantle Settlement IntentRegistered events");
+    async fn dispatch_registered_log(&self, log: Log, chain_id: u64) {
+        if let Err(e) = self.handle_registered_intent(log, chain_id as u32).await {
+            error!("❌ Error handling registered intent: {}", e);
+            self.record_error(e.to_string()).await;
+        }
+    }
 
-        let filter = Filter::new().address(self.config.mantle_settlement).event(
-            "IntentRegistered(bytes32,bytes32,address,uint256,uint32,uint64,bytes32[],uint256)",
-        );
-        let mut last_block = self.mantle_provider.get_block_number().await?.as_u64();
-        let mut poll_interval = interval(Duration::from_secs(3));
+    /// Watch pending transactions to the settlement contract and remember intents
+    /// that another solver is already filling.
+    async fn watch_pending_fills(self: Arc<Self>, chain_id: u64) -> Result<()> {
+        let (provider, settlement) = if chain_id == self.config.ethereum_chain_id {
+            (&self.ethereum_provider, self.config.ethereum_settlement)
+        } else {
+            (&self.mantle_provider, self.config.mantle_settlement)
+        };
 
-        loop {
-            poll_interval.tick().await;
+        let mut stream = match provider.subscribe_pending_txs().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                warn!(
+                    "⚠️ Mempool subscription not supported on chain {}: {}",
+                    chain_id, e
+                );
+                return Ok(());
+            }
+        };
 
-            let current_block = match self.mantle_provider.get_block_number().await {
-                Ok(block) => block.as_u64(),
-                Err(e) => {
-                    warn!("⚠️ Failed to get Mantle block number: {}", e);
-                    continue;
-                }
+        info!(
+            "👂 Watching mempool for competitor fills on chain {}",
+            chain_id
+        );
+
+        while let Some(tx_hash) = stream.next().await {
+            let tx = match provider.get_transaction(tx_hash).await {
+                Ok(Some(tx)) => tx,
+                _ => continue,
             };
 
-            if current_block <= last_block {
+            if tx.to != Some(settlement) || tx.from == self.config.solver_address {
                 continue;
             }
 
-            let logs = match self
-                .mantle_provider
-                .get_logs(
-                    &filter
-                        .clone()
-                        .from_block(last_block + 1)
-                        .to_block(current_block),
-                )
-                .await
-            {
-                Ok(logs) => logs,
-                Err(e) => {
-                    warn!("⚠️ Failed to fetch Mantle logs: {}", e);
-                    continue;
-                }
+            let Ok(call) = FillIntentCall::decode(&tx.input) else {
+                continue;
             };
 
-            for log in logs {
-                if let Err(e) = self
-                    .handle_registered_intent(log, self.config.mantle_chain_id as u32)
-                    .await
-                {
-                    error!("❌ Error handling registered intent: {}", e);
-                    self.record_error(e.to_string()).await;
-                }
+            let intent_id = H256::from(call.intent_id);
+            info!(
+                "👀 Competitor fill pending | Intent: {:?} | Solver: {:?} | Tx: {:?}",
+                intent_id, tx.from, tx_hash
+            );
+
+            let now = chrono::Utc::now().timestamp() as u64;
+            {
+                let mut competitor_fills = self.competitor_fills.write().await;
+                competitor_fills.retain(|_, seen_at| now.saturating_sub(*seen_at) < 600);
+                competitor_fills.insert(intent_id, now);
             }
 
-            last_block = current_block;
+            let mut metrics = self.metrics.write().await;
+            metrics.competitor_fills_detected += 1;
+        }
+
+        warn!("⚠️ Mempool subscription ended on chain {}", chain_id);
+        Ok(())
+    }
+
+    async fn abort_if_competitor_pending(&self, intent_id: H256) -> Result<()> {
+        if !self.competitor_fills.read().await.contains_key(&intent_id) {
+            return Ok(());
         }
+
+        warn!(
+            "🏁 Aborting fill, competitor fill already pending | Intent: {:?}",
+            intent_id
+        );
+
+        let mut metrics = self.metrics.write().await;
+        metrics.fills_aborted_for_competitor += 1;
+
+        Err(anyhow!("Competitor fill pending in mempool"))
     }
 
     async fn handle_registered_intent(&self, log: Log, chain_where_detected: u32) -> Result<()> {
+        let log_key = LogKey::of(&log, chain_where_detected as u64);
+        if let Some(key) = &log_key
+            && self.registered_logs.read().await.contains(key)
+        {
+            debug!(
+                "⏭️ Duplicate IntentRegistered delivery {:?}#{} ignored",
+                key.tx_hash, key.log_index
+            );
+            self.metrics.write().await.duplicate_deliveries += 1;
+            return Ok(());
+        }
+
         let settlement = if chain_where_detected == self.config.ethereum_chain_id as u32 {
             &self.ethereum_settlement
         } else {
@@ -422,18 +686,26 @@ impl CrossChainSolver {
 
         let intent_id = H256::from(event.intent_id);
 
+        if self.drain.is_draining() {
+            debug!("🚰 Draining, ignoring intent {:?}", intent_id);
+            return Ok(());
+        }
+
         // Immediate check-and-insert to prevent concurrent processing
-        {
-            let mut processed = self.processed_intents.write().await;
-            if processed.contains_key(&intent_id) {
-                debug!(
-                    "⏭️ Intent {:?} is already processed or cooling down",
-                    intent_id
-                );
-                return Ok(());
-            }
-            processed.insert(intent_id, true);
+        let claimed = self.processed_intents.write().await.try_claim(
+            intent_id,
+            event.deadline,
+            chrono::Utc::now().timestamp() as u64,
+        );
+        if !claimed {
+            debug!(
+                "⏭️ Intent {:?} is already processed or cooling down",
+                intent_id
+            );
+            self.metrics.write().await.duplicate_deliveries += 1;
+            return Ok(());
         }
+        self.persist_claim(intent_id).await;
 
         // Execute the actual filling logic
         match self
@@ -442,6 +714,18 @@ impl CrossChainSolver {
         {
             Ok(_) => {
                 info!("✅ Successfully processed intent {:?}", intent_id);
+                if let Some(key) = log_key
+                    && let Err(e) = self.registered_logs.write().await.record(
+                        key,
+                        intent_id,
+                        chrono::Utc::now().timestamp() as u64,
+                    )
+                {
+                    warn!(
+                        "⚠️ Failed to persist handled log for {:?}: {}",
+                        intent_id, e
+                    );
+                }
                 Ok(())
             }
             Err(e) => {
@@ -450,20 +734,48 @@ impl CrossChainSolver {
                     intent_id, e
                 );
 
-                // Unlock the intent after 12 seconds to allow the solver to try again
-                let processed_cache = self.processed_intents.clone();
-                tokio::spawn(async move {
-                    tokio::time::sleep(Duration::from_secs(12)).await;
-                    let mut processed = processed_cache.write().await;
-                    processed.remove(&intent_id);
-                    debug!("♻️ Intent {:?} lock released for retries", intent_id);
-                });
+                self.execution_reports
+                    .write()
+                    .await
+                    .failed(&intent_id, e.to_string());
+                self.release_intent_after(intent_id, RETRY_UNLOCK_DELAY);
 
                 Err(e)
             }
         }
     }
 
+    /// Unlock the intent after `delay` to allow the solver to try again.
+    fn release_intent_after(&self, intent_id: H256, delay: Duration) {
+        let processed_cache = self.processed_intents.clone();
+        let state_store = self.state_store.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            processed_cache.write().await.release(&intent_id);
+            if let Some(store) = &state_store
+                && let Err(e) = store.release_intent(&intent_id)
+            {
+                warn!(
+                    "⚠️ Failed to release stored claim on {:?}: {:#}",
+                    intent_id, e
+                );
+            }
+            debug!("♻️ Intent {:?} lock released for retries", intent_id);
+        });
+    }
+
+    async fn persist_claim(&self, intent_id: H256) {
+        let Some(store) = &self.state_store else {
+            return;
+        };
+        let Some(expires_at) = self.processed_intents.read().await.expires_at(&intent_id) else {
+            return;
+        };
+        if let Err(e) = store.claim_intent(intent_id, expires_at) {
+            warn!("⚠️ Failed to persist claim on {:?}: {:#}", intent_id, e);
+        }
+    }
+
     async fn process_intent_logic(
         &self,
         log: Log,
@@ -480,35 +792,65 @@ impl CrossChainSolver {
             dest_chain: chain_where_detected,
             source_block: log.block_number.context("Missing block number")?.as_u64(),
             detected_at: chrono::Utc::now().timestamp() as u64,
+            deadline: event.deadline,
         };
 
+        self.execution_reports.write().await.detected(&intent);
+
+        if self.is_own_rebalance(&intent.intent_id).await {
+            info!(
+                "⏭️ Skipping intent {:?}: our own rebalance transfer",
+                intent.intent_id
+            );
+            self.execution_reports.write().await.decided(
+                &intent.intent_id,
+                ExecutionStatus::Rejected,
+                DecisionRecord {
+                    reason: Some("Own rebalance transfer".to_string()),
+                    ..Default::default()
+                },
+            );
+            return Ok(());
+        }
+
+        if let Some(dest) = Chain::from_id(intent.dest_chain as u64)
+            && let Err(e) = self
+                .config
+                .route_minimums
+                .check(intent.token_type, dest, intent.amount)
+        {
+            info!("⏭️ Skipping intent {:?}: {}", intent.intent_id, e);
+            self.execution_reports.write().await.decided(
+                &intent.intent_id,
+                ExecutionStatus::Rejected,
+                DecisionRecord {
+                    reason: Some(e.to_string()),
+                    ..Default::default()
+                },
+            );
+            return Ok(());
+        }
+
         let now = chrono::Utc::now().timestamp() as u64;
         if event.deadline <= now {
             return Err(anyhow!("Intent expired"));
         }
 
-        let provider = if chain_where_detected == self.config.ethereum_chain_id as u32 {
-            &self.ethereum_provider
-        } else {
-            &self.mantle_provider
-        };
-
-        // Confirmation Wait Loop
-        let required_confirmations = 2;
-        let mut attempts = 0;
-        loop {
-            let current_block = provider.get_block_number().await?.as_u64();
-            let confirmations = current_block.saturating_sub(intent.source_block);
-
-            if confirmations >= required_confirmations {
-                break;
-            }
-            if attempts >= 60 {
+        // Wait for the registration to become final
+        let (provider, finality) = self.finality_for(chain_where_detected);
+        let started = std::time::Instant::now();
+        while !finality
+            .is_final(provider.as_ref(), intent.source_block)
+            .await?
+        {
+            if started.elapsed() >= FINALITY_WAIT_TIMEOUT {
                 return Err(anyhow!("Confirmation timeout"));
             }
+            if event.deadline <= chrono::Utc::now().timestamp() as u64 {
+                return Err(anyhow!("Intent expired"));
+            }
 
-            tokio::time::sleep(Duration::from_secs(1)).await;
-            attempts += 1;
+            tokio::time::sleep(FINALITY_POLL_INTERVAL).await;
         }
 
         // On-chain verification
@@ -528,30 +870,386 @@ impl CrossChainSolver {
         }
 
         let opportunity = self.evaluate_fill_opportunity(&intent).await?;
-        if self.should_fill(&opportunity).await? {
-            if chain_where_detected == self.config.mantle_chain_id as u32 {
-                self.execute_fill_on_mantle(&intent, &opportunity).await?;
-            } else {
-                self.execute_fill_on_ethereum(&intent, &opportunity).await?;
+        self.execution_reports.write().await.evaluated(&opportunity);
+
+        if self.config.optimizer_enabled {
+            match self.is_fill_candidate(&opportunity)? {
+                None => {
+                    self.record_decision(
+                        &opportunity,
+                        ExecutionStatus::Queued,
+                        DecisionRecord::default(),
+                    )
+                    .await;
+                    self.enqueue_opportunity(opportunity).await;
+                }
+                Some(reason) => self.reject(&opportunity, reason).await,
             }
+            return Ok(());
+        }
+
+        if self.should_fill(&opportunity).await? {
+            self.execute_fill(&opportunity).await?;
         }
 
         Ok(())
     }
 
-    async fn execute_fill_on_ethereum(
-        &self,
-        intent: &DetectedIntent,
-        opportunity: &FillOpportunity,
-    ) -> Result<()> {
-        info!("🔨 Executing fill on Ethereum: {:?}", intent.intent_id);
+    async fn execute_fill(&self, opportunity: &FillOpportunity) -> Result<()> {
+        // Counted before the check so a drain never reports done while a fill
+        // that got past it is still running
+        let _fill = self.drain.begin_fill();
+        if self.drain.is_draining() {
+            self.reject(opportunity, "Solver is draining".to_string())
+                .await;
+            return Ok(());
+        }
 
-        self.verify_provider_health(self.config.ethereum_chain_id)
-            .await
-            .context("Provider health check failed")?;
+        // An error rather than a rejection, so the intent is retried once
+        // settlements catch up
+        if let Some(reason) = self.capital_at_risk_block(opportunity).await? {
+            warn!(
+                "⏸️ FILL PAUSED - {} | Intent: {:?}",
+                reason, opportunity.intent.intent_id
+            );
+            return Err(anyhow!(reason));
+        }
 
-        let (
-            _commitment_check,
+        if self.dry_run.is_some() {
+            info!(
+                "🧪 DRY RUN - Would fill | Profit: {}bps (${:.4}) | Intent: {:?}",
+                opportunity.profit_bps, opportunity.profit_usd, opportunity.intent.intent_id
+            );
+            self.record_dry_run(opportunity, true, None).await;
+            self.execution_reports
+                .write()
+                .await
+                .simulated(&opportunity.intent.intent_id);
+            return Ok(());
+        }
+
+        let intent_id = opportunity.intent.intent_id;
+        let reserved = match &self.reservations {
+            Some(reservations) => match reservations.reserve(intent_id).await {
+                Ok(Reservation::Granted { expires_at }) => {
+                    debug!("🎟️ Reserved {:?} until {}", intent_id, expires_at);
+                    true
+                }
+                Ok(Reservation::Refused(reason)) => {
+                    self.reject(opportunity, format!("Reservation refused: {}", reason))
+                        .await;
+                    return Ok(());
+                }
+                // The relayer is only a coordination aid; without it the
+                // settlement contract still settles races on chain
+                Err(e) => {
+                    warn!(
+                        "⚠️ Could not reserve {:?}, filling on chain: {:#}",
+                        intent_id, e
+                    );
+                    false
+                }
+            },
+            None => false,
+        };
+
+        let result = match self.fill_chunks(&opportunity.intent) {
+            Some(chunks) => self.execute_chunked_fill(opportunity, chunks).await,
+            None => {
+                self.execute_fill_on_dest(&opportunity.intent, opportunity)
+                    .await
+            }
+        };
+
+        if result.is_err()
+            && reserved
+            && let Some(reservations) = &self.reservations
+            && let Err(e) = reservations.release(intent_id).await
+        {
+            warn!("⚠️ Failed to release reservation {:?}: {:#}", intent_id, e);
+        }
+        result
+    }
+
+    async fn execute_fill_on_dest(
+        &self,
+        intent: &DetectedIntent,
+        opportunity: &FillOpportunity,
+    ) -> Result<()> {
+        if intent.dest_chain == self.config.mantle_chain_id as u32 {
+            self.execute_fill_on_mantle(intent, opportunity).await
+        } else {
+            self.execute_fill_on_ethereum(intent, opportunity).await
+        }
+    }
+
+    /// The chunks an intent above its token's `max_capital_per_fill` is
+    /// filled in, if partial fills are enabled and it needs few enough.
+    fn fill_chunks(&self, intent: &DetectedIntent) -> Option<Vec<U256>> {
+        let max_capital = self.config.max_capital_per_fill.get(&intent.token_type)?;
+        self.config
+            .partial_fills
+            .chunks(intent.amount, *max_capital)
+    }
+
+    /// Send the chunks one after another, each as its own fill tx. Chunks a
+    /// previous attempt already sent are skipped, and the first failure
+    /// stops the rest so the intent is retried from there.
+    async fn execute_chunked_fill(
+        &self,
+        opportunity: &FillOpportunity,
+        chunks: Vec<U256>,
+    ) -> Result<()> {
+        let intent_id = opportunity.intent.intent_id;
+        let sent = self
+            .active_fills
+            .read()
+            .await
+            .get(&intent_id)
+            .filter(|fill| matches!(fill.status, FillStatus::Pending | FillStatus::Confirmed))
+            .map_or(U256::zero(), |fill| fill.filled_amount);
+
+        let total = chunks.len();
+        info!(
+            "🧩 Filling {} of intent {:?} in {} chunks",
+            opportunity.intent.amount, intent_id, total
+        );
+
+        let mut covered = U256::zero();
+        for (i, chunk) in chunks.into_iter().enumerate() {
+            covered = covered.saturating_add(chunk);
+            if covered <= sent {
+                continue;
+            }
+
+            info!(
+                "🧩 Chunk {}/{}: {} | Intent: {:?}",
+                i + 1,
+                total,
+                chunk,
+                intent_id
+            );
+            let intent = DetectedIntent {
+                amount: chunk,
+                ..opportunity.intent.clone()
+            };
+            self.execute_fill_on_dest(&intent, &opportunity.chunk(chunk))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn enqueue_opportunity(&self, opportunity: FillOpportunity) {
+        let intent_id = opportunity.intent.intent_id;
+        let pending = {
+            let mut pending = self.pending_opportunities.write().await;
+            pending.insert(intent_id, opportunity);
+            pending.len()
+        };
+
+        info!(
+            "🗂️ Queued opportunity {:?} for planning ({} pending)",
+            intent_id, pending
+        );
+
+        self.metrics.write().await.pending_opportunities = pending;
+        self.replan.notify_one();
+    }
+
+    /// Re-plan whenever an opportunity is queued or a fill finishes, with a
+    /// periodic tick as a fallback.
+    async fn run_fill_planner(self: Arc<Self>) {
+        info!("🧮 Fill optimizer enabled");
+
+        let mut tick = interval(REPLAN_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = self.replan.notified() => {}
+                _ = tick.tick() => {}
+            }
+
+            if let Err(e) = self.plan_and_dispatch().await {
+                error!("❌ Fill planning failed: {}", e);
+                self.record_error(e.to_string()).await;
+            }
+        }
+    }
+
+    async fn plan_and_dispatch(self: &Arc<Self>) -> Result<()> {
+        if self.drain.is_draining() {
+            return Ok(());
+        }
+
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        let candidates: Vec<FillOpportunity> = {
+            let mut pending = self.pending_opportunities.write().await;
+            pending.retain(|intent_id, opportunity| {
+                let fresh = now.saturating_sub(opportunity.intent.detected_at)
+                    < self.config.max_intent_age_secs;
+                if !fresh {
+                    warn!("⌛ Dropping stale opportunity {:?}", intent_id);
+                }
+                fresh
+            });
+            pending.values().cloned().collect()
+        };
+
+        if candidates.is_empty() {
+            self.metrics.write().await.pending_opportunities = 0;
+            return Ok(());
+        }
+
+        let (in_flight, locked) = self.committed_capital().await;
+        let slots = self.config.max_concurrent_fills.saturating_sub(in_flight);
+        if slots == 0 {
+            debug!(
+                "⏸️ No fill slots free, {} opportunities waiting",
+                candidates.len()
+            );
+            return Ok(());
+        }
+
+        let buckets: HashSet<CapitalKey> = candidates.iter().map(capital_key).collect();
+        let mut available = HashMap::new();
+        for (token, chain) in buckets {
+            let balance = self.get_token_balance(token, chain as u64).await?;
+            let committed = locked.get(&(token, chain)).copied().unwrap_or_default();
+            available.insert((token, chain), balance.saturating_sub(committed));
+        }
+
+        let plan = plan_fills(&candidates, &available, slots);
+        if plan.is_empty() {
+            debug!(
+                "⏸️ No affordable opportunities among {} pending",
+                candidates.len()
+            );
+            return Ok(());
+        }
+
+        info!(
+            "🧮 Planned {}/{} pending opportunities ({} slots free)",
+            plan.len(),
+            candidates.len(),
+            slots
+        );
+
+        let chosen: Vec<FillOpportunity> = {
+            let mut pending = self.pending_opportunities.write().await;
+            let chosen = plan
+                .iter()
+                .filter_map(|intent_id| pending.remove(intent_id))
+                .collect();
+            self.metrics.write().await.pending_opportunities = pending.len();
+            chosen
+        };
+
+        {
+            let mut dispatched = self.dispatched_fills.write().await;
+            for opportunity in &chosen {
+                dispatched.insert(opportunity.intent.intent_id, opportunity.clone());
+            }
+        }
+
+        for opportunity in &chosen {
+            let bucket = available
+                .get(&capital_key(opportunity))
+                .copied()
+                .unwrap_or_default();
+            self.record_decision(
+                opportunity,
+                ExecutionStatus::Approved,
+                DecisionRecord {
+                    available: Some(bucket.to_string()),
+                    required: Some(capital_with_margin(opportunity).to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
+        }
+
+        for opportunity in chosen {
+            let solver = Arc::clone(self);
+            tokio::spawn(async move {
+                let intent_id = opportunity.intent.intent_id;
+
+                if let Err(e) = solver.execute_fill(&opportunity).await {
+                    warn!(
+                        "❌ Planned fill {:?} failed: {}. Clearing lock for retry in 12s...",
+                        intent_id, e
+                    );
+                    solver.record_error(e.to_string()).await;
+                    solver
+                        .execution_reports
+                        .write()
+                        .await
+                        .failed(&intent_id, e.to_string());
+                    // Queuing counted as handled; a redelivery may retry now
+                    if let Err(e) = solver
+                        .registered_logs
+                        .write()
+                        .await
+                        .forget_intent(intent_id)
+                    {
+                        warn!("⚠️ Failed to forget handled log for {:?}: {}", intent_id, e);
+                    }
+                    solver.release_intent_after(intent_id, RETRY_UNLOCK_DELAY);
+                }
+
+                solver.dispatched_fills.write().await.remove(&intent_id);
+                solver.replan.notify_one();
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Fills occupying a slot and the capital they hold per bucket, counting
+    /// both dispatched fills and sent fills that are not yet claimed.
+    async fn committed_capital(&self) -> (usize, HashMap<CapitalKey, U256>) {
+        let dispatched = self.dispatched_fills.read().await;
+        let active = self.active_fills.read().await;
+
+        let mut locked: HashMap<CapitalKey, U256> = HashMap::new();
+        let mut in_flight = 0;
+
+        for opportunity in dispatched.values() {
+            let entry = locked.entry(capital_key(opportunity)).or_default();
+            *entry = entry.saturating_add(opportunity.capital_required);
+            in_flight += 1;
+        }
+
+        for fill in active.values() {
+            if dispatched.contains_key(&fill.intent_id)
+                || !(fill.status == FillStatus::Pending || fill.status == FillStatus::Confirmed)
+            {
+                continue;
+            }
+            let entry = locked
+                .entry((fill.token_type, fill.dest_chain))
+                .or_default();
+            *entry = entry.saturating_add(fill.amount);
+            in_flight += 1;
+        }
+
+        (in_flight, locked)
+    }
+
+    async fn execute_fill_on_ethereum(
+        &self,
+        intent: &DetectedIntent,
+        opportunity: &FillOpportunity,
+    ) -> Result<()> {
+        info!("🔨 Executing fill on Ethereum: {:?}", intent.intent_id);
+
+        self.verify_provider_health(self.config.ethereum_chain_id)
+            .await
+            .context("Provider health check failed")?;
+
+        let (
+            _commitment_check,
             _token_check,
             _amount_check,
             _source_chain_check,
@@ -577,11 +1275,16 @@ impl CrossChainSolver {
             .await
             .context("Failed to check fill status")?;
 
-        if solver_check != Address::zero() {
+        // Later chunks find the intent already filled by us
+        let own_partial =
+            intent.amount < opportunity.intent.amount && solver_check == self.config.solver_address;
+        if solver_check != Address::zero() && !own_partial {
             warn!("⚠️ Intent already filled by solver: {:?}", solver_check);
             return Err(anyhow!("Intent already filled"));
         }
 
+        self.abort_if_competitor_pending(intent.intent_id).await?;
+
         info!("🔍 Pre-flight balance check...");
         let current_balance = self
             .fetch_balance_inner(intent.token_type, self.config.ethereum_chain_id)
@@ -673,32 +1376,46 @@ impl CrossChainSolver {
             }
         };
 
-        let gas_with_buffer = gas_estimate.saturating_mul(U256::from(120)) / U256::from(100);
-        let tx = tx.gas(gas_with_buffer);
+        let gas_with_buffer = self.ethereum_tx_strategy.scale_gas_limit(gas_estimate);
+        let tx = self
+            .ethereum_tx_strategy
+            .prepare(self.ethereum_client.as_ref(), tx.gas(gas_with_buffer))
+            .await?;
+        let fill_tx = self
+            .price_fill_tx(&self.ethereum_client, tx.tx, opportunity, TokenType::ETH)
+            .await?;
+
+        self.abort_if_competitor_pending(intent.intent_id).await?;
 
         info!("📤 Sending fill transaction...");
-        let pending_tx = tx.send().await.context("Failed to send fill transaction")?;
+        let pending_tx = self
+            .ethereum_client
+            .send_transaction(fill_tx.clone(), None)
+            .await
+            .context("Failed to send fill transaction")?;
 
         let tx_hash = pending_tx.tx_hash();
         info!("✅ Fill tx sent: {:?}", tx_hash);
-
-        {
-            let mut active = self.active_fills.write().await;
-            active.insert(
-                intent.intent_id,
-                ActiveFill {
-                    intent_id: intent.intent_id,
-                    tx_hash,
-                    amount: intent.amount,
-                    token: intent.token,
-                    token_type: intent.token_type,
-                    filled_at: chrono::Utc::now().timestamp() as u64,
-                    confirmed_at: None,
-                    status: FillStatus::Pending,
-                    dest_chain: self.config.ethereum_chain_id as u32,
-                },
-            );
-        }
+        self.execution_reports
+            .write()
+            .await
+            .fill_sent(&intent.intent_id, tx_hash);
+
+        let started = self
+            .track_fill(ActiveFill {
+                intent_id: intent.intent_id,
+                tx_hash,
+                amount: opportunity.intent.amount,
+                filled_amount: intent.amount,
+                chunks: 1,
+                token: intent.token,
+                token_type: intent.token_type,
+                filled_at: chrono::Utc::now().timestamp() as u64,
+                confirmed_at: None,
+                status: FillStatus::Pending,
+                dest_chain: self.config.ethereum_chain_id as u32,
+            })
+            .await;
 
         {
             let mut metrics = self.metrics.write().await;
@@ -707,20 +1424,33 @@ impl CrossChainSolver {
                 .capital_deployed
                 .entry(intent.token_type)
                 .or_insert(U256::zero()) += opportunity.capital_required;
-            metrics.active_fills_count += 1;
+            if started {
+                metrics.active_fills_count += 1;
+            }
         }
 
-        match pending_tx.await? {
+        let receipt = self
+            .await_fill_receipt(
+                &self.ethereum_client,
+                opportunity,
+                TokenType::ETH,
+                fill_tx,
+                tx_hash,
+            )
+            .await?;
+
+        match receipt {
             Some(receipt) => {
                 if receipt.status == Some(0.into()) {
-                    error!("❌ Fill tx reverted: {:?}", tx_hash);
-                    let mut active = self.active_fills.write().await;
-                    if let Some(fill) = active.get_mut(&intent.intent_id) {
-                        fill.status = FillStatus::Failed;
-                    }
+                    error!("❌ Fill tx reverted: {:?}", receipt.transaction_hash);
+                    self.prometheus
+                        .fill(self.chain_key(intent.dest_chain), false);
+                    let failed = self.fail_fill_chunk(intent.intent_id, intent.amount).await;
                     let mut metrics = self.metrics.write().await;
                     metrics.failed_fills += 1;
-                    metrics.active_fills_count = metrics.active_fills_count.saturating_sub(1);
+                    if failed {
+                        metrics.active_fills_count = metrics.active_fills_count.saturating_sub(1);
+                    }
                     return Err(anyhow!("Transaction reverted"));
                 }
 
@@ -728,18 +1458,30 @@ impl CrossChainSolver {
                     "✅ Fill confirmed in block: {}",
                     receipt.block_number.unwrap()
                 );
-                let mut active = self.active_fills.write().await;
-                if let Some(fill) = active.get_mut(&intent.intent_id) {
+                self.prometheus
+                    .fill(self.chain_key(intent.dest_chain), true);
+                self.execution_reports
+                    .write()
+                    .await
+                    .fill_confirmed(&intent.intent_id, receipt.block_number.map(|b| b.as_u64()));
+                self.update_fill(intent.intent_id, |fill| {
                     fill.status = FillStatus::Confirmed;
                     fill.confirmed_at = Some(chrono::Utc::now().timestamp() as u64);
-                }
+                })
+                .await;
+                *self
+                    .metrics
+                    .write()
+                    .await
+                    .total_profit_earned
+                    .entry(intent.token_type)
+                    .or_default() += opportunity.estimated_profit;
             }
             None => {
                 error!("❌ Fill tx dropped: {:?}", tx_hash);
-                let mut active = self.active_fills.write().await;
-                if let Some(fill) = active.get_mut(&intent.intent_id) {
-                    fill.status = FillStatus::Failed;
-                }
+                self.prometheus
+                    .fill(self.chain_key(intent.dest_chain), false);
+                self.fail_fill_chunk(intent.intent_id, intent.amount).await;
                 return Err(anyhow!("Transaction dropped"));
             }
         }
@@ -785,11 +1527,16 @@ impl CrossChainSolver {
             .await
             .context("Failed to check fill status")?;
 
-        if solver_check != Address::zero() {
+        // Later chunks find the intent already filled by us
+        let own_partial =
+            intent.amount < opportunity.intent.amount && solver_check == self.config.solver_address;
+        if solver_check != Address::zero() && !own_partial {
             warn!("⚠️ Intent already filled by solver: {:?}", solver_check);
             return Err(anyhow!("Intent already filled"));
         }
 
+        self.abort_if_competitor_pending(intent.intent_id).await?;
+
         info!("🔍 Pre-flight balance check...");
         let current_balance = self
             .fetch_balance_inner(intent.token_type, self.config.mantle_chain_id)
@@ -881,32 +1628,46 @@ impl CrossChainSolver {
             }
         };
 
-        let gas_with_buffer = gas_estimate.saturating_mul(U256::from(120)) / U256::from(100);
-        let tx = tx.gas(gas_with_buffer);
+        let gas_with_buffer = self.mantle_tx_strategy.scale_gas_limit(gas_estimate);
+        let tx = self
+            .mantle_tx_strategy
+            .prepare(self.mantle_client.as_ref(), tx.gas(gas_with_buffer))
+            .await?;
+        let fill_tx = self
+            .price_fill_tx(&self.mantle_client, tx.tx, opportunity, TokenType::MNT)
+            .await?;
+
+        self.abort_if_competitor_pending(intent.intent_id).await?;
 
         info!("📤 Sending fill transaction...");
-        let pending_tx = tx.send().await.context("Failed to send fillIntent tx")?;
+        let pending_tx = self
+            .mantle_client
+            .send_transaction(fill_tx.clone(), None)
+            .await
+            .context("Failed to send fillIntent tx")?;
 
         let tx_hash = pending_tx.tx_hash();
         info!("✅ Fill tx sent: {:?}", tx_hash);
-
-        {
-            let mut active = self.active_fills.write().await;
-            active.insert(
-                intent.intent_id,
-                ActiveFill {
-                    intent_id: intent.intent_id,
-                    tx_hash,
-                    amount: intent.amount,
-                    token: intent.token,
-                    token_type: intent.token_type,
-                    filled_at: chrono::Utc::now().timestamp() as u64,
-                    confirmed_at: None,
-                    status: FillStatus::Pending,
-                    dest_chain: self.config.mantle_chain_id as u32,
-                },
-            );
-        }
+        self.execution_reports
+            .write()
+            .await
+            .fill_sent(&intent.intent_id, tx_hash);
+
+        let started = self
+            .track_fill(ActiveFill {
+                intent_id: intent.intent_id,
+                tx_hash,
+                amount: opportunity.intent.amount,
+                filled_amount: intent.amount,
+                chunks: 1,
+                token: intent.token,
+                token_type: intent.token_type,
+                filled_at: chrono::Utc::now().timestamp() as u64,
+                confirmed_at: None,
+                status: FillStatus::Pending,
+                dest_chain: self.config.mantle_chain_id as u32,
+            })
+            .await;
 
         {
             let mut metrics = self.metrics.write().await;
@@ -915,20 +1676,33 @@ impl CrossChainSolver {
                 .capital_deployed
                 .entry(intent.token_type)
                 .or_insert(U256::zero()) += opportunity.capital_required;
-            metrics.active_fills_count += 1;
+            if started {
+                metrics.active_fills_count += 1;
+            }
         }
 
-        match pending_tx.await? {
+        let receipt = self
+            .await_fill_receipt(
+                &self.mantle_client,
+                opportunity,
+                TokenType::MNT,
+                fill_tx,
+                tx_hash,
+            )
+            .await?;
+
+        match receipt {
             Some(receipt) => {
                 if receipt.status == Some(0.into()) {
-                    error!("❌ Fill tx reverted: {:?}", tx_hash);
-                    let mut active = self.active_fills.write().await;
-                    if let Some(fill) = active.get_mut(&intent.intent_id) {
-                        fill.status = FillStatus::Failed;
-                    }
+                    error!("❌ Fill tx reverted: {:?}", receipt.transaction_hash);
+                    self.prometheus
+                        .fill(self.chain_key(intent.dest_chain), false);
+                    let failed = self.fail_fill_chunk(intent.intent_id, intent.amount).await;
                     let mut metrics = self.metrics.write().await;
                     metrics.failed_fills += 1;
-                    metrics.active_fills_count = metrics.active_fills_count.saturating_sub(1);
+                    if failed {
+                        metrics.active_fills_count = metrics.active_fills_count.saturating_sub(1);
+                    }
                     return Err(anyhow!("Transaction reverted"));
                 }
 
@@ -936,18 +1710,30 @@ impl CrossChainSolver {
                     "✅ Fill confirmed in block: {}",
                     receipt.block_number.unwrap()
                 );
-                let mut active = self.active_fills.write().await;
-                if let Some(fill) = active.get_mut(&intent.intent_id) {
+                self.prometheus
+                    .fill(self.chain_key(intent.dest_chain), true);
+                self.execution_reports
+                    .write()
+                    .await
+                    .fill_confirmed(&intent.intent_id, receipt.block_number.map(|b| b.as_u64()));
+                self.update_fill(intent.intent_id, |fill| {
                     fill.status = FillStatus::Confirmed;
                     fill.confirmed_at = Some(chrono::Utc::now().timestamp() as u64);
-                }
+                })
+                .await;
+                *self
+                    .metrics
+                    .write()
+                    .await
+                    .total_profit_earned
+                    .entry(intent.token_type)
+                    .or_default() += opportunity.estimated_profit;
             }
             None => {
                 error!("❌ Fill tx dropped: {:?}", tx_hash);
-                let mut active = self.active_fills.write().await;
-                if let Some(fill) = active.get_mut(&intent.intent_id) {
-                    fill.status = FillStatus::Failed;
-                }
+                self.prometheus
+                    .fill(self.chain_key(intent.dest_chain), false);
+                self.fail_fill_chunk(intent.intent_id, intent.amount).await;
                 return Err(anyhow!("Transaction dropped"));
             }
         }
@@ -955,65 +1741,343 @@ impl CrossChainSolver {
         Ok(())
     }
 
-    async fn evaluate_fill_opportunity(&self, intent: &DetectedIntent) -> Result<FillOpportunity> {
-        let settlement_fee_bps = 200u128;
-        let fee_amount = intent.amount * U256::from(settlement_fee_bps) / U256::from(10000);
-        let gas_estimate = self.estimate_fill_gas(intent).await?;
+    fn max_fee_per_gas(&self) -> U256 {
+        self.config.max_gas_price_gwei * U256::exp10(9)
+    }
 
-        info!(
-            "💰 Evaluating opportunity for intent: {:?}",
-            intent.intent_id
-        );
+    /// Share of the fill's profit that fee escalation may spend, in wei of the
+    /// destination chain's gas token.
+    async fn escalation_budget(&self, opportunity: &FillOpportunity, gas_token: TokenType) -> U256 {
+        match self.price_feed.usd_price(gas_token).await {
+            Ok(price) => self
+                .config
+                .fee_escalation
+                .profit_budget(opportunity.profit_usd, price),
+            Err(e) => {
+                warn!(
+                    "⚠️ No {} price, fill fees will not be escalated: {}",
+                    gas_token.symbol(),
+                    e
+                );
+                U256::zero()
+            }
+        }
+    }
 
-        let fee_value_usd = self
-            .get_token_price_usd(intent.token_type, fee_amount)
-            .await?;
-        let gas_cost_usd = self.get_gas_cost_usd(gas_estimate).await?;
-        let intent_value_usd = self
-            .get_token_price_usd(intent.token_type, intent.amount)
-            .await?;
+    /// Escalate a priced fill for the time left before the intent's deadline
+    /// and pin its nonce, so replacements can reuse it.
+    async fn price_fill_tx(
+        &self,
+        client: &SignerMiddleware<Arc<Provider<Ws>>, SignerHandle>,
+        mut tx: TypedTransaction,
+        opportunity: &FillOpportunity,
+        gas_token: TokenType,
+    ) -> Result<TypedTransaction> {
+        let remaining = opportunity
+            .intent
+            .deadline
+            .saturating_sub(chrono::Utc::now().timestamp() as u64);
+        let budget = self.escalation_budget(opportunity, gas_token).await;
+
+        let multiplier =
+            self.config
+                .fee_escalation
+                .escalate(&mut tx, remaining, budget, self.max_fee_per_gas());
+        if multiplier > 100 {
+            info!(
+                "🔺 Escalating fill fees up to {}% ({}s before deadline)",
+                multiplier, remaining
+            );
+        }
 
-        let profit_usd = fee_value_usd - gas_cost_usd;
+        client
+            .fill_transaction(&mut tx, None)
+            .await
+            .context("Failed to assign nonce to fill transaction")?;
 
-        let estimated_profit = if profit_usd > 0.0 {
-            let profit_per_usd = fee_amount.as_u128() as f64 / fee_value_usd;
-            U256::from((profit_usd * profit_per_usd) as u128)
-        } else {
-            U256::zero()
-        };
+        Ok(tx)
+    }
 
-        let profit_bps = if intent_value_usd > 0.0 {
-            ((profit_usd / intent_value_usd) * 10000.0).max(0.0) as u16
-        } else {
-            0
-        };
+    /// Wait for a sent fill, re-sending it at the same nonce with escalated
+    /// fees while it sits unmined. Returns the receipt of whichever version was
+    /// mined, or `None` once every version has left the mempool.
+    async fn await_fill_receipt(
+        &self,
+        client: &SignerMiddleware<Arc<Provider<Ws>>, SignerHandle>,
+        opportunity: &FillOpportunity,
+        gas_token: TokenType,
+        original: TypedTransaction,
+        first_hash: H256,
+    ) -> Result<Option<TransactionReceipt>> {
+        let policy = &self.config.fee_escalation;
+        let intent_id = opportunity.intent.intent_id;
+
+        let mut current = original.clone();
+        let mut hashes = vec![first_hash];
+        let mut replacements = 0;
+        let mut can_replace = policy.enabled && policy.max_replacements > 0;
+        let mut last_sent = Instant::now();
+        let sent_at = last_sent;
 
-        info!("📊 Opportunity Analysis | Intent: {:?}", intent.intent_id);
-        info!(
-            "   Intent Value: ${:.6} | Fee (2%): ${:.6}",
-            intent_value_usd, fee_value_usd
-        );
-        info!("   Gas Cost: ${:.6}", gas_cost_usd);
-        info!("   Net Profit: ${:.6} ({} bps)", profit_usd, profit_bps);
-        info!(
-            "   Token: {:?} | Amount: {}",
-            intent.token_type, intent.amount
-        );
+        loop {
+            tokio::time::sleep(FILL_RECEIPT_POLL_INTERVAL).await;
 
-        let risk_score = self.calculate_risk_score(intent).await?;
+            let mut in_mempool = false;
+            for hash in &hashes {
+                if let Some(receipt) = client.get_transaction_receipt(*hash).await? {
+                    if hashes.last() != Some(hash) {
+                        self.track_fill_tx(intent_id, *hash, replacements).await;
+                    }
+                    self.prometheus.confirmed(
+                        self.chain_key(opportunity.intent.dest_chain),
+                        "fill_intent",
+                        sent_at.elapsed(),
+                        receipt.status == Some(1.into()),
+                    );
+                    return Ok(Some(receipt));
+                }
+                in_mempool |= client.get_transaction(*hash).await?.is_some();
+            }
 
-        info!("   Risk Score: {}/100", risk_score);
+            if !in_mempool {
+                return Ok(None);
+            }
 
-        Ok(FillOpportunity {
-            intent: intent.clone(),
-            estimated_profit,
-            profit_bps,
-            risk_score,
-            capital_required: intent.amount,
-            gas_estimate,
-        })
+            if !can_replace || last_sent.elapsed() < policy.replace_after {
+                continue;
+            }
+
+            let remaining = opportunity
+                .intent
+                .deadline
+                .saturating_sub(chrono::Utc::now().timestamp() as u64);
+            let budget = self.escalation_budget(opportunity, gas_token).await;
+
+            let Some(next) = policy.replacement(
+                &original,
+                &current,
+                remaining,
+                budget,
+                self.max_fee_per_gas(),
+            ) else {
+                debug!(
+                    "⏸️ Fill {:?} cannot be bumped further within budget",
+                    intent_id
+                );
+                can_replace = false;
+                continue;
+            };
+
+            match client.send_transaction(next.clone(), None).await {
+                Ok(pending) => {
+                    let hash = pending.tx_hash();
+                    replacements += 1;
+                    info!(
+                        "🔁 Replaced unmined fill tx with {:?} ({}/{}, {}s before deadline)",
+                        hash, replacements, policy.max_replacements, remaining
+                    );
+
+                    hashes.push(hash);
+                    current = next;
+                    last_sent = Instant::now();
+                    can_replace = replacements < policy.max_replacements;
+                    self.track_fill_tx(intent_id, hash, replacements).await;
+                }
+                Err(e) => {
+                    // Usually "nonce too low": a previous version was just mined
+                    warn!("⚠️ Fill replacement rejected: {}", e);
+                    can_replace = false;
+                }
+            }
+        }
+    }
+
+    async fn track_fill_tx(&self, intent_id: H256, tx_hash: H256, replacements: u32) {
+        self.execution_reports
+            .write()
+            .await
+            .fill_replaced(&intent_id, tx_hash, replacements);
+
+        self.update_fill(intent_id, |fill| fill.tx_hash = tx_hash)
+            .await;
+    }
+
+    /// Start tracking a sent fill, or add a further chunk to the partial
+    /// fill already tracked for its intent. Returns whether a new fill
+    /// started.
+    async fn track_fill(&self, fill: ActiveFill) -> bool {
+        let mut active = self.active_fills.write().await;
+        if fill.filled_amount < fill.amount
+            && let Some(tracked) = active.get_mut(&fill.intent_id)
+            && matches!(tracked.status, FillStatus::Pending | FillStatus::Confirmed)
+        {
+            tracked.filled_amount = tracked.filled_amount.saturating_add(fill.filled_amount);
+            tracked.chunks += 1;
+            tracked.tx_hash = fill.tx_hash;
+            tracked.status = FillStatus::Pending;
+            self.persist_fill(tracked);
+            return false;
+        }
+
+        self.persist_fill(&fill);
+        active.insert(fill.intent_id, fill);
+        true
+    }
+
+    /// Take a chunk that did not land off its fill. The fill fails only
+    /// when no earlier chunk landed; returns whether it did.
+    async fn fail_fill_chunk(&self, intent_id: H256, chunk: U256) -> bool {
+        let mut failed = false;
+        self.update_fill(intent_id, |fill| {
+            fill.filled_amount = fill.filled_amount.saturating_sub(chunk);
+            fill.chunks = fill.chunks.saturating_sub(1);
+            failed = fill.filled_amount.is_zero();
+            fill.status = if failed {
+                FillStatus::Failed
+            } else {
+                FillStatus::Confirmed
+            };
+        })
+        .await;
+        failed
+    }
+
+    /// Change a tracked fill in place and persist the result.
+    async fn update_fill(&self, intent_id: H256, update: impl FnOnce(&mut ActiveFill)) {
+        let mut active = self.active_fills.write().await;
+        if let Some(fill) = active.get_mut(&intent_id) {
+            update(fill);
+            self.persist_fill(fill);
+        }
+    }
+
+    fn persist_fill(&self, fill: &ActiveFill) {
+        if let Some(store) = &self.state_store
+            && let Err(e) = store.save_fill(fill)
+        {
+            warn!("⚠️ Failed to persist fill {:?}: {:#}", fill.intent_id, e);
+        }
     }
 
+    /// Read each chain's settlement fee into the cache. A failed read keeps
+    /// the last value, or the fallback if there is none yet.
+    async fn refresh_settlement_fees(&self) {
+        for (chain_id, settlement) in [
+            (self.config.ethereum_chain_id, &self.ethereum_settlement),
+            (self.config.mantle_chain_id, &self.mantle_settlement),
+        ] {
+            let read = settlement
+                .fee_bps()
+                .call()
+                .await
+                .context("Failed to read FEE_BPS");
+            let mut fees = self.settlement_fees.write().await;
+            let first_read = !fees.is_known(chain_id);
+            match read.and_then(|raw| fees.update(chain_id, raw)) {
+                Ok(Some(previous)) => warn!(
+                    "💱 Settlement fee on chain {} changed from {} to {} bps",
+                    chain_id,
+                    previous,
+                    fees.bps(chain_id)
+                ),
+                Ok(None) if first_read => info!(
+                    "💱 Settlement fee on chain {}: {} bps",
+                    chain_id,
+                    fees.bps(chain_id)
+                ),
+                Ok(None) => {}
+                Err(e) => warn!(
+                    "⚠️ Pricing chain {} at {} bps: {:#}",
+                    chain_id,
+                    fees.bps(chain_id),
+                    e
+                ),
+            }
+        }
+    }
+
+    async fn run_settlement_fee_refresh(&self) {
+        let mut ticker = interval(Duration::from_secs(
+            self.config.settlement_fees.refresh_interval_secs,
+        ));
+        // The first tick fires at once; startup has just read the fees
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            self.refresh_settlement_fees().await;
+        }
+    }
+
+    async fn evaluate_fill_opportunity(&self, intent: &DetectedIntent) -> Result<FillOpportunity> {
+        let settlement_fee_bps = self
+            .settlement_fees
+            .read()
+            .await
+            .bps(intent.dest_chain as u64);
+        let fee_amount = fee_amount(intent.amount, settlement_fee_bps);
+        let gas_estimate = self.estimate_fill_gas(intent).await?;
+
+        info!(
+            "💰 Evaluating opportunity for intent: {:?}",
+            intent.intent_id
+        );
+
+        let (fee_value_usd, token_stale) = self
+            .get_token_price_usd(intent.token_type, fee_amount)
+            .await?;
+        let (gas_cost_usd, gas_stale) = self.get_gas_cost_usd(gas_estimate).await?;
+        let (intent_value_usd, _) = self
+            .get_token_price_usd(intent.token_type, intent.amount)
+            .await?;
+        let stale_prices = token_stale || gas_stale;
+
+        let profit_usd = fee_value_usd - gas_cost_usd;
+
+        let estimated_profit = if profit_usd > 0.0 {
+            let profit_per_usd = fee_amount.as_u128() as f64 / fee_value_usd;
+            U256::from((profit_usd * profit_per_usd) as u128)
+        } else {
+            U256::zero()
+        };
+
+        let profit_bps = if intent_value_usd > 0.0 {
+            ((profit_usd / intent_value_usd) * 10000.0).max(0.0) as u16
+        } else {
+            0
+        };
+
+        info!("📊 Opportunity Analysis | Intent: {:?}", intent.intent_id);
+        info!(
+            "   Intent Value: ${:.6} | Fee ({} bps): ${:.6}",
+            intent_value_usd, settlement_fee_bps, fee_value_usd
+        );
+        info!("   Gas Cost: ${:.6}", gas_cost_usd);
+        info!("   Net Profit: ${:.6} ({} bps)", profit_usd, profit_bps);
+        if stale_prices {
+            warn!("   Priced from stale prices, a wider margin is required");
+        }
+        info!(
+            "   Token: {:?} | Amount: {}",
+            intent.token_type, intent.amount
+        );
+
+        let risk_score = self.calculate_risk_score(intent).await?;
+
+        info!("   Risk Score: {}/100", risk_score);
+
+        Ok(FillOpportunity {
+            intent: intent.clone(),
+            estimated_profit,
+            profit_bps,
+            profit_usd,
+            risk_score,
+            capital_required: intent.amount,
+            gas_estimate,
+            stale_prices,
+        })
+    }
+
+    /// Native cost of the fill: execution gas plus any rollup L1 data fee.
     async fn estimate_fill_gas(&self, intent: &DetectedIntent) -> Result<U256> {
         let base_gas = if intent.token_type.is_native() {
             U256::from(90_000)
@@ -1021,13 +2085,38 @@ impl CrossChainSolver {
             U256::from(120_000)
         };
 
-        let gas_price = if intent.dest_chain == self.config.ethereum_chain_id as u32 {
-            self.ethereum_provider.get_gas_price().await?
-        } else {
-            self.mantle_provider.get_gas_price().await?
-        };
+        let (strategy, settlement, provider) =
+            if intent.dest_chain == self.config.ethereum_chain_id as u32 {
+                (
+                    &self.ethereum_tx_strategy,
+                    &self.ethereum_settlement,
+                    &self.ethereum_provider,
+                )
+            } else {
+                (
+                    &self.mantle_tx_strategy,
+                    &self.mantle_settlement,
+                    &self.mantle_provider,
+                )
+            };
+        if strategy.breaker.is_open() {
+            return Err(anyhow!(
+                "{} fees are above the cap, not filling until they fall",
+                strategy.label
+            ));
+        }
+
+        let fill = settlement.fill_intent(
+            intent.intent_id.0,
+            intent.commitment.0,
+            intent.source_chain,
+            intent.token,
+            intent.amount,
+        );
 
-        Ok(base_gas * gas_price)
+        strategy
+            .estimate_cost(provider.clone(), &fill.tx, base_gas)
+            .await
     }
 
     async fn calculate_risk_score(&self, intent: &DetectedIntent) -> Result<u8> {
@@ -1044,7 +2133,10 @@ impl CrossChainSolver {
             score += 20;
         }
 
-        let max_amount = intent.token_type.max_amount();
+        let max_amount = self.config.tokens.listing(intent.token_type).map_or_else(
+            || intent.token_type.max_amount(),
+            |listing| listing.max_amount,
+        );
         if intent.amount > max_amount / U256::from(2) {
             score += 15;
         }
@@ -1061,37 +2153,60 @@ impl CrossChainSolver {
         Ok(score.min(100))
     }
 
-    async fn should_fill(&self, opportunity: &FillOpportunity) -> Result<bool> {
-        // Check profit
-        if opportunity.profit_bps < self.config.min_profit_bps {
+    /// Static checks (profit, risk, per-fill cap) that do not depend on current
+    /// capacity, so an opportunity can wait for capital instead of being dropped.
+    /// Returns the rejection reason, `None` if the opportunity qualifies.
+    fn is_fill_candidate(&self, opportunity: &FillOpportunity) -> Result<Option<String>> {
+        if self.drain.is_draining() {
+            return Ok(Some("Solver is draining".to_string()));
+        }
+
+        let intent = &opportunity.intent;
+        if let Err(e) = self
+            .config
+            .tokens
+            .enabled(intent.token_type)
+            .and_then(|listing| listing.check_amount(intent.amount))
+        {
             warn!(
-                "❌ FILL REJECTED - Low profit: {} bps < {} bps required | Intent: {:?}",
-                opportunity.profit_bps, self.config.min_profit_bps, opportunity.intent.intent_id
+                "❌ FILL REJECTED - {} | Intent: {:?}",
+                e, opportunity.intent.intent_id
             );
-            return Ok(false);
+            return Ok(Some(e.to_string()));
         }
 
-        // Check risk
-        if opportunity.risk_score > 70 {
+        // Check profit, with a wider margin while the price feed is degraded
+        let required_bps = if opportunity.stale_prices {
+            self.config
+                .min_profit_bps
+                .saturating_add(self.config.stale_price_margin_bps)
+        } else {
+            self.config.min_profit_bps
+        };
+        if opportunity.profit_bps < required_bps {
+            let stale = if opportunity.stale_prices {
+                " (stale prices)"
+            } else {
+                ""
+            };
             warn!(
-                "❌ FILL REJECTED - High risk: {} > 70 | Intent: {:?}",
-                opportunity.risk_score, opportunity.intent.intent_id
+                "❌ FILL REJECTED - Low profit: {} bps < {} bps required{} | Intent: {:?}",
+                opportunity.profit_bps, required_bps, stale, opportunity.intent.intent_id
             );
-            return Ok(false);
+            return Ok(Some(format!(
+                "Low profit: {} bps < {} bps required{}",
+                opportunity.profit_bps, required_bps, stale
+            )));
         }
 
-        // Check concurrent fills
-        let metrics = self.metrics.read().await;
-        if metrics.active_fills_count >= self.config.max_concurrent_fills {
+        // Check risk
+        if opportunity.risk_score > 70 {
             warn!(
-                "❌ FILL REJECTED - Max concurrent fills: {}/{} | Intent: {:?}",
-                metrics.active_fills_count,
-                self.config.max_concurrent_fills,
-                opportunity.intent.intent_id
+                "❌ FILL REJECTED - High risk: {} > 70 | Intent: {:?}",
+                opportunity.risk_score, opportunity.intent.intent_id
             );
-            return Ok(false);
+            return Ok(Some(format!("High risk: {} > 70", opportunity.risk_score)));
         }
-        drop(metrics);
 
         // Check max capital
         let max_capital = self
@@ -1101,6 +2216,17 @@ impl CrossChainSolver {
             .ok_or_else(|| anyhow!("Token not configured"))?;
 
         if opportunity.capital_required > *max_capital {
+            if let Some(chunks) = self.fill_chunks(intent) {
+                info!(
+                    "🧩 Exceeds max capital: {} > {}, filling in {} chunks | Intent: {:?}",
+                    opportunity.capital_required,
+                    max_capital,
+                    chunks.len(),
+                    opportunity.intent.intent_id
+                );
+                return Ok(None);
+            }
+
             warn!(
                 "❌ FILL REJECTED - Exceeds max capital: {} > {} | Token: {:?} | Intent: {:?}",
                 opportunity.capital_required,
@@ -1108,9 +2234,40 @@ impl CrossChainSolver {
                 opportunity.intent.token_type,
                 opportunity.intent.intent_id
             );
+            return Ok(Some(format!(
+                "Exceeds max capital: {} > {}",
+                opportunity.capital_required, max_capital
+            )));
+        }
+
+        Ok(None)
+    }
+
+    async fn should_fill(&self, opportunity: &FillOpportunity) -> Result<bool> {
+        if let Some(reason) = self.is_fill_candidate(opportunity)? {
+            self.reject(opportunity, reason).await;
             return Ok(false);
         }
 
+        // Check concurrent fills
+        let metrics = self.metrics.read().await;
+        if metrics.active_fills_count >= self.config.max_concurrent_fills {
+            warn!(
+                "❌ FILL REJECTED - Max concurrent fills: {}/{} | Intent: {:?}",
+                metrics.active_fills_count,
+                self.config.max_concurrent_fills,
+                opportunity.intent.intent_id
+            );
+            let reason = format!(
+                "Max concurrent fills: {}/{}",
+                metrics.active_fills_count, self.config.max_concurrent_fills
+            );
+            drop(metrics);
+            self.reject(opportunity, reason).await;
+            return Ok(false);
+        }
+        drop(metrics);
+
         // Determine destination chain
         let dest_chain = if opportunity.intent.source_chain == self.config.ethereum_chain_id as u32
         {
@@ -1151,6 +2308,17 @@ impl CrossChainSolver {
                 required_with_margin,
                 opportunity.intent.intent_id
             );
+            self.record_decision(
+                opportunity,
+                ExecutionStatus::Rejected,
+                DecisionRecord {
+                    reason: Some("Insufficient balance".to_string()),
+                    balance: Some(balance.to_string()),
+                    required: Some(required_with_margin.to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
             return Ok(false);
         }
 
@@ -1179,6 +2347,20 @@ impl CrossChainSolver {
                 required_with_margin,
                 opportunity.intent.intent_id
             );
+            drop(active_fills);
+            self.record_decision(
+                opportunity,
+                ExecutionStatus::Rejected,
+                DecisionRecord {
+                    reason: Some("Capital locked".to_string()),
+                    balance: Some(balance.to_string()),
+                    locked: Some(locked_capital.to_string()),
+                    available: Some(available_balance.to_string()),
+                    required: Some(required_with_margin.to_string()),
+                    ..Default::default()
+                },
+            )
+            .await;
             return Ok(false);
         }
 
@@ -1191,10 +2373,73 @@ impl CrossChainSolver {
             required_with_margin,
             opportunity.intent.intent_id
         );
+        drop(active_fills);
+
+        self.record_decision(
+            opportunity,
+            ExecutionStatus::Approved,
+            DecisionRecord {
+                balance: Some(balance.to_string()),
+                locked: Some(locked_capital.to_string()),
+                available: Some(available_balance.to_string()),
+                required: Some(required_with_margin.to_string()),
+                ..Default::default()
+            },
+        )
+        .await;
 
         Ok(true)
     }
-    
+
+    async fn record_decision(
+        &self,
+        opportunity: &FillOpportunity,
+        status: ExecutionStatus,
+        decision: DecisionRecord,
+    ) {
+        if status == ExecutionStatus::Rejected {
+            self.record_dry_run(opportunity, false, decision.reason.clone())
+                .await;
+        }
+        self.execution_reports.write().await.decided(
+            &opportunity.intent.intent_id,
+            status,
+            decision,
+        );
+    }
+
+    async fn record_dry_run(
+        &self,
+        opportunity: &FillOpportunity,
+        would_fill: bool,
+        reason: Option<String>,
+    ) {
+        let Some(dry_run) = &self.dry_run else {
+            return;
+        };
+        let decision = DryRunDecision::of(
+            opportunity,
+            would_fill,
+            reason,
+            chrono::Utc::now().timestamp() as u64,
+        );
+        if let Err(e) = dry_run.write().await.record(&decision) {
+            warn!("⚠️ Failed to log dry-run decision: {:#}", e);
+        }
+    }
+
+    async fn reject(&self, opportunity: &FillOpportunity, reason: String) {
+        self.record_decision(
+            opportunity,
+            ExecutionStatus::Rejected,
+            DecisionRecord {
+                reason: Some(reason),
+                ..Default::default()
+            },
+        )
+        .await;
+    }
+
     async fn verify_provider_health(&self, chain_id: u64) -> Result<()> {
         let provider = if chain_id == self.config.ethereum_chain_id {
             &self.ethereum_provider
@@ -1217,12 +2462,16 @@ impl CrossChainSolver {
         Ok(())
     }
 
-    async fn get_token_price_usd(&self, token_type: SupportedToken, amount: U256) -> Result<f64> {
-        let token_decimals = token_type.decimals();
-        let amount_decimal = amount.as_u128() as f64 / 10f64.powi(token_decimals as i32);
+    /// USD value of `amount`, and whether it came from a stale price.
+    async fn get_token_price_usd(
+        &self,
+        token_type: TokenType,
+        amount: U256,
+    ) -> Result<(f64, bool)> {
+        let amount_decimal = to_decimal(amount, token_type.decimals());
 
-        let price_per_token = match self.price_feed.get_usd_price(token_type).await {
-            Ok(price) => price,
+        let quote = match self.price_feed.usd_quote(token_type).await {
+            Ok(quote) => quote,
             Err(e) => {
                 warn!(
                     "⚠️ Failed to get USD price for {:?}: {}",
@@ -1233,38 +2482,40 @@ impl CrossChainSolver {
             }
         };
 
-        let value_usd = amount_decimal * price_per_token;
+        let value_usd = amount_decimal * quote.price;
 
         debug!(
-            "💵 Price lookup: {} {} (${:.4}/token) = ${:.6}",
+            "💵 Price lookup: {} {} (${:.4}/token, {}s old) = ${:.6}",
             amount_decimal,
             token_type.symbol(),
-            price_per_token,
+            quote.price,
+            quote.age_secs,
             value_usd
         );
 
-        Ok(value_usd)
+        Ok((value_usd, quote.stale))
     }
 
-    async fn get_gas_cost_usd(&self, gas_amount_wei: U256) -> Result<f64> {
-        let gas_amount_eth = gas_amount_wei.as_u128() as f64 / 10f64.powi(18);
+    /// USD cost of `gas_amount_wei`, and whether it came from a stale price.
+    async fn get_gas_cost_usd(&self, gas_amount_wei: U256) -> Result<(f64, bool)> {
+        let gas_amount_eth = to_decimal(gas_amount_wei, TokenType::ETH.decimals());
 
-        let eth_price = match self.price_feed.get_usd_price(SupportedToken::ETH).await {
-            Ok(price) => price,
+        let quote = match self.price_feed.usd_quote(TokenType::ETH).await {
+            Ok(quote) => quote,
             Err(e) => {
                 warn!("⚠️ Failed to get ETH price for gas calculation: {}", e);
                 return Err(e);
             }
         };
 
-        let value_usd = gas_amount_eth * eth_price;
+        let value_usd = gas_amount_eth * quote.price;
 
         debug!(
             "⛽ Gas cost: {} ETH (${:.4}/ETH) = ${:.6}",
-            gas_amount_eth, eth_price, value_usd
+            gas_amount_eth, quote.price, value_usd
         );
 
-        Ok(value_usd)
+        Ok((value_usd, quote.stale))
     }
 
     async fn approve_token_if_needed(
@@ -1272,7 +2523,7 @@ impl CrossChainSolver {
         token: Address,
         spender: Address,
         amount: U256,
-        client: Arc<SignerMiddleware<Arc<Provider<Ws>>, Wallet<SigningKey>>>,
+        client: Arc<SignerMiddleware<Arc<Provider<Ws>>, SignerHandle>>,
     ) -> Result<()> {
         let erc20 = ERC20Contract::new(token, client.clone());
 
@@ -1375,25 +2626,30 @@ impl CrossChainSolver {
             };
 
             for fill in active_fills {
-                if fill.status != FillStatus::Confirmed {
-                    continue;
-                }
+                let result = match fill.status {
+                    // A partial fill is claimed once its last chunk landed
+                    FillStatus::Confirmed if fill.is_complete() => {
+                        self.process_confirmed_fill(&fill).await
+                    }
+                    FillStatus::Claimed => self.check_repayment(&fill).await,
+                    _ => continue,
+                };
 
-                if let Err(e) = self.process_confirmed_fill(&fill).await {
-                    error!("❌ Error processing confirmed fill: {}", e);
+                if let Err(e) = result {
+                    error!("❌ Error processing {:?} fill: {}", fill.status, e);
                 }
             }
+
+            let at_risk = self.capital_at_risk().await;
+            for (token, at_risk) in &at_risk.by_token {
+                self.prometheus.set_capital_at_risk(token, at_risk.usd);
+            }
         }
     }
 
     async fn process_confirmed_fill(&self, fill: &ActiveFill) -> Result<()> {
-        let required_confirmations = 6;
-
-        let current_block = if fill.dest_chain == self.config.ethereum_chain_id as u32 {
-            self.ethereum_provider.get_block_number().await?.as_u64()
-        } else {
-            self.mantle_provider.get_block_number().await?.as_u64()
-        };
+        let (provider, finality) = self.finality_for(fill.dest_chain);
+        let current_block = provider.get_block_number().await?.as_u64();
 
         let fill_block = if fill.dest_chain == self.config.ethereum_chain_id as u32 {
             self.ethereum_provider
@@ -1412,11 +2668,18 @@ impl CrossChainSolver {
         };
 
         let confirmations = current_block.saturating_sub(fill_block);
+        let is_final = fill_block > 0 && finality.is_final(provider.as_ref(), fill_block).await?;
 
-        if confirmations < required_confirmations {
+        self.execution_reports.write().await.confirmations(
+            &fill.intent_id,
+            confirmations,
+            is_final,
+        );
+
+        if !is_final {
             debug!(
-                "⏳ Waiting for confirmations ({}/{}) for intent: {:?}",
-                confirmations, required_confirmations, fill.intent_id
+                "⏳ Waiting for fill to become final ({} confirmations, {}) for intent: {:?}",
+                confirmations, finality, fill.intent_id
             );
             return Ok(());
         }
@@ -1426,12 +2689,9 @@ impl CrossChainSolver {
             confirmations
         );
 
-        {
-            let mut active = self.active_fills.write().await;
-            if let Some(f) = active.get_mut(&fill.intent_id) {
-                f.status = FillStatus::Claimed;
-            }
-        }
+        self.update_fill(fill.intent_id, |f| f.status = FillStatus::Claimed)
+            .await;
+        self.prometheus.claim(self.chain_key(fill.dest_chain), true);
 
         {
             let mut metrics = self.metrics.write().await;
@@ -1439,10 +2699,237 @@ impl CrossChainSolver {
             metrics.active_fills_count = metrics.active_fills_count.saturating_sub(1);
         }
 
+        // A slot and its capital are free again
+        self.replan.notify_one();
+
+        Ok(())
+    }
+
+    /// Settle a final fill once the relayer repaid it on the source chain,
+    /// which `getIntent` reports as `filled`.
+    async fn check_repayment(&self, fill: &ActiveFill) -> Result<()> {
+        let (pool, provider) = if fill.dest_chain == self.config.ethereum_chain_id as u32 {
+            (self.config.mantle_intent_pool, &self.mantle_provider)
+        } else {
+            (self.config.ethereum_intent_pool, &self.ethereum_provider)
+        };
+
+        let intent = IntentPoolContract::new(pool, provider.clone())
+            .get_intent(fill.intent_id.0)
+            .call()
+            .await
+            .context("Failed to check source intent")?;
+        let (repaid, refunded) = (intent.8, intent.9);
+
+        if repaid {
+            info!("💸 Fill {:?} repaid on the source chain", fill.intent_id);
+            self.update_fill(fill.intent_id, |f| f.status = FillStatus::Settled)
+                .await;
+        } else if refunded {
+            // The user got the source funds back, so this fill is never repaid
+            error!(
+                "❌ Intent {:?} was refunded after our fill; {} {} will not be repaid",
+                fill.intent_id,
+                to_decimal(fill.amount, fill.token_type.decimals()),
+                fill.token_type.symbol()
+            );
+            self.update_fill(fill.intent_id, |f| f.status = FillStatus::Failed)
+                .await;
+        }
+
         Ok(())
     }
 
-    async fn get_token_balance(&self, token: SupportedToken, chain_id: u64) -> Result<U256> {
+    /// Landed fills not yet repaid, priced at the current USD rates. A token
+    /// whose price is unavailable is reported without a USD value.
+    pub async fn capital_at_risk(&self) -> CapitalAtRisk {
+        let fills: Vec<ActiveFill> = self
+            .active_fills
+            .read()
+            .await
+            .values()
+            .filter(|fill| is_at_risk(fill))
+            .cloned()
+            .collect();
+
+        let mut prices = HashMap::new();
+        for fill in &fills {
+            if prices.contains_key(&fill.token_type) {
+                continue;
+            }
+            match self.price_feed.usd_price(fill.token_type).await {
+                Ok(price) => {
+                    prices.insert(fill.token_type, price);
+                }
+                Err(e) => warn!(
+                    "⚠️ No USD price for {} capital at risk: {}",
+                    fill.token_type.symbol(),
+                    e
+                ),
+            }
+        }
+
+        CapitalAtRisk::of(&fills, &prices, self.config.capital_at_risk.max_usd)
+    }
+
+    /// Why `opportunity` must wait under `MAX_CAPITAL_AT_RISK_USD`, if it must.
+    async fn capital_at_risk_block(&self, opportunity: &FillOpportunity) -> Result<Option<String>> {
+        let policy: &CapitalAtRiskPolicy = &self.config.capital_at_risk;
+        if policy.max_usd.is_none() {
+            return Ok(None);
+        }
+
+        let (fill_usd, _) = self
+            .get_token_price_usd(opportunity.intent.token_type, opportunity.capital_required)
+            .await?;
+        Ok(policy.blocks(&self.capital_at_risk().await, fill_usd))
+    }
+
+    /// Rebuild processed intents and active fills from the state store. Each
+    /// in-flight fill is checked against `getFill` first, so a fill sent
+    /// before the restart is neither forgotten nor sent a second time.
+    async fn recover_state(self: &Arc<Self>) {
+        let Some(store) = &self.state_store else {
+            return;
+        };
+
+        match store.processed_intents(chrono::Utc::now().timestamp() as u64) {
+            Ok(claims) => {
+                let restored = claims.len();
+                let mut processed = self.processed_intents.write().await;
+                for (intent_id, expires_at) in claims {
+                    processed.restore(intent_id, expires_at);
+                }
+                info!("♻️ Restored {} processed intents", restored);
+            }
+            Err(e) => warn!("⚠️ Failed to restore processed intents: {:#}", e),
+        }
+
+        // Final fills only wait for repayment, which the monitor checks
+        match store.unsettled_fills() {
+            Ok(fills) => {
+                let restored = fills.len();
+                let mut active = self.active_fills.write().await;
+                for fill in fills {
+                    active.insert(fill.intent_id, fill);
+                }
+                info!("♻️ Restored {} fills awaiting repayment", restored);
+            }
+            Err(e) => warn!("⚠️ Failed to restore unsettled fills: {:#}", e),
+        }
+
+        let fills = match store.in_flight_fills() {
+            Ok(fills) => fills,
+            Err(e) => {
+                warn!("⚠️ Failed to restore active fills: {:#}", e);
+                return;
+            }
+        };
+
+        for fill in fills {
+            let intent_id = fill.intent_id;
+            let outcome = match self.reconcile_fill(&fill).await {
+                Ok(outcome) => outcome,
+                Err(e) => {
+                    // Assume the worst so the intent is not filled again
+                    warn!(
+                        "⚠️ Could not reconcile fill {:?}, treating it as in flight: {:#}",
+                        intent_id, e
+                    );
+                    RecoveredFill::InFlight
+                }
+            };
+            info!(
+                "♻️ Recovered fill {:?} on chain {}: {:?}",
+                intent_id, fill.dest_chain, outcome
+            );
+
+            self.active_fills.write().await.insert(intent_id, fill);
+            self.metrics.write().await.active_fills_count += 1;
+            self.apply_recovered(intent_id, outcome).await;
+
+            if outcome == RecoveredFill::InFlight {
+                tokio::spawn(Arc::clone(self).watch_recovered_fill(intent_id));
+            }
+        }
+    }
+
+    /// Check a fill against the destination settlement and its tx.
+    async fn reconcile_fill(&self, fill: &ActiveFill) -> Result<RecoveredFill> {
+        let (settlement, provider) = if fill.dest_chain == self.config.ethereum_chain_id as u32 {
+            (&self.ethereum_settlement, &self.ethereum_provider)
+        } else {
+            (&self.mantle_settlement, &self.mantle_provider)
+        };
+
+        let (filled_by, _token, _amount, _source_chain, _timestamp, _claimed) = settlement
+            .get_fill(fill.intent_id.0)
+            .call()
+            .await
+            .context("Failed to check fill status")?;
+        let tx_pending = provider
+            .get_transaction(fill.tx_hash)
+            .await
+            .context("Failed to look up fill tx")?
+            .is_some_and(|tx| tx.block_number.is_none());
+
+        Ok(RecoveredFill::classify(
+            filled_by,
+            self.config.solver_address,
+            tx_pending,
+        ))
+    }
+
+    /// Landed fills go on to wait for confirmations; fills that will not land
+    /// are failed, and lost ones free the intent for another attempt.
+    async fn apply_recovered(&self, intent_id: H256, outcome: RecoveredFill) {
+        match outcome {
+            RecoveredFill::Landed => {
+                self.update_fill(intent_id, |fill| {
+                    if fill.status == FillStatus::Pending {
+                        fill.status = FillStatus::Confirmed;
+                        fill.confirmed_at = Some(chrono::Utc::now().timestamp() as u64);
+                    }
+                })
+                .await;
+            }
+            RecoveredFill::InFlight => {}
+            RecoveredFill::FilledByOther | RecoveredFill::Lost => {
+                self.update_fill(intent_id, |fill| fill.status = FillStatus::Failed)
+                    .await;
+                {
+                    let mut metrics = self.metrics.write().await;
+                    metrics.active_fills_count = metrics.active_fills_count.saturating_sub(1);
+                }
+                if outcome == RecoveredFill::Lost {
+                    self.release_intent_after(intent_id, Duration::ZERO);
+                }
+                self.replan.notify_one();
+            }
+        }
+    }
+
+    /// Poll a recovered fill whose tx was still unmined until it resolves.
+    async fn watch_recovered_fill(self: Arc<Self>, intent_id: H256) {
+        loop {
+            tokio::time::sleep(RECOVERED_FILL_POLL_INTERVAL).await;
+
+            let Some(fill) = self.active_fills.read().await.get(&intent_id).cloned() else {
+                return;
+            };
+            match self.reconcile_fill(&fill).await {
+                Ok(RecoveredFill::InFlight) => {}
+                Ok(outcome) => {
+                    info!("♻️ Recovered fill {:?} resolved: {:?}", intent_id, outcome);
+                    self.apply_recovered(intent_id, outcome).await;
+                    return;
+                }
+                Err(e) => debug!("⏳ Recovered fill {:?} check failed: {:#}", intent_id, e),
+            }
+        }
+    }
+
+    async fn get_token_balance(&self, token: TokenType, chain_id: u64) -> Result<U256> {
         let key = (token, chain_id);
 
         {
@@ -1465,7 +2952,7 @@ impl CrossChainSolver {
 
     async fn fetch_balance_with_retry(
         &self,
-        token: SupportedToken,
+        token: TokenType,
         chain_id: u64,
         max_retries: u32,
     ) -> Result<U256> {
@@ -1496,7 +2983,7 @@ impl CrossChainSolver {
             .unwrap_or_else(|| anyhow!("Balance fetch failed after {} retries", max_retries)))
     }
 
-    async fn fetch_balance_inner(&self, token: SupportedToken, chain_id: u64) -> Result<U256> {
+    async fn fetch_balance_inner(&self, token: TokenType, chain_id: u64) -> Result<U256> {
         if token.is_native() {
             let provider = if chain_id == self.config.ethereum_chain_id {
                 &self.ethereum_provider
@@ -1515,7 +3002,10 @@ impl CrossChainSolver {
                 self.mantle_client.clone()
             };
 
-            let erc20 = ERC20Contract::new(token.address(chain_id), client);
+            let address = Chain::from_id(chain_id)
+                .and_then(|chain| self.config.tokens.address(token, chain))
+                .ok_or_else(|| anyhow!("Token {} is not listed", token.symbol()))?;
+            let erc20 = ERC20Contract::new(address, client);
             erc20
                 .balance_of(self.config.solver_address)
                 .call()
@@ -1524,6 +3014,24 @@ impl CrossChainSolver {
         }
     }
 
+    /// Provider and finality source of `chain_id`.
+    fn finality_for(&self, chain_id: u32) -> (&Arc<Provider<Ws>>, FinalitySource) {
+        if chain_id == self.config.ethereum_chain_id as u32 {
+            (&self.ethereum_provider, self.ethereum_finality)
+        } else {
+            (&self.mantle_provider, self.mantle_finality)
+        }
+    }
+
+    /// Lowercase chain name used as a metrics label.
+    fn chain_key(&self, chain_id: u32) -> &'static str {
+        if chain_id == self.config.ethereum_chain_id as u32 {
+            Chain::Ethereum.name()
+        } else {
+            Chain::Mantle.name()
+        }
+    }
+
     async fn get_source_block_number(&self, chain_id: u32) -> Result<u64> {
         let block = if chain_id == self.config.ethereum_chain_id as u32 {
             self.ethereum_provider.get_block_number().await?
@@ -1548,41 +3056,389 @@ impl CrossChainSolver {
     }
 
     async fn update_all_balances(&self) -> Result<()> {
-        for token in [
-            SupportedToken::ETH,
-            SupportedToken::WETH,
-            SupportedToken::USDC,
-            SupportedToken::USDT,
-            SupportedToken::MNT,
-        ] {
+        let now = chrono::Utc::now().timestamp() as u64;
+        let mut snapshots = Vec::new();
+        let mut balances = HashMap::new();
+
+        let listed: Vec<TokenType> = self
+            .config
+            .tokens
+            .listings()
+            .iter()
+            .map(|listing| listing.token)
+            .collect();
+
+        for token in listed {
             for chain_id in [self.config.ethereum_chain_id, self.config.mantle_chain_id] {
-                let balance = self.get_token_balance(token, chain_id).await?;
+                // Always refetch so top-ups and drawdown show up between fills
+                let balance = self.fetch_balance_with_retry(token, chain_id, 3).await?;
+                self.token_balances
+                    .write()
+                    .await
+                    .insert((token, chain_id), balance);
+                balances.insert((token, chain_id), balance);
 
                 debug!("💰 Balance {:?} on chain {}: {}", token, chain_id, balance);
+                self.prometheus.set_balance(
+                    self.chain_key(chain_id as u32),
+                    token.symbol(),
+                    to_decimal(balance, token.decimals()),
+                );
 
-                {
+                let metrics = {
                     let mut metrics = self.metrics.write().await;
                     metrics.capital_available.insert((token, chain_id), balance);
-                }
+                    metrics.clone()
+                };
+
+                snapshots.push(BalanceSnapshot {
+                    at: now,
+                    token,
+                    chain_id,
+                    balance: balance.to_string(),
+                    active_fills: metrics.active_fills_count,
+                    successful_fills: metrics.successful_fills.get(),
+                });
             }
         }
 
+        self.assess_route_capital(&balances).await;
+
+        let mut history = self.balance_history.write().await;
+        if history.is_due(now) {
+            history
+                .record(snapshots, now)
+                .context("Failed to persist balance snapshot")?;
+        }
+
         Ok(())
     }
 
-    fn identify_token(&self, token: Address, chain_id: u64) -> Result<SupportedToken> {
-        for supported in [
-            SupportedToken::ETH,
-            SupportedToken::USDC,
-            SupportedToken::USDT,
-            SupportedToken::WETH,
-            SupportedToken::MNT,
-        ] {
-            if supported.address(chain_id) == token {
-                return Ok(supported);
+    /// Check every token with a target after each balance refresh: close
+    /// transfers that arrived or timed out, then start one where a chain
+    /// runs low.
+    async fn run_rebalancer(self: Arc<Self>) {
+        let Some(rebalancer) = &self.rebalancer else {
+            return;
+        };
+        let tokens = self.config.rebalance.tokens();
+        info!(
+            "⚖️ Rebalancing {} between chains by {:?}",
+            tokens
+                .iter()
+                .map(|token| token.symbol())
+                .collect::<Vec<_>>()
+                .join(", "),
+            self.config.rebalance.method
+        );
+
+        let mut tick = interval(Duration::from_secs(self.config.balance_check_interval_secs));
+        loop {
+            tick.tick().await;
+            for token in &tokens {
+                self.rebalance_token(rebalancer, *token).await;
+            }
+        }
+    }
+
+    async fn rebalance_token(&self, rebalancer: &RwLock<Rebalancer>, token: TokenType) {
+        let inventory: HashMap<Chain, U256> = {
+            let balances = self.token_balances.read().await;
+            Chain::ALL
+                .into_iter()
+                .filter_map(|chain| {
+                    let balance = balances.get(&(token, chain.id() as u64))?;
+                    Some((chain, *balance))
+                })
+                .collect()
+        };
+        // Wait for the first balance refresh of both chains
+        if inventory.len() < Chain::ALL.len() {
+            return;
+        }
+
+        for chain in Chain::ALL {
+            if let Some(share) = share_bps(&inventory, chain) {
+                self.prometheus.set_inventory_share(
+                    chain.name(),
+                    token.symbol(),
+                    share as f64 / 10_000.0,
+                );
+            }
+        }
+
+        let now = chrono::Utc::now().timestamp() as u64;
+        let reserve = self
+            .config
+            .min_capital_reserve
+            .get(&token)
+            .copied()
+            .unwrap_or_default();
+        let decision = {
+            let mut rebalancer = rebalancer.write().await;
+            if let Some((transfer, outcome)) = rebalancer.resolve(token, &inventory, now) {
+                info!(
+                    "⚖️ Rebalance {:?} of {} {} to {}: {}",
+                    transfer.id,
+                    transfer.amount,
+                    token.symbol(),
+                    transfer.to.name(),
+                    outcome.as_str()
+                );
+                self.prometheus
+                    .rebalance(token.symbol(), transfer.to.name(), outcome.as_str());
+            }
+            rebalancer.decide(token, &inventory, reserve, now)
+        };
+
+        let plan = match decision {
+            RebalanceDecision::Hold => return,
+            RebalanceDecision::Limited(plan) => {
+                warn!(
+                    "⚠️ {} on {} is at {:.1}% of inventory (target {:.1}%) but its {} rebalances for today are used up",
+                    token.symbol(),
+                    plan.to.name(),
+                    plan.share_bps as f64 / 100.0,
+                    plan.target_bps as f64 / 100.0,
+                    self.config.rebalance.max_per_day
+                );
+                self.prometheus
+                    .rebalance(token.symbol(), plan.to.name(), "limited");
+                return;
+            }
+            RebalanceDecision::Start(plan) => plan,
+        };
+        if self.drain.is_draining() {
+            return;
+        }
+
+        let amount = to_decimal(plan.amount, token.decimals());
+        if self.config.dry_run.enabled {
+            info!(
+                "🧪 Would rebalance {} {} from {} to {}",
+                amount,
+                token.symbol(),
+                plan.from.name(),
+                plan.to.name()
+            );
+            rebalancer.write().await.back_off(token, now);
+            return;
+        }
+
+        info!(
+            "⚖️ Rebalancing {} {} from {} to {}: {:.1}% of inventory there, target {:.1}%",
+            amount,
+            token.symbol(),
+            plan.from.name(),
+            plan.to.name(),
+            plan.share_bps as f64 / 100.0,
+            plan.target_bps as f64 / 100.0
+        );
+        // The random intent id doubles as the webhook transfer id
+        let secrets = BridgeSecrets::generate();
+        let transfer = rebalancer
+            .write()
+            .await
+            .begin(&plan, secrets.intent_id, now);
+        self.prometheus
+            .rebalance(token.symbol(), plan.to.name(), "started");
+
+        let result = match (transfer.method, &self.rebalance_client) {
+            (RebalanceMethod::Bridge, _) => self.bridge_rebalance(&plan, &secrets).await.map(Some),
+            (RebalanceMethod::Webhook, Some(client)) => {
+                client.request_transfer(&transfer).await.map(|_| None)
+            }
+            (RebalanceMethod::Webhook, None) => Err(anyhow!("Rebalancing is not configured")),
+        };
+        match result {
+            Ok(tx_hash) => {
+                info!("✅ Rebalance {:?} sent", transfer.id);
+                rebalancer.write().await.sent(token, tx_hash);
+            }
+            Err(e) => {
+                error!("❌ Rebalance {:?} failed: {:#}", transfer.id, e);
+                rebalancer
+                    .write()
+                    .await
+                    .failed(token, chrono::Utc::now().timestamp() as u64);
+                self.prometheus
+                    .rebalance(token.symbol(), plan.to.name(), "failed");
+            }
+        }
+    }
+
+    /// Send `plan` as an intent through the bridge. The deposit goes in on
+    /// the chain with spare inventory; another solver fills it on the low
+    /// chain and the relayer claims the fill to us with the secrets we hand it.
+    async fn bridge_rebalance(
+        &self,
+        plan: &RebalancePlan,
+        secrets: &BridgeSecrets,
+    ) -> Result<H256> {
+        let relayer = self
+            .rebalance_client
+            .as_ref()
+            .ok_or_else(|| anyhow!("Rebalancing is not configured"))?;
+        let (client, pool_address) = match plan.from {
+            Chain::Ethereum => (
+                self.ethereum_client.clone(),
+                self.config.ethereum_intent_pool,
+            ),
+            Chain::Mantle => (self.mantle_client.clone(), self.config.mantle_intent_pool),
+        };
+        let token_on = |chain: Chain| {
+            self.config
+                .tokens
+                .address(plan.token, chain)
+                .ok_or_else(|| anyhow!("Token {} is not listed", plan.token.symbol()))
+        };
+        let source_token = token_on(plan.from)?;
+        let dest_token = token_on(plan.to)?;
+        let recipient = self.config.solver_address;
+
+        let pool = IntentPoolContract::new(pool_address, client.clone());
+        let hasher = pool
+            .poseidon_hasher()
+            .call()
+            .await
+            .context("Failed to read the Poseidon hasher")?;
+        let commitment = PoseidonHasherContract::new(hasher, client.clone())
+            .poseidon(secrets.commitment_inputs(plan.amount, plan.from))
+            .call()
+            .await
+            .context("Failed to compute the commitment")?;
+        let claim_auth = client
+            .signer()
+            .sign_message(secrets.claim_auth_hash(recipient).as_bytes())
+            .await
+            .context("Failed to sign the claim authorization")?;
+        let encrypted_secret = relayer.encrypt_for_relayer(secrets.secret)?;
+        let encrypted_nullifier = relayer.encrypt_for_relayer(secrets.nullifier)?;
+
+        self.approve_token_if_needed(source_token, pool_address, plan.amount, client.clone())
+            .await?;
+
+        let call = pool.create_intent(
+            secrets.intent_id.0,
+            commitment,
+            source_token,
+            plan.amount,
+            dest_token,
+            plan.amount,
+            plan.to.id(),
+            recipient,
+            0,
+        );
+        let pending = call
+            .send()
+            .await
+            .map_err(|e| anyhow!("Failed to send createIntent tx: {}", e))?;
+        let tx_hash = pending.tx_hash();
+        info!(
+            "📤 Rebalance intent {:?} sent: {:?}",
+            secrets.intent_id, tx_hash
+        );
+
+        let receipt = pending
+            .await
+            .context("Failed to wait for the createIntent receipt")?
+            .ok_or_else(|| anyhow!("createIntent tx dropped: {:?}", tx_hash))?;
+        if receipt.status == Some(0.into()) {
+            return Err(anyhow!("createIntent tx reverted: {:?}", tx_hash));
+        }
+
+        let request = serde_json::json!({
+            "intent_id": format!("{:?}", secrets.intent_id),
+            "user_address": format!("{:?}", recipient),
+            "source_chain": plan.from.name(),
+            "dest_chain": plan.to.name(),
+            "source_token": format!("{:?}", source_token),
+            "dest_token": format!("{:?}", dest_token),
+            "amount": plan.amount.to_string(),
+            "commitment": format!("0x{}", hex::encode(commitment)),
+            "refund_address": format!("{:?}", recipient),
+            "encrypted_secret": encrypted_secret,
+            "encrypted_nullifier": encrypted_nullifier,
+            "claim_auth": format!("0x{}", claim_auth),
+            "recipient": format!("{:?}", recipient),
+        });
+        // The deposit is already made; without the relayer it is refunded
+        // after its deadline, and the transfer expires here
+        if let Err(e) = relayer.submit_intent(&request).await {
+            warn!(
+                "⚠️ Rebalance intent {:?} is on chain but the relayer did not take it: {:#}",
+                secrets.intent_id, e
+            );
+        }
+
+        Ok(tx_hash)
+    }
+
+    /// Compare inventory on every route against the reserve and typical intent
+    /// size. The first pass (at startup) reports every route; later passes only
+    /// log routes whose capacity changed.
+    async fn assess_route_capital(&self, balances: &HashMap<(TokenType, u64), U256>) {
+        let mut routes = Vec::new();
+        for (&(token, chain_id), &balance) in balances {
+            let Some(chain) = Chain::from_id(chain_id) else {
+                continue;
+            };
+            let requirement = RouteRequirement::for_route(
+                token,
+                chain,
+                &self.config.min_capital_reserve,
+                &self.config.route_minimums,
+                &self.config.route_typical_amounts,
+            );
+            routes.push(assess_route(token, chain, balance, requirement));
+        }
+        routes.sort_by_key(|route| route.route());
+
+        let mut current = self.route_capital.write().await;
+        let previous = current.replace(routes.clone());
+
+        let Some(previous) = previous else {
+            info!("🏦 Route capital check:");
+            for route in &routes {
+                log_route_capital(route);
+            }
+            let disabled = routes
+                .iter()
+                .filter(|route| route.capacity == RouteCapacity::Disabled)
+                .count();
+            if disabled > 0 {
+                warn!(
+                    "⚠️ {}/{} routes disabled for lack of capital; their intents will be skipped",
+                    disabled,
+                    routes.len()
+                );
+            }
+            return;
+        };
+
+        for route in &routes {
+            let changed = previous
+                .iter()
+                .find(|old| old.token == route.token && old.chain == route.chain)
+                .is_none_or(|old| old.capacity != route.capacity);
+            if changed {
+                log_route_capital(route);
             }
         }
-        Err(anyhow!("Unsupported token: {:?}", token))
+    }
+
+    pub async fn get_route_capital(&self) -> Option<Vec<RouteCapital>> {
+        self.route_capital.read().await.clone()
+    }
+
+    pub async fn get_rpc_endpoints(&self, chain_id: u64) -> Vec<EndpointStatus> {
+        self.rpc_pool(chain_id).status().await
+    }
+
+    fn identify_token(&self, token: Address, chain_id: u64) -> Result<TokenType> {
+        Chain::from_id(chain_id)
+            .and_then(|chain| self.config.tokens.resolve(token, chain))
+            .ok_or_else(|| anyhow!("Unsupported token: {:?}", token))
     }
 
     async fn run_health_checks(&self) -> Result<()> {
@@ -1592,6 +3448,22 @@ impl CrossChainSolver {
         loop {
             check_interval.tick().await;
 
+            let expired = self
+                .processed_intents
+                .write()
+                .await
+                .evict_expired(chrono::Utc::now().timestamp() as u64);
+            if expired > 0 {
+                debug!("🧹 Dropped {} expired processed intents", expired);
+            }
+            if let Some(store) = &self.state_store {
+                match store.prune(chrono::Utc::now().timestamp() as u64) {
+                    Ok(0) => {}
+                    Ok(pruned) => debug!("🧹 Pruned {} stored solver state rows", pruned),
+                    Err(e) => warn!("⚠️ Failed to prune solver state: {:#}", e),
+                }
+            }
+
             if let Err(e) = self.perform_health_check().await {
                 error!("❌ Health check failed: {}", e);
             }
@@ -1631,4 +3503,150 @@ impl CrossChainSolver {
     pub async fn get_metrics(&self) -> SolverMetrics {
         self.metrics.read().await.clone()
     }
+
+    async fn is_own_rebalance(&self, intent_id: &H256) -> bool {
+        match &self.rebalancer {
+            Some(rebalancer) => rebalancer.read().await.is_own_intent(intent_id),
+            None => false,
+        }
+    }
+
+    pub async fn rebalance_stats(&self) -> Option<RebalanceStats> {
+        match &self.rebalancer {
+            Some(rebalancer) => Some(
+                rebalancer
+                    .read()
+                    .await
+                    .stats(chrono::Utc::now().timestamp() as u64),
+            ),
+            None => None,
+        }
+    }
+
+    pub async fn dry_run_stats(&self) -> Option<DryRunStats> {
+        match &self.dry_run {
+            Some(dry_run) => Some(dry_run.read().await.stats()),
+            None => None,
+        }
+    }
+
+    pub async fn price_sources(&self) -> Vec<SourceHealth> {
+        self.price_feed.source_health().await
+    }
+
+    pub async fn processed_intent_stats(&self) -> ProcessedIntentStats {
+        self.processed_intents.read().await.stats()
+    }
+
+    pub async fn save_metrics_snapshot(&self) -> Result<()> {
+        let snapshot = self
+            .metrics
+            .read()
+            .await
+            .counter_snapshot(chrono::Utc::now().timestamp());
+        self.config.metrics_snapshot.save(&snapshot)
+    }
+
+    async fn run_metrics_snapshots(&self) {
+        let mut ticker = interval(Duration::from_secs(
+            self.config.metrics_snapshot.interval_secs,
+        ));
+        // The first tick fires immediately; nothing new has been counted yet
+        ticker.tick().await;
+        loop {
+            ticker.tick().await;
+            if let Err(e) = self.save_metrics_snapshot().await {
+                warn!("⚠️ Failed to save metric counters: {}", e);
+            }
+        }
+    }
+
+    /// Stop picking up intents and drop queued opportunities; fills already
+    /// executing carry on. Idempotent.
+    pub async fn start_drain(&self) -> DrainStatus {
+        if self.drain.start() {
+            let dropped: Vec<FillOpportunity> = {
+                let mut pending = self.pending_opportunities.write().await;
+                pending
+                    .drain()
+                    .map(|(_, opportunity)| opportunity)
+                    .collect()
+            };
+            self.metrics.write().await.pending_opportunities = 0;
+            for opportunity in &dropped {
+                self.reject(opportunity, "Solver is draining".to_string())
+                    .await;
+            }
+
+            warn!(
+                "🚰 Draining: ignoring new intents, {} fills executing, {} queued opportunities dropped",
+                self.drain.executing(),
+                dropped.len()
+            );
+        }
+
+        self.drain_status().await
+    }
+
+    pub async fn drain_status(&self) -> DrainStatus {
+        let unconfirmed_fills = self
+            .active_fills
+            .read()
+            .await
+            .values()
+            .filter(|fill| matches!(fill.status, FillStatus::Pending | FillStatus::Confirmed))
+            .count();
+        let executing_fills = self.drain.executing();
+        let started_at = self.drain.started_at();
+        let now = chrono::Utc::now().timestamp() as u64;
+
+        DrainStatus {
+            draining: started_at.is_some(),
+            drained: started_at.is_some() && executing_fills == 0,
+            started_at,
+            elapsed_secs: started_at.map(|at| now.saturating_sub(at)),
+            executing_fills,
+            unconfirmed_fills,
+        }
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.drain.is_draining()
+    }
+
+    pub async fn get_execution_report(&self, intent_id: &H256) -> Option<ExecutionReport> {
+        self.execution_reports.read().await.get(intent_id).cloned()
+    }
+
+    pub async fn get_balance_history(&self, query: &BalanceHistoryQuery) -> Vec<BalanceSnapshot> {
+        self.balance_history.read().await.query(query)
+    }
+
+    /// Snapshot cadence and retention, for charting clients.
+    pub async fn balance_history_window(&self) -> (u64, u64) {
+        let history = self.balance_history.read().await;
+        (history.snapshot_interval_secs(), history.retention_secs())
+    }
+}
+
+fn log_route_capital(route: &RouteCapital) {
+    match route.capacity {
+        RouteCapacity::Ready => info!(
+            "   ✅ {} ready | Balance: {} | Reserve: {} | Typical intent: {}",
+            route.route(),
+            route.balance,
+            route.reserve,
+            route.typical_intent
+        ),
+        RouteCapacity::Limited => warn!(
+            "   ⚠️ {} limited | {}",
+            route.route(),
+            route.reason.as_deref().unwrap_or_default()
+        ),
+        RouteCapacity::Disabled => warn!(
+            "   ⛔ {} disabled | {}",
+            route.route(),
+            route.reason.as_deref().unwrap_or_default()
+        ),
+    }
 }
@@ -0,0 +1,384 @@
+use anyhow::{Context, Result, anyhow};
+use diesel::pg::PgConnection;
+use diesel::prelude::*;
+use diesel::r2d2::{ConnectionManager, Pool, PooledConnection};
+use diesel_migrations::{EmbeddedMigrations, MigrationHarness, embed_migrations};
+use ethers::types::{Address, H256, U256};
+use mantle_core::token::TokenType;
+use tracing::info;
+
+use crate::{
+    model::{ActiveFill, FillStatus},
+    schema::{solver_fills, solver_processed_intents},
+};
+
+pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+
+/// Settled and failed fills are kept this long for inspection, then pruned.
+const SETTLED_FILL_RETENTION_SECS: u64 = 7 * 86_400;
+
+type DbPool = Pool<ConnectionManager<PgConnection>>;
+
+/// Where active fills and picked-up intents are persisted across restarts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StateStorePolicy {
+    /// Postgres URL; `None` keeps solver state in memory only.
+    pub database_url: Option<String>,
+    pub max_connections: u32,
+}
+
+impl Default for StateStorePolicy {
+    fn default() -> Self {
+        Self {
+            database_url: None,
+            max_connections: 5,
+        }
+    }
+}
+
+impl StateStorePolicy {
+    /// Override the defaults from `SOLVER_DATABASE_URL` (empty disables the
+    /// store) and `SOLVER_DATABASE_MAX_CONNECTIONS`.
+    pub fn from_env() -> Result<Self> {
+        let mut policy = Self::default();
+
+        if let Ok(url) = std::env::var("SOLVER_DATABASE_URL") {
+            policy.database_url = (!url.trim().is_empty()).then(|| url.trim().to_string());
+        }
+        if let Ok(max) = std::env::var("SOLVER_DATABASE_MAX_CONNECTIONS") {
+            policy.max_connections = max
+                .parse::<u32>()
+                .context("Invalid SOLVER_DATABASE_MAX_CONNECTIONS")?
+                .max(1);
+        }
+
+        Ok(policy)
+    }
+}
+
+/// What a persisted in-flight fill turned out to be once checked on-chain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecoveredFill {
+    /// Our fill landed; it goes on to wait for confirmations.
+    Landed,
+    /// Another solver filled the intent first.
+    FilledByOther,
+    /// The fill tx is still waiting to be mined.
+    InFlight,
+    /// The fill tx reverted or was dropped, so the intent may be retried.
+    Lost,
+}
+
+impl RecoveredFill {
+    /// `filled_by` is the solver `getFill` reports for the intent, zero when
+    /// unfilled; `tx_pending` whether the fill tx is known but not yet mined.
+    pub fn classify(filled_by: Address, solver: Address, tx_pending: bool) -> Self {
+        if filled_by == solver {
+            Self::Landed
+        } else if !filled_by.is_zero() {
+            Self::FilledByOther
+        } else if tx_pending {
+            Self::InFlight
+        } else {
+            Self::Lost
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Queryable, Selectable, Insertable, AsChangeset)]
+#[diesel(table_name = solver_fills)]
+#[diesel(check_for_backend(diesel::pg::Pg))]
+#[diesel(treat_none_as_null = true)]
+struct DbFill {
+    intent_id: String,
+    tx_hash: String,
+    amount: String,
+    token: String,
+    token_type: String,
+    filled_at: i64,
+    confirmed_at: Option<i64>,
+    status: String,
+    dest_chain: i64,
+    filled_amount: Option<String>,
+    chunks: i32,
+}
+
+impl DbFill {
+    fn from_fill(fill: &ActiveFill) -> Self {
+        Self {
+            intent_id: format!("{:?}", fill.intent_id),
+            tx_hash: format!("{:?}", fill.tx_hash),
+            amount: fill.amount.to_string(),
+            token: format!("{:?}", fill.token),
+            token_type: fill.token_type.symbol().to_string(),
+            filled_at: fill.filled_at as i64,
+            confirmed_at: fill.confirmed_at.map(|at| at as i64),
+            status: status_name(&fill.status).to_string(),
+            dest_chain: fill.dest_chain as i64,
+            filled_amount: Some(fill.filled_amount.to_string()),
+            chunks: fill.chunks as i32,
+        }
+    }
+
+    fn into_fill(self) -> Result<ActiveFill> {
+        let amount = U256::from_dec_str(&self.amount).context("Invalid amount")?;
+        Ok(ActiveFill {
+            intent_id: self.intent_id.parse().context("Invalid intent_id")?,
+            tx_hash: self.tx_hash.parse().context("Invalid tx_hash")?,
+            amount,
+            // Fills from before partial fills were sent whole
+            filled_amount: match &self.filled_amount {
+                Some(filled) => U256::from_dec_str(filled).context("Invalid filled_amount")?,
+                None => amount,
+            },
+            chunks: self.chunks.max(0) as u32,
+            token: self.token.parse().context("Invalid token")?,
+            token_type: TokenType::from_symbol(&self.token_type)?,
+            filled_at: self.filled_at as u64,
+            confirmed_at: self.confirmed_at.map(|at| at as u64),
+            status: parse_status(&self.status)?,
+            dest_chain: self.dest_chain as u32,
+        })
+    }
+}
+
+fn status_name(status: &FillStatus) -> &'static str {
+    match status {
+        FillStatus::Pending => "Pending",
+        FillStatus::Confirmed => "Confirmed",
+        FillStatus::Claimed => "Claimed",
+        FillStatus::Settled => "Settled",
+        FillStatus::Failed => "Failed",
+    }
+}
+
+fn parse_status(status: &str) -> Result<FillStatus> {
+    match status {
+        "Pending" => Ok(FillStatus::Pending),
+        "Confirmed" => Ok(FillStatus::Confirmed),
+        "Claimed" => Ok(FillStatus::Claimed),
+        "Settled" => Ok(FillStatus::Settled),
+        "Failed" => Ok(FillStatus::Failed),
+        _ => Err(anyhow!("Unknown fill status: {}", status)),
+    }
+}
+
+/// Postgres-backed copy of the solver's active fills and processed intents.
+/// Every change is written through, so the in-memory maps can be rebuilt
+/// after a restart.
+#[derive(Clone)]
+pub struct StateStore {
+    pool: DbPool,
+}
+
+impl StateStore {
+    /// Connect and migrate; `None` when the policy has no database.
+    pub fn connect(policy: &StateStorePolicy) -> Result<Option<Self>> {
+        let Some(url) = &policy.database_url else {
+            return Ok(None);
+        };
+
+        let manager = ConnectionManager::<PgConnection>::new(url);
+        let pool = Pool::builder()
+            .max_size(policy.max_connections)
+            .build(manager)
+            .context("Failed to create solver database pool")?;
+
+        let mut conn = pool.get().context("Failed to get database connection")?;
+        conn.run_pending_migrations(MIGRATIONS)
+            .map_err(|e| anyhow!("Failed to run solver migrations: {}", e))?;
+        info!("🗄️ Solver state store ready");
+
+        Ok(Some(Self { pool }))
+    }
+
+    fn conn(&self) -> Result<PooledConnection<ConnectionManager<PgConnection>>> {
+        self.pool.get().context("Failed to get database connection")
+    }
+
+    /// Insert or overwrite the fill.
+    pub fn save_fill(&self, fill: &ActiveFill) -> Result<()> {
+        let row = DbFill::from_fill(fill);
+        diesel::insert_into(solver_fills::table)
+            .values(&row)
+            .on_conflict(solver_fills::intent_id)
+            .do_update()
+            .set(&row)
+            .execute(&mut self.conn()?)
+            .context("Failed to save fill")?;
+        Ok(())
+    }
+
+    /// Fills sent but not yet claimed or failed, oldest first.
+    pub fn in_flight_fills(&self) -> Result<Vec<ActiveFill>> {
+        let rows: Vec<DbFill> = solver_fills::table
+            .filter(solver_fills::status.eq_any([
+                status_name(&FillStatus::Pending),
+                status_name(&FillStatus::Confirmed),
+            ]))
+            .order(solver_fills::filled_at.asc())
+            .select(DbFill::as_select())
+            .load(&mut self.conn()?)
+            .context("Failed to load in-flight fills")?;
+
+        rows.into_iter().map(DbFill::into_fill).collect()
+    }
+
+    /// Final fills still waiting to be repaid on the source chain.
+    pub fn unsettled_fills(&self) -> Result<Vec<ActiveFill>> {
+        let rows: Vec<DbFill> = solver_fills::table
+            .filter(solver_fills::status.eq(status_name(&FillStatus::Claimed)))
+            .order(solver_fills::filled_at.asc())
+            .select(DbFill::as_select())
+            .load(&mut self.conn()?)
+            .context("Failed to load unsettled fills")?;
+
+        rows.into_iter().map(DbFill::into_fill).collect()
+    }
+
+    pub fn claim_intent(&self, intent_id: H256, expires_at: u64) -> Result<()> {
+        diesel::insert_into(solver_processed_intents::table)
+            .values((
+                solver_processed_intents::intent_id.eq(format!("{:?}", intent_id)),
+                solver_processed_intents::expires_at.eq(expires_at as i64),
+            ))
+            .on_conflict(solver_processed_intents::intent_id)
+            .do_update()
+            .set(solver_processed_intents::expires_at.eq(expires_at as i64))
+            .execute(&mut self.conn()?)
+            .context("Failed to save processed intent")?;
+        Ok(())
+    }
+
+    pub fn release_intent(&self, intent_id: &H256) -> Result<()> {
+        diesel::delete(
+            solver_processed_intents::table
+                .filter(solver_processed_intents::intent_id.eq(format!("{:?}", intent_id))),
+        )
+        .execute(&mut self.conn()?)
+        .context("Failed to release processed intent")?;
+        Ok(())
+    }
+
+    /// Claims still held at `now`, with when each lapses.
+    pub fn processed_intents(&self, now: u64) -> Result<Vec<(H256, u64)>> {
+        let rows: Vec<(String, i64)> = solver_processed_intents::table
+            .filter(solver_processed_intents::expires_at.gt(now as i64))
+            .select((
+                solver_processed_intents::intent_id,
+                solver_processed_intents::expires_at,
+            ))
+            .load(&mut self.conn()?)
+            .context("Failed to load processed intents")?;
+
+        rows.into_iter()
+            .map(|(intent_id, expires_at)| {
+                Ok((
+                    intent_id.parse().context("Invalid intent_id")?,
+                    expires_at as u64,
+                ))
+            })
+            .collect()
+    }
+
+    /// Drop lapsed claims and settled fills past retention; returns how many
+    /// rows went.
+    pub fn prune(&self, now: u64) -> Result<usize> {
+        let mut conn = self.conn()?;
+
+        let claims = diesel::delete(
+            solver_processed_intents::table
+                .filter(solver_processed_intents::expires_at.le(now as i64)),
+        )
+        .execute(&mut conn)
+        .context("Failed to prune processed intents")?;
+
+        let fills = diesel::delete(
+            solver_fills::table
+                .filter(solver_fills::status.eq_any([
+                    status_name(&FillStatus::Settled),
+                    status_name(&FillStatus::Failed),
+                ]))
+                .filter(
+                    solver_fills::filled_at
+                        .lt(now.saturating_sub(SETTLED_FILL_RETENTION_SECS) as i64),
+                ),
+        )
+        .execute(&mut conn)
+        .context("Failed to prune settled fills")?;
+
+        Ok(claims + fills)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fill_row_round_trip() {
+        let fill = ActiveFill {
+            intent_id: H256::repeat_byte(0xab),
+            tx_hash: H256::repeat_byte(0x01),
+            amount: U256::exp10(30) + 7,
+            filled_amount: U256::exp10(29),
+            chunks: 2,
+            token: Address::repeat_byte(0x42),
+            token_type: TokenType::USDC,
+            filled_at: 1_700_000_000,
+            confirmed_at: Some(1_700_000_030),
+            status: FillStatus::Confirmed,
+            dest_chain: 5003,
+        };
+
+        let row = DbFill::from_fill(&fill);
+        assert_eq!(row.status, "Confirmed");
+        assert_eq!(row.amount, "1000000000000000000000000000007");
+
+        let restored = row.into_fill().unwrap();
+        assert_eq!(restored.intent_id, fill.intent_id);
+        assert_eq!(restored.tx_hash, fill.tx_hash);
+        assert_eq!(restored.amount, fill.amount);
+        assert_eq!(restored.filled_amount, fill.filled_amount);
+        assert_eq!(restored.chunks, 2);
+        assert_eq!(restored.token, fill.token);
+        assert_eq!(restored.token_type, fill.token_type);
+        assert_eq!(restored.confirmed_at, fill.confirmed_at);
+        assert_eq!(restored.status, fill.status);
+        assert_eq!(restored.dest_chain, fill.dest_chain);
+
+        let legacy = DbFill {
+            filled_amount: None,
+            ..DbFill::from_fill(&fill)
+        };
+        assert_eq!(legacy.into_fill().unwrap().filled_amount, fill.amount);
+    }
+
+    #[test]
+    fn test_recovered_fill_classification() {
+        let solver = Address::repeat_byte(1);
+        let competitor = Address::repeat_byte(2);
+
+        assert_eq!(
+            RecoveredFill::classify(solver, solver, false),
+            RecoveredFill::Landed
+        );
+        // A landed fill wins over a stale pending view of the tx
+        assert_eq!(
+            RecoveredFill::classify(solver, solver, true),
+            RecoveredFill::Landed
+        );
+        assert_eq!(
+            RecoveredFill::classify(competitor, solver, true),
+            RecoveredFill::FilledByOther
+        );
+        assert_eq!(
+            RecoveredFill::classify(Address::zero(), solver, true),
+            RecoveredFill::InFlight
+        );
+        assert_eq!(
+            RecoveredFill::classify(Address::zero(), solver, false),
+            RecoveredFill::Lost
+        );
+    }
+}